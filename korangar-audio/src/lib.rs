@@ -46,6 +46,7 @@ struct BackgroundMusicTrack {
 
 enum QueuedSoundEffectType {
     Sound,
+    InterfaceSound,
     SpatialSound { position: Vector3<f32>, range: f32 },
     AmbientSound { ambient_key: AmbientKey },
 }
@@ -105,6 +106,7 @@ pub struct AudioEngine<F> {
 
 struct EngineContext<F> {
     active_spatial_tracks: HashMap<AmbientKey, SpatialTrackHandle>,
+    interface_sound_effect_track: TrackHandle,
     spatial_listener: ListenerHandle,
     ambient_sound: SimpleSlab<AmbientKey, AmbientSoundConfig>,
     spatial_sound_effect_track: TrackHandle,
@@ -151,6 +153,9 @@ impl<F: FileLoader> AudioEngine<F> {
         let spatial_sound_effect_track = manager
             .add_sub_track(TrackBuilder::new())
             .expect("Can't create spatial sound effect track");
+        let interface_sound_effect_track = manager
+            .add_sub_track(TrackBuilder::new())
+            .expect("Can't create interface sound effect track");
         let position = Vector3::new(0.0, 0.0, 0.0);
         let orientation = Quaternion::one();
         let spatial_listener = manager.add_listener(position, orientation).expect("Can't create spatial listener");
@@ -168,6 +173,7 @@ impl<F: FileLoader> AudioEngine<F> {
 
         let engine_context = Mutex::new(EngineContext {
             active_spatial_tracks: HashMap::default(),
+            interface_sound_effect_track,
             spatial_listener,
             ambient_sound: SimpleSlab::default(),
             spatial_sound_effect_track,
@@ -277,6 +283,14 @@ impl<F: FileLoader> AudioEngine<F> {
             .set_spatial_sound_effect_volume(linear_to_decibel(volume))
     }
 
+    /// Sets the volume of interface sound effects.
+    pub fn set_interface_sound_effect_volume(&self, volume: f32) {
+        self.engine_context
+            .lock()
+            .unwrap()
+            .set_interface_sound_effect_volume(linear_to_decibel(volume))
+    }
+
     /// Plays the background music track. Fades out the currently playing
     /// background music track and then start the new background music
     /// track.
@@ -289,6 +303,13 @@ impl<F: FileLoader> AudioEngine<F> {
         self.engine_context.lock().unwrap().play_sound_effect(sound_effect_key)
     }
 
+    /// Plays a sound effect of the user interface, like a button click.
+    /// Interface sound effects have their own volume, so they can be turned
+    /// down independently of the sound effects of the game world.
+    pub fn play_interface_sound_effect(&self, sound_effect_key: SoundEffectKey) {
+        self.engine_context.lock().unwrap().play_interface_sound_effect(sound_effect_key)
+    }
+
     /// Plays a spatial sound effect, which will get removed automatically once
     /// it finishes playing.
     pub fn play_spatial_sound_effect(&self, sound_effect_key: SoundEffectKey, position: Point3<f32>, range: f32) {
@@ -375,6 +396,13 @@ impl<F: FileLoader> EngineContext<F> {
         });
     }
 
+    fn set_interface_sound_effect_volume(&mut self, volume: Decibels) {
+        self.interface_sound_effect_track.set_volume(volume, Tween {
+            duration: Duration::from_millis(500),
+            ..Default::default()
+        });
+    }
+
     fn play_background_music_track(&mut self, track_name: Option<&str>) {
         let Some(track_name) = track_name else {
             if let Some(playing) = self.current_background_music_track.as_mut() {
@@ -435,6 +463,32 @@ impl<F: FileLoader> EngineContext<F> {
         }
     }
 
+    fn play_interface_sound_effect(&mut self, sound_effect_key: SoundEffectKey) {
+        match self
+            .cache
+            .get(&sound_effect_key)
+            .map(|cached_sound_effect| cached_sound_effect.0.clone())
+        {
+            Some(data) => {
+                if let Err(_error) = self.interface_sound_effect_track.play(data) {
+                    #[cfg(feature = "debug")]
+                    print_debug!("[{}] can't play interface sound effect: {:?}", "error".red(), _error);
+                }
+            }
+            None => {
+                queue_sound_effect_playback(
+                    self.game_file_loader.clone(),
+                    self.async_response_sender.clone(),
+                    &self.sound_effect_paths,
+                    &mut self.queued_sound_effect,
+                    &mut self.loading_sound_effect,
+                    sound_effect_key,
+                    QueuedSoundEffectType::InterfaceSound,
+                );
+            }
+        }
+    }
+
     fn play_spatial_sound_effect(&mut self, sound_effect_key: SoundEffectKey, position: Point3<f32>, range: f32) {
         // Kira uses a RH coordinate system, so we need to convert our LH vectors.
         let position = Vector3::new(position.x, position.y, -position.z);
@@ -723,6 +777,12 @@ impl<F: FileLoader> EngineContext<F> {
                         print_debug!("[{}] can't play sound effect: {:?}", "error".red(), _error);
                     }
                 }
+                QueuedSoundEffectType::InterfaceSound => {
+                    if let Err(_error) = self.interface_sound_effect_track.play(data) {
+                        #[cfg(feature = "debug")]
+                        print_debug!("[{}] can't play interface sound effect: {:?}", "error".red(), _error);
+                    }
+                }
                 QueuedSoundEffectType::SpatialSound { position, range } => {
                     let spatial_track = SpatialTrackBuilder::new()
                         .persist_until_sounds_finish(true)
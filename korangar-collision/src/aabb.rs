@@ -110,6 +110,38 @@ impl AABB {
             && self.max.z >= other.min.z
     }
 
+    /// Check if the line segment between two points intersects with the AABB.
+    pub fn intersects_segment(&self, start: Point3<f32>, end: Point3<f32>) -> bool {
+        let direction = end - start;
+        let mut entry: f32 = 0.0;
+        let mut exit: f32 = 1.0;
+
+        // Slab test: clip the segment against the two planes of every axis.
+        for axis in 0..3 {
+            let (origin, delta, min, max) = (start[axis], direction[axis], self.min[axis], self.max[axis]);
+
+            if delta.abs() < f32::EPSILON {
+                if origin < min || origin > max {
+                    return false;
+                }
+
+                continue;
+            }
+
+            let first = (min - origin) / delta;
+            let second = (max - origin) / delta;
+
+            entry = entry.max(first.min(second));
+            exit = exit.min(first.max(second));
+
+            if entry > exit {
+                return false;
+            }
+        }
+
+        true
+    }
+
     /// Creates a new AABB that is expanded by a given margin in all directions.
     pub fn expanded(&self, margin: f32) -> Self {
         AABB {
@@ -255,6 +287,18 @@ mod tests {
         assert!(aabb_2.intersects_aabb(&aabb_3));
     }
 
+    #[test]
+    fn test_intersects_segment() {
+        let aabb = AABB::new(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 2.0, 2.0));
+
+        assert!(aabb.intersects_segment(Point3::new(-1.0, 1.0, 1.0), Point3::new(3.0, 1.0, 1.0)));
+        assert!(aabb.intersects_segment(Point3::new(1.0, 1.0, 1.0), Point3::new(5.0, 5.0, 5.0)));
+        assert!(aabb.intersects_segment(Point3::new(-1.0, -1.0, -1.0), Point3::new(3.0, 3.0, 3.0)));
+        assert!(!aabb.intersects_segment(Point3::new(-2.0, 1.0, 1.0), Point3::new(-1.0, 1.0, 1.0)));
+        assert!(!aabb.intersects_segment(Point3::new(-1.0, 3.0, 1.0), Point3::new(3.0, 3.0, 1.0)));
+        assert!(!aabb.intersects_segment(Point3::new(-1.0, 0.0, 1.0), Point3::new(0.0, -1.0, 1.0)));
+    }
+
     #[test]
     fn test_expand() {
         let mut aabb = AABB::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0));
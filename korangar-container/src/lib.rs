@@ -63,13 +63,25 @@ mod simple_cache;
 mod simple_slab;
 
 pub use generational_slab::{GenerationalIter, GenerationalKey, GenerationalSlab, SecondaryGenerationalSlab};
-pub use simple_cache::{CacheError, CacheStatistics, Cacheable, SimpleCache};
+pub use simple_cache::{CacheError, CacheStatistics, Cacheable, HitRate, SimpleCache};
 pub use simple_slab::{SecondarySimpleSlab, SimpleIterator, SimpleKey, SimpleSlab};
 
 /// Bytes that are displayed with SI units.
 #[derive(Clone, Copy, PartialEq, Eq, Default)]
 pub struct HumanReadableBytes(usize);
 
+impl HumanReadableBytes {
+    /// Creates a new value from a number of bytes.
+    pub fn new(bytes: usize) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the number of bytes.
+    pub fn bytes(&self) -> usize {
+        self.0
+    }
+}
+
 impl std::fmt::Display for HumanReadableBytes {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
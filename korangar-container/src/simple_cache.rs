@@ -22,6 +22,36 @@ pub struct CacheStatistics {
     pub size: HumanReadableBytes,
     /// The maximal size of cached values.
     pub max_size: HumanReadableBytes,
+    /// The ratio of lookups that found a cached value.
+    pub hit_rate: HitRate,
+}
+
+/// Counts the lookups of a cache. Displayed as the percentage of successful
+/// lookups.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub struct HitRate {
+    /// The count of lookups that found a cached value.
+    pub hits: u64,
+    /// The count of lookups that didn't find a cached value.
+    pub misses: u64,
+}
+
+impl HitRate {
+    /// Returns the ratio of successful lookups or `None` if there were no
+    /// lookups yet.
+    pub fn ratio(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+        (lookups > 0).then(|| self.hits as f64 / lookups as f64)
+    }
+}
+
+impl std::fmt::Display for HitRate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.ratio() {
+            Some(ratio) => write!(f, "{:.1}% ({}/{})", ratio * 100.0, self.hits, self.hits + self.misses),
+            None => "-".fmt(f),
+        }
+    }
 }
 
 /// Something that can be cached.
@@ -130,6 +160,11 @@ impl<K: Clone + Eq + Hash> GhostList<K> {
         None
     }
 
+    fn clear(&mut self) {
+        self.map.clear();
+        self.queue.clear();
+    }
+
     fn should_compact(&self) -> bool {
         self.queue.len() > self.map.len() * 2
     }
@@ -184,6 +219,7 @@ pub struct SimpleCache<K, V> {
     max_main_size: usize,
     max_count: u32,
     max_size: usize,
+    hit_rate: HitRate,
 }
 
 impl<K: Clone + Eq + Hash, V: Cacheable> SimpleCache<K, V> {
@@ -212,6 +248,7 @@ impl<K: Clone + Eq + Hash, V: Cacheable> SimpleCache<K, V> {
             max_main_size: max_size - max_small_size,
             max_count,
             max_size,
+            hit_rate: HitRate::default(),
         }
     }
 
@@ -222,6 +259,7 @@ impl<K: Clone + Eq + Hash, V: Cacheable> SimpleCache<K, V> {
             max_count: self.max_count(),
             size: HumanReadableBytes(self.size()),
             max_size: HumanReadableBytes(self.max_size()),
+            hit_rate: self.hit_rate,
         }
     }
 
@@ -254,6 +292,34 @@ impl<K: Clone + Eq + Hash, V: Cacheable> SimpleCache<K, V> {
         self.ghost.compact();
     }
 
+    /// Removes all values from the cache, including the ghost list. The hit
+    /// rate is reset as well.
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.small_fifo.clear();
+        self.main_fifo.clear();
+        self.ghost.clear();
+        self.small_count = 0;
+        self.small_size = 0;
+        self.main_count = 0;
+        self.main_size = 0;
+        self.hit_rate = HitRate::default();
+    }
+
+    /// Evicts values until the cache holds at most `size` bytes. Values are
+    /// evicted in the same order as if new values were inserted.
+    pub fn shrink_to_size(&mut self, size: usize) {
+        while self.size() > size {
+            if !self.small_fifo.is_empty() {
+                self.evict_s();
+            } else if !self.main_fifo.is_empty() {
+                self.evict_m();
+            } else {
+                break;
+            }
+        }
+    }
+
     /// Inserts a value of the given size. The cache saves the given value and
     /// will drop it when there is not enough size for new cache entries.
     ///
@@ -304,8 +370,12 @@ impl<K: Clone + Eq + Hash, V: Cacheable> SimpleCache<K, V> {
     {
         let hash = self.values.hasher().hash_one(key);
         match self.values.raw_entry_mut().from_hash(hash, |k| eq(k)) {
-            RawEntryMut::Vacant(_) => None,
+            RawEntryMut::Vacant(_) => {
+                self.hit_rate.misses += 1;
+                None
+            }
             RawEntryMut::Occupied(entry) => {
+                self.hit_rate.hits += 1;
                 let value_entry = entry.into_mut();
                 value_entry.freq = std::cmp::min(value_entry.freq + 1, 3);
                 Some(&value_entry.value)
@@ -320,10 +390,17 @@ impl<K: Clone + Eq + Hash, V: Cacheable> SimpleCache<K, V> {
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        self.values.get_mut(key).map(|value_entry| {
-            value_entry.freq = std::cmp::min(value_entry.freq + 1, 3);
-            &value_entry.value
-        })
+        match self.values.get_mut(key) {
+            Some(value_entry) => {
+                self.hit_rate.hits += 1;
+                value_entry.freq = std::cmp::min(value_entry.freq + 1, 3);
+                Some(&value_entry.value)
+            }
+            None => {
+                self.hit_rate.misses += 1;
+                None
+            }
+        }
     }
 
     fn evict_s(&mut self) {
@@ -578,6 +655,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_clear() {
+        let mut cache: SimpleCache<String, TestData> = SimpleCache::new(NonZeroU32::new(100).unwrap(), NonZeroUsize::new(10000).unwrap());
+
+        for i in 0..20 {
+            let key = format!("key_{i}");
+            assert!(cache.insert(key, TestData::new(100)).is_ok());
+        }
+
+        cache.clear();
+
+        assert_eq!(cache.count(), 0);
+        assert_eq!(cache.size(), 0);
+        assert!(cache.get("key_15").is_none());
+
+        // Cleared keys are not in the ghost list anymore, so they start in the small
+        // FIFO again.
+        assert!(cache.insert("key_0".to_string(), TestData::new(100)).is_ok());
+        assert_eq!(cache.main_count, 0);
+        assert_eq!(cache.small_count, 1);
+    }
+
+    #[test]
+    fn test_hit_rate() {
+        let mut cache: SimpleCache<String, TestData> = SimpleCache::new(NonZeroU32::new(100).unwrap(), NonZeroUsize::new(10000).unwrap());
+
+        assert_eq!(cache.statistics().hit_rate.ratio(), None);
+
+        assert!(cache.insert("key".to_string(), TestData::new(100)).is_ok());
+        assert!(cache.get("key").is_some());
+        assert!(cache.get("key").is_some());
+        assert!(cache.get("key").is_some());
+        assert!(cache.get("missing").is_none());
+
+        let hit_rate = cache.statistics().hit_rate;
+        assert_eq!(hit_rate.hits, 3);
+        assert_eq!(hit_rate.misses, 1);
+        assert_eq!(hit_rate.ratio(), Some(0.75));
+
+        cache.clear();
+        assert_eq!(cache.statistics().hit_rate, HitRate::default());
+    }
+
     #[test]
     fn test_cache_eviction_by_count() {
         let mut cache: SimpleCache<String, TestData> = SimpleCache::new(NonZeroU32::new(100).unwrap(), NonZeroUsize::new(100000).unwrap());
@@ -641,4 +761,27 @@ mod tests {
         let data2 = TestData::new(1500);
         assert_eq!(cache.insert(key.clone(), data2.clone()), Err(CacheError::ValueAlreadyPresent));
     }
+
+    #[test]
+    fn test_shrink_to_size() {
+        let mut cache: SimpleCache<String, TestData> = SimpleCache::new(NonZeroU32::new(100).unwrap(), NonZeroUsize::new(10000).unwrap());
+
+        for i in 0..10 {
+            let key = format!("key_{i}");
+            assert!(cache.insert(key.clone(), TestData::new(100)).is_ok());
+
+            // Frequently used values are moved to the main queue.
+            if i % 2 == 0 {
+                let _ = cache.get(&key);
+                let _ = cache.get(&key);
+            }
+        }
+
+        cache.shrink_to_size(450);
+        assert!(cache.size() <= 450);
+
+        cache.shrink_to_size(0);
+        assert_eq!(cache.size(), 0);
+        assert_eq!(cache.count(), 0);
+    }
 }
@@ -0,0 +1,394 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Severity of a log message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error,
+    Warning,
+    Info,
+}
+
+impl LogLevel {
+    fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warning => "warning",
+            LogLevel::Info => "info",
+        }
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_lowercase().as_str() {
+            "error" => Some(LogLevel::Error),
+            "warning" | "warn" => Some(LogLevel::Warning),
+            "info" => Some(LogLevel::Info),
+            _ => None,
+        }
+    }
+}
+
+/// Per-module filter for log messages.
+///
+/// The filter is configured with one rule per line. A rule is either a plain
+/// level that applies to all modules, or `module=level` for a module and all
+/// of its sub modules. The most specific rule wins. Empty lines and lines
+/// starting with `#` are ignored.
+///
+/// ```text
+/// warning
+/// korangar::loaders=info
+/// korangar_networking=error
+/// ```
+#[derive(Debug, Clone)]
+pub struct LogFilter {
+    default_level: LogLevel,
+    module_levels: Vec<(String, LogLevel)>,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            default_level: LogLevel::Info,
+            module_levels: Vec::new(),
+        }
+    }
+}
+
+impl LogFilter {
+    pub fn parse(text: &str) -> Self {
+        let mut filter = Self::default();
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match line.split_once('=') {
+                Some((module, level)) => {
+                    if let Some(level) = LogLevel::parse(level) {
+                        filter.module_levels.push((module.trim().to_owned(), level));
+                    }
+                }
+                None => {
+                    if let Some(level) = LogLevel::parse(line) {
+                        filter.default_level = level;
+                    }
+                }
+            }
+        }
+
+        // Sort by length so the most specific rule is found first.
+        filter.module_levels.sort_by_key(|(module, _)| std::cmp::Reverse(module.len()));
+        filter
+    }
+
+    pub fn is_enabled(&self, module: &str, level: LogLevel) -> bool {
+        let maximum_level = self
+            .module_levels
+            .iter()
+            .find(|(filter_module, _)| {
+                module
+                    .strip_prefix(filter_module.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default_level);
+
+        level <= maximum_level
+    }
+}
+
+/// Configuration of the file logging backend.
+#[derive(Debug, Clone)]
+pub struct FileLoggingConfiguration {
+    /// Directory that the log files are written to.
+    pub directory: PathBuf,
+    /// Optional file containing the [`LogFilter`] rules. Changes to this file
+    /// are picked up while the client is running.
+    pub filter_file: Option<PathBuf>,
+    /// Size in bytes after which the log file is rotated.
+    pub maximum_file_size: u64,
+    /// Number of rotated log files to keep around.
+    pub maximum_file_count: usize,
+}
+
+impl FileLoggingConfiguration {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            filter_file: None,
+            maximum_file_size: 8 * 1024 * 1024,
+            maximum_file_count: 5,
+        }
+    }
+}
+
+/// How often the filter file is checked for changes.
+const FILTER_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+const LOG_FILE_NAME: &str = "korangar.log";
+
+struct FileLogger {
+    configuration: FileLoggingConfiguration,
+    writer: BufWriter<File>,
+    written_bytes: u64,
+    filter: LogFilter,
+    filter_modified: Option<SystemTime>,
+    last_filter_check: Instant,
+}
+
+impl FileLogger {
+    fn new(configuration: FileLoggingConfiguration) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&configuration.directory)?;
+
+        let path = configuration.directory.join(LOG_FILE_NAME);
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let written_bytes = file.metadata()?.len();
+
+        let mut logger = Self {
+            configuration,
+            writer: BufWriter::new(file),
+            written_bytes,
+            filter: LogFilter::default(),
+            filter_modified: None,
+            last_filter_check: Instant::now(),
+        };
+
+        logger.reload_filter();
+
+        Ok(logger)
+    }
+
+    fn reload_filter(&mut self) {
+        let Some(filter_file) = &self.configuration.filter_file else {
+            return;
+        };
+
+        let modified = std::fs::metadata(filter_file).and_then(|metadata| metadata.modified()).ok();
+
+        if modified == self.filter_modified {
+            return;
+        }
+
+        self.filter_modified = modified;
+        self.filter = std::fs::read_to_string(filter_file)
+            .map(|text| LogFilter::parse(&text))
+            .unwrap_or_default();
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.writer.flush()?;
+
+        let directory = &self.configuration.directory;
+        let rotated_path = |index: usize| directory.join(format!("{LOG_FILE_NAME}.{index}"));
+
+        if self.configuration.maximum_file_count > 0 {
+            let _ = std::fs::remove_file(rotated_path(self.configuration.maximum_file_count));
+
+            for index in (1..self.configuration.maximum_file_count).rev() {
+                let _ = std::fs::rename(rotated_path(index), rotated_path(index + 1));
+            }
+
+            std::fs::rename(directory.join(LOG_FILE_NAME), rotated_path(1))?;
+        }
+
+        let file = File::create(directory.join(LOG_FILE_NAME))?;
+        self.writer = BufWriter::new(file);
+        self.written_bytes = 0;
+
+        Ok(())
+    }
+
+    fn write(&mut self, level: LogLevel, module: &str, message: &str) -> std::io::Result<()> {
+        if self.last_filter_check.elapsed() >= FILTER_CHECK_INTERVAL {
+            self.last_filter_check = Instant::now();
+            self.reload_filter();
+        }
+
+        if !self.filter.is_enabled(module, level) {
+            return Ok(());
+        }
+
+        let line = format_line(&chrono::offset::Local::now().to_rfc3339(), level, module, message);
+
+        if self.written_bytes + line.len() as u64 > self.configuration.maximum_file_size {
+            self.rotate()?;
+        }
+
+        self.writer.write_all(line.as_bytes())?;
+        self.written_bytes += line.len() as u64;
+
+        // Errors are flushed right away so they are not lost if the client crashes.
+        if level == LogLevel::Error {
+            self.writer.flush()?;
+        }
+
+        Ok(())
+    }
+}
+
+static FILE_LOGGER: Mutex<Option<FileLogger>> = Mutex::new(None);
+
+/// Start writing log messages to rotating files.
+pub fn initialize_file_logging(configuration: FileLoggingConfiguration) -> std::io::Result<()> {
+    let logger = FileLogger::new(configuration)?;
+    *FILE_LOGGER.lock().unwrap() = Some(logger);
+    Ok(())
+}
+
+/// Write any buffered log messages to disk.
+pub fn flush_log_file() {
+    // The panic hook flushes the log file, so a poisoned logger is still used.
+    let mut logger = FILE_LOGGER.lock().unwrap_or_else(PoisonError::into_inner);
+
+    if let Some(logger) = logger.as_mut() {
+        let _ = logger.writer.flush();
+    }
+}
+
+/// Write a message to the log file if file logging is initialized and the
+/// filter allows it. Messages are written through
+/// [`print_debug`](super::print_debug), which picks the level from the
+/// message prefix.
+pub fn write_log(level: LogLevel, module: &str, message: &str) {
+    // Logging should never bring down the client, so a poisoned logger is still
+    // used.
+    let mut logger = FILE_LOGGER.lock().unwrap_or_else(PoisonError::into_inner);
+
+    if let Some(logger) = logger.as_mut() {
+        let _ = logger.write(level, module, message);
+    }
+}
+
+fn format_line(timestamp: &str, level: LogLevel, module: &str, message: &str) -> String {
+    format!(
+        "time={} level={} module={} message={:?}\n",
+        timestamp,
+        level.as_str(),
+        module,
+        strip_color_codes(message)
+    )
+}
+
+/// Remove the ANSI color codes added by [`Colorize`](super::Colorize).
+pub(crate) fn strip_color_codes(message: &str) -> String {
+    let mut stripped = String::with_capacity(message.len());
+    let mut characters = message.chars();
+
+    while let Some(character) = characters.next() {
+        if character == '\x1B' {
+            // Skip until the end of the escape sequence.
+            for character in characters.by_ref() {
+                if character.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            continue;
+        }
+
+        stripped.push(character);
+    }
+
+    stripped
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    use super::{FileLogger, FileLoggingConfiguration, LogFilter, LogLevel, format_line, strip_color_codes};
+
+    #[test]
+    fn filter_default_level() {
+        let filter = LogFilter::parse("warning");
+
+        assert!(filter.is_enabled("korangar", LogLevel::Error));
+        assert!(filter.is_enabled("korangar", LogLevel::Warning));
+        assert!(!filter.is_enabled("korangar", LogLevel::Info));
+    }
+
+    #[test]
+    fn filter_most_specific_module_wins() {
+        let filter = LogFilter::parse("# comment\nerror\nkorangar=warning\nkorangar::loaders=info\n");
+
+        assert!(filter.is_enabled("korangar::loaders::map", LogLevel::Info));
+        assert!(filter.is_enabled("korangar::world", LogLevel::Warning));
+        assert!(!filter.is_enabled("korangar::world", LogLevel::Info));
+        assert!(!filter.is_enabled("korangar_networking", LogLevel::Info));
+    }
+
+    #[test]
+    fn filter_ignores_invalid_lines() {
+        let filter = LogFilter::parse("loud\nwarning\nkorangar=verbose");
+
+        assert!(filter.is_enabled("korangar", LogLevel::Warning));
+        assert!(!filter.is_enabled("korangar", LogLevel::Info));
+    }
+
+    #[test]
+    fn color_codes_are_stripped() {
+        assert_eq!(strip_color_codes("\x1B[31merror\x1B[0m: failed"), "error: failed");
+    }
+
+    #[test]
+    fn line_format() {
+        let line = format_line("2025-01-01T00:00:00+00:00", LogLevel::Info, "korangar", "loading \"map\"");
+
+        assert_eq!(
+            line,
+            "time=2025-01-01T00:00:00+00:00 level=info module=korangar message=\"loading \\\"map\\\"\"\n"
+        );
+    }
+
+    #[test]
+    fn messages_below_level_are_dropped() {
+        let directory = std::env::temp_dir().join(format!("korangar-log-filter-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let filter_file = directory.join("log_filter.txt");
+        std::fs::write(&filter_file, "warning").unwrap();
+
+        let mut configuration = FileLoggingConfiguration::new(&directory);
+        configuration.filter_file = Some(filter_file);
+
+        let mut logger = FileLogger::new(configuration).unwrap();
+
+        logger.write(LogLevel::Info, "korangar", "dropped message").unwrap();
+        logger.write(LogLevel::Warning, "korangar", "kept message").unwrap();
+        logger.writer.flush().unwrap();
+
+        let log = std::fs::read_to_string(directory.join("korangar.log")).unwrap();
+
+        assert!(!log.contains("dropped message"));
+        assert!(log.contains("kept message"));
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn files_are_rotated() {
+        let directory = std::env::temp_dir().join(format!("korangar-log-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+
+        let mut configuration = FileLoggingConfiguration::new(&directory);
+        configuration.maximum_file_size = 128;
+        configuration.maximum_file_count = 2;
+
+        let mut logger = FileLogger::new(configuration).unwrap();
+
+        for _ in 0..10 {
+            logger.write(LogLevel::Info, "korangar", "some message").unwrap();
+        }
+
+        assert!(directory.join("korangar.log").exists());
+        assert!(directory.join("korangar.log.1").exists());
+        assert!(directory.join("korangar.log.2").exists());
+        assert!(!directory.join("korangar.log.3").exists());
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+}
@@ -1,4 +1,5 @@
 mod colors;
+mod file;
 mod stack;
 pub mod symbols;
 #[macro_use]
@@ -6,5 +7,6 @@ mod print;
 mod timer;
 
 pub use self::colors::{Colorize, Colorized};
-pub use self::print::{print_debug, print_indented};
+pub use self::file::{FileLoggingConfiguration, LogFilter, LogLevel, flush_log_file, initialize_file_logging, write_log};
+pub use self::print::{print_and_log, print_debug, print_indented, recent_messages};
 pub use self::timer::Timer;
@@ -1,9 +1,13 @@
+use std::collections::VecDeque;
+use std::sync::{Mutex, PoisonError};
+
+use crate::logging::file::{LogLevel, strip_color_codes, write_log};
 use crate::logging::stack::{get_message_count, increment_message_count, message_offset, stack_size};
 use crate::logging::symbols::{ARROW, NEWLINE};
 
 pub macro print_debug {
-    ($format:expr) => (print_indented(String::from($format), true)),
-    ($format:expr, $($arguments:tt)*) => (print_indented(format!($format, $($arguments)*), true)),
+    ($format:expr) => (print_and_log(String::from($format), module_path!())),
+    ($format:expr, $($arguments:tt)*) => (print_and_log(format!($format, $($arguments)*), module_path!())),
 }
 
 pub(crate) macro print_debug_prefix {
@@ -11,7 +15,52 @@ pub(crate) macro print_debug_prefix {
     ($format:expr, $($arguments:tt)*) => (print_indented(format!($format, $($arguments)*), false)),
 }
 
+/// Number of messages that are kept around, so that they can be attached to
+/// crash reports.
+const MESSAGE_HISTORY_SIZE: usize = 256;
+
+static MESSAGE_HISTORY: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Get the most recently printed messages, oldest first.
+pub fn recent_messages() -> Vec<String> {
+    let history = MESSAGE_HISTORY.lock().unwrap_or_else(PoisonError::into_inner);
+    history.iter().cloned().collect()
+}
+
+fn add_to_history(message: &str) {
+    // We might be called from a panic hook, so a poisoned history is still used.
+    let mut history = MESSAGE_HISTORY.lock().unwrap_or_else(PoisonError::into_inner);
+
+    if history.len() == MESSAGE_HISTORY_SIZE {
+        history.pop_front();
+    }
+
+    history.push_back(message.to_owned());
+}
+
+/// Level of a printed message. Errors and warnings are printed with an
+/// `[error]` or `[warning]` prefix, everything else is logged as info.
+fn message_level(message: &str) -> LogLevel {
+    let message = strip_color_codes(message);
+
+    if message.starts_with("[error]") {
+        LogLevel::Error
+    } else if message.starts_with("[warning]") {
+        LogLevel::Warning
+    } else {
+        LogLevel::Info
+    }
+}
+
+#[doc(hidden)]
+pub fn print_and_log(message: String, module: &str) {
+    write_log(message_level(&message), module, &message);
+    print_indented(message, true);
+}
+
 pub fn print_indented(message: String, newline: bool) {
+    add_to_history(&message);
+
     let offset = message_offset();
 
     if stack_size() > 0 {
@@ -36,3 +85,20 @@ pub fn print_indented(message: String, newline: bool) {
         println!();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::message_level;
+    use crate::logging::Colorize;
+    use crate::logging::file::LogLevel;
+
+    #[test]
+    fn prefixed_messages_get_their_level() {
+        assert_eq!(message_level(&format!("[{}] failed to load", "error".red())), LogLevel::Error);
+        assert_eq!(
+            message_level(&format!("[{}] slow frame", "warning".yellow())),
+            LogLevel::Warning
+        );
+        assert_eq!(message_level("loading map"), LogLevel::Info);
+    }
+}
@@ -199,6 +199,10 @@ where
 
     /// Update the size of a registered window.
     fn update_size(&mut self, window_class: App::WindowClass, size: App::Size);
+
+    /// Update whether or not a window is open. Windows that are closed
+    /// because all windows are closed at once keep their previous state.
+    fn update_open(&mut self, window_class: App::WindowClass, open: bool);
 }
 
 /// Glue between [`korangar_interface`] and the renderer of the application.
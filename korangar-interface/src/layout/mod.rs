@@ -621,14 +621,20 @@ impl<'a, App: Application> WindowLayout<'a, App> {
         }
     }
 
-    pub fn handle_click(&self, state: &Context<App>, queue: &mut EventQueue<App>, mouse_button: MouseButton) {
+    /// Returns `true` if any element handled the click.
+    pub fn handle_click(&self, state: &Context<App>, queue: &mut EventQueue<App>, mouse_button: MouseButton) -> bool {
+        let mut click_handled = false;
+
         for layer in self.layers.iter().rev() {
             for (registered_button, click_handler) in &layer.click_handlers {
                 if *registered_button == mouse_button {
                     click_handler.handle_click(state, queue);
+                    click_handled = true;
                 }
             }
         }
+
+        click_handled
     }
 
     pub fn handle_drop(&self, state: &Context<App>, queue: &mut EventQueue<App>, mouse_mode: &'a MouseMode<App>) {
@@ -348,6 +348,15 @@ where
         }
     }
 
+    /// Number of open windows.
+    pub fn window_cache(&self) -> &App::Cache {
+        &self.window_cache
+    }
+
+    pub fn window_count(&self) -> usize {
+        self.windows.len()
+    }
+
     pub fn is_window_with_class_open(&self, window_class: App::WindowClass) -> bool {
         self.windows
             .iter()
@@ -375,6 +384,10 @@ where
             }
         };
 
+        if let Some(window_class) = window_class {
+            self.window_cache.update_open(window_class, true);
+        }
+
         self.windows.push(WindowWrapper {
             window: Box::new(window),
             data: WindowData { id, anchor, size },
@@ -431,6 +444,16 @@ where
         }
     }
 
+    /// Close a window on request of the user, remembering that the window is
+    /// closed.
+    fn close_window(&mut self, index: usize) {
+        if let Some(window_class) = self.windows[index].window.get_class() {
+            self.window_cache.update_open(window_class, false);
+        }
+
+        self.remove_window(index);
+    }
+
     fn remove_window(&mut self, index: usize) {
         // Remove the cached window layout to avoid growing the cache indefinitely.
         self.window_layouts.remove(&self.windows[index].data.id);
@@ -441,7 +464,7 @@ where
     pub fn close_top_window(&mut self, state: &Context<App>) {
         if let Some(index_from_back) = self.windows.iter().rev().position(|wrapper| wrapper.window.is_closable(state)) {
             let index = self.windows.len() - 1 - index_from_back;
-            self.remove_window(index);
+            self.close_window(index);
         }
     }
 
@@ -455,7 +478,7 @@ where
             .position(|class_option| class_option.contains(&window_class))
         {
             let index = self.windows.len() - 1 - index_from_back;
-            self.remove_window(index);
+            self.close_window(index);
         }
     }
 
@@ -513,6 +536,10 @@ where
                 }
                 Event::CloseWindow { window_id } => {
                     if let Some(index) = self.windows.iter().position(|wrapper| wrapper.data.id == window_id) {
+                        if let Some(window_class) = self.windows[index].window.get_class() {
+                            self.window_cache.update_open(window_class, false);
+                        }
+
                         self.windows.remove(index);
                     }
                 }
@@ -840,8 +867,10 @@ impl<App: Application> InterfaceFrame<'_, App> {
         }
     }
 
+    /// Returns `true` if the click was handled by an element, for example a
+    /// button. Clicks on the empty space of a window return `false`.
     #[cfg_attr(feature = "debug", korangar_debug::profile)]
-    pub fn click(&mut self, state: &Context<App>, mouse_button: MouseButton) {
+    pub fn click(&mut self, state: &Context<App>, mouse_button: MouseButton) -> bool {
         self.event_queue.queue(Event::Unfocus);
         self.event_queue.queue(Event::CloseOverlay);
 
@@ -849,15 +878,19 @@ impl<App: Application> InterfaceFrame<'_, App> {
             self.event_queue.queue(Event::MoveWindowToTop { window_id: hovered_window });
         }
 
+        let mut click_handled = false;
+
         if let Some(layout) = &self.overlay_layout {
-            layout.handle_click(state, self.event_queue, mouse_button);
+            click_handled |= layout.handle_click(state, self.event_queue, mouse_button);
         }
 
         if let Some(window_id) = &self.hovered_window {
             let layout = self.window_layouts.get(window_id).unwrap();
 
-            layout.handle_click(state, self.event_queue, mouse_button);
+            click_handled |= layout.handle_click(state, self.event_queue, mouse_button);
         }
+
+        click_handled
     }
 
     #[cfg_attr(feature = "debug", korangar_debug::profile)]
@@ -45,7 +45,7 @@ async fn main() -> ExitCode {
     loop {
         networking_system.get_events(&mut network_event_buffer);
 
-        for event in network_event_buffer.drain() {
+        for event in network_event_buffer.drain(usize::MAX) {
             match event {
                 NetworkEvent::LoginServerConnected {
                     character_servers,
@@ -0,0 +1,41 @@
+use ragnarok_packets::Sex;
+
+/// Job that a new character starts with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+pub enum StartingJob {
+    #[default]
+    Novice,
+    /// The Doram race, which skips the novice job.
+    Summoner,
+}
+
+impl StartingJob {
+    pub fn job_id(self) -> u16 {
+        match self {
+            StartingJob::Novice => 0,
+            StartingJob::Summoner => 4218,
+        }
+    }
+}
+
+/// Choices made when creating a new character.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+pub struct CharacterCreationOptions {
+    pub hair_style: u16,
+    pub hair_color: u16,
+    pub starting_job: StartingJob,
+    pub sex: Sex,
+}
+
+impl Default for CharacterCreationOptions {
+    fn default() -> Self {
+        Self {
+            hair_style: 0,
+            hair_color: 0,
+            starting_job: StartingJob::Novice,
+            sex: Sex::Male,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use ragnarok_packets::*;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
 pub struct EntityData {
     pub entity_id: EntityId,
     pub movement_speed: u16,
@@ -12,6 +13,90 @@ pub struct EntityData {
     pub maximum_health_points: i32,
     pub head_direction: usize,
     pub sex: Sex,
+    pub appearance: Appearance,
+    pub body_state: BodyState,
+    pub hidden: bool,
+}
+
+/// Status ailment that changes the pose of an entity. Sent by the server as
+/// the body state of the entity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
+pub enum BodyState {
+    #[default]
+    Normal,
+    /// The entity is slowly turning to stone and can still move.
+    Petrifying,
+    Stone,
+    Freeze,
+    Stun,
+    Sleep,
+    Imprison,
+}
+
+impl From<u16> for BodyState {
+    fn from(value: u16) -> Self {
+        match value {
+            1 => BodyState::Stone,
+            2 => BodyState::Freeze,
+            3 => BodyState::Stun,
+            4 => BodyState::Sleep,
+            6 => BodyState::Petrifying,
+            8 => BodyState::Imprison,
+            // Burning and all unknown states don't change the pose.
+            _ => BodyState::Normal,
+        }
+    }
+}
+
+/// Hiding, cloaking, chase walk, and invisibility.
+const HIDDEN_EFFECT_STATE_MASK: u32 = 0x2 | 0x4 | 0x40 | 0x4000;
+
+/// Whether the effect state of an entity makes it hidden from other players.
+pub(crate) fn is_hidden(effect_state: u32) -> bool {
+    effect_state & HIDDEN_EFFECT_STATE_MASK != 0
+}
+
+/// View ids of the equipment that is visible on the sprite of an entity. A
+/// view id of `0` means that nothing is shown in that slot.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
+pub struct Appearance {
+    pub head_top: u16,
+    pub head_middle: u16,
+    pub head_bottom: u16,
+    pub robe: u16,
+}
+
+/// Slot of the [`Appearance`] that changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
+pub enum AppearanceSlot {
+    HeadTop,
+    HeadMiddle,
+    HeadBottom,
+    Robe,
+}
+
+impl Appearance {
+    pub fn set(&mut self, slot: AppearanceSlot, view_id: u16) {
+        match slot {
+            AppearanceSlot::HeadTop => self.head_top = view_id,
+            AppearanceSlot::HeadMiddle => self.head_middle = view_id,
+            AppearanceSlot::HeadBottom => self.head_bottom = view_id,
+            AppearanceSlot::Robe => self.robe = view_id,
+        }
+    }
+}
+
+/// Movement of a nearby entity to a new position.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
+pub struct EntityMovement {
+    pub entity_id: EntityId,
+    pub origin: WorldPosition,
+    pub destination: WorldPosition,
+    pub starting_timestamp: ClientTick,
 }
 
 impl EntityData {
@@ -27,6 +112,16 @@ impl EntityData {
             maximum_health_points: character_information.maximum_health_points as i32,
             head_direction: 0, // TODO: get correct rotation
             sex: character_information.sex,
+            appearance: Appearance {
+                head_top: character_information.accessory2 as u16,
+                head_middle: character_information.accessory3 as u16,
+                head_bottom: character_information.accessory as u16,
+                // The character information doesn't contain the garment, so it is
+                // only known once the server sends a sprite change.
+                robe: 0,
+            },
+            body_state: BodyState::Normal,
+            hidden: false,
         }
     }
 }
@@ -44,6 +139,14 @@ impl From<EntityAppearedPacket> for EntityData {
             maximum_health_points: packet.maximum_health_points,
             head_direction: packet.head_direction as usize,
             sex: packet.sex,
+            appearance: Appearance {
+                head_top: packet.accessory2,
+                head_middle: packet.accessory3,
+                head_bottom: packet.accessory,
+                robe: packet.robe,
+            },
+            body_state: BodyState::from(packet.body_state),
+            hidden: is_hidden(packet.effect_state),
         }
     }
 }
@@ -61,6 +164,14 @@ impl From<EntityAppeared2Packet> for EntityData {
             maximum_health_points: packet.maximum_health_points,
             head_direction: packet.head_direction as usize,
             sex: packet.sex,
+            appearance: Appearance {
+                head_top: packet.accessory2,
+                head_middle: packet.accessory3,
+                head_bottom: packet.accessory,
+                robe: packet.robe,
+            },
+            body_state: BodyState::from(packet.body_state),
+            hidden: is_hidden(packet.effect_state),
         }
     }
 }
@@ -80,6 +191,36 @@ impl From<MovingEntityAppearedPacket> for EntityData {
             maximum_health_points: packet.maximum_health_points,
             head_direction: packet.head_direction as usize,
             sex: packet.sex,
+            appearance: Appearance {
+                head_top: packet.accessory2,
+                head_middle: packet.accessory3,
+                head_bottom: packet.accessory,
+                robe: packet.robe,
+            },
+            body_state: BodyState::from(packet.body_state),
+            hidden: is_hidden(packet.effect_state),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{BodyState, is_hidden};
+
+    #[test]
+    fn body_state_from_server_value() {
+        assert_eq!(BodyState::from(0), BodyState::Normal);
+        assert_eq!(BodyState::from(2), BodyState::Freeze);
+        assert_eq!(BodyState::from(6), BodyState::Petrifying);
+        // Burning doesn't change the pose.
+        assert_eq!(BodyState::from(7), BodyState::Normal);
+    }
+
+    #[test]
+    fn hiding_and_cloaking_hide_the_entity() {
+        assert!(is_hidden(0x2));
+        assert!(is_hidden(0x4 | 0x8));
+        assert!(!is_hidden(0x8));
+        assert!(!is_hidden(0));
+    }
+}
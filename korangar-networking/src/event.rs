@@ -5,12 +5,18 @@ use ragnarok_packets::*;
 use crate::hotkey::HotkeyState;
 use crate::items::ShopItem;
 use crate::{
-    CharacterServerLoginData, EntityData, InventoryItem, LoginServerLoginData, MessageColor, NoMetadata,
-    UnifiedCharacterSelectionFailedReason, UnifiedLoginFailedReason,
+    AppearanceSlot, BodyState, CharacterServerLoginData, EntityData, EntityMovement, GameplayError, InventoryItem, LoginServerLoginData,
+    MessageColor, NoMetadata, UnifiedCharacterSelectionFailedReason, UnifiedLoginFailedReason,
 };
 
+/// Messages and paths that are known at compile time. Serde borrows fields
+/// that are written as `&str` from the input, which would only allow
+/// deserializing events from `'static` input, so the alias hides the reference.
+pub(crate) type StaticStr = &'static str;
+
 /// An event triggered by one of the Ragnarok Online servers.
 #[derive(Debug)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
 pub enum NetworkEvent {
     LoginServerConnected {
         character_servers: Vec<CharacterServerInformation>,
@@ -18,7 +24,8 @@ pub enum NetworkEvent {
     },
     LoginServerConnectionFailed {
         reason: UnifiedLoginFailedReason,
-        message: &'static str,
+        #[cfg_attr(feature = "recording", serde(deserialize_with = "crate::recording::deserialize_static_str"))]
+        message: StaticStr,
     },
     LoginServerDisconnected {
         reason: DisconnectReason,
@@ -28,7 +35,8 @@ pub enum NetworkEvent {
     },
     CharacterServerConnectionFailed {
         reason: LoginFailedReason,
-        message: &'static str,
+        #[cfg_attr(feature = "recording", serde(deserialize_with = "crate::recording::deserialize_static_str"))]
+        message: StaticStr,
     },
     CharacterServerDisconnected {
         reason: DisconnectReason,
@@ -44,19 +52,22 @@ pub enum NetworkEvent {
     },
     CharacterSelectionFailed {
         reason: UnifiedCharacterSelectionFailedReason,
-        message: &'static str,
+        #[cfg_attr(feature = "recording", serde(deserialize_with = "crate::recording::deserialize_static_str"))]
+        message: StaticStr,
     },
     CharacterCreated {
         character_information: CharacterInformation,
     },
     CharacterCreationFailed {
         reason: CharacterCreationFailedReason,
-        message: &'static str,
+        #[cfg_attr(feature = "recording", serde(deserialize_with = "crate::recording::deserialize_static_str"))]
+        message: StaticStr,
     },
     CharacterDeleted,
     CharacterDeletionFailed {
         reason: CharacterDeletionFailedReason,
-        message: &'static str,
+        #[cfg_attr(feature = "recording", serde(deserialize_with = "crate::recording::deserialize_static_str"))]
+        message: StaticStr,
     },
     MapServerDisconnected {
         reason: DisconnectReason,
@@ -74,13 +85,19 @@ pub enum NetworkEvent {
     ResurrectPlayer {
         entity_id: EntityId,
     },
+    /// Make a player sit down.
+    PlayerSitDown {
+        entity_id: EntityId,
+    },
     /// Make a player stand up.
     PlayerStandUp {
         entity_id: EntityId,
     },
-    /// Add an entity to the list of entities that the client is aware of.
-    AddEntity {
-        entity_data: EntityData,
+    /// Add entities to the list of entities that the client is aware of.
+    /// Consecutive events are merged, so entities appearing in a burst are
+    /// added at once.
+    AddEntities {
+        entities: Vec<EntityData>,
     },
     /// Remove an entity from the list of entities that the client is aware of
     /// by its id.
@@ -94,12 +111,26 @@ pub enum NetworkEvent {
         destination: WorldPosition,
         starting_timestamp: ClientTick,
     },
-    /// An Entity nearby is pathing to a new position.
-    EntityMove {
+    /// Entities nearby are pathing to new positions. Consecutive events are
+    /// merged, just like [`NetworkEvent::AddEntities`].
+    EntityMoves {
+        movements: Vec<EntityMovement>,
+    },
+    /// An entity picked up an item from the ground.
+    EntityPickUpItem {
         entity_id: EntityId,
-        origin: WorldPosition,
-        destination: WorldPosition,
-        starting_timestamp: ClientTick,
+    },
+    /// The status ailments or visibility of an entity changed.
+    EntityStateChanged {
+        entity_id: EntityId,
+        body_state: BodyState,
+        hidden: bool,
+    },
+    /// An entity turned its body or head.
+    EntityDirectionChanged {
+        entity_id: EntityId,
+        direction: Direction,
+        head_direction: usize,
     },
     /// Player was moved to a new position on a different map or the current map
     ChangeMap {
@@ -109,6 +140,8 @@ pub enum NetworkEvent {
     /// Update the client side to keep server and client synchronized.
     UpdateClientTick {
         client_tick: ClientTick,
+        /// Not recorded, since it is set when the event is replayed.
+        #[cfg_attr(feature = "recording", serde(skip, default = "Instant::now"))]
         received_at: Instant,
     },
     /// New chat message for the client.
@@ -116,6 +149,11 @@ pub enum NetworkEvent {
         text: String,
         color: MessageColor,
     },
+    /// Another player whispered to the player.
+    WhisperReceived {
+        sender_name: String,
+        message: String,
+    },
     CharacterSlotSwitched,
     CharacterSlotSwitchFailed,
     /// Update entity details. Mostly received when the client sends
@@ -158,6 +196,11 @@ pub enum NetworkEvent {
         choices: Vec<String>,
         npc_id: EntityId,
     },
+    /// Show or hide an illustration of the NPC the player is talking to.
+    DisplayImage {
+        image_name: String,
+        location: ImageLocation,
+    },
     AddQuestEffect {
         quest_effect: QuestEffectPacket,
     },
@@ -177,6 +220,10 @@ pub enum NetworkEvent {
         index: InventoryIndex,
         equipped_position: EquipPosition,
     },
+    /// The map server refused to equip an item.
+    ItemEquipFailed {
+        index: InventoryIndex,
+    },
     ChangeJob {
         account_id: AccountId,
         job_id: u32,
@@ -185,12 +232,18 @@ pub enum NetworkEvent {
         account_id: AccountId,
         hair_id: u32,
     },
+    ChangeAppearance {
+        account_id: AccountId,
+        slot: AppearanceSlot,
+        view_id: u16,
+    },
     LoggedOut,
     FriendRequest {
         requestee: Friend,
     },
     VisualEffect {
-        effect_path: &'static str,
+        #[cfg_attr(feature = "recording", serde(deserialize_with = "crate::recording::deserialize_static_str"))]
+        effect_path: StaticStr,
         entity_id: EntityId,
     },
     AddSkillUnit {
@@ -211,6 +264,21 @@ pub enum NetworkEvent {
         account_id: AccountId,
         character_id: CharacterId,
     },
+    /// A friend logged in or out.
+    FriendOnlineStatusChanged {
+        name: String,
+        online: bool,
+    },
+    /// An item that the server marks as a rare drop fell to the ground.
+    RareItemDropped {
+        item_id: ItemId,
+        is_identified: bool,
+        count: u16,
+    },
+    /// The player completed an achievement.
+    AchievementCompleted {
+        achievement_id: u32,
+    },
     SetHotkeyData {
         tab: HotbarTab,
         hotkeys: Vec<HotkeyState>,
@@ -230,17 +298,203 @@ pub enum NetworkEvent {
     SellingCompleted {
         result: SellItemsResult,
     },
+    /// The bank was opened, or the server refused to open it.
+    BankOpened {
+        result: OpenBankResult,
+    },
+    BankClosed,
+    /// Current balance of the bank account. Both the balance and the zeny
+    /// of the player are sent after every deposit and withdrawal.
+    BankBalance {
+        balance: i64,
+    },
+    BankDepositCompleted {
+        result: BankDepositResult,
+        balance: i64,
+        zeny: i32,
+    },
+    BankWithdrawCompleted {
+        result: BankWithdrawResult,
+        balance: i64,
+        zeny: i32,
+    },
+    OpenAuction,
+    CloseAuction,
+    /// One page of auctions, either from a search or from the list of the
+    /// player's own auctions.
+    AuctionListings {
+        listings: Vec<AuctionItemInformation>,
+        page_count: u32,
+    },
+    /// The server accepted or rejected the item the player wants to put up
+    /// for auction.
+    AuctionItemSet {
+        index: InventoryIndex,
+        success: bool,
+    },
+    AuctionResult {
+        result: AuctionResult,
+    },
+    OpenRoulette {
+        serial: u32,
+        step: u16,
+        index: u16,
+        gold_points: u32,
+        silver_points: u32,
+        bronze_points: u32,
+    },
+    /// Items on the roulette board.
+    RouletteBoard {
+        serial: u32,
+        items: Vec<RouletteItemInformation>,
+    },
+    CloseRoulette,
+    /// The roulette was spun. If the player `won` the prize at the new
+    /// position can be claimed.
+    RouletteSpun {
+        won: bool,
+        step: u16,
+        index: u16,
+        gold_points: u32,
+        silver_points: u32,
+        bronze_points: u32,
+    },
+    RouletteRewardClaimed,
+    /// The server opened the attendance interface.
+    OpenAttendance {
+        claimed_days: u32,
+    },
+    /// The attendance reward of the day was claimed. The reward is sent by
+    /// mail.
+    AttendanceRewardClaimed {
+        claimed_days: u32,
+    },
+    /// The party is waiting for a memorial dungeon to be created.
+    InstanceQueued {
+        name: String,
+        queue_position: u16,
+    },
+    InstanceQueuePositionChanged {
+        queue_position: u16,
+    },
+    /// The memorial dungeon was created and can be entered. Both times are
+    /// Unix timestamps.
+    InstanceReady {
+        name: String,
+        destroy_time: u32,
+        enter_time_limit: u32,
+    },
+    InstanceDestroyed {
+        reason: InstanceDestroyReason,
+    },
+    /// The item at `index` is now the equipped ammunition.
+    AmmunitionEquipped {
+        index: InventoryIndex,
+    },
     InventoryItemRemoved {
         reason: RemoveItemReason,
         index: InventoryIndex,
         amount: u16,
     },
+    /// An entity used an item. If the entity is the player, `amount` is the
+    /// amount of the item that is left in the inventory.
+    ItemUsed {
+        entity_id: EntityId,
+        index: InventoryIndex,
+        item_id: ItemId,
+        amount: u16,
+        success: bool,
+    },
     AttackFailed {
         target_entity_id: EntityId,
         target_position: TilePosition,
         player_position: TilePosition,
         attack_range: AttackRange,
     },
+    /// The server rejected an action or sent a packet that doesn't fit the
+    /// current state.
+    GameplayError {
+        error: GameplayError,
+    },
+    /// A member joined the party, changed maps or logged in or out. The
+    /// position is only meaningful if the member is on `map_name`.
+    PartyMemberUpdated {
+        account_id: AccountId,
+        name: String,
+        map_name: String,
+        position: TilePosition,
+        online: bool,
+    },
+    PartyMemberLeft {
+        account_id: AccountId,
+    },
+    PartyMemberMoved {
+        account_id: AccountId,
+        position: TilePosition,
+    },
+    PartyMemberHealthChanged {
+        account_id: AccountId,
+        health_points: u32,
+        maximum_health_points: u32,
+    },
+}
+
+/// Order in which events are processed when more events arrive than can be
+/// processed in a single frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EventPriority {
+    /// Connection and character selection events, which are never deferred.
+    Critical,
+    /// Events that change the game state. These are processed in the order
+    /// they arrived.
+    Regular,
+    /// Purely visual events, which are only processed once all regular events
+    /// are handled.
+    Cosmetic,
+}
+
+impl NetworkEvent {
+    pub fn priority(&self) -> EventPriority {
+        match self {
+            NetworkEvent::LoginServerConnected { .. }
+            | NetworkEvent::LoginServerConnectionFailed { .. }
+            | NetworkEvent::LoginServerDisconnected { .. }
+            | NetworkEvent::CharacterServerConnected { .. }
+            | NetworkEvent::CharacterServerConnectionFailed { .. }
+            | NetworkEvent::CharacterServerDisconnected { .. }
+            | NetworkEvent::AccountId { .. }
+            | NetworkEvent::CharacterList { .. }
+            | NetworkEvent::CharacterSelected { .. }
+            | NetworkEvent::CharacterSelectionFailed { .. }
+            | NetworkEvent::CharacterCreated { .. }
+            | NetworkEvent::CharacterCreationFailed { .. }
+            | NetworkEvent::CharacterDeleted
+            | NetworkEvent::CharacterDeletionFailed { .. }
+            | NetworkEvent::CharacterSlotSwitched
+            | NetworkEvent::CharacterSlotSwitchFailed
+            | NetworkEvent::MapServerDisconnected { .. } => EventPriority::Critical,
+            NetworkEvent::DamageEffect { .. } | NetworkEvent::HealEffect { .. } | NetworkEvent::VisualEffect { .. } => {
+                EventPriority::Cosmetic
+            }
+            _ => EventPriority::Regular,
+        }
+    }
+
+    /// Append the entities or movements of `event` to this event if both are
+    /// batches of the same kind. Otherwise `event` is returned unchanged.
+    pub(crate) fn merge(&mut self, event: NetworkEvent) -> Option<NetworkEvent> {
+        match (self, event) {
+            (NetworkEvent::AddEntities { entities }, NetworkEvent::AddEntities { entities: other }) => {
+                entities.extend(other);
+                None
+            }
+            (NetworkEvent::EntityMoves { movements }, NetworkEvent::EntityMoves { movements: other }) => {
+                movements.extend(other);
+                None
+            }
+            (_, event) => Some(event),
+        }
+    }
 }
 
 /// New-type so we can implement some `From` traits. This will help when
@@ -278,6 +532,33 @@ impl From<NoNetworkEvents> for NetworkEventList {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
+pub enum InstanceDestroyReason {
+    /// The time limit of the memorial dungeon ran out.
+    TimeLimit,
+    /// Nobody entered the memorial dungeon in time.
+    EnterTimeLimit,
+    /// The party leader destroyed the memorial dungeon.
+    Requested,
+    /// The memorial dungeon could not be created.
+    CreationFailed,
+}
+
+impl std::fmt::Display for InstanceDestroyReason {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstanceDestroyReason::TimeLimit => write!(formatter, "The memorial dungeon expired and was destroyed"),
+            InstanceDestroyReason::EnterTimeLimit => {
+                write!(formatter, "The memorial dungeon was not entered in time and was destroyed")
+            }
+            InstanceDestroyReason::Requested => write!(formatter, "The memorial dungeon was destroyed"),
+            InstanceDestroyReason::CreationFailed => write!(formatter, "Failed to create the memorial dungeon"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
 pub enum DisconnectReason {
     ClosedByClient,
     ConnectionError,
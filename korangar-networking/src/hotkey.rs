@@ -1,6 +1,7 @@
 use ragnarok_packets::HotkeyData;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
 pub enum HotkeyState {
     Bound(HotkeyData),
     Unbound,
@@ -1,9 +1,15 @@
 use ragnarok_packets::{EquipPosition, EquippableItemFlags, InventoryIndex, ItemId, ItemOptions, Price, RegularItemFlags};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
 pub struct NoMetadata;
 
+/// Card slots of forged, named and pet egg items don't contain cards but
+/// information about the item, so they start with one of these markers.
+const SPECIAL_SLOT_MARKERS: [u32; 3] = [0x00FF, 0x00FE, 0xFF00];
+
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
 pub enum InventoryItemDetails {
     Regular {
         amount: u16,
@@ -24,6 +30,7 @@ pub enum InventoryItemDetails {
 }
 
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
 pub struct InventoryItem<Meta> {
     pub metadata: Meta,
     pub index: InventoryIndex,
@@ -41,9 +48,40 @@ impl<Meta> InventoryItem<Meta> {
             InventoryItemDetails::Equippable { flags, .. } => flags.contains(EquippableItemFlags::IDENTIFIED),
         }
     }
+
+    /// Position the item is equipped at. Regular items can be equipped as
+    /// ammunition.
+    pub fn equipped_position(&self) -> EquipPosition {
+        match &self.details {
+            InventoryItemDetails::Regular { equipped_position, .. } | InventoryItemDetails::Equippable { equipped_position, .. } => {
+                *equipped_position
+            }
+        }
+    }
+
+    /// Ids of the cards and enchantments in the slots of the item.
+    pub fn cards(&self) -> impl Iterator<Item = ItemId> + '_ {
+        let has_cards = !SPECIAL_SLOT_MARKERS.contains(&self.slot[0]);
+
+        self.slot
+            .iter()
+            .filter(move |card| has_cards && **card != 0)
+            .map(|card| ItemId(*card))
+    }
+
+    /// Random options of the item.
+    pub fn options(&self) -> &[ItemOptions] {
+        match &self.details {
+            InventoryItemDetails::Regular { .. } => &[],
+            InventoryItemDetails::Equippable {
+                option_count, option_data, ..
+            } => &option_data[..(*option_count as usize).min(option_data.len())],
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
 pub enum ItemQuantity {
     Fixed(u32),
     Infinite,
@@ -59,6 +97,7 @@ impl From<u32> for ItemQuantity {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
 pub struct ShopItem<Meta> {
     pub metadata: Meta,
     pub item_id: ItemId,
@@ -1,14 +1,19 @@
 #![cfg_attr(feature = "interface", feature(impl_trait_in_assoc_type))]
 #![cfg_attr(feature = "interface", feature(negative_impls))]
 
+mod character;
 mod entity;
 mod event;
 mod hotkey;
 mod items;
 mod message;
 mod packet_versions;
+mod rate_limit;
+#[cfg(feature = "recording")]
+mod recording;
 mod server;
 
+use std::collections::VecDeque;
 use std::net::{IpAddr, SocketAddr};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
@@ -20,6 +25,7 @@ use ragnarok_bytes::encoding::UTF_8;
 use ragnarok_bytes::{ByteReader, ByteWriter, FromBytes};
 use ragnarok_packets::handler::{DuplicateHandlerError, HandlerResult, NoPacketCallback, PacketCallback, PacketHandler};
 use ragnarok_packets::*;
+use rate_limit::ActionRateLimiter;
 use server::{ServerConnectCommand, ServerConnection};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
@@ -27,24 +33,119 @@ use tokio::sync::mpsc::error::TryRecvError;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::task::JoinHandle;
 
-pub use self::entity::EntityData;
-pub use self::event::{DisconnectReason, NetworkEvent};
+pub use self::character::{CharacterCreationOptions, StartingJob};
+pub use self::entity::{Appearance, AppearanceSlot, BodyState, EntityData, EntityMovement};
+pub use self::event::{DisconnectReason, EventPriority, InstanceDestroyReason, NetworkEvent};
 pub use self::hotkey::HotkeyState;
 pub use self::items::{InventoryItem, InventoryItemDetails, ItemQuantity, NoMetadata, SellItem, ShopItem};
 pub use self::message::MessageColor;
 pub use self::packet_versions::SupportedPacketVersion;
+pub use self::rate_limit::RateLimitedAction;
+#[cfg(feature = "recording")]
+pub use self::recording::{NetworkPlayback, NetworkRecorder, RecordingError};
 pub use self::server::{
-    CharacterServerLoginData, LoginServerLoginData, NotConnectedError, UnifiedCharacterSelectionFailedReason, UnifiedLoginFailedReason,
+    CharacterServerLoginData, GameplayError, GameplayResult, LoginServerLoginData, UnifiedCharacterSelectionFailedReason,
+    UnifiedLoginFailedReason,
 };
 use crate::server::NetworkTaskError;
 
-/// Buffer for networking events. This struct exists to reduce heap allocations
-/// and is purely an optimization.
-pub struct NetworkEventBuffer(Vec<NetworkEvent>);
+/// Minimum free space in the receive buffer before reading from the server.
+const RECEIVE_BUFFER_SIZE: usize = 8192;
+/// Largest possible packet, since the length of variable length packets is
+/// stored as a `u16`.
+const MAXIMUM_PACKET_SIZE: usize = u16::MAX as usize;
+
+/// Metrics of the [`NetworkEventBuffer`], to spot frames where the client
+/// can't keep up with the server.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkEventStatistics {
+    /// Number of events processed in the last frame.
+    pub processed_events: usize,
+    /// Number of events that were deferred to the next frame.
+    pub queued_events: usize,
+    /// Highest number of events that were waiting at the start of a frame.
+    pub maximum_queue_depth: usize,
+    /// Number of events dropped because they belonged to a previous map or a
+    /// closed connection.
+    pub dropped_events: usize,
+}
+
+/// Buffer for networking events. Events are sorted by their
+/// [`EventPriority`] and only a limited number of them is handed out every
+/// frame, so a flood of events doesn't stall the client.
+#[derive(Default)]
+pub struct NetworkEventBuffer {
+    critical_events: Vec<NetworkEvent>,
+    regular_events: VecDeque<NetworkEvent>,
+    cosmetic_events: VecDeque<NetworkEvent>,
+    frame_events: Vec<NetworkEvent>,
+    statistics: NetworkEventStatistics,
+}
 
 impl NetworkEventBuffer {
-    pub fn drain(&mut self) -> std::vec::Drain<'_, NetworkEvent> {
-        self.0.drain(..)
+    /// Add an event. Events that don't come from a server, like the events of
+    /// offline scripts, can be added as well.
+    pub fn push(&mut self, event: NetworkEvent) {
+        match &event {
+            // Events of a closed map server connection can't be processed anymore.
+            NetworkEvent::MapServerDisconnected { .. } => {
+                self.statistics.dropped_events += self.regular_events.len() + self.cosmetic_events.len();
+                self.regular_events.clear();
+                self.cosmetic_events.clear();
+            }
+            // Effects that were deferred on the previous map would play on the new one.
+            NetworkEvent::ChangeMap { .. } => {
+                self.statistics.dropped_events += self.cosmetic_events.len();
+                self.cosmetic_events.clear();
+            }
+            _ => {}
+        }
+
+        match event.priority() {
+            EventPriority::Critical => self.critical_events.push(event),
+            EventPriority::Regular => {
+                let event = match self.regular_events.back_mut() {
+                    Some(last_event) => last_event.merge(event),
+                    None => Some(event),
+                };
+
+                if let Some(event) = event {
+                    self.regular_events.push_back(event);
+                }
+            }
+            EventPriority::Cosmetic => self.cosmetic_events.push_back(event),
+        }
+    }
+
+    /// Take the events of this frame. All critical events are processed,
+    /// followed by at most `event_budget` regular and cosmetic events. The
+    /// remaining events are kept for the next frame.
+    pub fn drain(&mut self, event_budget: usize) -> std::vec::Drain<'_, NetworkEvent> {
+        let queue_depth = self.critical_events.len() + self.regular_events.len() + self.cosmetic_events.len();
+
+        self.frame_events.append(&mut self.critical_events);
+
+        let regular_count = self.regular_events.len().min(event_budget);
+        self.frame_events.extend(self.regular_events.drain(..regular_count));
+
+        let cosmetic_count = self.cosmetic_events.len().min(event_budget - regular_count);
+        self.frame_events.extend(self.cosmetic_events.drain(..cosmetic_count));
+
+        self.statistics.processed_events = self.frame_events.len();
+        self.statistics.queued_events = queue_depth - self.frame_events.len();
+        self.statistics.maximum_queue_depth = self.statistics.maximum_queue_depth.max(queue_depth);
+
+        self.frame_events.drain(..)
+    }
+
+    pub fn statistics(&self) -> NetworkEventStatistics {
+        self.statistics
+    }
+}
+
+impl Extend<NetworkEvent> for NetworkEventBuffer {
+    fn extend<T: IntoIterator<Item = NetworkEvent>>(&mut self, events: T) {
+        events.into_iter().for_each(|event| self.push(event));
     }
 }
 
@@ -89,7 +190,10 @@ pub struct NetworkingSystem<Callback> {
     login_server_connection: ServerConnection,
     character_server_connection: ServerConnection,
     map_server_connection: ServerConnection,
+    rate_limiter: ActionRateLimiter,
     packet_callback: Callback,
+    #[cfg(feature = "recording")]
+    recorder: Option<NetworkRecorder>,
 }
 
 impl NetworkingSystem<NoPacketCallback> {
@@ -114,9 +218,12 @@ where
             login_server_connection: ServerConnection::Disconnected,
             character_server_connection: ServerConnection::Disconnected,
             map_server_connection: ServerConnection::Disconnected,
+            rate_limiter: ActionRateLimiter::default(),
             packet_callback,
+            #[cfg(feature = "recording")]
+            recorder: None,
         };
-        let event_buffer = NetworkEventBuffer(Vec::new());
+        let event_buffer = NetworkEventBuffer::default();
 
         (networking_system, event_buffer)
     }
@@ -234,7 +341,7 @@ where
         (command_sender, time_synchronization)
     }
 
-    fn handle_connection<Event>(connection: &mut ServerConnection, event_buffer: &mut NetworkEventBuffer)
+    fn handle_connection<Event>(connection: &mut ServerConnection, events: &mut impl Extend<NetworkEvent>)
     where
         Event: DisconnectedEvent,
     {
@@ -246,7 +353,7 @@ where
             } => loop {
                 match event_receiver.try_recv() {
                     Ok(login_event) => {
-                        event_buffer.0.push(login_event);
+                        events.extend(Some(login_event));
                     }
                     Err(TryRecvError::Empty) => {
                         *connection = ServerConnection::Connected {
@@ -257,24 +364,69 @@ where
                         break;
                     }
                     Err(..) => {
-                        event_buffer.0.push(Event::create_event(DisconnectReason::ConnectionError));
+                        events.extend(Some(Event::create_event(DisconnectReason::ConnectionError)));
                         *connection = ServerConnection::Disconnected;
                         break;
                     }
                 }
             },
             ServerConnection::ClosingManually => {
-                event_buffer.0.push(Event::create_event(DisconnectReason::ClosedByClient));
+                events.extend(Some(Event::create_event(DisconnectReason::ClosedByClient)));
                 *connection = ServerConnection::Disconnected;
             }
             _ => (),
         };
     }
 
-    pub fn get_events(&mut self, events: &mut NetworkEventBuffer) {
+    fn receive_events(&mut self, events: &mut impl Extend<NetworkEvent>) {
         Self::handle_connection::<LoginServerDisconnectedEvent>(&mut self.login_server_connection, events);
         Self::handle_connection::<CharacterServerDisconnectedEvent>(&mut self.character_server_connection, events);
         Self::handle_connection::<MapServerDisconnectedEvent>(&mut self.map_server_connection, events);
+
+        if let Some(position) = self.rate_limiter.take_pending_move(Instant::now()) {
+            let _ = self.send_player_move(position);
+        }
+    }
+
+    pub fn get_events(&mut self, events: &mut NetworkEventBuffer) {
+        // Events are recorded in the order they are received, before they are
+        // sorted by their priority.
+        #[cfg(feature = "recording")]
+        if let Some(mut recorder) = self.recorder.take() {
+            let mut received_events = Vec::new();
+            self.receive_events(&mut received_events);
+            recorder.record(&received_events);
+            events.extend(received_events);
+            self.recorder = Some(recorder);
+            return;
+        }
+
+        self.receive_events(events);
+    }
+
+    /// Change how often an action may be sent to the map server. Servers with
+    /// stricter flood protection might need longer intervals.
+    pub fn set_minimum_action_interval(&mut self, action: RateLimitedAction, interval: Duration) {
+        self.rate_limiter.set_minimum_interval(action, interval);
+    }
+
+    fn acquire_action(&mut self, action: RateLimitedAction) -> GameplayResult<()> {
+        match self.rate_limiter.try_acquire(action, Instant::now()) {
+            true => Ok(()),
+            false => Err(GameplayError::RateLimited { action: action.name() }),
+        }
+    }
+
+    /// Record all events that are received from now on.
+    #[cfg(feature = "recording")]
+    pub fn start_recording(&mut self, recorder: NetworkRecorder) {
+        self.recorder = Some(recorder);
+    }
+
+    /// Stop recording and hand out the recorder, so it can be flushed.
+    #[cfg(feature = "recording")]
+    pub fn stop_recording(&mut self) -> Option<NetworkRecorder> {
+        self.recorder.take()
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -297,77 +449,90 @@ where
     {
         let mut stream = TcpStream::connect(address).await.map_err(|_| NetworkTaskError::FailedToConnect)?;
         let mut interval = tokio::time::interval(ping_frequency);
-        let mut buffer = [0u8; 8192];
-        let mut cut_off_buffer_base = 0;
+        let mut receive_buffer: Vec<u8> = Vec::with_capacity(RECEIVE_BUFFER_SIZE);
+        let mut send_buffer = Vec::new();
         let mut events = Vec::new();
         let mut byte_writer = ByteWriter::with_encoding(UTF_8);
 
         loop {
+            receive_buffer.reserve(RECEIVE_BUFFER_SIZE);
+
             tokio::select! {
-                // Send a packet to the server.
+                // Send packets to the server.
                 action = action_receiver.recv() => {
                     let Some(action) = action else {
                         // Channel was closed by the main thread.
                         break Ok(());
                     };
 
-                    stream.write_all(&action).await.map_err(|_| NetworkTaskError::ConnectionClosed)?;
+                    send_buffer.extend_from_slice(&action);
+
+                    // Gather all packets that were queued in the meantime, so bursts of small
+                    // packets (like movement requests) are sent with a single write.
+                    while let Ok(action) = action_receiver.try_recv() {
+                        send_buffer.extend_from_slice(&action);
+                    }
+
+                    stream.write_all(&send_buffer).await.map_err(|_| NetworkTaskError::ConnectionClosed)?;
+                    send_buffer.clear();
                 }
                 // Receive some packets from the server.
-                received_bytes = stream.read(&mut buffer[cut_off_buffer_base..]) => {
+                received_bytes = stream.read_buf(&mut receive_buffer) => {
                     let Ok(received_bytes) = received_bytes else {
                         // Channel was closed by the main thread.
                         break Err(NetworkTaskError::ConnectionClosed);
                     };
 
                     if received_bytes == 0 {
-                        // Receiving Ok(0) means the stream was closed by the server, most
-                        // likely because the client sent an incorrect packet.
+                        // The server closed the connection.
                         break Err(NetworkTaskError::ConnectionClosed);
                     }
 
-                    let data = &buffer[..cut_off_buffer_base + received_bytes];
-                    let mut byte_reader = ByteReader::without_metadata(data);
+                    let mut byte_reader = ByteReader::without_metadata(&receive_buffer);
                     byte_reader.set_encoding(UTF_8);
 
                     if read_account_id {
-                        let account_id = AccountId::from_bytes(&mut byte_reader).unwrap();
+                        let Ok(account_id) = AccountId::from_bytes(&mut byte_reader) else {
+                            // Wait for the rest of the account id.
+                            continue;
+                        };
+
                         events.push(NetworkEvent::AccountId { account_id });
                         read_account_id = false;
                     }
 
+                    let mut discard_remaining = false;
+
                     while !byte_reader.is_empty() {
                         match packet_handler.process_one(&mut byte_reader) {
                             HandlerResult::Ok(packet_events) => events.extend(packet_events.0.into_iter()),
                             HandlerResult::PacketCutOff => {
-                                let packet_start = byte_reader.get_offset();
-                                let packet_end = cut_off_buffer_base + received_bytes;
-
-                                if packet_start == 0 {
-                                    // If the packet_start is 0, that means the packet is allegidly bigger than the MTU of a TCP packet.
-                                    // We limit the size of a packet to the MTU, to avoid getting stuck on packets that are parsed incorrectly.
-                                    // TODO: Call the packet callback?
-                                    cut_off_buffer_base = 0;
-                                    break;
-                                }
-
-                                buffer.copy_within(packet_start..packet_end, 0);
-                                cut_off_buffer_base = packet_end - packet_start;
-
+                                // A packet can never be longer than its u16 length field allows. If it
+                                // allegedly is, it was most likely parsed incorrectly, so we discard it
+                                // to avoid waiting for bytes that will never arrive.
+                                // TODO: Call the packet callback?
+                                discard_remaining = byte_reader.remaining_length() > MAXIMUM_PACKET_SIZE;
                                 break;
                             },
                             // The packet callback can take care of handling these properly.
                             HandlerResult::UnhandledPacket => {
-                                cut_off_buffer_base = 0;
+                                discard_remaining = true;
                                 break
                             },
                             HandlerResult::InternalError(..) => {
-                                cut_off_buffer_base = 0;
+                                discard_remaining = true;
                                 break
                             },
                         }
                     }
 
+                    // Keep only the bytes of the cut-off packet, so it is parsed once it is complete.
+                    let processed_bytes = match discard_remaining {
+                        true => receive_buffer.len(),
+                        false => byte_reader.get_offset(),
+                    };
+                    receive_buffer.drain(..processed_bytes);
+
                     for event in events.drain(..) {
                         if let NetworkEvent::UpdateClientTick {client_tick,received_at} = &event && let Ok(mut time_synchronization) = time_synchronization.lock() {
                             time_synchronization.estimated_client_tick(client_tick.0, *received_at);
@@ -519,6 +684,7 @@ where
             event_receiver,
             packet_version,
         };
+        self.rate_limiter.reset();
     }
 
     pub fn disconnect_from_login_server(&mut self) {
@@ -545,21 +711,21 @@ where
         matches!(self.map_server_connection, ServerConnection::Connected { .. })
     }
 
-    fn character_server_packet_version(&self) -> Result<SupportedPacketVersion, NotConnectedError> {
+    fn character_server_packet_version(&self) -> GameplayResult<SupportedPacketVersion> {
         match &self.character_server_connection {
             ServerConnection::Connected { packet_version, .. } => Ok(*packet_version),
-            _ => Err(NotConnectedError),
+            _ => Err(GameplayError::NotConnected),
         }
     }
 
-    fn map_server_packet_version(&self) -> Result<SupportedPacketVersion, NotConnectedError> {
+    fn map_server_packet_version(&self) -> GameplayResult<SupportedPacketVersion> {
         match &self.map_server_connection {
             ServerConnection::Connected { packet_version, .. } => Ok(*packet_version),
-            _ => Err(NotConnectedError),
+            _ => Err(GameplayError::NotConnected),
         }
     }
 
-    fn send_character_server_packet(&mut self, packet: impl CharacterServerPacket) -> Result<(), NotConnectedError> {
+    fn send_character_server_packet(&mut self, packet: impl CharacterServerPacket) -> GameplayResult<()> {
         match &mut self.character_server_connection {
             ServerConnection::Connected { action_sender, .. } => {
                 self.packet_callback.outgoing_packet(&packet);
@@ -567,13 +733,15 @@ where
                 // FIX: Don't unwrap.
                 let mut byte_writer = ByteWriter::with_encoding(UTF_8);
                 packet.packet_to_bytes(&mut byte_writer).unwrap();
-                action_sender.send(byte_writer.into_inner()).map_err(|_| NotConnectedError)
+                action_sender
+                    .send(byte_writer.into_inner())
+                    .map_err(|_| GameplayError::NotConnected)
             }
-            _ => Err(NotConnectedError),
+            _ => Err(GameplayError::NotConnected),
         }
     }
 
-    fn send_map_server_packet(&mut self, packet: impl MapServerPacket) -> Result<(), NotConnectedError> {
+    fn send_map_server_packet(&mut self, packet: impl MapServerPacket) -> GameplayResult<()> {
         match &mut self.map_server_connection {
             ServerConnection::Connected { action_sender, .. } => {
                 self.packet_callback.outgoing_packet(&packet);
@@ -581,9 +749,11 @@ where
                 // FIX: Don't unwrap.
                 let mut byte_writer = ByteWriter::with_encoding(UTF_8);
                 packet.packet_to_bytes(&mut byte_writer).unwrap();
-                action_sender.send(byte_writer.into_inner()).map_err(|_| NotConnectedError)
+                action_sender
+                    .send(byte_writer.into_inner())
+                    .map_err(|_| GameplayError::NotConnected)
             }
-            _ => Err(NotConnectedError),
+            _ => Err(GameplayError::NotConnected),
         }
     }
 
@@ -626,32 +796,39 @@ where
         Ok(packet_handler)
     }
 
-    pub fn request_character_list(&mut self) -> Result<(), NotConnectedError> {
+    pub fn request_character_list(&mut self) -> GameplayResult<()> {
         match self.character_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_character_server_packet(RequestCharacterListPacket::default()),
         }
     }
 
-    pub fn select_character(&mut self, character_slot: usize) -> Result<(), NotConnectedError> {
+    pub fn select_character(&mut self, character_slot: usize) -> GameplayResult<()> {
         match self.character_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_character_server_packet(SelectCharacterPacket::new(character_slot as u8)),
         }
     }
 
-    pub fn create_character(&mut self, slot: usize, name: String) -> Result<(), NotConnectedError> {
-        let hair_color = 0;
-        let hair_style = 0;
-        let start_job = 0;
-        let sex = Sex::Male;
+    pub fn create_character(&mut self, slot: usize, name: String, options: CharacterCreationOptions) -> GameplayResult<()> {
+        let CharacterCreationOptions {
+            hair_style,
+            hair_color,
+            starting_job,
+            sex,
+        } = options;
 
         match self.character_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_character_server_packet(CreateCharacterPacket::new(
-                name, slot as u8, hair_color, hair_style, start_job, sex,
+                name,
+                slot as u8,
+                hair_color,
+                hair_style,
+                starting_job.job_id(),
+                sex,
             )),
         }
     }
 
-    pub fn delete_character(&mut self, character_id: CharacterId) -> Result<(), NotConnectedError> {
+    pub fn delete_character(&mut self, character_id: CharacterId) -> GameplayResult<()> {
         let email = "a@a.com".to_string();
 
         match self.character_server_packet_version()? {
@@ -659,7 +836,7 @@ where
         }
     }
 
-    pub fn switch_character_slot(&mut self, origin_slot: usize, destination_slot: usize) -> Result<(), NotConnectedError> {
+    pub fn switch_character_slot(&mut self, origin_slot: usize, destination_slot: usize) -> GameplayResult<()> {
         match self.character_server_packet_version()? {
             SupportedPacketVersion::_20220406 => {
                 self.send_character_server_packet(SwitchCharacterSlotPacket::new(origin_slot as u16, destination_slot as u16))
@@ -667,13 +844,13 @@ where
         }
     }
 
-    pub fn map_loaded(&mut self) -> Result<(), NotConnectedError> {
+    pub fn map_loaded(&mut self) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(MapLoadedPacket::default()),
         }
     }
 
-    pub fn request_client_tick(&mut self) -> Result<(), NotConnectedError> {
+    pub fn request_client_tick(&mut self) -> GameplayResult<()> {
         let client_tick = self
             .time_synchronization
             .lock()
@@ -685,135 +862,185 @@ where
         }
     }
 
-    pub fn respawn(&mut self) -> Result<(), NotConnectedError> {
+    pub fn respawn(&mut self) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(RestartPacket::new(RestartType::Respawn)),
         }
     }
 
-    pub fn log_out(&mut self) -> Result<(), NotConnectedError> {
+    pub fn log_out(&mut self) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(RestartPacket::new(RestartType::Disconnect)),
         }
     }
 
-    pub fn player_move(&mut self, position: WorldPosition) -> Result<(), NotConnectedError> {
+    /// Throttled moves are not lost, only the latest destination is sent once
+    /// the rate limit allows it.
+    pub fn player_move(&mut self, position: WorldPosition) -> GameplayResult<()> {
+        // Check the connection first, so the rate limiter doesn't hold on to a
+        // destination that can never be sent.
+        self.map_server_packet_version()?;
+
+        match self.rate_limiter.request_move(position, Instant::now()) {
+            Some(position) => self.send_player_move(position),
+            None => Ok(()),
+        }
+    }
+
+    fn send_player_move(&mut self, position: WorldPosition) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestPlayerMovePacket::new(position)),
         }
     }
 
-    pub fn warp_to_map(&mut self, map_name: String, position: TilePosition) -> Result<(), NotConnectedError> {
+    pub fn warp_to_map(&mut self, map_name: String, position: TilePosition) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestWarpToMapPacket::new(map_name, position)),
         }
     }
 
-    pub fn entity_details(&mut self, entity_id: EntityId) -> Result<(), NotConnectedError> {
+    pub fn entity_details(&mut self, entity_id: EntityId) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestDetailsPacket::new(entity_id)),
         }
     }
 
-    pub fn player_attack(&mut self, entity_id: EntityId) -> Result<(), NotConnectedError> {
-        match self.map_server_packet_version()? {
+    pub fn player_attack(&mut self, entity_id: EntityId) -> GameplayResult<()> {
+        let packet_version = self.map_server_packet_version()?;
+        self.acquire_action(RateLimitedAction::Attack)?;
+
+        match packet_version {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestActionPacket::new(entity_id, Action::Attack)),
         }
     }
 
-    pub fn send_chat_message(&mut self, player_name: &str, text: &str) -> Result<(), NotConnectedError> {
+    pub fn send_chat_message(&mut self, player_name: &str, text: &str) -> GameplayResult<()> {
         let message = format!("{} : {}", player_name, text);
+        let packet_version = self.map_server_packet_version()?;
+        self.acquire_action(RateLimitedAction::ChatMessage)?;
 
-        match self.map_server_packet_version()? {
+        match packet_version {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(GlobalMessagePacket::new(message)),
         }
     }
 
-    pub fn start_dialog(&mut self, npc_id: EntityId) -> Result<(), NotConnectedError> {
+    pub fn start_dialog(&mut self, npc_id: EntityId) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(StartDialogPacket::new(npc_id)),
         }
     }
 
-    pub fn next_dialog(&mut self, npc_id: EntityId) -> Result<(), NotConnectedError> {
+    pub fn next_dialog(&mut self, npc_id: EntityId) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(NextDialogPacket::new(npc_id)),
         }
     }
 
-    pub fn close_dialog(&mut self, npc_id: EntityId) -> Result<(), NotConnectedError> {
+    pub fn close_dialog(&mut self, npc_id: EntityId) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(CloseDialogPacket::new(npc_id)),
         }
     }
 
-    pub fn choose_dialog_option(&mut self, npc_id: EntityId, option: i8) -> Result<(), NotConnectedError> {
+    pub fn choose_dialog_option(&mut self, npc_id: EntityId, option: i8) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(ChooseDialogOptionPacket::new(npc_id, option)),
         }
     }
 
-    pub fn request_item_equip(&mut self, item_index: InventoryIndex, equip_position: EquipPosition) -> Result<(), NotConnectedError> {
+    pub fn request_item_equip(&mut self, item_index: InventoryIndex, equip_position: EquipPosition) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestEquipItemPacket::new(item_index, equip_position)),
         }
     }
 
-    pub fn request_item_unequip(&mut self, item_index: InventoryIndex) -> Result<(), NotConnectedError> {
+    pub fn equip_ammunition(&mut self, item_index: InventoryIndex) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestEquipItemPacket::new(item_index, EquipPosition::AMMO)),
+        }
+    }
+
+    pub fn request_item_unequip(&mut self, item_index: InventoryIndex) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestUnequipItemPacket::new(item_index)),
         }
     }
 
-    pub fn cast_skill(&mut self, skill_id: SkillId, skill_level: SkillLevel, entity_id: EntityId) -> Result<(), NotConnectedError> {
+    pub fn use_item(&mut self, item_index: InventoryIndex, account_id: AccountId) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
-            SupportedPacketVersion::_20220406 => self.send_map_server_packet(UseSkillAtIdPacket::new(skill_level, skill_id, entity_id)),
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(UseItemPacket::new(item_index, account_id)),
         }
     }
 
-    pub fn cast_ground_skill(
-        &mut self,
-        skill_id: SkillId,
-        skill_level: SkillLevel,
-        target_position: TilePosition,
-    ) -> Result<(), NotConnectedError> {
+    pub fn drop_item(&mut self, item_index: InventoryIndex, amount: u16) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestDropItemPacket::new(item_index, amount)),
+        }
+    }
+
+    pub fn move_item_to_storage(&mut self, item_index: InventoryIndex, amount: u32) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestMoveItemToStoragePacket::new(item_index, amount)),
+        }
+    }
+
+    pub fn add_trade_item(&mut self, item_index: InventoryIndex, amount: u32) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(AddTradeItemPacket::new(item_index, amount)),
+        }
+    }
+
+    pub fn send_emotion(&mut self, emotion: u8) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestEmotionPacket::new(emotion)),
+        }
+    }
+
+    pub fn cast_skill(&mut self, skill_id: SkillId, skill_level: SkillLevel, entity_id: EntityId) -> GameplayResult<()> {
+        let packet_version = self.map_server_packet_version()?;
+        self.acquire_action(RateLimitedAction::Skill)?;
+
+        match packet_version {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(UseSkillAtIdPacket::new(skill_level, skill_id, entity_id)),
+        }
+    }
+
+    pub fn cast_ground_skill(&mut self, skill_id: SkillId, skill_level: SkillLevel, target_position: TilePosition) -> GameplayResult<()> {
+        let packet_version = self.map_server_packet_version()?;
+        self.acquire_action(RateLimitedAction::Skill)?;
+
+        match packet_version {
             SupportedPacketVersion::_20220406 => {
                 self.send_map_server_packet(UseSkillOnGroundPacket::new(skill_level, skill_id, target_position))
             }
         }
     }
 
-    pub fn cast_channeling_skill(
-        &mut self,
-        skill_id: SkillId,
-        skill_level: SkillLevel,
-        entity_id: EntityId,
-    ) -> Result<(), NotConnectedError> {
+    pub fn cast_channeling_skill(&mut self, skill_id: SkillId, skill_level: SkillLevel, entity_id: EntityId) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(StartUseSkillPacket::new(skill_id, skill_level, entity_id)),
         }
     }
 
-    pub fn stop_channeling_skill(&mut self, skill_id: SkillId) -> Result<(), NotConnectedError> {
+    pub fn stop_channeling_skill(&mut self, skill_id: SkillId) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(EndUseSkillPacket::new(skill_id)),
         }
     }
 
-    pub fn add_friend(&mut self, name: String) -> Result<(), NotConnectedError> {
+    pub fn add_friend(&mut self, name: String) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(AddFriendPacket::new(name)),
         }
     }
 
-    pub fn remove_friend(&mut self, account_id: AccountId, character_id: CharacterId) -> Result<(), NotConnectedError> {
+    pub fn remove_friend(&mut self, account_id: AccountId, character_id: CharacterId) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(RemoveFriendPacket::new(account_id, character_id)),
         }
     }
 
-    pub fn reject_friend_request(&mut self, account_id: AccountId, character_id: CharacterId) -> Result<(), NotConnectedError> {
+    pub fn reject_friend_request(&mut self, account_id: AccountId, character_id: CharacterId) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(FriendRequestResponsePacket::new(
                 account_id,
@@ -823,7 +1050,7 @@ where
         }
     }
 
-    pub fn accept_friend_request(&mut self, account_id: AccountId, character_id: CharacterId) -> Result<(), NotConnectedError> {
+    pub fn accept_friend_request(&mut self, account_id: AccountId, character_id: CharacterId) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(FriendRequestResponsePacket::new(
                 account_id,
@@ -833,19 +1060,19 @@ where
         }
     }
 
-    pub fn set_hotkey_data(&mut self, tab: HotbarTab, index: HotbarSlot, hotkey_data: HotkeyData) -> Result<(), NotConnectedError> {
+    pub fn set_hotkey_data(&mut self, tab: HotbarTab, index: HotbarSlot, hotkey_data: HotkeyData) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(SetHotkeyData2Packet::new(tab, index, hotkey_data)),
         }
     }
 
-    pub fn select_buy_or_sell(&mut self, shop_id: ShopId, buy_or_sell: BuyOrSellOption) -> Result<(), NotConnectedError> {
+    pub fn select_buy_or_sell(&mut self, shop_id: ShopId, buy_or_sell: BuyOrSellOption) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(SelectBuyOrSellPacket::new(shop_id, buy_or_sell)),
         }
     }
 
-    pub fn purchase_items(&mut self, items: Vec<ShopItem<u32>>) -> Result<(), NotConnectedError> {
+    pub fn purchase_items(&mut self, items: Vec<ShopItem<u32>>) -> GameplayResult<()> {
         let item_information = items
             .into_iter()
             .map(|item| BuyShopItemInformation {
@@ -859,23 +1086,157 @@ where
         }
     }
 
-    pub fn close_shop(&mut self) -> Result<(), NotConnectedError> {
+    pub fn close_shop(&mut self) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(CloseShopPacket::new()),
         }
     }
 
-    pub fn sell_items(&mut self, items: Vec<SoldItemInformation>) -> Result<(), NotConnectedError> {
+    pub fn sell_items(&mut self, items: Vec<SoldItemInformation>) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(SellItemsPacket { items }),
         }
     }
 
-    pub fn request_stat_up(&mut self, stat_type: StatUpType) -> Result<(), NotConnectedError> {
+    pub fn request_stat_up(&mut self, stat_type: StatUpType) -> GameplayResult<()> {
         match self.map_server_packet_version()? {
             SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestStatUpPacket::new(stat_type)),
         }
     }
+
+    pub fn open_bank(&mut self, account_id: AccountId) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(OpenBankPacket::new(account_id)),
+        }
+    }
+
+    pub fn close_bank(&mut self, account_id: AccountId) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(CloseBankPacket::new(account_id)),
+        }
+    }
+
+    pub fn request_bank_balance(&mut self, account_id: AccountId) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestBankBalancePacket::new(account_id)),
+        }
+    }
+
+    pub fn deposit_zeny(&mut self, account_id: AccountId, amount: i32) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(BankDepositPacket::new(account_id, amount)),
+        }
+    }
+
+    pub fn withdraw_zeny(&mut self, account_id: AccountId, amount: i32) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(BankWithdrawPacket::new(account_id, amount)),
+        }
+    }
+
+    /// Search the auctions. The `auction_id` is only used when searching by
+    /// [`AuctionSearchType::AuctionId`] and pages start at 1.
+    pub fn search_auctions(
+        &mut self,
+        search_type: AuctionSearchType,
+        auction_id: AuctionId,
+        search_text: String,
+        page: u16,
+    ) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => {
+                self.send_map_server_packet(AuctionSearchPacket::new(search_type, auction_id, search_text, page))
+            }
+        }
+    }
+
+    pub fn request_my_auctions(&mut self, listing_type: AuctionListingType) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestMyAuctionsPacket::new(listing_type)),
+        }
+    }
+
+    /// Bid on an auction. Bidding the buy now price buys the item right away.
+    pub fn bid_on_auction(&mut self, auction_id: AuctionId, price: Price) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(AuctionBidPacket::new(auction_id, price)),
+        }
+    }
+
+    /// Select the item to put up for auction. Needs to be called before
+    /// [`register_auction`](Self::register_auction).
+    pub fn set_auction_item(&mut self, index: InventoryIndex, amount: u32) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(AuctionSetItemPacket::new(index, amount)),
+        }
+    }
+
+    pub fn register_auction(&mut self, price: Price, buy_now_price: Price, hours: u16) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(AuctionRegisterPacket::new(price, buy_now_price, hours)),
+        }
+    }
+
+    pub fn cancel_auction(&mut self, auction_id: AuctionId) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(AuctionCancelPacket::new(auction_id)),
+        }
+    }
+
+    pub fn open_roulette(&mut self) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(OpenRoulettePacket::new()),
+        }
+    }
+
+    pub fn request_roulette_board(&mut self) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestRouletteInfoPacket::new()),
+        }
+    }
+
+    pub fn close_roulette(&mut self) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(CloseRoulettePacket::new()),
+        }
+    }
+
+    pub fn spin_roulette(&mut self) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(SpinRoulettePacket::new()),
+        }
+    }
+
+    pub fn claim_roulette_reward(&mut self) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(ClaimRouletteRewardPacket::new()),
+        }
+    }
+
+    /// Ask the server to open the attendance interface. The server replies
+    /// with [`NetworkEvent::OpenAttendance`] if attendance is enabled.
+    pub fn open_attendance(&mut self) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => {
+                self.send_map_server_packet(RequestOpenInterfacePacket::new(RequestedInterface::Attendance))
+            }
+        }
+    }
+
+    /// Destroy the memorial dungeon of the party. Only the party leader can do
+    /// this.
+    pub fn destroy_instance(&mut self) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(InstanceCommandPacket::new(InstanceCommand::ForceDestroy)),
+        }
+    }
+
+    /// Claim the attendance reward of the current day.
+    pub fn claim_attendance_reward(&mut self) -> GameplayResult<()> {
+        match self.map_server_packet_version()? {
+            SupportedPacketVersion::_20220406 => self.send_map_server_packet(RequestAttendanceRewardPacket::new()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -902,3 +1263,114 @@ mod packet_handlers {
         assert!(result.is_ok());
     }
 }
+
+#[cfg(test)]
+mod event_buffer {
+    use ragnarok_packets::{Direction, EntityId, Sex, TilePosition, WorldPosition};
+
+    use crate::{DisconnectReason, EntityData, NetworkEvent, NetworkEventBuffer};
+
+    fn heal_effect() -> NetworkEvent {
+        NetworkEvent::HealEffect {
+            entity_id: EntityId(0),
+            heal_amount: 10,
+        }
+    }
+
+    fn add_entity(entity_id: u32) -> NetworkEvent {
+        NetworkEvent::AddEntities {
+            entities: vec![EntityData {
+                entity_id: EntityId(entity_id),
+                movement_speed: 150,
+                job: 1002,
+                head: 0,
+                position: WorldPosition::new(0, 0, Direction::North),
+                destination: None,
+                health_points: 100,
+                maximum_health_points: 100,
+                head_direction: 0,
+                sex: Sex::Male,
+                appearance: Default::default(),
+                body_state: Default::default(),
+                hidden: false,
+            }],
+        }
+    }
+
+    fn change_map() -> NetworkEvent {
+        NetworkEvent::ChangeMap {
+            map_name: "prontera".to_owned(),
+            position: TilePosition { x: 0, y: 0 },
+        }
+    }
+
+    #[test]
+    fn critical_events_ignore_budget() {
+        let mut buffer = NetworkEventBuffer::default();
+
+        buffer.push(change_map());
+        buffer.push(change_map());
+        buffer.push(NetworkEvent::CharacterDeleted);
+
+        let events: Vec<_> = buffer.drain(1).collect();
+
+        assert!(matches!(events[0], NetworkEvent::CharacterDeleted));
+        assert!(matches!(events[1], NetworkEvent::ChangeMap { .. }));
+        assert_eq!(events.len(), 2);
+        assert_eq!(buffer.statistics().queued_events, 1);
+        assert_eq!(buffer.statistics().maximum_queue_depth, 3);
+    }
+
+    #[test]
+    fn cosmetic_events_are_deferred() {
+        let mut buffer = NetworkEventBuffer::default();
+
+        buffer.push(heal_effect());
+        buffer.push(NetworkEvent::RemoveQuestEffect { entity_id: EntityId(0) });
+
+        assert!(matches!(buffer.drain(1).next(), Some(NetworkEvent::RemoveQuestEffect { .. })));
+        assert_eq!(buffer.statistics().queued_events, 1);
+        assert!(matches!(buffer.drain(1).next(), Some(NetworkEvent::HealEffect { .. })));
+    }
+
+    #[test]
+    fn changing_map_drops_effects() {
+        let mut buffer = NetworkEventBuffer::default();
+
+        buffer.push(heal_effect());
+        buffer.push(change_map());
+
+        assert_eq!(buffer.drain(10).count(), 1);
+        assert_eq!(buffer.statistics().dropped_events, 1);
+    }
+
+    #[test]
+    fn entity_batches_are_merged() {
+        let mut buffer = NetworkEventBuffer::default();
+
+        buffer.push(add_entity(0));
+        buffer.push(add_entity(1));
+        buffer.push(change_map());
+        buffer.push(add_entity(2));
+
+        let events: Vec<_> = buffer.drain(10).collect();
+
+        assert!(matches!(&events[0], NetworkEvent::AddEntities { entities } if entities.len() == 2));
+        assert!(matches!(&events[1], NetworkEvent::ChangeMap { .. }));
+        assert!(matches!(&events[2], NetworkEvent::AddEntities { entities } if entities.len() == 1));
+    }
+
+    #[test]
+    fn disconnect_drops_pending_events() {
+        let mut buffer = NetworkEventBuffer::default();
+
+        buffer.push(change_map());
+        buffer.push(heal_effect());
+        buffer.push(NetworkEvent::MapServerDisconnected {
+            reason: DisconnectReason::ConnectionError,
+        });
+
+        assert_eq!(buffer.drain(10).count(), 1);
+        assert_eq!(buffer.statistics().dropped_events, 2);
+    }
+}
@@ -1,5 +1,6 @@
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
 pub enum MessageColor {
     Rgb { red: u8, green: u8, blue: u8 },
     Broadcast,
@@ -6,13 +6,25 @@ use std::time::Instant;
 use ragnarok_packets::handler::{DuplicateHandlerError, PacketCallback, PacketHandler};
 use ragnarok_packets::*;
 
+use crate::entity::is_hidden;
 use crate::event::{NetworkEventList, NoNetworkEvents};
 use crate::items::ItemQuantity;
 use crate::{
-    CharacterServerLoginData, HotkeyState, InventoryItem, InventoryItemDetails, LoginServerLoginData, MessageColor, NetworkEvent,
-    NoMetadata, ShopItem, UnifiedCharacterSelectionFailedReason, UnifiedLoginFailedReason,
+    AppearanceSlot, BodyState, CharacterServerLoginData, EntityMovement, GameplayError, HotkeyState, InstanceDestroyReason, InventoryItem,
+    InventoryItemDetails, LoginServerLoginData, MessageColor, NetworkEvent, NoMetadata, ShopItem, UnifiedCharacterSelectionFailedReason,
+    UnifiedLoginFailedReason,
 };
 
+/// Inventory packets are only expected between the start and the end of an
+/// inventory list.
+fn unexpected_inventory_packet() -> NetworkEvent {
+    NetworkEvent::GameplayError {
+        error: GameplayError::InvalidState {
+            reason: "received inventory items outside of an inventory list",
+        },
+    }
+}
+
 pub fn register_login_server_packets<Callback>(
     packet_handler: &mut PacketHandler<NetworkEventList, (), Callback>,
 ) -> Result<(), DuplicateHandlerError>
@@ -187,6 +199,10 @@ where
             color: MessageColor::Broadcast,
         }
     })?;
+    packet_handler.register(|packet: WhisperMessagePacket| NetworkEvent::WhisperReceived {
+        sender_name: packet.sender_name,
+        message: packet.message,
+    })?;
     packet_handler.register(|packet: ServerMessagePacket| NetworkEvent::ChatMessage {
         text: packet.message,
         color: MessageColor::Server,
@@ -214,14 +230,29 @@ where
 
         let (origin, destination) = from_to.to_origin_destination();
 
-        NetworkEvent::EntityMove {
-            entity_id,
-            origin,
-            destination,
-            starting_timestamp,
+        NetworkEvent::EntityMoves {
+            movements: vec![EntityMovement {
+                entity_id,
+                origin,
+                destination,
+                starting_timestamp,
+            }],
         }
     })?;
     packet_handler.register_noop::<EntityStopMovePacket>()?;
+    packet_handler.register(|packet: EntityChangeDirectionPacket| {
+        let EntityChangeDirectionPacket {
+            entity_id,
+            head_direction,
+            direction,
+        } = packet;
+
+        NetworkEvent::EntityDirectionChanged {
+            entity_id,
+            direction,
+            head_direction: head_direction as usize,
+        }
+    })?;
     packet_handler.register(|packet: PlayerMovePacket| {
         let PlayerMovePacket {
             starting_timestamp,
@@ -246,14 +277,14 @@ where
     packet_handler.register(|packet: ResurrectionPacket| NetworkEvent::ResurrectPlayer {
         entity_id: packet.entity_id,
     })?;
-    packet_handler.register(|packet: EntityAppearedPacket| NetworkEvent::AddEntity {
-        entity_data: packet.into(),
+    packet_handler.register(|packet: EntityAppearedPacket| NetworkEvent::AddEntities {
+        entities: vec![packet.into()],
     })?;
-    packet_handler.register(|packet: EntityAppeared2Packet| NetworkEvent::AddEntity {
-        entity_data: packet.into(),
+    packet_handler.register(|packet: EntityAppeared2Packet| NetworkEvent::AddEntities {
+        entities: vec![packet.into()],
     })?;
-    packet_handler.register(|packet: MovingEntityAppearedPacket| NetworkEvent::AddEntity {
-        entity_data: packet.into(),
+    packet_handler.register(|packet: MovingEntityAppearedPacket| NetworkEvent::AddEntities {
+        entities: vec![packet.into()],
     })?;
     packet_handler.register(|packet: EntityDisappearedPacket| NetworkEvent::RemoveEntity {
         entity_id: packet.entity_id,
@@ -277,7 +308,19 @@ where
     })?;
     packet_handler.register_noop::<UpdateAttackRangePacket>()?;
     packet_handler.register_noop::<NewMailStatusPacket>()?;
-    packet_handler.register_noop::<AchievementUpdatePacket>()?;
+    packet_handler.register(|packet: AchievementUpdatePacket| {
+        let AchievementData {
+            acheivement_id,
+            is_completed,
+            got_rewarded,
+            ..
+        } = packet.acheivement_data;
+
+        // The server sends the achievement again once the reward was claimed.
+        (is_completed != 0 && got_rewarded == 0).then_some(NetworkEvent::AchievementCompleted {
+            achievement_id: acheivement_id,
+        })
+    })?;
     packet_handler.register_noop::<AchievementListPacket>()?;
     packet_handler.register_noop::<CriticalWeightUpdatePacket>()?;
     packet_handler.register(|packet: SpriteChangePacket| match packet.sprite_type {
@@ -289,6 +332,26 @@ where
             account_id: packet.account_id,
             hair_id: packet.value,
         }),
+        SpriteChangeType::HeadTop => Some(NetworkEvent::ChangeAppearance {
+            account_id: packet.account_id,
+            slot: AppearanceSlot::HeadTop,
+            view_id: packet.value as u16,
+        }),
+        SpriteChangeType::HeadMiddle => Some(NetworkEvent::ChangeAppearance {
+            account_id: packet.account_id,
+            slot: AppearanceSlot::HeadMiddle,
+            view_id: packet.value as u16,
+        }),
+        SpriteChangeType::HeadBottom => Some(NetworkEvent::ChangeAppearance {
+            account_id: packet.account_id,
+            slot: AppearanceSlot::HeadBottom,
+            view_id: packet.value as u16,
+        }),
+        SpriteChangeType::Robe => Some(NetworkEvent::ChangeAppearance {
+            account_id: packet.account_id,
+            slot: AppearanceSlot::Robe,
+            view_id: packet.value as u16,
+        }),
         _ => None,
     })?;
     packet_handler.register({
@@ -303,56 +366,79 @@ where
         let inventory_items = inventory_items.clone();
 
         move |packet: RegularItemListPacket| {
-            inventory_items
-                .borrow_mut()
-                .as_mut()
-                .expect("Unexpected inventory packet")
-                .extend(packet.item_information.into_iter().map(|item_information| {
-                    let RegularItemInformation {
-                        index,
-                        item_id,
-                        item_type,
+            let mut inventory_items = inventory_items.borrow_mut();
+
+            let Some(inventory_items) = inventory_items.as_mut() else {
+                return Some(unexpected_inventory_packet());
+            };
+
+            inventory_items.extend(packet.item_information.into_iter().map(|item_information| {
+                let RegularItemInformation {
+                    index,
+                    item_id,
+                    item_type,
+                    amount,
+                    equipped_position,
+                    slot,
+                    hire_expiration_date,
+                    flags,
+                } = item_information;
+
+                InventoryItem {
+                    index,
+                    metadata: NoMetadata,
+                    item_id,
+                    item_type,
+                    slot,
+                    hire_expiration_date,
+                    details: InventoryItemDetails::Regular {
                         amount,
                         equipped_position,
-                        slot,
-                        hire_expiration_date,
                         flags,
-                    } = item_information;
-
-                    InventoryItem {
-                        index,
-                        metadata: NoMetadata,
-                        item_id,
-                        item_type,
-                        slot,
-                        hire_expiration_date,
-                        details: InventoryItemDetails::Regular {
-                            amount,
-                            equipped_position,
-                            flags,
-                        },
-                    }
-                }));
-            NoNetworkEvents
+                    },
+                }
+            }));
+            None
         }
     })?;
     packet_handler.register({
         let inventory_items = inventory_items.clone();
 
         move |packet: EquippableItemListPacket| {
-            inventory_items
-                .borrow_mut()
-                .as_mut()
-                .expect("Unexpected inventory packet")
-                .extend(packet.item_information.into_iter().map(|item| {
-                    let EquippableItemInformation {
-                        index,
-                        item_id,
-                        item_type,
+            let mut inventory_items = inventory_items.borrow_mut();
+
+            let Some(inventory_items) = inventory_items.as_mut() else {
+                return Some(unexpected_inventory_packet());
+            };
+
+            inventory_items.extend(packet.item_information.into_iter().map(|item| {
+                let EquippableItemInformation {
+                    index,
+                    item_id,
+                    item_type,
+                    equip_position,
+                    equipped_position,
+                    slot,
+                    hire_expiration_date,
+                    bind_on_equip_type,
+                    w_item_sprite_number,
+                    option_count,
+                    option_data,
+                    refinement_level,
+                    enchantment_level,
+                    flags,
+                } = item;
+
+                InventoryItem {
+                    index,
+                    metadata: NoMetadata,
+                    item_id,
+                    item_type,
+                    slot,
+                    hire_expiration_date,
+                    details: InventoryItemDetails::Equippable {
                         equip_position,
                         equipped_position,
-                        slot,
-                        hire_expiration_date,
                         bind_on_equip_type,
                         w_item_sprite_number,
                         option_count,
@@ -360,36 +446,20 @@ where
                         refinement_level,
                         enchantment_level,
                         flags,
-                    } = item;
-
-                    InventoryItem {
-                        index,
-                        metadata: NoMetadata,
-                        item_id,
-                        item_type,
-                        slot,
-                        hire_expiration_date,
-                        details: InventoryItemDetails::Equippable {
-                            equip_position,
-                            equipped_position,
-                            bind_on_equip_type,
-                            w_item_sprite_number,
-                            option_count,
-                            option_data,
-                            refinement_level,
-                            enchantment_level,
-                            flags,
-                        },
-                    }
-                }));
-            NoNetworkEvents
+                    },
+                }
+            }));
+            None
         }
     })?;
     packet_handler.register({
         let inventory_items = inventory_items.clone();
 
         move |_: InventoyEndPacket| {
-            let items = inventory_items.borrow_mut().take().expect("Unexpected inventory end packet");
+            let Some(items) = inventory_items.borrow_mut().take() else {
+                return unexpected_inventory_packet();
+            };
+
             NetworkEvent::SetInventory { items }
         }
     })?;
@@ -485,8 +555,16 @@ where
         NetworkEvent::VisualEffect { effect_path, entity_id }
     })?;
     packet_handler.register_noop::<DisplayGainedExperiencePacket>()?;
-    packet_handler.register_noop::<DisplayImagePacket>()?;
-    packet_handler.register_noop::<StateChangePacket>()?;
+    packet_handler.register(|packet: DisplayImagePacket| {
+        let DisplayImagePacket { image_name, location } = packet;
+
+        NetworkEvent::DisplayImage { image_name, location }
+    })?;
+    packet_handler.register(|packet: StateChangePacket| NetworkEvent::EntityStateChanged {
+        entity_id: packet.entity_id,
+        body_state: BodyState::from(packet.body_state),
+        hidden: is_hidden(packet.effect_state),
+    })?;
 
     packet_handler.register(|packet: QuestEffectPacket| match packet.effect {
         QuestEffect::None => NetworkEvent::RemoveQuestEffect {
@@ -515,7 +593,11 @@ where
         } = packet;
 
         if result != ItemPickupResult::Success {
-            todo!();
+            return NetworkEvent::GameplayError {
+                error: GameplayError::ServerRejected {
+                    reason: format!("failed to pick up item ({result:?})"),
+                },
+            };
         }
 
         // TODO: Not sure where to store these, since the *InventoryItem packets are not
@@ -562,11 +644,27 @@ where
 
         NetworkEvent::IventoryItemAdded { item }
     })?;
+    // TODO: Items on the ground are not shown yet, so only rare drops are
+    // reported.
+    packet_handler.register(|packet: ItemFallEntryPacket| {
+        (packet.show_drop_effect != 0).then_some(NetworkEvent::RareItemDropped {
+            item_id: packet.item_id,
+            is_identified: packet.is_identified != 0,
+            count: packet.count,
+        })
+    })?;
     packet_handler.register(|packet: RemoveItemFromInventoryPacket| NetworkEvent::InventoryItemRemoved {
         reason: packet.remove_reason,
         index: packet.index,
         amount: packet.amount,
     })?;
+    packet_handler.register(|packet: UseItemStatusPacket| NetworkEvent::ItemUsed {
+        entity_id: packet.entity_id,
+        index: packet.inventory_index,
+        item_id: packet.item_id,
+        amount: packet.amount,
+        success: packet.success != 0,
+    })?;
     packet_handler.register(|packet: ServerTickPacket| NetworkEvent::UpdateClientTick {
         client_tick: packet.client_tick,
         received_at: Instant::now(),
@@ -622,8 +720,15 @@ where
             attack_duration: packet.attack_duration,
             is_critical: true,
         }),
+        DamageType::PickUpItem => Some(NetworkEvent::EntityPickUpItem {
+            entity_id: packet.source_entity_id,
+        }),
+        // The server only sets the source entity when sitting down or standing up.
+        DamageType::SitDown => Some(NetworkEvent::PlayerSitDown {
+            entity_id: packet.source_entity_id,
+        }),
         DamageType::StandUp => Some(NetworkEvent::PlayerStandUp {
-            entity_id: packet.destination_entity_id,
+            entity_id: packet.source_entity_id,
         }),
         _ => None,
     })?;
@@ -642,8 +747,15 @@ where
             attack_duration: packet.attack_duration,
             is_critical: true,
         }),
+        DamageType::PickUpItem => Some(NetworkEvent::EntityPickUpItem {
+            entity_id: packet.source_entity_id,
+        }),
+        // The server only sets the source entity when sitting down or standing up.
+        DamageType::SitDown => Some(NetworkEvent::PlayerSitDown {
+            entity_id: packet.source_entity_id,
+        }),
         DamageType::StandUp => Some(NetworkEvent::PlayerStandUp {
-            entity_id: packet.destination_entity_id,
+            entity_id: packet.source_entity_id,
         }),
         _ => None,
     })?;
@@ -653,11 +765,13 @@ where
         NetworkEvent::OpenDialog { text, npc_id }
     })?;
     packet_handler.register(|packet: RequestEquipItemStatusPacket| match packet.result {
-        RequestEquipItemStatus::Success => Some(NetworkEvent::UpdateEquippedPosition {
+        RequestEquipItemStatus::Success => NetworkEvent::UpdateEquippedPosition {
             index: packet.inventory_index,
             equipped_position: packet.equipped_position,
-        }),
-        _ => None,
+        },
+        _ => NetworkEvent::ItemEquipFailed {
+            index: packet.inventory_index,
+        },
     })?;
     packet_handler.register(|packet: RequestUnequipItemStatusPacket| match packet.result {
         RequestUnequipItemStatus::Success => Some(NetworkEvent::UpdateEquippedPosition {
@@ -710,7 +824,10 @@ where
     packet_handler.register(|packet: FriendListPacket| NetworkEvent::SetFriendList {
         friend_list: packet.friend_list,
     })?;
-    packet_handler.register_noop::<FriendOnlineStatusPacket>()?;
+    packet_handler.register(|packet: FriendOnlineStatusPacket| NetworkEvent::FriendOnlineStatusChanged {
+        name: packet.name,
+        online: matches!(packet.state, OnlineState::Online),
+    })?;
     packet_handler.register(|packet: FriendRequestPacket| NetworkEvent::FriendRequest {
         requestee: packet.requestee,
     })?;
@@ -738,6 +855,25 @@ where
         character_id: packet.character_id,
     })?;
     packet_handler.register_noop::<PartyInvitePacket>()?;
+    packet_handler.register(|packet: PartyMemberInformationPacket| NetworkEvent::PartyMemberUpdated {
+        account_id: packet.account_id,
+        name: packet.name,
+        map_name: packet.map_name.replace(".gat", ""),
+        position: packet.position,
+        online: packet.offline == 0,
+    })?;
+    packet_handler.register(|packet: PartyMemberLeftPacket| NetworkEvent::PartyMemberLeft {
+        account_id: packet.account_id,
+    })?;
+    packet_handler.register(|packet: PartyMemberPositionPacket| NetworkEvent::PartyMemberMoved {
+        account_id: packet.account_id,
+        position: packet.position,
+    })?;
+    packet_handler.register(|packet: PartyMemberHealthPacket| NetworkEvent::PartyMemberHealthChanged {
+        account_id: packet.account_id,
+        health_points: packet.health_points,
+        maximum_health_points: packet.maximum_health_points,
+    })?;
     packet_handler.register_noop::<StatusChangeSequencePacket>()?;
     packet_handler.register_noop::<ReputationPacket>()?;
     packet_handler.register_noop::<ClanInfoPacket>()?;
@@ -766,9 +902,120 @@ where
     packet_handler.register_noop::<ParameterChangePacket>()?;
     packet_handler.register(|packet: SellListPacket| NetworkEvent::SellItemList { items: packet.items })?;
     packet_handler.register(|packet: SellItemsResultPacket| NetworkEvent::SellingCompleted { result: packet.result })?;
+    packet_handler.register(|packet: OpenBankResultPacket| NetworkEvent::BankOpened { result: packet.result })?;
+    packet_handler.register(|_: CloseBankResultPacket| NetworkEvent::BankClosed)?;
+    packet_handler.register(|packet: BankBalancePacket| NetworkEvent::BankBalance { balance: packet.balance })?;
+    packet_handler.register(|packet: BankDepositResultPacket| NetworkEvent::BankDepositCompleted {
+        result: packet.result,
+        balance: packet.balance,
+        zeny: packet.zeny,
+    })?;
+    packet_handler.register(|packet: BankWithdrawResultPacket| NetworkEvent::BankWithdrawCompleted {
+        result: packet.result,
+        balance: packet.balance,
+        zeny: packet.zeny,
+    })?;
+    packet_handler.register(|packet: AuctionWindowPacket| match packet.action {
+        AuctionWindowAction::Open => NetworkEvent::OpenAuction,
+        AuctionWindowAction::Close => NetworkEvent::CloseAuction,
+    })?;
+    packet_handler.register(|packet: AuctionSearchResultPacket| NetworkEvent::AuctionListings {
+        listings: packet.items,
+        page_count: packet.page_count,
+    })?;
+    packet_handler.register(|packet: AuctionSetItemResultPacket| NetworkEvent::AuctionItemSet {
+        index: packet.index,
+        success: packet.failed == 0,
+    })?;
+    packet_handler.register(|packet: AuctionResultPacket| NetworkEvent::AuctionResult { result: packet.result })?;
+    packet_handler.register(|packet: OpenRouletteResultPacket| match packet.result {
+        OpenRouletteResult::Success => NetworkEvent::OpenRoulette {
+            serial: packet.serial,
+            step: packet.step as u16,
+            index: packet.index as u16,
+            gold_points: packet.gold_points,
+            silver_points: packet.silver_points,
+            bronze_points: packet.bronze_points,
+        },
+        OpenRouletteResult::Failed => NetworkEvent::GameplayError {
+            error: GameplayError::ServerRejected {
+                reason: "failed to open the roulette".to_owned(),
+            },
+        },
+    })?;
+    packet_handler.register(|packet: RouletteInfoPacket| NetworkEvent::RouletteBoard {
+        serial: packet.serial,
+        items: packet.items,
+    })?;
+    packet_handler.register(|_: CloseRouletteResultPacket| NetworkEvent::CloseRoulette)?;
+    packet_handler.register(|packet: SpinRouletteResultPacket| match packet.result {
+        SpinRouletteResult::Won | SpinRouletteResult::Lost => NetworkEvent::RouletteSpun {
+            won: packet.result == SpinRouletteResult::Won,
+            step: packet.step,
+            index: packet.index,
+            gold_points: packet.gold_points,
+            silver_points: packet.silver_points,
+            bronze_points: packet.bronze_points,
+        },
+        result => NetworkEvent::GameplayError {
+            error: GameplayError::ServerRejected {
+                reason: format!("failed to spin the roulette ({result:?})"),
+            },
+        },
+    })?;
+    packet_handler.register(|packet: OpenInterfacePacket| match packet.interface {
+        ATTENDANCE_INTERFACE => Some(NetworkEvent::OpenAttendance { claimed_days: packet.data }),
+        // TODO: Handle the other interfaces.
+        _ => None,
+    })?;
+    packet_handler.register(|packet: AttendanceRewardPacket| NetworkEvent::AttendanceRewardClaimed {
+        claimed_days: packet.claimed_days,
+    })?;
+    packet_handler.register(|packet: InstanceQueuePacket| NetworkEvent::InstanceQueued {
+        name: packet.name,
+        queue_position: packet.queue_position,
+    })?;
+    packet_handler.register(
+        |packet: InstanceQueuePositionPacket| NetworkEvent::InstanceQueuePositionChanged {
+            queue_position: packet.queue_position,
+        },
+    )?;
+    packet_handler.register(|packet: InstanceInformationPacket| NetworkEvent::InstanceReady {
+        name: packet.name,
+        destroy_time: packet.destroy_time,
+        enter_time_limit: packet.enter_time_limit,
+    })?;
+    packet_handler.register(|packet: InstanceNotificationPacket| {
+        let reason = match packet.status {
+            1 => InstanceDestroyReason::TimeLimit,
+            2 => InstanceDestroyReason::EnterTimeLimit,
+            3 => InstanceDestroyReason::Requested,
+            4 => InstanceDestroyReason::CreationFailed,
+            _ => return None,
+        };
+
+        Some(NetworkEvent::InstanceDestroyed { reason })
+    })?;
+    packet_handler.register(|packet: ClaimRouletteRewardResultPacket| match packet.result {
+        ClaimRouletteRewardResult::Success => NetworkEvent::RouletteRewardClaimed,
+        result => NetworkEvent::GameplayError {
+            error: GameplayError::ServerRejected {
+                reason: format!("failed to claim the roulette reward ({result:?})"),
+            },
+        },
+    })?;
     packet_handler.register_noop::<RequestStatUpResponsePacket>()?;
-    packet_handler.register_noop::<EquipAmmunitionPacket>()?;
-    packet_handler.register_noop::<AmmunitionActionPacket>()?;
+    packet_handler.register(|packet: EquipAmmunitionPacket| NetworkEvent::AmmunitionEquipped {
+        index: packet.inventory_index,
+    })?;
+    packet_handler.register(|packet: AmmunitionActionPacket| match packet.action_type {
+        AmmunitionActionType::Equipped => None,
+        action_type => Some(NetworkEvent::GameplayError {
+            error: GameplayError::ServerRejected {
+                reason: format!("failed to use ammunition ({action_type:?})"),
+            },
+        }),
+    })?;
 
     Ok(())
 }
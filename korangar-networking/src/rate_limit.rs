@@ -0,0 +1,195 @@
+use std::time::{Duration, Instant};
+
+use ragnarok_packets::WorldPosition;
+
+/// Player actions that are sent to the map server at a limited rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitedAction {
+    Move,
+    Attack,
+    ChatMessage,
+    Skill,
+}
+
+impl RateLimitedAction {
+    const COUNT: usize = 4;
+
+    /// Minimum time between two requests of the same kind. These are chosen
+    /// to stay below the flood protection of rAthena, which disconnects
+    /// clients that send too many requests in a short period of time.
+    pub fn default_minimum_interval(self) -> Duration {
+        match self {
+            RateLimitedAction::Move => Duration::from_millis(100),
+            RateLimitedAction::Attack => Duration::from_millis(250),
+            RateLimitedAction::ChatMessage => Duration::from_millis(500),
+            RateLimitedAction::Skill => Duration::from_millis(100),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RateLimitedAction::Move => "Moving",
+            RateLimitedAction::Attack => "Attacking",
+            RateLimitedAction::ChatMessage => "Sending chat messages",
+            RateLimitedAction::Skill => "Casting skills",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Keeps track of when each [`RateLimitedAction`] was last sent.
+///
+/// Move requests are never rejected. If one arrives too early, it replaces
+/// any earlier pending destination and is sent once the interval has passed,
+/// so holding the mouse button only ever sends the latest destination.
+pub(crate) struct ActionRateLimiter {
+    minimum_intervals: [Duration; RateLimitedAction::COUNT],
+    last_sent: [Option<Instant>; RateLimitedAction::COUNT],
+    last_destination: Option<(u16, u16)>,
+    pending_move: Option<WorldPosition>,
+}
+
+impl Default for ActionRateLimiter {
+    fn default() -> Self {
+        let actions = [
+            RateLimitedAction::Move,
+            RateLimitedAction::Attack,
+            RateLimitedAction::ChatMessage,
+            RateLimitedAction::Skill,
+        ];
+
+        Self {
+            minimum_intervals: actions.map(RateLimitedAction::default_minimum_interval),
+            last_sent: [None; RateLimitedAction::COUNT],
+            last_destination: None,
+            pending_move: None,
+        }
+    }
+}
+
+impl ActionRateLimiter {
+    pub fn set_minimum_interval(&mut self, action: RateLimitedAction, interval: Duration) {
+        self.minimum_intervals[action.index()] = interval;
+    }
+
+    fn is_ready(&self, action: RateLimitedAction, now: Instant) -> bool {
+        self.last_sent[action.index()]
+            .is_none_or(|last_sent| now.saturating_duration_since(last_sent) >= self.minimum_intervals[action.index()])
+    }
+
+    /// Returns `true` and records the action if enough time passed since it
+    /// was last sent.
+    pub fn try_acquire(&mut self, action: RateLimitedAction, now: Instant) -> bool {
+        if !self.is_ready(action, now) {
+            return false;
+        }
+
+        self.last_sent[action.index()] = Some(now);
+        true
+    }
+
+    /// Returns the destination if it should be sent right away. Otherwise
+    /// the destination is kept until [`Self::take_pending_move`] releases it.
+    /// Walking to the tile we are already walking to is redundant, so those
+    /// requests are dropped.
+    pub fn request_move(&mut self, position: WorldPosition, now: Instant) -> Option<WorldPosition> {
+        let destination = (position.x, position.y);
+
+        if !self.is_ready(RateLimitedAction::Move, now) {
+            self.pending_move = (self.last_destination != Some(destination)).then_some(position);
+            return None;
+        }
+
+        self.pending_move = None;
+
+        if self.last_destination == Some(destination) {
+            return None;
+        }
+
+        self.last_destination = Some(destination);
+        self.last_sent[RateLimitedAction::Move.index()] = Some(now);
+        Some(position)
+    }
+
+    pub fn take_pending_move(&mut self, now: Instant) -> Option<WorldPosition> {
+        let position = self.pending_move.take()?;
+        self.request_move(position, now)
+    }
+
+    /// Forget all previous actions, for example after changing the map
+    /// server.
+    pub fn reset(&mut self) {
+        self.last_sent = [None; RateLimitedAction::COUNT];
+        self.last_destination = None;
+        self.pending_move = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use ragnarok_packets::{Direction, WorldPosition};
+
+    use super::{ActionRateLimiter, RateLimitedAction};
+
+    fn position(x: u16, y: u16) -> WorldPosition {
+        WorldPosition {
+            x,
+            y,
+            direction: Direction::North,
+        }
+    }
+
+    #[test]
+    fn actions_are_limited_per_kind() {
+        let mut rate_limiter = ActionRateLimiter::default();
+        let now = Instant::now();
+
+        assert!(rate_limiter.try_acquire(RateLimitedAction::Attack, now));
+        assert!(!rate_limiter.try_acquire(RateLimitedAction::Attack, now + Duration::from_millis(100)));
+        assert!(rate_limiter.try_acquire(RateLimitedAction::Skill, now + Duration::from_millis(100)));
+        assert!(rate_limiter.try_acquire(RateLimitedAction::Attack, now + Duration::from_millis(250)));
+
+        rate_limiter.set_minimum_interval(RateLimitedAction::Attack, Duration::ZERO);
+        assert!(rate_limiter.try_acquire(RateLimitedAction::Attack, now + Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn throttled_moves_are_coalesced() {
+        let mut rate_limiter = ActionRateLimiter::default();
+        let now = Instant::now();
+
+        assert!(rate_limiter.request_move(position(10, 10), now).is_some());
+        assert!(rate_limiter.request_move(position(11, 10), now).is_none());
+        assert!(rate_limiter.request_move(position(12, 10), now).is_none());
+        assert!(rate_limiter.take_pending_move(now).is_none());
+
+        let position = rate_limiter.take_pending_move(now + Duration::from_millis(100)).unwrap();
+        assert_eq!((position.x, position.y), (12, 10));
+        assert!(rate_limiter.take_pending_move(now + Duration::from_millis(200)).is_none());
+    }
+
+    #[test]
+    fn repeated_destination_is_dropped() {
+        let mut rate_limiter = ActionRateLimiter::default();
+        let now = Instant::now();
+
+        assert!(rate_limiter.request_move(position(10, 10), now).is_some());
+        assert!(rate_limiter.request_move(position(10, 10), now).is_none());
+        assert!(rate_limiter.take_pending_move(now + Duration::from_millis(100)).is_none());
+        assert!(
+            rate_limiter
+                .request_move(position(10, 10), now + Duration::from_millis(100))
+                .is_none()
+        );
+        assert!(
+            rate_limiter
+                .request_move(position(11, 10), now + Duration::from_millis(100))
+                .is_some()
+        );
+    }
+}
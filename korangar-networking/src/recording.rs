@@ -0,0 +1,256 @@
+//! Recording and playback of the [`NetworkEvent`] stream. A recording stores
+//! every event that the client received, together with the time it was
+//! received at, so a session can be replayed without a server. Playback only
+//! advances by the time that is passed to it, so replaying a recording always
+//! produces the same events in the same order.
+//!
+//! Recordings are stored as RON with one line per batch of events, so they can
+//! be inspected and edited by hand and a recording that was cut off still
+//! contains all batches up to that point.
+
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{NetworkEvent, NetworkEventBuffer};
+
+/// Events that were received in the same call to
+/// [`get_events`](crate::NetworkingSystem::get_events).
+#[derive(Serialize)]
+struct RecordedBatch<'a> {
+    /// Seconds since the recording started.
+    time: f64,
+    events: &'a [NetworkEvent],
+}
+
+#[derive(Deserialize)]
+struct ReplayedBatch {
+    time: f64,
+    events: Vec<NetworkEvent>,
+}
+
+#[derive(Debug)]
+pub enum RecordingError {
+    Io(std::io::Error),
+    Parse { line: usize, error: ron::error::SpannedError },
+}
+
+impl fmt::Display for RecordingError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordingError::Io(error) => write!(formatter, "{error}"),
+            RecordingError::Parse { line, error } => write!(formatter, "line {line}: {error}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for RecordingError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Writes the events to a recording as they are received.
+pub struct NetworkRecorder {
+    writer: Box<dyn Write + Send>,
+    start_time: Instant,
+    /// The recording stops at the first error, which is reported by
+    /// [`flush`](Self::flush).
+    error: Option<std::io::Error>,
+}
+
+impl NetworkRecorder {
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            writer: Box::new(writer),
+            start_time: Instant::now(),
+            error: None,
+        }
+    }
+
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, RecordingError> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+
+    pub(crate) fn record(&mut self, events: &[NetworkEvent]) {
+        if events.is_empty() || self.error.is_some() {
+            return;
+        }
+
+        let batch = RecordedBatch {
+            time: self.start_time.elapsed().as_secs_f64(),
+            events,
+        };
+
+        let result = ron::to_string(&batch)
+            .map_err(std::io::Error::other)
+            .and_then(|line| writeln!(self.writer, "{line}"));
+
+        self.error = result.err();
+    }
+
+    pub fn flush(&mut self) -> Result<(), RecordingError> {
+        match self.error.take() {
+            Some(error) => Err(error.into()),
+            None => Ok(self.writer.flush()?),
+        }
+    }
+}
+
+/// Plays back a recording, taking the place of the
+/// [`NetworkingSystem`](crate::NetworkingSystem).
+pub struct NetworkPlayback {
+    batches: VecDeque<ReplayedBatch>,
+    time: f64,
+}
+
+impl NetworkPlayback {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RecordingError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn parse(recording: &str) -> Result<Self, RecordingError> {
+        let batches = recording
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| ron::from_str(line).map_err(|error| RecordingError::Parse { line: index + 1, error }))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { batches, time: 0.0 })
+    }
+
+    /// Advance the playback by `delta_time` seconds and add all events that
+    /// were received up to that point.
+    pub fn get_events(&mut self, delta_time: f64, events: &mut NetworkEventBuffer) {
+        self.time += delta_time;
+
+        while self.batches.front().is_some_and(|batch| batch.time <= self.time) {
+            let batch = self.batches.pop_front().unwrap();
+
+            for mut event in batch.events {
+                if let NetworkEvent::UpdateClientTick { received_at, .. } = &mut event {
+                    *received_at = Instant::now();
+                }
+
+                events.push(event);
+            }
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.batches.is_empty()
+    }
+}
+
+/// Messages and effect paths of events are `&'static str`s. Every distinct
+/// string of a recording is only leaked once, so replaying the same recording
+/// repeatedly doesn't leak memory.
+pub(crate) fn deserialize_static_str<'de, D>(deserializer: D) -> Result<&'static str, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    static STRINGS: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+    let string = String::deserialize(deserializer)?;
+    let mut strings = STRINGS.lock().unwrap();
+
+    match strings.get(string.as_str()) {
+        Some(string) => Ok(string),
+        None => {
+            let string = Box::leak(string.into_boxed_str());
+            strings.insert(string);
+            Ok(string)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use ragnarok_packets::TilePosition;
+
+    use super::{NetworkPlayback, NetworkRecorder};
+    use crate::{MessageColor, NetworkEvent, NetworkEventBuffer};
+
+    #[derive(Clone, Default)]
+    struct SharedWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for SharedWriter {
+        fn write(&mut self, buffer: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buffer)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn events(playback: &mut NetworkPlayback, delta_time: f64) -> Vec<NetworkEvent> {
+        let mut event_buffer = NetworkEventBuffer::default();
+        playback.get_events(delta_time, &mut event_buffer);
+        event_buffer.drain(usize::MAX).collect()
+    }
+
+    #[test]
+    fn recorded_events_are_replayed() {
+        let writer = SharedWriter::default();
+        let mut recorder = NetworkRecorder::new(writer.clone());
+
+        recorder.record(&[
+            NetworkEvent::ChangeMap {
+                map_name: "prontera".to_owned(),
+                position: TilePosition::new(150, 150),
+            },
+            NetworkEvent::ChatMessage {
+                text: "Welcome".to_owned(),
+                color: MessageColor::Server,
+            },
+        ]);
+        recorder.record(&[]);
+
+        let recording = String::from_utf8(writer.0.lock().unwrap().clone()).unwrap();
+        let mut playback = NetworkPlayback::parse(&recording).unwrap();
+
+        assert_eq!(recording.lines().count(), 1);
+        assert!(matches!(&events(&mut playback, 1.0)[..], [
+            NetworkEvent::ChangeMap { map_name, position },
+            NetworkEvent::ChatMessage { text, .. },
+        ] if map_name == "prontera" && *position == TilePosition::new(150, 150) && text == "Welcome"));
+        assert!(playback.is_finished());
+    }
+
+    #[test]
+    fn playback_only_advances_by_delta_time() {
+        let mut playback = NetworkPlayback::parse(
+            "(time: 0.0, events: [LoggedOut])\n\
+             (time: 0.5, events: [RemoveSkillUnit(entity_id: (7))])\n\
+             (time: 0.5, events: [VisualEffect(effect_path: \"effect.str\", entity_id: (7))])\n",
+        )
+        .unwrap();
+
+        assert!(matches!(&events(&mut playback, 0.0)[..], [NetworkEvent::LoggedOut]));
+        assert!(events(&mut playback, 0.25).is_empty());
+        assert!(matches!(&events(&mut playback, 0.25)[..], [
+            NetworkEvent::RemoveSkillUnit { .. },
+            NetworkEvent::VisualEffect { effect_path: "effect.str", .. },
+        ]));
+        assert!(playback.is_finished());
+    }
+
+    #[test]
+    fn invalid_lines_are_reported() {
+        let error = NetworkPlayback::parse("(time: 0.0, events: [LoggedOut])\n(time: 0.5, events: [Unknown])")
+            .err()
+            .unwrap();
+
+        assert!(error.to_string().starts_with("line 2:"));
+    }
+}
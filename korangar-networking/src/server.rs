@@ -4,9 +4,10 @@ use ragnarok_packets::{AccountId, CharacterId, Sex};
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 
 use crate::SupportedPacketVersion;
-use crate::event::NetworkEvent;
+use crate::event::{NetworkEvent, StaticStr};
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
 pub struct LoginServerLoginData {
     pub account_id: AccountId,
     pub login_id1: u32,
@@ -15,6 +16,7 @@ pub struct LoginServerLoginData {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
 pub enum UnifiedLoginFailedReason {
     ServerClosed,
     AlreadyLoggedIn,
@@ -31,12 +33,14 @@ pub enum UnifiedLoginFailedReason {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
 pub enum UnifiedCharacterSelectionFailedReason {
     RejectedFromServer,
     MapServerUnavailable,
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
 pub struct CharacterServerLoginData {
     pub server_ip: IpAddr,
     pub server_port: u16,
@@ -70,8 +74,40 @@ pub(crate) enum NetworkTaskError {
     ConnectionClosed,
 }
 
-#[derive(Debug)]
-pub struct NotConnectedError;
+/// Reason why a gameplay action failed, so it can be shown to the player
+/// instead of being silently dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "recording", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameplayError {
+    /// There is no connection to the server that handles the action.
+    NotConnected,
+    /// The action or a packet of the server doesn't fit the current state.
+    InvalidState {
+        #[cfg_attr(feature = "recording", serde(deserialize_with = "crate::recording::deserialize_static_str"))]
+        reason: StaticStr,
+    },
+    /// The server refused to perform the action.
+    ServerRejected { reason: String },
+    /// The action was sent too quickly after the previous one and was dropped
+    /// to avoid being disconnected by the flood protection of the server.
+    RateLimited {
+        #[cfg_attr(feature = "recording", serde(deserialize_with = "crate::recording::deserialize_static_str"))]
+        action: StaticStr,
+    },
+}
+
+impl std::fmt::Display for GameplayError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameplayError::NotConnected => write!(formatter, "Not connected to the server"),
+            GameplayError::InvalidState { reason } => write!(formatter, "Invalid state: {reason}"),
+            GameplayError::ServerRejected { reason } => write!(formatter, "Rejected by the server: {reason}"),
+            GameplayError::RateLimited { action } => write!(formatter, "{action} too quickly, please slow down"),
+        }
+    }
+}
+
+pub type GameplayResult<T> = Result<T, GameplayError>;
 
 pub(crate) enum ServerConnection {
     Connected {
@@ -0,0 +1,34 @@
+-- Offline content of Geffen. See the wiki page on running the client for the
+-- functions of the `korangar` table.
+
+local guide = korangar.spawn({ job = 86, name = "Guide", x = 118, y = 50, direction = 4 })
+
+korangar.on_click(guide, function(npc)
+    korangar.say(npc, "[Guide]")
+    korangar.say(npc, "Welcome to Geffen, the city of magic.")
+    korangar.next(npc)
+
+    local choice = korangar.choose(npc, "Give me a potion", "Take me to Prontera", "Nothing, thanks")
+
+    if choice == 1 then
+        korangar.give_item(501, 5)
+        korangar.say(npc, "Here you go, take good care of yourself.")
+        korangar.close(npc)
+    elseif choice == 2 then
+        korangar.warp("prontera", 156, 191)
+    else
+        korangar.say(npc, "Enjoy your stay!")
+    end
+end)
+
+korangar.warp_portal({ x = 124, y = 50, map = "prontera", to_x = 156, to_y = 191 })
+
+-- A poring shows up near the guide every minute and leaves again after a while.
+korangar.every(60, function()
+    local poring = korangar.spawn({ job = 1002, x = 116, y = 46 })
+    korangar.announce("A poring appeared near the guide!")
+
+    korangar.after(20, function()
+        korangar.remove(poring)
+    end)
+end)
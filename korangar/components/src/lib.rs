@@ -1,6 +1,17 @@
 use korangar_interface::prelude::create_component_macro;
 use proc_macro::TokenStream;
 
+#[proc_macro]
+pub fn hotbar_slot(token_stream: TokenStream) -> TokenStream {
+    create_component_macro!(crate::interface::components::hotbar_slot::HotbarSlotBox, {
+        skill_path: !,
+        item_path: !,
+        slot: !,
+    });
+
+    macro_impl(token_stream.into()).into()
+}
+
 #[proc_macro]
 pub fn item_box(token_stream: TokenStream) -> TokenStream {
     create_component_macro!(crate::interface::components::item_box::ItemBox, {
@@ -11,6 +22,16 @@ pub fn item_box(token_stream: TokenStream) -> TokenStream {
     macro_impl(token_stream.into()).into()
 }
 
+#[proc_macro]
+pub fn radial_menu(token_stream: TokenStream) -> TokenStream {
+    create_component_macro!(crate::interface::components::radial_menu::RadialMenu, {
+        entries_path: !,
+        selected_path: !,
+    });
+
+    macro_impl(token_stream.into()).into()
+}
+
 #[proc_macro]
 pub fn skill_box(token_stream: TokenStream) -> TokenStream {
     create_component_macro!(crate::interface::components::skill_box::SkillBox, {
@@ -15,6 +15,9 @@ struct GlobalUniforms {
     point_light_count: u32,
     enhanced_lighting: u32,
     shadow_quality: u32,
+    light_map_mode: u32,
+    view_range: vec4<f32>,
+    wind: vec4<f32>,
 }
 
 struct DirectionalLightUniforms {
@@ -50,10 +53,11 @@ struct InstanceData {
     curvature: f32,
     mirror: u32,
     texture_index: i32,
+    highlight: f32,
 }
 
-struct TileLightIndices {
-    indices: array<u32, 256>,
+struct ClusterLightIndices {
+    indices: array<u32, 64>,
 }
 
 struct Vertex {
@@ -71,6 +75,7 @@ struct VertexOutput {
     @location(5) @interpolate(flat) original_depth_offset: f32,
     @location(6) @interpolate(flat) original_curvature: f32,
     @location(7) color: vec4<f32>,
+    @location(8) @interpolate(flat) highlight: f32,
 }
 
 struct FragmentOutput {
@@ -85,7 +90,9 @@ struct WboitOutput {
 }
 
 override PASS_MODE: u32;
-const TILE_SIZE: u32 = 16;
+const CLUSTER_TILE_SIZE: u32 = 64;
+const CLUSTER_DEPTH_SLICES: u32 = 16;
+const CLUSTER_FAR_PLANE: f32 = 1000.0;
 const PARTITION_COUNT: u32 = 3u;
 const NEAR_PLANE = 0.1;
 const DEPTH_EPSILON: f32 = 1.0e-7;
@@ -93,17 +100,22 @@ const DEPTH_EPSILON: f32 = 1.0e-7;
 // pixels in our WBOIT textures, or else they would dominate the
 // transparent pixels.
 const OPAGUE_EPSILON: f32 = 0.2;
+// Brightness that is added to highlighted entities, so dark sprites are also highlighted.
+const HIGHLIGHT_BASE_BRIGHTNESS: f32 = 0.1;
 // PCSS light source parameters (world units).
 const LIGHT_WORLD_SIZE: f32 = 5.0;
+// Distance over which the geometry beyond the view range fades to its dimmed color.
+const VIEW_RANGE_FALLOFF: f32 = 10.0;
 
 @group(0) @binding(0) var<uniform> global_uniforms: GlobalUniforms;
 @group(0) @binding(2) var linear_sampler: sampler;
 @group(0) @binding(4) var shadow_map_sampler: sampler_comparison;
+@group(0) @binding(5) var sprite_sampler: sampler;
 @group(1) @binding(0) var<uniform> directional_light: DirectionalLightUniforms;
 @group(1) @binding(1) var shadow_maps: texture_depth_2d_array;
 @group(1) @binding(2) var<storage, read> point_lights: array<PointLight>;
 @group(1) @binding(3) var light_count_texture: texture_2d<u32>;
-@group(1) @binding(4) var<storage, read> tile_light_indices: array<TileLightIndices>;
+@group(1) @binding(4) var<storage, read> cluster_light_indices: array<ClusterLightIndices>;
 @group(1) @binding(5) var point_shadow_maps: texture_depth_cube_array;
 @group(1) @binding(6) var<storage, read> directional_light_partitions: array<DirectionalLightPartition>;
 @group(2) @binding(0) var<storage, read> instance_data: array<InstanceData>;
@@ -148,6 +160,7 @@ fn vs_main(
     output.original_depth_offset = instance.depth_offset;
     output.original_curvature = instance.curvature;
     output.color = instance.color;
+    output.highlight = instance.highlight;
     return output;
 }
 
@@ -199,15 +212,14 @@ fn fragment(input: VertexOutput) -> FragmentOutput {
         // Transparent pass can early exit if invivisble or opague pixel was found.
         discard;
     } else {
-        // Calculate which tile this fragment belongs to
+        // Calculate which cluster this fragment belongs to
         let pixel_position = vec2<u32>(floor(input.position.xy));
-        let tile_x = pixel_position.x / TILE_SIZE;
-        let tile_y = pixel_position.y / TILE_SIZE;
-        let tile_count_x = (global_uniforms.forward_size.x + TILE_SIZE - 1u) / TILE_SIZE;
-        let tile_index = tile_y * tile_count_x + tile_x;
+        let cluster_coordinates = get_cluster_coordinates(pixel_position, nonLinearToLinear(input.position.z));
+        let cluster_count_x = (global_uniforms.forward_size.x + CLUSTER_TILE_SIZE - 1u) / CLUSTER_TILE_SIZE;
+        let cluster_index = cluster_coordinates.y * cluster_count_x + cluster_coordinates.x;
 
-        // Get the number of lights affecting this tile
-        let light_count = textureLoad(light_count_texture, vec2<u32>(tile_x, tile_y), 0).r;
+        // Get the number of lights affecting this cluster
+        let light_count = textureLoad(light_count_texture, cluster_coordinates, 0).r;
 
         let normal = normalize(input.normal);
 
@@ -278,7 +290,7 @@ fn fragment(input: VertexOutput) -> FragmentOutput {
         // Point lights
         var point_light_contribution = vec3<f32>(0.0);
         for (var index = 0u; index < light_count; index++) {
-            let light_index = tile_light_indices[tile_index].indices[index];
+            let light_index = cluster_light_indices[cluster_index].indices[index];
             let light = point_lights[light_index];
             let light_direction = normalize(adjusted_world_position.xyz - light.position.xyz);
             let light_percent = max(dot(light_direction, input.normal), 0.0);
@@ -312,6 +324,10 @@ fn fragment(input: VertexOutput) -> FragmentOutput {
             color = color_balance(color, -0.01, 0.0, 0.0);
         }
 
+        // Brighten hovered and targeted entities so they stand out in crowded scenes.
+        color = saturate(color * (1.0 + input.highlight) + vec3<f32>(HIGHLIGHT_BASE_BRIGHTNESS * input.highlight));
+        color *= view_range_dimming(input.world_position.xyz);
+
         output.color = vec4<f32>(color, alpha_channel);
         output.depth = depth;
     }
@@ -319,6 +335,15 @@ fn fragment(input: VertexOutput) -> FragmentOutput {
     return output;
 }
 
+// Darkens everything beyond the view range of the server. The view range is
+// square, since the server measures its distance in tiles.
+fn view_range_dimming(world_position: vec3<f32>) -> f32 {
+    let offset = abs(world_position.xz - global_uniforms.view_range.xy);
+    let distance = max(offset.x, offset.y);
+    let falloff = smoothstep(global_uniforms.view_range.z, global_uniforms.view_range.z + VIEW_RANGE_FALLOFF, distance);
+    return 1.0 - global_uniforms.view_range.w * falloff;
+}
+
 // Assuming inputs are in range [-1, 1] where:
 // -1 = full shift towards first color (Cyan/Magenta/Yellow)
 // +1 = full shift towards second color (Red/Green/Blue)
@@ -730,6 +755,16 @@ fn nonLinearToLinear(non_linear_depth: f32) -> f32 {
     return NEAR_PLANE / (non_linear_depth + DEPTH_EPSILON);
 }
 
+// The depth slices of the clusters are distributed exponentially and stacked
+// vertically in the light count texture.
+fn get_cluster_coordinates(pixel_position: vec2<u32>, linear_view_z: f32) -> vec2<u32> {
+    let cluster_count_y = (global_uniforms.forward_size.y + CLUSTER_TILE_SIZE - 1u) / CLUSTER_TILE_SIZE;
+    let slice_scale = f32(CLUSTER_DEPTH_SLICES) / log(CLUSTER_FAR_PLANE / NEAR_PLANE);
+    let slice = min(u32(max(log(linear_view_z / NEAR_PLANE) * slice_scale, 0.0)), CLUSTER_DEPTH_SLICES - 1u);
+    let cluster = pixel_position / CLUSTER_TILE_SIZE;
+    return vec2<u32>(cluster.x, slice * cluster_count_y + cluster.y);
+}
+
 fn linearToNonLinear(linear_depth: f32) -> f32 {
     return NEAR_PLANE / (linear_depth + DEPTH_EPSILON);
 }
@@ -770,10 +805,10 @@ fn compute_pixel_weights(uv: vec2<f32>, texture_size: vec2<f32>, inverse_texture
 }
 
 fn sample_bandlimited_pixel(uv: vec2<f32>, info: BandlimitedPixelInfo, lod_bias: f32) -> vec4<f32> {
-    var color = textureSampleLevel(texture, linear_sampler, uv, lod_bias);
+    var color = textureSampleLevel(texture, sprite_sampler, uv, lod_bias);
 
     if (info.length > 0.0) {
-        let bandlimited = textureSampleLevel(texture, linear_sampler, info.uv0, 0.0);
+        let bandlimited = textureSampleLevel(texture, sprite_sampler, info.uv0, 0.0);
         color = mix(color, bandlimited, info.length);
     }
 
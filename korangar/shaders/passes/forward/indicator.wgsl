@@ -38,8 +38,8 @@ struct PointLight {
     texture_index: i32,
 }
 
-struct TileLightIndices {
-    indices: array<u32, 256>,
+struct ClusterLightIndices {
+    indices: array<u32, 64>,
 }
 
 struct VertexOutput {
@@ -49,7 +49,9 @@ struct VertexOutput {
     @location(2) texture_coordinates: vec2<f32>,
 }
 
-const TILE_SIZE: u32 = 16;
+const CLUSTER_TILE_SIZE: u32 = 64;
+const CLUSTER_DEPTH_SLICES: u32 = 16;
+const CLUSTER_FAR_PLANE: f32 = 1000.0;
 const PARTITION_COUNT: u32 = 3u;
 const NEAR_PLANE = 0.1;
 const DEPTH_EPSILON: f32 = 1.0e-7;
@@ -63,7 +65,7 @@ const LIGHT_WORLD_SIZE: f32 = 5.0;
 @group(1) @binding(1) var shadow_maps: texture_depth_2d_array;
 @group(1) @binding(2) var<storage, read> point_lights: array<PointLight>;
 @group(1) @binding(3) var light_count_texture: texture_2d<u32>;
-@group(1) @binding(4) var<storage, read> tile_light_indices: array<TileLightIndices>;
+@group(1) @binding(4) var<storage, read> cluster_light_indices: array<ClusterLightIndices>;
 @group(1) @binding(5) var point_shadow_maps: texture_depth_cube_array;
 @group(1) @binding(6) var<storage, read> directional_light_partitions: array<DirectionalLightPartition>;
 @group(2) @binding(0) var texture: texture_2d<f32>;
@@ -96,15 +98,14 @@ fn get_shadow_offsets(normal: vec3<f32>, light_direction: vec3<f32>) -> vec2<f32
 fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
     let diffuse_color = textureSample(texture, nearest_sampler, input.texture_coordinates);
 
-    // Calculate which tile this fragment belongs to
+    // Calculate which cluster this fragment belongs to
     let pixel_position = vec2<u32>(floor(input.position.xy));
-    let tile_x = pixel_position.x / TILE_SIZE;
-    let tile_y = pixel_position.y / TILE_SIZE;
-    let tile_count_x = (global_uniforms.forward_size.x + TILE_SIZE - 1u) / TILE_SIZE;
-    let tile_index = tile_y * tile_count_x + tile_x;
+    let cluster_coordinates = get_cluster_coordinates(pixel_position, nonLinearToLinear(input.position.z));
+    let cluster_count_x = (global_uniforms.forward_size.x + CLUSTER_TILE_SIZE - 1u) / CLUSTER_TILE_SIZE;
+    let cluster_index = cluster_coordinates.y * cluster_count_x + cluster_coordinates.x;
 
-    // Get the number of lights affecting this tile
-    let light_count = textureLoad(light_count_texture, vec2<u32>(tile_x, tile_y), 0).r;
+    // Get the number of lights affecting this cluster
+    let light_count = textureLoad(light_count_texture, cluster_coordinates, 0).r;
 
     if (diffuse_color.a < 0.1) {
         discard;
@@ -165,7 +166,7 @@ fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
     // Point lights
     var point_light_contribution = vec3<f32>(0.0);
     for (var index = 0u; index < light_count; index++) {
-        let light_index = tile_light_indices[tile_index].indices[index];
+        let light_index = cluster_light_indices[cluster_index].indices[index];
         let light = point_lights[light_index];
         let light_direction = normalize(input.world_position.xyz - light.position.xyz);
         let light_percent = max(dot(light_direction, input.normal), 0.0);
@@ -638,6 +639,16 @@ fn nonLinearToLinear(non_linear_depth: f32) -> f32 {
     return NEAR_PLANE / (non_linear_depth + DEPTH_EPSILON);
 }
 
+// The depth slices of the clusters are distributed exponentially and stacked
+// vertically in the light count texture.
+fn get_cluster_coordinates(pixel_position: vec2<u32>, linear_view_z: f32) -> vec2<u32> {
+    let cluster_count_y = (global_uniforms.forward_size.y + CLUSTER_TILE_SIZE - 1u) / CLUSTER_TILE_SIZE;
+    let slice_scale = f32(CLUSTER_DEPTH_SLICES) / log(CLUSTER_FAR_PLANE / NEAR_PLANE);
+    let slice = min(u32(max(log(linear_view_z / NEAR_PLANE) * slice_scale, 0.0)), CLUSTER_DEPTH_SLICES - 1u);
+    let cluster = pixel_position / CLUSTER_TILE_SIZE;
+    return vec2<u32>(cluster.x, slice * cluster_count_y + cluster.y);
+}
+
 fn linearToNonLinear(linear_depth: f32) -> f32 {
     return NEAR_PLANE / (linear_depth + DEPTH_EPSILON);
 }
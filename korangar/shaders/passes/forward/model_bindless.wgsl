@@ -15,6 +15,9 @@ struct GlobalUniforms {
     point_light_count: u32,
     enhanced_lighting: u32,
     shadow_quality: u32,
+    light_map_mode: u32,
+    view_range: vec4<f32>,
+    wind: vec4<f32>,
 }
 
 struct DirectionalLightUniforms {
@@ -41,10 +44,11 @@ struct PointLight {
 struct InstanceData {
     world: mat4x4<f32>,
     inv_world: mat4x4<f32>,
+    opacity: f32,
 }
 
-struct TileLightIndices {
-    indices: array<u32, 256>,
+struct ClusterLightIndices {
+    indices: array<u32, 64>,
 }
 
 struct VertexOutput {
@@ -55,12 +59,16 @@ struct VertexOutput {
     @location(3) texture_coordinates: vec2<f32>,
     @location(4) color: vec3<f32>,
     @location(5) texture_index: i32,
+    @location(6) @interpolate(flat) opacity: f32,
+    @location(7) light_map_coordinates: vec2<f32>,
 }
 
 override PASS_MODE: u32;
 const MIP_SCALE: f32 = 0.25;
 const ALPHA_CUTOFF: f32 = 0.4;
-const TILE_SIZE: u32 = 16;
+const CLUSTER_TILE_SIZE: u32 = 64;
+const CLUSTER_DEPTH_SLICES: u32 = 16;
+const CLUSTER_FAR_PLANE: f32 = 1000.0;
 const PARTITION_COUNT: u32 = 3u;
 const NEAR_PLANE = 0.1;
 const DEPTH_EPSILON: f32 = 1.0e-7;
@@ -68,8 +76,16 @@ const DEPTH_EPSILON: f32 = 1.0e-7;
 // pixels in our WBOIT textures, or else they would dominate the
 // transparent pixels.
 const OPAQUE_EPSILON: f32 = 0.01;
+const BAYER_MATRIX_4X4: array<u32, 16> = array<u32, 16>(0, 8, 2, 10, 12, 4, 14, 6, 3, 11, 1, 9, 15, 7, 13, 5);
 // PCSS light source parameters (world units).
 const LIGHT_WORLD_SIZE: f32 = 5.0;
+// The original client renders with 16 bit colors, which gives the light maps
+// their banded look.
+const LIGHT_MAP_COLOR_LEVELS: f32 = 32.0;
+// Distance over which the geometry beyond the view range fades to its dimmed color.
+const VIEW_RANGE_FALLOFF: f32 = 10.0;
+// Height above the origin of a model at which vertices are moved with the full wind strength.
+const WIND_REFERENCE_HEIGHT: f32 = 40.0;
 
 struct WboitOutput {
     @location(1) accumulation: vec4<f32>,
@@ -85,10 +101,11 @@ struct WboitOutput {
 @group(1) @binding(1) var shadow_maps: texture_depth_2d_array;
 @group(1) @binding(2) var<storage, read> point_lights: array<PointLight>;
 @group(1) @binding(3) var light_count_texture: texture_2d<u32>;
-@group(1) @binding(4) var<storage, read> tile_light_indices: array<TileLightIndices>;
+@group(1) @binding(4) var<storage, read> cluster_light_indices: array<ClusterLightIndices>;
 @group(1) @binding(5) var point_shadow_maps: texture_depth_cube_array;
 @group(1) @binding(6) var<storage, read> directional_light_partitions: array<DirectionalLightPartition>;
 @group(2) @binding(0) var<storage, read> instance_data: array<InstanceData>;
+@group(2) @binding(1) var light_map_texture: texture_2d<f32>;
 @group(3) @binding(0) var textures: binding_array<texture_2d<f32>>;
 
 override ALPHA_TO_COVERAGE_ACTIVATED: bool;
@@ -102,12 +119,13 @@ fn vs_main(
     @location(3) color: vec3<f32>,
     @location(4) texture_index: i32,
     @location(5) wind_affinity: f32,
+    @location(6) light_map_coordinates: vec2<f32>,
 ) -> VertexOutput {
     let instance = instance_data[instance_id];
 
     let world_position = instance.world * vec4<f32>(position, 1.0);
-    let wind_position = world_position + vec4<f32>(global_uniforms.animation_timer);
-    let offset = vec4<f32>(sin(wind_position.x), 0.0, sin(wind_position.z), 0.0) * wind_affinity;
+    let model_origin = instance.world * vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    let offset = wind_offset(world_position, model_origin, wind_affinity);
     let final_world_position = world_position + offset;
 
     var output: VertexOutput;
@@ -117,6 +135,8 @@ fn vs_main(
     output.normal = normalize((instance.inv_world * vec4<f32>(normal, 0.0)).xyz);
     output.texture_coordinates = texture_coordinates;
     output.color = color;
+    output.opacity = instance.opacity;
+    output.light_map_coordinates = light_map_coordinates;
     output.texture_index = texture_index;
     return output;
 }
@@ -152,6 +172,12 @@ fn get_shadow_offsets(normal: vec3<f32>, light_direction: vec3<f32>) -> vec2<f32
 }
 
 fn fragment(input: VertexOutput) -> vec4<f32> {
+    // Models that hide the player are faded out with a dithered cutout, so they
+    // can stay in the opaque passes.
+    if (input.opacity < 1.0 && input.opacity <= dither_threshold(vec2<u32>(floor(input.position.xy)))) {
+        discard;
+    }
+
     var diffuse_color: vec4<f32>;
     var alpha_channel: f32;
 
@@ -175,15 +201,14 @@ fn fragment(input: VertexOutput) -> vec4<f32> {
         // Transparent pass can early exit if invisible or opaque pixel was found.
         discard;
     } else {
-        // Calculate which tile this fragment belongs to
+        // Calculate which cluster this fragment belongs to
         let pixel_position = vec2<u32>(floor(input.position.xy));
-        let tile_x = pixel_position.x / TILE_SIZE;
-        let tile_y = pixel_position.y / TILE_SIZE;
-        let tile_count_x = (global_uniforms.forward_size.x + TILE_SIZE - 1u) / TILE_SIZE;
-        let tile_index = tile_y * tile_count_x + tile_x;
+        let cluster_coordinates = get_cluster_coordinates(pixel_position, nonLinearToLinear(input.position.z));
+        let cluster_count_x = (global_uniforms.forward_size.x + CLUSTER_TILE_SIZE - 1u) / CLUSTER_TILE_SIZE;
+        let cluster_index = cluster_coordinates.y * cluster_count_x + cluster_coordinates.x;
 
-        // Get the number of lights affecting this tile
-        let light_count = textureLoad(light_count_texture, vec2<u32>(tile_x, tile_y), 0).r;
+        // Get the number of lights affecting this cluster
+        let light_count = textureLoad(light_count_texture, cluster_coordinates, 0).r;
 
         if (ALPHA_TO_COVERAGE_ACTIVATED) {
             // Apply mip level scaling for better mipmap coverage
@@ -257,7 +282,7 @@ fn fragment(input: VertexOutput) -> vec4<f32> {
         // Point lights
         var point_light_contribution = vec3<f32>(0.0);
         for (var index = 0u; index < light_count; index++) {
-            let light_index = tile_light_indices[tile_index].indices[index];
+            let light_index = cluster_light_indices[cluster_index].indices[index];
             let light = point_lights[light_index];
             let light_direction = normalize(input.world_position.xyz - light.position.xyz);
             let light_percent = max(dot(light_direction, normal), 0.0);
@@ -284,19 +309,67 @@ fn fragment(input: VertexOutput) -> vec4<f32> {
         }
 
         let base_color = diffuse_color.rgb * input.color;
-        let light_contributions = saturate(ambient_light_contribution + directional_light_contribution + point_light_contribution);
-        var color = base_color.rgb * light_contributions;
+        var color: vec3<f32>;
+
+        if (global_uniforms.light_map_mode != 0u && input.light_map_coordinates.x >= 0.0) {
+            // The baked light map already contains the shadows and the light sources of the map.
+            let light_map = textureSampleLevel(light_map_texture, linear_sampler, input.light_map_coordinates, 0.0);
+            var light_map_color = light_map.rgb;
+
+            if (global_uniforms.light_map_mode == 1u) {
+                light_map_color = floor(light_map_color * LIGHT_MAP_COLOR_LEVELS) / LIGHT_MAP_COLOR_LEVELS;
+            }
+
+            let baked_light = saturate(ambient_light_contribution + directional_light.color.rgb * light_percent) * light_map.a;
+            color = base_color * baked_light + light_map_color;
+        } else {
+            let light_contributions = saturate(ambient_light_contribution + directional_light_contribution + point_light_contribution);
+            color = base_color.rgb * light_contributions;
+        }
 
         if (global_uniforms.enhanced_lighting == 0) {
             color = color_balance(color, -0.01, 0.0, 0.0);
         }
 
+        color *= view_range_dimming(input.world_position.xyz);
+
         fragment_color = vec4<f32>(color, diffuse_color.a);
     }
 
     return fragment_color;
 }
 
+// Offset of a vertex that is moved by the wind. The wind uniform contains the horizontal wind direction
+// in `xy`, the wind strength in `z` and the current gust factor in `w`.
+fn wind_offset(world_position: vec4<f32>, model_origin: vec4<f32>, wind_affinity: f32) -> vec4<f32> {
+    let wind = global_uniforms.wind;
+
+    // Vertices close to the ground, like the trunk of a tree, barely move.
+    let height = saturate((world_position.y - model_origin.y) / WIND_REFERENCE_HEIGHT);
+
+    // Nearby vertices share a similar phase, so the foliage moves as a whole and doesn't tear apart.
+    let phase = dot(world_position.xz, vec2<f32>(0.05, 0.07)) + global_uniforms.animation_timer * 1.7;
+    let sway = wind.w + 0.35 * sin(phase) + 0.15 * sin(phase * 2.3 + 1.1);
+
+    let displacement = wind.z * wind_affinity * height * height * sway;
+    return vec4<f32>(wind.x * displacement, 0.0, wind.y * displacement, 0.0);
+}
+
+// Ordered 4x4 Bayer matrix threshold in the range (0, 1).
+fn dither_threshold(pixel_position: vec2<u32>) -> f32 {
+    let index = (pixel_position.y % 4u) * 4u + (pixel_position.x % 4u);
+    return (f32(BAYER_MATRIX_4X4[index]) + 0.5) / 16.0;
+}
+
+// Darkens everything beyond the view range of the server. The view range is
+// square, since the server measures its distance in tiles.
+fn view_range_dimming(world_position: vec3<f32>) -> f32 {
+    let offset = abs(world_position.xz - global_uniforms.view_range.xy);
+    let distance = max(offset.x, offset.y);
+    let falloff = smoothstep(global_uniforms.view_range.z, global_uniforms.view_range.z + VIEW_RANGE_FALLOFF, distance);
+    return 1.0 - global_uniforms.view_range.w * falloff;
+}
+
 // -1 = full shift towards first color (Cyan/Magenta/Yellow)
 // +1 = full shift towards second color (Red/Green/Blue)
 fn color_balance(color: vec3<f32>, cyan_red: f32, magenta_green: f32, yellow_blue: f32) -> vec3<f32> {
@@ -676,6 +749,16 @@ fn nonLinearToLinear(non_linear_depth: f32) -> f32 {
     return NEAR_PLANE / (non_linear_depth + DEPTH_EPSILON);
 }
 
+// The depth slices of the clusters are distributed exponentially and stacked
+// vertically in the light count texture.
+fn get_cluster_coordinates(pixel_position: vec2<u32>, linear_view_z: f32) -> vec2<u32> {
+    let cluster_count_y = (global_uniforms.forward_size.y + CLUSTER_TILE_SIZE - 1u) / CLUSTER_TILE_SIZE;
+    let slice_scale = f32(CLUSTER_DEPTH_SLICES) / log(CLUSTER_FAR_PLANE / NEAR_PLANE);
+    let slice = min(u32(max(log(linear_view_z / NEAR_PLANE) * slice_scale, 0.0)), CLUSTER_DEPTH_SLICES - 1u);
+    let cluster = pixel_position / CLUSTER_TILE_SIZE;
+    return vec2<u32>(cluster.x, slice * cluster_count_y + cluster.y);
+}
+
 fn linearToNonLinear(linear_depth: f32) -> f32 {
     return NEAR_PLANE / (linear_depth + DEPTH_EPSILON);
 }
@@ -38,7 +38,9 @@ struct VertexOutput {
     @location(0) fragment_position: vec2<f32>,
 }
 
-const TILE_SIZE: u32 = 16;
+const CLUSTER_TILE_SIZE: u32 = 64;
+const CLUSTER_DEPTH_SLICES: u32 = 16;
+const CLUSTER_FAR_PLANE: f32 = 1000.0;
 const NEAR_PLANE: f32 = 0.1;
 const DEPTH_EPSILON: f32 = 1.0e-7;
 
@@ -122,8 +124,10 @@ fn fs_main(
     }
 
     if (debug_uniforms.show_light_culling_count_buffer != 0u) {
-        let tile_coord = pixel_coord / i32(TILE_SIZE);
-        let count = textureLoad(light_count_texture, tile_coord, 0).r;
+        // Shows the light count of the cluster at the depth of the visible surface.
+        let linear_depth = nonLinearToLinear(textureLoad(depth_texture, pixel_coord, 0));
+        let cluster_coordinates = get_cluster_coordinates(vec2<u32>(pixel_coord), linear_depth);
+        let count = textureLoad(light_count_texture, cluster_coordinates, 0).r;
 
         var color = vec4<f32>(0.0);
 
@@ -173,6 +177,14 @@ fn nonLinearToLinear(non_linear_depth: f32) -> f32 {
     return NEAR_PLANE / (non_linear_depth + DEPTH_EPSILON);
 }
 
+fn get_cluster_coordinates(pixel_position: vec2<u32>, linear_view_z: f32) -> vec2<u32> {
+    let cluster_count_y = (global_uniforms.forward_size.y + CLUSTER_TILE_SIZE - 1u) / CLUSTER_TILE_SIZE;
+    let slice_scale = f32(CLUSTER_DEPTH_SLICES) / log(CLUSTER_FAR_PLANE / NEAR_PLANE);
+    let slice = min(u32(max(log(linear_view_z / NEAR_PLANE) * slice_scale, 0.0)), CLUSTER_DEPTH_SLICES - 1u);
+    let cluster = pixel_position / CLUSTER_TILE_SIZE;
+    return vec2<u32>(cluster.x, slice * cluster_count_y + cluster.y);
+}
+
 fn clip_to_uv(clip_space_position: vec2<f32>) -> vec2<f32> {
     return vec2<f32>((clip_space_position.x + 1.0) * 0.5, (1.0 - clip_space_position.y) * 0.5);
 }
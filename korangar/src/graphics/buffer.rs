@@ -180,6 +180,11 @@ impl<T: Sized + Pod + Zeroable> Buffer<T> {
     pub fn count(&self) -> u32 {
         (self.size.load(Ordering::Acquire) / size_of::<T>() as u64) as u32
     }
+
+    /// Returns the number of bytes allocated on the GPU.
+    pub fn allocated_size(&self) -> u64 {
+        self.buffer.size()
+    }
 }
 
 impl Buffer<u64> {
@@ -1,22 +1,33 @@
 #[cfg(feature = "debug")]
 use korangar_debug::logging::{Colorize, print_debug};
-use wgpu::{Adapter, Features, Limits, TextureFormat, TextureFormatFeatureFlags};
+use wgpu::{Adapter, DeviceType, Features, Limits, TextureFormat, TextureFormatFeatureFlags};
 
-use crate::graphics::{Msaa, RENDER_TO_TEXTURE_DEPTH_FORMAT, RENDER_TO_TEXTURE_FORMAT};
+use crate::graphics::{GraphicsPreset, Msaa, RENDER_TO_TEXTURE_DEPTH_FORMAT, RENDER_TO_TEXTURE_FORMAT};
 
 pub const MAX_BINDING_ARRAY_ELEMENTS_PER_SHADER_STAGE: u32 = 10240;
 
+/// The minimum size of a binding array for bindless rendering. Adapters that
+/// support less than [`MAX_BINDING_ARRAY_ELEMENTS_PER_SHADER_STAGE`] elements
+/// split big texture sets into multiple texture groups.
+const MIN_BINDING_ARRAY_ELEMENTS_PER_SHADER_STAGE: u32 = 128;
+
 pub const MAX_STORAGE_BUFFER_BINDING_SIZE: u32 = 268435456;
 
 /// The maximum texture size that is guaranteed by the graphic engine to be
 /// available.
 pub const MAX_TEXTURE_SIZE: u32 = 8192;
 
+/// Texture size that only adapters of the current generations support. We
+/// use it as a hint for the performance of an adapter, since the amount of
+/// video memory is not exposed.
+const HIGH_END_TEXTURE_SIZE: u32 = 32768;
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum BindlessSupport {
     /// Full bindless support.
     Full,
-    /// Limited bindless support (missing PARTIALLY_BOUND_BINDING_ARRAY).
+    /// Limited bindless support (missing PARTIALLY_BOUND_BINDING_ARRAY or only
+    /// small binding arrays). Only models are rendered bindless.
     Limited,
     /// Bindless is not supported at all.
     None,
@@ -32,6 +43,9 @@ pub struct Capabilities {
     polygon_mode_line: bool,
     required_features: Features,
     required_limits: Limits,
+    texture_binding_array_count: u32,
+    device_type: DeviceType,
+    adapter_max_texture_dimension_2d: u32,
 }
 
 impl Capabilities {
@@ -56,6 +70,9 @@ impl Capabilities {
             polygon_mode_line: false,
             required_features: Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
             required_limits,
+            texture_binding_array_count: MAX_BINDING_ARRAY_ELEMENTS_PER_SHADER_STAGE,
+            device_type: adapter.get_info().device_type,
+            adapter_max_texture_dimension_2d: adapter_limits.max_texture_dimension_2d,
         };
 
         if capabilities.required_limits.max_texture_dimension_2d < MAX_TEXTURE_SIZE {
@@ -84,16 +101,26 @@ impl Capabilities {
 
         if adapter_features
             .contains(Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING | Features::TEXTURE_BINDING_ARRAY)
-            && adapter_limits.max_binding_array_elements_per_shader_stage >= MAX_BINDING_ARRAY_ELEMENTS_PER_SHADER_STAGE
+            && adapter_limits.max_binding_array_elements_per_shader_stage >= MIN_BINDING_ARRAY_ELEMENTS_PER_SHADER_STAGE
         {
             capabilities.bindless = BindlessSupport::Limited;
 
             capabilities.required_features |=
                 Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING | Features::TEXTURE_BINDING_ARRAY;
 
-            capabilities.required_limits.max_binding_array_elements_per_shader_stage = MAX_BINDING_ARRAY_ELEMENTS_PER_SHADER_STAGE;
-
-            if adapter_features.contains(Features::PARTIALLY_BOUND_BINDING_ARRAY) {
+            // Older and integrated adapters often support smaller binding arrays. We still
+            // render bindless on them, but need to split big texture sets into multiple
+            // texture groups that are drawn one after another.
+            capabilities.texture_binding_array_count = adapter_limits
+                .max_binding_array_elements_per_shader_stage
+                .min(MAX_BINDING_ARRAY_ELEMENTS_PER_SHADER_STAGE);
+            capabilities.required_limits.max_binding_array_elements_per_shader_stage = capabilities.texture_binding_array_count;
+
+            // Passes that collect their textures every frame can't split them into texture
+            // groups, so they need the full binding array.
+            if adapter_features.contains(Features::PARTIALLY_BOUND_BINDING_ARRAY)
+                && capabilities.texture_binding_array_count == MAX_BINDING_ARRAY_ELEMENTS_PER_SHADER_STAGE
+            {
                 capabilities.bindless = BindlessSupport::Full;
                 capabilities.required_features |= Features::PARTIALLY_BOUND_BINDING_ARRAY;
             }
@@ -140,9 +167,10 @@ impl Capabilities {
         self.required_limits.max_texture_dimension_2d
     }
 
-    /// Returns the maximum count of textures inside a binding array.
-    pub const fn get_max_texture_binding_array_count(&self) -> u32 {
-        MAX_BINDING_ARRAY_ELEMENTS_PER_SHADER_STAGE
+    /// Returns the maximum count of textures inside a binding array. Texture
+    /// sets with more textures are split into texture groups of this size.
+    pub fn get_max_texture_binding_array_count(&self) -> u32 {
+        self.texture_binding_array_count
     }
 
     /// Returns `true` if the backend supports all features needed for multidraw
@@ -167,6 +195,22 @@ impl Capabilities {
         self.texture_compression
     }
 
+    /// Returns the graphics preset that should run well on the adapter. The
+    /// amount of video memory is not exposed, so the type of the adapter and
+    /// its features and limits are used to estimate its performance.
+    pub fn recommended_graphics_preset(&self) -> GraphicsPreset {
+        let fast_path = self.bindless == BindlessSupport::Full && self.multidraw_indirect && self.texture_compression;
+
+        match self.device_type {
+            DeviceType::DiscreteGpu if fast_path && self.adapter_max_texture_dimension_2d >= HIGH_END_TEXTURE_SIZE => GraphicsPreset::Ultra,
+            DeviceType::DiscreteGpu if fast_path => GraphicsPreset::High,
+            DeviceType::DiscreteGpu | DeviceType::IntegratedGpu if self.bindless != BindlessSupport::None => GraphicsPreset::Medium,
+            DeviceType::DiscreteGpu | DeviceType::IntegratedGpu | DeviceType::VirtualGpu | DeviceType::Other | DeviceType::Cpu => {
+                GraphicsPreset::Low
+            }
+        }
+    }
+
     /// Returns `true` if the backend allows drawing triangles as lines
     /// (wireframe) instead of filled.
     #[cfg(feature = "debug")]
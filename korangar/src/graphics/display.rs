@@ -0,0 +1,114 @@
+#[cfg(feature = "debug")]
+use korangar_debug::logging::{Colorize, print_debug};
+use winit::monitor::{MonitorHandle, VideoModeHandle};
+use winit::window::{Fullscreen, Window};
+
+use super::{DisplayMode, MonitorOption, VideoMode, VideoModeOption};
+
+fn find_monitor(window: &Window, monitor_name: Option<&str>) -> Option<MonitorHandle> {
+    monitor_name
+        .and_then(|monitor_name| {
+            window
+                .available_monitors()
+                .find(|monitor| monitor.name().as_deref() == Some(monitor_name))
+        })
+        .or_else(|| window.current_monitor())
+        .or_else(|| window.primary_monitor())
+}
+
+fn to_video_mode(video_mode_handle: &VideoModeHandle) -> VideoMode {
+    let size = video_mode_handle.size();
+
+    VideoMode {
+        width: size.width,
+        height: size.height,
+        refresh_rate_millihertz: video_mode_handle.refresh_rate_millihertz(),
+    }
+}
+
+/// Get all monitors that the window can be moved to.
+pub fn monitor_options(window: &Window) -> Vec<MonitorOption> {
+    let current_monitor = MonitorOption {
+        name: None,
+        label: "Current monitor".to_string(),
+    };
+
+    std::iter::once(current_monitor)
+        .chain(window.available_monitors().filter_map(|monitor| {
+            let name = monitor.name()?;
+            let size = monitor.size();
+
+            Some(MonitorOption {
+                label: format!("{name} ({}x{})", size.width, size.height),
+                name: Some(name),
+            })
+        }))
+        .collect()
+}
+
+/// Get all video modes for exclusive fullscreen on the given monitor.
+pub fn video_mode_options(window: &Window, monitor_name: Option<&str>) -> Vec<VideoModeOption> {
+    let native_video_mode = VideoModeOption {
+        video_mode: None,
+        label: "Native".to_string(),
+    };
+
+    let mut video_modes: Vec<VideoMode> = find_monitor(window, monitor_name)
+        .map(|monitor| monitor.video_modes().map(|video_mode| to_video_mode(&video_mode)).collect())
+        .unwrap_or_default();
+
+    // Monitors report the same mode multiple times with different bit depths.
+    video_modes.sort_by_key(|video_mode| std::cmp::Reverse((video_mode.width, video_mode.height, video_mode.refresh_rate_millihertz)));
+    video_modes.dedup();
+
+    std::iter::once(native_video_mode)
+        .chain(video_modes.into_iter().map(|video_mode| VideoModeOption {
+            label: video_mode.to_string(),
+            video_mode: Some(video_mode),
+        }))
+        .collect()
+}
+
+/// Apply the display mode to the window.
+///
+/// If the selected monitor is not available anymore, for example because it
+/// was unplugged, the current monitor is used instead. If the selected video
+/// mode is not available, we fall back to borderless fullscreen.
+pub fn apply_display_mode(window: &Window, display_mode: DisplayMode, monitor_name: Option<&str>, video_mode: Option<VideoMode>) {
+    let monitor = find_monitor(window, monitor_name);
+
+    let fullscreen = match display_mode {
+        DisplayMode::Windowed => None,
+        DisplayMode::BorderlessFullscreen => Some(Fullscreen::Borderless(monitor)),
+        DisplayMode::ExclusiveFullscreen => {
+            let video_mode_handle = monitor.as_ref().and_then(|monitor| {
+                let mut video_modes = monitor.video_modes();
+
+                match video_mode {
+                    Some(video_mode) => video_modes.find(|handle| to_video_mode(handle) == video_mode),
+                    // Use the native resolution with the highest refresh rate.
+                    None => video_modes.max_by_key(|handle| {
+                        let video_mode = to_video_mode(handle);
+                        (video_mode.width, video_mode.height, video_mode.refresh_rate_millihertz)
+                    }),
+                }
+            });
+
+            match video_mode_handle {
+                Some(video_mode_handle) => Some(Fullscreen::Exclusive(video_mode_handle)),
+                None => {
+                    #[cfg(feature = "debug")]
+                    print_debug!(
+                        "[{}] video mode not available, falling back to {}",
+                        "warning".yellow(),
+                        "borderless fullscreen".magenta()
+                    );
+
+                    Some(Fullscreen::Borderless(monitor))
+                }
+            }
+        }
+    };
+
+    window.set_fullscreen(fullscreen);
+}
@@ -3,6 +3,7 @@ use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
 use cgmath::Vector2;
+use hashbrown::HashSet;
 #[cfg(feature = "debug")]
 use korangar_debug::logging::{Colorize, print_debug};
 #[cfg(feature = "debug")]
@@ -19,9 +20,9 @@ use winit::window::Window;
 #[cfg(feature = "debug")]
 use super::BindlessSupport;
 use super::{
-    AntiAliasingResources, Capabilities, DirectionalShadowPartition, FramePacer, FrameStage, GlobalContext, LimitFramerate, Msaa,
-    PARTITION_COUNT, Partition, Prepare, PresentModeInfo, RENDER_TO_TEXTURE_FORMAT, ScreenSpaceAntiAliasing, ShadowDetail, Ssaa, Surface,
-    TextureSamplerType,
+    AntiAliasingResources, Capabilities, DirectionalShadowPartition, FramePacer, FrameStage, GlobalContext, GraphicsPreset, LimitFramerate,
+    Msaa, PARTITION_COUNT, Partition, Prepare, PresentModeInfo, RENDER_TO_TEXTURE_FORMAT, ScreenSpaceAntiAliasing, ShadowDetail,
+    SpriteFiltering, Ssaa, Surface, TextureSamplerType, WaterQuality,
 };
 use crate::graphics::ScreenSize;
 use crate::graphics::instruction::RenderInstruction;
@@ -84,8 +85,11 @@ struct EngineContext {
     point_shadow_pass_context: PointShadowRenderPassContext,
     light_culling_pass_context: LightCullingPassContext,
     forward_pass_context: ForwardRenderPassContext,
+    water_reflection_pass_context: WaterReflectionRenderPassContext,
+    water_pass_context: WaterRenderPassContext,
     sdsm_pass_context: SdsmPassContext,
     post_processing_pass_context: PostProcessingRenderPassContext,
+    effect_wboit_pass_context: EffectWboitRenderPassContext,
     screen_blit_pass_context: ScreenBlitRenderPassContext,
 
     interface_rectangle_drawer: InterfaceRectangleDrawer,
@@ -98,9 +102,13 @@ struct EngineContext {
     point_shadow_model_drawer: PointShadowModelDrawer,
     point_shadow_indicator_drawer: PointShadowIndicatorDrawer,
     light_culling_dispatcher: LightCullingDispatcher,
+    forward_decal_drawer: ForwardDecalDrawer,
     forward_entity_drawer: ForwardEntityDrawer,
     forward_indicator_drawer: ForwardIndicatorDrawer,
     forward_model_drawer: ForwardModelDrawer,
+    forward_shadow_blob_drawer: ForwardShadowBlobDrawer,
+    forward_sky_drawer: ForwardSkyDrawer,
+    water_reflection_model_drawer: WaterReflectionModelDrawer,
     water_wave_drawer: WaterWaveDrawer,
     clear_partitions_dispatcher: ClearPartitionsDispatcher,
     reduce_partitions_dispatcher: ReducePartitionsDispatcher,
@@ -110,6 +118,7 @@ struct EngineContext {
     compute_custom_partitions_dispatcher: ComputeCustomPartitionsDispatcher,
     post_processing_effect_drawer: PostProcessingEffectDrawer,
     post_processing_fxaa_drawer: PostProcessingFxaaDrawer,
+    post_processing_crt_drawer: PostProcessingCrtDrawer,
     post_processing_blitter_drawer: PostProcessingBlitterDrawer,
     post_processing_rectangle_drawer: PostProcessingRectangleDrawer,
     post_processing_wboit_resolve_drawer: PostProcessingWboitResolveDrawer,
@@ -180,10 +189,13 @@ impl GraphicsEngine {
         limit_framerate: LimitFramerate,
         shadow_detail: ShadowDetail,
         texture_sampler_type: TextureSamplerType,
+        sprite_filtering: SpriteFiltering,
         msaa: Msaa,
         ssaa: Ssaa,
         screen_space_anti_aliasing: ScreenSpaceAntiAliasing,
         high_quality_interface: bool,
+        crt_filter: bool,
+        order_independent_effects: bool,
     ) {
         self.set_limit_framerate(limit_framerate);
 
@@ -223,7 +235,10 @@ impl GraphicsEngine {
                             screen_size,
                             shadow_detail,
                             texture_sampler_type,
+                            sprite_filtering,
                             high_quality_interface,
+                            crt_filter,
+                            order_independent_effects,
                         );
 
                         let interface_render_pass_context =
@@ -237,9 +252,15 @@ impl GraphicsEngine {
                         let light_culling_pass_context = LightCullingPassContext::new(&self.device, &self.queue, &global_context);
                         let forward_pass_context =
                             ForwardRenderPassContext::new(&self.device, &self.queue, &self.texture_loader, &global_context);
+                        let water_reflection_pass_context =
+                            WaterReflectionRenderPassContext::new(&self.device, &self.queue, &self.texture_loader, &global_context);
+                        let water_pass_context =
+                            WaterRenderPassContext::new(&self.device, &self.queue, &self.texture_loader, &global_context);
                         let sdsm_pass_context = SdsmPassContext::new(&self.device, &self.queue, &global_context);
                         let post_processing_pass_context =
                             PostProcessingRenderPassContext::new(&self.device, &self.queue, &self.texture_loader, &global_context);
+                        let effect_wboit_pass_context =
+                            EffectWboitRenderPassContext::new(&self.device, &self.queue, &self.texture_loader, &global_context);
                         let screen_blit_pass_context =
                             ScreenBlitRenderPassContext::new(&self.device, &self.queue, &self.texture_loader, &global_context);
                     });
@@ -326,9 +347,12 @@ impl GraphicsEngine {
                             &light_culling_pass_context,
                         );
                         let ForwardResources {
+                            forward_decal_drawer,
                             forward_entity_drawer,
                             forward_indicator_drawer,
                             forward_model_drawer,
+                            forward_shadow_blob_drawer,
+                            forward_sky_drawer,
                         } = ForwardResources::create(
                             &self.capabilities,
                             &self.device,
@@ -337,13 +361,21 @@ impl GraphicsEngine {
                             &global_context,
                             &forward_pass_context,
                         );
+                        let water_reflection_model_drawer = WaterReflectionModelDrawer::new(
+                            &self.capabilities,
+                            &self.device,
+                            &self.queue,
+                            &self.shader_compiler,
+                            &global_context,
+                            &water_reflection_pass_context,
+                        );
                         let water_wave_drawer = WaterWaveDrawer::new(
                             &self.capabilities,
                             &self.device,
                             &self.queue,
                             &self.shader_compiler,
                             &global_context,
-                            &forward_pass_context,
+                            &water_pass_context,
                         );
                         let clear_partitions_dispatcher = ClearPartitionsDispatcher::new(
                             &self.capabilities,
@@ -396,6 +428,7 @@ impl GraphicsEngine {
                         let PostProcessingResources {
                             post_processing_effect_drawer,
                             post_processing_fxaa_drawer,
+                            post_processing_crt_drawer,
                             post_processing_blitter_drawer,
                             post_processing_rectangle_drawer,
                             post_processing_wboit_resolve_drawer,
@@ -442,8 +475,11 @@ impl GraphicsEngine {
                         point_shadow_pass_context,
                         light_culling_pass_context,
                         forward_pass_context,
+                        water_reflection_pass_context,
+                        water_pass_context,
                         sdsm_pass_context: SdsmPassContext {},
                         post_processing_pass_context,
+                        effect_wboit_pass_context,
                         screen_blit_pass_context,
                         interface_rectangle_drawer,
                         picker_entity_drawer,
@@ -455,9 +491,13 @@ impl GraphicsEngine {
                         point_shadow_indicator_drawer,
                         point_shadow_entity_drawer,
                         light_culling_dispatcher,
+                        forward_decal_drawer,
                         forward_entity_drawer,
                         forward_indicator_drawer,
                         forward_model_drawer,
+                        forward_shadow_blob_drawer,
+                        forward_sky_drawer,
+                        water_reflection_model_drawer,
                         water_wave_drawer,
                         clear_partitions_dispatcher,
                         reduce_partitions_dispatcher,
@@ -467,6 +507,7 @@ impl GraphicsEngine {
                         compute_custom_partitions_dispatcher,
                         post_processing_effect_drawer,
                         post_processing_fxaa_drawer,
+                        post_processing_crt_drawer,
                         post_processing_blitter_drawer,
                         post_processing_rectangle_drawer,
                         post_processing_wboit_resolve_drawer,
@@ -582,6 +623,14 @@ impl GraphicsEngine {
         }
     }
 
+    pub fn set_sprite_filtering(&mut self, sprite_filtering: SpriteFiltering) {
+        if let Some(engine_context) = self.engine_context.as_mut() {
+            engine_context
+                .global_context
+                .update_sprite_sampler(&self.device, &self.capabilities, sprite_filtering);
+        }
+    }
+
     pub fn set_screen_space_anti_aliasing(&mut self, screen_space_anti_aliasing: ScreenSpaceAntiAliasing) {
         if let Some(engine_context) = self.engine_context.as_mut() {
             engine_context
@@ -595,9 +644,12 @@ impl GraphicsEngine {
             engine_context.global_context.update_msaa(&self.device, msaa);
 
             let ForwardResources {
+                forward_decal_drawer,
                 forward_entity_drawer,
                 forward_indicator_drawer,
                 forward_model_drawer,
+                forward_shadow_blob_drawer,
+                forward_sky_drawer,
             } = ForwardResources::create(
                 &self.capabilities,
                 &self.device,
@@ -610,6 +662,7 @@ impl GraphicsEngine {
             let PostProcessingResources {
                 post_processing_effect_drawer,
                 post_processing_fxaa_drawer,
+                post_processing_crt_drawer,
                 post_processing_blitter_drawer,
                 post_processing_rectangle_drawer,
                 post_processing_wboit_resolve_drawer,
@@ -630,11 +683,15 @@ impl GraphicsEngine {
                 &engine_context.post_processing_pass_context,
             );
 
+            engine_context.forward_decal_drawer = forward_decal_drawer;
             engine_context.forward_entity_drawer = forward_entity_drawer;
             engine_context.forward_indicator_drawer = forward_indicator_drawer;
             engine_context.forward_model_drawer = forward_model_drawer;
+            engine_context.forward_shadow_blob_drawer = forward_shadow_blob_drawer;
+            engine_context.forward_sky_drawer = forward_sky_drawer;
             engine_context.post_processing_effect_drawer = post_processing_effect_drawer;
             engine_context.post_processing_fxaa_drawer = post_processing_fxaa_drawer;
+            engine_context.post_processing_crt_drawer = post_processing_crt_drawer;
             engine_context.post_processing_blitter_drawer = post_processing_blitter_drawer;
             engine_context.post_processing_rectangle_drawer = post_processing_rectangle_drawer;
             engine_context.post_processing_wboit_resolve_drawer = post_processing_wboit_resolve_drawer;
@@ -645,7 +702,7 @@ impl GraphicsEngine {
                 &self.queue,
                 &self.shader_compiler,
                 &engine_context.global_context,
-                &engine_context.forward_pass_context,
+                &engine_context.water_pass_context,
             );
 
             engine_context.clear_partitions_dispatcher = ClearPartitionsDispatcher::new(
@@ -735,6 +792,24 @@ impl GraphicsEngine {
             engine_context
                 .global_context
                 .update_high_quality_interface(&self.device, high_quality_interface);
+            engine_context.interface_rectangle_drawer.invalidate();
+        }
+    }
+
+    pub fn set_crt_filter(&mut self, crt_filter: bool) {
+        if let Some(engine_context) = self.engine_context.as_mut() {
+            engine_context.global_context.update_crt_filter(&self.device, crt_filter);
+        }
+    }
+
+    pub fn set_order_independent_effects(&mut self, order_independent_effects: bool) {
+        if let Some(engine_context) = self.engine_context.as_mut() {
+            engine_context
+                .global_context
+                .update_order_independent_effects(&self.device, order_independent_effects);
+            engine_context
+                .post_processing_effect_drawer
+                .set_order_independent_effects(order_independent_effects);
         }
     }
 
@@ -750,6 +825,10 @@ impl GraphicsEngine {
         self.capabilities.get_supported_msaa().to_vec()
     }
 
+    pub fn get_recommended_graphics_preset(&self) -> GraphicsPreset {
+        self.capabilities.recommended_graphics_preset()
+    }
+
     pub fn get_window_size(&self) -> Vector2<usize> {
         self.surface.as_ref().unwrap().window_size()
     }
@@ -797,6 +876,7 @@ impl GraphicsEngine {
                 engine_context
                     .global_context
                     .update_screen_size_resources(&self.device, screen_size);
+                engine_context.interface_rectangle_drawer.invalidate();
             }
         }
 
@@ -949,6 +1029,10 @@ impl GraphicsEngine {
             scope.spawn(|_| {
                 context.interface_rectangle_drawer.prepare(&self.device, instruction);
                 context.water_wave_drawer.prepare(&self.device, instruction);
+                context.water_reflection_model_drawer.prepare(&self.device, instruction);
+                context.forward_shadow_blob_drawer.prepare(&self.device, instruction);
+                context.forward_decal_drawer.prepare(&self.device, instruction);
+                context.forward_sky_drawer.prepare(&self.device, instruction);
             });
             scope.spawn(|_| {
                 context.point_shadow_entity_drawer.prepare(&self.device, instruction);
@@ -973,6 +1057,7 @@ impl GraphicsEngine {
                 context.global_context.prepare(&self.device, instruction);
                 context.directional_shadow_pass_context.prepare(&self.device, instruction);
                 context.point_shadow_pass_context.prepare(&self.device, instruction);
+                context.water_reflection_pass_context.prepare(&self.device, instruction);
                 context.picker_entity_drawer.prepare(&self.device, instruction);
             });
         });
@@ -995,8 +1080,13 @@ impl GraphicsEngine {
         visitor.upload(&mut context.point_shadow_model_drawer);
         visitor.upload(&mut context.point_shadow_pass_context);
         visitor.upload(&mut context.post_processing_effect_drawer);
+        visitor.upload(&mut context.forward_decal_drawer);
         visitor.upload(&mut context.forward_entity_drawer);
         visitor.upload(&mut context.forward_model_drawer);
+        visitor.upload(&mut context.forward_shadow_blob_drawer);
+        visitor.upload(&mut context.forward_sky_drawer);
+        visitor.upload(&mut context.water_reflection_pass_context);
+        visitor.upload(&mut context.water_reflection_model_drawer);
         visitor.upload(&mut context.water_wave_drawer);
         visitor.upload(&mut context.post_processing_rectangle_drawer);
 
@@ -1009,6 +1099,14 @@ impl GraphicsEngine {
             visitor.upload(&mut context.picker_marker_drawer);
         }
 
+        // Entities that are on screen should show up as soon as possible, so their
+        // textures are uploaded first.
+        let visible_textures: HashSet<u64> = instruction.entities.iter().map(|entity| entity.texture.get_id()).collect();
+
+        self.texture_loader
+            .upload_queue()
+            .upload(&self.device, &mut self.staging_belt, &mut encoder, &visible_textures);
+
         encoder.finish()
     }
 
@@ -1096,7 +1194,15 @@ impl GraphicsEngine {
             });
 
             // Interface Pass
+            //
+            // The interface is rendered into its own layer, which is composited every
+            // frame. We only need to render it again, when the interface
+            // changed since the last frame.
             scope.spawn(|_| {
+                if !engine_context.interface_rectangle_drawer.needs_redraw() {
+                    return;
+                }
+
                 let mut render_pass =
                     engine_context
                         .interface_render_pass_context
@@ -1201,6 +1307,8 @@ impl GraphicsEngine {
                     show_wireframe: instruction.render_options.show_wireframe,
                 };
 
+                engine_context.forward_sky_drawer.draw(&mut render_pass, ());
+
                 // Opaque
                 engine_context.forward_model_drawer.draw(&mut render_pass, ForwardModelDrawData {
                     batch_data,
@@ -1216,6 +1324,10 @@ impl GraphicsEngine {
                     .forward_indicator_drawer
                     .draw(&mut render_pass, instruction.indicator.as_ref());
 
+                engine_context.forward_shadow_blob_drawer.draw(&mut render_pass, ());
+
+                engine_context.forward_decal_drawer.draw(&mut render_pass, ());
+
                 engine_context.forward_entity_drawer.draw(&mut render_pass, ForwardEntityDrawData {
                     entities: instruction.entities,
                     pass_mode: EntityPassMode::Opaque,
@@ -1232,8 +1344,37 @@ impl GraphicsEngine {
                     pass_mode: ModelPassMode::Transparent,
                 });
 
+                drop(render_pass);
+
                 if let Some(water_instruction) = instruction.water.as_ref() {
-                    engine_context.water_wave_drawer.draw(&mut render_pass, water_instruction);
+                    // Water Reflection Pass
+                    if instruction.uniforms.water_quality == WaterQuality::High {
+                        let mut render_pass = engine_context.water_reflection_pass_context.create_pass(
+                            &mut forward_encoder,
+                            &engine_context.global_context,
+                            None,
+                        );
+
+                        engine_context
+                            .water_reflection_model_drawer
+                            .draw(&mut render_pass, ModelBatchDrawData {
+                                batches: instruction.model_batches,
+                                instructions: instruction.models,
+                                #[cfg(feature = "debug")]
+                                show_wireframe: false,
+                            });
+                    }
+
+                    // Water Pass
+                    let mut render_pass =
+                        engine_context
+                            .water_pass_context
+                            .create_pass(&mut forward_encoder, &engine_context.global_context, None);
+
+                    engine_context.water_wave_drawer.draw(&mut render_pass, WaterWaveDrawData {
+                        instruction: water_instruction,
+                        water_bind_group: &engine_context.global_context.water_bind_group,
+                    });
                 }
 
                 // SDSM Pass
@@ -1369,9 +1510,53 @@ impl GraphicsEngine {
                     .post_processing_rectangle_drawer
                     .draw(&mut render_pass, rectangle_data);
 
+                // Alpha blended effects are accumulated independent of their draw order and
+                // resolved below the effects that need to be blended in order.
+                let mut render_pass = match engine_context.global_context.effect_wboit_textures.as_ref() {
+                    Some(effect_wboit_textures) if engine_context.post_processing_effect_drawer.has_order_independent_effects() => {
+                        drop(render_pass);
+
+                        let mut wboit_pass = engine_context.effect_wboit_pass_context.create_pass(
+                            &mut post_processing_encoder,
+                            &engine_context.global_context,
+                            effect_wboit_textures,
+                        );
+
+                        engine_context
+                            .post_processing_effect_drawer
+                            .draw(&mut wboit_pass, PostProcessingEffectDrawData {
+                                instructions: instruction.effects,
+                                pass_mode: EffectPassMode::OrderIndependent,
+                            });
+
+                        drop(wboit_pass);
+
+                        let mut render_pass = engine_context.post_processing_pass_context.create_pass(
+                            &mut post_processing_encoder,
+                            &engine_context.global_context,
+                            engine_context.global_context.get_color_texture(),
+                        );
+
+                        let resolve_data = PostProcessingWboitResolveDrawData {
+                            accumulation_texture: &effect_wboit_textures.accumulation_texture,
+                            revealage_texture: &effect_wboit_textures.revealage_texture,
+                        };
+
+                        engine_context
+                            .post_processing_wboit_resolve_drawer
+                            .draw(&mut render_pass, resolve_data);
+
+                        render_pass
+                    }
+                    _ => render_pass,
+                };
+
                 engine_context
                     .post_processing_effect_drawer
-                    .draw(&mut render_pass, instruction.effects);
+                    .draw(&mut render_pass, PostProcessingEffectDrawData {
+                        instructions: instruction.effects,
+                        pass_mode: EffectPassMode::Blended,
+                    });
 
                 let rectangle_data = PostProcessingRectangleDrawData {
                     layer: PostProcessingRectangleLayer::Middle,
@@ -1381,6 +1566,38 @@ impl GraphicsEngine {
                     .post_processing_rectangle_drawer
                     .draw(&mut render_pass, rectangle_data);
 
+                // The CRT filter is applied to the scene and its effects, but not to the
+                // interface, so it stays readable.
+                if let Some(crt_texture) = engine_context.global_context.crt_texture.as_ref() {
+                    drop(render_pass);
+
+                    let mut crt_source_pass = engine_context.post_processing_pass_context.create_pass(
+                        &mut post_processing_encoder,
+                        &engine_context.global_context,
+                        crt_texture,
+                    );
+
+                    let blitter_data = PostProcessingBlitterDrawData {
+                        target_texture_format: RENDER_TO_TEXTURE_FORMAT,
+                        source_texture: engine_context.global_context.get_color_texture(),
+                        luma_in_alpha: false,
+                        alpha_blending: false,
+                    };
+                    engine_context
+                        .post_processing_blitter_drawer
+                        .draw(&mut crt_source_pass, blitter_data);
+
+                    drop(crt_source_pass);
+
+                    render_pass = engine_context.post_processing_pass_context.create_pass(
+                        &mut post_processing_encoder,
+                        &engine_context.global_context,
+                        engine_context.global_context.get_color_texture(),
+                    );
+
+                    engine_context.post_processing_crt_drawer.draw(&mut render_pass, crt_texture);
+                }
+
                 #[cfg(feature = "debug")]
                 {
                     let buffer_data = DebugBufferDrawData {
@@ -1450,9 +1667,12 @@ impl UploadVisitor<'_> {
 }
 
 struct ForwardResources {
+    forward_decal_drawer: ForwardDecalDrawer,
     forward_entity_drawer: ForwardEntityDrawer,
     forward_indicator_drawer: ForwardIndicatorDrawer,
     forward_model_drawer: ForwardModelDrawer,
+    forward_shadow_blob_drawer: ForwardShadowBlobDrawer,
+    forward_sky_drawer: ForwardSkyDrawer,
 }
 
 impl ForwardResources {
@@ -1489,10 +1709,40 @@ impl ForwardResources {
             forward_pass_context,
         );
 
+        let forward_decal_drawer = ForwardDecalDrawer::new(
+            capabilities,
+            device,
+            queue,
+            shader_compiler,
+            global_context,
+            forward_pass_context,
+        );
+
+        let forward_shadow_blob_drawer = ForwardShadowBlobDrawer::new(
+            capabilities,
+            device,
+            queue,
+            shader_compiler,
+            global_context,
+            forward_pass_context,
+        );
+
+        let forward_sky_drawer = ForwardSkyDrawer::new(
+            capabilities,
+            device,
+            queue,
+            shader_compiler,
+            global_context,
+            forward_pass_context,
+        );
+
         Self {
+            forward_decal_drawer,
             forward_entity_drawer,
             forward_indicator_drawer,
             forward_model_drawer,
+            forward_shadow_blob_drawer,
+            forward_sky_drawer,
         }
     }
 }
@@ -1500,6 +1750,7 @@ impl ForwardResources {
 struct PostProcessingResources {
     post_processing_effect_drawer: PostProcessingEffectDrawer,
     post_processing_fxaa_drawer: PostProcessingFxaaDrawer,
+    post_processing_crt_drawer: PostProcessingCrtDrawer,
     post_processing_blitter_drawer: PostProcessingBlitterDrawer,
     post_processing_rectangle_drawer: PostProcessingRectangleDrawer,
     post_processing_wboit_resolve_drawer: PostProcessingWboitResolveDrawer,
@@ -1538,6 +1789,14 @@ impl PostProcessingResources {
             global_context,
             post_processing_pass_context,
         );
+        let post_processing_crt_drawer = PostProcessingCrtDrawer::new(
+            capabilities,
+            device,
+            queue,
+            shader_compiler,
+            global_context,
+            post_processing_pass_context,
+        );
         let post_processing_blitter_drawer = PostProcessingBlitterDrawer::new(
             capabilities,
             device,
@@ -1602,6 +1861,7 @@ impl PostProcessingResources {
         Self {
             post_processing_effect_drawer,
             post_processing_fxaa_drawer,
+            post_processing_crt_drawer,
             post_processing_blitter_drawer,
             post_processing_rectangle_drawer,
             post_processing_wboit_resolve_drawer,
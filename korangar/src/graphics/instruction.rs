@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use cgmath::{Deg, Matrix4, Point3, SquareMatrix, Vector2, Vector3, Vector4, Zero};
+use cgmath::{Deg, Matrix4, Point3, Rad, SquareMatrix, Vector2, Vector3, Vector4, Zero};
 use ragnarok_packets::EntityId;
 use wgpu::BlendFactor;
 
@@ -8,7 +8,7 @@ use super::color::Color;
 #[cfg(feature = "debug")]
 use super::settings::RenderOptions;
 use super::vertices::ModelVertex;
-use super::{Buffer, ShadowQuality, Texture, TextureSet, TileVertex, WaterVertex};
+use super::{Buffer, LightMapMode, ShadowQuality, Texture, TextureSet, TileVertex, ViewRangeDimming, WaterQuality, WaterVertex};
 use crate::graphics::{CornerDiameter, ScreenClip, ScreenPosition, ScreenSize, ShadowPadding};
 #[cfg(feature = "debug")]
 use crate::world::MarkerIdentifier;
@@ -33,15 +33,19 @@ pub struct RenderInstruction<'a> {
     pub model_batches: &'a [ModelBatch],
     pub models: &'a mut [ModelInstruction],
     pub entities: &'a mut [EntityInstruction],
+    pub shadow_blobs: &'a [ShadowBlobInstruction],
+    pub decals: &'a [DecalInstruction],
     pub directional_shadow_model_batches: &'a [Vec<ModelBatch>],
     pub directional_shadow_models: &'a [ModelInstruction],
     pub directional_shadow_entities: &'a [Vec<EntityInstruction>],
     pub point_shadow_models: &'a [ModelInstruction],
     pub point_shadow_entities: &'a [EntityInstruction],
     pub effects: &'a [EffectInstruction],
+    pub sky: Option<SkyInstruction<'a>>,
     pub water: Option<WaterInstruction<'a>>,
     pub map_picker_tile_vertex_buffer: Option<&'a Buffer<TileVertex>>,
     pub map_picker_tile_index_buffer: Option<&'a Buffer<u32>>,
+    pub map_light_map_texture: Option<&'a Arc<Texture>>,
     pub font_map_texture: Option<&'a Texture>,
     #[cfg(feature = "debug")]
     pub render_options: RenderOptions,
@@ -64,6 +68,17 @@ pub struct Uniforms {
     pub ambient_light_color: Color,
     pub enhanced_lighting: bool,
     pub shadow_quality: ShadowQuality,
+    pub water_quality: WaterQuality,
+    pub light_map_mode: LightMapMode,
+    /// Center of the area that the server sends entities for. `None` if
+    /// there is no player.
+    pub view_range_center: Option<Point3<f32>>,
+    /// Distance from the center to the edge of the view range in world units.
+    pub view_range_radius: f32,
+    pub view_range_dimming: ViewRangeDimming,
+    /// Horizontal wind direction in `x` and `y`, the wind strength in `z` and
+    /// the current gust factor in `w`.
+    pub wind: Vector4<f32>,
 }
 
 impl Default for Uniforms {
@@ -76,15 +91,42 @@ impl Default for Uniforms {
             ambient_light_color: Color::default(),
             enhanced_lighting: false,
             shadow_quality: ShadowQuality::Hard,
+            water_quality: WaterQuality::Low,
+            light_map_mode: LightMapMode::Off,
+            view_range_center: None,
+            view_range_radius: 0.0,
+            view_range_dimming: ViewRangeDimming::Off,
+            wind: Vector4::zero(),
         }
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct SkyInstruction<'a> {
+    /// Color of the sky straight above the camera.
+    pub top_color: Color,
+    /// Color of the sky at and below the horizon.
+    pub horizon_color: Color,
+    pub show_stars: bool,
+    pub cloud_layers: &'a [CloudLayerInstruction],
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct CloudLayerInstruction {
+    pub color: Color,
+    /// Fraction of the layer that is covered by clouds.
+    pub coverage: f32,
+    pub scale: f32,
+    pub speed: f32,
+}
+
 #[derive(Clone, Debug)]
 pub struct WaterInstruction<'a> {
     pub water_texture: &'a Texture,
     pub water_vertex_buffer: &'a Buffer<WaterVertex>,
     pub water_index_buffer: &'a Buffer<u32>,
+    /// Height of the calm water surface.
+    pub water_level: f32,
     pub texture_repeat: f32,
     pub waveform_phase_shift: f32,
     pub waveform_amplitude: f32,
@@ -214,6 +256,8 @@ pub enum InterfaceRectangleInstruction {
         color: Color,
         corner_diameter: CornerDiameter,
         texture: Arc<Texture>,
+        texture_position: Vector2<f32>,
+        texture_size: Vector2<f32>,
         smooth: bool,
     },
     Sdf {
@@ -268,6 +312,8 @@ pub struct ModelInstruction {
     pub texture_index: i32,
     pub distance: f32,
     pub transparent: bool,
+    /// Opacity of models that hide the player. `1.0` means fully visible.
+    pub opacity: f32,
 }
 
 #[derive(Clone, Debug)]
@@ -281,6 +327,9 @@ pub struct EntityInstruction {
     pub depth_offset: f32,
     pub curvature: f32,
     pub color: Color,
+    /// Brightness boost of hovered and targeted entities. `0.0` means no
+    /// highlight.
+    pub highlight: f32,
     pub mirror: bool,
     pub entity_id: EntityId,
     pub add_to_picker: bool,
@@ -288,6 +337,28 @@ pub struct EntityInstruction {
     pub distance: f32,
 }
 
+/// Circular shadow on the ground below an entity.
+#[derive(Copy, Clone, Debug)]
+pub struct ShadowBlobInstruction {
+    pub position: Point3<f32>,
+    pub radius: f32,
+    pub opacity: f32,
+}
+
+/// Part of a decal that covers a single ground tile.
+#[derive(Clone, Debug)]
+pub struct DecalInstruction {
+    pub upper_left: Point3<f32>,
+    pub upper_right: Point3<f32>,
+    pub lower_left: Point3<f32>,
+    pub lower_right: Point3<f32>,
+    pub center: Point3<f32>,
+    pub size: Vector2<f32>,
+    pub rotation: Rad<f32>,
+    pub color: Color,
+    pub texture: Arc<Texture>,
+}
+
 #[derive(Clone, Debug)]
 pub struct EffectInstruction {
     pub top_left: ScreenPosition,
@@ -302,6 +373,8 @@ pub struct EffectInstruction {
     pub source_blend_factor: BlendFactor,
     pub destination_blend_factor: BlendFactor,
     pub texture: Arc<Texture>,
+    /// Depth of the effect origin, used to weight order independent blending.
+    pub depth: f32,
 }
 
 #[cfg(feature = "debug")]
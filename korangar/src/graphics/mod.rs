@@ -1,6 +1,7 @@
 mod buffer;
 mod capabilities;
 mod color;
+mod display;
 mod engine;
 #[cfg(feature = "debug")]
 mod error;
@@ -34,6 +35,7 @@ use wgpu::{
 pub use self::buffer::Buffer;
 pub use self::capabilities::*;
 pub use self::color::*;
+pub use self::display::{apply_display_mode, monitor_options, video_mode_options};
 pub use self::engine::{GraphicsEngine, GraphicsEngineDescriptor};
 #[cfg(feature = "debug")]
 pub use self::error::error_handler;
@@ -52,8 +54,12 @@ use crate::NUMBER_OF_POINT_LIGHTS_WITH_SHADOWS;
 use crate::graphics::sampler::{SamplerType, create_new_sampler};
 use crate::loaders::{ImageType, TextureLoader};
 
-/// The size of a tile in pixel of the tile based light culling.
-const LIGHT_TILE_SIZE: u32 = 16;
+/// The size of a cluster in pixel of the clustered light culling.
+const LIGHT_CLUSTER_TILE_SIZE: u32 = 64;
+/// The count of exponentially distributed depth slices of the clustered light
+/// culling. If you change this variable, you also need to replace the constants
+/// in the shaders of the same name.
+const LIGHT_CLUSTER_DEPTH_SLICES: u32 = 16;
 
 /// The count of shadow maps in which we partition the directional shadow.
 /// We can't make this an overridable constant in WGSL or runtime defined, since
@@ -66,6 +72,8 @@ pub const RENDER_TO_TEXTURE_FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrg
 pub const RENDER_TO_TEXTURE_DEPTH_FORMAT: TextureFormat = TextureFormat::Depth32Float;
 pub const INTERFACE_TEXTURE_FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
 pub const FXAA_COLOR_LUMA_TEXTURE_FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+pub const WBOIT_ACCUMULATION_TEXTURE_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+pub const WBOIT_REVEALAGE_TEXTURE_FORMAT: TextureFormat = TextureFormat::R8Unorm;
 
 /// Trait to prepare all GPU data of contexts, computer and renderer.
 pub(crate) trait Prepare {
@@ -95,7 +103,12 @@ pub(crate) struct GlobalUniforms {
     point_light_count: u32,
     enhanced_lighting: u32,
     shadow_quality: u32,
-    padding: [u32; 2],
+    light_map_mode: u32,
+    padding: u32,
+    /// Center of the view range on the ground plane, its radius, and the
+    /// strength of the dimming beyond it.
+    view_range: [f32; 4],
+    wind: [f32; 4],
 }
 
 #[derive(Copy, Clone, Default, Pod, Zeroable)]
@@ -185,8 +198,8 @@ pub(crate) struct DebugUniforms {
 
 #[derive(Copy, Clone, Pod, Zeroable)]
 #[repr(C)]
-pub(crate) struct TileLightIndices {
-    indices: [u32; 256],
+pub(crate) struct ClusterLightIndices {
+    indices: [u32; 64],
 }
 
 /// Holds all GPU resources that are shared by multiple passes.
@@ -196,6 +209,8 @@ pub(crate) struct GlobalContext {
     pub(crate) ssaa: Ssaa,
     pub(crate) screen_space_anti_aliasing: ScreenSpaceAntiAliasing,
     pub(crate) high_quality_interface: bool,
+    pub(crate) crt_filter: bool,
+    pub(crate) order_independent_effects: bool,
     pub(crate) solid_pixel_texture: Arc<Texture>,
     pub(crate) walk_indicator_texture: Arc<Texture>,
     pub(crate) forward_depth_texture: AttachmentTexture,
@@ -206,10 +221,14 @@ pub(crate) struct GlobalContext {
     pub(crate) forward_revealage_texture: AttachmentTexture,
     pub(crate) resolved_color_texture: Option<AttachmentTexture>,
     pub(crate) supersampled_color_texture: Option<AttachmentTexture>,
+    pub(crate) crt_texture: Option<AttachmentTexture>,
+    pub(crate) effect_wboit_textures: Option<EffectWboitTextures>,
     pub(crate) interface_buffer_texture: AttachmentTexture,
+    pub(crate) water_reflection_texture: AttachmentTexture,
+    pub(crate) water_reflection_depth_texture: AttachmentTexture,
     pub(crate) directional_shadow_map_texture: AttachmentTexture,
     pub(crate) point_shadow_map_textures: CubeArrayTexture,
-    pub(crate) tile_light_count_texture: StorageTexture,
+    pub(crate) cluster_light_count_texture: StorageTexture,
     pub(crate) global_uniforms_buffer: Buffer<GlobalUniforms>,
     pub(crate) directional_light_uniforms_buffer: Buffer<DirectionalLightUniforms>,
     pub(crate) directional_light_partitions_buffer: Buffer<DirectionalLightPartition>,
@@ -217,7 +236,7 @@ pub(crate) struct GlobalContext {
     #[cfg(feature = "debug")]
     pub(crate) debug_uniforms_buffer: Buffer<DebugUniforms>,
     pub(crate) picker_value_buffer: Buffer<u64>,
-    pub(crate) tile_light_indices_buffer: Buffer<TileLightIndices>,
+    pub(crate) cluster_light_indices_buffer: Buffer<ClusterLightIndices>,
     pub(crate) partition_data_buffer: Buffer<Partition>,
     pub(crate) partition_value_buffer: Buffer<Partition>,
     pub(crate) interval_data_buffer: Buffer<Interval>,
@@ -227,10 +246,12 @@ pub(crate) struct GlobalContext {
     pub(crate) linear_sampler: Sampler,
     pub(crate) texture_sampler: Sampler,
     pub(crate) shadow_map_sampler: Sampler,
+    pub(crate) sprite_sampler: Sampler,
     pub(crate) global_bind_group: BindGroup,
     pub(crate) light_culling_bind_group: BindGroup,
     pub(crate) forward_bind_group: BindGroup,
     pub(crate) sdsm_bind_group: BindGroup,
+    pub(crate) water_bind_group: BindGroup,
     #[cfg(feature = "debug")]
     pub(crate) debug_bind_group: BindGroup,
     pub(crate) screen_size: ScreenSize,
@@ -284,6 +305,15 @@ impl Prepare for GlobalContext {
 
         let view_projection = instructions.uniforms.projection_matrix * instructions.uniforms.view_matrix;
 
+        let view_range = instructions.uniforms.view_range_center.map_or([0.0; 4], |center| {
+            [
+                center.x,
+                center.z,
+                instructions.uniforms.view_range_radius,
+                instructions.uniforms.view_range_dimming.strength(),
+            ]
+        });
+
         self.global_uniforms = GlobalUniforms {
             view_projection: view_projection.into(),
             view: instructions.uniforms.view_matrix.into(),
@@ -306,7 +336,10 @@ impl Prepare for GlobalContext {
             point_light_count: (instructions.point_light_with_shadows.len() + instructions.point_light.len()) as u32,
             enhanced_lighting: instructions.uniforms.enhanced_lighting as u32,
             shadow_quality: instructions.uniforms.shadow_quality.into(),
+            light_map_mode: instructions.uniforms.light_map_mode.into(),
             padding: Default::default(),
+            view_range,
+            wind: instructions.uniforms.wind.into(),
         };
 
         self.directional_light_uniforms = DirectionalLightUniforms {
@@ -400,21 +433,22 @@ impl Prepare for GlobalContext {
                 &self.linear_sampler,
                 &self.texture_sampler,
                 &self.shadow_map_sampler,
+                &self.sprite_sampler,
             );
 
             self.light_culling_bind_group = Self::create_light_culling_bind_group(
                 device,
                 &self.point_light_data_buffer,
-                &self.tile_light_count_texture,
-                &self.tile_light_indices_buffer,
+                &self.cluster_light_count_texture,
+                &self.cluster_light_indices_buffer,
             );
 
             self.forward_bind_group = Self::create_forward_bind_group(
                 device,
                 &self.directional_light_uniforms_buffer,
                 &self.point_light_data_buffer,
-                &self.tile_light_count_texture,
-                &self.tile_light_indices_buffer,
+                &self.cluster_light_count_texture,
+                &self.cluster_light_indices_buffer,
                 &self.directional_shadow_map_texture,
                 &self.point_shadow_map_textures,
                 &self.directional_light_partitions_buffer,
@@ -430,6 +464,15 @@ impl Prepare for GlobalContext {
                 &self.bounds_data_buffer,
             );
 
+            self.water_bind_group = Self::create_water_bind_group(
+                device,
+                self.msaa,
+                &self.directional_light_uniforms_buffer,
+                self.get_forward_texture(),
+                &self.forward_depth_texture,
+                &self.water_reflection_texture,
+            );
+
             #[cfg(feature = "debug")]
             {
                 self.debug_bind_group = Self::create_debug_bind_group(
@@ -438,7 +481,7 @@ impl Prepare for GlobalContext {
                     &self.debug_uniforms_buffer,
                     &self.picker_buffer_texture,
                     &self.directional_shadow_map_texture,
-                    &self.tile_light_count_texture,
+                    &self.cluster_light_count_texture,
                     &self.point_shadow_map_textures,
                     &self.forward_depth_texture,
                     &self.partition_data_buffer,
@@ -461,7 +504,10 @@ impl GlobalContext {
         screen_size: ScreenSize,
         shadow_detail: ShadowDetail,
         texture_sampler: TextureSamplerType,
+        sprite_filtering: SpriteFiltering,
         high_quality_interface: bool,
+        crt_filter: bool,
+        order_independent_effects: bool,
     ) -> Self {
         let forward_size = ssaa.calculate_size(screen_size);
         let interface_size = if high_quality_interface { screen_size * 2.0 } else { screen_size };
@@ -491,7 +537,10 @@ impl GlobalContext {
         let point_shadow_map_textures = Self::create_point_shadow_textures(device, point_shadow_size);
         let resolved_color_texture = Self::create_resolved_color_texture(device, forward_size, msaa);
         let supersampled_color_texture = Self::create_supersampled_texture(device, screen_size, ssaa);
+        let crt_texture = Self::create_crt_texture(device, screen_size, crt_filter);
+        let effect_wboit_textures = Self::create_effect_wboit_textures(device, screen_size, order_independent_effects);
         let interface_buffer_texture = Self::create_interface_texture(device, interface_size);
+        let water_reflection_textures = Self::create_water_reflection_textures(device, forward_size);
 
         let picker_value_buffer = Buffer::with_capacity(
             device,
@@ -536,7 +585,7 @@ impl GlobalContext {
             (128 * size_of::<PointLightData>()) as _,
         );
 
-        let tile_light_indices_buffer = Self::create_tile_light_indices_buffer(device, forward_size);
+        let cluster_light_indices_buffer = Self::create_cluster_light_indices_buffer(device, forward_size);
 
         let partition_data_buffer = Buffer::with_capacity(
             device,
@@ -570,6 +619,7 @@ impl GlobalContext {
         let linear_sampler = create_new_sampler(device, capabilities, "linear", SamplerType::TextureLinear);
         let texture_sampler = create_new_sampler(device, capabilities, "texture", texture_sampler);
         let shadow_map_sampler = create_new_sampler(device, capabilities, "shadow map", SamplerType::DepthCompare);
+        let sprite_sampler = create_new_sampler(device, capabilities, "sprite", sprite_filtering);
 
         let anti_aliasing_resources = Self::create_anti_aliasing_resources(device, screen_space_anti_aliasing, screen_size);
 
@@ -580,21 +630,22 @@ impl GlobalContext {
             &linear_sampler,
             &texture_sampler,
             &shadow_map_sampler,
+            &sprite_sampler,
         );
 
         let light_culling_bind_group = Self::create_light_culling_bind_group(
             device,
             &point_light_data_buffer,
-            &forward_textures.tile_light_count_texture,
-            &tile_light_indices_buffer,
+            &forward_textures.cluster_light_count_texture,
+            &cluster_light_indices_buffer,
         );
 
         let forward_bind_group = Self::create_forward_bind_group(
             device,
             &directional_light_uniforms_buffer,
             &point_light_data_buffer,
-            &forward_textures.tile_light_count_texture,
-            &tile_light_indices_buffer,
+            &forward_textures.cluster_light_count_texture,
+            &cluster_light_indices_buffer,
             &directional_shadow_map_texture,
             &point_shadow_map_textures,
             &directional_light_partitions_buffer,
@@ -610,6 +661,15 @@ impl GlobalContext {
             &bounds_data_buffer,
         );
 
+        let water_bind_group = Self::create_water_bind_group(
+            device,
+            msaa,
+            &directional_light_uniforms_buffer,
+            resolved_color_texture.as_ref().unwrap_or(&forward_textures.forward_color_texture),
+            &forward_textures.forward_depth_texture,
+            &water_reflection_textures.water_reflection_texture,
+        );
+
         #[cfg(feature = "debug")]
         let debug_bind_group = Self::create_debug_bind_group(
             device,
@@ -617,7 +677,7 @@ impl GlobalContext {
             &debug_uniforms_buffer,
             &picker_textures.picker_buffer_texture,
             &directional_shadow_map_texture,
-            &forward_textures.tile_light_count_texture,
+            &forward_textures.cluster_light_count_texture,
             &point_shadow_map_textures,
             &forward_textures.forward_depth_texture,
             &partition_data_buffer,
@@ -629,6 +689,8 @@ impl GlobalContext {
             ssaa,
             screen_space_anti_aliasing,
             high_quality_interface,
+            crt_filter,
+            order_independent_effects,
             solid_pixel_texture,
             walk_indicator_texture,
             forward_depth_texture: forward_textures.forward_depth_texture,
@@ -639,17 +701,22 @@ impl GlobalContext {
             forward_revealage_texture: forward_textures.forward_revealage_texture,
             resolved_color_texture,
             supersampled_color_texture,
+            crt_texture,
+            effect_wboit_textures,
             interface_buffer_texture,
+            water_reflection_texture: water_reflection_textures.water_reflection_texture,
+            water_reflection_depth_texture: water_reflection_textures.water_reflection_depth_texture,
             directional_shadow_map_texture,
             point_shadow_map_textures,
-            tile_light_count_texture: forward_textures.tile_light_count_texture,
+            cluster_light_count_texture: forward_textures.cluster_light_count_texture,
             global_uniforms_buffer,
             forward_bind_group,
             sdsm_bind_group,
+            water_bind_group,
             #[cfg(feature = "debug")]
             debug_bind_group,
             directional_light_uniforms_buffer,
-            tile_light_indices_buffer,
+            cluster_light_indices_buffer,
             partition_data_buffer,
             partition_value_buffer,
             bounds_data_buffer,
@@ -663,6 +730,7 @@ impl GlobalContext {
             linear_sampler,
             texture_sampler,
             shadow_map_sampler,
+            sprite_sampler,
             global_bind_group,
             light_culling_bind_group,
             screen_size,
@@ -700,24 +768,32 @@ impl GlobalContext {
         let forward_depth_texture = factory.new_attachment("forward depth", RENDER_TO_TEXTURE_DEPTH_FORMAT, AttachmentTextureType::Depth);
         let forward_accumulation_texture = factory.new_attachment(
             "forward accumulation",
-            TextureFormat::Rgba16Float,
+            WBOIT_ACCUMULATION_TEXTURE_FORMAT,
             AttachmentTextureType::ColorAttachment,
         );
         let forward_revealage_texture = factory.new_attachment(
             "forward revealage",
-            TextureFormat::R8Unorm,
+            WBOIT_REVEALAGE_TEXTURE_FORMAT,
             AttachmentTextureType::ColorAttachment,
         );
 
-        let (tile_x, tile_y) = calculate_light_tile_count(forward_size);
-        let tile_light_count_texture = StorageTexture::new(device, "tile light count texture", tile_x, tile_y, TextureFormat::R32Uint);
+        // The depth slices of the clusters are stacked vertically, so that we can
+        // store the light count of all clusters in a single 2D texture.
+        let (cluster_x, cluster_y, cluster_z) = calculate_light_cluster_count(forward_size);
+        let cluster_light_count_texture = StorageTexture::new(
+            device,
+            "cluster light count texture",
+            cluster_x,
+            cluster_y * cluster_z,
+            TextureFormat::R32Uint,
+        );
 
         ForwardTextures {
             forward_depth_texture,
             forward_color_texture,
             forward_accumulation_texture,
             forward_revealage_texture,
-            tile_light_count_texture,
+            cluster_light_count_texture,
         }
     }
 
@@ -772,6 +848,43 @@ impl GlobalContext {
         }
     }
 
+    fn create_crt_texture(device: &Device, screen_size: ScreenSize, crt_filter: bool) -> Option<AttachmentTexture> {
+        match crt_filter {
+            true => {
+                let attachment_factory = AttachmentTextureFactory::new(device, screen_size, 1, None);
+                Some(attachment_factory.new_attachment("crt source", RENDER_TO_TEXTURE_FORMAT, AttachmentTextureType::ColorAttachment))
+            }
+            false => None,
+        }
+    }
+
+    fn create_effect_wboit_textures(
+        device: &Device,
+        screen_size: ScreenSize,
+        order_independent_effects: bool,
+    ) -> Option<EffectWboitTextures> {
+        match order_independent_effects {
+            true => {
+                let attachment_factory = AttachmentTextureFactory::new(device, screen_size, 1, None);
+                let accumulation_texture = attachment_factory.new_attachment(
+                    "effect accumulation",
+                    WBOIT_ACCUMULATION_TEXTURE_FORMAT,
+                    AttachmentTextureType::ColorAttachment,
+                );
+                let revealage_texture = attachment_factory.new_attachment(
+                    "effect revealage",
+                    WBOIT_REVEALAGE_TEXTURE_FORMAT,
+                    AttachmentTextureType::ColorAttachment,
+                );
+                Some(EffectWboitTextures {
+                    accumulation_texture,
+                    revealage_texture,
+                })
+            }
+            false => None,
+        }
+    }
+
     fn create_interface_texture(device: &Device, interface_size: ScreenSize) -> AttachmentTexture {
         let interface_screen_factory = AttachmentTextureFactory::new(device, interface_size, 1, None);
 
@@ -782,6 +895,27 @@ impl GlobalContext {
         )
     }
 
+    fn create_water_reflection_textures(device: &Device, forward_size: ScreenSize) -> WaterReflectionTextures {
+        // The reflection is distorted by the waves anyway, so half the resolution
+        // is plenty.
+        let factory = AttachmentTextureFactory::new(device, forward_size * 0.5, 1, None);
+        let water_reflection_texture = factory.new_attachment(
+            "water reflection",
+            RENDER_TO_TEXTURE_FORMAT,
+            AttachmentTextureType::ColorAttachment,
+        );
+        let water_reflection_depth_texture = factory.new_attachment(
+            "water reflection depth",
+            RENDER_TO_TEXTURE_DEPTH_FORMAT,
+            AttachmentTextureType::Depth,
+        );
+
+        WaterReflectionTextures {
+            water_reflection_texture,
+            water_reflection_depth_texture,
+        }
+    }
+
     fn create_directional_shadow_textures(device: &Device, shadow_size: ScreenSize) -> AttachmentTexture {
         let shadow_factory = AttachmentTextureFactory::new(device, shadow_size, 1, None);
 
@@ -793,14 +927,14 @@ impl GlobalContext {
         )
     }
 
-    fn create_tile_light_indices_buffer(device: &Device, forward_size: ScreenSize) -> Buffer<TileLightIndices> {
-        let (tile_count_x, tile_count_y) = calculate_light_tile_count(forward_size);
+    fn create_cluster_light_indices_buffer(device: &Device, forward_size: ScreenSize) -> Buffer<ClusterLightIndices> {
+        let (cluster_count_x, cluster_count_y, cluster_count_z) = calculate_light_cluster_count(forward_size);
 
         Buffer::with_capacity(
             device,
-            "tile light indices",
+            "cluster light indices",
             BufferUsages::STORAGE,
-            ((tile_count_x * tile_count_y).max(1) as usize * size_of::<TileLightIndices>()) as _,
+            ((cluster_count_x * cluster_count_y * cluster_count_z).max(1) as usize * size_of::<ClusterLightIndices>()) as _,
         )
     }
 
@@ -849,7 +983,7 @@ impl GlobalContext {
             forward_depth_texture,
             forward_accumulation_texture,
             forward_revealage_texture,
-            tile_light_count_texture,
+            cluster_light_count_texture,
         } = Self::create_forward_textures(device, self.forward_size, self.msaa);
 
         let PickerTextures {
@@ -869,29 +1003,39 @@ impl GlobalContext {
         self.picker_depth_texture = picker_depth_texture;
         self.resolved_color_texture = resolved_color_texture;
         self.supersampled_color_texture = supersampled_color_texture;
-        self.tile_light_count_texture = tile_light_count_texture;
+        self.cluster_light_count_texture = cluster_light_count_texture;
 
         self.interface_buffer_texture = Self::create_interface_texture(device, self.interface_size);
 
-        self.tile_light_indices_buffer = Self::create_tile_light_indices_buffer(device, self.forward_size);
+        let WaterReflectionTextures {
+            water_reflection_texture,
+            water_reflection_depth_texture,
+        } = Self::create_water_reflection_textures(device, self.forward_size);
+
+        self.water_reflection_texture = water_reflection_texture;
+        self.water_reflection_depth_texture = water_reflection_depth_texture;
+
+        self.cluster_light_indices_buffer = Self::create_cluster_light_indices_buffer(device, self.forward_size);
 
         self.anti_aliasing_resources = Self::create_anti_aliasing_resources(device, self.screen_space_anti_aliasing, self.screen_size);
+        self.crt_texture = Self::create_crt_texture(device, self.screen_size, self.crt_filter);
+        self.effect_wboit_textures = Self::create_effect_wboit_textures(device, self.screen_size, self.order_independent_effects);
 
         // We need to update this bind group, because it's content changed, and it isn't
         // re-created each frame.
         self.light_culling_bind_group = Self::create_light_culling_bind_group(
             device,
             &self.point_light_data_buffer,
-            &self.tile_light_count_texture,
-            &self.tile_light_indices_buffer,
+            &self.cluster_light_count_texture,
+            &self.cluster_light_indices_buffer,
         );
 
         self.forward_bind_group = Self::create_forward_bind_group(
             device,
             &self.directional_light_uniforms_buffer,
             &self.point_light_data_buffer,
-            &self.tile_light_count_texture,
-            &self.tile_light_indices_buffer,
+            &self.cluster_light_count_texture,
+            &self.cluster_light_indices_buffer,
             &self.directional_shadow_map_texture,
             &self.point_shadow_map_textures,
             &self.directional_light_partitions_buffer,
@@ -907,6 +1051,15 @@ impl GlobalContext {
             &self.bounds_data_buffer,
         );
 
+        self.water_bind_group = Self::create_water_bind_group(
+            device,
+            self.msaa,
+            &self.directional_light_uniforms_buffer,
+            self.get_forward_texture(),
+            &self.forward_depth_texture,
+            &self.water_reflection_texture,
+        );
+
         #[cfg(feature = "debug")]
         {
             self.debug_bind_group = Self::create_debug_bind_group(
@@ -915,7 +1068,7 @@ impl GlobalContext {
                 &self.debug_uniforms_buffer,
                 &self.picker_buffer_texture,
                 &self.directional_shadow_map_texture,
-                &self.tile_light_count_texture,
+                &self.cluster_light_count_texture,
                 &self.point_shadow_map_textures,
                 &self.forward_depth_texture,
                 &self.partition_data_buffer,
@@ -936,8 +1089,8 @@ impl GlobalContext {
             device,
             &self.directional_light_uniforms_buffer,
             &self.point_light_data_buffer,
-            &self.tile_light_count_texture,
-            &self.tile_light_indices_buffer,
+            &self.cluster_light_count_texture,
+            &self.cluster_light_indices_buffer,
             &self.directional_shadow_map_texture,
             &self.point_shadow_map_textures,
             &self.directional_light_partitions_buffer,
@@ -951,7 +1104,7 @@ impl GlobalContext {
                 &self.debug_uniforms_buffer,
                 &self.picker_buffer_texture,
                 &self.directional_shadow_map_texture,
-                &self.tile_light_count_texture,
+                &self.cluster_light_count_texture,
                 &self.point_shadow_map_textures,
                 &self.forward_depth_texture,
                 &self.partition_data_buffer,
@@ -968,6 +1121,20 @@ impl GlobalContext {
             &self.linear_sampler,
             &self.texture_sampler,
             &self.shadow_map_sampler,
+            &self.sprite_sampler,
+        );
+    }
+
+    fn update_sprite_sampler(&mut self, device: &Device, capabilities: &Capabilities, sprite_filtering: SpriteFiltering) {
+        self.sprite_sampler = create_new_sampler(device, capabilities, "sprite", sprite_filtering);
+        self.global_bind_group = Self::create_global_bind_group(
+            device,
+            &self.global_uniforms_buffer,
+            &self.nearest_sampler,
+            &self.linear_sampler,
+            &self.texture_sampler,
+            &self.shadow_map_sampler,
+            &self.sprite_sampler,
         );
     }
 
@@ -979,14 +1146,14 @@ impl GlobalContext {
             forward_depth_texture,
             forward_accumulation_texture,
             forward_revealage_texture,
-            tile_light_count_texture,
+            cluster_light_count_texture,
         } = Self::create_forward_textures(device, self.screen_size, self.msaa);
 
         self.forward_color_texture = forward_color_texture;
         self.forward_depth_texture = forward_depth_texture;
         self.forward_accumulation_texture = forward_accumulation_texture;
         self.forward_revealage_texture = forward_revealage_texture;
-        self.tile_light_count_texture = tile_light_count_texture;
+        self.cluster_light_count_texture = cluster_light_count_texture;
         self.resolved_color_texture = Self::create_resolved_color_texture(device, self.forward_size, self.msaa);
 
         self.sdsm_bind_group = Self::create_sdsm_bind_group(
@@ -999,6 +1166,15 @@ impl GlobalContext {
             &self.bounds_data_buffer,
         );
 
+        self.water_bind_group = Self::create_water_bind_group(
+            device,
+            self.msaa,
+            &self.directional_light_uniforms_buffer,
+            self.get_forward_texture(),
+            &self.forward_depth_texture,
+            &self.water_reflection_texture,
+        );
+
         #[cfg(feature = "debug")]
         {
             self.debug_bind_group = Self::create_debug_bind_group(
@@ -1007,7 +1183,7 @@ impl GlobalContext {
                 &self.debug_uniforms_buffer,
                 &self.picker_buffer_texture,
                 &self.directional_shadow_map_texture,
-                &self.tile_light_count_texture,
+                &self.cluster_light_count_texture,
                 &self.point_shadow_map_textures,
                 &self.forward_depth_texture,
                 &self.partition_data_buffer,
@@ -1026,6 +1202,16 @@ impl GlobalContext {
         self.anti_aliasing_resources = Self::create_anti_aliasing_resources(device, self.screen_space_anti_aliasing, self.screen_size);
     }
 
+    fn update_crt_filter(&mut self, device: &Device, crt_filter: bool) {
+        self.crt_filter = crt_filter;
+        self.crt_texture = Self::create_crt_texture(device, self.screen_size, self.crt_filter);
+    }
+
+    fn update_order_independent_effects(&mut self, device: &Device, order_independent_effects: bool) {
+        self.order_independent_effects = order_independent_effects;
+        self.effect_wboit_textures = Self::create_effect_wboit_textures(device, self.screen_size, self.order_independent_effects);
+    }
+
     fn update_high_quality_interface(&mut self, device: &Device, high_quality_interface: bool) {
         self.high_quality_interface = high_quality_interface;
         self.interface_size = if self.high_quality_interface {
@@ -1077,6 +1263,12 @@ impl GlobalContext {
                         ty: BindingType::Sampler(SamplerBindingType::Comparison),
                         count: None,
                     },
+                    BindGroupLayoutEntry {
+                        binding: 5,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                        count: None,
+                    },
                 ],
             })
         })
@@ -1114,7 +1306,7 @@ impl GlobalContext {
                         ty: BindingType::Buffer {
                             ty: BufferBindingType::Storage { read_only: false },
                             has_dynamic_offset: false,
-                            min_binding_size: NonZeroU64::new(size_of::<TileLightIndices>() as _),
+                            min_binding_size: NonZeroU64::new(size_of::<ClusterLightIndices>() as _),
                         },
                         count: None,
                     },
@@ -1175,7 +1367,7 @@ impl GlobalContext {
                         ty: BindingType::Buffer {
                             ty: BufferBindingType::Storage { read_only: true },
                             has_dynamic_offset: false,
-                            min_binding_size: NonZeroU64::new(size_of::<TileLightIndices>() as _),
+                            min_binding_size: NonZeroU64::new(size_of::<ClusterLightIndices>() as _),
                         },
                         count: None,
                     },
@@ -1273,6 +1465,65 @@ impl GlobalContext {
         })
     }
 
+    fn water_bind_group_layout(device: &Device, msaa: Msaa) -> &'static BindGroupLayout {
+        static LAYOUT_NO_MSAA: OnceLock<BindGroupLayout> = OnceLock::new();
+        static LAYOUT_WITH_MSAA: OnceLock<BindGroupLayout> = OnceLock::new();
+
+        let layout_lock = if msaa.multisampling_activated() {
+            &LAYOUT_WITH_MSAA
+        } else {
+            &LAYOUT_NO_MSAA
+        };
+
+        layout_lock.get_or_init(|| {
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some("water"),
+                entries: &[
+                    BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: ShaderStages::VERTEX_FRAGMENT,
+                        ty: BindingType::Buffer {
+                            ty: BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: NonZeroU64::new(size_of::<DirectionalLightUniforms>() as _),
+                        },
+                        count: None,
+                    },
+                    BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Texture {
+                            sample_type: TextureSampleType::Float { filterable: true },
+                            view_dimension: TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Texture {
+                            sample_type: TextureSampleType::Depth,
+                            view_dimension: TextureViewDimension::D2,
+                            multisampled: msaa.multisampling_activated(),
+                        },
+                        count: None,
+                    },
+                    BindGroupLayoutEntry {
+                        binding: 3,
+                        visibility: ShaderStages::FRAGMENT,
+                        ty: BindingType::Texture {
+                            sample_type: TextureSampleType::Float { filterable: true },
+                            view_dimension: TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                ],
+            })
+        })
+    }
+
     #[cfg(feature = "debug")]
     fn debug_bind_group_layout(device: &Device, msaa: Msaa) -> &'static BindGroupLayout {
         static LAYOUT_NO_MSAA: OnceLock<BindGroupLayout> = OnceLock::new();
@@ -1370,6 +1621,7 @@ impl GlobalContext {
         linear_sampler: &Sampler,
         texture_sampler: &Sampler,
         shadow_sampler: &Sampler,
+        sprite_sampler: &Sampler,
     ) -> BindGroup {
         device.create_bind_group(&BindGroupDescriptor {
             label: Some("global"),
@@ -1395,6 +1647,10 @@ impl GlobalContext {
                     binding: 4,
                     resource: BindingResource::Sampler(shadow_sampler),
                 },
+                BindGroupEntry {
+                    binding: 5,
+                    resource: BindingResource::Sampler(sprite_sampler),
+                },
             ],
         })
     }
@@ -1402,8 +1658,8 @@ impl GlobalContext {
     fn create_light_culling_bind_group(
         device: &Device,
         point_light_data_buffer: &Buffer<PointLightData>,
-        tile_light_count_texture: &StorageTexture,
-        tile_light_indices_buffer: &Buffer<TileLightIndices>,
+        cluster_light_count_texture: &StorageTexture,
+        cluster_light_indices_buffer: &Buffer<ClusterLightIndices>,
     ) -> BindGroup {
         device.create_bind_group(&BindGroupDescriptor {
             label: Some("light culling"),
@@ -1415,11 +1671,11 @@ impl GlobalContext {
                 },
                 BindGroupEntry {
                     binding: 1,
-                    resource: BindingResource::TextureView(tile_light_count_texture.get_texture_view()),
+                    resource: BindingResource::TextureView(cluster_light_count_texture.get_texture_view()),
                 },
                 BindGroupEntry {
                     binding: 2,
-                    resource: tile_light_indices_buffer.as_entire_binding(),
+                    resource: cluster_light_indices_buffer.as_entire_binding(),
                 },
             ],
         })
@@ -1429,8 +1685,8 @@ impl GlobalContext {
         device: &Device,
         directional_light_uniforms_buffer: &Buffer<DirectionalLightUniforms>,
         point_light_data_buffer: &Buffer<PointLightData>,
-        tile_light_count_texture: &StorageTexture,
-        tile_light_indices_buffer: &Buffer<TileLightIndices>,
+        cluster_light_count_texture: &StorageTexture,
+        cluster_light_indices_buffer: &Buffer<ClusterLightIndices>,
         directional_shadow_map_texture: &AttachmentTexture,
         point_shadow_maps_texture: &CubeArrayTexture,
         directional_light_partition: &Buffer<DirectionalLightPartition>,
@@ -1453,11 +1709,11 @@ impl GlobalContext {
                 },
                 BindGroupEntry {
                     binding: 3,
-                    resource: BindingResource::TextureView(tile_light_count_texture.get_texture_view()),
+                    resource: BindingResource::TextureView(cluster_light_count_texture.get_texture_view()),
                 },
                 BindGroupEntry {
                     binding: 4,
-                    resource: tile_light_indices_buffer.as_entire_binding(),
+                    resource: cluster_light_indices_buffer.as_entire_binding(),
                 },
                 BindGroupEntry {
                     binding: 5,
@@ -1508,6 +1764,38 @@ impl GlobalContext {
         })
     }
 
+    fn create_water_bind_group(
+        device: &Device,
+        msaa: Msaa,
+        directional_light_uniforms_buffer: &Buffer<DirectionalLightUniforms>,
+        refraction_texture: &AttachmentTexture,
+        forward_depth_texture: &AttachmentTexture,
+        water_reflection_texture: &AttachmentTexture,
+    ) -> BindGroup {
+        device.create_bind_group(&BindGroupDescriptor {
+            label: Some("water"),
+            layout: Self::water_bind_group_layout(device, msaa),
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: directional_light_uniforms_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(refraction_texture.get_texture_view()),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::TextureView(forward_depth_texture.get_texture_view()),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: BindingResource::TextureView(water_reflection_texture.get_texture_view()),
+                },
+            ],
+        })
+    }
+
     #[cfg(feature = "debug")]
     fn create_debug_bind_group(
         device: &Device,
@@ -1515,7 +1803,7 @@ impl GlobalContext {
         debug_uniforms_buffer: &Buffer<DebugUniforms>,
         picker_buffer_texture: &AttachmentTexture,
         directional_shadow_map_texture: &AttachmentTexture,
-        tile_light_count_texture: &StorageTexture,
+        cluster_light_count_texture: &StorageTexture,
         point_shadow_maps_texture: &CubeArrayTexture,
         forward_depth_texture: &AttachmentTexture,
         partition_data_buffer: &Buffer<Partition>,
@@ -1538,7 +1826,7 @@ impl GlobalContext {
                 },
                 BindGroupEntry {
                     binding: 3,
-                    resource: BindingResource::TextureView(tile_light_count_texture.get_texture_view()),
+                    resource: BindingResource::TextureView(cluster_light_count_texture.get_texture_view()),
                 },
                 BindGroupEntry {
                     binding: 4,
@@ -1557,10 +1845,10 @@ impl GlobalContext {
     }
 }
 
-fn calculate_light_tile_count(forward_size: ScreenSize) -> (u32, u32) {
-    let tile_count_x = (forward_size.width as u32).div_ceil(LIGHT_TILE_SIZE);
-    let tile_count_y = (forward_size.height as u32).div_ceil(LIGHT_TILE_SIZE);
-    (tile_count_x, tile_count_y)
+fn calculate_light_cluster_count(forward_size: ScreenSize) -> (u32, u32, u32) {
+    let cluster_count_x = (forward_size.width as u32).div_ceil(LIGHT_CLUSTER_TILE_SIZE);
+    let cluster_count_y = (forward_size.height as u32).div_ceil(LIGHT_CLUSTER_TILE_SIZE);
+    (cluster_count_x, cluster_count_y, LIGHT_CLUSTER_DEPTH_SLICES)
 }
 
 struct PickerTextures {
@@ -1568,12 +1856,24 @@ struct PickerTextures {
     picker_depth_texture: AttachmentTexture,
 }
 
+struct WaterReflectionTextures {
+    water_reflection_texture: AttachmentTexture,
+    water_reflection_depth_texture: AttachmentTexture,
+}
+
 struct ForwardTextures {
     forward_color_texture: AttachmentTexture,
     forward_depth_texture: AttachmentTexture,
     forward_accumulation_texture: AttachmentTexture,
     forward_revealage_texture: AttachmentTexture,
-    tile_light_count_texture: StorageTexture,
+    cluster_light_count_texture: StorageTexture,
+}
+
+/// The targets of the alpha blended effects, which are blended independent of
+/// their draw order and resolved on top of the scene.
+pub(crate) struct EffectWboitTextures {
+    pub(crate) accumulation_texture: AttachmentTexture,
+    pub(crate) revealage_texture: AttachmentTexture,
 }
 
 pub(crate) enum AntiAliasingResources {
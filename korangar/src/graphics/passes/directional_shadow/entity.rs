@@ -7,10 +7,9 @@ use hashbrown::HashMap;
 use wgpu::util::StagingBelt;
 use wgpu::{
     BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource,
-    BindingType, BufferBindingType, BufferUsages, CommandEncoder, CompareFunction, DepthBiasState, DepthStencilState, Device,
-    FragmentState, MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass,
-    RenderPipeline, RenderPipelineDescriptor, ShaderStages, StencilState, TextureSampleType, TextureView, TextureViewDimension,
-    VertexState,
+    BindingType, BufferBindingType, BufferUsages, CommandEncoder, DepthBiasState, DepthStencilState, Device, FragmentState,
+    MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass, RenderPipeline,
+    RenderPipelineDescriptor, ShaderStages, StencilState, TextureSampleType, TextureView, TextureViewDimension, VertexState,
 };
 
 use crate::graphics::passes::{
@@ -18,7 +17,8 @@ use crate::graphics::passes::{
 };
 use crate::graphics::shader_compiler::ShaderCompiler;
 use crate::graphics::{
-    BindlessSupport, Buffer, Capabilities, EntityInstruction, GlobalContext, PARTITION_COUNT, Prepare, RenderInstruction, Texture,
+    BindlessSupport, Buffer, Capabilities, DEPTH_COMPARE_FUNCTION, EntityInstruction, GlobalContext, PARTITION_COUNT, Prepare,
+    RenderInstruction, Texture, create_render_pipeline,
 };
 
 const DRAWER_NAME: &str = "directional shadow entity";
@@ -151,7 +151,7 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::None }, { DepthAtta
             push_constant_ranges: &[],
         });
 
-        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+        let pipeline = create_render_pipeline(device, &RenderPipelineDescriptor {
             label: Some(DRAWER_NAME),
             layout: Some(&pipeline_layout),
             vertex: VertexState {
@@ -172,7 +172,7 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::None }, { DepthAtta
             depth_stencil: Some(DepthStencilState {
                 format: render_pass_context.depth_attachment_output_format()[0],
                 depth_write_enabled: true,
-                depth_compare: CompareFunction::Greater,
+                depth_compare: DEPTH_COMPARE_FUNCTION,
                 stencil: StencilState::default(),
                 bias: DepthBiasState::default(),
             }),
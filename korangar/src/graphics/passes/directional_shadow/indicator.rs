@@ -1,15 +1,15 @@
 use wgpu::{
     BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType,
-    CompareFunction, DepthBiasState, DepthStencilState, Device, FragmentState, MultisampleState, PipelineCompilationOptions,
-    PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass, RenderPipeline, RenderPipelineDescriptor, ShaderStages, StencilState,
-    TextureSampleType, TextureViewDimension, VertexState,
+    DepthBiasState, DepthStencilState, Device, FragmentState, MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor,
+    PrimitiveState, Queue, RenderPass, RenderPipeline, RenderPipelineDescriptor, ShaderStages, StencilState, TextureSampleType,
+    TextureViewDimension, VertexState,
 };
 
 use crate::graphics::passes::{
     BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, DirectionalShadowRenderPassContext, Drawer, RenderPassContext,
 };
 use crate::graphics::shader_compiler::ShaderCompiler;
-use crate::graphics::{Capabilities, GlobalContext, IndicatorInstruction};
+use crate::graphics::{Capabilities, DEPTH_COMPARE_FUNCTION, GlobalContext, IndicatorInstruction, create_render_pipeline};
 
 const DRAWER_NAME: &str = "directional shadow indicator";
 
@@ -65,7 +65,7 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::None }, { DepthAtta
             push_constant_ranges: &[],
         });
 
-        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+        let pipeline = create_render_pipeline(device, &RenderPipelineDescriptor {
             label: Some(DRAWER_NAME),
             layout: Some(&pipeline_layout),
             vertex: VertexState {
@@ -86,7 +86,7 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::None }, { DepthAtta
             depth_stencil: Some(DepthStencilState {
                 format: render_pass_context.depth_attachment_output_format()[0],
                 depth_write_enabled: true,
-                depth_compare: CompareFunction::Greater,
+                depth_compare: DEPTH_COMPARE_FUNCTION,
                 stencil: StencilState::default(),
                 bias: DepthBiasState::default(),
             }),
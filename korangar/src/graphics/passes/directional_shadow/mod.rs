@@ -18,7 +18,7 @@ use wgpu::{
 
 use super::{BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, RenderPassContext};
 use crate::graphics::buffer::DynamicUniformBuffer;
-use crate::graphics::{GlobalContext, Prepare, RenderInstruction};
+use crate::graphics::{DEPTH_CLEAR_VALUE, GlobalContext, Prepare, RenderInstruction};
 use crate::loaders::TextureLoader;
 
 const PASS_NAME: &str = "directional shadow render pass";
@@ -31,6 +31,7 @@ struct PassUniforms {
     inverse_view: [[f32; 4]; 4],
     animation_timer: f32,
     padding: [u32; 3],
+    wind: [f32; 4],
 }
 
 pub(crate) struct DirectionalShadowRenderPassContext {
@@ -72,7 +73,7 @@ impl RenderPassContext<{ BindGroupCount::Two }, { ColorAttachmentCount::None },
             depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
                 view: global_context.directional_shadow_map_texture.get_array_texture_view(pass_data),
                 depth_ops: Some(Operations {
-                    load: LoadOp::Clear(0.0),
+                    load: LoadOp::Clear(DEPTH_CLEAR_VALUE),
                     store: StoreOp::Store,
                 }),
                 stencil_ops: None,
@@ -130,6 +131,7 @@ impl Prepare for DirectionalShadowRenderPassContext {
             inverse_view: caster.view_matrix.invert().unwrap_or(Matrix4::identity()).into(),
             animation_timer: instructions.uniforms.animation_timer_ms / 1000.0,
             padding: Default::default(),
+            wind: instructions.uniforms.wind.into(),
         });
         self.uniforms_buffer.write_data(uniforms);
     }
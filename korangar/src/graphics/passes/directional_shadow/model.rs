@@ -4,17 +4,20 @@ use bytemuck::{Pod, Zeroable};
 use wgpu::util::StagingBelt;
 use wgpu::{
     BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType,
-    BufferAddress, BufferBindingType, BufferUsages, CommandEncoder, CompareFunction, DepthBiasState, DepthStencilState, Device,
-    FragmentState, IndexFormat, MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass,
-    RenderPipeline, RenderPipelineDescriptor, ShaderStages, StencilState, VertexState,
+    BufferAddress, BufferBindingType, BufferUsages, CommandEncoder, DepthBiasState, DepthStencilState, Device, FragmentState, IndexFormat,
+    MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass, RenderPipeline,
+    RenderPipelineDescriptor, ShaderStages, StencilState, VertexState,
 };
 
 use crate::graphics::passes::{
     BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, DirectionalShadowRenderPassContext, DrawIndexedIndirectArgs, Drawer,
-    ModelBatchDrawData, RenderPassContext,
+    ModelBatchDrawData, RenderPassContext, texture_group_runs,
 };
 use crate::graphics::shader_compiler::ShaderCompiler;
-use crate::graphics::{BindlessSupport, Buffer, Capabilities, GlobalContext, ModelVertex, Prepare, RenderInstruction, Texture, TextureSet};
+use crate::graphics::{
+    BindlessSupport, Buffer, Capabilities, DEPTH_COMPARE_FUNCTION, GlobalContext, ModelVertex, Prepare, RenderInstruction, Texture,
+    TextureSet, create_render_pipeline,
+};
 
 const DRAWER_NAME: &str = "directional shadow model";
 const INITIAL_INSTRUCTION_SIZE: usize = 256;
@@ -104,7 +107,7 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::None }, { DepthAtta
             push_constant_ranges: &[],
         });
 
-        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+        let pipeline = create_render_pipeline(device, &RenderPipelineDescriptor {
             label: Some(DRAWER_NAME),
             layout: Some(&pipeline_layout),
             vertex: VertexState {
@@ -125,7 +128,7 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::None }, { DepthAtta
             depth_stencil: Some(DepthStencilState {
                 format: render_pass_context.depth_attachment_output_format()[0],
                 depth_write_enabled: true,
-                depth_compare: CompareFunction::Greater,
+                depth_compare: DEPTH_COMPARE_FUNCTION,
                 stencil: StencilState::default(),
                 bias: DepthBiasState::default(),
             }),
@@ -160,30 +163,32 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::None }, { DepthAtta
                         continue;
                     }
 
-                    pass.set_bind_group(3, batch.texture_set.get_bind_group().unwrap(), &[]);
                     pass.set_index_buffer(batch.index_buffer.slice(..), IndexFormat::Uint32);
                     pass.set_vertex_buffer(0, batch.vertex_buffer.slice(..));
 
-                    if self.multi_draw_indirect_support {
-                        pass.multi_draw_indexed_indirect(
-                            self.command_buffer.get_buffer(),
-                            (batch.offset * size_of::<DrawIndexedIndirectArgs>()) as BufferAddress,
-                            batch.count as u32,
-                        );
-                    } else {
-                        let start = batch.offset;
-                        let end = start + batch.count;
-
-                        for (index, instruction) in draw_data.instructions[start..end].iter().enumerate() {
-                            let index_start = instruction.index_offset;
-                            let index_end = index_start + instruction.index_count;
-                            let instance_offset = (start + index) as u32;
-
-                            pass.draw_indexed(
-                                index_start..index_end,
-                                instruction.base_vertex,
-                                instance_offset..instance_offset + 1,
+                    let instructions = &draw_data.instructions[batch.offset..batch.offset + batch.count];
+
+                    for (texture_group, run) in texture_group_runs(instructions, batch.offset) {
+                        pass.set_bind_group(3, batch.texture_set.get_bind_group(texture_group), &[]);
+
+                        if self.multi_draw_indirect_support {
+                            pass.multi_draw_indexed_indirect(
+                                self.command_buffer.get_buffer(),
+                                (run.start * size_of::<DrawIndexedIndirectArgs>()) as BufferAddress,
+                                run.len() as u32,
                             );
+                        } else {
+                            for (index, instruction) in draw_data.instructions[run.clone()].iter().enumerate() {
+                                let index_start = instruction.index_offset;
+                                let index_end = index_start + instruction.index_count;
+                                let instance_offset = (run.start + index) as u32;
+
+                                pass.draw_indexed(
+                                    index_start..index_end,
+                                    instruction.base_vertex,
+                                    instance_offset..instance_offset + 1,
+                                );
+                            }
                         }
                     }
                 }
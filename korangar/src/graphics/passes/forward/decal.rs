@@ -0,0 +1,234 @@
+use std::num::NonZeroU64;
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::StagingBelt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType,
+    BlendState, BufferBindingType, BufferUsages, ColorTargetState, ColorWrites, CommandEncoder, DepthBiasState, DepthStencilState, Device,
+    FragmentState, MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass,
+    RenderPipeline, RenderPipelineDescriptor, ShaderStages, StencilState, VertexState,
+};
+
+use crate::graphics::passes::{
+    BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, Drawer, ForwardRenderPassContext, RenderPassContext,
+};
+use crate::graphics::shader_compiler::ShaderCompiler;
+use crate::graphics::{
+    Buffer, Capabilities, DEPTH_COMPARE_FUNCTION, GlobalContext, Prepare, RenderInstruction, Texture, create_render_pipeline,
+};
+
+const DRAWER_NAME: &str = "forward decal";
+const INITIAL_INSTRUCTION_SIZE: usize = 256;
+
+#[derive(Copy, Clone, Pod, Zeroable)]
+#[repr(C)]
+pub(crate) struct InstanceData {
+    upper_left: [f32; 4],
+    upper_right: [f32; 4],
+    lower_left: [f32; 4],
+    lower_right: [f32; 4],
+    color: [f32; 4],
+    center: [f32; 2],
+    size: [f32; 2],
+    rotation: f32,
+    padding: [u32; 3],
+}
+
+/// Draws the decals on the ground. Every instance covers a single ground
+/// tile, and all instances that share a texture are drawn with a single draw
+/// call.
+pub(crate) struct ForwardDecalDrawer {
+    instance_data_buffer: Buffer<InstanceData>,
+    bind_group_layout: BindGroupLayout,
+    bind_group: BindGroup,
+    pipeline: RenderPipeline,
+    instance_data: Vec<InstanceData>,
+    batches: Vec<(Arc<Texture>, Range<u32>)>,
+}
+
+impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAttachmentCount::One }> for ForwardDecalDrawer {
+    type Context = ForwardRenderPassContext;
+    type DrawData<'data> = ();
+
+    fn new(
+        _capabilities: &Capabilities,
+        device: &Device,
+        _queue: &Queue,
+        shader_compiler: &ShaderCompiler,
+        global_context: &GlobalContext,
+        render_pass_context: &Self::Context,
+    ) -> Self {
+        let shader_module = shader_compiler.create_shader_module("forward", "decal");
+
+        let instance_data_buffer = Buffer::with_capacity(
+            device,
+            format!("{DRAWER_NAME} instance data"),
+            BufferUsages::COPY_DST | BufferUsages::STORAGE,
+            (size_of::<InstanceData>() * INITIAL_INSTRUCTION_SIZE) as _,
+        );
+
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some(DRAWER_NAME),
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::VERTEX,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Storage { read_only: true },
+                    has_dynamic_offset: false,
+                    min_binding_size: NonZeroU64::new(size_of::<InstanceData>() as _),
+                },
+                count: None,
+            }],
+        });
+
+        let bind_group = Self::create_bind_group(device, &bind_group_layout, &instance_data_buffer);
+
+        let pass_bind_group_layouts = Self::Context::bind_group_layout(device);
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some(DRAWER_NAME),
+            bind_group_layouts: &[
+                pass_bind_group_layouts[0],
+                pass_bind_group_layouts[1],
+                &bind_group_layout,
+                Texture::bind_group_layout(device),
+            ],
+            push_constant_ranges: &[],
+        });
+
+        let color_attachment_formats = render_pass_context.color_attachment_formats();
+
+        // Decals are blended onto the opaque ground and are not part of the
+        // order independent transparency.
+        let pipeline = create_render_pipeline(device, &RenderPipelineDescriptor {
+            label: Some(DRAWER_NAME),
+            layout: Some(&pipeline_layout),
+            vertex: VertexState {
+                module: &shader_module,
+                entry_point: Some("vs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(FragmentState {
+                module: &shader_module,
+                entry_point: Some("fs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                targets: &[
+                    Some(ColorTargetState {
+                        format: color_attachment_formats[0],
+                        blend: Some(BlendState::ALPHA_BLENDING),
+                        write_mask: ColorWrites::COLOR,
+                    }),
+                    Some(ColorTargetState {
+                        format: color_attachment_formats[1],
+                        blend: None,
+                        write_mask: ColorWrites::empty(),
+                    }),
+                    Some(ColorTargetState {
+                        format: color_attachment_formats[2],
+                        blend: None,
+                        write_mask: ColorWrites::empty(),
+                    }),
+                ],
+            }),
+            multiview: None,
+            primitive: PrimitiveState::default(),
+            multisample: MultisampleState {
+                count: global_context.msaa.sample_count(),
+                ..Default::default()
+            },
+            depth_stencil: Some(DepthStencilState {
+                format: render_pass_context.depth_attachment_output_format()[0],
+                depth_write_enabled: false,
+                depth_compare: DEPTH_COMPARE_FUNCTION,
+                stencil: StencilState::default(),
+                bias: DepthBiasState::default(),
+            }),
+            cache: None,
+        });
+
+        Self {
+            instance_data_buffer,
+            bind_group_layout,
+            bind_group,
+            pipeline,
+            instance_data: Vec::default(),
+            batches: Vec::default(),
+        }
+    }
+
+    fn draw(&mut self, pass: &mut RenderPass<'_>, _draw_data: Self::DrawData<'_>) {
+        if self.batches.is_empty() {
+            return;
+        }
+
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(2, &self.bind_group, &[]);
+
+        for (texture, instances) in self.batches.iter() {
+            pass.set_bind_group(3, texture.get_bind_group(), &[]);
+            pass.draw(0..6, instances.clone());
+        }
+    }
+}
+
+impl Prepare for ForwardDecalDrawer {
+    fn prepare(&mut self, device: &Device, instructions: &RenderInstruction) {
+        self.instance_data.clear();
+        self.batches.clear();
+
+        if instructions.decals.is_empty() {
+            return;
+        }
+
+        // Group the instances by texture, so that we need a single draw call per
+        // texture. The sort is stable, so overlapping decals of the same texture
+        // keep their order.
+        let mut decals: Vec<_> = instructions.decals.iter().collect();
+        decals.sort_by_key(|instruction| instruction.texture.get_id());
+
+        for instruction in decals {
+            let index = self.instance_data.len() as u32;
+
+            match self.batches.last_mut() {
+                Some((texture, instances)) if texture.get_id() == instruction.texture.get_id() => instances.end = index + 1,
+                _ => self.batches.push((instruction.texture.clone(), index..index + 1)),
+            }
+
+            self.instance_data.push(InstanceData {
+                upper_left: instruction.upper_left.to_homogeneous().into(),
+                upper_right: instruction.upper_right.to_homogeneous().into(),
+                lower_left: instruction.lower_left.to_homogeneous().into(),
+                lower_right: instruction.lower_right.to_homogeneous().into(),
+                color: instruction.color.components_linear(),
+                center: [instruction.center.x, instruction.center.z],
+                size: instruction.size.into(),
+                rotation: instruction.rotation.0,
+                padding: Default::default(),
+            });
+        }
+
+        self.instance_data_buffer.reserve(device, self.instance_data.len());
+        self.bind_group = Self::create_bind_group(device, &self.bind_group_layout, &self.instance_data_buffer);
+    }
+
+    fn upload(&mut self, device: &Device, staging_belt: &mut StagingBelt, command_encoder: &mut CommandEncoder) {
+        self.instance_data_buffer
+            .write(device, staging_belt, command_encoder, &self.instance_data);
+    }
+}
+
+impl ForwardDecalDrawer {
+    fn create_bind_group(device: &Device, bind_group_layout: &BindGroupLayout, instance_data_buffer: &Buffer<InstanceData>) -> BindGroup {
+        device.create_bind_group(&BindGroupDescriptor {
+            label: Some(DRAWER_NAME),
+            layout: bind_group_layout,
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: instance_data_buffer.as_entire_binding(),
+            }],
+        })
+    }
+}
@@ -8,17 +8,21 @@ use wgpu::util::StagingBelt;
 use wgpu::{
     BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource,
     BindingType, BlendComponent, BlendFactor, BlendOperation, BlendState, BufferBindingType, BufferUsages, ColorTargetState, ColorWrites,
-    CommandEncoder, CompareFunction, DepthBiasState, DepthStencilState, Device, Face, FragmentState, FrontFace, MultisampleState,
+    CommandEncoder, DepthBiasState, DepthStencilState, Device, Face, FragmentState, FrontFace, MultisampleState,
     PipelineCompilationOptions, PipelineLayout, PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass, RenderPipeline,
     RenderPipelineDescriptor, ShaderModule, ShaderModuleDescriptor, ShaderStages, StencilState, TextureFormat, TextureSampleType,
     TextureView, TextureViewDimension, VertexState, include_wgsl,
 };
 
 use crate::graphics::passes::{
-    BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, Drawer, ForwardRenderPassContext, RenderPassContext,
+    BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, Drawer, ForwardRenderPassContext, RenderPassContext, TextureBatch,
+    batch_by_texture,
 };
 use crate::graphics::shader_compiler::ShaderCompiler;
-use crate::graphics::{BindlessSupport, Buffer, Capabilities, EntityInstruction, GlobalContext, Prepare, RenderInstruction, Texture};
+use crate::graphics::{
+    BindlessSupport, Buffer, Capabilities, DEPTH_COMPARE_FUNCTION, EntityInstruction, GlobalContext, Prepare, RenderInstruction, Texture,
+    create_render_pipeline,
+};
 
 const SHADER: ShaderModuleDescriptor = include_wgsl!("../../../../shaders/passes/forward/entity.wgsl");
 const SHADER_BINDLESS: ShaderModuleDescriptor = include_wgsl!("../../../../shaders/passes/forward/entity_bindless.wgsl");
@@ -39,7 +43,7 @@ pub(crate) struct InstanceData {
     curvature: f32,
     mirror: u32,
     texture_index: i32,
-    padding: u32,
+    highlight: f32,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -67,6 +71,8 @@ pub(crate) struct ForwardEntityDrawer {
     instance_data: Vec<InstanceData>,
     bump: Bump,
     lookup: HashMap<u64, i32>,
+    texture_order: Vec<(u64, usize)>,
+    texture_batches: Vec<TextureBatch>,
 }
 
 impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAttachmentCount::One }> for ForwardEntityDrawer {
@@ -197,6 +203,8 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAtt
             instance_data: Vec::default(),
             bump: Bump::default(),
             lookup: HashMap::default(),
+            texture_order: Vec::default(),
+            texture_batches: Vec::default(),
         }
     }
 
@@ -215,16 +223,12 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAtt
         if self.bindless_support {
             pass.draw(0..6, 0..self.draw_count as u32);
         } else {
-            let mut current_texture_id = self.solid_pixel_texture.get_id();
-            pass.set_bind_group(3, self.solid_pixel_texture.get_bind_group(), &[]);
-
-            for (index, instruction) in draw_data.entities[0..self.draw_count].iter().enumerate() {
-                if instruction.texture.get_id() != current_texture_id {
-                    current_texture_id = instruction.texture.get_id();
-                    pass.set_bind_group(3, instruction.texture.get_bind_group(), &[]);
-                }
-                let index = index as u32;
-                pass.draw(0..6, index..index + 1);
+            // Entities that share a texture are drawn with a single instanced draw call.
+            // Neither pass depends on the draw order, since opaque parts are depth tested
+            // and transparent parts are blended order independent.
+            for batch in &self.texture_batches {
+                pass.set_bind_group(3, draw_data.entities[batch.instruction_index].texture.get_bind_group(), &[]);
+                pass.draw(0..6, batch.instances.clone());
             }
         }
     }
@@ -269,7 +273,7 @@ impl Prepare for ForwardEntityDrawer {
                     curvature: instruction.curvature,
                     mirror: instruction.mirror as u32,
                     texture_index,
-                    padding: Default::default(),
+                    highlight: instruction.highlight,
                 });
             }
 
@@ -280,7 +284,20 @@ impl Prepare for ForwardEntityDrawer {
             self.instance_data_buffer.reserve(device, self.instance_data.len());
             self.bind_group = Self::create_bind_group_bindless(device, &self.bind_group_layout, &self.instance_data_buffer, &texture_views)
         } else {
-            for instruction in instructions.entities.iter() {
+            self.texture_order.clear();
+            self.texture_order.extend(
+                instructions
+                    .entities
+                    .iter()
+                    .enumerate()
+                    .map(|(index, instruction)| (instruction.texture.get_id(), index)),
+            );
+
+            batch_by_texture(&mut self.texture_order, &mut self.texture_batches);
+
+            for &(_, index) in self.texture_order.iter() {
+                let instruction = &instructions.entities[index];
+
                 self.instance_data.push(InstanceData {
                     world: instruction.world.into(),
                     frame_part_transform: instruction.frame_part_transform.into(),
@@ -293,7 +310,7 @@ impl Prepare for ForwardEntityDrawer {
                     curvature: instruction.curvature,
                     mirror: instruction.mirror as u32,
                     texture_index: 0,
-                    padding: Default::default(),
+                    highlight: instruction.highlight,
                 });
             }
 
@@ -414,7 +431,7 @@ impl ForwardEntityDrawer {
 
         let constants = &[("PASS_MODE", f64::from(pass_mode as u32))];
 
-        device.create_render_pipeline(&RenderPipelineDescriptor {
+        create_render_pipeline(device, &RenderPipelineDescriptor {
             label: Some(&format!("{DRAWER_NAME} {pass_mode:?}")),
             layout: Some(pipeline_layout),
             vertex: VertexState {
@@ -452,7 +469,7 @@ impl ForwardEntityDrawer {
             depth_stencil: Some(DepthStencilState {
                 format: render_pass_context.depth_attachment_output_format()[0],
                 depth_write_enabled: pass_mode == EntityPassMode::Opaque,
-                depth_compare: CompareFunction::Greater,
+                depth_compare: DEPTH_COMPARE_FUNCTION,
                 stencil: StencilState::default(),
                 bias: DepthBiasState::default(),
             }),
@@ -1,16 +1,16 @@
 use wgpu::{
     BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType,
-    BlendComponent, BlendFactor, BlendOperation, BlendState, ColorTargetState, ColorWrites, CompareFunction, DepthBiasState,
-    DepthStencilState, Device, Face, FragmentState, FrontFace, MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor,
-    PrimitiveState, Queue, RenderPass, RenderPipeline, RenderPipelineDescriptor, ShaderModuleDescriptor, ShaderStages, StencilState,
-    TextureSampleType, TextureViewDimension, VertexState, include_wgsl,
+    BlendComponent, BlendFactor, BlendOperation, BlendState, ColorTargetState, ColorWrites, DepthBiasState, DepthStencilState, Device,
+    Face, FragmentState, FrontFace, MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, Queue,
+    RenderPass, RenderPipeline, RenderPipelineDescriptor, ShaderModuleDescriptor, ShaderStages, StencilState, TextureSampleType,
+    TextureViewDimension, VertexState, include_wgsl,
 };
 
 use crate::graphics::passes::{
     BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, Drawer, ForwardRenderPassContext, RenderPassContext,
 };
 use crate::graphics::shader_compiler::ShaderCompiler;
-use crate::graphics::{Capabilities, GlobalContext, IndicatorInstruction};
+use crate::graphics::{Capabilities, DEPTH_COMPARE_FUNCTION, GlobalContext, IndicatorInstruction, create_render_pipeline};
 
 const SHADER: ShaderModuleDescriptor = include_wgsl!("../../../../shaders/passes/forward/indicator.wgsl");
 const DRAWER_NAME: &str = "forward indicator";
@@ -67,7 +67,7 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAtt
             push_constant_ranges: &[],
         });
 
-        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+        let pipeline = create_render_pipeline(device, &RenderPipelineDescriptor {
             label: Some(DRAWER_NAME),
             layout: Some(&pipeline_layout),
             vertex: VertexState {
@@ -133,7 +133,7 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAtt
             depth_stencil: Some(DepthStencilState {
                 format: render_pass_context.depth_attachment_output_format()[0],
                 depth_write_enabled: true,
-                depth_compare: CompareFunction::Greater,
+                depth_compare: DEPTH_COMPARE_FUNCTION,
                 stencil: StencilState::default(),
                 bias: DepthBiasState::default(),
             }),
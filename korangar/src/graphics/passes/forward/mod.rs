@@ -1,19 +1,23 @@
+mod decal;
 mod entity;
 mod indicator;
 mod model;
-mod wave;
+mod shadow_blob;
+mod sky;
 
+pub(crate) use decal::ForwardDecalDrawer;
 pub(crate) use entity::{EntityPassMode, ForwardEntityDrawData, ForwardEntityDrawer};
 pub(crate) use indicator::ForwardIndicatorDrawer;
 pub(crate) use model::{ForwardModelDrawData, ForwardModelDrawer, ModelPassMode};
-pub(crate) use wave::WaterWaveDrawer;
+pub(crate) use shadow_blob::ForwardShadowBlobDrawer;
+pub(crate) use sky::ForwardSkyDrawer;
 use wgpu::{
     BindGroupLayout, Color, CommandEncoder, Device, LoadOp, Operations, Queue, RenderPass, RenderPassColorAttachment,
     RenderPassDepthStencilAttachment, RenderPassDescriptor, StoreOp, TextureFormat,
 };
 
 use super::{BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, RenderPassContext};
-use crate::graphics::GlobalContext;
+use crate::graphics::{DEPTH_CLEAR_VALUE, GlobalContext};
 use crate::loaders::TextureLoader;
 const PASS_NAME: &str = "forward render pass";
 
@@ -86,7 +90,7 @@ impl RenderPassContext<{ BindGroupCount::Two }, { ColorAttachmentCount::Three },
             depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
                 view: global_context.forward_depth_texture.get_texture_view(),
                 depth_ops: Some(Operations {
-                    load: LoadOp::Clear(0.0),
+                    load: LoadOp::Clear(DEPTH_CLEAR_VALUE),
                     store: StoreOp::Store,
                 }),
                 stencil_ops: None,
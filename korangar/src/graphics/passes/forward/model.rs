@@ -1,24 +1,27 @@
 use std::num::NonZeroU64;
+use std::sync::Arc;
 
 use bytemuck::{Pod, Zeroable};
 use cgmath::{Matrix, Matrix4, SquareMatrix, Transform};
 use wgpu::util::StagingBelt;
 use wgpu::{
-    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType,
-    BlendComponent, BlendFactor, BlendOperation, BlendState, BufferAddress, BufferBindingType, BufferUsages, ColorTargetState, ColorWrites,
-    CommandEncoder, CompareFunction, DepthBiasState, DepthStencilState, Device, Face, FragmentState, FrontFace, IndexFormat,
-    MultisampleState, PipelineCompilationOptions, PipelineLayout, PipelineLayoutDescriptor, PolygonMode, PrimitiveState, Queue, RenderPass,
-    RenderPipeline, RenderPipelineDescriptor, ShaderModule, ShaderModuleDescriptor, ShaderStages, StencilState, TextureFormat, VertexState,
-    include_wgsl,
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource,
+    BindingType, BlendComponent, BlendFactor, BlendOperation, BlendState, BufferAddress, BufferBindingType, BufferUsages, ColorTargetState,
+    ColorWrites, CommandEncoder, DepthBiasState, DepthStencilState, Device, Face, FragmentState, FrontFace, IndexFormat, MultisampleState,
+    PipelineCompilationOptions, PipelineLayout, PipelineLayoutDescriptor, PolygonMode, PrimitiveState, Queue, RenderPass, RenderPipeline,
+    RenderPipelineDescriptor, ShaderModule, ShaderModuleDescriptor, ShaderStages, StencilState, TextureFormat, TextureSampleType,
+    TextureViewDimension, VertexState, include_wgsl,
 };
 
 use crate::graphics::passes::forward::ForwardRenderPassContext;
 use crate::graphics::passes::{
     BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, DrawIndexedIndirectArgs, Drawer, ModelBatchDrawData, RenderPassContext,
+    texture_group_runs,
 };
 use crate::graphics::shader_compiler::ShaderCompiler;
 use crate::graphics::{
-    BindlessSupport, Buffer, Capabilities, GlobalContext, ModelBatch, ModelVertex, Msaa, Prepare, RenderInstruction, Texture, TextureSet,
+    BindlessSupport, Buffer, Capabilities, DEPTH_COMPARE_FUNCTION, GlobalContext, ModelBatch, ModelVertex, Msaa, Prepare,
+    RenderInstruction, Texture, TextureSet, create_render_pipeline,
 };
 
 const SHADER: ShaderModuleDescriptor = include_wgsl!("../../../../shaders/passes/forward/model.wgsl");
@@ -31,6 +34,8 @@ const INITIAL_INSTRUCTION_SIZE: usize = 256;
 struct InstanceData {
     world: [[f32; 4]; 4],
     inv_world: [[f32; 4]; 4],
+    opacity: f32,
+    padding: [u32; 3],
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -55,6 +60,9 @@ pub(crate) struct ForwardModelDrawer {
     command_buffer: Buffer<DrawIndexedIndirectArgs>,
     bind_group_layout: BindGroupLayout,
     bind_group: BindGroup,
+    light_map_texture: Arc<Texture>,
+    /// Bound for maps without light maps.
+    empty_light_map_texture: Arc<Texture>,
     opaque_pipeline: RenderPipeline,
     semi_transparent_pipeline: RenderPipeline,
     transparent_pipeline: RenderPipeline,
@@ -102,19 +110,32 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAtt
 
         let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
             label: Some(DRAWER_NAME),
-            entries: &[BindGroupLayoutEntry {
-                binding: 0,
-                visibility: ShaderStages::VERTEX,
-                ty: BindingType::Buffer {
-                    ty: BufferBindingType::Storage { read_only: true },
-                    has_dynamic_offset: false,
-                    min_binding_size: NonZeroU64::new(size_of::<InstanceData>() as _),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::VERTEX,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: NonZeroU64::new(size_of::<InstanceData>() as _),
+                    },
+                    count: None,
                 },
-                count: None,
-            }],
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+            ],
         });
 
-        let bind_group = Self::create_bind_group(device, &bind_group_layout, &instance_data_buffer);
+        let light_map_texture = global_context.solid_pixel_texture.clone();
+        let bind_group = Self::create_bind_group(device, &bind_group_layout, &instance_data_buffer, &light_map_texture);
 
         let pass_bind_group_layouts = Self::Context::bind_group_layout(device);
 
@@ -203,6 +224,8 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAtt
             command_buffer,
             bind_group_layout,
             bind_group,
+            empty_light_map_texture: light_map_texture.clone(),
+            light_map_texture,
             opaque_pipeline,
             semi_transparent_pipeline,
             transparent_pipeline,
@@ -234,30 +257,32 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAtt
             match bindless_support {
                 BindlessSupport::Full | BindlessSupport::Limited => {
                     for batch in batches {
-                        pass.set_bind_group(3, batch.texture_set.get_bind_group().unwrap(), &[]);
                         pass.set_index_buffer(batch.index_buffer.slice(..), IndexFormat::Uint32);
                         pass.set_vertex_buffer(0, batch.vertex_buffer.slice(..));
 
-                        match multi_draw_indirect_support {
-                            true => pass.multi_draw_indexed_indirect(
-                                command_buffer.get_buffer(),
-                                (batch.offset * size_of::<DrawIndexedIndirectArgs>()) as BufferAddress,
-                                batch.count as u32,
-                            ),
-                            false => {
-                                let start = batch.offset;
-                                let end = start + batch.count;
-
-                                for (index, instruction) in draw_data.instructions[start..end].iter().enumerate() {
-                                    let index_start = instruction.index_offset;
-                                    let index_end = index_start + instruction.index_count;
-                                    let instance_offset = (start + index) as u32;
-
-                                    pass.draw_indexed(
-                                        index_start..index_end,
-                                        instruction.base_vertex,
-                                        instance_offset..instance_offset + 1,
-                                    );
+                        let instructions = &draw_data.instructions[batch.offset..batch.offset + batch.count];
+
+                        for (texture_group, run) in texture_group_runs(instructions, batch.offset) {
+                            pass.set_bind_group(3, batch.texture_set.get_bind_group(texture_group), &[]);
+
+                            match multi_draw_indirect_support {
+                                true => pass.multi_draw_indexed_indirect(
+                                    command_buffer.get_buffer(),
+                                    (run.start * size_of::<DrawIndexedIndirectArgs>()) as BufferAddress,
+                                    run.len() as u32,
+                                ),
+                                false => {
+                                    for (index, instruction) in draw_data.instructions[run.clone()].iter().enumerate() {
+                                        let index_start = instruction.index_offset;
+                                        let index_end = index_start + instruction.index_count;
+                                        let instance_offset = (run.start + index) as u32;
+
+                                        pass.draw_indexed(
+                                            index_start..index_end,
+                                            instruction.base_vertex,
+                                            instance_offset..instance_offset + 1,
+                                        );
+                                    }
                                 }
                             }
                         }
@@ -356,13 +381,25 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAtt
 }
 
 impl Prepare for ForwardModelDrawer {
-    fn prepare(&mut self, _device: &Device, instructions: &RenderInstruction) {
+    fn prepare(&mut self, device: &Device, instructions: &RenderInstruction) {
         let draw_count = instructions.models.len();
 
         if draw_count == 0 {
             return;
         }
 
+        let light_map_texture = instructions.map_light_map_texture.unwrap_or(&self.empty_light_map_texture);
+
+        if light_map_texture.get_id() != self.light_map_texture.get_id() {
+            self.light_map_texture = light_map_texture.clone();
+            self.bind_group = Self::create_bind_group(
+                device,
+                &self.bind_group_layout,
+                &self.instance_data_buffer,
+                &self.light_map_texture,
+            );
+        }
+
         self.instance_data.clear();
         self.draw_commands.clear();
         self.opaque_batches.clear();
@@ -418,6 +455,8 @@ impl Prepare for ForwardModelDrawer {
                     .unwrap_or(Matrix4::identity())
                     .transpose()
                     .into(),
+                opacity: instruction.opacity,
+                padding: Default::default(),
             });
 
             self.draw_commands.push(DrawIndexedIndirectArgs {
@@ -438,20 +477,36 @@ impl Prepare for ForwardModelDrawer {
             .write(device, staging_belt, command_encoder, &self.draw_commands);
 
         if recreated {
-            self.bind_group = Self::create_bind_group(device, &self.bind_group_layout, &self.instance_data_buffer)
+            self.bind_group = Self::create_bind_group(
+                device,
+                &self.bind_group_layout,
+                &self.instance_data_buffer,
+                &self.light_map_texture,
+            )
         }
     }
 }
 
 impl ForwardModelDrawer {
-    fn create_bind_group(device: &Device, bind_group_layout: &BindGroupLayout, instance_data_buffer: &Buffer<InstanceData>) -> BindGroup {
+    fn create_bind_group(
+        device: &Device,
+        bind_group_layout: &BindGroupLayout,
+        instance_data_buffer: &Buffer<InstanceData>,
+        light_map_texture: &Texture,
+    ) -> BindGroup {
         device.create_bind_group(&BindGroupDescriptor {
             label: Some(DRAWER_NAME),
             layout: bind_group_layout,
-            entries: &[BindGroupEntry {
-                binding: 0,
-                resource: instance_data_buffer.as_entire_binding(),
-            }],
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: instance_data_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(light_map_texture.get_texture_view()),
+                },
+            ],
         })
     }
 
@@ -534,7 +589,7 @@ impl ForwardModelDrawer {
             ("PASS_MODE", f64::from(pass_mode as u32)),
         ];
 
-        device.create_render_pipeline(&RenderPipelineDescriptor {
+        create_render_pipeline(device, &RenderPipelineDescriptor {
             label: Some(&format!("{DRAWER_NAME} {pass_mode:?}")),
             layout: Some(pipeline_layout),
             vertex: VertexState {
@@ -574,7 +629,7 @@ impl ForwardModelDrawer {
             depth_stencil: Some(DepthStencilState {
                 format: render_pass_context.depth_attachment_output_format()[0],
                 depth_write_enabled: opaque,
-                depth_compare: CompareFunction::Greater,
+                depth_compare: DEPTH_COMPARE_FUNCTION,
                 stencil: StencilState::default(),
                 bias: DepthBiasState::default(),
             }),
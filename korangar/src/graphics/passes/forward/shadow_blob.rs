@@ -0,0 +1,194 @@
+use std::num::NonZeroU64;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::StagingBelt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType,
+    BlendState, BufferBindingType, BufferUsages, ColorTargetState, ColorWrites, CommandEncoder, DepthBiasState, DepthStencilState, Device,
+    FragmentState, MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass,
+    RenderPipeline, RenderPipelineDescriptor, ShaderStages, StencilState, VertexState,
+};
+
+use crate::graphics::passes::{
+    BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, Drawer, ForwardRenderPassContext, RenderPassContext,
+};
+use crate::graphics::shader_compiler::ShaderCompiler;
+use crate::graphics::{Buffer, Capabilities, DEPTH_COMPARE_FUNCTION, GlobalContext, Prepare, RenderInstruction, create_render_pipeline};
+
+const DRAWER_NAME: &str = "forward shadow blob";
+const INITIAL_INSTRUCTION_SIZE: usize = 256;
+
+#[derive(Copy, Clone, Pod, Zeroable)]
+#[repr(C)]
+pub(crate) struct InstanceData {
+    position: [f32; 3],
+    radius: f32,
+    opacity: f32,
+    padding: [u32; 3],
+}
+
+pub(crate) struct ForwardShadowBlobDrawer {
+    instance_data_buffer: Buffer<InstanceData>,
+    bind_group_layout: BindGroupLayout,
+    bind_group: BindGroup,
+    pipeline: RenderPipeline,
+    draw_count: usize,
+    instance_data: Vec<InstanceData>,
+}
+
+impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAttachmentCount::One }> for ForwardShadowBlobDrawer {
+    type Context = ForwardRenderPassContext;
+    type DrawData<'data> = ();
+
+    fn new(
+        _capabilities: &Capabilities,
+        device: &Device,
+        _queue: &Queue,
+        shader_compiler: &ShaderCompiler,
+        global_context: &GlobalContext,
+        render_pass_context: &Self::Context,
+    ) -> Self {
+        let shader_module = shader_compiler.create_shader_module("forward", "shadow_blob");
+
+        let instance_data_buffer = Buffer::with_capacity(
+            device,
+            format!("{DRAWER_NAME} instance data"),
+            BufferUsages::COPY_DST | BufferUsages::STORAGE,
+            (size_of::<InstanceData>() * INITIAL_INSTRUCTION_SIZE) as _,
+        );
+
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some(DRAWER_NAME),
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::VERTEX,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Storage { read_only: true },
+                    has_dynamic_offset: false,
+                    min_binding_size: NonZeroU64::new(size_of::<InstanceData>() as _),
+                },
+                count: None,
+            }],
+        });
+
+        let bind_group = Self::create_bind_group(device, &bind_group_layout, &instance_data_buffer);
+
+        let pass_bind_group_layouts = Self::Context::bind_group_layout(device);
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some(DRAWER_NAME),
+            bind_group_layouts: &[pass_bind_group_layouts[0], pass_bind_group_layouts[1], &bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let color_attachment_formats = render_pass_context.color_attachment_formats();
+
+        // The blobs darken the opaque color and are not part of the order
+        // independent transparency.
+        let pipeline = create_render_pipeline(device, &RenderPipelineDescriptor {
+            label: Some(DRAWER_NAME),
+            layout: Some(&pipeline_layout),
+            vertex: VertexState {
+                module: &shader_module,
+                entry_point: Some("vs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(FragmentState {
+                module: &shader_module,
+                entry_point: Some("fs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                targets: &[
+                    Some(ColorTargetState {
+                        format: color_attachment_formats[0],
+                        blend: Some(BlendState::ALPHA_BLENDING),
+                        write_mask: ColorWrites::COLOR,
+                    }),
+                    Some(ColorTargetState {
+                        format: color_attachment_formats[1],
+                        blend: None,
+                        write_mask: ColorWrites::empty(),
+                    }),
+                    Some(ColorTargetState {
+                        format: color_attachment_formats[2],
+                        blend: None,
+                        write_mask: ColorWrites::empty(),
+                    }),
+                ],
+            }),
+            multiview: None,
+            primitive: PrimitiveState::default(),
+            multisample: MultisampleState {
+                count: global_context.msaa.sample_count(),
+                ..Default::default()
+            },
+            depth_stencil: Some(DepthStencilState {
+                format: render_pass_context.depth_attachment_output_format()[0],
+                depth_write_enabled: false,
+                depth_compare: DEPTH_COMPARE_FUNCTION,
+                stencil: StencilState::default(),
+                bias: DepthBiasState::default(),
+            }),
+            cache: None,
+        });
+
+        Self {
+            instance_data_buffer,
+            bind_group_layout,
+            bind_group,
+            pipeline,
+            draw_count: 0,
+            instance_data: Vec::default(),
+        }
+    }
+
+    fn draw(&mut self, pass: &mut RenderPass<'_>, _draw_data: Self::DrawData<'_>) {
+        if self.draw_count == 0 {
+            return;
+        }
+
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(2, &self.bind_group, &[]);
+        pass.draw(0..6, 0..self.draw_count as u32);
+    }
+}
+
+impl Prepare for ForwardShadowBlobDrawer {
+    fn prepare(&mut self, device: &Device, instructions: &RenderInstruction) {
+        self.draw_count = instructions.shadow_blobs.len();
+
+        if self.draw_count == 0 {
+            return;
+        }
+
+        self.instance_data.clear();
+        self.instance_data
+            .extend(instructions.shadow_blobs.iter().map(|instruction| InstanceData {
+                position: instruction.position.into(),
+                radius: instruction.radius,
+                opacity: instruction.opacity,
+                padding: Default::default(),
+            }));
+
+        self.instance_data_buffer.reserve(device, self.instance_data.len());
+        self.bind_group = Self::create_bind_group(device, &self.bind_group_layout, &self.instance_data_buffer);
+    }
+
+    fn upload(&mut self, device: &Device, staging_belt: &mut StagingBelt, command_encoder: &mut CommandEncoder) {
+        self.instance_data_buffer
+            .write(device, staging_belt, command_encoder, &self.instance_data);
+    }
+}
+
+impl ForwardShadowBlobDrawer {
+    fn create_bind_group(device: &Device, bind_group_layout: &BindGroupLayout, instance_data_buffer: &Buffer<InstanceData>) -> BindGroup {
+        device.create_bind_group(&BindGroupDescriptor {
+            label: Some(DRAWER_NAME),
+            layout: bind_group_layout,
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: instance_data_buffer.as_entire_binding(),
+            }],
+        })
+    }
+}
@@ -0,0 +1,226 @@
+use std::num::NonZeroU64;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::StagingBelt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType,
+    BufferBindingType, BufferUsages, ColorTargetState, ColorWrites, CommandEncoder, CompareFunction, DepthBiasState, DepthStencilState,
+    Device, FragmentState, MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass,
+    RenderPipeline, RenderPipelineDescriptor, ShaderStages, StencilState, VertexState,
+};
+
+use crate::graphics::passes::{
+    BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, Drawer, ForwardRenderPassContext, RenderPassContext,
+};
+use crate::graphics::shader_compiler::ShaderCompiler;
+use crate::graphics::{Buffer, Capabilities, GlobalContext, Prepare, RenderInstruction, create_render_pipeline};
+
+const DRAWER_NAME: &str = "forward sky";
+/// Cloud layers beyond this are ignored.
+const MAXIMUM_CLOUD_LAYERS: usize = 4;
+
+#[derive(Copy, Clone, Default, Pod, Zeroable)]
+#[repr(C)]
+struct CloudLayerUniforms {
+    color: [f32; 4],
+    coverage: f32,
+    scale: f32,
+    speed: f32,
+    padding: u32,
+}
+
+#[derive(Copy, Clone, Default, Pod, Zeroable)]
+#[repr(C)]
+struct SkyUniforms {
+    top_color: [f32; 4],
+    horizon_color: [f32; 4],
+    cloud_layers: [CloudLayerUniforms; MAXIMUM_CLOUD_LAYERS],
+    cloud_layer_count: u32,
+    show_stars: u32,
+    padding: [u32; 2],
+}
+
+/// Draws the sky gradient, stars and cloud layers of the map. It is the first
+/// drawer of the forward pass and covers the whole screen without writing any
+/// depth, so all geometry is drawn on top of it.
+pub(crate) struct ForwardSkyDrawer {
+    uniforms: SkyUniforms,
+    uniforms_buffer: Buffer<SkyUniforms>,
+    bind_group_layout: BindGroupLayout,
+    bind_group: BindGroup,
+    pipeline: RenderPipeline,
+    draw: bool,
+}
+
+impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAttachmentCount::One }> for ForwardSkyDrawer {
+    type Context = ForwardRenderPassContext;
+    type DrawData<'data> = ();
+
+    fn new(
+        _capabilities: &Capabilities,
+        device: &Device,
+        queue: &Queue,
+        shader_compiler: &ShaderCompiler,
+        global_context: &GlobalContext,
+        render_pass_context: &Self::Context,
+    ) -> Self {
+        let shader_module = shader_compiler.create_shader_module("forward", "sky");
+
+        let uniforms_buffer = Buffer::with_data(
+            device,
+            queue,
+            format!("{DRAWER_NAME} uniforms"),
+            BufferUsages::COPY_DST | BufferUsages::UNIFORM,
+            &[SkyUniforms::default()],
+        );
+
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some(DRAWER_NAME),
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: NonZeroU64::new(size_of::<SkyUniforms>() as _),
+                },
+                count: None,
+            }],
+        });
+
+        let bind_group = Self::create_bind_group(device, &bind_group_layout, &uniforms_buffer);
+
+        let pass_bind_group_layouts = Self::Context::bind_group_layout(device);
+
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some(DRAWER_NAME),
+            bind_group_layouts: &[pass_bind_group_layouts[0], pass_bind_group_layouts[1], &bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let color_attachment_formats = render_pass_context.color_attachment_formats();
+
+        let pipeline = create_render_pipeline(device, &RenderPipelineDescriptor {
+            label: Some(DRAWER_NAME),
+            layout: Some(&pipeline_layout),
+            vertex: VertexState {
+                module: &shader_module,
+                entry_point: Some("vs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(FragmentState {
+                module: &shader_module,
+                entry_point: Some("fs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                targets: &[
+                    Some(ColorTargetState {
+                        format: color_attachment_formats[0],
+                        blend: None,
+                        write_mask: ColorWrites::ALL,
+                    }),
+                    Some(ColorTargetState {
+                        format: color_attachment_formats[1],
+                        blend: None,
+                        write_mask: ColorWrites::empty(),
+                    }),
+                    Some(ColorTargetState {
+                        format: color_attachment_formats[2],
+                        blend: None,
+                        write_mask: ColorWrites::empty(),
+                    }),
+                ],
+            }),
+            multiview: None,
+            primitive: PrimitiveState::default(),
+            multisample: MultisampleState {
+                count: global_context.msaa.sample_count(),
+                ..Default::default()
+            },
+            depth_stencil: Some(DepthStencilState {
+                format: render_pass_context.depth_attachment_output_format()[0],
+                depth_write_enabled: false,
+                depth_compare: CompareFunction::Always,
+                stencil: StencilState::default(),
+                bias: DepthBiasState::default(),
+            }),
+            cache: None,
+        });
+
+        Self {
+            uniforms: SkyUniforms::default(),
+            uniforms_buffer,
+            bind_group_layout,
+            bind_group,
+            pipeline,
+            draw: false,
+        }
+    }
+
+    fn draw(&mut self, pass: &mut RenderPass<'_>, _draw_data: Self::DrawData<'_>) {
+        if !self.draw {
+            return;
+        }
+
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(2, &self.bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+}
+
+impl Prepare for ForwardSkyDrawer {
+    fn prepare(&mut self, _device: &Device, instructions: &RenderInstruction) {
+        let Some(sky) = instructions.sky.as_ref() else {
+            self.draw = false;
+            return;
+        };
+
+        let mut cloud_layers = [CloudLayerUniforms::default(); MAXIMUM_CLOUD_LAYERS];
+        let cloud_layer_count = sky.cloud_layers.len().min(MAXIMUM_CLOUD_LAYERS);
+
+        cloud_layers.iter_mut().zip(sky.cloud_layers.iter()).for_each(|(uniforms, layer)| {
+            *uniforms = CloudLayerUniforms {
+                color: layer.color.components_linear(),
+                coverage: layer.coverage,
+                scale: layer.scale,
+                speed: layer.speed,
+                padding: 0,
+            }
+        });
+
+        self.uniforms = SkyUniforms {
+            top_color: sky.top_color.components_linear(),
+            horizon_color: sky.horizon_color.components_linear(),
+            cloud_layers,
+            cloud_layer_count: cloud_layer_count as u32,
+            show_stars: sky.show_stars as u32,
+            padding: Default::default(),
+        };
+        self.draw = true;
+    }
+
+    fn upload(&mut self, device: &Device, staging_belt: &mut StagingBelt, command_encoder: &mut CommandEncoder) {
+        if !self.draw {
+            return;
+        }
+
+        let recreated = self.uniforms_buffer.write(device, staging_belt, command_encoder, &[self.uniforms]);
+
+        if recreated {
+            self.bind_group = Self::create_bind_group(device, &self.bind_group_layout, &self.uniforms_buffer);
+        }
+    }
+}
+
+impl ForwardSkyDrawer {
+    fn create_bind_group(device: &Device, bind_group_layout: &BindGroupLayout, uniforms_buffer: &Buffer<SkyUniforms>) -> BindGroup {
+        device.create_bind_group(&BindGroupDescriptor {
+            label: Some(DRAWER_NAME),
+            layout: bind_group_layout,
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: uniforms_buffer.as_entire_binding(),
+            }],
+        })
+    }
+}
@@ -49,6 +49,12 @@ pub(crate) struct InterfaceRectangleDrawer {
     pipeline: RenderPipeline,
     draw_count: usize,
     instance_data: Vec<InstanceData>,
+    texture_ids: Vec<u64>,
+    previous_instance_data: Vec<InstanceData>,
+    previous_texture_ids: Vec<u64>,
+    font_map_texture_id: u64,
+    invalidated: bool,
+    dirty: bool,
     bump: Bump,
     lookup: HashMap<u64, i32>,
 }
@@ -208,6 +214,12 @@ impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttac
             pipeline,
             draw_count: 0,
             instance_data: Vec::default(),
+            texture_ids: Vec::default(),
+            previous_instance_data: Vec::default(),
+            previous_texture_ids: Vec::default(),
+            font_map_texture_id: 0,
+            invalidated: true,
+            dirty: true,
             bump: Bump::default(),
             lookup: HashMap::default(),
         }
@@ -250,15 +262,30 @@ impl Prepare for InterfaceRectangleDrawer {
     fn prepare(&mut self, device: &Device, instructions: &RenderInstruction) {
         self.draw_count = instructions.interface.len();
 
-        if self.draw_count == 0 {
-            return;
-        }
-
         let Some(font_map_texture) = instructions.font_map_texture else {
+            self.dirty = true;
             return;
         };
 
+        // Keep the data of the last frame around, so we can check if the
+        // interface layer needs to be rendered again.
+        std::mem::swap(&mut self.instance_data, &mut self.previous_instance_data);
+        std::mem::swap(&mut self.texture_ids, &mut self.previous_texture_ids);
         self.instance_data.clear();
+        self.texture_ids.clear();
+
+        self.texture_ids
+            .extend(instructions.interface.iter().map(|instruction| match instruction {
+                InterfaceRectangleInstruction::Sprite { texture, .. } | InterfaceRectangleInstruction::Sdf { texture, .. } => {
+                    texture.get_id()
+                }
+                InterfaceRectangleInstruction::Solid { .. } | InterfaceRectangleInstruction::Text { .. } => 0,
+            }));
+
+        if self.draw_count == 0 {
+            self.update_dirty(font_map_texture.get_id());
+            return;
+        }
 
         if self.bindless_support {
             self.bump.reset();
@@ -299,6 +326,8 @@ impl Prepare for InterfaceRectangleDrawer {
                         color,
                         corner_diameter,
                         texture,
+                        texture_position,
+                        texture_size,
                         smooth,
                     } => {
                         let rectangle_type = if *smooth { 1 } else { 2 };
@@ -322,8 +351,8 @@ impl Prepare for InterfaceRectangleDrawer {
                             shadow_padding: [0.0, 0.0, 0.0, 0.0],
                             screen_position: (*screen_position).into(),
                             screen_size: (*screen_size).into(),
-                            texture_position: [0.0, 0.0],
-                            texture_size: [1.0, 1.0],
+                            texture_position: (*texture_position).into(),
+                            texture_size: (*texture_size).into(),
                             rectangle_type,
                             texture_index,
                             padding: Default::default(),
@@ -389,6 +418,10 @@ impl Prepare for InterfaceRectangleDrawer {
                 }
             }
 
+            if !self.update_dirty(font_map_texture.get_id()) {
+                return;
+            }
+
             if texture_views.is_empty() {
                 texture_views.push(self.solid_pixel_texture.get_texture_view());
             }
@@ -436,6 +469,8 @@ impl Prepare for InterfaceRectangleDrawer {
                         color,
                         corner_diameter,
                         texture: _,
+                        texture_position,
+                        texture_size,
                         smooth,
                     } => {
                         let rectangle_type = if *smooth { 1 } else { 2 };
@@ -448,8 +483,8 @@ impl Prepare for InterfaceRectangleDrawer {
                             shadow_padding: [0.0, 0.0, 0.0, 0.0],
                             screen_position: (*screen_position).into(),
                             screen_size: (*screen_size).into(),
-                            texture_position: [0.0, 0.0],
-                            texture_size: [1.0, 1.0],
+                            texture_position: (*texture_position).into(),
+                            texture_size: (*texture_size).into(),
                             rectangle_type,
                             texture_index: 0,
                             padding: Default::default(),
@@ -504,6 +539,10 @@ impl Prepare for InterfaceRectangleDrawer {
                 }
             }
 
+            if !self.update_dirty(font_map_texture.get_id()) {
+                return;
+            }
+
             self.instance_data_buffer.reserve(device, self.instance_data.len());
             self.bind_group = Self::create_bind_group(
                 device,
@@ -515,12 +554,40 @@ impl Prepare for InterfaceRectangleDrawer {
     }
 
     fn upload(&mut self, device: &Device, staging_belt: &mut StagingBelt, command_encoder: &mut CommandEncoder) {
+        if !self.dirty {
+            return;
+        }
+
         self.instance_data_buffer
             .write(device, staging_belt, command_encoder, &self.instance_data);
     }
 }
 
 impl InterfaceRectangleDrawer {
+    /// Returns `true` if the cached interface layer is out of date and the
+    /// interface needs to be rendered again this frame.
+    pub(crate) fn needs_redraw(&self) -> bool {
+        self.dirty
+    }
+
+    /// Forces the interface to be rendered again, for example after the
+    /// interface layer texture was recreated.
+    pub(crate) fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
+    /// Compares the prepared instances with the ones of the last frame and
+    /// returns if the interface layer needs to be rendered again.
+    fn update_dirty(&mut self, font_map_texture_id: u64) -> bool {
+        self.dirty = std::mem::take(&mut self.invalidated)
+            || self.font_map_texture_id != font_map_texture_id
+            || self.texture_ids != self.previous_texture_ids
+            || bytemuck::cast_slice::<_, u8>(&self.instance_data) != bytemuck::cast_slice::<_, u8>(&self.previous_instance_data);
+        self.font_map_texture_id = font_map_texture_id;
+
+        self.dirty
+    }
+
     fn create_bind_group_bindless(
         device: &Device,
         bind_group_layout: &BindGroupLayout,
@@ -3,7 +3,7 @@ use wgpu::{ComputePass, ComputePipeline, ComputePipelineDescriptor, Device, Pipe
 use crate::graphics::passes::light_culling::LightCullingPassContext;
 use crate::graphics::passes::{BindGroupCount, ComputePassContext, Dispatch};
 use crate::graphics::shader_compiler::ShaderCompiler;
-use crate::graphics::{Capabilities, GlobalContext, ScreenSize, calculate_light_tile_count};
+use crate::graphics::{Capabilities, GlobalContext, ScreenSize, calculate_light_cluster_count};
 
 const DISPATCHER_NAME: &str = "light culling";
 
@@ -50,17 +50,35 @@ impl Dispatch<{ BindGroupCount::Two }> for LightCullingDispatcher {
 
     fn dispatch(&mut self, pass: &mut ComputePass<'_>, draw_data: Self::DispatchData<'_>) {
         pass.set_pipeline(&self.pipeline);
-        let (x, y) = calculate_dispatch_size(draw_data);
-        pass.dispatch_workgroups(x, y, 1);
+        let (x, y, z) = calculate_dispatch_size(draw_data);
+        pass.dispatch_workgroups(x, y, z);
     }
 }
 
-fn calculate_dispatch_size(forward_size: ScreenSize) -> (u32, u32) {
-    let (tiles_x, tiles_y) = calculate_light_tile_count(forward_size);
+fn calculate_dispatch_size(forward_size: ScreenSize) -> (u32, u32, u32) {
+    let (clusters_x, clusters_y, clusters_z) = calculate_light_cluster_count(forward_size);
 
-    // Round up division by workgroup size (8)
-    let dispatch_x = tiles_x.div_ceil(8);
-    let dispatch_y = tiles_y.div_ceil(8);
+    // Round up division by workgroup size (4x4x4)
+    let dispatch_x = clusters_x.div_ceil(4);
+    let dispatch_y = clusters_y.div_ceil(4);
+    let dispatch_z = clusters_z.div_ceil(4);
 
-    (dispatch_x, dispatch_y)
+    (dispatch_x, dispatch_y, dispatch_z)
+}
+
+#[cfg(test)]
+mod test {
+    use super::calculate_dispatch_size;
+    use crate::graphics::ScreenSize;
+
+    #[test]
+    fn dispatch_covers_all_clusters() {
+        // 1920x1080 is split into 30x17 tiles with 16 depth slices each.
+        let dispatch_size = calculate_dispatch_size(ScreenSize {
+            width: 1920.0,
+            height: 1080.0,
+        });
+
+        assert_eq!(dispatch_size, (8, 5, 4));
+    }
 }
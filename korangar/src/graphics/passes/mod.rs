@@ -8,8 +8,11 @@ mod point_shadow;
 mod postprocessing;
 mod screen_blit;
 mod sdsm;
+mod water;
+mod water_reflection;
 
 use std::marker::ConstParamTy;
+use std::ops::Range;
 
 use bytemuck::{Pod, Zeroable};
 pub(crate) use directional_shadow::*;
@@ -22,6 +25,8 @@ pub(crate) use point_shadow::*;
 pub(crate) use postprocessing::*;
 pub(crate) use screen_blit::*;
 pub(crate) use sdsm::*;
+pub(crate) use water::*;
+pub(crate) use water_reflection::*;
 use wgpu::{BindGroupLayout, CommandEncoder, ComputePass, Device, Queue, RenderPass, TextureFormat};
 
 use crate::graphics::shader_compiler::ShaderCompiler;
@@ -39,6 +44,7 @@ pub(crate) enum BindGroupCount {
 pub(crate) enum ColorAttachmentCount {
     None = 0,
     One = 1,
+    Two = 2,
     Three = 3,
 }
 
@@ -143,6 +149,52 @@ pub(crate) struct DrawIndexedIndirectArgs {
     first_instance: u32,
 }
 
+/// Instances that share the same texture, so they can be drawn with a single
+/// instanced draw call if the texture has to be bound explicitly.
+pub(crate) struct TextureBatch {
+    /// Index of the first instruction of the batch, which provides the
+    /// texture.
+    pub(crate) instruction_index: usize,
+    pub(crate) instances: Range<u32>,
+}
+
+/// Sorts the instructions by texture and collects the batches of instances
+/// that share a texture. `order` contains the texture id and the index of
+/// each instruction and is sorted into the order in which the instance data
+/// has to be written. The sort is stable, so instructions that share a
+/// texture keep their relative order.
+pub(crate) fn batch_by_texture(order: &mut [(u64, usize)], batches: &mut Vec<TextureBatch>) {
+    order.sort_by_key(|(texture_id, _)| *texture_id);
+    batches.clear();
+
+    for (instance, (texture_id, instruction_index)) in order.iter().enumerate() {
+        let instance = instance as u32;
+
+        match batches.last_mut() {
+            Some(batch) if order[batch.instances.start as usize].0 == *texture_id => batch.instances.end = instance + 1,
+            _ => batches.push(TextureBatch {
+                instruction_index: *instruction_index,
+                instances: instance..instance + 1,
+            }),
+        }
+    }
+}
+
+/// Splits the instructions of a model batch into runs that share a texture
+/// group. On adapters with small binding arrays, a texture set can consist of
+/// multiple texture groups and the texture index of model instructions
+/// selects the group. Each run contains the group and the range of
+/// instructions, offset by the start of the batch.
+pub(crate) fn texture_group_runs(instructions: &[ModelInstruction], offset: usize) -> impl Iterator<Item = (i32, Range<usize>)> + '_ {
+    instructions
+        .chunk_by(|left, right| left.texture_index == right.texture_index)
+        .scan(offset, |start, run| {
+            let range = *start..*start + run.len();
+            *start = range.end;
+            Some((run[0].texture_index, range))
+        })
+}
+
 /// A batch of models that share a specific texture group and model vertex
 /// buffer.
 pub(crate) struct ModelBatchDrawData<'a> {
@@ -151,3 +203,46 @@ pub(crate) struct ModelBatchDrawData<'a> {
     #[cfg(feature = "debug")]
     pub(crate) show_wireframe: bool,
 }
+
+#[cfg(test)]
+mod test {
+    use cgmath::{Matrix4, SquareMatrix};
+
+    use super::{batch_by_texture, texture_group_runs};
+    use crate::graphics::ModelInstruction;
+
+    #[test]
+    fn instructions_are_batched_by_texture() {
+        let mut order = vec![(7, 0), (3, 1), (7, 2), (3, 3), (5, 4)];
+        let mut batches = Vec::new();
+
+        batch_by_texture(&mut order, &mut batches);
+
+        assert_eq!(order, [(3, 1), (3, 3), (5, 4), (7, 0), (7, 2)]);
+        assert_eq!(batches.len(), 3);
+        assert_eq!((batches[0].instruction_index, batches[0].instances.clone()), (1, 0..2));
+        assert_eq!((batches[1].instruction_index, batches[1].instances.clone()), (4, 2..3));
+        assert_eq!((batches[2].instruction_index, batches[2].instances.clone()), (0, 3..5));
+    }
+
+    #[test]
+    fn instructions_are_split_by_texture_group() {
+        let instructions: Vec<ModelInstruction> = [0, 0, 1, 1, 1, 0]
+            .into_iter()
+            .map(|texture_index| ModelInstruction {
+                model_matrix: Matrix4::identity(),
+                index_offset: 0,
+                index_count: 0,
+                base_vertex: 0,
+                texture_index,
+                distance: 0.0,
+                transparent: false,
+                opacity: 1.0,
+            })
+            .collect();
+
+        let runs: Vec<_> = texture_group_runs(&instructions, 10).collect();
+
+        assert_eq!(runs, [(0, 10..12), (1, 12..15), (0, 15..16)]);
+    }
+}
@@ -7,18 +7,20 @@ use hashbrown::HashMap;
 use wgpu::util::StagingBelt;
 use wgpu::{
     BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource,
-    BindingType, BufferBindingType, BufferUsages, ColorTargetState, ColorWrites, CommandEncoder, CompareFunction, DepthBiasState,
-    DepthStencilState, Device, FragmentState, MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState,
-    Queue, RenderPass, RenderPipeline, RenderPipelineDescriptor, ShaderStages, StencilState, TextureSampleType, TextureView,
-    TextureViewDimension, VertexState,
+    BindingType, BufferBindingType, BufferUsages, ColorTargetState, ColorWrites, CommandEncoder, DepthBiasState, DepthStencilState, Device,
+    FragmentState, MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass,
+    RenderPipeline, RenderPipelineDescriptor, ShaderStages, StencilState, TextureSampleType, TextureView, TextureViewDimension,
+    VertexState,
 };
 
 use crate::graphics::passes::{
-    BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, Drawer, PickerRenderPassContext, RenderPassContext,
+    BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, Drawer, PickerRenderPassContext, RenderPassContext, TextureBatch,
+    batch_by_texture,
 };
 use crate::graphics::shader_compiler::ShaderCompiler;
 use crate::graphics::{
-    BindlessSupport, Buffer, Capabilities, EntityInstruction, GlobalContext, PickerTarget, Prepare, RenderInstruction, Texture,
+    BindlessSupport, Buffer, Capabilities, DEPTH_COMPARE_FUNCTION, EntityInstruction, GlobalContext, PickerTarget, Prepare,
+    RenderInstruction, Texture, create_render_pipeline,
 };
 
 const DRAWER_NAME: &str = "picker entity";
@@ -48,6 +50,8 @@ pub(crate) struct PickerEntityDrawer {
     instance_data: Vec<InstanceData>,
     bump: Bump,
     lookup: HashMap<u64, i32>,
+    texture_order: Vec<(u64, usize)>,
+    texture_batches: Vec<TextureBatch>,
 }
 
 impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttachmentCount::One }> for PickerEntityDrawer {
@@ -139,7 +143,7 @@ impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttac
             push_constant_ranges: &[],
         });
 
-        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+        let pipeline = create_render_pipeline(device, &RenderPipelineDescriptor {
             label: Some(DRAWER_NAME),
             layout: Some(&pipeline_layout),
             vertex: VertexState {
@@ -164,7 +168,7 @@ impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttac
             depth_stencil: Some(DepthStencilState {
                 format: render_pass_context.depth_attachment_output_format()[0],
                 depth_write_enabled: true,
-                depth_compare: CompareFunction::Greater,
+                depth_compare: DEPTH_COMPARE_FUNCTION,
                 stencil: StencilState::default(),
                 bias: DepthBiasState::default(),
             }),
@@ -182,6 +186,8 @@ impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttac
             instance_data: Vec::default(),
             bump: Bump::default(),
             lookup: HashMap::default(),
+            texture_order: Vec::default(),
+            texture_batches: Vec::default(),
         }
     }
 
@@ -196,22 +202,10 @@ impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttac
         if self.bindless_support {
             pass.draw(0..6, 0..self.draw_count as u32);
         } else {
-            let mut current_texture_id = self.solid_pixel_texture.get_id();
-            pass.set_bind_group(2, self.solid_pixel_texture.get_bind_group(), &[]);
-
-            draw_data
-                .iter()
-                .filter(|instruction| instruction.add_to_picker)
-                .enumerate()
-                .for_each(|(index, instruction)| {
-                    if instruction.texture.get_id() != current_texture_id {
-                        current_texture_id = instruction.texture.get_id();
-                        pass.set_bind_group(2, instruction.texture.get_bind_group(), &[]);
-                    }
-                    let index = index as u32;
-
-                    pass.draw(0..6, index..index + 1);
-                });
+            for batch in &self.texture_batches {
+                pass.set_bind_group(2, draw_data[batch.instruction_index].texture.get_bind_group(), &[]);
+                pass.draw(0..6, batch.instances.clone());
+            }
         }
     }
 }
@@ -267,10 +261,21 @@ impl Prepare for PickerEntityDrawer {
             self.instance_data_buffer.reserve(device, self.instance_data.len());
             self.bind_group = Self::create_bind_group_bindless(device, &self.bind_group_layout, &self.instance_data_buffer, &texture_views)
         } else {
-            instructions
-                .entities
+            self.texture_order.clear();
+            self.texture_order.extend(
+                instructions
+                    .entities
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, instruction)| instruction.add_to_picker)
+                    .map(|(index, instruction)| (instruction.texture.get_id(), index)),
+            );
+
+            batch_by_texture(&mut self.texture_order, &mut self.texture_batches);
+
+            self.texture_order
                 .iter()
-                .filter(|instruction| instruction.add_to_picker)
+                .map(|&(_, index)| &instructions.entities[index])
                 .for_each(|instruction| {
                     self.draw_count += 1;
 
@@ -4,16 +4,18 @@ use bytemuck::{Pod, Zeroable};
 use wgpu::util::StagingBelt;
 use wgpu::{
     BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType,
-    BufferBindingType, BufferUsages, ColorTargetState, ColorWrites, CommandEncoder, CompareFunction, DepthStencilState, Device,
-    FragmentState, MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass,
-    RenderPipeline, RenderPipelineDescriptor, ShaderStages, VertexState,
+    BufferBindingType, BufferUsages, ColorTargetState, ColorWrites, CommandEncoder, DepthStencilState, Device, FragmentState,
+    MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass, RenderPipeline,
+    RenderPipelineDescriptor, ShaderStages, VertexState,
 };
 
 use crate::graphics::passes::{
     BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, Drawer, PickerRenderPassContext, RenderPassContext,
 };
 use crate::graphics::shader_compiler::ShaderCompiler;
-use crate::graphics::{Buffer, Capabilities, GlobalContext, PickerTarget, Prepare, RenderInstruction};
+use crate::graphics::{
+    Buffer, Capabilities, DEPTH_COMPARE_FUNCTION, GlobalContext, PickerTarget, Prepare, RenderInstruction, create_render_pipeline,
+};
 
 const DRAWER_NAME: &str = "picker marker";
 const INITIAL_INSTRUCTION_SIZE: usize = 128;
@@ -113,7 +115,7 @@ impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttac
             push_constant_ranges: &[],
         });
 
-        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+        let pipeline = create_render_pipeline(device, &RenderPipelineDescriptor {
             label: Some(DRAWER_NAME),
             layout: Some(&layout),
             vertex: VertexState {
@@ -138,7 +140,7 @@ impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttac
             depth_stencil: Some(DepthStencilState {
                 format: render_pass_context.depth_attachment_output_format()[0],
                 depth_write_enabled: false,
-                depth_compare: CompareFunction::Greater,
+                depth_compare: DEPTH_COMPARE_FUNCTION,
                 stencil: Default::default(),
                 bias: Default::default(),
             }),
@@ -13,7 +13,7 @@ use wgpu::{
 };
 
 use super::{BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, RenderPassContext};
-use crate::graphics::{GlobalContext, PickerTarget};
+use crate::graphics::{DEPTH_CLEAR_VALUE, GlobalContext, PickerTarget};
 use crate::loaders::TextureLoader;
 
 const PASS_NAME: &str = "picker render pass";
@@ -64,7 +64,7 @@ impl RenderPassContext<{ BindGroupCount::One }, { ColorAttachmentCount::One }, {
             depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
                 view: global_context.picker_depth_texture.get_texture_view(),
                 depth_ops: Some(Operations {
-                    load: LoadOp::Clear(0.0),
+                    load: LoadOp::Clear(DEPTH_CLEAR_VALUE),
                     store: StoreOp::Discard,
                 }),
                 stencil_ops: None,
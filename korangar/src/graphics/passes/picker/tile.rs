@@ -1,7 +1,6 @@
 use wgpu::{
-    ColorTargetState, ColorWrites, CompareFunction, DepthStencilState, Device, FragmentState, IndexFormat, MultisampleState,
-    PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass, RenderPipeline, RenderPipelineDescriptor,
-    VertexState,
+    ColorTargetState, ColorWrites, DepthStencilState, Device, FragmentState, IndexFormat, MultisampleState, PipelineCompilationOptions,
+    PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass, RenderPipeline, RenderPipelineDescriptor, VertexState,
 };
 
 use crate::graphics::passes::{
@@ -9,7 +8,7 @@ use crate::graphics::passes::{
 };
 use crate::graphics::picker_target::PickerValueType;
 use crate::graphics::shader_compiler::ShaderCompiler;
-use crate::graphics::{Buffer, Capabilities, GlobalContext, TileVertex};
+use crate::graphics::{Buffer, Capabilities, DEPTH_COMPARE_FUNCTION, GlobalContext, TileVertex, create_render_pipeline};
 
 const DRAWER_NAME: &str = "picker tile";
 
@@ -47,7 +46,7 @@ impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttac
             ("0", PickerValueType::Tile as u32 as f64),
         ];
 
-        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+        let pipeline = create_render_pipeline(device, &RenderPipelineDescriptor {
             label: Some(DRAWER_NAME),
             layout: Some(&layout),
             vertex: VertexState {
@@ -78,7 +77,7 @@ impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttac
             depth_stencil: Some(DepthStencilState {
                 format: render_pass_context.depth_attachment_output_format()[0],
                 depth_write_enabled: true,
-                depth_compare: CompareFunction::Greater,
+                depth_compare: DEPTH_COMPARE_FUNCTION,
                 stencil: Default::default(),
                 bias: Default::default(),
             }),
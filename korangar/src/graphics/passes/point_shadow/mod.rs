@@ -18,7 +18,9 @@ use wgpu::{
 
 use super::{BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, RenderPassContext};
 use crate::graphics::buffer::DynamicUniformBuffer;
-use crate::graphics::{EntityInstruction, GlobalContext, ModelInstruction, PointLightWithShadowInstruction, Prepare, RenderInstruction};
+use crate::graphics::{
+    DEPTH_CLEAR_VALUE, EntityInstruction, GlobalContext, ModelInstruction, PointLightWithShadowInstruction, Prepare, RenderInstruction,
+};
 use crate::loaders::TextureLoader;
 
 const PASS_NAME: &str = "point shadow render pass";
@@ -33,6 +35,7 @@ struct PassUniforms {
     light_position: [f32; 4],
     animation_timer: f32,
     padding: [u32; 3],
+    wind: [f32; 4],
 }
 
 #[derive(Copy, Clone)]
@@ -96,7 +99,7 @@ impl RenderPassContext<{ BindGroupCount::Two }, { ColorAttachmentCount::None },
                     .point_shadow_map_textures
                     .get_texture_face_view(pass_data.shadow_caster_index, pass_data.face_index),
                 depth_ops: Some(Operations {
-                    load: LoadOp::Clear(0.0),
+                    load: LoadOp::Clear(DEPTH_CLEAR_VALUE),
                     store: StoreOp::Store,
                 }),
                 stencil_ops: None,
@@ -156,6 +159,7 @@ impl Prepare for PointShadowRenderPassContext {
                 light_position: caster.position.to_homogeneous().into(),
                 animation_timer: instructions.uniforms.animation_timer_ms / 1000.0,
                 padding: Default::default(),
+                wind: instructions.uniforms.wind.into(),
             })
         });
         self.uniforms_buffer.write_data(uniforms);
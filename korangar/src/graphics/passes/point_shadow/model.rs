@@ -4,17 +4,20 @@ use bytemuck::{Pod, Zeroable};
 use wgpu::util::StagingBelt;
 use wgpu::{
     BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType,
-    BufferAddress, BufferBindingType, BufferUsages, CommandEncoder, CompareFunction, DepthStencilState, Device, FragmentState, IndexFormat,
+    BufferAddress, BufferBindingType, BufferUsages, CommandEncoder, DepthStencilState, Device, FragmentState, IndexFormat,
     MultisampleState, PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass, RenderPipeline,
     RenderPipelineDescriptor, ShaderStages, VertexState,
 };
 
 use crate::graphics::passes::{
     BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, DrawIndexedIndirectArgs, Drawer, PointShadowModelBatchData,
-    PointShadowRenderPassContext, RenderPassContext,
+    PointShadowRenderPassContext, RenderPassContext, texture_group_runs,
 };
 use crate::graphics::shader_compiler::ShaderCompiler;
-use crate::graphics::{BindlessSupport, Buffer, Capabilities, GlobalContext, ModelVertex, Prepare, RenderInstruction, Texture, TextureSet};
+use crate::graphics::{
+    BindlessSupport, Buffer, Capabilities, DEPTH_COMPARE_FUNCTION, GlobalContext, ModelVertex, Prepare, RenderInstruction, Texture,
+    TextureSet, create_render_pipeline,
+};
 
 const DRAWER_NAME: &str = "point shadow model";
 const INITIAL_INSTRUCTION_SIZE: usize = 256;
@@ -102,7 +105,7 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::None }, { DepthAtta
             push_constant_ranges: &[],
         });
 
-        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+        let pipeline = create_render_pipeline(device, &RenderPipelineDescriptor {
             label: Some(DRAWER_NAME),
             layout: Some(&pipeline_layout),
             vertex: VertexState {
@@ -123,7 +126,7 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::None }, { DepthAtta
             depth_stencil: Some(DepthStencilState {
                 format: render_pass_context.depth_attachment_output_format()[0],
                 depth_write_enabled: true,
-                depth_compare: CompareFunction::Greater,
+                depth_compare: DEPTH_COMPARE_FUNCTION,
                 stencil: Default::default(),
                 bias: Default::default(),
             }),
@@ -159,30 +162,32 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::None }, { DepthAtta
 
         match self.bindless_support {
             BindlessSupport::Full | BindlessSupport::Limited => {
-                pass.set_bind_group(3, batch.model_texture_set.get_bind_group().unwrap(), &[]);
                 pass.set_index_buffer(batch.model_index_buffer.slice(..), IndexFormat::Uint32);
                 pass.set_vertex_buffer(0, batch.model_vertex_buffer.slice(..));
 
-                if self.multi_draw_indirect_support {
-                    pass.multi_draw_indexed_indirect(
-                        self.command_buffer.get_buffer(),
-                        (offset * size_of::<DrawIndexedIndirectArgs>()) as BufferAddress,
-                        count as u32,
-                    );
-                } else {
-                    let start = offset;
-                    let end = start + count;
-
-                    for (index, instruction) in draw_data.instructions[start..end].iter().enumerate() {
-                        let index_start = instruction.index_offset;
-                        let index_end = index_start + instruction.index_count;
-                        let instance_offset = (start + index) as u32;
-
-                        pass.draw_indexed(
-                            index_start..index_end,
-                            instruction.base_vertex,
-                            instance_offset..instance_offset + 1,
+                let instructions = &draw_data.instructions[offset..offset + count];
+
+                for (texture_group, run) in texture_group_runs(instructions, offset) {
+                    pass.set_bind_group(3, batch.model_texture_set.get_bind_group(texture_group), &[]);
+
+                    if self.multi_draw_indirect_support {
+                        pass.multi_draw_indexed_indirect(
+                            self.command_buffer.get_buffer(),
+                            (run.start * size_of::<DrawIndexedIndirectArgs>()) as BufferAddress,
+                            run.len() as u32,
                         );
+                    } else {
+                        for (index, instruction) in draw_data.instructions[run.clone()].iter().enumerate() {
+                            let index_start = instruction.index_offset;
+                            let index_end = index_start + instruction.index_count;
+                            let instance_offset = (run.start + index) as u32;
+
+                            pass.draw_indexed(
+                                index_start..index_end,
+                                instruction.base_vertex,
+                                instance_offset..instance_offset + 1,
+                            );
+                        }
                     }
                 }
             }
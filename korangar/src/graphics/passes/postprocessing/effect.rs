@@ -1,4 +1,5 @@
 use std::num::{NonZeroU32, NonZeroU64};
+use std::ops::Range;
 use std::sync::Arc;
 
 use bumpalo::Bump;
@@ -17,11 +18,18 @@ use crate::graphics::passes::{
     BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, Drawer, PostProcessingRenderPassContext, RenderPassContext,
 };
 use crate::graphics::shader_compiler::ShaderCompiler;
-use crate::graphics::{BindlessSupport, Buffer, Capabilities, EffectInstruction, GlobalContext, Prepare, RenderInstruction, Texture};
+use crate::graphics::{
+    BindlessSupport, Buffer, Capabilities, EffectInstruction, GlobalContext, Prepare, RenderInstruction, Texture,
+    WBOIT_ACCUMULATION_TEXTURE_FORMAT, WBOIT_REVEALAGE_TEXTURE_FORMAT,
+};
 
 const DRAWER_NAME: &str = "post processing effect";
 const INITIAL_INSTRUCTION_SIZE: usize = 256;
 
+/// Effects with this blend state are alpha blended. Only they can be blended
+/// order independent, all other blend states need to be applied in order.
+const ALPHA_BLENDING: (BlendFactor, BlendFactor) = (BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha);
+
 /// These are the TOP5 combinations we currently find in the korean client
 /// files and will preload at start.
 const PRELOAD_PIPELINES: &[(BlendFactor, BlendFactor)] = &[
@@ -54,11 +62,26 @@ pub(crate) struct InstanceData {
     color0: [f32; 2],
     color1: [f32; 2],
     texture_index: i32,
-    padding: u32,
+    depth: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EffectPassMode {
+    /// Draws all effects that are blended in order.
+    Blended,
+    /// Draws all alpha blended effects into the WBOIT targets, if order
+    /// independent effects are enabled.
+    OrderIndependent,
+}
+
+pub(crate) struct PostProcessingEffectDrawData<'a> {
+    pub(crate) instructions: &'a [EffectInstruction],
+    pub(crate) pass_mode: EffectPassMode,
 }
 
 pub(crate) struct PostProcessingEffectDrawer {
     bindless_support: bool,
+    order_independent_effects: bool,
     solid_pixel_texture: Arc<Texture>,
     instance_data_buffer: Buffer<InstanceData>,
     bind_group_layout: BindGroupLayout,
@@ -67,15 +90,21 @@ pub(crate) struct PostProcessingEffectDrawer {
     pipeline_layout: PipelineLayout,
     color_attachment_format: TextureFormat,
     pipelines: HashMap<(BlendFactor, BlendFactor), RenderPipeline>,
+    wboit_pipeline: RenderPipeline,
     instance_data: Vec<InstanceData>,
     bump: Bump,
     lookup: HashMap<u64, i32>,
     batches: Vec<EffectBatch>,
+    /// Indices of the effect instructions in the order of the instance data.
+    draw_order: Vec<usize>,
+    /// Number of effects at the start of the instance data that are blended
+    /// order independent.
+    order_independent_count: usize,
 }
 
 impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttachmentCount::None }> for PostProcessingEffectDrawer {
     type Context = PostProcessingRenderPassContext;
-    type DrawData<'data> = &'data [EffectInstruction];
+    type DrawData<'data> = PostProcessingEffectDrawData<'data>;
 
     fn new(
         capabilities: &Capabilities,
@@ -177,8 +206,11 @@ impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttac
             pipelines.insert((source_blend_factor, destination_blend_factor), pipeline);
         }
 
+        let wboit_pipeline = Self::create_wboit_pipeline(device, &shader_module, &pipeline_layout);
+
         Self {
             bindless_support: capabilities.bindless_support() == BindlessSupport::Full,
+            order_independent_effects: global_context.order_independent_effects,
             solid_pixel_texture: global_context.solid_pixel_texture.clone(),
             instance_data_buffer,
             bind_group_layout,
@@ -187,43 +219,47 @@ impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttac
             pipeline_layout,
             color_attachment_format,
             pipelines,
+            wboit_pipeline,
             instance_data: Vec::default(),
             bump: Bump::default(),
             lookup: HashMap::default(),
             batches: Vec::default(),
+            draw_order: Vec::default(),
+            order_independent_count: 0,
         }
     }
 
     fn draw(&mut self, pass: &mut RenderPass<'_>, draw_data: Self::DrawData<'_>) {
-        if self.batches.is_empty() {
-            return;
-        }
-
-        pass.set_bind_group(1, &self.bind_group, &[]);
-
-        for batch in self.batches.drain(..) {
-            if let Some(pipeline) = self.pipelines.get(&batch.blend_state) {
-                pass.set_pipeline(pipeline);
+        match draw_data.pass_mode {
+            EffectPassMode::Blended => {
+                if self.batches.is_empty() {
+                    return;
+                }
 
-                let start = batch.offset as u32;
-                let end = start + batch.count as u32;
+                pass.set_bind_group(1, &self.bind_group, &[]);
 
-                if self.bindless_support {
-                    pass.draw(0..6, start..end);
-                } else {
-                    let mut current_texture_id = self.solid_pixel_texture.get_id();
-                    pass.set_bind_group(2, self.solid_pixel_texture.get_bind_group(), &[]);
+                for batch in self.batches.iter() {
+                    if let Some(pipeline) = self.pipelines.get(&batch.blend_state) {
+                        pass.set_pipeline(pipeline);
 
-                    for (index, instruction) in draw_data[start as usize..end as usize].iter().enumerate() {
-                        if instruction.texture.get_id() != current_texture_id {
-                            current_texture_id = instruction.texture.get_id();
-                            pass.set_bind_group(2, instruction.texture.get_bind_group(), &[]);
-                        }
-                        let index = start + index as u32;
+                        let start = batch.offset as u32;
+                        let end = start + batch.count as u32;
 
-                        pass.draw(0..6, index..index + 1);
+                        self.draw_instances(pass, draw_data.instructions, start..end);
                     }
                 }
+
+                self.batches.clear();
+            }
+            EffectPassMode::OrderIndependent => {
+                if self.order_independent_count == 0 {
+                    return;
+                }
+
+                pass.set_bind_group(1, &self.bind_group, &[]);
+                pass.set_pipeline(&self.wboit_pipeline);
+
+                self.draw_instances(pass, draw_data.instructions, 0..self.order_independent_count as u32);
             }
         }
     }
@@ -233,6 +269,9 @@ impl Prepare for PostProcessingEffectDrawer {
     fn prepare(&mut self, device: &Device, instructions: &RenderInstruction) {
         let draw_count = instructions.effects.len();
 
+        self.draw_order.clear();
+        self.order_independent_count = 0;
+
         if draw_count == 0 {
             return;
         }
@@ -240,20 +279,34 @@ impl Prepare for PostProcessingEffectDrawer {
         self.instance_data.clear();
         self.batches.clear();
 
-        let first_effect = &instructions.effects[0];
-        let mut blend_state = (first_effect.source_blend_factor, first_effect.destination_blend_factor);
-        let mut offset = 0;
+        // Order independent effects are placed at the start of the instance data, so
+        // that they can be drawn with a single draw call.
+        let order_independent_effects = self.order_independent_effects;
+        let is_order_independent = |index: &usize| {
+            let instruction = &instructions.effects[*index];
+            order_independent_effects && (instruction.source_blend_factor, instruction.destination_blend_factor) == ALPHA_BLENDING
+        };
 
-        if self.bindless_support {
-            self.bump.reset();
-            self.lookup.clear();
+        self.draw_order.extend((0..draw_count).filter(is_order_independent));
+        self.order_independent_count = self.draw_order.len();
+        self.draw_order.extend((0..draw_count).filter(|index| !is_order_independent(index)));
+
+        self.bump.reset();
+        self.lookup.clear();
 
-            let mut texture_views = Vec::with_capacity_in(draw_count, &self.bump);
+        let mut texture_views = Vec::with_capacity_in(draw_count, &self.bump);
+        let mut blend_state = None;
+        let mut offset = self.order_independent_count;
 
-            for (index, instruction) in instructions.effects.iter().enumerate() {
+        for (position, &index) in self.draw_order.iter().enumerate() {
+            let instruction = &instructions.effects[index];
+
+            if position >= self.order_independent_count {
                 let effect_blend_state = (instruction.source_blend_factor, instruction.destination_blend_factor);
 
-                if effect_blend_state != blend_state {
+                if let Some(blend_state) = blend_state
+                    && blend_state != effect_blend_state
+                {
                     Self::push_effect_batch(
                         device,
                         &mut self.pipelines,
@@ -262,15 +315,20 @@ impl Prepare for PostProcessingEffectDrawer {
                         self.color_attachment_format,
                         &self.pipeline_layout,
                         blend_state,
-                        self.instance_data.len() - offset,
+                        position - offset,
                         offset,
                     );
 
-                    blend_state = effect_blend_state;
-                    offset = index;
+                    offset = position;
                 }
 
-                let mut texture_index = texture_views.len() as i32;
+                blend_state = Some(effect_blend_state);
+            }
+
+            let mut texture_index = 0;
+
+            if self.bindless_support {
+                texture_index = texture_views.len() as i32;
                 let id = instruction.texture.get_id();
                 let potential_index = self.lookup.get(&id);
 
@@ -280,24 +338,26 @@ impl Prepare for PostProcessingEffectDrawer {
                     self.lookup.insert(id, texture_index);
                     texture_views.push(instruction.texture.get_texture_view());
                 }
-
-                let color = instruction.color.components_linear();
-                self.instance_data.push(InstanceData {
-                    top_left: instruction.top_left.into(),
-                    bottom_left: instruction.bottom_left.into(),
-                    top_right: instruction.top_right.into(),
-                    bottom_right: instruction.bottom_right.into(),
-                    texture_top_left: instruction.texture_top_left.into(),
-                    texture_bottom_left: instruction.texture_bottom_left.into(),
-                    texture_top_right: instruction.texture_top_right.into(),
-                    texture_bottom_right: instruction.texture_bottom_right.into(),
-                    color0: [color[0], color[1]],
-                    color1: [color[2], color[3]],
-                    texture_index,
-                    padding: 0,
-                });
             }
 
+            let color = instruction.color.components_linear();
+            self.instance_data.push(InstanceData {
+                top_left: instruction.top_left.into(),
+                bottom_left: instruction.bottom_left.into(),
+                top_right: instruction.top_right.into(),
+                bottom_right: instruction.bottom_right.into(),
+                texture_top_left: instruction.texture_top_left.into(),
+                texture_bottom_left: instruction.texture_bottom_left.into(),
+                texture_top_right: instruction.texture_top_right.into(),
+                texture_bottom_right: instruction.texture_bottom_right.into(),
+                color0: [color[0], color[1]],
+                color1: [color[2], color[3]],
+                texture_index,
+                depth: instruction.depth,
+            });
+        }
+
+        if let Some(blend_state) = blend_state {
             Self::push_effect_batch(
                 device,
                 &mut self.pipelines,
@@ -309,64 +369,17 @@ impl Prepare for PostProcessingEffectDrawer {
                 self.instance_data.len() - offset,
                 offset,
             );
+        }
+
+        self.instance_data_buffer.reserve(device, self.instance_data.len());
 
+        if self.bindless_support {
             if texture_views.is_empty() {
                 texture_views.push(self.solid_pixel_texture.get_texture_view());
             }
 
-            self.instance_data_buffer.reserve(device, self.instance_data.len());
             self.bind_group = Self::create_bind_group_bindless(device, &self.bind_group_layout, &self.instance_data_buffer, &texture_views)
         } else {
-            for (index, instruction) in instructions.effects.iter().enumerate() {
-                let effect_blend_state = (instruction.source_blend_factor, instruction.destination_blend_factor);
-
-                if effect_blend_state != blend_state {
-                    Self::push_effect_batch(
-                        device,
-                        &mut self.pipelines,
-                        &mut self.batches,
-                        &self.shader_module,
-                        self.color_attachment_format,
-                        &self.pipeline_layout,
-                        blend_state,
-                        self.instance_data.len() - offset,
-                        offset,
-                    );
-
-                    blend_state = effect_blend_state;
-                    offset = index;
-                }
-
-                let color = instruction.color.components_linear();
-                self.instance_data.push(InstanceData {
-                    top_left: instruction.top_left.into(),
-                    bottom_left: instruction.bottom_left.into(),
-                    top_right: instruction.top_right.into(),
-                    bottom_right: instruction.bottom_right.into(),
-                    texture_top_left: instruction.texture_top_left.into(),
-                    texture_bottom_left: instruction.texture_bottom_left.into(),
-                    texture_top_right: instruction.texture_top_right.into(),
-                    texture_bottom_right: instruction.texture_bottom_right.into(),
-                    color0: [color[0], color[1]],
-                    color1: [color[2], color[3]],
-                    texture_index: 0,
-                    padding: 0,
-                });
-            }
-
-            Self::push_effect_batch(
-                device,
-                &mut self.pipelines,
-                &mut self.batches,
-                &self.shader_module,
-                self.color_attachment_format,
-                &self.pipeline_layout,
-                blend_state,
-                self.instance_data.len() - offset,
-                offset,
-            );
-
-            self.instance_data_buffer.reserve(device, self.instance_data.len());
             self.bind_group = Self::create_bind_group(device, &self.bind_group_layout, &self.instance_data_buffer)
         }
     }
@@ -378,6 +391,37 @@ impl Prepare for PostProcessingEffectDrawer {
 }
 
 impl PostProcessingEffectDrawer {
+    pub(crate) fn set_order_independent_effects(&mut self, order_independent_effects: bool) {
+        self.order_independent_effects = order_independent_effects;
+    }
+
+    /// Returns `true` if the current frame has effects that need to be drawn
+    /// into the WBOIT targets.
+    pub(crate) fn has_order_independent_effects(&self) -> bool {
+        self.order_independent_count > 0
+    }
+
+    fn draw_instances(&self, pass: &mut RenderPass<'_>, instructions: &[EffectInstruction], instances: Range<u32>) {
+        if self.bindless_support {
+            pass.draw(0..6, instances);
+            return;
+        }
+
+        let mut current_texture_id = self.solid_pixel_texture.get_id();
+        pass.set_bind_group(2, self.solid_pixel_texture.get_bind_group(), &[]);
+
+        for index in instances {
+            let instruction = &instructions[self.draw_order[index as usize]];
+
+            if instruction.texture.get_id() != current_texture_id {
+                current_texture_id = instruction.texture.get_id();
+                pass.set_bind_group(2, instruction.texture.get_bind_group(), &[]);
+            }
+
+            pass.draw(0..6, index..index + 1);
+        }
+    }
+
     fn create_bind_group_bindless(
         device: &Device,
         bind_group_layout: &BindGroupLayout,
@@ -457,6 +501,59 @@ impl PostProcessingEffectDrawer {
         })
     }
 
+    fn create_wboit_pipeline(device: &Device, shader_module: &ShaderModule, pipeline_layout: &PipelineLayout) -> RenderPipeline {
+        device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some(DRAWER_NAME),
+            layout: Some(pipeline_layout),
+            vertex: VertexState {
+                module: shader_module,
+                entry_point: Some("vs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(FragmentState {
+                module: shader_module,
+                entry_point: Some("fs_wboit"),
+                compilation_options: PipelineCompilationOptions::default(),
+                targets: &[
+                    Some(ColorTargetState {
+                        format: WBOIT_ACCUMULATION_TEXTURE_FORMAT,
+                        blend: Some(BlendState {
+                            color: BlendComponent {
+                                src_factor: BlendFactor::One,
+                                dst_factor: BlendFactor::One,
+                                operation: BlendOperation::Add,
+                            },
+                            alpha: BlendComponent {
+                                src_factor: BlendFactor::One,
+                                dst_factor: BlendFactor::One,
+                                operation: BlendOperation::Add,
+                            },
+                        }),
+                        write_mask: ColorWrites::ALL,
+                    }),
+                    Some(ColorTargetState {
+                        format: WBOIT_REVEALAGE_TEXTURE_FORMAT,
+                        blend: Some(BlendState {
+                            color: BlendComponent {
+                                src_factor: BlendFactor::Zero,
+                                dst_factor: BlendFactor::OneMinusSrc,
+                                operation: BlendOperation::Add,
+                            },
+                            alpha: BlendComponent::default(),
+                        }),
+                        write_mask: ColorWrites::RED,
+                    }),
+                ],
+            }),
+            primitive: PrimitiveState::default(),
+            multisample: MultisampleState::default(),
+            depth_stencil: None,
+            multiview: None,
+            cache: None,
+        })
+    }
+
     fn push_effect_batch(
         device: &Device,
         pipelines: &mut HashMap<(BlendFactor, BlendFactor), RenderPipeline>,
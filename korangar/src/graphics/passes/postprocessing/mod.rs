@@ -1,4 +1,5 @@
 mod blitter;
+mod crt;
 #[cfg(feature = "debug")]
 mod debug_aabb;
 #[cfg(feature = "debug")]
@@ -13,6 +14,7 @@ mod rectangle;
 mod wboit_resolve;
 
 pub(crate) use blitter::{PostProcessingBlitterDrawData, PostProcessingBlitterDrawer};
+pub(crate) use crt::PostProcessingCrtDrawer;
 #[cfg(feature = "debug")]
 pub(crate) use debug_aabb::DebugAabbDrawer;
 #[cfg(feature = "debug")]
@@ -21,19 +23,22 @@ pub(crate) use debug_buffer::{DebugBufferDrawData, DebugBufferDrawer};
 pub(crate) use debug_circle::DebugCircleDrawer;
 #[cfg(feature = "debug")]
 pub(crate) use debug_rectangle::DebugRectangleDrawer;
-pub(crate) use effect::PostProcessingEffectDrawer;
+pub(crate) use effect::{EffectPassMode, PostProcessingEffectDrawData, PostProcessingEffectDrawer};
 pub(crate) use fxaa::PostProcessingFxaaDrawer;
 pub(crate) use rectangle::{PostProcessingRectangleDrawData, PostProcessingRectangleDrawer, PostProcessingRectangleLayer};
 pub(crate) use wboit_resolve::{PostProcessingWboitResolveDrawData, PostProcessingWboitResolveDrawer};
 use wgpu::{
-    BindGroupLayout, CommandEncoder, Device, LoadOp, Operations, Queue, RenderPass, RenderPassColorAttachment, RenderPassDescriptor,
+    BindGroupLayout, Color, CommandEncoder, Device, LoadOp, Operations, Queue, RenderPass, RenderPassColorAttachment, RenderPassDescriptor,
     StoreOp, TextureFormat,
 };
 
 use super::{BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, RenderPassContext};
-use crate::graphics::{AttachmentTexture, GlobalContext};
+use crate::graphics::{
+    AttachmentTexture, EffectWboitTextures, GlobalContext, WBOIT_ACCUMULATION_TEXTURE_FORMAT, WBOIT_REVEALAGE_TEXTURE_FORMAT,
+};
 use crate::loaders::TextureLoader;
 const PASS_NAME: &str = "post processing render pass";
+const EFFECT_WBOIT_PASS_NAME: &str = "effect wboit render pass";
 
 pub(crate) struct PostProcessingRenderPassContext {
     color_texture_format: TextureFormat,
@@ -92,3 +97,66 @@ impl RenderPassContext<{ BindGroupCount::One }, { ColorAttachmentCount::One }, {
         []
     }
 }
+
+/// Accumulates the effects that are blended independent of their draw order.
+/// The result is resolved inside the post processing pass.
+pub(crate) struct EffectWboitRenderPassContext {}
+
+impl RenderPassContext<{ BindGroupCount::One }, { ColorAttachmentCount::Two }, { DepthAttachmentCount::None }>
+    for EffectWboitRenderPassContext
+{
+    type PassData<'data> = &'data EffectWboitTextures;
+
+    fn new(_device: &Device, _queue: &Queue, _texture_loader: &TextureLoader, _global_context: &GlobalContext) -> Self {
+        Self {}
+    }
+
+    fn create_pass<'encoder>(
+        &mut self,
+        encoder: &'encoder mut CommandEncoder,
+        global_context: &GlobalContext,
+        pass_data: Self::PassData<'_>,
+    ) -> RenderPass<'encoder> {
+        let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            label: Some(EFFECT_WBOIT_PASS_NAME),
+            color_attachments: &[
+                Some(RenderPassColorAttachment {
+                    view: pass_data.accumulation_texture.get_texture_view(),
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Clear(Color::TRANSPARENT),
+                        store: StoreOp::Store,
+                    },
+                }),
+                Some(RenderPassColorAttachment {
+                    view: pass_data.revealage_texture.get_texture_view(),
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Clear(Color::WHITE),
+                        store: StoreOp::Store,
+                    },
+                }),
+            ],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        pass.set_bind_group(0, &global_context.global_bind_group, &[]);
+        pass
+    }
+
+    fn bind_group_layout(device: &Device) -> [&'static BindGroupLayout; 1] {
+        [GlobalContext::global_bind_group_layout(device)]
+    }
+
+    fn color_attachment_formats(&self) -> [TextureFormat; 2] {
+        [WBOIT_ACCUMULATION_TEXTURE_FORMAT, WBOIT_REVEALAGE_TEXTURE_FORMAT]
+    }
+
+    fn depth_attachment_output_format(&self) -> [TextureFormat; 0] {
+        []
+    }
+}
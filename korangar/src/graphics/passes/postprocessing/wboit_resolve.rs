@@ -1,14 +1,14 @@
 use wgpu::{
     BlendComponent, BlendFactor, BlendOperation, BlendState, ColorTargetState, ColorWrites, Device, FragmentState, MultisampleState,
     PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass, RenderPipeline, RenderPipelineDescriptor,
-    ShaderModuleDescriptor, TextureSampleType, TextureViewDimension, VertexState, include_wgsl,
+    ShaderModuleDescriptor, TextureFormat, TextureSampleType, TextureViewDimension, VertexState, include_wgsl,
 };
 
 use crate::graphics::passes::{
     BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, Drawer, PostProcessingRenderPassContext, RenderPassContext,
 };
 use crate::graphics::shader_compiler::ShaderCompiler;
-use crate::graphics::{AttachmentTexture, Capabilities, GlobalContext};
+use crate::graphics::{AttachmentTexture, Capabilities, GlobalContext, Msaa};
 
 const SHADER_MSAA: ShaderModuleDescriptor = include_wgsl!("../../../../shaders/passes/postprocessing/wboit_resolve_msaa.wgsl");
 const DRAWER_NAME: &str = "post processing wboit resolve";
@@ -20,6 +20,9 @@ pub(crate) struct PostProcessingWboitResolveDrawData<'a> {
 
 pub(crate) struct PostProcessingWboitResolveDrawer {
     pipeline: RenderPipeline,
+    /// Resolves single sampled targets, like the ones of the effects, while
+    /// multisampling is activated.
+    single_sampled_pipeline: Option<RenderPipeline>,
 }
 
 impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttachmentCount::None }> for PostProcessingWboitResolveDrawer {
@@ -34,7 +37,41 @@ impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttac
         global_context: &GlobalContext,
         render_pass_context: &Self::Context,
     ) -> Self {
-        let msaa_activated = global_context.msaa.multisampling_activated();
+        let color_texture_format = render_pass_context.color_attachment_formats()[0];
+
+        let pipeline = Self::create_pipeline(device, shader_compiler, color_texture_format, global_context.msaa);
+        let single_sampled_pipeline = global_context
+            .msaa
+            .multisampling_activated()
+            .then(|| Self::create_pipeline(device, shader_compiler, color_texture_format, Msaa::Off));
+
+        Self {
+            pipeline,
+            single_sampled_pipeline,
+        }
+    }
+
+    fn draw(&mut self, pass: &mut RenderPass<'_>, draw_data: Self::DrawData<'_>) {
+        let pipeline = match draw_data.accumulation_texture.get_texture().sample_count() {
+            1 => self.single_sampled_pipeline.as_ref().unwrap_or(&self.pipeline),
+            _ => &self.pipeline,
+        };
+
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(1, draw_data.accumulation_texture.get_bind_group(), &[]);
+        pass.set_bind_group(2, draw_data.revealage_texture.get_bind_group(), &[]);
+        pass.draw(0..3, 0..1);
+    }
+}
+
+impl PostProcessingWboitResolveDrawer {
+    fn create_pipeline(
+        device: &Device,
+        shader_compiler: &ShaderCompiler,
+        color_texture_format: TextureFormat,
+        msaa: Msaa,
+    ) -> RenderPipeline {
+        let msaa_activated = msaa.multisampling_activated();
 
         let shader_module = if msaa_activated {
             device.create_shader_module(SHADER_MSAA)
@@ -42,8 +79,6 @@ impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttac
             shader_compiler.create_shader_module("postprocessing", "wboit_resolve")
         };
 
-        let color_texture_format = render_pass_context.color_attachment_formats()[0];
-
         let texture_bind_group_layout = AttachmentTexture::bind_group_layout(
             device,
             TextureViewDimension::D2,
@@ -53,16 +88,16 @@ impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttac
             msaa_activated,
         );
 
-        let pass_bind_group_layouts = Self::Context::bind_group_layout(device);
+        let pass_bind_group_layouts = PostProcessingRenderPassContext::bind_group_layout(device);
         let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
             label: Some(DRAWER_NAME),
             bind_group_layouts: &[pass_bind_group_layouts[0], &texture_bind_group_layout, &texture_bind_group_layout],
             push_constant_ranges: &[],
         });
 
-        let constants = &[("MSAA_SAMPLE_COUNT", f64::from(global_context.msaa.sample_count()))];
+        let constants = &[("MSAA_SAMPLE_COUNT", f64::from(msaa.sample_count()))];
 
-        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+        device.create_render_pipeline(&RenderPipelineDescriptor {
             label: Some(DRAWER_NAME),
             layout: Some(&pipeline_layout),
             vertex: VertexState {
@@ -103,15 +138,6 @@ impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::One }, { DepthAttac
             multisample: MultisampleState::default(),
             multiview: None,
             cache: None,
-        });
-
-        Self { pipeline }
-    }
-
-    fn draw(&mut self, pass: &mut RenderPass<'_>, draw_data: Self::DrawData<'_>) {
-        pass.set_pipeline(&self.pipeline);
-        pass.set_bind_group(1, draw_data.accumulation_texture.get_bind_group(), &[]);
-        pass.set_bind_group(2, draw_data.revealage_texture.get_bind_group(), &[]);
-        pass.draw(0..3, 0..1);
+        })
     }
 }
@@ -5,16 +5,19 @@ use wgpu::util::StagingBelt;
 use wgpu::{
     BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource,
     BindingType, BlendComponent, BlendFactor, BlendOperation, BlendState, BufferBindingType, BufferUsages, ColorTargetState, ColorWrites,
-    CommandEncoder, CompareFunction, DepthBiasState, DepthStencilState, Device, FragmentState, MultisampleState,
-    PipelineCompilationOptions, PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass, RenderPipeline, RenderPipelineDescriptor,
-    ShaderStages, StencilState, TextureSampleType, TextureViewDimension, VertexState,
+    CommandEncoder, DepthBiasState, DepthStencilState, Device, FragmentState, MultisampleState, PipelineCompilationOptions,
+    PipelineLayoutDescriptor, PrimitiveState, Queue, RenderPass, RenderPipeline, RenderPipelineDescriptor, ShaderStages, StencilState,
+    TextureSampleType, TextureViewDimension, VertexState,
 };
 
 use crate::graphics::passes::{
-    BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, Drawer, ForwardRenderPassContext, RenderPassContext,
+    BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, Drawer, RenderPassContext, WaterRenderPassContext,
 };
 use crate::graphics::shader_compiler::ShaderCompiler;
-use crate::graphics::{Buffer, Capabilities, GlobalContext, Prepare, RenderInstruction, Texture, WaterInstruction, WaterVertex};
+use crate::graphics::{
+    Buffer, Capabilities, DEPTH_COMPARE_FUNCTION, GlobalContext, Prepare, RenderInstruction, Texture, WaterInstruction, WaterVertex,
+    create_render_pipeline,
+};
 
 const DRAWER_NAME: &str = "water wave";
 
@@ -26,6 +29,13 @@ struct WaterWaveUniforms {
     waveform_amplitude: f32,
     waveform_frequency: f32,
     water_opacity: f32,
+    water_quality: u32,
+    padding: [u32; 2],
+}
+
+pub(crate) struct WaterWaveDrawData<'a> {
+    pub(crate) instruction: &'a WaterInstruction<'a>,
+    pub(crate) water_bind_group: &'a BindGroup,
 }
 
 pub(crate) struct WaterWaveDrawer {
@@ -36,9 +46,9 @@ pub(crate) struct WaterWaveDrawer {
     pipeline: RenderPipeline,
 }
 
-impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAttachmentCount::One }> for WaterWaveDrawer {
-    type Context = ForwardRenderPassContext;
-    type DrawData<'data> = &'data WaterInstruction<'data>;
+impl Drawer<{ BindGroupCount::One }, { ColorAttachmentCount::Three }, { DepthAttachmentCount::One }> for WaterWaveDrawer {
+    type Context = WaterRenderPassContext;
+    type DrawData<'data> = WaterWaveDrawData<'data>;
 
     fn new(
         _capabilities: &Capabilities,
@@ -48,7 +58,10 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAtt
         global_context: &GlobalContext,
         render_pass_context: &Self::Context,
     ) -> Self {
-        let shader_module = shader_compiler.create_shader_module("forward", "wave");
+        let shader_module = match global_context.msaa.multisampling_activated() {
+            true => shader_compiler.create_shader_module("water", "wave_msaa"),
+            false => shader_compiler.create_shader_module("water", "wave"),
+        };
 
         let uniforms_buffer = Buffer::with_data(
             device,
@@ -95,14 +108,18 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAtt
 
         let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
             label: Some(DRAWER_NAME),
-            bind_group_layouts: &[pass_bind_group_layouts[0], pass_bind_group_layouts[1], &bind_group_layout],
+            bind_group_layouts: &[
+                pass_bind_group_layouts[0],
+                GlobalContext::water_bind_group_layout(device, global_context.msaa),
+                &bind_group_layout,
+            ],
             push_constant_ranges: &[],
         });
 
         let color_attachment_formats = render_pass_context.color_attachment_formats();
         let depth_attachment_formats = render_pass_context.depth_attachment_output_format();
 
-        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+        let pipeline = create_render_pipeline(device, &RenderPipelineDescriptor {
             label: Some(DRAWER_NAME),
             layout: Some(&pipeline_layout),
             vertex: VertexState {
@@ -116,11 +133,7 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAtt
                 entry_point: Some("fs_main"),
                 compilation_options: PipelineCompilationOptions::default(),
                 targets: &[
-                    Some(ColorTargetState {
-                        format: color_attachment_formats[0],
-                        blend: Some(BlendState::PREMULTIPLIED_ALPHA_BLENDING),
-                        write_mask: ColorWrites::empty(),
-                    }),
+                    None,
                     Some(ColorTargetState {
                         format: color_attachment_formats[1],
                         blend: Some(BlendState {
@@ -160,7 +173,7 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAtt
             depth_stencil: Some(DepthStencilState {
                 format: depth_attachment_formats[0],
                 depth_write_enabled: false,
-                depth_compare: CompareFunction::Greater,
+                depth_compare: DEPTH_COMPARE_FUNCTION,
                 stencil: StencilState::default(),
                 bias: DepthBiasState::default(),
             }),
@@ -177,15 +190,18 @@ impl Drawer<{ BindGroupCount::Two }, { ColorAttachmentCount::Three }, { DepthAtt
     }
 
     fn draw(&mut self, pass: &mut RenderPass<'_>, draw_data: Self::DrawData<'_>) {
-        if draw_data.water_index_buffer.count() == 0 {
+        let instruction = draw_data.instruction;
+
+        if instruction.water_index_buffer.count() == 0 {
             return;
         }
 
         pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(1, draw_data.water_bind_group, &[]);
         pass.set_bind_group(2, &self.bind_group, &[]);
-        pass.set_index_buffer(draw_data.water_index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-        pass.set_vertex_buffer(0, draw_data.water_vertex_buffer.slice(..));
-        pass.draw_indexed(0..draw_data.water_index_buffer.count(), 0, 0..1);
+        pass.set_index_buffer(instruction.water_index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        pass.set_vertex_buffer(0, instruction.water_vertex_buffer.slice(..));
+        pass.draw_indexed(0..instruction.water_index_buffer.count(), 0, 0..1);
     }
 }
 
@@ -200,6 +216,8 @@ impl Prepare for WaterWaveDrawer {
                 waveform_amplitude: instruction.waveform_amplitude,
                 waveform_frequency: instruction.waveform_frequency.0,
                 water_opacity: instruction.water_opacity,
+                water_quality: instructions.uniforms.water_quality.into(),
+                padding: Default::default(),
             };
             self.bind_group = Self::create_bind_group(
                 device,
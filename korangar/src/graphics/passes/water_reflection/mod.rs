@@ -0,0 +1,200 @@
+mod model;
+
+use std::num::NonZeroU64;
+use std::sync::OnceLock;
+
+use bytemuck::{Pod, Zeroable};
+use cgmath::{Matrix4, Vector3};
+pub(crate) use model::WaterReflectionModelDrawer;
+use wgpu::util::StagingBelt;
+use wgpu::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType,
+    BufferBindingType, BufferUsages, Color, CommandEncoder, Device, LoadOp, Operations, Queue, RenderPass, RenderPassColorAttachment,
+    RenderPassDepthStencilAttachment, RenderPassDescriptor, ShaderStages, StoreOp, TextureFormat,
+};
+
+use super::{BindGroupCount, ColorAttachmentCount, DepthAttachmentCount, RenderPassContext};
+use crate::graphics::{Buffer, DEPTH_CLEAR_VALUE, GlobalContext, Prepare, RenderInstruction};
+use crate::loaders::TextureLoader;
+
+const PASS_NAME: &str = "water reflection render pass";
+
+#[derive(Copy, Clone, Default, Pod, Zeroable)]
+#[repr(C)]
+struct PassUniforms {
+    view_projection: [[f32; 4]; 4],
+    directional_light_color: [f32; 4],
+    directional_light_direction: [f32; 4],
+    water_level: f32,
+    animation_timer: f32,
+    padding: [u32; 2],
+    wind: [f32; 4],
+}
+
+/// Renders the scene mirrored at the water surface into the water reflection
+/// texture.
+pub(crate) struct WaterReflectionRenderPassContext {
+    uniforms: PassUniforms,
+    uniforms_buffer: Buffer<PassUniforms>,
+    bind_group: BindGroup,
+    color_texture_format: TextureFormat,
+    depth_texture_format: TextureFormat,
+}
+
+impl RenderPassContext<{ BindGroupCount::Two }, { ColorAttachmentCount::One }, { DepthAttachmentCount::One }>
+    for WaterReflectionRenderPassContext
+{
+    type PassData<'data> = Option<()>;
+
+    fn new(device: &Device, queue: &Queue, _texture_loader: &TextureLoader, global_context: &GlobalContext) -> Self {
+        let uniforms_buffer = Buffer::with_data(
+            device,
+            queue,
+            format!("{PASS_NAME} uniforms"),
+            BufferUsages::COPY_DST | BufferUsages::UNIFORM,
+            &[PassUniforms::default()],
+        );
+
+        let bind_group = Self::create_bind_group(device, &uniforms_buffer);
+
+        let color_texture_format = global_context.water_reflection_texture.get_format();
+        let depth_texture_format = global_context.water_reflection_depth_texture.get_format();
+
+        Self {
+            uniforms: PassUniforms::default(),
+            uniforms_buffer,
+            bind_group,
+            color_texture_format,
+            depth_texture_format,
+        }
+    }
+
+    fn create_pass<'encoder>(
+        &mut self,
+        encoder: &'encoder mut CommandEncoder,
+        global_context: &GlobalContext,
+        _pass_data: Option<()>,
+    ) -> RenderPass<'encoder> {
+        let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            label: Some(PASS_NAME),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: global_context.water_reflection_texture.get_texture_view(),
+                depth_slice: None,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(Color::TRANSPARENT),
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                view: global_context.water_reflection_depth_texture.get_texture_view(),
+                depth_ops: Some(Operations {
+                    load: LoadOp::Clear(DEPTH_CLEAR_VALUE),
+                    store: StoreOp::Discard,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        pass.set_bind_group(0, &global_context.global_bind_group, &[]);
+        pass.set_bind_group(1, &self.bind_group, &[]);
+
+        pass
+    }
+
+    fn bind_group_layout(device: &Device) -> [&'static BindGroupLayout; 2] {
+        static LAYOUT: OnceLock<BindGroupLayout> = OnceLock::new();
+
+        let layout = LAYOUT.get_or_init(|| {
+            device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                label: Some(PASS_NAME),
+                entries: &[BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::VERTEX_FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: NonZeroU64::new(size_of::<PassUniforms>() as _),
+                    },
+                    count: None,
+                }],
+            })
+        });
+
+        [GlobalContext::global_bind_group_layout(device), layout]
+    }
+
+    fn color_attachment_formats(&self) -> [TextureFormat; 1] {
+        [self.color_texture_format]
+    }
+
+    fn depth_attachment_output_format(&self) -> [TextureFormat; 1] {
+        [self.depth_texture_format]
+    }
+}
+
+impl Prepare for WaterReflectionRenderPassContext {
+    fn prepare(&mut self, _device: &Device, instructions: &RenderInstruction) {
+        let Some(water) = instructions.water.as_ref() else {
+            return;
+        };
+
+        let view_projection = instructions.uniforms.projection_matrix * instructions.uniforms.view_matrix;
+
+        self.uniforms = PassUniforms {
+            view_projection: (view_projection * reflection_matrix(water.water_level)).into(),
+            directional_light_color: instructions.directional_light.color.components_linear(),
+            directional_light_direction: instructions.directional_light.direction.extend(0.0).into(),
+            water_level: water.water_level,
+            animation_timer: instructions.uniforms.animation_timer_ms / 1000.0,
+            padding: Default::default(),
+            wind: instructions.uniforms.wind.into(),
+        };
+    }
+
+    fn upload(&mut self, device: &Device, staging_belt: &mut StagingBelt, command_encoder: &mut CommandEncoder) {
+        let recreated = self.uniforms_buffer.write(device, staging_belt, command_encoder, &[self.uniforms]);
+
+        if recreated {
+            self.bind_group = Self::create_bind_group(device, &self.uniforms_buffer);
+        }
+    }
+}
+
+impl WaterReflectionRenderPassContext {
+    fn create_bind_group(device: &Device, uniforms_buffer: &Buffer<PassUniforms>) -> BindGroup {
+        device.create_bind_group(&BindGroupDescriptor {
+            label: Some(PASS_NAME),
+            layout: Self::bind_group_layout(device)[1],
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: uniforms_buffer.as_entire_binding(),
+            }],
+        })
+    }
+}
+
+/// Mirrors world space positions at the horizontal plane at `water_level`.
+fn reflection_matrix(water_level: f32) -> Matrix4<f32> {
+    Matrix4::from_translation(Vector3::new(0.0, 2.0 * water_level, 0.0)) * Matrix4::from_nonuniform_scale(1.0, -1.0, 1.0)
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::{Point3, Transform};
+
+    use super::reflection_matrix;
+
+    #[test]
+    fn reflection_mirrors_at_water_level() {
+        let matrix = reflection_matrix(-5.0);
+
+        let above = matrix.transform_point(Point3::new(3.0, 10.0, 7.0));
+        let surface = matrix.transform_point(Point3::new(3.0, -5.0, 7.0));
+
+        assert_eq!(above, Point3::new(3.0, -20.0, 7.0));
+        assert_eq!(surface, Point3::new(3.0, -5.0, 7.0));
+    }
+}
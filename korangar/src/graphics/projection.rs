@@ -1,8 +1,45 @@
 use cgmath::{Angle, Matrix4, Rad, Vector4};
+#[cfg(debug_assertions)]
+use wgpu::DepthStencilState;
+use wgpu::{CompareFunction, Device, RenderPipeline, RenderPipelineDescriptor};
 
-/// The near-plane we use for all perspective projections.
+/// The default near-plane of our perspective projections. The point shadow
+/// shaders rely on this value, so it must not be changed for point shadow
+/// cameras.
 pub const NEAR_PLANE: f32 = 0.1;
 
+/// All our projections map the near plane to a depth of 1.0 and the far plane
+/// to a depth of 0.0 ("reverse Z"), so a fragment is closer to the camera if
+/// its depth is greater.
+pub const DEPTH_COMPARE_FUNCTION: CompareFunction = CompareFunction::Greater;
+
+/// The value depth attachments are cleared with, which is the far plane.
+pub const DEPTH_CLEAR_VALUE: f32 = 0.0;
+
+/// The clip planes of a perspective projection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClipPlanes {
+    pub near: f32,
+    /// The far plane, which may be at infinity.
+    pub far: f32,
+}
+
+impl ClipPlanes {
+    pub const fn infinite(near: f32) -> Self {
+        Self { near, far: f32::INFINITY }
+    }
+
+    pub const fn finite(near: f32, far: f32) -> Self {
+        Self { near, far }
+    }
+}
+
+impl Default for ClipPlanes {
+    fn default() -> Self {
+        Self::infinite(NEAR_PLANE)
+    }
+}
+
 /// Calculates an orthographic projection matrix for WebGPU or DirectX
 /// rendering.
 ///
@@ -27,21 +64,90 @@ pub fn orthographic_reverse_lh(left: f32, right: f32, bottom: f32, top: f32, nea
 
 /// Calculates a perspective projection matrix for WebGPU or DirectX rendering.
 ///
-/// This uses "reverse Z" with an infinite z-axis which helps greatly with Z
-/// fighting and some approximate numerical computations.
+/// This uses "reverse Z", which helps greatly with Z fighting and some
+/// approximate numerical computations. Without a far plane the z-axis is
+/// infinite.
 ///
 /// This function generates a matrix that transforms from left-handed, y-up
 /// world space to left-handed, y-up clip space with a depth range of 1.0 (near)
 /// to 0.0 (far).
-pub fn perspective_reverse_lh(vertical_fov: impl Into<Rad<f32>>, aspect_ratio: f32) -> Matrix4<f32> {
+pub fn perspective_reverse_lh(vertical_fov: impl Into<Rad<f32>>, aspect_ratio: f32, clip_planes: ClipPlanes) -> Matrix4<f32> {
+    debug_assert!(clip_planes.near > 0.0, "near plane must be in front of the camera");
+    debug_assert!(clip_planes.far > clip_planes.near, "far plane must be behind the near plane");
+
     let tangent = (vertical_fov.into() / 2.0).tan();
     let height = 1.0 / tangent;
     let width = height / aspect_ratio;
 
+    let (depth_scale, depth_offset) = if clip_planes.far.is_finite() {
+        let depth = 1.0 / (clip_planes.far - clip_planes.near);
+        (-clip_planes.near * depth, clip_planes.near * clip_planes.far * depth)
+    } else {
+        (0.0, clip_planes.near)
+    };
+
     Matrix4::from_cols(
         Vector4::new(width, 0.0, 0.0, 0.0),
         Vector4::new(0.0, height, 0.0, 0.0),
-        Vector4::new(0.0, 0.0, 0.0, 1.0),
-        Vector4::new(0.0, 0.0, NEAR_PLANE, 0.0),
+        Vector4::new(0.0, 0.0, depth_scale, 1.0),
+        Vector4::new(0.0, 0.0, depth_offset, 0.0),
     )
 }
+
+/// Creates a render pipeline. In debug builds this also validates that the
+/// pipeline follows our reverse Z convention.
+pub fn create_render_pipeline(device: &Device, descriptor: &RenderPipelineDescriptor) -> RenderPipeline {
+    #[cfg(debug_assertions)]
+    validate_depth_stencil_state(descriptor.label.unwrap_or("unnamed"), descriptor.depth_stencil.as_ref());
+
+    device.create_render_pipeline(descriptor)
+}
+
+/// Pipelines either test against the depth buffer with
+/// [`DEPTH_COMPARE_FUNCTION`] or always pass without writing depth, which is
+/// used for the sky that is drawn behind everything else.
+#[cfg(debug_assertions)]
+fn validate_depth_stencil_state(label: &str, depth_stencil: Option<&DepthStencilState>) {
+    let Some(depth_stencil) = depth_stencil else {
+        return;
+    };
+
+    let follows_convention = depth_stencil.depth_compare == DEPTH_COMPARE_FUNCTION
+        || (depth_stencil.depth_compare == CompareFunction::Always && !depth_stencil.depth_write_enabled);
+
+    assert!(
+        follows_convention,
+        "pipeline {label} uses the depth compare function {:?} (depth write: {}) but the depth convention is {:?}",
+        depth_stencil.depth_compare, depth_stencil.depth_write_enabled, DEPTH_COMPARE_FUNCTION,
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::{Deg, Vector4};
+
+    use super::{ClipPlanes, perspective_reverse_lh};
+
+    fn depth_at(clip_planes: ClipPlanes, distance: f32) -> f32 {
+        let clip_position = perspective_reverse_lh(Deg(45.0), 1.0, clip_planes) * Vector4::new(0.0, 0.0, distance, 1.0);
+        clip_position.z / clip_position.w
+    }
+
+    #[test]
+    fn infinite_perspective_uses_reverse_z() {
+        let clip_planes = ClipPlanes::infinite(0.5);
+
+        assert!((depth_at(clip_planes, 0.5) - 1.0).abs() < 1e-6);
+        assert!(depth_at(clip_planes, 10.0) > depth_at(clip_planes, 20.0));
+        assert!(depth_at(clip_planes, 1e7) > 0.0);
+    }
+
+    #[test]
+    fn finite_perspective_uses_reverse_z() {
+        let clip_planes = ClipPlanes::finite(1.0, 100.0);
+
+        assert!((depth_at(clip_planes, 1.0) - 1.0).abs() < 1e-6);
+        assert!(depth_at(clip_planes, 100.0).abs() < 1e-6);
+        assert!(depth_at(clip_planes, 10.0) > depth_at(clip_planes, 20.0));
+    }
+}
@@ -1,6 +1,6 @@
-use wgpu::{AddressMode, CompareFunction, Device, FilterMode, Sampler, SamplerBorderColor, SamplerDescriptor};
+use wgpu::{AddressMode, Device, FilterMode, Sampler, SamplerBorderColor, SamplerDescriptor};
 
-use crate::graphics::{Capabilities, TextureSamplerType};
+use crate::graphics::{Capabilities, DEPTH_COMPARE_FUNCTION, SpriteFiltering, TextureSamplerType};
 
 pub(crate) fn create_new_sampler(
     device: &Device,
@@ -62,7 +62,7 @@ pub(crate) fn create_new_sampler(
                 mipmap_filter: FilterMode::Linear,
                 lod_min_clamp: 0.0,
                 lod_max_clamp: 32.0,
-                compare: Some(CompareFunction::Greater),
+                compare: Some(DEPTH_COMPARE_FUNCTION),
                 anisotropy_clamp: 1,
                 border_color: None,
             };
@@ -94,3 +94,12 @@ impl From<TextureSamplerType> for SamplerType {
         }
     }
 }
+
+impl From<SpriteFiltering> for SamplerType {
+    fn from(value: SpriteFiltering) -> Self {
+        match value {
+            SpriteFiltering::Nearest => SamplerType::TextureNearest,
+            SpriteFiltering::Linear => SamplerType::TextureLinear,
+        }
+    }
+}
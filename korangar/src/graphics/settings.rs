@@ -4,9 +4,16 @@ use std::num::NonZeroU32;
 
 use korangar_interface::components::drop_down::DropDownItem;
 use korangar_interface::element::StateElement;
+#[cfg(feature = "debug")]
+use rust_state::Context;
+use rust_state::RustState;
 use serde::{Deserialize, Serialize};
 
 use crate::graphics::ScreenSize;
+#[cfg(feature = "debug")]
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+#[cfg(feature = "debug")]
+use crate::system::ConsoleCommandRegistry;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, StateElement)]
 pub enum LimitFramerate {
@@ -56,6 +63,153 @@ impl DropDownItem<TextureSamplerType> for TextureSamplerType {
     }
 }
 
+/// How the sprites of entities and the interface are filtered. This is
+/// independent of the texture filtering of the world.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, StateElement)]
+pub enum SpriteFiltering {
+    /// Crisp pixels, like in the original client.
+    Nearest,
+    #[default]
+    Linear,
+}
+
+impl DropDownItem<SpriteFiltering> for SpriteFiltering {
+    fn text(&self) -> &str {
+        match self {
+            SpriteFiltering::Nearest => "Pixel perfect",
+            SpriteFiltering::Linear => "Smooth",
+        }
+    }
+
+    fn value(&self) -> SpriteFiltering {
+        *self
+    }
+}
+
+/// Upscaling that is applied to sprites when they are loaded.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, StateElement)]
+pub enum SpriteUpscaling {
+    #[default]
+    Off,
+    Scale2x,
+    Scale4x,
+}
+
+impl SpriteUpscaling {
+    pub fn factor(self) -> u32 {
+        match self {
+            SpriteUpscaling::Off => 1,
+            SpriteUpscaling::Scale2x => 2,
+            SpriteUpscaling::Scale4x => 4,
+        }
+    }
+}
+
+impl DropDownItem<SpriteUpscaling> for SpriteUpscaling {
+    fn text(&self) -> &str {
+        match self {
+            SpriteUpscaling::Off => "Off",
+            SpriteUpscaling::Scale2x => "Scale2x",
+            SpriteUpscaling::Scale4x => "Scale4x",
+        }
+    }
+
+    fn value(&self) -> SpriteUpscaling {
+        *self
+    }
+}
+
+/// How the window is presented on the monitor.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, StateElement)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    /// A window without decorations that covers the entire monitor.
+    BorderlessFullscreen,
+    /// Fullscreen with a video mode selected by the user.
+    ExclusiveFullscreen,
+}
+
+impl DisplayMode {
+    /// The display mode to switch to when toggling fullscreen.
+    pub fn toggled(self) -> Self {
+        match self {
+            DisplayMode::Windowed => DisplayMode::BorderlessFullscreen,
+            DisplayMode::BorderlessFullscreen | DisplayMode::ExclusiveFullscreen => DisplayMode::Windowed,
+        }
+    }
+}
+
+impl DropDownItem<DisplayMode> for DisplayMode {
+    fn text(&self) -> &str {
+        match self {
+            DisplayMode::Windowed => "Windowed",
+            DisplayMode::BorderlessFullscreen => "Borderless",
+            DisplayMode::ExclusiveFullscreen => "Exclusive fullscreen",
+        }
+    }
+
+    fn value(&self) -> DisplayMode {
+        *self
+    }
+}
+
+/// Resolution and refresh rate used in exclusive fullscreen.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, RustState, StateElement)]
+pub struct VideoMode {
+    pub width: u32,
+    pub height: u32,
+    pub refresh_rate_millihertz: u32,
+}
+
+impl Display for VideoMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}x{} @ {:.0} Hz",
+            self.width,
+            self.height,
+            self.refresh_rate_millihertz as f32 / 1000.0
+        )
+    }
+}
+
+/// Monitor that can be selected in the graphics settings. `None` selects the
+/// monitor the window is currently on.
+#[derive(Debug, Clone, RustState, StateElement)]
+pub struct MonitorOption {
+    pub name: Option<String>,
+    pub label: String,
+}
+
+impl DropDownItem<Option<String>> for MonitorOption {
+    fn text(&self) -> &str {
+        &self.label
+    }
+
+    fn value(&self) -> Option<String> {
+        self.name.clone()
+    }
+}
+
+/// Video mode that can be selected in the graphics settings. `None` uses the
+/// current video mode of the monitor.
+#[derive(Debug, Clone, RustState, StateElement)]
+pub struct VideoModeOption {
+    pub video_mode: Option<VideoMode>,
+    pub label: String,
+}
+
+impl DropDownItem<Option<VideoMode>> for VideoModeOption {
+    fn text(&self) -> &str {
+        &self.label
+    }
+
+    fn value(&self) -> Option<VideoMode> {
+        self.video_mode
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, StateElement)]
 pub enum ShadowDetail {
     Normal,
@@ -135,6 +289,135 @@ impl From<ShadowQuality> for u32 {
     }
 }
 
+/// Quality of the water surface.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, StateElement)]
+pub enum WaterQuality {
+    /// Simple textured water plane.
+    Low,
+    /// Adds screen-space refraction and soft shorelines.
+    #[default]
+    Medium,
+    /// Additionally renders planar reflections of the models above the
+    /// water.
+    High,
+}
+
+impl DropDownItem<WaterQuality> for WaterQuality {
+    fn text(&self) -> &str {
+        match self {
+            Self::Low => "Low",
+            Self::Medium => "Medium",
+            Self::High => "High",
+        }
+    }
+
+    fn value(&self) -> WaterQuality {
+        *self
+    }
+}
+
+impl From<WaterQuality> for u32 {
+    fn from(value: WaterQuality) -> Self {
+        match value {
+            WaterQuality::Low => 0,
+            WaterQuality::Medium => 1,
+            WaterQuality::High => 2,
+        }
+    }
+}
+
+/// Use of the light maps that are baked into the ground of the original maps.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, StateElement)]
+pub enum LightMapMode {
+    /// The ground is only lit by the dynamic lights.
+    #[default]
+    Off,
+    /// Baked shadows and light colors with the reduced color depth of the
+    /// original client.
+    Classic,
+    /// Baked shadows and light colors in full color depth.
+    Smooth,
+}
+
+impl DropDownItem<LightMapMode> for LightMapMode {
+    fn text(&self) -> &str {
+        match self {
+            Self::Off => "Off",
+            Self::Classic => "Classic",
+            Self::Smooth => "Smooth",
+        }
+    }
+
+    fn value(&self) -> LightMapMode {
+        *self
+    }
+}
+
+impl From<LightMapMode> for u32 {
+    fn from(value: LightMapMode) -> Self {
+        match value {
+            LightMapMode::Off => 0,
+            LightMapMode::Classic => 1,
+            LightMapMode::Smooth => 2,
+        }
+    }
+}
+
+/// Darkening of the world beyond the view range of the server, which makes it
+/// clear where entities start to appear.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, StateElement)]
+pub enum ViewRangeDimming {
+    #[default]
+    Off,
+    Subtle,
+    Strong,
+}
+
+impl ViewRangeDimming {
+    /// Fraction of the brightness that is taken away beyond the view range.
+    pub fn strength(self) -> f32 {
+        match self {
+            Self::Off => 0.0,
+            Self::Subtle => 0.3,
+            Self::Strong => 0.65,
+        }
+    }
+}
+
+impl DropDownItem<ViewRangeDimming> for ViewRangeDimming {
+    fn text(&self) -> &str {
+        match self {
+            Self::Off => "Off",
+            Self::Subtle => "Subtle",
+            Self::Strong => "Strong",
+        }
+    }
+
+    fn value(&self) -> ViewRangeDimming {
+        *self
+    }
+}
+
+/// Set of graphics settings that fits the performance of a class of adapters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsPreset {
+    Low,
+    Medium,
+    High,
+    Ultra,
+}
+
+impl Display for GraphicsPreset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphicsPreset::Low => "Low".fmt(f),
+            GraphicsPreset::Medium => "Medium".fmt(f),
+            GraphicsPreset::High => "High".fmt(f),
+            GraphicsPreset::Ultra => "Ultra".fmt(f),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum Msaa {
     Off,
@@ -278,8 +561,88 @@ impl Display for ScreenSpaceAntiAliasing {
     }
 }
 
+/// Constants of the depth offset that keeps the billboards of sprites from
+/// clipping into the ground.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, RustState, StateElement)]
+pub struct SpriteDepthBias {
+    /// Depth offset relative to the sprite height when looking straight down.
+    pub offset_factor: f32,
+    /// Curvature of the billboard relative to the sprite width.
+    pub curvature_factor: f32,
+    /// Additional depth offset relative to the sprite height, scaled by how
+    /// steep the ground rises towards the camera.
+    pub slope_factor: f32,
+}
+
+impl SpriteDepthBias {
+    pub const CURVATURE_FACTOR_STEP: f32 = 0.5;
+    pub const OFFSET_FACTOR_STEP: f32 = 0.5;
+    pub const SLOPE_FACTOR_STEP: f32 = 0.25;
+}
+
+impl Default for SpriteDepthBias {
+    fn default() -> Self {
+        Self {
+            offset_factor: 10.0,
+            curvature_factor: 8.0,
+            slope_factor: 4.0,
+        }
+    }
+}
+
+/// The sprite depth bias is tuned separately for every camera mode, since
+/// their viewing angles differ a lot.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, RustState, StateElement)]
+pub struct SpriteDepthBiasSettings {
+    #[serde(default)]
+    pub player: SpriteDepthBias,
+    #[serde(default)]
+    pub start: SpriteDepthBias,
+    #[serde(default)]
+    pub debug: SpriteDepthBias,
+}
+
+/// Speed at which effects are played, to inspect them in slow motion.
+#[cfg(feature = "debug")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, StateElement)]
+pub enum EffectTimeScale {
+    Paused,
+    Quarter,
+    Half,
+    #[default]
+    Normal,
+}
+
 #[cfg(feature = "debug")]
-#[derive(Copy, Clone, Default, rust_state::RustState, StateElement)]
+impl EffectTimeScale {
+    pub fn factor(self) -> f64 {
+        match self {
+            EffectTimeScale::Paused => 0.0,
+            EffectTimeScale::Quarter => 0.25,
+            EffectTimeScale::Half => 0.5,
+            EffectTimeScale::Normal => 1.0,
+        }
+    }
+}
+
+#[cfg(feature = "debug")]
+impl DropDownItem<EffectTimeScale> for EffectTimeScale {
+    fn text(&self) -> &str {
+        match self {
+            EffectTimeScale::Paused => "Paused",
+            EffectTimeScale::Quarter => "25%",
+            EffectTimeScale::Half => "50%",
+            EffectTimeScale::Normal => "100%",
+        }
+    }
+
+    fn value(&self) -> EffectTimeScale {
+        *self
+    }
+}
+
+#[cfg(feature = "debug")]
+#[derive(Copy, Clone, Default, RustState, StateElement)]
 pub struct RenderOptions {
     pub show_frames_per_second: bool,
     pub frustum_culling: bool,
@@ -289,6 +652,8 @@ pub struct RenderOptions {
     pub show_entities: bool,
     pub show_entities_paper: bool,
     pub show_entities_debug: bool,
+    pub show_sky: bool,
+    pub show_decals: bool,
     pub show_water: bool,
     pub show_indicators: bool,
     pub enable_ambient_lighting: bool,
@@ -309,6 +674,7 @@ pub struct RenderOptions {
     pub show_picker_buffer: bool,
     pub show_directional_shadow_map: Option<NonZeroU32>,
     pub show_point_shadow_map: Option<NonZeroU32>,
+    pub effect_time_scale: EffectTimeScale,
     pub show_light_culling_count_buffer: bool,
     pub show_font_map: bool,
     pub show_sdsm_partitions: bool,
@@ -330,6 +696,8 @@ impl RenderOptions {
             show_entities: true,
             show_entities_paper: false,
             show_entities_debug: false,
+            show_sky: true,
+            show_decals: true,
             show_water: true,
             show_indicators: true,
             enable_ambient_lighting: true,
@@ -350,6 +718,7 @@ impl RenderOptions {
             show_picker_buffer: false,
             show_directional_shadow_map: None,
             show_point_shadow_map: None,
+            effect_time_scale: EffectTimeScale::Normal,
             show_light_culling_count_buffer: false,
             show_sdsm_partitions: false,
             show_font_map: false,
@@ -371,4 +740,96 @@ impl RenderOptions {
             || self.show_sdsm_partitions
             || self.show_font_map
     }
+
+    /// Hide the map and everything on it, so a single effect can be inspected
+    /// in an empty scene.
+    pub fn hide_scene(&mut self) {
+        self.show_map = false;
+        self.show_objects = false;
+        self.show_entities = false;
+        self.show_sky = false;
+        self.show_decals = false;
+        self.show_water = false;
+        self.show_indicators = false;
+    }
+
+    /// Register the `render` command to toggle render options from the debug
+    /// console.
+    pub fn register_console_commands(registry: &mut ConsoleCommandRegistry<Context<ClientState>>) {
+        registry.register(
+            "render",
+            "Toggle a render option: render [option] [on|off]",
+            |state, arguments| {
+                let render_options = state.follow_mut(client_state().render_options());
+
+                let Some(name) = arguments.first() else {
+                    let options = TOGGLEABLE_RENDER_OPTIONS
+                        .iter()
+                        .map(|(name, option)| format!("{name}: {}", *option(render_options)))
+                        .collect::<Vec<_>>();
+                    return Ok(options.join("\n"));
+                };
+
+                let Some((_, option)) = TOGGLEABLE_RENDER_OPTIONS.iter().find(|(option_name, _)| option_name == name) else {
+                    return Err(format!("unknown render option \"{name}\""));
+                };
+
+                let value = option(render_options);
+                *value = match arguments.get(1).copied() {
+                    None => !*value,
+                    Some("on") => true,
+                    Some("off") => false,
+                    Some(other) => return Err(format!("expected on or off, got \"{other}\"")),
+                };
+
+                Ok(format!("{name}: {}", *value))
+            },
+        );
+    }
 }
+
+/// Boolean render options that can be toggled from the debug console.
+#[cfg(feature = "debug")]
+const TOGGLEABLE_RENDER_OPTIONS: &[(&str, fn(&mut RenderOptions) -> &mut bool)] = &[
+    ("show_frames_per_second", |options| &mut options.show_frames_per_second),
+    ("frustum_culling", |options| &mut options.frustum_culling),
+    ("show_bounding_boxes", |options| &mut options.show_bounding_boxes),
+    ("show_map", |options| &mut options.show_map),
+    ("show_objects", |options| &mut options.show_objects),
+    ("show_entities", |options| &mut options.show_entities),
+    ("show_entities_paper", |options| &mut options.show_entities_paper),
+    ("show_entities_debug", |options| &mut options.show_entities_debug),
+    ("show_sky", |options| &mut options.show_sky),
+    ("show_decals", |options| &mut options.show_decals),
+    ("show_water", |options| &mut options.show_water),
+    ("show_indicators", |options| &mut options.show_indicators),
+    ("enable_ambient_lighting", |options| &mut options.enable_ambient_lighting),
+    ("enable_directional_lighting", |options| {
+        &mut options.enable_directional_lighting
+    }),
+    ("enable_point_lights", |options| &mut options.enable_point_lights),
+    ("enable_particle_lighting", |options| &mut options.enable_particle_lighting),
+    ("use_debug_camera", |options| &mut options.use_debug_camera),
+    ("show_wireframe", |options| &mut options.show_wireframe),
+    ("show_object_markers", |options| &mut options.show_object_markers),
+    ("show_light_markers", |options| &mut options.show_light_markers),
+    ("show_sound_markers", |options| &mut options.show_sound_markers),
+    ("show_effect_markers", |options| &mut options.show_effect_markers),
+    ("show_particle_markers", |options| &mut options.show_particle_markers),
+    ("show_entity_markers", |options| &mut options.show_entity_markers),
+    ("show_shadow_markers", |options| &mut options.show_shadow_markers),
+    ("show_map_tiles", |options| &mut options.show_map_tiles),
+    ("show_pathing", |options| &mut options.show_pathing),
+    ("show_picker_buffer", |options| &mut options.show_picker_buffer),
+    ("show_light_culling_count_buffer", |options| {
+        &mut options.show_light_culling_count_buffer
+    }),
+    ("show_font_map", |options| &mut options.show_font_map),
+    ("show_sdsm_partitions", |options| &mut options.show_sdsm_partitions),
+    ("show_rectangle_instructions", |options| {
+        &mut options.show_rectangle_instructions
+    }),
+    ("show_glyph_instructions", |options| &mut options.show_glyph_instructions),
+    ("show_sprite_instructions", |options| &mut options.show_sprite_instructions),
+    ("show_sdf_instructions", |options| &mut options.show_sdf_instructions),
+];
@@ -222,43 +222,58 @@ impl Texture {
 #[derive(Debug)]
 pub struct TextureSet {
     textures: Vec<Arc<Texture>>,
-    bind_group: Option<BindGroup>,
+    bind_groups: Vec<BindGroup>,
 }
 
 impl TextureSet {
     pub fn new(device: &Device, bindless_support: BindlessSupport, texture_set_size: u32, name: &str, textures: Vec<Arc<Texture>>) -> Self {
-        let bind_group = match bindless_support {
+        let bind_groups = match bindless_support {
             BindlessSupport::Full | BindlessSupport::Limited => {
-                let mut views = Vec::from_iter(textures.iter().map(|texture| texture.get_texture_view()));
-
-                if bindless_support == BindlessSupport::Limited && !textures.is_empty() {
-                    let default_view = textures[0].get_texture_view();
-                    views.resize_with(texture_set_size as usize, || default_view);
+                let create_bind_group = |textures: &[Arc<Texture>]| {
+                    let mut views = Vec::from_iter(textures.iter().map(|texture| texture.get_texture_view()));
+
+                    if bindless_support == BindlessSupport::Limited && !textures.is_empty() {
+                        let default_view = textures[0].get_texture_view();
+                        views.resize_with(texture_set_size as usize, || default_view);
+                    }
+
+                    device.create_bind_group(&BindGroupDescriptor {
+                        label: Some(name),
+                        layout: Self::bind_group_layout(device, texture_set_size),
+                        entries: &[BindGroupEntry {
+                            binding: 0,
+                            resource: BindingResource::TextureViewArray(&views),
+                        }],
+                    })
+                };
+
+                // Sets with more textures than fit into a single binding array are split
+                // into texture groups, which are bound one after another.
+                match textures.is_empty() {
+                    true => vec![create_bind_group(&textures)],
+                    false => textures.chunks(texture_set_size as usize).map(create_bind_group).collect(),
                 }
-
-                let bind_group = device.create_bind_group(&BindGroupDescriptor {
-                    label: Some(name),
-                    layout: Self::bind_group_layout(device, texture_set_size),
-                    entries: &[BindGroupEntry {
-                        binding: 0,
-                        resource: BindingResource::TextureViewArray(&views),
-                    }],
-                });
-
-                Some(bind_group)
             }
-            BindlessSupport::None => None,
+            BindlessSupport::None => Vec::new(),
         };
 
-        Self { textures, bind_group }
+        Self { textures, bind_groups }
     }
 
     pub fn get_texture_bind_group(&self, texture_id: i32) -> &BindGroup {
         self.textures[texture_id as usize].get_bind_group()
     }
 
-    pub fn get_bind_group(&self) -> Option<&BindGroup> {
-        self.bind_group.as_ref()
+    /// Returns the bind group of the given texture group. Panics if the set
+    /// was created without bindless support.
+    pub fn get_bind_group(&self, texture_group: i32) -> &BindGroup {
+        &self.bind_groups[texture_group as usize]
+    }
+
+    /// Combined size of all textures in the set. Textures may be shared with
+    /// the texture cache.
+    pub fn get_byte_size(&self) -> usize {
+        self.textures.iter().map(|texture| texture.get_byte_size()).sum()
     }
 
     pub fn bind_group_layout(device: &Device, texture_set_size: u32) -> &'static BindGroupLayout {
@@ -5,7 +5,7 @@ mod simple;
 mod tile;
 mod water;
 
-pub use self::model::{ModelVertex, reduce_vertices};
+pub use self::model::{ModelVertex, NO_LIGHT_MAP_COORDINATES, reduce_vertices};
 pub use self::native::NativeModelVertex;
 #[cfg(feature = "debug")]
 pub use self::simple::SimpleVertex;
@@ -7,6 +7,9 @@ use wgpu::{VertexAttribute, VertexBufferLayout, VertexStepMode, vertex_attr_arra
 
 use crate::Color;
 
+/// Light map coordinates of vertices that are not lit by a baked light map.
+pub const NO_LIGHT_MAP_COORDINATES: Vector2<f32> = Vector2::new(-1.0, -1.0);
+
 #[repr(C)]
 #[derive(Default, Debug, Clone, Copy, Zeroable, Pod)]
 pub struct ModelVertex {
@@ -16,6 +19,7 @@ pub struct ModelVertex {
     pub color: [f32; 3],
     pub texture_index: i32,
     pub wind_affinity: f32,
+    pub light_map_coordinates: [f32; 2],
 }
 
 impl PartialEq for ModelVertex {
@@ -33,6 +37,8 @@ impl PartialEq for ModelVertex {
             && (self.color[2] - other.color[2]).abs() < Self::EPSILON
             && self.texture_index == other.texture_index
             && (self.wind_affinity - other.wind_affinity).abs() < Self::EPSILON
+            && (self.light_map_coordinates[0] - other.light_map_coordinates[0]).abs() < Self::EPSILON
+            && (self.light_map_coordinates[1] - other.light_map_coordinates[1]).abs() < Self::EPSILON
     }
 }
 
@@ -69,6 +75,11 @@ impl Hash for ModelVertex {
         let wind_affinity = (self.wind_affinity / Self::EPSILON).round() as i32;
         wind_affinity.hash(state);
 
+        let lx = (self.light_map_coordinates[0] / Self::EPSILON).round() as i32;
+        let ly = (self.light_map_coordinates[1] / Self::EPSILON).round() as i32;
+        lx.hash(state);
+        ly.hash(state);
+
         self.texture_index.hash(state);
     }
 }
@@ -91,9 +102,15 @@ impl ModelVertex {
             color: [color.red, color.green, color.blue],
             texture_index,
             wind_affinity,
+            light_map_coordinates: [NO_LIGHT_MAP_COORDINATES.x, NO_LIGHT_MAP_COORDINATES.y],
         }
     }
 
+    pub const fn with_light_map_coordinates(mut self, light_map_coordinates: Vector2<f32>) -> Self {
+        self.light_map_coordinates = [light_map_coordinates.x, light_map_coordinates.y];
+        self
+    }
+
     pub fn buffer_layout() -> VertexBufferLayout<'static> {
         static ATTRIBUTES: &[VertexAttribute] = &vertex_attr_array!(
                 0 => Float32x3,
@@ -102,6 +119,7 @@ impl ModelVertex {
                 3 => Float32x3,
                 4 => Sint32,
                 5 => Float32,
+                6 => Float32x2,
         );
 
         VertexBufferLayout {
@@ -1,7 +1,7 @@
 use cgmath::{InnerSpace, Point3, Vector2, Vector3};
 use smallvec::{SmallVec, smallvec_inline};
 
-use crate::graphics::{Color, ModelVertex};
+use crate::graphics::{Color, ModelVertex, NO_LIGHT_MAP_COORDINATES};
 use crate::loaders::TextureSetTexture;
 
 #[derive(Clone)]
@@ -12,6 +12,7 @@ pub struct NativeModelVertex {
     pub texture_index: i32,
     pub color: Color,
     pub wind_affinity: f32,
+    pub light_map_coordinates: Vector2<f32>,
     pub smoothing_groups: SmallVec<[i32; 3]>,
 }
 
@@ -32,10 +33,16 @@ impl NativeModelVertex {
             texture_index,
             color,
             wind_affinity,
+            light_map_coordinates: NO_LIGHT_MAP_COORDINATES,
             smoothing_groups,
         }
     }
 
+    pub fn with_light_map_coordinates(mut self, light_map_coordinates: Vector2<f32>) -> Self {
+        self.light_map_coordinates = light_map_coordinates;
+        self
+    }
+
     pub const fn zeroed() -> NativeModelVertex {
         NativeModelVertex {
             position: Point3::new(0.0, 0.0, 0.0),
@@ -44,6 +51,7 @@ impl NativeModelVertex {
             texture_index: 0,
             color: Color::rgba(0.0, 0.0, 0.0, 0.0),
             wind_affinity: 0.0,
+            light_map_coordinates: NO_LIGHT_MAP_COORDINATES,
             smoothing_groups: smallvec_inline![0; 3],
         }
     }
@@ -57,6 +65,7 @@ impl NativeModelVertex {
             self.texture_index,
             self.wind_affinity,
         )
+        .with_light_map_coordinates(self.light_map_coordinates)
     }
 
     pub fn convert_to_model_vertices(
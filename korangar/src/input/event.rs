@@ -3,10 +3,10 @@ use cgmath::Vector2;
 #[cfg(feature = "debug")]
 use korangar_debug::profiling::FrameMeasurement;
 use korangar_interface::event::{ClickHandler, Event, EventQueue};
-use korangar_networking::{InventoryItem, ShopItem};
+use korangar_networking::{CharacterCreationOptions, InventoryItem, ShopItem};
 use ragnarok_packets::{
-    AccountId, BuyOrSellOption, CharacterId, CharacterServerInformation, EntityId, HotbarSlot, ShopId, SoldItemInformation, StatUpType,
-    TilePosition,
+    AccountId, BuyOrSellOption, CharacterId, CharacterServerInformation, EntityId, HotbarSlot, InventoryIndex, ItemId, ShopId,
+    SoldItemInformation, StatUpType, TilePosition,
 };
 use rust_state::Context;
 
@@ -15,8 +15,10 @@ use crate::inventory::Skill;
 use crate::loaders::ServiceId;
 use crate::state::ClientState;
 #[cfg(feature = "debug")]
-use crate::world::MarkerIdentifier;
+use crate::state::cache_statistics::ClearableCache;
 use crate::world::ResourceMetadata;
+#[cfg(feature = "debug")]
+use crate::world::{GizmoAxis, GizmoMode, MarkerIdentifier, MarkerProperty};
 
 /// An event triggered by the user through mouse or keyboard input.
 #[derive(Clone, Debug)]
@@ -57,6 +59,9 @@ pub enum InputEvent {
     ResetCameraRotation,
     /// Open or close the menu window. Only works while playing.
     ToggleMenuWindow,
+    /// Open or close the character overview window. Only works while
+    /// playing.
+    ToggleCharacterOverviewWindow,
     /// Open or close the inventory window. Only works while playing.
     ToggleInventoryWindow,
     /// Open or close the equipment window. Only works while playing.
@@ -71,10 +76,52 @@ pub enum InputEvent {
     ToggleInterfaceSettingsWindow,
     /// Open or close the graphics settings window.
     ToggleGraphicsSettingsWindow,
+    /// Detect the graphics preset that fits the adapter and apply it.
+    DetectGraphicsPreset,
     /// Open or close the audio settings window.
     ToggleAudioSettingsWindow,
     /// Open or close the friend list window. Only works while playing.
     ToggleFriendListWindow,
+    /// Open or close the session statistics window. Only works while playing.
+    ToggleSessionStatisticsWindow,
+    /// Reset the session statistics.
+    ResetSessionStatistics,
+    /// Export the session statistics to a CSV file.
+    ExportSessionStatistics,
+    /// Open or close the monster info window.
+    ToggleMonsterInfoWindow,
+    /// Show the information of a monster in the monster info window.
+    InspectMonster {
+        /// Id of the monster entity.
+        entity_id: EntityId,
+    },
+    /// Search the monster database and show the best match in the monster
+    /// info window.
+    SearchMonsters {
+        /// Name or part of the name of the monster.
+        query: String,
+    },
+    /// Open or close the item search window.
+    ToggleItemSearchWindow,
+    /// Search the item database with the query and filters of the item search
+    /// window.
+    SearchItems,
+    /// Show the information of an item in the item search window.
+    ShowItemInfo {
+        /// Id of the item.
+        item_id: ItemId,
+    },
+    /// Add a link to an item to the chat text box.
+    LinkItemInChat {
+        /// Id of the linked item.
+        item_id: ItemId,
+    },
+    /// Open or close the skill planner window.
+    ToggleSkillPlannerWindow,
+    /// Open or close the minimap window. Only works while playing.
+    ToggleMinimapWindow,
+    /// Load the skill build from the build string of the skill planner.
+    ImportSkillBuild,
     /// Close the most recently opened or clicked closable window.
     CloseTopWindow,
     /// Toggle if the user interface should be rendered or not.
@@ -95,6 +142,8 @@ pub enum InputEvent {
         slot: usize,
         /// Name of the new character.
         name: String,
+        /// Hair, sex and job of the new character.
+        options: CharacterCreationOptions,
     },
     /// Delete a character.
     DeleteCharacter {
@@ -134,6 +183,8 @@ pub enum InputEvent {
         /// Id of the NPC the player is in a dialog with.
         npc_id: EntityId,
     },
+    /// Open or close the window with the history of all dialogs.
+    ToggleDialogHistoryWindow,
     /// Choose an option in a dialog.
     ChooseDialogOption {
         /// Id of the NPC the player is in a dialog with.
@@ -150,6 +201,22 @@ pub enum InputEvent {
         /// Item to move.
         item: InventoryItem<ResourceMetadata>,
     },
+    /// Drop an item on the ground after the player confirmed it or picked
+    /// the amount.
+    DropItem { index: InventoryIndex, amount: u16 },
+    /// Lock or unlock an item, protecting it from being sold by accident.
+    ToggleItemLock { item_id: ItemId },
+    /// Save the equipped items as a gear set, using the name entered in the
+    /// equipment window.
+    SaveGearSet,
+    /// Equip all items of a saved gear set that are in the inventory.
+    EquipGearSet { index: usize },
+    /// Remove a saved gear set.
+    DeleteGearSet { index: usize },
+    /// Show the emote wheel.
+    OpenEmoteWheel,
+    /// Hide the emote wheel and use the selected entry.
+    CloseEmoteWheel,
     /// Move a skill in the user interface.
     MoveSkill {
         /// Source of the move.
@@ -164,6 +231,16 @@ pub enum InputEvent {
         /// Slot of the hotbar that the skill is bound to.
         slot: HotbarSlot,
     },
+    /// Use the item that is placed on the hotbar.
+    UseHotbarItem {
+        /// Slot of the hotbar that the item is placed on.
+        slot: HotbarSlot,
+    },
+    /// Cast the ground skill that is currently being targeted.
+    ConfirmSkillTarget {
+        /// Tile that the skill is cast on.
+        destination: TilePosition,
+    },
     /// Stop a skill.
     StopSkill {
         /// Slot of the hotbar that the skill is bound to.
@@ -213,9 +290,17 @@ pub enum InputEvent {
     SellItems {
         /// Items to sell.
         items: Vec<SoldItemInformation>,
+        /// The player confirmed selling locked or valuable items.
+        confirmed: bool,
     },
+    /// Claim the attendance reward of the day.
+    ClaimAttendanceReward,
     /// Up a stat.
     StatUp { stat_type: StatUpType },
+    /// Open the directory containing the crash reports in the file manager.
+    OpenCrashReportDirectory,
+    /// Switch between windowed and fullscreen.
+    ToggleFullscreen,
     /// Reload the language from disk.
     #[cfg(feature = "debug")]
     ReloadLanguage,
@@ -263,6 +348,170 @@ pub enum InputEvent {
     /// Open the cache statistics window.
     #[cfg(feature = "debug")]
     ToggleCacheStatisticsWindow,
+    /// Open the memory usage window.
+    #[cfg(feature = "debug")]
+    ToggleMemoryUsageWindow,
+    /// Open or close the window for tuning the depth offsets of sprites.
+    #[cfg(feature = "debug")]
+    ToggleSpriteDepthBiasWindow,
+    /// Drop all values of a cache.
+    #[cfg(feature = "debug")]
+    ClearCache {
+        /// The cache to clear.
+        cache: ClearableCache,
+    },
+    /// Open or close the map editor window. Only works while playing.
+    #[cfg(feature = "debug")]
+    ToggleMapEditorWindow,
+    /// Select a marker in the map editor.
+    #[cfg(feature = "debug")]
+    SelectMarker {
+        /// Id of the marker to select.
+        marker_identifier: MarkerIdentifier,
+    },
+    /// Move a marker in the map editor.
+    #[cfg(feature = "debug")]
+    MoveMarker {
+        /// Id of the marker to move.
+        marker_identifier: MarkerIdentifier,
+        /// Tile that the marker is moved above.
+        destination: TilePosition,
+    },
+    /// Change a property of the selected marker in the map editor.
+    #[cfg(feature = "debug")]
+    AdjustMarkerProperty {
+        /// The property to change.
+        property: MarkerProperty,
+        /// Number of steps to change the property by. Can be negative.
+        steps: i32,
+    },
+    /// Add a light source at the position of the player.
+    #[cfg(feature = "debug")]
+    AddLightSource,
+    /// Add a sound source at the position of the player.
+    #[cfg(feature = "debug")]
+    AddSoundSource,
+    /// Add an effect source at the position of the player.
+    #[cfg(feature = "debug")]
+    AddEffectSource,
+    /// Add a copy of the selected marker at the position of the player.
+    #[cfg(feature = "debug")]
+    DuplicateSelectedMarker,
+    /// Remove the selected marker from the map.
+    #[cfg(feature = "debug")]
+    RemoveSelectedMarker,
+    /// Save the edited map data as a new RSW file.
+    #[cfg(feature = "debug")]
+    ExportMap,
+    /// Set how the gizmo transforms the selected object.
+    #[cfg(feature = "debug")]
+    SetGizmoMode {
+        /// The new gizmo mode.
+        mode: GizmoMode,
+    },
+    /// Set the axis that the gizmo transforms the selected object along.
+    #[cfg(feature = "debug")]
+    SetGizmoAxis {
+        /// The new gizmo axis.
+        axis: GizmoAxis,
+    },
+    /// Transform the selected object by a number of gizmo steps.
+    #[cfg(feature = "debug")]
+    StepGizmo {
+        /// Number of steps. Can be negative.
+        steps: i32,
+    },
+    /// Transform an object by dragging the gizmo.
+    #[cfg(feature = "debug")]
+    DragGizmo {
+        /// Key of the dragged object.
+        object_key: u32,
+        /// Amount to transform the object by, depending on the gizmo mode.
+        amount: f32,
+    },
+    /// Undo the last transform change of an object.
+    #[cfg(feature = "debug")]
+    UndoTransform,
+    /// Redo the last undone transform change of an object.
+    #[cfg(feature = "debug")]
+    RedoTransform,
+    /// Make a tile walkable or block it with the walkability brush.
+    #[cfg(feature = "debug")]
+    PaintTile {
+        /// The painted tile.
+        position: TilePosition,
+        /// Whether the tile becomes walkable or blocked.
+        walkable: bool,
+    },
+    /// Open or close the asset browser window.
+    #[cfg(feature = "debug")]
+    ToggleAssetBrowserWindow,
+    /// Load a sprite and its actions and preview them in the asset browser.
+    #[cfg(feature = "debug")]
+    PreviewAsset {
+        /// Name of the sprite without the `data\sprite\` prefix and the file
+        /// extension.
+        name: String,
+    },
+    /// Show a different action of the previewed asset.
+    #[cfg(feature = "debug")]
+    StepAssetPreviewAction {
+        /// Number of actions to step. Can be negative.
+        steps: i32,
+    },
+    /// Pause the previewed asset and show a different frame.
+    #[cfg(feature = "debug")]
+    StepAssetPreviewFrame {
+        /// Number of frames to step. Can be negative.
+        steps: i32,
+    },
+    /// Pause or continue the animation of the previewed asset.
+    #[cfg(feature = "debug")]
+    ToggleAssetPreviewPlayback,
+    /// Open or close the effect preview window.
+    #[cfg(feature = "debug")]
+    ToggleEffectPreviewWindow,
+    /// Load the effect at the path entered in the effect preview and play it.
+    #[cfg(feature = "debug")]
+    PreviewEffect,
+    /// Play the previewed effect from the first frame again.
+    #[cfg(feature = "debug")]
+    RestartEffectPreview,
+    /// Pause the previewed effect and show a different frame.
+    #[cfg(feature = "debug")]
+    StepEffectPreviewFrame {
+        /// Number of frames to step. Can be negative.
+        steps: i32,
+    },
+    /// Open or close the model viewer window.
+    #[cfg(feature = "debug")]
+    ToggleModelViewerWindow,
+    /// Load the model at the path entered in the model viewer.
+    #[cfg(feature = "debug")]
+    PreviewModel,
+    /// Orbit the model viewer camera around the viewed model.
+    #[cfg(feature = "debug")]
+    OrbitModelViewer {
+        /// Mouse movement since the last frame.
+        offset: Vector2<f32>,
+    },
+    /// Open or close the debug console.
+    #[cfg(feature = "debug")]
+    ToggleConsoleWindow,
+    /// Execute the command currently entered in the debug console.
+    #[cfg(feature = "debug")]
+    ExecuteConsoleCommand,
+    /// Autocomplete the command currently entered in the debug console.
+    #[cfg(feature = "debug")]
+    AutocompleteConsoleCommand,
+    /// Replace the input of the debug console with the previous command from
+    /// the history.
+    #[cfg(feature = "debug")]
+    ConsoleHistoryPrevious,
+    /// Replace the input of the debug console with the next command from the
+    /// history.
+    #[cfg(feature = "debug")]
+    ConsoleHistoryNext,
     /// Move the view direction of the debug camera.
     #[cfg(feature = "debug")]
     CameraLookAround {
@@ -15,6 +15,7 @@ pub use self::event::InputEvent;
 pub use self::key::Key;
 pub use self::mode::{Grabbed, MouseInputMode, MouseModeExt};
 use crate::graphics::{PickerTarget, ScreenPosition, ScreenSize};
+use crate::settings::KeyBindings;
 
 const MOUSE_SCOLL_MULTIPLIER: f32 = 30.0;
 const KEY_COUNT: usize = variant_count::<KeyCode>();
@@ -52,6 +53,7 @@ pub struct InputSystem {
     input_buffer: Vec<char>,
     picker_value: Arc<AtomicU64>,
     previous_mouse_button: Option<PreviousMouseButton>,
+    key_bindings: KeyBindings,
 }
 
 impl InputSystem {
@@ -70,6 +72,7 @@ impl InputSystem {
 
         let input_buffer = Vec::new();
         let previous_mouse_button = None;
+        let key_bindings = KeyBindings::new();
 
         Self {
             previous_mouse_position,
@@ -84,6 +87,7 @@ impl InputSystem {
             input_buffer,
             picker_value,
             previous_mouse_button,
+            key_bindings,
         }
     }
 
@@ -210,40 +214,22 @@ impl InputSystem {
             events.push(InputEvent::ToggleMenuWindow);
         }
 
-        if alt_down && self.get_key(KeyCode::KeyE).pressed() {
-            events.push(InputEvent::ToggleInventoryWindow);
-        }
-
-        if alt_down && self.get_key(KeyCode::KeyS).pressed() {
-            events.push(InputEvent::ToggleSkillTreeWindow);
-        }
-
-        if alt_down && self.get_key(KeyCode::KeyA).pressed() {
-            events.push(InputEvent::ToggleStatsWindow);
-        }
-
-        if alt_down && self.get_key(KeyCode::KeyZ).pressed() {
-            events.push(InputEvent::ToggleFriendListWindow);
-        }
-
-        if alt_down && self.get_key(KeyCode::KeyQ).pressed() {
-            events.push(InputEvent::ToggleEquipmentWindow);
-        }
-
-        if control_down && self.get_key(KeyCode::KeyS).pressed() {
-            events.push(InputEvent::ToggleGameSettingsWindow);
-        }
-
-        if control_down && self.get_key(KeyCode::KeyI).pressed() {
-            events.push(InputEvent::ToggleInterfaceSettingsWindow);
-        }
+        for (action, binding) in self.key_bindings.iter() {
+            if binding.alt == alt_down && binding.control == control_down && self.get_key(binding.key.key_code()).pressed() {
+                events.push(action.input_event());
+            }
 
-        if control_down && self.get_key(KeyCode::KeyG).pressed() {
-            events.push(InputEvent::ToggleGraphicsSettingsWindow);
+            // The modifiers are not checked here, so letting go of them first
+            // doesn't leave the action held.
+            if self.get_key(binding.key.key_code()).released()
+                && let Some(event) = action.release_input_event()
+            {
+                events.push(event);
+            }
         }
 
-        if control_down && self.get_key(KeyCode::KeyA).pressed() {
-            events.push(InputEvent::ToggleAudioSettingsWindow);
+        if alt_down && self.get_key(KeyCode::Enter).pressed() {
+            events.push(InputEvent::ToggleFullscreen);
         }
 
         if control_down && self.get_key(KeyCode::KeyH).pressed() {
@@ -303,6 +289,21 @@ impl InputSystem {
             events.push(InputEvent::TogglePacketInspectorWindow);
         }
 
+        #[cfg(feature = "debug")]
+        if control_down && self.get_key(KeyCode::KeyB).pressed() {
+            events.push(InputEvent::ToggleAssetBrowserWindow);
+        }
+
+        #[cfg(feature = "debug")]
+        if control_down && self.get_key(KeyCode::KeyE).pressed() {
+            events.push(InputEvent::ToggleEffectPreviewWindow);
+        }
+
+        #[cfg(feature = "debug")]
+        if control_down && self.get_key(KeyCode::KeyO).pressed() {
+            events.push(InputEvent::ToggleModelViewerWindow);
+        }
+
         #[cfg(feature = "debug")]
         if self.get_key(KeyCode::ShiftLeft).pressed() && use_debug_camera {
             events.push(InputEvent::CameraAccelerate);
@@ -347,4 +348,22 @@ impl InputSystem {
 
         self.input_buffer.clear();
     }
+
+    /// Handle the keys of the debug console. Unlike the other keybinds these
+    /// are also handled while the interface has focus, so the console can be
+    /// used while typing in it.
+    #[cfg(feature = "debug")]
+    pub fn handle_console_input(&self, events: &mut Vec<InputEvent>, console_open: bool) {
+        if self.get_key(KeyCode::Backquote).pressed() {
+            events.push(InputEvent::ToggleConsoleWindow);
+        }
+
+        if console_open && self.get_key(KeyCode::ArrowUp).pressed() {
+            events.push(InputEvent::ConsoleHistoryPrevious);
+        }
+
+        if console_open && self.get_key(KeyCode::ArrowDown).pressed() {
+            events.push(InputEvent::ConsoleHistoryNext);
+        }
+    }
 }
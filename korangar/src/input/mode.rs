@@ -9,6 +9,8 @@ use crate::interface::resource::{ItemSource, SkillSource};
 use crate::inventory::Skill;
 use crate::loaders::Sprite;
 use crate::state::ClientState;
+#[cfg(feature = "debug")]
+use crate::world::MarkerIdentifier;
 use crate::world::{Actions, ResourceMetadata, SpriteAnimationState};
 
 #[derive(Debug, Clone)]
@@ -25,6 +27,16 @@ pub enum MouseInputMode {
         source: SkillSource,
         skill: Skill,
     },
+    /// Dragging a marker in the map editor.
+    #[cfg(feature = "debug")]
+    MoveMarker {
+        marker_identifier: MarkerIdentifier,
+    },
+    /// Painting tiles with the walkability brush of the map editor.
+    #[cfg(feature = "debug")]
+    PaintTiles {
+        walkable: bool,
+    },
 }
 
 impl From<MouseInputMode> for MouseMode<ClientState> {
@@ -44,6 +56,14 @@ pub trait MouseModeExt {
     fn walk_destination(&self) -> Option<TilePosition>;
 
     fn grabbed(&self) -> Option<Grabbed>;
+
+    fn dragged_item(&self) -> Option<(ItemSource, InventoryItem<ResourceMetadata>)>;
+
+    #[cfg(feature = "debug")]
+    fn moving_marker(&self) -> Option<MarkerIdentifier>;
+
+    #[cfg(feature = "debug")]
+    fn painted_walkability(&self) -> Option<bool>;
 }
 
 impl MouseModeExt for MouseMode<ClientState> {
@@ -77,4 +97,33 @@ impl MouseModeExt for MouseMode<ClientState> {
             _ => None,
         }
     }
+
+    fn dragged_item(&self) -> Option<(ItemSource, InventoryItem<ResourceMetadata>)> {
+        match self {
+            MouseMode::Custom {
+                mode: MouseInputMode::MoveItem { source, item },
+            } => Some((*source, item.clone())),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "debug")]
+    fn moving_marker(&self) -> Option<MarkerIdentifier> {
+        match self {
+            MouseMode::Custom {
+                mode: MouseInputMode::MoveMarker { marker_identifier },
+            } => Some(*marker_identifier),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "debug")]
+    fn painted_walkability(&self) -> Option<bool> {
+        match self {
+            MouseMode::Custom {
+                mode: MouseInputMode::PaintTiles { walkable },
+            } => Some(*walkable),
+            _ => None,
+        }
+    }
 }
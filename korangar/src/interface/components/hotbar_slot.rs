@@ -0,0 +1,167 @@
+use korangar_interface::MouseMode;
+use korangar_interface::element::store::{ElementStore, ElementStoreMut};
+use korangar_interface::element::{BaseLayoutInfo, Element};
+use korangar_interface::event::{ClickHandler, EventQueue};
+use korangar_interface::layout::area::Area;
+use korangar_interface::layout::{MouseButton, Resolver, WindowLayout};
+use korangar_interface::prelude::{HorizontalAlignment, VerticalAlignment};
+use ragnarok_packets::HotbarSlot;
+use rust_state::{Context, Path};
+
+use super::skill_box::SkillBox;
+use crate::graphics::{Color, CornerDiameter, ShadowPadding};
+use crate::input::{InputEvent, MouseInputMode};
+use crate::interface::resource::{ItemSource, SkillSource};
+use crate::interface::transfer::ItemDropHandler;
+use crate::inventory::{HotbarItem, Skill};
+use crate::loaders::{FontSize, OverflowBehavior};
+use crate::renderer::LayoutExt;
+use crate::state::ClientState;
+
+#[derive(Default)]
+struct AmountDisplay {
+    amount: u16,
+    string: Option<String>,
+}
+
+impl AmountDisplay {
+    fn update(&mut self, new_amount: u16) {
+        if self.string.is_none() || self.amount != new_amount {
+            self.string = Some(new_amount.to_string());
+            self.amount = new_amount;
+        }
+    }
+}
+
+struct HotbarItemHandler {
+    slot: HotbarSlot,
+}
+
+impl ClickHandler<ClientState> for HotbarItemHandler {
+    fn handle_click(&self, _: &Context<ClientState>, queue: &mut EventQueue<ClientState>) {
+        queue.queue(InputEvent::UseHotbarItem { slot: self.slot });
+    }
+}
+
+/// Slot of the hotbar. Holds either a skill, which is displayed by a
+/// [`SkillBox`], or a consumable with the amount that is left in the inventory.
+pub struct HotbarSlotBox<A, B> {
+    skill_box: SkillBox<A>,
+    item_path: B,
+    item_handler: HotbarItemHandler,
+    drop_handler: ItemDropHandler,
+    amount_display: AmountDisplay,
+}
+
+impl<A, B> HotbarSlotBox<A, B>
+where
+    A: Copy,
+{
+    /// This function is supposed to be called from a component macro
+    /// and not intended to be called manually.
+    #[inline(always)]
+    pub fn component_new(skill_path: A, item_path: B, slot: HotbarSlot) -> Self {
+        Self {
+            skill_box: SkillBox::component_new(skill_path, SkillSource::Hotbar { slot }),
+            item_path,
+            item_handler: HotbarItemHandler { slot },
+            drop_handler: ItemDropHandler::new(ItemSource::Hotbar { slot }),
+            amount_display: AmountDisplay::default(),
+        }
+    }
+}
+
+impl<A, B> Element<ClientState> for HotbarSlotBox<A, B>
+where
+    A: Path<ClientState, Skill, false>,
+    B: Path<ClientState, HotbarItem, false>,
+{
+    type LayoutInfo = BaseLayoutInfo;
+
+    fn create_layout_info(
+        &mut self,
+        state: &Context<ClientState>,
+        store: ElementStoreMut<'_>,
+        resolver: &mut Resolver<'_, ClientState>,
+    ) -> Self::LayoutInfo {
+        if let Some(item) = state.try_get(&self.item_path) {
+            self.amount_display.update(item.amount);
+        }
+
+        self.skill_box.create_layout_info(state, store, resolver)
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a Context<ClientState>,
+        store: ElementStore<'a>,
+        layout_info: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        self.skill_box.lay_out(state, store, layout_info, layout);
+
+        if let MouseMode::Custom {
+            mode: MouseInputMode::MoveItem { .. },
+        } = layout.get_mouse_mode()
+        {
+            let is_hovered = layout_info.area.check().any_mouse_mode().run(layout);
+
+            if is_hovered {
+                // Since we are not in default mouse mode we need to mark the window as
+                // hovered.
+                layout.set_hovered();
+                layout.register_drop_handler(&self.drop_handler);
+            }
+
+            let highlight_color = match is_hovered {
+                true => Color::rgba_u8(80, 180, 180, 120),
+                false => Color::rgba_u8(180, 180, 80, 120),
+            };
+
+            layout.add_rectangle(
+                layout_info.area,
+                CornerDiameter::uniform(20.0),
+                highlight_color,
+                Color::TRANSPARENT,
+                ShadowPadding::uniform(0.0),
+            );
+        }
+
+        if let Some(item) = state.try_get(&self.item_path) {
+            let texture_size = layout_info.area.width.min(layout_info.area.height);
+            let texture_area = Area {
+                left: layout_info.area.left + (layout_info.area.width - texture_size) / 2.0,
+                top: layout_info.area.top + (layout_info.area.height - texture_size) / 2.0,
+                width: texture_size,
+                height: texture_size,
+            };
+
+            // Grey the item out once there are none left, so the slot doesn't have to
+            // be set again after restocking.
+            match (&item.texture, item.amount) {
+                (Some(texture), 0) => layout.add_texture(texture_area, texture.clone(), Color::rgba_u8(90, 90, 90, 160), false),
+                (texture, _) => layout.add_item_icon(texture_area, texture.as_ref()),
+            }
+
+            if item.amount > 0 && layout_info.area.check().run(layout) {
+                layout.register_click_handler(MouseButton::Left, &self.item_handler);
+            }
+
+            layout.add_text(
+                layout_info.area,
+                self.amount_display.string.as_ref().unwrap(),
+                // TODO: Put this in the theme
+                FontSize(12.0),
+                // TODO: Put this in the theme
+                Color::rgb_u8(255, 200, 255),
+                // TODO: Put this in the theme
+                Color::rgb_u8(255, 160, 60),
+                // TODO: Put this in the theme
+                HorizontalAlignment::Right { offset: 3.0, border: 3.0 },
+                // TODO: Put this in the theme
+                VerticalAlignment::Bottom { offset: 3.0 },
+                OverflowBehavior::Shrink,
+            );
+        }
+    }
+}
@@ -1,19 +1,23 @@
 use korangar_interface::MouseMode;
 use korangar_interface::element::store::{ElementStore, ElementStoreMut};
 use korangar_interface::element::{BaseLayoutInfo, Element};
-use korangar_interface::event::{ClickHandler, DropHandler, Event, EventQueue};
+use korangar_interface::event::{ClickHandler, Event, EventQueue};
 use korangar_interface::layout::area::Area;
+use korangar_interface::layout::tooltip::TooltipExt;
 use korangar_interface::layout::{MouseButton, Resolver, WindowLayout};
 use korangar_interface::prelude::{HorizontalAlignment, VerticalAlignment};
 use korangar_networking::{InventoryItem, InventoryItemDetails};
+use ragnarok_packets::{EquipPosition, InventoryIndex};
 use rust_state::{Context, Path};
 
 use crate::graphics::{Color, CornerDiameter, ShadowPadding};
 use crate::input::{InputEvent, MouseInputMode};
 use crate::interface::resource::ItemSource;
+use crate::interface::transfer::ItemDropHandler;
+use crate::inventory::InventoryPathExt;
 use crate::loaders::{FontSize, OverflowBehavior};
 use crate::renderer::LayoutExt;
-use crate::state::ClientState;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
 use crate::world::ResourceMetadata;
 
 #[derive(Default)]
@@ -61,28 +65,101 @@ where
     }
 }
 
-impl<P> DropHandler<ClientState> for ItemBoxHandler<P>
+/// Handles right clicks on the item. Equipped items are taken off, all other
+/// items are locked or unlocked.
+struct ItemSecondaryHandler<P> {
+    item_path: P,
+    source: ItemSource,
+}
+
+impl<P> ClickHandler<ClientState> for ItemSecondaryHandler<P>
 where
     P: Path<ClientState, InventoryItem<ResourceMetadata>, false>,
 {
-    fn handle_drop(&self, _: &Context<ClientState>, queue: &mut EventQueue<ClientState>, mouse_mode: &MouseMode<ClientState>) {
-        if let MouseMode::Custom {
-            mode: MouseInputMode::MoveItem { source, item },
-        } = mouse_mode
-        {
-            queue.queue(InputEvent::MoveItem {
-                source: *source,
-                destination: self.source,
+    fn handle_click(&self, state: &Context<ClientState>, queue: &mut EventQueue<ClientState>) {
+        // SAFETY:
+        //
+        // Unwrapping here is fine since we only register the handler if the slot has a
+        // item.
+        let item = state.try_get(&self.item_path).unwrap();
+
+        match self.source {
+            ItemSource::Equipment { .. } => queue.queue(InputEvent::MoveItem {
+                source: self.source,
+                destination: ItemSource::Inventory,
                 item: item.clone(),
-            });
+            }),
+            _ => queue.queue(InputEvent::ToggleItemLock { item_id: item.item_id }),
+        }
+    }
+}
+
+/// Tooltip of an item in the inventory, followed by the tooltips of the items
+/// that are equipped in the same position.
+#[derive(Default)]
+struct ComparisonTooltip {
+    indices: Vec<InventoryIndex>,
+    string: Option<String>,
+}
+
+impl ComparisonTooltip {
+    fn update(&mut self, item: &InventoryItem<ResourceMetadata>, inventory_items: &[InventoryItem<ResourceMetadata>]) {
+        let InventoryItemDetails::Equippable {
+            equip_position,
+            equipped_position,
+            ..
+        } = &item.details
+        else {
+            self.clear();
+            return;
+        };
+
+        if *equipped_position != EquipPosition::NONE {
+            self.clear();
+            return;
+        }
+
+        let equipped_items: Vec<&InventoryItem<ResourceMetadata>> = inventory_items
+            .iter()
+            .filter(|equipped_item| equipped_item.equipped_position().intersects(*equip_position))
+            .collect();
+
+        if equipped_items.is_empty() {
+            self.clear();
+            return;
+        }
+
+        let indices: Vec<InventoryIndex> = std::iter::once(item.index)
+            .chain(equipped_items.iter().map(|equipped_item| equipped_item.index))
+            .collect();
+
+        if self.string.is_none() || self.indices != indices {
+            let mut string = item.metadata.tooltip.clone();
+
+            for equipped_item in equipped_items {
+                string.push_str("\n\nCurrently equipped:\n");
+                string.push_str(&equipped_item.metadata.tooltip);
+            }
+
+            self.string = Some(string);
+            self.indices = indices;
         }
     }
+
+    fn clear(&mut self) {
+        self.indices.clear();
+        self.string = None;
+    }
 }
 
 pub struct ItemBox<A> {
     item_path: A,
     handler: ItemBoxHandler<A>,
+    drop_handler: ItemDropHandler,
+    secondary_handler: ItemSecondaryHandler<A>,
     amount_display: AmountDisplay,
+    comparison_tooltip: ComparisonTooltip,
+    source: ItemSource,
 }
 
 impl<A> ItemBox<A>
@@ -96,7 +173,11 @@ where
         Self {
             item_path,
             handler: ItemBoxHandler::new(item_path, source),
+            drop_handler: ItemDropHandler::new(source),
+            secondary_handler: ItemSecondaryHandler { item_path, source },
             amount_display: AmountDisplay::default(),
+            comparison_tooltip: ComparisonTooltip::default(),
+            source,
         }
     }
 }
@@ -116,12 +197,19 @@ where
         let area = resolver.with_height(40.0);
 
         if let Some(item) = state.try_get(&self.item_path)
-            && item.metadata.texture.as_ref().is_some()
             && let InventoryItemDetails::Regular { amount, .. } = &item.details
         {
             self.amount_display.update(*amount);
         }
 
+        match state.try_get(&self.item_path) {
+            Some(item) if self.source == ItemSource::Inventory => {
+                let inventory_items = state.get(&client_state().inventory().items());
+                self.comparison_tooltip.update(item, inventory_items);
+            }
+            _ => self.comparison_tooltip.clear(),
+        }
+
         Self::LayoutInfo { area }
     }
 
@@ -160,12 +248,10 @@ where
         );
 
         if is_hovered {
-            layout.register_drop_handler(&self.handler);
+            layout.register_drop_handler(&self.drop_handler);
         }
 
-        if let Some(item) = state.try_get(&self.item_path)
-            && let Some(texture) = item.metadata.texture.as_ref()
-        {
+        if let Some(item) = state.try_get(&self.item_path) {
             let texture_size = layout_info.area.width.min(layout_info.area.height);
             let texture_area = Area {
                 left: layout_info.area.left + (layout_info.area.width - texture_size) / 2.0,
@@ -174,10 +260,55 @@ where
                 height: texture_size,
             };
 
-            layout.add_texture(texture_area, texture.clone(), Color::WHITE, false);
+            layout.add_item_icon(texture_area, item.metadata.texture.as_ref());
 
             if is_hovered {
                 layout.register_click_handler(MouseButton::Left, &self.handler);
+                layout.register_click_handler(MouseButton::Right, &self.secondary_handler);
+
+                let tooltip = self.comparison_tooltip.string.as_ref().unwrap_or(&item.metadata.tooltip);
+
+                struct ItemTooltip;
+                layout.add_tooltip(tooltip, ItemTooltip.tooltip_id());
+            }
+
+            // One marker per card slot, filled if the slot contains a card.
+            for slot in 0..item.metadata.slot_count {
+                let color = match (slot as usize) < item.metadata.cards.len() {
+                    // TODO: Put this in the theme
+                    true => Color::rgb_u8(120, 200, 255),
+                    // TODO: Put this in the theme
+                    false => Color::rgb_u8(90, 90, 90),
+                };
+
+                layout.add_rectangle(
+                    Area {
+                        left: layout_info.area.left + 3.0 + slot as f32 * 7.0,
+                        top: layout_info.area.top + layout_info.area.height - 9.0,
+                        width: 6.0,
+                        height: 6.0,
+                    },
+                    CornerDiameter::uniform(3.0),
+                    color,
+                    Color::rgba_u8(0, 0, 0, 100),
+                    ShadowPadding::diagonal(1.0, 2.0),
+                );
+            }
+
+            if state.get(&client_state().game_settings()).is_item_locked(item.item_id) {
+                layout.add_rectangle(
+                    Area {
+                        left: layout_info.area.left + 3.0,
+                        top: layout_info.area.top + 3.0,
+                        width: 8.0,
+                        height: 8.0,
+                    },
+                    CornerDiameter::uniform(4.0),
+                    // TODO: Put this in the theme
+                    Color::rgb_u8(230, 180, 60),
+                    Color::rgba_u8(0, 0, 0, 100),
+                    ShadowPadding::diagonal(1.0, 2.0),
+                );
             }
 
             if matches!(item.details, InventoryItemDetails::Regular { .. }) {
@@ -1,2 +1,6 @@
+pub mod hotbar_slot;
 pub mod item_box;
+pub mod radial_menu;
 pub mod skill_box;
+#[cfg(feature = "debug")]
+pub mod text_list;
@@ -0,0 +1,151 @@
+use std::f32::consts::TAU;
+
+use korangar_interface::element::store::{ElementStore, ElementStoreMut};
+use korangar_interface::element::{BaseLayoutInfo, Element};
+use korangar_interface::layout::area::Area;
+use korangar_interface::layout::{Resolver, WindowLayout};
+use korangar_interface::prelude::{HorizontalAlignment, VerticalAlignment};
+use rust_state::{Context, Path};
+
+use crate::graphics::{Color, CornerDiameter, ScreenSize, ShadowPadding};
+use crate::loaders::{FontSize, OverflowBehavior};
+use crate::state::ClientState;
+
+const MENU_SIZE: f32 = 260.0;
+const ENTRY_WIDTH: f32 = 84.0;
+const ENTRY_HEIGHT: f32 = 26.0;
+
+/// Segment of a radial menu with `count` entries that lies in the direction
+/// of `offset`. The first segment is at the top and the segments go around
+/// clockwise. Offsets inside the dead zone don't select anything, so the menu
+/// can be closed without picking an entry.
+///
+/// Only the direction matters, so the offset can come from the mouse as well
+/// as from a controller stick.
+pub fn radial_segment(offset: ScreenSize, count: usize, dead_zone: f32) -> Option<usize> {
+    if count == 0 || offset.width.hypot(offset.height) < dead_zone {
+        return None;
+    }
+
+    let segment_angle = TAU / count as f32;
+    let angle = offset.width.atan2(-offset.height).rem_euclid(TAU);
+
+    Some(((angle + segment_angle / 2.0) / segment_angle) as usize % count)
+}
+
+/// Entries arranged in a circle, with the selected one highlighted. The menu
+/// doesn't handle any input itself, the selection is driven by the owner
+/// through [`radial_segment`].
+pub struct RadialMenu<A, B> {
+    entries_path: A,
+    selected_path: B,
+}
+
+impl<A, B> RadialMenu<A, B> {
+    /// This function is supposed to be called from a component macro
+    /// and not intended to be called manually.
+    #[inline(always)]
+    pub fn component_new(entries_path: A, selected_path: B) -> Self {
+        Self {
+            entries_path,
+            selected_path,
+        }
+    }
+}
+
+impl<A, B> Element<ClientState> for RadialMenu<A, B>
+where
+    A: Path<ClientState, Vec<String>>,
+    B: Path<ClientState, Option<usize>>,
+{
+    type LayoutInfo = BaseLayoutInfo;
+
+    fn create_layout_info(
+        &mut self,
+        _: &Context<ClientState>,
+        _: ElementStoreMut<'_>,
+        resolver: &mut Resolver<'_, ClientState>,
+    ) -> Self::LayoutInfo {
+        let area = resolver.with_height(MENU_SIZE);
+
+        Self::LayoutInfo { area }
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a Context<ClientState>,
+        _: ElementStore<'a>,
+        layout_info: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        let entries = state.get(&self.entries_path);
+        let selected = *state.get(&self.selected_path);
+
+        let center_left = layout_info.area.left + layout_info.area.width / 2.0;
+        let center_top = layout_info.area.top + layout_info.area.height / 2.0;
+        let radius = (layout_info.area.width.min(layout_info.area.height) - ENTRY_WIDTH) / 2.0;
+        let segment_angle = TAU / entries.len().max(1) as f32;
+
+        for (index, entry) in entries.iter().enumerate() {
+            let angle = index as f32 * segment_angle;
+            let area = Area {
+                left: center_left + angle.sin() * radius - ENTRY_WIDTH / 2.0,
+                top: center_top - angle.cos() * radius - ENTRY_HEIGHT / 2.0,
+                width: ENTRY_WIDTH,
+                height: ENTRY_HEIGHT,
+            };
+
+            let background_color = match selected == Some(index) {
+                // TODO: Put this in the theme
+                true => Color::rgb_u8(80, 180, 180),
+                // TODO: Put this in the theme
+                false => Color::rgb_u8(40, 40, 40),
+            };
+
+            layout.add_rectangle(
+                area,
+                CornerDiameter::uniform(13.0),
+                background_color,
+                Color::rgba_u8(0, 0, 0, 100),
+                ShadowPadding::diagonal(2.0, 5.0),
+            );
+
+            layout.add_text(
+                area,
+                entry,
+                // TODO: Put this in the theme
+                FontSize(14.0),
+                Color::WHITE,
+                // TODO: Put this in the theme
+                Color::rgb_u8(255, 160, 60),
+                HorizontalAlignment::Center { offset: 0.0, border: 4.0 },
+                VerticalAlignment::Center { offset: 0.0 },
+                OverflowBehavior::Shrink,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::radial_segment;
+    use crate::graphics::ScreenSize;
+
+    #[test]
+    fn segments_go_around_clockwise() {
+        let segment = |width, height| radial_segment(ScreenSize { width, height }, 4, 10.0);
+
+        assert_eq!(segment(0.0, -50.0), Some(0));
+        assert_eq!(segment(50.0, 0.0), Some(1));
+        assert_eq!(segment(0.0, 50.0), Some(2));
+        assert_eq!(segment(-50.0, 0.0), Some(3));
+        // Slightly left of the top still belongs to the first segment.
+        assert_eq!(segment(-10.0, -50.0), Some(0));
+    }
+
+    #[test]
+    fn dead_zone_selects_nothing() {
+        assert_eq!(radial_segment(ScreenSize { width: 3.0, height: 4.0 }, 8, 10.0), None);
+        assert_eq!(radial_segment(ScreenSize { width: 30.0, height: 40.0 }, 0, 10.0), None);
+    }
+}
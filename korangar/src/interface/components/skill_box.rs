@@ -176,6 +176,7 @@ where
                 &skill.actions,
                 &skill.sprite,
                 &skill.animation_state,
+                0,
                 Color::WHITE,
             );
 
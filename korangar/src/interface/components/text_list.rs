@@ -0,0 +1,72 @@
+use std::cmp::Ordering;
+
+use korangar_interface::element::store::{ElementStore, ElementStoreMut};
+use korangar_interface::element::{Element, ElementBox};
+use korangar_interface::layout::{Resolver, WindowLayout};
+use rust_state::{Context, ManuallyAssertExt, Path, VecIndexExt};
+
+use crate::state::ClientState;
+
+/// Shows a line of text for every string in a list. Elements are added and
+/// removed as the list changes.
+pub struct TextList<A> {
+    texts_path: A,
+    elements: Vec<ElementBox<ClientState>>,
+}
+
+impl<A> TextList<A> {
+    pub fn new(texts_path: A) -> Self {
+        Self {
+            texts_path,
+            elements: Vec::new(),
+        }
+    }
+}
+
+impl<A> Element<ClientState> for TextList<A>
+where
+    A: Path<ClientState, Vec<String>>,
+{
+    type LayoutInfo = ();
+
+    fn create_layout_info(
+        &mut self,
+        state: &Context<ClientState>,
+        mut store: ElementStoreMut<'_>,
+        resolver: &mut Resolver<'_, ClientState>,
+    ) -> Self::LayoutInfo {
+        use korangar_interface::prelude::*;
+
+        let texts = state.get(&self.texts_path);
+
+        match texts.len().cmp(&self.elements.len()) {
+            Ordering::Less => {
+                self.elements.truncate(texts.len());
+            }
+            Ordering::Equal => {}
+            Ordering::Greater => {
+                for index in self.elements.len()..texts.len() {
+                    self.elements.push(ErasedElement::new(text! {
+                        text: self.texts_path.index(index).manually_asserted(),
+                    }));
+                }
+            }
+        }
+
+        self.elements.iter_mut().enumerate().for_each(|(index, element)| {
+            element.create_layout_info(state, store.child_store(index as u64), resolver);
+        });
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a Context<ClientState>,
+        store: ElementStore<'a>,
+        _: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        self.elements.iter().enumerate().for_each(|(index, element)| {
+            element.lay_out(state, store.child_store(index as u64), &(), layout);
+        });
+    }
+}
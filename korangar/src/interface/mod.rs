@@ -1,4 +1,7 @@
 pub mod components;
 pub mod cursor;
 pub mod resource;
+pub mod sound;
+pub mod toast;
+pub mod transfer;
 pub mod windows;
@@ -3,7 +3,16 @@ use ragnarok_packets::{EquipPosition, HotbarSlot};
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ItemSource {
     Inventory,
-    Equipment { position: EquipPosition },
+    Equipment {
+        position: EquipPosition,
+    },
+    Hotbar {
+        slot: HotbarSlot,
+    },
+    Storage,
+    Trade,
+    /// The item was dropped outside of the interface.
+    Ground,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
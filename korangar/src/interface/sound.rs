@@ -0,0 +1,70 @@
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use korangar_audio::{AudioEngine, SoundEffectKey};
+
+use crate::loaders::GameFileLoader;
+
+// All sound effects are loaded from `data\wav`. If a data pack doesn't
+// contain one of them, the corresponding feedback is silent.
+const CLICK_SOUND_EFFECT: &str = "버튼소리.wav";
+const OPEN_WINDOW_SOUND_EFFECT: &str = "버튼소리.wav";
+const CLOSE_WINDOW_SOUND_EFFECT: &str = "버튼소리.wav";
+const ERROR_SOUND_EFFECT: &str = "error.wav";
+const WHISPER_SOUND_EFFECT: &str = "whisper.wav";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterfaceSound {
+    Click,
+    OpenWindow,
+    CloseWindow,
+    Error,
+    Whisper,
+}
+
+/// Feedback of the user interface. All sounds are played on the interface
+/// track of the audio engine, so their volume can be set independently of
+/// the game world.
+pub struct InterfaceSoundEffects {
+    audio_engine: Arc<AudioEngine<GameFileLoader>>,
+    click: SoundEffectKey,
+    open_window: SoundEffectKey,
+    close_window: SoundEffectKey,
+    error: SoundEffectKey,
+    whisper: SoundEffectKey,
+}
+
+impl InterfaceSoundEffects {
+    pub fn new(audio_engine: Arc<AudioEngine<GameFileLoader>>) -> Self {
+        Self {
+            click: audio_engine.load(CLICK_SOUND_EFFECT),
+            open_window: audio_engine.load(OPEN_WINDOW_SOUND_EFFECT),
+            close_window: audio_engine.load(CLOSE_WINDOW_SOUND_EFFECT),
+            error: audio_engine.load(ERROR_SOUND_EFFECT),
+            whisper: audio_engine.load(WHISPER_SOUND_EFFECT),
+            audio_engine,
+        }
+    }
+
+    pub fn play(&self, sound: InterfaceSound) {
+        let sound_effect_key = match sound {
+            InterfaceSound::Click => self.click,
+            InterfaceSound::OpenWindow => self.open_window,
+            InterfaceSound::CloseWindow => self.close_window,
+            InterfaceSound::Error => self.error,
+            InterfaceSound::Whisper => self.whisper,
+        };
+
+        self.audio_engine.play_interface_sound_effect(sound_effect_key);
+    }
+
+    /// Plays the feedback for windows that were opened or closed since the
+    /// window count was last taken.
+    pub fn play_window_change(&self, previous_window_count: usize, window_count: usize) {
+        match window_count.cmp(&previous_window_count) {
+            Ordering::Greater => self.play(InterfaceSound::OpenWindow),
+            Ordering::Less => self.play(InterfaceSound::CloseWindow),
+            Ordering::Equal => {}
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::graphics::{Color, ScreenPosition, ScreenSize};
+use crate::renderer::{AlignHorizontal, GameInterfaceRenderer};
+use crate::state::theme::ToastTheme;
+
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+const TOAST_FADE_DURATION: Duration = Duration::from_millis(500);
+/// Number of toasts shown at once. If more arrive, the oldest ones are
+/// dismissed early.
+const MAXIMUM_TOASTS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastCategory {
+    FriendLogin,
+    RareDrop,
+    Achievement,
+    SystemWarning,
+}
+
+struct Toast {
+    category: ToastCategory,
+    text: String,
+    created_at: Instant,
+}
+
+impl Toast {
+    fn opacity(&self, now: Instant) -> f32 {
+        let remaining = TOAST_DURATION.saturating_sub(now.saturating_duration_since(self.created_at));
+        (remaining.as_secs_f32() / TOAST_FADE_DURATION.as_secs_f32()).min(1.0)
+    }
+}
+
+/// Short notifications for game events that are stacked in the top right
+/// corner of the screen and dismissed after a few seconds.
+#[derive(Default)]
+pub struct Toasts {
+    toasts: VecDeque<Toast>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, category: ToastCategory, text: String, now: Instant) {
+        if self.toasts.len() == MAXIMUM_TOASTS {
+            self.toasts.pop_front();
+        }
+
+        self.toasts.push_back(Toast {
+            category,
+            text,
+            created_at: now,
+        });
+    }
+
+    /// Dismiss all toasts that have been shown for long enough.
+    pub fn update(&mut self, now: Instant) {
+        self.toasts
+            .retain(|toast| now.saturating_duration_since(toast.created_at) < TOAST_DURATION);
+    }
+
+    pub fn render(&self, renderer: &GameInterfaceRenderer, theme: &ToastTheme, window_size: ScreenSize, scaling: f32, now: Instant) {
+        let size = theme.size * scaling;
+        let margin = theme.margin * scaling;
+        let gap = theme.gap * scaling;
+
+        // The newest toast is shown at the top.
+        for (index, toast) in self.toasts.iter().rev().enumerate() {
+            let opacity = toast.opacity(now);
+            let with_opacity = |color: Color| Color {
+                alpha: color.alpha * opacity,
+                ..color
+            };
+
+            let position = ScreenPosition {
+                left: window_size.width - margin.width - size.width,
+                top: margin.height + index as f32 * (size.height + gap),
+            };
+
+            let accent_color = match toast.category {
+                ToastCategory::FriendLogin => theme.friend_login_color,
+                ToastCategory::RareDrop => theme.rare_drop_color,
+                ToastCategory::Achievement => theme.achievement_color,
+                ToastCategory::SystemWarning => theme.system_warning_color,
+            };
+
+            renderer.render_rectangle(position, size, with_opacity(theme.background_color));
+            renderer.render_rectangle(
+                position,
+                ScreenSize {
+                    width: theme.accent_width * scaling,
+                    height: size.height,
+                },
+                with_opacity(accent_color),
+            );
+            renderer.render_text(
+                &toast.text,
+                position + theme.text_offset * scaling,
+                with_opacity(theme.foreground_color),
+                theme.font_size,
+                AlignHorizontal::Left,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::{MAXIMUM_TOASTS, TOAST_DURATION, TOAST_FADE_DURATION, ToastCategory, Toasts};
+
+    #[test]
+    fn oldest_toast_is_dismissed_when_full() {
+        let mut toasts = Toasts::default();
+        let now = Instant::now();
+
+        for index in 0..=MAXIMUM_TOASTS {
+            toasts.push(ToastCategory::SystemWarning, index.to_string(), now);
+        }
+
+        assert_eq!(toasts.toasts.len(), MAXIMUM_TOASTS);
+        assert_eq!(toasts.toasts.front().unwrap().text, "1");
+    }
+
+    #[test]
+    fn toasts_fade_out_and_expire() {
+        let mut toasts = Toasts::default();
+        let now = Instant::now();
+
+        toasts.push(ToastCategory::FriendLogin, "Poring has logged in".to_owned(), now);
+        toasts.push(ToastCategory::RareDrop, "Poring Card".to_owned(), now + Duration::from_secs(1));
+
+        let fade_start = now + TOAST_DURATION - TOAST_FADE_DURATION;
+        assert_eq!(toasts.toasts[0].opacity(fade_start), 1.0);
+        assert_eq!(toasts.toasts[0].opacity(fade_start + TOAST_FADE_DURATION / 2), 0.5);
+
+        toasts.update(now + TOAST_DURATION);
+        assert_eq!(toasts.toasts.len(), 1);
+        assert_eq!(toasts.toasts[0].category, ToastCategory::RareDrop);
+    }
+}
@@ -0,0 +1,224 @@
+use korangar_interface::MouseMode;
+use korangar_interface::event::{DropHandler, EventQueue};
+use korangar_networking::{InventoryItem, InventoryItemDetails};
+use ragnarok_packets::{EquipPosition, HotbarSlot, InventoryIndex, ItemId};
+use rust_state::Context;
+
+use crate::input::{InputEvent, MouseInputMode};
+use crate::interface::resource::ItemSource;
+use crate::state::ClientState;
+
+/// Drop handler shared by every element that accepts dragged items. Whether
+/// the item can actually be moved there is decided by [`ItemTransfer::new`].
+pub struct ItemDropHandler {
+    destination: ItemSource,
+}
+
+impl ItemDropHandler {
+    pub fn new(destination: ItemSource) -> Self {
+        Self { destination }
+    }
+}
+
+impl DropHandler<ClientState> for ItemDropHandler {
+    fn handle_drop(&self, _: &Context<ClientState>, queue: &mut EventQueue<ClientState>, mouse_mode: &MouseMode<ClientState>) {
+        if let MouseMode::Custom {
+            mode: MouseInputMode::MoveItem { source, item },
+        } = mouse_mode
+        {
+            queue.queue(InputEvent::MoveItem {
+                source: *source,
+                destination: self.destination,
+                item: item.clone(),
+            });
+        }
+    }
+}
+
+/// Reason why an item can't be moved to the destination it was dropped on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemTransferError {
+    /// The destination doesn't accept items from the source, for example
+    /// dropping an item back where it came from.
+    Unsupported,
+    NotEquippable,
+    WrongEquipSlot,
+    NotConsumable,
+    Equipped,
+    Locked,
+}
+
+impl std::fmt::Display for ItemTransferError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ItemTransferError::Unsupported => write!(formatter, "The item can't be moved there"),
+            ItemTransferError::NotEquippable => write!(formatter, "The item can't be equipped"),
+            ItemTransferError::WrongEquipSlot => write!(formatter, "The item doesn't fit in this equipment slot"),
+            ItemTransferError::NotConsumable => write!(formatter, "Only consumables can be placed on the hotbar"),
+            ItemTransferError::Equipped => write!(formatter, "Equipped items need to be unequipped first"),
+            ItemTransferError::Locked => write!(formatter, "The item is locked"),
+        }
+    }
+}
+
+/// Action that moves an item after it was dropped on a destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemTransfer {
+    Equip { index: InventoryIndex, position: EquipPosition },
+    EquipAmmunition { index: InventoryIndex },
+    Unequip { index: InventoryIndex },
+    AssignHotbar { slot: HotbarSlot, item_id: ItemId },
+    DepositStorage { index: InventoryIndex, amount: u32 },
+    OfferTrade { index: InventoryIndex, amount: u32 },
+    DropToGround { index: InventoryIndex, amount: u16 },
+}
+
+impl ItemTransfer {
+    /// Check if the item can be moved from `source` to `destination` and
+    /// return the action that does so. Stacks are always moved as a whole.
+    pub fn new<Meta>(
+        source: ItemSource,
+        destination: ItemSource,
+        item: &InventoryItem<Meta>,
+        is_locked: bool,
+    ) -> Result<Self, ItemTransferError> {
+        let index = item.index;
+        let (amount, equipped_position) = match &item.details {
+            InventoryItemDetails::Regular {
+                amount, equipped_position, ..
+            } => (*amount, *equipped_position),
+            InventoryItemDetails::Equippable { equipped_position, .. } => (1, *equipped_position),
+        };
+        let is_equipped = equipped_position != EquipPosition::NONE;
+
+        match (source, destination) {
+            (ItemSource::Inventory, ItemSource::Equipment { position }) if position == EquipPosition::AMMO => match &item.details {
+                InventoryItemDetails::Regular { .. } => Ok(Self::EquipAmmunition { index }),
+                InventoryItemDetails::Equippable { .. } => Err(ItemTransferError::WrongEquipSlot),
+            },
+            (ItemSource::Inventory, ItemSource::Equipment { position }) => match &item.details {
+                InventoryItemDetails::Regular { .. } => Err(ItemTransferError::NotEquippable),
+                InventoryItemDetails::Equippable { equip_position, .. } if !equip_position.intersects(position) => {
+                    Err(ItemTransferError::WrongEquipSlot)
+                }
+                InventoryItemDetails::Equippable { .. } => Ok(Self::Equip { index, position }),
+            },
+            (ItemSource::Equipment { .. }, ItemSource::Inventory) => Ok(Self::Unequip { index }),
+            (ItemSource::Inventory, ItemSource::Hotbar { slot }) => match &item.details {
+                InventoryItemDetails::Regular { .. } => Ok(Self::AssignHotbar {
+                    slot,
+                    item_id: item.item_id,
+                }),
+                InventoryItemDetails::Equippable { .. } => Err(ItemTransferError::NotConsumable),
+            },
+            (ItemSource::Inventory, ItemSource::Storage | ItemSource::Trade | ItemSource::Ground) if is_equipped => {
+                Err(ItemTransferError::Equipped)
+            }
+            // Storage is safe, so locked items can still be deposited.
+            (ItemSource::Inventory, ItemSource::Storage) => Ok(Self::DepositStorage {
+                index,
+                amount: amount as u32,
+            }),
+            (ItemSource::Inventory, ItemSource::Trade | ItemSource::Ground) if is_locked => Err(ItemTransferError::Locked),
+            (ItemSource::Inventory, ItemSource::Trade) => Ok(Self::OfferTrade {
+                index,
+                amount: amount as u32,
+            }),
+            (ItemSource::Inventory, ItemSource::Ground) => Ok(Self::DropToGround { index, amount }),
+            _ => Err(ItemTransferError::Unsupported),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use korangar_networking::InventoryItem;
+    use ragnarok_packets::{EquipPosition, HotbarSlot, InventoryIndex, ItemId};
+
+    use super::{ItemTransfer, ItemTransferError};
+    use crate::interface::resource::ItemSource;
+    use crate::test_fixtures::{equippable_item, regular_item};
+
+    fn potion() -> InventoryItem<()> {
+        regular_item((), 3, 501, 0, 10)
+    }
+
+    fn sword(equipped_position: EquipPosition) -> InventoryItem<()> {
+        equippable_item((), 2, 1201, 5, EquipPosition::RIGHT_HAND, equipped_position)
+    }
+
+    #[test]
+    fn equipment_slots_check_the_item() {
+        let right_hand = ItemSource::Equipment {
+            position: EquipPosition::RIGHT_HAND,
+        };
+        let shoes = ItemSource::Equipment {
+            position: EquipPosition::SHOES,
+        };
+
+        assert_eq!(
+            ItemTransfer::new(ItemSource::Inventory, right_hand, &sword(EquipPosition::NONE), false),
+            Ok(ItemTransfer::Equip {
+                index: InventoryIndex(2),
+                position: EquipPosition::RIGHT_HAND
+            })
+        );
+        assert_eq!(
+            ItemTransfer::new(ItemSource::Inventory, shoes, &sword(EquipPosition::NONE), false),
+            Err(ItemTransferError::WrongEquipSlot)
+        );
+        assert_eq!(
+            ItemTransfer::new(ItemSource::Inventory, right_hand, &potion(), false),
+            Err(ItemTransferError::NotEquippable)
+        );
+    }
+
+    #[test]
+    fn only_consumables_go_on_the_hotbar() {
+        let hotbar = ItemSource::Hotbar { slot: HotbarSlot(4) };
+
+        assert_eq!(
+            ItemTransfer::new(ItemSource::Inventory, hotbar, &potion(), false),
+            Ok(ItemTransfer::AssignHotbar {
+                slot: HotbarSlot(4),
+                item_id: ItemId(501)
+            })
+        );
+        assert_eq!(
+            ItemTransfer::new(ItemSource::Inventory, hotbar, &sword(EquipPosition::NONE), false),
+            Err(ItemTransferError::NotConsumable)
+        );
+    }
+
+    #[test]
+    fn equipped_and_locked_items_stay_with_the_player() {
+        let equipped = sword(EquipPosition::RIGHT_HAND);
+
+        assert_eq!(
+            ItemTransfer::new(ItemSource::Inventory, ItemSource::Ground, &equipped, false),
+            Err(ItemTransferError::Equipped)
+        );
+        assert_eq!(
+            ItemTransfer::new(ItemSource::Inventory, ItemSource::Trade, &potion(), true),
+            Err(ItemTransferError::Locked)
+        );
+        assert_eq!(
+            ItemTransfer::new(ItemSource::Inventory, ItemSource::Storage, &potion(), true),
+            Ok(ItemTransfer::DepositStorage {
+                index: InventoryIndex(3),
+                amount: 10
+            })
+        );
+        assert_eq!(
+            ItemTransfer::new(ItemSource::Inventory, ItemSource::Ground, &potion(), false),
+            Ok(ItemTransfer::DropToGround {
+                index: InventoryIndex(3),
+                amount: 10
+            })
+        );
+        assert_eq!(
+            ItemTransfer::new(ItemSource::Inventory, ItemSource::Inventory, &potion(), false),
+            Err(ItemTransferError::Unsupported)
+        );
+    }
+}
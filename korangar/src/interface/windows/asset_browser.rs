@@ -0,0 +1,231 @@
+use std::cmp::Ordering;
+
+use korangar_interface::components::text_box::DefaultHandler;
+use korangar_interface::element::store::{ElementStore, ElementStoreMut};
+use korangar_interface::element::{Element, ElementBox};
+use korangar_interface::layout::area::Area;
+use korangar_interface::layout::{Resolver, WindowLayout};
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::{Context, ManuallyAssertExt, Path, VecIndexExt};
+
+use crate::graphics::Color;
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::renderer::LayoutExt;
+use crate::state::ClientState;
+use crate::state::asset_browser::{AssetBrowserState, AssetBrowserStatePathExt};
+use crate::state::theme::InterfaceThemeType;
+
+/// Height of the area that the previewed sprite is rendered in.
+const PREVIEW_HEIGHT: f32 = 200.0;
+const MAXIMUM_FILTER_LENGTH: usize = 64;
+
+/// Lists a button for every sprite that matches the filter.
+struct AssetList<A> {
+    results_path: A,
+    elements: Vec<ElementBox<ClientState>>,
+}
+
+impl<A> AssetList<A> {
+    fn new(results_path: A) -> Self {
+        Self {
+            results_path,
+            elements: Vec::new(),
+        }
+    }
+}
+
+impl<A> Element<ClientState> for AssetList<A>
+where
+    A: Path<ClientState, Vec<String>>,
+{
+    type LayoutInfo = ();
+
+    fn create_layout_info(
+        &mut self,
+        state: &Context<ClientState>,
+        mut store: ElementStoreMut<'_>,
+        resolver: &mut Resolver<'_, ClientState>,
+    ) -> Self::LayoutInfo {
+        use korangar_interface::prelude::*;
+
+        let results = state.get(&self.results_path);
+
+        match results.len().cmp(&self.elements.len()) {
+            Ordering::Less => {
+                self.elements.truncate(results.len());
+            }
+            Ordering::Equal => {}
+            Ordering::Greater => {
+                for index in self.elements.len()..results.len() {
+                    let name_path = self.results_path.index(index).manually_asserted();
+
+                    self.elements.push(ErasedElement::new(button! {
+                        text: name_path,
+                        event: move |state: &Context<ClientState>, queue: &mut EventQueue<ClientState>| {
+                            let name = state.get(&name_path).clone();
+                            queue.queue(InputEvent::PreviewAsset { name });
+                        },
+                    }));
+                }
+            }
+        }
+
+        self.elements.iter_mut().enumerate().for_each(|(index, element)| {
+            element.create_layout_info(state, store.child_store(index as u64), resolver);
+        });
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a Context<ClientState>,
+        store: ElementStore<'a>,
+        _: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        self.elements.iter().enumerate().for_each(|(index, element)| {
+            element.lay_out(state, store.child_store(index as u64), &(), layout);
+        });
+    }
+}
+
+/// Renders the current frame of the previewed sprite.
+struct SpritePreviewElement<A> {
+    asset_browser_path: A,
+}
+
+impl<A> Element<ClientState> for SpritePreviewElement<A>
+where
+    A: Path<ClientState, AssetBrowserState>,
+{
+    type LayoutInfo = Area;
+
+    fn create_layout_info(
+        &mut self,
+        _: &Context<ClientState>,
+        _: ElementStoreMut<'_>,
+        resolver: &mut Resolver<'_, ClientState>,
+    ) -> Self::LayoutInfo {
+        resolver.with_height(PREVIEW_HEIGHT)
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a Context<ClientState>,
+        _: ElementStore<'a>,
+        layout_info: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        let Some(preview) = state.get(&self.asset_browser_path).preview() else {
+            return;
+        };
+
+        layout.add_sprite(
+            *layout_info,
+            &preview.actions,
+            &preview.sprite,
+            &preview.animation_state,
+            preview.direction(),
+            Color::WHITE,
+        );
+    }
+}
+
+pub struct AssetBrowserWindow<A> {
+    asset_browser_path: A,
+}
+
+impl<A> AssetBrowserWindow<A> {
+    pub fn new(asset_browser_path: A) -> Self {
+        Self { asset_browser_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for AssetBrowserWindow<A>
+where
+    A: Path<ClientState, AssetBrowserState>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::AssetBrowser)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        struct FilterTextBox;
+
+        let filter_action = move |state: &Context<ClientState>, _: &mut EventQueue<ClientState>| {
+            state.update_value_with(self.asset_browser_path, |asset_browser| asset_browser.update_results());
+        };
+
+        window! {
+            title: "Asset Browser",
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            minimum_width: 400.0,
+            elements: (
+                text_box! {
+                    ghost_text: "Filter sprites (press enter to apply)",
+                    state: self.asset_browser_path.filter(),
+                    input_handler: DefaultHandler::<_, _, MAXIMUM_FILTER_LENGTH>::new(self.asset_browser_path.filter(), filter_action),
+                    focus_id: FilterTextBox,
+                },
+                text! {
+                    text: self.asset_browser_path.status_text(),
+                },
+                collapsable! {
+                    text: "Sprites",
+                    children: (
+                        AssetList::new(self.asset_browser_path.results()),
+                    ),
+                },
+                text! {
+                    text: self.asset_browser_path.selected_text(),
+                },
+                SpritePreviewElement {
+                    asset_browser_path: self.asset_browser_path,
+                },
+                text! {
+                    text: self.asset_browser_path.frame_text(),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: "Previous action",
+                            event: InputEvent::StepAssetPreviewAction { steps: -1 },
+                        },
+                        button! {
+                            text: "Next action",
+                            event: InputEvent::StepAssetPreviewAction { steps: 1 },
+                        },
+                    ),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: "Previous frame",
+                            event: InputEvent::StepAssetPreviewFrame { steps: -1 },
+                        },
+                        button! {
+                            text: "Play / Pause",
+                            event: InputEvent::ToggleAssetPreviewPlayback,
+                        },
+                        button! {
+                            text: "Next frame",
+                            event: InputEvent::StepAssetPreviewFrame { steps: 1 },
+                        },
+                    ),
+                },
+                text! {
+                    text: self.asset_browser_path.action_metadata_text(),
+                },
+                text! {
+                    text: self.asset_browser_path.sprite_metadata_text(),
+                },
+            ),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use korangar_interface::window::{CustomWindow, Window};
+
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::state::ClientState;
+use crate::state::theme::InterfaceThemeType;
+
+pub struct AttendanceWindow {
+    claimed_days: u32,
+}
+
+impl AttendanceWindow {
+    pub fn new(claimed_days: u32) -> Self {
+        Self { claimed_days }
+    }
+}
+
+impl CustomWindow<ClientState> for AttendanceWindow {
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::Attendance)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: "Attendance",
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                text! {
+                    text: format!("Claim the reward for day ^000001{}^000000", self.claimed_days + 1),
+                },
+                button! {
+                    text: "Claim",
+                    event: InputEvent::ClaimAttendanceReward,
+                },
+            ),
+        }
+    }
+}
@@ -2,25 +2,31 @@ use korangar_interface::window::{CustomWindow, Window};
 use rust_state::Path;
 
 use crate::interface::windows::WindowClass;
-use crate::settings::{AudioSettings, AudioSettingsPathExt};
+use crate::loaders::OverflowBehavior;
+use crate::settings::{AudioSettings, AudioSettingsCapabilities, AudioSettingsCapabilitiesPathExt, AudioSettingsPathExt};
 use crate::state::localization::LocalizationPathExt;
 use crate::state::theme::InterfaceThemeType;
 use crate::state::{ClientState, ClientStatePathExt, client_state};
 
 #[derive(Default)]
-pub struct AudioSettingsWindow<A> {
+pub struct AudioSettingsWindow<A, B> {
     audio_settings_path: A,
+    capabilities_path: B,
 }
 
-impl<A> AudioSettingsWindow<A> {
-    pub fn new(audio_settings_path: A) -> Self {
-        Self { audio_settings_path }
+impl<A, B> AudioSettingsWindow<A, B> {
+    pub fn new(audio_settings_path: A, capabilities_path: B) -> Self {
+        Self {
+            audio_settings_path,
+            capabilities_path,
+        }
     }
 }
 
-impl<A> CustomWindow<ClientState> for AudioSettingsWindow<A>
+impl<A, B> CustomWindow<ClientState> for AudioSettingsWindow<A, B>
 where
     A: Path<ClientState, AudioSettings>,
+    B: Path<ClientState, AudioSettingsCapabilities>,
 {
     fn window_class() -> Option<WindowClass> {
         Some(WindowClass::AudioSettings)
@@ -40,6 +46,18 @@ where
                     state: self.audio_settings_path.mute_on_focus_loss(),
                     event: Toggle(self.audio_settings_path.mute_on_focus_loss()),
                 },
+                split! {
+                    children: (
+                        text! {
+                            text: client_state().localization().interface_volume_text(),
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        drop_down! {
+                            selected: self.audio_settings_path.interface_volume(),
+                            options: self.capabilities_path.volumes(),
+                        }
+                    )
+                },
             ),
         }
     }
@@ -13,10 +13,11 @@ use rust_state::{Context, ManuallyAssertExt, Path, Selector, VecIndexExt};
 
 use super::WindowClass;
 use crate::graphics::{Color, CornerDiameter, ShadowPadding};
+use crate::inventory::CartSummary;
 use crate::loaders::{FontSize, OverflowBehavior};
 use crate::renderer::LayoutExt;
-use crate::state::ClientState;
 use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, this_player};
 use crate::world::ResourceMetadata;
 
 struct PartialEqDisplayStr<T> {
@@ -140,21 +141,19 @@ where
             ShadowPadding::diagonal(2.0, 5.0),
         );
 
-        if let Some(texture) = &item.metadata.texture {
-            layout.add_texture(layout_info.texture_area, texture.clone(), Color::WHITE, false);
-
-            if matches!(item.quantity, ItemQuantity::Fixed(..)) {
-                layout.add_text(
-                    layout_info.texture_area,
-                    self.amount_string.get_str(),
-                    FontSize(16.0),
-                    Color::monochrome_u8(220),
-                    Color::rgb_u8(255, 160, 60),
-                    HorizontalAlignment::Right { offset: 3.0, border: 3.0 },
-                    VerticalAlignment::Bottom { offset: 0.0 },
-                    OverflowBehavior::Shrink,
-                );
-            }
+        layout.add_item_icon(layout_info.texture_area, item.metadata.texture.as_ref());
+
+        if matches!(item.quantity, ItemQuantity::Fixed(..)) {
+            layout.add_text(
+                layout_info.texture_area,
+                self.amount_string.get_str(),
+                FontSize(16.0),
+                Color::monochrome_u8(220),
+                Color::rgb_u8(255, 160, 60),
+                HorizontalAlignment::Right { offset: 3.0, border: 3.0 },
+                VerticalAlignment::Bottom { offset: 0.0 },
+                OverflowBehavior::Shrink,
+            );
         }
 
         layout.add_text(
@@ -242,10 +241,22 @@ where
                             // Unwrap is safe here because of the bounds.
                             let cart = cart_path.follow(state).unwrap();
 
-                            cart.iter()
+                            let out_of_stock = cart
+                                .iter()
                                 .find(|purchase| purchase.item_id == item.item_id)
                                 .map(|purchase| matches!(item.quantity, ItemQuantity::Fixed(quantity) if quantity - purchase.metadata.1 < amount))
-                                .unwrap_or_else(|| matches!(item.quantity, ItemQuantity::Fixed(quantity) if quantity < amount))
+                                .unwrap_or_else(|| matches!(item.quantity, ItemQuantity::Fixed(quantity) if quantity < amount));
+
+                            // Grey out purchases that the player can't afford or carry, so the
+                            // server never has to reject them.
+                            let unaffordable = this_player().follow(state).is_none_or(|player| {
+                                CartSummary::new(cart.iter().map(|purchase| (purchase, purchase.metadata.1)))
+                                    .with(item, amount)
+                                    .check_for_player(player)
+                                    .is_err()
+                            });
+
+                            out_of_stock || unaffordable
                         })
                     }
 
@@ -14,10 +14,11 @@ use rust_state::{Context, ManuallyAssertExt, Path, Selector, VecIndexExt};
 use super::WindowClass;
 use crate::InputEvent;
 use crate::graphics::{Color, CornerDiameter, ShadowPadding};
+use crate::inventory::CartSummary;
 use crate::loaders::{FontSize, OverflowBehavior};
 use crate::renderer::LayoutExt;
-use crate::state::ClientState;
 use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, this_player};
 use crate::world::ResourceMetadata;
 
 struct PartialEqDisplayStr<T> {
@@ -138,20 +139,18 @@ where
             ShadowPadding::diagonal(2.0, 5.0),
         );
 
-        if let Some(texture) = &item.metadata.0.texture {
-            layout.add_texture(layout_info.texture_area, texture.clone(), Color::WHITE, false);
-
-            layout.add_text(
-                layout_info.texture_area,
-                self.amount_string.get_str(),
-                FontSize(16.0),
-                Color::monochrome_u8(220),
-                Color::rgb_u8(255, 160, 60),
-                HorizontalAlignment::Right { offset: 3.0, border: 3.0 },
-                VerticalAlignment::Bottom { offset: 0.0 },
-                OverflowBehavior::Shrink,
-            );
-        }
+        layout.add_item_icon(layout_info.texture_area, item.metadata.0.texture.as_ref());
+
+        layout.add_text(
+            layout_info.texture_area,
+            self.amount_string.get_str(),
+            FontSize(16.0),
+            Color::monochrome_u8(220),
+            Color::rgb_u8(255, 160, 60),
+            HorizontalAlignment::Right { offset: 3.0, border: 3.0 },
+            VerticalAlignment::Bottom { offset: 0.0 },
+            OverflowBehavior::Shrink,
+        );
 
         layout.add_text(
             layout_info.text_area,
@@ -338,17 +337,52 @@ where
     fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
         use korangar_interface::prelude::*;
 
+        let cart_path = self.cart_path;
+        let summary = move |state: &ClientState| {
+            // SAFETY:
+            //
+            // Unwrap is safe here because of the bounds.
+            let cart = cart_path.follow(state).unwrap();
+
+            CartSummary::new(cart.iter().map(|item| (item, item.metadata.1)))
+        };
+
+        let summary_text = ComputedSelector::new_default(move |state: &ClientState| {
+            let summary = summary(state);
+
+            match this_player().follow(state) {
+                Some(player) => format!(
+                    "Total: {} z, weight: {} / {}",
+                    summary.price,
+                    summary.weight / 10,
+                    player.maximum_weight.saturating_sub(player.weight) / 10
+                ),
+                None => format!("Total: {} z, weight: {}", summary.price, summary.weight / 10),
+            }
+        });
+
+        let buy_disabled = ComputedSelector::new_default(move |state: &ClientState| {
+            this_player()
+                .follow(state)
+                .is_none_or(|player| summary(state).check_for_player(player).is_err())
+        });
+
         window! {
             title: "Cart",
             class: Self::window_class(),
             theme: InterfaceThemeType::InGame,
             resizable: true,
             elements: (
+                text! {
+                    text: summary_text,
+                    overflow_behavior: OverflowBehavior::Shrink,
+                },
                 split! {
                     gaps: theme().window().gaps(),
                     children: (
                         button! {
                             text: "Buy",
+                            disabled: buy_disabled,
                             event: move |state: &Context<ClientState>, queue: &mut EventQueue<ClientState>| {
                                 let cart = state.get(&self.cart_path);
                                 let items = cart
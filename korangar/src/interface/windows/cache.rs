@@ -9,16 +9,20 @@ use serde::{Deserialize, Serialize};
 use super::WindowClass;
 use crate::graphics::ScreenSize;
 use crate::state::ClientState;
+use crate::system::instance_settings_path;
 
 #[derive(Serialize, Deserialize)]
 pub struct WindowState {
     pub anchor: Anchor<ClientState>,
     pub size: ScreenSize,
+    /// Whether the window was open when the client was closed.
+    #[serde(default)]
+    pub open: bool,
 }
 
 impl WindowState {
     pub fn new(anchor: Anchor<ClientState>, size: ScreenSize) -> Self {
-        Self { anchor, size }
+        Self { anchor, size, open: false }
     }
 }
 
@@ -31,7 +35,8 @@ impl WindowCache {
     // Since `WindowClass` has some variants with debug features enabled, we use a
     // differen file to store the window cache. This avoids failing to load and
     // thereby wiping the previous window cache when switching between debug and
-    // non-debug builds.
+    // non-debug builds. Every client instance uses its own file, so running
+    // multiple clients doesn't mix up their layouts.
     #[cfg(not(feature = "debug"))]
     const FILE_NAME: &'static str = "client/window_cache.ron";
     #[cfg(feature = "debug")]
@@ -39,20 +44,32 @@ impl WindowCache {
 
     fn load() -> Option<Self> {
         #[cfg(feature = "debug")]
-        print_debug!("loading window cache from {}", Self::FILE_NAME.magenta());
+        print_debug!(
+            "loading window cache from {}",
+            instance_settings_path(Self::FILE_NAME).display().magenta()
+        );
 
-        std::fs::read_to_string(Self::FILE_NAME)
+        std::fs::read_to_string(instance_settings_path(Self::FILE_NAME))
             .ok()
             .and_then(|data| ron::from_str(&data).ok())
             .map(|entries| Self { entries })
     }
 
+    /// Classes of all windows that were open, either now or when the client
+    /// was closed.
+    pub fn open_window_classes(&self) -> impl Iterator<Item = WindowClass> + '_ {
+        self.entries.iter().filter(|(_, entry)| entry.open).map(|(class, _)| *class)
+    }
+
     fn save(&self) {
         #[cfg(feature = "debug")]
-        print_debug!("saving window cache to {}", Self::FILE_NAME.magenta());
+        print_debug!(
+            "saving window cache to {}",
+            instance_settings_path(Self::FILE_NAME).display().magenta()
+        );
 
         let data = ron::ser::to_string_pretty(&self.entries, PrettyConfig::new()).unwrap();
-        std::fs::write(Self::FILE_NAME, data).expect("unable to write file");
+        std::fs::write(instance_settings_path(Self::FILE_NAME), data).expect("unable to write file");
     }
 }
 
@@ -62,7 +79,7 @@ impl korangar_interface::application::WindowCache<ClientState> for WindowCache {
             #[cfg(feature = "debug")]
             print_debug!(
                 "failed to load window cache from {}. creating empty cache",
-                Self::FILE_NAME.magenta()
+                instance_settings_path(Self::FILE_NAME).display().magenta()
             );
 
             Default::default()
@@ -94,6 +111,12 @@ impl korangar_interface::application::WindowCache<ClientState> for WindowCache {
             entry.size = size;
         }
     }
+
+    fn update_open(&mut self, class: WindowClass, open: bool) {
+        if let Some(entry) = self.entries.get_mut(&class) {
+            entry.open = open;
+        }
+    }
 }
 
 impl Drop for WindowCache {
@@ -0,0 +1,97 @@
+use korangar_interface::element::StateElement;
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::Path;
+
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::state::ClientState;
+use crate::state::cache_statistics::{CacheStatistics, CacheStatisticsPathExt, ClearableCache};
+use crate::state::theme::InterfaceThemeType;
+
+pub struct CacheStatisticsWindow<A> {
+    cache_statistics_path: A,
+}
+
+impl<A> CacheStatisticsWindow<A> {
+    pub fn new(cache_statistics_path: A) -> Self {
+        Self { cache_statistics_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for CacheStatisticsWindow<A>
+where
+    A: Path<ClientState, CacheStatistics>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::CacheStatistics)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        let path = self.cache_statistics_path;
+
+        window! {
+            title: "Cache Statistics",
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            minimum_width: 350.0,
+            elements: (
+                fragment! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        StateElement::to_element(path.texture_cache(), "Texture cache".to_owned()),
+                        button! {
+                            text: "Clear",
+                            event: InputEvent::ClearCache { cache: ClearableCache::Texture },
+                        },
+                    ),
+                },
+                fragment! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        StateElement::to_element(path.sprite_cache(), "Sprite cache".to_owned()),
+                        button! {
+                            text: "Clear",
+                            event: InputEvent::ClearCache { cache: ClearableCache::Sprite },
+                        },
+                    ),
+                },
+                fragment! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        StateElement::to_element(path.action_cache(), "Action cache".to_owned()),
+                        button! {
+                            text: "Clear",
+                            event: InputEvent::ClearCache { cache: ClearableCache::Action },
+                        },
+                    ),
+                },
+                fragment! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        StateElement::to_element(path.animation_cache(), "Animation cache".to_owned()),
+                        button! {
+                            text: "Clear",
+                            event: InputEvent::ClearCache { cache: ClearableCache::Animation },
+                        },
+                    ),
+                },
+                fragment! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        StateElement::to_element(path.effect_cache(), "Effect cache".to_owned()),
+                        button! {
+                            text: "Clear",
+                            event: InputEvent::ClearCache { cache: ClearableCache::Effect },
+                        },
+                    ),
+                },
+                StateElement::to_element(path.font_cache(), "Font cache".to_owned()),
+                StateElement::to_element(path.shaping_cache(), "Shaping cache".to_owned()),
+                StateElement::to_element(path.sound_cache(), "Sound cache".to_owned()),
+            ),
+        }
+    }
+}
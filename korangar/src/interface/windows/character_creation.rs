@@ -1,5 +1,7 @@
 use korangar_interface::components::text_box::DefaultHandler;
 use korangar_interface::window::{CustomWindow, Window};
+use korangar_networking::{CharacterCreationOptions, StartingJob};
+use ragnarok_packets::Sex;
 use rust_state::{Context, Path};
 
 use crate::input::InputEvent;
@@ -11,21 +13,31 @@ use crate::state::{ClientState, ClientStatePathExt, client_state};
 
 const MINIMUM_NAME_LENGTH: usize = 4;
 const MAXIMUM_NAME_LENGTH: usize = 24;
+/// Highest hair style and color that the default server configuration
+/// accepts. Selecting past them wraps around.
+const MAXIMUM_HAIR_STYLE: u16 = 27;
+const MAXIMUM_HAIR_COLOR: u16 = 8;
 
-pub struct CharacterCreationWindow<A> {
+pub struct CharacterCreationWindow<A, B> {
     character_name_path: A,
+    options_path: B,
     slot: usize,
 }
 
-impl<A> CharacterCreationWindow<A> {
-    pub fn new(character_name_path: A, slot: usize) -> Self {
-        Self { character_name_path, slot }
+impl<A, B> CharacterCreationWindow<A, B> {
+    pub fn new(character_name_path: A, options_path: B, slot: usize) -> Self {
+        Self {
+            character_name_path,
+            options_path,
+            slot,
+        }
     }
 }
 
-impl<A> CustomWindow<ClientState> for CharacterCreationWindow<A>
+impl<A, B> CustomWindow<ClientState> for CharacterCreationWindow<A, B>
 where
     A: Path<ClientState, String>,
+    B: Path<ClientState, CharacterCreationOptions>,
 {
     fn window_class() -> Option<WindowClass> {
         Some(WindowClass::CharacterCreation)
@@ -40,11 +52,51 @@ where
             self.character_name_path.follow(state).unwrap().len() < MINIMUM_NAME_LENGTH
         });
 
+        let options_path = self.options_path;
+
         let create_action = move |state: &Context<ClientState>, queue: &mut EventQueue<ClientState>| {
             let name = state.get(&self.character_name_path).clone();
-            queue.queue(InputEvent::CreateCharacter { slot: self.slot, name });
+            let options = *state.get(&options_path);
+            queue.queue(InputEvent::CreateCharacter {
+                slot: self.slot,
+                name,
+                options,
+            });
+        };
+
+        let update_options = move |update: fn(&mut CharacterCreationOptions)| {
+            move |state: &Context<ClientState>, _: &mut EventQueue<ClientState>| {
+                let mut options = *state.get(&options_path);
+                update(&mut options);
+                state.update_value(options_path, options);
+            }
         };
 
+        let hair_style_text = ComputedSelector::new_default(move |state: &ClientState| {
+            format!(
+                "{} {}",
+                client_state().localization().hair_style_text().follow(state).unwrap(),
+                options_path.follow(state).unwrap().hair_style
+            )
+        });
+
+        let hair_color_text = ComputedSelector::new_default(move |state: &ClientState| {
+            format!(
+                "{} {}",
+                client_state().localization().hair_color_text().follow(state).unwrap(),
+                options_path.follow(state).unwrap().hair_color
+            )
+        });
+
+        let is_male = ComputedSelector::new_default(move |state: &ClientState| options_path.follow(state).unwrap().sex == Sex::Male);
+        let is_female = ComputedSelector::new_default(move |state: &ClientState| options_path.follow(state).unwrap().sex == Sex::Female);
+        let is_novice = ComputedSelector::new_default(move |state: &ClientState| {
+            options_path.follow(state).unwrap().starting_job == StartingJob::Novice
+        });
+        let is_summoner = ComputedSelector::new_default(move |state: &ClientState| {
+            options_path.follow(state).unwrap().starting_job == StartingJob::Summoner
+        });
+
         window! {
             title: client_state().localization().create_character_window_title(),
             class: Self::window_class(),
@@ -58,6 +110,70 @@ where
                     focus_id: CharacterName,
                     overflow_behavior: OverflowBehavior::Shrink,
                 },
+                split! {
+                    children: (
+                        button! {
+                            text: client_state().localization().male_button_text(),
+                            disabled: is_male,
+                            event: update_options(|options| options.sex = Sex::Male),
+                        },
+                        button! {
+                            text: client_state().localization().female_button_text(),
+                            disabled: is_female,
+                            event: update_options(|options| options.sex = Sex::Female),
+                        },
+                    ),
+                },
+                split! {
+                    children: (
+                        button! {
+                            text: client_state().localization().novice_button_text(),
+                            disabled: is_novice,
+                            event: update_options(|options| options.starting_job = StartingJob::Novice),
+                        },
+                        button! {
+                            text: client_state().localization().summoner_button_text(),
+                            disabled: is_summoner,
+                            event: update_options(|options| options.starting_job = StartingJob::Summoner),
+                        },
+                    ),
+                },
+                split! {
+                    children: (
+                        text! {
+                            text: hair_style_text,
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        button! {
+                            text: "<",
+                            event: update_options(|options| {
+                                options.hair_style = options.hair_style.checked_sub(1).unwrap_or(MAXIMUM_HAIR_STYLE);
+                            }),
+                        },
+                        button! {
+                            text: ">",
+                            event: update_options(|options| options.hair_style = (options.hair_style + 1) % (MAXIMUM_HAIR_STYLE + 1)),
+                        },
+                    ),
+                },
+                split! {
+                    children: (
+                        text! {
+                            text: hair_color_text,
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        button! {
+                            text: "<",
+                            event: update_options(|options| {
+                                options.hair_color = options.hair_color.checked_sub(1).unwrap_or(MAXIMUM_HAIR_COLOR);
+                            }),
+                        },
+                        button! {
+                            text: ">",
+                            event: update_options(|options| options.hair_color = (options.hair_color + 1) % (MAXIMUM_HAIR_COLOR + 1)),
+                        },
+                    ),
+                },
                 button! {
                     text: client_state().localization().create_character_button_text(),
                     disabled,
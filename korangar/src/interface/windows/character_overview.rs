@@ -112,6 +112,26 @@ where
                     text: client_state().localization().friend_list_button_text(),
                     event: InputEvent::ToggleFriendListWindow,
                 },
+                button! {
+                    text: client_state().localization().session_statistics_button_text(),
+                    event: InputEvent::ToggleSessionStatisticsWindow,
+                },
+                button! {
+                    text: client_state().localization().monster_info_button_text(),
+                    event: InputEvent::ToggleMonsterInfoWindow,
+                },
+                button! {
+                    text: client_state().localization().item_search_button_text(),
+                    event: InputEvent::ToggleItemSearchWindow,
+                },
+                button! {
+                    text: client_state().localization().skill_planner_button_text(),
+                    event: InputEvent::ToggleSkillPlannerWindow,
+                },
+                button! {
+                    text: client_state().localization().minimap_button_text(),
+                    event: InputEvent::ToggleMinimapWindow,
+                },
                 button! {
                     text: client_state().localization().menu_button_text(),
                     event: InputEvent::ToggleMenuWindow,
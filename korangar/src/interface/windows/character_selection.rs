@@ -15,11 +15,13 @@ use crate::state::{ClientState, ClientStatePathExt, client_state};
 mod character_slot_preview {
     use std::cell::{Cell, UnsafeCell};
     use std::fmt::Display;
+    use std::sync::Arc;
 
     use korangar_interface::element::store::{ElementStore, ElementStoreMut};
     use korangar_interface::element::{BaseLayoutInfo, Element};
     use korangar_interface::event::{ClickHandler, EventQueue};
     use korangar_interface::layout::alignment::{HorizontalAlignment, VerticalAlignment};
+    use korangar_interface::layout::area::Area;
     use korangar_interface::layout::tooltip::TooltipExt;
     use korangar_interface::layout::{MouseButton, Resolver, WindowLayout};
     use ragnarok_packets::{CharacterInformation, CharacterInformationPathExt};
@@ -28,7 +30,9 @@ mod character_slot_preview {
     use crate::graphics::{Color, CornerDiameter, ScreenPosition, ScreenSize, ShadowPadding};
     use crate::input::InputEvent;
     use crate::loaders::{FontSize, OverflowBehavior};
+    use crate::renderer::LayoutExt;
     use crate::state::ClientState;
+    use crate::world::AnimationData;
 
     pub struct OverlayHandler<A, B> {
         position: ScreenPosition,
@@ -116,17 +120,19 @@ mod character_slot_preview {
     //     pub background_color: ClientState,
     // }
 
-    pub struct CharacterSlotPreview<P, M, B> {
+    pub struct CharacterSlotPreview<P, Q, M, B> {
         character_information: P,
+        preview: Q,
         switch_request: M,
         click_handler: CharacterSlotPreviewHandler<B>,
         overlay_handler: OverlayHandler<M, P>,
         slot: usize,
     }
 
-    impl<P, M, B> CharacterSlotPreview<P, M, B> {
+    impl<P, Q, M, B> CharacterSlotPreview<P, Q, M, B> {
         pub fn new(
             character_information: P,
+            preview: Q,
             switch_request: M,
             click_handler: CharacterSlotPreviewHandler<B>,
             overlay_handler: OverlayHandler<M, P>,
@@ -134,6 +140,7 @@ mod character_slot_preview {
         ) -> Self {
             Self {
                 character_information,
+                preview,
                 switch_request,
                 click_handler,
                 overlay_handler,
@@ -142,9 +149,10 @@ mod character_slot_preview {
         }
     }
 
-    impl<P, M, B> Element<ClientState> for CharacterSlotPreview<P, M, B>
+    impl<P, Q, M, B> Element<ClientState> for CharacterSlotPreview<P, Q, M, B>
     where
         P: Path<ClientState, CharacterInformation, false>,
+        Q: Path<ClientState, Arc<AnimationData>, false>,
         M: Path<ClientState, Option<usize>>,
         B: Path<ClientState, Option<usize>>,
     {
@@ -330,6 +338,19 @@ mod character_slot_preview {
                     OverflowBehavior::Shrink,
                 );
 
+                if let Some(preview) = state.try_get(&self.preview) {
+                    layout.add_animation(
+                        Area {
+                            left: layout_info.area.left + layout_info.area.width / 2.0,
+                            top: layout_info.area.top + 30.0,
+                            width: layout_info.area.width / 2.0,
+                            height: layout_info.area.height - 40.0,
+                        },
+                        preview,
+                        Color::WHITE,
+                    );
+                }
+
                 if is_hoverered {
                     layout.register_click_handler(MouseButton::Left, &self.click_handler.select_character);
                     layout.register_click_handler(MouseButton::Right, &self.overlay_handler);
@@ -552,6 +573,7 @@ where
                             children: (
                                 CharacterSlotPreview::new(
                                     path.in_slot(slot),
+                                    path.preview_in_slot(slot),
                                     self.switch_request,
                                     CharacterSlotPreviewHandler::new(self.switch_request, slot),
                                     OverlayHandler::new(slot, self.switch_request, path.in_slot(slot)),
@@ -559,6 +581,7 @@ where
                                 ),
                                 CharacterSlotPreview::new(
                                     path.in_slot(slot + 1),
+                                    path.preview_in_slot(slot + 1),
                                     self.switch_request,
                                     CharacterSlotPreviewHandler::new(self.switch_request, slot + 1),
                                     OverlayHandler::new(slot + 1, self.switch_request, path.in_slot(slot + 1)),
@@ -566,6 +589,7 @@ where
                                 ),
                                 CharacterSlotPreview::new(
                                     path.in_slot(slot + 2),
+                                    path.preview_in_slot(slot + 2),
                                     self.switch_request,
                                     CharacterSlotPreviewHandler::new(self.switch_request, slot + 2),
                                     OverlayHandler::new(slot + 2, self.switch_request, path.in_slot(slot + 2)),
@@ -573,6 +597,7 @@ where
                                 ),
                                 CharacterSlotPreview::new(
                                     path.in_slot(slot + 3),
+                                    path.preview_in_slot(slot + 3),
                                     self.switch_request,
                                     CharacterSlotPreviewHandler::new(self.switch_request, slot + 3),
                                     OverlayHandler::new(slot + 3, self.switch_request, path.in_slot(slot + 3)),
@@ -580,6 +605,7 @@ where
                                 ),
                                 CharacterSlotPreview::new(
                                     path.in_slot(slot + 4),
+                                    path.preview_in_slot(slot + 4),
                                     self.switch_request,
                                     CharacterSlotPreviewHandler::new(self.switch_request, slot + 4),
                                     OverlayHandler::new(slot + 4, self.switch_request, path.in_slot(slot + 4)),
@@ -2,8 +2,9 @@ use korangar_interface::application::Size;
 use korangar_interface::components::text_box::DefaultHandler;
 use korangar_interface::element::store::{ElementStore, ElementStoreMut};
 use korangar_interface::element::{Element, StateElement};
+use korangar_interface::event::{ClickHandler, EventQueue};
 use korangar_interface::layout::area::Area;
-use korangar_interface::layout::{Resolver, WindowLayout};
+use korangar_interface::layout::{MouseButton, Resolver, WindowLayout};
 use korangar_interface::prelude::{HorizontalAlignment, VerticalAlignment};
 use korangar_interface::window::{CustomWindow, Window};
 use korangar_networking::MessageColor;
@@ -23,18 +24,27 @@ const MAXIMUM_CHAT_MESSAGE_LENGTH: usize = 80;
 /// focus the chat when pressing enter.
 pub struct ChatTextBox;
 
-struct ChatLayoutInfo {
+/// Clicking a message with item links shows the first linked item.
+impl ClickHandler<ClientState> for ChatMessage {
+    fn handle_click(&self, _: &Context<ClientState>, queue: &mut EventQueue<ClientState>) {
+        if let Some(&item_id) = self.item_links.first() {
+            queue.queue(InputEvent::ShowItemInfo { item_id });
+        }
+    }
+}
+
+pub(super) struct ChatLayoutInfo {
     area: Area,
     // TODO: Don't allocate this every frame.
     message_heights: Vec<f32>,
 }
 
-struct ChatElement<A> {
+pub(super) struct ChatElement<A> {
     chat_messages_path: A,
 }
 
 impl<A> ChatElement<A> {
-    fn new(chat_messages_path: A) -> Self {
+    pub(super) fn new(chat_messages_path: A) -> Self {
         Self { chat_messages_path }
     }
 }
@@ -135,6 +145,10 @@ where
                     height: *message_height,
                 };
 
+                if !chat_message.item_links.is_empty() && text_area.check().run(layout) {
+                    layout.register_click_handler(MouseButton::Left, chat_message);
+                }
+
                 layout.add_text(
                     text_area,
                     &chat_message.text,
@@ -158,6 +172,13 @@ pub struct ChatWindowState {
     current_text: String,
 }
 
+impl ChatWindowState {
+    /// Append text to the message that is currently being written.
+    pub fn append_text(&mut self, text: &str) {
+        self.current_text.push_str(text);
+    }
+}
+
 pub struct ChatWindow<A, B> {
     chat_window_state: A,
     chat_messages_path: B,
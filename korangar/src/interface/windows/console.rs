@@ -0,0 +1,214 @@
+use korangar_interface::element::StateElement;
+use korangar_interface::event::{Event, EventQueue, InputHandler};
+use korangar_interface::window::{CustomWindow, Window};
+use korangar_networking::MessageColor;
+use rust_state::{Context, Path, RustState};
+
+use super::WindowClass;
+use super::chat::ChatElement;
+use crate::graphics::Color;
+use crate::input::InputEvent;
+use crate::state::theme::{ChatThemePathExt, InterfaceThemeType};
+use crate::state::{ChatMessage, ClientState, client_theme};
+use crate::system::{ConsoleCommandRegistry, ConsoleOutput};
+
+const MAXIMUM_COMMAND_LENGTH: usize = 256;
+/// Number of lines kept in the output of the console.
+const MAXIMUM_OUTPUT_LINES: usize = 512;
+/// Number of commands kept in the history of the console.
+const MAXIMUM_HISTORY_LENGTH: usize = 64;
+
+/// ZST for getting the focus id of the console text box. This is needed to
+/// focus the console when it is opened.
+pub struct ConsoleTextBox;
+
+/// Internal state of the console window.
+#[derive(Default, RustState, StateElement)]
+pub struct ConsoleWindowState {
+    current_text: String,
+    output: Vec<ChatMessage>,
+    #[hidden_element]
+    history: Vec<String>,
+    /// Index into the history while the user is browsing it.
+    #[hidden_element]
+    history_index: Option<usize>,
+}
+
+impl ConsoleWindowState {
+    fn push_line(&mut self, text: &str, color: MessageColor) {
+        self.output
+            .extend(text.lines().map(|line| ChatMessage::new(line.to_owned(), color)));
+
+        if self.output.len() > MAXIMUM_OUTPUT_LINES {
+            let overflow = self.output.len() - MAXIMUM_OUTPUT_LINES;
+            self.output.drain(..overflow);
+        }
+    }
+
+    /// Take the current input and add it to the history.
+    pub fn take_command(&mut self) -> Option<String> {
+        let command = std::mem::take(&mut self.current_text).trim().to_owned();
+        self.history_index = None;
+
+        if command.is_empty() {
+            return None;
+        }
+
+        if self.history.last() != Some(&command) {
+            self.history.push(command.clone());
+
+            if self.history.len() > MAXIMUM_HISTORY_LENGTH {
+                self.history.remove(0);
+            }
+        }
+
+        self.push_line(&format!("> {command}"), MessageColor::Rgb {
+            red: 150,
+            green: 150,
+            blue: 150,
+        });
+
+        Some(command)
+    }
+
+    pub fn push_output(&mut self, output: ConsoleOutput) {
+        match output {
+            ConsoleOutput::Text(text) => self.push_line(&text, MessageColor::Rgb {
+                red: 255,
+                green: 255,
+                blue: 255,
+            }),
+            ConsoleOutput::Error(error) => self.push_line(&error, MessageColor::Rgb {
+                red: 255,
+                green: 100,
+                blue: 100,
+            }),
+            ConsoleOutput::Clear => self.output.clear(),
+        }
+    }
+
+    pub fn autocomplete<S>(&mut self, registry: &ConsoleCommandRegistry<S>) {
+        let completion = registry.complete(&self.current_text);
+
+        if completion.candidates.len() > 1 {
+            self.push_output(ConsoleOutput::Text(completion.candidates.join("  ")));
+        }
+
+        self.current_text = completion.text;
+    }
+
+    pub fn history_previous(&mut self) {
+        let index = match self.history_index {
+            Some(index) => index.saturating_sub(1),
+            None if !self.history.is_empty() => self.history.len() - 1,
+            None => return,
+        };
+
+        self.history_index = Some(index);
+        self.current_text = self.history[index].clone();
+    }
+
+    pub fn history_next(&mut self) {
+        let Some(index) = self.history_index else {
+            return;
+        };
+
+        match index + 1 < self.history.len() {
+            true => {
+                self.history_index = Some(index + 1);
+                self.current_text = self.history[index + 1].clone();
+            }
+            false => {
+                self.history_index = None;
+                self.current_text.clear();
+            }
+        }
+    }
+}
+
+/// Input handler of the console. Enter executes the command and tab
+/// autocompletes it.
+struct ConsoleInputHandler<P> {
+    current_text_path: P,
+}
+
+impl<P> InputHandler<ClientState> for ConsoleInputHandler<P>
+where
+    P: Path<ClientState, String>,
+{
+    fn handle_character(&self, state: &Context<ClientState>, queue: &mut EventQueue<ClientState>, character: char) {
+        match character {
+            // Enter
+            '\x0d' => queue.queue(InputEvent::ExecuteConsoleCommand),
+            // Tab
+            '\x09' => queue.queue(InputEvent::AutocompleteConsoleCommand),
+            // Escape
+            '\x1b' => queue.queue(Event::Unfocus),
+            // Backspace
+            '\x08' => state.update_value_with(self.current_text_path, |current_text| {
+                current_text.pop();
+            }),
+            // The key used to toggle the console.
+            '`' | '^' => {}
+            character if !character.is_control() => state.update_value_with(self.current_text_path, move |current_text| {
+                if current_text.len() < MAXIMUM_COMMAND_LENGTH {
+                    current_text.push(character);
+                }
+            }),
+            _ => {}
+        }
+    }
+}
+
+pub struct ConsoleWindow<A> {
+    console_window_state: A,
+}
+
+impl<A> ConsoleWindow<A> {
+    pub fn new(console_window_state: A) -> Self {
+        Self { console_window_state }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for ConsoleWindow<A>
+where
+    A: Path<ClientState, ConsoleWindowState>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::Console)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        let current_text_path = self.console_window_state.current_text();
+
+        window! {
+            title: "Console",
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            background_color: client_theme().chat().window_color(),
+            closable: true,
+            resizable: true,
+            minimum_width: 400.0,
+            minimum_height: 200.0,
+            maximum_height: 800.0,
+            elements: (
+                scroll_view! {
+                    follow: true,
+                    children: (
+                        ChatElement::new(self.console_window_state.output()),
+                    ),
+                },
+                text_box! {
+                    ghost_text: "Type \"help\" for a list of commands",
+                    state: current_text_path,
+                    input_handler: ConsoleInputHandler { current_text_path },
+                    background_color: client_theme().chat().text_box_background_color(),
+                    focused_background_color: Color::rgba(0.0, 0.0, 0.0, 0.8),
+                    focus_id: ConsoleTextBox,
+                },
+            ),
+        }
+    }
+}
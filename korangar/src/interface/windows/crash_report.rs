@@ -0,0 +1,45 @@
+use korangar_interface::window::{CustomWindow, Window};
+
+use crate::input::InputEvent;
+use crate::loaders::OverflowBehavior;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+/// Window that is shown on startup if the client crashed the last time it was
+/// running.
+pub struct CrashReportWindow {
+    bundle_path: String,
+}
+
+impl CrashReportWindow {
+    pub fn new(bundle_path: String) -> Self {
+        Self { bundle_path }
+    }
+}
+
+impl CustomWindow<ClientState> for CrashReportWindow {
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: client_state().localization().crash_report_window_title(),
+            theme: InterfaceThemeType::Menu,
+            closable: true,
+            elements: (
+                text! {
+                    text: client_state().localization().crash_report_text(),
+                    overflow_behavior: OverflowBehavior::LineBreak,
+                },
+                text! {
+                    text: self.bundle_path,
+                    overflow_behavior: OverflowBehavior::Shrink,
+                },
+                button! {
+                    text: client_state().localization().open_crash_report_directory_button_text(),
+                    event: InputEvent::OpenCrashReportDirectory,
+                },
+            ),
+        }
+    }
+}
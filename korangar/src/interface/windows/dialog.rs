@@ -1,15 +1,119 @@
-use std::cell::UnsafeCell;
+use std::cell::{Cell, UnsafeCell};
+use std::sync::Arc;
 
+use korangar_interface::element::store::{ElementStore, ElementStoreMut};
 use korangar_interface::element::{Element, ElementBox, ErasedElement, StateElement};
+use korangar_interface::layout::area::Area;
+use korangar_interface::layout::{Resolver, WindowLayout};
 use korangar_interface::window::{CustomWindow, Window};
-use ragnarok_packets::EntityId;
-use rust_state::{Context, Path, RustState};
+use korangar_networking::MessageColor;
+use ragnarok_packets::{EntityId, ImageLocation};
+use rust_state::{Context, Path, RustState, Selector};
 
 use super::WindowClass;
+use super::chat::ChatElement;
+use crate::graphics::{Color, Texture};
 use crate::input::InputEvent;
+use crate::renderer::LayoutExt;
+use crate::settings::DialogTextSpeed;
 use crate::state::localization::LocalizationPathExt;
 use crate::state::theme::InterfaceThemeType;
-use crate::state::{ClientState, ClientStatePathExt, client_state};
+use crate::state::{ChatMessage, ClientState, ClientStatePathExt, client_state};
+
+/// Number of messages kept in the dialog history.
+const MAXIMUM_HISTORY_LENGTH: usize = 256;
+/// Maximum height of cutin images inside the dialog window.
+const MAXIMUM_CUTIN_HEIGHT: f32 = 200.0;
+
+/// Length of a color code inside of NPC text, e.g. `^FF0000`.
+const COLOR_CODE_LENGTH: usize = 7;
+
+fn color_code_length(text: &str) -> Option<usize> {
+    let code = text.strip_prefix('^')?.get(..COLOR_CODE_LENGTH - 1)?;
+    code.chars()
+        .all(|character| character.is_ascii_hexdigit())
+        .then_some(COLOR_CODE_LENGTH)
+}
+
+/// Get the number of characters that are revealed one by one. Color codes are
+/// not counted since they are never displayed.
+fn visible_character_count(text: &str) -> usize {
+    let mut count = 0;
+    let mut rest = text;
+
+    while let Some(character) = rest.chars().next() {
+        match color_code_length(rest) {
+            Some(length) => rest = &rest[length..],
+            None => {
+                count += 1;
+                rest = &rest[character.len_utf8()..];
+            }
+        }
+    }
+
+    count
+}
+
+/// Get the part of the text that is visible after revealing `count`
+/// characters. Color codes are always kept intact.
+fn reveal_text(text: &str, count: usize) -> &str {
+    let mut remaining = count;
+    let mut rest = text;
+
+    while let Some(character) = rest.chars().next() {
+        match color_code_length(rest) {
+            Some(length) => rest = &rest[length..],
+            None if remaining == 0 => break,
+            None => {
+                remaining -= 1;
+                rest = &rest[character.len_utf8()..];
+            }
+        }
+    }
+
+    &text[..text.len() - rest.len()]
+}
+
+/// Selects the part of a dialog text that has already been revealed.
+struct RevealedTextSelector<A> {
+    text: String,
+    /// Number of characters revealed in the dialog before this text was added.
+    start: usize,
+    revealed_characters_path: A,
+    last_count: Cell<Option<usize>>,
+    revealed_text: UnsafeCell<String>,
+}
+
+impl<A> RevealedTextSelector<A> {
+    fn new(text: String, start: usize, revealed_characters_path: A) -> Self {
+        Self {
+            text,
+            start,
+            revealed_characters_path,
+            last_count: Cell::default(),
+            revealed_text: UnsafeCell::default(),
+        }
+    }
+}
+
+impl<A> Selector<ClientState, String> for RevealedTextSelector<A>
+where
+    A: Path<ClientState, usize>,
+{
+    fn select<'a>(&'a self, state: &'a ClientState) -> Option<&'a String> {
+        // SAFETY
+        // `unnwrap` is safe here because the bound of `A` specifies a safe path.
+        let revealed_characters = self.revealed_characters_path.follow(state).unwrap();
+        let count = revealed_characters.saturating_sub(self.start);
+
+        if self.last_count.get() != Some(count) {
+            unsafe { *self.revealed_text.get() = reveal_text(&self.text, count).to_owned() };
+            self.last_count.set(Some(count));
+        }
+
+        unsafe { Some(self.revealed_text.as_ref_unchecked()) }
+    }
+}
 
 /// A small wrapper struct that serves two purposes:
 /// - Making the elements nicer to construct by putting the [`UnsafeCell::new`]
@@ -50,6 +154,20 @@ pub struct DialogWindowState {
     /// Whether or not the elements should be cleared the next time
     /// [`start`](Self::start) is called.
     clear_next: bool,
+    /// Illustration of the NPC sent by the server.
+    #[hidden_element]
+    cutin: Option<(Arc<Texture>, ImageLocation)>,
+    /// All texts of the dialogs since the client was started.
+    history: Vec<ChatMessage>,
+    /// Number of characters of the current page that are visible.
+    #[hidden_element]
+    revealed_characters: usize,
+    /// Number of characters of the current page.
+    #[hidden_element]
+    total_characters: usize,
+    /// Fraction of the next character that will be revealed.
+    #[hidden_element]
+    reveal_progress: f64,
 }
 
 impl DialogWindowState {
@@ -67,16 +185,71 @@ impl DialogWindowState {
         if self.clear_next {
             self.elements.clear();
             self.clear_next = false;
+            self.revealed_characters = 0;
+            self.total_characters = 0;
+            self.reveal_progress = 0.0;
         }
 
+        self.history.push(ChatMessage::new(text.clone(), MessageColor::Rgb {
+            red: 255,
+            green: 255,
+            blue: 255,
+        }));
+
+        if self.history.len() > MAXIMUM_HISTORY_LENGTH {
+            self.history.remove(0);
+        }
+
+        let start = self.total_characters;
+        self.total_characters += visible_character_count(&text);
+
         self.elements.push(DialogElement::new(
             text! {
-                text: text,
+                text: RevealedTextSelector::new(text, start, client_state().dialog_window().revealed_characters()),
             },
             false,
         ));
     }
 
+    /// Reveal more characters of the current page based on the text speed.
+    pub fn update_text_reveal(&mut self, delta_time: f64, text_speed: DialogTextSpeed) {
+        if self.revealed_characters >= self.total_characters {
+            return;
+        }
+
+        let Some(characters_per_second) = text_speed.characters_per_second() else {
+            self.skip_text_reveal();
+            return;
+        };
+
+        self.reveal_progress += delta_time * characters_per_second;
+
+        let new_characters = self.reveal_progress as usize;
+        self.reveal_progress -= new_characters as f64;
+        self.revealed_characters = (self.revealed_characters + new_characters).min(self.total_characters);
+    }
+
+    /// Reveal the entire page at once. Returns `false` if the page was already
+    /// fully revealed.
+    pub fn skip_text_reveal(&mut self) -> bool {
+        let was_revealing = self.revealed_characters < self.total_characters;
+
+        self.revealed_characters = self.total_characters;
+        self.reveal_progress = 0.0;
+
+        was_revealing
+    }
+
+    /// Display an illustration of the NPC.
+    pub fn set_cutin(&mut self, texture: Arc<Texture>, location: ImageLocation) {
+        self.cutin = Some((texture, location));
+    }
+
+    /// Remove the illustration of the NPC.
+    pub fn clear_cutin(&mut self) {
+        self.cutin = None;
+    }
+
     /// Add add next button to the dialog.
     ///
     /// This also sets the internal state to clear the dialog the next time
@@ -153,6 +326,10 @@ impl DialogWindowState {
     pub fn end(&mut self) {
         self.elements.clear();
         self.clear_next = false;
+        self.cutin = None;
+        self.revealed_characters = 0;
+        self.total_characters = 0;
+        self.reveal_progress = 0.0;
     }
 }
 
@@ -163,10 +340,70 @@ impl Default for DialogWindowState {
             // Arguably not very clean but avoids using an Option.
             npc_id: EntityId(0),
             clear_next: false,
+            cutin: None,
+            history: Vec::new(),
+            revealed_characters: 0,
+            total_characters: 0,
+            reveal_progress: 0.0,
         }
     }
 }
 
+/// Displays the illustration of the NPC above the dialog text.
+struct CutinElement<A> {
+    cutin_path: A,
+}
+
+impl<A> Element<ClientState> for CutinElement<A>
+where
+    A: Path<ClientState, Option<(Arc<Texture>, ImageLocation)>>,
+{
+    type LayoutInfo = Option<Area>;
+
+    fn create_layout_info(
+        &mut self,
+        state: &Context<ClientState>,
+        _: ElementStoreMut<'_>,
+        resolver: &mut Resolver<'_, ClientState>,
+    ) -> Self::LayoutInfo {
+        let (texture, location) = state.get(&self.cutin_path).as_ref()?;
+
+        let texture_size = texture.get_size();
+        let area = resolver.with_height(MAXIMUM_CUTIN_HEIGHT.min(texture_size.height as f32));
+
+        // Keep the aspect ratio of the image.
+        let width = (area.height * texture_size.width as f32 / texture_size.height as f32).min(area.width);
+        let height = width * texture_size.height as f32 / texture_size.width as f32;
+
+        let left = match location {
+            ImageLocation::BottomLeft => area.left,
+            ImageLocation::BottomRight => area.left + area.width - width,
+            _ => area.left + (area.width - width) / 2.0,
+        };
+
+        Some(Area {
+            left,
+            top: area.top + area.height - height,
+            width,
+            height,
+        })
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a Context<ClientState>,
+        _: ElementStore<'a>,
+        layout_info: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        let (Some(area), Some((texture, _))) = (layout_info, state.get(&self.cutin_path)) else {
+            return;
+        };
+
+        layout.add_texture(*area, texture.clone(), Color::WHITE, true);
+    }
+}
+
 /// Wrapper struct for collecting all [`DialogElement::element`]s into a single
 /// element.
 struct InnerElement<A> {
@@ -254,10 +491,80 @@ where
             class: Self::window_class(),
             theme: InterfaceThemeType::InGame,
             elements: (
+                CutinElement {
+                    cutin_path: self.window_state_path.cutin(),
+                },
                 InnerElement {
                     dialog_elements_path: self.window_state_path.elements(),
                 },
+                button! {
+                    text: client_state().localization().dialog_history_button_text(),
+                    event: InputEvent::ToggleDialogHistoryWindow,
+                },
+            ),
+        }
+    }
+}
+
+/// A window showing the text of all previous dialogs.
+pub struct DialogHistoryWindow<A> {
+    history_path: A,
+}
+
+impl<A> DialogHistoryWindow<A> {
+    pub fn new(history_path: A) -> Self {
+        Self { history_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for DialogHistoryWindow<A>
+where
+    A: Path<ClientState, Vec<ChatMessage>>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::DialogHistory)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: client_state().localization().dialog_history_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            resizable: true,
+            minimum_width: 300.0,
+            minimum_height: 200.0,
+            maximum_height: 600.0,
+            elements: (
+                scroll_view! {
+                    follow: true,
+                    children: (
+                        ChatElement::new(self.history_path),
+                    ),
+                },
             ),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{reveal_text, visible_character_count};
+
+    #[test]
+    fn color_codes_are_not_counted() {
+        assert_eq!(visible_character_count("Hello"), 5);
+        assert_eq!(visible_character_count("^FF0000Hello^000000!"), 6);
+        assert_eq!(visible_character_count("^FFHello"), 8);
+    }
+
+    #[test]
+    fn reveal_keeps_color_codes() {
+        assert_eq!(reveal_text("^FF0000Hello^000000!", 0), "^FF0000");
+        assert_eq!(reveal_text("^FF0000Hello^000000!", 5), "^FF0000Hello^000000");
+        assert_eq!(reveal_text("^FF0000Hello^000000!", 10), "^FF0000Hello^000000!");
+        assert_eq!(reveal_text("Größe", 3), "Grö");
+    }
+}
@@ -0,0 +1,66 @@
+use korangar_interface::window::{CustomWindow, Window};
+use ragnarok_packets::InventoryIndex;
+
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::state::ClientState;
+use crate::state::theme::InterfaceThemeType;
+
+pub struct DropAmountWindow {
+    index: InventoryIndex,
+    name: String,
+    /// Number of items in the stack.
+    amount: u16,
+}
+
+impl DropAmountWindow {
+    pub fn new(index: InventoryIndex, name: String, amount: u16) -> Self {
+        Self { index, name, amount }
+    }
+}
+
+impl CustomWindow<ClientState> for DropAmountWindow {
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::DropAmount)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        let index = self.index;
+        let amount = self.amount;
+
+        // Amounts that are not smaller than the stack are covered by the "All" button.
+        let drop_button = move |text: &'static str, drop_amount: u16| {
+            button! {
+                text: text,
+                event: InputEvent::DropItem { index, amount: drop_amount },
+                disabled: ComputedSelector::new_default(move |_: &ClientState| drop_amount >= amount),
+            }
+        };
+
+        window! {
+            title: "Drop items",
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                text! {
+                    text: format!("How many of your {amount} {} do you want to drop?", self.name),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        drop_button("1", 1),
+                        drop_button("10", 10),
+                        drop_button("100", 100),
+                        button! {
+                            text: "All",
+                            event: InputEvent::DropItem { index, amount },
+                        },
+                    ),
+                },
+            ),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use korangar_interface::window::{CustomWindow, Window};
+use ragnarok_packets::InventoryIndex;
+
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::state::ClientState;
+use crate::state::theme::InterfaceThemeType;
+
+pub struct DropConfirmationWindow {
+    index: InventoryIndex,
+    /// Name of the valuable item together with the reason it needs to be
+    /// confirmed.
+    warning: String,
+}
+
+impl DropConfirmationWindow {
+    pub fn new(index: InventoryIndex, warning: String) -> Self {
+        Self { index, warning }
+    }
+}
+
+impl CustomWindow<ClientState> for DropConfirmationWindow {
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::DropConfirmation)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: "Drop valuable item",
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                text! {
+                    text: format!("Do you really want to drop this item? {}", self.warning),
+                },
+                button! {
+                    text: "Drop anyway",
+                    event: InputEvent::DropItem {
+                        index: self.index,
+                        amount: 1,
+                    },
+                },
+            ),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use korangar_interface::components::text_box::DefaultHandler;
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::Path;
+
+use crate::graphics::EffectTimeScale;
+use crate::input::InputEvent;
+use crate::interface::components::text_list::TextList;
+use crate::interface::windows::WindowClass;
+use crate::state::ClientState;
+use crate::state::effect_preview::{EffectPreviewState, EffectPreviewStatePathExt};
+use crate::state::theme::InterfaceThemeType;
+
+const MAXIMUM_PATH_LENGTH: usize = 128;
+
+pub struct EffectPreviewWindow<A> {
+    effect_preview_path: A,
+}
+
+impl<A> EffectPreviewWindow<A> {
+    pub fn new(effect_preview_path: A) -> Self {
+        Self { effect_preview_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for EffectPreviewWindow<A>
+where
+    A: Path<ClientState, EffectPreviewState>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::EffectPreview)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        struct PathTextBox;
+
+        let time_scale_options = vec![
+            EffectTimeScale::Paused,
+            EffectTimeScale::Quarter,
+            EffectTimeScale::Half,
+            EffectTimeScale::Normal,
+        ];
+
+        window! {
+            title: "Effect Preview",
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            minimum_width: 500.0,
+            elements: (
+                text_box! {
+                    ghost_text: "Effect path, for example firewall.str",
+                    state: self.effect_preview_path.path(),
+                    input_handler: DefaultHandler::<_, _, MAXIMUM_PATH_LENGTH>::new(self.effect_preview_path.path(), InputEvent::PreviewEffect),
+                    focus_id: PathTextBox,
+                },
+                button! {
+                    text: "Load",
+                    tooltip: "Load the effect from ^000001data\\texture\\effect\\^000000 and play it at the focus point of the camera",
+                    event: InputEvent::PreviewEffect,
+                },
+                text! {
+                    text: self.effect_preview_path.status_text(),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        state_button! {
+                            text: "Loop",
+                            state: self.effect_preview_path.looping(),
+                            event: Toggle(self.effect_preview_path.looping()),
+                        },
+                        state_button! {
+                            text: "Hide scene",
+                            tooltip: "Hide the map, entities, and all other effects while an effect is previewed",
+                            state: self.effect_preview_path.hide_scene(),
+                            event: Toggle(self.effect_preview_path.hide_scene()),
+                        },
+                    ),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        text! {
+                            text: "Speed",
+                        },
+                        drop_down! {
+                            selected: self.effect_preview_path.time_scale(),
+                            options: time_scale_options.clone(),
+                            click_handler: DefaultClickHandler::new(self.effect_preview_path.time_scale(), time_scale_options),
+                        },
+                    ),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: "Previous frame",
+                            event: InputEvent::StepEffectPreviewFrame { steps: -1 },
+                        },
+                        button! {
+                            text: "Restart",
+                            event: InputEvent::RestartEffectPreview,
+                        },
+                        button! {
+                            text: "Next frame",
+                            event: InputEvent::StepEffectPreviewFrame { steps: 1 },
+                        },
+                    ),
+                },
+                text! {
+                    text: self.effect_preview_path.frame_text(),
+                },
+                collapsable! {
+                    text: "Layers",
+                    initially_expanded: true,
+                    children: (
+                        TextList::new(self.effect_preview_path.layer_texts()),
+                    ),
+                },
+            ),
+        }
+    }
+}
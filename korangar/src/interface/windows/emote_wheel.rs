@@ -0,0 +1,124 @@
+use korangar_components::radial_menu;
+use korangar_interface::element::StateElement;
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::{Path, RustState};
+
+use crate::graphics::ScreenPosition;
+use crate::interface::components::radial_menu::radial_segment;
+use crate::interface::windows::WindowClass;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+/// Distance the mouse needs to move away from where the wheel was opened
+/// before an entry is selected.
+const SELECTION_DEAD_ZONE: f32 = 20.0;
+
+/// Emotions on the wheel, by their id on the map server.
+const EMOTIONS: [(u8, &str); 8] = [
+    (0, "!"),
+    (1, "?"),
+    (2, "Laugh"),
+    (3, "Love"),
+    (7, "Angry"),
+    (15, "Thanks"),
+    (17, "Sorry"),
+    (18, "Smile"),
+];
+
+/// Action of an entry of the emote wheel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmoteWheelAction {
+    Emotion(u8),
+    /// Chat message that is sent as if it was typed in the chat window.
+    ChatMacro(String),
+}
+
+impl EmoteWheelAction {
+    fn label(&self) -> String {
+        match self {
+            EmoteWheelAction::Emotion(emotion) => EMOTIONS
+                .iter()
+                .find(|(id, _)| id == emotion)
+                .map(|(_, name)| name.to_string())
+                .unwrap_or_default(),
+            EmoteWheelAction::ChatMacro(text) => text.clone(),
+        }
+    }
+}
+
+/// Internal state of the emote wheel window.
+#[derive(Default, RustState, StateElement)]
+pub struct EmoteWheelState {
+    entries: Vec<String>,
+    selected: Option<usize>,
+    #[hidden_element]
+    actions: Vec<EmoteWheelAction>,
+    /// Mouse position when the wheel was opened. Entries are selected by the
+    /// direction the mouse moved in since.
+    #[hidden_element]
+    origin: ScreenPosition,
+}
+
+impl EmoteWheelState {
+    /// Fill the wheel with the emotions and the chat macros that are set.
+    pub fn open(&mut self, chat_macros: &[String], origin: ScreenPosition) {
+        let chat_macros = chat_macros
+            .iter()
+            .filter(|chat_macro| !chat_macro.trim().is_empty())
+            .map(|chat_macro| EmoteWheelAction::ChatMacro(chat_macro.clone()));
+
+        self.actions = EMOTIONS
+            .iter()
+            .map(|(emotion, _)| EmoteWheelAction::Emotion(*emotion))
+            .chain(chat_macros)
+            .collect();
+        self.entries = self.actions.iter().map(EmoteWheelAction::label).collect();
+        self.selected = None;
+        self.origin = origin;
+    }
+
+    pub fn update_selection(&mut self, mouse_position: ScreenPosition) {
+        self.selected = radial_segment(mouse_position - self.origin, self.actions.len(), SELECTION_DEAD_ZONE);
+    }
+
+    /// Action of the selected entry. Nothing stays selected afterwards.
+    pub fn take_selected_action(&mut self) -> Option<EmoteWheelAction> {
+        self.selected.take().and_then(|index| self.actions.get(index).cloned())
+    }
+}
+
+pub struct EmoteWheelWindow<P> {
+    wheel_path: P,
+}
+
+impl<P> EmoteWheelWindow<P> {
+    pub fn new(wheel_path: P) -> Self {
+        Self { wheel_path }
+    }
+}
+
+impl<P> CustomWindow<ClientState> for EmoteWheelWindow<P>
+where
+    P: Path<ClientState, EmoteWheelState>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::EmoteWheel)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: client_state().localization().emote_wheel_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            elements: (
+                radial_menu! {
+                    entries_path: self.wheel_path.entries(),
+                    selected_path: self.wheel_path.selected(),
+                },
+            ),
+        }
+    }
+}
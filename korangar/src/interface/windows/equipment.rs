@@ -1,16 +1,26 @@
-use korangar_interface::element::Element;
+use std::cmp::Ordering;
+
+use korangar_interface::components::text_box::DefaultHandler;
+use korangar_interface::element::store::{ElementStore, ElementStoreMut};
+use korangar_interface::element::{Element, ElementBox};
+use korangar_interface::layout::{Resolver, WindowLayout};
 use korangar_interface::window::{CustomWindow, Window};
-use korangar_networking::{InventoryItem, InventoryItemDetails};
+use korangar_networking::InventoryItem;
 use ragnarok_packets::EquipPosition;
-use rust_state::{Path, Selector};
+use rust_state::{Context, Path, Selector};
 
 use crate::ItemSource;
+use crate::input::InputEvent;
 use crate::interface::windows::WindowClass;
+use crate::inventory::{Inventory, InventoryPathExt};
 use crate::loaders::OverflowBehavior;
-use crate::state::ClientState;
+use crate::state::localization::LocalizationPathExt;
 use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
 use crate::world::ResourceMetadata;
 
+const MAXIMUM_GEAR_SET_NAME_LENGTH: usize = 24;
+
 struct EquipmentPath<P> {
     equip_position: EquipPosition,
     path: P,
@@ -41,42 +51,117 @@ where
         // SAFETY:
         //
         // It is safe to unwrap here since its guaranteed to be `Some` by the bounds.
-        self.path.follow(state).unwrap().iter().find(|item| {
-            if let InventoryItemDetails::Equippable { equipped_position, .. } = &item.details {
-                return equipped_position.contains(self.equip_position);
-            }
-
-            false
-        })
+        self.path
+            .follow(state)
+            .unwrap()
+            .iter()
+            .find(|item| item.equipped_position().contains(self.equip_position))
     }
 
     fn follow_mut<'a>(&self, state: &'a mut ClientState) -> Option<&'a mut InventoryItem<ResourceMetadata>> {
         // SAFETY:
         //
         // It is safe to unwrap here since its guaranteed to be `Some` by the bounds.
-        self.path.follow_mut(state).unwrap().iter_mut().find(|item| {
-            if let InventoryItemDetails::Equippable { equipped_position, .. } = item.details {
-                return equipped_position.contains(self.equip_position);
+        self.path
+            .follow_mut(state)
+            .unwrap()
+            .iter_mut()
+            .find(|item| item.equipped_position().contains(self.equip_position))
+    }
+}
+
+/// Saved gear sets, each with a button to equip and to delete it.
+struct GearSetList {
+    elements: Vec<ElementBox<ClientState>>,
+}
+
+impl GearSetList {
+    fn new() -> Self {
+        Self { elements: Vec::new() }
+    }
+}
+
+impl Element<ClientState> for GearSetList {
+    type LayoutInfo = ();
+
+    fn create_layout_info(
+        &mut self,
+        state: &Context<ClientState>,
+        mut store: ElementStoreMut<'_>,
+        resolver: &mut Resolver<'_, ClientState>,
+    ) -> Self::LayoutInfo {
+        use korangar_interface::prelude::*;
+
+        let gear_set_count = state.get(&client_state().game_settings()).gear_sets.len();
+
+        match gear_set_count.cmp(&self.elements.len()) {
+            Ordering::Less => {
+                self.elements.truncate(gear_set_count);
+            }
+            Ordering::Equal => {}
+            Ordering::Greater => {
+                for index in self.elements.len()..gear_set_count {
+                    let name = ComputedSelector::new_default(move |state: &ClientState| {
+                        client_state()
+                            .game_settings()
+                            .follow(state)
+                            .and_then(|game_settings| game_settings.gear_sets.get(index))
+                            .map(|gear_set| gear_set.name.clone())
+                            .unwrap_or_default()
+                    });
+
+                    self.elements.push(ErasedElement::new(split! {
+                        gaps: theme().window().gaps(),
+                        children: (
+                            text! {
+                                text: name,
+                                overflow_behavior: OverflowBehavior::Shrink,
+                            },
+                            button! {
+                                text: client_state().localization().equip_gear_set_button_text(),
+                                event: InputEvent::EquipGearSet { index },
+                            },
+                            button! {
+                                text: client_state().localization().delete_gear_set_button_text(),
+                                event: InputEvent::DeleteGearSet { index },
+                            },
+                        ),
+                    }));
+                }
             }
+        }
+
+        self.elements.iter_mut().enumerate().for_each(|(index, element)| {
+            element.create_layout_info(state, store.child_store(index as u64), resolver);
+        });
+    }
 
-            false
-        })
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a Context<ClientState>,
+        store: ElementStore<'a>,
+        _: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        self.elements.iter().enumerate().for_each(|(index, element)| {
+            element.lay_out(state, store.child_store(index as u64), &(), layout);
+        });
     }
 }
 
-pub struct EquipmentWindow<A> {
-    items_path: A,
+pub struct EquipmentWindow<P> {
+    inventory_path: P,
 }
 
-impl<A> EquipmentWindow<A> {
-    pub fn new(items_path: A) -> Self {
-        Self { items_path }
+impl<P> EquipmentWindow<P> {
+    pub fn new(inventory_path: P) -> Self {
+        Self { inventory_path }
     }
 }
 
-impl<A> CustomWindow<ClientState> for EquipmentWindow<A>
+impl<P> CustomWindow<ClientState> for EquipmentWindow<P>
 where
-    A: Path<ClientState, Vec<InventoryItem<ResourceMetadata>>>,
+    P: Path<ClientState, Inventory>,
 {
     fn window_class() -> Option<WindowClass> {
         Some(WindowClass::Equipment)
@@ -85,19 +170,10 @@ where
     fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
         use korangar_interface::prelude::*;
 
-        fn equip_box(
-            items_path: impl Path<ClientState, Vec<InventoryItem<ResourceMetadata>>>,
-            equip_position: EquipPosition,
-        ) -> impl Element<ClientState> {
-            use korangar_components::item_box;
-            use korangar_interface::prelude::*;
-
-            let equipment_path = EquipmentPath {
-                equip_position,
-                path: items_path,
-            };
+        struct GearSetNameTextBox;
 
-            let display_name = match equip_position {
+        fn display_name(equip_position: EquipPosition) -> &'static str {
+            match equip_position {
                 _ if equip_position.contains(EquipPosition::HEAD_LOWER) => "Head lower",
                 _ if equip_position.contains(EquipPosition::HEAD_MIDDLE) => "Head middle",
                 _ if equip_position.contains(EquipPosition::HEAD_TOP) => "Head top",
@@ -113,7 +189,7 @@ where
                 _ if equip_position.contains(EquipPosition::COSTUME_HEAD_LOWER) => "Costume head lower",
                 _ if equip_position.contains(EquipPosition::COSTUME_GARMENT) => "Costume garment",
                 _ if equip_position.contains(EquipPosition::AMMO) => "Ammo",
-                _ if equip_position.contains(EquipPosition::SHADOW_ARMOR) => "Shadow ammo",
+                _ if equip_position.contains(EquipPosition::SHADOW_ARMOR) => "Shadow armor",
                 _ if equip_position.contains(EquipPosition::SHADOW_WEAPON) => "Shadow weapon",
                 _ if equip_position.contains(EquipPosition::SHADOW_SHIELD) => "Shadow shield",
                 _ if equip_position.contains(EquipPosition::SHADOW_SHOES) => "Shadow shoes",
@@ -123,41 +199,186 @@ where
                 _ if equip_position.contains(EquipPosition::LEFT_RIGHT_HAND) => "Two hand weapon",
                 _ if equip_position.contains(EquipPosition::SHADOW_LEFT_RIGHT_ACCESSORY) => "Shadow accessory",
                 _ => panic!("no display name for equip position"),
-            };
+            }
+        }
+
+        /// Slot on the left side of the paper doll, with the name to the right
+        /// of the item.
+        fn left_slot(
+            items_path: impl Path<ClientState, Vec<InventoryItem<ResourceMetadata>>>,
+            equip_position: EquipPosition,
+        ) -> impl Element<ClientState> {
+            use korangar_components::item_box;
+            use korangar_interface::prelude::*;
 
             split! {
                 gaps: theme().window().gaps(),
                 children: (
                     item_box! {
-                        item_path: equipment_path,
+                        item_path: EquipmentPath { equip_position, path: items_path },
                         source: ItemSource::Equipment { position: equip_position },
                     },
                     text! {
-                        text: display_name,
+                        text: display_name(equip_position),
+                        // Get this height from the skill box theme.
+                        height: 40.0,
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                ),
+            }
+        }
+
+        /// Slot on the right side of the paper doll, with the name to the left
+        /// of the item.
+        fn right_slot(
+            items_path: impl Path<ClientState, Vec<InventoryItem<ResourceMetadata>>>,
+            equip_position: EquipPosition,
+        ) -> impl Element<ClientState> {
+            use korangar_components::item_box;
+            use korangar_interface::prelude::*;
+
+            split! {
+                gaps: theme().window().gaps(),
+                children: (
+                    text! {
+                        text: display_name(equip_position),
                         // Get this height from the skill box theme.
                         height: 40.0,
+                        horizontal_alignment: HorizontalAlignment::Right { offset: 5.0, border: 5.0 },
                         overflow_behavior: OverflowBehavior::Shrink,
-                    }
+                    },
+                    item_box! {
+                        item_path: EquipmentPath { equip_position, path: items_path },
+                        source: ItemSource::Equipment { position: equip_position },
+                    },
+                ),
+            }
+        }
+
+        fn slot_rows(
+            items_path: impl Path<ClientState, Vec<InventoryItem<ResourceMetadata>>>,
+            left: [EquipPosition; 5],
+            right: [EquipPosition; 5],
+        ) -> impl Element<ClientState> {
+            use korangar_interface::prelude::*;
+
+            split! {
+                gaps: theme().window().gaps(),
+                children: (
+                    fragment! {
+                        gaps: theme().window().gaps(),
+                        children: left.map(|equip_position| left_slot(items_path, equip_position)),
+                    },
+                    fragment! {
+                        gaps: theme().window().gaps(),
+                        children: right.map(|equip_position| right_slot(items_path, equip_position)),
+                    },
                 ),
             }
         }
 
+        let inventory_path = self.inventory_path;
+        let items_path = inventory_path.items();
+        let save_action = |_: &Context<ClientState>, queue: &mut EventQueue<ClientState>| {
+            queue.queue(InputEvent::SaveGearSet);
+        };
+
         window! {
             title: "Equipment",
             class: Self::window_class(),
             theme: InterfaceThemeType::InGame,
             closable: true,
-            elements: [
-                equip_box(self.items_path, EquipPosition::HEAD_TOP),
-                equip_box(self.items_path, EquipPosition::HEAD_MIDDLE),
-                equip_box(self.items_path, EquipPosition::HEAD_LOWER),
-                equip_box(self.items_path, EquipPosition::ARMOR),
-                equip_box(self.items_path, EquipPosition::GARMENT),
-                equip_box(self.items_path, EquipPosition::SHOES),
-                equip_box(self.items_path, EquipPosition::LEFT_HAND),
-                equip_box(self.items_path, EquipPosition::RIGHT_HAND),
-                equip_box(self.items_path, EquipPosition::AMMO),
-            ],
+            elements: (
+                slot_rows(
+                    items_path,
+                    [
+                        EquipPosition::HEAD_TOP,
+                        EquipPosition::ARMOR,
+                        EquipPosition::RIGHT_HAND,
+                        EquipPosition::GARMENT,
+                        EquipPosition::LEFT_ACCESSORY,
+                    ],
+                    [
+                        EquipPosition::HEAD_MIDDLE,
+                        EquipPosition::HEAD_LOWER,
+                        EquipPosition::LEFT_HAND,
+                        EquipPosition::SHOES,
+                        EquipPosition::RIGTH_ACCESSORY,
+                    ],
+                ),
+                left_slot(items_path, EquipPosition::AMMO),
+                collapsable! {
+                    text: client_state().localization().costume_equipment_text(),
+                    children: (
+                        split! {
+                            gaps: theme().window().gaps(),
+                            children: (
+                                fragment! {
+                                    gaps: theme().window().gaps(),
+                                    children: (
+                                        left_slot(items_path, EquipPosition::COSTUME_HEAD_TOP),
+                                        left_slot(items_path, EquipPosition::COSTUME_GARMENT),
+                                    ),
+                                },
+                                fragment! {
+                                    gaps: theme().window().gaps(),
+                                    children: (
+                                        right_slot(items_path, EquipPosition::COSTUME_HEAD_MIDDLE),
+                                        right_slot(items_path, EquipPosition::COSTUME_HEAD_LOWER),
+                                    ),
+                                },
+                            ),
+                        },
+                    ),
+                },
+                collapsable! {
+                    text: client_state().localization().shadow_equipment_text(),
+                    children: (
+                        split! {
+                            gaps: theme().window().gaps(),
+                            children: (
+                                fragment! {
+                                    gaps: theme().window().gaps(),
+                                    children: (
+                                        left_slot(items_path, EquipPosition::SHADOW_ARMOR),
+                                        left_slot(items_path, EquipPosition::SHADOW_WEAPON),
+                                        left_slot(items_path, EquipPosition::SHADOW_LEFT_ACCESSORY),
+                                    ),
+                                },
+                                fragment! {
+                                    gaps: theme().window().gaps(),
+                                    children: (
+                                        right_slot(items_path, EquipPosition::SHADOW_SHOES),
+                                        right_slot(items_path, EquipPosition::SHADOW_SHIELD),
+                                        right_slot(items_path, EquipPosition::SHADOW_RIGHT_ACCESSORY),
+                                    ),
+                                },
+                            ),
+                        },
+                    ),
+                },
+                collapsable! {
+                    text: client_state().localization().gear_sets_text(),
+                    children: (
+                        split! {
+                            gaps: theme().window().gaps(),
+                            children: (
+                                text_box! {
+                                    ghost_text: client_state().localization().gear_set_name_text_box_message(),
+                                    state: inventory_path.gear_set_name(),
+                                    input_handler: DefaultHandler::<_, _, MAXIMUM_GEAR_SET_NAME_LENGTH>::new(inventory_path.gear_set_name(), save_action),
+                                    focus_id: GearSetNameTextBox,
+                                },
+                                button! {
+                                    text: client_state().localization().save_gear_set_button_text(),
+                                    event: InputEvent::SaveGearSet,
+                                },
+                            ),
+                        },
+                        GearSetList::new(),
+                    ),
+                },
+            ),
         }
     }
 }
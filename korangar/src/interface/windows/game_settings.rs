@@ -1,26 +1,38 @@
+use korangar_interface::components::text_box::DefaultHandler;
+use korangar_interface::element::Element;
 use korangar_interface::window::{CustomWindow, Window};
-use rust_state::Path;
+use rust_state::{Context, ManuallyAssertExt, Path, VecIndexExt};
 
 use crate::interface::windows::WindowClass;
-use crate::settings::{GameSettings, GameSettingsPathExt};
+use crate::loaders::OverflowBehavior;
+use crate::settings::{
+    ActionIntervalsPathExt, CHAT_MACRO_COUNT, GameSettings, GameSettingsCapabilities, GameSettingsCapabilitiesPathExt, GameSettingsPathExt,
+};
 use crate::state::localization::LocalizationPathExt;
 use crate::state::theme::InterfaceThemeType;
 use crate::state::{ClientState, ClientStatePathExt, client_state};
 
+const MAXIMUM_CHAT_MACRO_LENGTH: usize = 80;
+
 #[derive(Default)]
-pub struct GameSettingsWindow<A> {
+pub struct GameSettingsWindow<A, B> {
     game_settings_path: A,
+    capabilities_path: B,
 }
 
-impl<A> GameSettingsWindow<A> {
-    pub fn new(game_settings_path: A) -> Self {
-        Self { game_settings_path }
+impl<A, B> GameSettingsWindow<A, B> {
+    pub fn new(game_settings_path: A, capabilities_path: B) -> Self {
+        Self {
+            game_settings_path,
+            capabilities_path,
+        }
     }
 }
 
-impl<A> CustomWindow<ClientState> for GameSettingsWindow<A>
+impl<A, B> CustomWindow<ClientState> for GameSettingsWindow<A, B>
 where
     A: Path<ClientState, GameSettings>,
+    B: Path<ClientState, GameSettingsCapabilities>,
 {
     fn window_class() -> Option<WindowClass> {
         Some(WindowClass::GameSettings)
@@ -29,6 +41,27 @@ where
     fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
         use korangar_interface::prelude::*;
 
+        struct ChatMacroTextBox<const SLOT: usize>;
+
+        fn chat_macro_text_box<const SLOT: usize>(game_settings_path: impl Path<ClientState, GameSettings>) -> impl Element<ClientState> {
+            use korangar_interface::prelude::*;
+
+            let chat_macro_path = game_settings_path.chat_macros().index(SLOT).manually_asserted();
+            let unfocus_action = |_: &Context<ClientState>, queue: &mut EventQueue<ClientState>| {
+                queue.queue(Event::Unfocus);
+            };
+
+            text_box! {
+                ghost_text: client_state().localization().chat_macro_text_box_message(),
+                state: chat_macro_path,
+                input_handler: DefaultHandler::<_, _, MAXIMUM_CHAT_MACRO_LENGTH>::new(chat_macro_path, unfocus_action),
+                focus_id: ChatMacroTextBox::<SLOT>,
+            }
+        }
+
+        // Keep the text boxes in sync with the number of chat macros.
+        const _: () = assert!(CHAT_MACRO_COUNT == 4);
+
         window! {
             title: client_state().localization().game_settings_window_title(),
             class: Self::window_class(),
@@ -40,6 +73,168 @@ where
                     state: self.game_settings_path.auto_attack(),
                     event: Toggle(self.game_settings_path.auto_attack()),
                 },
+                state_button! {
+                    text: client_state().localization().party_member_names_button_text(),
+                    state: self.game_settings_path.party_member_names(),
+                    event: Toggle(self.game_settings_path.party_member_names()),
+                },
+                split! {
+                    children: (
+                        text! {
+                            text: client_state().localization().dialog_text_speed_text(),
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        drop_down! {
+                            selected: self.game_settings_path.dialog_text_speed(),
+                            options: self.capabilities_path.dialog_text_speeds(),
+                        }
+                    )
+                },
+                split! {
+                    children: (
+                        text! {
+                            text: client_state().localization().popup_aggregation_text(),
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        drop_down! {
+                            selected: self.game_settings_path.popup_aggregation(),
+                            options: self.capabilities_path.popup_aggregations(),
+                        }
+                    )
+                },
+                collapsable! {
+                    text: client_state().localization().notifications_text(),
+                    initially_expanded: true,
+                    children: (
+                        split! {
+                            gaps: theme().window().gaps(),
+                            children: (
+                                state_button! {
+                                    text: client_state().localization().friend_login_toasts_button_text(),
+                                    state: self.game_settings_path.friend_login_toasts(),
+                                    event: Toggle(self.game_settings_path.friend_login_toasts()),
+                                },
+                                state_button! {
+                                    text: client_state().localization().rare_drop_toasts_button_text(),
+                                    state: self.game_settings_path.rare_drop_toasts(),
+                                    event: Toggle(self.game_settings_path.rare_drop_toasts()),
+                                },
+                            ),
+                        },
+                        split! {
+                            gaps: theme().window().gaps(),
+                            children: (
+                                state_button! {
+                                    text: client_state().localization().achievement_toasts_button_text(),
+                                    state: self.game_settings_path.achievement_toasts(),
+                                    event: Toggle(self.game_settings_path.achievement_toasts()),
+                                },
+                                state_button! {
+                                    text: client_state().localization().system_warning_toasts_button_text(),
+                                    state: self.game_settings_path.system_warning_toasts(),
+                                    event: Toggle(self.game_settings_path.system_warning_toasts()),
+                                },
+                            ),
+                        },
+                    ),
+                },
+                collapsable! {
+                    text: client_state().localization().combat_feedback_text(),
+                    children: (
+                        split! {
+                            gaps: theme().window().gaps(),
+                            children: (
+                                state_button! {
+                                    text: client_state().localization().screen_shake_button_text(),
+                                    state: self.game_settings_path.screen_shake(),
+                                    event: Toggle(self.game_settings_path.screen_shake()),
+                                },
+                                state_button! {
+                                    text: client_state().localization().hit_stop_button_text(),
+                                    state: self.game_settings_path.hit_stop(),
+                                    event: Toggle(self.game_settings_path.hit_stop()),
+                                },
+                            ),
+                        },
+                        split! {
+                            gaps: theme().window().gaps(),
+                            children: (
+                                state_button! {
+                                    text: client_state().localization().hit_flash_button_text(),
+                                    state: self.game_settings_path.hit_flash(),
+                                    event: Toggle(self.game_settings_path.hit_flash()),
+                                },
+                                state_button! {
+                                    text: client_state().localization().disable_combat_feedback_button_text(),
+                                    state: self.game_settings_path.disable_combat_feedback(),
+                                    event: Toggle(self.game_settings_path.disable_combat_feedback()),
+                                },
+                            ),
+                        },
+                    ),
+                },
+                collapsable! {
+                    text: client_state().localization().action_intervals_text(),
+                    children: (
+                        split! {
+                            children: (
+                                text! {
+                                    text: client_state().localization().move_interval_text(),
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                                drop_down! {
+                                    selected: self.game_settings_path.action_intervals().move_interval(),
+                                    options: self.capabilities_path.action_intervals(),
+                                }
+                            )
+                        },
+                        split! {
+                            children: (
+                                text! {
+                                    text: client_state().localization().attack_interval_text(),
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                                drop_down! {
+                                    selected: self.game_settings_path.action_intervals().attack_interval(),
+                                    options: self.capabilities_path.action_intervals(),
+                                }
+                            )
+                        },
+                        split! {
+                            children: (
+                                text! {
+                                    text: client_state().localization().chat_message_interval_text(),
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                                drop_down! {
+                                    selected: self.game_settings_path.action_intervals().chat_message_interval(),
+                                    options: self.capabilities_path.action_intervals(),
+                                }
+                            )
+                        },
+                        split! {
+                            children: (
+                                text! {
+                                    text: client_state().localization().skill_interval_text(),
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                                drop_down! {
+                                    selected: self.game_settings_path.action_intervals().skill_interval(),
+                                    options: self.capabilities_path.action_intervals(),
+                                }
+                            )
+                        },
+                    ),
+                },
+                collapsable! {
+                    text: client_state().localization().chat_macros_text(),
+                    children: (
+                        chat_macro_text_box::<0>(self.game_settings_path),
+                        chat_macro_text_box::<1>(self.game_settings_path),
+                        chat_macro_text_box::<2>(self.game_settings_path),
+                        chat_macro_text_box::<3>(self.game_settings_path),
+                    ),
+                },
             ),
         }
     }
@@ -1,6 +1,7 @@
 use korangar_interface::window::{CustomWindow, Window};
 use rust_state::Path;
 
+use crate::input::InputEvent;
 use crate::interface::windows::WindowClass;
 use crate::loaders::OverflowBehavior;
 use crate::settings::{GraphicsSettingsCapabilitiesPathExt, GraphicsSettingsPathExt};
@@ -35,6 +36,47 @@ where
         use korangar_interface::prelude::*;
 
         let elements = (
+            button! {
+                text: "Detect recommended settings",
+                tooltip: "Apply the graphics preset that fits your graphics card",
+                event: InputEvent::DetectGraphicsPreset,
+            },
+            split! {
+                children: (
+                    text! {
+                        text: "Display mode",
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    drop_down! {
+                        selected: self.settings_path.display_mode(),
+                        options: self.capabilities_path.display_modes(),
+                    }
+                )
+            },
+            split! {
+                children: (
+                    text! {
+                        text: "Monitor",
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    drop_down! {
+                        selected: self.settings_path.monitor(),
+                        options: self.capabilities_path.monitor_options(),
+                    }
+                )
+            },
+            split! {
+                children: (
+                    text! {
+                        text: "Fullscreen resolution",
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    drop_down! {
+                        selected: self.settings_path.video_mode(),
+                        options: self.capabilities_path.video_mode_options(),
+                    }
+                )
+            },
             split! {
                 children: (
                     text! {
@@ -47,6 +89,18 @@ where
                     }
                 )
             },
+            split! {
+                children: (
+                    text! {
+                        text: "Light maps",
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    drop_down! {
+                        selected: self.settings_path.light_map_mode(),
+                        options: self.capabilities_path.light_map_modes(),
+                    }
+                )
+            },
             state_button! {
                 text: "Triple buffering",
                 state: self.settings_path.triple_buffering(),
@@ -83,6 +137,30 @@ where
                     }
                 )
             },
+            split! {
+                children: (
+                    text! {
+                        text: "Sprite filtering",
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    drop_down! {
+                        selected: self.settings_path.sprite_filtering(),
+                        options: self.capabilities_path.sprite_filtering_options(),
+                    }
+                )
+            },
+            split! {
+                children: (
+                    text! {
+                        text: "Sprite upscaling",
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    drop_down! {
+                        selected: self.settings_path.sprite_upscaling(),
+                        options: self.capabilities_path.sprite_upscaling_options(),
+                    }
+                )
+            },
             split! {
                 children: (
                     text! {
@@ -143,11 +221,96 @@ where
                     }
                 )
             },
+            split! {
+                children: (
+                    text! {
+                        text: "Entity shadows",
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    drop_down! {
+                        selected: self.settings_path.entity_shadows(),
+                        options: self.capabilities_path.entity_shadow_options(),
+                    }
+                )
+            },
+            split! {
+                children: (
+                    text! {
+                        text: "Entity draw distance",
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    drop_down! {
+                        selected: self.settings_path.entity_draw_distance(),
+                        options: self.capabilities_path.entity_draw_distance_options(),
+                    }
+                )
+            },
+            split! {
+                children: (
+                    text! {
+                        text: "Entity limit",
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    drop_down! {
+                        selected: self.settings_path.entity_limit(),
+                        options: self.capabilities_path.entity_limit_options(),
+                    }
+                )
+            },
+            split! {
+                children: (
+                    text! {
+                        text: "Water quality",
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    drop_down! {
+                        selected: self.settings_path.water_quality(),
+                        options: self.capabilities_path.water_quality_options(),
+                    }
+                )
+            },
+            split! {
+                children: (
+                    text! {
+                        text: "Dim beyond view range",
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    drop_down! {
+                        selected: self.settings_path.view_range_dimming(),
+                        options: self.capabilities_path.view_range_dimming_options(),
+                    }
+                )
+            },
             state_button! {
                 text: "High quality interface",
                 state: self.settings_path.high_quality_interface(),
                 event: Toggle(self.settings_path.high_quality_interface()),
             },
+            state_button! {
+                text: "Hide upper floors indoors",
+                state: self.settings_path.vertical_culling(),
+                event: Toggle(self.settings_path.vertical_culling()),
+            },
+            state_button! {
+                text: "Preload adjacent maps",
+                state: self.settings_path.preload_adjacent_maps(),
+                event: Toggle(self.settings_path.preload_adjacent_maps()),
+            },
+            state_button! {
+                text: "CRT filter",
+                state: self.settings_path.crt_filter(),
+                event: Toggle(self.settings_path.crt_filter()),
+            },
+            state_button! {
+                text: "Order independent effects",
+                state: self.settings_path.order_independent_effects(),
+                event: Toggle(self.settings_path.order_independent_effects()),
+            },
+            state_button! {
+                text: "Offset additional instances",
+                state: self.settings_path.offset_additional_instances(),
+                event: Toggle(self.settings_path.offset_additional_instances()),
+            },
         );
 
         window! {
@@ -1,28 +1,27 @@
-use korangar_components::skill_box;
+use korangar_components::hotbar_slot;
 use korangar_interface::window::{CustomWindow, Window};
 use ragnarok_packets::HotbarSlot;
 use rust_state::{ArrayLookupExt, OptionExt, Path};
 
-use crate::interface::resource::SkillSource;
 use crate::interface::windows::WindowClass;
-use crate::inventory::Skill;
+use crate::inventory::{Hotbar, HotbarPathExt};
 use crate::state::localization::LocalizationPathExt;
 use crate::state::theme::InterfaceThemeType;
 use crate::state::{ClientState, ClientStatePathExt, client_state};
 
-pub struct HotbarWindow<P, const N: usize> {
-    skills_path: P,
+pub struct HotbarWindow<P> {
+    hotbar_path: P,
 }
 
-impl<P, const N: usize> HotbarWindow<P, N> {
+impl<P> HotbarWindow<P> {
     pub fn new(path: P) -> Self {
-        Self { skills_path: path }
+        Self { hotbar_path: path }
     }
 }
 
-impl<P, const N: usize> CustomWindow<ClientState> for HotbarWindow<P, N>
+impl<P> CustomWindow<ClientState> for HotbarWindow<P>
 where
-    P: Path<ClientState, [Option<Skill>; N]>,
+    P: Path<ClientState, Hotbar>,
 {
     fn window_class() -> Option<WindowClass> {
         Some(WindowClass::Hotbar)
@@ -38,12 +37,11 @@ where
             elements: (
                 split! {
                     gaps: theme().window().gaps(),
-                    children: std::array::from_fn::<_, N, _>(|slot| {
-                        let path = self.skills_path.array_index(slot).unwrapped();
-
-                        skill_box! {
-                            skill_path: path,
-                            source: SkillSource::Hotbar { slot: HotbarSlot(slot as u16) },
+                    children: std::array::from_fn::<_, 10, _>(|slot| {
+                        hotbar_slot! {
+                            skill_path: self.hotbar_path.skills().array_index(slot).unwrapped(),
+                            item_path: self.hotbar_path.items().array_index(slot).unwrapped(),
+                            slot: HotbarSlot(slot as u16),
                         }
                     }),
                 },
@@ -95,6 +95,40 @@ where
                     }
                 )
             },
+            collapsable! {
+                text: client_state().localization().accessibility_text(),
+                children: (
+                    split! {
+                        children: (
+                            text! {
+                                text: client_state().localization().colorblind_mode_text(),
+                                overflow_behavior: OverflowBehavior::Shrink,
+                            },
+                            drop_down! {
+                                selected: self.settings_path.colorblind_mode(),
+                                options: self.capabilities_path.colorblind_modes(),
+                            }
+                        )
+                    },
+                    split! {
+                        children: (
+                            text! {
+                                text: client_state().localization().minimum_font_size_text(),
+                                overflow_behavior: OverflowBehavior::Shrink,
+                            },
+                            drop_down! {
+                                selected: self.settings_path.minimum_font_size(),
+                                options: self.capabilities_path.minimum_font_sizes(),
+                            }
+                        )
+                    },
+                    state_button! {
+                        text: client_state().localization().high_contrast_button_text(),
+                        state: self.settings_path.high_contrast(),
+                        event: Toggle(self.settings_path.high_contrast()),
+                    },
+                ),
+            },
         );
 
         window! {
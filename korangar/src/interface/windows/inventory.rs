@@ -1,28 +1,196 @@
+use std::cmp::Ordering;
+
 use korangar_components::item_box;
+use korangar_interface::components::text_box::DefaultHandler;
+use korangar_interface::element::store::{ElementStore, ElementStoreMut};
+use korangar_interface::element::{Element, ElementBox};
+use korangar_interface::layout::{Resolver, WindowLayout};
 use korangar_interface::window::{CustomWindow, Window};
 use korangar_networking::InventoryItem;
-use rust_state::{Path, VecIndexExt};
+use rust_state::{Context, Path, Selector};
 
 use crate::ItemSource;
 use crate::interface::windows::WindowClass;
+use crate::inventory::{Inventory, InventoryFilter, InventoryPathExt};
+use crate::loaders::OverflowBehavior;
 use crate::state::localization::LocalizationPathExt;
 use crate::state::theme::InterfaceThemeType;
 use crate::state::{ClientState, ClientStatePathExt, client_state};
 use crate::world::ResourceMetadata;
 
+const INVENTORY_COLUMNS: usize = 10;
+/// Minimum number of rows in the compact view, so the window doesn't shrink
+/// when the filter matches only a few items.
+const MINIMUM_INVENTORY_ROWS: usize = 4;
+const MAXIMUM_SEARCH_LENGTH: usize = 24;
+
+/// Path to the item at a position of the inventory window, after filtering and
+/// sorting.
+struct VisibleItemPath<P> {
+    inventory_path: P,
+    position: usize,
+}
+
+impl<P: Copy> Clone for VisibleItemPath<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P: Copy> Copy for VisibleItemPath<P> {}
+
+impl<P> Selector<ClientState, InventoryItem<ResourceMetadata>, false> for VisibleItemPath<P>
+where
+    P: Path<ClientState, Inventory>,
+{
+    fn select<'a>(&'a self, state: &'a ClientState) -> Option<&'a InventoryItem<ResourceMetadata>> {
+        self.follow(state)
+    }
+}
+
+impl<P> Path<ClientState, InventoryItem<ResourceMetadata>, false> for VisibleItemPath<P>
+where
+    P: Path<ClientState, Inventory>,
+{
+    fn follow<'a>(&self, state: &'a ClientState) -> Option<&'a InventoryItem<ResourceMetadata>> {
+        // SAFETY:
+        //
+        // It is safe to unwrap here since its guaranteed to be `Some` by the bounds.
+        self.inventory_path.follow(state).unwrap().visible_item(self.position)
+    }
+
+    fn follow_mut<'a>(&self, state: &'a mut ClientState) -> Option<&'a mut InventoryItem<ResourceMetadata>> {
+        // SAFETY:
+        //
+        // It is safe to unwrap here since its guaranteed to be `Some` by the bounds.
+        self.inventory_path.follow_mut(state).unwrap().visible_item_mut(self.position)
+    }
+}
+
+/// Items of the inventory, either as a grid of icons or as a list with
+/// names.
+struct InventoryItems<P> {
+    inventory_path: P,
+    compact: bool,
+    elements: Vec<ElementBox<ClientState>>,
+}
+
+impl<P> InventoryItems<P> {
+    fn new(inventory_path: P) -> Self {
+        Self {
+            inventory_path,
+            compact: true,
+            elements: Vec::new(),
+        }
+    }
+}
+
+impl<P> Element<ClientState> for InventoryItems<P>
+where
+    P: Path<ClientState, Inventory>,
+{
+    type LayoutInfo = ();
+
+    fn create_layout_info(
+        &mut self,
+        state: &Context<ClientState>,
+        mut store: ElementStoreMut<'_>,
+        resolver: &mut Resolver<'_, ClientState>,
+    ) -> Self::LayoutInfo {
+        use korangar_interface::prelude::*;
+
+        let inventory = state.get(&self.inventory_path);
+        let compact = inventory.is_compact();
+        let item_count = inventory.visible_item_count();
+
+        if compact != self.compact {
+            self.elements.clear();
+            self.compact = compact;
+        }
+
+        let row_count = match compact {
+            true => item_count.div_ceil(INVENTORY_COLUMNS).max(MINIMUM_INVENTORY_ROWS),
+            false => item_count,
+        };
+
+        match row_count.cmp(&self.elements.len()) {
+            Ordering::Less => {
+                self.elements.truncate(row_count);
+            }
+            Ordering::Equal => {}
+            Ordering::Greater => {
+                let inventory_path = self.inventory_path;
+
+                for row in self.elements.len()..row_count {
+                    match compact {
+                        true => self.elements.push(ErasedElement::new(split! {
+                            gaps: theme().window().gaps(),
+                            children: std::array::from_fn::<_, INVENTORY_COLUMNS, _>(|column| {
+                                item_box! {
+                                    item_path: VisibleItemPath { inventory_path, position: row * INVENTORY_COLUMNS + column },
+                                    source: ItemSource::Inventory,
+                                }
+                            }),
+                        })),
+                        false => {
+                            let item_path = VisibleItemPath {
+                                inventory_path,
+                                position: row,
+                            };
+                            let name = ComputedSelector::new_default(move |state: &ClientState| {
+                                item_path.follow(state).map(|item| item.metadata.name.clone()).unwrap_or_default()
+                            });
+
+                            self.elements.push(ErasedElement::new(split! {
+                                gaps: theme().window().gaps(),
+                                children: (
+                                    item_box! {
+                                        item_path: item_path,
+                                        source: ItemSource::Inventory,
+                                    },
+                                    text! {
+                                        text: name,
+                                        overflow_behavior: OverflowBehavior::Shrink,
+                                    },
+                                ),
+                            }))
+                        }
+                    }
+                }
+            }
+        }
+
+        self.elements.iter_mut().enumerate().for_each(|(index, element)| {
+            element.create_layout_info(state, store.child_store(index as u64), resolver);
+        });
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a Context<ClientState>,
+        store: ElementStore<'a>,
+        _: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        self.elements.iter().enumerate().for_each(|(index, element)| {
+            element.lay_out(state, store.child_store(index as u64), &(), layout);
+        });
+    }
+}
+
 pub struct InventoryWindow<P> {
-    items_path: P,
+    inventory_path: P,
 }
 
 impl<P> InventoryWindow<P> {
-    pub fn new(items_path: P) -> Self {
-        Self { items_path }
+    pub fn new(inventory_path: P) -> Self {
+        Self { inventory_path }
     }
 }
 
 impl<P> CustomWindow<ClientState> for InventoryWindow<P>
 where
-    P: Path<ClientState, Vec<InventoryItem<ResourceMetadata>>>,
+    P: Path<ClientState, Inventory>,
 {
     fn window_class() -> Option<WindowClass> {
         Some(WindowClass::Inventory)
@@ -31,28 +199,73 @@ where
     fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
         use korangar_interface::prelude::*;
 
-        // TODO: Probably this should be more dynamic
-        const INVENTORY_ROWS: usize = 4;
-        const INVENTORY_COLUMNS: usize = 10;
+        struct SearchTextBox;
+
+        fn filter_tab<P>(text: impl Path<ClientState, String>, inventory_path: P, filter: InventoryFilter) -> impl Element<ClientState>
+        where
+            P: Path<ClientState, Inventory>,
+        {
+            use korangar_interface::prelude::*;
+
+            button! {
+                text: text,
+                event: move |state: &Context<ClientState>, _: &mut EventQueue<ClientState>| {
+                    state.update_value_with(inventory_path, move |inventory| inventory.set_filter(filter));
+                },
+                disabled: ComputedSelector::new_default(move |state: &ClientState| {
+                    inventory_path.follow(state).unwrap().get_filter() == filter
+                }),
+            }
+        }
+
+        let inventory_path = self.inventory_path;
+        let search_action = move |state: &Context<ClientState>, _: &mut EventQueue<ClientState>| {
+            state.update_value_with(inventory_path, |inventory| inventory.update_visible_items());
+        };
 
         window! {
             title: client_state().localization().inventory_window_title(),
             class: Self::window_class(),
             theme: InterfaceThemeType::InGame,
             closable: true,
-            elements: std::array::from_fn::<_, INVENTORY_ROWS, _>(|row| {
+            elements: (
                 split! {
                     gaps: theme().window().gaps(),
-                    children: std::array::from_fn::<_, INVENTORY_COLUMNS, _>(|column| {
-                        let path = self.items_path.index(row * INVENTORY_COLUMNS + column);
-
-                        item_box! {
-                            item_path: path,
-                            source: ItemSource::Inventory,
-                        }
-                    }),
-                }
-            }),
+                    children: (
+                        filter_tab(client_state().localization().all_items_tab_text(), inventory_path, InventoryFilter::All),
+                        filter_tab(client_state().localization().consumables_tab_text(), inventory_path, InventoryFilter::Consumables),
+                        filter_tab(client_state().localization().equipment_tab_text(), inventory_path, InventoryFilter::Equipment),
+                        filter_tab(client_state().localization().etc_items_tab_text(), inventory_path, InventoryFilter::Etc),
+                    ),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        text_box! {
+                            ghost_text: client_state().localization().inventory_search_text_box_message(),
+                            state: inventory_path.search(),
+                            input_handler: DefaultHandler::<_, _, MAXIMUM_SEARCH_LENGTH>::new(inventory_path.search(), search_action),
+                            focus_id: SearchTextBox,
+                        },
+                        button! {
+                            text: inventory_path.sorting_text(),
+                            event: move |state: &Context<ClientState>, _: &mut EventQueue<ClientState>| {
+                                state.update_value_with(inventory_path, |inventory| inventory.cycle_sorting());
+                            },
+                        },
+                        state_button! {
+                            text: client_state().localization().compact_view_button_text(),
+                            state: inventory_path.compact(),
+                            event: Toggle(inventory_path.compact()),
+                        },
+                    ),
+                },
+                scroll_view! {
+                    children: (
+                        InventoryItems::new(inventory_path),
+                    ),
+                },
+            ),
         }
     }
 }
@@ -0,0 +1,526 @@
+use std::cmp::Ordering;
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+use korangar_interface::components::drop_down::DropDownItem;
+use korangar_interface::components::text_box::DefaultHandler;
+use korangar_interface::element::store::{ElementStore, ElementStoreMut};
+use korangar_interface::element::{Element, ElementBox, StateElement};
+use korangar_interface::layout::{Resolver, WindowLayout};
+use korangar_interface::window::{CustomWindow, Window};
+use ragnarok_packets::ItemId;
+use rust_state::{Context, ManuallyAssertExt, Path, RustState, VecIndexExt};
+
+use crate::graphics::Color;
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::loaders::OverflowBehavior;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+use crate::world::{EquipSlot, ItemSearchFilter, ItemType, Library};
+
+const MAXIMUM_QUERY_LENGTH: usize = 24;
+/// Maximum number of search results that are listed.
+const MAXIMUM_RESULTS: usize = 30;
+const VALUE_COLOR: Color = Color::rgb_u8(13, 231, 255);
+
+/// Item type filter of the item search window.
+#[derive(Clone, Copy, PartialEq, Eq, RustState, StateElement)]
+pub enum ItemTypeFilter {
+    Any,
+    Healing,
+    Usable,
+    Etc,
+    Weapon,
+    Armor,
+    Card,
+    Ammunition,
+}
+
+impl ItemTypeFilter {
+    const ALL: [Self; 8] = [
+        Self::Any,
+        Self::Healing,
+        Self::Usable,
+        Self::Etc,
+        Self::Weapon,
+        Self::Armor,
+        Self::Card,
+        Self::Ammunition,
+    ];
+
+    fn item_type(self) -> Option<ItemType> {
+        match self {
+            Self::Any => None,
+            Self::Healing => Some(ItemType::Healing),
+            Self::Usable => Some(ItemType::Usable),
+            Self::Etc => Some(ItemType::Etc),
+            Self::Weapon => Some(ItemType::Weapon),
+            Self::Armor => Some(ItemType::Armor),
+            Self::Card => Some(ItemType::Card),
+            Self::Ammunition => Some(ItemType::Ammunition),
+        }
+    }
+}
+
+impl DropDownItem<ItemTypeFilter> for ItemTypeFilter {
+    fn text(&self) -> &str {
+        match self {
+            Self::Any => "Any",
+            Self::Healing => "Healing",
+            Self::Usable => "Usable",
+            Self::Etc => "Etc",
+            Self::Weapon => "Weapon",
+            Self::Armor => "Armor",
+            Self::Card => "Card",
+            Self::Ammunition => "Ammunition",
+        }
+    }
+
+    fn value(&self) -> ItemTypeFilter {
+        *self
+    }
+}
+
+/// Equip slot filter of the item search window.
+#[derive(Clone, Copy, PartialEq, Eq, RustState, StateElement)]
+pub enum EquipSlotFilter {
+    Any,
+    HeadTop,
+    HeadMid,
+    HeadLow,
+    Armor,
+    Weapon,
+    Shield,
+    Garment,
+    Shoes,
+    Accessory,
+}
+
+impl EquipSlotFilter {
+    const ALL: [Self; 10] = [
+        Self::Any,
+        Self::HeadTop,
+        Self::HeadMid,
+        Self::HeadLow,
+        Self::Armor,
+        Self::Weapon,
+        Self::Shield,
+        Self::Garment,
+        Self::Shoes,
+        Self::Accessory,
+    ];
+
+    fn equip_slot(self) -> Option<EquipSlot> {
+        match self {
+            Self::Any => None,
+            Self::HeadTop => Some(EquipSlot::HeadTop),
+            Self::HeadMid => Some(EquipSlot::HeadMid),
+            Self::HeadLow => Some(EquipSlot::HeadLow),
+            Self::Armor => Some(EquipSlot::Armor),
+            Self::Weapon => Some(EquipSlot::Weapon),
+            Self::Shield => Some(EquipSlot::Shield),
+            Self::Garment => Some(EquipSlot::Garment),
+            Self::Shoes => Some(EquipSlot::Shoes),
+            Self::Accessory => Some(EquipSlot::Accessory),
+        }
+    }
+}
+
+impl DropDownItem<EquipSlotFilter> for EquipSlotFilter {
+    fn text(&self) -> &str {
+        match self {
+            Self::Any => "Any",
+            Self::HeadTop => "Upper headgear",
+            Self::HeadMid => "Middle headgear",
+            Self::HeadLow => "Lower headgear",
+            Self::Armor => "Armor",
+            Self::Weapon => "Weapon",
+            Self::Shield => "Shield",
+            Self::Garment => "Garment",
+            Self::Shoes => "Shoes",
+            Self::Accessory => "Accessory",
+        }
+    }
+
+    fn value(&self) -> EquipSlotFilter {
+        *self
+    }
+}
+
+/// Required level filter of the item search window.
+#[derive(Clone, Copy, PartialEq, Eq, RustState, StateElement)]
+pub enum LevelRangeFilter {
+    Any,
+    UpTo20,
+    UpTo40,
+    UpTo60,
+    UpTo80,
+    UpTo99,
+    Above99,
+}
+
+impl LevelRangeFilter {
+    const ALL: [Self; 7] = [
+        Self::Any,
+        Self::UpTo20,
+        Self::UpTo40,
+        Self::UpTo60,
+        Self::UpTo80,
+        Self::UpTo99,
+        Self::Above99,
+    ];
+
+    fn level_range(self) -> RangeInclusive<u16> {
+        match self {
+            Self::Any => 0..=u16::MAX,
+            Self::UpTo20 => 0..=20,
+            Self::UpTo40 => 21..=40,
+            Self::UpTo60 => 41..=60,
+            Self::UpTo80 => 61..=80,
+            Self::UpTo99 => 81..=99,
+            Self::Above99 => 100..=u16::MAX,
+        }
+    }
+}
+
+impl DropDownItem<LevelRangeFilter> for LevelRangeFilter {
+    fn text(&self) -> &str {
+        match self {
+            Self::Any => "Any",
+            Self::UpTo20 => "1 - 20",
+            Self::UpTo40 => "21 - 40",
+            Self::UpTo60 => "41 - 60",
+            Self::UpTo80 => "61 - 80",
+            Self::UpTo99 => "81 - 99",
+            Self::Above99 => "100+",
+        }
+    }
+
+    fn value(&self) -> LevelRangeFilter {
+        *self
+    }
+}
+
+#[derive(Clone, RustState)]
+pub struct ItemSearchResult {
+    item_id: ItemId,
+    name: String,
+}
+
+struct ItemSearchResults<A> {
+    results_path: A,
+    elements: Vec<ElementBox<ClientState>>,
+}
+
+impl<A> ItemSearchResults<A> {
+    fn new(results_path: A) -> Self {
+        Self {
+            results_path,
+            elements: Vec::new(),
+        }
+    }
+}
+
+impl<A> Element<ClientState> for ItemSearchResults<A>
+where
+    A: Path<ClientState, Vec<ItemSearchResult>>,
+{
+    type LayoutInfo = ();
+
+    fn create_layout_info(
+        &mut self,
+        state: &Context<ClientState>,
+        mut store: ElementStoreMut<'_>,
+        resolver: &mut Resolver<'_, ClientState>,
+    ) -> Self::LayoutInfo {
+        use korangar_interface::prelude::*;
+
+        let results = state.get(&self.results_path);
+
+        match results.len().cmp(&self.elements.len()) {
+            Ordering::Less => {
+                self.elements.truncate(results.len());
+            }
+            Ordering::Equal => {}
+            Ordering::Greater => {
+                for index in self.elements.len()..results.len() {
+                    let result_path = self.results_path.index(index).manually_asserted();
+
+                    self.elements.push(ErasedElement::new(button! {
+                        text: result_path.name(),
+                        event: move |state: &Context<ClientState>, queue: &mut EventQueue<ClientState>| {
+                            let item_id = state.get(&result_path).item_id;
+                            queue.queue(InputEvent::ShowItemInfo { item_id });
+                        },
+                    }));
+                }
+            }
+        }
+
+        self.elements.iter_mut().enumerate().for_each(|(index, element)| {
+            element.create_layout_info(state, store.child_store(index as u64), resolver);
+        });
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a Context<ClientState>,
+        store: ElementStore<'a>,
+        _: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        self.elements.iter().enumerate().for_each(|(index, element)| {
+            element.lay_out(state, store.child_store(index as u64), &(), layout);
+        });
+    }
+}
+
+/// Internal state of the item search window.
+#[derive(RustState, StateElement)]
+pub struct ItemSearchWindowState {
+    search: String,
+    item_type: ItemTypeFilter,
+    equip_slot: EquipSlotFilter,
+    level_range: LevelRangeFilter,
+    #[hidden_element]
+    item_type_options: Vec<ItemTypeFilter>,
+    #[hidden_element]
+    equip_slot_options: Vec<EquipSlotFilter>,
+    #[hidden_element]
+    level_range_options: Vec<LevelRangeFilter>,
+    #[hidden_element]
+    results: Vec<ItemSearchResult>,
+    #[hidden_element]
+    selected_item: Option<ItemId>,
+    name_text: String,
+    item_type_text: String,
+    equip_position_text: String,
+    required_level_text: String,
+    dropped_by_text: String,
+    sold_by_text: String,
+}
+
+impl Default for ItemSearchWindowState {
+    fn default() -> Self {
+        Self {
+            search: String::new(),
+            item_type: ItemTypeFilter::Any,
+            equip_slot: EquipSlotFilter::Any,
+            level_range: LevelRangeFilter::Any,
+            item_type_options: ItemTypeFilter::ALL.to_vec(),
+            equip_slot_options: EquipSlotFilter::ALL.to_vec(),
+            level_range_options: LevelRangeFilter::ALL.to_vec(),
+            results: Vec::new(),
+            selected_item: None,
+            name_text: String::new(),
+            item_type_text: String::new(),
+            equip_position_text: String::new(),
+            required_level_text: String::new(),
+            dropped_by_text: String::new(),
+            sold_by_text: String::new(),
+        }
+    }
+}
+
+impl ItemSearchWindowState {
+    pub fn query(&self) -> &str {
+        &self.search
+    }
+
+    pub fn filter(&self) -> ItemSearchFilter {
+        ItemSearchFilter {
+            item_type: self.item_type.item_type(),
+            equip_slot: self.equip_slot.equip_slot(),
+            level_range: self.level_range.level_range(),
+        }
+    }
+
+    pub fn selected_item(&self) -> Option<ItemId> {
+        self.selected_item
+    }
+
+    /// Show the results of a search.
+    pub fn set_results(&mut self, library: &Library, results: &[ItemId]) {
+        self.results = results
+            .iter()
+            .take(MAXIMUM_RESULTS)
+            .map(|&item_id| ItemSearchResult {
+                item_id,
+                name: library.get_item_name_from_id(item_id, true).to_owned(),
+            })
+            .collect();
+    }
+
+    /// Show the information of an item, including where it drops and which
+    /// NPCs sell it.
+    pub fn show_item(&mut self, library: &Library, item_id: ItemId) {
+        let details = library.get_item_details(item_id);
+
+        self.selected_item = Some(item_id);
+        self.name_text = library.get_item_name_from_id(item_id, true).to_owned();
+        self.item_type_text = details.map(|details| format!("{:?}", details.item_type)).unwrap_or_default();
+        self.equip_position_text = details
+            .map(|details| {
+                details
+                    .equip_slots
+                    .iter()
+                    .map(|equip_slot| format!("{equip_slot:?}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .unwrap_or_default();
+        self.required_level_text = details.map(|details| details.required_level.to_string()).unwrap_or_default();
+
+        self.dropped_by_text.clear();
+        for (monster, chance) in library.get_item_drops(item_id) {
+            let _ = writeln!(self.dropped_by_text, "{} {:.2}%", monster.name, chance as f32 / 100.0);
+        }
+
+        self.sold_by_text = details.map(|details| details.sold_by.join(", ")).unwrap_or_default();
+    }
+}
+
+pub struct ItemSearchWindow<A> {
+    window_state_path: A,
+}
+
+impl<A> ItemSearchWindow<A> {
+    pub fn new(window_state_path: A) -> Self {
+        Self { window_state_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for ItemSearchWindow<A>
+where
+    A: Path<ClientState, ItemSearchWindowState>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::ItemSearch)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        struct SearchTextBox;
+
+        fn info_row(label: impl Path<ClientState, String>, value: impl Path<ClientState, String>) -> impl Element<ClientState> {
+            use korangar_interface::prelude::*;
+
+            split! {
+                children: (
+                    text! {
+                        text: label,
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    text! {
+                        text: value,
+                        color: VALUE_COLOR,
+                        horizontal_alignment: HorizontalAlignment::Right { offset: 0.0, border: 3.0 },
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                ),
+            }
+        }
+
+        let search_action = |_: &Context<ClientState>, queue: &mut EventQueue<ClientState>| {
+            queue.queue(InputEvent::SearchItems);
+        };
+
+        window! {
+            title: client_state().localization().item_search_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            minimum_width: 320.0,
+            elements: (
+                text_box! {
+                    ghost_text: client_state().localization().item_search_text_box_message(),
+                    state: self.window_state_path.search(),
+                    input_handler: DefaultHandler::<_, _, MAXIMUM_QUERY_LENGTH>::new(self.window_state_path.search(), search_action),
+                    focus_id: SearchTextBox,
+                },
+                split! {
+                    children: (
+                        text! {
+                            text: client_state().localization().item_type_text(),
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        drop_down! {
+                            selected: self.window_state_path.item_type(),
+                            options: self.window_state_path.item_type_options(),
+                        },
+                    ),
+                },
+                split! {
+                    children: (
+                        text! {
+                            text: client_state().localization().equip_position_text(),
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        drop_down! {
+                            selected: self.window_state_path.equip_slot(),
+                            options: self.window_state_path.equip_slot_options(),
+                        },
+                    ),
+                },
+                split! {
+                    children: (
+                        text! {
+                            text: client_state().localization().level_range_text(),
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        drop_down! {
+                            selected: self.window_state_path.level_range(),
+                            options: self.window_state_path.level_range_options(),
+                        },
+                    ),
+                },
+                button! {
+                    text: client_state().localization().search_button_text(),
+                    event: InputEvent::SearchItems,
+                },
+                scroll_view! {
+                    children: (
+                        ItemSearchResults::new(self.window_state_path.results()),
+                    ),
+                },
+                text! {
+                    text: self.window_state_path.name_text(),
+                    color: VALUE_COLOR,
+                },
+                fragment! {
+                    gaps: 4.0,
+                    children: (
+                        info_row(client_state().localization().item_type_text(), self.window_state_path.item_type_text()),
+                        info_row(client_state().localization().equip_position_text(), self.window_state_path.equip_position_text()),
+                        info_row(client_state().localization().required_level_text(), self.window_state_path.required_level_text()),
+                    ),
+                },
+                text! {
+                    text: client_state().localization().dropped_by_text(),
+                },
+                text! {
+                    text: self.window_state_path.dropped_by_text(),
+                    color: VALUE_COLOR,
+                },
+                text! {
+                    text: client_state().localization().sold_by_text(),
+                },
+                text! {
+                    text: self.window_state_path.sold_by_text(),
+                    color: VALUE_COLOR,
+                },
+                button! {
+                    text: client_state().localization().link_in_chat_button_text(),
+                    event: move |state: &Context<ClientState>, queue: &mut EventQueue<ClientState>| {
+                        if let Some(item_id) = *state.get(&self.window_state_path.selected_item()) {
+                            queue.queue(InputEvent::LinkItemInChat { item_id });
+                        }
+                    },
+                },
+            ),
+        }
+    }
+}
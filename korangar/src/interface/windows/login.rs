@@ -109,11 +109,28 @@ where
 #[derive(RustState, StateElement)]
 pub struct LoginWindowState {
     selected_service: ServiceId,
+    /// Shows the countdown of the auto-login.
+    auto_login_status: String,
 }
 
 impl LoginWindowState {
     pub fn new(selected_service: ServiceId) -> Self {
-        Self { selected_service }
+        Self {
+            selected_service,
+            auto_login_status: String::new(),
+        }
+    }
+
+    pub fn selected_service(&self) -> ServiceId {
+        self.selected_service
+    }
+
+    pub fn select_service(&mut self, service_id: ServiceId) {
+        self.selected_service = service_id;
+    }
+
+    pub fn set_auto_login_status(&mut self, status: String) {
+        self.auto_login_status = status;
     }
 }
 
@@ -151,6 +168,7 @@ where
         let password_path = selected_service_path.password();
         let remember_username_path = selected_service_path.remember_username();
         let remember_password_path = selected_service_path.remember_password();
+        let auto_login_path = selected_service_path.auto_login();
 
         let disabled_selector = ComputedSelector::new_default(move |state: &ClientState| {
             selected_service_path.username().follow(state).unwrap().is_empty()
@@ -253,6 +271,14 @@ where
                             hovered_background_color: Color::TRANSPARENT,
                             shadow_padding: ShadowPadding::default(),
                         },
+                        state_button! {
+                            text: client_state().localization().auto_log_in_text(),
+                            state: auto_login_path,
+                            event: Toggle(auto_login_path),
+                            background_color: Color::TRANSPARENT,
+                            hovered_background_color: Color::TRANSPARENT,
+                            shadow_padding: ShadowPadding::default(),
+                        },
                     ),
                 },
                 button! {
@@ -261,6 +287,7 @@ where
                     disabled_tooltip: client_state().localization().log_in_button_tooltip(),
                     event: login_action,
                 },
+                text! { text: self.window_state_path.auto_login_status() },
             ),
         }
     }
@@ -0,0 +1,198 @@
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::Path;
+
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::state::ClientState;
+use crate::state::map_editor::{MapEditorState, MapEditorStatePathExt};
+use crate::state::theme::InterfaceThemeType;
+use crate::world::{GizmoAxis, GizmoMode, MarkerProperty};
+
+const PROPERTY_COUNT: usize = 9;
+
+/// Properties that can be adjusted, together with their display name.
+const PROPERTIES: [(&str, MarkerProperty); PROPERTY_COUNT] = [
+    ("Position X", MarkerProperty::PositionX),
+    ("Position Y", MarkerProperty::PositionY),
+    ("Position Z", MarkerProperty::PositionZ),
+    ("Range", MarkerProperty::Range),
+    ("Volume", MarkerProperty::Volume),
+    ("Red", MarkerProperty::Red),
+    ("Green", MarkerProperty::Green),
+    ("Blue", MarkerProperty::Blue),
+    ("Emit speed", MarkerProperty::EmitSpeed),
+];
+
+pub struct MapEditorWindow<A> {
+    map_editor_path: A,
+}
+
+impl<A> MapEditorWindow<A> {
+    pub fn new(map_editor_path: A) -> Self {
+        Self { map_editor_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for MapEditorWindow<A>
+where
+    A: Path<ClientState, MapEditorState>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::MapEditor)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: "Map Editor",
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            minimum_width: 300.0,
+            elements: (
+                state_button! {
+                    text: "Editing mode",
+                    tooltip: "Select and drag object, light, sound, and effect markers instead of opening their details",
+                    state: self.map_editor_path.enabled(),
+                    event: Toggle(self.map_editor_path.enabled()),
+                },
+                state_button! {
+                    text: "Walkability brush",
+                    tooltip: "Click or drag over tiles to make them walkable or block them. Enable the map tiles in the render options to see the tile types",
+                    state: self.map_editor_path.tile_brush(),
+                    event: Toggle(self.map_editor_path.tile_brush()),
+                },
+                text! {
+                    text: self.map_editor_path.selection_text(),
+                },
+                fragment! {
+                    gaps: theme().window().gaps(),
+                    children: std::array::from_fn::<_, PROPERTY_COUNT, _>(|index| {
+                        let (name, property) = PROPERTIES[index];
+
+                        split! {
+                            children: (
+                                text! {
+                                    text: name,
+                                },
+                                button! {
+                                    text: "-",
+                                    event: InputEvent::AdjustMarkerProperty { property, steps: -1 },
+                                },
+                                button! {
+                                    text: "+",
+                                    event: InputEvent::AdjustMarkerProperty { property, steps: 1 },
+                                },
+                            ),
+                        }
+                    }),
+                },
+                text! {
+                    text: self.map_editor_path.gizmo_text(),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: "Translate",
+                            event: InputEvent::SetGizmoMode { mode: GizmoMode::Translate },
+                        },
+                        button! {
+                            text: "Rotate",
+                            event: InputEvent::SetGizmoMode { mode: GizmoMode::Rotate },
+                        },
+                        button! {
+                            text: "Scale",
+                            event: InputEvent::SetGizmoMode { mode: GizmoMode::Scale },
+                        },
+                    ),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: "X",
+                            event: InputEvent::SetGizmoAxis { axis: GizmoAxis::X },
+                        },
+                        button! {
+                            text: "Y",
+                            event: InputEvent::SetGizmoAxis { axis: GizmoAxis::Y },
+                        },
+                        button! {
+                            text: "Z",
+                            event: InputEvent::SetGizmoAxis { axis: GizmoAxis::Z },
+                        },
+                    ),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: "-",
+                            tooltip: "Transform the selected object by one step",
+                            event: InputEvent::StepGizmo { steps: -1 },
+                        },
+                        button! {
+                            text: "+",
+                            tooltip: "Transform the selected object by one step",
+                            event: InputEvent::StepGizmo { steps: 1 },
+                        },
+                    ),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: "Undo",
+                            event: InputEvent::UndoTransform,
+                        },
+                        button! {
+                            text: "Redo",
+                            event: InputEvent::RedoTransform,
+                        },
+                    ),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: "Add light",
+                            tooltip: "Add a light source at the position of the player",
+                            event: InputEvent::AddLightSource,
+                        },
+                        button! {
+                            text: "Add sound",
+                            tooltip: "Add a sound source at the position of the player",
+                            event: InputEvent::AddSoundSource,
+                        },
+                        button! {
+                            text: "Add effect",
+                            tooltip: "Add an effect source at the position of the player",
+                            event: InputEvent::AddEffectSource,
+                        },
+                    ),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        button! {
+                            text: "Duplicate",
+                            tooltip: "Add a copy of the selected marker at the position of the player",
+                            event: InputEvent::DuplicateSelectedMarker,
+                        },
+                        button! {
+                            text: "Delete",
+                            event: InputEvent::RemoveSelectedMarker,
+                        },
+                        button! {
+                            text: "Export",
+                            tooltip: "Save the modified map and its tiles to client/map_export",
+                            event: InputEvent::ExportMap,
+                        },
+                    ),
+                },
+            ),
+        }
+    }
+}
@@ -1,14 +1,117 @@
+use std::sync::Arc;
+
+use hashbrown::HashMap;
+use korangar_interface::element::Element;
+use korangar_interface::element::store::{ElementStore, ElementStoreMut};
+use korangar_interface::layout::area::Area;
+use korangar_interface::layout::{Resolver, WindowLayout};
 use korangar_interface::window::{CustomWindow, Window};
 use ragnarok_packets::TilePosition;
+use rust_state::{Context, Path};
 
+use crate::graphics::{Color, Texture};
 use crate::input::InputEvent;
 use crate::interface::windows::WindowClass;
+use crate::renderer::LayoutExt;
 use crate::state::ClientState;
 use crate::state::theme::InterfaceThemeType;
 
-pub struct MapsWindow;
+/// Height of the map thumbnails inside the maps window.
+const THUMBNAIL_HEIGHT: f32 = 64.0;
+
+const MAP_COUNT: usize = 23;
+
+/// Maps that can be warped to from the maps window.
+pub const MAP_WARPS: [(&str, TilePosition); MAP_COUNT] = [
+    ("geffen", TilePosition { x: 119, y: 59 }),
+    ("alberta", TilePosition { x: 28, y: 234 }),
+    ("aldebaran", TilePosition { x: 140, y: 131 }),
+    ("amatsu", TilePosition { x: 198, y: 84 }),
+    ("ayothaya", TilePosition { x: 208, y: 166 }),
+    ("prontera", TilePosition { x: 155, y: 183 }),
+    ("brasilis", TilePosition { x: 196, y: 217 }),
+    ("einbech", TilePosition { x: 63, y: 35 }),
+    ("einbroch", TilePosition { x: 64, y: 200 }),
+    ("dicastes01", TilePosition { x: 198, y: 187 }),
+    ("gonryun", TilePosition { x: 160, y: 120 }),
+    ("hugel", TilePosition { x: 96, y: 145 }),
+    ("izlude", TilePosition { x: 128, y: 146 }),
+    ("jawaii", TilePosition { x: 251, y: 132 }),
+    ("lasagna", TilePosition { x: 193, y: 182 }),
+    ("lighthalzen", TilePosition { x: 158, y: 92 }),
+    ("louyang", TilePosition { x: 217, y: 100 }),
+    ("xmas", TilePosition { x: 147, y: 134 }),
+    ("c_tower1", TilePosition { x: 235, y: 218 }),
+    ("ama_dun01", TilePosition { x: 54, y: 107 }),
+    ("umbala", TilePosition { x: 97, y: 153 }),
+    ("rachel", TilePosition { x: 120, y: 120 }),
+    ("mid_camp", TilePosition { x: 180, y: 240 }),
+];
+
+/// Displays the thumbnail of a map. The space for the thumbnail is reserved
+/// while it is still loading, so the window doesn't change its size.
+struct MapThumbnailElement<A> {
+    thumbnails_path: A,
+    map_name: &'static str,
+}
+
+impl<A> Element<ClientState> for MapThumbnailElement<A>
+where
+    A: Path<ClientState, HashMap<String, Arc<Texture>>>,
+{
+    type LayoutInfo = Area;
+
+    fn create_layout_info(
+        &mut self,
+        _: &Context<ClientState>,
+        _: ElementStoreMut<'_>,
+        resolver: &mut Resolver<'_, ClientState>,
+    ) -> Self::LayoutInfo {
+        resolver.with_height(THUMBNAIL_HEIGHT)
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a Context<ClientState>,
+        _: ElementStore<'a>,
+        layout_info: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        let Some(texture) = state.get(&self.thumbnails_path).get(self.map_name) else {
+            return;
+        };
+
+        let texture_size = texture.get_size();
+
+        // Keep the aspect ratio of the thumbnail.
+        let width = (layout_info.height * texture_size.width as f32 / texture_size.height as f32).min(layout_info.width);
+        let height = width * texture_size.height as f32 / texture_size.width as f32;
 
-impl CustomWindow<ClientState> for MapsWindow {
+        let area = Area {
+            left: layout_info.left + (layout_info.width - width) / 2.0,
+            top: layout_info.top + (layout_info.height - height) / 2.0,
+            width,
+            height,
+        };
+
+        layout.add_texture(area, texture.clone(), Color::WHITE, true);
+    }
+}
+
+pub struct MapsWindow<A> {
+    thumbnails_path: A,
+}
+
+impl<A> MapsWindow<A> {
+    pub fn new(thumbnails_path: A) -> Self {
+        Self { thumbnails_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for MapsWindow<A>
+where
+    A: Path<ClientState, HashMap<String, Arc<Texture>>>,
+{
     fn window_class() -> Option<WindowClass> {
         Some(WindowClass::Maps)
     }
@@ -16,33 +119,6 @@ impl CustomWindow<ClientState> for MapsWindow {
     fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
         use korangar_interface::prelude::*;
 
-        const MAP_COUNT: usize = 23;
-        const MAP_WARPS: [(&str, TilePosition); MAP_COUNT] = [
-            ("geffen", TilePosition { x: 119, y: 59 }),
-            ("alberta", TilePosition { x: 28, y: 234 }),
-            ("aldebaran", TilePosition { x: 140, y: 131 }),
-            ("amatsu", TilePosition { x: 198, y: 84 }),
-            ("ayothaya", TilePosition { x: 208, y: 166 }),
-            ("prontera", TilePosition { x: 155, y: 183 }),
-            ("brasilis", TilePosition { x: 196, y: 217 }),
-            ("einbech", TilePosition { x: 63, y: 35 }),
-            ("einbroch", TilePosition { x: 64, y: 200 }),
-            ("dicastes01", TilePosition { x: 198, y: 187 }),
-            ("gonryun", TilePosition { x: 160, y: 120 }),
-            ("hugel", TilePosition { x: 96, y: 145 }),
-            ("izlude", TilePosition { x: 128, y: 146 }),
-            ("jawaii", TilePosition { x: 251, y: 132 }),
-            ("lasagna", TilePosition { x: 193, y: 182 }),
-            ("lighthalzen", TilePosition { x: 158, y: 92 }),
-            ("louyang", TilePosition { x: 217, y: 100 }),
-            ("xmas", TilePosition { x: 147, y: 134 }),
-            ("c_tower1", TilePosition { x: 235, y: 218 }),
-            ("ama_dun01", TilePosition { x: 54, y: 107 }),
-            ("umbala", TilePosition { x: 97, y: 153 }),
-            ("rachel", TilePosition { x: 120, y: 120 }),
-            ("mid_camp", TilePosition { x: 180, y: 240 }),
-        ];
-
         window! {
             title: "Maps",
             class: Self::window_class(),
@@ -51,13 +127,21 @@ impl CustomWindow<ClientState> for MapsWindow {
             elements: std::array::from_fn::<_, MAP_COUNT, _>(|index| {
                 let warp = MAP_WARPS[index];
 
-                button! {
-                    text: warp.0,
-                    tooltip: format!("Map: {}\nCoordinates: {}, {}", warp.0, warp.1.x, warp.1.y),
-                    event: InputEvent::WarpToMap {
-                        map_name: format!("{}.gat", warp.0),
-                        position: warp.1,
-                    },
+                split! {
+                    children: (
+                        MapThumbnailElement {
+                            thumbnails_path: self.thumbnails_path,
+                            map_name: warp.0,
+                        },
+                        button! {
+                            text: warp.0,
+                            tooltip: format!("Map: {}\nCoordinates: {}, {}", warp.0, warp.1.x, warp.1.y),
+                            event: InputEvent::WarpToMap {
+                                map_name: format!("{}.gat", warp.0),
+                                position: warp.1,
+                            },
+                        },
+                    )
                 }
             }),
         }
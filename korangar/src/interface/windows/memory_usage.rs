@@ -0,0 +1,42 @@
+use korangar_interface::element::StateElement;
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::Path;
+
+use crate::interface::windows::WindowClass;
+use crate::state::ClientState;
+use crate::state::memory_usage::MemoryUsage;
+use crate::state::theme::InterfaceThemeType;
+
+pub struct MemoryUsageWindow<A> {
+    memory_usage_path: A,
+}
+
+impl<A> MemoryUsageWindow<A> {
+    pub fn new(memory_usage_path: A) -> Self {
+        Self { memory_usage_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for MemoryUsageWindow<A>
+where
+    A: Path<ClientState, MemoryUsage>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::MemoryUsage)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: "Memory Usage",
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            minimum_width: 300.0,
+            elements: (
+                StateElement::to_element(self.memory_usage_path, "Memory usage".to_owned()),
+            ),
+        }
+    }
+}
@@ -76,6 +76,38 @@ impl CustomWindow<ClientState> for MenuWindow {
                     hovered_background_color: client_theme().debug_button().hovered_background_color(),
                 },
                 #[cfg(feature = "debug")]
+                button! {
+                    text: "Map editor",
+                    tooltip: "Transform objects and move, add, and remove light, sound, and effect sources (^000001only available in debug mode^000000)",
+                    event: InputEvent::ToggleMapEditorWindow,
+                    foreground_color: client_theme().debug_button().foreground_color(),
+                    hovered_background_color: client_theme().debug_button().hovered_background_color(),
+                },
+                #[cfg(feature = "debug")]
+                button! {
+                    text: "Asset browser",
+                    tooltip: "Preview the animations of sprites from the game files (^000001only available in debug mode^000000)",
+                    event: InputEvent::ToggleAssetBrowserWindow,
+                    foreground_color: client_theme().debug_button().foreground_color(),
+                    hovered_background_color: client_theme().debug_button().hovered_background_color(),
+                },
+                #[cfg(feature = "debug")]
+                button! {
+                    text: "Effect preview",
+                    tooltip: "Play an effect in isolation and inspect its layers (^000001only available in debug mode^000000)",
+                    event: InputEvent::ToggleEffectPreviewWindow,
+                    foreground_color: client_theme().debug_button().foreground_color(),
+                    hovered_background_color: client_theme().debug_button().hovered_background_color(),
+                },
+                #[cfg(feature = "debug")]
+                button! {
+                    text: "Model viewer",
+                    tooltip: "Inspect a single model and its nodes (^000001only available in debug mode^000000)",
+                    event: InputEvent::ToggleModelViewerWindow,
+                    foreground_color: client_theme().debug_button().foreground_color(),
+                    hovered_background_color: client_theme().debug_button().hovered_background_color(),
+                },
+                #[cfg(feature = "debug")]
                 button! {
                     text: "Commands",
                     tooltip: "List of commands used for testing (^000001only available in debug mode^000000)",
@@ -115,6 +147,22 @@ impl CustomWindow<ClientState> for MenuWindow {
                     foreground_color: client_theme().debug_button().foreground_color(),
                     hovered_background_color: client_theme().debug_button().hovered_background_color(),
                 },
+                #[cfg(feature = "debug")]
+                button! {
+                    text: "Memory usage",
+                    tooltip: "Shows the memory used by the subsystems of the client (^000001only available in debug mode^000000)",
+                    event: InputEvent::ToggleMemoryUsageWindow,
+                    foreground_color: client_theme().debug_button().foreground_color(),
+                    hovered_background_color: client_theme().debug_button().hovered_background_color(),
+                },
+                #[cfg(feature = "debug")]
+                button! {
+                    text: "Sprite depth bias",
+                    tooltip: "Tune the depth offsets that keep sprites from clipping into the ground (^000001only available in debug mode^000000)",
+                    event: InputEvent::ToggleSpriteDepthBiasWindow,
+                    foreground_color: client_theme().debug_button().foreground_color(),
+                    hovered_background_color: client_theme().debug_button().hovered_background_color(),
+                },
                 button! {
                     text: client_state().localization().log_out_button_text(),
                     event: InputEvent::LogOut,
@@ -0,0 +1,216 @@
+use std::sync::Arc;
+
+use hashbrown::HashMap;
+use korangar_interface::element::store::{ElementStore, ElementStoreMut};
+use korangar_interface::element::{Element, StateElement};
+use korangar_interface::layout::area::Area;
+use korangar_interface::layout::{Resolver, WindowLayout};
+use korangar_interface::prelude::{HorizontalAlignment, VerticalAlignment};
+use korangar_interface::window::{CustomWindow, Window};
+use ragnarok_packets::TilePosition;
+use rust_state::{Context, Path, RustState};
+
+use crate::graphics::{Color, CornerDiameter, ShadowPadding, Texture};
+use crate::interface::windows::WindowClass;
+use crate::loaders::{FontSize, OverflowBehavior};
+use crate::renderer::LayoutExt;
+use crate::settings::GameSettingsPathExt;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::party::Party;
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+/// Height of the minimap inside the minimap window.
+const MINIMAP_HEIGHT: f32 = 180.0;
+const MARKER_SIZE: f32 = 7.0;
+const LABEL_WIDTH: f32 = 80.0;
+const LABEL_HEIGHT: f32 = 14.0;
+
+/// Internal state of the minimap window.
+#[derive(Default, RustState, StateElement)]
+pub struct MinimapWindowState {
+    #[hidden_element]
+    map_name: String,
+    /// Width and height of the map in tiles.
+    #[hidden_element]
+    map_size: (u16, u16),
+    #[hidden_element]
+    player_position: Option<TilePosition>,
+}
+
+impl MinimapWindowState {
+    pub fn set_map(&mut self, map_name: String, map_size: (u16, u16)) {
+        self.map_name = map_name;
+        self.map_size = map_size;
+        self.player_position = None;
+    }
+
+    pub fn set_player_position(&mut self, position: TilePosition) {
+        self.player_position = Some(position);
+    }
+}
+
+/// Thumbnail of the current map with markers for the player and the members
+/// of the party on the same map.
+struct Minimap<A, B, C, D> {
+    minimap_path: A,
+    party_path: B,
+    thumbnails_path: C,
+    show_names_path: D,
+}
+
+impl<A, B, C, D> Element<ClientState> for Minimap<A, B, C, D>
+where
+    A: Path<ClientState, MinimapWindowState>,
+    B: Path<ClientState, Party>,
+    C: Path<ClientState, HashMap<String, Arc<Texture>>>,
+    D: Path<ClientState, bool>,
+{
+    type LayoutInfo = Area;
+
+    fn create_layout_info(
+        &mut self,
+        _: &Context<ClientState>,
+        _: ElementStoreMut<'_>,
+        resolver: &mut Resolver<'_, ClientState>,
+    ) -> Self::LayoutInfo {
+        resolver.with_height(MINIMAP_HEIGHT)
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a Context<ClientState>,
+        _: ElementStore<'a>,
+        layout_info: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        let minimap = state.get(&self.minimap_path);
+        let (map_width, map_height) = minimap.map_size;
+
+        if map_width == 0 || map_height == 0 {
+            return;
+        }
+
+        // Keep the aspect ratio of the map.
+        let width = (layout_info.height * map_width as f32 / map_height as f32).min(layout_info.width);
+        let height = width * map_height as f32 / map_width as f32;
+
+        let area = Area {
+            left: layout_info.left + (layout_info.width - width) / 2.0,
+            top: layout_info.top + (layout_info.height - height) / 2.0,
+            width,
+            height,
+        };
+
+        layout.add_rectangle(
+            area,
+            CornerDiameter::uniform(0.0),
+            // TODO: Put this in the theme
+            Color::rgb_u8(20, 20, 20),
+            Color::rgba_u8(0, 0, 0, 100),
+            ShadowPadding::diagonal(2.0, 5.0),
+        );
+
+        if let Some(texture) = state.get(&self.thumbnails_path).get(&minimap.map_name) {
+            layout.add_texture(area, texture.clone(), Color::WHITE, true);
+        }
+
+        // The first row of tiles is the southern edge of the map, which is at the
+        // bottom of the thumbnail.
+        let marker_area = |position: TilePosition| Area {
+            left: area.left + (position.x as f32 + 0.5) / map_width as f32 * area.width - MARKER_SIZE / 2.0,
+            top: area.top + (1.0 - (position.y as f32 + 0.5) / map_height as f32) * area.height - MARKER_SIZE / 2.0,
+            width: MARKER_SIZE,
+            height: MARKER_SIZE,
+        };
+
+        let show_names = *state.get(&self.show_names_path);
+
+        for member in state.get(&self.party_path).members() {
+            let Some(position) = member.position else {
+                continue;
+            };
+
+            let area = marker_area(position);
+
+            layout.add_rectangle(
+                area,
+                CornerDiameter::uniform(MARKER_SIZE),
+                member.color,
+                Color::rgba_u8(0, 0, 0, 150),
+                ShadowPadding::uniform(1.0),
+            );
+
+            if show_names && !member.name.is_empty() {
+                let label_area = Area {
+                    left: area.left + MARKER_SIZE / 2.0 - LABEL_WIDTH / 2.0,
+                    top: area.top + MARKER_SIZE,
+                    width: LABEL_WIDTH,
+                    height: LABEL_HEIGHT,
+                };
+
+                layout.add_text(
+                    label_area,
+                    &member.name,
+                    // TODO: Put this in the theme
+                    FontSize(11.0),
+                    member.color,
+                    member.color,
+                    HorizontalAlignment::Center { offset: 0.0, border: 0.0 },
+                    VerticalAlignment::Top { offset: 0.0 },
+                    OverflowBehavior::Shrink,
+                );
+            }
+        }
+
+        if let Some(position) = minimap.player_position {
+            layout.add_rectangle(
+                marker_area(position),
+                CornerDiameter::uniform(MARKER_SIZE),
+                Color::WHITE,
+                Color::rgba_u8(0, 0, 0, 150),
+                ShadowPadding::uniform(1.0),
+            );
+        }
+    }
+}
+
+pub struct MinimapWindow<A, B> {
+    minimap_path: A,
+    party_path: B,
+}
+
+impl<A, B> MinimapWindow<A, B> {
+    pub fn new(minimap_path: A, party_path: B) -> Self {
+        Self { minimap_path, party_path }
+    }
+}
+
+impl<A, B> CustomWindow<ClientState> for MinimapWindow<A, B>
+where
+    A: Path<ClientState, MinimapWindowState>,
+    B: Path<ClientState, Party>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::Minimap)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: client_state().localization().minimap_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                Minimap {
+                    minimap_path: self.minimap_path,
+                    party_path: self.party_path,
+                    thumbnails_path: client_state().map_thumbnails(),
+                    show_names_path: client_state().game_settings().party_member_names(),
+                },
+            ),
+        }
+    }
+}
@@ -1,15 +1,28 @@
+#[cfg(feature = "debug")]
+mod asset_browser;
+mod attendance;
 mod audio_settings;
 mod buy;
 mod buy_cart;
 mod buy_or_sell;
 mod cache;
+#[cfg(feature = "debug")]
+mod cache_statistics;
 mod character_creation;
 mod character_overview;
 mod character_selection;
 mod chat;
 #[cfg(feature = "debug")]
 mod commands;
+#[cfg(feature = "debug")]
+mod console;
+mod crash_report;
 mod dialog;
+mod drop_amount;
+mod drop_confirmation;
+#[cfg(feature = "debug")]
+mod effect_preview;
+mod emote_wheel;
 mod equipment;
 mod error;
 #[cfg(feature = "debug")]
@@ -21,10 +34,19 @@ mod graphics_settings;
 mod hotbar;
 mod interface_settings;
 mod inventory;
+mod item_search;
 mod login;
 #[cfg(feature = "debug")]
+mod map_editor;
+#[cfg(feature = "debug")]
 mod maps;
+#[cfg(feature = "debug")]
+mod memory_usage;
 mod menu;
+mod minimap;
+#[cfg(feature = "debug")]
+mod model_viewer;
+mod monster_info;
 #[cfg(feature = "debug")]
 mod packet_inspector;
 #[cfg(feature = "debug")]
@@ -34,26 +56,44 @@ mod render_options;
 mod respawn;
 mod sell;
 mod sell_cart;
+mod sell_confirmation;
 mod server_selection;
+mod session_statistics;
+mod skill_planner;
 mod skill_tree;
+#[cfg(feature = "debug")]
+mod sprite_depth_bias;
 mod stats;
 #[cfg(feature = "debug")]
 mod theme_inspector;
 
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "debug")]
+pub use self::asset_browser::AssetBrowserWindow;
+pub use self::attendance::AttendanceWindow;
 pub use self::audio_settings::AudioSettingsWindow;
 pub use self::buy::BuyWindow;
 pub use self::buy_cart::BuyCartWindow;
 pub use self::buy_or_sell::BuyOrSellWindow;
 pub use self::cache::WindowCache;
+#[cfg(feature = "debug")]
+pub use self::cache_statistics::CacheStatisticsWindow;
 pub use self::character_creation::CharacterCreationWindow;
 pub use self::character_overview::CharacterOverviewWindow;
 pub use self::character_selection::CharacterSelectionWindow;
 pub use self::chat::{ChatTextBox, ChatWindow, ChatWindowState};
 #[cfg(feature = "debug")]
 pub use self::commands::CommandsWindow;
-pub use self::dialog::{DialogWindow, DialogWindowState};
+#[cfg(feature = "debug")]
+pub use self::console::{ConsoleTextBox, ConsoleWindow, ConsoleWindowState};
+pub use self::crash_report::CrashReportWindow;
+pub use self::dialog::{DialogHistoryWindow, DialogWindow, DialogWindowState};
+pub use self::drop_amount::DropAmountWindow;
+pub use self::drop_confirmation::DropConfirmationWindow;
+#[cfg(feature = "debug")]
+pub use self::effect_preview::EffectPreviewWindow;
+pub use self::emote_wheel::{EmoteWheelAction, EmoteWheelState, EmoteWheelWindow};
 pub use self::equipment::EquipmentWindow;
 pub use self::error::ErrorWindow;
 #[cfg(feature = "debug")]
@@ -65,10 +105,19 @@ pub use self::graphics_settings::GraphicsSettingsWindow;
 pub use self::hotbar::HotbarWindow;
 pub use self::interface_settings::InterfaceSettingsWindow;
 pub use self::inventory::InventoryWindow;
+pub use self::item_search::{ItemSearchWindow, ItemSearchWindowState};
 pub use self::login::{LoginWindow, LoginWindowState};
 #[cfg(feature = "debug")]
-pub use self::maps::MapsWindow;
+pub use self::map_editor::MapEditorWindow;
+#[cfg(feature = "debug")]
+pub use self::maps::{MAP_WARPS, MapsWindow};
+#[cfg(feature = "debug")]
+pub use self::memory_usage::MemoryUsageWindow;
 pub use self::menu::MenuWindow;
+pub use self::minimap::{MinimapWindow, MinimapWindowState};
+#[cfg(feature = "debug")]
+pub use self::model_viewer::ModelViewerWindow;
+pub use self::monster_info::{MonsterInfoWindow, MonsterInfoWindowState};
 #[cfg(feature = "debug")]
 pub use self::packet_inspector::PacketInspectorWindow;
 #[cfg(feature = "debug")]
@@ -78,14 +127,20 @@ pub use self::render_options::RenderOptionsWindow;
 pub use self::respawn::RespawnWindow;
 pub use self::sell::SellWindow;
 pub use self::sell_cart::SellCartWindow;
+pub use self::sell_confirmation::SellConfirmationWindow;
 pub use self::server_selection::ServerSelectionWindow;
+pub use self::session_statistics::SessionStatisticsWindow;
+pub use self::skill_planner::SkillPlannerWindow;
 pub use self::skill_tree::SkillTreeWindow;
+#[cfg(feature = "debug")]
+pub use self::sprite_depth_bias::SpriteDepthBiasWindow;
 pub use self::stats::StatsWindow;
 #[cfg(feature = "debug")]
 pub use self::theme_inspector::{ThemeInspectorWindow, ThemeInspectorWindowState};
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WindowClass {
+    Attendance,
     AudioSettings,
     Buy,
     BuyCart,
@@ -95,6 +150,10 @@ pub enum WindowClass {
     CharacterOverview,
     CharacterSelection,
     Dialog,
+    DialogHistory,
+    DropAmount,
+    DropConfirmation,
+    EmoteWheel,
     GameSettings,
     InterfaceSettings,
     GraphicsSettings,
@@ -105,15 +164,29 @@ pub enum WindowClass {
     Stats,
     FriendList,
     FriendRequest,
+    MonsterInfo,
+    ItemSearch,
+    Minimap,
+    SkillPlanner,
+    SessionStatistics,
     Login,
     Menu,
     Respawn,
     SelectServer,
     Sell,
     SellCart,
+    SellConfirmation,
     #[cfg(feature = "debug")]
     Maps,
     #[cfg(feature = "debug")]
+    MapEditor,
+    #[cfg(feature = "debug")]
+    AssetBrowser,
+    #[cfg(feature = "debug")]
+    EffectPreview,
+    #[cfg(feature = "debug")]
+    ModelViewer,
+    #[cfg(feature = "debug")]
     ClientStateInspector,
     #[cfg(feature = "debug")]
     PacketInspector,
@@ -127,4 +200,10 @@ pub enum WindowClass {
     Profiler,
     #[cfg(feature = "debug")]
     CacheStatistics,
+    #[cfg(feature = "debug")]
+    MemoryUsage,
+    #[cfg(feature = "debug")]
+    SpriteDepthBias,
+    #[cfg(feature = "debug")]
+    Console,
 }
@@ -0,0 +1,102 @@
+use korangar_interface::components::text_box::DefaultHandler;
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::Path;
+
+use crate::graphics::RenderOptionsPathExt;
+use crate::input::InputEvent;
+use crate::interface::components::text_list::TextList;
+use crate::interface::windows::WindowClass;
+use crate::state::model_viewer::{ModelViewerState, ModelViewerStatePathExt};
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+const MAXIMUM_PATH_LENGTH: usize = 128;
+
+pub struct ModelViewerWindow<A> {
+    model_viewer_path: A,
+}
+
+impl<A> ModelViewerWindow<A> {
+    pub fn new(model_viewer_path: A) -> Self {
+        Self { model_viewer_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for ModelViewerWindow<A>
+where
+    A: Path<ClientState, ModelViewerState>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::ModelViewer)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        struct PathTextBox;
+
+        window! {
+            title: "Model Viewer",
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            minimum_width: 500.0,
+            elements: (
+                text_box! {
+                    ghost_text: "Model path, for example prontera\\fountain.rsm",
+                    state: self.model_viewer_path.path(),
+                    input_handler: DefaultHandler::<_, _, MAXIMUM_PATH_LENGTH>::new(self.model_viewer_path.path(), InputEvent::PreviewModel),
+                    focus_id: PathTextBox,
+                },
+                button! {
+                    text: "Load",
+                    tooltip: "Load the model from ^000001data\\model\\^000000. Orbit around it by holding the ^000001right mouse button^000000 and zoom with the ^000001mouse wheel^000000 while this window is open",
+                    event: InputEvent::PreviewModel,
+                },
+                text! {
+                    text: self.model_viewer_path.status_text(),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        state_button! {
+                            text: "Wireframe",
+                            tooltip: "Show all ^000001geometry^000000 as ^000001wireframe^000000",
+                            state: client_state().render_options().show_wireframe(),
+                            event: Toggle(client_state().render_options().show_wireframe()),
+                        },
+                        state_button! {
+                            text: "Normals",
+                            tooltip: "Tint the model by its ^000001vertex normals^000000",
+                            state: self.model_viewer_path.show_normals(),
+                            event: Toggle(self.model_viewer_path.show_normals()),
+                        },
+                    ),
+                },
+                split! {
+                    gaps: theme().window().gaps(),
+                    children: (
+                        state_button! {
+                            text: "Bounding box",
+                            state: self.model_viewer_path.show_bounding_box(),
+                            event: Toggle(self.model_viewer_path.show_bounding_box()),
+                        },
+                        state_button! {
+                            text: "Hide scene",
+                            tooltip: "Hide the map, entities, and effects while a model is viewed",
+                            state: self.model_viewer_path.hide_scene(),
+                            event: Toggle(self.model_viewer_path.hide_scene()),
+                        },
+                    ),
+                },
+                collapsable! {
+                    text: "Nodes",
+                    initially_expanded: true,
+                    children: (
+                        TextList::new(self.model_viewer_path.node_texts()),
+                    ),
+                },
+            ),
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::fmt::Write;
+
+use korangar_interface::components::text_box::DefaultHandler;
+use korangar_interface::element::{Element, StateElement};
+use korangar_interface::window::{CustomWindow, Window};
+use ragnarok_packets::ItemId;
+use rust_state::{Context, Path, RustState};
+
+use crate::graphics::Color;
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::loaders::OverflowBehavior;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+use crate::world::{Library, MonsterInfo};
+
+const MAXIMUM_QUERY_LENGTH: usize = 24;
+/// Maximum number of other matches listed below the search box.
+const MAXIMUM_LISTED_RESULTS: usize = 8;
+const VALUE_COLOR: Color = Color::rgb_u8(13, 231, 255);
+
+/// Internal state of the monster info window.
+#[derive(Default, RustState, StateElement)]
+pub struct MonsterInfoWindowState {
+    search: String,
+    search_results_text: String,
+    name_text: String,
+    level_text: String,
+    health_text: String,
+    base_experience_text: String,
+    job_experience_text: String,
+    attack_text: String,
+    defense_text: String,
+    element_text: String,
+    race_text: String,
+    size_text: String,
+    drops_text: String,
+    spawn_maps_text: String,
+}
+
+impl MonsterInfoWindowState {
+    /// Show the information of a monster.
+    pub fn show_monster(&mut self, library: &Library, monster: &MonsterInfo) {
+        self.name_text = monster.name.clone();
+        self.level_text = monster.level.to_string();
+        self.health_text = monster.health.to_string();
+        self.base_experience_text = monster.base_experience.to_string();
+        self.job_experience_text = monster.job_experience.to_string();
+        self.attack_text = format!("{} - {}", monster.minimum_attack, monster.maximum_attack);
+        self.defense_text = format!("{} / {}", monster.defense, monster.magic_defense);
+        self.element_text = format!("{:?} {}", monster.element, monster.element_level);
+        self.race_text = format!("{:?}", monster.race);
+        self.size_text = format!("{:?}", monster.size);
+
+        self.drops_text.clear();
+        for drop in &monster.drops {
+            let item_name = library.get_item_name_from_id(ItemId(drop.item_id), true);
+            let _ = writeln!(self.drops_text, "{item_name} {:.2}%", drop.chance as f32 / 100.0);
+        }
+
+        self.spawn_maps_text = monster.spawn_maps.join(", ");
+    }
+
+    /// Show the results of a search. The best match is shown in full and the
+    /// names of the other matches are listed.
+    pub fn show_search_results(&mut self, library: &Library, results: &[&MonsterInfo]) {
+        self.search_results_text = results
+            .iter()
+            .skip(1)
+            .take(MAXIMUM_LISTED_RESULTS)
+            .map(|monster| monster.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        match results.first() {
+            Some(monster) => self.show_monster(library, monster),
+            None => {
+                *self = Self {
+                    search: std::mem::take(&mut self.search),
+                    ..Self::default()
+                }
+            }
+        }
+    }
+}
+
+pub struct MonsterInfoWindow<A> {
+    window_state_path: A,
+}
+
+impl<A> MonsterInfoWindow<A> {
+    pub fn new(window_state_path: A) -> Self {
+        Self { window_state_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for MonsterInfoWindow<A>
+where
+    A: Path<ClientState, MonsterInfoWindowState>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::MonsterInfo)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        struct SearchTextBox;
+
+        fn info_row(label: impl Path<ClientState, String>, value: impl Path<ClientState, String>) -> impl Element<ClientState> {
+            use korangar_interface::prelude::*;
+
+            split! {
+                children: (
+                    text! {
+                        text: label,
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    text! {
+                        text: value,
+                        color: VALUE_COLOR,
+                        horizontal_alignment: HorizontalAlignment::Right { offset: 0.0, border: 3.0 },
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                ),
+            }
+        }
+
+        let search_action = move |state: &Context<ClientState>, queue: &mut EventQueue<ClientState>| {
+            let query = state.get(&self.window_state_path.search()).clone();
+            queue.queue(InputEvent::SearchMonsters { query });
+        };
+
+        window! {
+            title: client_state().localization().monster_info_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            minimum_width: 300.0,
+            elements: (
+                text_box! {
+                    ghost_text: client_state().localization().monster_search_text_box_message(),
+                    state: self.window_state_path.search(),
+                    input_handler: DefaultHandler::<_, _, MAXIMUM_QUERY_LENGTH>::new(self.window_state_path.search(), search_action),
+                    focus_id: SearchTextBox,
+                },
+                text! {
+                    text: self.window_state_path.search_results_text(),
+                },
+                text! {
+                    text: self.window_state_path.name_text(),
+                    color: VALUE_COLOR,
+                },
+                fragment! {
+                    gaps: 4.0,
+                    children: (
+                        info_row(client_state().localization().level_text(), self.window_state_path.level_text()),
+                        info_row(client_state().localization().health_text(), self.window_state_path.health_text()),
+                        info_row(client_state().localization().base_experience_text(), self.window_state_path.base_experience_text()),
+                        info_row(client_state().localization().job_experience_text(), self.window_state_path.job_experience_text()),
+                        info_row(client_state().localization().attack_text(), self.window_state_path.attack_text()),
+                        info_row(client_state().localization().defense_text(), self.window_state_path.defense_text()),
+                        info_row(client_state().localization().element_text(), self.window_state_path.element_text()),
+                        info_row(client_state().localization().race_text(), self.window_state_path.race_text()),
+                        info_row(client_state().localization().size_text(), self.window_state_path.size_text()),
+                    ),
+                },
+                text! {
+                    text: client_state().localization().drops_text(),
+                },
+                text! {
+                    text: self.window_state_path.drops_text(),
+                    color: VALUE_COLOR,
+                },
+                text! {
+                    text: client_state().localization().spawn_maps_text(),
+                },
+                text! {
+                    text: self.window_state_path.spawn_maps_text(),
+                    color: VALUE_COLOR,
+                },
+            ),
+        }
+    }
+}
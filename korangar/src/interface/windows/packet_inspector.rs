@@ -114,6 +114,9 @@ where
                         },
                     ),
                 },
+                text! {
+                    text: self.packet_history_path.event_statistics_text(),
+                },
                 scroll_view! {
                     follow: true,
                     children: (
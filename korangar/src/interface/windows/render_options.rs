@@ -3,7 +3,7 @@ use std::num::NonZeroU32;
 use korangar_interface::window::{CustomWindow, Window};
 use rust_state::Path;
 
-use crate::graphics::{PARTITION_COUNT, RenderOptions, RenderOptionsPathExt};
+use crate::graphics::{EffectTimeScale, PARTITION_COUNT, RenderOptions, RenderOptionsPathExt};
 use crate::interface::windows::WindowClass;
 use crate::state::ClientState;
 use crate::state::theme::InterfaceThemeType;
@@ -41,6 +41,13 @@ where
             NonZeroU32::new(6),
         ];
 
+        let effect_time_scale_options = vec![
+            EffectTimeScale::Paused,
+            EffectTimeScale::Quarter,
+            EffectTimeScale::Half,
+            EffectTimeScale::Normal,
+        ];
+
         let elements = (
             collapsable! {
                 text: "General",
@@ -114,6 +121,18 @@ where
                         state: self.render_options_path.show_entities(),
                         event: Toggle(self.render_options_path.show_entities()),
                     },
+                    state_button! {
+                        text: "Show sky",
+                        tooltip: "Show the ^000001sky^000000 behind the map",
+                        state: self.render_options_path.show_sky(),
+                        event: Toggle(self.render_options_path.show_sky()),
+                    },
+                    state_button! {
+                        text: "Show decals",
+                        tooltip: "Show ^000001decals^000000 on the ground, like skill areas",
+                        state: self.render_options_path.show_decals(),
+                        event: Toggle(self.render_options_path.show_decals()),
+                    },
                     state_button! {
                         text: "Show water",
                         tooltip: "Show the ^000001water plane^000000",
@@ -126,6 +145,18 @@ where
                         state: self.render_options_path.show_indicators(),
                         event: Toggle(self.render_options_path.show_indicators()),
                     },
+                    split! {
+                        children: (
+                            text! {
+                                text: "Effect speed"
+                            },
+                            drop_down! {
+                                selected: self.render_options_path.effect_time_scale(),
+                                options: effect_time_scale_options.clone(),
+                                click_handler: DefaultClickHandler::new(self.render_options_path.effect_time_scale(), effect_time_scale_options.clone()),
+                            },
+                        ),
+                    },
                 ),
             },
             collapsable! {
@@ -139,20 +139,18 @@ where
             ShadowPadding::diagonal(2.0, 5.0),
         );
 
-        if let Some(texture) = &item.metadata.0.texture {
-            layout.add_texture(layout_info.texture_area, texture.clone(), Color::WHITE, false);
-
-            layout.add_text(
-                layout_info.texture_area,
-                self.amount_string.get_str(),
-                FontSize(16.0),
-                Color::monochrome_u8(220),
-                Color::rgb_u8(255, 160, 60),
-                HorizontalAlignment::Right { offset: 3.0, border: 3.0 },
-                VerticalAlignment::Bottom { offset: 0.0 },
-                OverflowBehavior::Shrink,
-            );
-        }
+        layout.add_item_icon(layout_info.texture_area, item.metadata.0.texture.as_ref());
+
+        layout.add_text(
+            layout_info.texture_area,
+            self.amount_string.get_str(),
+            FontSize(16.0),
+            Color::monochrome_u8(220),
+            Color::rgb_u8(255, 160, 60),
+            HorizontalAlignment::Right { offset: 3.0, border: 3.0 },
+            VerticalAlignment::Bottom { offset: 0.0 },
+            OverflowBehavior::Shrink,
+        );
 
         layout.add_text(
             layout_info.text_area,
@@ -360,7 +358,7 @@ where
                                     })
                                     .collect();
 
-                                queue.queue(InputEvent::SellItems { items });
+                                queue.queue(InputEvent::SellItems { items, confirmed: false });
                             }
                         },
                         button! {
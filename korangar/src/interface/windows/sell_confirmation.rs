@@ -0,0 +1,49 @@
+use korangar_interface::window::{CustomWindow, Window};
+use ragnarok_packets::SoldItemInformation;
+
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::state::ClientState;
+use crate::state::theme::InterfaceThemeType;
+
+pub struct SellConfirmationWindow {
+    items: Vec<SoldItemInformation>,
+    /// Names of the valuable items together with the reason they need to be
+    /// confirmed.
+    warnings: Vec<String>,
+}
+
+impl SellConfirmationWindow {
+    pub fn new(items: Vec<SoldItemInformation>, warnings: Vec<String>) -> Self {
+        Self { items, warnings }
+    }
+}
+
+impl CustomWindow<ClientState> for SellConfirmationWindow {
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::SellConfirmation)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        window! {
+            title: "Sell valuable items",
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            elements: (
+                text! {
+                    text: format!("Do you really want to sell these items? {}", self.warnings.join(", ")),
+                },
+                button! {
+                    text: "Sell anyway",
+                    event: InputEvent::SellItems {
+                        items: self.items,
+                        confirmed: true,
+                    },
+                },
+            ),
+        }
+    }
+}
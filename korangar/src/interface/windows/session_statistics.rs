@@ -0,0 +1,154 @@
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::Path;
+
+use crate::graphics::Color;
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::loaders::OverflowBehavior;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::session_statistics::{SessionStatistics, SessionStatisticsPathExt};
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+pub struct SessionStatisticsWindow<A> {
+    statistics_path: A,
+}
+
+impl<A> SessionStatisticsWindow<A> {
+    pub fn new(statistics_path: A) -> Self {
+        Self { statistics_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for SessionStatisticsWindow<A>
+where
+    A: Path<ClientState, SessionStatistics>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::SessionStatistics)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        let value_color = Color::rgb_u8(13, 231, 255);
+
+        window! {
+            title: client_state().localization().session_statistics_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            minimum_width: 300.0,
+            elements: (
+                fragment! {
+                    gaps: 4.0,
+                    children: (
+                        split! {
+                            children: (
+                                text! {
+                                    text: client_state().localization().session_time_text(),
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                                text! {
+                                    text: self.statistics_path.session_time_text(),
+                                    color: value_color,
+                                    horizontal_alignment: HorizontalAlignment::Right { offset: 0.0, border: 3.0 },
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                            ),
+                        },
+                        split! {
+                            children: (
+                                text! {
+                                    text: client_state().localization().base_experience_text(),
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                                text! {
+                                    text: self.statistics_path.base_experience_text(),
+                                    color: value_color,
+                                    horizontal_alignment: HorizontalAlignment::Right { offset: 0.0, border: 3.0 },
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                            ),
+                        },
+                        split! {
+                            children: (
+                                text! {
+                                    text: client_state().localization().job_experience_text(),
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                                text! {
+                                    text: self.statistics_path.job_experience_text(),
+                                    color: value_color,
+                                    horizontal_alignment: HorizontalAlignment::Right { offset: 0.0, border: 3.0 },
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                            ),
+                        },
+                        split! {
+                            children: (
+                                text! {
+                                    text: client_state().localization().zeny_text(),
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                                text! {
+                                    text: self.statistics_path.zeny_text(),
+                                    color: value_color,
+                                    horizontal_alignment: HorizontalAlignment::Right { offset: 0.0, border: 3.0 },
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                            ),
+                        },
+                        split! {
+                            children: (
+                                text! {
+                                    text: client_state().localization().kills_text(),
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                                text! {
+                                    text: self.statistics_path.kill_count_text(),
+                                    color: value_color,
+                                    horizontal_alignment: HorizontalAlignment::Right { offset: 0.0, border: 3.0 },
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                            ),
+                        },
+                        text! {
+                            text: self.statistics_path.kills_text(),
+                        },
+                        split! {
+                            children: (
+                                text! {
+                                    text: client_state().localization().looted_items_text(),
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                                text! {
+                                    text: self.statistics_path.looted_item_count_text(),
+                                    color: value_color,
+                                    horizontal_alignment: HorizontalAlignment::Right { offset: 0.0, border: 3.0 },
+                                    overflow_behavior: OverflowBehavior::Shrink,
+                                },
+                            ),
+                        },
+                        text! {
+                            text: self.statistics_path.looted_items_text(),
+                        },
+                    ),
+                },
+                split! {
+                    gaps: 4.0,
+                    children: (
+                        button! {
+                            text: client_state().localization().reset_button_text(),
+                            event: InputEvent::ResetSessionStatistics,
+                        },
+                        button! {
+                            text: client_state().localization().export_button_text(),
+                            event: InputEvent::ExportSessionStatistics,
+                        },
+                    ),
+                },
+            ),
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use std::cmp::Ordering;
+
+use korangar_interface::components::text_box::DefaultHandler;
+use korangar_interface::element::store::{ElementStore, ElementStoreMut};
+use korangar_interface::element::{Element, ElementBox};
+use korangar_interface::layout::{Resolver, WindowLayout};
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::{Context, ManuallyAssertExt, Path, VecIndexExt};
+
+use crate::graphics::Color;
+use crate::input::InputEvent;
+use crate::interface::windows::WindowClass;
+use crate::loaders::OverflowBehavior;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::skill_planner::{
+    PlannerJobPathExt, PlannerSkillPathExt, PlannerStat, SkillPlanner, SkillPlannerPathExt, StatCalculatorPathExt,
+};
+use crate::state::theme::InterfaceThemeType;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+/// Maximum length of a build string. This is enough for every skill of a job
+/// at maximum level.
+const MAXIMUM_BUILD_STRING_LENGTH: usize = 256;
+const VALUE_COLOR: Color = Color::rgb_u8(13, 231, 255);
+
+/// Buttons to select the job that is planned.
+struct JobList<A> {
+    planner_path: A,
+    elements: Vec<ElementBox<ClientState>>,
+}
+
+impl<A> JobList<A> {
+    fn new(planner_path: A) -> Self {
+        Self {
+            planner_path,
+            elements: Vec::new(),
+        }
+    }
+}
+
+impl<A> Element<ClientState> for JobList<A>
+where
+    A: Path<ClientState, SkillPlanner>,
+{
+    type LayoutInfo = ();
+
+    fn create_layout_info(
+        &mut self,
+        state: &Context<ClientState>,
+        mut store: ElementStoreMut<'_>,
+        resolver: &mut Resolver<'_, ClientState>,
+    ) -> Self::LayoutInfo {
+        use korangar_interface::prelude::*;
+
+        let job_count = state.get(&self.planner_path.jobs()).len();
+
+        match job_count.cmp(&self.elements.len()) {
+            Ordering::Less => {
+                self.elements.truncate(job_count);
+            }
+            Ordering::Equal => {}
+            Ordering::Greater => {
+                for index in self.elements.len()..job_count {
+                    let planner_path = self.planner_path;
+                    let job_path = planner_path.jobs().index(index).manually_asserted();
+
+                    self.elements.push(ErasedElement::new(button! {
+                        text: job_path.name(),
+                        event: move |state: &Context<ClientState>, _: &mut EventQueue<ClientState>| {
+                            state.update_value_with(planner_path, move |planner| planner.select_job(index));
+                        },
+                    }));
+                }
+            }
+        }
+
+        self.elements.iter_mut().enumerate().for_each(|(index, element)| {
+            element.create_layout_info(state, store.child_store(index as u64), resolver);
+        });
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a Context<ClientState>,
+        store: ElementStore<'a>,
+        _: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        self.elements.iter().enumerate().for_each(|(index, element)| {
+            element.lay_out(state, store.child_store(index as u64), &(), layout);
+        });
+    }
+}
+
+/// Skills of the selected job with buttons to raise and lower them.
+struct SkillList<A> {
+    planner_path: A,
+    elements: Vec<ElementBox<ClientState>>,
+}
+
+impl<A> SkillList<A> {
+    fn new(planner_path: A) -> Self {
+        Self {
+            planner_path,
+            elements: Vec::new(),
+        }
+    }
+}
+
+impl<A> Element<ClientState> for SkillList<A>
+where
+    A: Path<ClientState, SkillPlanner>,
+{
+    type LayoutInfo = ();
+
+    fn create_layout_info(
+        &mut self,
+        state: &Context<ClientState>,
+        mut store: ElementStoreMut<'_>,
+        resolver: &mut Resolver<'_, ClientState>,
+    ) -> Self::LayoutInfo {
+        use korangar_interface::prelude::*;
+
+        let skill_count = state.get(&self.planner_path.skills()).len();
+
+        match skill_count.cmp(&self.elements.len()) {
+            Ordering::Less => {
+                self.elements.truncate(skill_count);
+            }
+            Ordering::Equal => {}
+            Ordering::Greater => {
+                for index in self.elements.len()..skill_count {
+                    let planner_path = self.planner_path;
+                    let skill_path = planner_path.skills().index(index).manually_asserted();
+
+                    self.elements.push(ErasedElement::new(split! {
+                        gaps: theme().window().gaps(),
+                        children: (
+                            text! {
+                                text: skill_path.text(),
+                                overflow_behavior: OverflowBehavior::Shrink,
+                            },
+                            button! {
+                                text: "-",
+                                event: move |state: &Context<ClientState>, _: &mut EventQueue<ClientState>| {
+                                    state.update_value_with(planner_path, move |planner| planner.lower(index));
+                                },
+                            },
+                            button! {
+                                text: "+",
+                                event: move |state: &Context<ClientState>, _: &mut EventQueue<ClientState>| {
+                                    state.update_value_with(planner_path, move |planner| planner.raise(index));
+                                },
+                            },
+                        ),
+                    }));
+                }
+            }
+        }
+
+        self.elements.iter_mut().enumerate().for_each(|(index, element)| {
+            element.create_layout_info(state, store.child_store(index as u64), resolver);
+        });
+    }
+
+    fn lay_out<'a>(
+        &'a self,
+        state: &'a Context<ClientState>,
+        store: ElementStore<'a>,
+        _: &'a Self::LayoutInfo,
+        layout: &mut WindowLayout<'a, ClientState>,
+    ) {
+        self.elements.iter().enumerate().for_each(|(index, element)| {
+            element.lay_out(state, store.child_store(index as u64), &(), layout);
+        });
+    }
+}
+
+pub struct SkillPlannerWindow<A> {
+    planner_path: A,
+}
+
+impl<A> SkillPlannerWindow<A> {
+    pub fn new(planner_path: A) -> Self {
+        Self { planner_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for SkillPlannerWindow<A>
+where
+    A: Path<ClientState, SkillPlanner>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::SkillPlanner)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        struct BuildStringTextBox;
+
+        fn stat_row<A>(label: impl Path<ClientState, String>, planner_path: A, stat: PlannerStat) -> impl Element<ClientState>
+        where
+            A: Path<ClientState, SkillPlanner>,
+        {
+            use korangar_interface::prelude::*;
+
+            let value_path = planner_path.calculator().stat_texts().index(stat as usize).manually_asserted();
+
+            split! {
+                gaps: theme().window().gaps(),
+                children: (
+                    text! {
+                        text: label,
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    text! {
+                        text: value_path,
+                        color: VALUE_COLOR,
+                        horizontal_alignment: HorizontalAlignment::Right { offset: 0.0, border: 3.0 },
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    button! {
+                        text: "-",
+                        event: move |state: &Context<ClientState>, _: &mut EventQueue<ClientState>| {
+                            state.update_value_with(planner_path, move |planner| planner.adjust_stat(stat, false));
+                        },
+                    },
+                    button! {
+                        text: "+",
+                        event: move |state: &Context<ClientState>, _: &mut EventQueue<ClientState>| {
+                            state.update_value_with(planner_path, move |planner| planner.adjust_stat(stat, true));
+                        },
+                    },
+                ),
+            }
+        }
+
+        fn result_row(label: impl Path<ClientState, String>, value: impl Path<ClientState, String>) -> impl Element<ClientState> {
+            use korangar_interface::prelude::*;
+
+            split! {
+                children: (
+                    text! {
+                        text: label,
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                    text! {
+                        text: value,
+                        color: VALUE_COLOR,
+                        horizontal_alignment: HorizontalAlignment::Right { offset: 0.0, border: 3.0 },
+                        overflow_behavior: OverflowBehavior::Shrink,
+                    },
+                ),
+            }
+        }
+
+        let import_action = |_: &Context<ClientState>, queue: &mut EventQueue<ClientState>| {
+            queue.queue(InputEvent::ImportSkillBuild);
+        };
+
+        window! {
+            title: client_state().localization().skill_planner_window_title(),
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            minimum_width: 340.0,
+            elements: (
+                collapsable! {
+                    text: self.planner_path.job_text(),
+                    children: (
+                        JobList::new(self.planner_path),
+                    ),
+                },
+                split! {
+                    children: (
+                        text! {
+                            text: client_state().localization().skill_points_text(),
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                        text! {
+                            text: self.planner_path.points_text(),
+                            color: VALUE_COLOR,
+                            horizontal_alignment: HorizontalAlignment::Right { offset: 0.0, border: 3.0 },
+                            overflow_behavior: OverflowBehavior::Shrink,
+                        },
+                    ),
+                },
+                scroll_view! {
+                    children: (
+                        SkillList::new(self.planner_path),
+                    ),
+                },
+                collapsable! {
+                    text: client_state().localization().stat_calculator_text(),
+                    children: (
+                        stat_row(client_state().localization().base_level_text(), self.planner_path, PlannerStat::BaseLevel),
+                        stat_row(client_state().localization().strength_text(), self.planner_path, PlannerStat::Strength),
+                        stat_row(client_state().localization().agility_text(), self.planner_path, PlannerStat::Agility),
+                        stat_row(client_state().localization().vitality_text(), self.planner_path, PlannerStat::Vitality),
+                        stat_row(client_state().localization().intelligence_text(), self.planner_path, PlannerStat::Intelligence),
+                        stat_row(client_state().localization().dexterity_text(), self.planner_path, PlannerStat::Dexterity),
+                        stat_row(client_state().localization().luck_text(), self.planner_path, PlannerStat::Luck),
+                        stat_row(client_state().localization().weapon_attack_text(), self.planner_path, PlannerStat::WeaponAttack),
+                        stat_row(client_state().localization().equipment_attack_text(), self.planner_path, PlannerStat::EquipmentAttack),
+                        stat_row(client_state().localization().gear_magic_attack_text(), self.planner_path, PlannerStat::MagicAttack),
+                        stat_row(client_state().localization().weapon_aspd_text(), self.planner_path, PlannerStat::BaseAspd),
+                        fragment! {
+                            gaps: 4.0,
+                            children: (
+                                result_row(client_state().localization().attack_text(), self.planner_path.calculator().attack_text()),
+                                result_row(client_state().localization().magic_attack_text(), self.planner_path.calculator().magic_attack_text()),
+                                result_row(client_state().localization().hit_text(), self.planner_path.calculator().hit_text()),
+                                result_row(client_state().localization().flee_text(), self.planner_path.calculator().flee_text()),
+                                result_row(client_state().localization().aspd_text(), self.planner_path.calculator().aspd_text()),
+                            ),
+                        },
+                        text! {
+                            text: client_state().localization().skill_damage_text(),
+                        },
+                        text! {
+                            text: self.planner_path.calculator().skill_damage_text(),
+                            color: VALUE_COLOR,
+                        },
+                    ),
+                },
+                button! {
+                    text: client_state().localization().reset_button_text(),
+                    event: move |state: &Context<ClientState>, _: &mut EventQueue<ClientState>| {
+                        state.update_value_with(self.planner_path, |planner| planner.reset());
+                    },
+                },
+                text_box! {
+                    ghost_text: client_state().localization().build_string_text_box_message(),
+                    state: self.planner_path.build_string(),
+                    input_handler: DefaultHandler::<_, _, MAXIMUM_BUILD_STRING_LENGTH>::new(self.planner_path.build_string(), import_action),
+                    focus_id: BuildStringTextBox,
+                    overflow_behavior: OverflowBehavior::Shrink,
+                },
+                split! {
+                    children: (
+                        button! {
+                            text: client_state().localization().export_build_button_text(),
+                            event: move |state: &Context<ClientState>, _: &mut EventQueue<ClientState>| {
+                                state.update_value_with(self.planner_path, |planner| planner.export_build());
+                            },
+                        },
+                        button! {
+                            text: client_state().localization().import_build_button_text(),
+                            event: InputEvent::ImportSkillBuild,
+                        },
+                    ),
+                },
+            ),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use korangar_interface::element::StateElement;
+use korangar_interface::window::{CustomWindow, Window};
+use rust_state::{Context, Path};
+
+use crate::graphics::{SpriteDepthBias, SpriteDepthBiasPathExt, SpriteDepthBiasSettings, SpriteDepthBiasSettingsPathExt};
+use crate::interface::windows::WindowClass;
+use crate::state::ClientState;
+use crate::state::theme::InterfaceThemeType;
+
+pub struct SpriteDepthBiasWindow<A> {
+    sprite_depth_bias_path: A,
+}
+
+impl<A> SpriteDepthBiasWindow<A> {
+    pub fn new(sprite_depth_bias_path: A) -> Self {
+        Self { sprite_depth_bias_path }
+    }
+}
+
+impl<A> CustomWindow<ClientState> for SpriteDepthBiasWindow<A>
+where
+    A: Path<ClientState, SpriteDepthBiasSettings>,
+{
+    fn window_class() -> Option<WindowClass> {
+        Some(WindowClass::SpriteDepthBias)
+    }
+
+    fn to_window<'a>(self) -> impl Window<ClientState> + 'a {
+        use korangar_interface::prelude::*;
+
+        fn tuning_row(value_path: impl Path<ClientState, f32>, name: &'static str, step: f32) -> impl Element<ClientState> {
+            use korangar_interface::prelude::*;
+
+            split! {
+                gaps: theme().window().gaps(),
+                children: (
+                    StateElement::to_element(value_path, name.to_owned()),
+                    button! {
+                        text: "-",
+                        event: move |state: &Context<ClientState>, _: &mut EventQueue<ClientState>| {
+                            state.update_value_with(value_path, move |value| *value -= step);
+                        },
+                    },
+                    button! {
+                        text: "+",
+                        event: move |state: &Context<ClientState>, _: &mut EventQueue<ClientState>| {
+                            state.update_value_with(value_path, move |value| *value += step);
+                        },
+                    },
+                ),
+            }
+        }
+
+        fn camera_mode(depth_bias_path: impl Path<ClientState, SpriteDepthBias>, name: &'static str) -> impl Element<ClientState> {
+            use korangar_interface::prelude::*;
+
+            collapsable! {
+                text: name,
+                initially_expanded: true,
+                children: (
+                    tuning_row(depth_bias_path.offset_factor(), "Offset factor", SpriteDepthBias::OFFSET_FACTOR_STEP),
+                    tuning_row(depth_bias_path.curvature_factor(), "Curvature factor", SpriteDepthBias::CURVATURE_FACTOR_STEP),
+                    tuning_row(depth_bias_path.slope_factor(), "Slope factor", SpriteDepthBias::SLOPE_FACTOR_STEP),
+                    button! {
+                        text: "Reset",
+                        event: move |state: &Context<ClientState>, _: &mut EventQueue<ClientState>| {
+                            state.update_value(depth_bias_path, SpriteDepthBias::default());
+                        },
+                    },
+                ),
+            }
+        }
+
+        window! {
+            title: "Sprite depth bias",
+            class: Self::window_class(),
+            theme: InterfaceThemeType::InGame,
+            closable: true,
+            minimum_width: 300.0,
+            elements: (
+                camera_mode(self.sprite_depth_bias_path.player(), "Player camera"),
+                camera_mode(self.sprite_depth_bias_path.start(), "Start camera"),
+                camera_mode(self.sprite_depth_bias_path.debug(), "Debug camera"),
+            ),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+/// Amount of equipped ammunition below which the player is warned.
+const LOW_AMMUNITION_THRESHOLD: u16 = 30;
+
+/// Warning shown when the equipped ammunition is running out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmmunitionWarning {
+    Low { remaining: u16 },
+    Empty,
+}
+
+impl AmmunitionWarning {
+    /// The warning to show after the equipped ammunition went from `previous`
+    /// to `remaining`. Every warning is only shown once, when the amount
+    /// crosses its threshold.
+    pub fn new(previous: u16, remaining: u16) -> Option<Self> {
+        if remaining == 0 && previous > 0 {
+            return Some(AmmunitionWarning::Empty);
+        }
+
+        (remaining < LOW_AMMUNITION_THRESHOLD && previous >= LOW_AMMUNITION_THRESHOLD).then_some(AmmunitionWarning::Low { remaining })
+    }
+}
+
+impl std::fmt::Display for AmmunitionWarning {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AmmunitionWarning::Low { remaining } => write!(formatter, "Running low on ammunition ({remaining} left)"),
+            AmmunitionWarning::Empty => write!(formatter, "Out of ammunition"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AmmunitionWarning, LOW_AMMUNITION_THRESHOLD};
+
+    #[test]
+    fn warnings_are_shown_when_crossing_the_threshold() {
+        assert_eq!(AmmunitionWarning::new(100, 99), None);
+        assert_eq!(
+            AmmunitionWarning::new(LOW_AMMUNITION_THRESHOLD, LOW_AMMUNITION_THRESHOLD - 1),
+            Some(AmmunitionWarning::Low {
+                remaining: LOW_AMMUNITION_THRESHOLD - 1
+            })
+        );
+        assert_eq!(
+            AmmunitionWarning::new(LOW_AMMUNITION_THRESHOLD - 1, LOW_AMMUNITION_THRESHOLD - 2),
+            None
+        );
+        assert_eq!(AmmunitionWarning::new(1, 0), Some(AmmunitionWarning::Empty));
+        assert_eq!(AmmunitionWarning::new(100, 0), Some(AmmunitionWarning::Empty));
+    }
+}
@@ -0,0 +1,145 @@
+use korangar_networking::InventoryItem;
+use ragnarok_packets::{EquipPosition, InventoryIndex, ItemId};
+use serde::{Deserialize, Serialize};
+
+/// Item of a gear set and the position it was equipped at. The position is
+/// stored as well, so accessories go back on the same side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GearSetItem {
+    pub item_id: u32,
+    pub equipped_position: u32,
+}
+
+/// Named selection of equipment that can be equipped with a single action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GearSet {
+    pub name: String,
+    pub items: Vec<GearSetItem>,
+}
+
+/// Request to equip a single item of a [`GearSet`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EquipRequest {
+    pub index: InventoryIndex,
+    pub position: EquipPosition,
+}
+
+impl GearSet {
+    /// Create a gear set from everything the player has equipped right now.
+    pub fn from_equipped<Meta>(name: String, items: &[InventoryItem<Meta>]) -> Self {
+        let items = items
+            .iter()
+            .filter(|item| item.equipped_position() != EquipPosition::NONE)
+            .map(|item| GearSetItem {
+                item_id: item.item_id.0,
+                equipped_position: item.equipped_position().bits(),
+            })
+            .collect();
+
+        Self { name, items }
+    }
+
+    /// Equip requests needed to put on the gear set, in the order they should
+    /// be sent. Items that are already equipped in the right position are
+    /// skipped, as are items that are no longer in the inventory.
+    pub fn equip_requests<Meta>(&self, items: &[InventoryItem<Meta>]) -> Vec<EquipRequest> {
+        let mut used_indices = Vec::new();
+        let mut missing_items = Vec::new();
+
+        for gear_set_item in &self.items {
+            let item_id = ItemId(gear_set_item.item_id);
+            let position = EquipPosition::from_bits_retain(gear_set_item.equipped_position);
+
+            match items
+                .iter()
+                .find(|item| item.item_id == item_id && item.equipped_position() == position && !used_indices.contains(&item.index))
+            {
+                Some(item) => used_indices.push(item.index),
+                None => missing_items.push((item_id, position)),
+            }
+        }
+
+        let mut requests = Vec::new();
+
+        for (item_id, position) in missing_items {
+            let used = &used_indices;
+            let candidates = move || {
+                items
+                    .iter()
+                    .filter(move |item| item.item_id == item_id && !used.contains(&item.index))
+            };
+
+            // Prefer items that are not equipped, so two identical accessories
+            // don't take each others place.
+            let item = candidates()
+                .find(|item| item.equipped_position() == EquipPosition::NONE)
+                .or_else(|| candidates().next());
+
+            if let Some(item) = item {
+                let index = item.index;
+
+                used_indices.push(index);
+                requests.push(EquipRequest { index, position });
+            }
+        }
+
+        requests
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use korangar_networking::InventoryItem;
+    use ragnarok_packets::{EquipPosition, InventoryIndex};
+
+    use super::{EquipRequest, GearSet};
+    use crate::test_fixtures::equippable_item;
+
+    fn equippable(index: u16, item_id: u32, equipped_position: EquipPosition) -> InventoryItem<()> {
+        equippable_item((), index, item_id, 4, EquipPosition::LEFT_RIGHT_ACCESSORY, equipped_position)
+    }
+
+    #[test]
+    fn saved_set_contains_equipped_items() {
+        let items = vec![
+            equippable(2, 2601, EquipPosition::LEFT_ACCESSORY),
+            equippable(3, 2602, EquipPosition::NONE),
+        ];
+        let gear_set = GearSet::from_equipped("Ring".to_owned(), &items);
+
+        assert_eq!(gear_set.items.len(), 1);
+        assert_eq!(gear_set.items[0].item_id, 2601);
+        assert_eq!(gear_set.items[0].equipped_position, EquipPosition::LEFT_ACCESSORY.bits());
+    }
+
+    #[test]
+    fn equipped_items_are_skipped() {
+        let items = vec![
+            equippable(2, 2601, EquipPosition::LEFT_ACCESSORY),
+            equippable(3, 2602, EquipPosition::NONE),
+        ];
+        let gear_set = GearSet::from_equipped("Current".to_owned(), &items);
+
+        assert!(gear_set.equip_requests(&items).is_empty());
+    }
+
+    #[test]
+    fn identical_items_fill_both_positions() {
+        let saved = vec![
+            equippable(2, 2601, EquipPosition::LEFT_ACCESSORY),
+            equippable(3, 2601, EquipPosition::RIGTH_ACCESSORY),
+        ];
+        let gear_set = GearSet::from_equipped("Rings".to_owned(), &saved);
+
+        let items = vec![
+            equippable(2, 2601, EquipPosition::RIGTH_ACCESSORY),
+            equippable(3, 2601, EquipPosition::NONE),
+            equippable(4, 1201, EquipPosition::NONE),
+        ];
+
+        assert_eq!(gear_set.equip_requests(&items), vec![EquipRequest {
+            index: InventoryIndex(3),
+            position: EquipPosition::LEFT_ACCESSORY,
+        }]);
+    }
+}
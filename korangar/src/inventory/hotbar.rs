@@ -1,20 +1,72 @@
+use std::sync::Arc;
+
 use korangar_interface::element::StateElement;
-use korangar_networking::NetworkingSystem;
+use korangar_networking::{InventoryItem, InventoryItemDetails, NetworkingSystem};
 use ragnarok_packets::handler::PacketCallback;
-use ragnarok_packets::{HotbarSlot, HotbarTab, HotkeyData};
+use ragnarok_packets::{HotbarSlot, HotbarTab, HotkeyData, ItemId, SkillLevel};
 use rust_state::RustState;
 
 use super::Skill;
+use crate::graphics::Texture;
+use crate::world::ResourceMetadata;
+
+/// Consumable placed on the hotbar. The amount is the total amount of the item
+/// in the inventory, so it can reach zero while the slot is kept.
+#[derive(Clone)]
+pub struct HotbarItem {
+    pub item_id: ItemId,
+    pub amount: u16,
+    pub texture: Option<Arc<Texture>>,
+}
+
+impl HotbarItem {
+    fn new(item_id: ItemId) -> Self {
+        Self {
+            item_id,
+            amount: 0,
+            texture: None,
+        }
+    }
+}
 
 #[derive(Default, RustState, StateElement)]
 pub struct Hotbar {
     skills: [Option<Skill>; 10],
+    #[hidden_element]
+    items: [Option<HotbarItem>; 10],
+}
+
+fn skill_hotkey_data(skill: &Skill) -> HotkeyData {
+    HotkeyData {
+        is_skill: true as u8,
+        skill_id: skill.skill_id.0 as u32,
+        quantity_or_skill_level: skill.skill_level,
+    }
+}
+
+fn item_hotkey_data(item: &HotbarItem) -> HotkeyData {
+    HotkeyData {
+        is_skill: false as u8,
+        skill_id: item.item_id.0,
+        quantity_or_skill_level: SkillLevel(0),
+    }
 }
 
 impl Hotbar {
+    fn hotkey_data(&self, slot: HotbarSlot) -> HotkeyData {
+        let index = slot.0 as usize;
+
+        match (&self.skills[index], &self.items[index]) {
+            (Some(skill), _) => skill_hotkey_data(skill),
+            (None, Some(item)) => item_hotkey_data(item),
+            (None, None) => HotkeyData::UNBOUND,
+        }
+    }
+
     /// Set the slot without notifying the map server.
     pub fn set_slot(&mut self, slot: HotbarSlot, skill: Skill) {
         self.skills[slot.0 as usize] = Some(skill);
+        self.items[slot.0 as usize] = None;
     }
 
     /// Update the slot and notify the map server.
@@ -22,13 +74,30 @@ impl Hotbar {
     where
         Callback: PacketCallback + Send,
     {
-        let _ = networking_system.set_hotkey_data(HotbarTab(0), slot, HotkeyData {
-            is_skill: true as u8,
-            skill_id: skill.skill_id.0 as u32,
-            quantity_or_skill_level: skill.skill_level,
-        });
+        let _ = networking_system.set_hotkey_data(HotbarTab(0), slot, skill_hotkey_data(&skill));
 
         self.skills[slot.0 as usize] = Some(skill);
+        self.items[slot.0 as usize] = None;
+    }
+
+    /// Put an item in the slot without notifying the map server. The amount
+    /// is filled in by the next call to [`Self::sync_items`].
+    pub fn set_item_slot(&mut self, slot: HotbarSlot, item_id: ItemId) {
+        self.skills[slot.0 as usize] = None;
+        self.items[slot.0 as usize] = Some(HotbarItem::new(item_id));
+    }
+
+    /// Put an item in the slot and notify the map server. The amount is
+    /// filled in by the next call to [`Self::sync_items`].
+    pub fn update_item_slot<Callback>(&mut self, networking_system: &mut NetworkingSystem<Callback>, slot: HotbarSlot, item_id: ItemId)
+    where
+        Callback: PacketCallback + Send,
+    {
+        let item = HotbarItem::new(item_id);
+        let _ = networking_system.set_hotkey_data(HotbarTab(0), slot, item_hotkey_data(&item));
+
+        self.skills[slot.0 as usize] = None;
+        self.items[slot.0 as usize] = Some(item);
     }
 
     /// Swap two slots in the hotbar and notify the map server.
@@ -41,38 +110,21 @@ impl Hotbar {
         Callback: PacketCallback + Send,
     {
         if source_slot != destination_slot {
-            let first = self.skills[source_slot.0 as usize].take();
-            let second = self.skills[destination_slot.0 as usize].take();
-
-            let first_data = first
-                .as_ref()
-                .map(|skill| HotkeyData {
-                    is_skill: true as u8,
-                    skill_id: skill.skill_id.0 as u32,
-                    quantity_or_skill_level: skill.skill_level,
-                })
-                .unwrap_or(HotkeyData::UNBOUND);
-
-            let second_data = second
-                .as_ref()
-                .map(|skill| HotkeyData {
-                    is_skill: true as u8,
-                    skill_id: skill.skill_id.0 as u32,
-                    quantity_or_skill_level: skill.skill_level,
-                })
-                .unwrap_or(HotkeyData::UNBOUND);
-
-            let _ = networking_system.set_hotkey_data(HotbarTab(0), destination_slot, first_data);
-            let _ = networking_system.set_hotkey_data(HotbarTab(0), source_slot, second_data);
-
-            self.skills[source_slot.0 as usize] = second;
-            self.skills[destination_slot.0 as usize] = first;
+            let source_index = source_slot.0 as usize;
+            let destination_index = destination_slot.0 as usize;
+
+            self.skills.swap(source_index, destination_index);
+            self.items.swap(source_index, destination_index);
+
+            let _ = networking_system.set_hotkey_data(HotbarTab(0), destination_slot, self.hotkey_data(destination_slot));
+            let _ = networking_system.set_hotkey_data(HotbarTab(0), source_slot, self.hotkey_data(source_slot));
         }
     }
 
     /// Clear the slot without notifying the map server.
     pub fn unset_slot(&mut self, slot: HotbarSlot) {
         self.skills[slot.0 as usize] = None;
+        self.items[slot.0 as usize] = None;
     }
 
     /// Clear the slot and notify the map server.
@@ -83,9 +135,35 @@ impl Hotbar {
         let _ = networking_system.set_hotkey_data(HotbarTab(0), slot, HotkeyData::UNBOUND);
 
         self.skills[slot.0 as usize] = None;
+        self.items[slot.0 as usize] = None;
     }
 
     pub fn get_skill_in_slot(&self, slot: HotbarSlot) -> &Option<Skill> {
         &self.skills[slot.0 as usize]
     }
+
+    pub fn get_item_in_slot(&self, slot: HotbarSlot) -> &Option<HotbarItem> {
+        &self.items[slot.0 as usize]
+    }
+
+    /// Update the amounts and icons of all items on the hotbar from the
+    /// inventory. Needs to be called whenever the inventory changes.
+    pub fn sync_items(&mut self, inventory_items: &[InventoryItem<ResourceMetadata>]) {
+        for item in self.items.iter_mut().flatten() {
+            item.amount = 0;
+
+            for inventory_item in inventory_items
+                .iter()
+                .filter(|inventory_item| inventory_item.item_id == item.item_id)
+            {
+                if let InventoryItemDetails::Regular { amount, .. } = &inventory_item.details {
+                    item.amount += amount;
+                }
+
+                if item.texture.is_none() {
+                    item.texture = inventory_item.metadata.texture.clone();
+                }
+            }
+        }
+    }
 }
@@ -1,24 +1,59 @@
+mod ammunition;
+mod gear_set;
 mod hotbar;
+mod shop;
 mod skills;
+mod view;
 
 use std::sync::Arc;
 
 use korangar_interface::element::StateElement;
 use korangar_networking::{InventoryItem, InventoryItemDetails, NoMetadata};
-use ragnarok_packets::{EquipPosition, InventoryIndex, ItemId};
+use ragnarok_packets::{EquipPosition, InventoryIndex};
 use rust_state::RustState;
 
-pub use self::hotbar::{Hotbar, HotbarPathExt};
+pub use self::ammunition::AmmunitionWarning;
+pub use self::gear_set::{EquipRequest, GearSet};
+pub use self::hotbar::{Hotbar, HotbarItem, HotbarPathExt};
+pub use self::shop::{CartSummary, PurchaseError, SellWarning};
 pub use self::skills::{Skill, SkillTree, SkillTreePathExt};
+pub use self::view::InventoryFilter;
+use self::view::InventorySorting;
 use crate::graphics::Texture;
 use crate::loaders::AsyncLoader;
 use crate::world::{Library, ResourceMetadata};
 
-#[derive(Default, RustState, StateElement)]
+#[derive(RustState, StateElement)]
 pub struct Inventory {
     // TODO: Unhide this.
     #[hidden_element]
     items: Vec<InventoryItem<ResourceMetadata>>,
+    filter: InventoryFilter,
+    sorting: InventorySorting,
+    sorting_text: String,
+    search: String,
+    /// Show the items as a grid of icons instead of a list with names.
+    compact: bool,
+    /// Positions in `items` of the items that are displayed, in display order.
+    #[hidden_element]
+    visible_items: Vec<usize>,
+    /// Name under which the equipped items are saved as a gear set.
+    gear_set_name: String,
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            filter: InventoryFilter::default(),
+            sorting: InventorySorting::default(),
+            sorting_text: InventorySorting::default().text().to_owned(),
+            search: String::new(),
+            compact: true,
+            visible_items: Vec::new(),
+            gear_set_name: String::new(),
+        }
+    }
 }
 
 impl Inventory {
@@ -27,6 +62,55 @@ impl Inventory {
             .into_iter()
             .map(|item| library.load_inventory_item_metadata(async_loader, item))
             .collect();
+
+        self.update_visible_items();
+    }
+
+    /// Recalculate which items are displayed. Needs to be called whenever the
+    /// items, the filter, the sorting, or the search change.
+    pub fn update_visible_items(&mut self) {
+        self.visible_items = self::view::visible_positions(&self.items, self.filter, self.sorting, &self.search, |metadata| {
+            metadata.name.as_str()
+        });
+    }
+
+    pub fn set_filter(&mut self, filter: InventoryFilter) {
+        self.filter = filter;
+        self.update_visible_items();
+    }
+
+    pub fn cycle_sorting(&mut self) {
+        self.sorting = self.sorting.next();
+        self.sorting_text = self.sorting.text().to_owned();
+        self.update_visible_items();
+    }
+
+    pub fn get_filter(&self) -> InventoryFilter {
+        self.filter
+    }
+
+    pub fn is_compact(&self) -> bool {
+        self.compact
+    }
+
+    pub fn visible_item_count(&self) -> usize {
+        self.visible_items.len()
+    }
+
+    /// Item at a position of the inventory window. The item keeps its
+    /// [`InventoryIndex`], so moving it works the same no matter how the
+    /// inventory is sorted.
+    pub fn visible_item(&self, position: usize) -> Option<&InventoryItem<ResourceMetadata>> {
+        self.visible_items.get(position).map(|&position| &self.items[position])
+    }
+
+    pub fn visible_item_mut(&mut self, position: usize) -> Option<&mut InventoryItem<ResourceMetadata>> {
+        self.visible_items.get(position).map(|&position| &mut self.items[position])
+    }
+
+    /// Take the entered gear set name, leaving the text box empty.
+    pub fn take_gear_set_name(&mut self) -> String {
+        std::mem::take(&mut self.gear_set_name).trim().to_owned()
     }
 
     pub fn add_item(&mut self, async_loader: &AsyncLoader, library: &Library, item: InventoryItem<NoMetadata>) {
@@ -45,12 +129,17 @@ impl Inventory {
 
             self.items.push(item);
         }
+
+        self.update_visible_items();
     }
 
-    pub fn update_item_sprite(&mut self, item_id: ItemId, texture: Arc<Texture>) {
-        self.items.iter_mut().filter(|item| item.item_id == item_id).for_each(|item| {
-            item.metadata.texture = Some(texture.clone());
-        });
+    pub fn update_item_icon(&mut self, icon_path: &str, texture: Arc<Texture>) {
+        self.items
+            .iter_mut()
+            .filter(|item| item.metadata.icon_path == icon_path)
+            .for_each(|item| {
+                item.metadata.texture = Some(texture.clone());
+            });
     }
 
     pub fn remove_item(&mut self, index: InventoryIndex, remove_amount: u16) {
@@ -68,16 +157,51 @@ impl Inventory {
         }
 
         self.items.remove(position);
+        self.update_visible_items();
+    }
+
+    /// Set the amount of a stackable item, removing it if none are left.
+    pub fn set_item_amount(&mut self, index: InventoryIndex, new_amount: u16) {
+        let Some(position) = self.items.iter().position(|item| item.index == index) else {
+            return;
+        };
+
+        match &mut self.items[position].details {
+            InventoryItemDetails::Regular { amount, .. } if new_amount > 0 => *amount = new_amount,
+            _ => {
+                self.items.remove(position);
+                self.update_visible_items();
+            }
+        }
     }
 
     pub fn update_equipped_position(&mut self, index: InventoryIndex, new_equipped_position: EquipPosition) {
         let item = self.items.iter_mut().find(|item| item.index == index).unwrap();
 
-        let InventoryItemDetails::Equippable { equipped_position, .. } = &mut item.details else {
-            // This can happen for ammunition for example.
-            return;
-        };
+        match &mut item.details {
+            InventoryItemDetails::Regular { equipped_position, .. } | InventoryItemDetails::Equippable { equipped_position, .. } => {
+                *equipped_position = new_equipped_position;
+            }
+        }
+    }
+
+    /// Mark the item as the equipped ammunition. Only one stack of ammunition
+    /// can be equipped at a time.
+    pub fn equip_ammunition(&mut self, index: InventoryIndex) {
+        for item in &mut self.items {
+            if let InventoryItemDetails::Regular { equipped_position, .. } = &mut item.details {
+                equipped_position.set(EquipPosition::AMMO, item.index == index);
+            }
+        }
+    }
 
-        *equipped_position = new_equipped_position;
+    /// Index and amount of the equipped ammunition.
+    pub fn equipped_ammunition(&self) -> Option<(InventoryIndex, u16)> {
+        self.items.iter().find_map(|item| match &item.details {
+            InventoryItemDetails::Regular {
+                amount, equipped_position, ..
+            } if equipped_position.contains(EquipPosition::AMMO) => Some((item.index, *amount)),
+            _ => None,
+        })
     }
 }
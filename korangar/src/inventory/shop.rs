@@ -0,0 +1,215 @@
+use std::fmt::{Display, Formatter};
+
+use korangar_networking::{InventoryItem, InventoryItemDetails, ShopItem};
+
+use crate::world::Player;
+
+/// Reason why the items in the cart can't be bought. Checked on the client,
+/// so the server is never asked to perform a purchase that will fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PurchaseError {
+    EmptyCart,
+    NotEnoughZeny {
+        price: u64,
+        zeny: u32,
+    },
+    /// Weights are given in tenths, like the server sends them.
+    Overweight {
+        weight: u64,
+        free_weight: u32,
+    },
+}
+
+impl Display for PurchaseError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PurchaseError::EmptyCart => write!(formatter, "The cart is empty"),
+            PurchaseError::NotEnoughZeny { price, zeny } => {
+                write!(
+                    formatter,
+                    "Not enough zeny: the items cost {price} z, but you only have {zeny} z"
+                )
+            }
+            PurchaseError::Overweight { weight, free_weight } => write!(
+                formatter,
+                "Too heavy: the items weigh {}, but you can only carry {} more",
+                weight / 10,
+                free_weight / 10
+            ),
+        }
+    }
+}
+
+/// Total price and weight of the items in a cart.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CartSummary {
+    pub item_count: u64,
+    pub price: u64,
+    /// Weight in tenths, like the server sends it.
+    pub weight: u64,
+}
+
+impl CartSummary {
+    pub fn new<'a, Meta: 'a>(cart: impl IntoIterator<Item = (&'a ShopItem<Meta>, u32)>) -> Self {
+        cart.into_iter()
+            .fold(Self::default(), |summary, (item, amount)| summary.with(item, amount))
+    }
+
+    /// The summary after adding `amount` more of `item` to the cart.
+    pub fn with<Meta>(self, item: &ShopItem<Meta>, amount: u32) -> Self {
+        let amount = amount as u64;
+
+        Self {
+            item_count: self.item_count + amount,
+            price: self.price + item.price.0 as u64 * amount,
+            weight: self.weight + item.weight as u64 * amount,
+        }
+    }
+
+    /// Check if the player can afford and carry the items.
+    pub fn check(&self, zeny: u32, weight: u32, maximum_weight: u32) -> Result<(), PurchaseError> {
+        let free_weight = maximum_weight.saturating_sub(weight);
+
+        if self.item_count == 0 {
+            return Err(PurchaseError::EmptyCart);
+        }
+
+        if self.price > zeny as u64 {
+            return Err(PurchaseError::NotEnoughZeny { price: self.price, zeny });
+        }
+
+        if self.weight > free_weight as u64 {
+            return Err(PurchaseError::Overweight {
+                weight: self.weight,
+                free_weight,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn check_for_player(&self, player: &Player) -> Result<(), PurchaseError> {
+        self.check(player.zeny, player.weight, player.maximum_weight)
+    }
+}
+
+/// Reason why an item should not be sold or dropped without asking the player
+/// first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SellWarning {
+    Locked,
+    Refined { refinement_level: u8 },
+    Carded,
+}
+
+impl Display for SellWarning {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SellWarning::Locked => write!(formatter, "locked"),
+            SellWarning::Refined { refinement_level } => write!(formatter, "refined to +{refinement_level}"),
+            SellWarning::Carded => write!(formatter, "has cards"),
+        }
+    }
+}
+
+impl SellWarning {
+    pub fn for_item<Meta>(item: &InventoryItem<Meta>, is_locked: bool) -> Option<Self> {
+        if is_locked {
+            return Some(SellWarning::Locked);
+        }
+
+        let InventoryItemDetails::Equippable { refinement_level, .. } = item.details else {
+            return None;
+        };
+
+        if refinement_level > 0 {
+            return Some(SellWarning::Refined { refinement_level });
+        }
+
+        item.cards().next().map(|_| SellWarning::Carded)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use korangar_networking::{InventoryItem, InventoryItemDetails, ItemQuantity, ShopItem};
+    use ragnarok_packets::{EquipPosition, ItemId, Price};
+
+    use super::{CartSummary, PurchaseError, SellWarning};
+    use crate::test_fixtures::{equippable_item, regular_item};
+
+    fn item(price: u32, weight: u16) -> ShopItem<()> {
+        ShopItem {
+            metadata: (),
+            item_id: ItemId(501),
+            item_type: 0,
+            price: Price(price),
+            quantity: ItemQuantity::Infinite,
+            weight,
+            location: 0,
+        }
+    }
+
+    #[test]
+    fn totals_multiply_by_amount() {
+        let potion = item(50, 70);
+        let arrow = item(1, 1);
+        let summary = CartSummary::new([(&potion, 3), (&arrow, 100)]);
+
+        assert_eq!(summary, CartSummary {
+            item_count: 103,
+            price: 250,
+            weight: 310,
+        });
+    }
+
+    #[test]
+    fn purchase_is_checked_against_zeny_and_weight() {
+        let potion = item(50, 70);
+        let summary = CartSummary::new([(&potion, 10)]);
+
+        assert_eq!(summary.check(500, 0, 700), Ok(()));
+        assert_eq!(
+            summary.check(499, 0, 700),
+            Err(PurchaseError::NotEnoughZeny { price: 500, zeny: 499 })
+        );
+        assert_eq!(
+            summary.check(500, 100, 700),
+            Err(PurchaseError::Overweight {
+                weight: 700,
+                free_weight: 600
+            })
+        );
+        assert_eq!(CartSummary::default().check(500, 0, 700), Err(PurchaseError::EmptyCart));
+    }
+
+    fn equipment(refinement_level: u8, slot: [u32; 4]) -> InventoryItem<()> {
+        let mut item = equippable_item((), 2, 1201, 5, EquipPosition::RIGHT_HAND, EquipPosition::NONE);
+        item.slot = slot;
+
+        if let InventoryItemDetails::Equippable { refinement_level: level, .. } = &mut item.details {
+            *level = refinement_level;
+        }
+
+        item
+    }
+
+    #[test]
+    fn valuable_items_need_confirmation() {
+        let potion = regular_item((), 3, 501, 0, 10);
+
+        assert_eq!(SellWarning::for_item(&potion, false), None);
+        assert_eq!(SellWarning::for_item(&potion, true), Some(SellWarning::Locked));
+        assert_eq!(SellWarning::for_item(&equipment(0, [0; 4]), false), None);
+        assert_eq!(
+            SellWarning::for_item(&equipment(7, [0; 4]), false),
+            Some(SellWarning::Refined { refinement_level: 7 })
+        );
+        assert_eq!(
+            SellWarning::for_item(&equipment(0, [4001, 0, 0, 0]), false),
+            Some(SellWarning::Carded)
+        );
+        // Forged items store the smith in the slots.
+        assert_eq!(SellWarning::for_item(&equipment(0, [0x00FF, 0, 150000, 1]), false), None);
+    }
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use korangar_interface::element::StateElement;
-use ragnarok_packets::{ClientTick, SkillId, SkillInformation, SkillLevel, SkillType};
+use ragnarok_packets::{AttackRange, ClientTick, SkillId, SkillInformation, SkillLevel, SkillType};
 use rust_state::RustState;
 
 use crate::loaders::{ActionLoader, Sprite, SpriteLoader};
@@ -12,6 +12,7 @@ pub struct Skill {
     pub skill_id: SkillId,
     pub skill_level: SkillLevel,
     pub skill_type: SkillType,
+    pub attack_range: AttackRange,
     pub skill_name: String,
     // TODO: Unhide this
     #[hidden_element]
@@ -22,6 +23,33 @@ pub struct Skill {
     pub animation_state: SpriteAnimationState,
 }
 
+impl Skill {
+    /// Width and height in tiles of the area that a ground skill covers
+    /// around the targeted tile. Skills that are not listed cover a single
+    /// tile.
+    pub fn area_size(&self) -> u16 {
+        match self.skill_id.0 {
+            // Pneuma
+            25 => 3,
+            // Sanctuary
+            70 => 5,
+            // Magnus Exorcismus
+            79 => 7,
+            // Meteor Storm
+            83 => 7,
+            // Lord of Vermilion
+            85 => 9,
+            // Storm Gust
+            89 => 9,
+            // Heaven's Drive
+            91 => 5,
+            // Quagmire
+            92 => 5,
+            _ => 1,
+        }
+    }
+}
+
 #[derive(Default, RustState, StateElement)]
 pub struct SkillTree {
     skills: Vec<Skill>,
@@ -46,6 +74,7 @@ impl SkillTree {
                     skill_id: skill_information.skill_id,
                     skill_level: skill_information.skill_level,
                     skill_type: skill_information.skill_type,
+                    attack_range: skill_information.attack_range,
                     skill_name: skill_information.skill_name,
                     sprite,
                     actions,
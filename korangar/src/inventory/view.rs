@@ -0,0 +1,155 @@
+use std::cmp::Ordering;
+
+use korangar_interface::element::StateElement;
+use korangar_networking::{InventoryItem, InventoryItemDetails};
+use rust_state::RustState;
+
+/// Item types of the map server that are used up when using the item.
+const CONSUMABLE_ITEM_TYPES: [u8; 4] = [0, 2, 11, 18];
+
+/// Tab of the inventory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, RustState, StateElement)]
+pub enum InventoryFilter {
+    #[default]
+    All,
+    Consumables,
+    Equipment,
+    Etc,
+}
+
+impl InventoryFilter {
+    pub fn matches<Meta>(self, item: &InventoryItem<Meta>) -> bool {
+        let is_equippable = matches!(item.details, InventoryItemDetails::Equippable { .. });
+        let is_consumable = CONSUMABLE_ITEM_TYPES.contains(&item.item_type);
+
+        match self {
+            Self::All => true,
+            Self::Consumables => !is_equippable && is_consumable,
+            Self::Equipment => is_equippable,
+            Self::Etc => !is_equippable && !is_consumable,
+        }
+    }
+}
+
+/// Order in which the inventory is displayed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, RustState, StateElement)]
+pub enum InventorySorting {
+    /// The order of the map server, which is the order in which the items were
+    /// picked up.
+    #[default]
+    Index,
+    Type,
+    Id,
+    Name,
+}
+
+impl InventorySorting {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Index => Self::Type,
+            Self::Type => Self::Id,
+            Self::Id => Self::Name,
+            Self::Name => Self::Index,
+        }
+    }
+
+    pub fn text(self) -> &'static str {
+        match self {
+            Self::Index => "Sort: None",
+            Self::Type => "Sort: Type",
+            Self::Id => "Sort: Id",
+            Self::Name => "Sort: Name",
+        }
+    }
+}
+
+/// Positions of the items that pass the filter and the search, in the order
+/// in which they are displayed. Items that compare equal keep the order of the
+/// map server.
+pub fn visible_positions<Meta>(
+    items: &[InventoryItem<Meta>],
+    filter: InventoryFilter,
+    sorting: InventorySorting,
+    search: &str,
+    name: impl Fn(&Meta) -> &str,
+) -> Vec<usize> {
+    let search = search.trim().to_lowercase();
+
+    let mut positions: Vec<usize> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| filter.matches(item))
+        .filter(|(_, item)| search.is_empty() || name(&item.metadata).to_lowercase().contains(&search))
+        .map(|(position, _)| position)
+        .collect();
+
+    positions.sort_by(|&first, &second| {
+        let (first, second) = (&items[first], &items[second]);
+
+        let ordering = match sorting {
+            InventorySorting::Index => Ordering::Equal,
+            InventorySorting::Type => first.item_type.cmp(&second.item_type),
+            InventorySorting::Id => first.item_id.0.cmp(&second.item_id.0),
+            InventorySorting::Name => name(&first.metadata).cmp(name(&second.metadata)),
+        };
+
+        ordering.then(first.index.0.cmp(&second.index.0))
+    });
+
+    positions
+}
+
+#[cfg(test)]
+mod test {
+    use korangar_networking::InventoryItem;
+    use ragnarok_packets::EquipPosition;
+
+    use super::{InventoryFilter, InventorySorting, visible_positions};
+    use crate::test_fixtures::{equippable_item, regular_item};
+
+    fn items() -> Vec<InventoryItem<&'static str>> {
+        vec![
+            regular_item("Jellopy", 4, 909, 3, 1),
+            equippable_item("Knife", 2, 1201, 5, EquipPosition::RIGHT_HAND, EquipPosition::NONE),
+            regular_item("Red Potion", 3, 501, 0, 1),
+            regular_item("Fly Wing", 5, 601, 2, 1),
+        ]
+    }
+
+    #[test]
+    fn filters_select_tabs() {
+        let items = items();
+        let visible = |filter| visible_positions(&items, filter, InventorySorting::Index, "", |name| *name);
+
+        assert_eq!(visible(InventoryFilter::All), vec![1, 2, 0, 3]);
+        assert_eq!(visible(InventoryFilter::Consumables), vec![2, 3]);
+        assert_eq!(visible(InventoryFilter::Equipment), vec![1]);
+        assert_eq!(visible(InventoryFilter::Etc), vec![0]);
+    }
+
+    #[test]
+    fn sorting_orders_positions() {
+        let items = items();
+        let visible = |sorting| visible_positions(&items, InventoryFilter::All, sorting, "", |name| *name);
+
+        assert_eq!(visible(InventorySorting::Type), vec![2, 3, 0, 1]);
+        assert_eq!(visible(InventorySorting::Id), vec![2, 3, 0, 1]);
+        assert_eq!(visible(InventorySorting::Name), vec![3, 0, 1, 2]);
+    }
+
+    #[test]
+    fn search_ignores_case() {
+        let items = items();
+
+        assert_eq!(
+            visible_positions(&items, InventoryFilter::All, InventorySorting::Index, " pOt", |name| *name),
+            vec![2]
+        );
+        assert_eq!(
+            visible_positions(&items, InventoryFilter::Equipment, InventorySorting::Index, "potion", |name| {
+                *name
+            }),
+            Vec::<usize>::new()
+        );
+    }
+}
@@ -2,9 +2,7 @@ use std::num::{NonZeroU32, NonZeroUsize};
 use std::sync::{Arc, Mutex};
 
 use korangar_audio::AudioEngine;
-#[cfg(feature = "debug")]
-use korangar_container::CacheStatistics;
-use korangar_container::SimpleCache;
+use korangar_container::{CacheStatistics, SimpleCache};
 #[cfg(feature = "debug")]
 use korangar_debug::logging::{Colorize, Timer, print_debug};
 use korangar_loaders::FileLoader;
@@ -13,7 +11,7 @@ use ragnarok_formats::action::ActionsData;
 use ragnarok_formats::version::InternalVersion;
 
 use super::error::LoadError;
-use crate::loaders::{FALLBACK_ACTIONS_FILE, GameFileLoader};
+use crate::loaders::{AssetKind, FALLBACK_ACTIONS_FILE, FallbackAudit, GameFileLoader};
 use crate::world::{ActionEvent, Actions};
 
 const MAX_CACHE_COUNT: u32 = 256;
@@ -22,14 +20,20 @@ const MAX_CACHE_SIZE: usize = usize::MAX;
 
 pub struct ActionLoader {
     game_file_loader: Arc<GameFileLoader>,
+    fallback_audit: Arc<FallbackAudit>,
     audio_engine: Arc<AudioEngine<GameFileLoader>>,
     cache: Mutex<SimpleCache<String, Arc<Actions>>>,
 }
 
 impl ActionLoader {
-    pub fn new(game_file_loader: Arc<GameFileLoader>, audio_engine: Arc<AudioEngine<GameFileLoader>>) -> Self {
+    pub fn new(
+        game_file_loader: Arc<GameFileLoader>,
+        fallback_audit: Arc<FallbackAudit>,
+        audio_engine: Arc<AudioEngine<GameFileLoader>>,
+    ) -> Self {
         Self {
             game_file_loader,
+            fallback_audit,
             audio_engine,
             cache: Mutex::new(SimpleCache::new(
                 NonZeroU32::new(MAX_CACHE_COUNT).unwrap(),
@@ -38,24 +42,37 @@ impl ActionLoader {
         }
     }
 
-    #[cfg(feature = "debug")]
     pub fn cache_statistics(&self) -> CacheStatistics {
         self.cache.lock().unwrap().statistics()
     }
 
+    /// Drops all cached actions, so they are loaded again the next time they
+    /// are requested.
+    #[cfg(feature = "debug")]
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Evicts cached actions until the cache holds at most `size` bytes.
+    pub fn shrink_cache(&self, size: usize) {
+        self.cache.lock().unwrap().shrink_to_size(size);
+    }
+
     fn load(&self, path: &str) -> Result<Arc<Actions>, LoadError> {
         #[cfg(feature = "debug")]
         let timer = Timer::new_dynamic(format!("load actions from {}", path.magenta()));
 
         let bytes = match self.game_file_loader.get(&format!("data\\sprite\\{path}")) {
             Ok(bytes) => bytes,
-            Err(_error) => {
+            Err(error) => {
                 #[cfg(feature = "debug")]
                 {
-                    print_debug!("Failed to load actions: {:?}", _error);
+                    print_debug!("Failed to load actions: {:?}", error);
                     print_debug!("Replacing with fallback");
                 }
 
+                self.fallback_audit.record(AssetKind::Actions, path, format!("{error:?}"));
+
                 return self.get_or_load(FALLBACK_ACTIONS_FILE);
             }
         };
@@ -63,13 +80,15 @@ impl ActionLoader {
 
         let actions_data = match ActionsData::from_bytes(&mut byte_reader) {
             Ok(actions_data) => actions_data,
-            Err(_error) => {
+            Err(error) => {
                 #[cfg(feature = "debug")]
                 {
-                    print_debug!("Failed to load actions: {:?}", _error);
+                    print_debug!("Failed to load actions: {:?}", error);
                     print_debug!("Replacing with fallback");
                 }
 
+                self.fallback_audit.record(AssetKind::Actions, path, format!("{error:?}"));
+
                 return self.get_or_load(FALLBACK_ACTIONS_FILE);
             }
         };
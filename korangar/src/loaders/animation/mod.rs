@@ -5,16 +5,14 @@ use std::sync::{Arc, Mutex};
 #[cfg(feature = "debug")]
 use cgmath::SquareMatrix;
 use cgmath::{Array, Matrix4, Rad, Vector2};
-#[cfg(feature = "debug")]
-use korangar_container::CacheStatistics;
-use korangar_container::SimpleCache;
+use korangar_container::{CacheStatistics, SimpleCache};
 #[cfg(feature = "debug")]
 use korangar_debug::logging::{Colorize, print_debug};
 use num::Zero;
 
 use super::error::LoadError;
 use crate::loaders::{ActionLoader, SpriteLoader};
-use crate::world::{ActionEvent, Animation, AnimationData, AnimationFrame, AnimationFramePart, AnimationPair};
+use crate::world::{ActionEvent, Animation, AnimationData, AnimationFrame, AnimationFramePart, AnimationPair, EntityPart, SpriteLayer};
 use crate::{Color, EntityType};
 
 const MAX_CACHE_COUNT: u32 = 256;
@@ -22,7 +20,7 @@ const MAX_CACHE_COUNT: u32 = 256;
 const MAX_CACHE_SIZE: usize = usize::MAX;
 
 pub struct AnimationLoader {
-    cache: Mutex<SimpleCache<Vec<String>, Arc<AnimationData>>>,
+    cache: Mutex<SimpleCache<Vec<EntityPart>, Arc<AnimationData>>>,
 }
 
 impl AnimationLoader {
@@ -35,23 +33,33 @@ impl AnimationLoader {
         }
     }
 
-    #[cfg(feature = "debug")]
     pub fn cache_statistics(&self) -> CacheStatistics {
         self.cache.lock().unwrap().statistics()
     }
 
+    /// Drops all cached animations, so they are built again from the sprites
+    /// the next time they are requested.
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Evicts cached animations until the cache holds at most `size` bytes.
+    pub fn shrink_cache(&self, size: usize) {
+        self.cache.lock().unwrap().shrink_to_size(size);
+    }
+
     pub fn load(
         &self,
         sprite_loader: &SpriteLoader,
         action_loader: &ActionLoader,
         entity_type: EntityType,
-        entity_part_files: &[String],
+        entity_part_files: &[EntityPart],
     ) -> Result<Arc<AnimationData>, LoadError> {
         let animation_pairs: Vec<AnimationPair> = entity_part_files
             .iter()
-            .map(|file_path| AnimationPair {
-                sprites: sprite_loader.get_or_load(&format!("{file_path}.spr")).unwrap(),
-                actions: action_loader.get_or_load(&format!("{file_path}.act")).unwrap(),
+            .map(|entity_part| AnimationPair {
+                sprites: sprite_loader.get_or_load(&format!("{}.spr", entity_part.file)).unwrap(),
+                actions: action_loader.get_or_load(&format!("{}.act", entity_part.file)).unwrap(),
             })
             .collect();
 
@@ -90,7 +98,7 @@ impl AnimationLoader {
                             sprite_number += animation_pair.sprites.palette_size;
                         }
 
-                        let texture_size = animation_pair.sprites.textures[sprite_number].get_size();
+                        let texture_size = animation_pair.sprites.texture_size(sprite_number);
                         let mut height = texture_size.height;
                         let mut width = texture_size.width;
 
@@ -131,24 +139,32 @@ impl AnimationLoader {
                         let mirror = sprite_clip.mirror_on != 0;
 
                         // Attach points have a different offset calculation.
-                        // Currently, this is hardcoded for the player heads. An `animation_index` of
-                        // `0` corresponds to the head, and `1` for the body.
+                        // The head, headgears, and garment of a player are all attached to
+                        // the body, which is always the first part.
                         let has_attach_point = match motion.attach_point_count {
                             Some(value) => value == 1,
                             None => false,
                         };
 
-                        if entity_type == EntityType::Player && has_attach_point && animation_index == 1 {
-                            let parent_animation_pair = &animation_pairs[0];
-                            let parent_action = &parent_animation_pair.actions.actions[action_index];
+                        if entity_type == EntityType::Player
+                            && has_attach_point
+                            && entity_part_files[animation_index].layer != SpriteLayer::Body
+                        {
                             // TODO: Precompute the size of each motion from the animation pair.
                             // Determine the minimum motion size to iterate without going out of bound.
                             // This check resolves the game crash when using the Assassin class.
-                            if parent_action.motions.len() <= motion_index {
+                            let Some(parent_motion) = animation_pairs[0]
+                                .actions
+                                .actions
+                                .get(action_index)
+                                .and_then(|parent_action| parent_action.motions.get(motion_index))
+                            else {
                                 continue;
-                            }
-                            let parent_motion = &parent_action.motions[motion_index];
-                            let parent_attach_point = parent_motion.attach_points[0].position;
+                            };
+                            let Some(parent_attach_point) = parent_motion.attach_points.first() else {
+                                continue;
+                            };
+                            let parent_attach_point = parent_attach_point.position;
                             let attach_point = motion.attach_points[0].position;
                             let new_offset = -attach_point + parent_attach_point;
                             offset += new_offset;
@@ -205,18 +221,18 @@ impl AnimationLoader {
         }
 
         let action_size = animation_pairs[0].actions.actions.len();
-        let animation_pair_size = animation_pairs.len();
 
         let mut animations: Vec<Animation> = Vec::new();
 
         // Merge the sprites from each motion by combining the animation pair.
         for action_index in 0..action_size {
             let motion_size = animation_pairs[0].actions.actions[action_index].motions.len();
+            let layer_order = draw_order(entity_part_files, action_index % 8);
             let mut frames: Vec<AnimationFrame> = Vec::new();
             for motion_index in 0..motion_size {
                 let mut generate: Vec<AnimationFrame> = Vec::new();
 
-                for pair in &animations_list[0..animation_pair_size] {
+                for pair in layer_order.iter().map(|index| &animations_list[*index]) {
                     if pair.len() <= action_index || pair[action_index].len() <= motion_index {
                         continue;
                     }
@@ -322,7 +338,11 @@ impl AnimationLoader {
             print_debug!(
                 "[{}] animation could not be added to cache. Entity Files: '{}': {:?}",
                 "error".red(),
-                &entity_part_files.join(";"),
+                entity_part_files
+                    .iter()
+                    .map(|entity_part| entity_part.file.as_str())
+                    .collect::<Vec<_>>()
+                    .join(";"),
                 error
             );
         }
@@ -330,12 +350,20 @@ impl AnimationLoader {
         Ok(animation_data)
     }
 
-    pub fn get(&self, entity_part_files: &[String]) -> Option<Arc<AnimationData>> {
+    pub fn get(&self, entity_part_files: &[EntityPart]) -> Option<Arc<AnimationData>> {
         let mut lock = self.cache.lock().unwrap();
         lock.get(entity_part_files).cloned()
     }
 }
 
+/// Indices of the entity parts in the order they are drawn for an action
+/// facing the given direction.
+fn draw_order(entity_part_files: &[EntityPart], direction: usize) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..entity_part_files.len()).collect();
+    order.sort_by_key(|index| entity_part_files[*index].layer.draw_priority(direction));
+    order
+}
+
 fn vector2_i32_to_f32(vector: Vector2<i32>) -> Vector2<f32> {
     vector.map(|value| value as f32)
 }
@@ -508,3 +536,49 @@ fn merge_frame(frames: &mut [AnimationFrame]) -> AnimationFrame {
         vertical_matrix: Matrix4::identity(),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::draw_order;
+    use crate::world::{EntityPart, SpriteLayer};
+
+    fn entity_parts(layers: &[SpriteLayer]) -> Vec<EntityPart> {
+        layers
+            .iter()
+            .map(|layer| EntityPart {
+                layer: *layer,
+                file: format!("{layer:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn garment_is_drawn_behind_body_when_facing_camera() {
+        let entity_parts = entity_parts(&[SpriteLayer::Body, SpriteLayer::Head, SpriteLayer::HeadgearTop, SpriteLayer::Garment]);
+
+        assert_eq!(draw_order(&entity_parts, 0), [3, 0, 1, 2]);
+        assert_eq!(draw_order(&entity_parts, 2), [3, 0, 1, 2]);
+    }
+
+    #[test]
+    fn garment_is_drawn_in_front_of_body_when_facing_away() {
+        let entity_parts = entity_parts(&[SpriteLayer::Body, SpriteLayer::Head, SpriteLayer::HeadgearTop, SpriteLayer::Garment]);
+
+        assert_eq!(draw_order(&entity_parts, 4), [0, 1, 2, 3]);
+        // The direction repeats for every action type.
+        assert_eq!(draw_order(&entity_parts, 8 + 3), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn headgears_are_drawn_from_bottom_to_top() {
+        let entity_parts = entity_parts(&[
+            SpriteLayer::Body,
+            SpriteLayer::Head,
+            SpriteLayer::HeadgearTop,
+            SpriteLayer::HeadgearBottom,
+            SpriteLayer::HeadgearMiddle,
+        ]);
+
+        assert_eq!(draw_order(&entity_parts, 0), [0, 1, 3, 4, 2]);
+    }
+}
@@ -27,8 +27,9 @@ pub struct FolderArchive {
 }
 
 impl FolderArchive {
-    /// Load the file mapping of a given directory.
-    fn load_mapping(directory: &PathBuf) -> HashMap<String, PathBuf> {
+    /// Load the file mapping of a given directory. Asset paths are relative to
+    /// `root`, which is either the directory itself or one of its parents.
+    fn load_mapping(directory: &Path, root: &Path) -> HashMap<String, PathBuf> {
         WalkDir::new(directory)
             .into_iter()
             .filter_map(|entry| entry.ok())
@@ -36,7 +37,7 @@ impl FolderArchive {
             .map(|file| {
                 let mut asset_path = file
                     .path()
-                    .strip_prefix(directory)
+                    .strip_prefix(root)
                     .unwrap()
                     .to_str()
                     .unwrap()
@@ -63,12 +64,23 @@ impl FolderArchive {
 
         (full_path, compressed)
     }
+
+    /// Load a directory of loose files that overlays the contents of other
+    /// archives. Unlike [`FolderArchive::from_path`], the name of the
+    /// directory is part of the asset paths, so the files in `data/` map
+    /// directly to the `data\` paths inside of GRF files.
+    pub fn overlay_from_path(path: &Path) -> Self {
+        let folder_path = path.parent().map(PathBuf::from).unwrap_or_default();
+        let file_mapping = Self::load_mapping(path, &folder_path);
+
+        Self { folder_path, file_mapping }
+    }
 }
 
 impl Archive for FolderArchive {
     fn from_path(path: &Path) -> Self {
         let folder_path = PathBuf::from(path);
-        let file_mapping = Self::load_mapping(&folder_path);
+        let file_mapping = Self::load_mapping(&folder_path, &folder_path);
 
         Self { folder_path, file_mapping }
     }
@@ -20,6 +20,20 @@ pub trait Archive: Send + Sync {
 
     /// Hashes the archive with the given hasher.
     fn hash(&self, hasher: &mut blake3::Hasher);
+
+    /// Checks the integrity of every file in the archive and collects the
+    /// files that can't be read. Returns the number of checked files.
+    /// Archives without checksums for their files don't check anything.
+    fn verify(&self, _corrupt_files: &mut Vec<CorruptFile>) -> usize {
+        0
+    }
+}
+
+/// A file inside of an archive that failed the integrity check.
+#[derive(Debug)]
+pub struct CorruptFile {
+    pub path: String,
+    pub reason: String,
 }
 
 pub enum ArchiveType {
@@ -4,7 +4,7 @@ mod mixcrypt;
 
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{Read, Seek, SeekFrom};
+use std::io::{Error, Read, Seek, SeekFrom};
 use std::ops::Deref;
 use std::path::Path;
 use std::sync::Mutex;
@@ -17,8 +17,8 @@ use ragnarok_bytes::{ByteReader, FixedByteSize, FromBytes};
 use ragnarok_formats::archive::{AssetTable, FileTableRow, Header};
 
 pub use self::builder::NativeArchiveBuilder;
-use crate::loaders::archive::Archive;
 use crate::loaders::archive::native::mixcrypt::decrypt_file;
+use crate::loaders::archive::{Archive, CorruptFile};
 
 /// Represents a GRF file. GRF Files are an archive to store game assets.
 /// Each GRF contains a [`Header`] with metadata (number of files, size,
@@ -30,6 +30,33 @@ pub struct NativeArchive {
     file_handle: Mutex<File>,
 }
 
+impl NativeArchive {
+    fn read_file(&self, file_information: &FileTableRow) -> Result<Vec<u8>, Error> {
+        let mut compressed_file_buffer = vec![0u8; file_information.compressed_size_aligned as usize];
+
+        let position = file_information.offset as u64 + Header::size_in_bytes() as u64;
+
+        {
+            // Since the calling threads are sharing the IO bandwidth anyhow, I don't think
+            // we need to allow this to run in parallel.
+            let mut file_handle = self.file_handle.lock().unwrap();
+            file_handle.seek(SeekFrom::Start(position))?;
+            file_handle.read_exact(&mut compressed_file_buffer)?;
+        }
+
+        decrypt_file(file_information, &mut compressed_file_buffer);
+
+        // The zlib stream ends with an Adler-32 checksum of the content, which the
+        // decoder validates. This is the closest thing to a CRC that GRF
+        // files store for their entries.
+        let mut decoder = ZlibDecoder::new(compressed_file_buffer.as_slice());
+        let mut decompressed = Vec::with_capacity(file_information.uncompressed_size as usize);
+        decoder.read_to_end(&mut decompressed)?;
+
+        Ok(decompressed)
+    }
+}
+
 impl Archive for NativeArchive {
     fn from_path(path: &Path) -> Self {
         #[cfg(feature = "debug")]
@@ -89,29 +116,9 @@ impl Archive for NativeArchive {
     }
 
     fn get_file_by_path(&self, asset_path: &str) -> Option<Vec<u8>> {
-        self.file_table.get(asset_path).map(|file_information| {
-            let mut compressed_file_buffer = vec![0u8; file_information.compressed_size_aligned as usize];
-
-            let position = file_information.offset as u64 + Header::size_in_bytes() as u64;
-
-            {
-                // Since the calling threads are sharing the IO bandwidth anyhow, I don't think
-                // we need to allow this to run in parallel.
-                let mut file_handle = self.file_handle.lock().unwrap();
-                file_handle.seek(SeekFrom::Start(position)).unwrap();
-                file_handle
-                    .read_exact(&mut compressed_file_buffer)
-                    .expect("can't read archive content");
-            }
-
-            decrypt_file(file_information, &mut compressed_file_buffer);
-
-            let mut decoder = ZlibDecoder::new(compressed_file_buffer.as_slice());
-            let mut decompressed = Vec::with_capacity(file_information.uncompressed_size as usize);
-            decoder.read_to_end(&mut decompressed).expect("can't decompress archive content");
-
-            decompressed
-        })
+        self.file_table
+            .get(asset_path)
+            .map(|file_information| self.read_file(file_information).expect("can't read archive content"))
     }
 
     fn get_files_with_extension(&self, files: &mut Vec<String>, extensions: &[&str]) {
@@ -124,6 +131,30 @@ impl Archive for NativeArchive {
         files.extend(found_files);
     }
 
+    fn verify(&self, corrupt_files: &mut Vec<CorruptFile>) -> usize {
+        let mut files: Vec<(&String, &FileTableRow)> = self.file_table.iter().filter(|(_, row)| row.flags == 0x01).collect();
+        files.sort_by_key(|(_, row)| row.offset);
+
+        for (file_name, file_information) in files.iter() {
+            let reason = match self.read_file(file_information) {
+                Ok(data) if data.len() == file_information.uncompressed_size as usize => continue,
+                Ok(data) => format!(
+                    "expected {} bytes but decompressed {}",
+                    file_information.uncompressed_size,
+                    data.len()
+                ),
+                Err(error) => error.to_string(),
+            };
+
+            corrupt_files.push(CorruptFile {
+                path: file_name.to_string(),
+                reason,
+            });
+        }
+
+        files.len()
+    }
+
     fn hash(&self, hasher: &mut Hasher) {
         let file = self.file_handle.lock().unwrap();
         if let Err(_err) = hasher.update_reader(file.deref()) {
@@ -1,39 +1,44 @@
 use std::cmp::PartialEq;
 use std::sync::{Arc, Mutex};
 
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 #[cfg(feature = "debug")]
 use korangar_debug::logging::print_debug;
 #[cfg(feature = "debug")]
 use korangar_debug::profiling::Profiler;
-use ragnarok_packets::{EntityId, ItemId, TilePosition};
+use ragnarok_packets::{EntityId, TilePosition};
 use rayon::{ThreadPool, ThreadPoolBuilder};
 
 use crate::graphics::Texture;
 use crate::init_tls_rand;
 use crate::loaders::error::LoadError;
-use crate::loaders::{ActionLoader, AnimationLoader, ImageType, MapLoader, ModelLoader, SpriteLoader, TextureLoader, VideoLoader};
+use crate::loaders::{
+    ActionLoader, AnimationLoader, ImageType, MapLoader, MapThumbnailLoader, ModelLoader, SpriteLoader, TextureLoader, VideoLoader,
+};
 #[cfg(feature = "debug")]
 use crate::threads;
-use crate::world::{AnimationData, EntityType, Library, Map};
+#[cfg(feature = "debug")]
+use crate::world::ModelPreview;
+use crate::world::{AnimationData, EntityPart, EntityType, Library, Map};
 
-#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
-pub enum ItemLocation {
-    Inventory,
-    Shop,
-}
+/// Number of maps that are kept loaded after leaving them.
+const MAXIMUM_RETAINED_MAPS: usize = 2;
+/// Number of adjacent maps that are loaded in the background.
+const MAXIMUM_PRELOADED_MAPS: usize = 4;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub enum LoaderId {
     AnimationData(EntityId),
-    ItemSprite(ItemId),
+    ItemIcon(String),
     Map(String),
+    MapThumbnail(String),
 }
 
 pub enum LoadableResource {
     AnimationData(Arc<AnimationData>),
-    ItemSprite { texture: Arc<Texture>, location: ItemLocation },
+    ItemIcon(Arc<Texture>),
     Map { map: Box<Map>, position: Option<TilePosition> },
+    MapThumbnail(Arc<Texture>),
 }
 
 enum LoadStatus {
@@ -48,17 +53,34 @@ impl PartialEq for LoadStatus {
     }
 }
 
+#[derive(Default)]
+struct MapPreloads {
+    /// Maps that are currently loaded in the background.
+    in_progress: HashSet<String>,
+    /// Maps that finished loading, most recently loaded first.
+    completed: Vec<Box<Map>>,
+    /// Map that was requested while it was still being preloaded.
+    awaited: Option<(String, Option<TilePosition>)>,
+}
+
 pub struct AsyncLoader {
     action_loader: Arc<ActionLoader>,
     animation_loader: Arc<AnimationLoader>,
     map_loader: Arc<MapLoader>,
+    map_thumbnail_loader: Arc<MapThumbnailLoader>,
     model_loader: Arc<ModelLoader>,
     sprite_loader: Arc<SpriteLoader>,
     texture_loader: Arc<TextureLoader>,
     video_loader: Arc<VideoLoader>,
     library: Arc<Library>,
     pending_loads: Arc<Mutex<HashMap<LoaderId, LoadStatus>>>,
+    /// Maps that were left but are kept loaded, most recently left first.
+    retained_maps: Mutex<Vec<Box<Map>>>,
+    map_preloads: Arc<Mutex<MapPreloads>>,
     thread_pool: ThreadPool,
+    /// Separate thread pool, so preloading maps doesn't delay the loads that
+    /// are needed right away.
+    preload_thread_pool: ThreadPool,
 }
 
 impl AsyncLoader {
@@ -66,6 +88,7 @@ impl AsyncLoader {
         action_loader: Arc<ActionLoader>,
         animation_loader: Arc<AnimationLoader>,
         map_loader: Arc<MapLoader>,
+        map_thumbnail_loader: Arc<MapThumbnailLoader>,
         model_loader: Arc<ModelLoader>,
         sprite_loader: Arc<SpriteLoader>,
         texture_loader: Arc<TextureLoader>,
@@ -79,17 +102,28 @@ impl AsyncLoader {
             .build()
             .unwrap();
 
+        let preload_thread_pool = ThreadPoolBuilder::new()
+            .thread_name(|number| format!("map preload thread pool {number}"))
+            .num_threads(1)
+            .start_handler(|_| init_tls_rand())
+            .build()
+            .unwrap();
+
         Self {
             action_loader,
             animation_loader,
             map_loader,
+            map_thumbnail_loader,
             model_loader,
             sprite_loader,
             texture_loader,
             video_loader,
             library,
             pending_loads: Arc::new(Mutex::new(HashMap::new())),
+            retained_maps: Mutex::new(Vec::new()),
+            map_preloads: Arc::new(Mutex::new(MapPreloads::default())),
             thread_pool,
+            preload_thread_pool,
         }
     }
 
@@ -98,7 +132,7 @@ impl AsyncLoader {
         &self,
         entity_id: EntityId,
         entity_type: EntityType,
-        entity_part_files: Vec<String>,
+        entity_part_files: Vec<EntityPart>,
     ) -> Option<Arc<AnimationData>> {
         match self.animation_loader.get(&entity_part_files) {
             Some(animation_data) => Some(animation_data),
@@ -123,32 +157,34 @@ impl AsyncLoader {
         }
     }
 
+    /// Get an item icon if it is already loaded. Otherwise the icon is decoded
+    /// in the background and `None` is returned, so a placeholder can be shown
+    /// until the load completes.
     #[must_use]
-    pub fn request_item_sprite_load(
-        &self,
-        item_location: ItemLocation,
-        item_id: ItemId,
-        path: &str,
-        image_type: ImageType,
-    ) -> Option<Arc<Texture>> {
-        match self.texture_loader.get(path, image_type) {
+    pub fn request_item_icon_load(&self, path: &str) -> Option<Arc<Texture>> {
+        match self.texture_loader.get(path, ImageType::Color) {
             Some(texture) => Some(texture),
             None => {
+                let id = LoaderId::ItemIcon(path.to_string());
+
+                // Many items of the same kind are added at once, for example when
+                // the inventory is received, so every icon is only decoded once.
+                if self.pending_loads.lock().unwrap().get(&id) == Some(&LoadStatus::Loading) {
+                    return None;
+                }
+
                 let texture_loader = self.texture_loader.clone();
                 let path = path.to_string();
 
-                self.request_load(LoaderId::ItemSprite(item_id), move || {
+                self.request_load(id, move || {
                     #[cfg(feature = "debug")]
-                    let _load_measurement = Profiler::start_measurement("item sprite load");
+                    let _load_measurement = Profiler::start_measurement("item icon load");
 
-                    let texture = match texture_loader.get(&path, image_type) {
-                        None => texture_loader.load(&path, image_type)?,
+                    let texture = match texture_loader.get(&path, ImageType::Color) {
                         Some(texture) => texture,
+                        None => texture_loader.load_item_icon(&path)?,
                     };
-                    Ok(LoadableResource::ItemSprite {
-                        texture,
-                        location: item_location,
-                    })
+                    Ok(LoadableResource::ItemIcon(texture))
                 });
 
                 None
@@ -157,6 +193,46 @@ impl AsyncLoader {
     }
 
     pub fn request_map_load(&self, map_name: String, position: Option<TilePosition>) {
+        let retained_map = {
+            let mut retained_maps = self.retained_maps.lock().unwrap();
+            retained_maps
+                .iter()
+                .position(|map| map.resource_file() == map_name)
+                .map(|index| retained_maps.remove(index))
+        };
+
+        let mut map_preloads = self.map_preloads.lock().unwrap();
+
+        let loaded_map = retained_map.or_else(|| {
+            map_preloads
+                .completed
+                .iter()
+                .position(|map| map.resource_file() == map_name)
+                .map(|index| map_preloads.completed.remove(index))
+        });
+
+        // Retained and preloaded maps are completed right away, so they are
+        // handled like any other loaded map.
+        if let Some(map) = loaded_map {
+            self.pending_loads.lock().unwrap().insert(
+                LoaderId::Map(map_name),
+                LoadStatus::Completed(LoadableResource::Map { map, position }),
+            );
+            return;
+        }
+
+        // The preload hands the map over once it completes.
+        if map_preloads.in_progress.contains(&map_name) {
+            self.pending_loads
+                .lock()
+                .unwrap()
+                .insert(LoaderId::Map(map_name.clone()), LoadStatus::Loading);
+            map_preloads.awaited = Some((map_name, position));
+            return;
+        }
+
+        drop(map_preloads);
+
         let map_loader = self.map_loader.clone();
         let model_loader = self.model_loader.clone();
         let texture_loader = self.texture_loader.clone();
@@ -171,6 +247,142 @@ impl AsyncLoader {
         });
     }
 
+    /// Load a model for the model viewer. This is done synchronously, since
+    /// the model viewer is only used for debugging.
+    #[cfg(feature = "debug")]
+    pub fn load_model_preview(&self, model_file: &str) -> Result<ModelPreview, LoadError> {
+        self.map_loader.load_model_preview(
+            model_file,
+            &self.model_loader,
+            self.texture_loader.clone(),
+            self.video_loader.clone(),
+        )
+    }
+
+    /// Keep a map loaded after leaving it, so entering it again doesn't
+    /// require loading it. Only the most recently left maps are kept.
+    pub fn retain_map(&self, map: Box<Map>) {
+        let mut retained_maps = self.retained_maps.lock().unwrap();
+        retained_maps.retain(|retained_map| retained_map.resource_file() != map.resource_file());
+        retained_maps.insert(0, map);
+        retained_maps.truncate(MAXIMUM_RETAINED_MAPS);
+    }
+
+    pub fn clear_retained_maps(&self) {
+        self.retained_maps.lock().unwrap().clear();
+    }
+
+    /// Load the given maps in the background, so walking through a warp
+    /// portal doesn't show a loading screen. Preloaded maps that are not in
+    /// the list are dropped.
+    pub fn preload_maps(&self, map_names: &[String]) {
+        let map_names = &map_names[..map_names.len().min(MAXIMUM_PRELOADED_MAPS)];
+        let mut map_preloads = self.map_preloads.lock().unwrap();
+
+        map_preloads
+            .completed
+            .retain(|map| map_names.iter().any(|map_name| map_name == map.resource_file()));
+
+        for map_name in map_names {
+            let is_loaded = map_preloads.completed.iter().any(|map| map.resource_file() == map_name);
+
+            if is_loaded || !map_preloads.in_progress.insert(map_name.clone()) {
+                continue;
+            }
+
+            let map_loader = self.map_loader.clone();
+            let model_loader = self.model_loader.clone();
+            let texture_loader = self.texture_loader.clone();
+            let video_loader = self.video_loader.clone();
+            let library = self.library.clone();
+            let map_preloads = Arc::clone(&self.map_preloads);
+            let pending_loads = Arc::clone(&self.pending_loads);
+            let map_name = map_name.clone();
+
+            self.preload_thread_pool.spawn(move || {
+                #[cfg(feature = "debug")]
+                let _measurement = threads::Loader::start_frame();
+
+                #[cfg(feature = "debug")]
+                let _load_measurement = Profiler::start_measurement("map preload");
+
+                let result = map_loader.load(map_name.clone(), &model_loader, texture_loader, video_loader, &library);
+
+                let mut map_preloads = map_preloads.lock().unwrap();
+                map_preloads.in_progress.remove(&map_name);
+
+                match map_preloads.awaited.take_if(|(awaited_map_name, _)| *awaited_map_name == map_name) {
+                    Some((_, position)) => {
+                        let status = match result {
+                            Ok(map) => LoadStatus::Completed(LoadableResource::Map { map, position }),
+                            Err(err) => LoadStatus::Failed(err),
+                        };
+
+                        pending_loads.lock().unwrap().insert(LoaderId::Map(map_name), status);
+                    }
+                    None => match result {
+                        Ok(map) => {
+                            map_preloads.completed.insert(0, map);
+                            map_preloads.completed.truncate(MAXIMUM_PRELOADED_MAPS);
+                        }
+                        Err(_error) => {
+                            #[cfg(feature = "debug")]
+                            print_debug!("Map preload error: {:?}", _error);
+                        }
+                    },
+                }
+            });
+        }
+    }
+
+    /// Keep a map loaded as if it was preloaded. Used for the map that was
+    /// just left, since it is usually adjacent to the new map.
+    pub fn add_preloaded_map(&self, map: Box<Map>) {
+        let mut map_preloads = self.map_preloads.lock().unwrap();
+        map_preloads
+            .completed
+            .retain(|preloaded_map| preloaded_map.resource_file() != map.resource_file());
+        map_preloads.completed.insert(0, map);
+        map_preloads.completed.truncate(MAXIMUM_PRELOADED_MAPS);
+    }
+
+    /// Memory on the GPU that is used by retained and preloaded maps.
+    pub fn kept_maps_gpu_memory_usage(&self) -> usize {
+        let retained_maps = self.retained_maps.lock().unwrap();
+        let map_preloads = self.map_preloads.lock().unwrap();
+
+        retained_maps
+            .iter()
+            .chain(map_preloads.completed.iter())
+            .map(|map| map.gpu_memory_usage())
+            .sum()
+    }
+
+    pub fn clear_preloaded_maps(&self) {
+        self.map_preloads.lock().unwrap().completed.clear();
+    }
+
+    #[must_use]
+    pub fn request_map_thumbnail_load(&self, map_name: &str) -> Option<Arc<Texture>> {
+        match self.map_thumbnail_loader.get(map_name) {
+            Some(texture) => Some(texture),
+            None => {
+                let map_thumbnail_loader = self.map_thumbnail_loader.clone();
+                let map_name = map_name.to_string();
+
+                self.request_load(LoaderId::MapThumbnail(map_name.clone()), move || {
+                    #[cfg(feature = "debug")]
+                    let _load_measurement = Profiler::start_measurement("map thumbnail load");
+
+                    let texture = map_thumbnail_loader.load(&map_name)?;
+                    Ok(LoadableResource::MapThumbnail(texture))
+                });
+
+                None
+            }
+        }
+    }
+
     fn request_load<F>(&self, id: LoaderId, load_function: F)
     where
         F: FnOnce() -> Result<LoadableResource, LoadError> + Send + 'static,
@@ -0,0 +1,131 @@
+//! Audit of the fallback assets that silently replace missing or broken game
+//! files. If enabled, the loaders record every substitution, so server owners
+//! can find and fix the broken files of their data packs.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Write};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AssetKind {
+    Texture,
+    Model,
+    Sprite,
+    Actions,
+}
+
+impl Display for AssetKind {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssetKind::Texture => formatter.write_str("texture"),
+            AssetKind::Model => formatter.write_str("model"),
+            AssetKind::Sprite => formatter.write_str("sprite"),
+            AssetKind::Actions => formatter.write_str("actions"),
+        }
+    }
+}
+
+/// A single asset that was replaced by a fallback.
+struct Substitution {
+    /// Cause of the first substitution.
+    cause: String,
+    /// Number of times the asset was replaced, since some loaders don't cache
+    /// fallbacks under the original path.
+    count: usize,
+}
+
+pub struct FallbackAudit {
+    enabled: bool,
+    substitutions: Mutex<BTreeMap<(AssetKind, String), Substitution>>,
+}
+
+impl FallbackAudit {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            substitutions: Mutex::default(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Record that the asset at `path` was replaced by a fallback. Does
+    /// nothing if the audit is disabled.
+    pub fn record(&self, kind: AssetKind, path: &str, cause: String) {
+        if !self.enabled {
+            return;
+        }
+
+        self.substitutions
+            .lock()
+            .unwrap()
+            .entry((kind, path.to_owned()))
+            .and_modify(|substitution| substitution.count += 1)
+            .or_insert(Substitution { cause, count: 1 });
+    }
+
+    /// Number of distinct assets that were replaced.
+    pub fn substitution_count(&self) -> usize {
+        self.substitutions.lock().unwrap().len()
+    }
+
+    /// Report of all substitutions, sorted by kind and path.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("kind,path,count,cause\n");
+
+        for ((kind, path), substitution) in self.substitutions.lock().unwrap().iter() {
+            let _ = writeln!(
+                csv,
+                "{kind},{},{},{}",
+                escape_csv(path),
+                substitution.count,
+                escape_csv(&substitution.cause)
+            );
+        }
+
+        csv
+    }
+}
+
+fn escape_csv(field: &str) -> String {
+    match field.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AssetKind, FallbackAudit};
+
+    #[test]
+    fn substitutions_are_grouped_by_asset() {
+        let audit = FallbackAudit::new(true);
+
+        audit.record(AssetKind::Texture, "prontera\\wall.bmp", "file not found".to_owned());
+        audit.record(
+            AssetKind::Model,
+            "prontera\\fountain.rsm",
+            "failed to parse, unexpected end of data".to_owned(),
+        );
+        audit.record(AssetKind::Texture, "prontera\\wall.bmp", "file not found".to_owned());
+
+        assert_eq!(audit.substitution_count(), 2);
+        assert_eq!(
+            audit.to_csv(),
+            "kind,path,count,cause\ntexture,prontera\\wall.bmp,2,file not found\nmodel,prontera\\fountain.rsm,1,\"failed to parse, \
+             unexpected end of data\"\n"
+        );
+    }
+
+    #[test]
+    fn disabled_audit_records_nothing() {
+        let audit = FallbackAudit::new(false);
+
+        audit.record(AssetKind::Sprite, "npc\\broken.spr", "file not found".to_owned());
+
+        assert_eq!(audit.substitution_count(), 0);
+    }
+}
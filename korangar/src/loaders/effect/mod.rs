@@ -2,9 +2,7 @@ use std::num::{NonZeroU32, NonZeroUsize};
 use std::sync::{Arc, Mutex};
 
 use cgmath::Deg;
-#[cfg(feature = "debug")]
-use korangar_container::CacheStatistics;
-use korangar_container::SimpleCache;
+use korangar_container::{CacheStatistics, SimpleCache};
 #[cfg(feature = "debug")]
 use korangar_debug::logging::{Colorize, Timer, print_debug};
 use korangar_loaders::FileLoader;
@@ -39,11 +37,22 @@ impl EffectLoader {
         }
     }
 
-    #[cfg(feature = "debug")]
     pub fn cache_statistics(&self) -> CacheStatistics {
         self.cache.lock().unwrap().statistics()
     }
 
+    /// Drops all cached effects, so they are loaded again the next time they
+    /// are requested.
+    #[cfg(feature = "debug")]
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Evicts cached effects until the cache holds at most `size` bytes.
+    pub fn shrink_cache(&self, size: usize) {
+        self.cache.lock().unwrap().shrink_to_size(size);
+    }
+
     #[allow(clippy::mut_range_bound)]
     fn load(&self, path: &str, texture_loader: &TextureLoader) -> Result<Arc<Effect>, LoadError> {
         #[cfg(feature = "debug")]
@@ -0,0 +1,351 @@
+//! The prebuilt MSDF font maps only cover a subset of the glyphs of a font,
+//! which is not enough for CJK text. Glyphs that are missing from the font map
+//! are rasterized from the font outline at runtime and packed into a reserved
+//! area at the bottom of the font map.
+//!
+//! The glyphs are stored as a single channel signed distance field that is
+//! written into all color channels, so they can be rendered with the same MSDF
+//! shader as the prebuilt glyphs.
+
+use std::sync::Arc;
+
+use cgmath::{EuclideanSpace, InnerSpace, Point2, Vector2};
+use cosmic_text::FontSystem;
+use cosmic_text::fontdb::ID;
+use cosmic_text::ttf_parser::{GlyphId, OutlineBuilder};
+use hashbrown::HashMap;
+use image::{Rgba, RgbaImage};
+#[cfg(feature = "debug")]
+use korangar_debug::logging::{Colorize, print_debug};
+use wgpu::{Extent3d, Origin3d, Queue, TexelCopyBufferLayout, TexelCopyTextureInfo, TextureAspect};
+
+use super::GlyphCoordinate;
+use crate::graphics::Texture;
+use crate::loaders::rectangle::Rectangle;
+use crate::loaders::texture::ShelfAllocator;
+
+/// Number of rows at the bottom of the font map that are reserved for glyphs
+/// rasterized at runtime.
+pub(super) const DYNAMIC_GLYPH_AREA_HEIGHT: u32 = 256;
+/// Resolution of rasterized glyphs in texels per em.
+const GLYPH_RESOLUTION: f32 = 32.0;
+/// Distance range of the signed distance field in texels. This needs to match
+/// the `PXRANGE` of the interface shader.
+const DISTANCE_RANGE: f32 = 6.0;
+/// Border around each glyph, so the distance field doesn't get cut off.
+const PADDING: u32 = (DISTANCE_RANGE / 2.0) as u32;
+/// Number of line segments used to approximate a bezier curve.
+const CURVE_SEGMENTS: usize = 8;
+
+/// Collects the outline of a glyph as line segments in texel space.
+struct OutlineCollector {
+    scale: f32,
+    offset: Vector2<f32>,
+    segments: Vec<[Point2<f32>; 2]>,
+    contour_start: Point2<f32>,
+    current: Point2<f32>,
+}
+
+impl OutlineCollector {
+    /// Font units are y-up while texels are y-down, so the outline is flipped
+    /// around the top of its bounding box.
+    fn new(scale: f32, x_min: f32, y_max: f32) -> Self {
+        Self {
+            scale,
+            offset: Vector2::new(-x_min * scale + PADDING as f32, y_max * scale + PADDING as f32),
+            segments: Vec::new(),
+            contour_start: Point2::new(0.0, 0.0),
+            current: Point2::new(0.0, 0.0),
+        }
+    }
+
+    fn transform(&self, x: f32, y: f32) -> Point2<f32> {
+        Point2::new(x * self.scale + self.offset.x, -y * self.scale + self.offset.y)
+    }
+
+    fn push_line(&mut self, to: Point2<f32>) {
+        if to != self.current {
+            self.segments.push([self.current, to]);
+        }
+        self.current = to;
+    }
+}
+
+impl OutlineBuilder for OutlineCollector {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.contour_start = self.transform(x, y);
+        self.current = self.contour_start;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let to = self.transform(x, y);
+        self.push_line(to);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let from = self.current;
+        let control = self.transform(x1, y1);
+        let to = self.transform(x, y);
+
+        for step in 1..=CURVE_SEGMENTS {
+            let t = step as f32 / CURVE_SEGMENTS as f32;
+            let inverse = 1.0 - t;
+            let point = from * (inverse * inverse) + control.to_vec() * (2.0 * inverse * t) + to.to_vec() * (t * t);
+            self.push_line(point);
+        }
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let from = self.current;
+        let control1 = self.transform(x1, y1);
+        let control2 = self.transform(x2, y2);
+        let to = self.transform(x, y);
+
+        for step in 1..=CURVE_SEGMENTS {
+            let t = step as f32 / CURVE_SEGMENTS as f32;
+            let inverse = 1.0 - t;
+            let point = from * (inverse * inverse * inverse)
+                + control1.to_vec() * (3.0 * inverse * inverse * t)
+                + control2.to_vec() * (3.0 * inverse * t * t)
+                + to.to_vec() * (t * t * t);
+            self.push_line(point);
+        }
+    }
+
+    fn close(&mut self) {
+        let contour_start = self.contour_start;
+        self.push_line(contour_start);
+    }
+}
+
+fn distance_to_segment(point: Point2<f32>, [start, end]: [Point2<f32>; 2]) -> f32 {
+    let segment = end - start;
+    let length_squared = segment.magnitude2();
+
+    let t = match length_squared > 0.0 {
+        true => ((point - start).dot(segment) / length_squared).clamp(0.0, 1.0),
+        false => 0.0,
+    };
+
+    (point - (start + segment * t)).magnitude()
+}
+
+/// Winding number of the outline around the point. TrueType and OpenType
+/// outlines use the non-zero fill rule.
+fn winding_number(point: Point2<f32>, segments: &[[Point2<f32>; 2]]) -> i32 {
+    segments.iter().fold(0, |winding, [start, end]| {
+        let is_left = (end.x - start.x) * (point.y - start.y) - (point.x - start.x) * (end.y - start.y);
+
+        if start.y <= point.y && end.y > point.y && is_left > 0.0 {
+            winding + 1
+        } else if start.y > point.y && end.y <= point.y && is_left < 0.0 {
+            winding - 1
+        } else {
+            winding
+        }
+    })
+}
+
+/// Generate a signed distance field of the outline, using the same encoding as
+/// the MSDF font maps: 0.5 is the edge and values above it are inside.
+fn generate_distance_field(segments: &[[Point2<f32>; 2]], width: u32, height: u32) -> RgbaImage {
+    RgbaImage::from_fn(width, height, |x, y| {
+        let point = Point2::new(x as f32 + 0.5, y as f32 + 0.5);
+        let distance = segments
+            .iter()
+            .map(|segment| distance_to_segment(point, *segment))
+            .fold(f32::MAX, f32::min);
+
+        let signed_distance = match winding_number(point, segments) != 0 {
+            true => distance,
+            false => -distance,
+        };
+
+        let value = ((0.5 + signed_distance / DISTANCE_RANGE).clamp(0.0, 1.0) * 255.0).round() as u8;
+        Rgba([value, value, value, 255])
+    })
+}
+
+pub(super) struct DynamicGlyphAtlas {
+    queue: Queue,
+    font_map: Arc<Texture>,
+    font_map_size: Vector2<f32>,
+    /// First row of the font map that belongs to the dynamic glyph area.
+    area_top: u32,
+    allocator: ShelfAllocator,
+    /// Glyphs that were already rasterized. Glyphs without an outline, like
+    /// spaces, or glyphs that didn't fit are stored as `None`.
+    glyphs: HashMap<(ID, u16), Option<GlyphCoordinate>>,
+}
+
+impl DynamicGlyphAtlas {
+    pub(super) fn new(queue: Queue, font_map: Arc<Texture>, area_top: u32) -> Self {
+        let size = font_map.get_size();
+
+        Self {
+            queue,
+            font_map,
+            font_map_size: Vector2::new(size.width as f32, size.height as f32),
+            area_top,
+            allocator: ShelfAllocator::new(size.width, size.height - area_top),
+            glyphs: HashMap::new(),
+        }
+    }
+
+    pub(super) fn get_or_rasterize(&mut self, font_system: &mut FontSystem, font_id: ID, glyph_id: u16) -> Option<GlyphCoordinate> {
+        if let Some(glyph) = self.glyphs.get(&(font_id, glyph_id)) {
+            return *glyph;
+        }
+
+        let glyph = self.rasterize(font_system, font_id, glyph_id);
+        self.glyphs.insert((font_id, glyph_id), glyph);
+        glyph
+    }
+
+    fn rasterize(&mut self, font_system: &mut FontSystem, font_id: ID, glyph_id: u16) -> Option<GlyphCoordinate> {
+        let font = font_system.get_font(font_id)?;
+        let face = font.rustybuzz();
+        let units_per_em = face.units_per_em() as f32;
+        let scale = GLYPH_RESOLUTION / units_per_em;
+
+        let bounds = face.glyph_bounding_box(GlyphId(glyph_id))?;
+        let mut collector = OutlineCollector::new(scale, bounds.x_min as f32, bounds.y_max as f32);
+        face.outline_glyph(GlyphId(glyph_id), &mut collector)?;
+
+        let width = ((bounds.x_max - bounds.x_min) as f32 * scale).ceil() as u32 + PADDING * 2;
+        let height = ((bounds.y_max - bounds.y_min) as f32 * scale).ceil() as u32 + PADDING * 2;
+
+        let Some(position) = self.allocator.allocate(width, height) else {
+            #[cfg(feature = "debug")]
+            print_debug!(
+                "[{}] dynamic glyph area of the font map is full, glyph {} is not rendered",
+                "warning".yellow(),
+                glyph_id.magenta()
+            );
+            return None;
+        };
+
+        let position = Vector2::new(position.x, position.y + self.area_top);
+        let image = generate_distance_field(&collector.segments, width, height);
+
+        self.queue.write_texture(
+            TexelCopyTextureInfo {
+                texture: self.font_map.get_texture(),
+                mip_level: 0,
+                origin: Origin3d {
+                    x: position.x,
+                    y: position.y,
+                    z: 0,
+                },
+                aspect: TextureAspect::All,
+            },
+            image.as_raw(),
+            TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(width * 4),
+                rows_per_image: None,
+            },
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        let padding = PADDING as f32 / GLYPH_RESOLUTION;
+        let texture_position = position.cast::<f32>().unwrap();
+        let texture_size = Vector2::new(width as f32, height as f32);
+
+        Some(GlyphCoordinate {
+            texture_coordinate: Rectangle::new(
+                Point2::new(
+                    texture_position.x / self.font_map_size.x,
+                    texture_position.y / self.font_map_size.y,
+                ),
+                Point2::new(
+                    (texture_position.x + texture_size.x) / self.font_map_size.x,
+                    (texture_position.y + texture_size.y) / self.font_map_size.y,
+                ),
+            ),
+            width: texture_size.x / GLYPH_RESOLUTION,
+            height: texture_size.y / GLYPH_RESOLUTION,
+            offset_top: -bounds.y_max as f32 / units_per_em - padding,
+            offset_left: bounds.x_min as f32 / units_per_em - padding,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::Point2;
+    use cosmic_text::ttf_parser::OutlineBuilder;
+
+    use super::{OutlineCollector, PADDING, generate_distance_field, winding_number};
+
+    fn square_outline() -> OutlineCollector {
+        // A square of 10 by 10 font units, scaled to 10 by 10 texels.
+        let mut collector = OutlineCollector::new(1.0, 0.0, 10.0);
+        collector.move_to(0.0, 0.0);
+        collector.line_to(10.0, 0.0);
+        collector.line_to(10.0, 10.0);
+        collector.line_to(0.0, 10.0);
+        collector.close();
+        collector
+    }
+
+    #[test]
+    fn outline_is_flipped_and_padded() {
+        let collector = square_outline();
+        let padding = PADDING as f32;
+
+        assert_eq!(collector.segments.len(), 4);
+        assert_eq!(collector.segments[0][0], Point2::new(padding, 10.0 + padding));
+        assert_eq!(collector.segments[2][0], Point2::new(10.0 + padding, padding));
+    }
+
+    #[test]
+    fn curves_are_flattened() {
+        let mut collector = OutlineCollector::new(1.0, 0.0, 0.0);
+        collector.move_to(0.0, 0.0);
+        collector.quad_to(5.0, 10.0, 10.0, 0.0);
+        collector.close();
+
+        assert_eq!(collector.segments.len(), super::CURVE_SEGMENTS + 1);
+        assert_eq!(collector.segments[super::CURVE_SEGMENTS - 1][1], collector.transform(10.0, 0.0));
+    }
+
+    #[test]
+    fn winding_number_of_square() {
+        let collector = square_outline();
+        let padding = PADDING as f32;
+
+        assert_ne!(
+            winding_number(Point2::new(5.0 + padding, 5.0 + padding), &collector.segments),
+            0
+        );
+        assert_eq!(
+            winding_number(Point2::new(padding - 1.0, 5.0 + padding), &collector.segments),
+            0
+        );
+        assert_eq!(
+            winding_number(Point2::new(5.0 + padding, 11.0 + padding), &collector.segments),
+            0
+        );
+    }
+
+    #[test]
+    fn distance_field_encodes_inside_and_outside() {
+        let collector = square_outline();
+        let size = 10 + PADDING * 2;
+        let image = generate_distance_field(&collector.segments, size, size);
+
+        let center = image.get_pixel(size / 2, size / 2)[0];
+        let corner = image.get_pixel(0, 0)[0];
+        let edge = image.get_pixel(PADDING, size / 2)[0];
+
+        assert!(center > 200);
+        assert!(corner < 50);
+        assert!((128..=160).contains(&edge));
+        assert!(image.pixels().all(|pixel| pixel[0] == pixel[1] && pixel[1] == pixel[2]));
+    }
+}
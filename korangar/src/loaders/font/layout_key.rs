@@ -77,3 +77,75 @@ impl<'a> LayoutKeyRef<'a> {
         }
     }
 }
+
+/// Key of the shaping cache. Shaping doesn't depend on the width available
+/// for the text, so it is not part of the key.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct ShapingKey {
+    pub(super) text: String,
+    pub(super) default_color: cosmic_text::Color,
+    pub(super) highlight_color: cosmic_text::Color,
+    pub(super) font_size: FontSize,
+    pub(super) line_height_scale: f32,
+}
+
+impl Hash for ShapingKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.text.hash(state);
+        self.default_color.hash(state);
+        self.highlight_color.hash(state);
+        self.font_size.0.to_bits().hash(state);
+        self.line_height_scale.to_bits().hash(state);
+    }
+}
+
+impl Eq for ShapingKey {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct ShapingKeyRef<'a> {
+    pub(super) text: &'a str,
+    pub(super) default_color: cosmic_text::Color,
+    pub(super) highlight_color: cosmic_text::Color,
+    pub(super) font_size: FontSize,
+    pub(super) line_height_scale: f32,
+}
+
+impl Hash for ShapingKeyRef<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.text.hash(state);
+        self.default_color.hash(state);
+        self.highlight_color.hash(state);
+        self.font_size.0.to_bits().hash(state);
+        self.line_height_scale.to_bits().hash(state);
+    }
+}
+
+impl Eq for ShapingKeyRef<'_> {}
+
+impl PartialEq<ShapingKey> for ShapingKeyRef<'_> {
+    fn eq(&self, other: &ShapingKey) -> bool {
+        self.text == other.text
+            && self.default_color == other.default_color
+            && self.highlight_color == other.highlight_color
+            && self.font_size == other.font_size
+            && self.line_height_scale == other.line_height_scale
+    }
+}
+
+impl PartialEq<ShapingKeyRef<'_>> for ShapingKey {
+    fn eq(&self, other: &ShapingKeyRef<'_>) -> bool {
+        other.eq(self)
+    }
+}
+
+impl<'a> ShapingKeyRef<'a> {
+    pub(super) fn to_owned(self) -> ShapingKey {
+        ShapingKey {
+            text: self.text.to_string(),
+            default_color: self.default_color,
+            highlight_color: self.highlight_color,
+            font_size: self.font_size,
+            line_height_scale: self.line_height_scale,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 mod color_span_iterator;
 mod font_file;
 mod font_map_descriptor;
+mod glyph_atlas;
 mod layout_key;
 
 use std::hash::Hash;
@@ -12,9 +13,7 @@ use cosmic_text::fontdb::ID;
 use cosmic_text::{Attrs, Buffer, Family, FontSystem, Metrics, Shaping, fontdb};
 use hashbrown::HashMap;
 use image::{ImageBuffer, Rgba, RgbaImage, imageops};
-#[cfg(feature = "debug")]
-use korangar_container::CacheStatistics;
-use korangar_container::{Cacheable, SimpleCache};
+use korangar_container::{CacheStatistics, Cacheable, SimpleCache};
 #[cfg(feature = "debug")]
 use korangar_debug::logging::Colorize;
 #[cfg(feature = "debug")]
@@ -24,17 +23,21 @@ use korangar_interface::components::drop_down::DropDownItem;
 use korangar_interface::element::{ElementDisplay, StateElement};
 use rust_state::RustState;
 use serde::{Deserialize, Serialize};
+use wgpu::Queue;
 
 use self::color_span_iterator::ColorSpanIterator;
+use self::glyph_atlas::{DYNAMIC_GLYPH_AREA_HEIGHT, DynamicGlyphAtlas};
 use super::{GameFileLoader, TextureLoader};
 use crate::graphics::{Color, MAX_TEXTURE_SIZE, ScreenSize, Texture};
 use crate::loaders::font::font_file::FontFile;
-use crate::loaders::font::layout_key::{LayoutKey, LayoutKeyRef};
+use crate::loaders::font::layout_key::{LayoutKey, LayoutKeyRef, ShapingKey, ShapingKeyRef};
 use crate::loaders::rectangle::Rectangle;
 use crate::state::ClientState;
 
 const MAX_CACHE_COUNT: u32 = 2048;
 const MAX_CACHE_SIZE: usize = 32 << 20;
+const MAX_SHAPING_CACHE_COUNT: u32 = 1024;
+const MAX_SHAPING_CACHE_SIZE: usize = 16 << 20;
 
 struct CachedLayout {
     glyphs: Vec<GlyphInstruction>,
@@ -50,6 +53,26 @@ impl Cacheable for CachedLayout {
     }
 }
 
+/// Shaped text that can be laid out for different widths without shaping it
+/// again.
+struct CachedShaping {
+    buffer: Buffer,
+}
+
+impl Cacheable for CachedShaping {
+    fn size(&self) -> usize {
+        self.buffer
+            .lines
+            .iter()
+            .map(|line| {
+                // Approximation for the shaped glyphs, which we can't access directly.
+                line.text().len() * 2 * size_of::<cosmic_text::ShapeGlyph>()
+            })
+            .sum::<usize>()
+            + size_of::<Buffer>()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct FontSize(pub f32);
@@ -152,11 +175,14 @@ pub struct FontLoader {
     primary_font_family: String,
     font_map: Arc<Texture>,
     glyph_cache: HashMap<ID, Arc<HashMap<u16, GlyphCoordinate>>>,
+    /// Glyphs that are missing from the prebuilt font maps.
+    dynamic_glyphs: Mutex<DynamicGlyphAtlas>,
     layout_cache: Mutex<SimpleCache<LayoutKey, CachedLayout>>,
+    shaping_cache: Mutex<SimpleCache<ShapingKey, CachedShaping>>,
 }
 
 impl FontLoader {
-    pub fn new(fonts: &[String], game_file_loader: &GameFileLoader, texture_loader: &TextureLoader) -> Self {
+    pub fn new(fonts: &[String], game_file_loader: &GameFileLoader, texture_loader: &TextureLoader, queue: Queue) -> Self {
         assert_ne!(fonts.len(), 0, "no font defined");
 
         let mut font_system = FontSystem::new_with_locale_and_db(Self::system_locale(), fontdb::Database::new());
@@ -169,28 +195,40 @@ impl FontLoader {
 
         let primary_font_family = Self::extract_primary_font_family(&font_system, &fonts);
         let font_map_image_data = Self::merge_font_maps(&mut glyph_cache, &mut font_system, fonts);
+        let dynamic_glyph_area_top = font_map_image_data.height();
+        let font_map_image_data = Self::reserve_dynamic_glyph_area(&mut glyph_cache, font_map_image_data);
 
         let font_map = texture_loader.create_msdf("font map", font_map_image_data);
+        let dynamic_glyphs = DynamicGlyphAtlas::new(queue, font_map.clone(), dynamic_glyph_area_top);
 
         let layout_cache = SimpleCache::new(
             NonZeroU32::new(MAX_CACHE_COUNT).unwrap(),
             NonZeroUsize::new(MAX_CACHE_SIZE).unwrap(),
         );
+        let shaping_cache = SimpleCache::new(
+            NonZeroU32::new(MAX_SHAPING_CACHE_COUNT).unwrap(),
+            NonZeroUsize::new(MAX_SHAPING_CACHE_SIZE).unwrap(),
+        );
 
         Self {
             font_system: Mutex::new(font_system),
             primary_font_family,
             font_map,
             glyph_cache,
+            dynamic_glyphs: Mutex::new(dynamic_glyphs),
             layout_cache: Mutex::new(layout_cache),
+            shaping_cache: Mutex::new(shaping_cache),
         }
     }
 
-    #[cfg(feature = "debug")]
     pub fn cache_statistics(&self) -> CacheStatistics {
         self.layout_cache.lock().unwrap().statistics()
     }
 
+    pub fn shaping_cache_statistics(&self) -> CacheStatistics {
+        self.shaping_cache.lock().unwrap().statistics()
+    }
+
     fn system_locale() -> String {
         sys_locale::get_locale().unwrap_or_else(|| {
             #[cfg(feature = "debug")]
@@ -282,6 +320,39 @@ impl FontLoader {
         }
     }
 
+    /// Add empty rows at the bottom of the font map for glyphs that are
+    /// rasterized at runtime and adjust the texture coordinates of all
+    /// existing glyphs to the new height.
+    fn reserve_dynamic_glyph_area(glyph_cache: &mut HashMap<ID, Arc<HashMap<u16, GlyphCoordinate>>>, font_map: RgbaImage) -> RgbaImage {
+        let height = font_map.height();
+        let reserved_height = DYNAMIC_GLYPH_AREA_HEIGHT.min(MAX_TEXTURE_SIZE.saturating_sub(height));
+
+        if reserved_height == 0 {
+            return font_map;
+        }
+
+        let overall_height = height + reserved_height;
+        let scale_factor = height as f32 / overall_height as f32;
+
+        for glyphs in glyph_cache.values_mut() {
+            *glyphs = Arc::new(
+                glyphs
+                    .iter()
+                    .map(|(&index, &coordinate)| {
+                        let mut new_coordinate = coordinate;
+                        new_coordinate.texture_coordinate.min.y *= scale_factor;
+                        new_coordinate.texture_coordinate.max.y *= scale_factor;
+                        (index, new_coordinate)
+                    })
+                    .collect(),
+            );
+        }
+
+        let mut font_map_image_data = RgbaImage::new(font_map.width(), overall_height);
+        imageops::replace(&mut font_map_image_data, &font_map, 0, 0);
+        font_map_image_data
+    }
+
     pub fn get_text_dimensions(
         &self,
         text: &str,
@@ -340,10 +411,11 @@ impl FontLoader {
         available_width: Option<f32>,
         glyphs: Option<&mut Vec<GlyphInstruction>>,
     ) -> Vector2<f32> {
-        // TODO: NHA We currently call get_text_dimensions() with different
+        // NOTE: We currently call get_text_dimensions() with different
         //       "available_width" and "overflow_behavior" then when we call
         //       layout_text() for with the resulting available_width.
-        //       This results us caching two instead of one layout.
+        //       This results us caching two instead of one layout, but the
+        //       text is only shaped once thanks to the shaping cache.
         let key = LayoutKeyRef {
             text,
             default_color: default_color.into(),
@@ -410,21 +482,61 @@ impl FontLoader {
         let metrics = Metrics::relative(font_size.0, line_height_scale);
         let attributes = Attrs::new().family(Family::Name(&self.primary_font_family));
 
+        let key = ShapingKeyRef {
+            text,
+            default_color: default_color.into(),
+            highlight_color: highlight_color.into(),
+            font_size,
+            line_height_scale,
+        };
+
         // We try to hold the mutex lock as short as possible.
         let buffer = {
             let mut font_system = self.font_system.lock().unwrap();
-            let mut buffer = Buffer::new(&mut font_system, metrics);
-
-            buffer.set_size(&mut font_system, available_width, None);
-            buffer.set_rich_text(
-                &mut font_system,
-                ColorSpanIterator::new(text, default_color, highlight_color, attributes.clone()),
-                &attributes,
-                Shaping::Advanced,
-                None,
-            );
-
-            buffer
+            let cached_buffer = self
+                .shaping_cache
+                .lock()
+                .unwrap()
+                .get_with(&key, |k| k == &key)
+                .map(|shaping| shaping.buffer.clone());
+
+            match cached_buffer {
+                // Changing the size only updates the layout and doesn't shape the text again.
+                Some(mut buffer) => {
+                    buffer.set_size(&mut font_system, available_width, None);
+                    buffer
+                }
+                None => {
+                    let mut buffer = Buffer::new(&mut font_system, metrics);
+
+                    buffer.set_size(&mut font_system, available_width, None);
+                    buffer.set_rich_text(
+                        &mut font_system,
+                        ColorSpanIterator::new(text, default_color, highlight_color, attributes.clone()),
+                        &attributes,
+                        Shaping::Advanced,
+                        None,
+                    );
+
+                    let _result = self
+                        .shaping_cache
+                        .lock()
+                        .unwrap()
+                        .insert(key.to_owned(), CachedShaping { buffer: buffer.clone() });
+
+                    #[cfg(feature = "debug")]
+                    if let Err(error) = _result {
+                        print_debug!(
+                            "[{}] shaped text could not be added to cache. Text: '{}': {:?}",
+                            "error".red(),
+                            text,
+                            error
+                        );
+                    }
+
+                    buffer
+                }
+            }
         };
 
         let mut rendered_glyphs = Vec::new();
@@ -436,15 +548,19 @@ impl FontLoader {
             for layout_glyph in run.glyphs.iter() {
                 let physical_glyph = layout_glyph.physical((0.0, 0.0), 1.0);
 
-                let Some(glyph_coordinate) = self.glyph_cache.get(&layout_glyph.font_id).and_then(|font| {
-                    font.get(&layout_glyph.glyph_id).copied().map(|mut glyph| {
+                let Some(glyph_coordinate) = self
+                    .glyph_cache
+                    .get(&layout_glyph.font_id)
+                    .and_then(|font| font.get(&layout_glyph.glyph_id).copied())
+                    .or_else(|| self.get_dynamic_glyph(layout_glyph.font_id, layout_glyph.glyph_id))
+                    .map(|mut glyph| {
                         glyph.width *= font_size.0;
                         glyph.height *= font_size.0;
                         glyph.offset_left *= font_size.0;
                         glyph.offset_top *= font_size.0;
                         glyph
                     })
-                }) else {
+                else {
                     continue;
                 };
 
@@ -467,7 +583,17 @@ impl FontLoader {
         (Vector2::new(text_width, text_height), rendered_glyphs)
     }
 
-    /// The texture of the static font map.
+    /// Get a glyph that is missing from the prebuilt font maps, rasterizing it
+    /// if it is used for the first time.
+    fn get_dynamic_glyph(&self, font_id: ID, glyph_id: u16) -> Option<GlyphCoordinate> {
+        let mut font_system = self.font_system.lock().unwrap();
+        self.dynamic_glyphs
+            .lock()
+            .unwrap()
+            .get_or_rasterize(&mut font_system, font_id, glyph_id)
+    }
+
+    /// The texture of the font map.
     pub fn get_font_map(&self) -> &Texture {
         &self.font_map
     }
@@ -12,6 +12,7 @@ use image::{EncodableLayout, RgbaImage};
 use korangar_loaders::FileLoader;
 use rayon::prelude::*;
 
+use super::open_cache_lock_file;
 use crate::SHUTDOWN_SIGNAL;
 use crate::loaders::archive::seven_zip::{SevenZipArchive, SevenZipArchiveBuilder};
 use crate::loaders::archive::{Archive, Compression, Writable};
@@ -50,6 +51,21 @@ fn is_ffmpeg_available() -> bool {
 
 pub fn sync_cache_archive(game_file_loader: &GameFileLoader, texture_loader: Arc<TextureLoader>, game_file_hash: Hash) {
     println!("Starting sync of cache");
+
+    // Other clients read from the cache, so it can't be replaced while they are
+    // running. The lock is held until the sync is done.
+    let lock_file = match open_cache_lock_file() {
+        Ok(lock_file) => lock_file,
+        Err(error) => {
+            println!("Failed to open the cache lock file: {error}");
+            return;
+        }
+    };
+
+    if lock_file.try_lock().is_err() {
+        println!("The cache is in use by another client. Close all other clients before syncing the cache");
+        return;
+    }
     let ffmpeg_available = is_ffmpeg_available();
 
     if !ffmpeg_available {
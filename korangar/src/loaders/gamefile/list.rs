@@ -6,21 +6,35 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, RustState, StateElement)]
 pub(super) struct GameArchiveList {
+    /// Archives ordered by ascending priority. If multiple archives contain
+    /// the same file, the one from the archive listed last is used.
     pub archives: Vec<String>,
+    /// Directories of loose files that override the contents of all
+    /// archives, ordered by ascending priority like the archives. The name
+    /// of the directory is part of the asset path, so `data/` overrides the
+    /// files in the `data\` folder of a GRF.
+    #[serde(default = "GameArchiveList::default_overlay_directories")]
+    pub overlay_directories: Vec<String>,
 }
 
 impl Default for GameArchiveList {
     fn default() -> Self {
         Self {
             archives: Self::DEFAULT_FILES.iter().map(ToString::to_string).collect(),
+            overlay_directories: Self::default_overlay_directories(),
         }
     }
 }
 
 impl GameArchiveList {
     const DEFAULT_FILES: &'static [&'static str] = &["data.grf", "rdata.grf", "archive/"];
+    const DEFAULT_OVERLAY_DIRECTORIES: &'static [&'static str] = &["data/"];
     const FILE_NAME: &'static str = "client/game_archives.ron";
 
+    fn default_overlay_directories() -> Vec<String> {
+        Self::DEFAULT_OVERLAY_DIRECTORIES.iter().map(ToString::to_string).collect()
+    }
+
     pub(super) fn load() -> Self {
         #[cfg(feature = "debug")]
         print_debug!("loading game archive list from {}", Self::FILE_NAME.magenta());
@@ -40,3 +54,23 @@ impl GameArchiveList {
             })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::GameArchiveList;
+
+    #[test]
+    fn overlay_directories_default_to_data() {
+        let list: GameArchiveList = ron::from_str(r#"(archives: ["data.grf", "custom.grf"])"#).unwrap();
+
+        assert_eq!(list.archives, ["data.grf", "custom.grf"]);
+        assert_eq!(list.overlay_directories, ["data/"]);
+    }
+
+    #[test]
+    fn overlay_directories_can_be_disabled() {
+        let list: GameArchiveList = ron::from_str(r#"(archives: ["data.grf"], overlay_directories: [])"#).unwrap();
+
+        assert!(list.overlay_directories.is_empty());
+    }
+}
@@ -4,10 +4,12 @@
 
 mod cache;
 mod list;
+mod verify;
 
 use core::panic;
+use std::fs::{File, OpenOptions};
 use std::path::Path;
-use std::sync::RwLock;
+use std::sync::{Mutex, RwLock};
 
 use blake3::Hash;
 #[cfg(feature = "debug")]
@@ -16,6 +18,7 @@ use korangar_loaders::{FileLoader, FileNotFoundError};
 
 pub use self::cache::{sync_cache_archive, texture_file_dds_name, video_file_ivf_name};
 use self::list::GameArchiveList;
+pub use self::verify::{ArchiveIntegrity, BrokenReference, IntegrityReport, ReferenceProblem};
 use super::archive::folder::FolderArchive;
 use super::archive::native::{NativeArchive, NativeArchiveBuilder};
 use super::archive::{Archive, ArchiveType, Compression, Writable};
@@ -25,6 +28,9 @@ pub(crate) const CACHE_FILE_NAME: &str = "cache.7z";
 pub(crate) const LUA_ARCHIVE_FILE_NAME: &str = "lua_files.7z";
 
 pub(crate) const TEMPORARY_CACHE_FILE_NAME: &str = "cache.7z.tmp";
+/// Running clients hold a shared lock on this file while they read from the
+/// cache, and syncing the cache needs an exclusive lock.
+pub(crate) const CACHE_LOCK_FILE_NAME: &str = "cache.7z.lock";
 pub(crate) const HASH_FILE_PATH: &str = "game_file_hash.txt";
 
 /// This string is used to derive an initialization vector for the game file
@@ -33,13 +39,19 @@ pub(crate) const HASH_FILE_PATH: &str = "game_file_hash.txt";
 const GAME_FILE_DERIVE_KEY: &str = "korangar 2025-03-09 14:17:23 game file key v1";
 
 struct LoaderArchive {
+    /// Path that the archive was loaded from.
+    name: String,
     archive: Box<dyn Archive>,
     is_game_archive: bool,
 }
 
 /// Type implementing the game file loader.
 ///
-/// Currently, there are two types implementing
+/// Archives are queried in reverse order of loading, so archives that are
+/// loaded later override the files of earlier ones. Directories of loose
+/// files are loaded after all archives to override their contents.
+///
+/// Currently, there are three types implementing
 /// [`Archive`]:
 /// - [`NativeArchive`] - Retrieve assets from GRF files.
 /// - [`FolderArchive`] - Retrieve assets from an OS folder.
@@ -47,6 +59,8 @@ struct LoaderArchive {
 #[derive(Default)]
 pub struct GameFileLoader {
     archives: RwLock<Vec<LoaderArchive>>,
+    /// Keeps other clients from syncing the cache while it is in use.
+    cache_lock: Mutex<Option<File>>,
 }
 
 impl FileLoader for GameFileLoader {
@@ -70,8 +84,12 @@ impl GameFileLoader {
             .any(|archive| archive.archive.file_exists(path))
     }
 
-    fn add_archive(&self, archive: Box<dyn Archive>, is_game_archive: bool) {
-        self.archives.write().unwrap().insert(0, LoaderArchive { archive, is_game_archive });
+    fn add_archive(&self, name: &str, archive: Box<dyn Archive>, is_game_archive: bool) {
+        self.archives.write().unwrap().insert(0, LoaderArchive {
+            name: name.to_string(),
+            archive,
+            is_game_archive,
+        });
     }
 
     fn get_archive_type_by_path(path: &Path) -> ArchiveType {
@@ -108,9 +126,24 @@ impl GameFileLoader {
 
         game_archive_list.archives.iter().for_each(|path| {
             let game_archive = Self::load_archive_from_path(path);
-            self.add_archive(game_archive, true);
+            self.add_archive(path, game_archive, true);
         });
 
+        // Overlays are added after the archives, so their loose files take
+        // precedence. They still count as game archives, since they change the
+        // assets that end up in the cache.
+        game_archive_list
+            .overlay_directories
+            .iter()
+            .filter(|path| Path::new(path).is_dir())
+            .for_each(|path| {
+                #[cfg(feature = "debug")]
+                print_debug!("loading overlay directory {}", path.magenta());
+
+                let overlay = FolderArchive::overlay_from_path(Path::new(path));
+                self.add_archive(path, Box::new(overlay), true);
+            });
+
         #[cfg(feature = "debug")]
         timer.stop();
     }
@@ -138,7 +171,7 @@ impl GameFileLoader {
         }
 
         let lua_archive = Self::load_archive_from_path(LUA_ARCHIVE_FILE_NAME);
-        self.add_archive(lua_archive, false);
+        self.add_archive(LUA_ARCHIVE_FILE_NAME, lua_archive, false);
     }
 
     pub fn get_files_with_extension(&self, extensions: &[&str]) -> Vec<String> {
@@ -230,6 +263,20 @@ impl GameFileLoader {
             return;
         }
 
+        let Ok(lock_file) = open_cache_lock_file() else {
+            #[cfg(feature = "debug")]
+            print_debug!("[{}] Can't open cache lock file. Using empty cache", "error".red());
+            return;
+        };
+
+        if lock_file.try_lock_shared().is_err() {
+            #[cfg(feature = "debug")]
+            print_debug!("[{}] Cache is being synced by another client. Using empty cache", "error".red());
+            return;
+        }
+
+        *self.cache_lock.lock().unwrap() = Some(lock_file);
+
         let archive = Box::new(SevenZipArchive::from_path(path));
 
         let Some(hash_file) = archive.get_file_by_path(HASH_FILE_PATH) else {
@@ -249,10 +296,18 @@ impl GameFileLoader {
             print_debug!("[{}] Cache is out of sync. Please re-sync or delete the cache", "error".red());
         }
 
-        self.add_archive(archive, false);
+        self.add_archive(CACHE_FILE_NAME, archive, false);
     }
 }
 
+fn open_cache_lock_file() -> std::io::Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(CACHE_LOCK_FILE_NAME)
+}
+
 pub fn fix_broken_texture_file_endings(path: &str) -> String {
     let mut path = path.to_string();
 
@@ -0,0 +1,278 @@
+//! Integrity verification of the game files. Checks the entries of all
+//! archives and makes sure that every file referenced by an installed map can
+//! be loaded, which helps to track down black textures and fallback models.
+
+use std::fmt::{Display, Formatter};
+
+use hashbrown::HashSet;
+use korangar_loaders::FileLoader;
+use ragnarok_bytes::{ByteReader, FromBytes};
+use ragnarok_formats::map::{GroundData, MapData};
+use ragnarok_formats::model::ModelData;
+use ragnarok_formats::version::InternalVersion;
+
+use super::{GameFileLoader, fix_broken_texture_file_endings};
+use crate::loaders::ModelLoader;
+use crate::loaders::archive::CorruptFile;
+
+/// The reason why a file referenced by a map can't be used.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReferenceProblem {
+    /// The file is not in any of the archives.
+    Missing,
+    /// The file exists but can't be parsed.
+    Unreadable(String),
+}
+
+/// A file referenced by a map that can't be used.
+#[derive(Debug)]
+pub struct BrokenReference {
+    pub map: String,
+    pub path: String,
+    pub problem: ReferenceProblem,
+}
+
+/// Corrupt files of a single archive.
+#[derive(Debug)]
+pub struct ArchiveIntegrity {
+    pub archive: String,
+    pub checked_files: usize,
+    pub corrupt_files: Vec<CorruptFile>,
+}
+
+/// Result of [`GameFileLoader::verify_integrity`].
+#[derive(Debug, Default)]
+pub struct IntegrityReport {
+    pub archives: Vec<ArchiveIntegrity>,
+    pub checked_maps: usize,
+    pub broken_references: Vec<BrokenReference>,
+}
+
+impl IntegrityReport {
+    pub fn is_healthy(&self) -> bool {
+        self.broken_references.is_empty() && self.archives.iter().all(|archive| archive.corrupt_files.is_empty())
+    }
+}
+
+impl Display for IntegrityReport {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(formatter, "Archives:")?;
+
+        for archive in &self.archives {
+            writeln!(
+                formatter,
+                "  {}: {} files checked, {} corrupt",
+                archive.archive,
+                archive.checked_files,
+                archive.corrupt_files.len()
+            )?;
+
+            for corrupt_file in &archive.corrupt_files {
+                writeln!(formatter, "    {} ({})", corrupt_file.path, corrupt_file.reason)?;
+            }
+        }
+
+        writeln!(
+            formatter,
+            "Maps: {} checked, {} broken references",
+            self.checked_maps,
+            self.broken_references.len()
+        )?;
+
+        for reference in &self.broken_references {
+            match &reference.problem {
+                ReferenceProblem::Missing => writeln!(formatter, "  {}: missing {}", reference.map, reference.path)?,
+                ReferenceProblem::Unreadable(reason) => {
+                    writeln!(formatter, "  {}: unreadable {} ({})", reference.map, reference.path, reason)?
+                }
+            }
+        }
+
+        match self.is_healthy() {
+            true => writeln!(formatter, "No problems found"),
+            false => writeln!(formatter, "Problems found"),
+        }
+    }
+}
+
+/// Collects the broken references of a single map. Files that were already
+/// checked for a previous map are skipped, so shared models and textures are
+/// only reported once.
+struct MapChecker<'a> {
+    game_file_loader: &'a GameFileLoader,
+    checked_paths: HashSet<String>,
+    broken_references: Vec<BrokenReference>,
+}
+
+impl MapChecker<'_> {
+    fn check_map(&mut self, map_file: &str) {
+        let map = map_file
+            .strip_prefix("data\\")
+            .and_then(|path| path.strip_suffix(".rsw"))
+            .unwrap_or(map_file)
+            .to_string();
+
+        let Some(map_data) = self.parse::<MapData>(&map, map_file) else {
+            return;
+        };
+
+        self.check_exists(&map, &format!("data\\{}", map_data.gat_file));
+
+        if let Some(ground_data) = self.parse::<GroundData>(&map, &format!("data\\{}", map_data.ground_file)) {
+            for texture in &ground_data.textures {
+                self.check_texture(&map, texture);
+            }
+        }
+
+        for object in &map_data.resources.objects {
+            self.check_model(&map, &object.model_name);
+        }
+    }
+
+    fn check_model(&mut self, map: &str, model_name: &str) {
+        let Some(model_data) = self.parse::<ModelData>(map, &format!("data\\model\\{model_name}")) else {
+            return;
+        };
+
+        let version: InternalVersion = model_data.version.into();
+
+        if version.equals_or_above(2, 4) {
+            self.report(
+                map,
+                &format!("data\\model\\{model_name}"),
+                ReferenceProblem::Unreadable(format!("unsupported version {version}")),
+            );
+            return;
+        }
+
+        for texture in ModelLoader::collect_versioned_texture_names(&version, &model_data) {
+            self.check_texture(map, &texture);
+        }
+    }
+
+    fn check_texture(&mut self, map: &str, texture: &str) {
+        self.check_exists(map, &fix_broken_texture_file_endings(&format!("data\\texture\\{texture}")));
+    }
+
+    fn check_exists(&mut self, map: &str, path: &str) {
+        let path = path.to_lowercase();
+
+        if self.checked_paths.insert(path.clone()) && !self.game_file_loader.file_exists(&path) {
+            self.report(map, &path, ReferenceProblem::Missing);
+        }
+    }
+
+    /// Parses a referenced file. Files that were already parsed for a previous
+    /// map return `None`, since everything they reference was checked already.
+    fn parse<Data: FromBytes>(&mut self, map: &str, path: &str) -> Option<Data> {
+        let path = path.to_lowercase();
+
+        if !self.checked_paths.insert(path.clone()) {
+            return None;
+        }
+
+        let Ok(bytes) = self.game_file_loader.get(&path) else {
+            self.report(map, &path, ReferenceProblem::Missing);
+            return None;
+        };
+
+        let mut byte_reader: ByteReader<Option<InternalVersion>> = ByteReader::with_default_metadata(&bytes);
+
+        match Data::from_bytes(&mut byte_reader) {
+            Ok(data) => Some(data),
+            Err(error) => {
+                self.report(map, &path, ReferenceProblem::Unreadable(format!("{error:?}")));
+                None
+            }
+        }
+    }
+
+    fn report(&mut self, map: &str, path: &str, problem: ReferenceProblem) {
+        self.broken_references.push(BrokenReference {
+            map: map.to_string(),
+            path: path.to_string(),
+            problem,
+        });
+    }
+}
+
+impl GameFileLoader {
+    /// Verify the integrity of all loaded archives and of the files that the
+    /// installed maps reference. This reads every file, so it takes a while.
+    pub fn verify_integrity(&self) -> IntegrityReport {
+        let archives = self
+            .archives
+            .read()
+            .unwrap()
+            .iter()
+            .rev()
+            .map(|archive| {
+                let mut corrupt_files = Vec::new();
+                let checked_files = archive.archive.verify(&mut corrupt_files);
+
+                ArchiveIntegrity {
+                    archive: archive.name.clone(),
+                    checked_files,
+                    corrupt_files,
+                }
+            })
+            .collect();
+
+        let map_files = self.get_files_with_extension(&[".rsw"]);
+
+        let mut map_checker = MapChecker {
+            game_file_loader: self,
+            checked_paths: HashSet::new(),
+            broken_references: Vec::new(),
+        };
+
+        map_files.iter().for_each(|map_file| map_checker.check_map(map_file));
+
+        IntegrityReport {
+            archives,
+            checked_maps: map_files.len(),
+            broken_references: map_checker.broken_references,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ArchiveIntegrity, BrokenReference, IntegrityReport, ReferenceProblem};
+    use crate::loaders::archive::CorruptFile;
+
+    #[test]
+    fn empty_report_is_healthy() {
+        let report = IntegrityReport::default();
+
+        assert!(report.is_healthy());
+        assert!(report.to_string().ends_with("No problems found\n"));
+    }
+
+    #[test]
+    fn report_lists_problems() {
+        let report = IntegrityReport {
+            archives: vec![ArchiveIntegrity {
+                archive: "data.grf".to_string(),
+                checked_files: 2,
+                corrupt_files: vec![CorruptFile {
+                    path: "data\\texture\\broken.bmp".to_string(),
+                    reason: "corrupt deflate stream".to_string(),
+                }],
+            }],
+            checked_maps: 1,
+            broken_references: vec![BrokenReference {
+                map: "prontera".to_string(),
+                path: "data\\model\\missing.rsm".to_string(),
+                problem: ReferenceProblem::Missing,
+            }],
+        };
+
+        let text = report.to_string();
+
+        assert!(!report.is_healthy());
+        assert!(text.contains("data.grf: 2 files checked, 1 corrupt"));
+        assert!(text.contains("data\\texture\\broken.bmp (corrupt deflate stream)"));
+        assert!(text.contains("prontera: missing data\\model\\missing.rsm"));
+    }
+}
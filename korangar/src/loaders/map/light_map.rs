@@ -0,0 +1,134 @@
+use cgmath::Vector2;
+use image::{Rgba, RgbaImage};
+use ragnarok_formats::map::GroundData;
+
+use crate::graphics::NO_LIGHT_MAP_COORDINATES;
+
+/// Light maps have a border of one texel around them, that only exists so
+/// that bilinear filtering blends into the neighboring surfaces.
+const LIGHT_MAP_BORDER: f32 = 1.0;
+
+/// All light maps of the ground packed into a single texture. Each light map
+/// stores the baked shadow intensity in the alpha channel and the baked light
+/// color in the color channels.
+pub struct LightMapAtlas {
+    light_map_count: usize,
+    light_map_width: usize,
+    light_map_height: usize,
+    columns: usize,
+    rows: usize,
+}
+
+impl LightMapAtlas {
+    /// Returns `None` if the ground data has no light maps.
+    pub fn new(ground_data: &GroundData) -> Option<Self> {
+        let light_map_count = usize::try_from(ground_data.light_map_count).ok()?;
+        let light_map_width = usize::try_from(ground_data.light_map_width).ok()?;
+        let light_map_height = usize::try_from(ground_data.light_map_height).ok()?;
+        let light_maps = ground_data.light_maps.as_ref()?;
+
+        if light_map_count == 0 || light_map_width == 0 || light_map_height == 0 {
+            return None;
+        }
+
+        if light_maps.len() < light_map_count * light_map_width * light_map_height * 4 {
+            return None;
+        }
+
+        let columns = (light_map_count as f32).sqrt().ceil() as usize;
+        let rows = light_map_count.div_ceil(columns);
+
+        Some(Self {
+            light_map_count,
+            light_map_width,
+            light_map_height,
+            columns,
+            rows,
+        })
+    }
+
+    pub fn create_image(&self, ground_data: &GroundData) -> RgbaImage {
+        let light_maps = ground_data.light_maps.as_deref().unwrap_or_default();
+        let texel_count = self.light_map_width * self.light_map_height;
+
+        let mut image = RgbaImage::new(
+            (self.columns * self.light_map_width) as u32,
+            (self.rows * self.light_map_height) as u32,
+        );
+
+        for index in 0..self.light_map_count {
+            // Every light map is made up of the shadow intensities followed by the RGB
+            // colors.
+            let data = &light_maps[index * texel_count * 4..(index + 1) * texel_count * 4];
+            let (intensities, colors) = data.split_at(texel_count);
+
+            let offset_x = (index % self.columns) * self.light_map_width;
+            let offset_y = (index / self.columns) * self.light_map_height;
+
+            for texel in 0..texel_count {
+                let x = offset_x + texel % self.light_map_width;
+                let y = offset_y + texel / self.light_map_width;
+                let color = &colors[texel * 3..texel * 3 + 3];
+
+                image.put_pixel(x as u32, y as u32, Rgba([color[0], color[1], color[2], intensities[texel]]));
+            }
+        }
+
+        image
+    }
+
+    /// Texture coordinates of the four corners of a surface in the same order
+    /// as the surface vertices.
+    pub fn coordinates(&self, light_map_index: i16) -> [Vector2<f32>; 4] {
+        let Some(index) = usize::try_from(light_map_index).ok().filter(|index| *index < self.light_map_count) else {
+            return [NO_LIGHT_MAP_COORDINATES; 4];
+        };
+
+        let atlas_width = (self.columns * self.light_map_width) as f32;
+        let atlas_height = (self.rows * self.light_map_height) as f32;
+
+        let left = ((index % self.columns) * self.light_map_width) as f32 + LIGHT_MAP_BORDER;
+        let top = ((index / self.columns) * self.light_map_height) as f32 + LIGHT_MAP_BORDER;
+        let right = ((index % self.columns + 1) * self.light_map_width) as f32 - LIGHT_MAP_BORDER;
+        let bottom = ((index / self.columns + 1) * self.light_map_height) as f32 - LIGHT_MAP_BORDER;
+
+        [
+            Vector2::new(left / atlas_width, top / atlas_height),
+            Vector2::new(right / atlas_width, top / atlas_height),
+            Vector2::new(left / atlas_width, bottom / atlas_height),
+            Vector2::new(right / atlas_width, bottom / atlas_height),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::Vector2;
+
+    use super::LightMapAtlas;
+    use crate::graphics::NO_LIGHT_MAP_COORDINATES;
+
+    fn atlas() -> LightMapAtlas {
+        LightMapAtlas {
+            light_map_count: 3,
+            light_map_width: 8,
+            light_map_height: 8,
+            columns: 2,
+            rows: 2,
+        }
+    }
+
+    #[test]
+    fn coordinates_skip_border() {
+        let coordinates = atlas().coordinates(1);
+
+        assert_eq!(coordinates[0], Vector2::new(9.0 / 16.0, 1.0 / 16.0));
+        assert_eq!(coordinates[3], Vector2::new(15.0 / 16.0, 7.0 / 16.0));
+    }
+
+    #[test]
+    fn invalid_index_has_no_light_map() {
+        assert_eq!(atlas().coordinates(-1), [NO_LIGHT_MAP_COORDINATES; 4]);
+        assert_eq!(atlas().coordinates(3), [NO_LIGHT_MAP_COORDINATES; 4]);
+    }
+}
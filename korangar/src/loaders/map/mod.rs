@@ -1,3 +1,5 @@
+mod light_map;
+mod thumbnail;
 mod vertices;
 mod water_plane;
 
@@ -13,20 +15,35 @@ use korangar_container::SimpleSlab;
 use korangar_debug::logging::Timer;
 use korangar_loaders::FileLoader;
 use ragnarok_bytes::{ByteReader, FromBytes};
+#[cfg(feature = "debug")]
+use ragnarok_formats::map::Tile;
 use ragnarok_formats::map::{GatData, GroundData, MapData, MapResources};
 use ragnarok_formats::version::InternalVersion;
 use wgpu::{BufferUsages, Device, Queue};
 
+use self::light_map::LightMapAtlas;
+pub use self::thumbnail::MapThumbnailLoader;
+#[cfg(feature = "debug")]
+use self::vertices::generate_tile_meshes;
 use self::vertices::{generate_tile_vertices, ground_vertices};
 use self::water_plane::generate_water_plane;
 use super::error::LoadError;
+#[cfg(feature = "debug")]
+use crate::graphics::TileVertex;
 use crate::graphics::{BindlessSupport, Buffer, ModelVertex, TextureSet};
-use crate::loaders::{GameFileLoader, ModelLoader, TextureLoader, TextureSetBuilder, VideoLoader, split_mesh_by_texture};
-use crate::world::{Library, LightSourceKey, Lighting, Model, SubMesh, Video};
+use crate::loaders::{
+    GameFileLoader, ModelLoader, TextureLoader, TextureSetBuilder, VideoLoader, split_mesh_by_texture, split_mesh_by_texture_group,
+};
+#[cfg(feature = "debug")]
+use crate::world::ModelPreview;
+use crate::world::{Library, LightSourceKey, Lighting, Model, Sky, SubMesh, Video};
 use crate::{EffectSourceExt, LightSourceExt, Map, Object, ObjectKey, SoundSourceExt};
 
 pub const GROUND_TILE_SIZE: f32 = 10.0;
 pub const GAT_TILE_SIZE: f32 = 5.0;
+/// Height that each object is offset by, relative to the previous object, to
+/// avoid depth buffer fighting.
+pub const OBJECT_DEPTH_OFFSET: f32 = 0.0005;
 
 #[cfg(feature = "debug")]
 fn assert_byte_reader_empty<Meta>(mut byte_reader: ByteReader<Meta>, file_name: &str) {
@@ -47,6 +64,7 @@ pub struct MapLoader {
     game_file_loader: Arc<GameFileLoader>,
     audio_engine: Arc<AudioEngine<GameFileLoader>>,
     bindless_support: BindlessSupport,
+    texture_group_size: u32,
 }
 
 impl MapLoader {
@@ -56,6 +74,7 @@ impl MapLoader {
         game_file_loader: Arc<GameFileLoader>,
         audio_engine: Arc<AudioEngine<GameFileLoader>>,
         bindless_support: BindlessSupport,
+        texture_group_size: u32,
     ) -> Self {
         Self {
             device,
@@ -63,6 +82,7 @@ impl MapLoader {
             game_file_loader,
             audio_engine,
             bindless_support,
+            texture_group_size,
         }
     }
 }
@@ -84,8 +104,8 @@ impl MapLoader {
         let map_file_name = format!("data\\{}.rsw", &resource_file);
         let mut map_data: MapData = parse_generic_data(&map_file_name, &self.game_file_loader)?;
 
-        // TODO: NHA Implement sky rendering
-        let _map_sky_data = library.get_map_sky_data_from_resource_file(&resource_file);
+        let sky = Sky::new(library.get_map_sky_data_from_resource_file(&resource_file));
+        let vertical_culling = library.is_indoor_map(&resource_file);
 
         let ground_file = format!("data\\{}", map_data.ground_file);
         let ground_data: GroundData = parse_generic_data(&ground_file, &self.game_file_loader)?;
@@ -102,7 +122,13 @@ impl MapLoader {
         #[cfg(not(feature = "debug"))]
         let (_, _, tile_picker_vertices, tile_picker_indices) = generate_tile_vertices(&mut gat_data);
 
-        let (mut model_vertices, mut model_indices, ground_textures) = ground_vertices(&ground_data, &mut texture_set_builder);
+        let light_map_atlas = LightMapAtlas::new(&ground_data);
+        let light_map_texture = light_map_atlas
+            .as_ref()
+            .map(|atlas| texture_loader.create_color(&format!("{resource_file} light map"), atlas.create_image(&ground_data), false));
+
+        let (mut model_vertices, mut model_indices, ground_textures) =
+            ground_vertices(&ground_data, &mut texture_set_builder, light_map_atlas.as_ref());
 
         // TODO: NHA Support reading water planes from GND files (version >= 2.6).
         let water_plane = generate_water_plane(
@@ -116,13 +142,7 @@ impl MapLoader {
 
         let sub_meshes = match self.bindless_support {
             BindlessSupport::Full | BindlessSupport::Limited => {
-                vec![SubMesh {
-                    index_offset: 0,
-                    index_count: model_indices.len() as u32,
-                    base_vertex: 0,
-                    texture_index: 0,
-                    transparent: false,
-                }]
+                split_mesh_by_texture_group(&mut model_vertices, &mut model_indices, self.texture_group_size, None)
             }
             BindlessSupport::None => {
                 let ground_texture_transparencies = ground_textures
@@ -140,18 +160,7 @@ impl MapLoader {
         };
 
         #[cfg(feature = "debug")]
-        let tile_submeshes = match self.bindless_support {
-            BindlessSupport::Full | BindlessSupport::Limited => {
-                vec![SubMesh {
-                    index_offset: 0,
-                    index_count: tile_indices.len() as u32,
-                    base_vertex: 0,
-                    texture_index: 0,
-                    transparent: true,
-                }]
-            }
-            BindlessSupport::None => split_mesh_by_texture(&tile_vertices, &mut tile_indices, None, None, None),
-        };
+        let tile_submeshes = create_tile_submeshes(self.bindless_support, &tile_vertices, &mut tile_indices);
 
         #[cfg(feature = "debug")]
         let tile_vertex_buffer = Arc::new(create_vertex_buffer(
@@ -259,11 +268,15 @@ impl MapLoader {
         let background_music_track_name = self.audio_engine.get_track_for_map(&map_file_name);
 
         let map = Map::new(
+            resource_file,
             gat_data.map_width as u16,
             gat_data.map_height as u16,
             object_kdtree.root_boundary(),
             lighting,
+            sky,
+            light_map_texture,
             water_plane,
+            vertical_culling,
             gat_data.tiles,
             sub_meshes,
             vertex_buffer,
@@ -288,6 +301,10 @@ impl MapLoader {
             videos,
             #[cfg(feature = "debug")]
             map_data_clone,
+            #[cfg(feature = "debug")]
+            map_offset(&ground_data),
+            #[cfg(feature = "debug")]
+            gat_data.version,
         );
 
         #[cfg(feature = "debug")]
@@ -296,6 +313,60 @@ impl MapLoader {
         Ok(Box::new(map))
     }
 
+    /// Load a single model with its own buffers and textures, so it can be
+    /// inspected in the model viewer.
+    #[cfg(feature = "debug")]
+    pub fn load_model_preview(
+        &self,
+        model_file: &str,
+        model_loader: &ModelLoader,
+        texture_loader: Arc<TextureLoader>,
+        video_loader: Arc<VideoLoader>,
+    ) -> Result<ModelPreview, LoadError> {
+        let mut texture_set_builder = TextureSetBuilder::new(texture_loader, video_loader, model_file);
+        let mut model_vertices = Vec::new();
+        let mut model_indices = Vec::new();
+
+        let model = model_loader.load(
+            &mut texture_set_builder,
+            &mut model_vertices,
+            &mut model_indices,
+            model_file,
+            false,
+        )?;
+
+        // Tint every vertex by its normal, mapping each axis from [-1, 1] to [0, 1].
+        let normal_vertices: Vec<ModelVertex> = model_vertices
+            .iter()
+            .map(|vertex| ModelVertex {
+                color: vertex.normal.map(|component| component * 0.5 + 0.5),
+                ..*vertex
+            })
+            .collect();
+        let normal_vertex_buffer = Arc::new(create_vertex_buffer(
+            &self.device,
+            &self.queue,
+            model_file,
+            "normal vertices",
+            &normal_vertices,
+        ));
+
+        let BufferAndTextures {
+            vertex_buffer,
+            index_buffer,
+            texture_set,
+            ..
+        } = self.build_buffer_and_textures(model_file, texture_set_builder, model_vertices, model_indices);
+
+        Ok(ModelPreview::new(
+            model,
+            vertex_buffer,
+            normal_vertex_buffer,
+            index_buffer,
+            texture_set,
+        ))
+    }
+
     fn build_buffer_and_textures(
         &self,
         resource_file: &str,
@@ -337,16 +408,23 @@ struct BufferAndTextures {
     videos: Mutex<Vec<Video>>,
 }
 
-/// We shift the map resources, so that the world coordinate system's origin has
-/// the same origin as the tile grids.
-fn apply_map_offset(ground_data: &GroundData, resources: &mut MapResources) {
-    let offset = Vector3::new(
+/// Offset between the resource positions inside the map file and the world.
+fn map_offset(ground_data: &GroundData) -> Vector3<f32> {
+    Vector3::new(
         (ground_data.width as f32 * GROUND_TILE_SIZE) / 2.0,
         0.0,
         (ground_data.height as f32 * GROUND_TILE_SIZE) / 2.0,
-    );
+    )
+}
 
-    resources.objects.iter_mut().for_each(|object| object.transform.position += offset);
+/// We shift the map resources, so that the world coordinate system's origin has
+/// the same origin as the tile grids.
+fn apply_map_offset(ground_data: &GroundData, resources: &mut MapResources) {
+    let offset = map_offset(ground_data);
+
+    resources.objects.iter_mut().enumerate().for_each(|(index, object)| {
+        object.transform.position += offset + Vector3::new(0.0, OBJECT_DEPTH_OFFSET, 0.0) * index as f32;
+    });
     resources
         .sound_sources
         .iter_mut()
@@ -373,6 +451,63 @@ fn parse_generic_data<Data: FromBytes>(resource_file: &str, game_file_loader: &G
     Ok(data)
 }
 
+/// Debug and picker meshes of the tiles of a map.
+#[cfg(feature = "debug")]
+pub struct TileMeshes {
+    pub vertex_buffer: Arc<Buffer<ModelVertex>>,
+    pub index_buffer: Arc<Buffer<u32>>,
+    pub submeshes: Vec<SubMesh>,
+    pub picker_vertex_buffer: Buffer<TileVertex>,
+    pub picker_index_buffer: Buffer<u32>,
+}
+
+/// Recreate the tile meshes of a map after its tiles were modified. The tile
+/// heights are expected to be flipped already.
+#[cfg(feature = "debug")]
+pub fn create_tile_meshes(
+    device: &Device,
+    queue: &Queue,
+    bindless_support: BindlessSupport,
+    resource_file: &str,
+    tiles: &[Tile],
+    map_width: usize,
+    include_blocked_tiles: bool,
+) -> TileMeshes {
+    let (tile_vertices, mut tile_indices, tile_picker_vertices, tile_picker_indices) =
+        generate_tile_meshes(tiles, map_width, include_blocked_tiles);
+    let submeshes = create_tile_submeshes(bindless_support, &tile_vertices, &mut tile_indices);
+
+    TileMeshes {
+        vertex_buffer: Arc::new(create_vertex_buffer(
+            device,
+            queue,
+            resource_file,
+            "tile vertex",
+            &tile_vertices,
+        )),
+        index_buffer: Arc::new(create_index_buffer(device, queue, resource_file, "tile index ", &tile_indices)),
+        submeshes,
+        picker_vertex_buffer: create_vertex_buffer(device, queue, resource_file, "tile picker vertex", &tile_picker_vertices),
+        picker_index_buffer: create_index_buffer(device, queue, resource_file, "tile picker index", &tile_picker_indices),
+    }
+}
+
+#[cfg(feature = "debug")]
+fn create_tile_submeshes(bindless_support: BindlessSupport, tile_vertices: &[ModelVertex], tile_indices: &mut [u32]) -> Vec<SubMesh> {
+    match bindless_support {
+        BindlessSupport::Full | BindlessSupport::Limited => {
+            vec![SubMesh {
+                index_offset: 0,
+                index_count: tile_indices.len() as u32,
+                base_vertex: 0,
+                texture_index: 0,
+                transparent: true,
+            }]
+        }
+        BindlessSupport::None => split_mesh_by_texture(tile_vertices, tile_indices, None, None, None),
+    }
+}
+
 fn create_vertex_buffer<T: Pod>(device: &Device, queue: &Queue, resource: &str, label: &str, vertices: &[T]) -> Buffer<T> {
     Buffer::with_data(
         device,
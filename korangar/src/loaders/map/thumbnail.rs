@@ -0,0 +1,232 @@
+//! Small top-down thumbnails of maps. Thumbnails are rendered from the
+//! ground of the map, where every ground tile becomes a single pixel that is
+//! colored with the average color of its texture and shaded by its slope.
+//! Rendered thumbnails are saved to disk, so every map is only rendered once.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use hashbrown::HashMap;
+use image::imageops::FilterType;
+use image::{ImageFormat, Rgba, RgbaImage};
+#[cfg(feature = "debug")]
+use korangar_debug::logging::{Colorize, Timer, print_debug};
+use korangar_loaders::FileLoader;
+use ragnarok_bytes::{ByteReader, FromBytes};
+use ragnarok_formats::map::{GroundData, GroundTile, MapData};
+use ragnarok_formats::version::InternalVersion;
+
+use crate::graphics::Texture;
+use crate::loaders::error::LoadError;
+use crate::loaders::{GameFileLoader, TextureLoader};
+
+/// Directory that rendered thumbnails are saved to.
+const THUMBNAIL_DIRECTORY: &str = "client/map_thumbnails";
+/// Size of the longer side of a thumbnail in pixels.
+const THUMBNAIL_SIZE: u32 = 128;
+/// How much the slope of a tile brightens or darkens it.
+const SLOPE_SHADING: f32 = 0.03;
+/// Color that submerged tiles are tinted with.
+const WATER_COLOR: [f32; 3] = [40.0, 90.0, 150.0];
+
+pub struct MapThumbnailLoader {
+    game_file_loader: Arc<GameFileLoader>,
+    texture_loader: Arc<TextureLoader>,
+    cache: Mutex<HashMap<String, Arc<Texture>>>,
+}
+
+impl MapThumbnailLoader {
+    pub fn new(game_file_loader: Arc<GameFileLoader>, texture_loader: Arc<TextureLoader>) -> Self {
+        Self {
+            game_file_loader,
+            texture_loader,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn thumbnail_path(map_name: &str) -> PathBuf {
+        PathBuf::from(THUMBNAIL_DIRECTORY).join(format!("{map_name}.png"))
+    }
+
+    /// Get the thumbnail of a map if it was already loaded.
+    pub fn get(&self, map_name: &str) -> Option<Arc<Texture>> {
+        self.cache.lock().unwrap().get(map_name).cloned()
+    }
+
+    /// Load the thumbnail of a map from disk. If it doesn't exist yet, it is
+    /// rendered and saved.
+    pub fn load(&self, map_name: &str) -> Result<Arc<Texture>, LoadError> {
+        let path = Self::thumbnail_path(map_name);
+
+        let image = match image::open(&path) {
+            Ok(image) => image.to_rgba8(),
+            Err(_) => {
+                let image = self.render(map_name)?;
+
+                if let Err(_error) = std::fs::create_dir_all(THUMBNAIL_DIRECTORY)
+                    .and_then(|_| image.save_with_format(&path, ImageFormat::Png).map_err(std::io::Error::other))
+                {
+                    #[cfg(feature = "debug")]
+                    print_debug!(
+                        "[{}] failed to save map thumbnail to {}: {:?}",
+                        "warning".yellow(),
+                        path.display().magenta(),
+                        _error
+                    );
+                }
+
+                image
+            }
+        };
+
+        let texture = self.texture_loader.create_color(&format!("{map_name} thumbnail"), image, false);
+
+        self.cache.lock().unwrap().insert(map_name.to_string(), texture.clone());
+
+        Ok(texture)
+    }
+
+    fn render(&self, map_name: &str) -> Result<RgbaImage, LoadError> {
+        #[cfg(feature = "debug")]
+        let timer = Timer::new_dynamic(format!("render map thumbnail of {}", map_name.magenta()));
+
+        let map_data: MapData = self.parse(&format!("data\\{map_name}.rsw"))?;
+        let ground_data: GroundData = self.parse(&format!("data\\{}", map_data.ground_file))?;
+
+        let texture_colors: Vec<[f32; 3]> = ground_data
+            .textures
+            .iter()
+            .map(
+                |texture_name| match self.texture_loader.load_texture_data(texture_name, false) {
+                    Ok((image, _)) => average_color(&image),
+                    Err(_) => [0.0; 3],
+                },
+            )
+            .collect();
+
+        // Ground heights point downwards, so we flip the sign of the water level
+        // to compare it against the tile heights.
+        let water_level = map_data
+            .water_settings
+            .as_ref()
+            .map(|water_settings| -water_settings.water_level.unwrap_or(0.0));
+
+        let image = render_ground(&ground_data, &texture_colors, water_level);
+
+        let scale = THUMBNAIL_SIZE as f32 / image.width().max(image.height()).max(1) as f32;
+        let width = ((image.width() as f32 * scale) as u32).max(1);
+        let height = ((image.height() as f32 * scale) as u32).max(1);
+        let thumbnail = image::imageops::resize(&image, width, height, FilterType::Triangle);
+
+        #[cfg(feature = "debug")]
+        timer.stop();
+
+        Ok(thumbnail)
+    }
+
+    fn parse<Data: FromBytes>(&self, path: &str) -> Result<Data, LoadError> {
+        let bytes = self.game_file_loader.get(path).map_err(LoadError::File)?;
+        let mut byte_reader: ByteReader<Option<InternalVersion>> = ByteReader::with_default_metadata(&bytes);
+
+        Data::from_bytes(&mut byte_reader).map_err(LoadError::Conversion)
+    }
+}
+
+/// Average color of all opaque pixels of an image.
+fn average_color(image: &RgbaImage) -> [f32; 3] {
+    let mut sum = [0.0; 3];
+    let mut weight = 0.0;
+
+    for Rgba([red, green, blue, alpha]) in image.pixels() {
+        let alpha = *alpha as f32 / 255.0;
+        sum[0] += *red as f32 * alpha;
+        sum[1] += *green as f32 * alpha;
+        sum[2] += *blue as f32 * alpha;
+        weight += alpha;
+    }
+
+    match weight > 0.0 {
+        true => sum.map(|channel| channel / weight),
+        false => [0.0; 3],
+    }
+}
+
+/// Height of the ground at each corner of a tile with up being positive.
+fn corner_heights(tile: &GroundTile) -> [f32; 4] {
+    [
+        -tile.southwest_corner_height,
+        -tile.southeast_corner_height,
+        -tile.northwest_corner_height,
+        -tile.northeast_corner_height,
+    ]
+}
+
+/// Render the ground of a map with one pixel per tile. The first row of
+/// tiles is the southern edge of the map, so it ends up at the bottom of the
+/// image.
+fn render_ground(ground_data: &GroundData, texture_colors: &[[f32; 3]], water_level: Option<f32>) -> RgbaImage {
+    let width = ground_data.width.max(0) as u32;
+    let height = ground_data.height.max(0) as u32;
+
+    RgbaImage::from_fn(width, height, |x, y| {
+        let tile_index = ((height - 1 - y) * width + x) as usize;
+
+        let Some(tile) = ground_data.ground_tiles.get(tile_index) else {
+            return Rgba([0, 0, 0, 0]);
+        };
+
+        let Some(surface) = usize::try_from(tile.top_surface_index)
+            .ok()
+            .and_then(|surface_index| ground_data.surfaces.get(surface_index))
+        else {
+            return Rgba([0, 0, 0, 0]);
+        };
+
+        let texture_color = usize::try_from(surface.texture_index)
+            .ok()
+            .and_then(|texture_index| texture_colors.get(texture_index))
+            .copied()
+            .unwrap_or([0.0; 3]);
+
+        let surface_color = [surface.color.red, surface.color.green, surface.color.blue].map(|channel| channel as f32 / 255.0);
+
+        // Light the ground from the north-west, so slopes that descend towards the
+        // north-west are brighter.
+        let [southwest, southeast, northwest, northeast] = corner_heights(tile);
+        let east_slope = (southeast + northeast) - (southwest + northwest);
+        let north_slope = (northwest + northeast) - (southwest + southeast);
+        let shading = (1.0 + (east_slope - north_slope) * SLOPE_SHADING).clamp(0.5, 1.5);
+
+        let mut color: [f32; 3] = std::array::from_fn(|channel| texture_color[channel] * surface_color[channel] * shading);
+
+        let average_height = (southwest + southeast + northwest + northeast) / 4.0;
+        if water_level.is_some_and(|water_level| average_height < water_level) {
+            color = std::array::from_fn(|channel| (color[channel] + WATER_COLOR[channel]) / 2.0);
+        }
+
+        let [red, green, blue] = color.map(|channel| channel.clamp(0.0, 255.0) as u8);
+        Rgba([red, green, blue, 255])
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use image::{Rgba, RgbaImage};
+
+    use super::average_color;
+
+    #[test]
+    fn transparent_pixels_are_ignored() {
+        let mut image = RgbaImage::from_pixel(2, 1, Rgba([255, 0, 0, 255]));
+        image.put_pixel(1, 0, Rgba([0, 255, 0, 0]));
+
+        assert_eq!(average_color(&image), [255.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn fully_transparent_image_is_black() {
+        let image = RgbaImage::from_pixel(2, 2, Rgba([255, 255, 255, 0]));
+
+        assert_eq!(average_color(&image), [0.0; 3]);
+    }
+}
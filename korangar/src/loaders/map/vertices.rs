@@ -1,11 +1,13 @@
 use cgmath::{Point3, Vector2};
-use ragnarok_formats::map::{GatData, GroundData, GroundTile, Surface, SurfaceType};
+#[cfg(feature = "debug")]
+use ragnarok_formats::map::TileFlags;
+use ragnarok_formats::map::{GatData, GroundData, GroundTile, Surface, SurfaceType, Tile};
 use smallvec::smallvec_inline;
 
 #[cfg(feature = "debug")]
 use crate::graphics::Color;
-use crate::graphics::{ModelVertex, NativeModelVertex, PickerTarget, TileVertex, reduce_vertices};
-use crate::loaders::map::{GAT_TILE_SIZE, GROUND_TILE_SIZE};
+use crate::graphics::{ModelVertex, NO_LIGHT_MAP_COORDINATES, NativeModelVertex, PickerTarget, TileVertex, reduce_vertices};
+use crate::loaders::map::{GAT_TILE_SIZE, GROUND_TILE_SIZE, LightMapAtlas};
 use crate::loaders::{TextureSetBuilder, TextureSetTexture, smooth_ground_normals};
 
 #[derive(Copy, Clone, Debug)]
@@ -19,6 +21,7 @@ pub enum Heights {
 pub fn ground_vertices(
     ground_data: &GroundData,
     texture_set_builder: &mut TextureSetBuilder,
+    light_map_atlas: Option<&LightMapAtlas>,
 ) -> (Vec<ModelVertex>, Vec<u32>, Vec<TextureSetTexture>) {
     let ground_textures: Vec<TextureSetTexture> = ground_data
         .textures
@@ -88,6 +91,10 @@ pub fn ground_vertices(
             let (first_texture_coordinates, second_texture_coordinates, third_texture_coordinates, fourth_texture_coordinates) =
                 map_texture_coordinates(&ground_textures, ground_surface);
 
+            let light_map_coordinates = light_map_atlas
+                .map(|atlas| atlas.coordinates(ground_surface.light_map_index))
+                .unwrap_or([NO_LIGHT_MAP_COORDINATES; 4]);
+
             let neighbor_color = |x_offset: usize, y_offset: usize| {
                 let Some(neighbor_tile) = ground_tiles.get(tile_x + x_offset + (tile_y + y_offset) * width) else {
                     return ground_surface.color.into();
@@ -107,63 +114,81 @@ pub fn ground_vertices(
             let color_north = neighbor_color(0, 1);
 
             if let Some(first_normal) = first_normal {
-                ground_vertices.push(NativeModelVertex::new(
-                    first_position,
-                    first_normal,
-                    first_texture_coordinates,
-                    ground_surface.texture_index as i32,
-                    ground_surface.color.into(),
-                    0.0,
-                    smallvec_inline![0;3],
-                ));
-                ground_vertices.push(NativeModelVertex::new(
-                    second_position,
-                    first_normal,
-                    second_texture_coordinates,
-                    ground_surface.texture_index as i32,
-                    color_east,
-                    0.0,
-                    smallvec_inline![0;3],
-                ));
-                ground_vertices.push(NativeModelVertex::new(
-                    third_position,
-                    first_normal,
-                    third_texture_coordinates,
-                    ground_surface.texture_index as i32,
-                    color_north,
-                    0.0,
-                    smallvec_inline![0;3],
-                ));
+                ground_vertices.push(
+                    NativeModelVertex::new(
+                        first_position,
+                        first_normal,
+                        first_texture_coordinates,
+                        ground_surface.texture_index as i32,
+                        ground_surface.color.into(),
+                        0.0,
+                        smallvec_inline![0;3],
+                    )
+                    .with_light_map_coordinates(light_map_coordinates[0]),
+                );
+                ground_vertices.push(
+                    NativeModelVertex::new(
+                        second_position,
+                        first_normal,
+                        second_texture_coordinates,
+                        ground_surface.texture_index as i32,
+                        color_east,
+                        0.0,
+                        smallvec_inline![0;3],
+                    )
+                    .with_light_map_coordinates(light_map_coordinates[1]),
+                );
+                ground_vertices.push(
+                    NativeModelVertex::new(
+                        third_position,
+                        first_normal,
+                        third_texture_coordinates,
+                        ground_surface.texture_index as i32,
+                        color_north,
+                        0.0,
+                        smallvec_inline![0;3],
+                    )
+                    .with_light_map_coordinates(light_map_coordinates[2]),
+                );
             }
 
             if let Some(second_normal) = second_normal {
-                ground_vertices.push(NativeModelVertex::new(
-                    third_position,
-                    second_normal,
-                    third_texture_coordinates,
-                    ground_surface.texture_index as i32,
-                    color_north,
-                    0.0,
-                    smallvec_inline![0;3],
-                ));
-                ground_vertices.push(NativeModelVertex::new(
-                    second_position,
-                    second_normal,
-                    second_texture_coordinates,
-                    ground_surface.texture_index as i32,
-                    color_east,
-                    0.0,
-                    smallvec_inline![0;3],
-                ));
-                ground_vertices.push(NativeModelVertex::new(
-                    fourth_position,
-                    second_normal,
-                    fourth_texture_coordinates,
-                    ground_surface.texture_index as i32,
-                    color_north_east,
-                    0.0,
-                    smallvec_inline![0;3],
-                ));
+                ground_vertices.push(
+                    NativeModelVertex::new(
+                        third_position,
+                        second_normal,
+                        third_texture_coordinates,
+                        ground_surface.texture_index as i32,
+                        color_north,
+                        0.0,
+                        smallvec_inline![0;3],
+                    )
+                    .with_light_map_coordinates(light_map_coordinates[2]),
+                );
+                ground_vertices.push(
+                    NativeModelVertex::new(
+                        second_position,
+                        second_normal,
+                        second_texture_coordinates,
+                        ground_surface.texture_index as i32,
+                        color_east,
+                        0.0,
+                        smallvec_inline![0;3],
+                    )
+                    .with_light_map_coordinates(light_map_coordinates[1]),
+                );
+                ground_vertices.push(
+                    NativeModelVertex::new(
+                        fourth_position,
+                        second_normal,
+                        fourth_texture_coordinates,
+                        ground_surface.texture_index as i32,
+                        color_north_east,
+                        0.0,
+                        smallvec_inline![0;3],
+                    )
+                    .with_light_map_coordinates(light_map_coordinates[3]),
+                );
             }
         }
     }
@@ -214,10 +239,6 @@ fn map_texture_coordinates(
 }
 
 pub fn generate_tile_vertices(gat_data: &mut GatData) -> (Vec<ModelVertex>, Vec<u32>, Vec<TileVertex>, Vec<u32>) {
-    #[allow(unused_mut)]
-    let mut tile_vertices = Vec::new();
-    let mut tile_picker_vertices = Vec::new();
-
     for tile in gat_data.tiles.iter_mut() {
         tile.southwest_corner_height = -tile.southwest_corner_height;
         tile.southeast_corner_height = -tile.southeast_corner_height;
@@ -225,14 +246,28 @@ pub fn generate_tile_vertices(gat_data: &mut GatData) -> (Vec<ModelVertex>, Vec<
         tile.northeast_corner_height = -tile.northeast_corner_height;
     }
 
-    let tile_picker_indices = gat_data
-        .tiles
+    generate_tile_meshes(&gat_data.tiles, gat_data.map_width as usize, false)
+}
+
+/// Generate the debug and picker meshes from tiles with flipped heights.
+/// Blocked tiles are skipped unless `include_blocked_tiles` is set, which is
+/// needed to paint them with the walkability brush.
+pub fn generate_tile_meshes(
+    tiles: &[Tile],
+    map_width: usize,
+    include_blocked_tiles: bool,
+) -> (Vec<ModelVertex>, Vec<u32>, Vec<TileVertex>, Vec<u32>) {
+    #[allow(unused_mut)]
+    let mut tile_vertices = Vec::new();
+    let mut tile_picker_vertices = Vec::new();
+
+    let tile_picker_indices = tiles
         .iter()
         .enumerate()
-        .filter(|(_, tile)| !tile.flags.is_empty())
+        .filter(|(_, tile)| include_blocked_tiles || !tile.flags.is_empty())
         .flat_map(|(index, tile)| {
-            let x = index % gat_data.map_width as usize;
-            let y = index / gat_data.map_width as usize;
+            let x = index % map_width;
+            let y = index / map_width;
 
             let offset = Vector2::new(x as f32 * GAT_TILE_SIZE, y as f32 * GAT_TILE_SIZE);
 
@@ -261,6 +296,7 @@ pub fn generate_tile_vertices(gat_data: &mut GatData) -> (Vec<ModelVertex>, Vec<
                 let second_normal = NativeModelVertex::calculate_normal(third_position, second_position, fourth_position);
 
                 let tile_type_index = TryInto::<u8>::try_into(tile.flags).unwrap() as usize;
+                let tile_color = tile_type_color(tile.flags);
 
                 let first_texture_coordinates = Vector2::new(0.0, 1.0);
                 let second_texture_coordinates = Vector2::new(1.0, 1.0);
@@ -272,7 +308,7 @@ pub fn generate_tile_vertices(gat_data: &mut GatData) -> (Vec<ModelVertex>, Vec<
                         first_position,
                         first_normal,
                         first_texture_coordinates,
-                        Color::WHITE,
+                        tile_color,
                         tile_type_index as i32,
                         0.0,
                     ));
@@ -280,7 +316,7 @@ pub fn generate_tile_vertices(gat_data: &mut GatData) -> (Vec<ModelVertex>, Vec<
                         second_position,
                         first_normal,
                         second_texture_coordinates,
-                        Color::WHITE,
+                        tile_color,
                         tile_type_index as i32,
                         0.0,
                     ));
@@ -288,7 +324,7 @@ pub fn generate_tile_vertices(gat_data: &mut GatData) -> (Vec<ModelVertex>, Vec<
                         third_position,
                         first_normal,
                         third_texture_coordinates,
-                        Color::WHITE,
+                        tile_color,
                         tile_type_index as i32,
                         0.0,
                     ));
@@ -299,7 +335,7 @@ pub fn generate_tile_vertices(gat_data: &mut GatData) -> (Vec<ModelVertex>, Vec<
                         third_position,
                         second_normal,
                         third_texture_coordinates,
-                        Color::WHITE,
+                        tile_color,
                         tile_type_index as i32,
                         0.0,
                     ));
@@ -307,7 +343,7 @@ pub fn generate_tile_vertices(gat_data: &mut GatData) -> (Vec<ModelVertex>, Vec<
                         second_position,
                         second_normal,
                         second_texture_coordinates,
-                        Color::WHITE,
+                        tile_color,
                         tile_type_index as i32,
                         0.0,
                     ));
@@ -315,7 +351,7 @@ pub fn generate_tile_vertices(gat_data: &mut GatData) -> (Vec<ModelVertex>, Vec<
                         fourth_position,
                         second_normal,
                         fourth_texture_coordinates,
-                        Color::WHITE,
+                        tile_color,
                         tile_type_index as i32,
                         0.0,
                     ));
@@ -347,6 +383,25 @@ pub fn generate_tile_vertices(gat_data: &mut GatData) -> (Vec<ModelVertex>, Vec<
     (reduced_tile_vertices, tile_indices, tile_picker_vertices, tile_picker_indices)
 }
 
+/// Color of the tile overlay, so the tile types can be told apart at a
+/// glance.
+#[cfg(feature = "debug")]
+fn tile_type_color(flags: TileFlags) -> Color {
+    if flags == TileFlags::WALKABLE {
+        Color::rgb(0.3, 0.9, 0.3)
+    } else if flags == TileFlags::WATER | TileFlags::WALKABLE {
+        Color::rgb(0.3, 0.8, 0.9)
+    } else if flags.contains(TileFlags::WATER) {
+        Color::rgb(0.2, 0.3, 0.9)
+    } else if flags == TileFlags::CLIFF | TileFlags::SNIPABLE {
+        Color::rgb(0.9, 0.8, 0.2)
+    } else if flags.contains(TileFlags::CLIFF) {
+        Color::rgb(0.9, 0.5, 0.1)
+    } else {
+        Color::rgb(0.9, 0.2, 0.2)
+    }
+}
+
 pub fn tile_surface_index(tile: &GroundTile, surface_type: SurfaceType) -> i32 {
     match surface_type {
         SurfaceType::North => tile.north_surface_index,
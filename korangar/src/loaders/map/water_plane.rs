@@ -70,6 +70,7 @@ pub fn generate_water_plane(
         .collect();
 
     Some(WaterPlane::new(
+        water_level,
         water_opacity,
         wave_height,
         wave_speed,
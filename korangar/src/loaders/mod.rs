@@ -5,6 +5,7 @@ mod color;
 mod rectangle;
 
 mod r#async;
+mod audit;
 mod effect;
 pub mod error;
 mod font;
@@ -20,15 +21,18 @@ mod video;
 pub use self::action::*;
 pub use self::animation::*;
 pub use self::r#async::*;
+pub use self::audit::{AssetKind, FallbackAudit};
 pub use self::effect::EffectLoader;
 pub use self::font::{FontLoader, FontSize, GlyphInstruction, OverflowBehavior, Scaling};
 pub use self::gamefile::*;
-pub use self::map::{GAT_TILE_SIZE, MapLoader};
+pub use self::map::{GAT_TILE_SIZE, MapLoader, MapThumbnailLoader, OBJECT_DEPTH_OFFSET};
+#[cfg(feature = "debug")]
+pub use self::map::{TileMeshes, create_tile_meshes};
 pub use self::model::*;
-pub use self::server::{ClientInfo, ClientInfoPathExt, PacketVersion, ServiceId, load_client_info};
+pub use self::server::{ClientInfo, ClientInfoPathExt, PacketVersion, Service, ServiceId, load_client_info};
 pub use self::smoothing::{smooth_ground_normals, smooth_model_normals};
 pub use self::sprite::*;
-pub use self::texture::{ImageType, TextureLoader, TextureSetBuilder, TextureSetTexture};
+pub use self::texture::{AtlasRegion, ImageType, InterfaceAtlas, TextureLoader, TextureSetBuilder, TextureSetTexture};
 pub use self::video::VideoLoader;
 
 pub const FALLBACK_BMP_FILE: &str = "missing.bmp";
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use cgmath::{Array, EuclideanSpace, Matrix4, Point3, Rad, SquareMatrix, Vector2, Vector3};
@@ -13,21 +14,30 @@ use ragnarok_formats::version::InternalVersion;
 use smallvec::SmallVec;
 
 use super::error::LoadError;
-use super::{FALLBACK_MODEL_FILE, TextureSetBuilder, TextureSetTexture, smooth_model_normals};
+use super::{AssetKind, FALLBACK_MODEL_FILE, FallbackAudit, TextureSetBuilder, TextureSetTexture, smooth_model_normals};
 use crate::graphics::{BindlessSupport, Color, ModelVertex, NativeModelVertex, reduce_vertices};
 use crate::loaders::GameFileLoader;
 use crate::world::{Model, Node, SubMesh};
 
 pub struct ModelLoader {
     game_file_loader: Arc<GameFileLoader>,
+    fallback_audit: Arc<FallbackAudit>,
     bindless_support: BindlessSupport,
+    texture_group_size: u32,
 }
 
 impl ModelLoader {
-    pub fn new(game_file_loader: Arc<GameFileLoader>, bindless_support: BindlessSupport) -> Self {
+    pub fn new(
+        game_file_loader: Arc<GameFileLoader>,
+        fallback_audit: Arc<FallbackAudit>,
+        bindless_support: BindlessSupport,
+        texture_group_size: u32,
+    ) -> Self {
         Self {
             game_file_loader,
+            fallback_audit,
             bindless_support,
+            texture_group_size,
         }
     }
 }
@@ -39,6 +49,7 @@ impl ModelLoader {
         texture_coordinates: &[Vector2<f32>],
         smoothing_groups: &SmallVec<[i32; 3]>,
         texture_index: i32,
+        wind_affinity: f32,
         reverse_vertices: bool,
         reverse_normal: bool,
     ) {
@@ -63,7 +74,7 @@ impl ModelLoader {
                     *texture_coordinates,
                     texture_index,
                     Color::WHITE,
-                    0.0, // TODO: actually add wind affinity
+                    wind_affinity,
                     smoothing_groups.clone(),
                 );
             }
@@ -77,14 +88,20 @@ impl ModelLoader {
                     *texture_coordinates,
                     texture_index,
                     Color::WHITE,
-                    0.0, // TODO: actually add wind affinity
+                    wind_affinity,
                     smoothing_groups.clone(),
                 );
             }
         }
     }
 
-    fn make_vertices(node: &NodeData, main_matrix: &Matrix4<f32>, reverse_order: bool, smooth_normals: bool) -> Vec<NativeModelVertex> {
+    fn make_vertices(
+        node: &NodeData,
+        main_matrix: &Matrix4<f32>,
+        wind_affinities: &[f32],
+        reverse_order: bool,
+        smooth_normals: bool,
+    ) -> Vec<NativeModelVertex> {
         let face_count = node.faces.len();
         let face_vertex_count = face_count * 3;
         let two_sided_face_count = node.faces.iter().filter(|face| face.two_sided != 0).count();
@@ -118,12 +135,15 @@ impl ModelLoader {
                 std::iter::once(face.smooth_group).chain(face.smooth_group_extra.as_ref().iter().flat_map(|extra| extra.iter().copied())),
             );
 
+            let wind_affinity = wind_affinities.get(face.texture_index as usize).copied().unwrap_or(0.0);
+
             Self::add_vertices(
                 &mut vertices[face_index..face_index + 3],
                 &vertex_positions,
                 &texture_coordinates,
                 &smoothing_groups,
                 face.texture_index as i32,
+                wind_affinity,
                 reverse_order,
                 false,
             );
@@ -136,6 +156,7 @@ impl ModelLoader {
                     &texture_coordinates,
                     &smoothing_groups,
                     face.texture_index as i32,
+                    wind_affinity,
                     !reverse_order,
                     true,
                 );
@@ -190,6 +211,7 @@ impl ModelLoader {
 
     fn process_node_mesh(
         bindless_support: BindlessSupport,
+        texture_group_size: u32,
         version: InternalVersion,
         current_node: &NodeData,
         nodes: &[NodeData],
@@ -235,6 +257,7 @@ impl ModelLoader {
             .map(|&index| {
                 Self::process_node_mesh(
                     bindless_support,
+                    texture_group_size,
                     version,
                     &nodes[index],
                     nodes,
@@ -252,25 +275,27 @@ impl ModelLoader {
             })
             .collect();
 
-        let node_textures: Vec<TextureSetTexture> = match texture_mapping {
+        let (node_textures, node_wind_affinities): (Vec<TextureSetTexture>, Vec<f32>) = match texture_mapping {
             TextureMapping::PreVersion2_3(vector_texture) => current_node
                 .texture_indices
                 .iter()
                 .map(|&index| vector_texture[index as usize])
-                .collect(),
+                .map(|model_texture| (model_texture.texture, model_texture.wind_affinity))
+                .unzip(),
             TextureMapping::PostVersion2_3(hashmap_texture) => current_node
                 .texture_names
                 .iter()
                 .map(|name| *hashmap_texture.get(name.as_ref()).unwrap())
-                .collect(),
+                .map(|model_texture| (model_texture.texture, model_texture.wind_affinity))
+                .unzip(),
         };
 
-        let node_native_vertices = Self::make_vertices(current_node, &main_matrix, reverse_order, smooth_normals);
+        let node_native_vertices = Self::make_vertices(current_node, &main_matrix, &node_wind_affinities, reverse_order, smooth_normals);
 
         let centroid = Self::calculate_centroid(&node_native_vertices);
 
         let node_vertices = NativeModelVertex::convert_to_model_vertices(node_native_vertices, Some(&node_textures));
-        let (node_vertices, mut node_indices) = reduce_vertices(&node_vertices);
+        let (mut node_vertices, mut node_indices) = reduce_vertices(&node_vertices);
 
         // Apply the frames per second on the keyframes values.
         let animation_length = match version.equals_or_above(2, 2) {
@@ -313,13 +338,29 @@ impl ModelLoader {
 
         match bindless_support {
             BindlessSupport::Full | BindlessSupport::Limited => {
-                // Remember the index offset, index count, base vertex and gather node vertices.
+                let texture_transparencies: HashMap<i32, bool> = node_textures
+                    .iter()
+                    .map(|texture| (texture.index, texture.is_transparent))
+                    .collect();
+
+                let mut submeshes = split_mesh_by_texture_group(
+                    &mut node_vertices,
+                    &mut node_indices,
+                    texture_group_size,
+                    Some(&texture_transparencies),
+                );
+
+                // Move the sub-meshes behind the vertices and indices of the previous nodes.
                 let index_offset = model_indices.len() as u32;
-                let index_count = node_indices.len() as u32;
                 let base_vertex = model_vertices.len() as i32;
                 model_vertices.extend(node_vertices);
                 model_indices.extend(node_indices);
 
+                submeshes.iter_mut().for_each(|submesh| {
+                    submesh.index_offset += index_offset;
+                    submesh.base_vertex = base_vertex;
+                });
+
                 Node::new(
                     version,
                     transform_matrix,
@@ -327,13 +368,7 @@ impl ModelLoader {
                     Matrix4::identity(),
                     position,
                     centroid,
-                    vec![SubMesh {
-                        index_offset,
-                        index_count,
-                        base_vertex,
-                        texture_index: 0,
-                        transparent: node_textures.iter().any(|texture| texture.is_transparent),
-                    }],
+                    submeshes,
                     child_nodes,
                     animation_length,
                     scale_keyframes,
@@ -429,7 +464,7 @@ impl ModelLoader {
     /// We need to make sure to always generate a texture atlas in the same
     /// order when creating an online texture atlas and an offline texture
     /// atlas.
-    fn collect_versioned_texture_names(version: &InternalVersion, model_data: &ModelData) -> Vec<String> {
+    pub(crate) fn collect_versioned_texture_names(version: &InternalVersion, model_data: &ModelData) -> Vec<String> {
         match version.equals_or_above(2, 3) {
             false => model_data
                 .texture_names
@@ -466,13 +501,15 @@ impl ModelLoader {
 
         let bytes = match self.game_file_loader.get(&format!("data\\model\\{model_file}")) {
             Ok(bytes) => bytes,
-            Err(_error) => {
+            Err(error) => {
                 #[cfg(feature = "debug")]
                 {
-                    print_debug!("Failed to load model: {:?}", _error);
+                    print_debug!("Failed to load model: {:?}", error);
                     print_debug!("Replacing with fallback");
                 }
 
+                self.fallback_audit.record(AssetKind::Model, model_file, format!("{error:?}"));
+
                 return self.load(
                     texture_set_builder,
                     model_vertices,
@@ -486,13 +523,15 @@ impl ModelLoader {
 
         let model_data = match ModelData::from_bytes(&mut byte_reader) {
             Ok(model_data) => model_data,
-            Err(_error) => {
+            Err(error) => {
                 #[cfg(feature = "debug")]
                 {
-                    print_debug!("Failed to load model: {:?}", _error);
+                    print_debug!("Failed to load model: {:?}", error);
                     print_debug!("Replacing with fallback");
                 }
 
+                self.fallback_audit.record(AssetKind::Model, model_file, format!("{error:?}"));
+
                 return self.load(
                     texture_set_builder,
                     model_vertices,
@@ -513,6 +552,9 @@ impl ModelLoader {
                 print_debug!("Replacing with fallback");
             }
 
+            self.fallback_audit
+                .record(AssetKind::Model, model_file, format!("unsupported version {version}"));
+
             return self.load(
                 texture_set_builder,
                 model_vertices,
@@ -524,15 +566,18 @@ impl ModelLoader {
 
         let texture_names = ModelLoader::collect_versioned_texture_names(&version, &model_data);
 
-        let model_textures: Vec<TextureSetTexture> = texture_names
+        let model_textures: Vec<ModelTexture> = texture_names
             .iter()
-            .map(|texture_name| texture_set_builder.register(texture_name.as_ref()))
+            .map(|texture_name| ModelTexture {
+                texture: texture_set_builder.register(texture_name.as_ref()),
+                wind_affinity: texture_wind_affinity(texture_name),
+            })
             .collect();
 
         let texture_mapping = match version.equals_or_above(2, 3) {
             true => {
                 let model_textures =
-                    HashMap::<String, TextureSetTexture>::from_iter(texture_names.into_iter().zip(model_textures.iter().copied()));
+                    HashMap::<String, ModelTexture>::from_iter(texture_names.into_iter().zip(model_textures.iter().copied()));
                 TextureMapping::PostVersion2_3(model_textures)
             }
             false => TextureMapping::PreVersion2_3(model_textures),
@@ -565,6 +610,7 @@ impl ModelLoader {
                 processed_node_indices[root_node_position] = true;
                 Self::process_node_mesh(
                     self.bindless_support,
+                    self.texture_group_size,
                     version,
                     root_node,
                     &model_data.nodes,
@@ -698,7 +744,98 @@ pub fn split_mesh_by_texture(
     submeshes
 }
 
+/// When the texture set is bigger than the binding arrays supported by the
+/// adapter, it is split into multiple texture groups. We then need to create
+/// separate meshes for each texture group used in the mesh so we can bind the
+/// appropriate group before drawing each sub-mesh. The texture index of the
+/// vertices is changed to the index inside of their texture group and the
+/// texture index of the sub-meshes is the texture group.
+pub fn split_mesh_by_texture_group(
+    vertices: &mut [ModelVertex],
+    indices: &mut [u32],
+    texture_group_size: u32,
+    texture_transparencies: Option<&HashMap<i32, bool>>,
+) -> Vec<SubMesh> {
+    let texture_group_size = texture_group_size as i32;
+    let mut group_to_faces: BTreeMap<i32, (Vec<[u32; 3]>, bool)> = BTreeMap::new();
+
+    indices.chunks_exact(3).for_each(|chunk| {
+        let texture_index = vertices[chunk[0] as usize].texture_index;
+        let transparent = texture_transparencies
+            .and_then(|transparencies| transparencies.get(&texture_index).copied())
+            .unwrap_or(false);
+
+        let (faces, group_transparent) = group_to_faces.entry(texture_index / texture_group_size).or_default();
+        faces.push([chunk[0], chunk[1], chunk[2]]);
+        *group_transparent |= transparent;
+    });
+
+    vertices.iter_mut().for_each(|vertex| vertex.texture_index %= texture_group_size);
+
+    let mut current_index = 0;
+
+    group_to_faces
+        .into_iter()
+        .map(|(texture_group, (faces, transparent))| {
+            let index_count = (faces.len() * 3) as u32;
+
+            for (index, &vertex_index) in faces.iter().flatten().enumerate() {
+                indices[current_index + index] = vertex_index;
+            }
+
+            let submesh = SubMesh {
+                index_offset: current_index as u32,
+                index_count,
+                base_vertex: 0,
+                texture_index: texture_group,
+                transparent,
+            };
+
+            current_index += index_count as usize;
+            submesh
+        })
+        .collect()
+}
+
+/// Parts of the texture names of foliage textures and how strongly the
+/// vertices using them are moved by the wind. The names of the original
+/// textures are mostly Korean, so both Korean and English words are used.
+const WIND_AFFINITY_KEYWORDS: &[(&str, f32)] = &[
+    ("잎", 1.0),
+    ("leaf", 1.0),
+    ("leaves", 1.0),
+    ("풀", 0.8),
+    ("grass", 0.8),
+    ("덩굴", 0.7),
+    ("vine", 0.7),
+    ("꽃", 0.6),
+    ("flower", 0.6),
+    ("덤불", 0.6),
+    ("bush", 0.6),
+    ("가지", 0.4),
+    ("branch", 0.4),
+];
+
+/// Guesses how strongly the vertices using a texture are moved by the wind,
+/// based on the name of the texture. Textures that don't look like foliage
+/// are not moved at all.
+fn texture_wind_affinity(texture_name: &str) -> f32 {
+    let texture_name = texture_name.to_lowercase();
+
+    WIND_AFFINITY_KEYWORDS
+        .iter()
+        .filter(|(keyword, _)| texture_name.contains(keyword))
+        .map(|(_, wind_affinity)| *wind_affinity)
+        .fold(0.0, f32::max)
+}
+
+#[derive(Clone, Copy)]
+struct ModelTexture {
+    texture: TextureSetTexture,
+    wind_affinity: f32,
+}
+
 enum TextureMapping {
-    PreVersion2_3(Vec<TextureSetTexture>),
-    PostVersion2_3(HashMap<String, TextureSetTexture>),
+    PreVersion2_3(Vec<ModelTexture>),
+    PostVersion2_3(HashMap<String, ModelTexture>),
 }
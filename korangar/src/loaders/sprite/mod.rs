@@ -1,10 +1,10 @@
+mod upscale;
+
 use std::num::{NonZeroU32, NonZeroUsize};
 use std::sync::{Arc, Mutex};
 
 use image::RgbaImage;
-#[cfg(feature = "debug")]
-use korangar_container::CacheStatistics;
-use korangar_container::{Cacheable, SimpleCache};
+use korangar_container::{CacheStatistics, Cacheable, SimpleCache};
 #[cfg(feature = "debug")]
 use korangar_debug::logging::{Colorize, Timer, print_debug};
 use korangar_interface::element::StateElement;
@@ -13,9 +13,11 @@ use ragnarok_bytes::{ByteReader, FromBytes};
 use ragnarok_formats::sprite::{PaletteColor, RgbaImageData, SpriteData};
 use ragnarok_formats::version::InternalVersion;
 use rust_state::RustState;
+use wgpu::Extent3d;
 
-use super::{FALLBACK_SPRITE_FILE, TextureLoader};
-use crate::graphics::Texture;
+use self::upscale::upscale;
+use super::{AssetKind, FALLBACK_SPRITE_FILE, FallbackAudit, TextureLoader};
+use crate::graphics::{SpriteUpscaling, Texture};
 use crate::loaders::GameFileLoader;
 use crate::loaders::color::premultiply_alpha;
 use crate::loaders::error::LoadError;
@@ -28,10 +30,26 @@ pub struct Sprite {
     pub palette_size: usize,
     #[hidden_element]
     pub textures: Vec<Arc<Texture>>,
+    /// Factor by which the textures were upscaled when loading the sprite.
+    pub upscale_factor: u32,
     #[cfg(feature = "debug")]
     sprite_data: SpriteData,
 }
 
+impl Sprite {
+    /// Size of the texture at `index` before it was upscaled. Sprites should
+    /// always be drawn at this size.
+    pub fn texture_size(&self, index: usize) -> Extent3d {
+        let size = self.textures[index].get_size();
+
+        Extent3d {
+            width: size.width / self.upscale_factor,
+            height: size.height / self.upscale_factor,
+            ..size
+        }
+    }
+}
+
 impl Cacheable for Sprite {
     fn size(&self) -> usize {
         self.textures.iter().map(|t| t.get_byte_size()).sum()
@@ -40,15 +58,24 @@ impl Cacheable for Sprite {
 
 pub struct SpriteLoader {
     game_file_loader: Arc<GameFileLoader>,
+    fallback_audit: Arc<FallbackAudit>,
     texture_loader: Arc<TextureLoader>,
     cache: Mutex<SimpleCache<String, Arc<Sprite>>>,
+    upscaling: Mutex<SpriteUpscaling>,
 }
 
 impl SpriteLoader {
-    pub fn new(game_file_loader: Arc<GameFileLoader>, texture_loader: Arc<TextureLoader>) -> Self {
+    pub fn new(
+        game_file_loader: Arc<GameFileLoader>,
+        fallback_audit: Arc<FallbackAudit>,
+        texture_loader: Arc<TextureLoader>,
+        upscaling: SpriteUpscaling,
+    ) -> Self {
         Self {
             game_file_loader,
+            fallback_audit,
             texture_loader,
+            upscaling: Mutex::new(upscaling),
             cache: Mutex::new(SimpleCache::new(
                 NonZeroU32::new(MAX_CACHE_COUNT).unwrap(),
                 NonZeroUsize::new(MAX_CACHE_SIZE).unwrap(),
@@ -56,24 +83,45 @@ impl SpriteLoader {
         }
     }
 
-    #[cfg(feature = "debug")]
     pub fn cache_statistics(&self) -> CacheStatistics {
         self.cache.lock().unwrap().statistics()
     }
 
+    /// Drops all cached sprites, so they are loaded again the next time they
+    /// are requested.
+    #[cfg(feature = "debug")]
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Evicts cached sprites until the cache holds at most `size` bytes.
+    pub fn shrink_cache(&self, size: usize) {
+        self.cache.lock().unwrap().shrink_to_size(size);
+    }
+
+    /// Sets the upscaling of all sprites that are loaded from now on. Cached
+    /// sprites are dropped, so they get loaded with the new upscaling the next
+    /// time they are requested.
+    pub fn set_upscaling(&self, upscaling: SpriteUpscaling) {
+        *self.upscaling.lock().unwrap() = upscaling;
+        self.cache.lock().unwrap().clear();
+    }
+
     fn load(&self, path: &str) -> Result<Arc<Sprite>, LoadError> {
         #[cfg(feature = "debug")]
         let timer = Timer::new_dynamic(format!("load sprite from {}", path.magenta()));
 
         let bytes = match self.game_file_loader.get(&format!("data\\sprite\\{path}")) {
             Ok(bytes) => bytes,
-            Err(_error) => {
+            Err(error) => {
                 #[cfg(feature = "debug")]
                 {
-                    print_debug!("Failed to load sprite: {:?}", _error);
+                    print_debug!("Failed to load sprite: {:?}", error);
                     print_debug!("Replacing with fallback");
                 }
 
+                self.fallback_audit.record(AssetKind::Sprite, path, format!("{error:?}"));
+
                 return self.get_or_load(FALLBACK_SPRITE_FILE);
             }
         };
@@ -81,13 +129,15 @@ impl SpriteLoader {
 
         let sprite_data = match SpriteData::from_bytes(&mut byte_reader) {
             Ok(sprite_data) => sprite_data,
-            Err(_error) => {
+            Err(error) => {
                 #[cfg(feature = "debug")]
                 {
-                    print_debug!("Failed to load sprite: {:?}", _error);
+                    print_debug!("Failed to load sprite: {:?}", error);
                     print_debug!("Replacing with fallback");
                 }
 
+                self.fallback_audit.record(AssetKind::Sprite, path, format!("{error:?}"));
+
                 return self.get_or_load(FALLBACK_SPRITE_FILE);
             }
         };
@@ -150,13 +200,15 @@ impl SpriteLoader {
             }
         });
         let palette_size = palette_images.len();
+        let upscale_factor = self.upscaling.lock().unwrap().factor();
 
         let textures = palette_images
             .chain(rgba_images)
+            .map(|image_data| upscale(image_data, upscale_factor))
             .map(|mut image_data| {
                 premultiply_alpha(&mut image_data.data);
 
-                self.texture_loader.create_color(
+                self.texture_loader.create_color_deferred(
                     path,
                     RgbaImage::from_raw(image_data.width as u32, image_data.height as u32, image_data.data).unwrap(),
                     false,
@@ -167,6 +219,7 @@ impl SpriteLoader {
         let sprite = Arc::new(Sprite {
             palette_size,
             textures,
+            upscale_factor,
             #[cfg(feature = "debug")]
             sprite_data: cloned_sprite_data,
         });
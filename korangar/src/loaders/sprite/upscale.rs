@@ -0,0 +1,126 @@
+//! Pixel art upscaling of sprites using the Scale2x algorithm. Unlike a
+//! regular filter, Scale2x only ever copies existing colors, so the upscaled
+//! sprites keep their palette and hard edges while diagonal lines get
+//! smoothed.
+
+use ragnarok_formats::sprite::RgbaImageData;
+
+/// Upscales an RGBA image by the given factor, which must be a power of two.
+pub(super) fn upscale(mut image_data: RgbaImageData, factor: u32) -> RgbaImageData {
+    debug_assert!(factor.is_power_of_two());
+
+    for _ in 0..factor.trailing_zeros() {
+        image_data = scale2x(&image_data);
+    }
+
+    image_data
+}
+
+fn scale2x(image_data: &RgbaImageData) -> RgbaImageData {
+    let width = image_data.width as usize;
+    let height = image_data.height as usize;
+    let scaled_width = width * 2;
+
+    let pixel = |x: usize, y: usize| -> [u8; 4] {
+        let index = (y * width + x) * 4;
+        image_data.data[index..index + 4].try_into().unwrap()
+    };
+
+    let mut data = vec![0; width * height * 16];
+
+    for y in 0..height {
+        for x in 0..width {
+            let center = pixel(x, y);
+            // Pixels outside the image are treated like the center pixel.
+            let up = pixel(x, y.saturating_sub(1));
+            let down = pixel(x, (y + 1).min(height - 1));
+            let left = pixel(x.saturating_sub(1), y);
+            let right = pixel((x + 1).min(width - 1), y);
+
+            let mut output = [center; 4];
+
+            if up != down && left != right {
+                if left == up {
+                    output[0] = up;
+                }
+                if up == right {
+                    output[1] = right;
+                }
+                if left == down {
+                    output[2] = left;
+                }
+                if down == right {
+                    output[3] = down;
+                }
+            }
+
+            for (index, color) in output.into_iter().enumerate() {
+                let scaled_x = x * 2 + index % 2;
+                let scaled_y = y * 2 + index / 2;
+                let offset = (scaled_y * scaled_width + scaled_x) * 4;
+                data[offset..offset + 4].copy_from_slice(&color);
+            }
+        }
+    }
+
+    RgbaImageData {
+        width: image_data.width * 2,
+        height: image_data.height * 2,
+        data,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ragnarok_formats::sprite::RgbaImageData;
+
+    use super::upscale;
+
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+    const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+    fn image(width: u16, height: u16, pixels: &[[u8; 4]]) -> RgbaImageData {
+        RgbaImageData {
+            width,
+            height,
+            data: pixels.concat(),
+        }
+    }
+
+    #[test]
+    fn flat_areas_are_repeated() {
+        let scaled = upscale(image(2, 1, &[WHITE, BLACK]), 2);
+
+        assert_eq!(scaled.width, 4);
+        assert_eq!(scaled.height, 2);
+        assert_eq!(scaled.data, [WHITE, WHITE, BLACK, BLACK, WHITE, WHITE, BLACK, BLACK].concat());
+    }
+
+    #[test]
+    fn diagonals_are_smoothed() {
+        #[rustfmt::skip]
+        let scaled = upscale(image(2, 2, &[
+            BLACK, WHITE,
+            WHITE, BLACK,
+        ]), 2);
+
+        #[rustfmt::skip]
+        let expected = [
+            BLACK, BLACK, WHITE, WHITE,
+            BLACK, WHITE, BLACK, WHITE,
+            WHITE, BLACK, WHITE, BLACK,
+            WHITE, WHITE, BLACK, BLACK,
+        ];
+
+        assert_eq!(scaled.data, expected.concat());
+    }
+
+    #[test]
+    fn factor_four_scales_twice() {
+        let scaled = upscale(image(1, 1, &[WHITE]), 4);
+
+        assert_eq!(scaled.width, 4);
+        assert_eq!(scaled.height, 4);
+        assert_eq!(scaled.data, [WHITE; 16].concat());
+    }
+}
@@ -0,0 +1,378 @@
+//! Runtime texture atlas for small interface images like item icons.
+//!
+//! Every interface image is its own texture, so drawing a window full of item
+//! icons switches the bound texture for every single icon. Small images are
+//! additionally packed into shared atlas pages, so consecutive sprites can be
+//! drawn from the same texture.
+
+use std::sync::{Arc, Weak};
+
+use cgmath::Vector2;
+use hashbrown::HashMap;
+use image::RgbaImage;
+use wgpu::{
+    Device, Extent3d, Origin3d, Queue, TexelCopyBufferLayout, TexelCopyTextureInfo, TextureAspect, TextureDescriptor, TextureDimension,
+    TextureFormat, TextureUsages,
+};
+
+use crate::graphics::Texture;
+
+/// Width and height of a single atlas page.
+const PAGE_SIZE: u32 = 1024;
+/// Maximum number of atlas pages. Images that don't fit are rendered from
+/// their own texture.
+const MAXIMUM_PAGE_COUNT: usize = 4;
+/// Images larger than this in either dimension are not packed into the atlas.
+pub const MAXIMUM_ATLAS_IMAGE_SIZE: u32 = 128;
+/// Border around every image. The border repeats the edge pixels of the image
+/// so linear filtering doesn't bleed into neighbouring images.
+const PADDING: u32 = 1;
+
+/// Allocates rectangles in an area using horizontal shelves. Interface images
+/// and glyphs have mostly the same height, so this wastes very little space.
+#[derive(Debug)]
+pub(crate) struct ShelfAllocator {
+    width: u32,
+    height: u32,
+    shelves: Vec<Shelf>,
+}
+
+#[derive(Debug)]
+struct Shelf {
+    top: u32,
+    height: u32,
+    /// Horizontal space already used by the shelf.
+    used_width: u32,
+}
+
+impl ShelfAllocator {
+    pub(crate) fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            shelves: Vec::new(),
+        }
+    }
+
+    fn next_shelf_top(&self) -> u32 {
+        self.shelves.last().map(|shelf| shelf.top + shelf.height).unwrap_or(0)
+    }
+
+    /// Allocate a rectangle and return its top left corner.
+    pub(crate) fn allocate(&mut self, width: u32, height: u32) -> Option<Vector2<u32>> {
+        if width > self.width || height > self.height {
+            return None;
+        }
+
+        // Use the shelf that wastes the least vertical space.
+        let best_shelf = self
+            .shelves
+            .iter_mut()
+            .filter(|shelf| shelf.height >= height && self.width - shelf.used_width >= width)
+            .min_by_key(|shelf| shelf.height - height);
+
+        if let Some(shelf) = best_shelf {
+            let position = Vector2::new(shelf.used_width, shelf.top);
+            shelf.used_width += width;
+            return Some(position);
+        }
+
+        let top = self.next_shelf_top();
+
+        if self.height - top < height {
+            return None;
+        }
+
+        self.shelves.push(Shelf {
+            top,
+            height,
+            used_width: width,
+        });
+
+        Some(Vector2::new(0, top))
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.shelves.clear();
+    }
+}
+
+/// Location of an image inside the atlas.
+#[derive(Clone, Debug)]
+pub struct AtlasRegion {
+    pub texture: Arc<Texture>,
+    /// Texture coordinates of the top left corner of the image.
+    pub texture_position: Vector2<f32>,
+    /// Size of the image in texture coordinates.
+    pub texture_size: Vector2<f32>,
+}
+
+struct AtlasPage {
+    texture: Arc<Texture>,
+    allocator: ShelfAllocator,
+}
+
+struct AtlasEntry {
+    /// The original texture. Once it is dropped the region can be reused.
+    source: Weak<Texture>,
+    page: usize,
+    position: Vector2<u32>,
+    size: Vector2<u32>,
+}
+
+/// Packs small interface images into shared pages. Regions are looked up by
+/// the id of the standalone texture of the image, so the rest of the client
+/// can keep passing around plain textures.
+pub struct InterfaceAtlas {
+    device: Device,
+    queue: Queue,
+    pages: Vec<AtlasPage>,
+    entries: HashMap<u64, AtlasEntry>,
+}
+
+impl InterfaceAtlas {
+    pub fn new(device: Device, queue: Queue) -> Self {
+        Self {
+            device,
+            queue,
+            pages: Vec::new(),
+            entries: HashMap::new(),
+        }
+    }
+
+    fn create_page(&self) -> AtlasPage {
+        let texture = Texture::new(
+            &self.device,
+            &TextureDescriptor {
+                label: Some(&format!("interface atlas page {}", self.pages.len())),
+                size: Extent3d {
+                    width: PAGE_SIZE,
+                    height: PAGE_SIZE,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8UnormSrgb,
+                usage: TextureUsages::COPY_DST | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            true,
+        );
+
+        AtlasPage {
+            texture: Arc::new(texture),
+            allocator: ShelfAllocator::new(PAGE_SIZE, PAGE_SIZE),
+        }
+    }
+
+    fn allocate(&mut self, width: u32, height: u32) -> Option<(usize, Vector2<u32>)> {
+        if let Some(allocation) = self
+            .pages
+            .iter_mut()
+            .enumerate()
+            .find_map(|(index, page)| page.allocator.allocate(width, height).map(|position| (index, position)))
+        {
+            return Some(allocation);
+        }
+
+        if self.pages.len() < MAXIMUM_PAGE_COUNT {
+            let mut page = self.create_page();
+            let position = page.allocator.allocate(width, height)?;
+            self.pages.push(page);
+            return Some((self.pages.len() - 1, position));
+        }
+
+        None
+    }
+
+    /// Remove the regions of dropped textures and reset all pages that don't
+    /// contain any images anymore. This only runs once an allocation fails, so
+    /// the regions of dropped textures stay in use until the atlas is full.
+    /// Since a page is only reset once all of its images are dropped, a
+    /// single long lived image can keep a page occupied.
+    fn collect_garbage(&mut self) -> bool {
+        self.entries.retain(|_, entry| entry.source.strong_count() > 0);
+
+        let mut reset_any = false;
+
+        for (index, page) in self.pages.iter_mut().enumerate() {
+            if !self.entries.values().any(|entry| entry.page == index) {
+                page.allocator.clear();
+                reset_any = true;
+            }
+        }
+
+        reset_any
+    }
+
+    /// Pack the image of a texture into the atlas. Returns `false` if the
+    /// image is too large or the atlas is full, in which case the texture is
+    /// rendered on its own.
+    pub fn insert(&mut self, texture: &Arc<Texture>, image: &RgbaImage) -> bool {
+        if image.width() > MAXIMUM_ATLAS_IMAGE_SIZE || image.height() > MAXIMUM_ATLAS_IMAGE_SIZE {
+            return false;
+        }
+
+        if self.entries.contains_key(&texture.get_id()) {
+            return true;
+        }
+
+        let padded_image = pad_image(image);
+
+        let allocation = match self.allocate(padded_image.width(), padded_image.height()) {
+            Some(allocation) => Some(allocation),
+            None if self.collect_garbage() => self.allocate(padded_image.width(), padded_image.height()),
+            None => None,
+        };
+
+        let Some((page, position)) = allocation else {
+            return false;
+        };
+
+        self.queue.write_texture(
+            TexelCopyTextureInfo {
+                texture: self.pages[page].texture.get_texture(),
+                mip_level: 0,
+                origin: Origin3d {
+                    x: position.x,
+                    y: position.y,
+                    z: 0,
+                },
+                aspect: TextureAspect::All,
+            },
+            padded_image.as_raw(),
+            TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_image.width() * 4),
+                rows_per_image: None,
+            },
+            Extent3d {
+                width: padded_image.width(),
+                height: padded_image.height(),
+                depth_or_array_layers: 1,
+            },
+        );
+
+        self.entries.insert(texture.get_id(), AtlasEntry {
+            source: Arc::downgrade(texture),
+            page,
+            position: position + Vector2::new(PADDING, PADDING),
+            size: Vector2::new(image.width(), image.height()),
+        });
+
+        true
+    }
+
+    /// Get the atlas region of a texture, if it was packed into the atlas.
+    pub fn get(&self, texture: &Texture) -> Option<AtlasRegion> {
+        let entry = self.entries.get(&texture.get_id())?;
+        let page_size = PAGE_SIZE as f32;
+
+        Some(AtlasRegion {
+            texture: self.pages[entry.page].texture.clone(),
+            texture_position: entry.position.cast::<f32>().unwrap() / page_size,
+            texture_size: entry.size.cast::<f32>().unwrap() / page_size,
+        })
+    }
+
+    #[cfg(feature = "debug")]
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    #[cfg(feature = "debug")]
+    pub fn image_count(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Add a border around the image that repeats its edge pixels.
+fn pad_image(image: &RgbaImage) -> RgbaImage {
+    let width = image.width() + PADDING * 2;
+    let height = image.height() + PADDING * 2;
+
+    // An empty image has no edge pixels to repeat.
+    if image.width() == 0 || image.height() == 0 {
+        return RgbaImage::new(width, height);
+    }
+
+    RgbaImage::from_fn(width, height, |x, y| {
+        let source_x = x.saturating_sub(PADDING).min(image.width() - 1);
+        let source_y = y.saturating_sub(PADDING).min(image.height() - 1);
+        *image.get_pixel(source_x, source_y)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use image::{Rgba, RgbaImage};
+
+    use super::{ShelfAllocator, pad_image};
+
+    #[test]
+    fn allocations_do_not_overlap() {
+        let mut allocator = ShelfAllocator::new(64, 64);
+        let mut allocations = Vec::new();
+
+        while let Some(position) = allocator.allocate(26, 26) {
+            allocations.push(position);
+        }
+
+        // Two images fit next to each other and two shelves fit on the page.
+        assert_eq!(allocations.len(), 4);
+
+        for (index, first) in allocations.iter().enumerate() {
+            for second in &allocations[index + 1..] {
+                let overlaps_x = first.x < second.x + 26 && second.x < first.x + 26;
+                let overlaps_y = first.y < second.y + 26 && second.y < first.y + 26;
+                assert!(!(overlaps_x && overlaps_y));
+            }
+        }
+    }
+
+    #[test]
+    fn smaller_images_reuse_shelves() {
+        let mut allocator = ShelfAllocator::new(64, 64);
+
+        allocator.allocate(20, 30).unwrap();
+        allocator.allocate(20, 10).unwrap();
+        let position = allocator.allocate(20, 20).unwrap();
+
+        assert_eq!((position.x, position.y), (40, 0));
+        assert_eq!(allocator.shelves.len(), 1);
+    }
+
+    #[test]
+    fn oversized_images_are_rejected() {
+        let mut allocator = ShelfAllocator::new(64, 64);
+
+        assert!(allocator.allocate(65, 10).is_none());
+        assert!(allocator.allocate(64, 64).is_some());
+        assert!(allocator.allocate(1, 1).is_none());
+
+        allocator.clear();
+        assert!(allocator.allocate(1, 1).is_some());
+    }
+
+    #[test]
+    fn padding_repeats_edges() {
+        let mut image = RgbaImage::new(2, 1);
+        image.put_pixel(0, 0, Rgba([255, 0, 0, 255]));
+        image.put_pixel(1, 0, Rgba([0, 255, 0, 255]));
+
+        let padded = pad_image(&image);
+
+        assert_eq!((padded.width(), padded.height()), (4, 3));
+        assert_eq!(padded.get_pixel(0, 0), &Rgba([255, 0, 0, 255]));
+        assert_eq!(padded.get_pixel(3, 2), &Rgba([0, 255, 0, 255]));
+        assert_eq!(padded.get_pixel(1, 1), &Rgba([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn padding_empty_image() {
+        let padded = pad_image(&RgbaImage::new(0, 0));
+
+        assert_eq!((padded.width(), padded.height()), (2, 2));
+    }
+}
@@ -0,0 +1,49 @@
+//! Item icons are decoded from many small bitmaps. Custom data packs
+//! sometimes ship icons in a higher resolution, so all icons are scaled down
+//! to a fixed size to make sure they fit into the interface atlas.
+
+use image::RgbaImage;
+use image::imageops::{FilterType, resize};
+
+/// Size of item icons in pixels. The icons of the original client already
+/// have this size.
+const ITEM_ICON_SIZE: u32 = 24;
+
+/// Scale an icon down to fit into [`ITEM_ICON_SIZE`], keeping its aspect
+/// ratio. Smaller icons are returned unchanged.
+pub(super) fn downscale_item_icon(image: RgbaImage) -> RgbaImage {
+    let largest_side = image.width().max(image.height());
+
+    if largest_side <= ITEM_ICON_SIZE {
+        return image;
+    }
+
+    let scale = |side: u32| ((side * ITEM_ICON_SIZE) as f32 / largest_side as f32).round().max(1.0) as u32;
+
+    // The image data is premultiplied, so filtering doesn't bleed the color of
+    // transparent pixels into the edges.
+    resize(&image, scale(image.width()), scale(image.height()), FilterType::Triangle)
+}
+
+#[cfg(test)]
+mod test {
+    use image::{Rgba, RgbaImage};
+
+    use super::{ITEM_ICON_SIZE, downscale_item_icon};
+
+    #[test]
+    fn small_icons_are_unchanged() {
+        let image = RgbaImage::from_pixel(ITEM_ICON_SIZE, 20, Rgba([10, 20, 30, 255]));
+
+        assert_eq!(downscale_item_icon(image.clone()), image);
+    }
+
+    #[test]
+    fn large_icons_keep_their_aspect_ratio() {
+        let image = RgbaImage::from_pixel(ITEM_ICON_SIZE * 4, ITEM_ICON_SIZE * 2, Rgba([255; 4]));
+        let icon = downscale_item_icon(image);
+
+        assert_eq!(icon.dimensions(), (ITEM_ICON_SIZE, ITEM_ICON_SIZE / 2));
+        assert!(icon.pixels().all(|pixel| *pixel == Rgba([255; 4])));
+    }
+}
@@ -1,3 +1,7 @@
+mod atlas;
+mod icon;
+mod upload;
+
 use std::io::{Cursor, Read};
 use std::num::{NonZeroU32, NonZeroUsize};
 use std::sync::atomic::Ordering;
@@ -6,9 +10,7 @@ use std::sync::{Arc, Mutex};
 use block_compression::{BC7Settings, CompressionVariant, GpuBlockCompressor};
 use hashbrown::HashMap;
 use image::{GrayImage, ImageBuffer, ImageFormat, ImageReader, Rgba, RgbaImage};
-#[cfg(feature = "debug")]
-use korangar_container::CacheStatistics;
-use korangar_container::SimpleCache;
+use korangar_container::{CacheStatistics, SimpleCache};
 #[cfg(feature = "debug")]
 use korangar_debug::logging::{Colorize, Timer, print_debug};
 use korangar_loaders::FileLoader;
@@ -18,10 +20,13 @@ use wgpu::{
     TextureViewDescriptor, TextureViewDimension,
 };
 
+pub(crate) use self::atlas::ShelfAllocator;
+pub use self::atlas::{AtlasRegion, InterfaceAtlas};
+pub(crate) use self::upload::TextureUploadQueue;
 use super::error::LoadError;
 use super::{
-    FALLBACK_BMP_FILE, FALLBACK_JPEG_FILE, FALLBACK_PNG_FILE, FALLBACK_TGA_FILE, VideoLoader, fix_broken_texture_file_endings,
-    texture_file_dds_name,
+    AssetKind, FALLBACK_BMP_FILE, FALLBACK_JPEG_FILE, FALLBACK_PNG_FILE, FALLBACK_TGA_FILE, FallbackAudit, VideoLoader,
+    fix_broken_texture_file_endings, texture_file_dds_name,
 };
 use crate::SHUTDOWN_SIGNAL;
 use crate::graphics::{BindlessSupport, Capabilities, Lanczos3Drawer, MipMapRenderPassContext, ShaderCompiler, Texture, TextureSet};
@@ -43,10 +48,13 @@ pub struct TextureLoader {
     device: Device,
     queue: Queue,
     game_file_loader: Arc<GameFileLoader>,
+    fallback_audit: Arc<FallbackAudit>,
     mip_map_render_context: MipMapRenderPassContext,
     lanczos3_drawer: Lanczos3Drawer,
     block_compressor: Mutex<GpuBlockCompressor>,
     cache: Mutex<SimpleCache<(String, ImageType), Arc<Texture>>>,
+    interface_atlas: Arc<Mutex<InterfaceAtlas>>,
+    upload_queue: TextureUploadQueue,
     bindless_support: BindlessSupport,
     supports_texture_compression: bool,
     max_texture_binding_array_count: u32,
@@ -59,14 +67,17 @@ impl TextureLoader {
         shader_compiler: &ShaderCompiler,
         capabilities: &Capabilities,
         game_file_loader: Arc<GameFileLoader>,
+        fallback_audit: Arc<FallbackAudit>,
     ) -> Self {
         let lanczos3_drawer = Lanczos3Drawer::new(&device, shader_compiler);
         let block_compressor = Mutex::new(GpuBlockCompressor::new(device.clone(), queue.clone()));
+        let interface_atlas = Arc::new(Mutex::new(InterfaceAtlas::new(device.clone(), queue.clone())));
 
         Self {
             device,
             queue,
             game_file_loader,
+            fallback_audit,
             mip_map_render_context: MipMapRenderPassContext::default(),
             lanczos3_drawer,
             block_compressor,
@@ -74,17 +85,38 @@ impl TextureLoader {
                 NonZeroU32::new(MAX_CACHE_COUNT).unwrap(),
                 NonZeroUsize::new(MAX_CACHE_SIZE).unwrap(),
             )),
+            interface_atlas,
+            upload_queue: TextureUploadQueue::new(),
             bindless_support: capabilities.bindless_support(),
             supports_texture_compression: capabilities.supports_texture_compression(),
             max_texture_binding_array_count: capabilities.get_max_texture_binding_array_count(),
         }
     }
 
-    #[cfg(feature = "debug")]
     pub fn cache_statistics(&self) -> CacheStatistics {
         self.cache.lock().unwrap().statistics()
     }
 
+    /// Drops all cached textures, so they are loaded again the next time they
+    /// are requested.
+    #[cfg(feature = "debug")]
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Evicts cached textures until the cache holds at most `size` bytes.
+    pub fn shrink_cache(&self, size: usize) {
+        self.cache.lock().unwrap().shrink_to_size(size);
+    }
+
+    pub fn interface_atlas(&self) -> Arc<Mutex<InterfaceAtlas>> {
+        self.interface_atlas.clone()
+    }
+
+    pub(crate) fn upload_queue(&self) -> &TextureUploadQueue {
+        &self.upload_queue
+    }
+
     pub fn create_raw(
         &self,
         name: &str,
@@ -160,6 +192,22 @@ impl TextureLoader {
         )
     }
 
+    /// Like [`create_color`](Self::create_color), but the data is uploaded
+    /// by the graphics engine over the next frames. The texture is fully
+    /// transparent until then.
+    pub fn create_color_deferred(&self, name: &str, image: RgbaImage, transparent: bool) -> Arc<Texture> {
+        let texture = self.create_raw(
+            name,
+            image.width(),
+            image.height(),
+            1,
+            TextureFormat::Rgba8UnormSrgb,
+            transparent,
+        );
+        self.upload_queue.push(texture.clone(), image.into_raw());
+        texture
+    }
+
     pub fn create_sdf(&self, name: &str, image: GrayImage) -> Arc<Texture> {
         self.create_raw_with_data(
             name,
@@ -488,6 +536,45 @@ impl TextureLoader {
         Ok(texture)
     }
 
+    /// Load an item icon. Icons are scaled down to a fixed size, so they are
+    /// always packed into the [`InterfaceAtlas`].
+    pub fn load_item_icon(&self, path: &str) -> Result<Arc<Texture>, LoadError> {
+        let path = fix_broken_texture_file_endings(path);
+        let (texture_data, transparent) = self.load_texture_data(&path, false)?;
+        let texture_data = icon::downscale_item_icon(texture_data);
+
+        // Item icons are drawn at their original size, so we don't need mip maps.
+        let texture = self.create_color(&path, texture_data.clone(), transparent);
+
+        if !self.interface_atlas.lock().unwrap().insert(&texture, &texture_data) {
+            #[cfg(feature = "debug")]
+            print_debug!(
+                "[{}] interface atlas is full, rendering '{}' from its own texture",
+                "warning".yellow(),
+                &path
+            );
+        }
+
+        let _result = self
+            .cache
+            .lock()
+            .as_mut()
+            .unwrap()
+            .insert((path.to_string(), ImageType::Color), texture.clone());
+
+        #[cfg(feature = "debug")]
+        if let Err(error) = _result {
+            print_debug!(
+                "[{}] texture could not be added to cache. Path: '{}': {:?}",
+                "error".red(),
+                &path,
+                error
+            );
+        }
+
+        Ok(texture)
+    }
+
     fn try_load_compressed(&self, path: &str) -> Option<Arc<Texture>> {
         if !self.supports_texture_compression {
             return None;
@@ -544,19 +631,24 @@ impl TextureLoader {
                     print_debug!("Replacing with fallback");
                 }
 
+                self.fallback_audit
+                    .record(AssetKind::Texture, path, "unknown image format".to_owned());
+
                 return self.load_texture_data(FALLBACK_PNG_FILE, raw);
             }
         };
 
         let file_data = match self.game_file_loader.get(&format!("data\\texture\\{path}")) {
             Ok(file_data) => file_data,
-            Err(_error) => {
+            Err(error) => {
                 #[cfg(feature = "debug")]
                 {
-                    print_debug!("Failed to load image: {:?}", _error);
+                    print_debug!("Failed to load image: {:?}", error);
                     print_debug!("Replacing with fallback");
                 }
 
+                self.fallback_audit.record(AssetKind::Texture, path, format!("{error:?}"));
+
                 return self.load_texture_data(FALLBACK_PNG_FILE, raw);
             }
         };
@@ -564,13 +656,15 @@ impl TextureLoader {
 
         let mut image_buffer = match reader.decode() {
             Ok(image) => image.to_rgba8(),
-            Err(_error) => {
+            Err(error) => {
                 #[cfg(feature = "debug")]
                 {
-                    print_debug!("Failed to decode image: {:?}", _error);
+                    print_debug!("Failed to decode image: {:?}", error);
                     print_debug!("Replacing with fallback");
                 }
 
+                self.fallback_audit.record(AssetKind::Texture, path, format!("{error:?}"));
+
                 let fallback_path = match image_format {
                     ImageFormat::Bmp => FALLBACK_BMP_FILE,
                     ImageFormat::Jpeg => FALLBACK_JPEG_FILE,
@@ -622,19 +716,24 @@ impl TextureLoader {
                     print_debug!("Replacing with fallback");
                 }
 
+                self.fallback_audit
+                    .record(AssetKind::Texture, path, "unknown image format".to_owned());
+
                 return self.load_grayscale_texture_data(FALLBACK_PNG_FILE);
             }
         };
 
         let file_data = match self.game_file_loader.get(&format!("data\\texture\\{path}")) {
             Ok(file_data) => file_data,
-            Err(_error) => {
+            Err(error) => {
                 #[cfg(feature = "debug")]
                 {
-                    print_debug!("Failed to load image: {:?}", _error);
+                    print_debug!("Failed to load image: {:?}", error);
                     print_debug!("Replacing with fallback");
                 }
 
+                self.fallback_audit.record(AssetKind::Texture, path, format!("{error:?}"));
+
                 return self.load_grayscale_texture_data(FALLBACK_PNG_FILE);
             }
         };
@@ -642,13 +741,15 @@ impl TextureLoader {
 
         let image_buffer = match reader.decode() {
             Ok(image) => image.to_luma8(),
-            Err(_error) => {
+            Err(error) => {
                 #[cfg(feature = "debug")]
                 {
-                    print_debug!("Failed to decode image: {:?}", _error);
+                    print_debug!("Failed to decode image: {:?}", error);
                     print_debug!("Replacing with fallback");
                 }
 
+                self.fallback_audit.record(AssetKind::Texture, path, format!("{error:?}"));
+
                 let fallback_path = match image_format {
                     ImageFormat::Png => FALLBACK_PNG_FILE,
                     ImageFormat::Tga => FALLBACK_TGA_FILE,
@@ -0,0 +1,210 @@
+//! Deferred uploading of texture data. Textures that are loaded while the
+//! game is running are created right away, but their data is only uploaded
+//! by the graphics engine, a bounded number of bytes per frame. This spreads
+//! the cost of loading many textures at once over multiple frames.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use hashbrown::HashSet;
+use wgpu::util::StagingBelt;
+use wgpu::{
+    BufferUsages, COPY_BYTES_PER_ROW_ALIGNMENT, CommandEncoder, Device, Origin3d, TexelCopyBufferInfo, TexelCopyBufferLayout,
+    TexelCopyTextureInfo, TextureAspect,
+};
+
+use crate::graphics::{Buffer, Texture};
+
+/// Number of bytes that are uploaded per frame. A single texture that is
+/// bigger than the budget is still uploaded, but on its own.
+const UPLOAD_BUDGET_PER_FRAME: usize = 4 << 20;
+
+struct PendingUpload {
+    texture: Arc<Texture>,
+    /// Tightly packed pixel data of the first mip level.
+    data: Vec<u8>,
+}
+
+impl PendingUpload {
+    fn bytes_per_row(&self) -> u32 {
+        let size = self.texture.get_size();
+        let block_size = self.texture.get_format().block_copy_size(None).unwrap();
+        size.width * block_size
+    }
+
+    /// Size that the data takes up in the upload buffer, with every row
+    /// padded to the alignment that is needed to copy it into the texture.
+    fn padded_size(&self) -> usize {
+        padded_bytes_per_row(self.bytes_per_row()) as usize * self.texture.get_size().height as usize
+    }
+}
+
+pub(crate) struct TextureUploadQueue {
+    pending: Mutex<VecDeque<PendingUpload>>,
+    upload_buffer: Mutex<Option<Buffer<u8>>>,
+}
+
+impl TextureUploadQueue {
+    pub(crate) fn new() -> Self {
+        Self {
+            pending: Mutex::new(VecDeque::new()),
+            upload_buffer: Mutex::new(None),
+        }
+    }
+
+    /// Queues the data of an uncompressed texture with a single mip level.
+    pub(crate) fn push(&self, texture: Arc<Texture>, data: Vec<u8>) {
+        debug_assert_eq!(texture.get_format().block_dimensions(), (1, 1));
+        debug_assert_eq!(texture.get_texture().mip_level_count(), 1);
+
+        self.pending.lock().unwrap().push_back(PendingUpload { texture, data });
+    }
+
+    /// Stages the uploads of the current frame and records the copies into
+    /// the textures. Textures that are in `visible_textures` are uploaded
+    /// first.
+    pub(crate) fn upload(
+        &self,
+        device: &Device,
+        staging_belt: &mut StagingBelt,
+        command_encoder: &mut CommandEncoder,
+        visible_textures: &HashSet<u64>,
+    ) {
+        let uploads = {
+            let mut pending = self.pending.lock().unwrap();
+
+            // Textures that are only referenced by the queue were dropped before they
+            // were uploaded, so there is no need to upload them at all.
+            pending.retain(|upload| Arc::strong_count(&upload.texture) > 1);
+
+            select_uploads(&mut pending, UPLOAD_BUDGET_PER_FRAME, PendingUpload::padded_size, |upload| {
+                visible_textures.contains(&upload.texture.get_id())
+            })
+        };
+
+        if uploads.is_empty() {
+            return;
+        }
+
+        let mut data = Vec::with_capacity(uploads.iter().map(PendingUpload::padded_size).sum());
+        let mut offsets = Vec::with_capacity(uploads.len());
+
+        for upload in uploads.iter() {
+            let bytes_per_row = upload.bytes_per_row() as usize;
+            let padding = padded_bytes_per_row(upload.bytes_per_row()) as usize - bytes_per_row;
+
+            offsets.push(data.len() as u64);
+
+            for row in upload.data.chunks_exact(bytes_per_row) {
+                data.extend_from_slice(row);
+                data.extend(std::iter::repeat_n(0, padding));
+            }
+        }
+
+        let mut upload_buffer = self.upload_buffer.lock().unwrap();
+        let upload_buffer = upload_buffer.get_or_insert_with(|| {
+            Buffer::with_capacity(
+                device,
+                "texture upload",
+                BufferUsages::COPY_DST | BufferUsages::COPY_SRC,
+                UPLOAD_BUDGET_PER_FRAME as _,
+            )
+        });
+
+        upload_buffer.write(device, staging_belt, command_encoder, &data);
+
+        for (upload, offset) in uploads.iter().zip(offsets) {
+            command_encoder.copy_buffer_to_texture(
+                TexelCopyBufferInfo {
+                    buffer: upload_buffer.get_buffer(),
+                    layout: TexelCopyBufferLayout {
+                        offset,
+                        bytes_per_row: Some(padded_bytes_per_row(upload.bytes_per_row())),
+                        rows_per_image: None,
+                    },
+                },
+                TexelCopyTextureInfo {
+                    texture: upload.texture.get_texture(),
+                    mip_level: 0,
+                    origin: Origin3d::ZERO,
+                    aspect: TextureAspect::All,
+                },
+                upload.texture.get_size(),
+            );
+        }
+    }
+}
+
+fn padded_bytes_per_row(bytes_per_row: u32) -> u32 {
+    bytes_per_row.next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT)
+}
+
+/// Takes the uploads of a single frame out of the queue. Visible uploads are
+/// taken first, the rest in the order they were queued, until the budget is
+/// used up. At least one upload is taken, even if it exceeds the budget.
+fn select_uploads<T>(pending: &mut VecDeque<T>, budget: usize, size: impl Fn(&T) -> usize, is_visible: impl Fn(&T) -> bool) -> Vec<T> {
+    // Stable partition, so uploads keep their order within both groups.
+    let (visible, other): (VecDeque<T>, VecDeque<T>) = pending.drain(..).partition(|upload| is_visible(upload));
+    pending.extend(visible);
+    pending.extend(other);
+
+    let mut selected = Vec::new();
+    let mut used = 0;
+
+    while let Some(upload) = pending.front() {
+        let upload_size = size(upload);
+
+        if !selected.is_empty() && used + upload_size > budget {
+            break;
+        }
+
+        used += upload_size;
+        selected.push(pending.pop_front().unwrap());
+    }
+
+    selected
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::VecDeque;
+
+    use super::{padded_bytes_per_row, select_uploads};
+
+    #[test]
+    fn rows_are_padded_to_copy_alignment() {
+        assert_eq!(padded_bytes_per_row(4), 256);
+        assert_eq!(padded_bytes_per_row(256), 256);
+        assert_eq!(padded_bytes_per_row(260), 512);
+    }
+
+    #[test]
+    fn uploads_stay_within_budget() {
+        let mut pending = VecDeque::from([10, 20, 30, 40]);
+
+        let selected = select_uploads(&mut pending, 35, |size| *size, |_| false);
+
+        assert_eq!(selected, [10, 20]);
+        assert_eq!(pending, [30, 40]);
+    }
+
+    #[test]
+    fn oversized_upload_is_taken_alone() {
+        let mut pending = VecDeque::from([100, 10]);
+
+        let selected = select_uploads(&mut pending, 50, |size| *size, |_| false);
+
+        assert_eq!(selected, [100]);
+        assert_eq!(pending, [10]);
+    }
+
+    #[test]
+    fn visible_uploads_come_first() {
+        let mut pending = VecDeque::from([1, 2, 3, 4]);
+
+        let selected = select_uploads(&mut pending, 2, |_| 1, |upload| upload % 2 == 0);
+
+        assert_eq!(selected, [2, 4]);
+        assert_eq!(pending, [1, 3]);
+    }
+}
@@ -41,21 +41,24 @@ mod networking;
 mod renderer;
 mod settings;
 mod system;
+#[cfg(test)]
+mod test_fixtures;
 mod world;
 
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::io::Cursor;
-use std::net::{SocketAddr, ToSocketAddrs};
 use std::ops::Deref;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Instant;
 
-use cgmath::{Point3, Vector3};
+use cgmath::{Point3, Vector2, Vector3};
 use image::{EncodableLayout, ImageFormat, ImageReader};
 use input::{MouseInputMode, MouseModeExt};
 use inventory::{HotbarPathExt, InventoryPathExt, SkillTreePathExt};
-use korangar_audio::{AudioEngine, SoundEffectKey};
+use korangar_audio::AudioEngine;
 #[cfg(feature = "debug")]
-use korangar_debug::logging::{Colorize, print_debug};
+use korangar_debug::logging::{Colorize, FileLoggingConfiguration, flush_log_file, initialize_file_logging, print_debug};
 #[cfg(feature = "debug")]
 use korangar_debug::profile_block;
 #[cfg(feature = "debug")]
@@ -63,15 +66,19 @@ use korangar_debug::profiling::Profiler;
 use korangar_interface::Interface;
 use korangar_interface::layout::MouseButton;
 use korangar_networking::{
-    DisconnectReason, HotkeyState, LoginServerLoginData, MessageColor, NetworkEvent, NetworkEventBuffer, NetworkingSystem, SellItem,
-    SupportedPacketVersion,
+    CharacterCreationOptions, DisconnectReason, EntityMovement, GameplayError, HotkeyState, MessageColor, NetworkEvent, NetworkEventBuffer,
+    NetworkPlayback, NetworkingSystem, SellItem, SupportedPacketVersion,
 };
 #[cfg(feature = "debug")]
 use networking::{PacketHistory, PacketHistoryCallback};
+#[cfg(feature = "debug")]
+use ragnarok_bytes::{ByteWriter, ToBytes};
+#[cfg(feature = "debug")]
+use ragnarok_packets::ClientTick;
 #[cfg(not(feature = "debug"))]
 use ragnarok_packets::handler::NoPacketCallback;
 use ragnarok_packets::{
-    BuyShopItemsResult, CharacterServerInformation, Direction, DisappearanceReason, HotbarSlot, SellItemsResult, SkillId, SkillType,
+    BuyShopItemsResult, Direction, DisappearanceReason, EntityId, HotbarSlot, ImageLocation, ItemId, SellItemsResult, SkillId, SkillType,
     TilePosition, UnitId, WorldPosition,
 };
 use renderer::InterfaceRenderer;
@@ -79,11 +86,15 @@ use rust_state::{Context, ManuallyAssertExt};
 #[cfg(feature = "debug")]
 use rust_state::{VecIndexExt, VecLookupExt};
 use settings::{
-    AudioSettings, AudioSettingsPathExt, GraphicsSettingsCapabilities, GraphicsSettingsPathExt, InterfaceSettings, InterfaceSettingsPathExt,
+    ActionIntervals, AudioSettings, AudioSettingsPathExt, GraphicsSettingsCapabilities, GraphicsSettingsPathExt, InterfaceSettings,
+    InterfaceSettingsPathExt, SettingsWatcher, Volume,
 };
 use state::localization::Localization;
+use state::moderation::ChatModeration;
 use state::theme::{CursorThemePathExt, IndicatorThemePathExt, InterfaceThemePathExt, WorldThemePathExt};
-use state::{ChatMessage, ClientState, ClientStatePathExt, ClientStateRootExt, client_state, this_entity, this_player};
+use state::{
+    ChatMessage, ChatMessageAggregation, ClientState, ClientStatePathExt, ClientStateRootExt, client_state, this_entity, this_player,
+};
 #[cfg(feature = "debug")]
 use wgpu::Device;
 use wgpu::util::initialize_adapter_from_env_or_default;
@@ -93,7 +104,7 @@ use wgpu::{
     Gles3MinorVersion, Instance, InstanceDescriptor, InstanceFlags, MemoryBudgetThresholds, MemoryHints, NoopBackendOptions, Queue, Trace,
 };
 use winit::application::ApplicationHandler;
-use winit::dpi::{LogicalSize, PhysicalSize};
+use winit::dpi::{LogicalPosition, LogicalSize, PhysicalSize};
 use winit::event::WindowEvent;
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
 use winit::keyboard::PhysicalKey;
@@ -103,14 +114,30 @@ use crate::graphics::*;
 use crate::input::{InputEvent, InputSystem};
 use crate::interface::cursor::{MouseCursor, MouseCursorState};
 use crate::interface::resource::{ItemSource, SkillSource};
+use crate::interface::sound::{InterfaceSound, InterfaceSoundEffects};
+use crate::interface::toast::{ToastCategory, Toasts};
+use crate::interface::transfer::{ItemTransfer, ItemTransferError};
 use crate::interface::windows::*;
+use crate::inventory::{AmmunitionWarning, CartSummary, EquipRequest, SellWarning, Skill};
 use crate::loaders::*;
 #[cfg(feature = "debug")]
 use crate::renderer::DebugMarkerRenderer;
 use crate::renderer::{AlignHorizontal, EffectRenderer, GameInterfaceRenderer};
-use crate::settings::{GameSettingsPathExt, GraphicsSettings, IN_GAME_THEMES_PATH, LightingMode, MENU_THEMES_PATH, WORLD_THEMES_PATH};
+use crate::settings::{
+    EntityShadows, GameSettingsPathExt, GraphicsSettings, IN_GAME_THEMES_PATH, LightingMode, MENU_THEMES_PATH, WORLD_THEMES_PATH,
+};
+#[cfg(feature = "debug")]
+use crate::state::cache_statistics::ClearableCache;
+#[cfg(feature = "debug")]
+use crate::state::map_editor::TransformChange;
+use crate::state::memory_usage::MemoryUsageSources;
+use crate::state::party::off_screen_indicator;
 use crate::state::theme::{InterfaceTheme, InterfaceThemeType, WorldTheme};
-use crate::system::GameTimer;
+use crate::system::{
+    AutoLogin, Benchmark, GameTimer, HeadlessOptions, LaunchOptions, OfflineScripts, Session, crash_report, resolve_service,
+};
+#[cfg(feature = "debug")]
+use crate::system::{ConsoleCommandRegistry, ConsoleOutput, StressTest};
 #[cfg(feature = "debug")]
 use crate::world::MarkerIdentifier;
 use crate::world::*;
@@ -118,9 +145,29 @@ use crate::world::*;
 const CLIENT_NAME: &str = "Korangar";
 const ROLLING_CUTTER_ID: SkillId = SkillId(2036);
 const DEFAULT_MAP: &str = "geffen";
+/// Directory that exported session statistics are saved to.
+const SESSION_STATISTICS_DIRECTORY: &str = "client/session_statistics";
+/// Directory that the fallback audit report is saved to on exit.
+const FALLBACK_AUDIT_DIRECTORY: &str = "client/fallback_audit";
+/// Directory that maps modified in the map editor are exported to.
+#[cfg(feature = "debug")]
+const MAP_EXPORT_DIRECTORY: &str = "client/map_export";
 const START_CAMERA_FOCUS_POINT: Point3<f32> = Point3::new(600.0, 0.0, 240.0);
 const DEFAULT_BACKGROUND_MUSIC: Option<&str> = Some("bgm\\01.mp3");
-const MAIN_MENU_CLICK_SOUND_EFFECT: &str = "버튼소리.wav";
+/// Texture that marks the ground covered by a skill unit.
+const SKILL_UNIT_DECAL_TEXTURE: &str = "effect\\ring_blue.tga";
+/// Color of the area preview of a ground skill if the cursor is in range.
+const SKILL_TARGET_VALID_COLOR: Color = Color::rgba_u8(80, 220, 100, 180);
+/// Color of the area preview of a ground skill if the cursor is out of range.
+const SKILL_TARGET_INVALID_COLOR: Color = Color::rgba_u8(230, 50, 50, 180);
+/// Highlight of the entity under the mouse cursor.
+const HOVERED_ENTITY_HIGHLIGHT: f32 = 0.25;
+/// Highlight of the entity the player is attacking.
+const TARGETED_ENTITY_HIGHLIGHT: f32 = 0.4;
+/// Distance of the off-screen indicators of party members from the edge of
+/// the screen.
+const PARTY_INDICATOR_MARGIN: f32 = 24.0;
+const PARTY_INDICATOR_SIZE: f32 = 16.0;
 // TODO: The number of point lights that can cast shadows should be configurable
 // through the graphics settings. For now I just chose an arbitrary smaller
 // number that should be playable on most devices.
@@ -131,7 +178,16 @@ const INITIAL_SCREEN_SIZE: ScreenSize = ScreenSize {
     height: 720.0,
 };
 
+/// Distance in logical pixels between the windows of multiple client
+/// instances.
+const INSTANCE_WINDOW_OFFSET: f64 = 48.0;
+
 const INITIAL_SCALING_FACTOR: Scaling = Scaling::new(1.0);
+
+/// Maximum number of regular and cosmetic network events processed in a
+/// single frame. Remaining events are processed in the following frames.
+const NETWORK_EVENT_BUDGET: usize = 256;
+
 const FALLBACK_PACKET_VERSION: SupportedPacketVersion = SupportedPacketVersion::_20220406;
 
 static ICON_DATA: &[u8] = include_bytes!("../archive/data/icon.png");
@@ -141,13 +197,24 @@ pub static SHUTDOWN_SIGNAL: LazyLock<AtomicBool> = LazyLock::new(|| AtomicBool::
 
 #[cfg(feature = "debug")]
 const DEBUG_WINDOWS: &[WindowClass] = &[
+    WindowClass::AssetBrowser,
     WindowClass::CacheStatistics,
     WindowClass::ClientStateInspector,
+    WindowClass::Console,
+    WindowClass::EffectPreview,
+    WindowClass::MemoryUsage,
+    WindowClass::ModelViewer,
     WindowClass::PacketInspector,
     WindowClass::Profiler,
     WindowClass::RenderOptions,
+    WindowClass::SpriteDepthBias,
 ];
 
+#[cfg(feature = "debug")]
+const LOG_DIRECTORY: &str = "client/logs";
+#[cfg(feature = "debug")]
+const LOG_FILTER_FILE: &str = "client/log_filter.txt";
+
 // Create the `threads` module.
 #[cfg(feature = "debug")]
 korangar_debug::create_profiler_threads!(threads, {
@@ -156,26 +223,39 @@ korangar_debug::create_profiler_threads!(threads, {
 });
 
 mod character_slots {
+    use std::sync::Arc;
+
     use korangar_interface::element::StateElement;
     use ragnarok_packets::{CharacterId, CharacterInformation};
     use rust_state::{Path, RustState, Selector};
 
+    use crate::loaders::{ActionLoader, AnimationLoader, SpriteLoader};
     use crate::state::ClientState;
+    use crate::world::{AnimationData, EntityType, Library, get_character_part_files};
 
     #[derive(Default, RustState, StateElement)]
     pub struct CharacterSlots {
         slots: Vec<Option<CharacterInformation>>,
+        /// Composed sprites of the characters, shown in the character
+        /// selection.
+        #[hidden_element]
+        previews: Vec<Option<Arc<AnimationData>>>,
     }
 
     impl CharacterSlots {
         pub fn set_slot_count(&mut self, slot_count: usize) {
             self.slots.resize(slot_count, None);
+            self.previews.resize(slot_count, None);
         }
 
         pub fn get_slot_count(&self) -> usize {
             self.slots.len()
         }
 
+        pub fn is_occupied(&self, slot: usize) -> bool {
+            self.slots.get(slot).is_some_and(Option::is_some)
+        }
+
         pub fn add_character(&mut self, character_information: CharacterInformation) {
             let Some(slot) = self.slots.get_mut(character_information.character_number as usize) else {
                 panic!("attempted to add character to a slot that doesn't exist");
@@ -183,20 +263,50 @@ mod character_slots {
 
             assert!(slot.is_none(), "attempted to add a character to an occupied slot");
 
+            self.previews[character_information.character_number as usize] = None;
             *slot = Some(character_information);
         }
 
         pub fn remove_with_id(&mut self, character_id: CharacterId) {
-            self.slots.iter_mut().for_each(|slot| {
+            self.slots.iter_mut().zip(self.previews.iter_mut()).for_each(|(slot, preview)| {
                 if slot
                     .as_ref()
                     .is_some_and(|character_information| character_information.character_id == character_id)
                 {
                     *slot = None;
+                    *preview = None;
                 }
             })
         }
 
+        /// Compose the sprites of all characters that don't have a preview yet.
+        pub fn load_previews(
+            &mut self,
+            library: &Library,
+            sprite_loader: &SpriteLoader,
+            action_loader: &ActionLoader,
+            animation_loader: &AnimationLoader,
+        ) {
+            for (slot, preview) in self.slots.iter().zip(self.previews.iter_mut()) {
+                let Some(character_information) = slot else {
+                    continue;
+                };
+
+                if preview.is_some() {
+                    continue;
+                }
+
+                let entity_part_files = get_character_part_files(library, character_information);
+
+                *preview = match animation_loader.get(&entity_part_files) {
+                    Some(animation_data) => Some(animation_data),
+                    None => animation_loader
+                        .load(sprite_loader, action_loader, EntityType::Player, &entity_part_files)
+                        .ok(),
+                };
+            }
+        }
+
         pub fn with_id(&self, character_id: CharacterId) -> Option<&CharacterInformation> {
             self.slots
                 .iter()
@@ -210,6 +320,7 @@ mod character_slots {
         pub fn set_characters(&mut self, characters: Vec<CharacterInformation>) {
             // Clear the character list.
             self.slots.iter_mut().for_each(|slot| *slot = None);
+            self.previews.iter_mut().for_each(|preview| *preview = None);
 
             characters
                 .into_iter()
@@ -259,8 +370,57 @@ mod character_slots {
         }
     }
 
+    #[derive(Clone, Copy)]
+    struct PreviewPath<P>
+    where
+        P: Copy,
+    {
+        path: P,
+        slot: usize,
+    }
+
+    impl<P> Path<ClientState, Arc<AnimationData>, false> for PreviewPath<P>
+    where
+        P: Path<ClientState, CharacterSlots>,
+    {
+        fn follow<'a>(&self, state: &'a ClientState) -> Option<&'a Arc<AnimationData>> {
+            // SAFETY
+            // Unwrapping is fine here since it's guaranteed to be `Some` from the trait
+            // bounds.
+            self.path
+                .follow(state)
+                .unwrap()
+                .previews
+                .get(self.slot)
+                .and_then(|preview| preview.as_ref())
+        }
+
+        fn follow_mut<'a>(&self, state: &'a mut ClientState) -> Option<&'a mut Arc<AnimationData>> {
+            // SAFETY
+            // Unwrapping is fine here since it's guaranteed to be `Some` from the trait
+            // bounds.
+            self.path
+                .follow_mut(state)
+                .unwrap()
+                .previews
+                .get_mut(self.slot)
+                .and_then(|preview| preview.as_mut())
+        }
+    }
+
+    impl<P> Selector<ClientState, Arc<AnimationData>, false> for PreviewPath<P>
+    where
+        P: Path<ClientState, CharacterSlots>,
+    {
+        fn select<'a>(&'a self, state: &'a ClientState) -> Option<&'a Arc<AnimationData>> {
+            self.follow(state)
+        }
+    }
+
     pub trait CharacterSlotsExt {
         fn in_slot(self, slot: usize) -> impl Path<ClientState, CharacterInformation, false>;
+
+        fn preview_in_slot(self, slot: usize) -> impl Path<ClientState, Arc<AnimationData>, false>;
     }
 
     impl<P> CharacterSlotsExt for P
@@ -270,6 +430,10 @@ mod character_slots {
         fn in_slot(self, slot: usize) -> impl Path<ClientState, CharacterInformation, false> {
             SlotPath { path: self, slot }
         }
+
+        fn preview_in_slot(self, slot: usize) -> impl Path<ClientState, Arc<AnimationData>, false> {
+            PreviewPath { path: self, slot }
+        }
     }
 }
 
@@ -285,6 +449,7 @@ fn main() {
     #[cfg(feature = "debug")]
     let _measurement = threads::Main::start_frame();
 
+    crash_report::install_panic_hook();
     initialize_shutdown_signal();
 
     time_phase!("create global thread pool", {
@@ -318,16 +483,129 @@ fn main() {
         }
     });
 
+    #[cfg(feature = "debug")]
+    time_phase!("initialize file logging", {
+        let mut logging_configuration = FileLoggingConfiguration::new(LOG_DIRECTORY);
+        logging_configuration.filter_file = Some(LOG_FILTER_FILE.into());
+
+        if let Err(error) = initialize_file_logging(logging_configuration) {
+            print_debug!("[{}] failed to initialize file logging: {:?}", "error".red(), error);
+        }
+    });
+
     let args: Vec<String> = std::env::args().collect();
     let sync_cache = args.len() > 1 && &args[1] == "sync-cache";
+    let benchmark_maps = (args.len() > 1 && &args[1] == "benchmark").then(|| args[2..].to_vec());
+
+    if args.len() > 1 && &args[1] == "verify-game-files" {
+        verify_game_files();
+        return;
+    }
+
+    if args.len() > 1 && &args[1] == "headless" {
+        run_headless(args.into_iter().skip(2));
+        return;
+    }
+
+    let launch_options = match sync_cache || benchmark_maps.is_some() {
+        true => LaunchOptions::default(),
+        false => match LaunchOptions::parse(args.into_iter().skip(1)) {
+            Ok(launch_options) => launch_options,
+            Err(error) => {
+                eprintln!("{error}\n\n{}", system::launch_options::USAGE);
+                return;
+            }
+        },
+    };
+
+    if let Some(settings_directory) = &launch_options.settings_directory {
+        settings::set_settings_directory(settings_directory);
+    }
+
+    system::register_instance();
 
-    let Some(mut client) = Client::init(sync_cache) else {
+    let Some(mut client) = Client::init(sync_cache, benchmark_maps, launch_options) else {
         return;
     };
 
     let event_loop = EventLoop::new().unwrap();
     event_loop.set_control_flow(ControlFlow::Poll);
     let _ = event_loop.run_app(&mut client);
+
+    if client.fallback_audit.is_enabled() {
+        export_fallback_audit(&client.fallback_audit);
+    }
+
+    #[cfg(feature = "debug")]
+    flush_log_file();
+}
+
+/// Verify the game files without starting the client and print a report of
+/// all corrupt and missing files.
+fn verify_game_files() {
+    let game_file_loader = GameFileLoader::default();
+    game_file_loader.load_archives_from_settings();
+
+    println!("Verifying game files, this might take a while");
+    let report = game_file_loader.verify_integrity();
+    print!("{report}");
+}
+
+/// Run the gameplay loop with a scripted bot and without creating a window.
+/// The process exits with an error code if the bot fails, so it can be used in
+/// CI.
+fn run_headless(arguments: impl Iterator<Item = String>) {
+    let arguments: Vec<String> = arguments.collect();
+
+    if let [flag, path] = arguments.as_slice()
+        && flag == "--replay"
+    {
+        match NetworkPlayback::load(path) {
+            Ok(playback) => print!("{}", system::headless::replay(playback)),
+            Err(error) => {
+                eprintln!("Failed to load recording {path}: {error}");
+                std::process::exit(1);
+            }
+        }
+
+        return;
+    }
+
+    let options = match HeadlessOptions::parse(arguments) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{error}\n\n{}", system::headless::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let result = system::headless::run(options);
+
+    #[cfg(feature = "debug")]
+    flush_log_file();
+
+    match result {
+        Ok(report) => println!("Finished headless run: {report}"),
+        Err(error) => {
+            eprintln!("Headless run failed: {error}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Save the fallback substitutions of the session, so broken game files can be
+/// tracked down after playing.
+fn export_fallback_audit(fallback_audit: &FallbackAudit) {
+    let timestamp = chrono::offset::Local::now().format("%Y-%m-%d_%H-%M-%S");
+    let path = format!("{FALLBACK_AUDIT_DIRECTORY}/{timestamp}.csv");
+
+    match std::fs::create_dir_all(FALLBACK_AUDIT_DIRECTORY).and_then(|_| std::fs::write(&path, fallback_audit.to_csv())) {
+        Ok(()) => println!(
+            "{} assets were replaced by a fallback, report exported to {path}",
+            fallback_audit.substitution_count()
+        ),
+        Err(error) => eprintln!("Failed to export fallback audit to {path}: {error}"),
+    }
 }
 
 fn initialize_shutdown_signal() {
@@ -338,8 +616,21 @@ fn initialize_shutdown_signal() {
     .expect("Error setting Ctrl-C handler");
 }
 
+/// Write a file exported by the map editor to the export directory.
+#[cfg(feature = "debug")]
+fn export_map_file(path: &str, data: &impl ToBytes) -> Result<(), String> {
+    let mut byte_writer = ByteWriter::new();
+
+    data.to_bytes(&mut byte_writer).map_err(|error| format!("{error:?}"))?;
+
+    std::fs::create_dir_all(MAP_EXPORT_DIRECTORY)
+        .and_then(|_| std::fs::write(path, byte_writer.as_slice()))
+        .map_err(|error| error.to_string())
+}
+
 struct Client {
     game_file_loader: Arc<GameFileLoader>,
+    fallback_audit: Arc<FallbackAudit>,
     action_loader: Arc<ActionLoader>,
     #[cfg(feature = "debug")]
     animation_loader: Arc<AnimationLoader>,
@@ -366,6 +657,8 @@ struct Client {
     model_batches: Vec<ModelBatch>,
     model_instructions: Vec<ModelInstruction>,
     entity_instructions: Vec<EntityInstruction>,
+    shadow_blob_instructions: Vec<ShadowBlobInstruction>,
+    decal_instructions: Vec<DecalInstruction>,
     directional_shadow_model_batches: [Vec<ModelBatch>; PARTITION_COUNT],
     directional_shadow_model_instructions: Vec<ModelInstruction>,
     directional_shadow_entity_instructions: [Vec<EntityInstruction>; PARTITION_COUNT],
@@ -384,6 +677,8 @@ struct Client {
 
     #[cfg(feature = "debug")]
     debug_camera: DebugCamera,
+    #[cfg(feature = "debug")]
+    model_viewer_camera: OrbitCamera,
     start_camera: StartCamera,
     player_camera: PlayerCamera,
     directional_shadow_camera: DirectionalShadowCamera,
@@ -392,22 +687,27 @@ struct Client {
 
     input_event_buffer: Vec<InputEvent>,
     network_event_buffer: NetworkEventBuffer,
-    // TODO: Move or remove this.
-    saved_login_data: Option<LoginServerLoginData>,
-    // TODO: Move or remove this.
-    saved_character_server: Option<CharacterServerInformation>,
-    // TODO: Move or remove this.
-    saved_login_server_address: Option<SocketAddr>,
-    // TODO: Move or remove this.
-    saved_password: String,
-    // TODO: Move or remove this.
-    saved_username: String,
-    // TODO: Move or remove this.
-    saved_packet_version: SupportedPacketVersion,
+    session: Session,
 
     particle_holder: ParticleHolder,
+    chat_message_aggregation: ChatMessageAggregation,
+    chat_moderation: ChatModeration,
+    toasts: Toasts,
+    /// Time at which the player is warned that the memorial dungeon is about
+    /// to be destroyed.
+    instance_warning_time: Option<Instant>,
     point_light_manager: PointLightManager,
     effect_holder: EffectHolder,
+    effect_lights: EffectLights,
+    animation_event_registry: AnimationEventRegistry,
+    triggered_animation_events: Vec<TriggeredAnimationEvent>,
+    decal_holder: DecalHolder,
+    /// Ground skill that is waiting for the player to pick a target tile.
+    targeted_skill: Option<Skill>,
+    combat_feedback: CombatFeedback,
+    /// Equip requests of a gear set. They are sent one at a time and the
+    /// first one is removed once the map server answered it.
+    pending_equip_requests: VecDeque<EquipRequest>,
     path_finder: PathFinder,
 
     point_light_set_buffer: ResourceSetBuffer<LightSourceKey>,
@@ -422,7 +722,7 @@ struct Client {
     #[cfg(feature = "debug")]
     tile_texture_set: Arc<TextureSet>,
 
-    main_menu_click_sound_effect: SoundEffectKey,
+    interface_sound_effects: InterfaceSoundEffects,
 
     #[cfg(feature = "debug")]
     networking_system: NetworkingSystem<PacketHistoryCallback>,
@@ -431,6 +731,9 @@ struct Client {
     audio_engine: Arc<AudioEngine<GameFileLoader>>,
     active_interface_settings: InterfaceSettings,
     active_graphics_settings: GraphicsSettings,
+    active_interface_volume: Volume,
+    active_action_intervals: ActionIntervals,
+    settings_watcher: SettingsWatcher,
     graphics_engine: GraphicsEngine,
     queue: Queue,
     #[cfg(feature = "debug")]
@@ -439,15 +742,34 @@ struct Client {
 
     map: Option<Box<Map>>,
     client_state: Context<ClientState>,
+    /// Scripts of the current map, only set in offline mode.
+    offline_scripts: Option<OfflineScripts>,
+    benchmark: Option<Benchmark>,
+    /// Character slot that is selected once the character list is received,
+    /// set from the command line.
+    launch_character_slot: Option<usize>,
+    /// Counts down before logging in automatically and stays set until the
+    /// character server list is received.
+    auto_login: Option<AutoLogin>,
+    #[cfg(feature = "debug")]
+    stress_test: Option<StressTest>,
+    #[cfg(feature = "debug")]
+    console_commands: ConsoleCommandRegistry<Context<ClientState>>,
+    /// Set when the console was opened, so the text box can be focused once
+    /// the window is laid out.
+    #[cfg(feature = "debug")]
+    focus_console: bool,
 }
 
 impl Client {
-    fn init(sync_cache: bool) -> Option<Self> {
+    fn init(sync_cache: bool, benchmark_maps: Option<Vec<String>>, launch_options: LaunchOptions) -> Option<Self> {
         time_phase!("load graphics settings", {
             let picker_value = Arc::new(AtomicU64::new(0));
             let directional_shadow_partitions = Arc::new(Mutex::new([DirectionalShadowPartition::default(); PARTITION_COUNT]));
             let input_system = InputSystem::new(picker_value.clone());
-            let graphics_settings = GraphicsSettings::new();
+            let first_run = !GraphicsSettings::exists();
+            let mut graphics_settings = GraphicsSettings::new();
+            crash_report::set_graphics_settings(&graphics_settings);
         });
 
         time_phase!("create adapter", {
@@ -470,6 +792,7 @@ impl Client {
             });
 
             let adapter = pollster::block_on(async { initialize_adapter_from_env_or_default(&instance, None).await.unwrap() });
+            crash_report::set_adapter_info(adapter.get_info());
 
             #[cfg(feature = "debug")]
             {
@@ -483,6 +806,16 @@ impl Client {
         time_phase!("create device", {
             let capabilities = Capabilities::from_adapter(&adapter);
 
+            if first_run {
+                let preset = capabilities.recommended_graphics_preset();
+
+                #[cfg(feature = "debug")]
+                print_debug!("first start, using the {} graphics preset", preset.magenta());
+
+                graphics_settings.apply_preset(preset, capabilities.get_supported_msaa());
+                crash_report::set_graphics_settings(&graphics_settings);
+            }
+
             let (device, queue) = pollster::block_on(async {
                 adapter
                     .request_device(&DeviceDescriptor {
@@ -519,6 +852,8 @@ impl Client {
             let game_file_hash = game_file_loader.calculate_hash();
             #[cfg(feature = "debug")]
             print_debug!("game file hash: {}", game_file_hash);
+
+            let benchmark = benchmark_maps.map(|maps| Benchmark::new(maps, game_file_hash));
         });
 
         time_phase!("create audio engine", {
@@ -531,19 +866,27 @@ impl Client {
             std::fs::create_dir_all(IN_GAME_THEMES_PATH).unwrap();
             std::fs::create_dir_all(WORLD_THEMES_PATH).unwrap();
 
-            let model_loader = Arc::new(ModelLoader::new(game_file_loader.clone(), capabilities.bindless_support()));
+            let fallback_audit = Arc::new(FallbackAudit::new(launch_options.audit_fallbacks));
+            let model_loader = Arc::new(ModelLoader::new(
+                game_file_loader.clone(),
+                fallback_audit.clone(),
+                capabilities.bindless_support(),
+                capabilities.get_max_texture_binding_array_count(),
+            ));
             let texture_loader = Arc::new(TextureLoader::new(
                 device.clone(),
                 queue.clone(),
                 &shader_compiler,
                 &capabilities,
                 game_file_loader.clone(),
+                fallback_audit.clone(),
             ));
             let video_loader = Arc::new(VideoLoader::new(game_file_loader.clone(), texture_loader.clone()));
             let font_loader = Arc::new(FontLoader::new(
                 &["NotoSans".to_owned(), "NotoSansKR".to_owned()],
                 &game_file_loader,
                 &texture_loader,
+                queue.clone(),
             ));
             let map_loader = Arc::new(MapLoader::new(
                 device.clone(),
@@ -551,9 +894,19 @@ impl Client {
                 game_file_loader.clone(),
                 audio_engine.clone(),
                 capabilities.bindless_support(),
+                capabilities.get_max_texture_binding_array_count(),
+            ));
+            let sprite_loader = Arc::new(SpriteLoader::new(
+                game_file_loader.clone(),
+                fallback_audit.clone(),
+                texture_loader.clone(),
+                graphics_settings.sprite_upscaling,
+            ));
+            let action_loader = Arc::new(ActionLoader::new(
+                game_file_loader.clone(),
+                fallback_audit.clone(),
+                audio_engine.clone(),
             ));
-            let sprite_loader = Arc::new(SpriteLoader::new(game_file_loader.clone(), texture_loader.clone()));
-            let action_loader = Arc::new(ActionLoader::new(game_file_loader.clone(), audio_engine.clone()));
             let effect_loader = Arc::new(EffectLoader::new(game_file_loader.clone()));
             let animation_loader = Arc::new(AnimationLoader::new());
 
@@ -582,10 +935,13 @@ impl Client {
 
             game_file_loader.load_cache_archive(game_file_hash);
 
+            let map_thumbnail_loader = Arc::new(MapThumbnailLoader::new(game_file_loader.clone(), texture_loader.clone()));
+
             let async_loader = Arc::new(AsyncLoader::new(
                 action_loader.clone(),
                 animation_loader.clone(),
                 map_loader.clone(),
+                map_thumbnail_loader,
                 model_loader.clone(),
                 sprite_loader.clone(),
                 texture_loader.clone(),
@@ -621,6 +977,8 @@ impl Client {
             let model_batches = Vec::default();
             let model_instructions = Vec::default();
             let entity_instructions = Vec::default();
+            let shadow_blob_instructions = Vec::default();
+            let decal_instructions = Vec::default();
             let directional_shadow_model_batches = Default::default();
             let directional_shadow_model_instructions = Vec::default();
             let directional_shadow_entity_instructions = Default::default();
@@ -658,6 +1016,8 @@ impl Client {
         time_phase!("initialize camera", {
             #[cfg(feature = "debug")]
             let debug_camera = DebugCamera::new();
+            #[cfg(feature = "debug")]
+            let model_viewer_camera = OrbitCamera::new();
             let mut start_camera = StartCamera::new();
             let player_camera = PlayerCamera::new();
             let mut directional_shadow_camera = DirectionalShadowCamera::new();
@@ -666,29 +1026,34 @@ impl Client {
         });
 
         // TODO: Move all of these to the ClientState
-        let saved_login_data: Option<LoginServerLoginData> = None;
-        let saved_character_server: Option<CharacterServerInformation> = None;
-        let saved_login_server_address = None;
-        let saved_password = String::new();
-        let saved_username = String::new();
-        let saved_packet_version = FALLBACK_PACKET_VERSION;
+        let session = Session::default();
 
         time_phase!("initialize networking", {
             #[cfg(not(feature = "debug"))]
-            let (networking_system, network_event_buffer) = NetworkingSystem::spawn();
+            let (mut networking_system, network_event_buffer) = NetworkingSystem::spawn();
 
             #[cfg(feature = "debug")]
             let (packet_history, packet_history_callback) = PacketHistory::new();
             #[cfg(feature = "debug")]
-            let (networking_system, network_event_buffer) = NetworkingSystem::spawn_with_callback(packet_history_callback);
+            let (mut networking_system, network_event_buffer) = NetworkingSystem::spawn_with_callback(packet_history_callback);
         });
 
         time_phase!("create resources", {
             let input_event_buffer = Vec::new();
 
             let particle_holder = ParticleHolder::default();
+            let chat_message_aggregation = ChatMessageAggregation::default();
+            let toasts = Toasts::default();
+            let instance_warning_time = None;
             let point_light_manager = PointLightManager::new();
             let effect_holder = EffectHolder::default();
+            let effect_lights = EffectLights::new();
+            let animation_event_registry = AnimationEventRegistry::load(&audio_engine);
+            let triggered_animation_events = Vec::new();
+            let decal_holder = DecalHolder::default();
+            let targeted_skill = None;
+            let combat_feedback = CombatFeedback::default();
+            let pending_equip_requests = VecDeque::new();
             let path_finder = PathFinder::default();
 
             let point_light_set_buffer = ResourceSetBuffer::default();
@@ -720,18 +1085,31 @@ impl Client {
             #[cfg(feature = "debug")]
             let tile_texture_set = Arc::new(tile_texture_set);
 
-            let main_menu_click_sound_effect = audio_engine.load(MAIN_MENU_CLICK_SOUND_EFFECT);
+            let interface_sound_effects = InterfaceSoundEffects::new(audio_engine.clone());
         });
 
         time_phase!("load default map", {
+            let initial_map = launch_options.map.as_deref().unwrap_or(DEFAULT_MAP);
             let map = map_loader
                 .load(
-                    DEFAULT_MAP.to_string(),
+                    initial_map.to_string(),
                     &model_loader,
                     texture_loader.clone(),
-                    video_loader,
+                    video_loader.clone(),
                     &library,
                 )
+                .or_else(|_error| {
+                    #[cfg(feature = "debug")]
+                    print_debug!("[{}] failed to load map {}: {:?}", "error".red(), initial_map.magenta(), _error);
+
+                    map_loader.load(
+                        DEFAULT_MAP.to_string(),
+                        &model_loader,
+                        texture_loader.clone(),
+                        video_loader,
+                        &library,
+                    )
+                })
                 .expect("failed to load initial map");
 
             directional_shadow_camera.set_level_bound(map.get_level_bound());
@@ -749,16 +1127,81 @@ impl Client {
             ));
         });
 
+        let chat_moderation = ChatModeration::new(&client_state.follow(crate::client_state().game_settings()).chat_filters);
+
         let active_interface_settings = client_state.follow(crate::client_state().interface_settings()).clone();
 
-        interface.open_window(LoginWindow::new(
-            ClientState::path().login_window(),
-            ClientState::path().login_settings(),
-            ClientState::path().client_info(),
-        ));
+        let active_interface_volume = *client_state.follow(crate::client_state().audio_settings().interface_volume());
+        audio_engine.set_interface_sound_effect_volume(active_interface_volume.linear());
+
+        let active_action_intervals = *client_state.follow(crate::client_state().game_settings().action_intervals());
+
+        for (action, interval) in active_action_intervals.intervals() {
+            networking_system.set_minimum_action_interval(action, interval);
+        }
+
+        if let Some(server) = &launch_options.server {
+            let service_id = client_state
+                .follow(crate::client_state().client_info())
+                .services
+                .iter()
+                .find(|service| service.display_name.as_ref() == Some(server) || &service.address == server)
+                .map(|service| service.service_id());
+
+            match service_id {
+                Some(service_id) => client_state
+                    .follow_mut(crate::client_state().login_window())
+                    .select_service(service_id),
+                None => eprintln!("unknown server \"{server}\""),
+            }
+        }
+
+        if benchmark.is_none() && !launch_options.offline {
+            interface.open_window(LoginWindow::new(
+                ClientState::path().login_window(),
+                ClientState::path().login_settings(),
+                ClientState::path().client_info(),
+            ));
+        }
+
+        let auto_login = match benchmark.is_none() && !launch_options.offline {
+            true => {
+                let service_id = client_state.follow(crate::client_state().login_window()).selected_service();
+
+                client_state
+                    .follow(crate::client_state().login_settings())
+                    .service_settings
+                    .get(&service_id)
+                    .filter(|service_settings| service_settings.can_log_in_automatically())
+                    .map(|_| AutoLogin::new(service_id))
+            }
+            false => None,
+        };
+
+        let offline_scripts = launch_options.offline.then(|| {
+            let mut offline_scripts = OfflineScripts::default();
+            offline_scripts.load_map(&game_file_loader, initial_map);
+            offline_scripts
+        });
+
+        if let Some(bundle_path) = crash_report::take_previous_crash_report() {
+            interface.open_window(CrashReportWindow::new(bundle_path));
+        }
+
+        #[cfg(feature = "debug")]
+        let console_commands = {
+            let mut console_commands = ConsoleCommandRegistry::default();
+            RenderOptions::register_console_commands(&mut console_commands);
+            state::cache_statistics::CacheStatistics::register_console_commands(&mut console_commands);
+            state::map_editor::MapEditorState::register_console_commands(&mut console_commands);
+            StressTest::register_console_commands(&mut console_commands);
+            settings::register_console_commands(&mut console_commands);
+            console_commands
+        };
 
         Some(Self {
             game_file_loader,
+            fallback_audit,
             action_loader,
             #[cfg(feature = "debug")]
             animation_loader,
@@ -784,6 +1227,8 @@ impl Client {
             model_batches,
             model_instructions,
             entity_instructions,
+            shadow_blob_instructions,
+            decal_instructions,
             directional_shadow_model_batches,
             directional_shadow_model_instructions,
             directional_shadow_entity_instructions,
@@ -799,6 +1244,8 @@ impl Client {
             game_timer,
             #[cfg(feature = "debug")]
             debug_camera,
+            #[cfg(feature = "debug")]
+            model_viewer_camera,
             start_camera,
             player_camera,
             directional_shadow_camera,
@@ -806,15 +1253,21 @@ impl Client {
             point_shadow_camera,
             input_event_buffer,
             network_event_buffer,
-            saved_login_data,
-            saved_character_server,
-            saved_login_server_address,
-            saved_password,
-            saved_username,
-            saved_packet_version,
+            session,
             particle_holder,
+            chat_message_aggregation,
+            chat_moderation,
+            toasts,
+            instance_warning_time,
             point_light_manager,
             effect_holder,
+            effect_lights,
+            animation_event_registry,
+            triggered_animation_events,
+            decal_holder,
+            targeted_skill,
+            combat_feedback,
+            pending_equip_requests,
             path_finder,
             point_light_set_buffer,
             directional_shadow_object_set_buffer,
@@ -826,11 +1279,14 @@ impl Client {
             pathing_texture_set,
             #[cfg(feature = "debug")]
             tile_texture_set,
-            main_menu_click_sound_effect,
+            interface_sound_effects,
             networking_system,
             audio_engine,
             active_interface_settings,
+            active_interface_volume,
+            active_action_intervals,
             active_graphics_settings: graphics_settings,
+            settings_watcher: SettingsWatcher::new(),
             graphics_engine,
             queue,
             #[cfg(feature = "debug")]
@@ -839,6 +1295,16 @@ impl Client {
 
             map: Some(map),
             client_state,
+            offline_scripts,
+            benchmark,
+            launch_character_slot: launch_options.character_slot,
+            auto_login,
+            #[cfg(feature = "debug")]
+            stress_test: None,
+            #[cfg(feature = "debug")]
+            console_commands,
+            #[cfg(feature = "debug")]
+            focus_console: false,
         })
     }
 
@@ -848,6 +1314,18 @@ impl Client {
             return;
         }
 
+        if let Some(benchmark) = &mut self.benchmark {
+            if let Some(map_name) = benchmark.next_map_to_load() {
+                self.async_loader.request_map_load(map_name, None);
+            }
+
+            if benchmark.is_finished() {
+                benchmark.save_results();
+                event_loop.exit();
+                return;
+            }
+        }
+
         #[cfg(feature = "debug")]
         let _measurement = threads::Main::start_frame();
 
@@ -871,6 +1349,8 @@ impl Client {
         self.model_batches.clear();
         self.model_instructions.clear();
         self.entity_instructions.clear();
+        self.shadow_blob_instructions.clear();
+        self.decal_instructions.clear();
         self.directional_shadow_model_batches.iter_mut().for_each(|batch| batch.clear());
         self.directional_shadow_model_instructions.clear();
         self.directional_shadow_entity_instructions
@@ -888,6 +1368,8 @@ impl Client {
         // We can only apply the graphic changes and reconfigure the surface once the
         // previous image was presented. Moving this function to the end of the
         // function results in surface configuration errors under DX12.
+        self.settings_watcher.reload_changed_settings(&mut self.client_state);
+        self.effect_lights.reload_changed();
         self.update_settings();
 
         // TODO: Shouldn't this happen later? After the scaling has been potentially
@@ -906,14 +1388,53 @@ impl Client {
         let animation_timer_ms = self.game_timer.get_animation_timer_ms();
         let client_tick = self.game_timer.get_client_tick();
 
+        if let Some(benchmark) = &mut self.benchmark {
+            benchmark.record_frame(delta_time);
+        }
+
+        self.update_auto_login(delta_time);
+
+        #[cfg(feature = "debug")]
+        self.update_stress_test(delta_time, client_tick);
+
+        #[cfg(feature = "debug")]
+        self.client_state.follow_mut(client_state().asset_browser()).update(client_tick);
+
         #[cfg(feature = "debug")]
         timer_measurement.stop();
 
         // TODO: Rename
-        let input_report = self.input_system.update_delta(client_tick);
+        let mut input_report = self.input_system.update_delta(client_tick);
+
+        // The picker buffer doesn't contain the ground at the edges of the map or
+        // below some effects, so we fall back to intersecting the cursor ray with
+        // the ground on the CPU. Like the picker, we ignore tiles without flags.
+        if matches!(input_report.mouse_target, PickerTarget::Nothing)
+            && self.client_state.try_follow(this_player()).is_some()
+            && let Some(map) = self.map.as_ref()
+        {
+            let window_size = self.graphics_engine.get_window_size();
+            let screen_space_position = Vector2::new(
+                input_report.mouse_position.left / window_size.x.max(1) as f32,
+                input_report.mouse_position.top / window_size.y.max(1) as f32,
+            );
+
+            if let Some(intersection) = map.screen_ground_intersection(&self.player_camera, screen_space_position)
+                && map.get_tile(intersection.tile).is_some_and(|tile| !tile.flags.is_empty())
+            {
+                input_report.mouse_target = PickerTarget::Tile {
+                    x: intersection.tile.x,
+                    y: intersection.tile.y,
+                };
+            }
+        }
 
         self.networking_system.get_events(&mut self.network_event_buffer);
 
+        if let Some(offline_scripts) = &mut self.offline_scripts {
+            offline_scripts.update(delta_time, &mut self.network_event_buffer);
+        }
+
         #[cfg(feature = "debug")]
         let picker_measurement = Profiler::start_measurement("update picker target");
 
@@ -935,15 +1456,12 @@ impl Client {
         #[cfg(feature = "debug")]
         let network_event_measurement = Profiler::start_measurement("process network events");
 
-        for event in self.network_event_buffer.drain() {
-            match event {
-                NetworkEvent::LoginServerConnected {
-                    character_servers,
-                    login_data,
-                } => {
-                    self.audio_engine.play_sound_effect(self.main_menu_click_sound_effect);
+        for event in self.network_event_buffer.drain(NETWORK_EVENT_BUDGET) {
+            self.session.handle_event(&mut self.networking_system, &event);
 
-                    self.saved_login_data = Some(login_data);
+            match event {
+                NetworkEvent::LoginServerConnected { character_servers, .. } => {
+                    self.interface_sound_effects.play(InterfaceSound::Click);
 
                     *self.client_state.follow_mut(client_state().character_servers()) = character_servers;
 
@@ -953,29 +1471,43 @@ impl Client {
                     #[cfg(feature = "debug")]
                     self.interface.close_all_windows_except(DEBUG_WINDOWS);
 
-                    self.interface
-                        .open_window(ServerSelectionWindow::new(client_state().character_servers()));
+                    // Auto-login picks the last used character server, or the only one if the
+                    // server was never selected before.
+                    let character_server_information = self.auto_login.take().and_then(|auto_login| {
+                        let character_servers = self.client_state.follow(client_state().character_servers());
+                        let last_character_server = self
+                            .client_state
+                            .follow(client_state().login_settings())
+                            .service_settings
+                            .get(&auto_login.service_id())?
+                            .last_character_server
+                            .as_ref();
+
+                        let character_server = match last_character_server {
+                            Some(server_name) => character_servers.iter().find(|server| &server.server_name == server_name),
+                            None if character_servers.len() == 1 => character_servers.first(),
+                            None => None,
+                        };
+
+                        character_server.cloned()
+                    });
+
+                    match character_server_information {
+                        Some(character_server_information) => self.input_event_buffer.push(InputEvent::SelectServer {
+                            character_server_information,
+                        }),
+                        None => self
+                            .interface
+                            .open_window(ServerSelectionWindow::new(client_state().character_servers())),
+                    }
                 }
                 NetworkEvent::LoginServerConnectionFailed { message, .. } => {
                     self.networking_system.disconnect_from_login_server();
+                    self.auto_login = None;
 
+                    self.interface_sound_effects.play(InterfaceSound::Error);
                     self.interface.open_window(ErrorWindow::new(message.to_owned()));
                 }
-                NetworkEvent::LoginServerDisconnected { reason } => {
-                    if reason != DisconnectReason::ClosedByClient {
-                        // TODO: Make this an on-screen popup.
-                        #[cfg(feature = "debug")]
-                        print_debug!("Disconnection from the character server with error");
-
-                        let socket_address = self.saved_login_server_address.unwrap();
-                        self.networking_system.connect_to_login_server(
-                            self.saved_packet_version,
-                            socket_address,
-                            &self.saved_username,
-                            &self.saved_password,
-                        );
-                    }
-                }
                 NetworkEvent::CharacterServerConnected { normal_slot_count } => {
                     self.client_state
                         .follow_mut(client_state().character_slots())
@@ -985,19 +1517,17 @@ impl Client {
                 }
                 NetworkEvent::CharacterServerConnectionFailed { message, .. } => {
                     self.networking_system.disconnect_from_character_server();
+                    self.interface_sound_effects.play(InterfaceSound::Error);
                     self.interface.open_window(ErrorWindow::new(message.to_owned()));
                 }
+                // The session reconnects after losing the connection.
+                // TODO: Make losing the connection an on-screen popup.
+                NetworkEvent::LoginServerDisconnected { .. } => {}
                 NetworkEvent::CharacterServerDisconnected { reason } => {
-                    if reason != DisconnectReason::ClosedByClient {
-                        // TODO: Make this an on-screen popup.
-                        #[cfg(feature = "debug")]
-                        print_debug!("Disconnection from the character server with error");
+                    if reason == DisconnectReason::ClosedByClient && !self.networking_system.is_map_server_connected() {
+                        self.async_loader.clear_retained_maps();
+                        self.async_loader.clear_preloaded_maps();
 
-                        let login_data = self.saved_login_data.as_ref().unwrap();
-                        let server = self.saved_character_server.clone().unwrap();
-                        self.networking_system
-                            .connect_to_character_server(self.saved_packet_version, login_data, server);
-                    } else if !self.networking_system.is_map_server_connected() {
                         #[cfg(not(feature = "debug"))]
                         self.interface.close_all_windows();
 
@@ -1013,25 +1543,48 @@ impl Client {
                 }
                 NetworkEvent::MapServerDisconnected { reason } => {
                     if reason != DisconnectReason::ClosedByClient {
-                        // TODO: Make this an on-screen popup.
                         #[cfg(feature = "debug")]
                         print_debug!("Disconnection from the map server with error");
-                    }
 
-                    let login_data = self.saved_login_data.as_ref().unwrap();
-                    let server = self.saved_character_server.clone().unwrap();
-                    self.networking_system
-                        .connect_to_character_server(self.saved_packet_version, login_data, server);
+                        if self
+                            .client_state
+                            .follow(client_state().game_settings())
+                            .shows_toast(ToastCategory::SystemWarning)
+                        {
+                            self.toasts.push(
+                                ToastCategory::SystemWarning,
+                                "Disconnected from the map server".to_owned(),
+                                Instant::now(),
+                            );
+                        }
+
+                        // Reconnect straight to the character that was played.
+                        if let Some(service_settings) = self
+                            .client_state
+                            .follow(client_state().login_settings())
+                            .recent_service_settings()
+                            && service_settings.can_log_in_automatically()
+                        {
+                            self.launch_character_slot = self.launch_character_slot.or(service_settings.last_character_slot);
+                        }
+                    }
 
-                    self.map = None;
+                    // Keep the map loaded, since the next character might be on the same map.
+                    if let Some(map) = self.map.take() {
+                        self.async_loader.retain_map(map);
+                    }
 
                     self.particle_holder.clear();
                     self.effect_holder.clear();
+                    self.decal_holder.clear();
+                    self.targeted_skill = None;
+                    self.combat_feedback.clear();
                     self.point_light_manager.clear();
                     self.audio_engine.clear_ambient_sound();
 
                     self.client_state.follow_mut(client_state().entities()).clear();
-                    self.client_state.follow_mut(client_state().dead_entities()).clear();
+                    self.client_state.follow_mut(client_state().despawning_entities()).clear();
+                    self.client_state.follow_mut(client_state().party()).clear();
 
                     self.audio_engine.play_background_music_track(None);
 
@@ -1071,6 +1624,16 @@ impl Client {
                         self.interface.close_window_with_class(WindowClass::Respawn);
                     }
                 }
+                NetworkEvent::PlayerSitDown { entity_id } => {
+                    if let Some(entity) = self
+                        .client_state
+                        .follow_mut(client_state().entities())
+                        .iter_mut()
+                        .find(|entity| entity.get_entity_id() == entity_id)
+                    {
+                        entity.set_sitting(client_tick);
+                    }
+                }
                 NetworkEvent::PlayerStandUp { entity_id } => {
                     if let Some(entity) = self
                         .client_state
@@ -1081,15 +1644,43 @@ impl Client {
                         entity.set_idle(client_tick);
                     }
                 }
+                NetworkEvent::EntityPickUpItem { entity_id } => {
+                    if let Some(entity) = self
+                        .client_state
+                        .follow_mut(client_state().entities())
+                        .iter_mut()
+                        .find(|entity| entity.get_entity_id() == entity_id)
+                    {
+                        entity.set_pick_up(client_tick);
+                    }
+                }
+                NetworkEvent::EntityStateChanged {
+                    entity_id,
+                    body_state,
+                    hidden,
+                } => {
+                    if let Some(entity) = self
+                        .client_state
+                        .follow_mut(client_state().entities())
+                        .iter_mut()
+                        .find(|entity| entity.get_entity_id() == entity_id)
+                    {
+                        entity.set_state(body_state, hidden, client_tick);
+                    }
+                }
                 NetworkEvent::AccountId { .. } => {}
                 NetworkEvent::CharacterList { characters } => {
-                    self.audio_engine.play_sound_effect(self.main_menu_click_sound_effect);
+                    self.interface_sound_effects.play(InterfaceSound::Click);
 
-                    self.client_state
-                        .follow_mut(client_state().character_slots())
-                        .set_characters(characters);
+                    let character_slots = self.client_state.follow_mut(client_state().character_slots());
+                    character_slots.set_characters(characters);
+                    character_slots.load_previews(&self.library, &self.sprite_loader, &self.action_loader, &self.animation_loader);
 
-                    if !self.interface.is_window_with_class_open(WindowClass::CharacterSelection) {
+                    if let Some(slot) = self.launch_character_slot.take()
+                        && self.client_state.follow(client_state().character_slots()).is_occupied(slot)
+                    {
+                        let _ = self.networking_system.select_character(slot);
+                    } else if !self.interface.is_window_with_class_open(WindowClass::CharacterSelection) {
                         // TODO: this will do one unnecessary restore_focus. check
                         // if that will be problematic
 
@@ -1105,7 +1696,10 @@ impl Client {
                         ));
                     }
                 }
-                NetworkEvent::CharacterSelectionFailed { message, .. } => self.interface.open_window(ErrorWindow::new(message.to_owned())),
+                NetworkEvent::CharacterSelectionFailed { message, .. } => {
+                    self.interface_sound_effects.play(InterfaceSound::Error);
+                    self.interface.open_window(ErrorWindow::new(message.to_owned()));
+                }
                 NetworkEvent::CharacterDeleted => {
                     if let Some(character_id) = self.client_state.follow_mut(client_state().currently_deleting()).take() {
                         self.client_state
@@ -1115,18 +1709,14 @@ impl Client {
                 }
                 NetworkEvent::CharacterDeletionFailed { message, .. } => {
                     *self.client_state.follow_mut(client_state().currently_deleting()) = None;
+                    self.interface_sound_effects.play(InterfaceSound::Error);
                     self.interface.open_window(ErrorWindow::new(message.to_owned()))
                 }
                 NetworkEvent::CharacterSelected { login_data, .. } => {
-                    self.audio_engine.play_sound_effect(self.main_menu_click_sound_effect);
+                    self.interface_sound_effects.play(InterfaceSound::Click);
 
-                    let saved_login_data = self.saved_login_data.as_ref().unwrap();
-                    self.networking_system.disconnect_from_character_server();
-                    self.networking_system
-                        .connect_to_map_server(self.saved_packet_version, saved_login_data, login_data);
-                    // Ask for the client tick right away, so that the player isn't de-synced when
-                    // they spawn on the map.
-                    let _ = self.networking_system.request_client_tick();
+                    // The character server only lets a character be selected after logging in.
+                    let account_id = self.session.login_data().unwrap().account_id;
 
                     let character_information = self
                         .client_state
@@ -1135,7 +1725,7 @@ impl Client {
                         .cloned()
                         .unwrap();
 
-                    let mut player = Entity::Player(Player::new(saved_login_data.account_id, &character_information, client_tick));
+                    let mut player = Entity::Player(Player::new(account_id, &character_information, client_tick));
 
                     *self.client_state.follow_mut(client_state().player_name()) = character_information.name;
 
@@ -1164,102 +1754,143 @@ impl Client {
                     ));
                     self.interface
                         .open_window(ChatWindow::new(client_state().chat_window(), client_state().chat_messages()));
-                    self.interface.open_window(HotbarWindow::new(client_state().hotbar().skills()));
+                    self.interface.open_window(HotbarWindow::new(client_state().hotbar()));
+
+                    // Reopen the windows that were open the last time the client was in game.
+                    let restored_windows =
+                        self.interface
+                            .window_cache()
+                            .open_window_classes()
+                            .filter_map(|window_class| match window_class {
+                                WindowClass::Inventory => Some(InputEvent::ToggleInventoryWindow),
+                                WindowClass::Equipment => Some(InputEvent::ToggleEquipmentWindow),
+                                WindowClass::SkillTree => Some(InputEvent::ToggleSkillTreeWindow),
+                                WindowClass::Stats => Some(InputEvent::ToggleStatsWindow),
+                                WindowClass::FriendList => Some(InputEvent::ToggleFriendListWindow),
+                                WindowClass::Minimap => Some(InputEvent::ToggleMinimapWindow),
+                                _ => None,
+                            });
+                    self.input_event_buffer.extend(restored_windows);
 
                     // Put the dialog system in a well-defined state.
                     self.client_state.follow_mut(client_state().dialog_window()).end();
 
-                    self.map = None;
+                    self.client_state.follow_mut(client_state().session_statistics()).forget_character();
+
+                    // Keep the map loaded, since the next character might be on the same map.
+                    if let Some(map) = self.map.take() {
+                        self.async_loader.retain_map(map);
+                    }
 
                     self.particle_holder.clear();
                     self.effect_holder.clear();
+                    self.decal_holder.clear();
+                    self.targeted_skill = None;
+                    self.combat_feedback.clear();
                     self.point_light_manager.clear();
                     self.audio_engine.clear_ambient_sound();
                 }
                 NetworkEvent::CharacterCreated { character_information } => {
-                    self.client_state
-                        .follow_mut(client_state().character_slots())
-                        .add_character(character_information);
+                    let character_slots = self.client_state.follow_mut(client_state().character_slots());
+                    character_slots.add_character(character_information);
+                    character_slots.load_previews(&self.library, &self.sprite_loader, &self.action_loader, &self.animation_loader);
 
                     self.interface.close_window_with_class(WindowClass::CharacterCreation);
                 }
                 NetworkEvent::CharacterCreationFailed { message, .. } => {
+                    self.interface_sound_effects.play(InterfaceSound::Error);
                     self.interface.open_window(ErrorWindow::new(message.to_owned()));
                 }
                 NetworkEvent::CharacterSlotSwitched => {
                     *self.client_state.follow_mut(client_state().switch_request()) = None;
                 }
                 NetworkEvent::CharacterSlotSwitchFailed => {
+                    self.interface_sound_effects.play(InterfaceSound::Error);
                     self.interface
                         .open_window(ErrorWindow::new("Failed to switch character slots".to_owned()));
                 }
-                NetworkEvent::AddEntity { entity_data } => {
-                    if let Some(map) = &self.map
-                        && let Some(npc) = Npc::new(map, &mut self.path_finder, entity_data, client_tick)
-                    {
-                        let mut npc = Entity::Npc(npc);
+                NetworkEvent::AddEntities { entities: entity_data } => {
+                    if let Some(map) = &self.map {
+                        let mut added_entity_ids = HashSet::with_capacity(entity_data.len());
+
+                        // An entity can appear multiple times in a single batch, in which case the
+                        // last entry is the most recent one.
+                        let mut new_entities: Vec<Entity> = entity_data
+                            .into_iter()
+                            .rev()
+                            .filter(|entity_data| added_entity_ids.insert(entity_data.entity_id))
+                            .filter_map(|entity_data| Npc::new(map, &mut self.path_finder, entity_data, client_tick))
+                            .map(|npc| {
+                                let mut npc = Entity::Npc(npc);
+
+                                let entity_id = npc.get_entity_id();
+                                let entity_type = npc.get_entity_type();
+                                let entity_part_files = npc.get_entity_part_files(&self.library);
+
+                                if let Some(animation_data) =
+                                    self.async_loader
+                                        .request_animation_data_load(entity_id, entity_type, entity_part_files)
+                                {
+                                    npc.set_animation_data(animation_data);
+                                }
 
-                        let entity_id = npc.get_entity_id();
-                        let entity_type = npc.get_entity_type();
-                        let entity_part_files = npc.get_entity_part_files(&self.library);
+                                #[cfg(feature = "debug")]
+                                npc.generate_pathing_mesh(&self.device, &self.queue, self.graphics_engine.bindless_support(), map);
+
+                                npc
+                            })
+                            .collect();
+                        new_entities.reverse();
 
+                        let new_entity_ids: HashSet<EntityId> = new_entities.iter().map(Entity::get_entity_id).collect();
                         let entities = self.client_state.follow_mut(client_state().entities());
 
                         // Sometimes (like after a job change) the server will tell the client
                         // that a new entity appeared, even though it was already on screen. So
                         // to prevent the entity existing twice, we remove the old one.
-                        entities.retain(|entity| entity.get_entity_id() != entity_id);
-
-                        if let Some(animation_data) =
-                            self.async_loader
-                                .request_animation_data_load(entity_id, entity_type, entity_part_files)
-                        {
-                            npc.set_animation_data(animation_data);
-                        }
-
-                        #[cfg(feature = "debug")]
-                        npc.generate_pathing_mesh(&self.device, &self.queue, self.graphics_engine.bindless_support(), map);
+                        entities.retain(|entity| !new_entity_ids.contains(&entity.get_entity_id()));
+                        entities.extend(new_entities);
 
-                        entities.push(npc);
+                        // An entity that comes back into sight replaces its fading copy. Corpses are
+                        // kept until they faded out, since monsters respawn with the same entity id.
+                        self.client_state
+                            .follow_mut(client_state().despawning_entities())
+                            .retain(|entity| entity.is_corpse() || !new_entity_ids.contains(&entity.get_entity_id()));
                     }
                 }
                 NetworkEvent::RemoveEntity { entity_id, reason } => {
-                    //If the motive is dead, you need to set the player to dead
-                    if reason == DisappearanceReason::Died {
-                        if let Some(entity) = self
-                            .client_state
-                            .follow_mut(client_state().entities())
-                            .iter_mut()
-                            .find(|entity| entity.get_entity_id() == entity_id)
-                        {
-                            let entity_type = entity.get_entity_type();
+                    let monster_name = self
+                        .client_state
+                        .follow(client_state().entities())
+                        .iter()
+                        .find(|entity| entity.get_entity_id() == entity_id && entity.get_entity_type() == EntityType::Monster)
+                        .map(|entity| self.library.get_job_identity_from_id(entity.get_job()));
 
-                            if entity_type == EntityType::Monster {
-                                let mut entity = entity.clone();
-                                entity.set_dead(client_tick);
-                                entity.stop_movement();
+                    if let Some(monster_name) = monster_name {
+                        self.client_state
+                            .follow_mut(client_state().session_statistics())
+                            .register_disappearance(entity_id, reason == DisappearanceReason::Died, monster_name);
+                    }
 
-                                // Remove the entity from the list of alive entities.
-                                self.client_state
-                                    .follow_mut(client_state().entities())
-                                    .retain(|entity| entity.get_entity_id() != entity_id);
+                    let entities = self.client_state.follow_mut(client_state().entities());
 
-                                // Add the entity to the list of dead entities.
-                                self.client_state.follow_mut(client_state().dead_entities()).push(entity);
-                            } else if entity_type == EntityType::Player {
-                                entity.set_dead(client_tick);
+                    if let Some(index) = entities.iter().position(|entity| entity.get_entity_id() == entity_id) {
+                        // Dead players stay on the map until they respawn.
+                        if reason == DisappearanceReason::Died && entities[index].get_entity_type() == EntityType::Player {
+                            entities[index].set_dead(client_tick);
 
-                                // If the player is us, we need to open the respawn window.
-                                if entity_id == self.client_state.follow(client_state().entities())[0].get_entity_id() {
-                                    self.interface.open_window(RespawnWindow);
-                                }
+                            // If the player is us, we need to open the respawn window.
+                            if index == 0 {
+                                self.interface.open_window(RespawnWindow);
                             }
+                        } else {
+                            // Removing the entity from the list of entities frees the entity id right
+                            // away, while the despawning copy fades out on its own.
+                            let mut entity = entities.remove(index);
+                            entity.despawn(&reason, client_tick);
+
+                            self.client_state.follow_mut(client_state().despawning_entities()).push(entity);
                         }
-                    } else {
-                        // TODO: Fade entity out.
-                        self.client_state
-                            .follow_mut(client_state().entities())
-                            .retain(|entity| entity.get_entity_id() != entity_id);
                     }
 
                     // If the entity that was removed had an attack buffered we remove the entity
@@ -1269,27 +1900,25 @@ impl Client {
                         *buffered_attack_entity = None;
                     }
                 }
-                NetworkEvent::EntityMove {
-                    entity_id,
-                    origin,
-                    destination,
-                    starting_timestamp,
-                } => {
-                    let entities = self.client_state.follow_mut(client_state().entities());
-                    let entity = entities.iter_mut().find(|entity| entity.get_entity_id() == entity_id);
-
-                    if let Some(entity) = entity
-                        && let Some(map) = &self.map
-                    {
-                        entity.move_from_to(
-                            map,
-                            &mut self.path_finder,
-                            origin.tile_position(),
-                            destination.tile_position(),
-                            starting_timestamp,
-                        );
-                        #[cfg(feature = "debug")]
-                        entity.generate_pathing_mesh(&self.device, &self.queue, self.graphics_engine.bindless_support(), map);
+                NetworkEvent::EntityMoves { movements } => {
+                    if let Some(map) = &self.map {
+                        // Only the most recent movement of every entity needs to be applied.
+                        let movements: HashMap<EntityId, EntityMovement> =
+                            movements.into_iter().map(|movement| (movement.entity_id, movement)).collect();
+
+                        for entity in self.client_state.follow_mut(client_state().entities()).iter_mut() {
+                            if let Some(movement) = movements.get(&entity.get_entity_id()) {
+                                entity.move_from_to(
+                                    map,
+                                    &mut self.path_finder,
+                                    movement.origin.tile_position(),
+                                    movement.destination.tile_position(),
+                                    movement.starting_timestamp,
+                                );
+                                #[cfg(feature = "debug")]
+                                entity.generate_pathing_mesh(&self.device, &self.queue, self.graphics_engine.bindless_support(), map);
+                            }
+                        }
                     }
                 }
                 NetworkEvent::PlayerMove {
@@ -1312,28 +1941,88 @@ impl Client {
                     }
                 }
                 NetworkEvent::ChangeMap { map_name, position } => {
-                    self.map = None;
+                    let previous_map = self.map.take();
+
                     self.particle_holder.clear();
                     self.effect_holder.clear();
+                    self.decal_holder.clear();
+                    self.targeted_skill = None;
+                    self.combat_feedback.clear();
                     self.point_light_manager.clear();
                     self.audio_engine.clear_ambient_sound();
 
-                    // Only the player must stay alive between map changes.
-                    self.client_state.follow_mut(client_state().entities()).truncate(1);
-                    self.client_state.follow_mut(client_state().dead_entities()).clear();
+                    self.client_state
+                        .follow_mut(client_state().session_statistics())
+                        .clear_attacked_entities();
+
+                    // Only the player must stay alive between map changes. There is no player in
+                    // offline mode.
+                    let player_count = usize::from(self.client_state.try_follow(this_entity()).is_some());
+                    self.client_state.follow_mut(client_state().entities()).truncate(player_count);
+                    self.client_state.follow_mut(client_state().despawning_entities()).clear();
+                    self.client_state.follow_mut(client_state().party()).clear_positions();
 
                     // Close any remaining dialogs.
                     self.interface.close_window_with_class(WindowClass::Dialog);
 
+                    // Warping inside the same map reuses the map that was just left.
+                    if let Some(map) = previous_map {
+                        match *self.client_state.follow(client_state().graphics_settings().preload_adjacent_maps()) {
+                            true => self.async_loader.add_preloaded_map(map),
+                            false => self.async_loader.retain_map(map),
+                        }
+                    }
+
                     self.async_loader.request_map_load(map_name, Some(position));
+
+                    // Maps that were kept loaded for a character switch are no longer needed
+                    // once the player moves on.
+                    self.async_loader.clear_retained_maps();
                 }
                 NetworkEvent::UpdateClientTick { client_tick, received_at } => {
                     self.game_timer.set_client_tick(client_tick, received_at);
                 }
+                NetworkEvent::WhisperReceived { sender_name, message } => {
+                    if self
+                        .client_state
+                        .follow(client_state().game_settings())
+                        .is_player_muted(&sender_name)
+                    {
+                        continue;
+                    }
+
+                    self.interface_sound_effects.play(InterfaceSound::Whisper);
+
+                    self.client_state.follow_mut(client_state().chat_messages()).push(ChatMessage::new(
+                        format!("(From {sender_name}) : {message}"),
+                        MessageColor::Rgb {
+                            red: 255,
+                            green: 255,
+                            blue: 0,
+                        },
+                    ));
+                }
                 NetworkEvent::ChatMessage { text, color } => {
-                    self.client_state
-                        .follow_mut(client_state().chat_messages())
-                        .push(ChatMessage::new(text, color));
+                    if self
+                        .chat_moderation
+                        .is_hidden(&self.client_state.follow(client_state().game_settings()).muted_players, &text)
+                    {
+                        continue;
+                    }
+
+                    let aggregation_window = self
+                        .client_state
+                        .follow(client_state().game_settings().popup_aggregation())
+                        .window();
+
+                    let (text, item_links) = self.library.resolve_item_links(&text);
+
+                    self.chat_message_aggregation.push(
+                        self.client_state.follow_mut(client_state().chat_messages()),
+                        ChatMessage::new(text, color).with_item_links(item_links),
+                        aggregation_window,
+                        Instant::now(),
+                    );
                 }
                 NetworkEvent::UpdateEntityDetails { entity_id, name } => {
                     let entity = self
@@ -1366,6 +2055,10 @@ impl Client {
                         .try_follow(this_entity())
                         .is_some_and(|player| player.get_entity_id() == source_entity_id)
                     {
+                        self.client_state
+                            .follow_mut(client_state().session_statistics())
+                            .register_attack(destination_entity_id);
+
                         let auto_attack = *self.client_state.follow(client_state().game_settings().auto_attack());
                         let buffered_attack_entity = self.client_state.follow_mut(client_state().buffered_attack_entity());
 
@@ -1378,6 +2071,34 @@ impl Client {
                         }
                     }
 
+                    let player_entity_id = self.client_state.try_follow(this_entity()).map(|player| player.get_entity_id());
+                    let game_settings = self.client_state.follow(client_state().game_settings());
+
+                    if let Some(damage_amount) = damage_amount {
+                        if player_entity_id == Some(destination_entity_id)
+                            && game_settings.shows_combat_feedback(CombatFeedbackKind::ScreenShake)
+                        {
+                            let maximum_health_points = self
+                                .client_state
+                                .try_follow(this_player())
+                                .map_or(0, |player| player.get_common().maximum_health_points);
+
+                            self.combat_feedback.player_hit(damage_amount, maximum_health_points);
+                        }
+
+                        if game_settings.shows_combat_feedback(CombatFeedbackKind::HitFlash) {
+                            self.combat_feedback.flash(destination_entity_id);
+                        }
+                    }
+
+                    if is_critical
+                        && player_entity_id == Some(source_entity_id)
+                        && game_settings.shows_combat_feedback(CombatFeedbackKind::HitStop)
+                    {
+                        self.combat_feedback
+                            .critical_hit(source_entity_id, destination_entity_id, client_tick);
+                    }
+
                     if let Some(entity) = self
                         .client_state
                         .follow_mut(client_state().entities())
@@ -1399,12 +2120,19 @@ impl Client {
                         .find(|entity| entity.get_entity_id() == destination_entity_id)
                         .or_else(|| self.client_state.try_follow(this_entity()))
                     {
-                        let particle: Box<dyn Particle + Send + Sync> = match damage_amount {
-                            Some(amount) => Box::new(DamageNumber::new(entity.get_position(), amount.to_string(), is_critical)),
-                            None => Box::new(Miss::new(entity.get_position())),
-                        };
-
-                        self.particle_holder.spawn_particle(particle);
+                        let aggregation_window = self
+                            .client_state
+                            .follow(client_state().game_settings().popup_aggregation())
+                            .window();
+
+                        self.particle_holder.spawn_damage(
+                            source_entity_id,
+                            destination_entity_id,
+                            entity.get_position(),
+                            damage_amount,
+                            is_critical,
+                            aggregation_window,
+                        );
                     }
                 }
                 NetworkEvent::HealEffect { entity_id, heal_amount } => {
@@ -1415,8 +2143,13 @@ impl Client {
                         .find(|entity| entity.get_entity_id() == entity_id)
                         .or_else(|| self.client_state.try_follow(this_entity()))
                     {
+                        let aggregation_window = self
+                            .client_state
+                            .follow(client_state().game_settings().popup_aggregation())
+                            .window();
+
                         self.particle_holder
-                            .spawn_particle(Box::new(HealNumber::new(entity.get_position(), heal_amount.to_string())));
+                            .spawn_heal(entity_id, entity.get_position(), heal_amount, aggregation_window);
                     }
                 }
                 NetworkEvent::UpdateEntityHealth {
@@ -1435,6 +2168,10 @@ impl Client {
                     }
                 }
                 NetworkEvent::UpdateStat { stat_type } => {
+                    self.client_state
+                        .follow_mut(client_state().session_statistics())
+                        .update_stat(&stat_type);
+
                     if let Some(player) = self.client_state.try_follow_mut(this_player()) {
                         player.update_stat(stat_type);
                     }
@@ -1478,6 +2215,26 @@ impl Client {
 
                     self.interface.open_window(DialogWindow::new(client_state().dialog_window()));
                 }
+                NetworkEvent::DisplayImage { image_name, location } => match location {
+                    ImageLocation::ClearAll => self.client_state.follow_mut(client_state().dialog_window()).clear_cutin(),
+                    location => {
+                        let path = match image_name.contains('.') {
+                            true => format!("유저인터페이스\\illust\\{image_name}"),
+                            false => format!("유저인터페이스\\illust\\{image_name}.bmp"),
+                        };
+
+                        match self.texture_loader.get_or_load(&path, ImageType::Color) {
+                            Ok(texture) => self
+                                .client_state
+                                .follow_mut(client_state().dialog_window())
+                                .set_cutin(texture, location),
+                            Err(_error) => {
+                                #[cfg(feature = "debug")]
+                                print_debug!("[{}] failed to load cutin {}: {:?}", "error".red(), path.magenta(), _error);
+                            }
+                        }
+                    }
+                },
                 NetworkEvent::AddQuestEffect { quest_effect } => {
                     if let Some(map) = &self.map {
                         self.particle_holder.add_quest_icon(&self.texture_loader, map, quest_effect)
@@ -1488,19 +2245,81 @@ impl Client {
                     self.client_state
                         .follow_mut(client_state().inventory())
                         .fill(&self.async_loader, &self.library, items);
+                    self.sync_hotbar_items();
                 }
                 NetworkEvent::IventoryItemAdded { item } => {
+                    // Items bought from a shop are not looted.
+                    if !self.interface.is_window_with_class_open(WindowClass::Buy) {
+                        let amount = match item.details {
+                            korangar_networking::InventoryItemDetails::Regular { amount, .. } => amount as u32,
+                            korangar_networking::InventoryItemDetails::Equippable { .. } => 1,
+                        };
+                        let item_name = self.library.get_item_name_from_id(item.item_id, item.is_identified());
+
+                        self.client_state
+                            .follow_mut(client_state().session_statistics())
+                            .register_loot(item_name, amount);
+                    }
+
                     self.client_state
                         .follow_mut(client_state().inventory())
                         .add_item(&self.async_loader, &self.library, item);
+                    self.sync_hotbar_items();
 
                     // TODO: Update the selling items. If you pick up an item
                     // that you already have the sell window
                     // should allow you to sell the new
                     // amount of items.
                 }
+                NetworkEvent::ItemUsed {
+                    entity_id,
+                    index,
+                    amount,
+                    success,
+                    ..
+                } => {
+                    let is_player = self
+                        .client_state
+                        .try_follow(this_entity())
+                        .is_some_and(|player| player.get_entity_id() == entity_id);
+
+                    if is_player && success {
+                        self.client_state
+                            .follow_mut(client_state().inventory())
+                            .set_item_amount(index, amount);
+                        self.sync_hotbar_items();
+                    }
+                }
+                NetworkEvent::AmmunitionEquipped { index } => {
+                    self.client_state.follow_mut(client_state().inventory()).equip_ammunition(index);
+                    self.advance_equip_requests(index);
+                }
                 NetworkEvent::InventoryItemRemoved { index, amount, .. } => {
-                    self.client_state.follow_mut(client_state().inventory()).remove_item(index, amount);
+                    let inventory = self.client_state.follow_mut(client_state().inventory());
+                    let ammunition = inventory
+                        .equipped_ammunition()
+                        .filter(|(ammunition_index, _)| *ammunition_index == index);
+
+                    inventory.remove_item(index, amount);
+                    self.sync_hotbar_items();
+
+                    // Ammunition is removed one by one while attacking, so warn the player
+                    // before they run out.
+                    let warning = ammunition.and_then(|(_, previous)| AmmunitionWarning::new(previous, previous.saturating_sub(amount)));
+
+                    if let Some(warning) = warning {
+                        if self
+                            .client_state
+                            .follow(client_state().game_settings())
+                            .shows_toast(ToastCategory::SystemWarning)
+                        {
+                            self.toasts.push(ToastCategory::SystemWarning, warning.to_string(), Instant::now());
+                        }
+
+                        self.client_state
+                            .follow_mut(client_state().chat_messages())
+                            .push(ChatMessage::new(warning.to_string(), MessageColor::Information));
+                    }
                 }
                 NetworkEvent::SkillTree { skill_information } => {
                     self.client_state.follow_mut(client_state().skill_tree()).fill(
@@ -1514,6 +2333,13 @@ impl Client {
                     self.client_state
                         .follow_mut(client_state().inventory())
                         .update_equipped_position(index, equipped_position);
+                    self.advance_equip_requests(index);
+                }
+                NetworkEvent::ItemEquipFailed { index } => {
+                    self.client_state
+                        .follow_mut(client_state().chat_messages())
+                        .push(ChatMessage::new("Failed to equip the item".to_owned(), MessageColor::Error));
+                    self.advance_equip_requests(index);
                 }
                 NetworkEvent::ChangeJob { account_id, job_id } => {
                     let entity = self
@@ -1555,6 +2381,40 @@ impl Client {
                         entity.set_animation_data(animation_data);
                     }
                 }
+                NetworkEvent::EntityDirectionChanged {
+                    entity_id,
+                    direction,
+                    head_direction,
+                } => {
+                    if let Some(entity) = self
+                        .client_state
+                        .follow_mut(client_state().entities())
+                        .iter_mut()
+                        .find(|entity| entity.get_entity_id() == entity_id)
+                    {
+                        entity.set_direction(direction, head_direction);
+                    }
+                }
+                NetworkEvent::ChangeAppearance { account_id, slot, view_id } => {
+                    let Some(entity) = self
+                        .client_state
+                        .follow_mut(client_state().entities())
+                        .iter_mut()
+                        .find(|entity| entity.get_entity_id().0 == account_id.0)
+                    else {
+                        continue;
+                    };
+
+                    entity.set_appearance(slot, view_id);
+
+                    if let Some(animation_data) = self.async_loader.request_animation_data_load(
+                        entity.get_entity_id(),
+                        entity.get_entity_type(),
+                        entity.get_entity_part_files(&self.library),
+                    ) {
+                        entity.set_animation_data(animation_data);
+                    }
+                }
                 NetworkEvent::LoggedOut => {
                     self.networking_system.disconnect_from_map_server();
                 }
@@ -1569,6 +2429,46 @@ impl Client {
                 NetworkEvent::FriendAdded { friend } => {
                     self.client_state.follow_mut(client_state().friend_list()).push(friend);
                 }
+                NetworkEvent::FriendOnlineStatusChanged { name, online } => {
+                    let game_settings = self.client_state.follow(client_state().game_settings());
+
+                    if online && game_settings.shows_toast(ToastCategory::FriendLogin) {
+                        self.toasts
+                            .push(ToastCategory::FriendLogin, format!("{name} has logged in"), Instant::now());
+                    }
+                }
+                NetworkEvent::RareItemDropped {
+                    item_id,
+                    is_identified,
+                    count,
+                } => {
+                    let item_name = self.library.get_item_name_from_id(item_id, is_identified);
+                    let text = match count {
+                        1 => format!("Rare drop: {item_name}"),
+                        count => format!("Rare drop: {count} x {item_name}"),
+                    };
+
+                    if self
+                        .client_state
+                        .follow(client_state().game_settings())
+                        .shows_toast(ToastCategory::RareDrop)
+                    {
+                        self.toasts.push(ToastCategory::RareDrop, text, Instant::now());
+                    }
+                }
+                NetworkEvent::AchievementCompleted { achievement_id } => {
+                    if self
+                        .client_state
+                        .follow(client_state().game_settings())
+                        .shows_toast(ToastCategory::Achievement)
+                    {
+                        self.toasts.push(
+                            ToastCategory::Achievement,
+                            format!("Achievement {achievement_id} completed"),
+                            Instant::now(),
+                        );
+                    }
+                }
                 NetworkEvent::VisualEffect { effect_path, entity_id } => {
                     let effect = self.effect_loader.get_or_load(effect_path, &self.texture_loader).unwrap();
                     let frame_timer = effect.new_frame_timer();
@@ -1584,9 +2484,7 @@ impl Client {
                         // visual artifacts, such as flickering, as the point lights switch
                         // between shadows and no shadows.
                         PointLightId::new(entity_id.0),
-                        Vector3::new(0.0, 12.0, 0.0),
-                        Color::WHITE,
-                        50.0,
+                        self.effect_lights.get(effect_path),
                         false,
                     )));
                 }
@@ -1617,13 +2515,16 @@ impl Client {
                                     EffectCenter::Position(position),
                                     Vector3::new(0.0, 0.0, 0.0),
                                     PointLightId::new(unit_id as u32),
-                                    Vector3::new(0.0, 6.0, 0.0),
-                                    Color::rgb_u8(255, 30, 0),
-                                    60.0,
+                                    self.effect_lights.get("firewall.str"),
                                     true,
                                 )),
                                 entity_id,
                             );
+
+                            let texture = self.texture_loader.get_or_load(SKILL_UNIT_DECAL_TEXTURE, ImageType::Color).unwrap();
+                            let decal = Decal::new(texture, position, Vector2::new(GAT_TILE_SIZE, GAT_TILE_SIZE))
+                                .with_color(Color::rgb_u8(255, 30, 0));
+                            self.decal_holder.add_unit(decal, entity_id);
                         }
                         UnitId::Pneuma => {
                             let Some(position) = map.get_world_position(position) else {
@@ -1642,19 +2543,24 @@ impl Client {
                                     EffectCenter::Position(position),
                                     Vector3::new(0.0, 0.0, 0.0),
                                     PointLightId::new(unit_id as u32),
-                                    Vector3::new(0.0, 6.0, 0.0),
-                                    Color::rgb_u8(83, 220, 108),
-                                    40.0,
+                                    self.effect_lights.get("pneuma1.str"),
                                     false,
                                 )),
                                 entity_id,
                             );
+
+                            // Pneuma covers the 3x3 tiles around its center.
+                            let texture = self.texture_loader.get_or_load(SKILL_UNIT_DECAL_TEXTURE, ImageType::Color).unwrap();
+                            let decal = Decal::new(texture, position, Vector2::new(GAT_TILE_SIZE * 3.0, GAT_TILE_SIZE * 3.0))
+                                .with_color(Color::rgb_u8(83, 220, 108));
+                            self.decal_holder.add_unit(decal, entity_id);
                         }
                         _ => {}
                     }
                 }
                 NetworkEvent::RemoveSkillUnit { entity_id } => {
                     self.effect_holder.remove_unit(entity_id);
+                    self.decal_holder.remove_unit(entity_id);
                 }
                 NetworkEvent::SetFriendList { friend_list } => {
                     *self.client_state.follow_mut(client_state().friend_list()) = friend_list;
@@ -1668,6 +2574,11 @@ impl Client {
 
                     for (index, hotkey) in hotkeys.into_iter().take(10).enumerate() {
                         match hotkey {
+                            HotkeyState::Bound(hotkey) if hotkey.is_skill == 0 => {
+                                self.client_state
+                                    .follow_mut(client_state().hotbar())
+                                    .set_item_slot(HotbarSlot(index as u16), ItemId(hotkey.skill_id));
+                            }
                             HotkeyState::Bound(hotkey) => {
                                 let Some(mut skill) = self
                                     .client_state
@@ -1691,6 +2602,8 @@ impl Client {
                                 .unset_slot(HotbarSlot(index as u16)),
                         }
                     }
+
+                    self.sync_hotbar_items();
                 }
                 NetworkEvent::OpenShop { items } => {
                     // Close the dialog. Some NPCs don't use the `BuyOrSellPacket` and instead use
@@ -1722,9 +2635,24 @@ impl Client {
                         self.interface.close_window_with_class(WindowClass::BuyCart);
                     }
                     BuyShopItemsResult::Error => {
+                        // The server doesn't tell us why the purchase failed, so check the cart
+                        // again in case the zeny or weight changed in the meantime.
+                        let buy_cart = self.client_state.follow(client_state().buy_cart());
+                        let reason = self
+                            .client_state
+                            .try_follow(this_player())
+                            .and_then(|player| {
+                                CartSummary::new(buy_cart.iter().map(|item| (item, item.metadata.1)))
+                                    .check_for_player(player)
+                                    .err()
+                            })
+                            .map(|error| format!("Failed to buy items: {error}"))
+                            .unwrap_or_else(|| "Failed to buy items".to_owned());
+
+                        self.interface_sound_effects.play(InterfaceSound::Error);
                         self.client_state
                             .follow_mut(client_state().chat_messages())
-                            .push(ChatMessage::new("Failed to buy items".to_owned(), MessageColor::Error));
+                            .push(ChatMessage::new(reason, MessageColor::Error));
                     }
                 },
                 NetworkEvent::SellItemList { items } => {
@@ -1743,15 +2671,14 @@ impl Client {
                                 .find(|inventory_item| inventory_item.index == item.inventory_index)
                                 .expect("item not in inventory");
 
-                            let name = inventory_item.metadata.name.clone();
-                            let texture = inventory_item.metadata.texture.clone();
+                            let metadata = inventory_item.metadata.clone();
                             let quantity = match &inventory_item.details {
                                 korangar_networking::InventoryItemDetails::Regular { amount, .. } => *amount,
                                 korangar_networking::InventoryItemDetails::Equippable { .. } => 1,
                             };
 
                             SellItem {
-                                metadata: (ResourceMetadata { name, texture }, quantity),
+                                metadata: (metadata, quantity),
                                 inventory_index: item.inventory_index,
                                 price: item.price,
                                 overcharge_price: item.overcharge_price,
@@ -1779,6 +2706,42 @@ impl Client {
                             .push(ChatMessage::new("Failed to sell items".to_owned(), MessageColor::Error));
                     }
                 },
+                // TODO: Show the balance once there is a bank window.
+                NetworkEvent::BankOpened { result } => self.bank_opened(result),
+                NetworkEvent::BankClosed => {}
+                NetworkEvent::BankBalance { .. } => {}
+                NetworkEvent::BankDepositCompleted { result, zeny, .. } => self.bank_deposit_completed(result, zeny),
+                NetworkEvent::BankWithdrawCompleted { result, zeny, .. } => self.bank_withdraw_completed(result, zeny),
+                // TODO: Show the auctions once there is an auction window.
+                NetworkEvent::OpenAuction | NetworkEvent::CloseAuction => {}
+                NetworkEvent::AuctionListings { .. } => {}
+                NetworkEvent::AuctionItemSet { .. } => {}
+                NetworkEvent::AuctionResult { result } => self.auction_result(result),
+                NetworkEvent::OpenRoulette {
+                    serial,
+                    step,
+                    index,
+                    gold_points,
+                    silver_points,
+                    bronze_points,
+                } => self.open_roulette(serial, step, index, gold_points, silver_points, bronze_points),
+                NetworkEvent::RouletteBoard { serial, items } => self.set_roulette_board(serial, items),
+                NetworkEvent::CloseRoulette => self.close_roulette(),
+                NetworkEvent::RouletteSpun {
+                    won,
+                    step,
+                    index,
+                    gold_points,
+                    silver_points,
+                    bronze_points,
+                } => self.roulette_spun(won, step, index, gold_points, silver_points, bronze_points),
+                NetworkEvent::RouletteRewardClaimed => self.roulette_reward_claimed(),
+                NetworkEvent::OpenAttendance { claimed_days } => self.open_attendance(claimed_days),
+                NetworkEvent::AttendanceRewardClaimed { claimed_days } => self.attendance_reward_claimed(claimed_days),
+                NetworkEvent::InstanceQueued { name, queue_position } => self.instance_queued(name, queue_position),
+                NetworkEvent::InstanceQueuePositionChanged { queue_position } => self.instance_queue_position_changed(queue_position),
+                NetworkEvent::InstanceReady { name, destroy_time, .. } => self.instance_ready(name, destroy_time),
+                NetworkEvent::InstanceDestroyed { reason } => self.instance_destroyed(reason),
                 NetworkEvent::AttackFailed {
                     target_entity_id,
                     target_position,
@@ -1808,6 +2771,33 @@ impl Client {
                         *self.client_state.follow_mut(client_state().buffered_attack_entity()) = Some(target_entity_id);
                     }
                 }
+                NetworkEvent::GameplayError { error } => {
+                    if self
+                        .client_state
+                        .follow(client_state().game_settings())
+                        .shows_toast(ToastCategory::SystemWarning)
+                    {
+                        self.toasts.push(ToastCategory::SystemWarning, error.to_string(), Instant::now());
+                    }
+
+                    self.client_state
+                        .follow_mut(client_state().chat_messages())
+                        .push(ChatMessage::new(error.to_string(), MessageColor::Error));
+                }
+                NetworkEvent::PartyMemberUpdated {
+                    account_id,
+                    name,
+                    map_name,
+                    position,
+                    online,
+                } => self.party_member_updated(account_id, name, map_name, position, online),
+                NetworkEvent::PartyMemberLeft { account_id } => self.party_member_left(account_id),
+                NetworkEvent::PartyMemberMoved { account_id, position } => self.party_member_moved(account_id, position),
+                NetworkEvent::PartyMemberHealthChanged {
+                    account_id,
+                    health_points,
+                    maximum_health_points,
+                } => self.party_member_health_changed(account_id, health_points, maximum_health_points),
             }
         }
 
@@ -1817,12 +2807,26 @@ impl Client {
         #[cfg(feature = "debug")]
         let input_event_measurement = Profiler::start_measurement("process user events");
 
+        // Windows are opened and closed by the interface events and by the user events,
+        // so the sounds are played once both are processed.
+        let previous_window_count = self.interface.window_count();
+
         self.interface.process_events(&mut self.input_event_buffer);
         let interface_has_focus = self.interface.has_focus();
 
+        #[cfg(feature = "debug")]
+        let model_viewer_active = self.model_viewer_active();
+
         if self.interface.get_mouse_mode().is_rotating_camera() {
+            #[cfg(feature = "debug")]
+            if model_viewer_active {
+                let offset = Vector2::new(input_report.mouse_delta.width, input_report.mouse_delta.height);
+                self.input_event_buffer.push(InputEvent::OrbitModelViewer { offset });
+            }
+
             // TODO: Does this really need to be a InputEvent?
             let rotation = input_report.mouse_delta.width;
+            #[cfg_attr(feature = "debug", korangar_debug::debug_condition(!model_viewer_active))]
             self.input_event_buffer.push(InputEvent::RotateCamera { rotation });
         }
 
@@ -1836,6 +2840,18 @@ impl Client {
             );
         }
 
+        #[cfg(feature = "debug")]
+        self.input_system.handle_console_input(
+            &mut self.input_event_buffer,
+            self.interface.is_window_with_class_open(WindowClass::Console),
+        );
+
+        if self.interface.is_window_with_class_open(WindowClass::EmoteWheel) {
+            self.client_state
+                .follow_mut(client_state().emote_wheel())
+                .update_selection(input_report.mouse_position);
+        }
+
         for event in self.input_event_buffer.drain(..) {
             match event {
                 InputEvent::LogIn {
@@ -1849,47 +2865,31 @@ impl Client {
                         .iter()
                         .find(|service| service.service_id() == service_id)
                         .unwrap();
-                    let address = format!("{}:{}", service.address, service.port);
-                    let socket_address = address
-                        .to_socket_addrs()
-                        .expect("Failed to resolve IP")
-                        .next()
-                        .expect("ill formatted service IP");
-
-                    let packet_version = match service.packet_version {
-                        Some(packet_version) => match packet_version {
-                            PacketVersion::_20220406 => SupportedPacketVersion::_20220406,
-                            PacketVersion::Unsupported(packet_version) => {
-                                self.interface.open_window(ErrorWindow::new(format!(
-                                    "Selected server has an unsupported package version: {packet_version}"
-                                )));
-                                continue;
-                            }
-                        },
-                        None => FALLBACK_PACKET_VERSION,
-                    };
 
-                    self.saved_login_server_address = Some(socket_address);
-                    self.saved_username = username.clone();
-                    self.saved_password = password.clone();
-                    self.saved_packet_version = packet_version;
-
-                    self.networking_system
-                        .connect_to_login_server(packet_version, socket_address, username, password);
+                    match resolve_service(service) {
+                        Ok((socket_address, packet_version)) => {
+                            self.session
+                                .log_in(&mut self.networking_system, packet_version, socket_address, username, password);
+                        }
+                        Err(message) => {
+                            self.interface_sound_effects.play(InterfaceSound::Error);
+                            self.interface.open_window(ErrorWindow::new(message));
+                        }
+                    }
                 }
                 InputEvent::SelectServer {
                     character_server_information,
                 } => {
-                    self.saved_character_server = Some(character_server_information.clone());
-
-                    self.networking_system.disconnect_from_login_server();
+                    if let Some(service_settings) = self
+                        .client_state
+                        .follow_mut(client_state().login_settings())
+                        .recent_service_settings_mut()
+                    {
+                        service_settings.last_character_server = Some(character_server_information.server_name.clone());
+                    }
 
-                    // Korangar should never attempt to connect to the character
-                    // server before it logged in to the login server, so it's fine to
-                    // unwrap here.
-                    let login_data = self.saved_login_data.as_ref().unwrap();
-                    self.networking_system
-                        .connect_to_character_server(self.saved_packet_version, login_data, character_server_information);
+                    self.session
+                        .select_character_server(&mut self.networking_system, character_server_information);
                 }
                 InputEvent::Respawn => {
                     let _ = self.networking_system.respawn();
@@ -1902,10 +2902,23 @@ impl Client {
                     self.networking_system.disconnect_from_character_server();
                 }
                 InputEvent::Exit => event_loop.exit(),
-                InputEvent::ZoomCamera { zoom_factor } => self.player_camera.soft_zoom(zoom_factor),
+                InputEvent::ZoomCamera { zoom_factor } => {
+                    #[cfg(feature = "debug")]
+                    if model_viewer_active {
+                        self.model_viewer_camera.zoom(zoom_factor);
+                        continue;
+                    }
+
+                    self.player_camera.soft_zoom(zoom_factor);
+                }
                 InputEvent::RotateCamera { rotation } => self.player_camera.soft_rotate(rotation),
                 InputEvent::ResetCameraRotation => self.player_camera.reset_rotation(),
                 InputEvent::ToggleMenuWindow => {
+                    // Escape cancels the targeting of a ground skill before it opens the menu.
+                    if self.targeted_skill.take().is_some() {
+                        continue;
+                    }
+
                     if self.client_state.try_follow(this_entity()).is_some() {
                         match self.interface.is_window_with_class_open(WindowClass::Menu) {
                             true => self.interface.close_window_with_class(WindowClass::Menu),
@@ -1913,11 +2926,23 @@ impl Client {
                         }
                     }
                 }
+                InputEvent::ToggleCharacterOverviewWindow => {
+                    if self.client_state.try_follow(this_entity()).is_some() {
+                        match self.interface.is_window_with_class_open(WindowClass::CharacterOverview) {
+                            true => self.interface.close_window_with_class(WindowClass::CharacterOverview),
+                            false => self.interface.open_window(CharacterOverviewWindow::new(
+                                client_state().player_name(),
+                                this_player().manually_asserted().base_level(),
+                                this_player().manually_asserted().job_level(),
+                            )),
+                        }
+                    }
+                }
                 InputEvent::ToggleInventoryWindow => {
                     if self.client_state.try_follow(this_entity()).is_some() {
                         match self.interface.is_window_with_class_open(WindowClass::Inventory) {
                             true => self.interface.close_window_with_class(WindowClass::Inventory),
-                            false => self.interface.open_window(InventoryWindow::new(client_state().inventory().items())),
+                            false => self.interface.open_window(InventoryWindow::new(client_state().inventory())),
                         }
                     }
                 }
@@ -1925,7 +2950,7 @@ impl Client {
                     if self.client_state.try_follow(this_entity()).is_some() {
                         match self.interface.is_window_with_class_open(WindowClass::Equipment) {
                             true => self.interface.close_window_with_class(WindowClass::Equipment),
-                            false => self.interface.open_window(EquipmentWindow::new(client_state().inventory().items())),
+                            false => self.interface.open_window(EquipmentWindow::new(client_state().inventory())),
                         }
                     }
                 }
@@ -1949,7 +2974,10 @@ impl Client {
                 }
                 InputEvent::ToggleGameSettingsWindow => match self.interface.is_window_with_class_open(WindowClass::GameSettings) {
                     true => self.interface.close_window_with_class(WindowClass::GameSettings),
-                    false => self.interface.open_window(GameSettingsWindow::new(client_state().game_settings())),
+                    false => self.interface.open_window(GameSettingsWindow::new(
+                        client_state().game_settings(),
+                        client_state().game_settings_capabilities(),
+                    )),
                 },
                 InputEvent::ToggleInterfaceSettingsWindow => match self.interface.is_window_with_class_open(WindowClass::InterfaceSettings)
                 {
@@ -1966,11 +2994,25 @@ impl Client {
                         client_state().graphics_settings_capabilities(),
                     )),
                 },
+                InputEvent::DetectGraphicsPreset => {
+                    let preset = self.graphics_engine.get_recommended_graphics_preset();
+                    let supported_msaa = self.graphics_engine.get_supported_msaa();
+
+                    self.client_state
+                        .follow_mut(client_state().graphics_settings())
+                        .apply_preset(preset, &supported_msaa);
+
+                    self.client_state.follow_mut(client_state().chat_messages()).push(ChatMessage::new(
+                        format!("Applied the {preset} graphics preset"),
+                        MessageColor::Information,
+                    ));
+                }
                 InputEvent::ToggleAudioSettingsWindow => match self.interface.is_window_with_class_open(WindowClass::AudioSettings) {
                     true => self.interface.close_window_with_class(WindowClass::AudioSettings),
-                    false => self
-                        .interface
-                        .open_window(AudioSettingsWindow::new(client_state().audio_settings())),
+                    false => self.interface.open_window(AudioSettingsWindow::new(
+                        client_state().audio_settings(),
+                        client_state().audio_settings_capabilities(),
+                    )),
                 },
                 InputEvent::ToggleFriendListWindow => {
                     if self.client_state.try_follow(this_entity()).is_some() {
@@ -1983,20 +3025,148 @@ impl Client {
                         }
                     }
                 }
-                InputEvent::CloseTopWindow => self.interface.close_top_window(&self.client_state),
-                InputEvent::ToggleShowInterface => self.show_interface = !self.show_interface,
-                InputEvent::SelectCharacter { slot } => {
-                    let _ = self.networking_system.select_character(slot);
+                InputEvent::ToggleSessionStatisticsWindow => {
+                    if self.client_state.try_follow(this_entity()).is_some() {
+                        match self.interface.is_window_with_class_open(WindowClass::SessionStatistics) {
+                            true => self.interface.close_window_with_class(WindowClass::SessionStatistics),
+                            false => self
+                                .interface
+                                .open_window(SessionStatisticsWindow::new(client_state().session_statistics())),
+                        }
+                    }
                 }
-                InputEvent::OpenCharacterCreationWindow { slot } => {
-                    // Clear the name before opening the window.
-                    self.client_state.follow_mut(client_state().create_character_name()).clear();
-
-                    self.interface
-                        .open_window(CharacterCreationWindow::new(client_state().create_character_name(), slot))
+                InputEvent::ResetSessionStatistics => {
+                    self.client_state.follow_mut(client_state().session_statistics()).reset();
                 }
-                InputEvent::CreateCharacter { slot, name } => {
-                    let _ = self.networking_system.create_character(slot, name);
+                InputEvent::ExportSessionStatistics => {
+                    let timestamp = chrono::offset::Local::now().format("%Y-%m-%d_%H-%M-%S");
+                    let path = format!("{SESSION_STATISTICS_DIRECTORY}/{timestamp}.csv");
+                    let csv = self.client_state.follow(client_state().session_statistics()).to_csv();
+
+                    let message = match std::fs::create_dir_all(SESSION_STATISTICS_DIRECTORY).and_then(|_| std::fs::write(&path, csv)) {
+                        Ok(()) => ChatMessage::new(format!("Session statistics exported to {path}"), MessageColor::Information),
+                        Err(error) => ChatMessage::new(
+                            format!("Failed to export session statistics to {path}: {error}"),
+                            MessageColor::Error,
+                        ),
+                    };
+
+                    self.client_state.follow_mut(client_state().chat_messages()).push(message);
+                }
+                InputEvent::ToggleMonsterInfoWindow => match self.interface.is_window_with_class_open(WindowClass::MonsterInfo) {
+                    true => self.interface.close_window_with_class(WindowClass::MonsterInfo),
+                    false => self
+                        .interface
+                        .open_window(MonsterInfoWindow::new(client_state().monster_info_window())),
+                },
+                InputEvent::InspectMonster { entity_id } => {
+                    let monster_info = self
+                        .client_state
+                        .follow(client_state().entities())
+                        .iter()
+                        .find(|entity| entity.get_entity_id() == entity_id && entity.get_entity_type() == EntityType::Monster)
+                        .and_then(|entity| self.library.get_monster_info(entity.get_job()));
+
+                    if let Some(monster_info) = monster_info {
+                        self.client_state
+                            .follow_mut(client_state().monster_info_window())
+                            .show_monster(&self.library, monster_info);
+
+                        if !self.interface.is_window_with_class_open(WindowClass::MonsterInfo) {
+                            self.interface
+                                .open_window(MonsterInfoWindow::new(client_state().monster_info_window()));
+                        }
+                    }
+                }
+                InputEvent::SearchMonsters { query } => {
+                    let results = self.library.search_monsters(&query);
+
+                    self.client_state
+                        .follow_mut(client_state().monster_info_window())
+                        .show_search_results(&self.library, &results);
+                }
+                InputEvent::ToggleItemSearchWindow => match self.interface.is_window_with_class_open(WindowClass::ItemSearch) {
+                    true => self.interface.close_window_with_class(WindowClass::ItemSearch),
+                    false => self
+                        .interface
+                        .open_window(ItemSearchWindow::new(client_state().item_search_window())),
+                },
+                InputEvent::SearchItems => {
+                    let window_state = self.client_state.follow(client_state().item_search_window());
+                    let results = self.library.search_items(window_state.query(), &window_state.filter());
+
+                    self.client_state
+                        .follow_mut(client_state().item_search_window())
+                        .set_results(&self.library, &results);
+                }
+                InputEvent::ShowItemInfo { item_id } => {
+                    self.client_state
+                        .follow_mut(client_state().item_search_window())
+                        .show_item(&self.library, item_id);
+
+                    if !self.interface.is_window_with_class_open(WindowClass::ItemSearch) {
+                        self.interface
+                            .open_window(ItemSearchWindow::new(client_state().item_search_window()));
+                    }
+                }
+                InputEvent::LinkItemInChat { item_id } => {
+                    self.client_state
+                        .follow_mut(client_state().chat_window())
+                        .append_text(&item_link(item_id));
+                }
+                InputEvent::ToggleSkillPlannerWindow => match self.interface.is_window_with_class_open(WindowClass::SkillPlanner) {
+                    true => self.interface.close_window_with_class(WindowClass::SkillPlanner),
+                    false => {
+                        self.client_state
+                            .follow_mut(client_state().skill_planner())
+                            .set_skill_trees(self.library.get_skill_trees());
+                        self.interface.open_window(SkillPlannerWindow::new(client_state().skill_planner()));
+                    }
+                },
+                InputEvent::ToggleMinimapWindow => {
+                    if self.client_state.try_follow(this_entity()).is_some() {
+                        match self.interface.is_window_with_class_open(WindowClass::Minimap) {
+                            true => self.interface.close_window_with_class(WindowClass::Minimap),
+                            false => self
+                                .interface
+                                .open_window(MinimapWindow::new(client_state().minimap_window(), client_state().party())),
+                        }
+                    }
+                }
+                InputEvent::ImportSkillBuild => {
+                    if !self.client_state.follow_mut(client_state().skill_planner()).import_build() {
+                        self.client_state.follow_mut(client_state().chat_messages()).push(ChatMessage::new(
+                            "The build string is not a valid skill build".to_owned(),
+                            MessageColor::Error,
+                        ));
+                    }
+                }
+                InputEvent::CloseTopWindow => self.interface.close_top_window(&self.client_state),
+                InputEvent::ToggleShowInterface => self.show_interface = !self.show_interface,
+                InputEvent::SelectCharacter { slot } => {
+                    if let Some(service_settings) = self
+                        .client_state
+                        .follow_mut(client_state().login_settings())
+                        .recent_service_settings_mut()
+                    {
+                        service_settings.last_character_slot = Some(slot);
+                    }
+
+                    let _ = self.networking_system.select_character(slot);
+                }
+                InputEvent::OpenCharacterCreationWindow { slot } => {
+                    // Clear the name and options before opening the window.
+                    self.client_state.follow_mut(client_state().create_character_name()).clear();
+                    *self.client_state.follow_mut(client_state().create_character_options()) = CharacterCreationOptions::default();
+
+                    self.interface.open_window(CharacterCreationWindow::new(
+                        client_state().create_character_name(),
+                        client_state().create_character_options(),
+                        slot,
+                    ))
+                }
+                InputEvent::CreateCharacter { slot, name, options } => {
+                    let _ = self.networking_system.create_character(slot, name, options);
                 }
                 InputEvent::DeleteCharacter { character_id } => {
                     if self.client_state.follow(client_state().currently_deleting()).is_none() {
@@ -2023,6 +3193,11 @@ impl Client {
                     *self.client_state.follow_mut(client_state().buffered_attack_entity()) = None;
                 }
                 InputEvent::PlayerInteract { entity_id } => {
+                    if let Some(offline_scripts) = &mut self.offline_scripts {
+                        offline_scripts.interact(entity_id);
+                        continue;
+                    }
+
                     let entity = self
                         .client_state
                         .follow_mut(client_state().entities())
@@ -2066,34 +3241,175 @@ impl Client {
                         continue;
                     }
 
-                    let _ = self
+                    if let Some(name) = text.strip_prefix("/mute ") {
+                        let is_muted = self
+                            .client_state
+                            .follow_mut(client_state().game_settings())
+                            .toggle_player_mute(name);
+
+                        let message = match is_muted {
+                            true => format!("Muted {name}"),
+                            false => format!("Unmuted {name}"),
+                        };
+
+                        self.client_state
+                            .follow_mut(client_state().chat_messages())
+                            .push(ChatMessage::new(message, MessageColor::Information));
+                        continue;
+                    }
+
+                    if let Some(pattern) = text.strip_prefix("/filter ") {
+                        let message = match self.chat_moderation.add_filter(pattern) {
+                            Ok(()) => {
+                                let chat_filters = &mut self.client_state.follow_mut(client_state().game_settings()).chat_filters;
+
+                                if !chat_filters.iter().any(|filter| filter == pattern) {
+                                    chat_filters.push(pattern.to_owned());
+                                }
+
+                                ChatMessage::new(format!("Hiding messages matching {pattern}"), MessageColor::Information)
+                            }
+                            Err(error) => ChatMessage::new(format!("Invalid filter: {error}"), MessageColor::Error),
+                        };
+
+                        self.client_state.follow_mut(client_state().chat_messages()).push(message);
+                        continue;
+                    }
+
+                    if let Some(pattern) = text.strip_prefix("/unfilter ") {
+                        self.chat_moderation.remove_filter(pattern);
+                        self.client_state
+                            .follow_mut(client_state().game_settings())
+                            .chat_filters
+                            .retain(|filter| filter != pattern);
+
+                        self.client_state.follow_mut(client_state().chat_messages()).push(ChatMessage::new(
+                            format!("Showing messages matching {pattern} again"),
+                            MessageColor::Information,
+                        ));
+                        continue;
+                    }
+
+                    // Let the player know that the message was dropped, so it can be sent again.
+                    if let Err(error @ GameplayError::RateLimited { .. }) = self
                         .networking_system
-                        .send_chat_message(self.client_state.follow(client_state().player_name()), &text);
+                        .send_chat_message(self.client_state.follow(client_state().player_name()), &text)
+                    {
+                        self.client_state
+                            .follow_mut(client_state().chat_messages())
+                            .push(ChatMessage::new(error.to_string(), MessageColor::Error));
+                    }
                 }
                 InputEvent::NextDialog { npc_id } => {
-                    let _ = self.networking_system.next_dialog(npc_id);
+                    // The first click only reveals the rest of the text.
+                    if !self.client_state.follow_mut(client_state().dialog_window()).skip_text_reveal() {
+                        match &mut self.offline_scripts {
+                            Some(offline_scripts) => offline_scripts.next_dialog(npc_id),
+                            None => {
+                                let _ = self.networking_system.next_dialog(npc_id);
+                            }
+                        }
+                    }
                 }
                 InputEvent::CloseDialog { npc_id } => {
-                    let _ = self.networking_system.close_dialog(npc_id);
+                    match &mut self.offline_scripts {
+                        Some(offline_scripts) => offline_scripts.close_dialog(npc_id),
+                        None => {
+                            let _ = self.networking_system.close_dialog(npc_id);
+                        }
+                    }
+
                     self.client_state.follow_mut(client_state().dialog_window()).end();
                     self.interface.close_window_with_class(WindowClass::Dialog);
                 }
+                InputEvent::ToggleDialogHistoryWindow => match self.interface.is_window_with_class_open(WindowClass::DialogHistory) {
+                    true => self.interface.close_window_with_class(WindowClass::DialogHistory),
+                    false => self
+                        .interface
+                        .open_window(DialogHistoryWindow::new(client_state().dialog_window().history())),
+                },
                 InputEvent::ChooseDialogOption { npc_id, option } => {
-                    let _ = self.networking_system.choose_dialog_option(npc_id, option);
+                    match &mut self.offline_scripts {
+                        Some(offline_scripts) => offline_scripts.choose_dialog_option(npc_id, option),
+                        None => {
+                            let _ = self.networking_system.choose_dialog_option(npc_id, option);
+                        }
+                    }
 
                     if option == -1 {
                         self.interface.close_window_with_class(WindowClass::Dialog);
                     }
                 }
-                InputEvent::MoveItem { source, destination, item } => match (source, destination) {
-                    (ItemSource::Inventory, ItemSource::Equipment { position }) => {
-                        let _ = self.networking_system.request_item_equip(item.index, position);
-                    }
-                    (ItemSource::Equipment { .. }, ItemSource::Inventory) => {
-                        let _ = self.networking_system.request_item_unequip(item.index);
+                InputEvent::MoveItem { source, destination, item } => {
+                    let is_locked = self
+                        .client_state
+                        .follow(client_state().game_settings())
+                        .is_item_locked(item.item_id);
+
+                    match ItemTransfer::new(source, destination, &item, is_locked) {
+                        Ok(ItemTransfer::Equip { index, position }) => {
+                            let _ = self.networking_system.request_item_equip(index, position);
+                        }
+                        Ok(ItemTransfer::EquipAmmunition { index }) => {
+                            let _ = self.networking_system.equip_ammunition(index);
+                        }
+                        Ok(ItemTransfer::Unequip { index }) => {
+                            let _ = self.networking_system.request_item_unequip(index);
+                        }
+                        Ok(ItemTransfer::AssignHotbar { slot, item_id }) => {
+                            self.client_state.follow_mut(client_state().hotbar()).update_item_slot(
+                                &mut self.networking_system,
+                                slot,
+                                item_id,
+                            );
+                            self.sync_hotbar_items();
+                        }
+                        Ok(ItemTransfer::DepositStorage { index, amount }) => {
+                            let _ = self.networking_system.move_item_to_storage(index, amount);
+                        }
+                        Ok(ItemTransfer::OfferTrade { index, amount }) => {
+                            let _ = self.networking_system.add_trade_item(index, amount);
+                        }
+                        Ok(ItemTransfer::DropToGround { index, amount }) if amount > 1 => {
+                            self.interface.open_window(DropAmountWindow::new(index, item.metadata.name.clone(), amount));
+                        }
+                        // Locked items can't be dropped at all, so only refined or carded
+                        // equipment needs to be confirmed.
+                        Ok(ItemTransfer::DropToGround { index, amount }) => match SellWarning::for_item(&item, false) {
+                            Some(warning) => {
+                                let warning = format!("{} ({warning})", item.metadata.name);
+                                self.interface.open_window(DropConfirmationWindow::new(index, warning));
+                            }
+                            None => {
+                                let _ = self.networking_system.drop_item(index, amount);
+                            }
+                        },
+                        // Dropping an item where it can't go, like back on its source, is not
+                        // worth a message.
+                        Err(ItemTransferError::Unsupported) => {}
+                        Err(error) => {
+                            self.client_state
+                                .follow_mut(client_state().chat_messages())
+                                .push(ChatMessage::new(error.to_string(), MessageColor::Error));
+                        }
                     }
-                    _ => {}
-                },
+                }
+                InputEvent::DropItem { index, amount } => {
+                    self.interface.close_window_with_class(WindowClass::DropAmount);
+                    self.interface.close_window_with_class(WindowClass::DropConfirmation);
+
+                    let _ = self.networking_system.drop_item(index, amount);
+                }
+                InputEvent::ToggleItemLock { item_id } => {
+                    self.client_state
+                        .follow_mut(client_state().game_settings())
+                        .toggle_item_lock(item_id);
+                }
+                InputEvent::SaveGearSet => self.save_gear_set(),
+                InputEvent::EquipGearSet { index } => self.equip_gear_set(index),
+                InputEvent::DeleteGearSet { index } => self.delete_gear_set(index),
+                InputEvent::OpenEmoteWheel => self.open_emote_wheel(input_report.mouse_position),
+                InputEvent::CloseEmoteWheel => self.close_emote_wheel(),
                 InputEvent::MoveSkill {
                     source,
                     destination,
@@ -2113,6 +3429,10 @@ impl Client {
                     }
                     _ => {}
                 },
+                InputEvent::CastSkill { slot } if self.client_state.follow(client_state().hotbar()).get_item_in_slot(slot).is_some() => {
+                    self.use_hotbar_item(slot);
+                }
+                InputEvent::UseHotbarItem { slot } => self.use_hotbar_item(slot),
                 InputEvent::CastSkill { slot } => {
                     if let Some(skill) = self.client_state.follow(client_state().hotbar()).get_skill_in_slot(slot).as_ref() {
                         match skill.skill_type {
@@ -2123,11 +3443,8 @@ impl Client {
                                 }
                             }
                             SkillType::Ground | SkillType::Trap => {
-                                if let PickerTarget::Tile { x, y } = input_report.mouse_target {
-                                    let _ = self
-                                        .networking_system
-                                        .cast_ground_skill(skill.skill_id, skill.skill_level, TilePosition { x, y });
-                                }
+                                // The skill is cast once the player picks a target tile.
+                                self.targeted_skill = Some(skill.clone());
                             }
                             SkillType::SelfCast => match skill.skill_id == ROLLING_CUTTER_ID {
                                 true => {
@@ -2159,6 +3476,22 @@ impl Client {
                         }
                     }
                 }
+                InputEvent::ConfirmSkillTarget { destination } => {
+                    // Only send the skill if the server would accept it, otherwise the player can
+                    // keep picking a target.
+                    if let Some(map) = &self.map
+                        && let Some(skill) = &self.targeted_skill
+                        && let Some(player) = self.client_state.try_follow(this_entity())
+                        && self
+                            .path_finder
+                            .is_in_skill_range(&**map, player.get_tile_position(), destination, skill.attack_range)
+                    {
+                        let _ = self
+                            .networking_system
+                            .cast_ground_skill(skill.skill_id, skill.skill_level, destination);
+                        self.targeted_skill = None;
+                    }
+                }
                 InputEvent::StopSkill { slot } => {
                     if let Some(skill) = self.client_state.follow(client_state().hotbar()).get_skill_in_slot(slot).as_ref()
                         && skill.skill_id == ROLLING_CUTTER_ID
@@ -2186,7 +3519,24 @@ impl Client {
                     self.interface.close_window_with_class(WindowClass::FriendRequest);
                 }
                 InputEvent::BuyItems { items } => {
-                    let _ = self.networking_system.purchase_items(items);
+                    let purchase = self
+                        .client_state
+                        .try_follow(this_player())
+                        .map(|player| CartSummary::new(items.iter().map(|item| (item, item.metadata))).check_for_player(player));
+
+                    if let Some(Err(error)) = purchase {
+                        self.interface_sound_effects.play(InterfaceSound::Error);
+                        self.client_state
+                            .follow_mut(client_state().chat_messages())
+                            .push(ChatMessage::new(error.to_string(), MessageColor::Error));
+                        continue;
+                    }
+
+                    if let Err(error) = self.networking_system.purchase_items(items) {
+                        self.client_state
+                            .follow_mut(client_state().chat_messages())
+                            .push(ChatMessage::new(error.to_string(), MessageColor::Error));
+                    }
                 }
                 InputEvent::CloseShop => {
                     let _ = self.networking_system.close_shop();
@@ -2199,17 +3549,52 @@ impl Client {
                     self.interface.close_window_with_class(WindowClass::BuyCart);
                     self.interface.close_window_with_class(WindowClass::Sell);
                     self.interface.close_window_with_class(WindowClass::SellCart);
+                    self.interface.close_window_with_class(WindowClass::SellConfirmation);
                 }
                 InputEvent::BuyOrSell { shop_id, buy_or_sell } => {
                     let _ = self.networking_system.select_buy_or_sell(shop_id, buy_or_sell);
                     self.interface.close_window_with_class(WindowClass::BuyOrSell);
                 }
-                InputEvent::SellItems { items } => {
-                    let _ = self.networking_system.sell_items(items);
+                InputEvent::SellItems { items, confirmed } => {
+                    if !confirmed {
+                        let game_settings = self.client_state.follow(client_state().game_settings());
+                        let inventory_items = self.client_state.follow(client_state().inventory().items());
+
+                        let warnings: Vec<String> = items
+                            .iter()
+                            .filter_map(|sold_item| {
+                                let item = inventory_items.iter().find(|item| item.index == sold_item.inventory_index)?;
+                                let warning = SellWarning::for_item(item, game_settings.is_item_locked(item.item_id))?;
+
+                                Some(format!("{} ({warning})", item.metadata.name))
+                            })
+                            .collect();
+
+                        if !warnings.is_empty() {
+                            self.interface.open_window(SellConfirmationWindow::new(items, warnings));
+                            continue;
+                        }
+                    }
+
+                    self.interface.close_window_with_class(WindowClass::SellConfirmation);
+
+                    if let Err(error) = self.networking_system.sell_items(items) {
+                        self.client_state
+                            .follow_mut(client_state().chat_messages())
+                            .push(ChatMessage::new(error.to_string(), MessageColor::Error));
+                    }
+                }
+                InputEvent::ClaimAttendanceReward => {
+                    let _ = self.networking_system.claim_attendance_reward();
                 }
                 InputEvent::StatUp { stat_type } => {
                     let _ = self.networking_system.request_stat_up(stat_type);
                 }
+                InputEvent::OpenCrashReportDirectory => crash_report::open_crash_report_directory(),
+                InputEvent::ToggleFullscreen => {
+                    let display_mode = self.client_state.follow_mut(client_state().graphics_settings().display_mode());
+                    *display_mode = display_mode.toggled();
+                }
                 #[cfg(feature = "debug")]
                 InputEvent::ReloadLanguage => {
                     let language = *self.client_state.follow(client_state().interface_settings().language());
@@ -2304,7 +3689,17 @@ impl Client {
                     if self.map.is_some() {
                         match self.interface.is_window_with_class_open(WindowClass::Maps) {
                             true => self.interface.close_window_with_class(WindowClass::Maps),
-                            false => self.interface.open_window(MapsWindow),
+                            false => {
+                                for (map_name, _) in MAP_WARPS {
+                                    if let Some(texture) = self.async_loader.request_map_thumbnail_load(map_name) {
+                                        self.client_state
+                                            .follow_mut(client_state().map_thumbnails())
+                                            .insert(map_name.to_string(), texture);
+                                    }
+                                }
+
+                                self.interface.open_window(MapsWindow::new(client_state().map_thumbnails()));
+                            }
                         }
                     }
                 }
@@ -2342,9 +3737,401 @@ impl Client {
                 #[cfg(feature = "debug")]
                 InputEvent::ToggleCacheStatisticsWindow => match self.interface.is_window_with_class_open(WindowClass::CacheStatistics) {
                     true => self.interface.close_window_with_class(WindowClass::CacheStatistics),
-                    false => self.interface.open_state_window(client_state().cache_statistics()),
+                    false => self
+                        .interface
+                        .open_window(CacheStatisticsWindow::new(client_state().cache_statistics())),
+                },
+                #[cfg(feature = "debug")]
+                InputEvent::ToggleMemoryUsageWindow => match self.interface.is_window_with_class_open(WindowClass::MemoryUsage) {
+                    true => self.interface.close_window_with_class(WindowClass::MemoryUsage),
+                    false => self.interface.open_window(MemoryUsageWindow::new(client_state().memory_usage())),
+                },
+                #[cfg(feature = "debug")]
+                InputEvent::ToggleSpriteDepthBiasWindow => match self.interface.is_window_with_class_open(WindowClass::SpriteDepthBias) {
+                    true => self.interface.close_window_with_class(WindowClass::SpriteDepthBias),
+                    false => self.interface.open_window(SpriteDepthBiasWindow::new(
+                        client_state().graphics_settings().sprite_depth_bias(),
+                    )),
+                },
+                #[cfg(feature = "debug")]
+                InputEvent::ToggleAssetBrowserWindow => match self.interface.is_window_with_class_open(WindowClass::AssetBrowser) {
+                    true => self.interface.close_window_with_class(WindowClass::AssetBrowser),
+                    false => {
+                        let mut asset_browser = self.client_state.follow_mut(client_state().asset_browser());
+
+                        // Listing the files is slow, so only do it the first time the window is opened.
+                        if !asset_browser.has_files() {
+                            let files = self
+                                .game_file_loader
+                                .get_files_with_extension(&[".act"])
+                                .into_iter()
+                                .filter_map(|file| {
+                                    file.strip_prefix("data\\sprite\\")
+                                        .and_then(|file| file.strip_suffix(".act"))
+                                        .map(str::to_owned)
+                                })
+                                .collect();
+
+                            asset_browser.set_files(files);
+                        }
+
+                        self.interface.open_window(AssetBrowserWindow::new(client_state().asset_browser()));
+                    }
+                },
+                #[cfg(feature = "debug")]
+                InputEvent::PreviewAsset { name } => {
+                    let sprite = self.sprite_loader.get_or_load(&format!("{name}.spr"));
+                    let actions = self.action_loader.get_or_load(&format!("{name}.act"));
+                    let mut asset_browser = self.client_state.follow_mut(client_state().asset_browser());
+
+                    match sprite.and_then(|sprite| actions.map(|actions| (sprite, actions))) {
+                        Ok((sprite, actions)) => asset_browser.set_preview(&name, sprite, actions, client_tick),
+                        Err(error) => asset_browser.set_preview_error(&name, format!("{error:?}")),
+                    }
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::StepAssetPreviewAction { steps } => {
+                    self.client_state
+                        .follow_mut(client_state().asset_browser())
+                        .step_action(steps, client_tick);
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::StepAssetPreviewFrame { steps } => {
+                    self.client_state.follow_mut(client_state().asset_browser()).step_frame(steps);
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::ToggleAssetPreviewPlayback => {
+                    self.client_state
+                        .follow_mut(client_state().asset_browser())
+                        .toggle_playback(client_tick);
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::ToggleEffectPreviewWindow => match self.interface.is_window_with_class_open(WindowClass::EffectPreview) {
+                    true => self.interface.close_window_with_class(WindowClass::EffectPreview),
+                    false => self
+                        .interface
+                        .open_window(EffectPreviewWindow::new(client_state().effect_preview())),
+                },
+                #[cfg(feature = "debug")]
+                InputEvent::PreviewEffect => {
+                    let effect_preview = self.client_state.follow_mut(client_state().effect_preview());
+
+                    match self.effect_loader.get_or_load(effect_preview.path(), &self.texture_loader) {
+                        Ok(effect) => effect_preview.set_effect(effect),
+                        Err(error) => effect_preview.set_error(format!("Failed to load effect: {error:?}")),
+                    }
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::RestartEffectPreview => self.client_state.follow_mut(client_state().effect_preview()).restart(),
+                #[cfg(feature = "debug")]
+                InputEvent::StepEffectPreviewFrame { steps } => {
+                    self.client_state.follow_mut(client_state().effect_preview()).step_frame(steps);
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::ToggleModelViewerWindow => match self.interface.is_window_with_class_open(WindowClass::ModelViewer) {
+                    true => self.interface.close_window_with_class(WindowClass::ModelViewer),
+                    false => self.interface.open_window(ModelViewerWindow::new(client_state().model_viewer())),
+                },
+                #[cfg(feature = "debug")]
+                InputEvent::PreviewModel => {
+                    let path = self.client_state.follow(client_state().model_viewer()).path().to_owned();
+
+                    match self.async_loader.load_model_preview(&path) {
+                        Ok(mut preview) => {
+                            // Place the model where the camera is currently looking, so the
+                            // lighting of the map matches the surroundings.
+                            let focus_point = match self.client_state.try_follow(this_player()).is_some() {
+                                true => self.player_camera.focus_point(),
+                                false => self.start_camera.focus_point(),
+                            };
+
+                            preview.set_position(focus_point);
+                            self.model_viewer_camera.focus(preview.center(), preview.radius() * 3.0);
+                            self.client_state.follow_mut(client_state().model_viewer()).set_preview(preview);
+                        }
+                        Err(error) => {
+                            self.client_state
+                                .follow_mut(client_state().model_viewer())
+                                .set_error(format!("Failed to load model: {error:?}"));
+                        }
+                    }
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::OrbitModelViewer { offset } => self.model_viewer_camera.orbit(offset),
+                #[cfg(feature = "debug")]
+                InputEvent::ClearCache { cache } => match cache {
+                    ClearableCache::Texture => self.texture_loader.clear_cache(),
+                    ClearableCache::Sprite => self.sprite_loader.clear_cache(),
+                    ClearableCache::Action => self.action_loader.clear_cache(),
+                    ClearableCache::Animation => self.animation_loader.clear_cache(),
+                    ClearableCache::Effect => self.effect_loader.clear_cache(),
                 },
                 #[cfg(feature = "debug")]
+                InputEvent::ToggleMapEditorWindow => {
+                    if self.map.is_some() {
+                        match self.interface.is_window_with_class_open(WindowClass::MapEditor) {
+                            true => self.interface.close_window_with_class(WindowClass::MapEditor),
+                            false => self.interface.open_window(MapEditorWindow::new(client_state().map_editor())),
+                        }
+                    }
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::SelectMarker { marker_identifier } => {
+                    if let Some(map) = &self.map {
+                        self.client_state
+                            .follow_mut(client_state().map_editor())
+                            .select(map, Some(marker_identifier));
+                    }
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::MoveMarker {
+                    marker_identifier,
+                    destination,
+                } => {
+                    let Some(map) = &mut self.map else {
+                        continue;
+                    };
+
+                    // Markers keep their height and only follow the mouse horizontally.
+                    if let Some(world_position) = map.get_world_position(destination)
+                        && let Some(marker_position) = map.marker_position(marker_identifier)
+                    {
+                        let position = Point3::new(world_position.x, marker_position.y, world_position.z);
+
+                        if map.move_marker(marker_identifier, position) {
+                            if let MarkerIdentifier::SoundSource(..) = marker_identifier {
+                                map.set_ambient_sound_sources(&self.audio_engine);
+                            }
+
+                            self.client_state.follow_mut(client_state().map_editor()).update_selection_text(map);
+                        }
+                    }
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::AdjustMarkerProperty { property, steps } => {
+                    let Some(map) = &mut self.map else {
+                        continue;
+                    };
+
+                    let map_editor = self.client_state.follow_mut(client_state().map_editor());
+
+                    let Some(marker_identifier) = map_editor.selected() else {
+                        continue;
+                    };
+
+                    let object_key = match marker_identifier {
+                        MarkerIdentifier::Object(key) => Some(key),
+                        _ => None,
+                    };
+                    let previous_transform = object_key.and_then(|key| map.object_transform(key));
+
+                    if map.adjust_marker_property(marker_identifier, property, steps) {
+                        if let MarkerIdentifier::SoundSource(..) = marker_identifier {
+                            map.set_ambient_sound_sources(&self.audio_engine);
+                        }
+
+                        if let Some(object_key) = object_key
+                            && let Some(previous) = previous_transform
+                            && let Some(new) = map.object_transform(object_key)
+                        {
+                            map_editor.record_change(TransformChange { object_key, previous, new }, false);
+                        }
+
+                        map_editor.update_selection_text(map);
+                    }
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::SetGizmoMode { mode } => {
+                    self.client_state.follow_mut(client_state().map_editor()).set_gizmo_mode(mode);
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::SetGizmoAxis { axis } => {
+                    self.client_state.follow_mut(client_state().map_editor()).set_gizmo_axis(axis);
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::StepGizmo { .. } | InputEvent::DragGizmo { .. } => {
+                    let Some(map) = &mut self.map else {
+                        continue;
+                    };
+
+                    let map_editor = self.client_state.follow_mut(client_state().map_editor());
+
+                    let (object_key, amount, dragging) = match event {
+                        InputEvent::DragGizmo { object_key, amount } => (Some(object_key), amount, true),
+                        InputEvent::StepGizmo { steps } => {
+                            let object_key = match map_editor.selected() {
+                                Some(MarkerIdentifier::Object(key)) => Some(key),
+                                _ => None,
+                            };
+
+                            (object_key, map_editor.gizmo_mode().step() * steps as f32, false)
+                        }
+                        _ => unreachable!(),
+                    };
+
+                    if let Some(object_key) = object_key
+                        && let Some((previous, new)) =
+                            map.transform_object(object_key, map_editor.gizmo_mode(), map_editor.gizmo_axis(), amount)
+                    {
+                        map_editor.record_change(TransformChange { object_key, previous, new }, dragging);
+                        map_editor.update_selection_text(map);
+                    }
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::UndoTransform | InputEvent::RedoTransform => {
+                    let Some(map) = &mut self.map else {
+                        continue;
+                    };
+
+                    let map_editor = self.client_state.follow_mut(client_state().map_editor());
+
+                    let change = match event {
+                        InputEvent::UndoTransform => map_editor.undo(),
+                        _ => map_editor.redo(),
+                    };
+
+                    if let Some((object_key, transform)) = change {
+                        map.set_object_transform(object_key, transform);
+                        map_editor.update_selection_text(map);
+                    }
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::PaintTile { position, walkable } => {
+                    let Some(map) = &mut self.map else {
+                        continue;
+                    };
+
+                    if map.set_tile_walkable(position, walkable) {
+                        let include_blocked_tiles = map.includes_blocked_tiles();
+                        map.update_tile_meshes(
+                            &self.device,
+                            &self.queue,
+                            self.graphics_engine.bindless_support(),
+                            include_blocked_tiles,
+                        );
+                    }
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::AddLightSource
+                | InputEvent::AddSoundSource
+                | InputEvent::AddEffectSource
+                | InputEvent::DuplicateSelectedMarker => {
+                    let Some(map) = &mut self.map else {
+                        continue;
+                    };
+
+                    let Some(player) = self.client_state.try_follow(this_entity()) else {
+                        continue;
+                    };
+
+                    let position = player.get_position();
+                    let map_editor = self.client_state.follow_mut(client_state().map_editor());
+
+                    let marker_identifier = match event {
+                        InputEvent::AddLightSource => map.add_light_source(position),
+                        InputEvent::AddSoundSource => Some(map.add_sound_source(position)),
+                        InputEvent::AddEffectSource => Some(map.add_effect_source(position)),
+                        _ => map_editor
+                            .selected()
+                            .and_then(|marker_identifier| map.duplicate_marker(marker_identifier, position)),
+                    };
+
+                    if let Some(MarkerIdentifier::SoundSource(..)) = marker_identifier {
+                        map.set_ambient_sound_sources(&self.audio_engine);
+                    }
+
+                    if marker_identifier.is_some() {
+                        map_editor.select(map, marker_identifier);
+                    }
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::RemoveSelectedMarker => {
+                    let Some(map) = &mut self.map else {
+                        continue;
+                    };
+
+                    let map_editor = self.client_state.follow_mut(client_state().map_editor());
+
+                    if let Some(marker_identifier) = map_editor.selected()
+                        && map.remove_marker(marker_identifier)
+                    {
+                        if let MarkerIdentifier::SoundSource(..) = marker_identifier {
+                            map.set_ambient_sound_sources(&self.audio_engine);
+                        }
+
+                        map_editor.select(map, None);
+                    }
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::ExportMap => {
+                    let Some(map) = &self.map else {
+                        continue;
+                    };
+
+                    let map_path = format!("{MAP_EXPORT_DIRECTORY}/{}.rsw", map.resource_file());
+                    let gat_path = format!("{MAP_EXPORT_DIRECTORY}/{}.gat", map.resource_file());
+
+                    let results = [
+                        (map_path.as_str(), export_map_file(&map_path, &map.export_map_data())),
+                        (gat_path.as_str(), export_map_file(&gat_path, &map.export_gat_data())),
+                    ];
+
+                    for (path, result) in results {
+                        let message = match result {
+                            Ok(()) => ChatMessage::new(format!("Map exported to {path}"), MessageColor::Information),
+                            Err(error) => ChatMessage::new(format!("Failed to export map to {path}: {error}"), MessageColor::Error),
+                        };
+
+                        self.client_state.follow_mut(client_state().chat_messages()).push(message);
+                    }
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::ToggleConsoleWindow => match self.interface.is_window_with_class_open(WindowClass::Console) {
+                    true => self.interface.close_window_with_class(WindowClass::Console),
+                    false => {
+                        self.interface.open_window(ConsoleWindow::new(client_state().console_window()));
+                        self.focus_console = true;
+                    }
+                },
+                #[cfg(feature = "debug")]
+                InputEvent::ExecuteConsoleCommand => {
+                    if let Some(command) = self.client_state.follow_mut(client_state().console_window()).take_command() {
+                        let output = self.console_commands.execute(&mut self.client_state, &command);
+                        self.client_state.follow_mut(client_state().console_window()).push_output(output);
+                    }
+
+                    if self
+                        .client_state
+                        .follow_mut(client_state().map_editor())
+                        .take_navigation_grid_request()
+                    {
+                        let output = match &self.map {
+                            Some(map) => {
+                                let path = format!("{MAP_EXPORT_DIRECTORY}/{}.navgrid", map.resource_file());
+
+                                match std::fs::create_dir_all(MAP_EXPORT_DIRECTORY)
+                                    .and_then(|_| std::fs::write(&path, map.export_navigation_grid()))
+                                {
+                                    Ok(()) => ConsoleOutput::Text(format!("navigation grid exported to {path}")),
+                                    Err(error) => ConsoleOutput::Error(format!("failed to export navigation grid to {path}: {error}")),
+                                }
+                            }
+                            None => ConsoleOutput::Error("no map is loaded".to_owned()),
+                        };
+
+                        self.client_state.follow_mut(client_state().console_window()).push_output(output);
+                    }
+                }
+                #[cfg(feature = "debug")]
+                InputEvent::AutocompleteConsoleCommand => self
+                    .client_state
+                    .follow_mut(client_state().console_window())
+                    .autocomplete(&self.console_commands),
+                #[cfg(feature = "debug")]
+                InputEvent::ConsoleHistoryPrevious => self.client_state.follow_mut(client_state().console_window()).history_previous(),
+                #[cfg(feature = "debug")]
+                InputEvent::ConsoleHistoryNext => self.client_state.follow_mut(client_state().console_window()).history_next(),
+                #[cfg(feature = "debug")]
                 InputEvent::CameraLookAround { offset } => self.debug_camera.look_around(offset),
                 #[cfg(feature = "debug")]
                 InputEvent::CameraMoveForward => self.debug_camera.move_forward(delta_time as f32),
@@ -2365,6 +4152,9 @@ impl Client {
             }
         }
 
+        self.interface_sound_effects
+            .play_window_change(previous_window_count, self.interface.window_count());
+
         #[cfg(feature = "debug")]
         input_event_measurement.stop();
 
@@ -2383,22 +4173,81 @@ impl Client {
                         entity.set_animation_data(animation_data);
                     }
                 }
-                (LoaderId::ItemSprite(item_id), LoadableResource::ItemSprite { texture, location }) => match location {
-                    ItemLocation::Inventory => {
-                        self.client_state
-                            .follow_mut(client_state().inventory())
-                            .update_item_sprite(item_id, texture);
-                    }
-                    ItemLocation::Shop => {
+                (LoaderId::ItemIcon(icon_path), LoadableResource::ItemIcon(texture)) => {
+                    self.client_state
+                        .follow_mut(client_state().inventory())
+                        .update_item_icon(&icon_path, texture.clone());
+                    self.sync_hotbar_items();
+
+                    // Items in the shop windows are copied from the inventory or the shop,
+                    // so they might have been created before the icon was loaded.
+                    let update_metadata = |metadata: &mut ResourceMetadata| {
+                        if metadata.icon_path == icon_path {
+                            metadata.texture = Some(texture.clone());
+                        }
+                    };
+
+                    self.client_state
+                        .follow_mut(client_state().shop_items())
+                        .iter_mut()
+                        .for_each(|item| update_metadata(&mut item.metadata));
+                    self.client_state
+                        .follow_mut(client_state().buy_cart())
+                        .iter_mut()
+                        .for_each(|item| update_metadata(&mut item.metadata.0));
+                    self.client_state
+                        .follow_mut(client_state().sell_items())
+                        .iter_mut()
+                        .for_each(|item| update_metadata(&mut item.metadata.0));
+                    self.client_state
+                        .follow_mut(client_state().sell_cart())
+                        .iter_mut()
+                        .for_each(|item| update_metadata(&mut item.metadata.0));
+                }
+                (LoaderId::MapThumbnail(map_name), LoadableResource::MapThumbnail(texture)) => {
+                    self.client_state
+                        .follow_mut(client_state().map_thumbnails())
+                        .insert(map_name, texture);
+                }
+                (LoaderId::Map(map_name), LoadableResource::Map { map, position }) => {
+                    #[cfg(feature = "debug")]
+                    self.client_state.follow_mut(client_state().map_editor()).clear_selection();
+
+                    // The dummy entities of a running stress test belong to the previous map.
+                    #[cfg(feature = "debug")]
+                    if self.stress_test.take().is_some() {
                         self.client_state
-                            .follow_mut(client_state().shop_items())
-                            .iter_mut()
-                            .filter(|item| item.item_id == item_id)
-                            .for_each(|item| item.metadata.texture = Some(texture.clone()));
+                            .follow_mut(client_state().entities())
+                            .retain(|entity| !StressTest::is_dummy_entity(entity.get_entity_id()));
                     }
-                },
-                (LoaderId::Map(..), LoadableResource::Map { map, position }) => {
+
                     match self.client_state.try_follow(this_player()).is_none() {
+                        true if self.benchmark.as_mut().is_some_and(|benchmark| benchmark.map_loaded(&map_name)) => {
+                            // Load of a benchmarked map
+                            let map = self.map.insert(map);
+
+                            map.set_ambient_sound_sources(&self.audio_engine);
+
+                            self.start_camera.set_focus_point(map.get_level_bound().center());
+                            self.directional_shadow_camera.set_level_bound(map.get_level_bound());
+                        }
+                        true if self.offline_scripts.is_some() => {
+                            // Map change in offline mode
+                            let map = self.map.insert(map);
+
+                            map.set_ambient_sound_sources(&self.audio_engine);
+                            self.audio_engine.play_background_music_track(map.background_music_track_name());
+
+                            let focus_point = position
+                                .and_then(|position| map.get_world_position(position))
+                                .unwrap_or_else(|| map.get_level_bound().center());
+                            self.start_camera.set_focus_point(focus_point);
+                            self.directional_shadow_camera.set_level_bound(map.get_level_bound());
+
+                            if let Some(offline_scripts) = &mut self.offline_scripts {
+                                offline_scripts.load_map(&self.game_file_loader, &map_name);
+                            }
+                        }
                         true => {
                             // Load of main menu map
                             let map = self.map.insert(map);
@@ -2434,6 +4283,22 @@ impl Client {
 
                             self.directional_shadow_camera.set_level_bound(map.get_level_bound());
                             let _ = self.networking_system.map_loaded();
+
+                            self.client_state
+                                .follow_mut(client_state().minimap_window())
+                                .set_map(map_name.clone(), map.size());
+
+                            if let Some(texture) = self.async_loader.request_map_thumbnail_load(&map_name) {
+                                self.client_state
+                                    .follow_mut(client_state().map_thumbnails())
+                                    .insert(map_name, texture);
+                            }
+
+                            // The map that was just left stays loaded as long as it is adjacent.
+                            match *self.client_state.follow(client_state().graphics_settings().preload_adjacent_maps()) {
+                                true => self.async_loader.preload_maps(self.library.get_adjacent_maps(map.resource_file())),
+                                false => self.async_loader.clear_preloaded_maps(),
+                            }
                         }
                     }
                 }
@@ -2450,11 +4315,34 @@ impl Client {
             profile_block!("update packet history");
 
             let is_packet_inspector_open = self.interface.is_window_with_class_open(WindowClass::PacketInspector);
+            let packet_history = self.client_state.follow_mut(client_state().packet_history());
+            packet_history.update(is_packet_inspector_open);
+            packet_history.update_event_statistics(self.network_event_buffer.statistics());
+        }
+
+        self.client_state.follow_mut(client_state().session_statistics()).update(delta_time);
+
+        if let Some(position) = self.client_state.try_follow(this_entity()).map(|player| player.get_tile_position()) {
             self.client_state
-                .follow_mut(client_state().packet_history())
-                .update(is_packet_inspector_open);
+                .follow_mut(client_state().minimap_window())
+                .set_player_position(position);
         }
 
+        self.client_state.follow_mut(client_state().memory_usage()).update(
+            delta_time,
+            MemoryUsageSources {
+                texture_loader: &self.texture_loader,
+                sprite_loader: &self.sprite_loader,
+                action_loader: &self.action_loader,
+                animation_loader: &self.animation_loader,
+                effect_loader: &self.effect_loader,
+                font_loader: &self.font_loader,
+                audio_engine: &self.audio_engine,
+                async_loader: &self.async_loader,
+            },
+            self.map.as_deref(),
+        );
+
         #[cfg(feature = "debug")]
         {
             profile_block!("update cache statistics");
@@ -2471,6 +4359,22 @@ impl Client {
             );
         }
 
+        // Blocked tiles can only be picked while they are part of the tile meshes, so
+        // the meshes are updated when the walkability brush is toggled.
+        #[cfg(feature = "debug")]
+        if let Some(map) = &mut self.map {
+            let is_painting_tiles = self.client_state.follow(client_state().map_editor()).is_painting_tiles();
+
+            if map.includes_blocked_tiles() != is_painting_tiles {
+                map.update_tile_meshes(
+                    &self.device,
+                    &self.queue,
+                    self.graphics_engine.bindless_support(),
+                    is_painting_tiles,
+                );
+            }
+        }
+
         // Main map update and render loop
         if self.map.is_some() {
             #[cfg(feature = "debug")]
@@ -2479,6 +4383,16 @@ impl Client {
             let window_size = self.graphics_engine.get_window_size();
             let screen_size: ScreenSize = window_size.into();
 
+            #[cfg(feature = "debug")]
+            if let Some(stress_test) = &self.stress_test {
+                let focus_point = stress_test.camera_focus_point();
+                self.player_camera.set_focus_point(focus_point);
+                self.start_camera.set_focus_point(focus_point);
+            }
+
+            self.combat_feedback.update(delta_time as f32);
+            self.player_camera.set_shake_offset(self.combat_feedback.shake_offset());
+
             if self.client_state.try_follow(this_entity()).is_some() {
                 self.player_camera.update(delta_time);
                 self.player_camera.generate_view_projection(window_size);
@@ -2488,7 +4402,22 @@ impl Client {
             }
 
             #[cfg(feature = "debug")]
-            let render_options = *self.client_state.follow(client_state().render_options());
+            let model_viewer_active = self.model_viewer_active();
+
+            #[cfg(feature = "debug")]
+            let hide_scene = self.client_state.follow(client_state().effect_preview()).hides_scene()
+                || (model_viewer_active && self.client_state.follow(client_state().model_viewer()).hides_scene());
+
+            #[cfg(feature = "debug")]
+            let render_options = {
+                let mut render_options = *self.client_state.follow(client_state().render_options());
+
+                if hide_scene {
+                    render_options.hide_scene();
+                }
+
+                render_options
+            };
 
             #[cfg(feature = "debug")]
             self.interface_renderer.update_render_options(&render_options);
@@ -2498,6 +4427,12 @@ impl Client {
                 self.debug_camera.generate_view_projection(window_size);
             }
 
+            #[cfg(feature = "debug")]
+            if model_viewer_active {
+                self.model_viewer_camera.update();
+                self.model_viewer_camera.generate_view_projection(window_size);
+            }
+
             #[cfg(feature = "debug")]
             update_main_camera_measurement.stop();
 
@@ -2510,6 +4445,8 @@ impl Client {
                 let current_camera: &(dyn Camera + Send + Sync) = match currently_playing {
                     #[cfg(feature = "debug")]
                     _ if render_options.use_debug_camera => &self.debug_camera,
+                    #[cfg(feature = "debug")]
+                    _ if model_viewer_active => &self.model_viewer_camera,
                     true => &self.player_camera,
                     false => &self.start_camera,
                 };
@@ -2517,12 +4454,87 @@ impl Client {
                 self.client_state
                     .follow_mut(client_state().entities())
                     .iter_mut()
-                    .for_each(|entity| entity.update(&self.audio_engine, self.map.as_ref().unwrap(), current_camera, client_tick));
+                    .for_each(|entity| {
+                        // Entities that are held by a hit-stop don't advance.
+                        let client_tick = self.combat_feedback.entity_tick(entity.get_entity_id(), client_tick);
+
+                        entity.update(
+                            &self.audio_engine,
+                            self.map.as_ref().unwrap(),
+                            current_camera,
+                            &self.animation_event_registry,
+                            &mut self.triggered_animation_events,
+                            client_tick,
+                        )
+                    });
 
-                self.client_state
-                    .follow_mut(client_state().dead_entities())
-                    .iter_mut()
-                    .for_each(|entity| entity.update(&self.audio_engine, self.map.as_ref().unwrap(), current_camera, client_tick));
+                let despawning_entities = self.client_state.follow_mut(client_state().despawning_entities());
+
+                despawning_entities.iter_mut().for_each(|entity| {
+                    entity.update(
+                        &self.audio_engine,
+                        self.map.as_ref().unwrap(),
+                        current_camera,
+                        &self.animation_event_registry,
+                        &mut self.triggered_animation_events,
+                        client_tick,
+                    )
+                });
+                despawning_entities.retain(|entity| !entity.is_despawned());
+
+                // Entities beyond the draw distance or limit are still updated above, so they
+                // show up in the correct state as soon as they are drawn again.
+                {
+                    let entity_draw_distance = *self.client_state.follow(client_state().graphics_settings().entity_draw_distance());
+                    let entity_limit = *self.client_state.follow(client_state().graphics_settings().entity_limit());
+                    let buffered_attack_entity = *self.client_state.follow(client_state().buffered_attack_entity());
+
+                    let entities = self.client_state.follow_mut(client_state().entities());
+                    let positions: Vec<TilePosition> = entities.iter().map(Entity::get_tile_position).collect();
+                    let prioritized =
+                        buffered_attack_entity.and_then(|entity_id| entities.iter().position(|entity| entity.get_entity_id() == entity_id));
+
+                    let visible = select_visible_entities(&positions, prioritized, entity_draw_distance.tiles(), entity_limit.count());
+
+                    entities
+                        .iter_mut()
+                        .zip(visible)
+                        .for_each(|(entity, visible)| entity.set_culled(!visible));
+                }
+
+                // Entities standing on the same tile are moved apart slightly, so they can be
+                // told apart. This is purely visual and doesn't change their tile.
+                {
+                    let entities = self.client_state.follow_mut(client_state().entities());
+                    let stacking: Vec<(EntityId, Option<TilePosition>)> = entities
+                        .iter()
+                        .map(|entity| {
+                            (
+                                entity.get_entity_id(),
+                                (!entity.is_moving()).then(|| entity.get_tile_position()),
+                            )
+                        })
+                        .collect();
+
+                    entities
+                        .iter_mut()
+                        .zip(separation_offsets(&stacking))
+                        .for_each(|(entity, target_offset)| entity.update_separation(target_offset, delta_time as f32));
+                }
+
+                for triggered_event in self.triggered_animation_events.drain(..) {
+                    match triggered_event.event {
+                        AnimationEvent::Sound { key } => {
+                            self.audio_engine
+                                .play_spatial_sound_effect(key, triggered_event.position, SPATIAL_SOUND_RANGE);
+                        }
+                        AnimationEvent::AttackImpact => {
+                            // TODO: Show the damage of an attack at its impact
+                            // instead of when the
+                            //       server reports it.
+                        }
+                    }
+                }
 
                 // Buffered attack (the player tried attacking while out of range).
                 let auto_attack = *self.client_state.follow(client_state().game_settings().auto_attack());
@@ -2560,16 +4572,28 @@ impl Client {
                 // `manually_asserted` is safe because we are in the branch where `this_player`
                 // is not `None`.
                 let position = self.client_state.follow(this_entity().manually_asserted()).get_position();
+
+                #[cfg_attr(feature = "debug", korangar_debug::debug_condition(self.stress_test.is_none()))]
                 self.player_camera.set_smoothed_focus_point(position);
             }
 
             let current_camera: &(dyn Camera + Send + Sync) = match currently_playing {
                 #[cfg(feature = "debug")]
                 _ if render_options.use_debug_camera => &self.debug_camera,
+                #[cfg(feature = "debug")]
+                _ if model_viewer_active => &self.model_viewer_camera,
                 true => &self.player_camera,
                 false => &self.start_camera,
             };
 
+            let sprite_depth_bias_settings = *self.client_state.follow(client_state().graphics_settings().sprite_depth_bias());
+            let sprite_depth_bias = match currently_playing {
+                #[cfg(feature = "debug")]
+                _ if render_options.use_debug_camera || model_viewer_active => sprite_depth_bias_settings.debug,
+                true => sprite_depth_bias_settings.player,
+                false => sprite_depth_bias_settings.start,
+            };
+
             let (view_matrix, projection_matrix) = current_camera.view_projection_matrices();
             let camera_position = current_camera.camera_position().to_homogeneous();
 
@@ -2579,8 +4603,23 @@ impl Client {
             let lighting_mode = *self.client_state.follow(client_state().graphics_settings().lighting_mode());
             let shadow_detail = *self.client_state.follow(client_state().graphics_settings().shadow_detail());
             let shadow_quality = *self.client_state.follow(client_state().graphics_settings().shadow_quality());
+            let entity_shadows = *self.client_state.follow(client_state().graphics_settings().entity_shadows());
+            let water_quality = *self.client_state.follow(client_state().graphics_settings().water_quality());
+            let light_map_mode = *self.client_state.follow(client_state().graphics_settings().light_map_mode());
+            let view_range_dimming = *self.client_state.follow(client_state().graphics_settings().view_range_dimming());
+
+            let view_range_center = currently_playing.then(|| {
+                // SAFETY
+                // `manually_asserted` is safe because `currently_playing` is only true if
+                // `this_player` is not `None`.
+                self.client_state.follow(this_entity().manually_asserted()).get_position()
+            });
+            // The player stands in the center of a tile, so the view range ends half a tile
+            // further out.
+            let view_range_radius = (SERVER_VIEW_RANGE as f32 + 0.5) * GAT_TILE_SIZE;
 
             let ambient_light_color = map.ambient_light_color();
+            let wind = map.wind(animation_timer_ms);
 
             let (directional_light_direction, directional_light_color) = map.directional_light();
 
@@ -2626,16 +4665,39 @@ impl Client {
             #[cfg(feature = "debug")]
             let prepare_frame_measurement = Profiler::start_measurement("prepare frame");
 
+            let dialog_text_speed = *self.client_state.follow(client_state().game_settings().dialog_text_speed());
+            self.client_state
+                .follow_mut(client_state().dialog_window())
+                .update_text_reveal(delta_time, dialog_text_speed);
+
             self.particle_holder.update(delta_time as f32);
+            // Effects can be slowed down for debugging, without affecting the rest of the
+            // client.
+            #[cfg(feature = "debug")]
+            let effect_delta_time = delta_time * render_options.effect_time_scale.factor();
+            #[cfg(not(feature = "debug"))]
+            let effect_delta_time = delta_time;
+
             self.effect_holder
-                .update(self.client_state.follow(client_state().entities()), delta_time as f32);
+                .update(self.client_state.follow(client_state().entities()), effect_delta_time);
+
+            // The effect preview has its own time scale.
+            #[cfg(feature = "debug")]
+            self.client_state.follow_mut(client_state().effect_preview()).update(delta_time);
+            self.decal_holder.update(delta_time as f32);
 
             self.mouse_cursor.update(client_tick);
 
             let walk_indicator_color = *self.client_state.follow(client_state().world_theme().indicator().walking());
 
+            #[cfg(feature = "debug")]
+            let moving_marker_identifier = self.interface.get_mouse_mode().moving_marker();
+
+            // A marker that is dragged in the map editor stays highlighted, even when the
+            // mouse is not exactly above it.
             #[cfg(feature = "debug")]
             let hovered_marker_identifier = match input_report.mouse_target {
+                _ if moving_marker_identifier.is_some() => moving_marker_identifier,
                 PickerTarget::Marker(marker_identifier) => Some(marker_identifier),
                 _ => None,
             };
@@ -2671,19 +4733,24 @@ impl Client {
                 top: input_report.mouse_position.top.clamp(0.0, window_size.y as f32),
             };
             let mut indicator_instruction = None;
+            let mut sky_instruction = None;
             let mut water_instruction = None;
 
             // Marker
             {
+                // While dragging a marker, we don't render the markers into the picker, so
+                // the tile below the mouse can be picked.
                 #[cfg(feature = "debug")]
-                map.render_markers(
-                    &mut self.debug_marker_renderer,
-                    current_camera,
-                    &render_options,
-                    self.client_state.follow(client_state().entities()),
-                    &point_light_set,
-                    hovered_marker_identifier,
-                );
+                if moving_marker_identifier.is_none() {
+                    map.render_markers(
+                        &mut self.debug_marker_renderer,
+                        current_camera,
+                        &render_options,
+                        self.client_state.follow(client_state().entities()),
+                        &point_light_set,
+                        hovered_marker_identifier,
+                    );
+                }
 
                 #[cfg(feature = "debug")]
                 map.render_markers(
@@ -2718,6 +4785,7 @@ impl Client {
                         &object_set,
                         animation_timer_ms,
                         &partition_camera,
+                        None,
                     );
 
                     #[cfg_attr(feature = "debug", korangar_debug::debug_condition(render_options.show_map))]
@@ -2750,19 +4818,24 @@ impl Client {
                         &self.pathing_texture_set,
                     );
 
-                    #[cfg_attr(feature = "debug", korangar_debug::debug_condition(render_options.show_entities))]
-                    map.render_entities(
-                        entity_instructions,
-                        self.client_state.follow(client_state().entities()),
-                        &partition_camera,
-                    );
+                    // Entities with blob shadows don't cast shadows into the shadow maps.
+                    if entity_shadows == EntityShadows::Projected {
+                        #[cfg_attr(feature = "debug", korangar_debug::debug_condition(render_options.show_entities))]
+                        map.render_entities(
+                            entity_instructions,
+                            self.client_state.follow(client_state().entities()),
+                            &partition_camera,
+                            &sprite_depth_bias,
+                        );
 
-                    #[cfg_attr(feature = "debug", korangar_debug::debug_condition(render_options.show_entities))]
-                    map.render_dead_entities(
-                        entity_instructions,
-                        self.client_state.follow(client_state().dead_entities()),
-                        &partition_camera,
-                    );
+                        #[cfg_attr(feature = "debug", korangar_debug::debug_condition(render_options.show_entities))]
+                        map.render_despawning_entities(
+                            entity_instructions,
+                            self.client_state.follow(client_state().despawning_entities()),
+                            &partition_camera,
+                            &sprite_depth_bias,
+                        );
+                    }
                 }
             }
 
@@ -2793,10 +4866,31 @@ impl Client {
                     render_options.frustum_culling,
                 );
 
+                let player_position = currently_playing.then(|| {
+                    // SAFETY
+                    // `manually_asserted` is safe because `currently_playing` is only true if
+                    // `this_player` is not `None`.
+                    self.client_state.follow(this_entity().manually_asserted()).get_position()
+                });
+
+                map.update_object_fade(&object_set, current_camera.camera_position(), player_position, delta_time);
+
+                // Shadows are still cast by the hidden objects, so the lighting inside
+                // buildings doesn't change.
+                let culling_height = player_position
+                    .filter(|_| *self.client_state.follow(client_state().graphics_settings().vertical_culling()))
+                    .and_then(|player_position| map.vertical_culling_height(player_position));
+
                 let offset = self.model_instructions.len();
 
                 #[cfg_attr(feature = "debug", korangar_debug::debug_condition(render_options.show_objects))]
-                map.render_objects(&mut self.model_instructions, &object_set, animation_timer_ms, current_camera);
+                map.render_objects(
+                    &mut self.model_instructions,
+                    &object_set,
+                    animation_timer_ms,
+                    current_camera,
+                    culling_height,
+                );
 
                 #[cfg_attr(feature = "debug", korangar_debug::debug_condition(render_options.show_map))]
                 map.render_ground(&mut self.model_instructions);
@@ -2824,6 +4918,25 @@ impl Client {
                     &self.pathing_texture_set,
                 );
 
+                #[cfg(feature = "debug")]
+                if model_viewer_active {
+                    let model_viewer = self.client_state.follow(client_state().model_viewer());
+
+                    if let Some(preview) = model_viewer.preview() {
+                        preview.render_geometry(
+                            &mut self.model_instructions,
+                            &mut self.model_batches,
+                            animation_timer_ms,
+                            current_camera,
+                            model_viewer.shows_normals(),
+                        );
+
+                        if model_viewer.shows_bounding_box() {
+                            preview.render_bounding_box(&mut self.aabb_instructions, Color::rgb_u8(0, 200, 255));
+                        }
+                    }
+                }
+
                 let entity_camera = match true {
                     #[cfg(feature = "debug")]
                     _ if *self.client_state.follow(client_state().render_options().show_entities_paper()) => &self.player_camera,
@@ -2835,15 +4948,55 @@ impl Client {
                     &mut self.entity_instructions,
                     self.client_state.follow(client_state().entities()),
                     entity_camera,
+                    &sprite_depth_bias,
                 );
 
+                // Highlight the hovered entity, the current target of the player and entities
+                // that flash after being damaged.
+                {
+                    let hovered_entity_id = match input_report.mouse_target {
+                        PickerTarget::Entity(entity_id) => Some(entity_id),
+                        _ => None,
+                    };
+                    let targeted_entity_id = *self.client_state.follow(client_state().buffered_attack_entity());
+
+                    self.entity_instructions.iter_mut().for_each(|instruction| {
+                        if Some(instruction.entity_id) == targeted_entity_id {
+                            instruction.highlight = TARGETED_ENTITY_HIGHLIGHT;
+                        } else if Some(instruction.entity_id) == hovered_entity_id {
+                            instruction.highlight = HOVERED_ENTITY_HIGHLIGHT;
+                        }
+
+                        if let Some(flash_highlight) = self.combat_feedback.flash_highlight(instruction.entity_id) {
+                            instruction.highlight = instruction.highlight.max(flash_highlight);
+                        }
+                    });
+                }
+
+                // Rendered after the highlighting, since a despawning entity can share its
+                // entity id with a new entity.
                 #[cfg_attr(feature = "debug", korangar_debug::debug_condition(render_options.show_entities))]
-                map.render_dead_entities(
+                map.render_despawning_entities(
                     &mut self.entity_instructions,
-                    self.client_state.follow(client_state().dead_entities()),
+                    self.client_state.follow(client_state().despawning_entities()),
                     entity_camera,
+                    &sprite_depth_bias,
                 );
 
+                if entity_shadows == EntityShadows::Blob {
+                    #[cfg_attr(feature = "debug", korangar_debug::debug_condition(render_options.show_entities))]
+                    map.render_shadow_blobs(
+                        &mut self.shadow_blob_instructions,
+                        self.client_state.follow(client_state().entities()),
+                    );
+
+                    #[cfg_attr(feature = "debug", korangar_debug::debug_condition(render_options.show_entities))]
+                    map.render_shadow_blobs(
+                        &mut self.shadow_blob_instructions,
+                        self.client_state.follow(client_state().despawning_entities()),
+                    );
+                }
+
                 #[cfg(feature = "debug")]
                 if render_options.show_entities_debug {
                     map.render_entities_debug(
@@ -2854,11 +5007,36 @@ impl Client {
 
                     map.render_entities_debug(
                         &mut self.rectangle_instructions,
-                        self.client_state.follow(client_state().dead_entities()),
+                        self.client_state.follow(client_state().despawning_entities()),
                         entity_camera,
                     );
                 }
 
+                #[cfg_attr(feature = "debug", korangar_debug::debug_condition(render_options.show_decals))]
+                map.render_decals(&mut self.decal_instructions, &self.decal_holder);
+
+                if let Some(skill) = &self.targeted_skill
+                    && let PickerTarget::Tile { x, y } = input_report.mouse_target
+                    && let Some(position) = map.get_world_position(TilePosition { x, y })
+                {
+                    let in_range = self.client_state.try_follow(this_entity()).is_some_and(|player| {
+                        self.path_finder
+                            .is_in_skill_range(&**map, player.get_tile_position(), TilePosition { x, y }, skill.attack_range)
+                    });
+                    let color = match in_range {
+                        true => SKILL_TARGET_VALID_COLOR,
+                        false => SKILL_TARGET_INVALID_COLOR,
+                    };
+
+                    let size = skill.area_size() as f32 * GAT_TILE_SIZE;
+                    let texture = self.texture_loader.get_or_load(SKILL_UNIT_DECAL_TEXTURE, ImageType::Color).unwrap();
+                    let decal = Decal::new(texture, position, Vector2::new(size, size)).with_color(color);
+                    map.render_decal(&mut self.decal_instructions, &decal);
+                }
+
+                #[cfg_attr(feature = "debug", korangar_debug::debug_condition(render_options.show_sky))]
+                map.render_sky(&mut sky_instruction);
+
                 #[cfg_attr(feature = "debug", korangar_debug::debug_condition(render_options.show_water))]
                 map.render_water(&mut water_instruction, animation_timer_ms);
 
@@ -2894,16 +5072,44 @@ impl Client {
                     );
                 }
 
+                #[cfg(feature = "debug")]
+                {
+                    let map_editor = self.client_state.follow(client_state().map_editor());
+
+                    if map_editor.is_enabled()
+                        && let Some(MarkerIdentifier::Object(object_key)) = map_editor.selected()
+                    {
+                        map.render_gizmo(
+                            &mut self.aabb_instructions,
+                            &mut self.circle_instructions,
+                            current_camera,
+                            object_key,
+                            map_editor.gizmo_mode(),
+                            map_editor.gizmo_axis(),
+                        );
+                    }
+                }
+
+                #[cfg_attr(feature = "debug", korangar_debug::debug_condition(!hide_scene))]
                 self.particle_holder.render(
                     &self.bottom_interface_renderer,
                     current_camera,
                     screen_size,
                     scaling,
                     self.client_state.follow(client_state().entities()),
+                    self.client_state.follow(client_state().world_theme()),
                 );
 
+                #[cfg_attr(feature = "debug", korangar_debug::debug_condition(!hide_scene))]
                 self.effect_holder.render(&mut self.effect_renderer, current_camera);
 
+                #[cfg(feature = "debug")]
+                self.client_state.follow(client_state().effect_preview()).render(
+                    &mut self.effect_renderer,
+                    current_camera,
+                    current_camera.focus_point(),
+                );
+
                 if let Some(player) = self.client_state.try_follow(this_entity()) {
                     #[cfg(feature = "debug")]
                     profile_block!("render player status");
@@ -2916,9 +5122,75 @@ impl Client {
                     );
                 }
 
+                if currently_playing {
+                    #[cfg(feature = "debug")]
+                    profile_block!("render party indicators");
+
+                    let show_names = *self.client_state.follow(client_state().game_settings().party_member_names());
+                    let theme = self.client_state.follow(client_state().world_theme());
+                    let margin = PARTY_INDICATOR_MARGIN * scaling.get_factor();
+                    let arrow_size = PARTY_INDICATOR_SIZE * scaling.get_factor();
+
+                    for member in self.client_state.follow(client_state().party()).members() {
+                        let Some(world_position) = member.position.and_then(|position| map.get_world_position(position)) else {
+                            continue;
+                        };
+
+                        let clip_space_position = current_camera.view_projection_matrix() * world_position.to_homogeneous();
+                        let screen_position = current_camera.clip_to_screen_space(clip_space_position);
+                        let screen_position = ScreenPosition {
+                            left: screen_position.x * screen_size.width,
+                            top: screen_position.y * screen_size.height,
+                        };
+
+                        let behind_camera = clip_space_position.w < 0.0;
+
+                        let Some(indicator) = off_screen_indicator(screen_position, behind_camera, screen_size, margin) else {
+                            continue;
+                        };
+
+                        self.middle_interface_renderer
+                            .render_arrow(indicator.position, indicator.direction, arrow_size, member.color);
+
+                        // Put the name and the health bar on the side of the arrow that faces the
+                        // center of the screen.
+                        let label_position = indicator.position
+                            - ScreenPosition {
+                                left: indicator.direction.x * arrow_size * 3.0,
+                                top: indicator.direction.y * arrow_size * 3.0,
+                            };
+
+                        if member.maximum_health_points > 0 {
+                            self.middle_interface_renderer.render_bar(
+                                label_position,
+                                ScreenSize {
+                                    width: theme.status_bar.enemy_bar_width,
+                                    height: theme.status_bar.enemy_health_height,
+                                },
+                                theme.status_bar.player_health_color,
+                                member.maximum_health_points as f32,
+                                member.health_points as f32,
+                            );
+                        }
+
+                        if show_names && !member.name.is_empty() {
+                            self.middle_interface_renderer.render_text(
+                                &member.name,
+                                label_position + ScreenPosition::only_top(theme.status_bar.enemy_health_height + 2.0),
+                                member.color,
+                                FontSize(14.0),
+                                AlignHorizontal::Mid,
+                            );
+                        }
+                    }
+                }
+
                 let mouse_mode = self.interface.get_mouse_mode();
                 let is_mouse_mode_default = mouse_mode.is_default();
                 let last_walking_destination = mouse_mode.walk_destination();
+                let dragged_item = mouse_mode.dragged_item();
+                #[cfg(feature = "debug")]
+                let painted_walkability = mouse_mode.painted_walkability();
 
                 let mut interface_frame = {
                     #[cfg(feature = "debug")]
@@ -2956,16 +5228,35 @@ impl Client {
 
                     if let Some(mouse_button) = input_report.mouse_click {
                         if is_interface_hovered {
-                            interface_frame.click(&self.client_state, mouse_button);
+                            if interface_frame.click(&self.client_state, mouse_button) {
+                                self.interface_sound_effects.play(InterfaceSound::Click);
+                            }
                         } else {
                             interface_frame.unfocus();
 
                             if mouse_button == MouseButton::Left {
                                 match input_report.mouse_target {
+                                    PickerTarget::Tile { x, y } if self.targeted_skill.is_some() => {
+                                        let destination = TilePosition { x, y };
+                                        self.input_event_buffer.push(InputEvent::ConfirmSkillTarget { destination });
+                                    }
                                     PickerTarget::Nothing => {}
                                     PickerTarget::Entity(entity_id) => {
                                         self.input_event_buffer.push(InputEvent::PlayerInteract { entity_id })
                                     }
+                                    #[cfg(feature = "debug")]
+                                    PickerTarget::Tile { x, y }
+                                        if self.client_state.follow(client_state().map_editor()).is_painting_tiles() =>
+                                    {
+                                        let position = TilePosition { x, y };
+                                        // The first painted tile decides if the stroke makes tiles walkable or blocks
+                                        // them.
+                                        let walkable = !map.is_walkable(position);
+
+                                        interface_frame.set_mouse_mode(MouseInputMode::PaintTiles { walkable });
+
+                                        self.input_event_buffer.push(InputEvent::PaintTile { position, walkable });
+                                    }
                                     PickerTarget::Tile { x, y } => {
                                         let destination = TilePosition { x, y };
 
@@ -2974,10 +5265,28 @@ impl Client {
                                         self.input_event_buffer.push(InputEvent::PlayerMove { destination });
                                     }
                                     #[cfg(feature = "debug")]
+                                    PickerTarget::Marker(marker_identifier)
+                                        if marker_identifier.is_editable()
+                                            && self.client_state.follow(client_state().map_editor()).is_enabled() =>
+                                    {
+                                        interface_frame.set_mouse_mode(MouseInputMode::MoveMarker { marker_identifier });
+
+                                        self.input_event_buffer.push(InputEvent::SelectMarker { marker_identifier });
+                                    }
+                                    #[cfg(feature = "debug")]
                                     PickerTarget::Marker(marker_identifier) => {
                                         self.input_event_buffer.push(InputEvent::OpenMarkerDetails { marker_identifier })
                                     }
                                 }
+                            } else if mouse_button == MouseButton::Right && self.targeted_skill.is_some() {
+                                self.targeted_skill = None;
+                            } else if mouse_button == MouseButton::Right
+                                && let PickerTarget::Entity(entity_id) = input_report.mouse_target
+                                && cursor_state == MouseCursorState::Attack
+                            {
+                                // Right clicking a monster shows its information instead of rotating the
+                                // camera.
+                                self.input_event_buffer.push(InputEvent::InspectMonster { entity_id });
                             } else if mouse_button == MouseButton::Right && currently_playing {
                                 #[cfg_attr(feature = "debug", korangar_debug::debug_condition(!render_options.use_debug_camera))]
                                 interface_frame.set_mouse_mode(MouseInputMode::RotateCamera);
@@ -2998,7 +5307,64 @@ impl Client {
                         }
                     }
 
+                    #[cfg(feature = "debug")]
+                    if let Some(marker_identifier) = moving_marker_identifier
+                        && input_report.left_mouse_button_down
+                    {
+                        match (marker_identifier, input_report.mouse_target) {
+                            // Objects are transformed with the gizmo, which follows the movement of the
+                            // mouse.
+                            (MarkerIdentifier::Object(object_key), _) => {
+                                let map_editor = self.client_state.follow(client_state().map_editor());
+
+                                if let Some(position) = map.marker_position(marker_identifier) {
+                                    let amount = gizmo_drag_amount(
+                                        current_camera,
+                                        screen_size,
+                                        position,
+                                        map_editor.gizmo_mode(),
+                                        map_editor.gizmo_axis(),
+                                        input_report.mouse_delta,
+                                    );
+
+                                    if amount != 0.0 {
+                                        self.input_event_buffer.push(InputEvent::DragGizmo { object_key, amount });
+                                    }
+                                }
+                            }
+                            (_, PickerTarget::Tile { x, y }) => {
+                                let destination = TilePosition { x, y };
+                                self.input_event_buffer.push(InputEvent::MoveMarker {
+                                    marker_identifier,
+                                    destination,
+                                });
+                            }
+                            _ => {}
+                        }
+                    }
+
+                    #[cfg(feature = "debug")]
+                    if let Some(walkable) = painted_walkability
+                        && let PickerTarget::Tile { x, y } = input_report.mouse_target
+                        && input_report.left_mouse_button_down
+                    {
+                        let position = TilePosition { x, y };
+                        self.input_event_buffer.push(InputEvent::PaintTile { position, walkable });
+                    }
+
                     if input_report.mouse_button_released {
+                        // Items that are dropped outside of the interface are dropped to the
+                        // ground.
+                        if let Some((source, item)) = dragged_item
+                            && !is_interface_hovered
+                        {
+                            self.input_event_buffer.push(InputEvent::MoveItem {
+                                source,
+                                destination: ItemSource::Ground,
+                                item,
+                            });
+                        }
+
                         interface_frame.drop(&self.client_state);
                     }
 
@@ -3021,6 +5387,11 @@ impl Client {
                         interface_frame.focus_element(ChatTextBox);
                     }
 
+                    #[cfg(feature = "debug")]
+                    if std::mem::take(&mut self.focus_console) {
+                        interface_frame.focus_element(ConsoleTextBox);
+                    }
+
                     interface_frame
                 };
 
@@ -3128,7 +5499,19 @@ impl Client {
                     );
                 }
 
+                self.update_instance_warning();
+
                 if self.show_interface {
+                    let now = Instant::now();
+                    self.toasts.update(now);
+                    self.toasts.render(
+                        &self.top_interface_renderer,
+                        self.client_state.follow(client_state().world_theme().toast()),
+                        *self.client_state.follow(client_state().window_size()),
+                        self.client_state.follow(client_state().interface_settings().scaling()).get_factor(),
+                        now,
+                    );
+
                     self.mouse_cursor.render(
                         &self.top_interface_renderer,
                         input_report.mouse_position,
@@ -3161,6 +5544,12 @@ impl Client {
                     ambient_light_color,
                     enhanced_lighting: lighting_mode == LightingMode::Enhanced,
                     shadow_quality,
+                    water_quality,
+                    light_map_mode,
+                    view_range_center,
+                    view_range_radius,
+                    view_range_dimming,
+                    wind,
                 },
                 indicator: indicator_instruction,
                 interface: interface_instructions.as_slice(),
@@ -3178,15 +5567,19 @@ impl Client {
                 model_batches: &self.model_batches,
                 models: &mut self.model_instructions,
                 entities: &mut self.entity_instructions,
+                shadow_blobs: &self.shadow_blob_instructions,
+                decals: &self.decal_instructions,
                 directional_shadow_model_batches: &self.directional_shadow_model_batches,
                 directional_shadow_models: &self.directional_shadow_model_instructions,
                 directional_shadow_entities: &self.directional_shadow_entity_instructions,
                 point_shadow_models: &self.point_shadow_model_instructions,
                 point_shadow_entities: &self.point_shadow_entity_instructions,
                 effects: self.effect_renderer.get_instructions(),
+                sky: sky_instruction,
                 water: water_instruction,
                 map_picker_tile_vertex_buffer: Some(map.get_tile_picker_vertex_buffer()),
                 map_picker_tile_index_buffer: Some(map.get_tile_picker_index_buffer()),
+                map_light_map_texture: map.get_light_map_texture(),
                 font_map_texture: Some(self.font_loader.get_font_map()),
                 #[cfg(feature = "debug")]
                 render_options,
@@ -3218,6 +5611,36 @@ impl Client {
         self.client_state.apply();
     }
 
+    fn push_chat_message(&mut self, color: MessageColor, text: impl Into<String>) {
+        self.client_state
+            .follow_mut(client_state().chat_messages())
+            .push(ChatMessage::new(text.into(), color));
+    }
+
+    /// Whether the model viewer is open and has a model loaded, in which case
+    /// it takes over the camera.
+    #[cfg(feature = "debug")]
+    fn model_viewer_active(&self) -> bool {
+        self.interface.is_window_with_class_open(WindowClass::ModelViewer)
+            && self.client_state.follow(client_state().model_viewer()).preview().is_some()
+    }
+
+    /// Update the monitors and video modes that can be selected in the
+    /// graphics settings.
+    fn update_display_options(&mut self) {
+        let Some(window) = &self.window else {
+            return;
+        };
+
+        let monitor = self.client_state.follow(client_state().graphics_settings().monitor());
+        let monitor_options = monitor_options(window);
+        let video_mode_options = video_mode_options(window, monitor.as_deref());
+
+        self.client_state
+            .follow_mut(client_state().graphics_settings_capabilities())
+            .update_display_options(monitor_options, video_mode_options);
+    }
+
     #[cfg_attr(feature = "debug", korangar_debug::profile)]
     fn update_settings(&mut self) {
         let graphics_settings = self.client_state.follow(client_state().graphics_settings());
@@ -3242,6 +5665,19 @@ impl Client {
             self.active_graphics_settings.texture_filtering = graphics_settings.texture_filtering;
         }
 
+        if self.active_graphics_settings.sprite_filtering != graphics_settings.sprite_filtering {
+            self.graphics_engine.set_sprite_filtering(graphics_settings.sprite_filtering);
+            self.active_graphics_settings.sprite_filtering = graphics_settings.sprite_filtering;
+        }
+
+        if self.active_graphics_settings.sprite_upscaling != graphics_settings.sprite_upscaling {
+            // Sprites are upscaled when loading them, so we need to drop everything that
+            // was built from the old sprites.
+            self.sprite_loader.set_upscaling(graphics_settings.sprite_upscaling);
+            self.animation_loader.clear_cache();
+            self.active_graphics_settings.sprite_upscaling = graphics_settings.sprite_upscaling;
+        }
+
         if self.active_graphics_settings.msaa != graphics_settings.msaa {
             self.graphics_engine.set_msaa(graphics_settings.msaa);
             self.active_graphics_settings.msaa = graphics_settings.msaa;
@@ -3271,6 +5707,50 @@ impl Client {
             self.active_graphics_settings.high_quality_interface = graphics_settings.high_quality_interface;
         }
 
+        if self.active_graphics_settings.crt_filter != graphics_settings.crt_filter {
+            self.graphics_engine.set_crt_filter(graphics_settings.crt_filter);
+            self.active_graphics_settings.crt_filter = graphics_settings.crt_filter;
+        }
+
+        if self.active_graphics_settings.order_independent_effects != graphics_settings.order_independent_effects {
+            self.graphics_engine
+                .set_order_independent_effects(graphics_settings.order_independent_effects);
+            self.active_graphics_settings.order_independent_effects = graphics_settings.order_independent_effects;
+        }
+
+        if self.active_graphics_settings.display_mode != graphics_settings.display_mode
+            || self.active_graphics_settings.monitor != graphics_settings.monitor
+            || self.active_graphics_settings.video_mode != graphics_settings.video_mode
+        {
+            let monitor_changed = self.active_graphics_settings.monitor != graphics_settings.monitor;
+
+            self.active_graphics_settings.display_mode = graphics_settings.display_mode;
+            self.active_graphics_settings.monitor = graphics_settings.monitor.clone();
+            self.active_graphics_settings.video_mode = graphics_settings.video_mode;
+
+            if let Some(window) = &self.window {
+                apply_display_mode(
+                    window,
+                    graphics_settings.display_mode,
+                    graphics_settings.monitor.as_deref(),
+                    graphics_settings.video_mode,
+                );
+
+                if monitor_changed {
+                    self.update_display_options();
+                }
+            }
+        }
+
+        // The remaining settings are read straight from the client state, but we still
+        // track them to only update the crash report when something changed.
+        let graphics_settings = self.client_state.follow(client_state().graphics_settings());
+
+        if self.active_graphics_settings != *graphics_settings {
+            crash_report::set_graphics_settings(graphics_settings);
+            self.active_graphics_settings.assign(graphics_settings);
+        }
+
         let language = *self.client_state.follow(client_state().interface_settings().language());
 
         if self.active_interface_settings.language != language {
@@ -3278,32 +5758,67 @@ impl Client {
             self.active_interface_settings.language = language;
         }
 
-        let interface_settings = self.client_state.follow_mut(client_state().interface_settings());
+        let interface_settings = self.client_state.follow(client_state().interface_settings());
+
+        // The accessibility options are applied on top of the themes, so all themes
+        // are reloaded when one of them changes.
+        let accessibility_changed = self.active_interface_settings.colorblind_mode != interface_settings.colorblind_mode
+            || self.active_interface_settings.high_contrast != interface_settings.high_contrast
+            || self.active_interface_settings.minimum_font_size != interface_settings.minimum_font_size;
+
+        if accessibility_changed {
+            self.active_interface_settings.colorblind_mode = interface_settings.colorblind_mode;
+            self.active_interface_settings.high_contrast = interface_settings.high_contrast;
+            self.active_interface_settings.minimum_font_size = interface_settings.minimum_font_size;
+        }
+
+        let high_contrast = self.active_interface_settings.high_contrast;
+        let minimum_font_size = self.active_interface_settings.minimum_font_size;
 
-        if self.active_interface_settings.menu_theme != interface_settings.menu_theme {
+        if accessibility_changed || self.active_interface_settings.menu_theme != interface_settings.menu_theme {
             let menu_theme = interface_settings.menu_theme.clone();
-            let theme = InterfaceTheme::load(state::theme::InterfaceThemeType::Menu, &menu_theme);
+            let mut theme = InterfaceTheme::load(state::theme::InterfaceThemeType::Menu, &menu_theme);
+            theme.apply_accessibility(high_contrast, minimum_font_size);
             *self.client_state.follow_mut(client_state().menu_theme()) = theme;
             self.active_interface_settings.menu_theme = menu_theme;
         }
 
         let interface_settings = self.client_state.follow(client_state().interface_settings());
 
-        if self.active_interface_settings.in_game_theme != interface_settings.in_game_theme {
+        if accessibility_changed || self.active_interface_settings.in_game_theme != interface_settings.in_game_theme {
             let in_game_theme = interface_settings.in_game_theme.clone();
-            let theme = InterfaceTheme::load(InterfaceThemeType::InGame, &in_game_theme);
+            let mut theme = InterfaceTheme::load(InterfaceThemeType::InGame, &in_game_theme);
+            theme.apply_accessibility(high_contrast, minimum_font_size);
             *self.client_state.follow_mut(client_state().in_game_theme()) = theme;
             self.active_interface_settings.in_game_theme = in_game_theme;
         }
 
         let interface_settings = self.client_state.follow(client_state().interface_settings());
 
-        if self.active_interface_settings.world_theme != interface_settings.world_theme {
+        if accessibility_changed || self.active_interface_settings.world_theme != interface_settings.world_theme {
             let world_theme = interface_settings.world_theme.clone();
-            let theme = WorldTheme::load(&world_theme);
+            let mut theme = WorldTheme::load(&world_theme);
+            theme.apply_accessibility(self.active_interface_settings.colorblind_mode, minimum_font_size);
             *self.client_state.follow_mut(client_state().world_theme()) = theme;
             self.active_interface_settings.world_theme = world_theme;
         }
+
+        let interface_volume = *self.client_state.follow(client_state().audio_settings().interface_volume());
+
+        if self.active_interface_volume != interface_volume {
+            self.audio_engine.set_interface_sound_effect_volume(interface_volume.linear());
+            self.active_interface_volume = interface_volume;
+        }
+
+        let action_intervals = *self.client_state.follow(client_state().game_settings().action_intervals());
+
+        if self.active_action_intervals != action_intervals {
+            for (action, interval) in action_intervals.intervals() {
+                self.networking_system.set_minimum_action_interval(action, interval);
+            }
+
+            self.active_action_intervals = action_intervals;
+        }
     }
 }
 
@@ -3320,7 +5835,7 @@ impl ApplicationHandler for Client {
                 assert_eq!(image_buffer.width(), image_buffer.height(), "icon must be square");
                 let icon = Icon::from_rgba(image_data, image_buffer.width(), image_buffer.height()).unwrap();
 
-                let window_attributes = Window::default_attributes()
+                let mut window_attributes = Window::default_attributes()
                     .with_inner_size(LogicalSize {
                         width: INITIAL_SCREEN_SIZE.width,
                         height: INITIAL_SCREEN_SIZE.height,
@@ -3328,6 +5843,17 @@ impl ApplicationHandler for Client {
                     .with_title(CLIENT_NAME)
                     .with_window_icon(Some(icon))
                     .with_visible(false);
+
+                let instance_index = system::instance_index();
+
+                if instance_index > 0
+                    && *self
+                        .client_state
+                        .follow(client_state().graphics_settings().offset_additional_instances())
+                {
+                    let offset = INSTANCE_WINDOW_OFFSET * instance_index as f64;
+                    window_attributes = window_attributes.with_position(LogicalPosition::new(offset, offset));
+                }
                 let window = Arc::new(event_loop.create_window(window_attributes).unwrap());
 
                 let backend_name = self.graphics_engine.get_backend_name();
@@ -3354,10 +5880,13 @@ impl ApplicationHandler for Client {
                 graphics_settings.limit_framerate,
                 graphics_settings.shadow_detail,
                 graphics_settings.texture_filtering,
+                graphics_settings.sprite_filtering,
                 graphics_settings.msaa,
                 graphics_settings.ssaa,
                 graphics_settings.screen_space_anti_aliasing,
                 graphics_settings.high_quality_interface,
+                graphics_settings.crt_filter,
+                graphics_settings.order_independent_effects,
             );
 
             // Update graphics settings capabilities based on the new surface.
@@ -3371,7 +5900,15 @@ impl ApplicationHandler for Client {
                     self.graphics_engine.get_present_mode_info(),
                 );
 
+            apply_display_mode(
+                window,
+                graphics_settings.display_mode,
+                graphics_settings.monitor.as_deref(),
+                graphics_settings.video_mode,
+            );
             window.set_visible(true);
+
+            self.update_display_options();
         }
 
         if *self.client_state.follow(client_state().audio_settings().mute_on_focus_loss()) {
@@ -3411,9 +5948,19 @@ impl ApplicationHandler for Client {
             WindowEvent::CursorLeft { .. } => self.mouse_cursor.hide(),
             WindowEvent::CursorEntered { .. } => self.mouse_cursor.show(),
             WindowEvent::CursorMoved { position, .. } => self.input_system.update_mouse_position(position),
-            WindowEvent::MouseInput { button, state, .. } => self.input_system.update_mouse_buttons(button, state),
+            WindowEvent::MouseInput { button, state, .. } => {
+                if state.is_pressed() {
+                    self.cancel_auto_login();
+                }
+
+                self.input_system.update_mouse_buttons(button, state);
+            }
             WindowEvent::MouseWheel { delta, .. } => self.input_system.update_mouse_wheel(delta),
             WindowEvent::KeyboardInput { event, .. } => {
+                if event.state.is_pressed() {
+                    self.cancel_auto_login();
+                }
+
                 if let PhysicalKey::Code(keycode) = event.physical_key {
                     self.input_system.update_keyboard(keycode, event.state);
                 }
@@ -9,6 +9,7 @@ use korangar_interface::element::{Element, StateElement};
 use korangar_interface::layout::{Resolver, WindowLayout};
 use korangar_interface::prelude::*;
 use korangar_interface::theme::theme;
+use korangar_networking::NetworkEventStatistics;
 use ragnarok_bytes::{ByteReader, ByteWriter, ConversionError, ConversionResult};
 use ragnarok_packets::handler::PacketCallback;
 use ragnarok_packets::{Packet, PacketHeader};
@@ -357,6 +358,7 @@ pub struct PacketHistory {
     pub show_incoming: bool,
     pub show_outgoing: bool,
     pub show_pings: bool,
+    pub event_statistics_text: String,
 }
 
 impl PacketHistory {
@@ -369,6 +371,7 @@ impl PacketHistory {
             show_incoming: true,
             show_outgoing: true,
             show_pings: false,
+            event_statistics_text: String::new(),
         };
         let packet_history_callback = PacketHistoryCallback { sender };
 
@@ -405,6 +408,19 @@ impl PacketHistory {
         }
     }
 
+    pub fn update_event_statistics(&mut self, statistics: NetworkEventStatistics) {
+        let NetworkEventStatistics {
+            processed_events,
+            queued_events,
+            maximum_queue_depth,
+            dropped_events,
+        } = statistics;
+
+        self.event_statistics_text = format!(
+            "Events: {processed_events} processed, {queued_events} queued, {maximum_queue_depth} maximum queued, {dropped_events} dropped"
+        );
+    }
+
     pub fn get_entries(&self) -> &[PacketEntry] {
         &self.entries
     }
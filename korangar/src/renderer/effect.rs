@@ -49,6 +49,7 @@ impl EffectRenderer {
 
         let clip_space_position = camera.view_projection_matrix() * position.to_homogeneous();
         let screen_space_position = camera.clip_to_screen_space(clip_space_position);
+        let depth = (clip_space_position.z / clip_space_position.w).clamp(0.0, 1.0);
 
         let half_screen = Vector2::new(self.window_size.width / 2.0, self.window_size.height / 2.0);
         let rotation_matrix = Matrix2::from_angle(angle);
@@ -78,6 +79,7 @@ impl EffectRenderer {
             source_blend_factor,
             destination_blend_factor,
             texture,
+            depth,
         });
     }
 }
@@ -243,6 +243,24 @@ impl GameInterfaceRenderer {
         self.render_rectangle(position - bar_offset, bar_size, color);
     }
 
+    /// Render an arrow made of squares that get smaller towards the tip. The
+    /// tip is at `position` and the arrow points in `direction`, which has to
+    /// be normalized.
+    pub fn render_arrow(&self, position: ScreenPosition, direction: Vector2<f32>, size: f32, color: Color) {
+        const SEGMENTS: usize = 4;
+
+        for segment in 0..SEGMENTS {
+            let distance = segment as f32 * size / 2.0;
+            let side = size * (segment + 1) as f32 / SEGMENTS as f32;
+            let center = ScreenPosition {
+                left: position.left - direction.x * distance,
+                top: position.top - direction.y * distance,
+            };
+
+            self.render_rectangle(center - ScreenSize::uniform(side / 2.0), ScreenSize::uniform(side), color);
+        }
+    }
+
     pub fn render_rectangle(&self, position: ScreenPosition, size: ScreenSize, color: Color) {
         let screen_position = position / self.window_size;
         let screen_size = size / self.window_size;
@@ -1,7 +1,7 @@
 use std::cell::{Ref, RefCell};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use cgmath::EuclideanSpace;
+use cgmath::{EuclideanSpace, Vector2};
 #[cfg(feature = "debug")]
 use korangar_interface::application::Clip;
 use korangar_interface::application::{RenderLayer, ShadowPadding as _};
@@ -11,16 +11,19 @@ use korangar_interface::layout::{ClipId, Icon, WindowLayout};
 use crate::graphics::{
     Color, CornerDiameter, InterfaceRectangleInstruction, ScreenClip, ScreenPosition, ScreenSize, ShadowPadding, Texture,
 };
-use crate::loaders::{FontLoader, FontSize, GlyphInstruction, ImageType, OverflowBehavior, Sprite, TextureLoader};
+use crate::loaders::{
+    AtlasRegion, FontLoader, FontSize, GlyphInstruction, ImageType, InterfaceAtlas, OverflowBehavior, Sprite, TextureLoader,
+};
 use crate::renderer::SpriteRenderer;
 use crate::state::ClientState;
-use crate::world::{Actions, SpriteAnimationState};
+use crate::world::{Actions, AnimationData, SpriteAnimationState};
 
 /// Renders the interface provided by [`korangar_interface`].
 pub struct InterfaceRenderer {
     instructions: RefCell<Vec<InterfaceRectangleInstruction>>,
     glyphs: RefCell<Vec<GlyphInstruction>>,
     font_loader: Arc<FontLoader>,
+    interface_atlas: Arc<Mutex<InterfaceAtlas>>,
     filled_box_texture: Arc<Texture>,
     unfilled_box_texture: Arc<Texture>,
     expanded_arrow_texture: Arc<Texture>,
@@ -61,6 +64,7 @@ impl InterfaceRenderer {
         let eye_open_texture = texture_loader.get_or_load("eye_open.png", ImageType::Sdf).unwrap();
         let eye_closed_texture = texture_loader.get_or_load("eye_closed.png", ImageType::Sdf).unwrap();
         let trash_can_texture = texture_loader.get_or_load("trash_can.png", ImageType::Sdf).unwrap();
+        let interface_atlas = texture_loader.interface_atlas();
 
         let interface_size = if high_quality_interface { window_size * 2.0 } else { window_size };
 
@@ -77,6 +81,7 @@ impl InterfaceRenderer {
             instructions,
             glyphs,
             font_loader,
+            interface_atlas,
             filled_box_texture,
             unfilled_box_texture,
             expanded_arrow_texture,
@@ -471,6 +476,19 @@ impl SpriteRenderer for InterfaceRenderer {
 
         let corner_diameter = CornerDiameter::default();
 
+        // Use the atlas page if the texture was packed into the interface atlas, so
+        // consecutive sprites don't need to switch textures.
+        let atlas_region = self.interface_atlas.lock().unwrap().get(&texture);
+        let AtlasRegion {
+            texture,
+            texture_position,
+            texture_size,
+        } = atlas_region.unwrap_or(AtlasRegion {
+            texture,
+            texture_position: Vector2::new(0.0, 0.0),
+            texture_size: Vector2::new(1.0, 1.0),
+        });
+
         self.instructions.borrow_mut().push(InterfaceRectangleInstruction::Sprite {
             screen_position,
             screen_size,
@@ -478,6 +496,8 @@ impl SpriteRenderer for InterfaceRenderer {
             color,
             corner_diameter,
             texture,
+            texture_position,
+            texture_size,
             smooth,
         });
     }
@@ -567,6 +587,21 @@ struct SpriteInstruction<'a> {
     actions: &'a Actions,
     sprite: &'a Sprite,
     animation_state: &'a SpriteAnimationState,
+    direction: usize,
+    clip_id: ClipId,
+    area: Area,
+    color: Color,
+    scaling: f32,
+}
+
+/// An instruction to render the first idle frame of a composed entity
+/// sprite, standing at the bottom center of the area.
+///
+/// These are not used outside this module but are exposed through
+/// [`CustomInstruction`]. Thus we explicitly allow a private interface.
+#[allow(private_interfaces)]
+struct AnimationInstruction<'a> {
+    animation_data: &'a AnimationData,
     clip_id: ClipId,
     area: Area,
     color: Color,
@@ -582,6 +617,8 @@ pub enum CustomInstruction<'a> {
     Texture(TextureInstruction),
     /// An instruction to render a sprite.
     Sprite(SpriteInstruction<'a>),
+    /// An instruction to render a composed entity sprite.
+    Animation(AnimationInstruction<'a>),
 }
 
 impl RenderLayer<ClientState> for InterfaceRenderer {
@@ -630,6 +667,7 @@ impl RenderLayer<ClientState> for InterfaceRenderer {
                 actions,
                 sprite,
                 animation_state,
+                direction,
                 clip_id,
                 area,
                 color,
@@ -641,7 +679,50 @@ impl RenderLayer<ClientState> for InterfaceRenderer {
                 };
                 let screen_clip = clips[clip_id.as_index()];
 
-                actions.render_sprite(self, sprite, animation_state, position, 0, screen_clip, color, scaling);
+                actions.render_sprite(self, sprite, animation_state, position, direction, screen_clip, color, scaling);
+            }
+            CustomInstruction::Animation(AnimationInstruction {
+                animation_data,
+                clip_id,
+                area,
+                color,
+                scaling,
+            }) => {
+                let Some(frame) = animation_data.animations.first().and_then(|animation| animation.frames.first()) else {
+                    return;
+                };
+
+                let origin = Vector2::new(area.left + area.width / 2.0, area.top + area.height);
+                let screen_clip = clips[clip_id.as_index()];
+
+                for frame_part in &frame.frame_parts {
+                    // Empty frames contain a placeholder part that doesn't reference a sprite.
+                    let Some(texture) = animation_data
+                        .animation_pair
+                        .get(frame_part.animation_index)
+                        .and_then(|animation_pair| animation_pair.sprites.textures.get(frame_part.sprite_number))
+                    else {
+                        continue;
+                    };
+
+                    let size = frame_part.size.map(|component| component as f32) * scaling;
+                    let position = origin + frame_part.offset.map(|component| component as f32) * scaling - size / 2.0;
+
+                    self.render_sprite(
+                        texture.clone(),
+                        ScreenPosition {
+                            left: position.x,
+                            top: position.y,
+                        },
+                        ScreenSize {
+                            width: size.x,
+                            height: size.y,
+                        },
+                        screen_clip,
+                        color,
+                        false,
+                    );
+                }
             }
             CustomInstruction::Texture(TextureInstruction {
                 texture,
@@ -672,8 +753,24 @@ pub trait LayoutExt<'a> {
     /// Add an instruction to render a texture.
     fn add_texture(&mut self, area: Area, texture: Arc<Texture>, color: Color, smooth: bool);
 
-    /// Add an instruction to render a sprite.
-    fn add_sprite(&mut self, area: Area, actions: &'a Actions, sprite: &'a Sprite, animation_state: &'a SpriteAnimationState, color: Color);
+    /// Add an instruction to render an item icon, or a placeholder if the
+    /// icon is still loading.
+    fn add_item_icon(&mut self, area: Area, texture: Option<&Arc<Texture>>);
+
+    /// Add an instruction to render a sprite facing the given direction.
+    fn add_sprite(
+        &mut self,
+        area: Area,
+        actions: &'a Actions,
+        sprite: &'a Sprite,
+        animation_state: &'a SpriteAnimationState,
+        direction: usize,
+        color: Color,
+    );
+
+    /// Add an instruction to render a composed entity sprite standing at the
+    /// bottom center of the area.
+    fn add_animation(&mut self, area: Area, animation_data: &'a AnimationData, color: Color);
 }
 
 impl<'a> LayoutExt<'a> for WindowLayout<'a, ClientState> {
@@ -690,12 +787,38 @@ impl<'a> LayoutExt<'a> for WindowLayout<'a, ClientState> {
         }));
     }
 
+    fn add_item_icon(&mut self, area: Area, texture: Option<&Arc<Texture>>) {
+        match texture {
+            Some(texture) => self.add_texture(area, texture.clone(), Color::WHITE, false),
+            None => {
+                // Keep the placeholder a bit smaller than the icon, so it doesn't touch the
+                // border of the item box.
+                let padding = area.width.min(area.height) / 6.0;
+                let placeholder_area = Area {
+                    left: area.left + padding,
+                    top: area.top + padding,
+                    width: area.width - padding * 2.0,
+                    height: area.height - padding * 2.0,
+                };
+
+                self.add_rectangle(
+                    placeholder_area,
+                    CornerDiameter::uniform(padding * 2.0),
+                    Color::rgba_u8(255, 255, 255, 30),
+                    Color::TRANSPARENT,
+                    ShadowPadding::uniform(0.0),
+                );
+            }
+        }
+    }
+
     fn add_sprite(
         &mut self,
         area: Area,
         actions: &'a Actions,
         sprite: &'a Sprite,
         animation_state: &'a SpriteAnimationState,
+        direction: usize,
         color: Color,
     ) {
         let clip_id = self.get_active_clip_id();
@@ -706,6 +829,21 @@ impl<'a> LayoutExt<'a> for WindowLayout<'a, ClientState> {
             actions,
             sprite,
             animation_state,
+            direction,
+            clip_id,
+            area,
+            color,
+            scaling,
+        }));
+    }
+
+    fn add_animation(&mut self, area: Area, animation_data: &'a AnimationData, color: Color) {
+        let clip_id = self.get_active_clip_id();
+        let area = self.scale_area(area);
+        let scaling = self.get_interface_scaling();
+
+        self.add_custom_instruction(CustomInstruction::Animation(AnimationInstruction {
+            animation_data,
             clip_id,
             area,
             color,
@@ -1,23 +1,72 @@
 #[cfg(feature = "debug")]
 use korangar_debug::logging::{Colorize, print_debug};
+use korangar_interface::components::drop_down::DropDownItem;
 use korangar_interface::element::StateElement;
 use ron::ser::PrettyConfig;
 use rust_state::RustState;
 use serde::{Deserialize, Serialize};
 
+use super::settings_path;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, StateElement)]
+pub enum Volume {
+    Off,
+    Quarter,
+    Half,
+    ThreeQuarters,
+    #[default]
+    Full,
+}
+
+impl Volume {
+    /// Linear volume in the range of 0.0 to 1.0.
+    pub fn linear(self) -> f32 {
+        match self {
+            Volume::Off => 0.0,
+            Volume::Quarter => 0.25,
+            Volume::Half => 0.5,
+            Volume::ThreeQuarters => 0.75,
+            Volume::Full => 1.0,
+        }
+    }
+}
+
+impl DropDownItem<Volume> for Volume {
+    fn text(&self) -> &str {
+        match self {
+            Volume::Off => "0%",
+            Volume::Quarter => "25%",
+            Volume::Half => "50%",
+            Volume::ThreeQuarters => "75%",
+            Volume::Full => "100%",
+        }
+    }
+
+    fn value(&self) -> Volume {
+        *self
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, RustState, StateElement)]
 pub struct AudioSettings {
     pub mute_on_focus_loss: bool,
+    /// Volume of the interface feedback, like button clicks and window
+    /// sounds.
+    #[serde(default)]
+    pub interface_volume: Volume,
 }
 
 impl Default for AudioSettings {
     fn default() -> Self {
-        Self { mute_on_focus_loss: true }
+        Self {
+            mute_on_focus_loss: true,
+            interface_volume: Volume::default(),
+        }
     }
 }
 
 impl AudioSettings {
-    const FILE_NAME: &'static str = "client/audio_settings.ron";
+    pub(super) const FILE_NAME: &'static str = "client/audio_settings.ron";
 
     pub fn new() -> Self {
         Self::load().unwrap_or_else(|| {
@@ -30,7 +79,7 @@ impl AudioSettings {
     pub fn load() -> Option<Self> {
         #[cfg(feature = "debug")]
         print_debug!("loading audio settings from {}", Self::FILE_NAME.magenta());
-        std::fs::read_to_string(Self::FILE_NAME)
+        std::fs::read_to_string(settings_path(Self::FILE_NAME))
             .ok()
             .and_then(|data| ron::from_str(&data).ok())
     }
@@ -41,7 +90,7 @@ impl AudioSettings {
 
         let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
 
-        if let Err(_error) = std::fs::write(Self::FILE_NAME, data) {
+        if let Err(_error) = std::fs::write(settings_path(Self::FILE_NAME), data) {
             #[cfg(feature = "debug")]
             print_debug!(
                 "failed to save audio settings to {}: {:?}",
@@ -57,3 +106,16 @@ impl Drop for AudioSettings {
         self.save();
     }
 }
+
+#[derive(RustState, StateElement)]
+pub struct AudioSettingsCapabilities {
+    volumes: Vec<Volume>,
+}
+
+impl Default for AudioSettingsCapabilities {
+    fn default() -> Self {
+        Self {
+            volumes: vec![Volume::Off, Volume::Quarter, Volume::Half, Volume::ThreeQuarters, Volume::Full],
+        }
+    }
+}
@@ -0,0 +1,276 @@
+//! Secure storage for the passwords of the login settings, so they are never
+//! written to the login settings file in plain text.
+//!
+//! Passwords are stored in the keychain of the operating system if one is
+//! available. Otherwise they are encrypted with XChaCha20-Poly1305 and written
+//! to a file, with the key in a separate file that only the current user can
+//! read.
+
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::random::{DefaultRandomSource, RandomSource};
+use std::sync::OnceLock;
+
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+#[cfg(feature = "debug")]
+use korangar_debug::logging::{Colorize, print_debug};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+use super::settings_path;
+use crate::loaders::ServiceId;
+
+const KEY_SIZE: usize = 32;
+const NONCE_SIZE: usize = 24;
+/// Name under which the passwords are stored in the keychain.
+const KEYCHAIN_SERVICE: &str = "korangar";
+/// Keychain entry that is only used to check if the keychain is available.
+const KEYCHAIN_PROBE_USER: &str = "availability-check";
+
+#[derive(Serialize, Deserialize)]
+struct EncryptedPassword {
+    nonce: [u8; NONCE_SIZE],
+    ciphertext: Vec<u8>,
+}
+
+enum Backend {
+    Keychain,
+    EncryptedFile { cipher: XChaCha20Poly1305 },
+}
+
+pub struct CredentialStore {
+    backend: Backend,
+}
+
+impl CredentialStore {
+    const FILE_NAME: &'static str = "client/credentials.ron";
+    const KEY_FILE_NAME: &'static str = "client/credentials.key";
+
+    /// Use the keychain of the operating system if it can be reached, and the
+    /// encrypted file otherwise.
+    pub fn new() -> Self {
+        if keychain_available() {
+            return Self {
+                backend: Backend::Keychain,
+            };
+        }
+
+        #[cfg(feature = "debug")]
+        print_debug!("no keychain available, storing credentials in {}", Self::FILE_NAME.magenta());
+
+        let key = Self::load_or_create_key();
+        let cipher = XChaCha20Poly1305::new(&key.into());
+
+        Self {
+            backend: Backend::EncryptedFile { cipher },
+        }
+    }
+
+    fn load_or_create_key() -> [u8; KEY_SIZE] {
+        let path = settings_path(Self::KEY_FILE_NAME);
+
+        if let Some(key) = std::fs::read(&path).ok().and_then(|data| data.try_into().ok()) {
+            // Key files of older versions were created readable for everyone.
+            restrict_permissions(&path);
+            return key;
+        }
+
+        #[cfg(feature = "debug")]
+        print_debug!("creating new credential key in {}", Self::KEY_FILE_NAME.magenta());
+
+        let mut key = [0; KEY_SIZE];
+        DefaultRandomSource.fill_bytes(&mut key);
+
+        if let Err(_error) = write_private_file(&path, &key) {
+            #[cfg(feature = "debug")]
+            print_debug!(
+                "failed to save credential key to {}: {:?}",
+                Self::KEY_FILE_NAME.magenta(),
+                _error.red()
+            );
+        }
+
+        key
+    }
+
+    /// Load the stored passwords of the given services. Passwords that can't
+    /// be found or decrypted are skipped.
+    pub fn load_passwords(&self, service_ids: impl Iterator<Item = ServiceId>) -> HashMap<ServiceId, String> {
+        match &self.backend {
+            Backend::Keychain => service_ids
+                .filter_map(|service_id| {
+                    let password = keychain_entry(service_id)?.get_password().ok()?;
+                    Some((service_id, password))
+                })
+                .collect(),
+            Backend::EncryptedFile { cipher } => {
+                #[cfg(feature = "debug")]
+                print_debug!("loading credentials from {}", Self::FILE_NAME.magenta());
+
+                let mut encrypted_passwords = std::fs::read_to_string(settings_path(Self::FILE_NAME))
+                    .ok()
+                    .and_then(|data| ron::from_str::<HashMap<ServiceId, EncryptedPassword>>(&data).ok())
+                    .unwrap_or_default();
+
+                service_ids
+                    .filter_map(|service_id| {
+                        let encrypted_password = encrypted_passwords.remove(&service_id)?;
+                        Some((service_id, decrypt(cipher, service_id, &encrypted_password)?))
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Store the password of every service that is `Some` and forget the
+    /// password of every service that is `None`.
+    pub fn save_passwords<'a>(&self, passwords: impl Iterator<Item = (ServiceId, Option<&'a str>)>) {
+        match &self.backend {
+            Backend::Keychain => passwords.for_each(|(service_id, password)| {
+                let Some(entry) = keychain_entry(service_id) else {
+                    return;
+                };
+
+                let _result = match password {
+                    Some(password) => entry.set_password(password),
+                    None => entry.delete_credential().or_else(|error| match error {
+                        keyring::Error::NoEntry => Ok(()),
+                        error => Err(error),
+                    }),
+                };
+
+                #[cfg(feature = "debug")]
+                if let Err(error) = _result {
+                    print_debug!("[{}] failed to update password in the keychain: {:?}", "error".red(), error);
+                }
+            }),
+            Backend::EncryptedFile { cipher } => {
+                #[cfg(feature = "debug")]
+                print_debug!("saving credentials to {}", Self::FILE_NAME.magenta());
+
+                let encrypted_passwords: HashMap<ServiceId, EncryptedPassword> = passwords
+                    .filter_map(|(service_id, password)| Some((service_id, encrypt(cipher, service_id, password?))))
+                    .collect();
+                let data = ron::ser::to_string_pretty(&encrypted_passwords, PrettyConfig::new()).unwrap();
+
+                if let Err(_error) = write_private_file(&settings_path(Self::FILE_NAME), data.as_bytes()) {
+                    #[cfg(feature = "debug")]
+                    print_debug!(
+                        "failed to save credentials to {}: {:?}",
+                        Self::FILE_NAME.magenta(),
+                        _error.red()
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// The keychain is only used if it can actually be reached. On Linux for
+/// example there might be no secret service running. Probing can take a
+/// moment, so it only happens once per process.
+fn keychain_available() -> bool {
+    static KEYCHAIN_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+    *KEYCHAIN_AVAILABLE.get_or_init(|| {
+        keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_PROBE_USER)
+            .and_then(|entry| entry.get_password())
+            .map_or_else(|error| matches!(error, keyring::Error::NoEntry), |_| true)
+    })
+}
+
+fn keychain_entry(service_id: ServiceId) -> Option<keyring::Entry> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, &format!("service-{}", service_id.0)).ok()
+}
+
+/// The service id is used as associated data, so a password can't be moved
+/// to a different service in the file.
+fn encrypt(cipher: &XChaCha20Poly1305, service_id: ServiceId, password: &str) -> EncryptedPassword {
+    let mut nonce = [0; NONCE_SIZE];
+    DefaultRandomSource.fill_bytes(&mut nonce);
+
+    let payload = Payload {
+        msg: password.as_bytes(),
+        aad: &service_id.0.to_le_bytes(),
+    };
+    // Encrypting only fails for messages that are many gigabytes long.
+    let ciphertext = cipher.encrypt(XNonce::from_slice(&nonce), payload).unwrap();
+
+    EncryptedPassword { nonce, ciphertext }
+}
+
+fn decrypt(cipher: &XChaCha20Poly1305, service_id: ServiceId, encrypted_password: &EncryptedPassword) -> Option<String> {
+    let payload = Payload {
+        msg: &encrypted_password.ciphertext,
+        aad: &service_id.0.to_le_bytes(),
+    };
+    let password = cipher.decrypt(XNonce::from_slice(&encrypted_password.nonce), payload).ok()?;
+
+    String::from_utf8(password).ok()
+}
+
+/// Write a file that only the current user can read. On Windows the keychain
+/// is always available, so this is only used on Unix in practice.
+fn write_private_file(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    options.open(path)?.write_all(data)?;
+
+    // The mode is only applied when the file is created.
+    restrict_permissions(path);
+
+    Ok(())
+}
+
+fn restrict_permissions(_path: &Path) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let _ = std::fs::set_permissions(_path, std::fs::Permissions::from_mode(0o600));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chacha20poly1305::XChaCha20Poly1305;
+    use chacha20poly1305::aead::KeyInit;
+
+    use super::{decrypt, encrypt};
+    use crate::loaders::ServiceId;
+
+    fn cipher(key: u8) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(&[key; 32].into())
+    }
+
+    #[test]
+    fn password_round_trip() {
+        let encrypted_password = encrypt(&cipher(7), ServiceId(1), "hunter2");
+
+        assert_ne!(encrypted_password.ciphertext, b"hunter2");
+        assert_eq!(
+            decrypt(&cipher(7), ServiceId(1), &encrypted_password).as_deref(),
+            Some("hunter2")
+        );
+    }
+
+    #[test]
+    fn tampered_password_is_rejected() {
+        let mut encrypted_password = encrypt(&cipher(7), ServiceId(1), "hunter2");
+        encrypted_password.ciphertext[0] ^= 1;
+
+        assert_eq!(decrypt(&cipher(7), ServiceId(1), &encrypted_password), None);
+
+        let encrypted_password = encrypt(&cipher(7), ServiceId(1), "hunter2");
+
+        assert_eq!(decrypt(&cipher(8), ServiceId(1), &encrypted_password), None);
+        assert_eq!(decrypt(&cipher(7), ServiceId(2), &encrypted_password), None);
+    }
+}
@@ -1,23 +1,358 @@
+use std::time::Duration;
+
 #[cfg(feature = "debug")]
 use korangar_debug::logging::{Colorize, print_debug};
+use korangar_interface::components::drop_down::DropDownItem;
 use korangar_interface::element::StateElement;
+use korangar_networking::RateLimitedAction;
+use ragnarok_packets::ItemId;
 use ron::ser::PrettyConfig;
 use rust_state::RustState;
 use serde::{Deserialize, Serialize};
 
+use super::settings_path;
+use crate::interface::toast::ToastCategory;
+use crate::inventory::GearSet;
+use crate::world::CombatFeedbackKind;
+
+/// Number of chat macros on the emote wheel.
+pub const CHAT_MACRO_COUNT: usize = 4;
+
+/// Speed at which the text of NPC dialogs is revealed.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, RustState, StateElement)]
+pub enum DialogTextSpeed {
+    Slow,
+    Normal,
+    #[default]
+    Fast,
+    Instant,
+}
+
+impl DialogTextSpeed {
+    /// Number of characters revealed per second, or `None` if the text
+    /// should be shown right away.
+    pub fn characters_per_second(self) -> Option<f64> {
+        match self {
+            DialogTextSpeed::Slow => Some(30.0),
+            DialogTextSpeed::Normal => Some(60.0),
+            DialogTextSpeed::Fast => Some(120.0),
+            DialogTextSpeed::Instant => None,
+        }
+    }
+}
+
+impl DropDownItem<DialogTextSpeed> for DialogTextSpeed {
+    fn text(&self) -> &str {
+        match self {
+            DialogTextSpeed::Slow => "Slow",
+            DialogTextSpeed::Normal => "Normal",
+            DialogTextSpeed::Fast => "Fast",
+            DialogTextSpeed::Instant => "Instant",
+        }
+    }
+
+    fn value(&self) -> DialogTextSpeed {
+        *self
+    }
+}
+
+/// How long damage numbers and repeated chat messages are collected before
+/// they are combined and shown.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, RustState, StateElement)]
+pub enum PopupAggregation {
+    Off,
+    #[default]
+    Short,
+    Normal,
+    Long,
+}
+
+impl PopupAggregation {
+    /// Length of the aggregation window in seconds, or `None` if every popup
+    /// should be shown right away.
+    pub fn window(self) -> Option<f32> {
+        match self {
+            PopupAggregation::Off => None,
+            PopupAggregation::Short => Some(0.2),
+            PopupAggregation::Normal => Some(0.5),
+            PopupAggregation::Long => Some(1.0),
+        }
+    }
+}
+
+impl DropDownItem<PopupAggregation> for PopupAggregation {
+    fn text(&self) -> &str {
+        match self {
+            PopupAggregation::Off => "Off",
+            PopupAggregation::Short => "200 ms",
+            PopupAggregation::Normal => "500 ms",
+            PopupAggregation::Long => "1 s",
+        }
+    }
+
+    fn value(&self) -> PopupAggregation {
+        *self
+    }
+}
+
+/// Minimum time between two requests of the same kind that are sent to the
+/// map server. The limit can't be turned off, only shortened.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, RustState, StateElement)]
+pub enum ActionInterval {
+    // Older settings files could turn the limit off entirely.
+    #[serde(alias = "Off")]
+    Milliseconds50,
+    Milliseconds100,
+    Milliseconds250,
+    Milliseconds500,
+    Milliseconds1000,
+}
+
+impl ActionInterval {
+    const OPTIONS: [Self; 5] = [
+        Self::Milliseconds50,
+        Self::Milliseconds100,
+        Self::Milliseconds250,
+        Self::Milliseconds500,
+        Self::Milliseconds1000,
+    ];
+
+    /// The shortest option that is at least as long as the given duration.
+    fn at_least(duration: Duration) -> Self {
+        Self::OPTIONS
+            .into_iter()
+            .find(|option| option.duration() >= duration)
+            .unwrap_or(Self::Milliseconds1000)
+    }
+
+    pub fn duration(self) -> Duration {
+        match self {
+            ActionInterval::Milliseconds50 => Duration::from_millis(50),
+            ActionInterval::Milliseconds100 => Duration::from_millis(100),
+            ActionInterval::Milliseconds250 => Duration::from_millis(250),
+            ActionInterval::Milliseconds500 => Duration::from_millis(500),
+            ActionInterval::Milliseconds1000 => Duration::from_millis(1000),
+        }
+    }
+}
+
+impl DropDownItem<ActionInterval> for ActionInterval {
+    fn text(&self) -> &str {
+        match self {
+            ActionInterval::Milliseconds50 => "50 ms",
+            ActionInterval::Milliseconds100 => "100 ms",
+            ActionInterval::Milliseconds250 => "250 ms",
+            ActionInterval::Milliseconds500 => "500 ms",
+            ActionInterval::Milliseconds1000 => "1 s",
+        }
+    }
+
+    fn value(&self) -> ActionInterval {
+        *self
+    }
+}
+
+/// Minimum intervals of the actions that are rate limited by the networking
+/// system. Servers with a stricter flood protection might need longer
+/// intervals than the defaults.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, RustState, StateElement)]
+pub struct ActionIntervals {
+    pub move_interval: ActionInterval,
+    pub attack_interval: ActionInterval,
+    pub chat_message_interval: ActionInterval,
+    pub skill_interval: ActionInterval,
+}
+
+impl Default for ActionIntervals {
+    fn default() -> Self {
+        let interval = |action: RateLimitedAction| ActionInterval::at_least(action.default_minimum_interval());
+
+        Self {
+            move_interval: interval(RateLimitedAction::Move),
+            attack_interval: interval(RateLimitedAction::Attack),
+            chat_message_interval: interval(RateLimitedAction::ChatMessage),
+            skill_interval: interval(RateLimitedAction::Skill),
+        }
+    }
+}
+
+impl ActionIntervals {
+    /// The minimum interval of every rate limited action.
+    pub fn intervals(&self) -> [(RateLimitedAction, Duration); 4] {
+        [
+            (RateLimitedAction::Move, self.move_interval.duration()),
+            (RateLimitedAction::Attack, self.attack_interval.duration()),
+            (RateLimitedAction::ChatMessage, self.chat_message_interval.duration()),
+            (RateLimitedAction::Skill, self.skill_interval.duration()),
+        ]
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, RustState, StateElement)]
 pub struct GameSettings {
     pub auto_attack: bool,
+    #[serde(default)]
+    pub dialog_text_speed: DialogTextSpeed,
+    #[serde(default)]
+    pub popup_aggregation: PopupAggregation,
+    #[serde(default = "GameSettings::default_toasts")]
+    pub friend_login_toasts: bool,
+    #[serde(default = "GameSettings::default_toasts")]
+    pub rare_drop_toasts: bool,
+    #[serde(default = "GameSettings::default_toasts")]
+    pub achievement_toasts: bool,
+    #[serde(default = "GameSettings::default_toasts")]
+    pub system_warning_toasts: bool,
+    /// Show the names of party members on the minimap and next to the
+    /// indicators of members that are off-screen.
+    #[serde(default = "GameSettings::default_party_member_names")]
+    pub party_member_names: bool,
+    #[serde(default = "GameSettings::default_combat_feedback")]
+    pub screen_shake: bool,
+    #[serde(default = "GameSettings::default_combat_feedback")]
+    pub hit_stop: bool,
+    #[serde(default = "GameSettings::default_combat_feedback")]
+    pub hit_flash: bool,
+    /// Turn off all combat feedback, regardless of the individual settings.
+    /// Meant for players that are sensitive to motion or flashing.
+    #[serde(default)]
+    pub disable_combat_feedback: bool,
+    #[serde(default)]
+    pub action_intervals: ActionIntervals,
+    /// Ids of the items that are protected from being sold by accident. All
+    /// items with the same id are locked together.
+    #[serde(default)]
+    #[hidden_element]
+    pub locked_items: Vec<u32>,
+    /// Names of the players whose messages are hidden.
+    #[serde(default)]
+    #[hidden_element]
+    pub muted_players: Vec<String>,
+    /// Patterns of public chat messages that are hidden.
+    #[serde(default)]
+    #[hidden_element]
+    pub chat_filters: Vec<String>,
+    /// Gear sets saved in the equipment window.
+    #[serde(default)]
+    #[hidden_element]
+    pub gear_sets: Vec<GearSet>,
+    /// Chat messages that can be sent from the emote wheel. Always contains
+    /// [`CHAT_MACRO_COUNT`] entries, empty ones are not shown on the wheel.
+    #[serde(default = "GameSettings::default_chat_macros")]
+    #[hidden_element]
+    pub chat_macros: Vec<String>,
 }
 
 impl Default for GameSettings {
     fn default() -> Self {
-        Self { auto_attack: true }
+        Self {
+            auto_attack: true,
+            dialog_text_speed: DialogTextSpeed::default(),
+            popup_aggregation: PopupAggregation::default(),
+            friend_login_toasts: Self::default_toasts(),
+            rare_drop_toasts: Self::default_toasts(),
+            achievement_toasts: Self::default_toasts(),
+            system_warning_toasts: Self::default_toasts(),
+            party_member_names: Self::default_party_member_names(),
+            screen_shake: Self::default_combat_feedback(),
+            hit_stop: Self::default_combat_feedback(),
+            hit_flash: Self::default_combat_feedback(),
+            disable_combat_feedback: false,
+            action_intervals: ActionIntervals::default(),
+            locked_items: Vec::new(),
+            muted_players: Vec::new(),
+            chat_filters: Vec::new(),
+            gear_sets: Vec::new(),
+            chat_macros: Self::default_chat_macros(),
+        }
     }
 }
 
 impl GameSettings {
-    const FILE_NAME: &'static str = "client/game_settings.ron";
+    pub(super) const FILE_NAME: &'static str = "client/game_settings.ron";
+
+    fn default_toasts() -> bool {
+        true
+    }
+
+    fn default_party_member_names() -> bool {
+        true
+    }
+
+    fn default_combat_feedback() -> bool {
+        true
+    }
+
+    fn default_chat_macros() -> Vec<String> {
+        vec![String::new(); CHAT_MACRO_COUNT]
+    }
+
+    pub fn shows_toast(&self, category: ToastCategory) -> bool {
+        match category {
+            ToastCategory::FriendLogin => self.friend_login_toasts,
+            ToastCategory::RareDrop => self.rare_drop_toasts,
+            ToastCategory::Achievement => self.achievement_toasts,
+            ToastCategory::SystemWarning => self.system_warning_toasts,
+        }
+    }
+
+    pub fn shows_combat_feedback(&self, kind: CombatFeedbackKind) -> bool {
+        if self.disable_combat_feedback {
+            return false;
+        }
+
+        match kind {
+            CombatFeedbackKind::ScreenShake => self.screen_shake,
+            CombatFeedbackKind::HitStop => self.hit_stop,
+            CombatFeedbackKind::HitFlash => self.hit_flash,
+        }
+    }
+
+    pub fn is_item_locked(&self, item_id: ItemId) -> bool {
+        self.locked_items.contains(&item_id.0)
+    }
+
+    pub fn toggle_item_lock(&mut self, item_id: ItemId) {
+        match self.locked_items.iter().position(|locked_item| *locked_item == item_id.0) {
+            Some(index) => {
+                self.locked_items.remove(index);
+            }
+            None => self.locked_items.push(item_id.0),
+        }
+    }
+
+    pub fn is_player_muted(&self, name: &str) -> bool {
+        self.muted_players.iter().any(|muted_player| muted_player == name)
+    }
+
+    /// Mute or unmute a player. Returns `true` if the player is muted
+    /// afterwards.
+    pub fn toggle_player_mute(&mut self, name: &str) -> bool {
+        match self.muted_players.iter().position(|muted_player| muted_player == name) {
+            Some(index) => {
+                self.muted_players.remove(index);
+                false
+            }
+            None => {
+                self.muted_players.push(name.to_owned());
+                true
+            }
+        }
+    }
+
+    /// Save a gear set, replacing any gear set with the same name.
+    pub fn save_gear_set(&mut self, gear_set: GearSet) {
+        match self.gear_sets.iter_mut().find(|saved| saved.name == gear_set.name) {
+            Some(saved) => *saved = gear_set,
+            None => self.gear_sets.push(gear_set),
+        }
+    }
+
+    pub fn remove_gear_set(&mut self, index: usize) {
+        if index < self.gear_sets.len() {
+            self.gear_sets.remove(index);
+        }
+    }
 
     pub fn new() -> Self {
         Self::load().unwrap_or_else(|| {
@@ -30,9 +365,13 @@ impl GameSettings {
     pub fn load() -> Option<Self> {
         #[cfg(feature = "debug")]
         print_debug!("loading game settings from {}", Self::FILE_NAME.magenta());
-        std::fs::read_to_string(Self::FILE_NAME)
+        std::fs::read_to_string(settings_path(Self::FILE_NAME))
             .ok()
-            .and_then(|data| ron::from_str(&data).ok())
+            .and_then(|data| ron::from_str::<Self>(&data).ok())
+            .map(|mut settings| {
+                settings.chat_macros.resize(CHAT_MACRO_COUNT, String::new());
+                settings
+            })
     }
 
     pub fn save(&self) {
@@ -41,7 +380,7 @@ impl GameSettings {
 
         let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
 
-        if let Err(_error) = std::fs::write(Self::FILE_NAME, data) {
+        if let Err(_error) = std::fs::write(settings_path(Self::FILE_NAME), data) {
             #[cfg(feature = "debug")]
             print_debug!(
                 "failed to save game settings to {}: {:?}",
@@ -57,3 +396,30 @@ impl Drop for GameSettings {
         self.save();
     }
 }
+
+#[derive(RustState, StateElement)]
+pub struct GameSettingsCapabilities {
+    dialog_text_speeds: Vec<DialogTextSpeed>,
+    popup_aggregations: Vec<PopupAggregation>,
+    action_intervals: Vec<ActionInterval>,
+}
+
+impl Default for GameSettingsCapabilities {
+    fn default() -> Self {
+        Self {
+            dialog_text_speeds: vec![
+                DialogTextSpeed::Slow,
+                DialogTextSpeed::Normal,
+                DialogTextSpeed::Fast,
+                DialogTextSpeed::Instant,
+            ],
+            popup_aggregations: vec![
+                PopupAggregation::Off,
+                PopupAggregation::Short,
+                PopupAggregation::Normal,
+                PopupAggregation::Long,
+            ],
+            action_intervals: ActionInterval::OPTIONS.to_vec(),
+        }
+    }
+}
@@ -6,23 +6,72 @@ use ron::ser::PrettyConfig;
 use rust_state::RustState;
 use serde::{Deserialize, Serialize};
 
+use super::settings_path;
 use crate::graphics::{
-    LimitFramerate, Msaa, PresentModeInfo, ScreenSpaceAntiAliasing, ShadowDetail, ShadowQuality, Ssaa, TextureSamplerType,
+    DisplayMode, GraphicsPreset, LightMapMode, LimitFramerate, MonitorOption, Msaa, PresentModeInfo, ScreenSpaceAntiAliasing, ShadowDetail,
+    ShadowQuality, SpriteDepthBiasSettings, SpriteFiltering, SpriteUpscaling, Ssaa, TextureSamplerType, VideoMode, VideoModeOption,
+    ViewRangeDimming, WaterQuality,
 };
 
-#[derive(Clone, Serialize, Deserialize, RustState, StateElement)]
+#[derive(Clone, PartialEq, Serialize, Deserialize, RustState, StateElement)]
 pub struct GraphicsSettings {
     pub lighting_mode: LightingMode,
     pub vsync: bool,
     pub limit_framerate: LimitFramerate,
     pub triple_buffering: bool,
     pub texture_filtering: TextureSamplerType,
+    #[serde(default)]
+    pub sprite_filtering: SpriteFiltering,
+    #[serde(default)]
+    pub sprite_upscaling: SpriteUpscaling,
     pub msaa: Msaa,
     pub ssaa: Ssaa,
     pub screen_space_anti_aliasing: ScreenSpaceAntiAliasing,
     pub shadow_detail: ShadowDetail,
     pub shadow_quality: ShadowQuality,
     pub high_quality_interface: bool,
+    #[serde(default)]
+    pub crt_filter: bool,
+    /// Blend overlapping alpha blended effects independent of the order they
+    /// are drawn in.
+    #[serde(default = "GraphicsSettings::default_order_independent_effects")]
+    pub order_independent_effects: bool,
+    #[serde(default)]
+    pub display_mode: DisplayMode,
+    /// Name of the monitor used in fullscreen. `None` uses the monitor the
+    /// window is currently on.
+    #[serde(default)]
+    pub monitor: Option<String>,
+    /// Video mode used in exclusive fullscreen. `None` uses the native video
+    /// mode of the monitor.
+    #[serde(default)]
+    pub video_mode: Option<VideoMode>,
+    #[serde(default)]
+    pub entity_shadows: EntityShadows,
+    #[serde(default)]
+    pub entity_draw_distance: EntityDrawDistance,
+    #[serde(default)]
+    pub entity_limit: EntityLimit,
+    #[serde(default)]
+    pub water_quality: WaterQuality,
+    #[serde(default)]
+    pub light_map_mode: LightMapMode,
+    #[serde(default)]
+    pub view_range_dimming: ViewRangeDimming,
+    /// Move the window of additional client instances, so they don't open
+    /// exactly on top of the first one.
+    #[serde(default = "GraphicsSettings::default_offset_additional_instances")]
+    pub offset_additional_instances: bool,
+    /// Hide ceilings and upper floors of indoor maps.
+    #[serde(default = "GraphicsSettings::default_vertical_culling")]
+    pub vertical_culling: bool,
+    /// Load the maps behind the warp portals of the current map in the
+    /// background.
+    #[serde(default = "GraphicsSettings::default_preload_adjacent_maps")]
+    pub preload_adjacent_maps: bool,
+    /// Depth offsets of the sprite billboards for every camera mode.
+    #[serde(default)]
+    pub sprite_depth_bias: SpriteDepthBiasSettings,
 }
 
 impl Default for GraphicsSettings {
@@ -33,18 +82,51 @@ impl Default for GraphicsSettings {
             limit_framerate: LimitFramerate::Unlimited,
             triple_buffering: true,
             texture_filtering: TextureSamplerType::Anisotropic(4),
+            sprite_filtering: SpriteFiltering::default(),
+            sprite_upscaling: SpriteUpscaling::default(),
             msaa: Msaa::X4,
             ssaa: Ssaa::Off,
             screen_space_anti_aliasing: ScreenSpaceAntiAliasing::Off,
             shadow_detail: ShadowDetail::Normal,
             shadow_quality: ShadowQuality::SoftPCSSx16,
             high_quality_interface: true,
+            crt_filter: false,
+            order_independent_effects: Self::default_order_independent_effects(),
+            display_mode: DisplayMode::Windowed,
+            monitor: None,
+            video_mode: None,
+            entity_shadows: EntityShadows::default(),
+            entity_draw_distance: EntityDrawDistance::default(),
+            entity_limit: EntityLimit::default(),
+            water_quality: WaterQuality::default(),
+            light_map_mode: LightMapMode::default(),
+            view_range_dimming: ViewRangeDimming::default(),
+            offset_additional_instances: Self::default_offset_additional_instances(),
+            vertical_culling: Self::default_vertical_culling(),
+            preload_adjacent_maps: Self::default_preload_adjacent_maps(),
+            sprite_depth_bias: SpriteDepthBiasSettings::default(),
         }
     }
 }
 
 impl GraphicsSettings {
-    const FILE_NAME: &'static str = "client/graphics_settings.ron";
+    pub(super) const FILE_NAME: &'static str = "client/graphics_settings.ron";
+
+    fn default_offset_additional_instances() -> bool {
+        true
+    }
+
+    fn default_vertical_culling() -> bool {
+        true
+    }
+
+    fn default_preload_adjacent_maps() -> bool {
+        true
+    }
+
+    fn default_order_independent_effects() -> bool {
+        true
+    }
 
     pub fn new() -> Self {
         Self::load().unwrap_or_else(|| {
@@ -55,13 +137,25 @@ impl GraphicsSettings {
         })
     }
 
+    /// Returns `true` if the settings were saved before. If not, this is the
+    /// first time the client is started.
+    pub fn exists() -> bool {
+        settings_path(Self::FILE_NAME).exists()
+    }
+
     pub fn load() -> Option<Self> {
         #[cfg(feature = "debug")]
         print_debug!("loading graphics settings from {}", Self::FILE_NAME.magenta());
 
-        std::fs::read_to_string(Self::FILE_NAME)
+        std::fs::read_to_string(settings_path(Self::FILE_NAME))
             .ok()
-            .and_then(|data| ron::from_str(&data).ok())
+            .and_then(|data| ron::from_str::<Self>(&data).ok())
+            .map(|mut settings| {
+                // Hand edited files might contain values that are not in the drop downs.
+                settings.entity_draw_distance = settings.entity_draw_distance.nearest_option();
+                settings.entity_limit = settings.entity_limit.nearest_option();
+                settings
+            })
     }
 
     pub fn save(&self) {
@@ -70,7 +164,7 @@ impl GraphicsSettings {
 
         let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
 
-        if let Err(_error) = std::fs::write(Self::FILE_NAME, data) {
+        if let Err(_error) = std::fs::write(settings_path(Self::FILE_NAME), data) {
             #[cfg(feature = "debug")]
             print_debug!(
                 "failed to save graphics settings to {}: {:?}",
@@ -79,6 +173,140 @@ impl GraphicsSettings {
             );
         }
     }
+
+    /// Copy all values of `other` into these settings. Unlike assigning a
+    /// clone, this doesn't drop the current settings, so they are not saved.
+    pub fn assign(&mut self, other: &Self) {
+        // Destructuring makes sure that new settings are not forgotten here.
+        let Self {
+            lighting_mode,
+            vsync,
+            limit_framerate,
+            triple_buffering,
+            texture_filtering,
+            sprite_filtering,
+            sprite_upscaling,
+            msaa,
+            ssaa,
+            screen_space_anti_aliasing,
+            shadow_detail,
+            shadow_quality,
+            high_quality_interface,
+            crt_filter,
+            order_independent_effects,
+            display_mode,
+            monitor,
+            video_mode,
+            entity_shadows,
+            entity_draw_distance,
+            entity_limit,
+            water_quality,
+            light_map_mode,
+            view_range_dimming,
+            offset_additional_instances,
+            vertical_culling,
+            preload_adjacent_maps,
+            sprite_depth_bias,
+        } = other;
+
+        self.lighting_mode = *lighting_mode;
+        self.vsync = *vsync;
+        self.limit_framerate = *limit_framerate;
+        self.triple_buffering = *triple_buffering;
+        self.texture_filtering = *texture_filtering;
+        self.sprite_filtering = *sprite_filtering;
+        self.sprite_upscaling = *sprite_upscaling;
+        self.msaa = *msaa;
+        self.ssaa = *ssaa;
+        self.screen_space_anti_aliasing = *screen_space_anti_aliasing;
+        self.shadow_detail = *shadow_detail;
+        self.shadow_quality = *shadow_quality;
+        self.high_quality_interface = *high_quality_interface;
+        self.crt_filter = *crt_filter;
+        self.order_independent_effects = *order_independent_effects;
+        self.display_mode = *display_mode;
+        self.monitor = monitor.clone();
+        self.video_mode = *video_mode;
+        self.entity_shadows = *entity_shadows;
+        self.entity_draw_distance = *entity_draw_distance;
+        self.entity_limit = *entity_limit;
+        self.water_quality = *water_quality;
+        self.light_map_mode = *light_map_mode;
+        self.view_range_dimming = *view_range_dimming;
+        self.offset_additional_instances = *offset_additional_instances;
+        self.vertical_culling = *vertical_culling;
+        self.preload_adjacent_maps = *preload_adjacent_maps;
+        self.sprite_depth_bias = *sprite_depth_bias;
+    }
+
+    /// Change all settings that affect the performance to the values of the
+    /// preset. Settings that only change the look of the game are kept.
+    pub fn apply_preset(&mut self, preset: GraphicsPreset, supported_msaa: &[Msaa]) {
+        let msaa = match preset {
+            GraphicsPreset::Low => Msaa::Off,
+            GraphicsPreset::Medium => Msaa::X2,
+            GraphicsPreset::High => Msaa::X4,
+            GraphicsPreset::Ultra => Msaa::X8,
+        };
+
+        // Not every adapter supports every sample count, so we fall back to the highest
+        // supported one below it.
+        self.msaa = supported_msaa
+            .iter()
+            .copied()
+            .filter(|supported| supported.sample_count() <= msaa.sample_count())
+            .max_by_key(|supported| supported.sample_count())
+            .unwrap_or(Msaa::Off);
+
+        self.ssaa = Ssaa::Off;
+
+        match preset {
+            GraphicsPreset::Low => {
+                self.texture_filtering = TextureSamplerType::Linear;
+                self.screen_space_anti_aliasing = ScreenSpaceAntiAliasing::Fxaa;
+                self.shadow_detail = ShadowDetail::Normal;
+                self.shadow_quality = ShadowQuality::Hard;
+                self.entity_shadows = EntityShadows::Blob;
+                self.entity_limit = EntityLimit::Limit(100);
+                self.water_quality = WaterQuality::Low;
+                self.sprite_upscaling = SpriteUpscaling::Off;
+                self.preload_adjacent_maps = false;
+            }
+            GraphicsPreset::Medium => {
+                self.texture_filtering = TextureSamplerType::Anisotropic(4);
+                self.screen_space_anti_aliasing = ScreenSpaceAntiAliasing::Off;
+                self.shadow_detail = ShadowDetail::Normal;
+                self.shadow_quality = ShadowQuality::SoftPCF;
+                self.entity_shadows = EntityShadows::Projected;
+                self.entity_limit = EntityLimit::Unlimited;
+                self.water_quality = WaterQuality::Medium;
+                self.sprite_upscaling = SpriteUpscaling::Off;
+                self.preload_adjacent_maps = true;
+            }
+            GraphicsPreset::High => {
+                self.texture_filtering = TextureSamplerType::Anisotropic(8);
+                self.screen_space_anti_aliasing = ScreenSpaceAntiAliasing::Off;
+                self.shadow_detail = ShadowDetail::Ultra;
+                self.shadow_quality = ShadowQuality::SoftPCSSx16;
+                self.entity_shadows = EntityShadows::Projected;
+                self.entity_limit = EntityLimit::Unlimited;
+                self.water_quality = WaterQuality::High;
+                self.sprite_upscaling = SpriteUpscaling::Off;
+                self.preload_adjacent_maps = true;
+            }
+            GraphicsPreset::Ultra => {
+                self.texture_filtering = TextureSamplerType::Anisotropic(16);
+                self.screen_space_anti_aliasing = ScreenSpaceAntiAliasing::Off;
+                self.shadow_detail = ShadowDetail::Insane;
+                self.shadow_quality = ShadowQuality::SoftPCSSx32;
+                self.entity_shadows = EntityShadows::Projected;
+                self.entity_limit = EntityLimit::Unlimited;
+                self.water_quality = WaterQuality::High;
+                self.sprite_upscaling = SpriteUpscaling::Scale2x;
+                self.preload_adjacent_maps = true;
+            }
+        }
+    }
 }
 
 impl Drop for GraphicsSettings {
@@ -109,16 +337,156 @@ impl DropDownItem<LightingMode> for LightingMode {
     }
 }
 
+/// How the shadows of entities are rendered.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize, StateElement)]
+pub enum EntityShadows {
+    /// Circular shadow on the ground below the entity, like in the original
+    /// client.
+    Blob,
+    /// Entities cast shadows into the shadow maps.
+    #[default]
+    Projected,
+}
+
+impl DropDownItem<EntityShadows> for EntityShadows {
+    fn text(&self) -> &str {
+        match self {
+            EntityShadows::Blob => "Blob",
+            EntityShadows::Projected => "Projected",
+        }
+    }
+
+    fn value(&self) -> EntityShadows {
+        *self
+    }
+}
+
+/// Maximum distance in tiles from the player at which other entities are
+/// drawn.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize, StateElement)]
+pub enum EntityDrawDistance {
+    Tiles(u16),
+    #[default]
+    Unlimited,
+}
+
+impl EntityDrawDistance {
+    const OPTIONS: [Self; 4] = [Self::Tiles(8), Self::Tiles(11), Self::Tiles(14), Self::Unlimited];
+
+    pub fn tiles(self) -> Option<usize> {
+        match self {
+            EntityDrawDistance::Tiles(tiles) => Some(tiles as usize),
+            EntityDrawDistance::Unlimited => None,
+        }
+    }
+
+    /// The option from the drop down that is closest to this distance.
+    fn nearest_option(self) -> Self {
+        match self {
+            EntityDrawDistance::Tiles(tiles) => Self::OPTIONS
+                .into_iter()
+                .filter_map(|option| option.tiles().map(|option_tiles| (option, option_tiles)))
+                .min_by_key(|(_, option_tiles)| option_tiles.abs_diff(tiles as usize))
+                .map(|(option, _)| option)
+                .unwrap_or_default(),
+            EntityDrawDistance::Unlimited => self,
+        }
+    }
+}
+
+impl DropDownItem<EntityDrawDistance> for EntityDrawDistance {
+    fn text(&self) -> &str {
+        match self {
+            EntityDrawDistance::Tiles(8) => "8 tiles",
+            EntityDrawDistance::Tiles(11) => "11 tiles",
+            EntityDrawDistance::Tiles(14) => "14 tiles",
+            EntityDrawDistance::Tiles(_) => "Custom",
+            EntityDrawDistance::Unlimited => "Unlimited",
+        }
+    }
+
+    fn value(&self) -> EntityDrawDistance {
+        *self
+    }
+}
+
+/// Maximum number of other entities that are drawn at the same time. The
+/// entities closest to the player are drawn first.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize, StateElement)]
+pub enum EntityLimit {
+    Limit(u16),
+    #[default]
+    Unlimited,
+}
+
+impl EntityLimit {
+    const OPTIONS: [Self; 5] = [
+        Self::Limit(25),
+        Self::Limit(50),
+        Self::Limit(100),
+        Self::Limit(200),
+        Self::Unlimited,
+    ];
+
+    pub fn count(self) -> Option<usize> {
+        match self {
+            EntityLimit::Limit(count) => Some(count as usize),
+            EntityLimit::Unlimited => None,
+        }
+    }
+
+    /// The option from the drop down that is closest to this limit.
+    fn nearest_option(self) -> Self {
+        match self {
+            EntityLimit::Limit(count) => Self::OPTIONS
+                .into_iter()
+                .filter_map(|option| option.count().map(|option_count| (option, option_count)))
+                .min_by_key(|(_, option_count)| option_count.abs_diff(count as usize))
+                .map(|(option, _)| option)
+                .unwrap_or_default(),
+            EntityLimit::Unlimited => self,
+        }
+    }
+}
+
+impl DropDownItem<EntityLimit> for EntityLimit {
+    fn text(&self) -> &str {
+        match self {
+            EntityLimit::Limit(25) => "25",
+            EntityLimit::Limit(50) => "50",
+            EntityLimit::Limit(100) => "100",
+            EntityLimit::Limit(200) => "200",
+            EntityLimit::Limit(_) => "Custom",
+            EntityLimit::Unlimited => "Unlimited",
+        }
+    }
+
+    fn value(&self) -> EntityLimit {
+        *self
+    }
+}
+
 #[derive(RustState, StateElement)]
 pub struct GraphicsSettingsCapabilities {
     lighting_modes: Vec<LightingMode>,
+    light_map_modes: Vec<LightMapMode>,
     texture_filtering_options: Vec<TextureSamplerType>,
+    sprite_filtering_options: Vec<SpriteFiltering>,
+    sprite_upscaling_options: Vec<SpriteUpscaling>,
     limit_framerate_options: Vec<LimitFramerate>,
     supported_msaa: Vec<Msaa>,
     ssaa_options: Vec<Ssaa>,
     screen_space_anti_aliasing_options: Vec<ScreenSpaceAntiAliasing>,
     shadow_quality_options: Vec<ShadowQuality>,
     shadow_detail_options: Vec<ShadowDetail>,
+    entity_shadow_options: Vec<EntityShadows>,
+    entity_draw_distance_options: Vec<EntityDrawDistance>,
+    entity_limit_options: Vec<EntityLimit>,
+    water_quality_options: Vec<WaterQuality>,
+    view_range_dimming_options: Vec<ViewRangeDimming>,
+    display_modes: Vec<DisplayMode>,
+    monitor_options: Vec<MonitorOption>,
+    video_mode_options: Vec<VideoModeOption>,
     vsync_setting_disabled: bool,
 }
 
@@ -126,6 +494,7 @@ impl Default for GraphicsSettingsCapabilities {
     fn default() -> Self {
         Self {
             lighting_modes: vec![LightingMode::Classic, LightingMode::Enhanced],
+            light_map_modes: vec![LightMapMode::Off, LightMapMode::Classic, LightMapMode::Smooth],
             texture_filtering_options: vec![
                 TextureSamplerType::Nearest,
                 TextureSamplerType::Linear,
@@ -133,6 +502,8 @@ impl Default for GraphicsSettingsCapabilities {
                 TextureSamplerType::Anisotropic(8),
                 TextureSamplerType::Anisotropic(16),
             ],
+            sprite_filtering_options: vec![SpriteFiltering::Nearest, SpriteFiltering::Linear],
+            sprite_upscaling_options: vec![SpriteUpscaling::Off, SpriteUpscaling::Scale2x, SpriteUpscaling::Scale4x],
             limit_framerate_options: vec![
                 LimitFramerate::Unlimited,
                 LimitFramerate::Limit(30),
@@ -153,6 +524,18 @@ impl Default for GraphicsSettingsCapabilities {
                 ShadowQuality::SoftPCSSx64,
             ],
             shadow_detail_options: vec![ShadowDetail::Normal, ShadowDetail::Ultra, ShadowDetail::Insane],
+            entity_shadow_options: vec![EntityShadows::Blob, EntityShadows::Projected],
+            entity_draw_distance_options: EntityDrawDistance::OPTIONS.to_vec(),
+            entity_limit_options: EntityLimit::OPTIONS.to_vec(),
+            water_quality_options: vec![WaterQuality::Low, WaterQuality::Medium, WaterQuality::High],
+            view_range_dimming_options: vec![ViewRangeDimming::Off, ViewRangeDimming::Subtle, ViewRangeDimming::Strong],
+            display_modes: vec![
+                DisplayMode::Windowed,
+                DisplayMode::BorderlessFullscreen,
+                DisplayMode::ExclusiveFullscreen,
+            ],
+            monitor_options: Vec::new(),
+            video_mode_options: Vec::new(),
             vsync_setting_disabled: true,
         }
     }
@@ -163,4 +546,9 @@ impl GraphicsSettingsCapabilities {
         self.supported_msaa = supported_msaa;
         self.vsync_setting_disabled = !present_mode_info.supports_mailbox && !present_mode_info.supports_immediate;
     }
+
+    pub fn update_display_options(&mut self, monitor_options: Vec<MonitorOption>, video_mode_options: Vec<VideoModeOption>) {
+        self.monitor_options = monitor_options;
+        self.video_mode_options = video_mode_options;
+    }
 }
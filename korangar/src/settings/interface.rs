@@ -1,11 +1,13 @@
 #[cfg(feature = "debug")]
 use korangar_debug::logging::{Colorize, print_debug};
+use korangar_interface::components::drop_down::DropDownItem;
 use korangar_interface::element::StateElement;
 use ron::ser::PrettyConfig;
 use rust_state::RustState;
 use serde::{Deserialize, Serialize};
 
-use crate::loaders::Scaling;
+use super::settings_path;
+use crate::loaders::{FontSize, Scaling};
 use crate::state::localization::Language;
 
 /// This theme name includes a zero byte so that it can not point to an actual
@@ -17,6 +19,71 @@ pub const MENU_THEMES_PATH: &str = "client/menu_themes";
 pub const IN_GAME_THEMES_PATH: &str = "client/in_game_themes";
 pub const WORLD_THEMES_PATH: &str = "client/world_themes";
 
+/// Kind of color blindness that the colors of health bars, damage numbers and
+/// quest markers are adjusted for.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, RustState, StateElement)]
+pub enum ColorblindMode {
+    #[default]
+    Off,
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl DropDownItem<ColorblindMode> for ColorblindMode {
+    fn text(&self) -> &str {
+        match self {
+            ColorblindMode::Off => "Off",
+            ColorblindMode::Protanopia => "Protanopia",
+            ColorblindMode::Deuteranopia => "Deuteranopia",
+            ColorblindMode::Tritanopia => "Tritanopia",
+        }
+    }
+
+    fn value(&self) -> ColorblindMode {
+        *self
+    }
+}
+
+/// Smallest font size used by the themes. Smaller font sizes of the theme are
+/// raised to it.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, RustState, StateElement)]
+pub enum MinimumFontSize {
+    #[default]
+    Off,
+    Medium,
+    Large,
+    ExtraLarge,
+}
+
+impl MinimumFontSize {
+    /// Minimum font size, or `None` if the font sizes of the theme should be
+    /// used as they are.
+    pub fn font_size(self) -> Option<FontSize> {
+        match self {
+            MinimumFontSize::Off => None,
+            MinimumFontSize::Medium => Some(FontSize(16.0)),
+            MinimumFontSize::Large => Some(FontSize(18.0)),
+            MinimumFontSize::ExtraLarge => Some(FontSize(22.0)),
+        }
+    }
+}
+
+impl DropDownItem<MinimumFontSize> for MinimumFontSize {
+    fn text(&self) -> &str {
+        match self {
+            MinimumFontSize::Off => "Off",
+            MinimumFontSize::Medium => "16",
+            MinimumFontSize::Large => "18",
+            MinimumFontSize::ExtraLarge => "22",
+        }
+    }
+
+    fn value(&self) -> MinimumFontSize {
+        *self
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, RustState, StateElement)]
 pub struct InterfaceSettings {
     pub language: Language,
@@ -24,6 +91,14 @@ pub struct InterfaceSettings {
     pub menu_theme: String,
     pub in_game_theme: String,
     pub world_theme: String,
+    #[serde(default)]
+    pub colorblind_mode: ColorblindMode,
+    /// Use a variant of the interface themes with stronger contrast between
+    /// text and background.
+    #[serde(default)]
+    pub high_contrast: bool,
+    #[serde(default)]
+    pub minimum_font_size: MinimumFontSize,
 }
 
 impl Default for InterfaceSettings {
@@ -34,12 +109,15 @@ impl Default for InterfaceSettings {
             menu_theme: DEFAULT_THEME_NAME.to_string(),
             in_game_theme: DEFAULT_THEME_NAME.to_string(),
             world_theme: DEFAULT_THEME_NAME.to_string(),
+            colorblind_mode: ColorblindMode::default(),
+            high_contrast: false,
+            minimum_font_size: MinimumFontSize::default(),
         }
     }
 }
 
 impl InterfaceSettings {
-    const FILE_NAME: &'static str = "client/interface_settings.ron";
+    pub(super) const FILE_NAME: &'static str = "client/interface_settings.ron";
 
     pub fn new() -> Self {
         Self::load().unwrap_or_else(|| {
@@ -54,7 +132,7 @@ impl InterfaceSettings {
         #[cfg(feature = "debug")]
         print_debug!("loading interface settings from {}", Self::FILE_NAME.magenta());
 
-        std::fs::read_to_string(Self::FILE_NAME)
+        std::fs::read_to_string(settings_path(Self::FILE_NAME))
             .ok()
             .and_then(|data| ron::from_str(&data).ok())
     }
@@ -65,7 +143,7 @@ impl InterfaceSettings {
 
         let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
 
-        if let Err(_error) = std::fs::write(Self::FILE_NAME, data) {
+        if let Err(_error) = std::fs::write(settings_path(Self::FILE_NAME), data) {
             #[cfg(feature = "debug")]
             print_debug!(
                 "failed to save interface settings to {}: {:?}",
@@ -89,6 +167,8 @@ pub struct InterfaceSettingsCapabilities {
     menu_themes: Vec<String>,
     in_game_themes: Vec<String>,
     world_themes: Vec<String>,
+    colorblind_modes: Vec<ColorblindMode>,
+    minimum_font_sizes: Vec<MinimumFontSize>,
 }
 
 impl InterfaceSettingsCapabilities {
@@ -136,6 +216,18 @@ impl Default for InterfaceSettingsCapabilities {
             menu_themes: Self::load_themes(MENU_THEMES_PATH),
             in_game_themes: Self::load_themes(IN_GAME_THEMES_PATH),
             world_themes: Self::load_themes(WORLD_THEMES_PATH),
+            colorblind_modes: vec![
+                ColorblindMode::Off,
+                ColorblindMode::Protanopia,
+                ColorblindMode::Deuteranopia,
+                ColorblindMode::Tritanopia,
+            ],
+            minimum_font_sizes: vec![
+                MinimumFontSize::Off,
+                MinimumFontSize::Medium,
+                MinimumFontSize::Large,
+                MinimumFontSize::ExtraLarge,
+            ],
         }
     }
 }
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "debug")]
+use korangar_debug::logging::{Colorize, print_debug};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use winit::keyboard::KeyCode;
+
+use super::settings_path;
+use crate::input::InputEvent;
+
+macro_rules! binding_keys {
+    ($($key:ident),* $(,)?) => {
+        /// Key that can be bound to an action.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+        pub enum BindingKey {
+            $($key,)*
+        }
+
+        impl BindingKey {
+            pub fn key_code(self) -> KeyCode {
+                match self {
+                    $(Self::$key => KeyCode::$key,)*
+                }
+            }
+        }
+    };
+}
+
+binding_keys!(
+    KeyA, KeyB, KeyC, KeyD, KeyE, KeyF, KeyG, KeyH, KeyI, KeyJ, KeyK, KeyL, KeyM, KeyN, KeyO, KeyP, KeyQ, KeyR, KeyS, KeyT, KeyU, KeyV,
+    KeyW, KeyX, KeyY, KeyZ, Digit0, Digit1, Digit2, Digit3, Digit4, Digit5, Digit6, Digit7, Digit8, Digit9, F1, F2, F3, F4, F5, F6, F7, F8,
+    F9, F10, F11, F12, Tab, Space, Enter, Insert, Delete, Home, End, PageUp, PageDown,
+);
+
+/// Key combination that triggers an action. The modifiers need to match
+/// exactly, so `ALT+S` and `S` can be bound to different actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBinding {
+    pub key: BindingKey,
+    #[serde(default)]
+    pub alt: bool,
+    #[serde(default)]
+    pub control: bool,
+}
+
+impl KeyBinding {
+    const fn new(key: BindingKey) -> Self {
+        Self {
+            key,
+            alt: false,
+            control: false,
+        }
+    }
+
+    const fn alt(key: BindingKey) -> Self {
+        Self {
+            key,
+            alt: true,
+            control: false,
+        }
+    }
+
+    const fn control(key: BindingKey) -> Self {
+        Self {
+            key,
+            alt: false,
+            control: true,
+        }
+    }
+}
+
+/// Action that can be triggered with a key binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KeyAction {
+    ToggleCharacterOverviewWindow,
+    ToggleInventoryWindow,
+    ToggleEquipmentWindow,
+    ToggleSkillTreeWindow,
+    ToggleStatsWindow,
+    ToggleFriendListWindow,
+    ToggleGameSettingsWindow,
+    ToggleInterfaceSettingsWindow,
+    ToggleGraphicsSettingsWindow,
+    ToggleAudioSettingsWindow,
+    ToggleMinimapWindow,
+    /// Held to show the emote wheel. The selected entry is used once the key
+    /// is released.
+    EmoteWheel,
+}
+
+impl KeyAction {
+    /// Default bindings, matching the shortcuts of the original client.
+    const DEFAULT_BINDINGS: [(Self, KeyBinding); 12] = [
+        (Self::ToggleCharacterOverviewWindow, KeyBinding::alt(BindingKey::KeyV)),
+        (Self::ToggleInventoryWindow, KeyBinding::alt(BindingKey::KeyE)),
+        (Self::ToggleEquipmentWindow, KeyBinding::alt(BindingKey::KeyQ)),
+        (Self::ToggleSkillTreeWindow, KeyBinding::alt(BindingKey::KeyS)),
+        (Self::ToggleStatsWindow, KeyBinding::alt(BindingKey::KeyA)),
+        (Self::ToggleFriendListWindow, KeyBinding::alt(BindingKey::KeyH)),
+        (Self::ToggleGameSettingsWindow, KeyBinding::control(BindingKey::KeyS)),
+        (Self::ToggleInterfaceSettingsWindow, KeyBinding::control(BindingKey::KeyI)),
+        (Self::ToggleGraphicsSettingsWindow, KeyBinding::control(BindingKey::KeyG)),
+        (Self::ToggleAudioSettingsWindow, KeyBinding::control(BindingKey::KeyA)),
+        (Self::ToggleMinimapWindow, KeyBinding::control(BindingKey::Tab)),
+        (Self::EmoteWheel, KeyBinding::new(BindingKey::KeyT)),
+    ];
+
+    pub fn input_event(self) -> InputEvent {
+        match self {
+            Self::ToggleCharacterOverviewWindow => InputEvent::ToggleCharacterOverviewWindow,
+            Self::ToggleInventoryWindow => InputEvent::ToggleInventoryWindow,
+            Self::ToggleEquipmentWindow => InputEvent::ToggleEquipmentWindow,
+            Self::ToggleSkillTreeWindow => InputEvent::ToggleSkillTreeWindow,
+            Self::ToggleStatsWindow => InputEvent::ToggleStatsWindow,
+            Self::ToggleFriendListWindow => InputEvent::ToggleFriendListWindow,
+            Self::ToggleGameSettingsWindow => InputEvent::ToggleGameSettingsWindow,
+            Self::ToggleInterfaceSettingsWindow => InputEvent::ToggleInterfaceSettingsWindow,
+            Self::ToggleGraphicsSettingsWindow => InputEvent::ToggleGraphicsSettingsWindow,
+            Self::ToggleAudioSettingsWindow => InputEvent::ToggleAudioSettingsWindow,
+            Self::ToggleMinimapWindow => InputEvent::ToggleMinimapWindow,
+            Self::EmoteWheel => InputEvent::OpenEmoteWheel,
+        }
+    }
+
+    /// Event for releasing the key, for actions that are held.
+    pub fn release_input_event(self) -> Option<InputEvent> {
+        match self {
+            Self::EmoteWheel => Some(InputEvent::CloseEmoteWheel),
+            _ => None,
+        }
+    }
+}
+
+/// Keyboard shortcuts of the client. The bindings can be changed by editing
+/// the settings file. Actions that are missing from the file use their
+/// default binding.
+#[derive(Serialize, Deserialize)]
+pub struct KeyBindings {
+    bindings: HashMap<KeyAction, KeyBinding>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            bindings: KeyAction::DEFAULT_BINDINGS.into_iter().collect(),
+        }
+    }
+}
+
+impl KeyBindings {
+    const FILE_NAME: &'static str = "client/key_bindings.ron";
+
+    pub fn new() -> Self {
+        Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load key bindings from {}", Self::FILE_NAME.magenta());
+            Default::default()
+        })
+    }
+
+    pub fn load() -> Option<Self> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading key bindings from {}", Self::FILE_NAME.magenta());
+
+        let mut key_bindings: Self = std::fs::read_to_string(settings_path(Self::FILE_NAME))
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())?;
+
+        for (action, binding) in KeyAction::DEFAULT_BINDINGS {
+            key_bindings.bindings.entry(action).or_insert(binding);
+        }
+
+        Some(key_bindings)
+    }
+
+    pub fn save(&self) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving key bindings to {}", Self::FILE_NAME.magenta());
+
+        let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
+
+        if let Err(_error) = std::fs::write(settings_path(Self::FILE_NAME), data) {
+            #[cfg(feature = "debug")]
+            print_debug!(
+                "failed to save key bindings to {}: {:?}",
+                Self::FILE_NAME.magenta(),
+                _error.red()
+            );
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (KeyAction, KeyBinding)> + '_ {
+        self.bindings.iter().map(|(action, binding)| (*action, *binding))
+    }
+}
+
+impl Drop for KeyBindings {
+    fn drop(&mut self) {
+        self.save();
+    }
+}
@@ -8,6 +8,7 @@ use rust_state::{MapItem, RustState};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Serialize, Serializer};
 
+use super::{CredentialStore, settings_path};
 use crate::loaders::ServiceId;
 
 #[derive(Clone, Default, RustState, Serialize, Deserialize, StateElement)]
@@ -28,6 +29,21 @@ pub struct ServiceSettings {
     pub password: String,
     pub remember_username: bool,
     pub remember_password: bool,
+    /// Log in with the remembered credentials and select the last character
+    /// when the client starts, or reconnects after losing the connection.
+    #[serde(default)]
+    pub auto_login: bool,
+    #[serde(default)]
+    pub last_character_server: Option<String>,
+    #[serde(default)]
+    pub last_character_slot: Option<usize>,
+}
+
+impl ServiceSettings {
+    /// Auto-login needs both the username and the password to be remembered.
+    pub fn can_log_in_automatically(&self) -> bool {
+        self.auto_login && self.remember_username && self.remember_password && !self.username.is_empty() && !self.password.is_empty()
+    }
 }
 
 impl Serialize for ServiceSettings {
@@ -35,19 +51,19 @@ impl Serialize for ServiceSettings {
     where
         S: Serializer,
     {
-        let mut serde_state = Serializer::serialize_struct(serializer, "ServiceSettings", 4)?;
+        let mut serde_state = Serializer::serialize_struct(serializer, "ServiceSettings", 7)?;
         SerializeStruct::serialize_field(
             &mut serde_state,
             "username",
             if self.remember_username { self.username.as_str() } else { "" },
         )?;
-        SerializeStruct::serialize_field(
-            &mut serde_state,
-            "password",
-            if self.remember_password { self.password.as_str() } else { "" },
-        )?;
+        // Passwords are kept in the credential store instead.
+        SerializeStruct::serialize_field(&mut serde_state, "password", "")?;
         SerializeStruct::serialize_field(&mut serde_state, "remember_username", &self.remember_username)?;
         SerializeStruct::serialize_field(&mut serde_state, "remember_password", &self.remember_password)?;
+        SerializeStruct::serialize_field(&mut serde_state, "auto_login", &self.auto_login)?;
+        SerializeStruct::serialize_field(&mut serde_state, "last_character_server", &self.last_character_server)?;
+        SerializeStruct::serialize_field(&mut serde_state, "last_character_slot", &self.last_character_slot)?;
         SerializeStruct::end(serde_state)
     }
 }
@@ -55,6 +71,15 @@ impl Serialize for ServiceSettings {
 impl LoginSettings {
     const FILE_NAME: &'static str = "client/login_settings.ron";
 
+    /// Settings of the service that was logged in to last.
+    pub fn recent_service_settings(&self) -> Option<&ServiceSettings> {
+        self.service_settings.get(self.recent_service_id.as_ref()?)
+    }
+
+    pub fn recent_service_settings_mut(&mut self) -> Option<&mut ServiceSettings> {
+        self.service_settings.get_mut(self.recent_service_id.as_ref()?)
+    }
+
     pub fn new() -> Self {
         Self::load().unwrap_or_else(|| {
             #[cfg(feature = "debug")]
@@ -68,18 +93,41 @@ impl LoginSettings {
         #[cfg(feature = "debug")]
         print_debug!("loading login settings from {}", Self::FILE_NAME.magenta());
 
-        std::fs::read_to_string(Self::FILE_NAME)
+        let mut login_settings: Self = std::fs::read_to_string(settings_path(Self::FILE_NAME))
             .ok()
-            .and_then(|data| ron::from_str(&data).ok())
+            .and_then(|data| ron::from_str(&data).ok())?;
+
+        let mut passwords = CredentialStore::new().load_passwords(login_settings.service_settings.keys().copied());
+
+        for (service_id, service_settings) in &mut login_settings.service_settings {
+            // Older settings files contain the password in plain text. These are moved to
+            // the credential store the next time the settings are saved.
+            if service_settings.remember_password
+                && service_settings.password.is_empty()
+                && let Some(password) = passwords.remove(service_id)
+            {
+                service_settings.password = password;
+            }
+        }
+
+        Some(login_settings)
     }
 
     pub fn save(&self) {
         #[cfg(feature = "debug")]
         print_debug!("saving login settings to {}", Self::FILE_NAME.magenta());
 
+        // Passwords that are no longer remembered are removed from the credential store.
+        let passwords = self.service_settings.iter().map(|(service_id, service_settings)| {
+            let remembered = service_settings.remember_password && !service_settings.password.is_empty();
+            (*service_id, remembered.then_some(service_settings.password.as_str()))
+        });
+
+        CredentialStore::new().save_passwords(passwords);
+
         let data = ron::ser::to_string_pretty(self, PrettyConfig::new()).unwrap();
 
-        if let Err(_error) = std::fs::write(Self::FILE_NAME, data) {
+        if let Err(_error) = std::fs::write(settings_path(Self::FILE_NAME), data) {
             #[cfg(feature = "debug")]
             print_debug!(
                 "failed to save login settings to {}: {:?}",
@@ -1,13 +1,95 @@
 //! Module that implements all persistent setting files.
 
 mod audio;
+mod credentials;
 mod game;
 mod graphic;
 mod interface;
+mod key_bindings;
 mod login;
+mod watcher;
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 pub use audio::*;
+pub use credentials::*;
 pub use game::*;
 pub use graphic::*;
 pub use interface::*;
+pub use key_bindings::*;
+#[cfg(feature = "debug")]
+use korangar_debug::logging::{Colorize, print_debug};
 pub use login::*;
+#[cfg(feature = "debug")]
+use rust_state::Context;
+
+#[cfg(feature = "debug")]
+use self::watcher::SettingsFile;
+pub use self::watcher::SettingsWatcher;
+#[cfg(feature = "debug")]
+use crate::state::ClientState;
+#[cfg(feature = "debug")]
+use crate::system::ConsoleCommandRegistry;
+
+/// Directory of the settings files, if it was changed on the command line.
+static SETTINGS_DIRECTORY: OnceLock<PathBuf> = OnceLock::new();
+
+/// Load and save all settings in a different directory. Needs to be called
+/// before any settings are loaded.
+pub fn set_settings_directory(directory: impl Into<PathBuf>) {
+    let directory = directory.into();
+
+    if let Err(_error) = std::fs::create_dir_all(&directory) {
+        #[cfg(feature = "debug")]
+        print_debug!(
+            "[{}] failed to create settings directory {}: {:?}",
+            "error".red(),
+            directory.display().magenta(),
+            _error
+        );
+    }
+
+    let _ = SETTINGS_DIRECTORY.set(directory);
+}
+
+/// Resolve the path of a settings file, taking the settings directory into
+/// account.
+pub fn settings_path(file_name: &str) -> PathBuf {
+    match SETTINGS_DIRECTORY.get() {
+        Some(directory) => directory.join(Path::new(file_name).file_name().unwrap_or_default()),
+        None => PathBuf::from(file_name),
+    }
+}
+
+/// Replace the settings with the ones on disk.
+fn reload_settings<T>(settings: &mut T, load: fn() -> Option<T>) -> bool {
+    let Some(loaded) = load() else {
+        return false;
+    };
+
+    // Dropping the old settings would write them to disk and overwrite the file we
+    // just loaded, so they are forgotten instead. This leaks their few
+    // allocations, but only happens when a file is edited by hand.
+    std::mem::forget(std::mem::replace(settings, loaded));
+
+    true
+}
+
+/// Register the `reload_settings` command to reload all settings from disk in
+/// the debug console. Changes are applied on the next frame.
+#[cfg(feature = "debug")]
+pub fn register_console_commands(registry: &mut ConsoleCommandRegistry<Context<ClientState>>) {
+    registry.register("reload_settings", "Reload all settings from disk", |state, _| {
+        let failed: Vec<&str> = SettingsFile::ALL
+            .into_iter()
+            .filter(|settings_file| !settings_file.reload(state))
+            .map(SettingsFile::name)
+            .collect();
+
+        match failed.is_empty() {
+            true => Ok("reloaded all settings".to_owned()),
+            false => Err(format!("failed to load {} settings", failed.join(", "))),
+        }
+    });
+}
@@ -0,0 +1,119 @@
+use std::time::{Duration, Instant, SystemTime};
+
+#[cfg(feature = "debug")]
+use korangar_debug::logging::{Colorize, print_debug};
+use rust_state::Context;
+
+use super::{AudioSettings, GameSettings, GraphicsSettings, InterfaceSettings, reload_settings, settings_path};
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+
+/// How often the settings files are checked for changes.
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SettingsFile {
+    Audio,
+    Game,
+    Graphics,
+    Interface,
+}
+
+impl SettingsFile {
+    pub(super) const ALL: [SettingsFile; 4] = [
+        SettingsFile::Audio,
+        SettingsFile::Game,
+        SettingsFile::Graphics,
+        SettingsFile::Interface,
+    ];
+
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            SettingsFile::Audio => "audio",
+            SettingsFile::Game => "game",
+            SettingsFile::Graphics => "graphics",
+            SettingsFile::Interface => "interface",
+        }
+    }
+
+    fn path(self) -> &'static str {
+        match self {
+            SettingsFile::Audio => AudioSettings::FILE_NAME,
+            SettingsFile::Game => GameSettings::FILE_NAME,
+            SettingsFile::Graphics => GraphicsSettings::FILE_NAME,
+            SettingsFile::Interface => InterfaceSettings::FILE_NAME,
+        }
+    }
+
+    fn modified(self) -> Option<SystemTime> {
+        std::fs::metadata(settings_path(self.path()))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    /// Load the settings from disk and replace the ones in the client state.
+    pub(super) fn reload(self, client_state: &mut Context<ClientState>) -> bool {
+        match self {
+            SettingsFile::Audio => reload_settings(client_state.follow_mut(client_state().audio_settings()), AudioSettings::load),
+            SettingsFile::Game => reload_settings(client_state.follow_mut(client_state().game_settings()), GameSettings::load),
+            SettingsFile::Graphics => reload_settings(
+                client_state.follow_mut(client_state().graphics_settings()),
+                GraphicsSettings::load,
+            ),
+            SettingsFile::Interface => reload_settings(
+                client_state.follow_mut(client_state().interface_settings()),
+                InterfaceSettings::load,
+            ),
+        }
+    }
+}
+
+/// Watches the settings files for changes made outside of the client and
+/// loads them into the client state. The changes are then applied by the
+/// same code that applies changes made in the settings windows.
+///
+/// The files are polled for their modification time instead of relying on
+/// file system notifications, since there are only a handful of them and
+/// they rarely change.
+pub struct SettingsWatcher {
+    modified: [Option<SystemTime>; SettingsFile::ALL.len()],
+    last_check: Instant,
+}
+
+impl SettingsWatcher {
+    pub fn new() -> Self {
+        Self {
+            modified: SettingsFile::ALL.map(SettingsFile::modified),
+            last_check: Instant::now(),
+        }
+    }
+
+    /// Reload all settings files that changed since the last check.
+    pub fn reload_changed_settings(&mut self, client_state: &mut Context<ClientState>) {
+        if self.last_check.elapsed() < CHECK_INTERVAL {
+            return;
+        }
+
+        self.last_check = Instant::now();
+
+        for (settings_file, modified) in SettingsFile::ALL.into_iter().zip(self.modified.iter_mut()) {
+            let current_modified = settings_file.modified();
+
+            if current_modified == *modified {
+                continue;
+            }
+
+            #[cfg(feature = "debug")]
+            print_debug!("settings file {} changed on disk", settings_file.path().magenta());
+
+            // If the file can't be parsed an editor might still be in the middle of
+            // writing it. Once it's done the modification time changes again, so
+            // we don't need to retry in the meantime.
+            *modified = current_modified;
+
+            if !settings_file.reload(client_state) {
+                #[cfg(feature = "debug")]
+                print_debug!("[{}] failed to reload {}", "error".red(), settings_file.path().magenta());
+            }
+        }
+    }
+}
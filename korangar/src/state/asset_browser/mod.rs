@@ -0,0 +1,233 @@
+//! State of the asset browser, which lists the sprites of the game files and
+//! previews their animations without having to enter a map.
+
+use std::sync::Arc;
+
+use korangar_interface::element::StateElement;
+use ragnarok_packets::ClientTick;
+use rust_state::RustState;
+
+use crate::loaders::Sprite;
+use crate::world::{Actions, SpriteAnimationState};
+
+/// Maximum number of files listed, so the window stays at a reasonable size.
+const MAXIMUM_RESULT_COUNT: usize = 40;
+/// Every animation of an action file has one action per direction.
+const DIRECTION_COUNT: usize = 8;
+
+/// Loaded sprite and action files of the previewed asset.
+pub struct AssetPreview {
+    pub sprite: Arc<Sprite>,
+    pub actions: Arc<Actions>,
+    pub animation_state: SpriteAnimationState,
+    action: usize,
+}
+
+impl AssetPreview {
+    /// Direction that is rendered, since the animation state only selects
+    /// a group of eight actions.
+    pub fn direction(&self) -> usize {
+        self.action % DIRECTION_COUNT
+    }
+
+    fn motion_count(&self) -> usize {
+        self.actions.actions[self.action].motions.len().max(1)
+    }
+
+    /// Time in milliseconds that a frame of the current action is shown.
+    fn frame_duration(&self) -> f32 {
+        self.actions.delays[self.action % self.actions.delays.len()] * 50.0
+    }
+
+    /// The frame that is currently shown. This matches the calculation in
+    /// [`Actions::render_sprite`].
+    fn frame(&self) -> usize {
+        (f64::from(self.animation_state.time) / f64::from(self.frame_duration())) as usize % self.motion_count()
+    }
+}
+
+#[derive(Default, RustState, StateElement)]
+pub struct AssetBrowserState {
+    filter: String,
+    status_text: String,
+    results: Vec<String>,
+    selected_text: String,
+    frame_text: String,
+    action_metadata_text: String,
+    sprite_metadata_text: String,
+    /// Names of all sprites in the game files, without the `data\sprite\`
+    /// prefix and the file extension.
+    #[hidden_element]
+    files: Vec<String>,
+    #[hidden_element]
+    preview: Option<AssetPreview>,
+    #[hidden_element]
+    playing: bool,
+}
+
+impl AssetBrowserState {
+    pub fn has_files(&self) -> bool {
+        !self.files.is_empty()
+    }
+
+    /// Set the names of all sprites that can be previewed.
+    pub fn set_files(&mut self, files: Vec<String>) {
+        self.files = files;
+        self.update_results();
+    }
+
+    /// List all sprites that contain the filter text.
+    pub fn update_results(&mut self) {
+        let filter = self.filter.to_lowercase();
+        let matching_files: Vec<&String> = self.files.iter().filter(|file| file.to_lowercase().contains(&filter)).collect();
+
+        self.status_text = match matching_files.len() > MAXIMUM_RESULT_COUNT {
+            true => format!(
+                "Showing {MAXIMUM_RESULT_COUNT} of {} matching sprites, refine the filter to see more",
+                matching_files.len()
+            ),
+            false => format!("{} matching sprites", matching_files.len()),
+        };
+
+        self.results = matching_files.into_iter().take(MAXIMUM_RESULT_COUNT).cloned().collect();
+    }
+
+    pub fn preview(&self) -> Option<&AssetPreview> {
+        self.preview.as_ref()
+    }
+
+    pub fn set_preview(&mut self, name: &str, sprite: Arc<Sprite>, actions: Arc<Actions>, client_tick: ClientTick) {
+        if actions.actions.is_empty() {
+            self.set_preview_error(name, "the action file doesn't contain any actions".to_owned());
+            return;
+        }
+
+        self.selected_text = name.to_owned();
+        self.action_metadata_text = format!(
+            "ACT version {}, {} actions, {} events",
+            actions.actions_data.version,
+            actions.actions.len(),
+            actions.events.len()
+        );
+        self.sprite_metadata_text = format!(
+            "SPR {} images, palette size {}, upscaled {}x",
+            sprite.textures.len(),
+            sprite.palette_size,
+            sprite.upscale_factor
+        );
+        self.preview = Some(AssetPreview {
+            sprite,
+            actions,
+            animation_state: SpriteAnimationState::new(client_tick),
+            action: 0,
+        });
+        self.playing = true;
+        self.update_frame_text();
+    }
+
+    /// Show why the asset couldn't be previewed.
+    pub fn set_preview_error(&mut self, name: &str, error: String) {
+        self.selected_text = format!("{name}: {error}");
+        self.frame_text.clear();
+        self.action_metadata_text.clear();
+        self.sprite_metadata_text.clear();
+        self.preview = None;
+    }
+
+    /// Advance the animation while it is playing.
+    pub fn update(&mut self, client_tick: ClientTick) {
+        if let Some(preview) = &mut self.preview
+            && self.playing
+        {
+            preview.animation_state.update(client_tick);
+            self.update_frame_text();
+        }
+    }
+
+    pub fn toggle_playback(&mut self, client_tick: ClientTick) {
+        let Some(preview) = &mut self.preview else {
+            return;
+        };
+
+        // Continue from the current frame instead of jumping ahead.
+        if !self.playing {
+            preview.animation_state.start_time = ClientTick(client_tick.0.wrapping_sub(preview.animation_state.time));
+        }
+
+        self.playing = !self.playing;
+    }
+
+    /// Pause the animation and show a different frame of the current action.
+    pub fn step_frame(&mut self, steps: i32) {
+        let Some(preview) = &mut self.preview else {
+            return;
+        };
+
+        let frame = (preview.frame() as i64 + steps as i64).rem_euclid(preview.motion_count() as i64);
+
+        // Use the middle of the frame, so rounding doesn't show the previous one.
+        preview.animation_state.time = ((frame as f32 + 0.5) * preview.frame_duration()) as u32;
+        self.playing = false;
+        self.update_frame_text();
+    }
+
+    /// Show a different action and start it from the first frame.
+    pub fn step_action(&mut self, steps: i32, client_tick: ClientTick) {
+        let Some(preview) = &mut self.preview else {
+            return;
+        };
+
+        let action_count = preview.actions.actions.len() as i64;
+        preview.action = (preview.action as i64 + steps as i64).rem_euclid(action_count) as usize;
+        preview.animation_state.action_base_offset = preview.action / DIRECTION_COUNT;
+        preview.animation_state.start_time = client_tick;
+        preview.animation_state.time = 0;
+        self.update_frame_text();
+    }
+
+    fn update_frame_text(&mut self) {
+        let Some(preview) = &self.preview else {
+            return;
+        };
+
+        self.frame_text = format!(
+            "Action {}/{} (direction {}), frame {}/{}, {:.0}ms per frame",
+            preview.action + 1,
+            preview.actions.actions.len(),
+            preview.direction(),
+            preview.frame() + 1,
+            preview.motion_count(),
+            preview.frame_duration()
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AssetBrowserState, MAXIMUM_RESULT_COUNT};
+
+    #[test]
+    fn results_are_filtered() {
+        let mut state = AssetBrowserState::default();
+        state.set_files(vec![
+            "cursors".to_owned(),
+            "monster\\poring".to_owned(),
+            "monster\\Poporing".to_owned(),
+        ]);
+
+        assert_eq!(state.results.len(), 3);
+
+        state.filter = "PORING".to_owned();
+        state.update_results();
+
+        assert_eq!(state.results, ["monster\\poring", "monster\\Poporing"]);
+    }
+
+    #[test]
+    fn results_are_limited() {
+        let mut state = AssetBrowserState::default();
+        state.set_files((0..100).map(|index| format!("sprite_{index}")).collect());
+
+        assert_eq!(state.results.len(), MAXIMUM_RESULT_COUNT);
+    }
+}
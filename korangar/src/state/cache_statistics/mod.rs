@@ -1,18 +1,27 @@
 use korangar_audio::AudioEngine;
 use korangar_interface::element::StateElement;
-use korangar_interface::window::StateWindow;
-use rust_state::RustState;
+use rust_state::{Context, RustState};
 
-use crate::interface::windows::WindowClass;
 use crate::loaders::{ActionLoader, AnimationLoader, EffectLoader, FontLoader, GameFileLoader, SpriteLoader, TextureLoader};
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+use crate::system::ConsoleCommandRegistry;
 
-#[derive(Clone, Copy, PartialEq, Default, RustState, StateElement, StateWindow)]
-#[window_class(WindowClass::CacheStatistics)]
-#[window_title("Cache Statistics")]
+/// Caches that can be cleared from the cache statistics window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClearableCache {
+    Texture,
+    Sprite,
+    Action,
+    Animation,
+    Effect,
+}
+
+#[derive(Clone, Copy, PartialEq, Default, RustState, StateElement)]
 pub struct CacheStatistics {
     texture_cache: korangar_container::CacheStatistics,
     sprite_cache: korangar_container::CacheStatistics,
     font_cache: korangar_container::CacheStatistics,
+    shaping_cache: korangar_container::CacheStatistics,
     sound_cache: korangar_container::CacheStatistics,
     action_cache: korangar_container::CacheStatistics,
     animation_cache: korangar_container::CacheStatistics,
@@ -41,10 +50,41 @@ impl CacheStatistics {
             self.texture_cache = texture_loader.cache_statistics();
             self.sprite_cache = sprite_loader.cache_statistics();
             self.font_cache = font_loader.cache_statistics();
+            self.shaping_cache = font_loader.shaping_cache_statistics();
             self.sound_cache = audio_engine.cache_statistics();
             self.action_cache = action_loader.cache_statistics();
             self.animation_cache = animation_loader.cache_statistics();
             self.effect_cache = effect_loader.cache_statistics();
         }
     }
+
+    /// Register the `caches` command to dump the cache statistics in the
+    /// debug console.
+    pub fn register_console_commands(registry: &mut ConsoleCommandRegistry<Context<ClientState>>) {
+        registry.register("caches", "Print the statistics of all caches", |state, _| {
+            let statistics = state.follow(client_state().cache_statistics());
+            let caches = [
+                ("texture", &statistics.texture_cache),
+                ("sprite", &statistics.sprite_cache),
+                ("font", &statistics.font_cache),
+                ("shaping", &statistics.shaping_cache),
+                ("sound", &statistics.sound_cache),
+                ("action", &statistics.action_cache),
+                ("animation", &statistics.animation_cache),
+                ("effect", &statistics.effect_cache),
+            ];
+
+            let lines = caches
+                .iter()
+                .map(|(name, cache)| {
+                    format!(
+                        "{name}: {}/{} entries, {}/{}, hit rate {}",
+                        cache.count, cache.max_count, cache.size, cache.max_size, cache.hit_rate
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            Ok(lines.join("\n"))
+        });
+    }
 }
@@ -0,0 +1,200 @@
+//! State of the effect preview, which plays a single effect in isolation and
+//! shows the interpolated frame data of its layers.
+
+use std::sync::Arc;
+
+use cgmath::Point3;
+use korangar_interface::element::StateElement;
+use rust_state::RustState;
+
+use crate::graphics::EffectTimeScale;
+use crate::renderer::EffectRenderer;
+use crate::world::{Camera, Effect, FrameTimer};
+
+struct PreviewedEffect {
+    effect: Arc<Effect>,
+    frame_timer: FrameTimer,
+    /// Time in seconds since the effect started, scaled by the time scale.
+    time: f64,
+    finished: bool,
+}
+
+#[derive(RustState, StateElement)]
+pub struct EffectPreviewState {
+    /// Path of the effect relative to `data\texture\effect\`.
+    path: String,
+    looping: bool,
+    hide_scene: bool,
+    time_scale: EffectTimeScale,
+    status_text: String,
+    frame_text: String,
+    layer_texts: Vec<String>,
+    #[hidden_element]
+    preview: Option<PreviewedEffect>,
+}
+
+impl Default for EffectPreviewState {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            looping: true,
+            hide_scene: false,
+            time_scale: EffectTimeScale::Normal,
+            status_text: "No effect loaded".to_owned(),
+            frame_text: String::new(),
+            layer_texts: Vec::new(),
+            preview: None,
+        }
+    }
+}
+
+impl EffectPreviewState {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Whether the rest of the scene should be hidden, so only the previewed
+    /// effect is visible.
+    pub fn hides_scene(&self) -> bool {
+        self.hide_scene && self.preview.is_some()
+    }
+
+    pub fn set_effect(&mut self, effect: Arc<Effect>) {
+        self.status_text = format!(
+            "{} layers, {} frames at {} fps",
+            effect.layer_count(),
+            effect.max_key(),
+            effect.frames_per_second()
+        );
+        self.preview = Some(PreviewedEffect {
+            frame_timer: effect.new_frame_timer(),
+            effect,
+            time: 0.0,
+            finished: false,
+        });
+        self.update(0.0);
+    }
+
+    /// Show why the effect couldn't be previewed.
+    pub fn set_error(&mut self, error: String) {
+        self.status_text = error;
+        self.frame_text.clear();
+        self.layer_texts.clear();
+        self.preview = None;
+    }
+
+    /// Play the effect from the first frame again.
+    pub fn restart(&mut self) {
+        if let Some(preview) = &mut self.preview {
+            preview.frame_timer = preview.effect.new_frame_timer();
+            preview.time = 0.0;
+            preview.finished = false;
+        }
+
+        self.update(0.0);
+    }
+
+    /// Advance the effect by the scaled delta time.
+    pub fn update(&mut self, delta_time: f64) {
+        let Some(preview) = &mut self.preview else {
+            return;
+        };
+
+        if preview.finished {
+            return;
+        }
+
+        preview.time += delta_time * self.time_scale.factor();
+
+        if !preview.frame_timer.update(preview.time) && !self.looping {
+            preview.finished = true;
+        }
+
+        self.update_texts();
+    }
+
+    /// Pause the effect and show a different frame.
+    pub fn step_frame(&mut self, steps: i32) {
+        let Some(preview) = &mut self.preview else {
+            return;
+        };
+
+        let frame_count = preview.effect.max_key().max(1) as i64;
+        let frame = (preview.frame_timer.current_frame() as i64 + steps as i64).rem_euclid(frame_count);
+
+        // Use the middle of the frame, so rounding doesn't show the previous one.
+        preview.time = (frame as f64 + 0.5) / preview.effect.frames_per_second().max(1) as f64;
+        preview.finished = false;
+        self.time_scale = EffectTimeScale::Paused;
+        self.update(0.0);
+    }
+
+    pub fn render(&self, renderer: &mut EffectRenderer, camera: &dyn Camera, position: Point3<f32>) {
+        if let Some(preview) = &self.preview
+            && !preview.finished
+        {
+            preview.effect.render(renderer, camera, &preview.frame_timer, position);
+        }
+    }
+
+    fn update_texts(&mut self) {
+        let Some(preview) = &self.preview else {
+            return;
+        };
+
+        self.frame_text = match preview.finished {
+            true => "Finished, restart to play it again".to_owned(),
+            false => format!(
+                "Frame {}/{} at {:.2}s",
+                preview.frame_timer.current_frame() + 1,
+                preview.effect.max_key(),
+                preview.time
+            ),
+        };
+        self.layer_texts = preview.effect.describe_layers(&preview.frame_timer);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::EffectPreviewState;
+    use crate::graphics::EffectTimeScale;
+    use crate::world::Effect;
+
+    fn effect() -> Arc<Effect> {
+        Arc::new(Effect::new(10, 5, Vec::new()))
+    }
+
+    #[test]
+    fn effect_finishes_without_looping() {
+        let mut state = EffectPreviewState {
+            looping: false,
+            ..Default::default()
+        };
+        state.set_effect(effect());
+
+        state.update(0.3);
+        assert!(!state.preview.as_ref().unwrap().finished);
+
+        state.update(0.3);
+        assert!(state.preview.as_ref().unwrap().finished);
+
+        state.restart();
+        assert!(!state.preview.as_ref().unwrap().finished);
+    }
+
+    #[test]
+    fn stepping_pauses_the_effect() {
+        let mut state = EffectPreviewState::default();
+        state.set_effect(effect());
+
+        state.step_frame(-1);
+        assert_eq!(state.time_scale, EffectTimeScale::Paused);
+        assert_eq!(state.preview.as_ref().unwrap().frame_timer.current_frame(), 4);
+
+        state.update(1.0);
+        assert_eq!(state.preview.as_ref().unwrap().frame_timer.current_frame(), 4);
+    }
+}
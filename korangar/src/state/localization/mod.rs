@@ -140,6 +140,8 @@ pub struct Localization {
     password_text: String,
     remember_username_text: String,
     remember_password_text: String,
+    auto_log_in_text: String,
+    auto_log_in_countdown_text: String,
     log_in_button_text: String,
     log_in_button_tooltip: String,
     menu_window_title: String,
@@ -163,10 +165,17 @@ pub struct Localization {
     chat_text_box_message: String,
     audio_settings_window_title: String,
     mute_audio_on_focus_loss_button_text: String,
+    interface_volume_text: String,
     create_character_window_title: String,
     character_name_text: String,
     create_character_button_text: String,
     create_character_button_tooltip: String,
+    male_button_text: String,
+    female_button_text: String,
+    novice_button_text: String,
+    summoner_button_text: String,
+    hair_style_text: String,
+    hair_color_text: String,
     dialog_window_title: String,
     next_button_text: String,
     close_button_text: String,
@@ -197,6 +206,100 @@ pub struct Localization {
     dexterity_text: String,
     luck_text: String,
     auto_attack_button_text: String,
+    dialog_text_speed_text: String,
+    popup_aggregation_text: String,
+    notifications_text: String,
+    friend_login_toasts_button_text: String,
+    rare_drop_toasts_button_text: String,
+    achievement_toasts_button_text: String,
+    system_warning_toasts_button_text: String,
+    crash_report_window_title: String,
+    crash_report_text: String,
+    open_crash_report_directory_button_text: String,
+    dialog_history_window_title: String,
+    dialog_history_button_text: String,
+    session_statistics_button_text: String,
+    session_statistics_window_title: String,
+    session_time_text: String,
+    base_experience_text: String,
+    job_experience_text: String,
+    zeny_text: String,
+    kills_text: String,
+    looted_items_text: String,
+    reset_button_text: String,
+    export_button_text: String,
+    monster_info_button_text: String,
+    monster_info_window_title: String,
+    monster_search_text_box_message: String,
+    level_text: String,
+    health_text: String,
+    attack_text: String,
+    defense_text: String,
+    element_text: String,
+    race_text: String,
+    size_text: String,
+    drops_text: String,
+    spawn_maps_text: String,
+    item_search_button_text: String,
+    item_search_window_title: String,
+    item_search_text_box_message: String,
+    item_type_text: String,
+    equip_position_text: String,
+    level_range_text: String,
+    search_button_text: String,
+    required_level_text: String,
+    dropped_by_text: String,
+    sold_by_text: String,
+    link_in_chat_button_text: String,
+    skill_planner_button_text: String,
+    skill_planner_window_title: String,
+    skill_points_text: String,
+    export_build_button_text: String,
+    import_build_button_text: String,
+    build_string_text_box_message: String,
+    stat_calculator_text: String,
+    weapon_attack_text: String,
+    equipment_attack_text: String,
+    gear_magic_attack_text: String,
+    weapon_aspd_text: String,
+    magic_attack_text: String,
+    hit_text: String,
+    flee_text: String,
+    aspd_text: String,
+    skill_damage_text: String,
+    all_items_tab_text: String,
+    consumables_tab_text: String,
+    equipment_tab_text: String,
+    etc_items_tab_text: String,
+    compact_view_button_text: String,
+    inventory_search_text_box_message: String,
+    costume_equipment_text: String,
+    shadow_equipment_text: String,
+    gear_sets_text: String,
+    gear_set_name_text_box_message: String,
+    save_gear_set_button_text: String,
+    equip_gear_set_button_text: String,
+    delete_gear_set_button_text: String,
+    emote_wheel_window_title: String,
+    chat_macros_text: String,
+    chat_macro_text_box_message: String,
+    minimap_window_title: String,
+    minimap_button_text: String,
+    party_member_names_button_text: String,
+    combat_feedback_text: String,
+    screen_shake_button_text: String,
+    hit_stop_button_text: String,
+    hit_flash_button_text: String,
+    disable_combat_feedback_button_text: String,
+    accessibility_text: String,
+    colorblind_mode_text: String,
+    minimum_font_size_text: String,
+    high_contrast_button_text: String,
+    action_intervals_text: String,
+    move_interval_text: String,
+    attack_interval_text: String,
+    chat_message_interval_text: String,
+    skill_interval_text: String,
 }
 
 impl Localization {
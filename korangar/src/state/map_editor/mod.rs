@@ -0,0 +1,221 @@
+//! State of the map editor, which allows transforming the objects, moving,
+//! adding, and removing the light, sound, and effect sources, and painting
+//! the walkability of the tiles of the current map.
+
+use korangar_interface::element::StateElement;
+use ragnarok_formats::transform::Transform;
+use rust_state::{Context, RustState};
+
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+use crate::system::ConsoleCommandRegistry;
+use crate::world::{GizmoAxis, GizmoMode, Map, MarkerIdentifier};
+
+/// Maximum number of transform changes that can be undone.
+const MAXIMUM_HISTORY_LENGTH: usize = 100;
+
+/// A change of the transform of an object that can be undone.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TransformChange {
+    pub object_key: u32,
+    pub previous: Transform,
+    pub new: Transform,
+}
+
+#[derive(Default, RustState, StateElement)]
+pub struct MapEditorState {
+    /// While enabled, clicking a marker selects it instead of opening its
+    /// details, and markers can be dragged across the map.
+    enabled: bool,
+    /// While enabled, clicking and dragging over tiles flips their
+    /// walkability.
+    tile_brush: bool,
+    selection_text: String,
+    gizmo_text: String,
+    #[hidden_element]
+    selected: Option<MarkerIdentifier>,
+    #[hidden_element]
+    gizmo_mode: GizmoMode,
+    #[hidden_element]
+    gizmo_axis: GizmoAxis,
+    #[hidden_element]
+    undo_stack: Vec<TransformChange>,
+    #[hidden_element]
+    redo_stack: Vec<TransformChange>,
+    /// Set while dragging the gizmo, so the whole drag is undone at once.
+    #[hidden_element]
+    merge_changes: bool,
+    /// Set by the console command, since the map is not part of the state.
+    #[hidden_element]
+    navigation_grid_requested: bool,
+}
+
+impl MapEditorState {
+    pub fn new() -> Self {
+        let mut state = Self::default();
+        state.clear_selection();
+        state.update_gizmo_text();
+        state
+    }
+
+    /// Register the `export_navigation_grid` command to export the
+    /// walkability of the current map for server-side path finding.
+    pub fn register_console_commands(registry: &mut ConsoleCommandRegistry<Context<ClientState>>) {
+        registry.register(
+            "export_navigation_grid",
+            "Export the navigation grid of the current map",
+            |state, _| {
+                state.follow_mut(client_state().map_editor()).navigation_grid_requested = true;
+                Ok("exporting the navigation grid".to_owned())
+            },
+        );
+    }
+
+    /// Returns `true` once after the navigation grid was requested.
+    pub fn take_navigation_grid_request(&mut self) -> bool {
+        std::mem::take(&mut self.navigation_grid_requested)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Blocked tiles need to be pickable while painting, so the tile meshes
+    /// are updated whenever this changes.
+    pub fn is_painting_tiles(&self) -> bool {
+        self.enabled && self.tile_brush
+    }
+
+    pub fn selected(&self) -> Option<MarkerIdentifier> {
+        self.selected
+    }
+
+    pub fn select(&mut self, map: &Map, marker_identifier: Option<MarkerIdentifier>) {
+        self.selected = marker_identifier.filter(MarkerIdentifier::is_editable);
+        self.merge_changes = false;
+        self.update_selection_text(map);
+    }
+
+    /// Update the description of the selected marker after it was modified.
+    pub fn update_selection_text(&mut self, map: &Map) {
+        self.selection_text = self
+            .selected
+            .and_then(|marker_identifier| map.marker_description(marker_identifier))
+            .unwrap_or_else(|| "Nothing selected".to_string());
+    }
+
+    /// Called when the map changes, since the selected marker and the history
+    /// belong to the previous map.
+    pub fn clear_selection(&mut self) {
+        self.selected = None;
+        self.selection_text = "Nothing selected".to_string();
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+    pub fn gizmo_mode(&self) -> GizmoMode {
+        self.gizmo_mode
+    }
+
+    pub fn gizmo_axis(&self) -> GizmoAxis {
+        self.gizmo_axis
+    }
+
+    pub fn set_gizmo_mode(&mut self, mode: GizmoMode) {
+        self.gizmo_mode = mode;
+        self.update_gizmo_text();
+    }
+
+    pub fn set_gizmo_axis(&mut self, axis: GizmoAxis) {
+        self.gizmo_axis = axis;
+        self.update_gizmo_text();
+    }
+
+    fn update_gizmo_text(&mut self) {
+        self.gizmo_text = format!("{:?} along {:?}", self.gizmo_mode, self.gizmo_axis);
+    }
+
+    /// Record a change of an object transform. Consecutive changes while
+    /// dragging the gizmo are merged into a single change.
+    pub fn record_change(&mut self, change: TransformChange, dragging: bool) {
+        self.redo_stack.clear();
+
+        match self.undo_stack.last_mut() {
+            Some(last_change) if dragging && self.merge_changes && last_change.object_key == change.object_key => {
+                last_change.new = change.new;
+            }
+            _ => {
+                if self.undo_stack.len() == MAXIMUM_HISTORY_LENGTH {
+                    self.undo_stack.remove(0);
+                }
+
+                self.undo_stack.push(change);
+            }
+        }
+
+        self.merge_changes = dragging;
+    }
+
+    /// Take the last change from the history. Returns the object and the
+    /// transform it should be reset to.
+    pub fn undo(&mut self) -> Option<(u32, Transform)> {
+        let change = self.undo_stack.pop()?;
+        self.redo_stack.push(change);
+        self.merge_changes = false;
+
+        Some((change.object_key, change.previous))
+    }
+
+    /// Reapply the last undone change. Returns the object and the transform it
+    /// should be set to.
+    pub fn redo(&mut self) -> Option<(u32, Transform)> {
+        let change = self.redo_stack.pop()?;
+        self.undo_stack.push(change);
+        self.merge_changes = false;
+
+        Some((change.object_key, change.new))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::Point3;
+    use ragnarok_formats::transform::Transform;
+
+    use super::{MapEditorState, TransformChange};
+
+    fn change(object_key: u32, previous: f32, new: f32) -> TransformChange {
+        TransformChange {
+            object_key,
+            previous: Transform::position(Point3::new(previous, 0.0, 0.0)),
+            new: Transform::position(Point3::new(new, 0.0, 0.0)),
+        }
+    }
+
+    #[test]
+    fn dragging_is_undone_at_once() {
+        let mut state = MapEditorState::new();
+
+        state.record_change(change(0, 0.0, 1.0), true);
+        state.record_change(change(0, 1.0, 2.0), true);
+        state.record_change(change(0, 2.0, 3.0), true);
+
+        assert_eq!(state.undo(), Some((0, Transform::position(Point3::new(0.0, 0.0, 0.0)))));
+        assert_eq!(state.undo(), None);
+    }
+
+    #[test]
+    fn redo_is_cleared_by_new_changes() {
+        let mut state = MapEditorState::new();
+
+        state.record_change(change(0, 0.0, 1.0), false);
+        state.record_change(change(1, 0.0, 1.0), false);
+
+        assert_eq!(state.undo(), Some((1, Transform::position(Point3::new(0.0, 0.0, 0.0)))));
+        assert_eq!(state.redo(), Some((1, Transform::position(Point3::new(1.0, 0.0, 0.0)))));
+        assert_eq!(state.undo(), Some((1, Transform::position(Point3::new(0.0, 0.0, 0.0)))));
+
+        state.record_change(change(0, 1.0, 5.0), false);
+
+        assert_eq!(state.redo(), None);
+    }
+}
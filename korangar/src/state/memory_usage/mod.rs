@@ -0,0 +1,137 @@
+use korangar_audio::AudioEngine;
+use korangar_container::HumanReadableBytes;
+use korangar_interface::element::StateElement;
+use rust_state::RustState;
+
+use crate::loaders::{ActionLoader, AnimationLoader, AsyncLoader, EffectLoader, FontLoader, GameFileLoader, SpriteLoader, TextureLoader};
+use crate::world::Map;
+
+/// Soft limit for the combined size of the caches that can be evicted. Once
+/// it is exceeded, all of them are shrunk by the same ratio.
+const EVICTABLE_CACHE_BUDGET: usize = 768 << 20;
+/// Soft limit for the maps that are kept loaded in the background. Once it is
+/// exceeded, retained and preloaded maps are dropped.
+const KEPT_MAPS_BUDGET: usize = 512 << 20;
+/// Seconds between two updates of the memory usage.
+const UPDATE_INTERVAL: f64 = 1.0;
+
+/// Loaders whose caches count towards the memory budget.
+pub struct MemoryUsageSources<'a> {
+    pub texture_loader: &'a TextureLoader,
+    pub sprite_loader: &'a SpriteLoader,
+    pub action_loader: &'a ActionLoader,
+    pub animation_loader: &'a AnimationLoader,
+    pub effect_loader: &'a EffectLoader,
+    pub font_loader: &'a FontLoader,
+    pub audio_engine: &'a AudioEngine<GameFileLoader>,
+    pub async_loader: &'a AsyncLoader,
+}
+
+/// Memory used by the different subsystems of the client. Sizes of GPU
+/// resources are estimated from their dimensions.
+#[derive(Clone, Copy, PartialEq, Default, RustState, StateElement)]
+pub struct MemoryUsage {
+    textures: HumanReadableBytes,
+    sprites: HumanReadableBytes,
+    actions: HumanReadableBytes,
+    animations: HumanReadableBytes,
+    effects: HumanReadableBytes,
+    fonts: HumanReadableBytes,
+    sounds: HumanReadableBytes,
+    current_map: HumanReadableBytes,
+    kept_maps: HumanReadableBytes,
+    total: HumanReadableBytes,
+    /// Number of times a budget was exceeded and memory was released.
+    budget_evictions: u32,
+    #[hidden_element]
+    last_update: f64,
+}
+
+impl MemoryUsage {
+    /// Update the tallies and release memory of subsystems that are over their
+    /// budget.
+    pub fn update(&mut self, delta_time: f64, sources: MemoryUsageSources, map: Option<&Map>) {
+        self.last_update += delta_time;
+
+        if self.last_update < UPDATE_INTERVAL {
+            return;
+        }
+
+        self.last_update = 0.0;
+        self.tally(&sources, map);
+
+        let evictable = [self.textures, self.sprites, self.actions, self.animations, self.effects].map(|size| size.bytes());
+        let evictable_size: usize = evictable.iter().sum();
+
+        if evictable_size > EVICTABLE_CACHE_BUDGET {
+            let [textures, sprites, actions, animations, effects] =
+                evictable.map(|size| shrunk_size(size, evictable_size, EVICTABLE_CACHE_BUDGET));
+
+            sources.texture_loader.shrink_cache(textures);
+            sources.sprite_loader.shrink_cache(sprites);
+            sources.action_loader.shrink_cache(actions);
+            sources.animation_loader.shrink_cache(animations);
+            sources.effect_loader.shrink_cache(effects);
+
+            self.budget_evictions += 1;
+        }
+
+        if self.kept_maps.bytes() > KEPT_MAPS_BUDGET {
+            sources.async_loader.clear_retained_maps();
+            sources.async_loader.clear_preloaded_maps();
+            self.budget_evictions += 1;
+        }
+    }
+
+    fn tally(&mut self, sources: &MemoryUsageSources, map: Option<&Map>) {
+        let font_size = sources.font_loader.cache_statistics().size.bytes() + sources.font_loader.shaping_cache_statistics().size.bytes();
+
+        self.textures = sources.texture_loader.cache_statistics().size;
+        self.sprites = sources.sprite_loader.cache_statistics().size;
+        self.actions = sources.action_loader.cache_statistics().size;
+        self.animations = sources.animation_loader.cache_statistics().size;
+        self.effects = sources.effect_loader.cache_statistics().size;
+        self.fonts = HumanReadableBytes::new(font_size);
+        self.sounds = sources.audio_engine.cache_statistics().size;
+        self.current_map = HumanReadableBytes::new(map.map_or(0, |map| map.gpu_memory_usage()));
+        self.kept_maps = HumanReadableBytes::new(sources.async_loader.kept_maps_gpu_memory_usage());
+
+        let total = [
+            self.textures,
+            self.sprites,
+            self.actions,
+            self.animations,
+            self.effects,
+            self.fonts,
+            self.sounds,
+            self.current_map,
+            self.kept_maps,
+        ]
+        .iter()
+        .map(HumanReadableBytes::bytes)
+        .sum();
+
+        self.total = HumanReadableBytes::new(total);
+    }
+}
+
+/// Size a cache is shrunk to, so that all caches together fit into the budget
+/// while keeping their relative sizes.
+fn shrunk_size(size: usize, total_size: usize, budget: usize) -> usize {
+    (size as u128 * budget as u128 / total_size as u128) as usize
+}
+
+#[cfg(test)]
+mod test {
+    use super::shrunk_size;
+
+    #[test]
+    fn caches_are_shrunk_proportionally() {
+        let sizes = [600, 300, 100];
+        let total_size = sizes.iter().sum();
+        let shrunk_sizes = sizes.map(|size| shrunk_size(size, total_size, 500));
+
+        assert_eq!(shrunk_sizes, [300, 150, 50]);
+        assert!(shrunk_sizes.iter().sum::<usize>() <= 500);
+    }
+}
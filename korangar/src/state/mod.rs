@@ -1,11 +1,27 @@
 #[cfg(feature = "debug")]
+pub mod asset_browser;
+#[cfg(feature = "debug")]
 pub mod cache_statistics;
+#[cfg(feature = "debug")]
+pub mod effect_preview;
 pub mod localization;
+#[cfg(feature = "debug")]
+pub mod map_editor;
+pub mod memory_usage;
+#[cfg(feature = "debug")]
+pub mod model_viewer;
+pub mod moderation;
+pub mod party;
+pub mod roulette;
+pub mod session_statistics;
+pub mod skill_planner;
 pub mod theme;
 
 use std::cell::Cell;
 use std::sync::Arc;
+use std::time::Instant;
 
+use hashbrown::HashMap;
 use korangar_interface::application::Application;
 use korangar_interface::components::button::ButtonTheme;
 use korangar_interface::components::collapsable::CollapsableTheme;
@@ -18,32 +34,51 @@ use korangar_interface::element::StateElement;
 use korangar_interface::layout::tooltip::TooltipTheme;
 use korangar_interface::theme::ThemePathGetter;
 use korangar_interface::window::{StateWindow, WindowTheme};
-use korangar_networking::{MessageColor, SellItem, ShopItem};
+use korangar_networking::{CharacterCreationOptions, MessageColor, SellItem, ShopItem};
 use localization::Localization;
 #[cfg(feature = "debug")]
 use ragnarok_formats::map::{EffectSource, LightSource, MapData, SoundSource};
-use ragnarok_packets::{CharacterId, CharacterServerInformation, EntityId, Friend};
+use ragnarok_packets::{CharacterId, CharacterServerInformation, EntityId, Friend, ItemId};
 #[cfg(feature = "debug")]
 use rust_state::{ManuallyAssertExt, VecIndexExt};
 use rust_state::{Path, RustState, Selector};
 use theme::{InterfaceTheme, InterfaceThemePathExt, InterfaceThemeType};
 
+#[cfg(feature = "debug")]
+use self::asset_browser::AssetBrowserState;
 #[cfg(feature = "debug")]
 use self::cache_statistics::CacheStatistics;
 #[cfg(feature = "debug")]
+use self::effect_preview::EffectPreviewState;
+#[cfg(feature = "debug")]
+use self::map_editor::MapEditorState;
+use self::memory_usage::MemoryUsage;
+#[cfg(feature = "debug")]
+use self::model_viewer::ModelViewerState;
+use self::party::Party;
+use self::roulette::RouletteBoard;
+use self::session_statistics::SessionStatistics;
+use self::skill_planner::SkillPlanner;
+#[cfg(feature = "debug")]
 use crate::PacketHistory;
 use crate::character_slots::CharacterSlots;
 #[cfg(feature = "debug")]
 use crate::graphics::RenderOptions;
-use crate::graphics::{Color, CornerDiameter, ScreenClip, ScreenPosition, ScreenSize, ShadowPadding};
+use crate::graphics::{Color, CornerDiameter, ScreenClip, ScreenPosition, ScreenSize, ShadowPadding, Texture};
 use crate::input::{InputEvent, MouseInputMode};
-use crate::interface::windows::{ChatWindowState, DialogWindowState, FriendListWindowState, LoginWindowState, WindowCache, WindowClass};
+use crate::interface::windows::{
+    ChatWindowState, DialogWindowState, EmoteWheelState, FriendListWindowState, ItemSearchWindowState, LoginWindowState,
+    MinimapWindowState, MonsterInfoWindowState, WindowCache, WindowClass,
+};
 #[cfg(feature = "debug")]
-use crate::interface::windows::{ProfilerWindowState, ThemeInspectorWindowState};
+use crate::interface::windows::{ConsoleWindowState, ProfilerWindowState, ThemeInspectorWindowState};
 use crate::inventory::{Hotbar, Inventory, SkillTree};
 use crate::loaders::{ClientInfo, FontLoader, FontSize, GameFileLoader, OverflowBehavior, load_client_info};
 use crate::renderer::InterfaceRenderer;
-use crate::settings::{GameSettings, GraphicsSettingsCapabilities, InterfaceSettings, InterfaceSettingsCapabilities, LoginSettings};
+use crate::settings::{
+    AudioSettingsCapabilities, GameSettings, GameSettingsCapabilities, GraphicsSettingsCapabilities, InterfaceSettings,
+    InterfaceSettingsCapabilities, LoginSettings,
+};
 use crate::state::theme::WorldTheme;
 #[cfg(feature = "debug")]
 use crate::world::Object;
@@ -60,11 +95,70 @@ pub struct ChatMessage {
     pub text: String,
     /// Color of the message.
     pub color: MessageColor,
+    /// Items linked in the message.
+    #[hidden_element]
+    pub item_links: Vec<ItemId>,
 }
 
 impl ChatMessage {
     pub fn new(text: String, color: MessageColor) -> Self {
-        Self { text, color }
+        Self {
+            text,
+            color,
+            item_links: Vec::new(),
+        }
+    }
+
+    pub fn with_item_links(self, item_links: Vec<ItemId>) -> Self {
+        Self { item_links, ..self }
+    }
+}
+
+/// Combines identical chat messages that arrive in quick succession into a
+/// single message with a repeat count, so spammed messages only take up a
+/// single line.
+#[derive(Default)]
+pub struct ChatMessageAggregation {
+    last_message: Option<AggregatedChatMessage>,
+}
+
+struct AggregatedChatMessage {
+    /// Text of the message without the repeat count.
+    text: String,
+    /// Index of the message in the list of chat messages.
+    index: usize,
+    count: usize,
+    received_at: Instant,
+}
+
+impl ChatMessageAggregation {
+    pub fn push(
+        &mut self,
+        chat_messages: &mut Vec<ChatMessage>,
+        message: ChatMessage,
+        aggregation_window: Option<f32>,
+        received_at: Instant,
+    ) {
+        if let Some(aggregation_window) = aggregation_window
+            && let Some(last_message) = &mut self.last_message
+            && last_message.text == message.text
+            && last_message.index + 1 == chat_messages.len()
+            && received_at.duration_since(last_message.received_at).as_secs_f32() <= aggregation_window
+        {
+            last_message.count += 1;
+            last_message.received_at = received_at;
+            chat_messages[last_message.index].text = format!("{} (x{})", last_message.text, last_message.count);
+            return;
+        }
+
+        self.last_message = Some(AggregatedChatMessage {
+            text: message.text.clone(),
+            index: chat_messages.len(),
+            count: 1,
+            received_at,
+        });
+
+        chat_messages.push(message);
     }
 }
 
@@ -83,8 +177,12 @@ pub struct ClientState {
     login_settings: LoginSettings,
     /// Saved audio settings.
     audio_settings: AudioSettings,
+    /// Audio capabilities used in the audio settings window.
+    audio_settings_capabilities: AudioSettingsCapabilities,
     /// Saved game settings.
     game_settings: GameSettings,
+    /// Game capabilities used in the game settings window.
+    game_settings_capabilities: GameSettingsCapabilities,
     /// Saved interface settings.
     interface_settings: InterfaceSettings,
     /// Interface capabilities used in the interface settings window.
@@ -112,11 +210,20 @@ pub struct ClientState {
     friend_list_window: FriendListWindowState,
     /// Internal state of the dialog window.
     dialog_window: DialogWindowState,
+    /// Internal state of the monster info window.
+    monster_info_window: MonsterInfoWindowState,
+    /// Internal state of the item search window.
+    item_search_window: ItemSearchWindowState,
+    /// Internal state of the emote wheel.
+    emote_wheel: EmoteWheelState,
+    /// Internal state of the minimap window.
+    minimap_window: MinimapWindowState,
 
     /// All entities on the map.
     entities: Vec<Entity>,
-    /// All dead entities on the map.
-    dead_entities: Vec<Entity>,
+    /// Entities that were removed by the server but are still fading out, like
+    /// corpses.
+    despawning_entities: Vec<Entity>,
 
     /// List of all received chat messages.
     chat_messages: Vec<ChatMessage>,
@@ -147,6 +254,20 @@ pub struct ClientState {
     inventory: Inventory,
     /// Player skill tree.
     skill_tree: SkillTree,
+    /// Members of the party the player is in.
+    party: Party,
+    /// Thumbnails of maps by their name. Thumbnails are loaded on demand,
+    /// so maps without an entry are still loading.
+    #[hidden_element]
+    map_thumbnails: HashMap<String, Arc<Texture>>,
+    /// Statistics of the current play session.
+    session_statistics: SessionStatistics,
+    /// Memory used by the subsystems of the client.
+    memory_usage: MemoryUsage,
+    /// State of the roulette mini-game.
+    roulette: RouletteBoard,
+    /// Hypothetical skill build of the skill planner.
+    skill_planner: SkillPlanner,
 
     /// List of all available character servers.
     character_servers: Vec<CharacterServerInformation>,
@@ -160,6 +281,8 @@ pub struct ClientState {
     switch_request: Option<usize>,
     /// Name of the character being created currently.
     create_character_name: String,
+    /// Hair, sex and job of the character being created currently.
+    create_character_options: CharacterCreationOptions,
 
     /// Size of the Korangar window.
     window_size: ScreenSize,
@@ -209,6 +332,26 @@ pub struct ClientState {
     /// Statistics of all caches of the loaders.
     #[cfg(feature = "debug")]
     cache_statistics: CacheStatistics,
+    /// State of the map editor.
+    #[cfg(feature = "debug")]
+    map_editor: MapEditorState,
+    /// State of the asset browser.
+    #[cfg(feature = "debug")]
+    asset_browser: AssetBrowserState,
+    /// State of the effect preview.
+    #[cfg(feature = "debug")]
+    effect_preview: EffectPreviewState,
+    /// State of the model viewer.
+    #[cfg(feature = "debug")]
+    model_viewer: ModelViewerState,
+    /// Internal state of the debug console.
+    #[cfg(feature = "debug")]
+    console_window: ConsoleWindowState,
+    /// Number of dummy entities requested by the `stress_test` console
+    /// command, since the map is not part of the state.
+    #[cfg(feature = "debug")]
+    #[hidden_element]
+    stress_test_request: Option<usize>,
 }
 
 impl ClientState {
@@ -220,7 +363,9 @@ impl ClientState {
         time_phase!("load settings", {
             let mut login_settings = LoginSettings::new();
             let audio_settings = AudioSettings::new();
+            let audio_settings_capabilities = AudioSettingsCapabilities::default();
             let game_settings = GameSettings::new();
+            let game_settings_capabilities = GameSettingsCapabilities::default();
             let interface_settings = InterfaceSettings::new();
             let interface_settings_capabilities = InterfaceSettingsCapabilities::default();
         });
@@ -230,9 +375,13 @@ impl ClientState {
         });
 
         time_phase!("load themes", {
-            let menu_theme = InterfaceTheme::load(InterfaceThemeType::Menu, &interface_settings.menu_theme);
-            let in_game_theme = InterfaceTheme::load(InterfaceThemeType::InGame, &interface_settings.in_game_theme);
-            let world_theme = WorldTheme::load(&interface_settings.world_theme);
+            let mut menu_theme = InterfaceTheme::load(InterfaceThemeType::Menu, &interface_settings.menu_theme);
+            let mut in_game_theme = InterfaceTheme::load(InterfaceThemeType::InGame, &interface_settings.in_game_theme);
+            let mut world_theme = WorldTheme::load(&interface_settings.world_theme);
+
+            menu_theme.apply_accessibility(interface_settings.high_contrast, interface_settings.minimum_font_size);
+            in_game_theme.apply_accessibility(interface_settings.high_contrast, interface_settings.minimum_font_size);
+            world_theme.apply_accessibility(interface_settings.colorblind_mode, interface_settings.minimum_font_size);
         });
 
         time_phase!("create login window state", {
@@ -282,6 +431,7 @@ impl ClientState {
             // TODO: This could be in a single struct.
             let switch_request = None;
             let create_character_name = String::new();
+            let create_character_options = CharacterCreationOptions::default();
         });
 
         time_phase!("create friend list state", {
@@ -291,6 +441,10 @@ impl ClientState {
 
         time_phase!("create player resources", {
             let dialog_window = DialogWindowState::default();
+            let monster_info_window = MonsterInfoWindowState::default();
+            let item_search_window = ItemSearchWindowState::default();
+            let emote_wheel = EmoteWheelState::default();
+            let minimap_window = MinimapWindowState::default();
 
             let shop_items = Vec::default();
             let buy_cart = Vec::default();
@@ -300,6 +454,11 @@ impl ClientState {
             let hotbar = Hotbar::default();
             let inventory = Inventory::default();
             let skill_tree = SkillTree::default();
+            let party = Party::default();
+            let map_thumbnails = HashMap::new();
+            let session_statistics = SessionStatistics::new();
+            let roulette = RouletteBoard::default();
+            let skill_planner = SkillPlanner::default();
         });
 
         time_phase!("create window resources", {
@@ -333,6 +492,16 @@ impl ClientState {
 
         #[cfg(feature = "debug")]
         let cache_statistics = CacheStatistics::default();
+        #[cfg(feature = "debug")]
+        let map_editor = MapEditorState::new();
+        #[cfg(feature = "debug")]
+        let asset_browser = AssetBrowserState::default();
+        #[cfg(feature = "debug")]
+        let effect_preview = EffectPreviewState::default();
+        #[cfg(feature = "debug")]
+        let model_viewer = ModelViewerState::default();
+        #[cfg(feature = "debug")]
+        let console_window = ConsoleWindowState::default();
 
         #[cfg(feature = "debug")]
         debug_timer.stop();
@@ -341,7 +510,9 @@ impl ClientState {
             localization,
             login_settings,
             audio_settings,
+            audio_settings_capabilities,
             game_settings,
+            game_settings_capabilities,
             interface_settings,
             interface_settings_capabilities,
             graphics_settings,
@@ -354,8 +525,12 @@ impl ClientState {
             chat_window,
             friend_list_window,
             dialog_window,
+            monster_info_window,
+            item_search_window,
+            emote_wheel,
+            minimap_window,
             entities: Vec::new(),
-            dead_entities: Vec::new(),
+            despawning_entities: Vec::new(),
             chat_messages,
             friend_list,
             shop_items,
@@ -366,11 +541,18 @@ impl ClientState {
             hotbar,
             inventory,
             skill_tree,
+            party,
+            map_thumbnails,
+            session_statistics,
+            memory_usage: MemoryUsage::default(),
+            roulette,
+            skill_planner,
             character_servers,
             character_slots,
             currently_deleting,
             switch_request,
             create_character_name,
+            create_character_options,
             window_size,
             buffered_attack_entity,
             #[cfg(feature = "debug")]
@@ -393,6 +575,18 @@ impl ClientState {
             packet_history,
             #[cfg(feature = "debug")]
             cache_statistics,
+            #[cfg(feature = "debug")]
+            map_editor,
+            #[cfg(feature = "debug")]
+            asset_browser,
+            #[cfg(feature = "debug")]
+            effect_preview,
+            #[cfg(feature = "debug")]
+            model_viewer,
+            #[cfg(feature = "debug")]
+            console_window,
+            #[cfg(feature = "debug")]
+            stress_test_request: None,
         }
     }
 }
@@ -649,3 +843,43 @@ pub fn prepare_effect_source_inspection(
 
     client_state().inspecting_effect_sources().index(index).manually_asserted()
 }
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use korangar_networking::MessageColor;
+
+    use super::{ChatMessage, ChatMessageAggregation};
+
+    fn message(text: &str) -> ChatMessage {
+        ChatMessage::new(text.to_string(), MessageColor::Server)
+    }
+
+    #[test]
+    fn repeated_messages_are_combined() {
+        let mut aggregation = ChatMessageAggregation::default();
+        let mut chat_messages = Vec::new();
+        let now = Instant::now();
+
+        aggregation.push(&mut chat_messages, message("spam"), Some(0.2), now);
+        aggregation.push(&mut chat_messages, message("spam"), Some(0.2), now + Duration::from_millis(100));
+        aggregation.push(&mut chat_messages, message("spam"), Some(0.2), now + Duration::from_millis(200));
+
+        assert_eq!(chat_messages.len(), 1);
+        assert_eq!(chat_messages[0].text, "spam (x3)");
+    }
+
+    #[test]
+    fn messages_outside_of_window_are_kept() {
+        let mut aggregation = ChatMessageAggregation::default();
+        let mut chat_messages = Vec::new();
+        let now = Instant::now();
+
+        aggregation.push(&mut chat_messages, message("spam"), Some(0.2), now);
+        aggregation.push(&mut chat_messages, message("spam"), Some(0.2), now + Duration::from_millis(500));
+        aggregation.push(&mut chat_messages, message("spam"), None, now + Duration::from_millis(550));
+
+        assert_eq!(chat_messages.len(), 3);
+    }
+}
@@ -0,0 +1,226 @@
+//! State of the model viewer, which renders a single model in isolation and
+//! lists the node hierarchy of its file.
+
+use korangar_collision::AABB;
+use korangar_interface::element::StateElement;
+use ragnarok_formats::model::{ModelData, NodeData};
+use rust_state::RustState;
+
+use crate::world::ModelPreview;
+
+#[derive(RustState, StateElement)]
+pub struct ModelViewerState {
+    /// Path of the model relative to `data\model\`.
+    path: String,
+    show_normals: bool,
+    show_bounding_box: bool,
+    hide_scene: bool,
+    status_text: String,
+    node_texts: Vec<String>,
+    #[hidden_element]
+    preview: Option<ModelPreview>,
+}
+
+impl Default for ModelViewerState {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            show_normals: false,
+            show_bounding_box: true,
+            hide_scene: false,
+            status_text: "No model loaded".to_owned(),
+            node_texts: Vec::new(),
+            preview: None,
+        }
+    }
+}
+
+impl ModelViewerState {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn preview(&self) -> Option<&ModelPreview> {
+        self.preview.as_ref()
+    }
+
+    pub fn shows_normals(&self) -> bool {
+        self.show_normals
+    }
+
+    pub fn shows_bounding_box(&self) -> bool {
+        self.show_bounding_box
+    }
+
+    /// Whether the rest of the scene should be hidden, so only the model is
+    /// visible.
+    pub fn hides_scene(&self) -> bool {
+        self.hide_scene && self.preview.is_some()
+    }
+
+    pub fn set_preview(&mut self, preview: ModelPreview) {
+        let model_data = &preview.model.model_data;
+        let size = preview.model.bounding_box.size();
+
+        self.status_text = format!(
+            "RSM {}, {} nodes, {} textures, animation length {}, {}, size ({:.1}, {:.1}, {:.1})",
+            preview.model.version,
+            model_data.nodes.len(),
+            model_data.texture_names.len(),
+            model_data.animation_length,
+            match preview.model.is_static {
+                true => "static",
+                false => "animated",
+            },
+            size.x,
+            size.y,
+            size.z
+        );
+        self.node_texts = describe_nodes(model_data);
+        self.preview = Some(preview);
+    }
+
+    /// Show why the model couldn't be loaded.
+    pub fn set_error(&mut self, error: String) {
+        self.status_text = error;
+        self.node_texts.clear();
+        self.preview = None;
+    }
+}
+
+/// Names of the root nodes, which changed to a list in version 2.2.
+fn root_node_names(model_data: &ModelData) -> Vec<&str> {
+    match &model_data.root_node_name {
+        Some(root_node_name) => vec![root_node_name.as_ref()],
+        None => model_data.root_node_names.iter().map(|name| name.as_ref()).collect(),
+    }
+}
+
+/// Order the nodes depth first, starting from the root nodes. Returns the
+/// index and depth of every node that is reachable from a root node, so
+/// broken parent references show up as missing nodes.
+fn hierarchy_order(node_names: &[(&str, &str)], root_names: &[&str]) -> Vec<(usize, usize)> {
+    fn visit(node_names: &[(&str, &str)], index: usize, depth: usize, visited: &mut [bool], order: &mut Vec<(usize, usize)>) {
+        // Guard against cycles in the hierarchy.
+        if visited[index] {
+            return;
+        }
+
+        visited[index] = true;
+        order.push((index, depth));
+
+        let name = node_names[index].0;
+        for (child_index, (_, parent_name)) in node_names.iter().enumerate() {
+            if *parent_name == name {
+                visit(node_names, child_index, depth + 1, visited, order);
+            }
+        }
+    }
+
+    let mut visited = vec![false; node_names.len()];
+    let mut order = Vec::with_capacity(node_names.len());
+
+    for root_name in root_names {
+        if let Some(index) = node_names.iter().position(|(name, _)| name == root_name) {
+            visit(node_names, index, 0, &mut visited, &mut order);
+        }
+    }
+
+    order
+}
+
+fn describe_nodes(model_data: &ModelData) -> Vec<String> {
+    let node_names: Vec<(&str, &str)> = model_data
+        .nodes
+        .iter()
+        .map(|node| (node.node_name.as_ref(), node.parent_node_name.as_ref()))
+        .collect();
+    let order = hierarchy_order(&node_names, &root_node_names(model_data));
+
+    let mut texts: Vec<String> = order
+        .iter()
+        .flat_map(|(index, depth)| describe_node(&model_data.nodes[*index], *depth))
+        .collect();
+
+    let unreachable_count = model_data.nodes.len() - order.len();
+    if unreachable_count > 0 {
+        texts.push(format!("{unreachable_count} nodes are not connected to a root node"));
+    }
+
+    texts
+}
+
+fn describe_node(node: &NodeData, depth: usize) -> [String; 3] {
+    let indentation = "    ".repeat(depth);
+
+    let mut transform = format!(
+        "{indentation}    position ({:.2}, {:.2}, {:.2})",
+        node.translation2.x, node.translation2.y, node.translation2.z
+    );
+
+    if let Some(offset) = node.translation1 {
+        transform += &format!(", offset ({:.2}, {:.2}, {:.2})", offset.x, offset.y, offset.z);
+    }
+
+    if let (Some(angle), Some(axis)) = (node.rotation_angle, node.rotation_axis) {
+        transform += &format!(
+            ", rotation {:.1}° around ({:.2}, {:.2}, {:.2})",
+            angle.to_degrees(),
+            axis.x,
+            axis.y,
+            axis.z
+        );
+    }
+
+    if let Some(scale) = node.scale {
+        transform += &format!(", scale ({:.2}, {:.2}, {:.2})", scale.x, scale.y, scale.z);
+    }
+
+    [
+        format!(
+            "{indentation}{}: {} vertices, {} faces, {} rotation / {} translation / {} scale keyframes",
+            node.node_name.as_ref(),
+            node.vertex_positions.len(),
+            node.faces.len(),
+            node.rotation_keyframes.len(),
+            node.translation_keyframes.len(),
+            node.scale_keyframes.len()
+        ),
+        transform,
+        describe_bounds(node, &indentation),
+    ]
+}
+
+/// Bounding box of the node in its local space.
+fn describe_bounds(node: &NodeData, indentation: &str) -> String {
+    if node.vertex_positions.is_empty() {
+        return format!("{indentation}    no vertices");
+    }
+
+    let bounding_box = AABB::from_vertices(node.vertex_positions.iter().copied());
+    let (minimum, maximum) = (bounding_box.min(), bounding_box.max());
+
+    format!(
+        "{indentation}    bounds ({:.2}, {:.2}, {:.2}) to ({:.2}, {:.2}, {:.2})",
+        minimum.x, minimum.y, minimum.z, maximum.x, maximum.y, maximum.z
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::hierarchy_order;
+
+    #[test]
+    fn nodes_are_ordered_depth_first() {
+        let node_names = [("wheel", "cart"), ("cart", ""), ("handle", "cart"), ("spoke", "wheel")];
+
+        assert_eq!(hierarchy_order(&node_names, &["cart"]), [(1, 0), (0, 1), (3, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn cycles_and_unreachable_nodes_are_skipped() {
+        let node_names = [("root", "root"), ("first", "second"), ("second", "first")];
+
+        assert_eq!(hierarchy_order(&node_names, &["root"]), [(0, 0)]);
+    }
+}
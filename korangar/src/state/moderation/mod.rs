@@ -0,0 +1,78 @@
+//! Client side chat moderation. Messages of muted players and public chat
+//! messages that match one of the filters are dropped before they reach the
+//! chat window. Both lists are stored in the game settings.
+
+use regex::{Regex, RegexBuilder};
+
+/// Split a public chat message into the name of the sender and the message.
+/// The server sends them in the form `name : message`.
+fn split_public_message(text: &str) -> Option<(&str, &str)> {
+    text.split_once(" : ")
+}
+
+#[derive(Default)]
+pub struct ChatModeration {
+    filters: Vec<Regex>,
+}
+
+impl ChatModeration {
+    /// Compile the saved filters. Patterns that are not valid anymore are
+    /// skipped.
+    pub fn new(patterns: &[String]) -> Self {
+        let filters = patterns.iter().filter_map(|pattern| compile_filter(pattern).ok()).collect();
+
+        Self { filters }
+    }
+
+    pub fn add_filter(&mut self, pattern: &str) -> Result<(), regex::Error> {
+        if !self.filters.iter().any(|filter| filter.as_str() == pattern) {
+            self.filters.push(compile_filter(pattern)?);
+        }
+
+        Ok(())
+    }
+
+    pub fn remove_filter(&mut self, pattern: &str) -> bool {
+        let filter_count = self.filters.len();
+        self.filters.retain(|filter| filter.as_str() != pattern);
+        self.filters.len() != filter_count
+    }
+
+    /// Whether a public chat message should be hidden, either because the
+    /// sender is muted or because the message matches a filter.
+    pub fn is_hidden(&self, muted_players: &[String], text: &str) -> bool {
+        let Some((sender, message)) = split_public_message(text) else {
+            return false;
+        };
+
+        muted_players.iter().any(|muted_player| muted_player == sender) || self.filters.iter().any(|filter| filter.is_match(message))
+    }
+}
+
+/// Filters match case insensitive, so a plain keyword catches all spellings.
+fn compile_filter(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern).case_insensitive(true).build()
+}
+
+#[cfg(test)]
+mod test {
+    use super::ChatModeration;
+
+    #[test]
+    fn hides_muted_players_and_filtered_messages() {
+        let mut moderation = ChatModeration::new(&["zeny[^a-z]*shop".to_owned(), "(".to_owned()]);
+        moderation.add_filter("casino").unwrap();
+        let muted_players = vec!["Spammer".to_owned()];
+
+        assert!(moderation.is_hidden(&muted_players, "Spammer : hello"));
+        assert!(moderation.is_hidden(&muted_players, "Seller : cheap ZENY - SHOP here"));
+        assert!(moderation.is_hidden(&muted_players, "Seller : visit the Casino"));
+        assert!(!moderation.is_hidden(&muted_players, "Friend : hello"));
+        // Server messages don't have a sender and are never hidden.
+        assert!(!moderation.is_hidden(&muted_players, "casino event starts now"));
+
+        assert!(moderation.remove_filter("casino"));
+        assert!(!moderation.is_hidden(&muted_players, "Seller : visit the Casino"));
+        assert!(moderation.add_filter("(").is_err());
+    }
+}
@@ -0,0 +1,229 @@
+//! Members of the party the player is in. The map server identifies party
+//! members by their account in all party packets, so members are tracked by
+//! account as well.
+
+use cgmath::{InnerSpace, Vector2};
+use korangar_interface::element::StateElement;
+use ragnarok_packets::{AccountId, TilePosition};
+use rust_state::RustState;
+
+use crate::graphics::{Color, ScreenPosition, ScreenSize};
+
+/// Colors of the party members on the minimap and the off-screen indicators.
+/// Every member keeps their color until they leave the party.
+// TODO: Put this in the theme
+const MEMBER_COLORS: [Color; 12] = [
+    Color::rgb_u8(240, 80, 80),
+    Color::rgb_u8(80, 160, 240),
+    Color::rgb_u8(120, 220, 90),
+    Color::rgb_u8(240, 200, 60),
+    Color::rgb_u8(200, 100, 230),
+    Color::rgb_u8(60, 220, 210),
+    Color::rgb_u8(250, 140, 50),
+    Color::rgb_u8(240, 120, 190),
+    Color::rgb_u8(170, 230, 170),
+    Color::rgb_u8(150, 150, 250),
+    Color::rgb_u8(210, 170, 120),
+    Color::rgb_u8(230, 230, 230),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartyMember {
+    pub account_id: AccountId,
+    pub name: String,
+    /// Position on the map the player is on. `None` if the member is on a
+    /// different map or offline.
+    pub position: Option<TilePosition>,
+    pub health_points: u32,
+    pub maximum_health_points: u32,
+    pub color: Color,
+}
+
+#[derive(Default, RustState, StateElement)]
+pub struct Party {
+    /// Members of the party, not including the player.
+    #[hidden_element]
+    members: Vec<PartyMember>,
+}
+
+impl Party {
+    pub fn members(&self) -> &[PartyMember] {
+        &self.members
+    }
+
+    fn member_mut(&mut self, account_id: AccountId) -> &mut PartyMember {
+        if let Some(index) = self.members.iter().position(|member| member.account_id == account_id) {
+            return &mut self.members[index];
+        }
+
+        // Pick the first color that isn't used yet, so members that stay in the party
+        // don't change their color when someone else leaves.
+        let color = MEMBER_COLORS
+            .iter()
+            .copied()
+            .find(|color| !self.members.iter().any(|member| member.color == *color))
+            .unwrap_or(MEMBER_COLORS[self.members.len() % MEMBER_COLORS.len()]);
+
+        self.members.push(PartyMember {
+            account_id,
+            name: String::new(),
+            position: None,
+            health_points: 0,
+            maximum_health_points: 0,
+            color,
+        });
+
+        self.members.last_mut().unwrap()
+    }
+
+    pub fn update_member(&mut self, account_id: AccountId, name: String, position: Option<TilePosition>) {
+        let member = self.member_mut(account_id);
+
+        member.name = name;
+        member.position = position;
+    }
+
+    /// Position updates can arrive before the information about the member,
+    /// so unknown members are added without a name.
+    pub fn update_position(&mut self, account_id: AccountId, position: TilePosition) {
+        self.member_mut(account_id).position = Some(position);
+    }
+
+    pub fn update_health(&mut self, account_id: AccountId, health_points: u32, maximum_health_points: u32) {
+        let member = self.member_mut(account_id);
+
+        member.health_points = health_points;
+        member.maximum_health_points = maximum_health_points;
+    }
+
+    pub fn remove_member(&mut self, account_id: AccountId) {
+        self.members.retain(|member| member.account_id != account_id);
+    }
+
+    /// Forget all positions when the player changes maps. The map server
+    /// sends the positions of the members on the new map again.
+    pub fn clear_positions(&mut self) {
+        self.members.iter_mut().for_each(|member| member.position = None);
+    }
+
+    pub fn clear(&mut self) {
+        self.members.clear();
+    }
+}
+
+/// Indicator at the edge of the screen, pointing towards a party member that
+/// is off-screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OffScreenIndicator {
+    pub position: ScreenPosition,
+    /// Normalized direction from the center of the screen to the member.
+    pub direction: Vector2<f32>,
+}
+
+/// Indicator for a party member at `screen_position`, clamped to the edges of
+/// the screen with a `margin`. Positions behind the camera are mirrored when
+/// projected, so `behind_camera` flips them back. Returns `None` if the member
+/// is on screen.
+pub fn off_screen_indicator(
+    screen_position: ScreenPosition,
+    behind_camera: bool,
+    screen_size: ScreenSize,
+    margin: f32,
+) -> Option<OffScreenIndicator> {
+    let center = Vector2::new(screen_size.width / 2.0, screen_size.height / 2.0);
+    let mut offset = Vector2::new(screen_position.left - center.x, screen_position.top - center.y);
+
+    if !behind_camera && offset.x.abs() <= center.x && offset.y.abs() <= center.y {
+        return None;
+    }
+
+    if behind_camera {
+        offset = -offset;
+    }
+
+    // Directly behind the camera there is no direction to point in, so we point
+    // down, towards the camera.
+    if offset.x == 0.0 && offset.y == 0.0 {
+        offset = Vector2::new(0.0, 1.0);
+    }
+
+    let bounds = Vector2::new((center.x - margin).max(0.0), (center.y - margin).max(0.0));
+    let scale = (bounds.x / offset.x.abs()).min(bounds.y / offset.y.abs());
+    let position = center + offset * scale;
+
+    Some(OffScreenIndicator {
+        position: ScreenPosition {
+            left: position.x,
+            top: position.y,
+        },
+        direction: offset.normalize(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use ragnarok_packets::{AccountId, TilePosition};
+
+    use super::{Party, ScreenPosition, ScreenSize, off_screen_indicator};
+
+    const SCREEN_SIZE: ScreenSize = ScreenSize {
+        width: 800.0,
+        height: 600.0,
+    };
+
+    #[test]
+    fn members_keep_their_color() {
+        let mut party = Party::default();
+
+        party.update_member(AccountId(1), "First".to_owned(), None);
+        party.update_member(AccountId(2), "Second".to_owned(), None);
+        party.update_member(AccountId(3), "Third".to_owned(), None);
+
+        let third_color = party.members()[2].color;
+
+        party.remove_member(AccountId(2));
+        party.update_position(AccountId(3), TilePosition { x: 10, y: 12 });
+        party.update_member(AccountId(4), "Fourth".to_owned(), None);
+
+        assert_eq!(party.members()[1].color, third_color);
+        assert_ne!(party.members()[2].color, party.members()[0].color);
+        assert_ne!(party.members()[2].color, third_color);
+    }
+
+    #[test]
+    fn unknown_members_are_added_on_update() {
+        let mut party = Party::default();
+
+        party.update_health(AccountId(7), 50, 100);
+        party.update_member(AccountId(7), "Member".to_owned(), Some(TilePosition { x: 1, y: 2 }));
+
+        assert_eq!(party.members().len(), 1);
+        assert_eq!(party.members()[0].name, "Member");
+        assert_eq!(party.members()[0].health_points, 50);
+
+        party.clear_positions();
+        assert_eq!(party.members()[0].position, None);
+    }
+
+    #[test]
+    fn indicator_is_clamped_to_edge() {
+        let on_screen = ScreenPosition { left: 100.0, top: 500.0 };
+        assert_eq!(off_screen_indicator(on_screen, false, SCREEN_SIZE, 20.0), None);
+
+        let indicator = off_screen_indicator(ScreenPosition { left: 1920.0, top: 300.0 }, false, SCREEN_SIZE, 20.0).unwrap();
+        assert_eq!(indicator.position, ScreenPosition { left: 780.0, top: 300.0 });
+        assert_eq!(indicator.direction.x, 1.0);
+
+        let indicator = off_screen_indicator(ScreenPosition { left: 400.0, top: -820.0 }, false, SCREEN_SIZE, 20.0).unwrap();
+        assert_eq!(indicator.position, ScreenPosition { left: 400.0, top: 20.0 });
+    }
+
+    #[test]
+    fn indicator_behind_camera_is_mirrored() {
+        // Behind the camera even a position that projects onto the screen is
+        // off-screen, in the opposite direction.
+        let indicator = off_screen_indicator(ScreenPosition { left: 400.0, top: 200.0 }, true, SCREEN_SIZE, 20.0).unwrap();
+
+        assert_eq!(indicator.position, ScreenPosition { left: 400.0, top: 580.0 });
+    }
+}
@@ -0,0 +1,133 @@
+//! State of the roulette mini-game. The board has a row of prizes for every
+//! step. Each spin moves the player to a prize on the next row, until they
+//! either lose or claim the prize they are on.
+
+use korangar_interface::element::StateElement;
+use ragnarok_packets::RouletteItemInformation;
+use rust_state::RustState;
+
+/// Points used to spin the roulette.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, RustState, StateElement)]
+pub struct RoulettePoints {
+    pub gold: u32,
+    pub silver: u32,
+    pub bronze: u32,
+}
+
+#[derive(Default, RustState, StateElement)]
+pub struct RouletteBoard {
+    is_open: bool,
+    serial: u32,
+    items: Vec<RouletteItemInformation>,
+    /// Row of the board the player is on.
+    step: u16,
+    /// Position in the row the player is on.
+    index: u16,
+    points: RoulettePoints,
+    /// The player won the prize at the current position and hasn't claimed
+    /// it yet.
+    has_prize: bool,
+}
+
+impl RouletteBoard {
+    pub fn open(&mut self, serial: u32, step: u16, index: u16, points: RoulettePoints) {
+        // The items only need to be requested again if the board changed.
+        if serial != self.serial {
+            self.items.clear();
+        }
+
+        self.is_open = true;
+        self.serial = serial;
+        self.step = step;
+        self.index = index;
+        self.points = points;
+    }
+
+    /// Check if the items of the board need to be requested from the server.
+    pub fn needs_items(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn set_items(&mut self, serial: u32, items: Vec<RouletteItemInformation>) {
+        self.serial = serial;
+        self.items = items;
+    }
+
+    pub fn spun(&mut self, won: bool, step: u16, index: u16, points: RoulettePoints) {
+        self.step = step;
+        self.index = index;
+        self.points = points;
+        self.has_prize = won;
+    }
+
+    pub fn reward_claimed(&mut self) {
+        self.has_prize = false;
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.has_prize = false;
+    }
+
+    /// The prize that can be claimed, if the last spin was won.
+    pub fn prize(&self) -> Option<&RouletteItemInformation> {
+        if !self.has_prize {
+            return None;
+        }
+
+        self.items.iter().find(|item| item.row == self.step && item.position == self.index)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ragnarok_packets::{ItemId, RouletteItemInformation};
+
+    use super::{RouletteBoard, RoulettePoints};
+
+    fn item(row: u16, position: u16, item_id: u32) -> RouletteItemInformation {
+        RouletteItemInformation {
+            row,
+            position,
+            item_id: ItemId(item_id),
+            count: 1,
+        }
+    }
+
+    #[test]
+    fn prize_is_item_at_current_position() {
+        let mut board = RouletteBoard::default();
+        let points = RoulettePoints {
+            gold: 0,
+            silver: 0,
+            bronze: 3,
+        };
+
+        board.open(1, 0, 0, points);
+        board.set_items(1, vec![item(0, 0, 501), item(1, 0, 502), item(1, 1, 503)]);
+        assert_eq!(board.prize(), None);
+
+        board.spun(true, 1, 1, RoulettePoints { bronze: 2, ..points });
+        assert_eq!(board.prize(), Some(&item(1, 1, 503)));
+        assert_eq!(board.points.bronze, 2);
+
+        board.reward_claimed();
+        assert_eq!(board.prize(), None);
+    }
+
+    #[test]
+    fn items_are_kept_for_the_same_board() {
+        let mut board = RouletteBoard::default();
+
+        board.open(1, 0, 0, RoulettePoints::default());
+        assert!(board.needs_items());
+
+        board.set_items(1, vec![item(0, 0, 501)]);
+        board.close();
+        board.open(1, 0, 0, RoulettePoints::default());
+        assert!(!board.needs_items());
+
+        board.open(2, 0, 0, RoulettePoints::default());
+        assert!(board.needs_items());
+    }
+}
@@ -0,0 +1,310 @@
+//! Statistics of the current play session, like the experience and zeny
+//! gained per hour. The statistics are accumulated from gameplay events and
+//! can be reset and exported at any time.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use hashbrown::HashSet;
+use korangar_interface::element::StateElement;
+use ragnarok_packets::{EntityId, StatType};
+use rust_state::RustState;
+
+/// Time between updates of the displayed statistics in seconds.
+const TEXT_UPDATE_INTERVAL: f64 = 1.0;
+/// Minimum duration that hourly rates are calculated over in seconds. This
+/// avoids absurd rates right after the session was started.
+const MINIMUM_RATE_DURATION: f64 = 60.0;
+
+/// Tracks the gained experience of one kind, since the server only sends the
+/// absolute experience of the current level.
+#[derive(Default)]
+pub struct ExperienceCounter {
+    level: Option<u32>,
+    experience: Option<u64>,
+    next_experience: u64,
+    /// Experience that was missing to the next level at the last update.
+    remaining_experience: u64,
+    leveled_up: bool,
+    gained: i64,
+}
+
+impl ExperienceCounter {
+    fn update_level(&mut self, level: u32) {
+        if self.level.is_some_and(|previous| level > previous) {
+            self.leveled_up = true;
+        }
+
+        self.level = Some(level);
+    }
+
+    fn update_next_experience(&mut self, next_experience: u64) {
+        self.next_experience = next_experience;
+    }
+
+    fn update_experience(&mut self, experience: u64) {
+        if let Some(previous) = self.experience {
+            self.gained += match experience < previous && self.leveled_up {
+                // The experience resets on level up, so the experience that was missing to the
+                // previous level was gained as well.
+                true => (self.remaining_experience + experience) as i64,
+                // Experience can also be lost, for example when dying.
+                false => experience as i64 - previous as i64,
+            };
+        }
+
+        self.experience = Some(experience);
+        self.remaining_experience = self.next_experience.saturating_sub(experience);
+        self.leveled_up = false;
+    }
+
+    /// Forget the values of the last character, so switching characters
+    /// doesn't count as experience gained.
+    fn forget_character(&mut self) {
+        *self = Self {
+            gained: self.gained,
+            ..Self::default()
+        };
+    }
+}
+
+#[derive(Default, RustState, StateElement)]
+pub struct SessionStatistics {
+    session_time_text: String,
+    base_experience_text: String,
+    job_experience_text: String,
+    zeny_text: String,
+    kill_count_text: String,
+    kills_text: String,
+    looted_item_count_text: String,
+    looted_items_text: String,
+    #[hidden_element]
+    elapsed_time: f64,
+    #[hidden_element]
+    last_update: f64,
+    #[hidden_element]
+    base_experience: ExperienceCounter,
+    #[hidden_element]
+    job_experience: ExperienceCounter,
+    #[hidden_element]
+    zeny: Option<u32>,
+    #[hidden_element]
+    zeny_gained: i64,
+    /// Entities that the player attacked. Only these count as kills when they
+    /// die.
+    #[hidden_element]
+    attacked_entities: HashSet<EntityId>,
+    /// Number of kills by monster name.
+    #[hidden_element]
+    kills: BTreeMap<String, u32>,
+    /// Number of looted items by item name.
+    #[hidden_element]
+    looted_items: BTreeMap<String, u32>,
+}
+
+impl SessionStatistics {
+    pub fn new() -> Self {
+        let mut statistics = Self::default();
+        statistics.update_texts();
+        statistics
+    }
+
+    pub fn update(&mut self, delta_time: f64) {
+        self.elapsed_time += delta_time;
+        self.last_update += delta_time;
+
+        if self.last_update >= TEXT_UPDATE_INTERVAL {
+            self.last_update = 0.0;
+            self.update_texts();
+        }
+    }
+
+    /// Start a new session. The current experience and zeny stay the starting
+    /// point for the new session.
+    pub fn reset(&mut self) {
+        self.elapsed_time = 0.0;
+        self.base_experience.gained = 0;
+        self.job_experience.gained = 0;
+        self.zeny_gained = 0;
+        self.kills.clear();
+        self.looted_items.clear();
+        self.update_texts();
+    }
+
+    /// Called when a character is selected, so the values of the previous
+    /// character are not compared against the new one.
+    pub fn forget_character(&mut self) {
+        self.base_experience.forget_character();
+        self.job_experience.forget_character();
+        self.zeny = None;
+        self.attacked_entities.clear();
+    }
+
+    pub fn update_stat(&mut self, stat_type: &StatType) {
+        match *stat_type {
+            StatType::BaseLevel(level) => self.base_experience.update_level(level),
+            StatType::JobLevel(level) => self.job_experience.update_level(level),
+            StatType::BaseExperience(experience) => self.base_experience.update_experience(experience),
+            StatType::JobExperience(experience) => self.job_experience.update_experience(experience),
+            StatType::NextBaseExperience(experience) => self.base_experience.update_next_experience(experience),
+            StatType::NextJobExperience(experience) => self.job_experience.update_next_experience(experience),
+            StatType::Zeny(zeny) => {
+                if let Some(previous) = self.zeny {
+                    self.zeny_gained += zeny as i64 - previous as i64;
+                }
+
+                self.zeny = Some(zeny);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn register_attack(&mut self, entity_id: EntityId) {
+        self.attacked_entities.insert(entity_id);
+    }
+
+    /// Called when a monster disappears. Only monsters that died after being
+    /// attacked by the player count as kills.
+    pub fn register_disappearance(&mut self, entity_id: EntityId, died: bool, monster_name: &str) {
+        if self.attacked_entities.remove(&entity_id) && died {
+            *self.kills.entry(monster_name.to_string()).or_default() += 1;
+        }
+    }
+
+    /// Called when the player changes the map, since none of the attacked
+    /// entities can die anymore.
+    pub fn clear_attacked_entities(&mut self) {
+        self.attacked_entities.clear();
+    }
+
+    pub fn register_loot(&mut self, item_name: &str, amount: u32) {
+        *self.looted_items.entry(item_name.to_string()).or_default() += amount;
+    }
+
+    fn hourly_rate(&self, value: i64) -> i64 {
+        (value as f64 * 3600.0 / self.elapsed_time.max(MINIMUM_RATE_DURATION)) as i64
+    }
+
+    fn update_texts(&mut self) {
+        let seconds = self.elapsed_time as u64;
+        self.session_time_text = format!("{}:{:02}:{:02}", seconds / 3600, (seconds / 60) % 60, seconds % 60);
+
+        let base_experience = self.base_experience.gained;
+        let job_experience = self.job_experience.gained;
+
+        self.base_experience_text = format!("{base_experience} ({}/h)", self.hourly_rate(base_experience));
+        self.job_experience_text = format!("{job_experience} ({}/h)", self.hourly_rate(job_experience));
+        self.zeny_text = format!("{:+} ({:+}/h)", self.zeny_gained, self.hourly_rate(self.zeny_gained));
+
+        self.kill_count_text = self.kills.values().sum::<u32>().to_string();
+        self.kills_text = list_text(&self.kills);
+        self.looted_item_count_text = self.looted_items.values().sum::<u32>().to_string();
+        self.looted_items_text = list_text(&self.looted_items);
+    }
+
+    /// Export the statistics as CSV with one value per line.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("category,name,value\n");
+
+        let rows = [
+            ("session", "duration_seconds", self.elapsed_time as i64),
+            ("experience", "base", self.base_experience.gained),
+            ("experience", "base_per_hour", self.hourly_rate(self.base_experience.gained)),
+            ("experience", "job", self.job_experience.gained),
+            ("experience", "job_per_hour", self.hourly_rate(self.job_experience.gained)),
+            ("zeny", "gained", self.zeny_gained),
+            ("zeny", "gained_per_hour", self.hourly_rate(self.zeny_gained)),
+        ];
+
+        for (category, name, value) in rows {
+            let _ = writeln!(csv, "{category},{name},{value}");
+        }
+
+        for (name, count) in &self.kills {
+            let _ = writeln!(csv, "kills,{},{count}", escape_csv(name));
+        }
+
+        for (name, amount) in &self.looted_items {
+            let _ = writeln!(csv, "loot,{},{amount}", escape_csv(name));
+        }
+
+        csv
+    }
+}
+
+/// One line per entry, sorted by name.
+fn list_text(entries: &BTreeMap<String, u32>) -> String {
+    entries
+        .iter()
+        .map(|(name, count)| format!("{count}x {name}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn escape_csv(field: &str) -> String {
+    match field.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ragnarok_packets::{EntityId, StatType};
+
+    use super::SessionStatistics;
+
+    #[test]
+    fn experience_is_tracked_across_level_ups() {
+        let mut statistics = SessionStatistics::new();
+
+        statistics.update_stat(&StatType::BaseLevel(10));
+        statistics.update_stat(&StatType::NextBaseExperience(1000));
+        statistics.update_stat(&StatType::BaseExperience(900));
+        statistics.update_stat(&StatType::BaseExperience(950));
+
+        // The server sends the new level and requirement before resetting the
+        // experience.
+        statistics.update_stat(&StatType::BaseLevel(11));
+        statistics.update_stat(&StatType::NextBaseExperience(2000));
+        statistics.update_stat(&StatType::BaseExperience(30));
+
+        assert_eq!(statistics.base_experience.gained, 50 + 50 + 30);
+    }
+
+    #[test]
+    fn experience_loss_is_subtracted() {
+        let mut statistics = SessionStatistics::new();
+
+        statistics.update_stat(&StatType::JobExperience(500));
+        statistics.update_stat(&StatType::JobExperience(400));
+
+        assert_eq!(statistics.job_experience.gained, -100);
+    }
+
+    #[test]
+    fn only_attacked_monsters_count_as_kills() {
+        let mut statistics = SessionStatistics::new();
+
+        statistics.register_attack(EntityId(1));
+        statistics.register_disappearance(EntityId(1), true, "PORING");
+        statistics.register_disappearance(EntityId(2), true, "PORING");
+
+        assert_eq!(statistics.kills.get("PORING"), Some(&1));
+    }
+
+    #[test]
+    fn csv_escapes_names() {
+        let mut statistics = SessionStatistics::new();
+
+        statistics.update_stat(&StatType::Zeny(100));
+        statistics.update_stat(&StatType::Zeny(250));
+        statistics.register_loot("Apple, Red", 3);
+
+        let csv = statistics.to_csv();
+
+        assert!(csv.starts_with("category,name,value\n"));
+        assert!(csv.contains("zeny,gained,150\n"));
+        assert!(csv.contains("loot,\"Apple, Red\",3\n"));
+    }
+}
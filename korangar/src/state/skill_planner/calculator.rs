@@ -0,0 +1,174 @@
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+use korangar_interface::element::StateElement;
+use rust_state::RustState;
+
+use super::SkillPlan;
+use crate::world::{CombatGear, CombatStats, DerivedCombatStats, JobSkillTree};
+
+/// Value of the stat calculator that can be adjusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlannerStat {
+    BaseLevel,
+    Strength,
+    Agility,
+    Vitality,
+    Intelligence,
+    Dexterity,
+    Luck,
+    WeaponAttack,
+    EquipmentAttack,
+    MagicAttack,
+    BaseAspd,
+}
+
+impl PlannerStat {
+    pub const ALL: [Self; 11] = [
+        Self::BaseLevel,
+        Self::Strength,
+        Self::Agility,
+        Self::Vitality,
+        Self::Intelligence,
+        Self::Dexterity,
+        Self::Luck,
+        Self::WeaponAttack,
+        Self::EquipmentAttack,
+        Self::MagicAttack,
+        Self::BaseAspd,
+    ];
+
+    fn step(self) -> u16 {
+        match self {
+            Self::WeaponAttack | Self::EquipmentAttack | Self::MagicAttack => 5,
+            _ => 1,
+        }
+    }
+
+    fn range(self) -> RangeInclusive<u16> {
+        match self {
+            Self::BaseLevel => 1..=99,
+            Self::Strength | Self::Agility | Self::Vitality | Self::Intelligence | Self::Dexterity | Self::Luck => 1..=130,
+            Self::WeaponAttack | Self::EquipmentAttack | Self::MagicAttack => 0..=1000,
+            Self::BaseAspd => 140..=160,
+        }
+    }
+}
+
+/// Estimates the combat values of hypothetical stats and gear.
+#[derive(RustState, StateElement)]
+pub struct StatCalculator {
+    attack_text: String,
+    magic_attack_text: String,
+    hit_text: String,
+    flee_text: String,
+    aspd_text: String,
+    skill_damage_text: String,
+    #[hidden_element]
+    stats: CombatStats,
+    #[hidden_element]
+    gear: CombatGear,
+    /// Displayed values of the adjustable stats, in the order of
+    /// [`PlannerStat::ALL`].
+    #[hidden_element]
+    stat_texts: Vec<String>,
+}
+
+impl Default for StatCalculator {
+    fn default() -> Self {
+        let mut calculator = Self {
+            attack_text: String::new(),
+            magic_attack_text: String::new(),
+            hit_text: String::new(),
+            flee_text: String::new(),
+            aspd_text: String::new(),
+            skill_damage_text: String::new(),
+            stats: CombatStats::default(),
+            gear: CombatGear::default(),
+            stat_texts: Vec::new(),
+        };
+
+        calculator.update_stat_texts();
+        calculator
+    }
+}
+
+impl StatCalculator {
+    fn value(&self, stat: PlannerStat) -> u16 {
+        match stat {
+            PlannerStat::BaseLevel => self.stats.base_level,
+            PlannerStat::Strength => self.stats.strength,
+            PlannerStat::Agility => self.stats.agility,
+            PlannerStat::Vitality => self.stats.vitality,
+            PlannerStat::Intelligence => self.stats.intelligence,
+            PlannerStat::Dexterity => self.stats.dexterity,
+            PlannerStat::Luck => self.stats.luck,
+            PlannerStat::WeaponAttack => self.gear.weapon_attack,
+            PlannerStat::EquipmentAttack => self.gear.equipment_attack,
+            PlannerStat::MagicAttack => self.gear.magic_attack,
+            PlannerStat::BaseAspd => self.gear.base_aspd,
+        }
+    }
+
+    fn value_mut(&mut self, stat: PlannerStat) -> &mut u16 {
+        match stat {
+            PlannerStat::BaseLevel => &mut self.stats.base_level,
+            PlannerStat::Strength => &mut self.stats.strength,
+            PlannerStat::Agility => &mut self.stats.agility,
+            PlannerStat::Vitality => &mut self.stats.vitality,
+            PlannerStat::Intelligence => &mut self.stats.intelligence,
+            PlannerStat::Dexterity => &mut self.stats.dexterity,
+            PlannerStat::Luck => &mut self.stats.luck,
+            PlannerStat::WeaponAttack => &mut self.gear.weapon_attack,
+            PlannerStat::EquipmentAttack => &mut self.gear.equipment_attack,
+            PlannerStat::MagicAttack => &mut self.gear.magic_attack,
+            PlannerStat::BaseAspd => &mut self.gear.base_aspd,
+        }
+    }
+
+    /// Raise or lower a stat by one step, staying in its valid range.
+    pub fn adjust(&mut self, stat: PlannerStat, raise: bool) {
+        let range = stat.range();
+        let value = self.value_mut(stat);
+
+        *value = match raise {
+            true => value.saturating_add(stat.step()),
+            false => value.saturating_sub(stat.step()),
+        }
+        .clamp(*range.start(), *range.end());
+
+        self.update_stat_texts();
+    }
+
+    fn update_stat_texts(&mut self) {
+        self.stat_texts = PlannerStat::ALL.iter().map(|&stat| self.value(stat).to_string()).collect();
+    }
+
+    /// Recalculate the combat values, including the damage of every planned
+    /// offensive skill.
+    pub fn update(&mut self, skill_tree: &JobSkillTree, plan: &SkillPlan) {
+        let derived = DerivedCombatStats::calculate(&self.stats, &self.gear);
+
+        self.attack_text = derived.attack.to_string();
+        self.magic_attack_text = derived.magic_attack.to_string();
+        self.hit_text = derived.hit.to_string();
+        self.flee_text = derived.flee.to_string();
+        self.aspd_text = format!("{:.1} ({:.2}/s)", derived.aspd, derived.attacks_per_second());
+
+        self.skill_damage_text.clear();
+        for skill in &skill_tree.skills {
+            let level = plan.level(skill.skill_id);
+
+            if let Some(damage) = &skill.damage
+                && level > 0
+            {
+                let _ = writeln!(
+                    self.skill_damage_text,
+                    "{} {level}: {}",
+                    skill.name,
+                    derived.skill_damage(damage, level)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,397 @@
+//! Planning of hypothetical skill builds. All computations happen on the
+//! client using the bundled skill trees, so nothing is sent to the server.
+
+mod calculator;
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use korangar_interface::element::StateElement;
+use rust_state::RustState;
+
+pub use self::calculator::{PlannerStat, StatCalculator};
+use crate::world::JobSkillTree;
+
+/// Separates the job from the skill levels in a build string.
+const JOB_SEPARATOR: char = ':';
+/// Separates the skills in a build string.
+const SKILL_SEPARATOR: char = ',';
+/// Separates the id and level of a skill in a build string.
+const LEVEL_SEPARATOR: char = '=';
+
+/// Hypothetical skill levels of a single job.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SkillPlan {
+    job_id: usize,
+    /// Planned levels by skill id. Skills without an entry are not learned.
+    levels: BTreeMap<u16, u8>,
+}
+
+impl SkillPlan {
+    pub fn new(job_id: usize) -> Self {
+        Self {
+            job_id,
+            levels: BTreeMap::new(),
+        }
+    }
+
+    pub fn level(&self, skill_id: u16) -> u8 {
+        self.levels.get(&skill_id).copied().unwrap_or_default()
+    }
+
+    pub fn points_used(&self) -> u16 {
+        self.levels.values().map(|&level| level as u16).sum()
+    }
+
+    pub fn requirements_met(&self, skill_tree: &JobSkillTree, skill_id: u16) -> bool {
+        skill_tree.get_skill(skill_id).is_some_and(|skill| {
+            skill
+                .requirements
+                .iter()
+                .all(|requirement| self.level(requirement.skill_id) >= requirement.level)
+        })
+    }
+
+    /// Raise a skill by one level. Required skills are raised as well, like
+    /// the player would have to. Returns `false` if the skill is already at
+    /// its maximum level or there are not enough skill points left.
+    pub fn raise(&mut self, skill_tree: &JobSkillTree, skill_id: u16) -> bool {
+        let Some(skill) = skill_tree.get_skill(skill_id) else {
+            return false;
+        };
+
+        let level = self.level(skill_id);
+
+        if level >= skill.maximum_level {
+            return false;
+        }
+
+        let mut plan = self.clone();
+        plan.raise_to(skill_tree, skill_id, level + 1);
+
+        if plan.points_used() > skill_tree.skill_points {
+            return false;
+        }
+
+        *self = plan;
+        true
+    }
+
+    fn raise_to(&mut self, skill_tree: &JobSkillTree, skill_id: u16, level: u8) {
+        if let Some(skill) = skill_tree.get_skill(skill_id) {
+            for requirement in &skill.requirements {
+                self.raise_to(skill_tree, requirement.skill_id, requirement.level);
+            }
+        }
+
+        let planned_level = self.levels.entry(skill_id).or_default();
+        *planned_level = (*planned_level).max(level);
+    }
+
+    /// Lower a skill by one level. Skills that no longer have their
+    /// requirements met are unlearned. Returns `false` if the skill was not
+    /// learned.
+    pub fn lower(&mut self, skill_tree: &JobSkillTree, skill_id: u16) -> bool {
+        match self.level(skill_id) {
+            0 => return false,
+            1 => {
+                self.levels.remove(&skill_id);
+            }
+            level => {
+                self.levels.insert(skill_id, level - 1);
+            }
+        }
+
+        loop {
+            let unmet: Vec<u16> = self
+                .levels
+                .keys()
+                .copied()
+                .filter(|&skill_id| !self.requirements_met(skill_tree, skill_id))
+                .collect();
+
+            if unmet.is_empty() {
+                return true;
+            }
+
+            unmet.iter().for_each(|skill_id| {
+                self.levels.remove(skill_id);
+            });
+        }
+    }
+
+    /// Encode the plan as a build string of the form `job:skill=level,...`.
+    pub fn to_build_string(&self) -> String {
+        let skills = self
+            .levels
+            .iter()
+            .map(|(skill_id, level)| format!("{skill_id}{LEVEL_SEPARATOR}{level}"))
+            .collect::<Vec<_>>()
+            .join(&SKILL_SEPARATOR.to_string());
+
+        format!("{}{JOB_SEPARATOR}{skills}", self.job_id)
+    }
+
+    /// Decode a build string. Returns `None` if the string is malformed or
+    /// describes a build that isn't possible with the given skill trees.
+    pub fn from_build_string(skill_trees: &[JobSkillTree], build_string: &str) -> Option<Self> {
+        let (job_id, skills) = build_string.trim().split_once(JOB_SEPARATOR)?;
+        let job_id = job_id.parse().ok()?;
+        let skill_tree = skill_trees.iter().find(|skill_tree| skill_tree.job_id == job_id)?;
+
+        let mut plan = Self::new(job_id);
+
+        for skill in skills.split(SKILL_SEPARATOR).filter(|skill| !skill.is_empty()) {
+            let (skill_id, level) = skill.split_once(LEVEL_SEPARATOR)?;
+            let skill_id = skill_id.parse().ok()?;
+            let level = level.parse().ok()?;
+            let skill = skill_tree.get_skill(skill_id)?;
+
+            if level == 0 || level > skill.maximum_level {
+                return None;
+            }
+
+            plan.levels.insert(skill_id, level);
+        }
+
+        let valid = plan.points_used() <= skill_tree.skill_points
+            && plan.levels.keys().all(|&skill_id| plan.requirements_met(skill_tree, skill_id));
+
+        valid.then_some(plan)
+    }
+}
+
+#[derive(Clone, RustState)]
+pub struct PlannerJob {
+    name: String,
+}
+
+#[derive(Clone, RustState)]
+pub struct PlannerSkill {
+    text: String,
+}
+
+/// State of the skill planner window.
+#[derive(Default, RustState, StateElement)]
+pub struct SkillPlanner {
+    job_text: String,
+    points_text: String,
+    build_string: String,
+    calculator: StatCalculator,
+    #[hidden_element]
+    skill_trees: Vec<JobSkillTree>,
+    #[hidden_element]
+    job_index: usize,
+    #[hidden_element]
+    plan: SkillPlan,
+    #[hidden_element]
+    jobs: Vec<PlannerJob>,
+    #[hidden_element]
+    skills: Vec<PlannerSkill>,
+}
+
+impl SkillPlanner {
+    /// Set the skill trees that can be planned. The skill trees only need to
+    /// be set once.
+    pub fn set_skill_trees(&mut self, skill_trees: &[JobSkillTree]) {
+        if !self.skill_trees.is_empty() {
+            return;
+        }
+
+        self.skill_trees = skill_trees.to_vec();
+        self.jobs = skill_trees
+            .iter()
+            .map(|skill_tree| PlannerJob {
+                name: skill_tree.name.clone(),
+            })
+            .collect();
+
+        self.select_job(0);
+    }
+
+    pub fn select_job(&mut self, job_index: usize) {
+        let Some(skill_tree) = self.skill_trees.get(job_index) else {
+            return;
+        };
+
+        self.job_index = job_index;
+        self.plan = SkillPlan::new(skill_tree.job_id);
+        self.update_texts();
+    }
+
+    /// Raise the skill with the given index in the skill tree of the selected
+    /// job.
+    pub fn raise(&mut self, skill_index: usize) {
+        if let Some(skill_tree) = self.skill_trees.get(self.job_index)
+            && let Some(skill) = skill_tree.skills.get(skill_index)
+            && self.plan.raise(skill_tree, skill.skill_id)
+        {
+            self.update_texts();
+        }
+    }
+
+    /// Lower the skill with the given index in the skill tree of the selected
+    /// job.
+    pub fn lower(&mut self, skill_index: usize) {
+        if let Some(skill_tree) = self.skill_trees.get(self.job_index)
+            && let Some(skill) = skill_tree.skills.get(skill_index)
+            && self.plan.lower(skill_tree, skill.skill_id)
+        {
+            self.update_texts();
+        }
+    }
+
+    /// Raise or lower a stat of the stat calculator.
+    pub fn adjust_stat(&mut self, stat: PlannerStat, raise: bool) {
+        self.calculator.adjust(stat, raise);
+        self.update_texts();
+    }
+
+    pub fn reset(&mut self) {
+        self.select_job(self.job_index);
+    }
+
+    /// Put the build string of the current plan into the build string text
+    /// box.
+    pub fn export_build(&mut self) {
+        self.build_string = self.plan.to_build_string();
+    }
+
+    /// Load the plan from the build string text box. Returns `false` if the
+    /// build string is not valid.
+    pub fn import_build(&mut self) -> bool {
+        let Some(plan) = SkillPlan::from_build_string(&self.skill_trees, &self.build_string) else {
+            return false;
+        };
+
+        self.job_index = self
+            .skill_trees
+            .iter()
+            .position(|skill_tree| skill_tree.job_id == plan.job_id)
+            .unwrap_or_default();
+        self.plan = plan;
+        self.update_texts();
+
+        true
+    }
+
+    fn update_texts(&mut self) {
+        let Some(skill_tree) = self.skill_trees.get(self.job_index) else {
+            return;
+        };
+
+        self.job_text = skill_tree.name.clone();
+        self.points_text = format!("{} / {}", self.plan.points_used(), skill_tree.skill_points);
+        self.skills = skill_tree
+            .skills
+            .iter()
+            .map(|skill| {
+                let mut text = format!("{} {} / {}", skill.name, self.plan.level(skill.skill_id), skill.maximum_level);
+
+                for requirement in &skill.requirements {
+                    if let Some(required_skill) = skill_tree.get_skill(requirement.skill_id) {
+                        let _ = write!(text, "\n  {} {}", required_skill.name, requirement.level);
+                    }
+                }
+
+                PlannerSkill { text }
+            })
+            .collect();
+
+        self.calculator.update(skill_tree, &self.plan);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SkillPlan;
+    use crate::world::{JobSkillTree, SkillRequirement, SkillTreeEntry};
+
+    const BASH: u16 = 5;
+    const PROVOKE: u16 = 6;
+    const MAGNUM_BREAK: u16 = 7;
+
+    fn skill_tree() -> JobSkillTree {
+        let skill = |skill_id, requirements: Vec<SkillRequirement>| SkillTreeEntry {
+            skill_id,
+            name: String::new(),
+            maximum_level: 10,
+            requirements,
+            damage: None,
+        };
+
+        JobSkillTree {
+            job_id: 1,
+            name: "Swordman".to_owned(),
+            skill_points: 20,
+            skills: vec![
+                skill(BASH, Vec::new()),
+                skill(PROVOKE, Vec::new()),
+                skill(MAGNUM_BREAK, vec![SkillRequirement { skill_id: BASH, level: 5 }]),
+            ],
+        }
+    }
+
+    #[test]
+    fn raising_learns_requirements() {
+        let skill_tree = skill_tree();
+        let mut plan = SkillPlan::new(1);
+
+        assert!(plan.raise(&skill_tree, MAGNUM_BREAK));
+        assert_eq!(plan.level(BASH), 5);
+        assert_eq!(plan.level(MAGNUM_BREAK), 1);
+        assert_eq!(plan.points_used(), 6);
+    }
+
+    #[test]
+    fn raising_respects_skill_points() {
+        let skill_tree = skill_tree();
+        let mut plan = SkillPlan::new(1);
+
+        (0..10).for_each(|_| assert!(plan.raise(&skill_tree, BASH)));
+        (0..10).for_each(|_| assert!(plan.raise(&skill_tree, PROVOKE)));
+
+        assert!(!plan.raise(&skill_tree, BASH));
+        assert!(!plan.raise(&skill_tree, MAGNUM_BREAK));
+        assert_eq!(plan.points_used(), 20);
+    }
+
+    #[test]
+    fn lowering_unlearns_dependent_skills() {
+        let skill_tree = skill_tree();
+        let mut plan = SkillPlan::new(1);
+
+        (0..3).for_each(|_| assert!(plan.raise(&skill_tree, MAGNUM_BREAK)));
+        assert!(plan.lower(&skill_tree, BASH));
+
+        assert_eq!(plan.level(BASH), 4);
+        assert_eq!(plan.level(MAGNUM_BREAK), 0);
+        assert!(!plan.lower(&skill_tree, MAGNUM_BREAK));
+    }
+
+    #[test]
+    fn build_string_round_trip() {
+        let skill_trees = [skill_tree()];
+        let mut plan = SkillPlan::new(1);
+
+        (0..2).for_each(|_| assert!(plan.raise(&skill_trees[0], MAGNUM_BREAK)));
+        assert!(plan.raise(&skill_trees[0], PROVOKE));
+
+        let build_string = plan.to_build_string();
+
+        assert_eq!(build_string, "1:5=5,6=1,7=2");
+        assert_eq!(SkillPlan::from_build_string(&skill_trees, &build_string), Some(plan));
+    }
+
+    #[test]
+    fn impossible_builds_are_rejected() {
+        let skill_trees = [skill_tree()];
+
+        assert!(SkillPlan::from_build_string(&skill_trees, "1:7=1").is_none());
+        assert!(SkillPlan::from_build_string(&skill_trees, "1:5=11").is_none());
+        assert!(SkillPlan::from_build_string(&skill_trees, "1:5=10,6=10,7=1").is_none());
+        assert!(SkillPlan::from_build_string(&skill_trees, "2:5=1").is_none());
+        assert!(SkillPlan::from_build_string(&skill_trees, "garbage").is_none());
+        assert_eq!(SkillPlan::from_build_string(&skill_trees, "1:"), Some(SkillPlan::new(1)));
+    }
+}
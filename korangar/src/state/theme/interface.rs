@@ -16,7 +16,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::graphics::{Color, CornerDiameter, ScreenSize, ShadowPadding};
 use crate::loaders::{FontSize, OverflowBehavior};
-use crate::settings::{IN_GAME_THEMES_PATH, MENU_THEMES_PATH};
+use crate::settings::{IN_GAME_THEMES_PATH, MENU_THEMES_PATH, MinimumFontSize};
 use crate::state::ClientState;
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -26,6 +26,15 @@ pub enum InterfaceThemeType {
     Menu,
 }
 
+/// Raise `font_size` to `minimum` and grow `height` by the same amount, so the
+/// text still fits.
+fn raise_font_size(font_size: &mut FontSize, height: &mut f32, minimum: FontSize) {
+    let growth = (minimum.0 - font_size.0).max(0.0);
+
+    font_size.0 += growth;
+    *height += growth;
+}
+
 #[derive(Serialize, Deserialize, RustState, StateElement)]
 pub struct DebugButtonTheme {
     foreground_color: Color,
@@ -95,6 +104,108 @@ impl InterfaceTheme {
         theme
     }
 
+    /// Apply the high contrast variant and the minimum font size on top of the
+    /// loaded theme, so they work with any theme.
+    pub fn apply_accessibility(&mut self, high_contrast: bool, minimum_font_size: MinimumFontSize) {
+        if high_contrast {
+            self.apply_high_contrast();
+        }
+
+        if let Some(minimum) = minimum_font_size.font_size() {
+            raise_font_size(&mut self.window.font_size, &mut self.window.title_height, minimum);
+            raise_font_size(&mut self.text.font_size, &mut self.text.height, minimum);
+            raise_font_size(&mut self.button.font_size, &mut self.button.height, minimum);
+            raise_font_size(&mut self.state_button.font_size, &mut self.state_button.height, minimum);
+            raise_font_size(&mut self.text_box.font_size, &mut self.text_box.height, minimum);
+            raise_font_size(&mut self.collapsable.font_size, &mut self.collapsable.title_height, minimum);
+            raise_font_size(&mut self.drop_down.item_font_size, &mut self.drop_down.item_height, minimum);
+            raise_font_size(&mut self.drop_down.button_font_size, &mut self.drop_down.button_height, minimum);
+            raise_font_size(&mut self.field.font_size, &mut self.field.height, minimum);
+            self.tooltip.font_size.0 = self.tooltip.font_size.0.max(minimum.0);
+        }
+    }
+
+    /// White text on black backgrounds, with a bright yellow for highlights.
+    /// Interactive elements stay a bit brighter than the window, so they can
+    /// still be told apart from it.
+    fn apply_high_contrast(&mut self) {
+        let foreground_color = Color::WHITE;
+        let highlight_color = Color::rgb_u8(255, 220, 0);
+        let background_color = Color::BLACK;
+        let element_color = Color::monochrome_u8(60);
+        let hovered_element_color = Color::monochrome_u8(110);
+        let disabled_element_color = Color::monochrome_u8(30);
+        let disabled_foreground_color = Color::monochrome_u8(150);
+
+        self.window.title_color = foreground_color;
+        self.window.hovered_title_color = highlight_color;
+        self.window.background_color = background_color;
+        self.window.highlight_color = highlight_color;
+
+        self.text.color = foreground_color;
+        self.text.highlight_color = highlight_color;
+
+        self.button.background_color = element_color;
+        self.button.foreground_color = foreground_color;
+        self.button.highlight_color = highlight_color;
+        self.button.hovered_background_color = hovered_element_color;
+        self.button.hovered_foreground_color = highlight_color;
+        self.button.disabled_background_color = disabled_element_color;
+        self.button.disabled_foreground_color = disabled_foreground_color;
+
+        self.state_button.background_color = element_color;
+        self.state_button.foreground_color = foreground_color;
+        self.state_button.highlight_color = highlight_color;
+        self.state_button.hovered_background_color = hovered_element_color;
+        self.state_button.hovered_foreground_color = highlight_color;
+        self.state_button.disabled_background_color = disabled_element_color;
+        self.state_button.disabled_foreground_color = disabled_foreground_color;
+        self.state_button.checkbox_color = foreground_color;
+        self.state_button.hovered_checkbox_color = highlight_color;
+        self.state_button.disabled_checkbox_color = disabled_foreground_color;
+
+        self.text_box.background_color = disabled_element_color;
+        self.text_box.foreground_color = foreground_color;
+        self.text_box.highlight_color = highlight_color;
+        self.text_box.hovered_background_color = element_color;
+        self.text_box.hovered_foreground_color = foreground_color;
+        self.text_box.focused_background_color = hovered_element_color;
+        self.text_box.focused_foreground_color = foreground_color;
+        self.text_box.ghost_foreground_color = disabled_foreground_color;
+        self.text_box.hide_icon_color = foreground_color;
+        self.text_box.hovered_hide_icon_color = highlight_color;
+
+        self.collapsable.background_color = disabled_element_color;
+        self.collapsable.secondary_background_color = background_color;
+        self.collapsable.foreground_color = foreground_color;
+        self.collapsable.highlight_color = highlight_color;
+        self.collapsable.hovered_foreground_color = highlight_color;
+        self.collapsable.icon_color = foreground_color;
+
+        self.drop_down.item_background_color = element_color;
+        self.drop_down.item_foreground_color = foreground_color;
+        self.drop_down.item_highlight_color = highlight_color;
+        self.drop_down.item_hovered_background_color = hovered_element_color;
+        self.drop_down.item_hovered_foreground_color = highlight_color;
+        self.drop_down.list_background_color = background_color;
+        self.drop_down.button_background_color = element_color;
+        self.drop_down.button_foreground_color = foreground_color;
+        self.drop_down.button_highlight_color = highlight_color;
+        self.drop_down.button_hovered_background_color = hovered_element_color;
+        self.drop_down.button_hovered_foreground_color = highlight_color;
+
+        self.field.background_color = element_color;
+        self.field.foreground_color = foreground_color;
+        self.field.highlight_color = highlight_color;
+
+        self.tooltip.background_color = background_color;
+        self.tooltip.foreground_color = foreground_color;
+        self.tooltip.highlight_color = highlight_color;
+
+        self.chat.window_color = Color::rgba_u8(0, 0, 0, 230);
+        self.chat.text_box_background_color = Color::rgba_u8(0, 0, 0, 230);
+    }
+
     #[cfg(feature = "debug")]
     pub fn save(&self, theme_type: InterfaceThemeType, name: &str) {
         let timer = Timer::new("Save theme");
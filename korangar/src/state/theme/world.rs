@@ -1,11 +1,13 @@
 #[cfg(feature = "debug")]
 use korangar_debug::logging::{Colorize, Timer, print_debug};
 use korangar_interface::element::StateElement;
+use ragnarok_packets::QuestColor;
 use rust_state::RustState;
 use serde::{Deserialize, Serialize};
 
 use crate::graphics::{Color, ScreenPosition, ScreenSize};
 use crate::loaders::FontSize;
+use crate::settings::{ColorblindMode, MinimumFontSize};
 
 #[derive(Serialize, Deserialize, RustState, StateElement)]
 pub struct OverlayTheme {
@@ -87,12 +89,109 @@ impl Default for CursorTheme {
     }
 }
 
+#[derive(Serialize, Deserialize, RustState, StateElement)]
+pub struct ToastTheme {
+    pub background_color: Color,
+    pub foreground_color: Color,
+    pub friend_login_color: Color,
+    pub rare_drop_color: Color,
+    pub achievement_color: Color,
+    pub system_warning_color: Color,
+    pub font_size: FontSize,
+    pub size: ScreenSize,
+    pub margin: ScreenSize,
+    pub text_offset: ScreenPosition,
+    pub accent_width: f32,
+    pub gap: f32,
+}
+
+impl Default for ToastTheme {
+    fn default() -> Self {
+        Self {
+            background_color: Color::rgba_u8(30, 30, 30, 210),
+            foreground_color: Color::monochrome_u8(220),
+            friend_login_color: Color::rgb_u8(67, 163, 83),
+            rare_drop_color: Color::rgb_u8(218, 145, 81),
+            achievement_color: Color::rgb_u8(0, 129, 163),
+            system_warning_color: Color::rgb_u8(206, 49, 116),
+            font_size: FontSize(16.0),
+            size: ScreenSize {
+                width: 300.0,
+                height: 34.0,
+            },
+            margin: ScreenSize { width: 20.0, height: 20.0 },
+            text_offset: ScreenPosition { left: 14.0, top: 8.0 },
+            accent_width: 4.0,
+            gap: 6.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, RustState, StateElement)]
+pub struct DamageNumberTheme {
+    pub damage_color: Color,
+    pub critical_color: Color,
+    pub heal_color: Color,
+    pub miss_color: Color,
+    pub font_size: FontSize,
+    pub miss_font_size: FontSize,
+}
+
+impl Default for DamageNumberTheme {
+    fn default() -> Self {
+        Self {
+            damage_color: Color::WHITE,
+            critical_color: Color::rgb_u8(255, 180, 0),
+            heal_color: Color::rgb_u8(30, 255, 30),
+            miss_color: Color::rgb_u8(255, 0, 0),
+            font_size: FontSize(16.0),
+            miss_font_size: FontSize(20.0),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, RustState, StateElement)]
+pub struct QuestMarkerTheme {
+    pub yellow: Color,
+    pub orange: Color,
+    pub green: Color,
+    pub purple: Color,
+}
+
+impl QuestMarkerTheme {
+    pub fn color(&self, quest_color: &QuestColor) -> Color {
+        match quest_color {
+            QuestColor::Yellow => self.yellow,
+            QuestColor::Orange => self.orange,
+            QuestColor::Green => self.green,
+            QuestColor::Purple => self.purple,
+        }
+    }
+}
+
+impl Default for QuestMarkerTheme {
+    fn default() -> Self {
+        Self {
+            yellow: Color::rgb_u8(200, 200, 30),
+            orange: Color::rgb_u8(200, 100, 30),
+            green: Color::rgb_u8(30, 200, 30),
+            purple: Color::rgb_u8(200, 30, 200),
+        }
+    }
+}
+
 #[derive(Default, Serialize, Deserialize, RustState, StateElement)]
 pub struct WorldTheme {
     pub overlay: OverlayTheme,
     pub status_bar: StatusBarTheme,
     pub indicator: IndicatorTheme,
     pub cursor: CursorTheme,
+    #[serde(default)]
+    pub toast: ToastTheme,
+    #[serde(default)]
+    pub damage_number: DamageNumberTheme,
+    #[serde(default)]
+    pub quest_marker: QuestMarkerTheme,
 }
 
 impl WorldTheme {
@@ -122,6 +221,57 @@ impl WorldTheme {
         theme
     }
 
+    /// Replace the colors that are hard to tell apart with the given kind of
+    /// color blindness and raise all font sizes to the minimum font size. This
+    /// is applied on top of the loaded theme, so it works with any theme.
+    pub fn apply_accessibility(&mut self, colorblind_mode: ColorblindMode, minimum_font_size: MinimumFontSize) {
+        // Colors are taken from the palette by Okabe and Ito, which stays
+        // distinguishable for red-green color blindness. Protanopia and
+        // deuteranopia confuse the same colors, so they share a palette.
+        match colorblind_mode {
+            ColorblindMode::Off => {}
+            ColorblindMode::Protanopia | ColorblindMode::Deuteranopia => {
+                self.status_bar.player_health_color = Color::rgb_u8(0, 114, 178);
+                self.status_bar.enemy_health_color = Color::rgb_u8(213, 94, 0);
+                self.status_bar.spell_point_color = Color::rgb_u8(86, 180, 233);
+                self.status_bar.activity_point_color = Color::rgb_u8(240, 228, 66);
+                self.damage_number.critical_color = Color::rgb_u8(230, 159, 0);
+                self.damage_number.heal_color = Color::rgb_u8(86, 180, 233);
+                self.damage_number.miss_color = Color::rgb_u8(213, 94, 0);
+                self.quest_marker.yellow = Color::rgb_u8(240, 228, 66);
+                self.quest_marker.orange = Color::rgb_u8(213, 94, 0);
+                self.quest_marker.green = Color::rgb_u8(0, 114, 178);
+                self.quest_marker.purple = Color::rgb_u8(204, 121, 167);
+            }
+            // Tritanopia confuses blue with green and yellow with pink, so the
+            // palette relies on red and teal instead.
+            ColorblindMode::Tritanopia => {
+                self.status_bar.player_health_color = Color::rgb_u8(0, 160, 160);
+                self.status_bar.enemy_health_color = Color::rgb_u8(220, 40, 60);
+                self.status_bar.spell_point_color = Color::rgb_u8(150, 150, 150);
+                self.status_bar.activity_point_color = Color::rgb_u8(255, 130, 150);
+                self.damage_number.critical_color = Color::rgb_u8(255, 90, 90);
+                self.damage_number.heal_color = Color::rgb_u8(0, 200, 200);
+                self.damage_number.miss_color = Color::rgb_u8(220, 40, 60);
+                self.quest_marker.yellow = Color::rgb_u8(255, 130, 150);
+                self.quest_marker.orange = Color::rgb_u8(220, 40, 60);
+                self.quest_marker.green = Color::rgb_u8(0, 160, 160);
+                self.quest_marker.purple = Color::rgb_u8(150, 150, 150);
+            }
+        }
+
+        if let Some(minimum) = minimum_font_size.font_size() {
+            for font_size in [
+                &mut self.overlay.font_size,
+                &mut self.toast.font_size,
+                &mut self.damage_number.font_size,
+                &mut self.damage_number.miss_font_size,
+            ] {
+                font_size.0 = font_size.0.max(minimum.0);
+            }
+        }
+    }
+
     #[cfg(feature = "debug")]
     pub fn save(&self, name: &str) {
         use crate::settings::WORLD_THEMES_PATH;
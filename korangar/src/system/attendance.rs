@@ -0,0 +1,21 @@
+//! Handling of the attendance events.
+
+use korangar_networking::MessageColor;
+
+use crate::Client;
+use crate::interface::windows::{AttendanceWindow, WindowClass};
+
+impl Client {
+    pub(crate) fn open_attendance(&mut self, claimed_days: u32) {
+        self.interface.open_window(AttendanceWindow::new(claimed_days));
+    }
+
+    /// The reward is sent by mail, so the player is told where to find it.
+    pub(crate) fn attendance_reward_claimed(&mut self, claimed_days: u32) {
+        self.interface.close_window_with_class(WindowClass::Attendance);
+        self.push_chat_message(
+            MessageColor::Information,
+            format!("The attendance reward for day {claimed_days} was sent to your mailbox"),
+        );
+    }
+}
@@ -0,0 +1,26 @@
+//! Handling of the auction events. There is no auction window yet, so the
+//! results are reported in the chat.
+
+use korangar_networking::MessageColor;
+use ragnarok_packets::AuctionResult;
+
+use crate::Client;
+
+impl Client {
+    pub(crate) fn auction_result(&mut self, result: AuctionResult) {
+        let (message, color) = match result {
+            AuctionResult::BidFailed => ("Failed to bid on the auction", MessageColor::Error),
+            AuctionResult::BidSuccessful => ("Bid on the auction", MessageColor::Information),
+            AuctionResult::Cancelled => ("The auction was cancelled", MessageColor::Information),
+            AuctionResult::CancelFailedHasBidder => ("Auctions with a bid can't be cancelled", MessageColor::Error),
+            AuctionResult::TooManyAuctions => ("You can't have any more auctions", MessageColor::Error),
+            AuctionResult::NotEnoughZenyForFee => ("Not enough zeny to pay the auction fee", MessageColor::Error),
+            AuctionResult::AuctionWon => ("You won the auction", MessageColor::Information),
+            AuctionResult::AuctionLost => ("You lost the auction", MessageColor::Information),
+            AuctionResult::NotEnoughZeny => ("Not enough zeny", MessageColor::Error),
+            AuctionResult::TooManyBids => ("You can't place any more bids", MessageColor::Error),
+        };
+
+        self.push_chat_message(color, message);
+    }
+}
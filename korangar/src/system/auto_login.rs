@@ -0,0 +1,130 @@
+//! Automatic login for services that have it enabled. After a short countdown
+//! the client logs in with the remembered credentials and selects the last
+//! used character server and character, skipping all the menus.
+
+use crate::Client;
+use crate::input::InputEvent;
+use crate::loaders::ServiceId;
+use crate::state::localization::LocalizationPathExt;
+use crate::state::{ClientStatePathExt, client_state};
+
+/// Time in seconds before logging in, giving the player a chance to cancel.
+const COUNTDOWN_DURATION: f64 = 3.0;
+
+pub struct AutoLogin {
+    service_id: ServiceId,
+    remaining_time: f64,
+}
+
+impl AutoLogin {
+    pub fn new(service_id: ServiceId) -> Self {
+        Self {
+            service_id,
+            remaining_time: COUNTDOWN_DURATION,
+        }
+    }
+
+    pub fn service_id(&self) -> ServiceId {
+        self.service_id
+    }
+
+    /// The countdown can be canceled by any input. Once it ran out, the login
+    /// is in progress and input no longer cancels it.
+    pub fn is_counting_down(&self) -> bool {
+        self.remaining_time > 0.0
+    }
+
+    /// Whole seconds left, for displaying the countdown.
+    pub fn remaining_seconds(&self) -> u32 {
+        self.remaining_time.max(0.0).ceil() as u32
+    }
+
+    /// Advance the countdown. Returns `true` exactly once, in the frame the
+    /// countdown runs out.
+    pub fn update(&mut self, delta_time: f64) -> bool {
+        if !self.is_counting_down() {
+            return false;
+        }
+
+        self.remaining_time -= delta_time;
+        !self.is_counting_down()
+    }
+}
+
+impl Client {
+    /// Advance the auto-login countdown and log in once it runs out.
+    pub(crate) fn update_auto_login(&mut self, delta_time: f64) {
+        let Some(auto_login) = &mut self.auto_login else {
+            return;
+        };
+
+        if !auto_login.update(delta_time) {
+            if auto_login.is_counting_down() {
+                let countdown_text = self.client_state.follow(client_state().localization().auto_log_in_countdown_text());
+                let status = format!("{countdown_text} ({})", auto_login.remaining_seconds());
+
+                self.client_state
+                    .follow_mut(client_state().login_window())
+                    .set_auto_login_status(status);
+            }
+
+            return;
+        }
+
+        self.client_state
+            .follow_mut(client_state().login_window())
+            .set_auto_login_status(String::new());
+
+        let service_id = auto_login.service_id();
+        let login_settings = self.client_state.follow_mut(client_state().login_settings());
+        login_settings.recent_service_id = Some(service_id);
+
+        // SAFETY:
+        //
+        // Every service has a settings entry, see `ClientState::new`.
+        let service_settings = &login_settings.service_settings[&service_id];
+
+        // A character slot from the command line takes precedence.
+        self.launch_character_slot = self.launch_character_slot.or(service_settings.last_character_slot);
+
+        self.input_event_buffer.push(InputEvent::LogIn {
+            service_id,
+            username: service_settings.username.clone(),
+            password: service_settings.password.clone(),
+        });
+    }
+
+    /// Any key press or mouse click during the countdown cancels the
+    /// auto-login.
+    pub(crate) fn cancel_auto_login(&mut self) {
+        if self.auto_login.as_ref().is_some_and(AutoLogin::is_counting_down) {
+            self.auto_login = None;
+            self.client_state
+                .follow_mut(client_state().login_window())
+                .set_auto_login_status(String::new());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AutoLogin, COUNTDOWN_DURATION};
+    use crate::loaders::ServiceId;
+
+    #[test]
+    fn countdown_runs_out_once() {
+        let mut auto_login = AutoLogin::new(ServiceId(0));
+
+        assert!(auto_login.is_counting_down());
+        assert_eq!(auto_login.remaining_seconds(), COUNTDOWN_DURATION.ceil() as u32);
+
+        assert!(!auto_login.update(COUNTDOWN_DURATION - 0.5));
+        assert_eq!(auto_login.remaining_seconds(), 1);
+
+        assert!(auto_login.update(1.0));
+        assert!(!auto_login.is_counting_down());
+        assert_eq!(auto_login.remaining_seconds(), 0);
+
+        assert!(!auto_login.update(1.0));
+    }
+}
@@ -0,0 +1,49 @@
+//! Handling of the bank events. There is no bank window yet, so failed
+//! requests are reported in the chat.
+
+use korangar_networking::MessageColor;
+use ragnarok_packets::{BankDepositResult, BankWithdrawResult, OpenBankResult};
+
+use crate::Client;
+use crate::state::this_player;
+
+impl Client {
+    pub(crate) fn bank_opened(&mut self, result: OpenBankResult) {
+        if result == OpenBankResult::Error {
+            self.push_chat_message(MessageColor::Error, "Failed to open the bank");
+        }
+    }
+
+    pub(crate) fn bank_deposit_completed(&mut self, result: BankDepositResult, zeny: i32) {
+        self.set_player_zeny(zeny);
+
+        let message = match result {
+            BankDepositResult::Success => return,
+            BankDepositResult::Error => "Failed to deposit zeny",
+            BankDepositResult::NotEnoughZeny => "Not enough zeny to deposit",
+            BankDepositResult::BalanceLimitExceeded => "The bank can't hold any more zeny",
+        };
+
+        self.push_chat_message(MessageColor::Error, message);
+    }
+
+    pub(crate) fn bank_withdraw_completed(&mut self, result: BankWithdrawResult, zeny: i32) {
+        self.set_player_zeny(zeny);
+
+        let message = match result {
+            BankWithdrawResult::Success => return,
+            BankWithdrawResult::NotEnoughBalance => "Not enough zeny in the bank",
+            BankWithdrawResult::ZenyLimitExceeded => "You can't carry any more zeny",
+        };
+
+        self.push_chat_message(MessageColor::Error, message);
+    }
+
+    /// The zeny of the player are sent with every result, even if the
+    /// request failed.
+    fn set_player_zeny(&mut self, zeny: i32) {
+        if let Some(player) = self.client_state.try_follow_mut(this_player()) {
+            player.zeny = zeny.max(0) as u32;
+        }
+    }
+}
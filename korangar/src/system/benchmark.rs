@@ -0,0 +1,259 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use blake3::Hash;
+#[cfg(feature = "debug")]
+use korangar_debug::logging::{Colorize, print_debug};
+use ron::ser::PrettyConfig;
+use serde::Serialize;
+
+/// Maps that are benchmarked if none are passed on the command line.
+const DEFAULT_BENCHMARK_MAPS: &[&str] = &["geffen", "prontera", "payon", "alberta", "aldebaran"];
+/// Time spent measuring frame times on every map.
+const MEASUREMENT_DURATION: Duration = Duration::from_secs(20);
+/// Frames to skip after loading a map, so that the measurement isn't skewed
+/// by uploads and pipeline creation.
+const WARMUP_FRAMES: usize = 60;
+/// If a map takes longer than this to load, we assume that loading failed
+/// and move on to the next map.
+const LOAD_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Results for a single benchmarked map.
+#[derive(Debug, Serialize)]
+pub struct MapBenchmarkResult {
+    pub map_name: String,
+    /// Time in milliseconds it took to load the map. `None` if the map
+    /// failed to load.
+    pub load_time_ms: Option<f64>,
+    pub frame_count: usize,
+    pub average_frame_time_ms: f64,
+    pub median_frame_time_ms: f64,
+    pub percentile_95_frame_time_ms: f64,
+    pub percentile_99_frame_time_ms: f64,
+    pub maximum_frame_time_ms: f64,
+}
+
+impl MapBenchmarkResult {
+    pub(super) fn new(map_name: String, load_time: Option<Duration>, mut frame_times_ms: Vec<f64>) -> Self {
+        frame_times_ms.sort_unstable_by(f64::total_cmp);
+
+        let frame_count = frame_times_ms.len();
+        let average_frame_time_ms = match frame_count {
+            0 => 0.0,
+            _ => frame_times_ms.iter().sum::<f64>() / frame_count as f64,
+        };
+
+        Self {
+            map_name,
+            load_time_ms: load_time.map(|duration| duration.as_secs_f64() * 1000.0),
+            frame_count,
+            average_frame_time_ms,
+            median_frame_time_ms: percentile(&frame_times_ms, 50.0),
+            percentile_95_frame_time_ms: percentile(&frame_times_ms, 95.0),
+            percentile_99_frame_time_ms: percentile(&frame_times_ms, 99.0),
+            maximum_frame_time_ms: frame_times_ms.last().copied().unwrap_or_default(),
+        }
+    }
+}
+
+/// Nearest-rank percentile of an already sorted slice.
+fn percentile(sorted_values: &[f64], percentile: f64) -> f64 {
+    if sorted_values.is_empty() {
+        return 0.0;
+    }
+
+    let rank = ((percentile / 100.0) * sorted_values.len() as f64).ceil() as usize;
+    sorted_values[rank.clamp(1, sorted_values.len()) - 1]
+}
+
+#[derive(Debug, Serialize)]
+struct BenchmarkReport<'a> {
+    game_file_hash: String,
+    results: &'a [MapBenchmarkResult],
+}
+
+enum BenchmarkPhase {
+    /// Waiting for the next map load to be requested.
+    Idle,
+    Loading {
+        map_name: String,
+        started: Instant,
+    },
+    Measuring {
+        map_name: String,
+        load_time: Duration,
+        started: Instant,
+        skipped_frames: usize,
+        frame_times_ms: Vec<f64>,
+    },
+    Finished,
+}
+
+/// Loads a list of maps one after the other and measures the load time and
+/// frame times while the start camera orbits the center of the map.
+pub struct Benchmark {
+    game_file_hash: Hash,
+    maps: VecDeque<String>,
+    phase: BenchmarkPhase,
+    results: Vec<MapBenchmarkResult>,
+}
+
+impl Benchmark {
+    const FILE_NAME: &'static str = "client/benchmark.ron";
+
+    pub fn new(maps: Vec<String>, game_file_hash: Hash) -> Self {
+        let maps = match maps.is_empty() {
+            true => DEFAULT_BENCHMARK_MAPS.iter().map(|map_name| map_name.to_string()).collect(),
+            false => maps.into(),
+        };
+
+        Self {
+            game_file_hash,
+            maps,
+            phase: BenchmarkPhase::Idle,
+            results: Vec::new(),
+        }
+    }
+
+    /// Returns the name of the next map to load, if the benchmark is ready
+    /// for it.
+    pub fn next_map_to_load(&mut self) -> Option<String> {
+        if let BenchmarkPhase::Loading { map_name, started } = &self.phase
+            && started.elapsed() > LOAD_TIMEOUT
+        {
+            #[cfg(feature = "debug")]
+            print_debug!("[{}] map {} timed out while loading", "benchmark".yellow(), map_name.magenta());
+
+            self.results.push(MapBenchmarkResult::new(map_name.clone(), None, Vec::new()));
+            self.phase = BenchmarkPhase::Idle;
+        }
+
+        if !matches!(self.phase, BenchmarkPhase::Idle) {
+            return None;
+        }
+
+        match self.maps.pop_front() {
+            Some(map_name) => {
+                self.phase = BenchmarkPhase::Loading {
+                    map_name: map_name.clone(),
+                    started: Instant::now(),
+                };
+
+                Some(map_name)
+            }
+            None => {
+                self.phase = BenchmarkPhase::Finished;
+                None
+            }
+        }
+    }
+
+    /// Returns `true` if the map load was requested by the benchmark.
+    pub fn map_loaded(&mut self, loaded_map_name: &str) -> bool {
+        let BenchmarkPhase::Loading { map_name, started } = &self.phase else {
+            return false;
+        };
+
+        if map_name != loaded_map_name {
+            return false;
+        }
+
+        self.phase = BenchmarkPhase::Measuring {
+            map_name: map_name.clone(),
+            load_time: started.elapsed(),
+            started: Instant::now(),
+            skipped_frames: 0,
+            frame_times_ms: Vec::new(),
+        };
+
+        true
+    }
+
+    pub fn record_frame(&mut self, delta_time: f64) {
+        let BenchmarkPhase::Measuring {
+            map_name,
+            load_time,
+            started,
+            skipped_frames,
+            frame_times_ms,
+        } = &mut self.phase
+        else {
+            return;
+        };
+
+        if *skipped_frames < WARMUP_FRAMES {
+            *skipped_frames += 1;
+            *started = Instant::now();
+            return;
+        }
+
+        frame_times_ms.push(delta_time * 1000.0);
+
+        if started.elapsed() >= MEASUREMENT_DURATION {
+            let result = MapBenchmarkResult::new(map_name.clone(), Some(*load_time), std::mem::take(frame_times_ms));
+
+            #[cfg(feature = "debug")]
+            print_debug!(
+                "[{}] {}: loaded in {:.2}ms, average frame time {:.2}ms, 99th percentile {:.2}ms",
+                "benchmark".green(),
+                result.map_name.magenta(),
+                result.load_time_ms.unwrap_or_default(),
+                result.average_frame_time_ms,
+                result.percentile_99_frame_time_ms
+            );
+
+            self.results.push(result);
+            self.phase = BenchmarkPhase::Idle;
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self.phase, BenchmarkPhase::Finished)
+    }
+
+    pub fn save_results(&self) {
+        let report = BenchmarkReport {
+            game_file_hash: self.game_file_hash.to_hex().to_string(),
+            results: &self.results,
+        };
+
+        let data = ron::ser::to_string_pretty(&report, PrettyConfig::new()).unwrap();
+
+        if let Err(_error) = std::fs::write(Self::FILE_NAME, data) {
+            #[cfg(feature = "debug")]
+            print_debug!("[{}] failed to save benchmark results: {:?}", "error".red(), _error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MapBenchmarkResult, percentile};
+
+    #[test]
+    fn percentile_of_empty_slice() {
+        assert_eq!(percentile(&[], 99.0), 0.0);
+    }
+
+    #[test]
+    fn percentile_nearest_rank() {
+        let values: Vec<f64> = (1..=100).map(f64::from).collect();
+
+        assert_eq!(percentile(&values, 50.0), 50.0);
+        assert_eq!(percentile(&values, 95.0), 95.0);
+        assert_eq!(percentile(&values, 99.0), 99.0);
+        assert_eq!(percentile(&values, 100.0), 100.0);
+        assert_eq!(percentile(&values, 0.0), 1.0);
+    }
+
+    #[test]
+    fn result_statistics() {
+        let result = MapBenchmarkResult::new("test".to_string(), None, vec![4.0, 2.0, 8.0, 2.0]);
+
+        assert_eq!(result.frame_count, 4);
+        assert_eq!(result.average_frame_time_ms, 4.0);
+        assert_eq!(result.median_frame_time_ms, 2.0);
+        assert_eq!(result.maximum_frame_time_ms, 8.0);
+        assert_eq!(result.load_time_ms, None);
+    }
+}
@@ -0,0 +1,211 @@
+//! Command registry for the debug console. Modules register their commands
+//! once on startup and the console dispatches lines the user typed to them.
+
+/// Handler of a console command. It receives the state and the arguments
+/// following the command name and returns the text that should be displayed
+/// in the console.
+pub type ConsoleCommandHandler<S> = fn(&mut S, &[&str]) -> Result<String, String>;
+
+pub struct ConsoleCommand<S> {
+    pub name: &'static str,
+    /// Short description shown by the `help` command.
+    pub description: &'static str,
+    pub handler: ConsoleCommandHandler<S>,
+}
+
+/// Output of executing a single line in the console.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ConsoleOutput {
+    Text(String),
+    Error(String),
+    /// Clear the output of the console.
+    Clear,
+}
+
+/// Result of autocompleting the current input of the console.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Completion {
+    /// The input with the command name completed as far as it is unambiguous.
+    pub text: String,
+    /// All command names that match the input.
+    pub candidates: Vec<&'static str>,
+}
+
+pub struct ConsoleCommandRegistry<S> {
+    commands: Vec<ConsoleCommand<S>>,
+}
+
+impl<S> Default for ConsoleCommandRegistry<S> {
+    fn default() -> Self {
+        Self { commands: Vec::new() }
+    }
+}
+
+impl<S> ConsoleCommandRegistry<S> {
+    /// Commands that are handled by the registry itself.
+    const BUILTIN_COMMANDS: &'static [(&'static str, &'static str)] =
+        &[("help", "List all commands"), ("clear", "Clear the console output")];
+
+    /// Register a new command. Registering a command with a name that is
+    /// already taken replaces the previous command.
+    pub fn register(&mut self, name: &'static str, description: &'static str, handler: ConsoleCommandHandler<S>) {
+        debug_assert!(
+            !Self::BUILTIN_COMMANDS.iter().any(|(builtin_name, _)| *builtin_name == name),
+            "console command {name} shadows a builtin command"
+        );
+
+        let command = ConsoleCommand {
+            name,
+            description,
+            handler,
+        };
+
+        match self.commands.iter_mut().find(|command| command.name == name) {
+            Some(existing) => *existing = command,
+            None => self.commands.push(command),
+        }
+
+        self.commands.sort_by_key(|command| command.name);
+    }
+
+    fn command_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        Self::BUILTIN_COMMANDS
+            .iter()
+            .map(|(name, _)| *name)
+            .chain(self.commands.iter().map(|command| command.name))
+    }
+
+    pub fn execute(&self, state: &mut S, line: &str) -> ConsoleOutput {
+        let mut words = line.split_whitespace();
+
+        let Some(name) = words.next() else {
+            return ConsoleOutput::Text(String::new());
+        };
+
+        let arguments: Vec<&str> = words.collect();
+
+        match name {
+            "help" => ConsoleOutput::Text(self.help()),
+            "clear" => ConsoleOutput::Clear,
+            _ => match self.commands.iter().find(|command| command.name == name) {
+                Some(command) => match (command.handler)(state, &arguments) {
+                    Ok(text) => ConsoleOutput::Text(text),
+                    Err(error) => ConsoleOutput::Error(error),
+                },
+                None => ConsoleOutput::Error(format!("unknown command \"{name}\", type \"help\" for a list of commands")),
+            },
+        }
+    }
+
+    fn help(&self) -> String {
+        Self::BUILTIN_COMMANDS
+            .iter()
+            .copied()
+            .chain(self.commands.iter().map(|command| (command.name, command.description)))
+            .map(|(name, description)| format!("{name} - {description}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Complete the command name of the input. Arguments are not completed.
+    pub fn complete(&self, input: &str) -> Completion {
+        let prefix = input.trim_start();
+
+        if prefix.contains(char::is_whitespace) {
+            return Completion {
+                text: input.to_owned(),
+                candidates: Vec::new(),
+            };
+        }
+
+        let mut candidates: Vec<&'static str> = self.command_names().filter(|name| name.starts_with(prefix)).collect();
+        candidates.sort_unstable();
+
+        let text = match candidates.as_slice() {
+            [] => input.to_owned(),
+            [name] => format!("{name} "),
+            [first, rest @ ..] => rest.iter().fold(first.to_string(), |common, name| {
+                let length = common.chars().zip(name.chars()).take_while(|(left, right)| left == right).count();
+                common.chars().take(length).collect()
+            }),
+        };
+
+        Completion { text, candidates }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ConsoleCommandRegistry, ConsoleOutput};
+
+    fn registry() -> ConsoleCommandRegistry<u32> {
+        let mut registry = ConsoleCommandRegistry::<u32>::default();
+        registry.register("render_map", "Toggle the map", |_, _| Ok("map".to_owned()));
+        registry.register("render_water", "Toggle the water", |_, _| Ok("water".to_owned()));
+        registry.register("add", "Add to the state", |state, arguments| {
+            let value: u32 = arguments
+                .first()
+                .ok_or("missing value")?
+                .parse()
+                .map_err(|_| "invalid value".to_owned())?;
+            *state += value;
+            Ok(state.to_string())
+        });
+        registry
+    }
+
+    #[test]
+    fn execute_command() {
+        let mut state = 1;
+
+        assert_eq!(
+            registry().execute(&mut state, "  add   2 "),
+            ConsoleOutput::Text("3".to_owned())
+        );
+        assert_eq!(state, 3);
+    }
+
+    #[test]
+    fn execute_errors() {
+        let mut state = 0;
+
+        assert_eq!(
+            registry().execute(&mut state, "add"),
+            ConsoleOutput::Error("missing value".to_owned())
+        );
+        assert!(matches!(registry().execute(&mut state, "missing"), ConsoleOutput::Error(_)));
+        assert_eq!(registry().execute(&mut state, "clear"), ConsoleOutput::Clear);
+    }
+
+    #[test]
+    fn registering_twice_replaces_command() {
+        let mut registry = registry();
+        registry.register("add", "Replaced", |_, _| Ok("replaced".to_owned()));
+
+        assert_eq!(registry.execute(&mut 0, "add 1"), ConsoleOutput::Text("replaced".to_owned()));
+    }
+
+    #[test]
+    fn complete_unique_command() {
+        let completion = registry().complete("ad");
+
+        assert_eq!(completion.text, "add ");
+        assert_eq!(completion.candidates, vec!["add"]);
+    }
+
+    #[test]
+    fn complete_common_prefix() {
+        let completion = registry().complete("re");
+
+        assert_eq!(completion.text, "render_");
+        assert_eq!(completion.candidates, vec!["render_map", "render_water"]);
+    }
+
+    #[test]
+    fn arguments_are_not_completed() {
+        let completion = registry().complete("add 1");
+
+        assert_eq!(completion.text, "add 1");
+        assert!(completion.candidates.is_empty());
+    }
+}
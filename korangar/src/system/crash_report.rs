@@ -0,0 +1,150 @@
+//! Captures panics and writes them, together with some information about the
+//! system, into a diagnostic bundle that users can attach to bug reports.
+
+use std::backtrace::Backtrace;
+use std::fs::File;
+use std::io::BufWriter;
+use std::panic::PanicHookInfo;
+use std::path::Path;
+use std::sync::Mutex;
+
+#[cfg(feature = "debug")]
+use korangar_debug::logging::{Colorize, print_debug};
+use ron::ser::PrettyConfig;
+use sevenz_rust2::{ArchiveEntry, ArchiveWriter};
+use wgpu::AdapterInfo;
+
+use crate::settings::GraphicsSettings;
+
+const CRASH_REPORT_DIRECTORY: &str = "client/crash_reports";
+/// File containing the path of the latest diagnostic bundle. It only exists
+/// until the user was notified about the crash on the next start.
+const LAST_CRASH_FILE: &str = "client/crash_reports/last_crash.txt";
+
+/// Information about the running client that is attached to a crash report.
+struct CrashContext {
+    adapter_info: Option<AdapterInfo>,
+    /// Serialized graphics settings. We don't keep a copy of the settings
+    /// themselves, since dropping one would save it to disk.
+    graphics_settings: Option<String>,
+}
+
+static CRASH_CONTEXT: Mutex<CrashContext> = Mutex::new(CrashContext {
+    adapter_info: None,
+    graphics_settings: None,
+});
+
+/// Install a panic hook that writes a diagnostic bundle before calling the
+/// default panic hook.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |panic_info| {
+        write_diagnostic_bundle(panic_info);
+        default_hook(panic_info);
+    }));
+}
+
+pub fn set_adapter_info(adapter_info: AdapterInfo) {
+    if let Ok(mut context) = CRASH_CONTEXT.lock() {
+        context.adapter_info = Some(adapter_info);
+    }
+}
+
+/// Remember the graphics settings for the crash report. This serializes the
+/// settings, so it should only be called when they change.
+pub fn set_graphics_settings(graphics_settings: &GraphicsSettings) {
+    let Ok(serialized) = ron::ser::to_string_pretty(graphics_settings, PrettyConfig::new()) else {
+        return;
+    };
+
+    if let Ok(mut context) = CRASH_CONTEXT.lock() {
+        context.graphics_settings = Some(serialized);
+    }
+}
+
+/// Get the path of the diagnostic bundle of the last crash, if the user
+/// wasn't notified about it yet.
+pub fn take_previous_crash_report() -> Option<String> {
+    let bundle_path = std::fs::read_to_string(LAST_CRASH_FILE).ok()?;
+    let _ = std::fs::remove_file(LAST_CRASH_FILE);
+
+    Some(bundle_path.trim().to_owned())
+}
+
+/// Open the directory containing the crash reports in the file manager of the
+/// operating system.
+pub fn open_crash_report_directory() {
+    #[cfg(target_os = "windows")]
+    let program = "explorer";
+    #[cfg(target_os = "macos")]
+    let program = "open";
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let program = "xdg-open";
+
+    if let Err(_error) = std::process::Command::new(program).arg(CRASH_REPORT_DIRECTORY).spawn() {
+        #[cfg(feature = "debug")]
+        print_debug!("[{}] failed to open crash report directory: {:?}", "error".red(), _error);
+    }
+}
+
+fn write_diagnostic_bundle(panic_info: &PanicHookInfo) {
+    let backtrace = Backtrace::force_capture();
+    let thread = std::thread::current();
+    let timestamp = chrono::offset::Local::now();
+
+    let panic_report = format!(
+        "time: {}\nversion: {}\nthread: {}\n{}\n\nbacktrace:\n{}",
+        timestamp.to_rfc3339(),
+        env!("CARGO_PKG_VERSION"),
+        thread.name().unwrap_or("<unnamed>"),
+        panic_info,
+        backtrace
+    );
+
+    let mut files = vec![("panic.txt", panic_report)];
+
+    // The panic could have happened while the context was locked, in which case we
+    // skip the additional information instead of dead-locking.
+    if let Ok(context) = CRASH_CONTEXT.try_lock() {
+        if let Some(adapter_info) = &context.adapter_info {
+            files.push(("adapter.txt", format!("{adapter_info:#?}")));
+        }
+
+        if let Some(graphics_settings) = &context.graphics_settings {
+            files.push(("graphics_settings.ron", graphics_settings.clone()));
+        }
+    }
+
+    #[cfg(feature = "debug")]
+    {
+        korangar_debug::logging::flush_log_file();
+        files.push(("log.txt", korangar_debug::logging::recent_messages().join("\n")));
+    }
+
+    let bundle_path = format!("{CRASH_REPORT_DIRECTORY}/crash_{}.7z", timestamp.format("%Y-%m-%d_%H-%M-%S"));
+
+    let result = std::fs::create_dir_all(CRASH_REPORT_DIRECTORY)
+        .and_then(|_| write_archive(Path::new(&bundle_path), &files))
+        .and_then(|_| std::fs::write(LAST_CRASH_FILE, &bundle_path));
+
+    match result {
+        Ok(()) => eprintln!("wrote diagnostic bundle to {bundle_path}"),
+        Err(error) => eprintln!("failed to write diagnostic bundle: {error:?}"),
+    }
+}
+
+fn write_archive(path: &Path, files: &[(&str, String)]) -> Result<(), std::io::Error> {
+    let file = File::create(path)?;
+    let mut writer = ArchiveWriter::new(BufWriter::new(file)).map_err(std::io::Error::other)?;
+
+    for (name, content) in files {
+        writer
+            .push_archive_entry(ArchiveEntry::new_file(name), Some(content.as_bytes()))
+            .map_err(std::io::Error::other)?;
+    }
+
+    writer.finish()?;
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Handling of the emote wheel, which sends emotions and the chat macros of
+//! the game settings.
+
+use korangar_networking::{GameplayError, MessageColor};
+
+use crate::Client;
+use crate::graphics::ScreenPosition;
+use crate::interface::windows::{EmoteWheelAction, EmoteWheelWindow, WindowClass};
+use crate::settings::GameSettingsPathExt;
+use crate::state::{ClientStatePathExt, client_state, this_entity};
+
+impl Client {
+    /// Open the emote wheel around the cursor. The wheel can only be opened
+    /// while playing a character.
+    pub(crate) fn open_emote_wheel(&mut self, mouse_position: ScreenPosition) {
+        if self.client_state.try_follow(this_entity()).is_none() || self.interface.is_window_with_class_open(WindowClass::EmoteWheel) {
+            return;
+        }
+
+        let chat_macros = self.client_state.follow(client_state().game_settings()).chat_macros.clone();
+
+        self.client_state
+            .follow_mut(client_state().emote_wheel())
+            .open(&chat_macros, mouse_position);
+        self.interface.open_window(EmoteWheelWindow::new(client_state().emote_wheel()));
+    }
+
+    /// Close the emote wheel and run the action that was selected.
+    pub(crate) fn close_emote_wheel(&mut self) {
+        if !self.interface.is_window_with_class_open(WindowClass::EmoteWheel) {
+            return;
+        }
+
+        self.interface.close_window_with_class(WindowClass::EmoteWheel);
+
+        match self.client_state.follow_mut(client_state().emote_wheel()).take_selected_action() {
+            Some(EmoteWheelAction::Emotion(emotion)) => {
+                let _ = self.networking_system.send_emotion(emotion);
+            }
+            Some(EmoteWheelAction::ChatMacro(text)) => {
+                if let Err(error @ GameplayError::RateLimited { .. }) = self
+                    .networking_system
+                    .send_chat_message(self.client_state.follow(client_state().player_name()), &text)
+                {
+                    self.push_chat_message(MessageColor::Error, error.to_string());
+                }
+            }
+            None => {}
+        }
+    }
+}
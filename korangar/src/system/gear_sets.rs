@@ -0,0 +1,61 @@
+//! Handling of the gear set events. Equipping a gear set sends one equip
+//! request at a time, since the map server answers them in order.
+
+use ragnarok_packets::{EquipPosition, InventoryIndex};
+
+use crate::Client;
+use crate::inventory::{EquipRequest, GearSet};
+use crate::settings::GameSettingsPathExt;
+use crate::state::{ClientStatePathExt, client_state};
+
+impl Client {
+    pub(crate) fn save_gear_set(&mut self) {
+        let name = self.client_state.follow_mut(client_state().inventory()).take_gear_set_name();
+
+        if !name.is_empty() {
+            let inventory_items = self.client_state.follow(client_state().inventory().items());
+            let gear_set = GearSet::from_equipped(name, inventory_items);
+
+            self.client_state.follow_mut(client_state().game_settings()).save_gear_set(gear_set);
+        }
+    }
+
+    pub(crate) fn equip_gear_set(&mut self, index: usize) {
+        if let Some(gear_set) = self.client_state.follow(client_state().game_settings()).gear_sets.get(index) {
+            let inventory_items = self.client_state.follow(client_state().inventory().items());
+
+            self.pending_equip_requests = gear_set.equip_requests(inventory_items).into();
+            self.send_equip_request();
+        }
+    }
+
+    pub(crate) fn delete_gear_set(&mut self, index: usize) {
+        self.client_state.follow_mut(client_state().game_settings()).remove_gear_set(index);
+    }
+
+    /// Send the next equip request of a gear set once the map server answered
+    /// the current one.
+    pub(crate) fn advance_equip_requests(&mut self, index: InventoryIndex) {
+        if self.pending_equip_requests.front().is_some_and(|request| request.index == index) {
+            self.pending_equip_requests.pop_front();
+            self.send_equip_request();
+        }
+    }
+
+    /// Send the first pending equip request of a gear set.
+    fn send_equip_request(&mut self) {
+        let Some(&EquipRequest { index, position }) = self.pending_equip_requests.front() else {
+            return;
+        };
+
+        let result = match position == EquipPosition::AMMO {
+            true => self.networking_system.equip_ammunition(index),
+            false => self.networking_system.request_item_equip(index, position),
+        };
+
+        // Don't wait for an answer that will never come.
+        if result.is_err() {
+            self.pending_equip_requests.clear();
+        }
+    }
+}
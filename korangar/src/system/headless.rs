@@ -0,0 +1,664 @@
+//! Headless mode that runs the networking and gameplay loop without creating
+//! a window or a GPU device. A scripted bot plays the character, so
+//! integration tests and server load tests can run in CI or on servers
+//! without a GPU.
+//!
+//! Runs can be recorded and replayed without a server, to compare the
+//! resulting world state against golden files.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "debug")]
+use korangar_debug::logging::{Colorize, print_debug};
+use korangar_networking::{
+    DisconnectReason, NetworkEvent, NetworkEventBuffer, NetworkPlayback, NetworkRecorder, NetworkingSystem, SupportedPacketVersion,
+};
+use ragnarok_packets::handler::NoPacketCallback;
+use ragnarok_packets::{Direction, EntityId, TilePosition, WorldPosition};
+use rand_aes::tls::rand_f32;
+use serde::Deserialize;
+
+use crate::loaders::{GameFileLoader, load_client_info};
+use crate::system::{Session, resolve_service};
+
+/// Usage text that is printed if the headless options are invalid.
+pub const USAGE: &str = "\
+Usage: korangar headless --server <NAME> --username <NAME> [OPTIONS]
+       korangar headless --replay <PATH>
+
+Options:
+  --server <NAME>       Name or address of the server to log in to
+  --username <NAME>     Username of the account
+  --password <PASSWORD> Password of the account, read from KORANGAR_PASSWORD if not specified
+  --character <SLOT>    Character slot to play, defaults to the first character
+  --script <PATH>       Bot script to run, defaults to walking around the spawn point
+  --duration <SECONDS>  Log out after the given time instead of running until the script is finished
+  --record <PATH>       Save the events that are received to a recording
+  --replay <PATH>       Print the world state at the end of a recording instead of connecting to a server";
+
+/// Environment variable that the password is read from, so it doesn't show
+/// up in the process list.
+const PASSWORD_VARIABLE: &str = "KORANGAR_PASSWORD";
+/// Time between two iterations of the gameplay loop.
+const TICK_DURATION: Duration = Duration::from_millis(50);
+/// Without a renderer there is no reason to spread the events over multiple
+/// ticks.
+const NETWORK_EVENT_BUDGET: usize = usize::MAX;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeadlessOptions {
+    /// Display name or address of the server.
+    pub server: String,
+    pub username: String,
+    pub password: String,
+    /// Character slot that is played. The first character is played if this
+    /// is not set.
+    pub character_slot: Option<usize>,
+    /// Path of the bot script.
+    pub script: Option<String>,
+    /// Time after which the bot logs out.
+    pub duration: Option<Duration>,
+    /// Path that the recording of the run is saved to.
+    pub record: Option<String>,
+}
+
+impl HeadlessOptions {
+    /// Parse the command line arguments following the `headless` command.
+    pub fn parse(arguments: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut server = None;
+        let mut username = None;
+        let mut password = None;
+        let mut character_slot = None;
+        let mut script = None;
+        let mut duration = None;
+        let mut record = None;
+        let mut arguments = arguments.into_iter();
+
+        while let Some(argument) = arguments.next() {
+            let mut value = || arguments.next().ok_or_else(|| format!("missing value for {argument}"));
+
+            match argument.as_str() {
+                "--server" => server = Some(value()?),
+                "--username" => username = Some(value()?),
+                "--password" => password = Some(value()?),
+                "--character" => {
+                    let slot = value()?;
+                    character_slot = Some(slot.parse().map_err(|_| format!("invalid character slot \"{slot}\""))?);
+                }
+                "--script" => script = Some(value()?),
+                "--duration" => {
+                    let seconds = value()?;
+                    let seconds = seconds.parse().map_err(|_| format!("invalid duration \"{seconds}\""))?;
+                    duration = Some(Duration::from_secs(seconds));
+                }
+                "--record" => record = Some(value()?),
+                _ => return Err(format!("unknown option \"{argument}\"")),
+            }
+        }
+
+        let password = password
+            .or_else(|| std::env::var(PASSWORD_VARIABLE).ok())
+            .ok_or_else(|| format!("missing --password or {PASSWORD_VARIABLE}"))?;
+
+        Ok(Self {
+            server: server.ok_or("missing --server")?,
+            username: username.ok_or("missing --username")?,
+            password,
+            character_slot,
+            script,
+            duration,
+            record,
+        })
+    }
+}
+
+/// Single step of a [`BotScript`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum BotAction {
+    /// Wait before running the next action.
+    Wait { milliseconds: u64 },
+    /// Walk to a tile on the current map.
+    Walk { x: u16, y: u16 },
+    /// Walk to a random tile around the position the bot entered the map at.
+    WalkRandom { radius: u16 },
+    /// Send a message to the public chat.
+    Chat { text: String },
+    /// Log out and stop the headless client.
+    LogOut,
+}
+
+/// Actions that the bot runs once it entered a map, loaded from a RON file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct BotScript {
+    pub actions: Vec<BotAction>,
+    /// Start over once the last action ran.
+    #[serde(default)]
+    pub repeat: bool,
+}
+
+impl Default for BotScript {
+    fn default() -> Self {
+        Self {
+            actions: vec![BotAction::WalkRandom { radius: 5 }, BotAction::Wait { milliseconds: 2000 }],
+            repeat: true,
+        }
+    }
+}
+
+impl BotScript {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let data = std::fs::read_to_string(path).map_err(|error| format!("failed to read bot script {path}: {error}"))?;
+        ron::from_str(&data).map_err(|error| format!("failed to parse bot script {path}: {error}"))
+    }
+}
+
+/// Steps through a [`BotScript`], handling the waits.
+pub struct BotDriver {
+    script: BotScript,
+    next_index: usize,
+    resume_time: Option<Instant>,
+}
+
+impl BotDriver {
+    pub fn new(script: BotScript) -> Self {
+        Self {
+            script,
+            next_index: 0,
+            resume_time: None,
+        }
+    }
+
+    /// Get the next action that should run. Returns `None` while waiting and
+    /// once the script is finished.
+    pub fn next_action(&mut self, now: Instant) -> Option<BotAction> {
+        if let Some(resume_time) = self.resume_time {
+            if now < resume_time {
+                return None;
+            }
+
+            self.resume_time = None;
+        }
+
+        if self.next_index == self.script.actions.len() && self.script.repeat {
+            self.next_index = 0;
+        }
+
+        let action = self.script.actions.get(self.next_index)?.clone();
+        self.next_index += 1;
+
+        match action {
+            BotAction::Wait { milliseconds } => {
+                self.resume_time = Some(now + Duration::from_millis(milliseconds));
+                None
+            }
+            action => Some(action),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        !self.script.repeat && self.resume_time.is_none() && self.next_index >= self.script.actions.len()
+    }
+}
+
+/// The part of the world state that can be tracked without loading the map.
+/// Movement is not interpolated, so positions are the destinations of the
+/// last movement.
+#[derive(Debug, Default)]
+pub struct HeadlessWorld {
+    pub map_name: Option<String>,
+    /// Position the player entered the current map at.
+    pub spawn_position: Option<TilePosition>,
+    pub player_position: Option<TilePosition>,
+    pub entities: HashMap<EntityId, TilePosition>,
+    pub received_events: usize,
+    pub chat_messages: usize,
+}
+
+impl HeadlessWorld {
+    pub fn handle_event(&mut self, event: &NetworkEvent) {
+        self.received_events += 1;
+
+        match event {
+            NetworkEvent::ChangeMap { map_name, position } => {
+                self.map_name = Some(map_name.clone());
+                self.spawn_position = Some(*position);
+                self.player_position = Some(*position);
+                self.entities.clear();
+            }
+            NetworkEvent::PlayerMove { destination, .. } => {
+                self.player_position = Some(destination.tile_position());
+            }
+            NetworkEvent::AddEntities { entities } => {
+                for entity in entities {
+                    let position = entity.destination.unwrap_or(entity.position);
+                    self.entities.insert(entity.entity_id, position.tile_position());
+                }
+            }
+            NetworkEvent::RemoveEntity { entity_id, .. } => {
+                self.entities.remove(entity_id);
+            }
+            NetworkEvent::EntityMoves { movements } => {
+                for movement in movements {
+                    self.entities.insert(movement.entity_id, movement.destination.tile_position());
+                }
+            }
+            NetworkEvent::ChatMessage { .. } => self.chat_messages += 1,
+            _ => {}
+        }
+    }
+}
+
+/// Lists the whole state in a stable order, so it can be compared against a
+/// golden file.
+impl fmt::Display for HeadlessWorld {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format_position = |position: Option<TilePosition>| match position {
+            Some(position) => format!("{}, {}", position.x, position.y),
+            None => "none".to_owned(),
+        };
+
+        writeln!(formatter, "map: {}", self.map_name.as_deref().unwrap_or("none"))?;
+        writeln!(formatter, "spawn position: {}", format_position(self.spawn_position))?;
+        writeln!(formatter, "player position: {}", format_position(self.player_position))?;
+        writeln!(formatter, "received events: {}", self.received_events)?;
+        writeln!(formatter, "chat messages: {}", self.chat_messages)?;
+
+        let mut entities: Vec<_> = self.entities.iter().collect();
+        entities.sort_by_key(|(entity_id, _)| entity_id.0);
+
+        for (entity_id, position) in entities {
+            writeln!(formatter, "entity {}: {}, {}", entity_id.0, position.x, position.y)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Summary of a headless run.
+pub struct HeadlessReport {
+    pub action_count: usize,
+    pub world: HeadlessWorld,
+}
+
+impl fmt::Display for HeadlessReport {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let world = &self.world;
+
+        write!(
+            formatter,
+            "ran {} actions, received {} events and {} chat messages",
+            self.action_count, world.received_events, world.chat_messages
+        )?;
+
+        if let (Some(map_name), Some(position)) = (&world.map_name, world.player_position) {
+            write!(
+                formatter,
+                ", last position {map_name} {}, {} with {} entities in view",
+                position.x,
+                position.y,
+                world.entities.len()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Log in, play the character with the bot script and log out again. Fails
+/// if any of the connections fails or is lost.
+pub fn run(options: HeadlessOptions) -> Result<HeadlessReport, String> {
+    let script = match &options.script {
+        Some(path) => BotScript::load(path)?,
+        None => BotScript::default(),
+    };
+
+    let game_file_loader = GameFileLoader::default();
+    game_file_loader.load_archives_from_settings();
+    let client_info = load_client_info(&game_file_loader);
+
+    let service = client_info
+        .services
+        .iter()
+        .find(|service| service.display_name.as_ref() == Some(&options.server) || service.address == options.server)
+        .ok_or_else(|| format!("unknown server \"{}\"", options.server))?;
+
+    let (socket_address, packet_version) = resolve_service(service)?;
+
+    let (mut networking_system, mut network_event_buffer) = NetworkingSystem::spawn();
+
+    if let Some(path) = &options.record {
+        let recorder = NetworkRecorder::create(path).map_err(|error| format!("failed to create recording {path}: {error}"))?;
+        networking_system.start_recording(recorder);
+    }
+
+    let record = options.record.clone();
+    let result = play(
+        options,
+        script,
+        &mut networking_system,
+        &mut network_event_buffer,
+        packet_version,
+        socket_address,
+    );
+
+    // Failed runs are saved as well, since they are the most interesting to replay.
+    if let (Some(path), Some(mut recorder)) = (record, networking_system.stop_recording()) {
+        recorder
+            .flush()
+            .map_err(|error| format!("failed to save recording {path}: {error}"))?;
+
+        #[cfg(feature = "debug")]
+        print_debug!("saved recording to {}", path.magenta());
+    }
+
+    result
+}
+
+fn play(
+    options: HeadlessOptions,
+    script: BotScript,
+    networking_system: &mut NetworkingSystem<NoPacketCallback>,
+    network_event_buffer: &mut NetworkEventBuffer,
+    packet_version: SupportedPacketVersion,
+    socket_address: SocketAddr,
+) -> Result<HeadlessReport, String> {
+    let mut world = HeadlessWorld::default();
+    let mut bot_driver = BotDriver::new(script);
+    let mut session = Session::default();
+    let mut character_name = String::new();
+    let mut action_count = 0;
+    let mut logging_out = false;
+    let start_time = Instant::now();
+
+    #[cfg(feature = "debug")]
+    print_debug!("logging in to {} as {}", options.server.magenta(), options.username.magenta());
+
+    session.log_in(
+        networking_system,
+        packet_version,
+        socket_address,
+        options.username,
+        options.password,
+    );
+
+    loop {
+        networking_system.get_events(network_event_buffer);
+
+        for event in network_event_buffer.drain(NETWORK_EVENT_BUDGET) {
+            world.handle_event(&event);
+            session.handle_event(networking_system, &event);
+
+            match event {
+                NetworkEvent::LoginServerConnected { character_servers, .. } => {
+                    let character_server = character_servers
+                        .into_iter()
+                        .next()
+                        .ok_or("login server sent no character servers")?;
+
+                    session.select_character_server(networking_system, character_server);
+                }
+                NetworkEvent::LoginServerConnectionFailed { message, .. } => {
+                    return Err(format!("failed to connect to login server: {message}"));
+                }
+                NetworkEvent::CharacterServerConnected { .. } => {
+                    networking_system.request_character_list().map_err(|error| error.to_string())?;
+                }
+                NetworkEvent::CharacterServerConnectionFailed { message, .. } => {
+                    return Err(format!("failed to connect to character server: {message}"));
+                }
+                NetworkEvent::CharacterList { characters } => {
+                    let character = match options.character_slot {
+                        Some(slot) => characters.iter().find(|character| character.character_number as usize == slot),
+                        None => characters.iter().min_by_key(|character| character.character_number),
+                    }
+                    .ok_or("no character to play")?;
+
+                    #[cfg(feature = "debug")]
+                    print_debug!("playing {} in slot {}", character.name.magenta(), character.character_number);
+
+                    character_name = character.name.clone();
+
+                    networking_system
+                        .select_character(character.character_number as usize)
+                        .map_err(|error| error.to_string())?;
+                }
+                NetworkEvent::CharacterSelectionFailed { message, .. } => {
+                    return Err(format!("failed to select character: {message}"));
+                }
+                NetworkEvent::ChangeMap {
+                    map_name: _map_name,
+                    position: _position,
+                } => {
+                    #[cfg(feature = "debug")]
+                    print_debug!("entered {} at {}, {}", _map_name.magenta(), _position.x, _position.y);
+
+                    // There is no map to load, so the map change is confirmed right away.
+                    networking_system.map_loaded().map_err(|error| error.to_string())?;
+                }
+                NetworkEvent::LoggedOut => {
+                    networking_system.disconnect_from_map_server();
+                    return Ok(HeadlessReport { action_count, world });
+                }
+                NetworkEvent::LoginServerDisconnected {
+                    reason: DisconnectReason::ConnectionError,
+                }
+                | NetworkEvent::CharacterServerDisconnected {
+                    reason: DisconnectReason::ConnectionError,
+                }
+                | NetworkEvent::MapServerDisconnected {
+                    reason: DisconnectReason::ConnectionError,
+                } => return Err("lost the connection to the server".to_owned()),
+                NetworkEvent::MapServerDisconnected { .. } if logging_out => {
+                    return Ok(HeadlessReport { action_count, world });
+                }
+                _ => {}
+            }
+        }
+
+        let timed_out = options.duration.is_some_and(|duration| start_time.elapsed() >= duration);
+
+        if !logging_out && (timed_out || bot_driver.is_finished() || crate::SHUTDOWN_SIGNAL.load(Ordering::SeqCst)) {
+            if world.map_name.is_none() {
+                return Err("stopped before entering a map".to_owned());
+            }
+
+            logging_out = true;
+            let _ = networking_system.log_out();
+        }
+
+        if !logging_out
+            && let Some(spawn_position) = world.spawn_position
+            && let Some(action) = bot_driver.next_action(Instant::now())
+        {
+            action_count += 1;
+
+            let result = match action {
+                BotAction::Wait { .. } => Ok(()),
+                BotAction::Walk { x, y } => networking_system.player_move(WorldPosition::new(x, y, Direction::South)),
+                BotAction::WalkRandom { radius } => {
+                    let offset = || ((rand_f32() * 2.0 - 1.0) * radius as f32) as i16;
+                    let x = spawn_position.x.saturating_add_signed(offset());
+                    let y = spawn_position.y.saturating_add_signed(offset());
+
+                    networking_system.player_move(WorldPosition::new(x, y, Direction::South))
+                }
+                BotAction::Chat { text } => networking_system.send_chat_message(&character_name, &text),
+                BotAction::LogOut => {
+                    logging_out = true;
+                    networking_system.log_out()
+                }
+            };
+
+            result.map_err(|error| error.to_string())?;
+        }
+
+        std::thread::sleep(TICK_DURATION);
+    }
+}
+
+/// Replay a recording of a headless run. The playback advances by the same
+/// tick as a live run, but without waiting, and no actions are sent.
+pub fn replay(mut playback: NetworkPlayback) -> HeadlessWorld {
+    let mut network_event_buffer = NetworkEventBuffer::default();
+    let mut world = HeadlessWorld::default();
+
+    while !playback.is_finished() {
+        playback.get_events(TICK_DURATION.as_secs_f64(), &mut network_event_buffer);
+
+        for event in network_event_buffer.drain(NETWORK_EVENT_BUDGET) {
+            world.handle_event(&event);
+        }
+    }
+
+    world
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+    use std::time::{Duration, Instant};
+
+    use korangar_networking::{NetworkEvent, NetworkPlayback};
+    use ragnarok_packets::{ClientTick, Direction, TilePosition, WorldPosition};
+
+    use super::{BotAction, BotDriver, BotScript, HeadlessOptions, HeadlessWorld, replay};
+
+    /// Setting this variable writes the current world state to the golden
+    /// files instead of comparing against them.
+    const UPDATE_GOLDEN_FILES_VARIABLE: &str = "KORANGAR_UPDATE_GOLDEN_FILES";
+
+    fn parse(arguments: &[&str]) -> Result<HeadlessOptions, String> {
+        HeadlessOptions::parse(arguments.iter().map(|argument| argument.to_string()))
+    }
+
+    #[test]
+    fn parse_options() {
+        let options = parse(&[
+            "--server",
+            "Local",
+            "--username",
+            "bot",
+            "--password",
+            "secret",
+            "--character",
+            "1",
+            "--duration",
+            "60",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            options,
+            HeadlessOptions {
+                server: "Local".to_owned(),
+                username: "bot".to_owned(),
+                password: "secret".to_owned(),
+                character_slot: Some(1),
+                script: None,
+                duration: Some(Duration::from_secs(60)),
+                record: None,
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_options_are_rejected() {
+        assert!(parse(&["--username", "bot", "--password", "secret"]).is_err());
+        assert!(
+            parse(&[
+                "--server",
+                "Local",
+                "--username",
+                "bot",
+                "--password",
+                "secret",
+                "--duration",
+                "soon"
+            ])
+            .is_err()
+        );
+        assert!(parse(&["--offline"]).is_err());
+    }
+
+    #[test]
+    fn script_waits_between_actions() {
+        let script: BotScript = ron::from_str("(actions: [Chat(text: \"hi\"), Wait(milliseconds: 100), LogOut])").unwrap();
+        let mut bot_driver = BotDriver::new(script);
+        let start = Instant::now();
+
+        assert_eq!(bot_driver.next_action(start), Some(BotAction::Chat { text: "hi".to_owned() }));
+        assert_eq!(bot_driver.next_action(start), None);
+        assert_eq!(bot_driver.next_action(start + Duration::from_millis(50)), None);
+        assert_eq!(
+            bot_driver.next_action(start + Duration::from_millis(100)),
+            Some(BotAction::LogOut)
+        );
+        assert!(bot_driver.is_finished());
+    }
+
+    #[test]
+    fn repeating_script_starts_over() {
+        let mut bot_driver = BotDriver::new(BotScript {
+            actions: vec![BotAction::Walk { x: 10, y: 12 }],
+            repeat: true,
+        });
+        let now = Instant::now();
+
+        assert_eq!(bot_driver.next_action(now), Some(BotAction::Walk { x: 10, y: 12 }));
+        assert_eq!(bot_driver.next_action(now), Some(BotAction::Walk { x: 10, y: 12 }));
+        assert!(!bot_driver.is_finished());
+    }
+
+    #[test]
+    fn world_tracks_player_position() {
+        let mut world = HeadlessWorld::default();
+
+        world.handle_event(&NetworkEvent::ChangeMap {
+            map_name: "prontera".to_owned(),
+            position: TilePosition { x: 150, y: 150 },
+        });
+        world.handle_event(&NetworkEvent::PlayerMove {
+            origin: WorldPosition::new(150, 150, Direction::South),
+            destination: WorldPosition::new(155, 148, Direction::South),
+            starting_timestamp: ClientTick(0),
+        });
+
+        assert_eq!(world.map_name.as_deref(), Some("prontera"));
+        assert_eq!(world.spawn_position, Some(TilePosition { x: 150, y: 150 }));
+        assert_eq!(world.player_position, Some(TilePosition { x: 155, y: 148 }));
+        assert_eq!(world.received_events, 2);
+    }
+
+    #[test]
+    fn replays_match_golden_files() {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/recordings");
+        let mut replayed_recordings = 0;
+
+        for entry in std::fs::read_dir(&directory).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path.extension().is_none_or(|extension| extension != "recording") {
+                continue;
+            }
+
+            let playback = NetworkPlayback::load(&path).unwrap();
+            let world_state = replay(playback).to_string();
+            let golden_path = path.with_extension("golden");
+
+            if std::env::var_os(UPDATE_GOLDEN_FILES_VARIABLE).is_some() {
+                std::fs::write(&golden_path, &world_state).unwrap();
+            } else {
+                let expected = std::fs::read_to_string(&golden_path).unwrap();
+                assert_eq!(world_state, expected, "world state of {} changed", path.display());
+            }
+
+            replayed_recordings += 1;
+        }
+
+        assert!(replayed_recordings > 0);
+    }
+}
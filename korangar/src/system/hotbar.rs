@@ -0,0 +1,45 @@
+//! Keeping the hotbar in sync with the inventory and using the items placed
+//! in it.
+
+use korangar_networking::InventoryItemDetails;
+use ragnarok_packets::HotbarSlot;
+
+use crate::Client;
+use crate::inventory::InventoryPathExt;
+use crate::state::{ClientStatePathExt, client_state};
+
+impl Client {
+    /// Update the item amounts shown on the hotbar from the inventory.
+    pub(crate) fn sync_hotbar_items(&mut self) {
+        let mut hotbar = std::mem::take(self.client_state.follow_mut(client_state().hotbar()));
+        hotbar.sync_items(self.client_state.follow(client_state().inventory().items()));
+        *self.client_state.follow_mut(client_state().hotbar()) = hotbar;
+    }
+
+    /// Use a stack of the item that is placed in the hotbar slot, if there is
+    /// any left.
+    pub(crate) fn use_hotbar_item(&mut self, slot: HotbarSlot) {
+        let Some(item_id) = self
+            .client_state
+            .follow(client_state().hotbar())
+            .get_item_in_slot(slot)
+            .as_ref()
+            .map(|item| item.item_id)
+        else {
+            return;
+        };
+
+        let index = self
+            .client_state
+            .follow(client_state().inventory().items())
+            .iter()
+            .find(|item| item.item_id == item_id && matches!(item.details, InventoryItemDetails::Regular { amount, .. } if amount > 0))
+            .map(|item| item.index);
+
+        if let Some(index) = index
+            && let Some(login_data) = self.session.login_data()
+        {
+            let _ = self.networking_system.use_item(index, login_data.account_id);
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Detection of other running clients, so multiple instances can be used at
+//! the same time without overwriting each other's window layout.
+//!
+//! Every instance reserves an index by locking a file. The operating system
+//! releases the lock once the process exits, even if it crashes, so stale
+//! reservations can't happen.
+
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+#[cfg(feature = "debug")]
+use korangar_debug::logging::{Colorize, print_debug};
+
+use crate::settings::settings_path;
+
+/// Number of instances that are told apart. Any further instances share the
+/// files of the first one.
+const MAXIMUM_INSTANCE_COUNT: usize = 8;
+
+struct Instance {
+    index: usize,
+    _lock_file: Option<File>,
+}
+
+static INSTANCE: OnceLock<Instance> = OnceLock::new();
+
+/// Reserve the first index that is not used by another running client. Needs
+/// to be called after the settings directory is set and before the window
+/// cache is loaded.
+pub fn register_instance() {
+    let instance = (0..MAXIMUM_INSTANCE_COUNT)
+        .find_map(|index| {
+            let lock_file = OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(settings_path(&format!("client/instance_{index}.lock")))
+                .ok()?;
+
+            match lock_file.try_lock() {
+                Ok(()) => Some(Instance {
+                    index,
+                    _lock_file: Some(lock_file),
+                }),
+                Err(TryLockError::WouldBlock) => None,
+                // Locking is not supported, so we can't tell the instances apart.
+                Err(TryLockError::Error(_)) => Some(Instance {
+                    index: 0,
+                    _lock_file: None,
+                }),
+            }
+        })
+        .unwrap_or(Instance {
+            index: 0,
+            _lock_file: None,
+        });
+
+    #[cfg(feature = "debug")]
+    print_debug!("running as client instance {}", (instance.index + 1).magenta());
+
+    let _ = INSTANCE.set(instance);
+}
+
+/// Index of this client, starting at zero for the first instance.
+pub fn instance_index() -> usize {
+    INSTANCE.get().map_or(0, |instance| instance.index)
+}
+
+/// Path of a settings file that is specific to this instance. The first
+/// instance uses the name unchanged, so running a single client behaves as
+/// before.
+pub fn instance_settings_path(file_name: &str) -> PathBuf {
+    settings_path(&file_name_for_instance(file_name, instance_index()))
+}
+
+fn file_name_for_instance(file_name: &str, index: usize) -> String {
+    if index == 0 {
+        return file_name.to_owned();
+    }
+
+    match file_name.rsplit_once('.') {
+        Some((stem, extension)) => format!("{stem}_{}.{extension}", index + 1),
+        None => format!("{file_name}_{}", index + 1),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::file_name_for_instance;
+
+    #[test]
+    fn first_instance_uses_original_name() {
+        assert_eq!(file_name_for_instance("client/window_cache.ron", 0), "client/window_cache.ron");
+    }
+
+    #[test]
+    fn other_instances_are_numbered() {
+        assert_eq!(
+            file_name_for_instance("client/window_cache.ron", 1),
+            "client/window_cache_2.ron"
+        );
+        assert_eq!(file_name_for_instance("client/window_cache", 2), "client/window_cache_3");
+    }
+}
@@ -0,0 +1,120 @@
+//! Command line options for launching the client into a specific state, which
+//! is useful for scripted launches, taking screenshots for the documentation,
+//! and iterating faster during development.
+
+/// Usage text that is printed if the command line options are invalid.
+pub const USAGE: &str = "\
+Usage: korangar [OPTIONS]
+       korangar sync-cache
+       korangar benchmark [MAPS]...
+       korangar verify-game-files
+       korangar headless --server <NAME> --username <NAME> [OPTIONS]
+       korangar headless --replay <PATH>
+
+Options:
+  --server <NAME>       Select the server with the given name or address in the login window
+  --offline             Don't open the login window and only show the map
+  --map <NAME>          Map that is shown in offline mode, implies --offline
+  --character <SLOT>    Select the character in the given slot after logging in
+  --settings <PATH>     Load and save the settings in the given directory
+  --audit-fallbacks     Record every asset that is replaced by a fallback and export a report on exit";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LaunchOptions {
+    /// Display name or address of the server that is selected in the login
+    /// window.
+    pub server: Option<String>,
+    /// Skip the login window and only show the map.
+    pub offline: bool,
+    /// Map that is shown instead of the default map.
+    pub map: Option<String>,
+    /// Character slot that is selected once the character list is received.
+    pub character_slot: Option<usize>,
+    /// Directory of the settings files.
+    pub settings_directory: Option<String>,
+    /// Record all assets that are replaced by a fallback during the session.
+    pub audit_fallbacks: bool,
+}
+
+impl LaunchOptions {
+    /// Parse the command line arguments, excluding the name of the binary.
+    pub fn parse(arguments: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut launch_options = Self::default();
+        let mut arguments = arguments.into_iter();
+
+        while let Some(argument) = arguments.next() {
+            let mut value = || arguments.next().ok_or_else(|| format!("missing value for {argument}"));
+
+            match argument.as_str() {
+                "--server" => launch_options.server = Some(value()?),
+                "--offline" => launch_options.offline = true,
+                "--map" => {
+                    launch_options.map = Some(value()?);
+                    launch_options.offline = true;
+                }
+                "--character" => {
+                    let slot = value()?;
+                    let slot = slot.parse().map_err(|_| format!("invalid character slot \"{slot}\""))?;
+                    launch_options.character_slot = Some(slot);
+                }
+                "--settings" => launch_options.settings_directory = Some(value()?),
+                "--audit-fallbacks" => launch_options.audit_fallbacks = true,
+                _ => return Err(format!("unknown option \"{argument}\"")),
+            }
+        }
+
+        if launch_options.offline && (launch_options.server.is_some() || launch_options.character_slot.is_some()) {
+            return Err("--server and --character can't be used in offline mode".to_owned());
+        }
+
+        Ok(launch_options)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LaunchOptions;
+
+    fn parse(arguments: &[&str]) -> Result<LaunchOptions, String> {
+        LaunchOptions::parse(arguments.iter().map(|argument| argument.to_string()))
+    }
+
+    #[test]
+    fn parse_options() {
+        let launch_options = parse(&[
+            "--server",
+            "Local",
+            "--character",
+            "2",
+            "--settings",
+            "profiles/test",
+            "--audit-fallbacks",
+        ])
+        .unwrap();
+
+        assert_eq!(launch_options, LaunchOptions {
+            server: Some("Local".to_owned()),
+            offline: false,
+            map: None,
+            character_slot: Some(2),
+            settings_directory: Some("profiles/test".to_owned()),
+            audit_fallbacks: true,
+        });
+    }
+
+    #[test]
+    fn map_implies_offline() {
+        let launch_options = parse(&["--map", "prontera"]).unwrap();
+
+        assert!(launch_options.offline);
+        assert_eq!(launch_options.map.as_deref(), Some("prontera"));
+    }
+
+    #[test]
+    fn invalid_options_are_rejected() {
+        assert!(parse(&["--character"]).is_err());
+        assert!(parse(&["--character", "first"]).is_err());
+        assert!(parse(&["--offline", "--server", "Local"]).is_err());
+        assert!(parse(&["--fullscreen"]).is_err());
+    }
+}
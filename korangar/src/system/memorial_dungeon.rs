@@ -0,0 +1,83 @@
+//! Handling of the memorial dungeon events. The player is warned shortly
+//! before the memorial dungeon is destroyed.
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use korangar_networking::{InstanceDestroyReason, MessageColor};
+
+use crate::Client;
+use crate::interface::toast::ToastCategory;
+use crate::settings::GameSettingsPathExt;
+use crate::state::{ClientStatePathExt, client_state};
+
+/// How long before the memorial dungeon is destroyed the player is warned.
+const INSTANCE_WARNING_DURATION: Duration = Duration::from_secs(5 * 60);
+
+impl Client {
+    pub(crate) fn instance_queued(&mut self, name: String, queue_position: u16) {
+        self.push_chat_message(
+            MessageColor::Information,
+            format!("Waiting for the memorial dungeon {name} to be created (position {queue_position})"),
+        );
+    }
+
+    pub(crate) fn instance_queue_position_changed(&mut self, queue_position: u16) {
+        self.push_chat_message(
+            MessageColor::Information,
+            format!("Position in the memorial dungeon queue: {queue_position}"),
+        );
+    }
+
+    /// The destroy time is a unix timestamp.
+    pub(crate) fn instance_ready(&mut self, name: String, destroy_time: u32) {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let remaining_time = Duration::from_secs(destroy_time as u64).saturating_sub(now);
+
+        self.instance_warning_time = remaining_time
+            .checked_sub(INSTANCE_WARNING_DURATION)
+            .map(|until_warning| Instant::now() + until_warning);
+
+        self.push_chat_message(
+            MessageColor::Information,
+            format!(
+                "The memorial dungeon {name} is ready and will be destroyed in {} minutes",
+                remaining_time.as_secs() / 60
+            ),
+        );
+    }
+
+    pub(crate) fn instance_destroyed(&mut self, reason: InstanceDestroyReason) {
+        self.instance_warning_time = None;
+
+        if self
+            .client_state
+            .follow(client_state().game_settings())
+            .shows_toast(ToastCategory::SystemWarning)
+        {
+            self.toasts.push(ToastCategory::SystemWarning, reason.to_string(), Instant::now());
+        }
+
+        self.push_chat_message(MessageColor::Information, reason.to_string());
+    }
+
+    /// Show the warning once the memorial dungeon is about to be destroyed.
+    pub(crate) fn update_instance_warning(&mut self) {
+        if self.instance_warning_time.is_none_or(|warning_time| Instant::now() < warning_time) {
+            return;
+        }
+
+        self.instance_warning_time = None;
+
+        if self
+            .client_state
+            .follow(client_state().game_settings())
+            .shows_toast(ToastCategory::SystemWarning)
+        {
+            let text = format!(
+                "The memorial dungeon will be destroyed in {} minutes",
+                INSTANCE_WARNING_DURATION.as_secs() / 60
+            );
+            self.toasts.push(ToastCategory::SystemWarning, text, Instant::now());
+        }
+    }
+}
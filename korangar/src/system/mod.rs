@@ -1,3 +1,35 @@
+mod attendance;
+mod auction;
+mod auto_login;
+mod bank;
+mod benchmark;
+#[cfg(feature = "debug")]
+mod console;
+pub mod crash_report;
+mod emote_wheel;
+mod gear_sets;
+pub mod headless;
+mod hotbar;
+mod instance;
+pub mod launch_options;
+mod memorial_dungeon;
+mod offline_script;
+mod party;
+mod roulette;
+mod session;
+#[cfg(feature = "debug")]
+mod stress_test;
 mod timer;
 
+pub use self::auto_login::AutoLogin;
+pub use self::benchmark::Benchmark;
+#[cfg(feature = "debug")]
+pub use self::console::{ConsoleCommandRegistry, ConsoleOutput};
+pub use self::headless::HeadlessOptions;
+pub use self::instance::{instance_settings_path, instance_index, register_instance};
+pub use self::launch_options::LaunchOptions;
+pub use self::offline_script::OfflineScripts;
+pub use self::session::{Session, resolve_service};
+#[cfg(feature = "debug")]
+pub use self::stress_test::StressTest;
 pub use self::timer::GameTimer;
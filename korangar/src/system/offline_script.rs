@@ -0,0 +1,578 @@
+//! Lua scripts that fill the maps in offline mode with content. Every map can
+//! have a script at `data\script\offline\<map>.lua`, which is run when the map
+//! is entered. Scripts can spawn NPCs and warp portals, talk to the player,
+//! grant items, warp the player and run timed events through the functions of
+//! the global `korangar` table.
+//!
+//! Scripts act like a server: everything they do is turned into
+//! [`NetworkEvent`]s, so the client handles offline content the same way as
+//! content of a real server.
+
+use std::collections::HashMap;
+
+#[cfg(feature = "debug")]
+use korangar_debug::logging::{Colorize, print_debug};
+use korangar_loaders::FileLoader;
+use korangar_networking::{EntityData, InventoryItem, InventoryItemDetails, MessageColor, NetworkEvent, NetworkEventBuffer, NoMetadata};
+use mlua::{Function, IntoLuaMulti, Lua, Table, Thread, ThreadStatus};
+use ragnarok_packets::{
+    Direction, DisappearanceReason, EntityId, EquipPosition, InventoryIndex, ItemId, RegularItemFlags, Sex, TilePosition, WorldPosition,
+};
+
+use crate::loaders::GameFileLoader;
+
+/// Job of the warp portal entity.
+const WARP_PORTAL_JOB: u16 = 45;
+/// Item type of etc items, which granted items are shown as.
+const ETC_ITEM_TYPE: u8 = 3;
+/// Inventory indices of the server start at 2.
+const FIRST_INVENTORY_INDEX: u16 = 2;
+
+/// Dialog functions that wait for the player are written in Lua, since
+/// only Lua code can yield the coroutine of a conversation.
+const PRELUDE: &str = r#"
+function korangar.next(npc)
+    korangar.add_next_button(npc)
+    coroutine.yield()
+end
+
+function korangar.choose(npc, ...)
+    korangar.add_choice_buttons(npc, { ... })
+    return coroutine.yield()
+end
+
+function korangar.close(npc)
+    korangar.add_close_button(npc)
+    coroutine.yield()
+end
+"#;
+
+struct Timer {
+    remaining_time: f64,
+    /// Timers with an interval are repeated until the map is left.
+    interval: Option<f64>,
+    callback: Function,
+}
+
+/// State that is shared with the functions of the `korangar` table.
+#[derive(Default)]
+struct ScriptState {
+    events: Vec<NetworkEvent>,
+    next_entity_id: u32,
+    click_handlers: HashMap<EntityId, Function>,
+    warp_portals: HashMap<EntityId, (String, TilePosition)>,
+    timers: Vec<Timer>,
+    /// NPC of the dialog window that is currently open.
+    dialog_npc: Option<EntityId>,
+}
+
+impl ScriptState {
+    fn spawn(&mut self, job: u16, position: TilePosition, direction: Direction, name: Option<String>) -> EntityId {
+        self.next_entity_id += 1;
+        let entity_id = EntityId(self.next_entity_id);
+
+        self.events.push(NetworkEvent::AddEntities {
+            entities: vec![EntityData {
+                entity_id,
+                movement_speed: 150,
+                job,
+                head: 0,
+                position: WorldPosition::new(position.x, position.y, direction),
+                destination: None,
+                health_points: 100,
+                maximum_health_points: 100,
+                head_direction: 0,
+                sex: Sex::Male,
+                appearance: Default::default(),
+                body_state: Default::default(),
+                hidden: false,
+            }],
+        });
+
+        if let Some(name) = name {
+            self.events.push(NetworkEvent::UpdateEntityDetails { entity_id, name });
+        }
+
+        entity_id
+    }
+}
+
+/// A conversation with an NPC, which runs in its own coroutine so it can wait
+/// for the player.
+struct Conversation {
+    npc_id: EntityId,
+    thread: Thread,
+}
+
+/// Scripts of offline mode. Only the script of the current map is running.
+#[derive(Default)]
+pub struct OfflineScripts {
+    lua: Option<Lua>,
+    conversation: Option<Conversation>,
+    /// Inventory indices of the granted items, so items of the same kind
+    /// stack. These are kept when changing maps, just like the inventory.
+    item_indices: HashMap<ItemId, InventoryIndex>,
+}
+
+impl OfflineScripts {
+    /// Stop the script of the previous map and run the script of the map
+    /// that was entered, if it has one.
+    pub fn load_map(&mut self, game_file_loader: &GameFileLoader, map_name: &str) {
+        self.lua = None;
+        self.conversation = None;
+
+        let path = format!("data\\script\\offline\\{map_name}.lua");
+        let Ok(data) = game_file_loader.get(&path) else {
+            return;
+        };
+
+        #[cfg(feature = "debug")]
+        print_debug!("running offline script {}", path.magenta());
+
+        self.run(path, &data);
+    }
+
+    fn run(&mut self, name: String, source: &[u8]) {
+        let lua = Lua::new();
+        lua.set_app_data(ScriptState::default());
+
+        let result = Self::register_functions(&lua).and_then(|_| lua.load(source).set_name(name).exec());
+
+        match result {
+            Ok(()) => self.lua = Some(lua),
+            Err(_error) => {
+                #[cfg(feature = "debug")]
+                print_debug!("[{}] failed to run offline script: {}", "error".red(), _error);
+            }
+        }
+    }
+
+    fn register_functions(lua: &Lua) -> mlua::Result<()> {
+        let korangar = lua.create_table()?;
+
+        // Spawn an NPC or monster, depending on the job.
+        korangar.set(
+            "spawn",
+            lua.create_function(|lua, options: Table| {
+                let job = options.get("job")?;
+                let position = TilePosition::new(options.get("x")?, options.get("y")?);
+                let direction = Direction::from(options.get::<Option<u16>>("direction")?.unwrap_or(0));
+                let name = options.get("name")?;
+                let on_click = options.get::<Option<Function>>("on_click")?;
+
+                let mut state = lua.app_data_mut::<ScriptState>().unwrap();
+                let entity_id = state.spawn(job, position, direction, name);
+
+                if let Some(on_click) = on_click {
+                    state.click_handlers.insert(entity_id, on_click);
+                }
+
+                Ok(entity_id.0)
+            })?,
+        )?;
+
+        korangar.set(
+            "remove",
+            lua.create_function(|lua, entity_id: u32| {
+                let entity_id = EntityId(entity_id);
+                let mut state = lua.app_data_mut::<ScriptState>().unwrap();
+
+                state.click_handlers.remove(&entity_id);
+                state.warp_portals.remove(&entity_id);
+                state.events.push(NetworkEvent::RemoveEntity {
+                    entity_id,
+                    reason: DisappearanceReason::OutOfSight,
+                });
+
+                Ok(())
+            })?,
+        )?;
+
+        korangar.set(
+            "on_click",
+            lua.create_function(|lua, (entity_id, callback): (u32, Function)| {
+                let mut state = lua.app_data_mut::<ScriptState>().unwrap();
+                state.click_handlers.insert(EntityId(entity_id), callback);
+                Ok(())
+            })?,
+        )?;
+
+        // Warp portals take the player to the destination when clicked, since
+        // there is no player walking into them in offline mode.
+        korangar.set(
+            "warp_portal",
+            lua.create_function(|lua, options: Table| {
+                let position = TilePosition::new(options.get("x")?, options.get("y")?);
+                let map_name = options.get("map")?;
+                let destination = TilePosition::new(options.get("to_x")?, options.get("to_y")?);
+
+                let mut state = lua.app_data_mut::<ScriptState>().unwrap();
+                let entity_id = state.spawn(WARP_PORTAL_JOB, position, Direction::South, None);
+                state.warp_portals.insert(entity_id, (map_name, destination));
+
+                Ok(entity_id.0)
+            })?,
+        )?;
+
+        korangar.set(
+            "warp",
+            lua.create_function(|lua, (map_name, x, y): (String, u16, u16)| {
+                let mut state = lua.app_data_mut::<ScriptState>().unwrap();
+                state.events.push(NetworkEvent::ChangeMap {
+                    map_name,
+                    position: TilePosition::new(x, y),
+                });
+                Ok(())
+            })?,
+        )?;
+
+        korangar.set(
+            "say",
+            lua.create_function(|lua, (npc_id, text): (u32, String)| {
+                let npc_id = EntityId(npc_id);
+                let mut state = lua.app_data_mut::<ScriptState>().unwrap();
+
+                state.dialog_npc = Some(npc_id);
+                state.events.push(NetworkEvent::OpenDialog { text, npc_id });
+                Ok(())
+            })?,
+        )?;
+
+        korangar.set(
+            "add_next_button",
+            lua.create_function(|lua, npc_id: u32| {
+                let npc_id = EntityId(npc_id);
+                let mut state = lua.app_data_mut::<ScriptState>().unwrap();
+
+                state.dialog_npc = Some(npc_id);
+                state.events.push(NetworkEvent::AddNextButton { npc_id });
+                Ok(())
+            })?,
+        )?;
+
+        korangar.set(
+            "add_choice_buttons",
+            lua.create_function(|lua, (npc_id, choices): (u32, Vec<String>)| {
+                let npc_id = EntityId(npc_id);
+                let mut state = lua.app_data_mut::<ScriptState>().unwrap();
+
+                state.dialog_npc = Some(npc_id);
+                state.events.push(NetworkEvent::AddChoiceButtons { choices, npc_id });
+                Ok(())
+            })?,
+        )?;
+
+        korangar.set(
+            "add_close_button",
+            lua.create_function(|lua, npc_id: u32| {
+                let mut state = lua.app_data_mut::<ScriptState>().unwrap();
+
+                state.dialog_npc = None;
+                state.events.push(NetworkEvent::AddCloseButton { npc_id: EntityId(npc_id) });
+                Ok(())
+            })?,
+        )?;
+
+        korangar.set(
+            "give_item",
+            lua.create_function(|lua, (item_id, amount): (u32, Option<u16>)| {
+                let mut state = lua.app_data_mut::<ScriptState>().unwrap();
+                state.events.push(NetworkEvent::IventoryItemAdded {
+                    item: InventoryItem {
+                        metadata: NoMetadata,
+                        // The index is assigned once the event is handed out.
+                        index: InventoryIndex(0),
+                        item_id: ItemId(item_id),
+                        item_type: ETC_ITEM_TYPE,
+                        slot: [0; 4],
+                        hire_expiration_date: 0,
+                        details: InventoryItemDetails::Regular {
+                            amount: amount.unwrap_or(1),
+                            equipped_position: EquipPosition::NONE,
+                            flags: RegularItemFlags::IDENTIFIED,
+                        },
+                    },
+                });
+                Ok(())
+            })?,
+        )?;
+
+        korangar.set(
+            "announce",
+            lua.create_function(|lua, text: String| {
+                let mut state = lua.app_data_mut::<ScriptState>().unwrap();
+                state.events.push(NetworkEvent::ChatMessage {
+                    text,
+                    color: MessageColor::Server,
+                });
+                Ok(())
+            })?,
+        )?;
+
+        korangar.set(
+            "after",
+            lua.create_function(|lua, (seconds, callback): (f64, Function)| {
+                let mut state = lua.app_data_mut::<ScriptState>().unwrap();
+                state.timers.push(Timer {
+                    remaining_time: seconds,
+                    interval: None,
+                    callback,
+                });
+                Ok(())
+            })?,
+        )?;
+
+        korangar.set(
+            "every",
+            lua.create_function(|lua, (seconds, callback): (f64, Function)| {
+                let mut state = lua.app_data_mut::<ScriptState>().unwrap();
+                state.timers.push(Timer {
+                    remaining_time: seconds,
+                    interval: Some(seconds),
+                    callback,
+                });
+                Ok(())
+            })?,
+        )?;
+
+        lua.globals().set("korangar", korangar)?;
+        lua.load(PRELUDE).set_name("prelude").exec()
+    }
+
+    /// Run the timed events and hand out the events of the script.
+    pub fn update(&mut self, delta_time: f64, network_event_buffer: &mut NetworkEventBuffer) {
+        let Some(lua) = &self.lua else {
+            return;
+        };
+
+        // The callbacks are collected first, since they need to access the state
+        // themselves.
+        let mut callbacks = Vec::new();
+
+        lua.app_data_mut::<ScriptState>().unwrap().timers.retain_mut(|timer| {
+            timer.remaining_time -= delta_time;
+
+            if timer.remaining_time > 0.0 {
+                return true;
+            }
+
+            callbacks.push(timer.callback.clone());
+
+            match timer.interval {
+                Some(interval) => {
+                    timer.remaining_time += interval;
+                    true
+                }
+                None => false,
+            }
+        });
+
+        for callback in callbacks {
+            report_error(callback.call::<()>(()));
+        }
+
+        let mut state = lua.app_data_mut::<ScriptState>().unwrap();
+
+        for mut event in state.events.drain(..) {
+            if let NetworkEvent::IventoryItemAdded { item } = &mut event {
+                let next_index = InventoryIndex(FIRST_INVENTORY_INDEX + self.item_indices.len() as u16);
+                item.index = *self.item_indices.entry(item.item_id).or_insert(next_index);
+            }
+
+            network_event_buffer.push(event);
+        }
+    }
+
+    /// Start the conversation of an NPC or use a warp portal.
+    pub fn interact(&mut self, entity_id: EntityId) {
+        let Some(lua) = &self.lua else {
+            return;
+        };
+
+        let mut state = lua.app_data_mut::<ScriptState>().unwrap();
+
+        if let Some((map_name, position)) = state.warp_portals.get(&entity_id).cloned() {
+            state.events.push(NetworkEvent::ChangeMap { map_name, position });
+            return;
+        }
+
+        // Only one conversation can run at a time.
+        if self.conversation.is_some() {
+            return;
+        }
+
+        let Some(handler) = state.click_handlers.get(&entity_id).cloned() else {
+            return;
+        };
+
+        drop(state);
+
+        match lua.create_thread(handler) {
+            Ok(thread) => {
+                self.conversation = Some(Conversation { npc_id: entity_id, thread });
+                self.resume_conversation(entity_id.0);
+            }
+            Err(error) => report_error::<()>(Err(error)),
+        }
+    }
+
+    pub fn next_dialog(&mut self, npc_id: EntityId) {
+        if self.is_talking_to(npc_id) {
+            self.resume_conversation(());
+        }
+    }
+
+    /// Options start at 1. Option -1 cancels the conversation.
+    pub fn choose_dialog_option(&mut self, npc_id: EntityId, option: i8) {
+        if !self.is_talking_to(npc_id) {
+            return;
+        }
+
+        match option {
+            -1 => self.close_dialog(npc_id),
+            option => self.resume_conversation(option),
+        }
+    }
+
+    /// Closing the dialog ends the conversation.
+    pub fn close_dialog(&mut self, npc_id: EntityId) {
+        if !self.is_talking_to(npc_id) {
+            return;
+        }
+
+        self.conversation = None;
+
+        if let Some(lua) = &self.lua {
+            lua.app_data_mut::<ScriptState>().unwrap().dialog_npc = None;
+        }
+    }
+
+    fn is_talking_to(&self, npc_id: EntityId) -> bool {
+        self.conversation.as_ref().is_some_and(|conversation| conversation.npc_id == npc_id)
+    }
+
+    fn resume_conversation(&mut self, arguments: impl IntoLuaMulti) {
+        let (Some(lua), Some(conversation)) = (&self.lua, &self.conversation) else {
+            return;
+        };
+
+        let result = conversation.thread.resume::<()>(arguments);
+        let is_finished = result.is_err() || !matches!(conversation.thread.status(), ThreadStatus::Resumable);
+
+        report_error(result);
+
+        if is_finished {
+            let npc_id = conversation.npc_id;
+            self.conversation = None;
+
+            // A conversation that ends without closing the dialog gets a close
+            // button, so the player can close it.
+            let mut state = lua.app_data_mut::<ScriptState>().unwrap();
+
+            if state.dialog_npc.take() == Some(npc_id) {
+                state.events.push(NetworkEvent::AddCloseButton { npc_id });
+            }
+        }
+    }
+}
+
+fn report_error<T>(_result: mlua::Result<T>) {
+    #[cfg(feature = "debug")]
+    if let Err(error) = _result {
+        print_debug!("[{}] offline script error: {}", "error".red(), error);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use korangar_networking::{InventoryItemDetails, NetworkEvent, NetworkEventBuffer};
+    use ragnarok_packets::{EntityId, InventoryIndex, ItemId};
+
+    use super::OfflineScripts;
+
+    const SCRIPT: &str = r#"
+        local npc = korangar.spawn({ job = 86, name = "Guide", x = 10, y = 12 })
+
+        korangar.on_click(npc, function(npc)
+            korangar.say(npc, "Hello")
+            korangar.next(npc)
+
+            if korangar.choose(npc, "Potion", "Warp") == 1 then
+                korangar.give_item(501, 2)
+            else
+                korangar.warp("prontera", 150, 150)
+            end
+        end)
+    "#;
+
+    fn events(offline_scripts: &mut OfflineScripts) -> Vec<NetworkEvent> {
+        let mut network_event_buffer = NetworkEventBuffer::default();
+        offline_scripts.update(0.0, &mut network_event_buffer);
+        network_event_buffer.drain(usize::MAX).collect()
+    }
+
+    fn start_script() -> OfflineScripts {
+        let mut offline_scripts = OfflineScripts::default();
+        offline_scripts.run("test".to_owned(), SCRIPT.as_bytes());
+        offline_scripts
+    }
+
+    #[test]
+    fn spawned_npc_is_added() {
+        let mut offline_scripts = start_script();
+
+        let events = events(&mut offline_scripts);
+
+        assert!(matches!(&events[..], [
+            NetworkEvent::AddEntities { entities },
+            NetworkEvent::UpdateEntityDetails { name, .. },
+        ] if entities.len() == 1 && entities[0].job == 86 && name == "Guide"));
+    }
+
+    #[test]
+    fn conversation_waits_for_the_player() {
+        let mut offline_scripts = start_script();
+        let npc_id = EntityId(1);
+        events(&mut offline_scripts);
+
+        offline_scripts.interact(npc_id);
+
+        assert!(matches!(&events(&mut offline_scripts)[..], [
+            NetworkEvent::OpenDialog { text, .. },
+            NetworkEvent::AddNextButton { .. },
+        ] if text == "Hello"));
+
+        offline_scripts.next_dialog(npc_id);
+
+        assert!(matches!(&events(&mut offline_scripts)[..], [NetworkEvent::AddChoiceButtons { choices, .. }] if choices.len() == 2));
+
+        offline_scripts.choose_dialog_option(npc_id, 1);
+
+        assert!(matches!(&events(&mut offline_scripts)[..], [
+            NetworkEvent::IventoryItemAdded { item },
+            NetworkEvent::AddCloseButton { .. },
+        ] if item.item_id == ItemId(501)
+            && item.index == InventoryIndex(2)
+            && matches!(item.details, InventoryItemDetails::Regular { amount: 2, .. })));
+    }
+
+    #[test]
+    fn canceled_conversation_ends() {
+        let mut offline_scripts = start_script();
+        let npc_id = EntityId(1);
+        events(&mut offline_scripts);
+
+        offline_scripts.interact(npc_id);
+        offline_scripts.next_dialog(npc_id);
+        offline_scripts.choose_dialog_option(npc_id, -1);
+        events(&mut offline_scripts);
+
+        // The conversation starts over on the next click.
+        offline_scripts.interact(npc_id);
+
+        assert!(matches!(
+            &events(&mut offline_scripts)[..],
+            [NetworkEvent::OpenDialog { .. }, NetworkEvent::AddNextButton { .. }]
+        ));
+    }
+}
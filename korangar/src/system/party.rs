@@ -0,0 +1,63 @@
+//! Handling of the party events. The members are tracked in the
+//! [`Party`](crate::state::party::Party), so they can be shown on the
+//! minimap and as off-screen indicators.
+
+use ragnarok_packets::{AccountId, TilePosition};
+
+use crate::Client;
+use crate::state::{ClientStatePathExt, client_state};
+
+impl Client {
+    pub(crate) fn party_member_updated(
+        &mut self,
+        account_id: AccountId,
+        name: String,
+        map_name: String,
+        position: TilePosition,
+        online: bool,
+    ) {
+        if self.is_player_account(account_id) {
+            return;
+        }
+
+        let is_on_current_map = online && self.map.as_ref().is_some_and(|map| map.resource_file() == map_name);
+
+        self.client_state
+            .follow_mut(client_state().party())
+            .update_member(account_id, name, is_on_current_map.then_some(position));
+    }
+
+    pub(crate) fn party_member_left(&mut self, account_id: AccountId) {
+        let is_player = self.is_player_account(account_id);
+        let party = self.client_state.follow_mut(client_state().party());
+
+        // If the player left, the whole party is gone.
+        match is_player {
+            true => party.clear(),
+            false => party.remove_member(account_id),
+        }
+    }
+
+    pub(crate) fn party_member_moved(&mut self, account_id: AccountId, position: TilePosition) {
+        if !self.is_player_account(account_id) {
+            self.client_state
+                .follow_mut(client_state().party())
+                .update_position(account_id, position);
+        }
+    }
+
+    pub(crate) fn party_member_health_changed(&mut self, account_id: AccountId, health_points: u32, maximum_health_points: u32) {
+        if !self.is_player_account(account_id) {
+            self.client_state
+                .follow_mut(client_state().party())
+                .update_health(account_id, health_points, maximum_health_points);
+        }
+    }
+
+    /// Check if an account id of a party packet belongs to the player.
+    fn is_player_account(&self, account_id: AccountId) -> bool {
+        self.session
+            .login_data()
+            .is_some_and(|login_data| login_data.account_id == account_id)
+    }
+}
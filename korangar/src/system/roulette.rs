@@ -0,0 +1,48 @@
+//! Handling of the roulette events. The state of the board is kept in the
+//! [`RouletteBoard`](crate::state::roulette::RouletteBoard).
+
+use korangar_networking::MessageColor;
+use ragnarok_packets::RouletteItemInformation;
+
+use crate::Client;
+use crate::state::roulette::RoulettePoints;
+use crate::state::{ClientStatePathExt, client_state};
+
+impl Client {
+    pub(crate) fn open_roulette(&mut self, serial: u32, step: u16, index: u16, gold: u32, silver: u32, bronze: u32) {
+        let roulette = self.client_state.follow_mut(client_state().roulette());
+        roulette.open(serial, step, index, RoulettePoints { gold, silver, bronze });
+
+        if roulette.needs_items() {
+            let _ = self.networking_system.request_roulette_board();
+        }
+    }
+
+    pub(crate) fn set_roulette_board(&mut self, serial: u32, items: Vec<RouletteItemInformation>) {
+        self.client_state.follow_mut(client_state().roulette()).set_items(serial, items);
+    }
+
+    pub(crate) fn close_roulette(&mut self) {
+        self.client_state.follow_mut(client_state().roulette()).close();
+    }
+
+    pub(crate) fn roulette_spun(&mut self, won: bool, step: u16, index: u16, gold: u32, silver: u32, bronze: u32) {
+        self.client_state
+            .follow_mut(client_state().roulette())
+            .spun(won, step, index, RoulettePoints { gold, silver, bronze });
+    }
+
+    pub(crate) fn roulette_reward_claimed(&mut self) {
+        let roulette = self.client_state.follow_mut(client_state().roulette());
+        let message = roulette.prize().map(|prize| {
+            let item_name = self.library.get_item_name_from_id(prize.item_id, true);
+            format!("Received {} {item_name} from the roulette", prize.count)
+        });
+
+        roulette.reward_claimed();
+
+        if let Some(message) = message {
+            self.push_chat_message(MessageColor::Information, message);
+        }
+    }
+}
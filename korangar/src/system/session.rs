@@ -0,0 +1,154 @@
+//! Connection flow from logging in to entering a map. The flow doesn't
+//! depend on a window or a GPU device, so the client and the headless mode
+//! handle the connection events with the same code.
+
+use std::net::{SocketAddr, ToSocketAddrs};
+
+#[cfg(feature = "debug")]
+use korangar_debug::logging::{Colorize, print_debug};
+use korangar_networking::{DisconnectReason, LoginServerLoginData, NetworkEvent, NetworkingSystem, SupportedPacketVersion};
+use ragnarok_packets::CharacterServerInformation;
+use ragnarok_packets::handler::PacketCallback;
+
+use crate::loaders::{PacketVersion, Service};
+
+/// Resolve the socket address and packet version of a service.
+pub fn resolve_service(service: &Service) -> Result<(SocketAddr, SupportedPacketVersion), String> {
+    let packet_version = match service.packet_version {
+        Some(PacketVersion::_20220406) => SupportedPacketVersion::_20220406,
+        Some(PacketVersion::Unsupported(packet_version)) => {
+            return Err(format!("Selected server has an unsupported packet version: {packet_version}"));
+        }
+        None => crate::FALLBACK_PACKET_VERSION,
+    };
+
+    let socket_address = format!("{}:{}", service.address, service.port)
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addresses| addresses.next())
+        .ok_or_else(|| format!("Failed to resolve the address of {}:{}", service.address, service.port))?;
+
+    Ok((socket_address, packet_version))
+}
+
+/// Everything that is needed to reconnect to the servers after the
+/// connection was lost.
+pub struct Session {
+    packet_version: SupportedPacketVersion,
+    login_server_address: Option<SocketAddr>,
+    username: String,
+    password: String,
+    login_data: Option<LoginServerLoginData>,
+    character_server: Option<CharacterServerInformation>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            packet_version: crate::FALLBACK_PACKET_VERSION,
+            login_server_address: None,
+            username: String::new(),
+            password: String::new(),
+            login_data: None,
+            character_server: None,
+        }
+    }
+}
+
+impl Session {
+    pub fn log_in<Callback>(
+        &mut self,
+        networking_system: &mut NetworkingSystem<Callback>,
+        packet_version: SupportedPacketVersion,
+        socket_address: SocketAddr,
+        username: String,
+        password: String,
+    ) where
+        Callback: PacketCallback + Send,
+    {
+        self.packet_version = packet_version;
+        self.login_server_address = Some(socket_address);
+        self.username = username;
+        self.password = password;
+
+        networking_system.connect_to_login_server(packet_version, socket_address, &self.username, &self.password);
+    }
+
+    /// Leave the login server and connect to a character server. Has no
+    /// effect before the login server sent the login data.
+    pub fn select_character_server<Callback>(
+        &mut self,
+        networking_system: &mut NetworkingSystem<Callback>,
+        character_server: CharacterServerInformation,
+    ) where
+        Callback: PacketCallback + Send,
+    {
+        let Some(login_data) = &self.login_data else {
+            return;
+        };
+
+        self.character_server = Some(character_server.clone());
+
+        networking_system.disconnect_from_login_server();
+        networking_system.connect_to_character_server(self.packet_version, login_data, character_server);
+    }
+
+    /// Login data of the account, which is available once the login server
+    /// accepted the login.
+    pub fn login_data(&self) -> Option<&LoginServerLoginData> {
+        self.login_data.as_ref()
+    }
+
+    /// Update the session and move on to the next server. Needs to see every
+    /// event before the caller reacts to it.
+    pub fn handle_event<Callback>(&mut self, networking_system: &mut NetworkingSystem<Callback>, event: &NetworkEvent)
+    where
+        Callback: PacketCallback + Send,
+    {
+        match event {
+            NetworkEvent::LoginServerConnected { login_data, .. } => {
+                self.login_data = Some(*login_data);
+            }
+            NetworkEvent::LoginServerDisconnected { reason } if *reason != DisconnectReason::ClosedByClient => {
+                #[cfg(feature = "debug")]
+                print_debug!("[{}] disconnected from the login server with an error", "error".red());
+
+                if let Some(socket_address) = self.login_server_address {
+                    networking_system.connect_to_login_server(self.packet_version, socket_address, &self.username, &self.password);
+                }
+            }
+            NetworkEvent::CharacterServerDisconnected { reason } if *reason != DisconnectReason::ClosedByClient => {
+                #[cfg(feature = "debug")]
+                print_debug!("[{}] disconnected from the character server with an error", "error".red());
+
+                self.connect_to_character_server(networking_system);
+            }
+            // Logging out and losing the connection both lead back to the character
+            // selection.
+            NetworkEvent::MapServerDisconnected { .. } => {
+                self.connect_to_character_server(networking_system);
+            }
+            NetworkEvent::CharacterSelected { login_data, .. } => {
+                let Some(saved_login_data) = &self.login_data else {
+                    return;
+                };
+
+                networking_system.disconnect_from_character_server();
+                networking_system.connect_to_map_server(self.packet_version, saved_login_data, *login_data);
+                // Ask for the client tick right away, so that the player isn't de-synced when
+                // they spawn on the map.
+                let _ = networking_system.request_client_tick();
+            }
+            _ => {}
+        }
+    }
+
+    fn connect_to_character_server<Callback>(&self, networking_system: &mut NetworkingSystem<Callback>)
+    where
+        Callback: PacketCallback + Send,
+    {
+        if let (Some(login_data), Some(character_server)) = (&self.login_data, &self.character_server) {
+            networking_system.connect_to_character_server(self.packet_version, login_data, character_server.clone());
+        }
+    }
+}
@@ -0,0 +1,305 @@
+//! Spawns dummy entities with random animations around the center of the
+//! current map and moves the camera around them, to profile the entity and
+//! effect renderers without needing a crowded server.
+
+use std::f32::consts::TAU;
+
+use cgmath::{Point3, Vector3};
+use korangar_debug::logging::{Colorize, print_debug};
+use korangar_networking::EntityData;
+use ragnarok_packets::{ClientTick, Direction, EntityId, Sex, TilePosition, WorldPosition};
+use rand_aes::tls::rand_f32;
+use rust_state::Context;
+
+use super::benchmark::MapBenchmarkResult;
+use super::{ConsoleCommandRegistry, ConsoleOutput};
+use crate::Client;
+use crate::loaders::GAT_TILE_SIZE;
+use crate::state::{ClientState, ClientStatePathExt, client_state};
+use crate::world::{Entity, Npc, Traversable};
+
+/// Number of entities spawned if the console command doesn't specify one.
+const DEFAULT_ENTITY_COUNT: usize = 500;
+/// Time in seconds spent measuring frame times.
+const MEASUREMENT_DURATION: f64 = 30.0;
+/// Frames to skip after spawning the entities, so the measurement isn't
+/// skewed by loading their sprites.
+const WARMUP_FRAMES: usize = 60;
+/// Time in seconds between picking new animations for the entities.
+const ACTION_INTERVAL: f64 = 1.0;
+/// Maximum distance in tiles between the center and the spawned entities.
+const SPAWN_RADIUS: f32 = 25.0;
+/// Number of random tiles tried before giving up on finding a walkable one.
+const SPAWN_ATTEMPTS: usize = 10;
+/// Distance of the camera path from the center in world units.
+const CAMERA_PATH_RADIUS: f32 = 60.0;
+/// Entity ids of the dummy entities start here, so they don't collide with
+/// entities sent by the server.
+const FIRST_ENTITY_ID: u32 = 0xFFF0_0000;
+/// Monsters that are picked for the dummy entities.
+const MONSTER_JOBS: [u16; 8] = [1002, 1007, 1011, 1031, 1049, 1052, 1063, 1113];
+/// Duration of the attack animation of the dummy entities.
+const ATTACK_DURATION: u32 = 500;
+
+/// Animation that a dummy entity plays until the next action is picked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StressTestAction {
+    Walk { destination: TilePosition },
+    Attack,
+    Idle,
+}
+
+pub struct StressTest {
+    entity_count: usize,
+    center_tile: TilePosition,
+    center: Point3<f32>,
+    elapsed: f64,
+    time_since_action: f64,
+    skipped_frames: usize,
+    frame_times_ms: Vec<f64>,
+}
+
+impl StressTest {
+    pub fn new(entity_count: usize, center_tile: TilePosition, center: Point3<f32>) -> Self {
+        Self {
+            entity_count,
+            center_tile,
+            center,
+            elapsed: 0.0,
+            time_since_action: 0.0,
+            skipped_frames: 0,
+            frame_times_ms: Vec::new(),
+        }
+    }
+
+    /// Register the `stress_test` command, which takes the number of entities
+    /// to spawn as an optional argument.
+    pub fn register_console_commands(registry: &mut ConsoleCommandRegistry<Context<ClientState>>) {
+        registry.register(
+            "stress_test",
+            "Spawn dummy entities and measure the frame times",
+            |state, arguments| {
+                let entity_count = match arguments.first() {
+                    Some(argument) => argument.parse().map_err(|_| format!("invalid entity count \"{argument}\""))?,
+                    None => DEFAULT_ENTITY_COUNT,
+                };
+
+                *state.follow_mut(client_state().stress_test_request()) = Some(entity_count);
+                Ok(format!("starting stress test with {entity_count} entities"))
+            },
+        );
+    }
+
+    pub fn is_dummy_entity(entity_id: EntityId) -> bool {
+        entity_id.0 >= FIRST_ENTITY_ID
+    }
+
+    /// Create the data of all dummy entities. Entities that can't be placed
+    /// on a walkable tile are skipped.
+    pub fn entity_data(&self, map: &impl Traversable) -> Vec<EntityData> {
+        (0..self.entity_count)
+            .filter_map(|index| {
+                let position = self.random_walkable_tile(map)?;
+                let direction = Direction::from((rand_f32() * 8.0) as u16);
+
+                Some(EntityData {
+                    entity_id: EntityId(FIRST_ENTITY_ID + index as u32),
+                    movement_speed: 150,
+                    job: MONSTER_JOBS[(rand_f32() * MONSTER_JOBS.len() as f32) as usize % MONSTER_JOBS.len()],
+                    head: 0,
+                    position: WorldPosition::new(position.x, position.y, direction),
+                    destination: None,
+                    health_points: 100,
+                    maximum_health_points: 100,
+                    head_direction: 0,
+                    sex: Sex::Male,
+                    appearance: Default::default(),
+                    body_state: Default::default(),
+                    hidden: false,
+                })
+            })
+            .collect()
+    }
+
+    /// Pick a random animation for a dummy entity.
+    pub fn random_action(&self, map: &impl Traversable) -> StressTestAction {
+        match rand_f32() {
+            chance if chance < 0.5 => match self.random_walkable_tile(map) {
+                Some(destination) => StressTestAction::Walk { destination },
+                None => StressTestAction::Idle,
+            },
+            chance if chance < 0.75 => StressTestAction::Attack,
+            _ => StressTestAction::Idle,
+        }
+    }
+
+    fn random_walkable_tile(&self, map: &impl Traversable) -> Option<TilePosition> {
+        (0..SPAWN_ATTEMPTS).find_map(|_| {
+            let offset_x = (rand_f32() * 2.0 - 1.0) * SPAWN_RADIUS;
+            let offset_y = (rand_f32() * 2.0 - 1.0) * SPAWN_RADIUS;
+            let position = TilePosition {
+                x: self.center_tile.x.saturating_add_signed(offset_x as i16),
+                y: self.center_tile.y.saturating_add_signed(offset_y as i16),
+            };
+
+            map.is_walkable(position).then_some(position)
+        })
+    }
+
+    /// The point the camera looks at. The camera circles the center once
+    /// during the measurement.
+    pub fn camera_focus_point(&self) -> Point3<f32> {
+        let angle = (self.elapsed / MEASUREMENT_DURATION) as f32 * TAU;
+        self.center + Vector3::new(angle.cos(), 0.0, angle.sin()) * CAMERA_PATH_RADIUS
+    }
+
+    /// Record the time of the last frame. Returns `true` if new animations
+    /// should be picked for the entities.
+    pub fn record_frame(&mut self, delta_time: f64) -> bool {
+        if self.skipped_frames < WARMUP_FRAMES {
+            self.skipped_frames += 1;
+            return false;
+        }
+
+        self.elapsed += delta_time;
+        self.time_since_action += delta_time;
+        self.frame_times_ms.push(delta_time * 1000.0);
+
+        if self.time_since_action >= ACTION_INTERVAL {
+            self.time_since_action = 0.0;
+            return true;
+        }
+
+        false
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= MEASUREMENT_DURATION
+    }
+
+    /// Summarize the measured frame times.
+    pub fn report(self, spawned_entity_count: usize) -> String {
+        let result = MapBenchmarkResult::new(String::new(), None, self.frame_times_ms);
+
+        let report = format!(
+            "stress test with {spawned_entity_count} entities: {} frames, average {:.2}ms, median {:.2}ms, 99th percentile {:.2}ms, \
+             maximum {:.2}ms",
+            result.frame_count,
+            result.average_frame_time_ms,
+            result.median_frame_time_ms,
+            result.percentile_99_frame_time_ms,
+            result.maximum_frame_time_ms,
+        );
+
+        print_debug!("[{}] {}", "stress test".green(), report);
+
+        report
+    }
+}
+
+impl Client {
+    /// Start a requested stress test and advance the running one. Once it is
+    /// finished, the dummy entities are removed and the frame statistics are
+    /// printed to the console.
+    pub(crate) fn update_stress_test(&mut self, delta_time: f64, client_tick: ClientTick) {
+        if let Some(entity_count) = self.client_state.follow_mut(client_state().stress_test_request()).take() {
+            let output = self.start_stress_test(entity_count, client_tick);
+            self.client_state.follow_mut(client_state().console_window()).push_output(output);
+        }
+
+        let Some(stress_test) = &mut self.stress_test else {
+            return;
+        };
+
+        let pick_actions = stress_test.record_frame(delta_time);
+
+        if stress_test.is_finished() {
+            let entities = self.client_state.follow_mut(client_state().entities());
+            let entity_count_before = entities.len();
+            entities.retain(|entity| !StressTest::is_dummy_entity(entity.get_entity_id()));
+            let spawned_entity_count = entity_count_before - entities.len();
+
+            if let Some(stress_test) = self.stress_test.take() {
+                let report = stress_test.report(spawned_entity_count);
+                self.client_state
+                    .follow_mut(client_state().console_window())
+                    .push_output(ConsoleOutput::Text(report));
+            }
+
+            return;
+        }
+
+        if pick_actions && let Some(map) = &self.map {
+            let dummy_entities = self
+                .client_state
+                .follow_mut(client_state().entities())
+                .iter_mut()
+                .filter(|entity| StressTest::is_dummy_entity(entity.get_entity_id()));
+
+            for entity in dummy_entities {
+                match stress_test.random_action(map.as_ref()) {
+                    StressTestAction::Walk { destination } => {
+                        let position = entity.get_tile_position();
+                        entity.move_from_to(map, &mut self.path_finder, position, destination, client_tick);
+                    }
+                    StressTestAction::Attack => entity.set_attack(ATTACK_DURATION, false, client_tick),
+                    StressTestAction::Idle => {
+                        entity.stop_movement();
+                        entity.set_idle(client_tick);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Spawn the dummy entities of a stress test around the center of the
+    /// current map.
+    fn start_stress_test(&mut self, entity_count: usize, client_tick: ClientTick) -> ConsoleOutput {
+        let Some(map) = &self.map else {
+            return ConsoleOutput::Error("no map is loaded".to_owned());
+        };
+
+        let entities = self.client_state.follow_mut(client_state().entities());
+
+        // Only one stress test can run at a time.
+        if self.stress_test.is_some() {
+            entities.retain(|entity| !StressTest::is_dummy_entity(entity.get_entity_id()));
+        }
+
+        let level_center = map.get_level_bound().center();
+        let center_tile = TilePosition {
+            x: (level_center.x / GAT_TILE_SIZE) as u16,
+            y: (level_center.z / GAT_TILE_SIZE) as u16,
+        };
+        let center = map.get_world_position(center_tile).unwrap_or(level_center);
+        let stress_test = StressTest::new(entity_count, center_tile, center);
+
+        let mut spawned_entity_count = 0;
+
+        for entity_data in stress_test.entity_data(map.as_ref()) {
+            let Some(npc) = Npc::new(map, &mut self.path_finder, entity_data, client_tick) else {
+                continue;
+            };
+
+            let mut npc = Entity::Npc(npc);
+
+            let entity_id = npc.get_entity_id();
+            let entity_type = npc.get_entity_type();
+            let entity_part_files = npc.get_entity_part_files(&self.library);
+
+            if let Some(animation_data) = self
+                .async_loader
+                .request_animation_data_load(entity_id, entity_type, entity_part_files)
+            {
+                npc.set_animation_data(animation_data);
+            }
+
+            entities.push(npc);
+            spawned_entity_count += 1;
+        }
+
+        self.stress_test = Some(stress_test);
+
+        ConsoleOutput::Text(format!("spawned {spawned_entity_count} dummy entities"))
+    }
+}
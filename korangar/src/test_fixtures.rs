@@ -0,0 +1,113 @@
+//! Fixtures that are shared by the unit tests of multiple modules.
+
+use hashbrown::HashSet;
+use korangar_networking::{InventoryItem, InventoryItemDetails};
+use ragnarok_formats::map::{Tile, TileFlags};
+use ragnarok_packets::{EquipPosition, EquippableItemFlags, InventoryIndex, ItemId, ItemOptions, RegularItemFlags, TilePosition};
+
+use crate::world::Traversable;
+
+/// Identified stack of a regular item that is not equipped.
+pub fn regular_item<Meta>(metadata: Meta, index: u16, item_id: u32, item_type: u8, amount: u16) -> InventoryItem<Meta> {
+    InventoryItem {
+        metadata,
+        index: InventoryIndex(index),
+        item_id: ItemId(item_id),
+        item_type,
+        slot: [0; 4],
+        hire_expiration_date: 0,
+        details: InventoryItemDetails::Regular {
+            amount,
+            equipped_position: EquipPosition::NONE,
+            flags: RegularItemFlags::IDENTIFIED,
+        },
+    }
+}
+
+/// Identified piece of equipment without cards, options or refinement.
+pub fn equippable_item<Meta>(
+    metadata: Meta,
+    index: u16,
+    item_id: u32,
+    item_type: u8,
+    equip_position: EquipPosition,
+    equipped_position: EquipPosition,
+) -> InventoryItem<Meta> {
+    InventoryItem {
+        metadata,
+        index: InventoryIndex(index),
+        item_id: ItemId(item_id),
+        item_type,
+        slot: [0; 4],
+        hire_expiration_date: 0,
+        details: InventoryItemDetails::Equippable {
+            equip_position,
+            equipped_position,
+            bind_on_equip_type: 0,
+            w_item_sprite_number: 0,
+            option_count: 0,
+            option_data: std::array::from_fn(|_| ItemOptions {
+                index: 0,
+                value: 0,
+                parameter: 0,
+            }),
+            refinement_level: 0,
+            enchantment_level: 0,
+            flags: EquippableItemFlags::IDENTIFIED,
+        },
+    }
+}
+
+/// Walkable ground tile with the given corner heights.
+pub fn tile(southwest: f32, southeast: f32, northwest: f32, northeast: f32) -> Tile {
+    Tile {
+        southwest_corner_height: southwest,
+        southeast_corner_height: southeast,
+        northwest_corner_height: northwest,
+        northeast_corner_height: northeast,
+        flags: TileFlags::WALKABLE,
+        unused: [0; 3],
+    }
+}
+
+/// Rectangular map where every tile is walkable and snipeable unless it is
+/// marked otherwise.
+pub struct TestMap {
+    width: u16,
+    height: u16,
+    not_walkable: HashSet<TilePosition>,
+    not_snipable: HashSet<TilePosition>,
+}
+
+impl TestMap {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            not_walkable: HashSet::new(),
+            not_snipable: HashSet::new(),
+        }
+    }
+
+    pub fn set_unwalkable(&mut self, points: &[TilePosition]) {
+        for point in points {
+            self.not_walkable.insert(*point);
+        }
+    }
+
+    pub fn set_unsnipable(&mut self, points: &[TilePosition]) {
+        for point in points {
+            self.not_snipable.insert(*point);
+        }
+    }
+}
+
+impl Traversable for TestMap {
+    fn is_walkable(&self, position: TilePosition) -> bool {
+        position.x < self.width && position.y < self.height && !self.not_walkable.contains(&position)
+    }
+
+    fn is_snipeable(&self, position: TilePosition) -> bool {
+        position.x < self.width && position.y < self.height && !self.not_snipable.contains(&position)
+    }
+}
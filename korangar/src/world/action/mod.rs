@@ -91,7 +91,7 @@ impl Actions {
             let dimensions = sprite_clip
                 .size
                 .unwrap_or_else(|| {
-                    let image_size = texture.get_size();
+                    let image_size = sprite.texture_size(sprite_clip.sprite_number as usize);
                     Vector2::new(image_size.width, image_size.height)
                 })
                 .map(|component| component as f32);
@@ -0,0 +1,131 @@
+//! Events that are triggered when an entity animation reaches a specific
+//! frame, like footsteps or the moment an attack hits.
+
+use cgmath::Point3;
+use hashbrown::HashMap;
+use korangar_audio::{AudioEngine, SoundEffectKey};
+#[cfg(feature = "debug")]
+use korangar_debug::logging::{Colorize, print_debug};
+use serde::{Deserialize, Serialize};
+
+use super::AnimationActionType;
+use crate::loaders::GameFileLoader;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationEvent {
+    /// Play a sound effect at the position of the entity.
+    Sound { key: SoundEffectKey },
+    /// The frame at which an attack hits its target.
+    AttackImpact,
+}
+
+/// An animation event that was triggered by an entity in this frame.
+#[derive(Debug, Clone, Copy)]
+pub struct TriggeredAnimationEvent {
+    pub position: Point3<f32>,
+    pub event: AnimationEvent,
+}
+
+#[derive(Serialize, Deserialize)]
+enum AnimationEventData {
+    Sound(String),
+    AttackImpact,
+}
+
+/// A single entry of the animation events file.
+#[derive(Serialize, Deserialize)]
+struct AnimationEventEntry {
+    job_id: usize,
+    action: AnimationActionType,
+    frame: usize,
+    event: AnimationEventData,
+}
+
+/// Maps frames of entity animations to the events they trigger. Frames are
+/// identified by the job of the entity, the action that is played and the
+/// index of the frame inside the action.
+#[derive(Default)]
+pub struct AnimationEventRegistry {
+    events: HashMap<(usize, AnimationActionType, usize), Vec<AnimationEvent>>,
+}
+
+impl AnimationEventRegistry {
+    const FILE_NAME: &'static str = "client/animation_events.ron";
+
+    /// Load the registry from disk. Sounds are loaded by the audio engine
+    /// right away, so they are ready once the event is triggered.
+    pub fn load(audio_engine: &AudioEngine<GameFileLoader>) -> Self {
+        #[cfg(feature = "debug")]
+        print_debug!("loading animation events from {}", Self::FILE_NAME.magenta());
+
+        let entries: Vec<AnimationEventEntry> = std::fs::read_to_string(Self::FILE_NAME)
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+            .unwrap_or_else(|| {
+                #[cfg(feature = "debug")]
+                print_debug!("failed to load animation events from {}", Self::FILE_NAME.magenta());
+                Vec::new()
+            });
+
+        let mut registry = Self::default();
+
+        for entry in entries {
+            let event = match entry.event {
+                AnimationEventData::Sound(path) => AnimationEvent::Sound {
+                    key: audio_engine.load(&path),
+                },
+                AnimationEventData::AttackImpact => AnimationEvent::AttackImpact,
+            };
+
+            registry.register(entry.job_id, entry.action, entry.frame, event);
+        }
+
+        registry
+    }
+
+    pub fn register(&mut self, job_id: usize, action_type: AnimationActionType, frame_index: usize, event: AnimationEvent) {
+        self.events.entry((job_id, action_type, frame_index)).or_default().push(event);
+    }
+
+    /// All events of the given frame.
+    pub fn get(&self, job_id: usize, action_type: AnimationActionType, frame_index: usize) -> &[AnimationEvent] {
+        self.events
+            .get(&(job_id, action_type, frame_index))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AnimationEvent, AnimationEventData, AnimationEventEntry, AnimationEventRegistry};
+    use crate::world::AnimationActionType;
+
+    #[test]
+    fn events_are_registered_per_frame() {
+        let mut registry = AnimationEventRegistry::default();
+        registry.register(1002, AnimationActionType::Attack1, 3, AnimationEvent::AttackImpact);
+
+        assert_eq!(registry.get(1002, AnimationActionType::Attack1, 3), &[
+            AnimationEvent::AttackImpact
+        ]);
+        assert!(registry.get(1002, AnimationActionType::Attack1, 2).is_empty());
+        assert!(registry.get(1002, AnimationActionType::Walk, 3).is_empty());
+        assert!(registry.get(1001, AnimationActionType::Attack1, 3).is_empty());
+    }
+
+    #[test]
+    fn entries_are_parsed() {
+        let entries: Vec<AnimationEventEntry> = ron::from_str(
+            r#"[
+                (job_id: 0, action: Walk, frame: 2, event: Sound("footstep.wav")),
+                (job_id: 1002, action: Attack1, frame: 3, event: AttackImpact),
+            ]"#,
+        )
+        .unwrap();
+
+        assert!(matches!(&entries[0].event, AnimationEventData::Sound(path) if path == "footstep.wav"));
+        assert_eq!(entries[1].action, AnimationActionType::Attack1);
+        assert!(matches!(entries[1].event, AnimationEventData::AttackImpact));
+    }
+}
@@ -1,3 +1,5 @@
+mod events;
+
 use std::sync::Arc;
 
 use cgmath::{Array, Matrix4, Point3, Transform, Vector2, Vector3, Zero};
@@ -5,10 +7,12 @@ use korangar_container::Cacheable;
 use korangar_interface::element::StateElement;
 use ragnarok_packets::{ClientTick, Direction, EntityId};
 use rust_state::RustState;
+use serde::{Deserialize, Serialize};
 
+pub use self::events::{AnimationEvent, AnimationEventRegistry, TriggeredAnimationEvent};
 #[cfg(feature = "debug")]
 use crate::graphics::DebugRectangleInstruction;
-use crate::graphics::{Color, EntityInstruction};
+use crate::graphics::{Color, EntityInstruction, SpriteDepthBias};
 use crate::loaders::Sprite;
 use crate::world::{ActionEvent, Actions, Camera, EntityType};
 
@@ -16,8 +20,8 @@ const TILE_SIZE: f32 = 10.0;
 const SPRITE_SCALE: f32 = 1.4;
 
 #[allow(dead_code)]
-#[derive(Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
-enum AnimationActionType {
+#[derive(Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
+pub enum AnimationActionType {
     Attack1,
     Attack2,
     Attack3,
@@ -36,6 +40,11 @@ enum AnimationActionType {
 }
 
 impl AnimationActionType {
+    /// Players can only turn their head while standing or sitting.
+    pub fn turns_head(self) -> bool {
+        matches!(self, AnimationActionType::Idle | AnimationActionType::Sit)
+    }
+
     pub fn action_base_offset(&self, entity_type: EntityType) -> usize {
         match entity_type {
             EntityType::Hidden | EntityType::Player => match self {
@@ -67,6 +76,15 @@ impl AnimationActionType {
     }
 }
 
+/// Frame of a standing or sitting player for the given head direction. Head
+/// directions that the sprite doesn't have frames for look straight ahead.
+fn head_frame_index(head_direction: usize, frame_count: usize) -> usize {
+    match head_direction < frame_count {
+        true => head_direction,
+        false => 0,
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct AnimationState {
     action_type: AnimationActionType,
@@ -76,6 +94,8 @@ pub struct AnimationState {
     duration: Option<u32>,
     factor: Option<f32>,
     looping: bool,
+    /// The entity is frozen in the first frame of the action.
+    paused: bool,
 }
 
 impl AnimationState {
@@ -89,6 +109,7 @@ impl AnimationState {
             duration: None,
             factor: None,
             looping: true,
+            paused: false,
         }
     }
 
@@ -99,6 +120,7 @@ impl AnimationState {
         self.duration = None;
         self.factor = None;
         self.looping = true;
+        self.paused = false;
     }
 
     pub fn attack(&mut self, entity_type: EntityType, attack_duration: u32, critical: bool, client_tick: ClientTick) {
@@ -111,6 +133,7 @@ impl AnimationState {
         self.duration = Some(attack_duration);
         self.factor = None;
         self.looping = false;
+        self.paused = false;
     }
 
     pub fn walk(&mut self, entity_type: EntityType, movement_speed: usize, client_tick: ClientTick) {
@@ -120,6 +143,7 @@ impl AnimationState {
         self.duration = None;
         self.factor = Some(movement_speed as f32 * 100.0 / 150.0 / 5.0);
         self.looping = true;
+        self.paused = false;
     }
 
     pub fn dead(&mut self, entity_type: EntityType, client_tick: ClientTick) {
@@ -129,6 +153,48 @@ impl AnimationState {
         self.duration = None;
         self.factor = None;
         self.looping = false;
+        self.paused = false;
+    }
+
+    pub fn sit(&mut self, entity_type: EntityType, client_tick: ClientTick) {
+        self.action_type = AnimationActionType::Sit;
+        self.action_base_offset = self.action_type.action_base_offset(entity_type);
+        self.start_time = client_tick;
+        self.duration = None;
+        self.factor = None;
+        self.looping = true;
+        self.paused = false;
+    }
+
+    pub fn pick_up(&mut self, entity_type: EntityType, client_tick: ClientTick) {
+        self.action_type = AnimationActionType::Pickup;
+        self.action_base_offset = self.action_type.action_base_offset(entity_type);
+        self.start_time = client_tick;
+        self.duration = None;
+        self.factor = None;
+        self.looping = false;
+        self.paused = false;
+    }
+
+    /// Hold a pose, for example while the entity is frozen or stunned.
+    pub fn freeze(&mut self, entity_type: EntityType, action_type: AnimationActionType, client_tick: ClientTick) {
+        self.action_type = action_type;
+        self.action_base_offset = self.action_type.action_base_offset(entity_type);
+        self.start_time = client_tick;
+        self.time = 0;
+        self.duration = None;
+        self.factor = None;
+        self.looping = false;
+        self.paused = true;
+    }
+
+    /// Actions that play once and return to idle afterwards.
+    pub fn is_one_shot(&self) -> bool {
+        self.is_attack() || self.action_type == AnimationActionType::Pickup
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.paused
     }
 
     pub fn is_attack(&self) -> bool {
@@ -142,8 +208,20 @@ impl AnimationState {
         self.action_type == AnimationActionType::Walk
     }
 
+    pub fn action_type(&self) -> AnimationActionType {
+        self.action_type
+    }
+
+    /// Client tick at which the current action started. Together with the
+    /// frame index this identifies a single playback of a frame.
+    pub fn start_time(&self) -> ClientTick {
+        self.start_time
+    }
+
     pub fn update(&mut self, client_tick: ClientTick) {
-        self.time = client_tick.0.wrapping_sub(self.start_time.0);
+        if !self.paused {
+            self.time = client_tick.0.wrapping_sub(self.start_time.0);
+        }
     }
 }
 
@@ -169,6 +247,41 @@ pub struct AnimationPair {
     pub actions: Arc<Actions>,
 }
 
+/// Layer of a composed entity sprite. The variants are ordered the way the
+/// layers are drawn when the entity faces the camera, from back to front.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpriteLayer {
+    Body,
+    Head,
+    HeadgearBottom,
+    HeadgearMiddle,
+    HeadgearTop,
+    Garment,
+}
+
+impl SpriteLayer {
+    /// Position of the layer in the draw order of an action facing the given
+    /// direction. Garments are drawn behind the body, unless the back of the
+    /// entity faces the camera.
+    pub fn draw_priority(self, direction: usize) -> usize {
+        // Directions of an action start at south and go clockwise, so these are
+        // north-west, north, and north-east.
+        let faces_away = matches!(direction % 8, 3..=5);
+
+        match self {
+            SpriteLayer::Garment if !faces_away => 0,
+            layer => layer as usize + 1,
+        }
+    }
+}
+
+/// Sprite and action file of a single layer, without the file extension.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntityPart {
+    pub layer: SpriteLayer,
+    pub file: String,
+}
+
 #[derive(RustState, Clone, StateElement)]
 pub struct Animation {
     #[hidden_element]
@@ -235,7 +348,26 @@ impl AnimationData {
         frame_time as usize > animation.frames.len()
     }
 
-    pub fn get_frame(&self, animation_state: &AnimationState, camera: &dyn Camera, direction: Direction) -> &AnimationFrame {
+    pub fn get_frame(
+        &self,
+        animation_state: &AnimationState,
+        camera: &dyn Camera,
+        direction: Direction,
+        head_direction: usize,
+    ) -> &AnimationFrame {
+        let (animation_index, frame_index) = self.get_frame_index(animation_state, camera, direction, head_direction);
+        &self.animations[animation_index].frames[frame_index]
+    }
+
+    /// Returns the index of the animation and the index of the frame inside
+    /// that animation for the current animation state.
+    pub fn get_frame_index(
+        &self,
+        animation_state: &AnimationState,
+        camera: &dyn Camera,
+        direction: Direction,
+        head_direction: usize,
+    ) -> (usize, usize) {
         let camera_direction = camera.camera_direction();
         let direction = (camera_direction + u16::from(direction) as usize) & 7;
         let animation_action_index = animation_state.action_type.action_base_offset(self.entity_type) * 8 + direction;
@@ -258,11 +390,13 @@ impl AnimationData {
             false => (frame_time as usize).min(animation.frames.len().saturating_sub(1)),
         };
 
-        // Remove Doridori animation from Player
-        if self.entity_type == EntityType::Player && animation_state.action_type == AnimationActionType::Idle {
-            &animation.frames[0]
+        // Standing and sitting players don't play the Doridori animation. Instead, the
+        // frames show the head looking straight, to the right, or to the left. In all
+        // other actions the head is locked to the body.
+        if self.entity_type == EntityType::Player && animation_state.action_type.turns_head() {
+            (animation_index, head_frame_index(head_direction, animation.frames.len()))
         } else {
-            &animation.frames[frame_index]
+            (animation_index, frame_index)
         }
     }
 
@@ -295,23 +429,31 @@ impl AnimationData {
         entity_position: Point3<f32>,
         animation_state: &AnimationState,
         direction: Direction,
+        head_direction: usize,
+        ground_gradient: Vector2<f32>,
+        depth_bias: &SpriteDepthBias,
     ) {
-        let frame = self.get_frame(animation_state, camera, direction);
+        let frame = self.get_frame(animation_state, camera, direction, head_direction);
         let world_matrix = self.calculate_world_matrix(camera, frame, entity_position);
 
+        // All frame parts share the billboard of the entity. Their own transforms are
+        // applied in the vertex shader, so everything that only depends on the
+        // billboard is calculated once per entity instead of once per frame
+        // part.
+        let frame_size = Vector2::new(frame.size.x as f32, frame.size.y as f32);
+        let (texture_size, texture_position) = self.get_texture_coordinates();
+        let (depth_offset, curvature) =
+            camera.calculate_depth_offset_and_curvature(&world_matrix, SPRITE_SCALE, SPRITE_SCALE, ground_gradient, depth_bias);
+        let position = world_matrix.transform_point(Point3::from_value(0.0));
+        let distance = camera.distance_to(position);
+
+        instructions.reserve(frame.frame_parts.len());
+
         for (index, frame_part) in frame.frame_parts.iter().enumerate() {
             let animation_index = frame_part.animation_index;
             let sprite_number = frame_part.sprite_number;
             let texture = &self.animation_pair[animation_index].sprites.textures[sprite_number];
 
-            let frame_size = Vector2::new(frame.size.x as f32, frame.size.y as f32);
-
-            let (texture_size, texture_position) = self.get_texture_coordinates();
-            let (depth_offset, curvature) = camera.calculate_depth_offset_and_curvature(&world_matrix, SPRITE_SCALE, SPRITE_SCALE);
-
-            let position = world_matrix.transform_point(Point3::from_value(0.0));
-            let distance = camera.distance_to(position);
-
             instructions.push(EntityInstruction {
                 world: world_matrix,
                 frame_part_transform: frame_part.affine_matrix,
@@ -322,6 +464,7 @@ impl AnimationData {
                 extra_depth_offset: 0.005 * index as f32,
                 curvature,
                 color: frame_part.color,
+                highlight: 0.0,
                 mirror: frame_part.mirror,
                 entity_id,
                 add_to_picker,
@@ -339,10 +482,11 @@ impl AnimationData {
         entity_position: Point3<f32>,
         animation_state: &AnimationState,
         direction: Direction,
+        head_direction: usize,
         color_external: Color,
         color_internal: Color,
     ) {
-        let frame = self.get_frame(animation_state, camera, direction);
+        let frame = self.get_frame(animation_state, camera, direction, head_direction);
         let world_matrix = self.calculate_world_matrix(camera, frame, entity_position);
         instructions.push(DebugRectangleInstruction {
             world: world_matrix,
@@ -365,3 +509,21 @@ impl AnimationData {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::head_frame_index;
+
+    #[test]
+    fn head_direction_selects_idle_frame() {
+        assert_eq!(head_frame_index(0, 3), 0);
+        assert_eq!(head_frame_index(1, 3), 1);
+        assert_eq!(head_frame_index(2, 3), 2);
+    }
+
+    #[test]
+    fn missing_head_frames_look_straight_ahead() {
+        assert_eq!(head_frame_index(2, 1), 0);
+        assert_eq!(head_frame_index(7, 3), 0);
+    }
+}
@@ -1,12 +1,13 @@
 use cgmath::{Deg, InnerSpace, Matrix4, Point3, Quaternion, Rad, Rotation, Rotation3, Vector2, Vector3, Zero};
 
 use super::Camera;
-use crate::graphics::perspective_reverse_lh;
+use crate::graphics::{ClipPlanes, NEAR_PLANE, perspective_reverse_lh};
 
 const LOOK_AROUND_SPEED: f32 = 0.005;
 const FLY_SPEED_FAST: f32 = 1000.0;
 const FLY_SPEED_SLOW: f32 = 100.0;
 const VERTICAL_FOV: Deg<f32> = Deg(45.0);
+const CLIP_PLANES: ClipPlanes = ClipPlanes::infinite(NEAR_PLANE);
 const LOOK_UP_VECTOR: Vector3<f32> = Vector3::new(0.0, 1.0, 0.0);
 
 pub struct DebugCamera {
@@ -79,7 +80,7 @@ impl Camera for DebugCamera {
     fn generate_view_projection(&mut self, window_size: Vector2<usize>) {
         let aspect_ratio = window_size.x as f32 / window_size.y as f32;
         self.view_matrix = Matrix4::look_to_lh(self.camera_position, self.view_direction(), LOOK_UP_VECTOR);
-        self.projection_matrix = perspective_reverse_lh(VERTICAL_FOV, aspect_ratio);
+        self.projection_matrix = perspective_reverse_lh(VERTICAL_FOV, aspect_ratio, CLIP_PLANES);
         self.view_projection_matrix = self.projection_matrix * self.view_matrix;
     }
 
@@ -2,13 +2,15 @@ use cgmath::{EuclideanSpace, InnerSpace, Matrix4, Point3, SquareMatrix, Transfor
 use korangar_collision::{AABB, lerp};
 
 use super::Camera;
-use crate::graphics::{DirectionalLightPartitionInstruction, DirectionalShadowPartition, PARTITION_COUNT, orthographic_reverse_lh};
+use crate::graphics::{
+    ClipPlanes, DirectionalLightPartitionInstruction, DirectionalShadowPartition, NEAR_PLANE, PARTITION_COUNT, orthographic_reverse_lh,
+};
 
 const ORIGIN: Point3<f32> = Point3::new(0.0, 0.0, 0.0);
 const LOOK_UP: Vector3<f32> = Vector3::new(0.0, 1.0, 0.0);
 
-const CAMERA_NEAR_PLANE: f32 = 0.1;
-const CAMERA_FAR_PLANE: f32 = 1000.0;
+/// The part of the view frustum of the main camera that receives shadows.
+const SHADOWED_CLIP_PLANES: ClipPlanes = ClipPlanes::finite(NEAR_PLANE, 1000.0);
 
 /// Safety multiplier for the planes of the partitions. Needed to get some
 /// safety margin while the camera moves. This combats missing shadows.
@@ -98,8 +100,8 @@ impl DirectionalShadowCamera {
             &camera_view_inv,
             main_camera_proj,
             &light_view,
-            CAMERA_NEAR_PLANE,
-            CAMERA_FAR_PLANE,
+            SHADOWED_CLIP_PLANES.near,
+            SHADOWED_CLIP_PLANES.far,
         );
 
         // Center the light view on the frustum extents.
@@ -281,7 +283,7 @@ impl DirectionalShadowCamera {
 
         for partition_index in 0..PARTITION_COUNT {
             let mut light_view = self.view_matrix;
-            let far_depth = Self::pssm_partition_from_range(partition_index, 100.0, CAMERA_FAR_PLANE);
+            let far_depth = Self::pssm_partition_from_range(partition_index, 100.0, SHADOWED_CLIP_PLANES.far);
 
             let (min, max) =
                 Self::compute_frustum_extents(&camera_view_inverse, main_camera_projection, &light_view, near_depth, far_depth);
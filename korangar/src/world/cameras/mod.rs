@@ -1,6 +1,8 @@
 #[cfg(feature = "debug")]
 mod debug;
 mod directional_shadow;
+#[cfg(feature = "debug")]
+mod orbit;
 mod player;
 mod point_shadow;
 pub mod smoothed;
@@ -8,15 +10,18 @@ mod start;
 
 use std::f32::consts::FRAC_PI_2;
 
-use cgmath::{Array, EuclideanSpace, InnerSpace, Matrix4, MetricSpace, Point3, Vector2, Vector3, Vector4};
+use cgmath::{Array, EuclideanSpace, InnerSpace, Matrix4, MetricSpace, Point3, SquareMatrix, Vector2, Vector3, Vector4};
 
 #[cfg(feature = "debug")]
 pub use self::debug::DebugCamera;
 pub use self::directional_shadow::DirectionalShadowCamera;
+#[cfg(feature = "debug")]
+pub use self::orbit::OrbitCamera;
 pub use self::player::PlayerCamera;
 pub use self::point_shadow::PointShadowCamera;
 pub use self::smoothed::SmoothedValue;
 pub use self::start::StartCamera;
+use crate::graphics::SpriteDepthBias;
 #[cfg(feature = "debug")]
 use crate::graphics::{ScreenPosition, ScreenSize};
 
@@ -79,10 +84,18 @@ pub trait Camera {
         (top_left_position, bottom_right_position)
     }
 
-    fn calculate_depth_offset_and_curvature(&self, world_matrix: &Matrix4<f32>, sprite_height: f32, sprite_width: f32) -> (f32, f32) {
-        const OFFSET_FACTOR: f32 = 10.0;
-        const CURVATURE_FACTOR: f32 = 8.0;
-
+    /// Calculates the depth offset and curvature of a sprite billboard. The
+    /// `ground_gradient` is the change in ground height along the X and Z
+    /// axis below the sprite, so sprites standing on slopes that rise
+    /// towards the camera get pushed further out of the ground.
+    fn calculate_depth_offset_and_curvature(
+        &self,
+        world_matrix: &Matrix4<f32>,
+        sprite_height: f32,
+        sprite_width: f32,
+        ground_gradient: Vector2<f32>,
+        depth_bias: &SpriteDepthBias,
+    ) -> (f32, f32) {
         let sprite_height = 2.0 * sprite_height;
 
         let sprite_position = world_matrix * Vector4::new(0.0, 0.0, 0.0, 1.0);
@@ -98,8 +111,15 @@ pub trait Camera {
         let sprite_angle = (sprite_angle - FRAC_PI_2).to_degrees();
         let angle_progress = sprite_angle / -90.0;
 
+        // Calculate how steep the ground rises towards the camera.
+        let horizontal_view_direction = Vector2::new(view_direction.x, view_direction.z);
+        let ground_rise = match horizontal_view_direction.magnitude2() > f32::EPSILON {
+            true => (-ground_gradient.dot(horizontal_view_direction.normalize())).max(0.0),
+            false => 0.0,
+        };
+
         // Calculate offset point in the opposite view direction.
-        let offset_magnitude = OFFSET_FACTOR * sprite_height * angle_progress;
+        let offset_magnitude = sprite_height * (depth_bias.offset_factor * angle_progress + depth_bias.slope_factor * ground_rise);
         let offset_point = sprite_position - view_direction * offset_magnitude;
 
         // Calculate linear depth offset in view space.
@@ -108,7 +128,7 @@ pub trait Camera {
         let offset_view = view_matrix * offset_point;
         let depth_offset = offset_view.z - sprite_view.z;
 
-        let curvature = CURVATURE_FACTOR * sprite_width;
+        let curvature = depth_bias.curvature_factor * sprite_width;
 
         (depth_offset, curvature)
     }
@@ -138,6 +158,24 @@ pub trait Camera {
         Vector4::new(x, y, 0.0, 1.0)
     }
 
+    /// Calculates the ray from the camera through the given screen space
+    /// coordinates (UV). Returns the origin on the near plane and the
+    /// normalized direction of the ray.
+    fn screen_space_ray(&self, screen_space_position: Vector2<f32>) -> (Point3<f32>, Vector3<f32>) {
+        let inverse_view_projection = self.view_projection_matrix().invert().unwrap_or_else(Matrix4::identity);
+        let clip_space_position = self.screen_to_clip_space(screen_space_position);
+
+        // With reverse Z the near plane is at a depth of 1.0. The far plane may be at
+        // infinity, so we use a point between both planes to get the direction.
+        let near = inverse_view_projection * Vector4::new(clip_space_position.x, clip_space_position.y, 1.0, 1.0);
+        let middle = inverse_view_projection * Vector4::new(clip_space_position.x, clip_space_position.y, 0.5, 1.0);
+
+        let near = Point3::from_homogeneous(near);
+        let middle = Point3::from_homogeneous(middle);
+
+        (near, (middle - near).normalize())
+    }
+
     fn distance_to(&self, position: Point3<f32>) -> f32 {
         self.camera_position().distance(position)
     }
@@ -191,3 +229,49 @@ mod conversion {
         assert_relative_eq!(original.w, converted.w, epsilon = 1e-6);
     }
 }
+
+#[cfg(test)]
+mod screen_space_ray {
+    use cgmath::{InnerSpace, Vector2, assert_relative_eq};
+
+    use crate::world::{Camera, PlayerCamera};
+
+    #[test]
+    fn center_ray_follows_view_direction() {
+        let mut camera = PlayerCamera::new();
+        camera.update(0.0);
+        camera.generate_view_projection(Vector2::new(1920, 1080));
+
+        let (origin, direction) = camera.screen_space_ray(Vector2::new(0.5, 0.5));
+
+        assert_relative_eq!(direction.dot(camera.view_direction().normalize()), 1.0, epsilon = 1e-4);
+        assert!((origin - camera.camera_position()).magnitude() < 1.0);
+    }
+}
+
+#[cfg(test)]
+mod depth_offset {
+    use cgmath::{EuclideanSpace, InnerSpace, Matrix4, Vector2, assert_relative_eq};
+
+    use crate::graphics::SpriteDepthBias;
+    use crate::world::{Camera, PlayerCamera};
+
+    #[test]
+    fn ground_rising_towards_camera() {
+        let mut camera = PlayerCamera::new();
+        camera.update(0.0);
+        camera.generate_view_projection(Vector2::new(1920, 1080));
+
+        let world_matrix = Matrix4::from_translation(camera.focus_point().to_vec());
+        let depth_bias = SpriteDepthBias::default();
+        let view_direction = camera.view_direction();
+        let towards_camera = -Vector2::new(view_direction.x, view_direction.z).normalize();
+
+        let (flat, _) = camera.calculate_depth_offset_and_curvature(&world_matrix, 1.0, 1.0, Vector2::new(0.0, 0.0), &depth_bias);
+        let (rising, _) = camera.calculate_depth_offset_and_curvature(&world_matrix, 1.0, 1.0, towards_camera, &depth_bias);
+        let (falling, _) = camera.calculate_depth_offset_and_curvature(&world_matrix, 1.0, 1.0, -towards_camera, &depth_bias);
+
+        assert!(rising.abs() > flat.abs());
+        assert_relative_eq!(falling, flat, epsilon = 1e-4);
+    }
+}
@@ -0,0 +1,105 @@
+use cgmath::{Array, Deg, InnerSpace, Matrix4, Point3, Quaternion, Rad, Rotation, Rotation3, Vector2, Vector3, Zero};
+
+use super::Camera;
+use crate::graphics::{ClipPlanes, NEAR_PLANE, perspective_reverse_lh};
+
+const ORBIT_SPEED: f32 = 0.01;
+const ZOOM_SPEED: f32 = 0.005;
+const DEFAULT_YAW: f32 = 180_f32.to_radians();
+const DEFAULT_PITCH: f32 = -30_f32.to_radians();
+const MAXIMUM_PITCH: f32 = 89_f32.to_radians();
+const MINIMUM_DISTANCE: f32 = 5.0;
+const MAXIMUM_DISTANCE: f32 = 2000.0;
+const VERTICAL_FOV: Deg<f32> = Deg(45.0);
+const CLIP_PLANES: ClipPlanes = ClipPlanes::infinite(NEAR_PLANE);
+const LOOK_UP: Vector3<f32> = Vector3::new(0.0, 1.0, 0.0);
+
+/// Camera that orbits around a single point, used for inspecting models in
+/// the model viewer.
+pub struct OrbitCamera {
+    focus_point: Point3<f32>,
+    camera_position: Point3<f32>,
+    view_direction: Vector3<f32>,
+    yaw: f32,
+    pitch: f32,
+    distance: f32,
+    view_matrix: Matrix4<f32>,
+    projection_matrix: Matrix4<f32>,
+    view_projection_matrix: Matrix4<f32>,
+}
+
+impl OrbitCamera {
+    pub fn new() -> Self {
+        Self {
+            focus_point: Point3::from_value(0.0),
+            camera_position: Point3::from_value(0.0),
+            view_direction: Vector3::zero(),
+            yaw: DEFAULT_YAW,
+            pitch: DEFAULT_PITCH,
+            distance: MINIMUM_DISTANCE,
+            view_matrix: Matrix4::zero(),
+            projection_matrix: Matrix4::zero(),
+            view_projection_matrix: Matrix4::zero(),
+        }
+    }
+
+    /// Look at a new point from the default angle. The distance should be
+    /// large enough to fit the inspected object on the screen.
+    pub fn focus(&mut self, focus_point: Point3<f32>, distance: f32) {
+        self.focus_point = focus_point;
+        self.yaw = DEFAULT_YAW;
+        self.pitch = DEFAULT_PITCH;
+        self.distance = distance.clamp(MINIMUM_DISTANCE, MAXIMUM_DISTANCE);
+    }
+
+    pub fn orbit(&mut self, offset: Vector2<f32>) {
+        self.yaw += offset.x * ORBIT_SPEED;
+        self.pitch = (self.pitch - offset.y * ORBIT_SPEED).clamp(-MAXIMUM_PITCH, MAXIMUM_PITCH);
+    }
+
+    pub fn zoom(&mut self, zoom_factor: f32) {
+        self.distance = (self.distance * (1.0 + zoom_factor * ZOOM_SPEED)).clamp(MINIMUM_DISTANCE, MAXIMUM_DISTANCE);
+    }
+
+    pub fn update(&mut self) {
+        let pitch_rotation = Quaternion::from_angle_x(Rad(self.pitch));
+        let yaw_rotation = Quaternion::from_angle_y(Rad(self.yaw));
+        let rotated_offset = (yaw_rotation * pitch_rotation).rotate_vector(Vector3::new(0.0, 0.0, self.distance));
+
+        self.camera_position = self.focus_point + rotated_offset;
+        self.view_direction = -rotated_offset.normalize();
+    }
+}
+
+impl Camera for OrbitCamera {
+    fn camera_position(&self) -> Point3<f32> {
+        self.camera_position
+    }
+
+    fn focus_point(&self) -> Point3<f32> {
+        self.focus_point
+    }
+
+    fn generate_view_projection(&mut self, window_size: Vector2<usize>) {
+        let aspect_ratio = window_size.x as f32 / window_size.y as f32;
+        self.view_matrix = Matrix4::look_to_lh(self.camera_position, self.view_direction, LOOK_UP);
+        self.projection_matrix = perspective_reverse_lh(VERTICAL_FOV, aspect_ratio, CLIP_PLANES);
+        self.view_projection_matrix = self.projection_matrix * self.view_matrix;
+    }
+
+    fn look_up_vector(&self) -> Vector3<f32> {
+        LOOK_UP
+    }
+
+    fn view_projection_matrices(&self) -> (Matrix4<f32>, Matrix4<f32>) {
+        (self.view_matrix, self.projection_matrix)
+    }
+
+    fn view_projection_matrix(&self) -> Matrix4<f32> {
+        self.view_projection_matrix
+    }
+
+    fn view_direction(&self) -> Vector3<f32> {
+        self.view_direction
+    }
+}
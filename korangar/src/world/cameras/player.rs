@@ -1,7 +1,7 @@
 use cgmath::{Array, Deg, InnerSpace, Matrix4, Point3, Quaternion, Rad, Rotation, Rotation3, Vector2, Vector3, Zero};
 
 use super::{Camera, MAXIMUM_CAMERA_DISTANCE, MINIMUM_CAMERA_DISTANCE, SmoothedValue};
-use crate::graphics::perspective_reverse_lh;
+use crate::graphics::{ClipPlanes, NEAR_PLANE, perspective_reverse_lh};
 
 const ZOOM_SPEED: f32 = 1.0;
 const ROTATION_SPEED: f32 = 0.005;
@@ -9,6 +9,7 @@ const DEFAULT_DISTANCE: f32 = 400.0;
 const DEFAULT_ANGLE: f32 = 180_f32.to_radians();
 const CAMERA_PITCH: Deg<f32> = Deg(-55.0);
 const VERTICAL_FOV: Deg<f32> = Deg(15.5);
+const CLIP_PLANES: ClipPlanes = ClipPlanes::infinite(NEAR_PLANE);
 const THRESHOLD: f32 = 0.01;
 const LOOK_UP: Vector3<f32> = Vector3::new(0.0, 1.0, 0.0);
 
@@ -19,6 +20,7 @@ pub struct PlayerCamera {
     focus_point: Point3<SmoothedValue>,
     camera_position: Point3<f32>,
     view_direction: Vector3<f32>,
+    shake_offset: Vector3<f32>,
     view_angle: SmoothedValue,
     camera_distance: SmoothedValue,
     view_matrix: Matrix4<f32>,
@@ -32,6 +34,7 @@ impl PlayerCamera {
             focus_point: [SmoothedValue::new(0.0, THRESHOLD, 5.0); 3].into(),
             camera_position: Point3::from_value(0.0),
             view_direction: Vector3::zero(),
+            shake_offset: Vector3::zero(),
             view_angle: SmoothedValue::new(DEFAULT_ANGLE, THRESHOLD, 15.0),
             camera_distance: SmoothedValue::new(DEFAULT_DISTANCE, THRESHOLD, 5.0),
             view_matrix: Matrix4::zero(),
@@ -52,6 +55,12 @@ impl PlayerCamera {
         self.focus_point.z.set_desired(position.z);
     }
 
+    /// Offset the camera from its position for a screen shake. The offset only
+    /// moves the view, the focus point stays where it is.
+    pub fn set_shake_offset(&mut self, offset: Vector3<f32>) {
+        self.shake_offset = offset;
+    }
+
     pub fn soft_zoom(&mut self, zoom_factor: f32) {
         self.camera_distance
             .move_desired_clamp(zoom_factor * ZOOM_SPEED, MINIMUM_CAMERA_DISTANCE, MAXIMUM_CAMERA_DISTANCE);
@@ -104,8 +113,8 @@ impl Camera for PlayerCamera {
 
     fn generate_view_projection(&mut self, window_size: Vector2<usize>) {
         let aspect_ratio = window_size.x as f32 / window_size.y as f32;
-        self.view_matrix = Matrix4::look_to_lh(self.camera_position, self.view_direction, LOOK_UP);
-        self.projection_matrix = perspective_reverse_lh(VERTICAL_FOV, aspect_ratio);
+        self.view_matrix = Matrix4::look_to_lh(self.camera_position + self.shake_offset, self.view_direction, LOOK_UP);
+        self.projection_matrix = perspective_reverse_lh(VERTICAL_FOV, aspect_ratio, CLIP_PLANES);
         self.view_projection_matrix = self.projection_matrix * self.view_matrix;
     }
 
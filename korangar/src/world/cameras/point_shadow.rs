@@ -1,9 +1,11 @@
 use cgmath::{Deg, Matrix4, Point3, Vector2, Vector3, Zero};
 
 use super::Camera;
-use crate::graphics::perspective_reverse_lh;
+use crate::graphics::{ClipPlanes, NEAR_PLANE, perspective_reverse_lh};
 
 const VERTICAL_FOV: Deg<f32> = Deg(90.0);
+// The point shadow shaders linearize depth with the default near plane.
+const CLIP_PLANES: ClipPlanes = ClipPlanes::infinite(NEAR_PLANE);
 
 pub struct PointShadowCamera {
     camera_position: Point3<f32>,
@@ -54,7 +56,7 @@ impl Camera for PointShadowCamera {
 
     fn generate_view_projection(&mut self, _window_size: Vector2<usize>) {
         self.view_matrix = Matrix4::look_to_lh(self.camera_position, self.view_direction, self.look_up_vector);
-        self.projection_matrix = perspective_reverse_lh(VERTICAL_FOV, 1.0);
+        self.projection_matrix = perspective_reverse_lh(VERTICAL_FOV, 1.0, CLIP_PLANES);
         self.view_projection_matrix = self.projection_matrix * self.view_matrix;
     }
 
@@ -1,12 +1,13 @@
 use cgmath::{Array, Deg, InnerSpace, Matrix4, Point3, Quaternion, Rad, Rotation, Rotation3, Vector2, Vector3, Zero};
 
 use super::Camera;
-use crate::graphics::perspective_reverse_lh;
+use crate::graphics::{ClipPlanes, NEAR_PLANE, perspective_reverse_lh};
 
 const DEFAULT_VIEW_ANGLE: f32 = 180_f32.to_radians();
 const DEFAULT_VIEW_DISTANCE: f32 = 150.0;
 const ROTATION_SPEED: f32 = 0.03;
 const VERTICAL_FOV: Deg<f32> = Deg(45.0);
+const CLIP_PLANES: ClipPlanes = ClipPlanes::infinite(NEAR_PLANE);
 const LOOK_UP: Vector3<f32> = Vector3::new(0.0, 1.0, 0.0);
 
 pub struct StartCamera {
@@ -63,7 +64,7 @@ impl Camera for StartCamera {
         let aspect_ratio = window_size.x as f32 / window_size.y as f32;
         let camera_position = self.camera_position();
         self.view_matrix = Matrix4::look_to_lh(camera_position, self.view_direction, LOOK_UP);
-        self.projection_matrix = perspective_reverse_lh(VERTICAL_FOV, aspect_ratio);
+        self.projection_matrix = perspective_reverse_lh(VERTICAL_FOV, aspect_ratio, CLIP_PLANES);
         self.view_projection_matrix = self.projection_matrix * self.view_matrix;
     }
 
@@ -0,0 +1,194 @@
+//! Renewal formulas for the combat values of a character. Everything that
+//! estimates combat values on the client should go through these, so the
+//! numbers shown in different places can't diverge.
+
+use crate::world::SkillDamage;
+
+/// Highest ASPD that a character can reach.
+const MAXIMUM_ASPD: f32 = 193.0;
+/// Agility from which the ASPD correction no longer applies.
+const ASPD_CORRECTION_AGILITY: f32 = 205.0;
+
+/// Base level and base stats of a character, without bonuses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CombatStats {
+    pub base_level: u16,
+    pub strength: u16,
+    pub agility: u16,
+    pub vitality: u16,
+    pub intelligence: u16,
+    pub dexterity: u16,
+    pub luck: u16,
+}
+
+impl Default for CombatStats {
+    fn default() -> Self {
+        Self {
+            base_level: 1,
+            strength: 1,
+            agility: 1,
+            vitality: 1,
+            intelligence: 1,
+            dexterity: 1,
+            luck: 1,
+        }
+    }
+}
+
+/// Combat relevant values of the equipped gear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CombatGear {
+    pub weapon_attack: u16,
+    pub equipment_attack: u16,
+    /// Magic attack of the weapon and the equipment combined.
+    pub magic_attack: u16,
+    /// Base ASPD of the weapon type, including the penalty of a shield.
+    pub base_aspd: u16,
+}
+
+impl Default for CombatGear {
+    fn default() -> Self {
+        Self {
+            weapon_attack: 0,
+            equipment_attack: 0,
+            magic_attack: 0,
+            base_aspd: 156,
+        }
+    }
+}
+
+/// Combat values derived from the stats and gear of a character.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DerivedCombatStats {
+    pub attack: u32,
+    pub magic_attack: u32,
+    pub hit: u32,
+    pub flee: u32,
+    pub aspd: f32,
+}
+
+impl DerivedCombatStats {
+    pub fn calculate(stats: &CombatStats, gear: &CombatGear) -> Self {
+        let level_bonus = stats.base_level as u32 / 4;
+        let luck = stats.luck as u32;
+        let dexterity = stats.dexterity as u32;
+        let intelligence = stats.intelligence as u32;
+
+        let status_attack = level_bonus + stats.strength as u32 + dexterity / 5 + luck / 3;
+        let weapon_attack = gear.weapon_attack as u32 + gear.weapon_attack as u32 * stats.strength as u32 / 200;
+        let status_magic_attack = level_bonus + intelligence + intelligence / 2 + dexterity / 5 + luck / 3;
+
+        Self {
+            attack: status_attack + weapon_attack + gear.equipment_attack as u32,
+            magic_attack: status_magic_attack + gear.magic_attack as u32,
+            hit: 175 + stats.base_level as u32 + dexterity + luck / 3,
+            flee: 100 + stats.base_level as u32 + stats.agility as u32 + luck / 5,
+            aspd: Self::calculate_aspd(stats, gear),
+        }
+    }
+
+    fn calculate_aspd(stats: &CombatStats, gear: &CombatGear) -> f32 {
+        let base_aspd = gear.base_aspd as f32;
+        let agility = stats.agility as f32;
+        let dexterity = stats.dexterity as f32;
+
+        let correction = match agility < ASPD_CORRECTION_AGILITY {
+            true => ASPD_CORRECTION_AGILITY.sqrt() - agility.sqrt(),
+            false => 0.0,
+        };
+        let penalty = match base_aspd > 145.0 {
+            true => 1.0 - (base_aspd - 144.0) / 50.0,
+            false => 0.96,
+        };
+
+        let aspd = base_aspd - correction + (agility * 9.9987 + dexterity * 0.1922).sqrt() * penalty;
+
+        aspd.clamp(0.0, MAXIMUM_ASPD)
+    }
+
+    pub fn attacks_per_second(&self) -> f32 {
+        50.0 / (200.0 - self.aspd)
+    }
+
+    /// Damage of a skill before the defense of the target is applied.
+    pub fn skill_damage(&self, damage: &SkillDamage, level: u8) -> u32 {
+        let attack = match damage.magical {
+            true => self.magic_attack,
+            false => self.attack,
+        };
+
+        attack * damage.percent(level) / 100
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CombatGear, CombatStats, DerivedCombatStats, MAXIMUM_ASPD};
+    use crate::world::SkillDamage;
+
+    fn stats(base_level: u16, strength: u16, agility: u16, dexterity: u16, luck: u16) -> CombatStats {
+        CombatStats {
+            base_level,
+            strength,
+            agility,
+            dexterity,
+            luck,
+            ..CombatStats::default()
+        }
+    }
+
+    #[test]
+    fn hit_and_flee() {
+        let derived = DerivedCombatStats::calculate(&stats(50, 1, 40, 30, 15), &CombatGear::default());
+
+        assert_eq!(derived.hit, 175 + 50 + 30 + 5);
+        assert_eq!(derived.flee, 100 + 50 + 40 + 3);
+    }
+
+    #[test]
+    fn attack_includes_weapon_strength_bonus() {
+        let gear = CombatGear {
+            weapon_attack: 100,
+            equipment_attack: 10,
+            ..CombatGear::default()
+        };
+        let derived = DerivedCombatStats::calculate(&stats(40, 100, 1, 10, 3), &gear);
+
+        assert_eq!(derived.attack, (10 + 100 + 2 + 1) + (100 + 50) + 10);
+    }
+
+    #[test]
+    fn aspd_grows_with_agility_and_is_capped() {
+        let gear = CombatGear::default();
+        let slow = DerivedCombatStats::calculate(&stats(1, 1, 1, 1, 1), &gear);
+        let fast = DerivedCombatStats::calculate(&stats(1, 1, 99, 1, 1), &gear);
+        let capped = DerivedCombatStats::calculate(&stats(1, 1, 1000, 1000, 1), &gear);
+
+        assert!(fast.aspd > slow.aspd);
+        assert!(fast.attacks_per_second() > slow.attacks_per_second());
+        assert_eq!(capped.aspd, MAXIMUM_ASPD);
+    }
+
+    #[test]
+    fn skill_damage_uses_matching_attack() {
+        let gear = CombatGear {
+            weapon_attack: 100,
+            magic_attack: 50,
+            ..CombatGear::default()
+        };
+        let derived = DerivedCombatStats::calculate(&CombatStats::default(), &gear);
+        let bash = SkillDamage {
+            base_percent: 100,
+            percent_per_level: 30,
+            magical: false,
+        };
+        let fire_bolt = SkillDamage {
+            base_percent: 0,
+            percent_per_level: 100,
+            magical: true,
+        };
+
+        assert_eq!(derived.skill_damage(&bash, 10), derived.attack * 4);
+        assert_eq!(derived.skill_damage(&fire_bolt, 3), derived.magic_attack * 3);
+    }
+}
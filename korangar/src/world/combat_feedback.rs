@@ -0,0 +1,199 @@
+//! Visual feedback for hits: the screen shakes when the player takes a big
+//! hit, entities freeze for a moment on critical hits dealt by the player, and
+//! damaged entities flash. All of this is purely client-side and doesn't
+//! change the timing of anything on the server.
+
+use cgmath::{Vector3, Zero};
+use ragnarok_packets::{ClientTick, EntityId};
+
+/// Fraction of the maximum health a single hit needs to take to shake the
+/// screen.
+const SHAKE_HEALTH_FRACTION: f32 = 0.1;
+/// Amplitude of the shake in world units for a hit that takes all health.
+const MAXIMUM_SHAKE_AMPLITUDE: f32 = 4.0;
+/// Duration of the shake in seconds.
+const SHAKE_DURATION: f32 = 0.35;
+/// Duration in seconds that the attacker and the target freeze for on a
+/// critical hit.
+const HIT_STOP_DURATION: f32 = 0.07;
+/// Duration of the flash of a damaged entity in seconds.
+const FLASH_DURATION: f32 = 0.12;
+/// Highlight of a damaged entity at the start of the flash.
+const FLASH_HIGHLIGHT: f32 = 0.7;
+
+/// Kinds of combat feedback that can be turned off individually.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombatFeedbackKind {
+    ScreenShake,
+    HitStop,
+    HitFlash,
+}
+
+struct HitStop {
+    entity_ids: [EntityId; 2],
+    client_tick: ClientTick,
+    remaining: f32,
+}
+
+struct Flash {
+    entity_id: EntityId,
+    remaining: f32,
+}
+
+#[derive(Default)]
+pub struct CombatFeedback {
+    shake_amplitude: f32,
+    shake_remaining: f32,
+    shake_time: f32,
+    hit_stop: Option<HitStop>,
+    flashes: Vec<Flash>,
+}
+
+impl CombatFeedback {
+    /// Shake the screen if the hit took a large part of the health of the
+    /// player. Bigger hits shake the screen more.
+    pub fn player_hit(&mut self, damage: usize, maximum_health_points: usize) {
+        if maximum_health_points == 0 {
+            return;
+        }
+
+        let fraction = (damage as f32 / maximum_health_points as f32).min(1.0);
+
+        if fraction < SHAKE_HEALTH_FRACTION {
+            return;
+        }
+
+        // A smaller hit doesn't weaken a shake that is still running.
+        self.shake_amplitude = self.shake_amplitude.max(MAXIMUM_SHAKE_AMPLITUDE * fraction);
+        self.shake_remaining = SHAKE_DURATION;
+    }
+
+    /// Freeze the attacker and the target at the current tick for a moment.
+    pub fn critical_hit(&mut self, source_entity_id: EntityId, destination_entity_id: EntityId, client_tick: ClientTick) {
+        self.hit_stop = Some(HitStop {
+            entity_ids: [source_entity_id, destination_entity_id],
+            client_tick,
+            remaining: HIT_STOP_DURATION,
+        });
+    }
+
+    pub fn flash(&mut self, entity_id: EntityId) {
+        match self.flashes.iter_mut().find(|flash| flash.entity_id == entity_id) {
+            Some(flash) => flash.remaining = FLASH_DURATION,
+            None => self.flashes.push(Flash {
+                entity_id,
+                remaining: FLASH_DURATION,
+            }),
+        }
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        self.shake_remaining = (self.shake_remaining - delta_time).max(0.0);
+        self.shake_time += delta_time;
+
+        if self.shake_remaining == 0.0 {
+            self.shake_amplitude = 0.0;
+            self.shake_time = 0.0;
+        }
+
+        if let Some(hit_stop) = &mut self.hit_stop {
+            hit_stop.remaining -= delta_time;
+
+            if hit_stop.remaining <= 0.0 {
+                self.hit_stop = None;
+            }
+        }
+
+        self.flashes.iter_mut().for_each(|flash| flash.remaining -= delta_time);
+        self.flashes.retain(|flash| flash.remaining > 0.0);
+    }
+
+    /// Offset of the camera for the screen shake. The shake fades out over its
+    /// duration.
+    pub fn shake_offset(&self) -> Vector3<f32> {
+        if self.shake_remaining == 0.0 {
+            return Vector3::zero();
+        }
+
+        // Two sines with unrelated frequencies give a shake that doesn't look
+        // like a regular wobble.
+        let amplitude = self.shake_amplitude * self.shake_remaining / SHAKE_DURATION;
+        let x = (self.shake_time * 73.0).sin();
+        let z = (self.shake_time * 97.0 + 1.3).sin();
+
+        Vector3::new(x, 0.0, z) * amplitude
+    }
+
+    /// Client tick to update an entity with. Entities that are frozen by a
+    /// hit-stop stay at the tick of the hit.
+    pub fn entity_tick(&self, entity_id: EntityId, client_tick: ClientTick) -> ClientTick {
+        match &self.hit_stop {
+            Some(hit_stop) if hit_stop.entity_ids.contains(&entity_id) => hit_stop.client_tick,
+            _ => client_tick,
+        }
+    }
+
+    /// Highlight of an entity that is flashing after being damaged.
+    pub fn flash_highlight(&self, entity_id: EntityId) -> Option<f32> {
+        self.flashes
+            .iter()
+            .find(|flash| flash.entity_id == entity_id)
+            .map(|flash| FLASH_HIGHLIGHT * flash.remaining / FLASH_DURATION)
+    }
+
+    /// Stop all feedback, for example when the map changes.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::Zero;
+    use ragnarok_packets::{ClientTick, EntityId};
+
+    use super::{CombatFeedback, FLASH_DURATION, HIT_STOP_DURATION, SHAKE_DURATION};
+
+    #[test]
+    fn only_big_hits_shake_the_screen() {
+        let mut combat_feedback = CombatFeedback::default();
+
+        combat_feedback.player_hit(5, 100);
+        combat_feedback.update(0.01);
+        assert!(combat_feedback.shake_offset().is_zero());
+
+        combat_feedback.player_hit(50, 100);
+        combat_feedback.update(0.01);
+        assert!(!combat_feedback.shake_offset().is_zero());
+
+        combat_feedback.update(SHAKE_DURATION);
+        assert!(combat_feedback.shake_offset().is_zero());
+    }
+
+    #[test]
+    fn hit_stop_holds_tick_of_involved_entities() {
+        let mut combat_feedback = CombatFeedback::default();
+
+        combat_feedback.critical_hit(EntityId(1), EntityId(2), ClientTick(100));
+
+        assert_eq!(combat_feedback.entity_tick(EntityId(2), ClientTick(130)).0, 100);
+        assert_eq!(combat_feedback.entity_tick(EntityId(3), ClientTick(130)).0, 130);
+
+        combat_feedback.update(HIT_STOP_DURATION);
+        assert_eq!(combat_feedback.entity_tick(EntityId(1), ClientTick(200)).0, 200);
+    }
+
+    #[test]
+    fn flash_fades_out() {
+        let mut combat_feedback = CombatFeedback::default();
+
+        combat_feedback.flash(EntityId(4));
+        let start = combat_feedback.flash_highlight(EntityId(4)).unwrap();
+
+        combat_feedback.update(FLASH_DURATION / 2.0);
+        assert!(combat_feedback.flash_highlight(EntityId(4)).unwrap() < start);
+
+        combat_feedback.update(FLASH_DURATION);
+        assert_eq!(combat_feedback.flash_highlight(EntityId(4)), None);
+    }
+}
@@ -0,0 +1,143 @@
+//! Textures that are projected onto the ground, like skill areas, warp portals
+//! or blood splats.
+
+use std::sync::Arc;
+
+use cgmath::{Point3, Rad, Vector2};
+use ragnarok_packets::EntityId;
+
+use crate::graphics::{Color, Texture};
+
+/// Time in seconds in which a decal fades out before it is removed.
+const FADE_OUT_DURATION: f32 = 0.5;
+
+pub struct Decal {
+    texture: Arc<Texture>,
+    position: Point3<f32>,
+    size: Vector2<f32>,
+    rotation: Rad<f32>,
+    color: Color,
+    /// Time in seconds until the decal is removed. `None` means that the decal
+    /// stays until it is removed explicitly.
+    lifetime: Option<f32>,
+    elapsed: f32,
+}
+
+impl Decal {
+    /// Creates a decal centered at `position` that covers `size` world units
+    /// of the ground.
+    pub fn new(texture: Arc<Texture>, position: Point3<f32>, size: Vector2<f32>) -> Self {
+        Self {
+            texture,
+            position,
+            size,
+            rotation: Rad(0.0),
+            color: Color::WHITE,
+            lifetime: None,
+            elapsed: 0.0,
+        }
+    }
+
+    pub fn with_rotation(mut self, rotation: Rad<f32>) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_lifetime(mut self, lifetime: f32) -> Self {
+        self.lifetime = Some(lifetime);
+        self
+    }
+
+    pub fn texture(&self) -> &Arc<Texture> {
+        &self.texture
+    }
+
+    pub fn position(&self) -> Point3<f32> {
+        self.position
+    }
+
+    pub fn size(&self) -> Vector2<f32> {
+        self.size
+    }
+
+    pub fn rotation(&self) -> Rad<f32> {
+        self.rotation
+    }
+
+    /// Color of the decal including the fade out at the end of its lifetime.
+    pub fn color(&self) -> Color {
+        let opacity = self.lifetime.map_or(1.0, |lifetime| fade_out_opacity(self.elapsed, lifetime));
+
+        Color {
+            alpha: self.color.alpha * opacity,
+            ..self.color
+        }
+    }
+
+    /// Returns `false` if the decal reached the end of its lifetime.
+    fn update(&mut self, delta_time: f32) -> bool {
+        self.elapsed += delta_time;
+        self.lifetime.is_none_or(|lifetime| self.elapsed < lifetime)
+    }
+}
+
+fn fade_out_opacity(elapsed: f32, lifetime: f32) -> f32 {
+    let remaining = lifetime - elapsed;
+    (remaining / FADE_OUT_DURATION).clamp(0.0, 1.0)
+}
+
+/// Holds all decals of the current map. Decals can be bound to an entity, so
+/// that they are removed together with it.
+#[derive(Default)]
+pub struct DecalHolder {
+    decals: Vec<(Decal, Option<EntityId>)>,
+}
+
+impl DecalHolder {
+    pub fn add_decal(&mut self, decal: Decal) {
+        self.decals.push((decal, None));
+    }
+
+    pub fn add_unit(&mut self, decal: Decal, entity_id: EntityId) {
+        self.decals.push((decal, Some(entity_id)));
+    }
+
+    pub fn remove_unit(&mut self, removed_entity_id: EntityId) {
+        self.decals
+            .retain(|(_, entity_id)| !entity_id.is_some_and(|entity_id| entity_id == removed_entity_id));
+    }
+
+    pub fn clear(&mut self) {
+        self.decals.clear();
+    }
+
+    pub fn update(&mut self, delta_time: f32) {
+        self.decals.retain_mut(|(decal, _)| decal.update(delta_time));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Decal> {
+        self.decals.iter().map(|(decal, _)| decal)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FADE_OUT_DURATION, fade_out_opacity};
+
+    #[test]
+    fn opaque_before_fade_out() {
+        assert_eq!(fade_out_opacity(0.0, 5.0), 1.0);
+        assert_eq!(fade_out_opacity(5.0 - FADE_OUT_DURATION, 5.0), 1.0);
+    }
+
+    #[test]
+    fn fades_out_at_end_of_lifetime() {
+        assert_eq!(fade_out_opacity(5.0 - FADE_OUT_DURATION / 2.0, 5.0), 0.5);
+        assert_eq!(fade_out_opacity(6.0, 5.0), 0.0);
+    }
+}
@@ -0,0 +1,210 @@
+//! Mapping of effects to the point lights that they emit. The mapping is
+//! read from a data file, so the lighting of effects can be tuned without
+//! recompiling the client.
+
+use std::time::{Duration, Instant, SystemTime};
+
+use cgmath::Vector3;
+use hashbrown::HashMap;
+#[cfg(feature = "debug")]
+use korangar_debug::logging::{Colorize, print_debug};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+use crate::graphics::Color;
+
+/// How often the mapping file is checked for changes.
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Parameters of the point light that is emitted by an effect.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EffectLight {
+    pub color: Color,
+    pub intensity: f32,
+    /// Offset of the light from the center of the effect.
+    pub offset: Vector3<f32>,
+    /// Time in seconds after which the light fades out. `None` means that
+    /// the light lives as long as the effect.
+    #[serde(default)]
+    pub duration: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EffectLightMapping {
+    /// Light of all effects that are not in the mapping.
+    default: EffectLight,
+    /// Lights by the path of the STR file of the effect.
+    effects: HashMap<String, EffectLight>,
+}
+
+impl Default for EffectLightMapping {
+    fn default() -> Self {
+        let effects = [
+            ("firewall.str", EffectLight {
+                color: Color::rgb_u8(255, 30, 0),
+                intensity: 60.0,
+                offset: Vector3::new(0.0, 6.0, 0.0),
+                duration: None,
+            }),
+            ("pneuma1.str", EffectLight {
+                color: Color::rgb_u8(83, 220, 108),
+                intensity: 40.0,
+                offset: Vector3::new(0.0, 6.0, 0.0),
+                duration: None,
+            }),
+        ];
+
+        Self {
+            default: EffectLight {
+                color: Color::WHITE,
+                intensity: 50.0,
+                offset: Vector3::new(0.0, 12.0, 0.0),
+                duration: None,
+            },
+            effects: effects
+                .into_iter()
+                .map(|(effect_path, light)| (effect_path.to_string(), light))
+                .collect(),
+        }
+    }
+}
+
+impl EffectLightMapping {
+    fn get(&self, effect_path: &str) -> EffectLight {
+        self.effects.get(effect_path).copied().unwrap_or(self.default)
+    }
+}
+
+/// Lights of all effects. The mapping file is watched for changes and
+/// reloaded while the client is running. Effects that are already playing
+/// keep their light, the changes only apply to new effects.
+pub struct EffectLights {
+    mapping: EffectLightMapping,
+    modified: Option<SystemTime>,
+    last_check: Instant,
+}
+
+impl EffectLights {
+    const FILE_NAME: &'static str = "client/effect_lights.ron";
+
+    pub fn new() -> Self {
+        let mapping = Self::load().unwrap_or_else(|| {
+            #[cfg(feature = "debug")]
+            print_debug!("failed to load effect lights from {}", Self::FILE_NAME.magenta());
+
+            let mapping = EffectLightMapping::default();
+
+            // Write the default mapping to disk so it can be used as a starting point
+            // for tuning. An existing file is left alone, since it might only have
+            // a typo in it.
+            if !std::fs::exists(Self::FILE_NAME).unwrap_or(true) {
+                Self::save(&mapping);
+            }
+
+            mapping
+        });
+
+        Self {
+            mapping,
+            modified: Self::modified(),
+            last_check: Instant::now(),
+        }
+    }
+
+    fn load() -> Option<EffectLightMapping> {
+        #[cfg(feature = "debug")]
+        print_debug!("loading effect lights from {}", Self::FILE_NAME.magenta());
+
+        std::fs::read_to_string(Self::FILE_NAME)
+            .ok()
+            .and_then(|data| ron::from_str(&data).ok())
+    }
+
+    fn save(mapping: &EffectLightMapping) {
+        #[cfg(feature = "debug")]
+        print_debug!("saving effect lights to {}", Self::FILE_NAME.magenta());
+
+        let data = ron::ser::to_string_pretty(mapping, PrettyConfig::new()).unwrap();
+
+        if let Err(_error) = std::fs::write(Self::FILE_NAME, data) {
+            #[cfg(feature = "debug")]
+            print_debug!(
+                "failed to save effect lights to {}: {:?}",
+                Self::FILE_NAME.magenta(),
+                _error.red()
+            );
+        }
+    }
+
+    fn modified() -> Option<SystemTime> {
+        std::fs::metadata(Self::FILE_NAME).and_then(|metadata| metadata.modified()).ok()
+    }
+
+    /// Light of the effect with the given STR file path.
+    pub fn get(&self, effect_path: &str) -> EffectLight {
+        self.mapping.get(effect_path)
+    }
+
+    /// Reload the mapping if the file changed since the last check.
+    pub fn reload_changed(&mut self) {
+        if self.last_check.elapsed() < CHECK_INTERVAL {
+            return;
+        }
+
+        self.last_check = Instant::now();
+
+        let current_modified = Self::modified();
+
+        if current_modified == self.modified {
+            return;
+        }
+
+        self.modified = current_modified;
+
+        #[cfg(feature = "debug")]
+        print_debug!("effect lights file {} changed on disk", Self::FILE_NAME.magenta());
+
+        // If the file can't be parsed an editor might still be in the middle of
+        // writing it, so we keep the old mapping until it changes again.
+        match Self::load() {
+            Some(mapping) => self.mapping = mapping,
+            None => {
+                #[cfg(feature = "debug")]
+                print_debug!("[{}] failed to reload {}", "error".red(), Self::FILE_NAME.magenta());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::Vector3;
+
+    use super::EffectLightMapping;
+    use crate::graphics::Color;
+
+    #[test]
+    fn default_mapping_round_trips() {
+        let data = ron::to_string(&EffectLightMapping::default()).unwrap();
+        let mapping: EffectLightMapping = ron::from_str(&data).unwrap();
+
+        assert_eq!(mapping.get("firewall.str"), EffectLightMapping::default().get("firewall.str"));
+    }
+
+    #[test]
+    fn unknown_effect_uses_default() {
+        let mapping: EffectLightMapping = ron::from_str(
+            r#"(
+                default: (color: (red: 1.0, green: 1.0, blue: 1.0, alpha: 1.0), intensity: 50.0, offset: (x: 0.0, y: 12.0, z: 0.0)),
+                effects: {
+                    "firewall.str": (color: (red: 1.0, green: 0.0, blue: 0.0, alpha: 1.0), intensity: 60.0, offset: (x: 0.0, y: 6.0, z: 0.0), duration: Some(2.0)),
+                },
+            )"#,
+        )
+        .unwrap();
+
+        assert_eq!(mapping.get("firewall.str").duration, Some(2.0));
+        assert_eq!(mapping.get("unknown.str").color, Color::WHITE);
+        assert_eq!(mapping.get("unknown.str").offset, Vector3::new(0.0, 12.0, 0.0));
+    }
+}
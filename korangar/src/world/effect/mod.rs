@@ -1,5 +1,9 @@
+mod lights;
+
 use std::sync::Arc;
 
+#[cfg(feature = "debug")]
+use cgmath::Deg;
 use cgmath::{Point3, Rad, Vector2, Vector3};
 use korangar_collision::{Frustum, Sphere};
 use korangar_container::Cacheable;
@@ -7,6 +11,7 @@ use ragnarok_formats::map::EffectSource;
 use ragnarok_packets::EntityId;
 use wgpu::BlendFactor;
 
+pub use self::lights::{EffectLight, EffectLights};
 use crate::graphics::{Color, Texture};
 use crate::renderer::EffectRenderer;
 #[cfg(feature = "debug")]
@@ -16,7 +21,9 @@ use crate::world::MarkerIdentifier;
 use crate::world::{Camera, PointLightId, PointLightManager};
 
 pub trait EffectBase {
-    fn update(&mut self, entities: &[crate::world::Entity], delta_time: f32) -> bool;
+    /// Advances the effect. `time` is the absolute time of the effect clock
+    /// in seconds and `delta_time` the time since the last update.
+    fn update(&mut self, entities: &[crate::world::Entity], time: f64, delta_time: f32) -> bool;
 
     fn mark_for_deletion(&mut self);
 
@@ -62,7 +69,7 @@ impl Effect {
 impl Effect {
     pub fn new_frame_timer(&self) -> FrameTimer {
         FrameTimer {
-            total_timer: 0.0,
+            start_time: None,
             frames_per_second: self.frames_per_second,
             max_key: self.max_key,
             current_frame: 0,
@@ -105,6 +112,62 @@ impl Effect {
     }
 }
 
+#[cfg(feature = "debug")]
+impl Effect {
+    pub fn frames_per_second(&self) -> usize {
+        self.frames_per_second
+    }
+
+    pub fn max_key(&self) -> usize {
+        self.max_key
+    }
+
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Describe the interpolated frame of every layer at the current frame
+    /// of the timer, for inspecting effects in the effect preview.
+    pub fn describe_layers(&self, frame_timer: &FrameTimer) -> Vec<String> {
+        self.layers
+            .iter()
+            .enumerate()
+            .map(|(index, layer)| {
+                let Some(key_index) = layer.indices.get(frame_timer.current_frame).copied().flatten() else {
+                    return format!("Layer {index}: not visible");
+                };
+                let Some(frame) = layer.interpolate_frame(frame_timer) else {
+                    return format!("Layer {index}: not visible");
+                };
+
+                let key_frame = &layer.frames[key_index];
+                let interpolation = match layer.frames.get(key_index + 2) {
+                    Some(next_frame) => format!("interpolated {} -> {}", key_frame.frame_index, next_frame.frame_index),
+                    None => format!("key {}", key_frame.frame_index),
+                };
+
+                format!(
+                    "Layer {index}: {interpolation}, {:?} {:?}, texture {}/{}, offset ({:.1}, {:.1}), angle {:.1}°, color ({:.2}, {:.2}, \
+                     {:.2}, {:.2}), blend {:?} {:?}",
+                    frame.frame_type,
+                    frame.animation_type,
+                    frame.texture_index,
+                    layer.textures.len(),
+                    frame.offset.x,
+                    frame.offset.y,
+                    Deg::from(frame.angle).0,
+                    frame.color.red,
+                    frame.color.green,
+                    frame.color.blue,
+                    frame.color.alpha,
+                    frame.source_blend_factor,
+                    frame.destination_blend_factor,
+                )
+            })
+            .collect()
+    }
+}
+
 impl Cacheable for Effect {
     fn size(&self) -> usize {
         // We cache effects only by count.
@@ -265,26 +328,31 @@ pub enum MultiTexturePresent {
     None,
 }
 
+/// Keeps track of the current frame of an effect. The frame is derived from
+/// the time that passed since the effect started instead of accumulating the
+/// frame times, so effects don't drift at low frame rates and loop exactly.
 pub struct FrameTimer {
-    total_timer: f32,
+    /// Time of the effect clock at the first update.
+    start_time: Option<f64>,
     frames_per_second: usize,
     max_key: usize,
     current_frame: usize,
 }
 
 impl FrameTimer {
-    pub fn update(&mut self, delta_time: f32) -> bool {
-        self.total_timer += delta_time;
-        self.current_frame = (self.total_timer / (1.0 / self.frames_per_second as f32)) as usize;
-
-        if self.current_frame >= self.max_key {
-            // TODO: better wrapping
-            self.total_timer = 0.0;
-            self.current_frame = 0;
-            return false;
-        }
+    /// Returns `false` once the effect played through at least once.
+    pub fn update(&mut self, time: f64) -> bool {
+        let start_time = *self.start_time.get_or_insert(time);
+        let elapsed_frames = ((time - start_time) * self.frames_per_second as f64) as usize;
 
-        true
+        self.current_frame = elapsed_frames % self.max_key.max(1);
+
+        elapsed_frames < self.max_key
+    }
+
+    #[cfg(feature = "debug")]
+    pub fn current_frame(&self) -> usize {
+        self.current_frame
     }
 }
 
@@ -307,10 +375,9 @@ pub struct EffectWithLight {
     center: EffectCenter,
     effect_offset: Vector3<f32>,
     point_light_id: PointLightId,
-    light_offset: Vector3<f32>,
-    light_color: Color,
-    light_intensity: f32,
+    light: EffectLight,
     repeating: bool,
+    light_elapsed: f32,
     current_light_intensity: f32,
     gets_deleted: bool,
 }
@@ -322,9 +389,7 @@ impl EffectWithLight {
         center: EffectCenter,
         effect_offset: Vector3<f32>,
         point_light_id: PointLightId,
-        light_offset: Vector3<f32>,
-        light_color: Color,
-        light_intensity: f32,
+        light: EffectLight,
         repeating: bool,
     ) -> Self {
         Self {
@@ -333,10 +398,9 @@ impl EffectWithLight {
             center,
             effect_offset,
             point_light_id,
-            light_offset,
-            light_color,
-            light_intensity,
+            light,
             repeating,
+            light_elapsed: 0.0,
             current_light_intensity: 0.0,
             gets_deleted: false,
         }
@@ -344,7 +408,7 @@ impl EffectWithLight {
 }
 
 impl EffectBase for EffectWithLight {
-    fn update(&mut self, entities: &[crate::world::Entity], delta_time: f32) -> bool {
+    fn update(&mut self, entities: &[crate::world::Entity], time: f64, delta_time: f32) -> bool {
         const FADE_SPEED: f32 = 5.0;
 
         if let EffectCenter::Entity(entity_id, position) = &mut self.center
@@ -354,13 +418,16 @@ impl EffectBase for EffectWithLight {
             *position = new_position;
         }
 
-        if !self.gets_deleted && !self.frame_timer.update(delta_time) && !self.repeating {
+        if !self.gets_deleted && !self.frame_timer.update(time) && !self.repeating {
             self.gets_deleted = true;
         }
 
-        let (target, clamping_function): (f32, fn(f32, f32) -> f32) = match self.gets_deleted {
+        self.light_elapsed += delta_time;
+        let light_expired = self.light.duration.is_some_and(|duration| self.light_elapsed >= duration);
+
+        let (target, clamping_function): (f32, fn(f32, f32) -> f32) = match self.gets_deleted || light_expired {
             true => (0.0, f32::max),
-            false => (self.light_intensity, f32::min),
+            false => (self.light.intensity, f32::min),
         };
 
         self.current_light_intensity += (target - self.current_light_intensity) * FADE_SPEED * delta_time;
@@ -376,15 +443,15 @@ impl EffectBase for EffectWithLight {
     fn register_point_lights(&self, point_light_manager: &mut PointLightManager, camera: &dyn Camera) {
         let frustum = Frustum::new(camera.view_projection_matrix(), true);
 
-        let light_position = self.center.to_position() + self.light_offset;
+        let light_position = self.center.to_position() + self.light.offset;
 
         if frustum.intersects_sphere(&Sphere::new(light_position, self.current_light_intensity)) {
             point_light_manager.register_fading(
                 self.point_light_id,
                 light_position,
-                self.light_color,
+                self.light.color,
                 self.current_light_intensity,
-                self.light_intensity,
+                self.light.intensity,
             )
         }
     }
@@ -404,6 +471,10 @@ impl EffectBase for EffectWithLight {
 #[derive(Default)]
 pub struct EffectHolder {
     effects: Vec<(Box<dyn EffectBase + Send + Sync>, Option<EntityId>)>,
+    /// Absolute time of the effect clock in seconds. It only advances while
+    /// effects are updated, so it can run slower or faster than the game
+    /// timer.
+    time: f64,
 }
 
 impl EffectHolder {
@@ -426,8 +497,12 @@ impl EffectHolder {
         self.effects.clear();
     }
 
-    pub fn update(&mut self, entities: &[crate::world::Entity], delta_time: f32) {
-        self.effects.retain_mut(|(effect, _)| effect.update(entities, delta_time));
+    pub fn update(&mut self, entities: &[crate::world::Entity], delta_time: f64) {
+        self.time += delta_time;
+
+        let time = self.time;
+        self.effects
+            .retain_mut(|(effect, _)| effect.update(entities, time, delta_time as f32));
     }
 
     pub fn register_point_lights(&self, point_light_manager: &mut PointLightManager, camera: &dyn Camera) {
@@ -440,3 +515,40 @@ impl EffectHolder {
         self.effects.iter().for_each(|(effect, _)| effect.render(renderer, camera));
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::FrameTimer;
+
+    fn frame_timer() -> FrameTimer {
+        FrameTimer {
+            start_time: None,
+            frames_per_second: 10,
+            max_key: 5,
+            current_frame: 0,
+        }
+    }
+
+    #[test]
+    fn frame_timer_starts_at_first_update() {
+        let mut frame_timer = frame_timer();
+
+        assert!(frame_timer.update(100.0));
+        assert_eq!(frame_timer.current_frame, 0);
+
+        assert!(frame_timer.update(100.45));
+        assert_eq!(frame_timer.current_frame, 4);
+    }
+
+    #[test]
+    fn frame_timer_loops() {
+        let mut frame_timer = frame_timer();
+        frame_timer.update(0.0);
+
+        assert!(!frame_timer.update(0.55));
+        assert_eq!(frame_timer.current_frame, 0);
+
+        assert!(!frame_timer.update(1.25));
+        assert_eq!(frame_timer.current_frame, 2);
+    }
+}
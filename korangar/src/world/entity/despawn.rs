@@ -0,0 +1,102 @@
+use ragnarok_packets::{ClientTick, DisappearanceReason};
+
+/// Time that a corpse stays on the ground before it starts fading out in
+/// milliseconds.
+const CORPSE_DURATION: u32 = 3000;
+const CORPSE_FADE_DURATION: u32 = 1000;
+const LOG_OUT_FADE_DURATION: u32 = 600;
+const OUT_OF_SIGHT_FADE_DURATION: u32 = 300;
+const TELEPORT_FADE_DURATION: u32 = 150;
+
+/// Fade-out of an entity that was removed by the server. The entity is kept
+/// around until the fade-out is finished, so it doesn't vanish from one
+/// frame to the next.
+#[derive(Clone, Debug)]
+pub struct Despawn {
+    start_time: ClientTick,
+    /// Time before the fade-out starts.
+    delay: u32,
+    fade_duration: u32,
+    /// The entity died and plays its death animation while it despawns.
+    corpse: bool,
+    opacity: f32,
+    finished: bool,
+}
+
+impl Despawn {
+    pub fn new(reason: &DisappearanceReason, client_tick: ClientTick) -> Self {
+        let corpse = matches!(reason, DisappearanceReason::Died | DisappearanceReason::TrickDead);
+        let (delay, fade_duration) = match reason {
+            DisappearanceReason::Died | DisappearanceReason::TrickDead => (CORPSE_DURATION, CORPSE_FADE_DURATION),
+            DisappearanceReason::LoggedOut => (0, LOG_OUT_FADE_DURATION),
+            DisappearanceReason::OutOfSight => (0, OUT_OF_SIGHT_FADE_DURATION),
+            DisappearanceReason::Teleported => (0, TELEPORT_FADE_DURATION),
+        };
+
+        Self {
+            start_time: client_tick,
+            delay,
+            fade_duration,
+            corpse,
+            opacity: 1.0,
+            finished: false,
+        }
+    }
+
+    pub fn is_corpse(&self) -> bool {
+        self.corpse
+    }
+
+    pub fn update(&mut self, client_tick: ClientTick) {
+        let elapsed = client_tick.0.wrapping_sub(self.start_time.0);
+        let fade_time = elapsed.saturating_sub(self.delay);
+
+        self.opacity = 1.0 - (fade_time as f32 / self.fade_duration as f32).min(1.0);
+        self.finished = fade_time >= self.fade_duration;
+    }
+
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    /// The entity is fully transparent and can be dropped.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ragnarok_packets::{ClientTick, DisappearanceReason};
+
+    use super::{CORPSE_DURATION, CORPSE_FADE_DURATION, Despawn, OUT_OF_SIGHT_FADE_DURATION};
+
+    #[test]
+    fn corpse_stays_before_fading() {
+        let mut despawn = Despawn::new(&DisappearanceReason::Died, ClientTick(1000));
+        assert!(despawn.is_corpse());
+
+        despawn.update(ClientTick(1000 + CORPSE_DURATION));
+        assert_eq!(despawn.opacity(), 1.0);
+
+        despawn.update(ClientTick(1000 + CORPSE_DURATION + CORPSE_FADE_DURATION / 2));
+        assert_eq!(despawn.opacity(), 0.5);
+        assert!(!despawn.is_finished());
+
+        despawn.update(ClientTick(1000 + CORPSE_DURATION + CORPSE_FADE_DURATION));
+        assert_eq!(despawn.opacity(), 0.0);
+        assert!(despawn.is_finished());
+    }
+
+    #[test]
+    fn out_of_sight_fades_immediately() {
+        let mut despawn = Despawn::new(&DisappearanceReason::OutOfSight, ClientTick(0));
+        assert!(!despawn.is_corpse());
+
+        despawn.update(ClientTick(OUT_OF_SIGHT_FADE_DURATION / 2));
+        assert_eq!(despawn.opacity(), 0.5);
+
+        despawn.update(ClientTick(OUT_OF_SIGHT_FADE_DURATION * 2));
+        assert!(despawn.is_finished());
+    }
+}
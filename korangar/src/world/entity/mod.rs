@@ -1,44 +1,59 @@
+mod despawn;
+mod separation;
+mod visibility;
+
 use std::string::String;
 use std::sync::Arc;
 
 use arrayvec::ArrayVec;
-use cgmath::{EuclideanSpace, Point3, Vector2, VectorSpace};
+use cgmath::{EuclideanSpace, InnerSpace, Point3, Vector2, Vector3, VectorSpace, Zero};
 use korangar_audio::{AudioEngine, SoundEffectKey};
 #[cfg(feature = "debug")]
 use korangar_debug::logging::Colorize;
 use korangar_interface::element::StateElement;
 use korangar_interface::window::{StateWindow, Window};
-use korangar_networking::EntityData;
-use ragnarok_packets::{AccountId, CharacterInformation, ClientTick, Direction, EntityId, Sex, StatType, TilePosition, WorldPosition};
+use korangar_networking::{Appearance, AppearanceSlot, BodyState, EntityData};
+use ragnarok_packets::{
+    AccountId, CharacterInformation, ClientTick, Direction, DisappearanceReason, EntityId, Sex, StatType, TilePosition, WorldPosition,
+};
 use rust_state::{Path, RustState, VecItem};
 #[cfg(feature = "debug")]
 use smallvec::smallvec_inline;
 #[cfg(feature = "debug")]
 use wgpu::{BufferUsages, Device, Queue};
 
+use self::despawn::Despawn;
+use self::separation::SEPARATION_SPEED;
+pub use self::separation::separation_offsets;
+pub use self::visibility::{SERVER_VIEW_RANGE, select_visible_entities};
 #[cfg(feature = "debug")]
 use crate::graphics::reduce_vertices;
 #[cfg(feature = "debug")]
 use crate::graphics::{BindlessSupport, DebugRectangleInstruction};
-use crate::graphics::{EntityInstruction, ScreenPosition, ScreenSize};
-use crate::loaders::GameFileLoader;
+use crate::graphics::{Color, EntityInstruction, ScreenPosition, ScreenSize, ShadowBlobInstruction, SpriteDepthBias};
 #[cfg(feature = "debug")]
-use crate::loaders::{GAT_TILE_SIZE, split_mesh_by_texture};
+use crate::loaders::split_mesh_by_texture;
+use crate::loaders::{GAT_TILE_SIZE, GameFileLoader};
 use crate::renderer::GameInterfaceRenderer;
 #[cfg(feature = "debug")]
 use crate::renderer::MarkerRenderer;
 use crate::state::ClientState;
 use crate::state::theme::{InterfaceThemeType, WorldTheme};
-use crate::world::{ActionEvent, AnimationData, AnimationState, Camera, Library, MAX_WALK_PATH_SIZE, Map, PathFinder};
+use crate::world::{
+    ActionEvent, AnimationActionType, AnimationData, AnimationEvent, AnimationEventRegistry, AnimationState, Camera, EntityPart, Library,
+    MAX_WALK_PATH_SIZE, Map, PathFinder, SpriteLayer, TriggeredAnimationEvent,
+};
 #[cfg(feature = "debug")]
 use crate::world::{MarkerIdentifier, SubMesh};
 #[cfg(feature = "debug")]
-use crate::{Buffer, Color, ModelVertex};
+use crate::{Buffer, ModelVertex};
 
 const MALE_HAIR_LOOKUP: &[usize] = &[2, 2, 1, 7, 5, 4, 3, 6, 8, 9, 10, 12, 11];
 const FEMALE_HAIR_LOOKUP: &[usize] = &[2, 2, 4, 7, 1, 5, 3, 6, 12, 10, 9, 11, 8];
 const SOUND_COOLDOWN_DURATION: u32 = 200;
-const SPATIAL_SOUND_RANGE: f32 = 250.0;
+pub const SPATIAL_SOUND_RANGE: f32 = 250.0;
+const SHADOW_BLOB_OPACITY: f32 = 0.5;
+const HIDDEN_OPACITY: f32 = 0.4;
 
 #[derive(Clone)]
 pub enum ResourceState<T> {
@@ -113,6 +128,18 @@ impl From<usize> for EntityType {
     }
 }
 
+impl EntityType {
+    /// Radius of the shadow blob below entities of this type. Entities that
+    /// are not visible don't have a shadow.
+    pub fn shadow_blob_radius(self) -> Option<f32> {
+        match self {
+            EntityType::Player | EntityType::Npc => Some(GAT_TILE_SIZE * 0.8),
+            EntityType::Monster => Some(GAT_TILE_SIZE),
+            EntityType::Hidden | EntityType::Warp => None,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Default)]
 pub struct SoundState {
     previous_key: Option<SoundEffectKey>,
@@ -153,6 +180,12 @@ pub struct Common {
     pub direction: Direction,
     pub head_direction: usize,
     pub sex: Sex,
+    #[hidden_element]
+    pub appearance: Appearance,
+    #[hidden_element]
+    pub body_state: BodyState,
+    /// Hidden or cloaked entities are drawn translucent.
+    pub hidden: bool,
 
     #[hidden_element]
     pub entity_type: EntityType,
@@ -167,6 +200,21 @@ pub struct Common {
     stopped_moving: bool,
     #[hidden_element]
     sound_state: SoundState,
+    /// Start time of the action and index of the frame that was shown in the
+    /// last update, so that frame events only trigger once.
+    #[hidden_element]
+    last_frame: Option<(u32, usize)>,
+    /// Set once the server removed the entity, while it's still fading out.
+    #[hidden_element]
+    despawn: Option<Despawn>,
+    /// The entity is still updated, but not drawn because of the entity draw
+    /// distance or limit.
+    #[hidden_element]
+    culled: bool,
+    /// Visual offset that keeps entities standing on the same tile from
+    /// overlapping perfectly. Doesn't affect the game logic.
+    #[hidden_element]
+    separation_offset: Vector2<f32>,
 }
 
 #[cfg_attr(feature = "debug", korangar_debug::profile)]
@@ -320,7 +368,37 @@ fn get_sprite_path_for_player_job(job_id: usize) -> &'static str {
     }
 }
 
-fn get_entity_part_files(library: &Library, entity_type: EntityType, job_id: usize, sex: Sex, head: Option<usize>) -> Vec<String> {
+/// Pose that an entity holds while its body state is active.
+fn body_state_pose(body_state: BodyState) -> Option<AnimationActionType> {
+    match body_state {
+        BodyState::Stone | BodyState::Freeze => Some(AnimationActionType::Freeze1),
+        BodyState::Stun | BodyState::Sleep | BodyState::Imprison => Some(AnimationActionType::Freeze2),
+        // Petrifying entities can still move until they turn to stone.
+        BodyState::Normal | BodyState::Petrifying => None,
+    }
+}
+
+/// Color that the sprite of an entity is multiplied with while its body state
+/// is active.
+fn body_state_tint(body_state: BodyState) -> Option<Color> {
+    match body_state {
+        BodyState::Stone => Some(Color::monochrome(0.55)),
+        BodyState::Petrifying => Some(Color::monochrome(0.8)),
+        BodyState::Freeze => Some(Color::rgb(0.6, 0.8, 1.0)),
+        BodyState::Normal | BodyState::Stun | BodyState::Sleep | BodyState::Imprison => None,
+    }
+}
+
+/// Sprite files of all layers of an entity. The body is always the first
+/// part, since all other layers are attached to it.
+fn get_entity_part_files(
+    library: &Library,
+    entity_type: EntityType,
+    job_id: usize,
+    sex: Sex,
+    head: Option<usize>,
+    appearance: &Appearance,
+) -> Vec<EntityPart> {
     let sex_sprite_path = match sex == Sex::Female {
         true => "여",
         false => "남",
@@ -339,6 +417,25 @@ fn get_entity_part_files(library: &Library, entity_type: EntityType, job_id: usi
         format!("인간족\\머리통\\{}\\{}_{}", sex_sprite_path, head_id, sex_sprite_path)
     }
 
+    // The accessory names already start with an underscore.
+    fn player_headgear_path(sex_sprite_path: &str, accessory_name: &str) -> String {
+        format!("악세사리\\{}\\{}{}", sex_sprite_path, sex_sprite_path, accessory_name)
+    }
+
+    fn player_garment_path(sex_sprite_path: &str, robe_name: &str, job_id: usize) -> String {
+        format!(
+            "로브\\{}\\{}\\{}_{}",
+            robe_name,
+            sex_sprite_path,
+            get_sprite_path_for_player_job(job_id),
+            sex_sprite_path
+        )
+    }
+
+    fn entity_part(layer: SpriteLayer, file: String) -> EntityPart {
+        EntityPart { layer, file }
+    }
+
     let head_id = match (sex, head) {
         (Sex::Male, Some(head)) if (0..MALE_HAIR_LOOKUP.len()).contains(&head) => MALE_HAIR_LOOKUP[head],
         (Sex::Male, Some(head)) => head,
@@ -348,16 +445,84 @@ fn get_entity_part_files(library: &Library, entity_type: EntityType, job_id: usi
     };
 
     match entity_type {
-        EntityType::Player => vec![
-            player_body_path(sex_sprite_path, job_id),
-            player_head_path(sex_sprite_path, head_id),
-        ],
-        EntityType::Npc => vec![format!("npc\\{}", library.get_job_identity_from_id(job_id))],
-        EntityType::Monster => vec![format!("몬스터\\{}", library.get_job_identity_from_id(job_id))],
-        EntityType::Warp | EntityType::Hidden => vec![format!("npc\\{}", library.get_job_identity_from_id(job_id))], // TODO: change
+        EntityType::Player => {
+            let mut entity_parts = vec![
+                entity_part(SpriteLayer::Body, player_body_path(sex_sprite_path, job_id)),
+                entity_part(SpriteLayer::Head, player_head_path(sex_sprite_path, head_id)),
+            ];
+
+            // Headgears that occupy multiple slots have the same view id in each of
+            // them, but are only drawn once.
+            let headgears = [
+                (SpriteLayer::HeadgearTop, appearance.head_top, true),
+                (
+                    SpriteLayer::HeadgearMiddle,
+                    appearance.head_middle,
+                    appearance.head_middle != appearance.head_top,
+                ),
+                (
+                    SpriteLayer::HeadgearBottom,
+                    appearance.head_bottom,
+                    appearance.head_bottom != appearance.head_top && appearance.head_bottom != appearance.head_middle,
+                ),
+            ];
+
+            for (layer, view_id, is_distinct) in headgears {
+                if view_id != 0
+                    && is_distinct
+                    && let Some(accessory_name) = library.get_accessory_name_from_view_id(view_id as usize)
+                {
+                    entity_parts.push(entity_part(layer, player_headgear_path(sex_sprite_path, accessory_name)));
+                }
+            }
+
+            if appearance.robe != 0
+                && let Some(robe_name) = library.get_robe_name_from_view_id(appearance.robe as usize)
+            {
+                entity_parts.push(entity_part(
+                    SpriteLayer::Garment,
+                    player_garment_path(sex_sprite_path, robe_name, job_id),
+                ));
+            }
+
+            entity_parts
+        }
+        EntityType::Npc => vec![entity_part(
+            SpriteLayer::Body,
+            format!("npc\\{}", library.get_job_identity_from_id(job_id)),
+        )],
+        EntityType::Monster => vec![entity_part(
+            SpriteLayer::Body,
+            format!("몬스터\\{}", library.get_job_identity_from_id(job_id)),
+        )],
+        // TODO: change
+        EntityType::Warp | EntityType::Hidden => vec![entity_part(
+            SpriteLayer::Body,
+            format!("npc\\{}", library.get_job_identity_from_id(job_id)),
+        )],
     }
 }
 
+/// Sprite files of a character shown in the character selection.
+pub fn get_character_part_files(library: &Library, character_information: &CharacterInformation) -> Vec<EntityPart> {
+    let appearance = Appearance {
+        head_top: character_information.accessory2 as u16,
+        head_middle: character_information.accessory3 as u16,
+        head_bottom: character_information.accessory as u16,
+        // The character information doesn't contain the garment.
+        robe: 0,
+    };
+
+    get_entity_part_files(
+        library,
+        EntityType::Player,
+        character_information.job as usize,
+        character_information.sex,
+        Some(character_information.head as usize),
+        &appearance,
+    )
+}
+
 impl Common {
     pub fn new(entity_data: &EntityData, tile_position: TilePosition, world_position: Point3<f32>, client_tick: ClientTick) -> Self {
         let entity_id = entity_data.entity_id;
@@ -369,12 +534,19 @@ impl Common {
         let health_points = entity_data.health_points as usize;
         let maximum_health_points = entity_data.maximum_health_points as usize;
         let sex = entity_data.sex;
+        let appearance = entity_data.appearance;
+        let body_state = entity_data.body_state;
+        let hidden = entity_data.hidden;
 
         let active_movement = None;
         let entity_type = job_id.into();
 
         let details = ResourceState::Unavailable;
-        let animation_state = AnimationState::new(entity_type, client_tick);
+        let mut animation_state = AnimationState::new(entity_type, client_tick);
+
+        if let Some(pose) = body_state_pose(body_state) {
+            animation_state.freeze(entity_type, pose, client_tick);
+        }
 
         Self {
             tile_position,
@@ -384,6 +556,9 @@ impl Common {
             direction,
             head_direction,
             sex,
+            appearance,
+            body_state,
+            hidden,
             active_movement,
             entity_type,
             movement_speed,
@@ -394,34 +569,74 @@ impl Common {
             animation_state,
             stopped_moving: false,
             sound_state: SoundState::default(),
+            last_frame: None,
+            despawn: None,
+            culled: false,
+            separation_offset: Vector2::zero(),
         }
     }
 
-    pub fn get_entity_part_files(&self, library: &Library) -> Vec<String> {
-        get_entity_part_files(library, self.entity_type, self.job_id, self.sex, None)
+    /// Position at which the entity is drawn, which includes the separation
+    /// offset.
+    fn render_position(&self) -> Point3<f32> {
+        self.world_position + Vector3::new(self.separation_offset.x, 0.0, self.separation_offset.y)
+    }
+
+    pub fn get_entity_part_files(&self, library: &Library) -> Vec<EntityPart> {
+        get_entity_part_files(library, self.entity_type, self.job_id, self.sex, None, &self.appearance)
     }
 
-    pub fn update(&mut self, audio_engine: &AudioEngine<GameFileLoader>, map: &Map, camera: &dyn Camera, client_tick: ClientTick) {
+    pub fn update(
+        &mut self,
+        audio_engine: &AudioEngine<GameFileLoader>,
+        map: &Map,
+        camera: &dyn Camera,
+        animation_event_registry: &AnimationEventRegistry,
+        triggered_animation_events: &mut Vec<TriggeredAnimationEvent>,
+        client_tick: ClientTick,
+    ) {
         self.update_movement(map, client_tick);
         self.animation_state.update(client_tick);
 
+        if let Some(despawn) = &mut self.despawn {
+            despawn.update(client_tick);
+        }
+
         if let Some(animation_data) = self.animation_data.as_ref() {
-            if animation_data.is_animation_over(&self.animation_state) && self.animation_state.is_attack() {
+            if animation_data.is_animation_over(&self.animation_state) && self.animation_state.is_one_shot() {
                 self.animation_state.idle(self.entity_type, client_tick);
             }
 
-            let frame = animation_data.get_frame(&self.animation_state, camera, self.direction);
+            let (animation_index, frame_index) =
+                animation_data.get_frame_index(&self.animation_state, camera, self.direction, self.head_direction);
+            let frame = &animation_data.animations[animation_index].frames[frame_index];
+
+            // A frame is shown for multiple updates, but its events should only trigger
+            // once.
+            let frame_key = (self.animation_state.start_time().0, frame_index);
+            let entered_frame = self.last_frame != Some(frame_key);
+            self.last_frame = Some(frame_key);
+
+            let mut trigger = |event| {
+                triggered_animation_events.push(TriggeredAnimationEvent {
+                    position: self.world_position,
+                    event,
+                })
+            };
 
             match frame.event {
                 Some(ActionEvent::Sound { key }) => {
                     self.sound_state.update(audio_engine, self.world_position, key, client_tick);
                 }
-                Some(ActionEvent::Attack) => {
-                    // TODO: NHA What do we need to do at this event? Other
-                    //       clients are playing the attackers weapon attack
-                    //       sound using this event.
-                }
-                None | Some(ActionEvent::Unknown) => { /* Nothing to do */ }
+                Some(ActionEvent::Attack) if entered_frame => trigger(AnimationEvent::AttackImpact),
+                Some(ActionEvent::Attack) | None | Some(ActionEvent::Unknown) => { /* Nothing to do */ }
+            }
+
+            if entered_frame {
+                animation_event_registry
+                    .get(self.job_id, self.animation_state.action_type(), frame_index)
+                    .iter()
+                    .for_each(|event| trigger(*event));
             }
         }
     }
@@ -541,6 +756,8 @@ impl Common {
             // If there is only a single step the player is already on the correct tile.
             if steps.len() > 1 {
                 self.active_movement = Movement::new(steps, starting_timestamp.0).into();
+                // Walking resets the head to face forward, even after the entity stops.
+                self.head_direction = 0;
 
                 if !self.animation_state.is_walking() {
                     self.animation_state.walk(self.entity_type, self.movement_speed, starting_timestamp);
@@ -648,7 +865,7 @@ impl Common {
 
     #[cfg(feature = "debug")]
     pub fn generate_pathing_mesh(&mut self, device: &Device, queue: &Queue, bindless_support: BindlessSupport, map: &Map) {
-        use crate::{Color, NativeModelVertex};
+        use crate::NativeModelVertex;
 
         const PATHING_MESH_OFFSET: f32 = 0.95;
 
@@ -807,18 +1024,51 @@ impl Common {
         }
     }
 
-    pub fn render(&self, instructions: &mut Vec<EntityInstruction>, camera: &dyn Camera, add_to_picker: bool) {
+    pub fn render(
+        &self,
+        instructions: &mut Vec<EntityInstruction>,
+        camera: &dyn Camera,
+        add_to_picker: bool,
+        ground_gradient: Vector2<f32>,
+        depth_bias: &SpriteDepthBias,
+    ) {
+        let offset = instructions.len();
+
         if let Some(animation_data) = self.animation_data.as_ref() {
             animation_data.render(
                 instructions,
                 camera,
                 add_to_picker,
                 self.entity_id,
-                self.world_position,
+                self.render_position(),
                 &self.animation_state,
                 self.direction,
+                self.head_direction,
+                ground_gradient,
+                depth_bias,
             );
         }
+
+        if let Some(tint) = body_state_tint(self.body_state) {
+            instructions[offset..].iter_mut().for_each(|instruction| {
+                instruction.color.red *= tint.red;
+                instruction.color.green *= tint.green;
+                instruction.color.blue *= tint.blue;
+            });
+        }
+
+        let mut opacity = self.despawn.as_ref().map_or(1.0, Despawn::opacity);
+
+        if self.hidden {
+            opacity *= HIDDEN_OPACITY;
+        }
+
+        // Sprites use premultiplied alpha, so all channels are faded.
+        if opacity < 1.0 {
+            instructions[offset..]
+                .iter_mut()
+                .for_each(|instruction| instruction.color = instruction.color * opacity);
+        }
     }
 
     #[cfg(feature = "debug")]
@@ -830,6 +1080,7 @@ impl Common {
                 self.world_position,
                 &self.animation_state,
                 self.direction,
+                self.head_direction,
                 Color::rgb_u8(255, 0, 0),
                 Color::rgb_u8(0, 255, 0),
             );
@@ -878,6 +1129,10 @@ pub struct Player {
     pub bonus_luck: i32,
     pub luck_stat_points_cost: u8,
     pub attack_speed: u32,
+    pub zeny: u32,
+    /// Weight in tenths, like the server sends it.
+    pub weight: u32,
+    pub maximum_weight: u32,
 }
 
 impl Player {
@@ -929,6 +1184,9 @@ impl Player {
             bonus_luck: 0,
             luck_stat_points_cost: 0,
             attack_speed: 0,
+            zeny: character_information.money.max(0) as u32,
+            weight: 0,
+            maximum_weight: 0,
         }
     }
 
@@ -983,12 +1241,15 @@ impl Player {
             StatType::DexterityStatPointCost(cost) => self.dexterity_stat_points_cost = cost,
             StatType::LuckStatPointCost(cost) => self.luck_stat_points_cost = cost,
             StatType::AttackSpeed(attack_speed) => self.attack_speed = attack_speed,
+            StatType::Zeny(zeny) => self.zeny = zeny,
+            StatType::Weight(weight) => self.weight = weight,
+            StatType::MaximumWeight(maximum_weight) => self.maximum_weight = maximum_weight,
             _ => {}
         }
     }
 
     pub fn render_status(&self, renderer: &GameInterfaceRenderer, camera: &dyn Camera, theme: &WorldTheme, window_size: ScreenSize) {
-        let clip_space_position = camera.view_projection_matrix() * self.common.world_position.to_homogeneous();
+        let clip_space_position = camera.view_projection_matrix() * self.common.render_position().to_homogeneous();
         let screen_position = camera.clip_to_screen_space(clip_space_position);
         let final_position = ScreenPosition {
             left: screen_position.x * window_size.width,
@@ -1049,9 +1310,16 @@ impl Player {
         );
     }
 
-    pub fn get_entity_part_files(&self, library: &Library) -> Vec<String> {
+    pub fn get_entity_part_files(&self, library: &Library) -> Vec<EntityPart> {
         let common = self.get_common();
-        get_entity_part_files(library, common.entity_type, common.job_id, common.sex, Some(self.hair_id))
+        get_entity_part_files(
+            library,
+            common.entity_type,
+            common.job_id,
+            common.sex,
+            Some(self.hair_id),
+            &common.appearance,
+        )
     }
 }
 
@@ -1100,7 +1368,7 @@ impl Npc {
             return;
         }
 
-        let clip_space_position = camera.view_projection_matrix() * self.common.world_position.to_homogeneous();
+        let clip_space_position = camera.view_projection_matrix() * self.common.render_position().to_homogeneous();
         let screen_position = camera.clip_to_screen_space(clip_space_position);
         let final_position = ScreenPosition {
             left: screen_position.x * window_size.width,
@@ -1167,6 +1435,10 @@ impl Entity {
         }
     }
 
+    pub fn get_job(&self) -> usize {
+        self.get_common().job_id
+    }
+
     pub fn set_job(&mut self, job_id: usize) {
         self.get_common_mut().job_id = job_id;
     }
@@ -1177,11 +1449,15 @@ impl Entity {
         }
     }
 
+    pub fn set_appearance(&mut self, slot: AppearanceSlot, view_id: u16) {
+        self.get_common_mut().appearance.set(slot, view_id);
+    }
+
     pub fn set_animation_data(&mut self, animation_data: Arc<AnimationData>) {
         self.get_common_mut().animation_data = Some(animation_data)
     }
 
-    pub fn get_entity_part_files(&self, library: &Library) -> Vec<String> {
+    pub fn get_entity_part_files(&self, library: &Library) -> Vec<EntityPart> {
         match self {
             Self::Player(player) => player.get_entity_part_files(library),
             Self::Npc(npc) => npc.get_common().get_entity_part_files(library),
@@ -1217,6 +1493,63 @@ impl Entity {
         self.get_common_mut().animation_state.dead(entity_type, client_tick);
     }
 
+    pub fn set_sitting(&mut self, client_tick: ClientTick) {
+        let entity_type = self.get_entity_type();
+        self.get_common_mut().animation_state.sit(entity_type, client_tick);
+    }
+
+    pub fn set_pick_up(&mut self, client_tick: ClientTick) {
+        let entity_type = self.get_entity_type();
+        self.get_common_mut().animation_state.pick_up(entity_type, client_tick);
+    }
+
+    /// Apply the status ailments and visibility sent by the server. Entities
+    /// hold a pose while they are frozen, stunned, or asleep.
+    pub fn set_state(&mut self, body_state: BodyState, hidden: bool, client_tick: ClientTick) {
+        let entity_type = self.get_entity_type();
+        let common = self.get_common_mut();
+
+        common.body_state = body_state;
+        common.hidden = hidden;
+
+        match body_state_pose(body_state) {
+            Some(pose) => common.animation_state.freeze(entity_type, pose, client_tick),
+            None if common.animation_state.is_frozen() => common.animation_state.idle(entity_type, client_tick),
+            None => {}
+        }
+    }
+
+    /// Start fading out the entity after the server removed it. Entities that
+    /// died stay on the ground as a corpse for a while.
+    pub fn despawn(&mut self, reason: &DisappearanceReason, client_tick: ClientTick) {
+        let despawn = Despawn::new(reason, client_tick);
+
+        if despawn.is_corpse() {
+            self.set_dead(client_tick);
+            self.stop_movement();
+        }
+
+        self.get_common_mut().despawn = Some(despawn);
+    }
+
+    /// The entity is despawning and shows its corpse.
+    pub fn is_corpse(&self) -> bool {
+        self.get_common().despawn.as_ref().is_some_and(Despawn::is_corpse)
+    }
+
+    pub fn is_culled(&self) -> bool {
+        self.get_common().culled
+    }
+
+    pub fn set_culled(&mut self, culled: bool) {
+        self.get_common_mut().culled = culled;
+    }
+
+    /// The entity faded out completely and can be removed.
+    pub fn is_despawned(&self) -> bool {
+        self.get_common().despawn.as_ref().is_some_and(Despawn::is_finished)
+    }
+
     pub fn set_idle(&mut self, client_tick: ClientTick) {
         let entity_type = self.get_entity_type();
         self.get_common_mut().animation_state.idle(entity_type, client_tick);
@@ -1231,10 +1564,24 @@ impl Entity {
             (common.tile_position.x as isize - target_position.x as isize).clamp(-1, 1),
             (common.tile_position.y as isize - target_position.y as isize).clamp(-1, 1),
         ]) {
+            // Turning the body also turns the head to face forward.
             common.direction = direction;
+            common.head_direction = 0;
         }
     }
 
+    /// Turn the body and head, for example when the entity looks at another
+    /// entity. Walking players can't turn their head.
+    pub fn set_direction(&mut self, direction: Direction, head_direction: usize) {
+        let common = self.get_common_mut();
+
+        common.direction = direction;
+        common.head_direction = match common.animation_state.action_type().turns_head() {
+            true => head_direction,
+            false => 0,
+        };
+    }
+
     pub fn set_attack(&mut self, attack_duration: u32, critical: bool, client_tick: ClientTick) {
         let entity_type = self.get_entity_type();
         self.get_common_mut()
@@ -1242,6 +1589,23 @@ impl Entity {
             .attack(entity_type, attack_duration, critical, client_tick);
     }
 
+    pub fn is_moving(&self) -> bool {
+        self.get_common().active_movement.is_some()
+    }
+
+    /// Moves the entity towards its separated position. See
+    /// [`separation_offsets`].
+    pub fn update_separation(&mut self, target_offset: Vector2<f32>, delta_time: f32) {
+        let separation_offset = &mut self.get_common_mut().separation_offset;
+        let difference = target_offset - *separation_offset;
+        let step = SEPARATION_SPEED * delta_time;
+
+        *separation_offset = match difference.magnitude() <= step {
+            true => target_offset,
+            false => *separation_offset + difference.normalize_to(step),
+        };
+    }
+
     pub fn stopped_moving(&self) -> bool {
         self.get_common().stopped_moving
     }
@@ -1256,8 +1620,23 @@ impl Entity {
         common.maximum_health_points = maximum_health_points;
     }
 
-    pub fn update(&mut self, audio_engine: &AudioEngine<GameFileLoader>, map: &Map, camera: &dyn Camera, client_tick: ClientTick) {
-        self.get_common_mut().update(audio_engine, map, camera, client_tick);
+    pub fn update(
+        &mut self,
+        audio_engine: &AudioEngine<GameFileLoader>,
+        map: &Map,
+        camera: &dyn Camera,
+        animation_event_registry: &AnimationEventRegistry,
+        triggered_animation_events: &mut Vec<TriggeredAnimationEvent>,
+        client_tick: ClientTick,
+    ) {
+        self.get_common_mut().update(
+            audio_engine,
+            map,
+            camera,
+            animation_event_registry,
+            triggered_animation_events,
+            client_tick,
+        );
     }
 
     pub fn move_from_to(
@@ -1276,8 +1655,38 @@ impl Entity {
         self.get_common_mut().generate_pathing_mesh(device, queue, bindless_support, map);
     }
 
-    pub fn render(&self, instructions: &mut Vec<EntityInstruction>, camera: &dyn Camera, add_to_picker: bool) {
-        self.get_common().render(instructions, camera, add_to_picker);
+    pub fn render(
+        &self,
+        instructions: &mut Vec<EntityInstruction>,
+        camera: &dyn Camera,
+        add_to_picker: bool,
+        ground_gradient: Vector2<f32>,
+        depth_bias: &SpriteDepthBias,
+    ) {
+        if self.is_culled() {
+            return;
+        }
+
+        self.get_common()
+            .render(instructions, camera, add_to_picker, ground_gradient, depth_bias);
+    }
+
+    pub fn render_shadow_blob(&self, instructions: &mut Vec<ShadowBlobInstruction>) {
+        let common = self.get_common();
+
+        if common.animation_data.is_none() || common.culled {
+            return;
+        }
+
+        if let Some(radius) = common.entity_type.shadow_blob_radius() {
+            let despawn_opacity = common.despawn.as_ref().map_or(1.0, Despawn::opacity);
+
+            instructions.push(ShadowBlobInstruction {
+                position: common.render_position(),
+                radius,
+                opacity: SHADOW_BLOB_OPACITY * despawn_opacity,
+            });
+        }
     }
 
     #[cfg(feature = "debug")]
@@ -0,0 +1,100 @@
+use std::f32::consts::TAU;
+
+use cgmath::{Vector2, Zero};
+use hashbrown::HashMap;
+use ragnarok_packets::{EntityId, TilePosition};
+
+use crate::loaders::GAT_TILE_SIZE;
+
+/// Distance in world units that entities standing on the same tile are moved
+/// away from the center of the tile.
+const SEPARATION_RADIUS: f32 = GAT_TILE_SIZE * 0.3;
+/// Speed in world units per second at which entities move to and from their
+/// separated position.
+pub const SEPARATION_SPEED: f32 = GAT_TILE_SIZE * 2.0;
+
+/// Calculates the visual offsets of entities that stand on the same tile, so
+/// they don't overlap perfectly. The offsets are purely visual and only
+/// depend on the entity ids, so they are stable from frame to frame. Each
+/// entry contains the id of an entity and the tile it stands on, or `None`
+/// if the entity is moving and should not be separated.
+pub fn separation_offsets(entities: &[(EntityId, Option<TilePosition>)]) -> Vec<Vector2<f32>> {
+    let mut stacks: HashMap<TilePosition, Vec<EntityId>> = HashMap::new();
+
+    entities
+        .iter()
+        .filter_map(|(entity_id, tile_position)| tile_position.map(|tile_position| (tile_position, *entity_id)))
+        .for_each(|(tile_position, entity_id)| stacks.entry(tile_position).or_default().push(entity_id));
+
+    stacks
+        .values_mut()
+        .for_each(|stack| stack.sort_unstable_by_key(|entity_id| entity_id.0));
+
+    entities
+        .iter()
+        .map(|(entity_id, tile_position)| {
+            let Some(stack) = tile_position.and_then(|tile_position| stacks.get(&tile_position)) else {
+                return Vector2::zero();
+            };
+
+            if stack.len() < 2 {
+                return Vector2::zero();
+            }
+
+            // Entities are spread evenly around the center of the tile, ordered by their
+            // id. The rotation of the circle depends on the lowest id, so stacks don't all
+            // look the same.
+            let index = stack.iter().position(|other_id| other_id == entity_id).unwrap_or_default();
+            let rotation = (stack[0].0.wrapping_mul(0x9E37_79B9) >> 8) as f32 / (1 << 24) as f32 * TAU;
+            let angle = rotation + index as f32 / stack.len() as f32 * TAU;
+
+            Vector2::new(angle.cos(), angle.sin()) * SEPARATION_RADIUS
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::{InnerSpace, Zero};
+    use ragnarok_packets::{EntityId, TilePosition};
+
+    use super::{SEPARATION_RADIUS, separation_offsets};
+
+    const TILE: TilePosition = TilePosition { x: 50, y: 50 };
+    const OTHER_TILE: TilePosition = TilePosition { x: 51, y: 50 };
+
+    #[test]
+    fn single_entities_are_not_moved() {
+        let offsets = separation_offsets(&[(EntityId(1), Some(TILE)), (EntityId(2), Some(OTHER_TILE))]);
+
+        assert!(offsets.iter().all(|offset| offset.is_zero()));
+    }
+
+    #[test]
+    fn moving_entities_are_not_moved() {
+        let offsets = separation_offsets(&[(EntityId(1), Some(TILE)), (EntityId(2), None)]);
+
+        assert!(offsets.iter().all(|offset| offset.is_zero()));
+    }
+
+    #[test]
+    fn stacked_entities_are_separated() {
+        let offsets = separation_offsets(&[(EntityId(7), Some(TILE)), (EntityId(3), Some(TILE)), (EntityId(5), Some(TILE))]);
+
+        for offset in &offsets {
+            assert!((offset.magnitude() - SEPARATION_RADIUS).abs() < 1e-4);
+        }
+
+        assert!((offsets[0] - offsets[1]).magnitude() > SEPARATION_RADIUS);
+        assert!((offsets[1] - offsets[2]).magnitude() > SEPARATION_RADIUS);
+    }
+
+    #[test]
+    fn offsets_are_independent_of_order() {
+        let first = separation_offsets(&[(EntityId(7), Some(TILE)), (EntityId(3), Some(TILE))]);
+        let second = separation_offsets(&[(EntityId(3), Some(TILE)), (EntityId(7), Some(TILE))]);
+
+        assert_eq!(first[0], second[1]);
+        assert_eq!(first[1], second[0]);
+    }
+}
@@ -0,0 +1,92 @@
+use ragnarok_packets::TilePosition;
+
+/// Distance in tiles up to which the server sends entities to the client.
+pub const SERVER_VIEW_RANGE: usize = 14;
+
+/// Distance in tiles, measured the same way the server measures its view
+/// range.
+fn tile_distance(from: TilePosition, to: TilePosition) -> usize {
+    let horizontal = (from.x as isize - to.x as isize).unsigned_abs();
+    let vertical = (from.y as isize - to.y as isize).unsigned_abs();
+    horizontal.max(vertical)
+}
+
+/// Decide which entities are drawn. The first position belongs to the player,
+/// who is always drawn, just like the prioritized entity. All other entities
+/// are drawn nearest first, until the limit is reached.
+pub fn select_visible_entities(
+    positions: &[TilePosition],
+    prioritized: Option<usize>,
+    maximum_distance: Option<usize>,
+    maximum_count: Option<usize>,
+) -> Vec<bool> {
+    let mut visible = vec![false; positions.len()];
+
+    let Some(player_position) = positions.first().copied() else {
+        return visible;
+    };
+
+    visible[0] = true;
+
+    let mut remaining = maximum_count.unwrap_or(usize::MAX);
+
+    if let Some(index) = prioritized
+        && index != 0
+        && index < positions.len()
+    {
+        visible[index] = true;
+        remaining = remaining.saturating_sub(1);
+    }
+
+    let mut candidates: Vec<(usize, usize)> = positions
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(index, _)| !visible[*index])
+        .map(|(index, position)| (index, tile_distance(player_position, *position)))
+        .filter(|(_, distance)| maximum_distance.is_none_or(|maximum_distance| *distance <= maximum_distance))
+        .collect();
+
+    candidates.sort_by_key(|(_, distance)| *distance);
+    candidates.into_iter().take(remaining).for_each(|(index, _)| visible[index] = true);
+
+    visible
+}
+
+#[cfg(test)]
+mod test {
+    use ragnarok_packets::TilePosition;
+
+    use super::select_visible_entities;
+
+    fn positions(coordinates: &[(u16, u16)]) -> Vec<TilePosition> {
+        coordinates.iter().map(|(x, y)| TilePosition { x: *x, y: *y }).collect()
+    }
+
+    #[test]
+    fn nearest_entities_are_drawn_first() {
+        let positions = positions(&[(50, 50), (60, 50), (51, 51), (50, 55)]);
+
+        assert_eq!(select_visible_entities(&positions, None, None, Some(2)), [
+            true, false, true, true
+        ]);
+    }
+
+    #[test]
+    fn entities_beyond_draw_distance_are_hidden() {
+        let positions = positions(&[(50, 50), (60, 50), (51, 51), (50, 55)]);
+
+        assert_eq!(select_visible_entities(&positions, None, Some(5), None), [
+            true, false, true, true
+        ]);
+    }
+
+    #[test]
+    fn prioritized_entity_is_always_drawn() {
+        let positions = positions(&[(50, 50), (60, 50), (51, 51), (50, 55)]);
+
+        assert_eq!(select_visible_entities(&positions, Some(1), Some(5), Some(2)), [
+            true, true, true, false
+        ]);
+    }
+}
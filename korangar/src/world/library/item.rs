@@ -0,0 +1,184 @@
+use std::ops::RangeInclusive;
+
+use ragnarok_packets::ItemId;
+use serde::Deserialize;
+
+const ITEM_LINK_START: &str = "<ITEM>";
+const ITEM_LINK_END: &str = "</ITEM>";
+
+/// Type of an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ItemType {
+    Healing,
+    Usable,
+    Etc,
+    Weapon,
+    Armor,
+    Card,
+    Ammunition,
+}
+
+/// Slot that an item can be equipped in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum EquipSlot {
+    HeadTop,
+    HeadMid,
+    HeadLow,
+    Armor,
+    Weapon,
+    Shield,
+    Garment,
+    Shoes,
+    Accessory,
+}
+
+/// Entry of the bundled item database. Names and icons of items come from
+/// the item info of the client, so the database only contains the
+/// information used for searching.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ItemDetails {
+    pub item_id: u32,
+    pub item_type: ItemType,
+    #[serde(default)]
+    pub equip_slots: Vec<EquipSlot>,
+    /// Base level required to equip or use the item.
+    #[serde(default)]
+    pub required_level: u16,
+    /// Names of the NPCs that sell the item.
+    #[serde(default)]
+    pub sold_by: Vec<String>,
+}
+
+/// Filters of an item search. Items without an entry in the item database
+/// only match if no filter is set.
+#[derive(Debug, Clone)]
+pub struct ItemSearchFilter {
+    pub item_type: Option<ItemType>,
+    pub equip_slot: Option<EquipSlot>,
+    pub level_range: RangeInclusive<u16>,
+}
+
+impl Default for ItemSearchFilter {
+    fn default() -> Self {
+        Self {
+            item_type: None,
+            equip_slot: None,
+            level_range: 0..=u16::MAX,
+        }
+    }
+}
+
+impl ItemSearchFilter {
+    fn is_empty(&self) -> bool {
+        self.item_type.is_none() && self.equip_slot.is_none() && self.level_range == (0..=u16::MAX)
+    }
+
+    pub fn matches(&self, details: Option<&ItemDetails>) -> bool {
+        let Some(details) = details else {
+            return self.is_empty();
+        };
+
+        self.item_type.is_none_or(|item_type| details.item_type == item_type)
+            && self.equip_slot.is_none_or(|equip_slot| details.equip_slots.contains(&equip_slot))
+            && self.level_range.contains(&details.required_level)
+    }
+}
+
+/// Text that is sent in a chat message to link an item.
+pub fn item_link(item_id: ItemId) -> String {
+    format!("{ITEM_LINK_START}{}{ITEM_LINK_END}", item_id.0)
+}
+
+/// Replaces the item links in a chat message with the names of the linked
+/// items and returns the linked items in order. Malformed links are left as
+/// they are.
+pub fn resolve_item_links(text: &str, item_name: impl Fn(ItemId) -> String) -> (String, Vec<ItemId>) {
+    let mut resolved = String::with_capacity(text.len());
+    let mut item_links = Vec::new();
+    let mut remaining = text;
+
+    while let Some(start) = remaining.find(ITEM_LINK_START) {
+        let (before, link) = remaining.split_at(start);
+        resolved.push_str(before);
+
+        let item_id = link[ITEM_LINK_START.len()..]
+            .split_once(ITEM_LINK_END)
+            .and_then(|(item_id, rest)| Some((ItemId(item_id.parse().ok()?), rest)));
+
+        match item_id {
+            Some((item_id, rest)) => {
+                resolved.push('[');
+                resolved.push_str(&item_name(item_id));
+                resolved.push(']');
+                item_links.push(item_id);
+                remaining = rest;
+            }
+            None => {
+                resolved.push_str(ITEM_LINK_START);
+                remaining = &link[ITEM_LINK_START.len()..];
+            }
+        }
+    }
+
+    resolved.push_str(remaining);
+
+    (resolved, item_links)
+}
+
+#[cfg(test)]
+mod test {
+    use ragnarok_packets::ItemId;
+
+    use super::{EquipSlot, ItemDetails, ItemSearchFilter, ItemType, item_link, resolve_item_links};
+
+    fn details(item_type: ItemType, equip_slots: Vec<EquipSlot>, required_level: u16) -> ItemDetails {
+        ItemDetails {
+            item_id: 0,
+            item_type,
+            equip_slots,
+            required_level,
+            sold_by: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        let filter = ItemSearchFilter::default();
+
+        assert!(filter.matches(None));
+        assert!(filter.matches(Some(&details(ItemType::Etc, Vec::new(), 0))));
+    }
+
+    #[test]
+    fn filters_are_combined() {
+        let filter = ItemSearchFilter {
+            item_type: Some(ItemType::Armor),
+            equip_slot: Some(EquipSlot::HeadTop),
+            level_range: 10..=50,
+        };
+
+        assert!(filter.matches(Some(&details(ItemType::Armor, vec![EquipSlot::HeadTop], 30))));
+        assert!(!filter.matches(Some(&details(ItemType::Armor, vec![EquipSlot::Shoes], 30))));
+        assert!(!filter.matches(Some(&details(ItemType::Weapon, vec![EquipSlot::HeadTop], 30))));
+        assert!(!filter.matches(Some(&details(ItemType::Armor, vec![EquipSlot::HeadTop], 60))));
+        assert!(!filter.matches(None));
+    }
+
+    #[test]
+    fn item_links_are_resolved() {
+        let text = format!("wts {} and {} cheap", item_link(ItemId(909)), item_link(ItemId(4001)));
+        let (resolved, item_links) = resolve_item_links(&text, |item_id| format!("item {}", item_id.0));
+
+        assert_eq!(resolved, "wts [item 909] and [item 4001] cheap");
+        assert_eq!(item_links, [ItemId(909), ItemId(4001)]);
+    }
+
+    #[test]
+    fn malformed_item_links_are_kept() {
+        let text = "<ITEM>abc</ITEM> <ITEM>12";
+        let (resolved, item_links) = resolve_item_links(text, |_| unreachable!());
+
+        assert_eq!(resolved, text);
+        assert!(item_links.is_empty());
+    }
+}
@@ -1,19 +1,40 @@
+mod item;
+mod monster;
+mod search;
+mod skill_tree;
+
 use std::sync::Arc;
 
 use encoding_rs::EUC_KR;
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use korangar_loaders::FileLoader;
-use korangar_networking::{InventoryItem, NoMetadata, ShopItem};
+use korangar_networking::{InventoryItem, InventoryItemDetails, NoMetadata, ShopItem};
 use mlua::{Lua, Value};
-use ragnarok_packets::ItemId;
+use ragnarok_packets::{ItemId, ItemOptions};
 
+pub use self::item::{EquipSlot, ItemDetails, ItemSearchFilter, ItemType, item_link};
+pub use self::monster::{MonsterDrop, MonsterElement, MonsterInfo, MonsterRace, MonsterSize};
+use self::search::fuzzy_score;
+pub use self::skill_tree::{JobSkillTree, SkillDamage, SkillRequirement, SkillTreeEntry};
 use crate::graphics::{Color, Texture};
-use crate::loaders::{AsyncLoader, GameFileLoader, ImageType, ItemLocation};
+use crate::loaders::{AsyncLoader, GameFileLoader};
+
+/// Link type of warp portals inside the navigation data. Other link types
+/// are warps through NPCs, like the Kafra service.
+const WARP_PORTAL_LINK_TYPE: usize = 200;
 
 #[derive(Debug, Clone)]
 pub struct ResourceMetadata {
+    /// Icon of the item, or `None` while it is still loading.
     pub texture: Option<Arc<Texture>>,
+    pub icon_path: String,
     pub name: String,
+    /// Number of card slots of the item.
+    pub slot_count: u8,
+    /// Names of the cards and enchantments in the slots of the item.
+    pub cards: Vec<String>,
+    /// Name, cards and random options of the item, as shown when hovering it.
+    pub tooltip: String,
 }
 
 #[derive(Debug, Clone)]
@@ -22,41 +43,57 @@ struct ItemInfo {
     unidentified_name: Option<String>,
     identified_resource: Option<String>,
     unidentified_resource: Option<String>,
+    slot_count: u8,
 }
 
 #[allow(unused)]
 #[derive(Debug, Clone)]
 pub struct MapSkyData {
-    old_cloud_effect: Option<usize>,
-    bg_color: Option<Color>,
-    bg_fog: bool,
-    star_effect: bool,
-    cloud_effect: Vec<CloudEffect>,
+    pub old_cloud_effect: Option<usize>,
+    pub bg_color: Option<Color>,
+    pub bg_fog: bool,
+    pub star_effect: bool,
+    pub cloud_effect: Vec<CloudEffect>,
 }
 
 #[allow(unused)]
 #[derive(Debug, Clone)]
 pub struct CloudEffect {
-    num: usize,
-    cull_dist: usize,
-    color: Color,
-    size: usize,
-    size_extra: usize,
-    expand_rate: f32,
-    alpha_inc_time: usize,
-    alpha_inc_time_extra: usize,
-    alpha_inc_speed: usize,
-    alpha_dec_time: usize,
-    alpha_dec_time_extra: usize,
-    alpha_dec_speed: f32,
-    height: usize,
-    height_extra: usize,
+    pub num: usize,
+    pub cull_dist: usize,
+    pub color: Color,
+    pub size: usize,
+    pub size_extra: usize,
+    pub expand_rate: f32,
+    pub alpha_inc_time: usize,
+    pub alpha_inc_time_extra: usize,
+    pub alpha_inc_speed: usize,
+    pub alpha_dec_time: usize,
+    pub alpha_dec_time_extra: usize,
+    pub alpha_dec_speed: f32,
+    pub height: usize,
+    pub height_extra: usize,
 }
 
 pub struct Library {
     job_identity_table: HashMap<usize, String>,
     item_table: HashMap<ItemId, ItemInfo>,
     map_sky_data_table: HashMap<String, MapSkyData>,
+    accessory_name_table: HashMap<usize, String>,
+    robe_name_table: HashMap<usize, String>,
+    random_option_name_table: HashMap<usize, String>,
+    /// Resource files of maps that the official client treats as indoors.
+    indoor_map_table: HashSet<String>,
+    /// Maps that can be reached through a warp portal, keyed by the resource
+    /// file of the map.
+    warp_graph: HashMap<String, Vec<String>>,
+    /// Bundled monster database, keyed by the job id of the monster.
+    monster_table: HashMap<usize, MonsterInfo>,
+    /// Bundled item database with the information used for searching items.
+    item_database: HashMap<ItemId, ItemDetails>,
+    /// Bundled skill trees of all jobs, in the order they are listed in the
+    /// database.
+    skill_trees: Vec<JobSkillTree>,
 }
 
 impl Library {
@@ -93,10 +130,56 @@ impl Library {
             Err(_) => HashMap::new(),
         };
 
+        let accessory_name_table = Self::load_view_name_table(game_file_loader, "accessoryid.lub", "accname.lub", "AccNameTable")?;
+        let robe_name_table = Self::load_view_name_table(game_file_loader, "spriterobeid.lub", "spriterobename.lub", "RobeNameTable")?;
+        let random_option_name_table = Self::load_view_name_table(
+            game_file_loader,
+            "enumvar.lub",
+            "addrandomoption_nametable.lub",
+            "NameTable_VAR",
+        )?;
+
+        let indoor_map_table = match game_file_loader.get("data\\indoorrswtable.txt") {
+            Ok(data) => Self::load_indoor_map_table(&data),
+            Err(_) => HashSet::new(),
+        };
+
+        let warp_graph = match game_file_loader.get("data\\luafiles514\\lua files\\navigation\\navi_link_krpri.lub") {
+            Ok(data) => {
+                let state = Lua::new();
+                state.load(&data).exec()?;
+                Self::load_warp_graph(&state)?
+            }
+            Err(_) => HashMap::new(),
+        };
+
+        let monster_table = match game_file_loader.get("data\\monsterdb.ron") {
+            Ok(data) => Self::load_monster_table(&data),
+            Err(_) => HashMap::new(),
+        };
+
+        let item_database = match game_file_loader.get("data\\itemdb.ron") {
+            Ok(data) => Self::load_item_database(&data),
+            Err(_) => HashMap::new(),
+        };
+
+        let skill_trees = match game_file_loader.get("data\\skilltree.ron") {
+            Ok(data) => ron::de::from_bytes(&data).unwrap_or_default(),
+            Err(_) => Vec::new(),
+        };
+
         Ok(Self {
             job_identity_table,
             item_table,
             map_sky_data_table,
+            accessory_name_table,
+            robe_name_table,
+            random_option_name_table,
+            indoor_map_table,
+            warp_graph,
+            monster_table,
+            item_database,
+            skill_trees,
         })
     }
 
@@ -153,6 +236,7 @@ impl Library {
                     unidentified_name: item_table.get("unidentifiedDisplayName").ok().map(fix_encoding),
                     identified_resource: item_table.get("identifiedResourceName").ok().map(fix_encoding),
                     unidentified_resource: item_table.get("unidentifiedResourceName").ok().map(fix_encoding),
+                    slot_count: item_table.get("slotCount").unwrap_or_default(),
                 };
 
                 result.insert(ItemId(item_id), info);
@@ -181,6 +265,98 @@ impl Library {
         Ok(compacted)
     }
 
+    /// Load the sprite names of headgears or garments by their view id, or the
+    /// names of random options by their index. The id file defines the
+    /// constants that the name file uses as keys. Older data folders might not
+    /// contain the files, in which case no names are known.
+    fn load_view_name_table(
+        game_file_loader: &GameFileLoader,
+        id_file: &str,
+        name_file: &str,
+        table_name: &str,
+    ) -> mlua::Result<HashMap<usize, String>> {
+        let id_data = game_file_loader.get(&format!("data\\luafiles514\\lua files\\datainfo\\{id_file}"));
+        let name_data = game_file_loader.get(&format!("data\\luafiles514\\lua files\\datainfo\\{name_file}"));
+
+        let (Ok(id_data), Ok(name_data)) = (id_data, name_data) else {
+            return Ok(HashMap::new());
+        };
+
+        let state = Lua::new();
+        state.load(&id_data).exec()?;
+        state.load(&name_data).exec()?;
+
+        let globals = state.globals();
+        let mut result = HashMap::new();
+
+        if let Ok(table) = globals.get::<mlua::Table>(table_name) {
+            for (view_id, name) in table.pairs::<usize, String>().flatten() {
+                result.insert(view_id, fix_encoding(name));
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Every line of the table names one map, for example `prt_church.rsw#`.
+    fn load_indoor_map_table(data: &[u8]) -> HashSet<String> {
+        String::from_utf8_lossy(data)
+            .lines()
+            .filter_map(|line| line.split('#').next())
+            .map(|entry| entry.trim().to_lowercase())
+            .filter_map(|entry| entry.strip_suffix(".rsw").map(str::to_owned))
+            .collect()
+    }
+
+    /// The monster database is a RON list of [`MonsterInfo`]. An invalid
+    /// database is treated like a missing one.
+    fn load_monster_table(data: &[u8]) -> HashMap<usize, MonsterInfo> {
+        ron::de::from_bytes::<Vec<MonsterInfo>>(data)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|monster| (monster.job_id, monster))
+            .collect()
+    }
+
+    /// The item database is a RON list of [`ItemDetails`]. An invalid database
+    /// is treated like a missing one.
+    fn load_item_database(data: &[u8]) -> HashMap<ItemId, ItemDetails> {
+        ron::de::from_bytes::<Vec<ItemDetails>>(data)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|details| (ItemId(details.item_id), details))
+            .collect()
+    }
+
+    /// Every link is a list of the form `{ map, id, type, class, name, ...,
+    /// x, y, destination map, destination x, destination y }`.
+    fn load_warp_graph(state: &Lua) -> mlua::Result<HashMap<String, Vec<String>>> {
+        let globals = state.globals();
+        let mut result: HashMap<String, Vec<String>> = HashMap::new();
+
+        let Ok(table) = globals.get::<mlua::Table>("Navi_Link") else {
+            return Ok(result);
+        };
+
+        for link in table.sequence_values::<mlua::Table>().flatten() {
+            let (Ok(source), Ok(link_type), Ok(destination)) = (link.get::<String>(1), link.get::<usize>(3), link.get::<String>(9)) else {
+                continue;
+            };
+
+            if link_type != WARP_PORTAL_LINK_TYPE || source == destination {
+                continue;
+            }
+
+            let adjacent_maps = result.entry(source).or_default();
+
+            if !adjacent_maps.contains(&destination) {
+                adjacent_maps.push(destination);
+            }
+        }
+
+        Ok(result)
+    }
+
     fn parse_map_sky_data(table: &mlua::Table) -> MapSkyData {
         let mut cloud_effect = Vec::new();
 
@@ -224,7 +400,7 @@ impl Library {
             .unwrap_or("1_f_maria")
     }
 
-    fn get_item_name_from_id(&self, item_id: ItemId, is_identified: bool) -> &str {
+    pub fn get_item_name_from_id(&self, item_id: ItemId, is_identified: bool) -> &str {
         match is_identified {
             true => self.item_table.get(&item_id).and_then(|info| info.identified_name.as_deref()),
             false => self.item_table.get(&item_id).and_then(|info| info.unidentified_name.as_deref()),
@@ -240,10 +416,131 @@ impl Library {
         .unwrap_or("사과") // Apple
     }
 
+    /// Sprite name of the headgear with the given view id, for example
+    /// `_고글`.
+    pub fn get_accessory_name_from_view_id(&self, view_id: usize) -> Option<&str> {
+        self.accessory_name_table.get(&view_id).map(|name| name.as_str())
+    }
+
+    /// Sprite name of the garment with the given view id, for example
+    /// `천사날개`.
+    pub fn get_robe_name_from_view_id(&self, view_id: usize) -> Option<&str> {
+        self.robe_name_table.get(&view_id).map(|name| name.as_str())
+    }
+
+    fn get_item_slot_count_from_id(&self, item_id: ItemId) -> u8 {
+        self.item_table.get(&item_id).map(|info| info.slot_count).unwrap_or_default()
+    }
+
+    /// Description of a random option, for example `MaxHP + 5`.
+    fn get_random_option_description(&self, option: &ItemOptions) -> String {
+        match self.random_option_name_table.get(&(option.index as usize)) {
+            Some(format) => format.replace("%d", &option.value.to_string()).replace("%%", "%"),
+            None => format!("Random option {} ({})", option.index, option.value),
+        }
+    }
+
     pub fn get_map_sky_data_from_resource_file(&self, resource_file: &str) -> Option<&MapSkyData> {
         self.map_sky_data_table.get(resource_file)
     }
 
+    /// Maps that can be reached from the given map by walking through a warp
+    /// portal.
+    pub fn get_adjacent_maps(&self, resource_file: &str) -> &[String] {
+        self.warp_graph.get(resource_file).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn get_monster_info(&self, job_id: usize) -> Option<&MonsterInfo> {
+        self.monster_table.get(&job_id)
+    }
+
+    /// Monsters whose name fuzzily matches the query, best matches first.
+    pub fn search_monsters(&self, query: &str) -> Vec<&MonsterInfo> {
+        let mut results: Vec<(u32, &MonsterInfo)> = self
+            .monster_table
+            .values()
+            .filter_map(|monster| fuzzy_score(query, &monster.name).map(|score| (score, monster)))
+            .collect();
+
+        results.sort_by(|(first_score, first), (second_score, second)| {
+            second_score
+                .cmp(first_score)
+                .then_with(|| first.name.len().cmp(&second.name.len()))
+                .then_with(|| first.name.cmp(&second.name))
+        });
+
+        results.into_iter().map(|(_, monster)| monster).collect()
+    }
+
+    pub fn get_item_details(&self, item_id: ItemId) -> Option<&ItemDetails> {
+        self.item_database.get(&item_id)
+    }
+
+    /// Identified items whose name fuzzily matches the query and that pass the
+    /// filter, best matches first.
+    pub fn search_items(&self, query: &str, filter: &ItemSearchFilter) -> Vec<ItemId> {
+        let mut results: Vec<(u32, &str, ItemId)> = self
+            .item_table
+            .iter()
+            .filter(|(item_id, _)| filter.matches(self.item_database.get(item_id)))
+            .filter_map(|(item_id, info)| {
+                let name = info.identified_name.as_deref()?;
+                fuzzy_score(query, name).map(|score| (score, name, *item_id))
+            })
+            .collect();
+
+        results.sort_by(|(first_score, first_name, first_id), (second_score, second_name, second_id)| {
+            second_score
+                .cmp(first_score)
+                .then_with(|| first_name.len().cmp(&second_name.len()))
+                .then_with(|| first_name.cmp(second_name))
+                .then_with(|| first_id.0.cmp(&second_id.0))
+        });
+
+        results.into_iter().map(|(_, _, item_id)| item_id).collect()
+    }
+
+    /// Monsters that drop the item, together with the drop chance in
+    /// hundredths of a percent. The most likely drops come first.
+    pub fn get_item_drops(&self, item_id: ItemId) -> Vec<(&MonsterInfo, u16)> {
+        let mut drops: Vec<(&MonsterInfo, u16)> = self
+            .monster_table
+            .values()
+            .flat_map(|monster| {
+                monster
+                    .drops
+                    .iter()
+                    .filter(|drop| drop.item_id == item_id.0)
+                    .map(move |drop| (monster, drop.chance))
+            })
+            .collect();
+
+        drops.sort_by(|(first, first_chance), (second, second_chance)| {
+            second_chance.cmp(first_chance).then_with(|| first.name.cmp(&second.name))
+        });
+
+        drops
+    }
+
+    /// Replaces the item links in a chat message with the names of the linked
+    /// items, see [`item_link`].
+    pub fn resolve_item_links(&self, text: &str) -> (String, Vec<ItemId>) {
+        item::resolve_item_links(text, |item_id| self.get_item_name_from_id(item_id, true).to_owned())
+    }
+
+    pub fn get_skill_trees(&self) -> &[JobSkillTree] {
+        &self.skill_trees
+    }
+
+    pub fn is_indoor_map(&self, resource_file: &str) -> bool {
+        self.indoor_map_table.contains(&resource_file.to_lowercase())
+    }
+
+    fn get_item_icon_path_from_id(&self, item_id: ItemId, is_identified: bool) -> String {
+        let resource_name = self.get_item_resource_from_id(item_id, is_identified);
+        format!("유저인터페이스\\item\\{resource_name}.bmp")
+    }
+
     pub fn load_inventory_item_metadata(
         &self,
         async_loader: &AsyncLoader,
@@ -251,23 +548,67 @@ impl Library {
     ) -> InventoryItem<ResourceMetadata> {
         let is_identified = item.is_identified();
 
-        let resource_name = self.get_item_resource_from_id(item.item_id, is_identified);
-        let full_path = format!("유저인터페이스\\item\\{resource_name}.bmp");
-        let texture = async_loader.request_item_sprite_load(ItemLocation::Inventory, item.item_id, &full_path, ImageType::Color);
+        let icon_path = self.get_item_icon_path_from_id(item.item_id, is_identified);
+        let texture = async_loader.request_item_icon_load(&icon_path);
         let name = self.get_item_name_from_id(item.item_id, is_identified).to_string();
 
-        let metadata = ResourceMetadata { texture, name };
+        // Unidentified items don't reveal their slots.
+        let slot_count = match is_identified {
+            true => self.get_item_slot_count_from_id(item.item_id),
+            false => 0,
+        };
+        let cards: Vec<String> = item
+            .cards()
+            .map(|card_id| self.get_item_name_from_id(card_id, true).to_string())
+            .collect();
+
+        let mut tooltip = match &item.details {
+            InventoryItemDetails::Equippable { refinement_level, .. } if *refinement_level > 0 => format!("+{refinement_level} {name}"),
+            _ => name.clone(),
+        };
+
+        if slot_count > 0 {
+            tooltip.push_str(&format!(" [{slot_count}]"));
+        }
+
+        for card in &cards {
+            tooltip.push_str(&format!("\n{card}"));
+        }
+
+        for option in item.options().iter().filter(|option| option.index != 0) {
+            tooltip.push_str(&format!("\n{}", self.get_random_option_description(option)));
+        }
+
+        let metadata = ResourceMetadata {
+            texture,
+            icon_path,
+            name,
+            slot_count,
+            cards,
+            tooltip,
+        };
 
         InventoryItem { metadata, ..item }
     }
 
     pub fn load_shop_item_metadata(&self, async_loader: &AsyncLoader, item: ShopItem<NoMetadata>) -> ShopItem<ResourceMetadata> {
-        let resource_name = self.get_item_resource_from_id(item.item_id, true);
-        let full_path = format!("유저인터페이스\\item\\{resource_name}.bmp");
-        let texture = async_loader.request_item_sprite_load(ItemLocation::Shop, item.item_id, &full_path, ImageType::Color);
+        let icon_path = self.get_item_icon_path_from_id(item.item_id, true);
+        let texture = async_loader.request_item_icon_load(&icon_path);
         let name = self.get_item_name_from_id(item.item_id, true).to_string();
+        let slot_count = self.get_item_slot_count_from_id(item.item_id);
+        let tooltip = match slot_count {
+            0 => name.clone(),
+            slot_count => format!("{name} [{slot_count}]"),
+        };
 
-        let metadata = ResourceMetadata { texture, name };
+        let metadata = ResourceMetadata {
+            texture,
+            icon_path,
+            name,
+            slot_count,
+            cards: Vec::new(),
+            tooltip,
+        };
 
         ShopItem { metadata, ..item }
     }
@@ -280,3 +621,82 @@ fn fix_encoding(broken: String) -> String {
         Some(char) => char.to_string(),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use mlua::Lua;
+
+    use super::{ItemDetails, JobSkillTree, Library, MonsterInfo};
+
+    #[test]
+    fn load_indoor_map_table() {
+        let table = Library::load_indoor_map_table(b"prt_church.rsw#\r\nGEF_TOWER.rsw#\n\n// comment\n");
+
+        assert_eq!(table.len(), 2);
+        assert!(table.contains("prt_church"));
+        assert!(table.contains("gef_tower"));
+    }
+
+    #[test]
+    fn bundled_monster_database_is_valid() {
+        let data = std::fs::read("archive/data/monsterdb.ron").expect("monster database should exist");
+        let monsters: Vec<MonsterInfo> = ron::de::from_bytes(&data).expect("monster database should be valid");
+
+        let monster_table = Library::load_monster_table(&data);
+
+        assert!(!monsters.is_empty());
+        assert_eq!(monster_table.len(), monsters.len(), "job ids should be unique");
+    }
+
+    #[test]
+    fn bundled_item_database_is_valid() {
+        let data = std::fs::read("archive/data/itemdb.ron").expect("item database should exist");
+        let items: Vec<ItemDetails> = ron::de::from_bytes(&data).expect("item database should be valid");
+
+        let item_database = Library::load_item_database(&data);
+
+        assert!(!items.is_empty());
+        assert_eq!(item_database.len(), items.len(), "item ids should be unique");
+    }
+
+    #[test]
+    fn bundled_skill_trees_are_valid() {
+        let data = std::fs::read("archive/data/skilltree.ron").expect("skill tree database should exist");
+        let skill_trees: Vec<JobSkillTree> = ron::de::from_bytes(&data).expect("skill tree database should be valid");
+
+        assert!(!skill_trees.is_empty());
+
+        for skill_tree in &skill_trees {
+            for skill in &skill_tree.skills {
+                for requirement in &skill.requirements {
+                    let required_skill = skill_tree
+                        .get_skill(requirement.skill_id)
+                        .expect("required skills should be part of the same skill tree");
+
+                    assert!(requirement.level <= required_skill.maximum_level);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn load_warp_graph() {
+        let state = Lua::new();
+        state
+            .load(
+                r#"Navi_Link = {
+                    { "prontera", 10001, 200, 99999, "", "", 156, 22, "prt_fild08", 170, 375 },
+                    { "prontera", 10002, 200, 99999, "", "", 156, 23, "prt_fild08", 171, 375 },
+                    { "prontera", 10003, 201, 114, "Kafra", "", 146, 89, "izlude", 128, 111 },
+                    { "prt_fild08", 10004, 200, 99999, "", "", 170, 378, "prontera", 156, 26 },
+                }"#,
+            )
+            .exec()
+            .unwrap();
+
+        let warp_graph = Library::load_warp_graph(&state).unwrap();
+
+        assert_eq!(warp_graph["prontera"], ["prt_fild08"]);
+        assert_eq!(warp_graph["prt_fild08"], ["prontera"]);
+    }
+}
@@ -0,0 +1,72 @@
+use serde::Deserialize;
+
+/// Element of a monster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum MonsterElement {
+    Neutral,
+    Water,
+    Earth,
+    Fire,
+    Wind,
+    Poison,
+    Holy,
+    Shadow,
+    Ghost,
+    Undead,
+}
+
+/// Race of a monster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum MonsterRace {
+    Formless,
+    Undead,
+    Brute,
+    Plant,
+    Insect,
+    Fish,
+    Demon,
+    DemiHuman,
+    Angel,
+    Dragon,
+}
+
+/// Size of a monster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum MonsterSize {
+    Small,
+    Medium,
+    Large,
+}
+
+/// Item that a monster can drop.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MonsterDrop {
+    pub item_id: u32,
+    /// Chance of the drop in hundredths of a percent.
+    pub chance: u16,
+}
+
+/// Entry of the bundled monster database.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MonsterInfo {
+    /// Job id of the monster, as sent by the server.
+    pub job_id: usize,
+    pub name: String,
+    pub level: u16,
+    pub health: u32,
+    pub base_experience: u32,
+    pub job_experience: u32,
+    pub minimum_attack: u32,
+    pub maximum_attack: u32,
+    pub defense: u16,
+    pub magic_defense: u16,
+    pub element: MonsterElement,
+    pub element_level: u8,
+    pub race: MonsterRace,
+    pub size: MonsterSize,
+    #[serde(default)]
+    pub drops: Vec<MonsterDrop>,
+    /// Resource files of the maps that the monster spawns on.
+    #[serde(default)]
+    pub spawn_maps: Vec<String>,
+}
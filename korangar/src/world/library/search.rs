@@ -0,0 +1,79 @@
+/// Scores how well the query matches the candidate, or `None` if the
+/// characters of the query don't appear in the candidate in order. The
+/// comparison ignores case, and higher scores are better matches.
+///
+/// Consecutive characters and characters at the start of a word score
+/// higher, so `"poring"` prefers `Poring` over `Poison Spore`.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    const MATCH_SCORE: u32 = 1;
+    const CONSECUTIVE_BONUS: u32 = 4;
+    const WORD_START_BONUS: u32 = 3;
+
+    let mut query_characters = query.chars().filter(|character| !character.is_whitespace()).peekable();
+    let mut score = 0;
+    let mut previous_matched = false;
+    let mut previous_character = None;
+
+    for character in candidate.chars() {
+        let Some(query_character) = query_characters.peek() else {
+            break;
+        };
+
+        let is_word_start = previous_character.is_none_or(|previous: char| !previous.is_alphanumeric());
+
+        if character.to_lowercase().eq(query_character.to_lowercase()) {
+            score += MATCH_SCORE;
+
+            if previous_matched {
+                score += CONSECUTIVE_BONUS;
+            }
+
+            if is_word_start {
+                score += WORD_START_BONUS;
+            }
+
+            query_characters.next();
+            previous_matched = true;
+        } else {
+            previous_matched = false;
+        }
+
+        previous_character = Some(character);
+    }
+
+    query_characters.peek().is_none().then_some(score)
+}
+
+#[cfg(test)]
+mod test {
+    use super::fuzzy_score;
+
+    #[test]
+    fn subsequence_matches() {
+        assert!(fuzzy_score("prg", "Poring").is_some());
+        assert!(fuzzy_score("PORING", "poring").is_some());
+        assert!(fuzzy_score("", "Poring").is_some());
+    }
+
+    #[test]
+    fn missing_characters_do_not_match() {
+        assert!(fuzzy_score("porx", "Poring").is_none());
+        assert!(fuzzy_score("gnirop", "Poring").is_none());
+    }
+
+    #[test]
+    fn consecutive_matches_score_higher() {
+        let consecutive = fuzzy_score("por", "Poring").unwrap();
+        let scattered = fuzzy_score("por", "Poison Spore").unwrap();
+
+        assert!(consecutive > scattered);
+    }
+
+    #[test]
+    fn word_starts_score_higher() {
+        let word_start = fuzzy_score("wolf", "Baby Wolf").unwrap();
+        let inside_word = fuzzy_score("wolf", "Werewolf").unwrap();
+
+        assert!(word_start > inside_word);
+    }
+}
@@ -0,0 +1,54 @@
+use serde::Deserialize;
+
+/// Level of another skill that is needed before a skill can be learned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct SkillRequirement {
+    pub skill_id: u16,
+    pub level: u8,
+}
+
+/// Damage of an offensive skill in percent of the attack of the character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct SkillDamage {
+    pub base_percent: u16,
+    pub percent_per_level: u16,
+    /// Whether the damage is based on the magic attack instead of the attack.
+    #[serde(default)]
+    pub magical: bool,
+}
+
+impl SkillDamage {
+    pub fn percent(&self, level: u8) -> u32 {
+        self.base_percent as u32 + self.percent_per_level as u32 * level as u32
+    }
+}
+
+/// Skill that can be learned by a job.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SkillTreeEntry {
+    pub skill_id: u16,
+    pub name: String,
+    pub maximum_level: u8,
+    #[serde(default)]
+    pub requirements: Vec<SkillRequirement>,
+    /// Damage of the skill. Skills that don't deal damage have no entry.
+    #[serde(default)]
+    pub damage: Option<SkillDamage>,
+}
+
+/// Skill tree of a single job from the bundled skill tree database.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JobSkillTree {
+    pub job_id: usize,
+    pub name: String,
+    /// Number of skill points that a character of this job gets until the
+    /// maximum job level.
+    pub skill_points: u16,
+    pub skills: Vec<SkillTreeEntry>,
+}
+
+impl JobSkillTree {
+    pub fn get_skill(&self, skill_id: u16) -> Option<&SkillTreeEntry> {
+        self.skills.iter().find(|skill| skill.skill_id == skill_id)
+    }
+}
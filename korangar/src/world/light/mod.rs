@@ -258,6 +258,7 @@ impl PointLightSet<'_> {
                     &object_set,
                     animation_timer_ms,
                     point_shadow_camera,
+                    None,
                 );
 
                 #[cfg_attr(feature = "debug", korangar_debug::debug_condition(render_options.show_map))]
@@ -0,0 +1,753 @@
+//! Editing of the objects, the light, sound, and effect sources, and the
+//! tile walkability of a loaded map. All changes only affect the loaded map,
+//! but they can be exported as new RSW and GAT files.
+
+use cgmath::{Deg, EuclideanSpace, InnerSpace, Matrix4, Point3, Rad, Vector2, Vector3};
+use korangar_collision::{AABB, KDTree, Sphere};
+use korangar_container::SimpleKey;
+use ragnarok_formats::color::ColorRGB;
+use ragnarok_formats::map::{EffectSource, GatData, LightSource, MapData, MapResources, SoundSource, Tile, TileFlags};
+use ragnarok_formats::signature::Signature;
+use ragnarok_formats::transform::Transform;
+use ragnarok_packets::TilePosition;
+use wgpu::{Device, Queue};
+
+use super::{DEFAULT_SOUND_CYCLE, LightSourceKey, Map, MarkerIdentifier, ObjectKey};
+use crate::graphics::{BindlessSupport, Color, DebugAabbInstruction, DebugCircleInstruction, ScreenSize};
+use crate::loaders::{OBJECT_DEPTH_OFFSET, TileMeshes, create_tile_meshes};
+use crate::world::{Camera, EffectSourceExt, LightSourceExt, SoundSourceExt, navigation_grid};
+
+/// Range of newly added light and sound sources.
+const DEFAULT_RANGE: f32 = 50.0;
+/// Length of the axes of the gizmo in world units.
+const GIZMO_LENGTH: f32 = 10.0;
+/// Half of the thickness of the axes of the gizmo.
+const GIZMO_THICKNESS: f32 = 0.1;
+/// Half of the size of the handles of the scale gizmo.
+const GIZMO_HANDLE_SIZE: f32 = 0.5;
+/// Rotation in degrees when dragging the rotation gizmo by one pixel.
+const GIZMO_ROTATION_PER_PIXEL: f32 = 0.5;
+/// Scale change when dragging the scale gizmo by one world unit.
+const GIZMO_SCALE_PER_UNIT: f32 = 0.05;
+/// Smallest absolute scale of an object, so it never collapses.
+const MINIMUM_SCALE: f32 = 0.01;
+
+/// How the gizmo transforms the selected object.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GizmoMode {
+    #[default]
+    Translate,
+    Rotate,
+    Scale,
+}
+
+impl GizmoMode {
+    /// The amount a single step changes the transform by.
+    pub fn step(self) -> f32 {
+        match self {
+            GizmoMode::Translate => 1.0,
+            GizmoMode::Rotate => 15.0,
+            GizmoMode::Scale => 0.1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GizmoAxis {
+    #[default]
+    X,
+    Y,
+    Z,
+}
+
+impl GizmoAxis {
+    pub const ALL: [GizmoAxis; 3] = [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z];
+
+    pub fn vector(self) -> Vector3<f32> {
+        match self {
+            GizmoAxis::X => Vector3::unit_x(),
+            GizmoAxis::Y => Vector3::unit_y(),
+            GizmoAxis::Z => Vector3::unit_z(),
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            GizmoAxis::X => Color::rgb_u8(230, 60, 60),
+            GizmoAxis::Y => Color::rgb_u8(60, 230, 60),
+            GizmoAxis::Z => Color::rgb_u8(60, 60, 230),
+        }
+    }
+}
+
+/// Property of a light, sound, or effect source that can be adjusted in the
+/// map editor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MarkerProperty {
+    PositionX,
+    PositionY,
+    PositionZ,
+    Range,
+    Volume,
+    Red,
+    Green,
+    Blue,
+    EmitSpeed,
+}
+
+impl MarkerProperty {
+    /// The amount a single step changes the property by.
+    pub fn step(self) -> f32 {
+        match self {
+            MarkerProperty::PositionX | MarkerProperty::PositionY | MarkerProperty::PositionZ => 1.0,
+            MarkerProperty::Range => 5.0,
+            MarkerProperty::Volume | MarkerProperty::Red | MarkerProperty::Green | MarkerProperty::Blue => 0.05,
+            MarkerProperty::EmitSpeed => 0.1,
+        }
+    }
+}
+
+impl MarkerIdentifier {
+    /// Only objects and light, sound, and effect sources can be edited.
+    pub fn is_editable(&self) -> bool {
+        matches!(
+            self,
+            MarkerIdentifier::Object(..)
+                | MarkerIdentifier::LightSource(..)
+                | MarkerIdentifier::SoundSource(..)
+                | MarkerIdentifier::EffectSource(..)
+        )
+    }
+}
+
+impl Map {
+    pub fn marker_position(&self, marker_identifier: MarkerIdentifier) -> Option<Point3<f32>> {
+        match marker_identifier {
+            MarkerIdentifier::Object(key) => self.object_transform(key).map(|transform| transform.position),
+            MarkerIdentifier::LightSource(key) => self
+                .light_sources
+                .get(LightSourceKey::new(key))
+                .map(|light_source| light_source.position),
+            MarkerIdentifier::SoundSource(index) => self.sound_sources.get(index as usize).map(|sound_source| sound_source.position),
+            MarkerIdentifier::EffectSource(index) => self.effect_sources.get(index as usize).map(|effect_source| effect_source.position),
+            _ => None,
+        }
+    }
+
+    /// Move an object or a light, sound, or effect source to a new position.
+    /// Returns `false` if the marker can't be moved.
+    pub fn move_marker(&mut self, marker_identifier: MarkerIdentifier, position: Point3<f32>) -> bool {
+        let Some(current_position) = self.marker_position(marker_identifier) else {
+            return false;
+        };
+
+        let offset = position - current_position;
+
+        match marker_identifier {
+            MarkerIdentifier::Object(key) => {
+                let Some(mut transform) = self.object_transform(key) else {
+                    return false;
+                };
+
+                transform.position = position;
+                self.set_object_transform(key, transform);
+            }
+            MarkerIdentifier::LightSource(key) => {
+                if let Some(light_source) = self.light_sources.get_mut(LightSourceKey::new(key)) {
+                    light_source.offset(offset);
+                }
+
+                self.rebuild_light_source_kdtree();
+            }
+            MarkerIdentifier::SoundSource(index) => self.sound_sources[index as usize].offset(offset),
+            MarkerIdentifier::EffectSource(index) => self.effect_sources[index as usize].offset(offset),
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Change a property of a light, sound, or effect source by a number of
+    /// steps. Returns `false` if the marker doesn't have the property.
+    pub fn adjust_marker_property(&mut self, marker_identifier: MarkerIdentifier, property: MarkerProperty, steps: i32) -> bool {
+        let amount = property.step() * steps as f32;
+
+        let position_offset = match property {
+            MarkerProperty::PositionX => Some(Vector3::new(amount, 0.0, 0.0)),
+            MarkerProperty::PositionY => Some(Vector3::new(0.0, amount, 0.0)),
+            MarkerProperty::PositionZ => Some(Vector3::new(0.0, 0.0, amount)),
+            _ => None,
+        };
+
+        if let Some(offset) = position_offset {
+            return match self.marker_position(marker_identifier) {
+                Some(position) => self.move_marker(marker_identifier, position + offset),
+                None => false,
+            };
+        }
+
+        match marker_identifier {
+            MarkerIdentifier::LightSource(key) => {
+                let Some(light_source) = self.light_sources.get_mut(LightSourceKey::new(key)) else {
+                    return false;
+                };
+
+                let value = match property {
+                    MarkerProperty::Range => &mut light_source.range,
+                    MarkerProperty::Red => &mut light_source.color.red,
+                    MarkerProperty::Green => &mut light_source.color.green,
+                    MarkerProperty::Blue => &mut light_source.color.blue,
+                    _ => return false,
+                };
+
+                *value = clamp_property(property, *value + amount);
+                self.rebuild_light_source_kdtree();
+            }
+            MarkerIdentifier::SoundSource(index) => {
+                let Some(sound_source) = self.sound_sources.get_mut(index as usize) else {
+                    return false;
+                };
+
+                let value = match property {
+                    MarkerProperty::Range => &mut sound_source.range,
+                    MarkerProperty::Volume => &mut sound_source.volume,
+                    _ => return false,
+                };
+
+                *value = clamp_property(property, *value + amount);
+            }
+            MarkerIdentifier::EffectSource(index) => {
+                let Some(effect_source) = self.effect_sources.get_mut(index as usize) else {
+                    return false;
+                };
+
+                let value = match property {
+                    MarkerProperty::EmitSpeed => &mut effect_source.emit_speed,
+                    _ => return false,
+                };
+
+                *value = clamp_property(property, *value + amount);
+            }
+            _ => return false,
+        }
+
+        true
+    }
+
+    pub fn add_light_source(&mut self, position: Point3<f32>) -> Option<MarkerIdentifier> {
+        let light_source = LightSource {
+            name: format!("light{}", self.light_sources.count()),
+            position,
+            color: ColorRGB {
+                red: 1.0,
+                green: 1.0,
+                blue: 1.0,
+            },
+            range: DEFAULT_RANGE,
+        };
+
+        let key = self.light_sources.insert(light_source)?;
+        self.rebuild_light_source_kdtree();
+
+        Some(MarkerIdentifier::LightSource(key.key()))
+    }
+
+    /// Add a new sound source. Since there is no default sound, the sound file
+    /// of the first sound source of the map is used.
+    pub fn add_sound_source(&mut self, position: Point3<f32>) -> MarkerIdentifier {
+        let sound_file = self
+            .sound_sources
+            .first()
+            .map(|sound_source| sound_source.sound_file.clone())
+            .unwrap_or_default();
+
+        self.sound_sources.push(SoundSource {
+            name: format!("sound{}", self.sound_sources.len()),
+            sound_file,
+            position,
+            volume: 1.0,
+            width: 0,
+            height: 0,
+            range: DEFAULT_RANGE,
+            cycle: Some(DEFAULT_SOUND_CYCLE),
+        });
+
+        MarkerIdentifier::SoundSource(self.sound_sources.len() as u32 - 1)
+    }
+
+    pub fn add_effect_source(&mut self, position: Point3<f32>) -> MarkerIdentifier {
+        self.effect_sources.push(EffectSource {
+            name: format!("effect{}", self.effect_sources.len()),
+            position,
+            effect_type: 0,
+            emit_speed: 1.0,
+            _param0: 0.0,
+            _param1: 0.0,
+            _param2: 0.0,
+            _param3: 0.0,
+        });
+
+        MarkerIdentifier::EffectSource(self.effect_sources.len() as u32 - 1)
+    }
+
+    /// Add a copy of a light, sound, or effect source at a new position.
+    pub fn duplicate_marker(&mut self, marker_identifier: MarkerIdentifier, position: Point3<f32>) -> Option<MarkerIdentifier> {
+        match marker_identifier {
+            MarkerIdentifier::LightSource(key) => {
+                let mut light_source = self.light_sources.get(LightSourceKey::new(key))?.clone();
+                light_source.position = position;
+
+                let key = self.light_sources.insert(light_source)?;
+                self.rebuild_light_source_kdtree();
+
+                Some(MarkerIdentifier::LightSource(key.key()))
+            }
+            MarkerIdentifier::SoundSource(index) => {
+                let mut sound_source = self.sound_sources.get(index as usize)?.clone();
+                sound_source.position = position;
+                self.sound_sources.push(sound_source);
+
+                Some(MarkerIdentifier::SoundSource(self.sound_sources.len() as u32 - 1))
+            }
+            MarkerIdentifier::EffectSource(index) => {
+                let mut effect_source = self.effect_sources.get(index as usize)?.clone();
+                effect_source.position = position;
+                self.effect_sources.push(effect_source);
+
+                Some(MarkerIdentifier::EffectSource(self.effect_sources.len() as u32 - 1))
+            }
+            _ => None,
+        }
+    }
+
+    /// Remove a light, sound, or effect source. Since sound and effect sources
+    /// are identified by their index, this invalidates the identifiers of all
+    /// following sources of the same kind.
+    pub fn remove_marker(&mut self, marker_identifier: MarkerIdentifier) -> bool {
+        match marker_identifier {
+            MarkerIdentifier::LightSource(key) => {
+                let removed = self.light_sources.remove(LightSourceKey::new(key)).is_some();
+                self.rebuild_light_source_kdtree();
+                removed
+            }
+            MarkerIdentifier::SoundSource(index) if (index as usize) < self.sound_sources.len() => {
+                self.sound_sources.remove(index as usize);
+                true
+            }
+            MarkerIdentifier::EffectSource(index) if (index as usize) < self.effect_sources.len() => {
+                self.effect_sources.remove(index as usize);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Short description of a marker for the map editor window.
+    pub fn marker_description(&self, marker_identifier: MarkerIdentifier) -> Option<String> {
+        let position = self.marker_position(marker_identifier)?;
+
+        let details = match marker_identifier {
+            MarkerIdentifier::Object(key) => {
+                let object = self.objects.get(ObjectKey::new(key))?;
+                let rotation = object.transform.rotation.map(|radians| Deg::from(radians).0);
+                let scale = object.transform.scale;
+
+                format!(
+                    "Object \"{}\"\nmodel: {}\nrotation: {:.1}, {:.1}, {:.1}\nscale: {:.2}, {:.2}, {:.2}",
+                    object.name.as_deref().unwrap_or_default(),
+                    object.model_name,
+                    rotation.x,
+                    rotation.y,
+                    rotation.z,
+                    scale.x,
+                    scale.y,
+                    scale.z
+                )
+            }
+            MarkerIdentifier::LightSource(key) => {
+                let light_source = self.light_sources.get(LightSourceKey::new(key))?;
+                let color = light_source.color;
+
+                format!(
+                    "Light source \"{}\"\nrange: {:.1}\ncolor: {:.2}, {:.2}, {:.2}",
+                    light_source.name, light_source.range, color.red, color.green, color.blue
+                )
+            }
+            MarkerIdentifier::SoundSource(index) => {
+                let sound_source = self.sound_sources.get(index as usize)?;
+
+                format!(
+                    "Sound source \"{}\"\nfile: {}\nrange: {:.1}\nvolume: {:.2}",
+                    sound_source.name, sound_source.sound_file, sound_source.range, sound_source.volume
+                )
+            }
+            MarkerIdentifier::EffectSource(index) => {
+                let effect_source = self.effect_sources.get(index as usize)?;
+
+                format!(
+                    "Effect source \"{}\"\ntype: {}\nemit speed: {:.2}",
+                    effect_source.name, effect_source.effect_type, effect_source.emit_speed
+                )
+            }
+            _ => return None,
+        };
+
+        Some(format!(
+            "{details}\nposition: {:.1}, {:.1}, {:.1}",
+            position.x, position.y, position.z
+        ))
+    }
+
+    pub fn object_transform(&self, key: u32) -> Option<Transform> {
+        self.objects.get(ObjectKey::new(key)).map(|object| object.transform)
+    }
+
+    /// Replace the transform of an object. This is also used to undo and redo
+    /// changes.
+    pub fn set_object_transform(&mut self, key: u32, transform: Transform) -> bool {
+        let object_key = ObjectKey::new(key);
+
+        let Some(object) = self.objects.get_mut(object_key) else {
+            return false;
+        };
+
+        object.transform = transform;
+        self.modified_objects.insert(object_key);
+        self.rebuild_object_kdtree();
+
+        true
+    }
+
+    /// Transform an object along a single axis. Returns the previous and the
+    /// new transform of the object.
+    pub fn transform_object(&mut self, key: u32, mode: GizmoMode, axis: GizmoAxis, amount: f32) -> Option<(Transform, Transform)> {
+        let previous = self.object_transform(key)?;
+        let mut transform = previous;
+
+        match mode {
+            GizmoMode::Translate => transform.position += axis.vector() * amount,
+            GizmoMode::Rotate => {
+                let rotation: Rad<f32> = Deg(amount).into();
+
+                match axis {
+                    GizmoAxis::X => transform.rotation.x += rotation,
+                    GizmoAxis::Y => transform.rotation.y += rotation,
+                    GizmoAxis::Z => transform.rotation.z += rotation,
+                }
+            }
+            GizmoMode::Scale => {
+                // Changing the sign of the scale would flip the faces of the model, so we keep
+                // it.
+                let scale = match axis {
+                    GizmoAxis::X => &mut transform.scale.x,
+                    GizmoAxis::Y => &mut transform.scale.y,
+                    GizmoAxis::Z => &mut transform.scale.z,
+                };
+
+                *scale = (scale.abs() + amount).max(MINIMUM_SCALE) * scale.signum();
+            }
+        }
+
+        self.set_object_transform(key, transform);
+
+        Some((previous, transform))
+    }
+
+    /// Render the axes of the gizmo of the selected object.
+    pub fn render_gizmo(
+        &self,
+        aabb_instructions: &mut Vec<DebugAabbInstruction>,
+        circle_instructions: &mut Vec<DebugCircleInstruction>,
+        camera: &dyn Camera,
+        key: u32,
+        mode: GizmoMode,
+        active_axis: GizmoAxis,
+    ) {
+        let Some(transform) = self.object_transform(key) else {
+            return;
+        };
+
+        let position = transform.position;
+
+        for axis in GizmoAxis::ALL {
+            let color = match axis == active_axis {
+                true => Color::rgb_u8(255, 230, 0),
+                false => axis.color(),
+            };
+
+            let direction = axis.vector();
+            let extent = direction * (GIZMO_LENGTH / 2.0) + (Vector3::new(1.0, 1.0, 1.0) - direction) * GIZMO_THICKNESS;
+
+            aabb_instructions.push(DebugAabbInstruction {
+                world: Matrix4::from_translation(position.to_vec() + direction * (GIZMO_LENGTH / 2.0))
+                    * Matrix4::from_nonuniform_scale(extent.x, extent.y, extent.z),
+                color,
+            });
+
+            if mode == GizmoMode::Scale {
+                aabb_instructions.push(DebugAabbInstruction {
+                    world: Matrix4::from_translation(position.to_vec() + direction * GIZMO_LENGTH) * Matrix4::from_scale(GIZMO_HANDLE_SIZE),
+                    color,
+                });
+            }
+        }
+
+        if mode == GizmoMode::Rotate
+            && let Some((screen_position, screen_size)) = Self::calculate_circle_screen_position_size(camera, position, GIZMO_LENGTH)
+        {
+            circle_instructions.push(DebugCircleInstruction {
+                position,
+                color: Color::rgb_u8(255, 230, 0),
+                screen_position,
+                screen_size,
+            });
+        }
+    }
+
+    /// Create the map data of the original RSW file with the current objects
+    /// and light, sound, and effect sources.
+    pub fn export_map_data(&self) -> MapData {
+        let mut map_data = self.map_data.clone();
+
+        // Undo the offset that was applied when loading the map.
+        let offset = -self.map_offset;
+
+        // Objects keep their original transform unless they were modified, so
+        // unmodified objects are exported without any rounding errors.
+        for object_key in &self.modified_objects {
+            if let (Some(object), Some(object_data)) = (
+                self.objects.get(*object_key),
+                map_data.resources.objects.get_mut(object_key.key() as usize),
+            ) {
+                let depth_offset = Vector3::new(0.0, OBJECT_DEPTH_OFFSET, 0.0) * object_key.key() as f32;
+
+                object_data.transform = object.transform;
+                object_data.transform.position += offset - depth_offset;
+            }
+        }
+
+        let light_sources = self
+            .light_sources
+            .iter()
+            .map(|(_, light_source)| {
+                let mut light_source = light_source.clone();
+                light_source.offset(offset);
+                light_source
+            })
+            .collect();
+
+        let sound_sources = self
+            .sound_sources
+            .iter()
+            .map(|sound_source| {
+                let mut sound_source = sound_source.clone();
+                sound_source.offset(offset);
+                sound_source
+            })
+            .collect();
+
+        let effect_sources = self
+            .effect_sources
+            .iter()
+            .map(|effect_source| {
+                let mut effect_source = effect_source.clone();
+                effect_source.offset(offset);
+                effect_source
+            })
+            .collect();
+
+        let objects = std::mem::take(&mut map_data.resources.objects);
+        map_data.resources = MapResources::new(objects, light_sources, sound_sources, effect_sources);
+
+        map_data
+    }
+
+    /// Make a tile walkable or block it. Returns `true` if the tile changed,
+    /// in which case the tile meshes need to be updated.
+    pub fn set_tile_walkable(&mut self, position: TilePosition, walkable: bool) -> bool {
+        if position.x >= self.width || position.y >= self.height {
+            return false;
+        }
+
+        let tile = &mut self.tiles[position.x as usize + position.y as usize * self.width as usize];
+        let flags = walkability_flags(tile.flags, walkable);
+
+        if flags == tile.flags {
+            return false;
+        }
+
+        tile.flags = flags;
+        true
+    }
+
+    pub fn includes_blocked_tiles(&self) -> bool {
+        self.includes_blocked_tiles
+    }
+
+    /// Recreate the tile overlay and the tile picker meshes from the current
+    /// tiles. Blocked tiles can only be picked while they are included.
+    pub fn update_tile_meshes(&mut self, device: &Device, queue: &Queue, bindless_support: BindlessSupport, include_blocked_tiles: bool) {
+        let TileMeshes {
+            vertex_buffer,
+            index_buffer,
+            submeshes,
+            picker_vertex_buffer,
+            picker_index_buffer,
+        } = create_tile_meshes(
+            device,
+            queue,
+            bindless_support,
+            &self.resource_file,
+            &self.tiles,
+            self.width as usize,
+            include_blocked_tiles,
+        );
+
+        self.tile_vertex_buffer = vertex_buffer;
+        self.tile_index_buffer = index_buffer;
+        self.tile_submeshes = submeshes;
+        self.tile_picker_vertex_buffer = picker_vertex_buffer;
+        self.tile_picker_index_buffer = picker_index_buffer;
+        self.includes_blocked_tiles = include_blocked_tiles;
+    }
+
+    /// Create the GAT data of the map with the current tiles.
+    pub fn export_gat_data(&self) -> GatData {
+        // The tile heights were flipped when loading the map.
+        let tiles = self
+            .tiles
+            .iter()
+            .map(|tile| Tile {
+                southwest_corner_height: -tile.southwest_corner_height,
+                southeast_corner_height: -tile.southeast_corner_height,
+                northwest_corner_height: -tile.northwest_corner_height,
+                northeast_corner_height: -tile.northeast_corner_height,
+                flags: tile.flags,
+                unused: tile.unused,
+            })
+            .collect();
+
+        GatData {
+            signature: Signature,
+            version: self.gat_version,
+            map_width: self.width as i32,
+            map_height: self.height as i32,
+            tiles,
+        }
+    }
+
+    /// Create the navigation grid of the current tiles, see
+    /// [`navigation_grid`].
+    pub fn export_navigation_grid(&self) -> String {
+        navigation_grid(self, &self.resource_file, self.width, self.height)
+    }
+
+    fn rebuild_object_kdtree(&mut self) {
+        let object_bounding_boxes: Vec<(ObjectKey, AABB)> = self
+            .objects
+            .iter()
+            .map(|(key, object)| (key, object.calculate_object_aabb()))
+            .collect();
+
+        self.object_kdtree = KDTree::from_objects(&object_bounding_boxes);
+    }
+
+    fn rebuild_light_source_kdtree(&mut self) {
+        let light_source_spheres: Vec<(LightSourceKey, Sphere)> = self
+            .light_sources
+            .iter()
+            .map(|(key, light_source)| (key, Sphere::new(light_source.position, light_source.range)))
+            .collect();
+
+        self.light_source_kdtree = KDTree::from_objects(&light_source_spheres);
+    }
+}
+
+fn clamp_property(property: MarkerProperty, value: f32) -> f32 {
+    match property {
+        MarkerProperty::Volume | MarkerProperty::Red | MarkerProperty::Green | MarkerProperty::Blue => value.clamp(0.0, 1.0),
+        _ => value.max(0.0),
+    }
+}
+
+/// The flags of a tile after making it walkable or blocking it. Water stays
+/// water, so only combinations with a valid GAT encoding are produced.
+fn walkability_flags(flags: TileFlags, walkable: bool) -> TileFlags {
+    if flags.contains(TileFlags::WALKABLE) == walkable {
+        return flags;
+    }
+
+    match (flags.contains(TileFlags::WATER), walkable) {
+        (true, true) => TileFlags::WATER | TileFlags::WALKABLE,
+        (true, false) => TileFlags::WATER,
+        (false, true) => TileFlags::WALKABLE,
+        (false, false) => TileFlags::empty(),
+    }
+}
+
+/// Convert the movement of the mouse into the amount the gizmo transforms the
+/// selected object. Translation and scale follow the mouse along the axis on
+/// screen, while the rotation follows the horizontal movement of the mouse.
+pub fn gizmo_drag_amount(
+    camera: &dyn Camera,
+    window_size: ScreenSize,
+    position: Point3<f32>,
+    mode: GizmoMode,
+    axis: GizmoAxis,
+    mouse_delta: ScreenSize,
+) -> f32 {
+    if mode == GizmoMode::Rotate {
+        return mouse_delta.width * GIZMO_ROTATION_PER_PIXEL;
+    }
+
+    let view_projection_matrix = camera.view_projection_matrix();
+    let project = |point: Point3<f32>| {
+        let screen_position = camera.clip_to_screen_space(view_projection_matrix * point.to_homogeneous());
+        Vector2::new(screen_position.x * window_size.width, screen_position.y * window_size.height)
+    };
+
+    // The length of one world unit along the axis on screen.
+    let screen_axis = project(position + axis.vector()) - project(position);
+    let screen_axis_length = screen_axis.magnitude2();
+
+    if screen_axis_length < f32::EPSILON {
+        return 0.0;
+    }
+
+    let distance = Vector2::new(mouse_delta.width, mouse_delta.height).dot(screen_axis) / screen_axis_length;
+
+    match mode {
+        GizmoMode::Scale => distance * GIZMO_SCALE_PER_UNIT,
+        _ => distance,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ragnarok_bytes::{ByteWriter, ToBytes};
+    use ragnarok_formats::map::TileFlags;
+
+    use super::walkability_flags;
+
+    #[test]
+    fn painted_tiles_can_be_encoded() {
+        let encodable_flags = [
+            TileFlags::WALKABLE,
+            TileFlags::empty(),
+            TileFlags::WATER,
+            TileFlags::WATER | TileFlags::WALKABLE,
+            TileFlags::WATER | TileFlags::SNIPABLE,
+            TileFlags::CLIFF | TileFlags::SNIPABLE,
+            TileFlags::CLIFF,
+        ];
+
+        for flags in encodable_flags {
+            for walkable in [true, false] {
+                let painted_flags = walkability_flags(flags, walkable);
+
+                assert_eq!(painted_flags.contains(TileFlags::WALKABLE), walkable);
+                assert!(painted_flags.to_bytes(&mut ByteWriter::new()).is_ok());
+            }
+        }
+    }
+}
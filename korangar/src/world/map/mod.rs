@@ -1,10 +1,16 @@
+#[cfg(feature = "debug")]
+mod editor;
 mod lighting;
+mod ray;
+mod sky;
+mod wind;
 
+use std::collections::HashMap;
 #[cfg(feature = "debug")]
 use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 
-use cgmath::{Deg, Matrix4, Point3, SquareMatrix, Vector3};
+use cgmath::{Deg, InnerSpace, Matrix4, Point3, SquareMatrix, Vector2, Vector3, Vector4, Zero};
 use korangar_audio::AudioEngine;
 use korangar_collision::{AABB, Frustum, KDTree, Sphere};
 use korangar_container::{SimpleKey, SimpleSlab, create_simple_key};
@@ -19,25 +25,49 @@ use ragnarok_formats::map::MapData;
 use ragnarok_formats::map::{LightSource, SoundSource, Tile, TileFlags};
 #[cfg(feature = "debug")]
 use ragnarok_formats::transform::Transform;
+#[cfg(feature = "debug")]
+use ragnarok_formats::version::{MajorFirst, Version};
 use ragnarok_packets::TilePosition;
 use rust_state::RustState;
 use wgpu::Queue;
 
+#[cfg(feature = "debug")]
+pub use self::editor::{GizmoAxis, GizmoMode, MarkerProperty, gizmo_drag_amount};
 pub use self::lighting::Lighting;
-use super::{Camera, Entity, Object, PointLightId, PointLightManager, ResourceSet, ResourceSetBuffer, SubMesh, Video};
+pub use self::ray::GroundIntersection;
+pub use self::sky::Sky;
+use super::{Camera, Decal, DecalHolder, Entity, Object, PointLightId, PointLightManager, ResourceSet, ResourceSetBuffer, SubMesh, Video};
 #[cfg(feature = "debug")]
 use super::{LightSourceExt, Model, PointLightSet};
 #[cfg(feature = "debug")]
 use crate::graphics::{
     DebugAabbInstruction, DebugCircleInstruction, DebugRectangleInstruction, ModelBatch, RenderOptions, ScreenPosition, ScreenSize,
 };
-use crate::graphics::{EntityInstruction, IndicatorInstruction, ModelInstruction, Texture, TextureSet, WaterInstruction, WaterVertex};
+use crate::graphics::{
+    DecalInstruction, EntityInstruction, IndicatorInstruction, ModelInstruction, ShadowBlobInstruction, SkyInstruction, SpriteDepthBias,
+    Texture, TextureSet, WaterInstruction, WaterVertex,
+};
 use crate::loaders::GAT_TILE_SIZE;
 #[cfg(feature = "debug")]
 use crate::renderer::MarkerRenderer;
 use crate::world::pathing::Traversable;
 use crate::{Buffer, Color, GameFileLoader, ModelVertex, TileVertex};
 
+/// Opacity of objects that hide the player.
+const OCCLUDING_OBJECT_OPACITY: f32 = 0.3;
+/// Time in seconds it takes to fade an object in or out completely.
+const OBJECT_FADE_DURATION: f32 = 0.25;
+/// Height above the ground of the point on the player that should stay
+/// visible.
+const OCCLUSION_TARGET_HEIGHT: f32 = GAT_TILE_SIZE * 2.0;
+/// Height above the camera focus at which indoor maps are sliced. Objects
+/// that start above it, like ceilings and upper floors, are hidden.
+const VERTICAL_CULLING_HEIGHT: f32 = GAT_TILE_SIZE * 5.0;
+
+/// Cycle of sound sources in seconds. Sound sources of older maps don't
+/// specify a cycle.
+const DEFAULT_SOUND_CYCLE: f32 = 4.0;
+
 create_simple_key!(ObjectKey, "Key to an object inside the map");
 create_simple_key!(LightSourceKey, "Key to an light source inside the map");
 
@@ -59,6 +89,7 @@ impl MarkerIdentifier {
 }
 
 pub struct WaterPlane {
+    water_level: f32,
     water_opacity: f32,
     wave_height: f32,
     wave_speed: Deg<f32>,
@@ -72,6 +103,7 @@ pub struct WaterPlane {
 
 impl WaterPlane {
     pub fn new(
+        water_level: f32,
         water_opacity: f32,
         wave_height: f32,
         wave_speed: Deg<f32>,
@@ -83,6 +115,7 @@ impl WaterPlane {
         index_buffer: Arc<Buffer<u32>>,
     ) -> Self {
         Self {
+            water_level,
             water_opacity,
             wave_height,
             wave_speed,
@@ -98,11 +131,17 @@ impl WaterPlane {
 
 #[derive(RustState)]
 pub struct Map {
+    resource_file: String,
     width: u16,
     height: u16,
     level_bound: AABB,
     lighting: Lighting,
+    sky: Sky,
+    light_map_texture: Option<Arc<Texture>>,
     water_plane: Option<WaterPlane>,
+    /// Set for maps that hide geometry above the player, like the official
+    /// client does for indoor maps.
+    vertical_culling: bool,
     tiles: Vec<Tile>,
     sub_meshes: Vec<SubMesh>,
     vertex_buffer: Arc<Buffer<ModelVertex>>,
@@ -125,18 +164,37 @@ pub struct Map {
     light_source_kdtree: KDTree<LightSourceKey, Sphere>,
     background_music_track_name: Option<String>,
     videos: Mutex<Vec<Video>>,
+    /// Opacity of objects that are faded out because they hide the player.
+    object_opacities: Mutex<HashMap<ObjectKey, f32>>,
     #[cfg(feature = "debug")]
     map_data: MapData,
+    /// Offset between the resource positions inside the map file and the
+    /// world.
+    #[cfg(feature = "debug")]
+    map_offset: Vector3<f32>,
+    /// Objects that were transformed in the map editor.
+    #[cfg(feature = "debug")]
+    modified_objects: HashSet<ObjectKey>,
+    #[cfg(feature = "debug")]
+    gat_version: Version<MajorFirst>,
+    /// Set while the tile meshes include blocked tiles, so they can be
+    /// painted in the map editor.
+    #[cfg(feature = "debug")]
+    includes_blocked_tiles: bool,
 }
 
 impl Map {
     #[cfg(not(feature = "debug"))]
     pub fn new(
+        resource_file: String,
         width: u16,
         height: u16,
         level_bound: AABB,
         lighting: Lighting,
+        sky: Sky,
+        light_map_texture: Option<Arc<Texture>>,
         water_plane: Option<WaterPlane>,
+        vertical_culling: bool,
         tiles: Vec<Tile>,
         sub_meshes: Vec<SubMesh>,
         vertex_buffer: Arc<Buffer<ModelVertex>>,
@@ -153,11 +211,15 @@ impl Map {
         videos: Mutex<Vec<Video>>,
     ) -> Self {
         Self {
+            resource_file,
             width,
             height,
             level_bound,
             lighting,
+            sky,
+            light_map_texture,
             water_plane,
+            vertical_culling,
             tiles,
             sub_meshes,
             vertex_buffer,
@@ -172,16 +234,21 @@ impl Map {
             light_source_kdtree,
             background_music_track_name,
             videos,
+            object_opacities: Mutex::default(),
         }
     }
 
     #[cfg(feature = "debug")]
     pub fn new(
+        resource_file: String,
         width: u16,
         height: u16,
         level_bound: AABB,
         lighting: Lighting,
+        sky: Sky,
+        light_map_texture: Option<Arc<Texture>>,
         water_plane: Option<WaterPlane>,
+        vertical_culling: bool,
         tiles: Vec<Tile>,
         sub_meshes: Vec<SubMesh>,
         vertex_buffer: Arc<Buffer<ModelVertex>>,
@@ -201,13 +268,19 @@ impl Map {
         background_music_track_name: Option<String>,
         videos: Mutex<Vec<Video>>,
         map_data: MapData,
+        map_offset: Vector3<f32>,
+        gat_version: Version<MajorFirst>,
     ) -> Self {
         Self {
+            resource_file,
             width,
             height,
             level_bound,
             lighting,
+            sky,
+            light_map_texture,
             water_plane,
+            vertical_culling,
             tiles,
             sub_meshes,
             vertex_buffer,
@@ -226,7 +299,12 @@ impl Map {
             light_source_kdtree,
             background_music_track_name,
             videos,
+            object_opacities: Mutex::default(),
             map_data,
+            map_offset,
+            modified_objects: HashSet::new(),
+            gat_version,
+            includes_blocked_tiles: false,
         }
     }
 }
@@ -246,10 +324,71 @@ impl Map {
         ))
     }
 
+    /// Change of the ground height along the X and Z axis at the given
+    /// position, interpolated over the corners of the tile.
+    pub fn ground_gradient(&self, position: Point3<f32>) -> Vector2<f32> {
+        let tile_x = position.x / GAT_TILE_SIZE;
+        let tile_y = position.z / GAT_TILE_SIZE;
+
+        if tile_x < 0.0 || tile_y < 0.0 || tile_x >= self.width as f32 || tile_y >= self.height as f32 {
+            return Vector2::zero();
+        }
+
+        let tile = &self.tiles[tile_x as usize + tile_y as usize * self.width as usize];
+        tile_gradient(tile, Vector2::new(tile_x.fract(), tile_y.fract()))
+    }
+
+    /// Intersects a ray with the ground of the map on the CPU. This doesn't
+    /// rely on the picker buffer, so it also works at the edges of the map
+    /// and below effects.
+    pub fn ground_intersection(&self, origin: Point3<f32>, direction: Vector3<f32>) -> Option<GroundIntersection> {
+        ray::intersect_ground(&self.tiles, self.width as usize, self.height as usize, origin, direction)
+    }
+
+    /// Intersects the ray from the camera through the given screen space
+    /// coordinates (UV) with the ground of the map.
+    pub fn screen_ground_intersection(&self, camera: &dyn Camera, screen_space_position: Vector2<f32>) -> Option<GroundIntersection> {
+        let (origin, direction) = camera.screen_space_ray(screen_space_position);
+        self.ground_intersection(origin, direction)
+    }
+
     pub fn get_tile(&self, position: TilePosition) -> Option<&Tile> {
         self.tiles.get(position.x as usize + position.y as usize * self.width as usize)
     }
 
+    /// Width and height of the map in tiles.
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    pub fn resource_file(&self) -> &str {
+        &self.resource_file
+    }
+
+    /// Height above which objects are hidden, if the map uses vertical
+    /// culling.
+    pub fn vertical_culling_height(&self, focus_position: Point3<f32>) -> Option<f32> {
+        self.vertical_culling.then_some(focus_position.y + VERTICAL_CULLING_HEIGHT)
+    }
+
+    /// Memory on the GPU that is used by the geometry and textures of the map.
+    pub fn gpu_memory_usage(&self) -> usize {
+        let water_size = self.water_plane.as_ref().map_or(0, |water_plane| {
+            let textures_size: usize = water_plane.water_textures.iter().map(|texture| texture.get_byte_size()).sum();
+            water_plane.vertex_buffer.allocated_size() as usize + water_plane.index_buffer.allocated_size() as usize + textures_size
+        });
+
+        let buffers_size = self.vertex_buffer.allocated_size()
+            + self.index_buffer.allocated_size()
+            + self.tile_picker_vertex_buffer.allocated_size()
+            + self.tile_picker_index_buffer.allocated_size();
+
+        buffers_size as usize
+            + self.texture_set.get_byte_size()
+            + self.light_map_texture.as_ref().map_or(0, |texture| texture.get_byte_size())
+            + water_size
+    }
+
     pub fn background_music_track_name(&self) -> Option<&str> {
         self.background_music_track_name.as_deref()
     }
@@ -266,6 +405,10 @@ impl Map {
         &self.index_buffer
     }
 
+    pub fn get_light_map_texture(&self) -> Option<&Arc<Texture>> {
+        self.light_map_texture.as_ref()
+    }
+
     pub fn get_tile_picker_vertex_buffer(&self) -> &Buffer<TileVertex> {
         &self.tile_picker_vertex_buffer
     }
@@ -286,7 +429,7 @@ impl Map {
         // This is the only correct place to clear the ambient sound.
         audio_engine.clear_ambient_sound();
 
-        for sound in self.sound_sources.iter() {
+        for sound in self.sound_sources.iter().filter(|sound| !sound.sound_file.is_empty()) {
             let sound_effect_key = audio_engine.load(&sound.sound_file);
 
             audio_engine.add_ambient_sound(
@@ -294,7 +437,7 @@ impl Map {
                 sound.position,
                 sound.range * AMBIENT_SOUND_MULTIPLIER,
                 sound.volume,
-                sound.cycle,
+                Some(sound.cycle.unwrap_or(DEFAULT_SOUND_CYCLE)),
             );
         }
 
@@ -352,14 +495,79 @@ impl Map {
         object_set: &ResourceSet<ObjectKey>,
         animation_timer_ms: f32,
         camera: &dyn Camera,
+        culling_height: Option<f32>,
     ) {
+        let object_opacities = self.object_opacities.lock().unwrap();
+
         for object_key in object_set.iterate_visible().copied() {
             if let Some(object) = self.objects.get(object_key) {
+                if culling_height.is_some_and(|culling_height| object.calculate_object_aabb().min().y > culling_height) {
+                    continue;
+                }
+
+                let offset = instructions.len();
+
                 object.render_geometry(instructions, animation_timer_ms, camera);
+
+                if let Some(opacity) = object_opacities.get(&object_key) {
+                    instructions[offset..]
+                        .iter_mut()
+                        .for_each(|instruction| instruction.opacity = *opacity);
+                }
             }
         }
     }
 
+    /// Fade out all objects that are between the camera and the target
+    /// position, so the player doesn't get hidden behind walls and buildings.
+    /// Objects that stop blocking the view fade back in.
+    #[cfg_attr(feature = "debug", korangar_debug::profile)]
+    pub fn update_object_fade(
+        &self,
+        object_set: &ResourceSet<ObjectKey>,
+        camera_position: Point3<f32>,
+        target_position: Option<Point3<f32>>,
+        delta_time: f64,
+    ) {
+        let mut object_opacities = self.object_opacities.lock().unwrap();
+
+        let occluding_objects: Vec<ObjectKey> = match target_position {
+            Some(target_position) => {
+                let target_position = target_position + Vector3::unit_y() * OCCLUSION_TARGET_HEIGHT;
+
+                object_set
+                    .iterate_visible()
+                    .copied()
+                    .filter(|object_key| {
+                        self.objects.get(*object_key).is_some_and(|object| {
+                            let aabb = object.calculate_object_aabb();
+
+                            // Objects that the player is standing inside of, like bridges,
+                            // should not be faded out.
+                            !aabb.contains_point(target_position) && aabb.intersects_segment(camera_position, target_position)
+                        })
+                    })
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+
+        for object_key in &occluding_objects {
+            object_opacities.entry(*object_key).or_insert(1.0);
+        }
+
+        let step = delta_time as f32 / OBJECT_FADE_DURATION;
+
+        object_opacities.retain(|object_key, opacity| {
+            match occluding_objects.contains(object_key) {
+                true => *opacity = (*opacity - step).max(OCCLUDING_OBJECT_OPACITY),
+                false => *opacity += step,
+            }
+
+            *opacity < 1.0
+        });
+    }
+
     #[cfg_attr(feature = "debug", korangar_debug::profile)]
     pub fn render_ground(&self, instructions: &mut Vec<ModelInstruction>) {
         self.sub_meshes.iter().for_each(|mesh| {
@@ -371,11 +579,25 @@ impl Map {
                 texture_index: mesh.texture_index,
                 distance: f32::MAX,
                 transparent: mesh.transparent,
+                opacity: 1.0,
             });
         });
     }
 
     #[cfg_attr(feature = "debug", korangar_debug::profile)]
+    pub fn render_sky<'a>(&'a self, sky_instruction: &mut Option<SkyInstruction<'a>>) {
+        self.sky.render_sky(sky_instruction);
+    }
+
+    /// Wind that moves the foliage of the map objects. Indoor maps have no
+    /// wind.
+    pub fn wind(&self, animation_timer_ms: f32) -> Vector4<f32> {
+        match self.vertical_culling {
+            true => Vector4::zero(),
+            false => wind::wind_uniform(animation_timer_ms),
+        }
+    }
+
     pub fn render_water<'a>(&'a self, water_instruction: &mut Option<WaterInstruction<'a>>, animation_timer_ms: f32) {
         if let Some(water_plane) = self.water_plane.as_ref() {
             let frame = animation_timer_ms / (1000.0 / 60.0);
@@ -391,6 +613,7 @@ impl Map {
                 water_texture: &water_plane.water_textures[water_texture_index as usize],
                 water_vertex_buffer: &water_plane.vertex_buffer,
                 water_index_buffer: &water_plane.index_buffer,
+                water_level: water_plane.water_level,
                 texture_repeat: water_plane.texture_repeat,
                 waveform_phase_shift,
                 waveform_amplitude,
@@ -401,16 +624,82 @@ impl Map {
     }
 
     #[cfg_attr(feature = "debug", korangar_debug::profile)]
-    pub fn render_entities(&self, instructions: &mut Vec<EntityInstruction>, entities: &[Entity], camera: &dyn Camera) {
-        entities
-            .iter()
-            .enumerate()
-            .for_each(|(index, entity)| entity.render(instructions, camera, index != 0));
+    pub fn render_entities(
+        &self,
+        instructions: &mut Vec<EntityInstruction>,
+        entities: &[Entity],
+        camera: &dyn Camera,
+        depth_bias: &SpriteDepthBias,
+    ) {
+        entities.iter().enumerate().for_each(|(index, entity)| {
+            let ground_gradient = self.ground_gradient(entity.get_position());
+            entity.render(instructions, camera, index != 0, ground_gradient, depth_bias);
+        });
+    }
+
+    #[cfg_attr(feature = "debug", korangar_debug::profile)]
+    pub fn render_shadow_blobs(&self, instructions: &mut Vec<ShadowBlobInstruction>, entities: &[Entity]) {
+        entities.iter().for_each(|entity| entity.render_shadow_blob(instructions));
     }
 
     #[cfg_attr(feature = "debug", korangar_debug::profile)]
-    pub fn render_dead_entities(&self, instructions: &mut Vec<EntityInstruction>, entities: &[Entity], camera: &dyn Camera) {
-        entities.iter().for_each(|entity| entity.render(instructions, camera, false));
+    pub fn render_decals(&self, instructions: &mut Vec<DecalInstruction>, decal_holder: &DecalHolder) {
+        decal_holder.iter().for_each(|decal| self.render_decal(instructions, decal));
+    }
+
+    /// Emits one instruction for every ground tile that the decal covers.
+    pub fn render_decal(&self, instructions: &mut Vec<DecalInstruction>, decal: &Decal) {
+        // Lift the decals slightly above the ground to avoid z-fighting.
+        const OFFSET: f32 = 0.5;
+
+        let position = decal.position();
+        let size = decal.size();
+        // The decal can be rotated, so we need to cover its circumcircle.
+        let radius = size.magnitude() / 2.0;
+
+        let first_x = ((position.x - radius) / GAT_TILE_SIZE).floor().max(0.0) as usize;
+        let first_y = ((position.z - radius) / GAT_TILE_SIZE).floor().max(0.0) as usize;
+        let last_x = (((position.x + radius) / GAT_TILE_SIZE).floor().max(0.0) as usize).min(self.width as usize - 1);
+        let last_y = (((position.z + radius) / GAT_TILE_SIZE).floor().max(0.0) as usize).min(self.height as usize - 1);
+
+        for y in first_y..=last_y {
+            for x in first_x..=last_x {
+                let tile = &self.tiles[x + y * self.width as usize];
+
+                let base_x = x as f32 * GAT_TILE_SIZE;
+                let base_y = y as f32 * GAT_TILE_SIZE;
+
+                instructions.push(DecalInstruction {
+                    upper_left: Point3::new(base_x, tile.southwest_corner_height + OFFSET, base_y),
+                    upper_right: Point3::new(base_x + GAT_TILE_SIZE, tile.southeast_corner_height + OFFSET, base_y),
+                    lower_left: Point3::new(base_x, tile.northwest_corner_height + OFFSET, base_y + GAT_TILE_SIZE),
+                    lower_right: Point3::new(
+                        base_x + GAT_TILE_SIZE,
+                        tile.northeast_corner_height + OFFSET,
+                        base_y + GAT_TILE_SIZE,
+                    ),
+                    center: position,
+                    size,
+                    rotation: decal.rotation(),
+                    color: decal.color(),
+                    texture: decal.texture().clone(),
+                });
+            }
+        }
+    }
+
+    #[cfg_attr(feature = "debug", korangar_debug::profile)]
+    pub fn render_despawning_entities(
+        &self,
+        instructions: &mut Vec<EntityInstruction>,
+        entities: &[Entity],
+        camera: &dyn Camera,
+        depth_bias: &SpriteDepthBias,
+    ) {
+        entities.iter().for_each(|entity| {
+            let ground_gradient = self.ground_gradient(entity.get_position());
+            entity.render(instructions, camera, false, ground_gradient, depth_bias);
+        });
     }
 
     #[cfg(feature = "debug")]
@@ -574,6 +863,7 @@ impl Map {
                 texture_index: mesh.texture_index,
                 distance: f32::MAX,
                 transparent: mesh.transparent,
+                opacity: 1.0,
             });
         });
 
@@ -608,6 +898,7 @@ impl Map {
                         texture_index: mesh.texture_index,
                         distance: f32::MAX,
                         transparent: mesh.transparent,
+                        opacity: 1.0,
                     });
                 });
 
@@ -818,6 +1109,20 @@ impl Map {
     }
 }
 
+/// Gradient of the bilinear surface spanned by the corners of a tile. The
+/// `fraction` is the position inside the tile in the range of 0.0 to 1.0.
+fn tile_gradient(tile: &Tile, fraction: Vector2<f32>) -> Vector2<f32> {
+    let south_slope = tile.southeast_corner_height - tile.southwest_corner_height;
+    let north_slope = tile.northeast_corner_height - tile.northwest_corner_height;
+    let west_slope = tile.northwest_corner_height - tile.southwest_corner_height;
+    let east_slope = tile.northeast_corner_height - tile.southeast_corner_height;
+
+    Vector2::new(
+        south_slope + (north_slope - south_slope) * fraction.y,
+        west_slope + (east_slope - west_slope) * fraction.x,
+    ) / GAT_TILE_SIZE
+}
+
 impl Traversable for Map {
     fn is_walkable(&self, position: TilePosition) -> bool {
         self.get_tile(position)
@@ -831,3 +1136,39 @@ impl Traversable for Map {
             .unwrap_or(false)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use cgmath::{Vector2, assert_relative_eq};
+
+    use super::tile_gradient;
+    use crate::loaders::GAT_TILE_SIZE;
+    use crate::test_fixtures::tile;
+
+    #[test]
+    fn flat_tile_gradient() {
+        let gradient = tile_gradient(&tile(5.0, 5.0, 5.0, 5.0), Vector2::new(0.5, 0.5));
+
+        assert_relative_eq!(gradient.x, 0.0);
+        assert_relative_eq!(gradient.y, 0.0);
+    }
+
+    #[test]
+    fn sloped_tile_gradient() {
+        // Rises by one tile size towards the east.
+        let gradient = tile_gradient(&tile(0.0, GAT_TILE_SIZE, 0.0, GAT_TILE_SIZE), Vector2::new(0.25, 0.75));
+
+        assert_relative_eq!(gradient.x, 1.0);
+        assert_relative_eq!(gradient.y, 0.0);
+
+        // Only the north east corner is raised, so the slope depends on the position.
+        let raised_corner = tile(0.0, 0.0, 0.0, GAT_TILE_SIZE);
+        let south_west = tile_gradient(&raised_corner, Vector2::new(0.0, 0.0));
+        let north_east = tile_gradient(&raised_corner, Vector2::new(1.0, 1.0));
+
+        assert_relative_eq!(south_west.x, 0.0);
+        assert_relative_eq!(south_west.y, 0.0);
+        assert_relative_eq!(north_east.x, 1.0);
+        assert_relative_eq!(north_east.y, 1.0);
+    }
+}
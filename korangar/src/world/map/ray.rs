@@ -0,0 +1,240 @@
+use cgmath::{InnerSpace, Point3, Vector3};
+use ragnarok_formats::map::Tile;
+use ragnarok_packets::TilePosition;
+
+use crate::loaders::GAT_TILE_SIZE;
+
+/// Intersection of a ray with the ground of a map.
+#[derive(Clone, Copy, Debug)]
+pub struct GroundIntersection {
+    /// World position of the intersection.
+    pub position: Point3<f32>,
+    /// Tile that was hit.
+    pub tile: TilePosition,
+    /// Distance along the ray from its origin to the intersection.
+    pub distance: f32,
+}
+
+/// Intersects a ray with the tiles of a map, which are expected to have
+/// flipped heights. The ray is traced through the tiles in order, so the
+/// first tile that is hit is also the closest one.
+pub fn intersect_ground(
+    tiles: &[Tile],
+    width: usize,
+    height: usize,
+    origin: Point3<f32>,
+    direction: Vector3<f32>,
+) -> Option<GroundIntersection> {
+    if width == 0 || height == 0 || direction.magnitude2() <= f32::EPSILON {
+        return None;
+    }
+
+    let direction = direction.normalize();
+    let (entry_distance, exit_distance) = clip_to_map(width, height, origin, direction)?;
+
+    // Step through the tiles along the horizontal direction of the ray, using
+    // a digital differential analyzer.
+    let entry = origin + direction * entry_distance;
+    let mut tile_x = ((entry.x / GAT_TILE_SIZE).floor() as isize).clamp(0, width as isize - 1);
+    let mut tile_y = ((entry.z / GAT_TILE_SIZE).floor() as isize).clamp(0, height as isize - 1);
+
+    let step_x = if direction.x >= 0.0 { 1 } else { -1 };
+    let step_y = if direction.z >= 0.0 { 1 } else { -1 };
+
+    let boundary_distance = |tile: isize, step: isize, origin: f32, direction: f32| match direction.abs() > f32::EPSILON {
+        true => {
+            let boundary = (tile + step.max(0)) as f32 * GAT_TILE_SIZE;
+            (boundary - origin) / direction
+        }
+        false => f32::INFINITY,
+    };
+
+    let mut next_x = boundary_distance(tile_x, step_x, origin.x, direction.x);
+    let mut next_y = boundary_distance(tile_y, step_y, origin.z, direction.z);
+    let delta_x = GAT_TILE_SIZE / direction.x.abs();
+    let delta_y = GAT_TILE_SIZE / direction.z.abs();
+
+    loop {
+        let tile = &tiles[tile_x as usize + tile_y as usize * width];
+        let tile_position = TilePosition {
+            x: tile_x as u16,
+            y: tile_y as u16,
+        };
+
+        if let Some(distance) = intersect_tile(tile, tile_position, origin, direction) {
+            return Some(GroundIntersection {
+                position: origin + direction * distance,
+                tile: tile_position,
+                distance,
+            });
+        }
+
+        let next_distance = next_x.min(next_y);
+
+        if !next_distance.is_finite() || next_distance > exit_distance {
+            return None;
+        }
+
+        if next_x < next_y {
+            tile_x += step_x;
+            next_x += delta_x;
+        } else {
+            tile_y += step_y;
+            next_y += delta_y;
+        }
+
+        if tile_x < 0 || tile_y < 0 || tile_x >= width as isize || tile_y >= height as isize {
+            return None;
+        }
+    }
+}
+
+/// Clips the ray against the horizontal bounds of the map and returns the
+/// distances at which the ray enters and leaves the map.
+fn clip_to_map(width: usize, height: usize, origin: Point3<f32>, direction: Vector3<f32>) -> Option<(f32, f32)> {
+    let mut entry_distance = 0.0f32;
+    let mut exit_distance = f32::INFINITY;
+
+    for (origin, direction, size) in [
+        (origin.x, direction.x, width as f32 * GAT_TILE_SIZE),
+        (origin.z, direction.z, height as f32 * GAT_TILE_SIZE),
+    ] {
+        if direction.abs() <= f32::EPSILON {
+            if origin < 0.0 || origin > size {
+                return None;
+            }
+            continue;
+        }
+
+        let first = (0.0 - origin) / direction;
+        let second = (size - origin) / direction;
+
+        entry_distance = entry_distance.max(first.min(second));
+        exit_distance = exit_distance.min(first.max(second));
+    }
+
+    (entry_distance <= exit_distance).then_some((entry_distance, exit_distance))
+}
+
+/// Intersects the ray with the two triangles of a tile and returns the
+/// distance to the closest hit.
+fn intersect_tile(tile: &Tile, position: TilePosition, origin: Point3<f32>, direction: Vector3<f32>) -> Option<f32> {
+    let base_x = position.x as f32 * GAT_TILE_SIZE;
+    let base_y = position.y as f32 * GAT_TILE_SIZE;
+
+    let southwest = Point3::new(base_x, tile.southwest_corner_height, base_y);
+    let southeast = Point3::new(base_x + GAT_TILE_SIZE, tile.southeast_corner_height, base_y);
+    let northwest = Point3::new(base_x, tile.northwest_corner_height, base_y + GAT_TILE_SIZE);
+    let northeast = Point3::new(base_x + GAT_TILE_SIZE, tile.northeast_corner_height, base_y + GAT_TILE_SIZE);
+
+    let first = intersect_triangle(origin, direction, [southwest, southeast, northwest]);
+    let second = intersect_triangle(origin, direction, [northwest, southeast, northeast]);
+
+    match (first, second) {
+        (Some(first), Some(second)) => Some(first.min(second)),
+        (first, second) => first.or(second),
+    }
+}
+
+/// Möller–Trumbore intersection of a ray with a triangle. Both sides of the
+/// triangle are considered.
+fn intersect_triangle(origin: Point3<f32>, direction: Vector3<f32>, [first, second, third]: [Point3<f32>; 3]) -> Option<f32> {
+    const EPSILON: f32 = 1e-6;
+
+    let first_edge = second - first;
+    let second_edge = third - first;
+
+    let perpendicular = direction.cross(second_edge);
+    let determinant = first_edge.dot(perpendicular);
+
+    if determinant.abs() < EPSILON {
+        return None;
+    }
+
+    let inverse_determinant = 1.0 / determinant;
+    let offset = origin - first;
+
+    let u = offset.dot(perpendicular) * inverse_determinant;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let offset_cross = offset.cross(first_edge);
+    let v = direction.dot(offset_cross) * inverse_determinant;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    let distance = second_edge.dot(offset_cross) * inverse_determinant;
+    (distance >= 0.0).then_some(distance)
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::{Point3, Vector3, assert_relative_eq};
+    use ragnarok_formats::map::Tile;
+
+    use super::intersect_ground;
+    use crate::loaders::GAT_TILE_SIZE;
+    use crate::test_fixtures::tile;
+
+    fn tiles(width: usize, height: usize, corner_height: impl Fn(usize, usize) -> f32) -> Vec<Tile> {
+        (0..width * height)
+            .map(|index| {
+                let x = index % width;
+                let y = index / width;
+
+                tile(
+                    corner_height(x, y),
+                    corner_height(x + 1, y),
+                    corner_height(x, y + 1),
+                    corner_height(x + 1, y + 1),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn straight_down_on_flat_ground() {
+        let tiles = tiles(4, 4, |_, _| 2.0);
+        let origin = Point3::new(GAT_TILE_SIZE * 2.5, 100.0, GAT_TILE_SIZE * 1.5);
+
+        let intersection = intersect_ground(&tiles, 4, 4, origin, Vector3::new(0.0, -1.0, 0.0)).unwrap();
+
+        assert_eq!((intersection.tile.x, intersection.tile.y), (2, 1));
+        assert_relative_eq!(intersection.position.y, 2.0, epsilon = 1e-4);
+        assert_relative_eq!(intersection.distance, 98.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn slanted_ray_enters_from_outside_the_map() {
+        let tiles = tiles(8, 8, |_, _| 0.0);
+        let origin = Point3::new(-GAT_TILE_SIZE * 2.0, GAT_TILE_SIZE * 4.5, GAT_TILE_SIZE * 0.5);
+        let direction = Vector3::new(1.0, -1.0, 0.0);
+
+        let intersection = intersect_ground(&tiles, 8, 8, origin, direction).unwrap();
+
+        assert_eq!((intersection.tile.x, intersection.tile.y), (2, 0));
+        assert_relative_eq!(intersection.position.x, GAT_TILE_SIZE * 2.5, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn hill_blocks_the_ray() {
+        // A wall of raised tiles in the third column.
+        let tiles = tiles(8, 1, |x, _| if x == 3 { 50.0 } else { 0.0 });
+        let origin = Point3::new(GAT_TILE_SIZE * 0.5, 10.0, GAT_TILE_SIZE * 0.5);
+
+        let intersection = intersect_ground(&tiles, 8, 1, origin, Vector3::new(1.0, 0.0, 0.0)).unwrap();
+
+        assert_eq!(intersection.tile.x, 2);
+    }
+
+    #[test]
+    fn ray_pointing_away_misses() {
+        let tiles = tiles(4, 4, |_, _| 0.0);
+        let origin = Point3::new(GAT_TILE_SIZE * 2.0, 10.0, GAT_TILE_SIZE * 2.0);
+
+        assert!(intersect_ground(&tiles, 4, 4, origin, Vector3::new(0.3, 1.0, 0.2)).is_none());
+        assert!(intersect_ground(&tiles, 4, 4, Point3::new(-10.0, 10.0, -10.0), Vector3::new(-1.0, -1.0, 0.0)).is_none());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::graphics::{CloudLayerInstruction, Color, SkyInstruction};
+use crate::world::{CloudEffect, MapSkyData};
+
+/// Sky colors of maps that have no entry in the map sky data table.
+const DEFAULT_TOP_COLOR: Color = Color::rgb_u8(72, 112, 176);
+const DEFAULT_HORIZON_COLOR: Color = Color::rgb_u8(168, 192, 224);
+/// How much of the way towards white the horizon of a single colored sky is.
+const HORIZON_BRIGHTENING: f32 = 0.45;
+/// Amount of clouds of a cloud effect that fully covers its layer.
+const FULL_COVERAGE_CLOUD_COUNT: f32 = 200.0;
+/// Cloud height at which the cloud texture is shown at its original scale.
+const REFERENCE_CLOUD_HEIGHT: f32 = 100.0;
+/// Coverage of the layer that is used for maps with the old cloud effect.
+const OLD_CLOUD_EFFECT_COVERAGE: f32 = 0.35;
+
+/// Background of a map, drawn behind all geometry.
+pub struct Sky {
+    top_color: Color,
+    horizon_color: Color,
+    show_stars: bool,
+    cloud_layers: Vec<CloudLayerInstruction>,
+}
+
+impl Default for Sky {
+    fn default() -> Self {
+        Self {
+            top_color: DEFAULT_TOP_COLOR,
+            horizon_color: DEFAULT_HORIZON_COLOR,
+            show_stars: false,
+            cloud_layers: Vec::new(),
+        }
+    }
+}
+
+impl Sky {
+    pub fn new(map_sky_data: Option<&MapSkyData>) -> Self {
+        let Some(map_sky_data) = map_sky_data else {
+            return Self::default();
+        };
+
+        let (top_color, horizon_color) = match map_sky_data.bg_color {
+            // Fog hides the gradient, so the whole sky has the background color.
+            Some(color) if map_sky_data.bg_fog => (color, color),
+            Some(color) => (color, color + (Color::WHITE - color) * HORIZON_BRIGHTENING),
+            None => (DEFAULT_TOP_COLOR, DEFAULT_HORIZON_COLOR),
+        };
+
+        let mut cloud_layers: Vec<CloudLayerInstruction> = map_sky_data.cloud_effect.iter().map(cloud_layer).collect();
+
+        if cloud_layers.is_empty() && map_sky_data.old_cloud_effect.is_some() {
+            cloud_layers.push(CloudLayerInstruction {
+                color: Color::WHITE,
+                coverage: OLD_CLOUD_EFFECT_COVERAGE,
+                scale: 1.0,
+                speed: 1.0,
+            });
+        }
+
+        Self {
+            top_color,
+            horizon_color,
+            show_stars: map_sky_data.star_effect,
+            cloud_layers,
+        }
+    }
+
+    pub fn render_sky<'a>(&'a self, sky_instruction: &mut Option<SkyInstruction<'a>>) {
+        *sky_instruction = Some(SkyInstruction {
+            top_color: self.top_color,
+            horizon_color: self.horizon_color,
+            show_stars: self.show_stars,
+            cloud_layers: &self.cloud_layers,
+        });
+    }
+}
+
+fn cloud_layer(cloud_effect: &CloudEffect) -> CloudLayerInstruction {
+    let height = cloud_effect.height as f32 + cloud_effect.height_extra as f32 * 0.5;
+
+    CloudLayerInstruction {
+        color: cloud_effect.color,
+        coverage: (cloud_effect.num as f32 / FULL_COVERAGE_CLOUD_COUNT).clamp(0.0, 1.0),
+        // Higher clouds look bigger and move slower.
+        scale: REFERENCE_CLOUD_HEIGHT / height.max(1.0),
+        speed: cloud_effect.expand_rate.max(0.0) + 1.0,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DEFAULT_HORIZON_COLOR, DEFAULT_TOP_COLOR, Sky};
+    use crate::graphics::Color;
+    use crate::world::MapSkyData;
+
+    fn map_sky_data(bg_color: Option<Color>, bg_fog: bool) -> MapSkyData {
+        MapSkyData {
+            old_cloud_effect: None,
+            bg_color,
+            bg_fog,
+            star_effect: false,
+            cloud_effect: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn missing_sky_data_uses_default_gradient() {
+        let sky = Sky::new(None);
+
+        assert_eq!(sky.top_color, DEFAULT_TOP_COLOR);
+        assert_eq!(sky.horizon_color, DEFAULT_HORIZON_COLOR);
+        assert!(sky.cloud_layers.is_empty());
+    }
+
+    #[test]
+    fn background_color_brightens_towards_horizon() {
+        let color = Color::rgb(0.2, 0.2, 0.2);
+        let sky = Sky::new(Some(&map_sky_data(Some(color), false)));
+
+        assert_eq!(sky.top_color, color);
+        assert!(sky.horizon_color.red > color.red);
+    }
+
+    #[test]
+    fn fog_flattens_gradient() {
+        let color = Color::rgb(0.2, 0.3, 0.4);
+        let sky = Sky::new(Some(&map_sky_data(Some(color), true)));
+
+        assert_eq!(sky.top_color, color);
+        assert_eq!(sky.horizon_color, color);
+    }
+}
@@ -0,0 +1,76 @@
+use std::f32::consts::{PI, TAU};
+
+use cgmath::Vector4;
+
+/// Strength of the wind, which is the maximum distance in world units that
+/// the top of a fully affected model is moved.
+const WIND_STRENGTH: f32 = 1.5;
+/// Direction of the wind in radians, measured from the x-axis towards the
+/// z-axis.
+const BASE_WIND_DIRECTION: f32 = 0.6;
+/// Maximum deviation of the wind direction from its base direction in
+/// radians.
+const WIND_DIRECTION_VARIATION: f32 = 0.35;
+/// Time in seconds it takes the wind direction to swing back and forth once.
+const WIND_DIRECTION_PERIOD: f32 = 47.0;
+/// Time in seconds between two gusts.
+const GUST_PERIOD: f32 = 9.0;
+/// Share of the gust period that a gust lasts.
+const GUST_DURATION: f32 = 0.35;
+/// How much stronger the wind is at the peak of a gust.
+const GUST_STRENGTH: f32 = 0.8;
+
+/// Calculates the wind uniform for the given point in time.
+///
+/// The horizontal wind direction is stored in `x` and `y`, the wind strength
+/// in `z` and the current gust factor in `w`.
+pub fn wind_uniform(animation_timer_ms: f32) -> Vector4<f32> {
+    let seconds = animation_timer_ms / 1000.0;
+
+    let direction = BASE_WIND_DIRECTION + f32::sin(seconds / WIND_DIRECTION_PERIOD * TAU) * WIND_DIRECTION_VARIATION;
+
+    // Gusts smoothly build up and fade out again, with calm periods in
+    // between.
+    let gust_progress = (seconds / GUST_PERIOD).fract() / GUST_DURATION;
+    let gust = match gust_progress < 1.0 {
+        true => f32::sin(gust_progress * PI).powi(2) * GUST_STRENGTH,
+        false => 0.0,
+    };
+
+    Vector4::new(f32::cos(direction), f32::sin(direction), WIND_STRENGTH, gust)
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::{InnerSpace, Vector2};
+
+    use super::{GUST_PERIOD, GUST_STRENGTH, WIND_STRENGTH, wind_uniform};
+
+    #[test]
+    fn direction_is_normalized() {
+        for animation_timer_ms in [0.0, 1234.0, 98765.0] {
+            let wind = wind_uniform(animation_timer_ms);
+            let direction = Vector2::new(wind.x, wind.y);
+
+            assert!((direction.magnitude() - 1.0).abs() < 1e-5);
+            assert_eq!(wind.z, WIND_STRENGTH);
+        }
+    }
+
+    #[test]
+    fn gusts_stay_in_range() {
+        for step in 0..100 {
+            let animation_timer_ms = step as f32 * GUST_PERIOD * 10.0;
+            let gust = wind_uniform(animation_timer_ms).w;
+
+            assert!((0.0..=GUST_STRENGTH).contains(&gust));
+        }
+    }
+
+    #[test]
+    fn calm_between_gusts() {
+        let animation_timer_ms = GUST_PERIOD * 0.9 * 1000.0;
+
+        assert_eq!(wind_uniform(animation_timer_ms).w, 0.0);
+    }
+}
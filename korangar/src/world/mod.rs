@@ -1,12 +1,17 @@
 mod action;
 mod animation;
 mod cameras;
+mod combat;
+mod combat_feedback;
+mod decal;
 mod effect;
 mod entity;
 mod library;
 mod light;
 mod map;
 mod model;
+#[cfg(feature = "debug")]
+mod navigation_grid;
 mod object;
 mod particles;
 mod pathing;
@@ -16,12 +21,17 @@ mod video;
 pub use self::action::*;
 pub use self::animation::*;
 pub use self::cameras::*;
+pub use self::combat::*;
+pub use self::combat_feedback::*;
+pub use self::decal::*;
 pub use self::effect::*;
 pub use self::entity::*;
 pub use self::library::*;
 pub use self::light::*;
 pub use self::map::*;
 pub use self::model::*;
+#[cfg(feature = "debug")]
+pub use self::navigation_grid::navigation_grid;
 pub use self::object::*;
 pub use self::particles::*;
 pub use self::pathing::*;
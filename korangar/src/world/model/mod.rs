@@ -1,4 +1,6 @@
 mod node;
+#[cfg(feature = "debug")]
+mod preview;
 
 use std::ops::Mul;
 
@@ -13,6 +15,8 @@ use rust_state::RustState;
 
 pub use self::node::{Node, SubMesh};
 #[cfg(feature = "debug")]
+pub use self::preview::ModelPreview;
+#[cfg(feature = "debug")]
 use crate::graphics::Color;
 #[cfg(feature = "debug")]
 use crate::graphics::DebugAabbInstruction;
@@ -278,6 +278,7 @@ impl Node {
                 texture_index: mesh.texture_index,
                 distance,
                 transparent: mesh.transparent,
+                opacity: 1.0,
             });
         });
 
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use cgmath::{InnerSpace, Point3};
+use ragnarok_formats::transform::Transform;
+
+use super::Model;
+use crate::graphics::{Buffer, Color, DebugAabbInstruction, ModelBatch, ModelInstruction, ModelVertex, TextureSet};
+use crate::world::Camera;
+
+/// A single model with its own buffers and textures, so it can be inspected
+/// independently of the map that is currently loaded.
+pub struct ModelPreview {
+    pub model: Model,
+    vertex_buffer: Arc<Buffer<ModelVertex>>,
+    /// Same vertices, but colored by their normals.
+    normal_vertex_buffer: Arc<Buffer<ModelVertex>>,
+    index_buffer: Arc<Buffer<u32>>,
+    texture_set: Arc<TextureSet>,
+    transform: Transform,
+}
+
+impl ModelPreview {
+    pub fn new(
+        model: Model,
+        vertex_buffer: Arc<Buffer<ModelVertex>>,
+        normal_vertex_buffer: Arc<Buffer<ModelVertex>>,
+        index_buffer: Arc<Buffer<u32>>,
+        texture_set: Arc<TextureSet>,
+    ) -> Self {
+        Self {
+            model,
+            vertex_buffer,
+            normal_vertex_buffer,
+            index_buffer,
+            texture_set,
+            transform: Transform::position(Point3::new(0.0, 0.0, 0.0)),
+        }
+    }
+
+    pub fn set_position(&mut self, position: Point3<f32>) {
+        self.transform.position = position;
+    }
+
+    /// Center of the model in world space.
+    pub fn center(&self) -> Point3<f32> {
+        self.model.calculate_aabb(&self.transform).center()
+    }
+
+    /// Radius of a sphere that contains the whole model.
+    pub fn radius(&self) -> f32 {
+        self.model.calculate_aabb(&self.transform).size().magnitude() / 2.0
+    }
+
+    pub fn render_geometry(
+        &self,
+        instructions: &mut Vec<ModelInstruction>,
+        model_batches: &mut Vec<ModelBatch>,
+        animation_timer_ms: f32,
+        camera: &dyn Camera,
+        show_normals: bool,
+    ) {
+        let offset = instructions.len();
+
+        self.model
+            .render_geometry(instructions, &self.transform, animation_timer_ms, camera);
+
+        let vertex_buffer = match show_normals {
+            true => self.normal_vertex_buffer.clone(),
+            false => self.vertex_buffer.clone(),
+        };
+
+        model_batches.push(ModelBatch {
+            offset,
+            count: instructions.len() - offset,
+            texture_set: self.texture_set.clone(),
+            vertex_buffer,
+            index_buffer: self.index_buffer.clone(),
+        });
+    }
+
+    pub fn render_bounding_box(&self, instructions: &mut Vec<DebugAabbInstruction>, color: Color) {
+        self.model.render_bounding_box(instructions, &self.transform, color);
+    }
+}
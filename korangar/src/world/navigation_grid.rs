@@ -0,0 +1,109 @@
+//! Export of the walkability of a map as a plain text navigation grid, so
+//! server developers can validate their path finding against the client.
+//!
+//! The file starts with a header, followed by two grids with one line per row
+//! of tiles, starting at `y = 0`:
+//!
+//! ```text
+//! navigation_grid 1
+//! map prontera
+//! size 312 392
+//! tiles
+//! 0033...
+//! neighbors
+//! 00000f0f...
+//! ```
+//!
+//! Every tile of the `tiles` grid is a single digit, where bit 0 is set if
+//! the tile is walkable and bit 1 is set if it can be shot through. Every tile
+//! of the `neighbors` grid is a hexadecimal byte, where each bit is set if the
+//! client can walk to that neighbor in a single step. The bits follow
+//! [`ORTHOGONAL_NEIGHBORS`] and then [`DIAGONAL_NEIGHBORS`]. Like in the path
+//! finder, blocked tiles still list the neighbors they could walk to.
+
+use std::fmt::Write;
+
+use ragnarok_packets::TilePosition;
+
+use super::{DIAGONAL_NEIGHBORS, ORTHOGONAL_NEIGHBORS, PathFinder, Traversable};
+
+/// Version of the navigation grid format. Needs to be increased whenever the
+/// format changes.
+const NAVIGATION_GRID_VERSION: u32 = 1;
+
+/// Create the navigation grid of a map.
+pub fn navigation_grid(map: &impl Traversable, map_name: &str, width: u16, height: u16) -> String {
+    let mut path_finder = PathFinder::default();
+    let mut output = String::new();
+
+    let _ = writeln!(output, "navigation_grid {NAVIGATION_GRID_VERSION}");
+    let _ = writeln!(output, "map {map_name}");
+    let _ = writeln!(output, "size {width} {height}");
+
+    output.push_str("tiles\n");
+
+    for y in 0..height {
+        for x in 0..width {
+            let position = TilePosition { x, y };
+            let tile_bits = map.is_walkable(position) as u8 | (map.is_snipeable(position) as u8) << 1;
+
+            let _ = write!(output, "{tile_bits}");
+        }
+
+        output.push('\n');
+    }
+
+    output.push_str("neighbors\n");
+
+    for y in 0..height {
+        for x in 0..width {
+            let position = TilePosition { x, y };
+            let neighbor_mask = path_finder
+                .walkable_neighbors(map, position)
+                .iter()
+                .filter_map(|neighbor| neighbor_index(position, *neighbor))
+                .fold(0u8, |mask, index| mask | 1 << index);
+
+            let _ = write!(output, "{neighbor_mask:02x}");
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
+
+fn neighbor_index(position: TilePosition, neighbor: TilePosition) -> Option<usize> {
+    let offset = (
+        neighbor.x.wrapping_sub(position.x) as i16,
+        neighbor.y.wrapping_sub(position.y) as i16,
+    );
+
+    ORTHOGONAL_NEIGHBORS
+        .iter()
+        .chain(DIAGONAL_NEIGHBORS.iter())
+        .position(|neighbor_offset| *neighbor_offset == offset)
+}
+
+#[cfg(test)]
+mod test {
+    use ragnarok_packets::TilePosition;
+
+    use super::navigation_grid;
+    use crate::test_fixtures::TestMap;
+
+    #[test]
+    fn grid_matches_path_finder() {
+        // Only the top right tile is blocked, but it can still be shot through.
+        let mut map = TestMap::new(3, 2);
+        map.set_unwalkable(&[TilePosition { x: 2, y: 1 }]);
+
+        let grid = navigation_grid(&map, "test", 3, 2);
+
+        // Diagonal steps next to the blocked tile are not allowed, but the
+        // blocked tile itself still has neighbors, just like in the path finder.
+        let expected = "navigation_grid 1\nmap test\nsize 3 2\ntiles\n333\n332\nneighbors\n154702\n298a8a\n";
+
+        assert_eq!(grid, expected);
+    }
+}
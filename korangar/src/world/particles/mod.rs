@@ -8,16 +8,17 @@ use korangar_interface::application::Clip;
 use ragnarok_packets::{EntityId, QuestColor, QuestEffectPacket};
 use rand_aes::tls::rand_f32;
 
-use crate::graphics::{Color, ScreenClip, ScreenPosition, ScreenSize, Texture};
-use crate::loaders::{FontSize, ImageType, Scaling, TextureLoader};
+use crate::graphics::{ScreenClip, ScreenPosition, ScreenSize, Texture};
+use crate::loaders::{ImageType, Scaling, TextureLoader};
 use crate::renderer::{GameInterfaceRenderer, SpriteRenderer};
+use crate::state::theme::WorldTheme;
 use crate::world::Camera;
 use crate::{Entity, Map};
 
 pub trait Particle {
     fn update(&mut self, delta_time: f32) -> bool;
 
-    fn render(&self, renderer: &GameInterfaceRenderer, camera: &dyn Camera, window_size: ScreenSize);
+    fn render(&self, renderer: &GameInterfaceRenderer, camera: &dyn Camera, window_size: ScreenSize, theme: &WorldTheme);
 }
 
 fn random_velocity() -> f32 {
@@ -60,7 +61,7 @@ impl Particle for DamageNumber {
         self.timer > 0.0
     }
 
-    fn render(&self, renderer: &GameInterfaceRenderer, camera: &dyn Camera, window_size: ScreenSize) {
+    fn render(&self, renderer: &GameInterfaceRenderer, camera: &dyn Camera, window_size: ScreenSize, theme: &WorldTheme) {
         let clip_space_position = camera.view_projection_matrix() * self.position.to_homogeneous();
         let screen_position = camera.clip_to_screen_space(clip_space_position);
         let final_position = ScreenPosition {
@@ -69,11 +70,11 @@ impl Particle for DamageNumber {
         };
 
         let color = match self.is_critical {
-            true => Color::rgb_u8(255, 180, 0),
-            false => Color::WHITE,
+            true => theme.damage_number.critical_color,
+            false => theme.damage_number.damage_color,
         };
 
-        renderer.render_damage_text(&self.damage_amount, final_position, color, FontSize(16.0));
+        renderer.render_damage_text(&self.damage_amount, final_position, color, theme.damage_number.font_size);
     }
 }
 
@@ -96,7 +97,7 @@ impl Particle for Miss {
         self.timer > 0.0
     }
 
-    fn render(&self, renderer: &GameInterfaceRenderer, camera: &dyn Camera, window_size: ScreenSize) {
+    fn render(&self, renderer: &GameInterfaceRenderer, camera: &dyn Camera, window_size: ScreenSize, theme: &WorldTheme) {
         let clip_space_position = camera.view_projection_matrix() * self.position.to_homogeneous();
         let screen_position = camera.clip_to_screen_space(clip_space_position);
         let final_position = ScreenPosition {
@@ -105,7 +106,12 @@ impl Particle for Miss {
         };
         let alpha = (self.timer * 10.0).min(1.0);
 
-        renderer.render_damage_text("miss", final_position, Color::rgba(1.0, 0.0, 0.0, alpha), FontSize(20.0));
+        renderer.render_damage_text(
+            "miss",
+            final_position,
+            theme.damage_number.miss_color.multiply_alpha(alpha),
+            theme.damage_number.miss_font_size,
+        );
     }
 }
 
@@ -137,7 +143,7 @@ impl Particle for HealNumber {
         self.timer > 0.0
     }
 
-    fn render(&self, renderer: &GameInterfaceRenderer, camera: &dyn Camera, window_size: ScreenSize) {
+    fn render(&self, renderer: &GameInterfaceRenderer, camera: &dyn Camera, window_size: ScreenSize, theme: &WorldTheme) {
         let clip_space_position = camera.view_projection_matrix() * self.position.to_homogeneous();
         let screen_position = camera.clip_to_screen_space(clip_space_position);
         let final_position = ScreenPosition {
@@ -145,14 +151,19 @@ impl Particle for HealNumber {
             top: screen_position.y * window_size.height,
         };
 
-        renderer.render_damage_text(&self.heal_amount, final_position, Color::rgb_u8(30, 255, 30), FontSize(16.0));
+        renderer.render_damage_text(
+            &self.heal_amount,
+            final_position,
+            theme.damage_number.heal_color,
+            theme.damage_number.font_size,
+        );
     }
 }
 
 pub struct QuestIcon {
     position: Point3<f32>,
     texture: Arc<Texture>,
-    color: Color,
+    color: QuestColor,
 }
 
 impl QuestIcon {
@@ -173,17 +184,19 @@ impl QuestIcon {
                 ImageType::Color,
             )
             .unwrap();
-        let color = match quest_effect.color {
-            QuestColor::Yellow => Color::rgb_u8(200, 200, 30),
-            QuestColor::Orange => Color::rgb_u8(200, 100, 30),
-            QuestColor::Green => Color::rgb_u8(30, 200, 30),
-            QuestColor::Purple => Color::rgb_u8(200, 30, 200),
-        };
+        let color = quest_effect.color;
 
         Some(Self { position, texture, color })
     }
 
-    fn render(&self, renderer: &GameInterfaceRenderer, camera: &dyn Camera, window_size: ScreenSize, scaling_factor: f32) {
+    fn render(
+        &self,
+        renderer: &GameInterfaceRenderer,
+        camera: &dyn Camera,
+        window_size: ScreenSize,
+        scaling_factor: f32,
+        theme: &WorldTheme,
+    ) {
         let clip_space_position = camera.view_projection_matrix() * self.position.to_homogeneous();
         let screen_position = camera.clip_to_screen_space(clip_space_position);
         let final_position = ScreenPosition {
@@ -196,16 +209,53 @@ impl QuestIcon {
             final_position - ScreenSize::uniform(15.0 * scaling_factor),
             ScreenSize::uniform(30.0 * scaling_factor),
             ScreenClip::unbound(),
-            self.color,
+            theme.quest_marker.color(&self.color),
             true,
         );
     }
 }
 
+/// Damage of a source to a target that is combined into a single damage
+/// number.
+struct PendingDamage {
+    position: Point3<f32>,
+    damage_amount: usize,
+    hits: usize,
+    is_critical: bool,
+    remaining_time: f32,
+}
+
+impl PendingDamage {
+    fn to_particle(&self) -> Box<dyn Particle + Send + Sync> {
+        match self.hits {
+            0 => Box::new(Miss::new(self.position)),
+            1 => Box::new(DamageNumber::new(
+                self.position,
+                self.damage_amount.to_string(),
+                self.is_critical,
+            )),
+            hits => Box::new(DamageNumber::new(
+                self.position,
+                format!("{} x{hits}", self.damage_amount),
+                self.is_critical,
+            )),
+        }
+    }
+}
+
+/// Healing of an entity that is combined into a single heal number.
+struct PendingHeal {
+    position: Point3<f32>,
+    heal_amount: usize,
+    remaining_time: f32,
+}
+
 #[derive(Default)]
 pub struct ParticleHolder {
     particles: Vec<Box<dyn Particle + Send + Sync>>,
     quest_icons: HashMap<EntityId, QuestIcon>,
+    pending_damage: HashMap<(EntityId, EntityId), PendingDamage>,
+    pending_heals: HashMap<EntityId, PendingHeal>,
 }
 
 impl ParticleHolder {
@@ -213,6 +263,67 @@ impl ParticleHolder {
         self.particles.push(particle);
     }
 
+    /// Spawn a damage number or a miss. If an aggregation window is given, all
+    /// damage of the source to the target within the window is combined into a
+    /// single damage number. Misses are only shown if every attack missed.
+    pub fn spawn_damage(
+        &mut self,
+        source_entity_id: EntityId,
+        destination_entity_id: EntityId,
+        position: Point3<f32>,
+        damage_amount: Option<usize>,
+        is_critical: bool,
+        aggregation_window: Option<f32>,
+    ) {
+        let Some(aggregation_window) = aggregation_window else {
+            let particle: Box<dyn Particle + Send + Sync> = match damage_amount {
+                Some(amount) => Box::new(DamageNumber::new(position, amount.to_string(), is_critical)),
+                None => Box::new(Miss::new(position)),
+            };
+
+            self.spawn_particle(particle);
+            return;
+        };
+
+        let pending_damage = self
+            .pending_damage
+            .entry((source_entity_id, destination_entity_id))
+            .or_insert(PendingDamage {
+                position,
+                damage_amount: 0,
+                hits: 0,
+                is_critical: false,
+                remaining_time: aggregation_window,
+            });
+
+        // Show the number where the target is when the damage number spawns.
+        pending_damage.position = position;
+        pending_damage.is_critical |= is_critical;
+
+        if let Some(damage_amount) = damage_amount {
+            pending_damage.damage_amount += damage_amount;
+            pending_damage.hits += 1;
+        }
+    }
+
+    /// Spawn a heal number. If an aggregation window is given, all healing of
+    /// the entity within the window is combined into a single heal number.
+    pub fn spawn_heal(&mut self, entity_id: EntityId, position: Point3<f32>, heal_amount: usize, aggregation_window: Option<f32>) {
+        let Some(aggregation_window) = aggregation_window else {
+            self.spawn_particle(Box::new(HealNumber::new(position, heal_amount.to_string())));
+            return;
+        };
+
+        let pending_heal = self.pending_heals.entry(entity_id).or_insert(PendingHeal {
+            position,
+            heal_amount: 0,
+            remaining_time: aggregation_window,
+        });
+
+        pending_heal.position = position;
+        pending_heal.heal_amount += heal_amount;
+    }
+
     pub fn add_quest_icon(&mut self, texture_loader: &TextureLoader, map: &Map, quest_effect: QuestEffectPacket) {
         let entity_id = quest_effect.entity_id;
 
@@ -228,11 +339,40 @@ impl ParticleHolder {
     pub fn clear(&mut self) {
         self.particles.clear();
         self.quest_icons.clear();
+        self.pending_damage.clear();
+        self.pending_heals.clear();
     }
 
     #[cfg_attr(feature = "debug", korangar_debug::profile("update particles"))]
     pub fn update(&mut self, delta_time: f32) {
         self.particles.retain_mut(|particle| particle.update(delta_time));
+
+        let particles = &mut self.particles;
+
+        self.pending_damage.retain(|_, pending_damage| {
+            pending_damage.remaining_time -= delta_time;
+
+            if pending_damage.remaining_time > 0.0 {
+                return true;
+            }
+
+            particles.push(pending_damage.to_particle());
+            false
+        });
+
+        self.pending_heals.retain(|_, pending_heal| {
+            pending_heal.remaining_time -= delta_time;
+
+            if pending_heal.remaining_time > 0.0 {
+                return true;
+            }
+
+            particles.push(Box::new(HealNumber::new(
+                pending_heal.position,
+                pending_heal.heal_amount.to_string(),
+            )));
+            false
+        });
     }
 
     #[cfg_attr(feature = "debug", korangar_debug::profile("render particles"))]
@@ -243,14 +383,52 @@ impl ParticleHolder {
         window_size: ScreenSize,
         scaling: Scaling,
         entities: &[Entity],
+        theme: &WorldTheme,
     ) {
         self.particles
             .iter()
-            .for_each(|particle| particle.render(renderer, camera, window_size));
+            .for_each(|particle| particle.render(renderer, camera, window_size, theme));
 
         entities
             .iter()
             .filter_map(|entity| self.quest_icons.get(&entity.get_entity_id()))
-            .for_each(|quest_icon| quest_icon.render(renderer, camera, window_size, scaling.get_factor()));
+            .for_each(|quest_icon| quest_icon.render(renderer, camera, window_size, scaling.get_factor(), theme));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::Point3;
+    use ragnarok_packets::EntityId;
+
+    use super::ParticleHolder;
+
+    const SOURCE: EntityId = EntityId(1);
+    const TARGET: EntityId = EntityId(2);
+
+    #[test]
+    fn damage_is_spawned_right_away_without_aggregation() {
+        let mut particle_holder = ParticleHolder::default();
+
+        particle_holder.spawn_damage(SOURCE, TARGET, Point3::new(0.0, 0.0, 0.0), Some(10), false, None);
+        particle_holder.spawn_damage(SOURCE, TARGET, Point3::new(0.0, 0.0, 0.0), Some(10), false, None);
+
+        assert_eq!(particle_holder.particles.len(), 2);
+    }
+
+    #[test]
+    fn damage_within_window_is_combined() {
+        let mut particle_holder = ParticleHolder::default();
+
+        particle_holder.spawn_damage(SOURCE, TARGET, Point3::new(0.0, 0.0, 0.0), Some(10), false, Some(0.2));
+        particle_holder.spawn_damage(SOURCE, TARGET, Point3::new(0.0, 0.0, 0.0), Some(15), true, Some(0.2));
+        particle_holder.spawn_damage(TARGET, SOURCE, Point3::new(0.0, 0.0, 0.0), None, false, Some(0.2));
+
+        particle_holder.update(0.1);
+        assert!(particle_holder.particles.is_empty());
+
+        particle_holder.update(0.15);
+        assert_eq!(particle_holder.particles.len(), 2);
+        assert!(particle_holder.pending_damage.is_empty());
     }
 }
@@ -10,6 +10,11 @@ const MOVE_DIAGONAL_COST: usize = 14;
 const MOVE_ORTHOGONAL_COST: usize = 10;
 /// The maximum size a walkable path can have.
 pub const MAX_WALK_PATH_SIZE: usize = 32;
+/// Offsets of the orthogonal neighbors of a tile, in the order they are
+/// visited.
+pub const ORTHOGONAL_NEIGHBORS: [(i16, i16); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+/// Offsets of the diagonal neighbors of a tile, in the order they are visited.
+pub const DIAGONAL_NEIGHBORS: [(i16, i16); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
 
 /// Essential trait that is needed to be implements for pathfinding.
 pub trait Traversable {
@@ -130,10 +135,23 @@ impl PathFinder {
         None
     }
 
+    /// Returns `true` if the goal is at most one attack range away from the
+    /// start and there is a path between them that can be shot through. This
+    /// is what the server checks for skills that target the ground.
+    pub fn is_in_skill_range(
+        &mut self,
+        map: &impl Traversable,
+        start: TilePosition,
+        goal: TilePosition,
+        attack_range: AttackRange,
+    ) -> bool {
+        start.x.abs_diff(goal.x).max(start.y.abs_diff(goal.y)) <= attack_range.0
+            && map.is_snipeable(goal)
+            && self.find_snipable_path(map, start, goal).is_some()
+    }
+
     /// Returns the shortest path between start and goal that can be shot
     /// through.
-    // TODO: Unused for now.
-    #[allow(dead_code)]
     pub fn find_snipable_path(&mut self, map: &impl Traversable, start: TilePosition, goal: TilePosition) -> Option<&[TilePosition]> {
         self.path.clear();
 
@@ -198,6 +216,15 @@ impl PathFinder {
         Some(&self.path)
     }
 
+    /// Returns all tiles that can be walked to from the position in a single
+    /// step.
+    #[cfg(feature = "debug")]
+    pub fn walkable_neighbors(&mut self, map: &impl Traversable, position: TilePosition) -> &[TilePosition] {
+        self.neighbors.clear();
+        self.find_neighbors(map, position);
+        &self.neighbors
+    }
+
     fn heuristic(start: TilePosition, goal: TilePosition) -> usize {
         let dx = (start.x as isize - goal.x as isize).unsigned_abs();
         let dy = (start.y as isize - goal.y as isize).unsigned_abs();
@@ -206,9 +233,7 @@ impl PathFinder {
     }
 
     fn find_neighbors(&mut self, map: &impl Traversable, position: TilePosition) {
-        let orthogonal_neighbors = [(1, 0), (-1, 0), (0, 1), (0, -1)];
-
-        for (dx, dy) in orthogonal_neighbors {
+        for (dx, dy) in ORTHOGONAL_NEIGHBORS {
             let new_x = position.x.wrapping_add_signed(dx);
             let new_y = position.y.wrapping_add_signed(dy);
             let new_position = TilePosition { x: new_x, y: new_y };
@@ -218,9 +243,7 @@ impl PathFinder {
             }
         }
 
-        let diagonal_neighbors = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
-
-        for (dx, dy) in diagonal_neighbors {
+        for (dx, dy) in DIAGONAL_NEIGHBORS {
             let new_x = position.x.wrapping_add_signed(dx);
             let new_y = position.y.wrapping_add_signed(dy);
             let new_position = TilePosition { x: new_x, y: new_y };
@@ -258,46 +281,7 @@ impl PathFinder {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    struct TestMap {
-        width: u16,
-        height: u16,
-        not_walkable: HashSet<TilePosition>,
-        not_snipable: HashSet<TilePosition>,
-    }
-
-    impl TestMap {
-        fn new(width: u16, height: u16) -> Self {
-            Self {
-                width,
-                height,
-                not_walkable: HashSet::new(),
-                not_snipable: HashSet::new(),
-            }
-        }
-
-        fn set_unwalkable(&mut self, points: &[TilePosition]) {
-            for point in points {
-                self.not_walkable.insert(*point);
-            }
-        }
-
-        fn set_unsnipable(&mut self, points: &[TilePosition]) {
-            for point in points {
-                self.not_snipable.insert(*point);
-            }
-        }
-    }
-
-    impl Traversable for TestMap {
-        fn is_walkable(&self, position: TilePosition) -> bool {
-            position.x < self.width && position.y < self.height && !self.not_walkable.contains(&position)
-        }
-
-        fn is_snipeable(&self, position: TilePosition) -> bool {
-            position.x < self.width && position.y < self.height && !self.not_snipable.contains(&position)
-        }
-    }
+    use crate::test_fixtures::TestMap;
 
     #[test]
     fn test_straight_path() {
@@ -421,4 +405,18 @@ mod tests {
 
         assert!(pathfinder.find_snipable_path(&map, start, goal).is_none());
     }
+
+    #[test]
+    fn test_skill_range() {
+        let mut map = TestMap::new(10, 10);
+        map.set_unsnipable(&[TilePosition { x: 2, y: 0 }]);
+
+        let mut pathfinder = PathFinder::default();
+        let start = TilePosition { x: 0, y: 0 };
+
+        assert!(pathfinder.is_in_skill_range(&map, start, TilePosition { x: 3, y: 3 }, AttackRange(3)));
+        assert!(!pathfinder.is_in_skill_range(&map, start, TilePosition { x: 4, y: 1 }, AttackRange(3)));
+        assert!(!pathfinder.is_in_skill_range(&map, start, TilePosition { x: 3, y: 0 }, AttackRange(3)));
+        assert!(!pathfinder.is_in_skill_range(&map, start, TilePosition { x: 2, y: 0 }, AttackRange(3)));
+    }
 }
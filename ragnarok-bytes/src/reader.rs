@@ -138,6 +138,11 @@ where
         self.offset >= self.limit
     }
 
+    /// Number of bytes that can still be read.
+    pub fn remaining_length(&self) -> usize {
+        self.limit.saturating_sub(self.offset)
+    }
+
     pub fn get_metadata<Caller, As>(&self) -> ConversionResult<&As>
     where
         As: Any + 'static,
@@ -234,6 +239,29 @@ mod save_point {
     }
 }
 
+#[cfg(test)]
+mod remaining_length {
+    use crate::ByteReader;
+
+    const TEST_BYTE_SIZE: usize = 10;
+
+    #[test]
+    fn respects_offset_and_limit() {
+        let mut byte_reader = ByteReader::without_metadata(&[0; TEST_BYTE_SIZE]);
+
+        assert_eq!(byte_reader.remaining_length(), TEST_BYTE_SIZE);
+
+        byte_reader.offset = 2;
+        byte_reader.limit = TEST_BYTE_SIZE / 2;
+
+        assert_eq!(byte_reader.remaining_length(), 3);
+
+        byte_reader.offset = TEST_BYTE_SIZE;
+
+        assert_eq!(byte_reader.remaining_length(), 0);
+    }
+}
+
 #[cfg(test)]
 mod temporary_limit {
     use crate::ByteReader;
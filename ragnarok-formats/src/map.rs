@@ -1,6 +1,6 @@
 use std::collections::VecDeque;
 
-use cgmath::{Point3, Vector3};
+use cgmath::Point3;
 use ragnarok_bytes::{ByteConvertable, ByteReader, ByteWriter, ConversionError, ConversionResult, ConversionResultExt, FromBytes, ToBytes};
 
 use crate::color::{ColorBGRA, ColorRGB};
@@ -140,6 +140,24 @@ impl FromBytes for QuadTreeData {
     }
 }
 
+impl ToBytes for QuadTreeData {
+    fn to_bytes(&self, byte_writer: &mut ByteWriter) -> ConversionResult<usize> {
+        // Nodes are stored depth first, in the same order they are read.
+        byte_writer.write_counted(|write| {
+            self.max.to_bytes(write).trace::<Self>()?;
+            self.min.to_bytes(write).trace::<Self>()?;
+            self.half_size.to_bytes(write).trace::<Self>()?;
+            self.center.to_bytes(write).trace::<Self>()?;
+
+            for child in &self.children {
+                child.to_bytes(write)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
 impl FromBytes for TileFlags {
     fn from_bytes<Meta>(byte_reader: &mut ByteReader<Meta>) -> ConversionResult<Self> {
         match <Self as bitflags::Flags>::Bits::from_bytes(byte_reader).trace::<Self>()? {
@@ -232,7 +250,7 @@ pub struct GroundData {
     #[version_equals_or_above(1, 7)]
     #[repeating_expr(light_map_count as usize * light_map_width as usize * light_map_height as usize * 4)]
     #[new_default]
-    pub _skip: Option<Vec<u8>>,
+    pub light_maps: Option<Vec<u8>>,
     #[version_smaller(1, 7)]
     #[repeating_expr(light_map_count * 16)]
     #[new_default]
@@ -336,6 +354,19 @@ impl FromBytes for ResourceType {
     }
 }
 
+impl ToBytes for ResourceType {
+    fn to_bytes(&self, byte_writer: &mut ByteWriter) -> ConversionResult<usize> {
+        let index: i32 = match self {
+            ResourceType::Object => 1,
+            ResourceType::LightSource => 2,
+            ResourceType::SoundSource => 3,
+            ResourceType::EffectSource => 4,
+        };
+
+        index.to_bytes(byte_writer).trace::<Self>()
+    }
+}
+
 #[derive(Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
 pub struct ObjectData {
@@ -399,14 +430,12 @@ impl FromBytes for MapResources {
         let mut sound_sources = Vec::new();
         let mut effect_sources = Vec::new();
 
-        for index in 0..resources_amount {
+        for _ in 0..resources_amount {
             let resource_type = ResourceType::from_bytes(byte_reader).trace::<Self>()?;
 
             match resource_type {
                 ResourceType::Object => {
-                    let mut object = ObjectData::from_bytes(byte_reader).trace::<Self>()?;
-                    // offset the objects slightly to avoid depth buffer fighting
-                    object.transform.position += Vector3::new(0.0, 0.0005, 0.0) * index as f32;
+                    let object = ObjectData::from_bytes(byte_reader).trace::<Self>()?;
                     objects.push(object);
                 }
                 ResourceType::LightSource => {
@@ -423,11 +452,6 @@ impl FromBytes for MapResources {
                 ResourceType::SoundSource => {
                     let mut sound_source = SoundSource::from_bytes(byte_reader).trace::<Self>()?;
                     sound_source.position.y = -sound_source.position.y;
-
-                    if sound_source.cycle.is_none() {
-                        sound_source.cycle = Some(4.0);
-                    }
-
                     sound_sources.push(sound_source);
                 }
                 ResourceType::EffectSource => {
@@ -454,18 +478,33 @@ impl ToBytes for MapResources {
             self.resources_amount.to_bytes(write)?;
 
             for object in &self.objects {
+                ResourceType::Object.to_bytes(write)?;
                 object.to_bytes(write)?;
             }
 
+            // The y axis of the sources is flipped when reading them, so we need to flip it
+            // back.
             for light_source in &self.light_sources {
+                let mut light_source = light_source.clone();
+                light_source.position.y = -light_source.position.y;
+
+                ResourceType::LightSource.to_bytes(write)?;
                 light_source.to_bytes(write)?;
             }
 
             for sound_source in &self.sound_sources {
+                let mut sound_source = sound_source.clone();
+                sound_source.position.y = -sound_source.position.y;
+
+                ResourceType::SoundSource.to_bytes(write)?;
                 sound_source.to_bytes(write)?;
             }
 
             for effect_source in &self.effect_sources {
+                let mut effect_source = effect_source.clone();
+                effect_source.position.y = -effect_source.position.y;
+
+                ResourceType::EffectSource.to_bytes(write)?;
                 effect_source.to_bytes(write)?;
             }
 
@@ -675,4 +714,52 @@ mod conversion {
             }
         }
     }
+
+    mod resources {
+        use cgmath::Point3;
+        use ragnarok_bytes::{ByteReader, ByteWriter, FromBytes, ToBytes};
+
+        use crate::color::ColorRGB;
+        use crate::map::{LightSource, MapResources, SoundSource};
+        use crate::version::InternalVersion;
+
+        // Make sure that the resources of a map can be written back to a file without
+        // changing them.
+        #[test]
+        fn encode_decode() {
+            let light_source = LightSource {
+                name: "light".to_owned(),
+                position: Point3::new(1.0, 2.0, 3.0),
+                color: ColorRGB {
+                    red: 1.0,
+                    green: 0.5,
+                    blue: 0.0,
+                },
+                range: 40.0,
+            };
+            let sound_source = SoundSource {
+                name: "sound".to_owned(),
+                sound_file: "wind.wav".to_owned(),
+                position: Point3::new(-1.0, -2.0, -3.0),
+                volume: 0.8,
+                width: 10,
+                height: 10,
+                range: 100.0,
+                cycle: Some(4.0),
+            };
+            let resources = MapResources::new(Vec::new(), vec![light_source.clone()], vec![sound_source.clone()], Vec::new());
+
+            let mut byte_writer = ByteWriter::new();
+            resources.to_bytes(&mut byte_writer).unwrap();
+            let bytes = byte_writer.into_inner();
+
+            let version = Some(InternalVersion { major: 2, minor: 0 });
+            let mut byte_reader = ByteReader::with_metadata(&bytes, version);
+            let decoded = MapResources::from_bytes(&mut byte_reader).unwrap();
+
+            assert_eq!(decoded.resources_amount, 2);
+            assert!(decoded.light_sources == [light_source]);
+            assert!(decoded.sound_sources == [sound_source]);
+        }
+    }
 }
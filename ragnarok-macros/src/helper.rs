@@ -53,12 +53,12 @@ pub fn byte_convertable_helper(data_struct: DataStruct) -> (TokenStream, Vec<Tok
             None => quote!(ragnarok_bytes::FromBytes::from_bytes(byte_reader)),
         };
 
-        let to_length = match length {
+        let to_length = |value: TokenStream| match length.clone() {
             Some(length) if syn::parse::<syn::Ident>(length.clone().into()).is_ok() => {
-                quote!(ragnarok_bytes::ToBytesExt::to_n_bytes(&self.#field_identifier, writer, self.#length as usize))
+                quote!(ragnarok_bytes::ToBytesExt::to_n_bytes(#value, writer, self.#length as usize))
             }
-            Some(length) => quote!(ragnarok_bytes::ToBytesExt::to_n_bytes(&self.#field_identifier, writer, #length as usize)),
-            None => quote!(ragnarok_bytes::ToBytes::to_bytes(&self.#field_identifier, writer)),
+            Some(length) => quote!(ragnarok_bytes::ToBytesExt::to_n_bytes(#value, writer, #length as usize)),
+            None => quote!(ragnarok_bytes::ToBytes::to_bytes(#value, writer)),
         };
 
         let mut repeating: Option<(syn::Ident, bool)> = None;
@@ -186,8 +186,20 @@ pub fn byte_convertable_helper(data_struct: DataStruct) -> (TokenStream, Vec<Tok
 
         // base to byte implementation
         let to_implementation = match version_restricted {
-            true => quote!(panic!("version restricted fields can't be serialized at the moment");),
-            false => quote!(ragnarok_bytes::ConversionResultExt::trace::<Self>(#to_length)?;),
+            // Version restricted fields are only set if the version matched when reading
+            // them, so we only write them if they are present.
+            true => {
+                let to_length = to_length(quote!(value));
+                quote! {
+                    if let Some(value) = &self.#field_identifier {
+                        ragnarok_bytes::ConversionResultExt::trace::<Self>(#to_length)?;
+                    }
+                }
+            }
+            false => {
+                let to_length = to_length(quote!(&self.#field_identifier));
+                quote!(ragnarok_bytes::ConversionResultExt::trace::<Self>(#to_length)?;)
+            }
         };
         to_bytes_implementations.push(to_implementation);
 
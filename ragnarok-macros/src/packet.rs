@@ -72,6 +72,7 @@ pub fn derive_packet_struct(
         impl #impl_generics ragnarok_packets::Packet for #name #type_generics #where_clause {
             const IS_PING: bool = #is_ping;
             const HEADER: ragnarok_packets::PacketHeader = ragnarok_packets::PacketHeader(#signature);
+            const IS_VARIABLE_LENGTH: bool = #is_variable_length;
 
             fn payload_from_bytes<Meta>(byte_reader: &mut ragnarok_bytes::ByteReader<Meta>) -> ragnarok_bytes::ConversionResult<Self> {
                 let base_offset = byte_reader.get_offset();
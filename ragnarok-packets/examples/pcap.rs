@@ -214,11 +214,13 @@ fn main() {
         BroadcastMessagePacket,
         Broadcast2MessagePacket,
         OverheadMessagePacket,
+        WhisperMessagePacket,
         ServerMessagePacket,
         EntityMessagePacket,
         DisplayEmotionPacket,
         EntityMovePacket,
         EntityStopMovePacket,
+        EntityChangeDirectionPacket,
         PlayerMovePacket,
         ChangeMapPacket,
         ResurrectionPacket,
@@ -272,7 +274,9 @@ fn main() {
         StateChangePacket,
         QuestEffectPacket,
         ItemPickupPacket,
+        ItemFallEntryPacket,
         RemoveItemFromInventoryPacket,
+        UseItemStatusPacket,
         ServerTickPacket,
         RequestPlayerDetailsSuccessPacket,
         RequestEntityDetailsSuccessPacket,
@@ -297,6 +301,10 @@ fn main() {
         FriendRequestResultPacket,
         NotifyFriendRemovedPacket,
         PartyInvitePacket,
+        PartyMemberInformationPacket,
+        PartyMemberLeftPacket,
+        PartyMemberPositionPacket,
+        PartyMemberHealthPacket,
         StatusChangeSequencePacket,
         ReputationPacket,
         ClanInfoPacket,
@@ -310,6 +318,26 @@ fn main() {
         SellListPacket,
         SellItemsPacket,
         SellItemsResultPacket,
+        OpenBankResultPacket,
+        CloseBankResultPacket,
+        BankBalancePacket,
+        BankDepositResultPacket,
+        BankWithdrawResultPacket,
+        AuctionWindowPacket,
+        AuctionSearchResultPacket,
+        AuctionSetItemResultPacket,
+        AuctionResultPacket,
+        OpenRouletteResultPacket,
+        RouletteInfoPacket,
+        CloseRouletteResultPacket,
+        SpinRouletteResultPacket,
+        ClaimRouletteRewardResultPacket,
+        OpenInterfacePacket,
+        AttendanceRewardPacket,
+        InstanceQueuePacket,
+        InstanceQueuePositionPacket,
+        InstanceInformationPacket,
+        InstanceNotificationPacket,
     ]);
 
     let mut server_map_handler = create_handler!(ServerType::Map, Direction::Outgoing, [
@@ -326,6 +354,11 @@ fn main() {
         ChooseDialogOptionPacket,
         RequestEquipItemPacket,
         RequestUnequipItemPacket,
+        UseItemPacket,
+        RequestDropItemPacket,
+        RequestMoveItemToStoragePacket,
+        AddTradeItemPacket,
+        RequestEmotionPacket,
         UseSkillAtIdPacket,
         UseSkillOnGroundPacket,
         StartUseSkillPacket,
@@ -339,6 +372,25 @@ fn main() {
         CloseShopPacket,
         SellItemsPacket,
         RequestServerTickPacket,
+        OpenBankPacket,
+        CloseBankPacket,
+        RequestBankBalancePacket,
+        BankDepositPacket,
+        BankWithdrawPacket,
+        AuctionSearchPacket,
+        RequestMyAuctionsPacket,
+        AuctionBidPacket,
+        AuctionSetItemPacket,
+        AuctionRegisterPacket,
+        AuctionCancelPacket,
+        OpenRoulettePacket,
+        RequestRouletteInfoPacket,
+        CloseRoulettePacket,
+        SpinRoulettePacket,
+        ClaimRouletteRewardPacket,
+        RequestOpenInterfacePacket,
+        RequestAttendanceRewardPacket,
+        InstanceCommandPacket,
     ]);
 
     println!("{}", "Listening for packets".green());
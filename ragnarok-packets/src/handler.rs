@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use ragnarok_bytes::{ByteReader, ConversionError, ConversionResult, FromBytes};
 
@@ -69,6 +69,9 @@ where
     Meta: 'static,
 {
     handlers: HashMap<PacketHeader, HandlerFunction<Output, Meta>>,
+    /// Headers of registered packets that have a variable length, so cut-off
+    /// packets can be detected without parsing them.
+    variable_length_headers: HashSet<PacketHeader>,
     packet_callback: Callback,
 }
 
@@ -80,6 +83,7 @@ where
     fn default() -> Self {
         Self {
             handlers: Default::default(),
+            variable_length_headers: Default::default(),
             packet_callback: Default::default(),
         }
     }
@@ -95,6 +99,7 @@ where
     pub fn with_callback(packet_callback: Callback) -> Self {
         Self {
             handlers: Default::default(),
+            variable_length_headers: Default::default(),
             packet_callback,
         }
     }
@@ -105,6 +110,10 @@ where
         Packet: ragnarok_packets::Packet,
         Return: Into<Output>,
     {
+        if Packet::IS_VARIABLE_LENGTH {
+            self.variable_length_headers.insert(Packet::HEADER);
+        }
+
         let packet_callback = self.packet_callback.clone();
         let old_handler = self.handlers.insert(
             Packet::HEADER,
@@ -130,6 +139,10 @@ where
     where
         Packet: ragnarok_packets::Packet,
     {
+        if Packet::IS_VARIABLE_LENGTH {
+            self.variable_length_headers.insert(Packet::HEADER);
+        }
+
         let packet_callback = self.packet_callback.clone();
         let old_handler = self.handlers.insert(
             Packet::HEADER,
@@ -168,6 +181,23 @@ where
             return HandlerResult::UnhandledPacket;
         };
 
+        if self.variable_length_headers.contains(&header) {
+            let length_save_point = byte_reader.create_save_point();
+
+            // The packet length includes the header, which was already read.
+            let is_cut_off = match u16::from_bytes(byte_reader) {
+                Ok(packet_length) => byte_reader.remaining_length() + 4 < packet_length as usize,
+                Err(..) => true,
+            };
+
+            if is_cut_off {
+                byte_reader.restore_save_point(save_point);
+                return HandlerResult::PacketCutOff;
+            }
+
+            byte_reader.restore_save_point(length_save_point);
+        }
+
         match handler(byte_reader) {
             Ok(output) => HandlerResult::Ok(output),
             // Cut-off packet (probably).
@@ -35,6 +35,9 @@ pub trait Packet: std::fmt::Debug + Send + Clone + 'static {
     const IS_PING: bool;
     /// The header of the Packet.
     const HEADER: PacketHeader;
+    /// Variable length packets store their full length (including the header)
+    /// as a `u16` right after the header.
+    const IS_VARIABLE_LENGTH: bool = false;
 
     /// Read packet **without the header**. To read the packet with the header,
     /// use [`PacketExt::packet_from_bytes`].
@@ -102,14 +105,17 @@ pub trait MapServerPacket: Packet {}
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ClientTick(pub u32);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AccountId(pub u32);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CharacterId(pub u32);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, Hash)]
@@ -118,18 +124,22 @@ pub struct PartyId(pub u32);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EntityId(pub u32);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SkillId(pub u16);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SkillLevel(pub u16);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HotbarTab(pub u16);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, Hash)]
@@ -138,18 +148,22 @@ pub struct HotbarSlot(pub u16);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ShopId(pub u32);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Price(pub u32);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AttackRange(pub u16);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ServerAddress(pub [u8; 4]);
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize)]
@@ -168,6 +182,7 @@ impl From<ServerAddress> for Ipv4Addr {
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TilePosition {
     pub x: u16,
     pub y: u16,
@@ -201,6 +216,7 @@ pub struct ColorRGBA {
 /// Item index is always actual index + 2.
 #[derive(Clone, Copy, Debug, FixedByteSize, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InventoryIndex(pub u16);
 
 impl FromBytes for InventoryIndex {
@@ -217,10 +233,12 @@ impl ToBytes for InventoryIndex {
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ItemId(pub u32);
 
 #[derive(Copy, Debug, Clone, ByteConvertable, FixedByteSize, PartialEq)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Sex {
     Female,
     Male,
@@ -362,6 +380,7 @@ pub struct MapServerLoginSuccessPacket {
 
 #[derive(Debug, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LoginFailedReason {
     #[numeric_value(1)]
     ServerClosed,
@@ -442,6 +461,7 @@ pub struct CharacterSelectionSuccessPacket {
 
 #[derive(Debug, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CharacterCreationFailedReason {
     CharacterNameAlreadyUsed,
     NotOldEnough,
@@ -473,6 +493,7 @@ pub struct LoginServerKeepalivePacket {
 
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CharacterServerInformation {
     pub server_ip: ServerAddress,
     pub server_port: u16,
@@ -482,9 +503,16 @@ pub struct CharacterServerInformation {
     pub server_type: u16, // ServerType
     pub display_new: u16, // bool16 ?
     #[new_value([0; 128])]
+    #[cfg_attr(feature = "serde", serde(skip, default = "unknown_bytes"))]
     pub unknown: [u8; 128],
 }
 
+/// Unknown bytes are not serialized.
+#[cfg(feature = "serde")]
+fn unknown_bytes<const SIZE: usize>() -> [u8; SIZE] {
+    [0; SIZE]
+}
+
 /// Sent by the client to the character server after after successfully logging
 /// into the login server.
 /// Attempts to log into the character server using the provided information.
@@ -544,6 +572,7 @@ pub struct CreateCharacterPacket {
 
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CharacterInformation {
     pub character_id: CharacterId,
     pub experience: i64,
@@ -681,6 +710,19 @@ pub struct EntityStopMovePacket {
     pub position: TilePosition,
 }
 
+/// Sent by the map server to the client.
+/// Informs the client that an entity turned its body or head, for example
+/// to look at another entity. A head direction of `0` looks straight ahead,
+/// `1` to the right and `2` to the left.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x009C)]
+pub struct EntityChangeDirectionPacket {
+    pub entity_id: EntityId,
+    pub head_direction: u16,
+    pub direction: Direction,
+}
+
 /// Sent by the map server to the client.
 /// Informs the client that the player is pathing towards a new position.
 /// Provides the initial position and destination of the movement, as well as a
@@ -713,6 +755,7 @@ pub struct DeleteCharacterPacket {
 
 #[derive(Debug, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CharacterDeletionFailedReason {
     NotAllowed,
     CharacterNotFound,
@@ -903,6 +946,7 @@ pub struct InventoyEndPacket {
 
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ItemOptions {
     pub index: u16,
     pub value: u16,
@@ -912,6 +956,7 @@ pub struct ItemOptions {
 bitflags::bitflags! {
     #[derive(Debug, Clone)]
     #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct RegularItemFlags: u8 {
         const IDENTIFIED = 0b01;
         const IN_ETC_TAB = 0b10;
@@ -962,6 +1007,7 @@ pub struct RegularItemListPacket {
 bitflags::bitflags! {
     #[derive(Debug, Clone)]
     #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct EquippableItemFlags: u8 {
         const IDENTIFIED = 0b001;
         const IS_BROKEN = 0b010;
@@ -1068,6 +1114,20 @@ pub struct BroadcastMessagePacket {
     pub message: String,
 }
 
+/// Sent by the map server to the client when another player whispers to the
+/// player. Provides the name of the sender and the message.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0097)]
+#[variable_length]
+pub struct WhisperMessagePacket {
+    #[length(24)]
+    pub sender_name: String,
+    pub is_admin: u32,
+    #[length_remaining]
+    pub message: String,
+}
+
 /// Sent by the map server to the client when when someone writes in proximity
 /// chat. Provides the source player and message to be displayed in the chat
 /// window and the speach bubble.
@@ -1108,6 +1168,7 @@ pub struct DisplayEmotionPacket {
 /// All UpdateStatPackets do the same, they just have different sizes
 /// correlating to the space the updated value requires.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StatType {
     Weight(u32),
     MaximumWeight(u32),
@@ -1661,6 +1722,7 @@ pub struct ChangeMapPacket {
 
 #[derive(Debug, Clone, ByteConvertable, PartialEq)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DisappearanceReason {
     OutOfSight,
     Died,
@@ -1817,6 +1879,7 @@ pub struct EntityAppeared2Packet {
 
 #[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u32)]
 pub enum SkillType {
     #[numeric_value(0)]
@@ -1835,6 +1898,7 @@ pub enum SkillType {
 
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SkillInformation {
     pub skill_id: SkillId,
     pub skill_type: SkillType,
@@ -1857,6 +1921,7 @@ pub struct UpdateSkillTreePacket {
 
 #[derive(Debug, Clone, PartialEq, Eq, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HotkeyData {
     pub is_skill: u8,
     pub skill_id: u32,
@@ -3348,6 +3413,7 @@ pub struct DisplayGainedExperiencePacket {
 
 #[derive(Debug, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImageLocation {
     BottomLeft,
     BottomMiddle,
@@ -3413,8 +3479,28 @@ pub struct ItemPickupPacket {
     pub enchantment_level: u8,
 }
 
+/// Sent by the map server when an item drops to the ground, for example
+/// after a monster died.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0ADD)]
+pub struct ItemFallEntryPacket {
+    pub entity_id: EntityId,
+    pub item_id: ItemId,
+    pub item_type: u16,
+    pub is_identified: u8,
+    pub position: TilePosition,
+    pub sub_x: u8,
+    pub sub_y: u8,
+    pub count: u16,
+    /// Set by the server for items that are marked as rare drops.
+    pub show_drop_effect: u8,
+    pub drop_effect_mode: u16,
+}
+
 #[derive(Debug, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u16)]
 pub enum RemoveItemReason {
     Normal,
@@ -3439,6 +3525,7 @@ pub struct RemoveItemFromInventoryPacket {
 // TODO: improve names
 #[derive(Debug, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u16)]
 pub enum QuestEffect {
     Quest,
@@ -3458,6 +3545,7 @@ pub enum QuestEffect {
 
 #[derive(Debug, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u16)]
 pub enum QuestColor {
     Yellow,
@@ -3468,6 +3556,7 @@ pub enum QuestColor {
 
 #[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[header(0x0446)]
 pub struct QuestEffectPacket {
     pub entity_id: EntityId,
@@ -3535,6 +3624,7 @@ pub struct ChooseDialogOptionPacket {
 bitflags::bitflags! {
     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
     #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct EquipPosition: u32 {
         const NONE = 0;
         const HEAD_LOWER = 1;
@@ -3549,7 +3639,7 @@ bitflags::bitflags! {
         const RIGTH_ACCESSORY = 128;
         const COSTUME_HEAD_TOP = 1024;
         const COSTUME_HEAD_MIDDLE = 2048;
-        const COSTUME_HEAD_LOWER = 4196;
+        const COSTUME_HEAD_LOWER = 4096;
         const COSTUME_GARMENT = 8192;
         const AMMO = 32768;
         const SHADOW_ARMOR = 65536;
@@ -3638,6 +3728,60 @@ pub struct RequestUnequipItemPacket {
     pub inventory_index: InventoryIndex,
 }
 
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0439)]
+pub struct UseItemPacket {
+    pub inventory_index: InventoryIndex,
+    pub account_id: AccountId,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0363)]
+pub struct RequestDropItemPacket {
+    pub inventory_index: InventoryIndex,
+    pub amount: u16,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0364)]
+pub struct RequestMoveItemToStoragePacket {
+    pub inventory_index: InventoryIndex,
+    pub amount: u32,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00E8)]
+pub struct AddTradeItemPacket {
+    pub inventory_index: InventoryIndex,
+    pub amount: u32,
+}
+
+/// Show an emotion above the player. The map server answers with a
+/// [`DisplayEmotionPacket`] to everyone in range.
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x00BF)]
+pub struct RequestEmotionPacket {
+    pub emotion: u8,
+}
+
+/// Sent by the map server after an entity used an item. For the player,
+/// `amount` is the amount of the item that is left in the inventory.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x01C8)]
+pub struct UseItemStatusPacket {
+    pub inventory_index: InventoryIndex,
+    pub item_id: ItemId,
+    pub entity_id: EntityId,
+    pub amount: u16,
+    pub success: u8,
+}
+
 #[derive(Debug, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
 pub enum RequestUnequipItemStatus {
@@ -3771,6 +3915,7 @@ pub struct ToUseSkillSuccessPacket {
 
 #[derive(Debug, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u32)]
 pub enum UnitId {
     #[numeric_value(0x7E)]
@@ -3998,6 +4143,7 @@ pub struct SkillUnitDisappearPacket {
 
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Friend {
     pub account_id: AccountId,
     pub character_id: CharacterId,
@@ -4107,6 +4253,59 @@ pub struct PartyInvitePacket {
     pub party_name: String,
 }
 
+/// Sent for every member of the party when the player logs in and whenever a
+/// member joins, changes maps or logs in or out.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0AE4)]
+pub struct PartyMemberInformationPacket {
+    pub account_id: AccountId,
+    pub character_id: CharacterId,
+    /// Zero for the leader of the party.
+    pub role: u32,
+    pub job: u16,
+    pub base_level: u16,
+    pub position: TilePosition,
+    pub offline: u8,
+    #[length(24)]
+    pub party_name: String,
+    #[length(24)]
+    pub name: String,
+    #[length(16)]
+    pub map_name: String,
+    pub share_pickup: u8,
+    pub share_loot: u8,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0105)]
+pub struct PartyMemberLeftPacket {
+    pub account_id: AccountId,
+    #[length(24)]
+    pub name: String,
+    pub result: u8,
+}
+
+/// Position of a party member on the same map as the player. The map server
+/// sends it periodically while the member moves.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0107)]
+pub struct PartyMemberPositionPacket {
+    pub account_id: AccountId,
+    pub position: TilePosition,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x080E)]
+pub struct PartyMemberHealthPacket {
+    pub account_id: AccountId,
+    pub health_points: u32,
+    pub maximum_health_points: u32,
+}
+
 #[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
 pub struct ReputationEntry {
@@ -4298,6 +4497,7 @@ pub struct BuyShopItemsPacket {
 
 #[derive(Debug, Clone, Copy, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[numeric_type(u16)]
 pub enum BuyShopItemsResult {
     #[numeric_value(0)]
@@ -4331,6 +4531,7 @@ pub struct CloseShopPacket {}
 
 #[derive(Debug, Clone, FixedByteSize, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SellItemInformation {
     pub inventory_index: InventoryIndex,
     pub price: Price,
@@ -4364,6 +4565,7 @@ pub struct SellItemsPacket {
 
 #[derive(Debug, Clone, Copy, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SellItemsResult {
     Success,
     Error,
@@ -4375,3 +4577,523 @@ pub enum SellItemsResult {
 pub struct SellItemsResultPacket {
     pub result: SellItemsResult,
 }
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09B6)]
+pub struct OpenBankPacket {
+    pub account_id: AccountId,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[numeric_type(u16)]
+pub enum OpenBankResult {
+    Success,
+    Error,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09B7)]
+pub struct OpenBankResultPacket {
+    pub result: OpenBankResult,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09B8)]
+pub struct CloseBankPacket {
+    pub account_id: AccountId,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09B9)]
+pub struct CloseBankResultPacket {
+    pub result: u16,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09AB)]
+pub struct RequestBankBalancePacket {
+    pub account_id: AccountId,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09A6)]
+pub struct BankBalancePacket {
+    pub balance: i64,
+    pub reason: u16,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09A7)]
+pub struct BankDepositPacket {
+    pub account_id: AccountId,
+    pub amount: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[numeric_type(u16)]
+pub enum BankDepositResult {
+    #[numeric_value(0)]
+    Success,
+    #[numeric_value(1)]
+    Error,
+    #[numeric_value(2)]
+    NotEnoughZeny,
+    #[numeric_value(3)]
+    BalanceLimitExceeded,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09A8)]
+pub struct BankDepositResultPacket {
+    pub result: BankDepositResult,
+    pub balance: i64,
+    pub zeny: i32,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09A9)]
+pub struct BankWithdrawPacket {
+    pub account_id: AccountId,
+    pub amount: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[numeric_type(u16)]
+pub enum BankWithdrawResult {
+    #[numeric_value(0)]
+    Success,
+    #[numeric_value(1)]
+    NotEnoughBalance,
+    #[numeric_value(2)]
+    ZenyLimitExceeded,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x09AA)]
+pub struct BankWithdrawResultPacket {
+    pub result: BankWithdrawResult,
+    pub balance: i64,
+    pub zeny: i32,
+}
+
+#[derive(Clone, Copy, Debug, ByteConvertable, FixedByteSize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuctionId(pub u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[numeric_type(u32)]
+pub enum AuctionWindowAction {
+    Open,
+    Close,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x025F)]
+pub struct AuctionWindowPacket {
+    pub action: AuctionWindowAction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[numeric_type(u16)]
+pub enum AuctionSearchType {
+    Armor,
+    Weapon,
+    Card,
+    Miscellaneous,
+    ItemName,
+    AuctionId,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0251)]
+pub struct AuctionSearchPacket {
+    pub search_type: AuctionSearchType,
+    /// Only used when searching by [`AuctionSearchType::AuctionId`].
+    pub auction_id: AuctionId,
+    #[length(24)]
+    pub search_text: String,
+    /// Pages start at 1.
+    pub page: u16,
+}
+
+#[derive(Debug, Clone, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuctionItemInformation {
+    pub auction_id: AuctionId,
+    #[length(24)]
+    pub seller_name: String,
+    pub item_id: ItemId,
+    pub item_type: u32,
+    pub amount: u16,
+    pub is_identified: u8,
+    pub is_broken: u8,
+    pub refinement_level: u8,
+    pub cards: [u32; 4],
+    /// Highest bid so far.
+    pub price: Price,
+    pub buy_now_price: Price,
+    #[length(24)]
+    pub buyer_name: String,
+    /// Unix timestamp of the end of the auction.
+    pub end_time: u32,
+}
+
+/// Sent as a reply to both a search and a request for the player's own
+/// auctions.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0252)]
+#[variable_length]
+pub struct AuctionSearchResultPacket {
+    pub page_count: u32,
+    #[new_derive]
+    pub item_count: u32,
+    #[repeating(item_count)]
+    pub items: Vec<AuctionItemInformation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[numeric_type(u16)]
+pub enum AuctionListingType {
+    Selling,
+    Buying,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x025C)]
+pub struct RequestMyAuctionsPacket {
+    pub listing_type: AuctionListingType,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x024F)]
+pub struct AuctionBidPacket {
+    pub auction_id: AuctionId,
+    pub price: Price,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x024C)]
+pub struct AuctionSetItemPacket {
+    pub index: InventoryIndex,
+    pub amount: u32,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0256)]
+pub struct AuctionSetItemResultPacket {
+    pub index: InventoryIndex,
+    pub failed: u8,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x024D)]
+pub struct AuctionRegisterPacket {
+    pub price: Price,
+    pub buy_now_price: Price,
+    pub hours: u16,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x024E)]
+pub struct AuctionCancelPacket {
+    pub auction_id: AuctionId,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AuctionResult {
+    #[numeric_value(0)]
+    BidFailed,
+    #[numeric_value(1)]
+    BidSuccessful,
+    #[numeric_value(2)]
+    Cancelled,
+    #[numeric_value(3)]
+    CancelFailedHasBidder,
+    #[numeric_value(4)]
+    TooManyAuctions,
+    #[numeric_value(5)]
+    NotEnoughZenyForFee,
+    #[numeric_value(6)]
+    AuctionWon,
+    #[numeric_value(7)]
+    AuctionLost,
+    #[numeric_value(8)]
+    NotEnoughZeny,
+    #[numeric_value(9)]
+    TooManyBids,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0250)]
+pub struct AuctionResultPacket {
+    pub result: AuctionResult,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A19)]
+pub struct OpenRoulettePacket {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+pub enum OpenRouletteResult {
+    Success,
+    Failed,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A1A)]
+pub struct OpenRouletteResultPacket {
+    pub result: OpenRouletteResult,
+    pub serial: u32,
+    /// Row of the board the player is currently on.
+    pub step: u8,
+    /// Position in the row the player is currently on.
+    pub index: u8,
+    pub additional_item_id: ItemId,
+    pub gold_points: u32,
+    pub silver_points: u32,
+    pub bronze_points: u32,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A1B)]
+pub struct RequestRouletteInfoPacket {}
+
+#[derive(Debug, Clone, PartialEq, Eq, ByteConvertable, FixedByteSize)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RouletteItemInformation {
+    pub row: u16,
+    pub position: u16,
+    pub item_id: ItemId,
+    pub count: u16,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A1C)]
+#[variable_length]
+pub struct RouletteInfoPacket {
+    pub serial: u32,
+    #[repeating_remaining]
+    pub items: Vec<RouletteItemInformation>,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A1D)]
+pub struct CloseRoulettePacket {}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A1E)]
+pub struct CloseRouletteResultPacket {
+    pub result: u8,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A1F)]
+pub struct SpinRoulettePacket {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+pub enum SpinRouletteResult {
+    #[numeric_value(0)]
+    Won,
+    #[numeric_value(1)]
+    Failed,
+    #[numeric_value(2)]
+    NotEnoughPoints,
+    #[numeric_value(3)]
+    Lost,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A20)]
+pub struct SpinRouletteResultPacket {
+    pub result: SpinRouletteResult,
+    pub step: u16,
+    pub index: u16,
+    pub additional_item_id: ItemId,
+    pub gold_points: u32,
+    pub silver_points: u32,
+    pub bronze_points: u32,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A21)]
+pub struct ClaimRouletteRewardPacket {
+    #[new_default]
+    pub condition: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+pub enum ClaimRouletteRewardResult {
+    #[numeric_value(0)]
+    Success,
+    #[numeric_value(1)]
+    Failed,
+    #[numeric_value(2)]
+    TooManyItems,
+    #[numeric_value(3)]
+    Overweight,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A22)]
+pub struct ClaimRouletteRewardResultPacket {
+    pub result: ClaimRouletteRewardResult,
+    pub additional_item_id: ItemId,
+}
+
+/// Interfaces that the client can ask the server to open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+pub enum RequestedInterface {
+    #[numeric_value(2)]
+    MacroRegister,
+    #[numeric_value(3)]
+    MacroDetector,
+    #[numeric_value(5)]
+    Attendance,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0A68)]
+pub struct RequestOpenInterfacePacket {
+    pub interface: RequestedInterface,
+}
+
+/// Value of [`OpenInterfacePacket::interface`] for the attendance interface.
+pub const ATTENDANCE_INTERFACE: u8 = 7;
+
+/// Sent by the map server to open one of the special interfaces. The meaning
+/// of `data` depends on the interface. For the attendance interface it is the
+/// number of days the player has already claimed a reward for.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0AE2)]
+pub struct OpenInterfacePacket {
+    pub interface: u8,
+    pub data: u32,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0AEF)]
+pub struct RequestAttendanceRewardPacket {}
+
+/// Sent by the map server after the attendance reward of the day was claimed.
+/// The reward itself is sent by mail.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x0AF0)]
+pub struct AttendanceRewardPacket {
+    pub interface: u32,
+    /// Number of days the player has claimed a reward for, including today.
+    pub claimed_days: u32,
+}
+
+/// Sent by the map server when the party queued for creating a memorial
+/// dungeon.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x02CB)]
+pub struct InstanceQueuePacket {
+    #[length(61)]
+    pub name: String,
+    pub queue_position: u16,
+}
+
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x02CC)]
+pub struct InstanceQueuePositionPacket {
+    pub queue_position: u16,
+}
+
+/// Sent by the map server once the memorial dungeon was created and can be
+/// entered.
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x02CD)]
+pub struct InstanceInformationPacket {
+    #[length(61)]
+    pub name: String,
+    /// Unix timestamp at which the memorial dungeon is destroyed.
+    pub destroy_time: u32,
+    /// Unix timestamp after which the memorial dungeon can't be entered
+    /// anymore.
+    pub enter_time_limit: u32,
+}
+
+/// Status of the memorial dungeon. Known values are listed in
+/// [`InstanceNotificationPacket::status`].
+#[derive(Debug, Clone, Packet, ServerPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x02CE)]
+pub struct InstanceNotificationPacket {
+    /// 1: destroyed because of the time limit, 2: destroyed because nobody
+    /// entered in time, 3: destroyed on request, 4: creation failed.
+    pub status: u32,
+    pub enter_time_limit: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ByteConvertable)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[numeric_type(u32)]
+pub enum InstanceCommand {
+    #[numeric_value(0)]
+    Destroy,
+    #[numeric_value(3)]
+    ForceDestroy,
+}
+
+#[derive(Debug, Clone, Packet, ClientPacket, MapServer)]
+#[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[header(0x02CF)]
+pub struct InstanceCommandPacket {
+    pub command: InstanceCommand,
+}
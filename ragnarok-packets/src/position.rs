@@ -4,6 +4,7 @@ use crate::TilePosition;
 
 #[derive(Debug, Copy, Clone, ByteConvertable)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     North = 0,
     NorthEast = 1,
@@ -62,6 +63,7 @@ impl TryFrom<[isize; 2]> for Direction {
 
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "interface", derive(rust_state::RustState, korangar_interface::element::StateElement))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WorldPosition {
     pub x: u16,
     pub y: u16,